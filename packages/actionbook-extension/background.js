@@ -7,6 +7,10 @@ const RECONNECT_MAX_MS = 30000;
 const MAX_RETRIES = 8;
 const BRIDGE_PROBE_TIMEOUT_MS = 750;
 
+// Bridge protocol spoken by this build (sent in the hello, reported by
+// Extension.status / Extension.getVersion).
+const PROTOCOL_VERSION = "0.4.0";
+
 const HANDSHAKE_TIMEOUT_MS = 2000;
 const L3_CONFIRM_TIMEOUT_MS = 30000;
 
@@ -30,6 +34,11 @@ let groupingEnabled = true;
 const CDP_ALLOWLIST = {
   // L1 - Read only (auto-approved)
   'Page.captureScreenshot': 'L1',
+  // `browser record-video`: frames arrive as Page.screencastFrame events and
+  // each one must be acknowledged before Chrome sends the next.
+  'Page.startScreencast': 'L1',
+  'Page.stopScreencast': 'L1',
+  'Page.screencastFrameAck': 'L1',
   'Page.getLayoutMetrics': 'L1',
   'Page.getNavigationHistory': 'L1',
   'DOM.getDocument': 'L1',
@@ -70,6 +79,9 @@ const CDP_ALLOWLIST = {
   'DOM.setFileInputFiles': 'L2',
   'Emulation.setDeviceMetricsOverride': 'L2',
   'Emulation.clearDeviceMetricsOverride': 'L2',
+  'Emulation.setTouchEmulationEnabled': 'L2',
+  'Emulation.setUserAgentOverride': 'L2',
+  'Network.emulateNetworkConditions': 'L2',
   'Page.printToPDF': 'L2',
 
   // L3 - High risk (requires confirmation)
@@ -106,6 +118,11 @@ let wasReplaced = false; // true when bridge notified us another extension insta
 
 // L3 confirmation state: pending command waiting for user approval
 let pendingL3 = null; // { id, method, params, domain, nonce, resolve }
+// Request ids the bridge gave up on (Extension.cancel); their responses are not sent.
+const cancelledIds = new Set();
+// Tabs with an Extension.startRecording in progress. recorder.js is
+// re-injected into them after every navigation.
+const recordingTabs = new Set();
 let l3NonceCounter = 0;
 
 // --- Debug Logging ---
@@ -230,7 +247,9 @@ async function connect() {
     wsSend({
       type: "hello",
       role: "extension",
-      version: "0.4.0",
+      version: PROTOCOL_VERSION,
+      // Checked against the WS Origin; lets the CLI accept configured builds.
+      extensionId: chrome.runtime.id,
     });
 
     // Start handshake timeout - if no hello_ack within this window, treat as auth failure
@@ -328,11 +347,15 @@ async function connect() {
     if (!handshakeCompleted) return;
 
     const response = await handleCommand(msg);
+    if (msg.method !== "Extension.cancel" && cancelledIds.delete(msg.id)) return;
     wsSend(response);
   };
 
   ws.onclose = () => {
     ws = null;
+    cancelledIds.clear();
+    // Nobody is listening any more; a new CLI session starts its own.
+    recordingTabs.clear();
 
     if (handshakeTimer) {
       clearTimeout(handshakeTimer);
@@ -553,11 +576,72 @@ async function handleCommand(msg) {
   }
 }
 
+// --- Flow Recording ---
+
+// Inject the recorder content script into a tab's main frame. Pages the
+// extension cannot script (chrome://, the Web Store) are skipped silently;
+// their navigations are still reported.
+async function injectRecorder(tabId) {
+  try {
+    await chrome.scripting.executeScript({ target: { tabId }, files: ["recorder.js"] });
+  } catch (err) {
+    debugLog(`[actionbook] recorder injection failed for tab ${tabId}:`, err?.message || err);
+  }
+}
+
+function sendRecordingEvent(tabId, event) {
+  // Same framing as forwarded CDP events: no id, root-level tabId.
+  wsSend({ method: "Extension.recordingEvent", params: { event }, tabId });
+}
+
+// Extension.* methods handled below, reported as capabilities.
+const EXTENSION_METHODS = [
+  "ping",
+  "getVersion",
+  "cancel",
+  "listTabs",
+  "attachTab",
+  "createTab",
+  "activateTab",
+  "detachTab",
+  "closeTabs",
+  "status",
+  "getCookies",
+  "setCookie",
+  "removeCookie",
+  "clearCookies",
+  "startRecording",
+  "stopRecording",
+];
+
 async function handleExtensionCommand(id, method, params) {
   switch (method) {
     case "Extension.ping":
       return { id, result: { status: "pong", timestamp: Date.now() } };
 
+    case "Extension.getVersion":
+      return {
+        id,
+        result: {
+          version: chrome.runtime.getManifest().version,
+          protocolVersion: PROTOCOL_VERSION,
+          capabilities: EXTENSION_METHODS,
+        },
+      };
+
+    case "Extension.cancel": {
+      // Sent by the bridge when a request timed out on its side. Abandon a
+      // pending L3 confirmation for it and suppress its late response.
+      const target = params.id;
+      if (typeof target !== "number") {
+        return { id, error: { code: -32602, message: "Missing required param: id" } };
+      }
+      cancelledIds.add(target);
+      const wasPending = pendingL3 !== null && pendingL3.id === target;
+      if (wasPending) pendingL3.resolve(false);
+      return { id, result: { cancelled: true, pendingConfirmation: wasPending } };
+    }
+
     case "Extension.listTabs": {
       // Only return tabs that Actionbook is actually managing:
       //   * tabs currently in the "Actionbook" tab group (any window), OR
@@ -725,13 +809,46 @@ async function handleExtensionCommand(id, method, params) {
       return { id, result: { closed, failed } };
     }
 
+    case "Extension.startRecording": {
+      const tabId = params.tabId;
+      if (typeof tabId !== "number") {
+        return { id, error: { code: -32602, message: "Missing or invalid tabId" } };
+      }
+      let tab;
+      try {
+        tab = await chrome.tabs.get(tabId);
+      } catch (_) {
+        return { id, error: { code: -32000, message: `Tab ${tabId} not found` } };
+      }
+      recordingTabs.add(tabId);
+      await injectRecorder(tabId);
+      return { id, result: { recording: true, tabId, url: tab.url || "" } };
+    }
+
+    case "Extension.stopRecording": {
+      const tabId = params.tabId;
+      if (typeof tabId !== "number") {
+        return { id, error: { code: -32602, message: "Missing or invalid tabId" } };
+      }
+      const wasRecording = recordingTabs.delete(tabId);
+      // Silence the listeners already in the page; they stay installed until
+      // it navigates, and a later startRecording turns them back on.
+      try {
+        await chrome.scripting.executeScript({
+          target: { tabId },
+          func: () => { window.__actionbookRecorderOff = true; },
+        });
+      } catch (_) {}
+      return { id, result: { recording: false, wasRecording, tabId } };
+    }
+
     case "Extension.status": {
       return {
         id,
         result: {
           connected: connectionState === "connected",
           attachedTabIds: Array.from(attachedTabs),
-          version: "0.4.0",
+          version: PROTOCOL_VERSION,
         },
       };
     }
@@ -927,6 +1044,9 @@ async function requestL3Confirmation(id, method, domain) {
     };
 
     broadcastL3Status({ method, domain: domain || "unknown", nonce });
+    // Tell the bridge a human is deciding, so it extends the request's
+    // deadline instead of cancelling it mid-prompt.
+    wsSend({ method: "Extension.confirmationPending", params: { id } });
   });
 }
 
@@ -1037,6 +1157,14 @@ function isSenderPopup(sender) {
 
 // Listen for messages from popup and offscreen document
 chrome.runtime.onMessage.addListener((message, sender, sendResponse) => {
+  if (message.type === "recordingEvent") {
+    // From recorder.js: only the main frame of a tab being recorded counts.
+    const tabId = sender.tab?.id;
+    if (sender.id === chrome.runtime.id && sender.frameId === 0 && recordingTabs.has(tabId)) {
+      sendRecordingEvent(tabId, message.event);
+    }
+    return false;
+  }
   if (message.type === "getState") {
     sendResponse({
       connectionState,
@@ -1113,11 +1241,24 @@ chrome.runtime.onMessage.addListener((message, sender, sendResponse) => {
 
 // Clean up debugger state when a tab is closed.
 chrome.tabs.onRemoved.addListener((tabId) => {
+  recordingTabs.delete(tabId);
   if (attachedTabs.delete(tabId)) {
     broadcastState();
   }
 });
 
+// Recorded tabs: report main-frame navigations and re-inject the recorder
+// into each new document.
+chrome.tabs.onUpdated.addListener((tabId, changeInfo) => {
+  if (!recordingTabs.has(tabId)) return;
+  if (changeInfo.url) {
+    sendRecordingEvent(tabId, { type: "navigate", url: changeInfo.url });
+  }
+  if (changeInfo.status === "complete") {
+    injectRecorder(tabId);
+  }
+});
+
 // Handle debugger detach events (user cancelled the debug banner, tab crashed,
 // etc.). Remove only the affected tab from the attached set.
 chrome.debugger.onDetach.addListener((source, reason) => {
@@ -0,0 +1,106 @@
+// Actionbook recorder content script.
+//
+// Injected by `Extension.startRecording` into a tab's main frame (isolated
+// world) and again after every navigation while the recording lasts. Reports
+// trusted clicks, committed field values, selections and Enter presses to the
+// background worker, which forwards them to the bridge as
+// `Extension.recordingEvent`.
+//
+// Event shapes, selector rules and anchors match LISTENER_JS, SELECTOR_JS and
+// FINGERPRINT_JS in packages/cli/src/daemon/flow_recording.rs; keep them in
+// sync.
+
+(() => {
+  window.__actionbookRecorderOff = false;
+  if (window.__actionbookRecorder) return;
+  window.__actionbookRecorder = true;
+
+  const send = (event) => {
+    if (window.__actionbookRecorderOff) return;
+    try {
+      chrome.runtime.sendMessage({ type: "recordingEvent", event });
+    } catch (_) {
+      // Extension reloaded: this document can no longer report.
+    }
+  };
+  const unique = (sel) => {
+    try { return document.querySelectorAll(sel).length === 1; } catch (_) { return false; }
+  };
+  const selectorFor = (el) => {
+    if (el.id && unique("#" + CSS.escape(el.id))) return "#" + CSS.escape(el.id);
+    const tag = el.tagName.toLowerCase();
+    for (const attr of ["data-testid", "data-test", "data-qa", "name", "aria-label", "placeholder"]) {
+      const v = el.getAttribute(attr);
+      if (!v) continue;
+      const sel = `${tag}[${attr}="${v.replace(/\\/g, "\\\\").replace(/"/g, '\\"')}"]`;
+      if (unique(sel)) return sel;
+    }
+    const parts = [];
+    for (let node = el; node && node.nodeType === 1 && node !== document.documentElement; node = node.parentElement) {
+      if (node.id) { parts.unshift("#" + CSS.escape(node.id)); break; }
+      const name = node.tagName.toLowerCase();
+      const same = node.parentElement
+        ? Array.from(node.parentElement.children).filter((c) => c.tagName === node.tagName)
+        : [];
+      parts.unshift(same.length > 1 ? `${name}:nth-of-type(${same.indexOf(node) + 1})` : name);
+    }
+    return parts.join(" > ");
+  };
+  const IMPLICIT = { a: "link", button: "button", select: "combobox", textarea: "textbox", summary: "button", img: "img", option: "option" };
+  const INPUT_ROLES = { checkbox: "checkbox", radio: "radio", submit: "button", button: "button", reset: "button", image: "button", range: "slider", search: "searchbox" };
+  const clip = (s) => (s || "").replace(/\s+/g, " ").trim().slice(0, 80);
+  const anchorFor = (el) => {
+    const tag = el.tagName.toLowerCase();
+    const role = el.getAttribute("role") || (tag === "input" ? INPUT_ROLES[el.type] || "textbox" : IMPLICIT[tag]) || "";
+    const field = tag === "input" || tag === "select" || tag === "textarea";
+    const labelledBy = (el.getAttribute("aria-labelledby") || "").split(" ")[0];
+    const byId = labelledBy && document.getElementById(labelledBy);
+    const label = (el.labels && el.labels[0]) || el.closest("label");
+    const name = clip(el.getAttribute("aria-label") || (byId && byId.innerText) || (label && label.innerText)
+      || el.getAttribute("alt") || el.getAttribute("title")
+      || (field ? el.getAttribute("placeholder") || (["submit", "button", "reset"].includes(el.type) ? el.value : "") : el.innerText));
+    const text = field ? "" : clip(el.innerText);
+    let near = "";
+    for (let node = el.parentElement, i = 0; node && i < 3 && !near; node = node.parentElement, i++) {
+      near = clip((node.innerText || "").replace(el.innerText || "", ""));
+    }
+    const attrs = {};
+    for (const a of ["id", "name", "type", "data-testid", "data-test", "data-qa", "aria-label", "placeholder", "href"]) {
+      const v = el.getAttribute(a);
+      if (v) attrs[a] = v.slice(0, 200);
+    }
+    const fp = { tag, role, name, text, near, attrs };
+    for (const k of ["role", "name", "text", "near"]) if (!fp[k]) delete fp[k];
+    return fp;
+  };
+  const NOT_TEXT = ["checkbox", "radio", "submit", "button", "reset", "file", "image", "range", "color"];
+  const isText = (el) =>
+    el.tagName === "TEXTAREA" || (el.tagName === "INPUT" && !NOT_TEXT.includes(el.type));
+  const sent = new WeakMap();
+  const sendFill = (el) => {
+    if (sent.get(el) === el.value) return;
+    sent.set(el, el.value);
+    const secret = el.type === "password" || /pass|secret|token|otp/i.test(el.name + " " + el.autocomplete);
+    send({ type: "fill", selector: selectorFor(el), anchor: anchorFor(el), text: secret ? "" : el.value, secret });
+  };
+
+  document.addEventListener("click", (e) => {
+    if (!e.isTrusted) return;
+    const el = e.target.closest(
+      'a, button, input, select, textarea, label, summary, [role="button"], [role="link"], [role="menuitem"], [role="tab"], [role="checkbox"], [onclick]'
+    ) || e.target;
+    if (isText(el) || el.tagName === "SELECT") return;
+    send({ type: "click", selector: selectorFor(el), anchor: anchorFor(el) });
+  }, true);
+  document.addEventListener("change", (e) => {
+    if (!e.isTrusted) return;
+    const el = e.target;
+    if (el.tagName === "SELECT") send({ type: "select", selector: selectorFor(el), anchor: anchorFor(el), value: el.value });
+    else if (isText(el)) sendFill(el);
+  }, true);
+  document.addEventListener("keydown", (e) => {
+    if (!e.isTrusted || e.key !== "Enter" || !isText(e.target) || e.target.tagName === "TEXTAREA") return;
+    sendFill(e.target);
+    send({ type: "press", key: "Enter" });
+  }, true);
+})();
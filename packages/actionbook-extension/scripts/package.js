@@ -19,6 +19,7 @@ const zipPath = path.join(DIST, zipName)
 const includeFiles = [
   'manifest.json',
   'background.js',
+  'recorder.js',
   'popup.html',
   'popup.js',
   'offscreen.html',
@@ -1,9 +1,14 @@
 use serde::{Deserialize, Serialize};
 
-use crate::browser::{cookies, interaction, navigation, observation, session, storage, tab, wait};
+use crate::browser::{
+    cookies, downloads, indexeddb, interaction, navigation, observation, session, storage, tab,
+    wait,
+};
 use crate::extension;
 
 /// CLI → Daemon action protocol. Each variant wraps the command's Cmd type.
+// One per request; boxing `StartSession` would only add noise at every match.
+#[allow(clippy::large_enum_variant)]
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(tag = "type")]
 pub enum Action {
@@ -13,29 +18,44 @@ pub enum Action {
     SessionStatus(session::status::Cmd),
     Close(session::close::Cmd),
     Restart(session::restart::Cmd),
+    Emulate(session::emulate::Cmd),
+    EmulateNetwork(session::emulate_network::Cmd),
+    StateBundle(session::state_bundle::BundleCmd),
+    StateImport(session::state_bundle::ImportCmd),
 
     // ── Tab management ─────────────────────────────────────────
     NewTab(tab::open::Cmd),
     BatchOpen(tab::batch_open::Cmd),
     CloseTab(tab::close::Cmd),
+    Switch(tab::switch::Cmd),
     ListTabs(tab::list::Cmd),
+    Window(tab::window::Cmd),
 
     // ── Navigation ─────────────────────────────────────────────
     Goto(navigation::goto::Cmd),
     Back(navigation::back::Cmd),
     Forward(navigation::forward::Cmd),
     Reload(navigation::reload::Cmd),
+    Fetch(navigation::fetch::Cmd),
+    TraceRedirects(navigation::trace_redirects::Cmd),
 
     // ── Observation ────────────────────────────────────────────
     BatchSnapshot(observation::batch_snapshot::Cmd),
     Snapshot(observation::snapshot::Cmd),
     Screenshot(observation::screenshot::Cmd),
     Title(observation::title::Cmd),
+    Meta(observation::meta::Cmd),
+    Response(observation::response::Cmd),
+    Assert(observation::assert::Cmd),
+    Feeds(observation::feeds::Cmd),
+    Images(observation::images::Cmd),
+    Table(observation::table::Cmd),
     Url(observation::url::Cmd),
     Viewport(observation::viewport::Cmd),
     Html(observation::html::Cmd),
     Text(observation::text::Cmd),
     Value(observation::value::Cmd),
+    Options(observation::options::Cmd),
     Attr(observation::attr::Cmd),
     Attrs(observation::attrs::Cmd),
     Box(observation::r#box::Cmd),
@@ -45,12 +65,16 @@ pub enum Action {
     Query(observation::query::Cmd),
     InspectPoint(observation::inspect_point::Cmd),
     Pdf(observation::pdf::Cmd),
+    Save(observation::save::Cmd),
     LogsConsole(observation::logs_console::Cmd),
     LogsErrors(observation::logs_errors::Cmd),
     NetworkRequests(observation::network_requests::Cmd),
     NetworkRequestDetail(observation::network_request_detail::Cmd),
     NetworkHarStart(observation::network_har::StartCmd),
     NetworkHarStop(observation::network_har::StopCmd),
+    NetworkRouteAdd(observation::network_route::AddCmd),
+    NetworkRouteList(observation::network_route::ListCmd),
+    NetworkRouteRemove(observation::network_route::RemoveCmd),
 
     // ── Cookies ────────────────────────────────────────────────
     CookiesList(cookies::list::Cmd),
@@ -58,6 +82,12 @@ pub enum Action {
     CookiesSet(cookies::set::Cmd),
     CookiesDelete(cookies::delete::Cmd),
     CookiesClear(cookies::clear::Cmd),
+    CookiesExport(cookies::export::Cmd),
+    CookiesImport(cookies::import::Cmd),
+
+    // ── Downloads ──────────────────────────────────────────────
+    DownloadsList(downloads::list::Cmd),
+    DownloadsWait(downloads::wait::Cmd),
 
     // ── Storage ────────────────────────────────────────────────
     StorageList(storage::list::Cmd),
@@ -65,31 +95,52 @@ pub enum Action {
     StorageSet(storage::set::Cmd),
     StorageDelete(storage::delete::Cmd),
     StorageClear(storage::clear::Cmd),
+    IndexedDbList(indexeddb::list::Cmd),
+    IndexedDbDump(indexeddb::dump::Cmd),
 
     // ── Wait ───────────────────────────────────────────────────
     WaitElement(wait::element::Cmd),
     WaitNavigation(wait::navigation::Cmd),
     WaitNetworkIdle(wait::network_idle::Cmd),
     WaitCondition(wait::condition::Cmd),
+    WaitText(wait::text::Cmd),
+    WaitUrl(wait::url::Cmd),
 
     // ── Extension ──────────────────────────────────────────────
     ExtensionStatus(extension::status::Cmd),
+    ExtensionVersion(extension::version::Cmd),
 
     // ── Interaction ────────────────────────────────────────────
     Eval(interaction::eval::Cmd),
     Click(interaction::click::Cmd),
     BatchClick(interaction::batch_click::Cmd),
     Hover(interaction::hover::Cmd),
+    Menu(interaction::menu::Cmd),
+    Handoff(interaction::handoff::Cmd),
     Focus(interaction::focus::Cmd),
+    TabTo(interaction::tabto::Cmd),
     Press(interaction::press::Cmd),
     Type(interaction::type_text::Cmd),
     Fill(interaction::fill::Cmd),
     Select(interaction::select::Cmd),
+    CanvasClickColor(interaction::canvas::ClickColorCmd),
+    CanvasDraw(interaction::canvas::DrawCmd),
+    RecordStart(interaction::record::StartCmd),
+    RecordStop(interaction::record::StopCmd),
+    RecordVideoStart(observation::record_video::StartCmd),
+    RecordVideoStop(observation::record_video::StopCmd),
+    TraceStart(observation::trace::StartCmd),
+    TraceStop(observation::trace::StopCmd),
+    Date(interaction::date::Cmd),
+    Slide(interaction::slide::Cmd),
     Drag(interaction::drag::Cmd),
     Upload(interaction::upload::Cmd),
     MouseMove(interaction::mouse_move::Cmd),
     CursorPosition(interaction::cursor_position::Cmd),
     Scroll(interaction::scroll::Cmd),
+    Tap(interaction::touch::TapCmd),
+    Swipe(interaction::touch::SwipeCmd),
+    Pinch(interaction::touch::PinchCmd),
 }
 
 impl Action {
@@ -112,17 +163,24 @@ impl Action {
         match self {
             // Extension (no session/tab)
             Action::ExtensionStatus(_) => "-".into(),
+            Action::ExtensionVersion(_) => "-".into(),
 
             // Session-level (no tab)
             Action::StartSession(_) | Action::ListSessions(_) => "-".into(),
             Action::SessionStatus(c) => s_only!(c),
             Action::Close(c) => s_only!(c),
             Action::Restart(c) => s_only!(c),
+            Action::Emulate(c) => s_only!(c),
+            Action::EmulateNetwork(c) => s_only!(c),
+            Action::StateBundle(c) => s_only!(c),
+            Action::StateImport(c) => s_only!(c),
 
             // Tab management
             Action::NewTab(c) => s_only!(c),
             Action::BatchOpen(c) => s_only!(c),
             Action::CloseTab(c) => st!(c),
+            Action::Switch(c) => st!(c),
+            Action::Window(c) => st!(c),
             Action::ListTabs(c) => s_only!(c),
 
             // Navigation
@@ -130,17 +188,26 @@ impl Action {
             Action::Back(c) => st!(c),
             Action::Forward(c) => st!(c),
             Action::Reload(c) => st!(c),
+            Action::Fetch(c) => st!(c),
+            Action::TraceRedirects(c) => st!(c),
 
             // Observation
             Action::BatchSnapshot(c) => c.session.clone(),
             Action::Snapshot(c) => st!(c),
             Action::Screenshot(c) => st!(c),
             Action::Title(c) => st!(c),
+            Action::Meta(c) => st!(c),
+            Action::Response(c) => st!(c),
+            Action::Assert(c) => st!(c),
+            Action::Feeds(c) => st!(c),
+            Action::Images(c) => st!(c),
+            Action::Table(c) => st!(c),
             Action::Url(c) => st!(c),
             Action::Viewport(c) => st!(c),
             Action::Html(c) => st!(c),
             Action::Text(c) => st!(c),
             Action::Value(c) => st!(c),
+            Action::Options(c) => st!(c),
             Action::Attr(c) => st!(c),
             Action::Attrs(c) => st!(c),
             Action::Box(c) => st!(c),
@@ -150,12 +217,16 @@ impl Action {
             Action::Query(c) => format!("{}/{}", c.session(), c.tab()),
             Action::InspectPoint(c) => st!(c),
             Action::Pdf(c) => st!(c),
+            Action::Save(c) => st!(c),
             Action::LogsConsole(c) => st!(c),
             Action::LogsErrors(c) => st!(c),
             Action::NetworkRequests(c) => st!(c),
             Action::NetworkRequestDetail(c) => st!(c),
             Action::NetworkHarStart(c) => st!(c),
             Action::NetworkHarStop(c) => st!(c),
+            Action::NetworkRouteAdd(c) => st!(c),
+            Action::NetworkRouteList(c) => st!(c),
+            Action::NetworkRouteRemove(c) => st!(c),
 
             // Cookies (session-level, no tab)
             Action::CookiesList(c) => s_only!(c),
@@ -163,6 +234,11 @@ impl Action {
             Action::CookiesSet(c) => s_only!(c),
             Action::CookiesDelete(c) => s_only!(c),
             Action::CookiesClear(c) => s_only!(c),
+            Action::CookiesExport(c) => s_only!(c),
+            Action::CookiesImport(c) => s_only!(c),
+            // Downloads (session-level, no tab)
+            Action::DownloadsList(c) => s_only!(c),
+            Action::DownloadsWait(c) => s_only!(c),
 
             // Storage
             Action::StorageList(c) => st!(c),
@@ -170,28 +246,139 @@ impl Action {
             Action::StorageSet(c) => st!(c),
             Action::StorageDelete(c) => st!(c),
             Action::StorageClear(c) => st!(c),
+            Action::IndexedDbList(c) => st!(c),
+            Action::IndexedDbDump(c) => st!(c),
 
             // Wait
             Action::WaitElement(c) => st!(c),
             Action::WaitNavigation(c) => st!(c),
             Action::WaitNetworkIdle(c) => st!(c),
             Action::WaitCondition(c) => st!(c),
+            Action::WaitText(c) => st!(c),
+            Action::WaitUrl(c) => st!(c),
 
             // Interaction
             Action::Eval(c) => st!(c),
             Action::Click(c) => st!(c),
             Action::BatchClick(c) => st!(c),
             Action::Hover(c) => st!(c),
+            Action::Menu(c) => st!(c),
+            Action::Handoff(c) => st!(c),
             Action::Focus(c) => st!(c),
+            Action::TabTo(c) => st!(c),
             Action::Press(c) => st!(c),
             Action::Type(c) => st!(c),
             Action::Fill(c) => st!(c),
             Action::Select(c) => st!(c),
+            Action::CanvasClickColor(c) => st!(c),
+            Action::CanvasDraw(c) => st!(c),
+            Action::RecordStart(c) => st!(c),
+            Action::RecordStop(c) => st!(c),
+            Action::RecordVideoStart(c) => st!(c),
+            Action::RecordVideoStop(c) => st!(c),
+            Action::TraceStart(c) => s_only!(c),
+            Action::TraceStop(c) => s_only!(c),
+            Action::Date(c) => st!(c),
+            Action::Slide(c) => st!(c),
             Action::Drag(c) => st!(c),
             Action::Upload(c) => st!(c),
             Action::MouseMove(c) => st!(c),
             Action::CursorPosition(c) => st!(c),
             Action::Scroll(c) => st!(c),
+            Action::Tap(c) => st!(c),
+            Action::Swipe(c) => st!(c),
+            Action::Pinch(c) => st!(c),
+        }
+    }
+
+    /// The (session, tab) a tab-scoped command addresses, with the tab
+    /// writable so `--tab active` can be resolved before routing.
+    pub fn tab_slot(&mut self) -> Option<(&str, &mut String)> {
+        match self {
+            Action::CloseTab(c) => Some((&c.session, &mut c.tab)),
+            Action::Window(c) => Some((&c.session, &mut c.tab)),
+            Action::Goto(c) => Some((&c.session, &mut c.tab)),
+            Action::Back(c) => Some((&c.session, &mut c.tab)),
+            Action::Forward(c) => Some((&c.session, &mut c.tab)),
+            Action::Reload(c) => Some((&c.session, &mut c.tab)),
+            Action::Fetch(c) => Some((&c.session, &mut c.tab)),
+            Action::TraceRedirects(c) => Some((&c.session, &mut c.tab)),
+            Action::Snapshot(c) => Some((&c.session, &mut c.tab)),
+            Action::Screenshot(c) => Some((&c.session, &mut c.tab)),
+            Action::Title(c) => Some((&c.session, &mut c.tab)),
+            Action::Meta(c) => Some((&c.session, &mut c.tab)),
+            Action::Response(c) => Some((&c.session, &mut c.tab)),
+            Action::Assert(c) => Some((&c.session, &mut c.tab)),
+            Action::Feeds(c) => Some((&c.session, &mut c.tab)),
+            Action::Images(c) => Some((&c.session, &mut c.tab)),
+            Action::Table(c) => Some((&c.session, &mut c.tab)),
+            Action::Url(c) => Some((&c.session, &mut c.tab)),
+            Action::Viewport(c) => Some((&c.session, &mut c.tab)),
+            Action::Html(c) => Some((&c.session, &mut c.tab)),
+            Action::Text(c) => Some((&c.session, &mut c.tab)),
+            Action::Value(c) => Some((&c.session, &mut c.tab)),
+            Action::Options(c) => Some((&c.session, &mut c.tab)),
+            Action::Attr(c) => Some((&c.session, &mut c.tab)),
+            Action::Attrs(c) => Some((&c.session, &mut c.tab)),
+            Action::Box(c) => Some((&c.session, &mut c.tab)),
+            Action::Styles(c) => Some((&c.session, &mut c.tab)),
+            Action::Describe(c) => Some((&c.session, &mut c.tab)),
+            Action::State(c) => Some((&c.session, &mut c.tab)),
+            Action::InspectPoint(c) => Some((&c.session, &mut c.tab)),
+            Action::Pdf(c) => Some((&c.session, &mut c.tab)),
+            Action::Save(c) => Some((&c.session, &mut c.tab)),
+            Action::LogsConsole(c) => Some((&c.session, &mut c.tab)),
+            Action::LogsErrors(c) => Some((&c.session, &mut c.tab)),
+            Action::NetworkRequests(c) => Some((&c.session, &mut c.tab)),
+            Action::NetworkRequestDetail(c) => Some((&c.session, &mut c.tab)),
+            Action::NetworkHarStart(c) => Some((&c.session, &mut c.tab)),
+            Action::NetworkHarStop(c) => Some((&c.session, &mut c.tab)),
+            Action::NetworkRouteAdd(c) => Some((&c.session, &mut c.tab)),
+            Action::NetworkRouteList(c) => Some((&c.session, &mut c.tab)),
+            Action::NetworkRouteRemove(c) => Some((&c.session, &mut c.tab)),
+            Action::StorageList(c) => Some((&c.session, &mut c.tab)),
+            Action::StorageGet(c) => Some((&c.session, &mut c.tab)),
+            Action::StorageSet(c) => Some((&c.session, &mut c.tab)),
+            Action::StorageDelete(c) => Some((&c.session, &mut c.tab)),
+            Action::StorageClear(c) => Some((&c.session, &mut c.tab)),
+            Action::IndexedDbList(c) => Some((&c.session, &mut c.tab)),
+            Action::IndexedDbDump(c) => Some((&c.session, &mut c.tab)),
+            Action::WaitElement(c) => Some((&c.session, &mut c.tab)),
+            Action::WaitNavigation(c) => Some((&c.session, &mut c.tab)),
+            Action::WaitNetworkIdle(c) => Some((&c.session, &mut c.tab)),
+            Action::WaitCondition(c) => Some((&c.session, &mut c.tab)),
+            Action::WaitText(c) => Some((&c.session, &mut c.tab)),
+            Action::WaitUrl(c) => Some((&c.session, &mut c.tab)),
+            Action::Eval(c) => Some((&c.session, &mut c.tab)),
+            Action::Click(c) => Some((&c.session, &mut c.tab)),
+            Action::BatchClick(c) => Some((&c.session, &mut c.tab)),
+            Action::Hover(c) => Some((&c.session, &mut c.tab)),
+            Action::Menu(c) => Some((&c.session, &mut c.tab)),
+            Action::Handoff(c) => Some((&c.session, &mut c.tab)),
+            Action::Focus(c) => Some((&c.session, &mut c.tab)),
+            Action::TabTo(c) => Some((&c.session, &mut c.tab)),
+            Action::Press(c) => Some((&c.session, &mut c.tab)),
+            Action::Type(c) => Some((&c.session, &mut c.tab)),
+            Action::Fill(c) => Some((&c.session, &mut c.tab)),
+            Action::Select(c) => Some((&c.session, &mut c.tab)),
+            Action::CanvasClickColor(c) => Some((&c.session, &mut c.tab)),
+            Action::CanvasDraw(c) => Some((&c.session, &mut c.tab)),
+            Action::RecordStart(c) => Some((&c.session, &mut c.tab)),
+            Action::RecordStop(c) => Some((&c.session, &mut c.tab)),
+            Action::RecordVideoStart(c) => Some((&c.session, &mut c.tab)),
+            Action::RecordVideoStop(c) => Some((&c.session, &mut c.tab)),
+            Action::Date(c) => Some((&c.session, &mut c.tab)),
+            Action::Slide(c) => Some((&c.session, &mut c.tab)),
+            Action::Drag(c) => Some((&c.session, &mut c.tab)),
+            Action::Upload(c) => Some((&c.session, &mut c.tab)),
+            Action::MouseMove(c) => Some((&c.session, &mut c.tab)),
+            Action::CursorPosition(c) => Some((&c.session, &mut c.tab)),
+            Action::Scroll(c) => Some((&c.session, &mut c.tab)),
+            Action::Tap(c) => Some((&c.session, &mut c.tab)),
+            Action::Swipe(c) => Some((&c.session, &mut c.tab)),
+            Action::Pinch(c) => Some((&c.session, &mut c.tab)),
+            Action::Query(c) => Some(c.session_and_tab_mut()),
+            _ => None,
         }
     }
 
@@ -199,28 +386,44 @@ impl Action {
     pub fn command_name(&self) -> &str {
         match self {
             Action::ExtensionStatus(_) => extension::status::COMMAND_NAME,
+            Action::ExtensionVersion(_) => extension::version::COMMAND_NAME,
             Action::StartSession(_) => session::start::COMMAND_NAME,
             Action::ListSessions(_) => session::list::COMMAND_NAME,
             Action::SessionStatus(_) => session::status::COMMAND_NAME,
             Action::Close(_) => session::close::COMMAND_NAME,
             Action::Restart(_) => session::restart::COMMAND_NAME,
+            Action::Emulate(_) => session::emulate::COMMAND_NAME,
+            Action::EmulateNetwork(_) => session::emulate_network::COMMAND_NAME,
+            Action::StateBundle(_) => session::state_bundle::BUNDLE_COMMAND_NAME,
+            Action::StateImport(_) => session::state_bundle::IMPORT_COMMAND_NAME,
             Action::NewTab(_) => tab::open::COMMAND_NAME,
             Action::BatchOpen(_) => tab::batch_open::COMMAND_NAME,
             Action::CloseTab(_) => tab::close::COMMAND_NAME,
+            Action::Switch(_) => tab::switch::COMMAND_NAME,
+            Action::Window(_) => tab::window::COMMAND_NAME,
             Action::ListTabs(_) => tab::list::COMMAND_NAME,
             Action::Goto(_) => navigation::goto::COMMAND_NAME,
             Action::Back(_) => navigation::back::COMMAND_NAME,
             Action::Forward(_) => navigation::forward::COMMAND_NAME,
             Action::Reload(_) => navigation::reload::COMMAND_NAME,
+            Action::Fetch(_) => navigation::fetch::COMMAND_NAME,
+            Action::TraceRedirects(_) => navigation::trace_redirects::COMMAND_NAME,
             Action::BatchSnapshot(_) => observation::batch_snapshot::COMMAND_NAME,
             Action::Snapshot(_) => observation::snapshot::COMMAND_NAME,
             Action::Screenshot(_) => observation::screenshot::COMMAND_NAME,
             Action::Title(_) => observation::title::COMMAND_NAME,
+            Action::Meta(_) => observation::meta::COMMAND_NAME,
+            Action::Response(_) => observation::response::COMMAND_NAME,
+            Action::Assert(_) => observation::assert::COMMAND_NAME,
+            Action::Feeds(_) => observation::feeds::COMMAND_NAME,
+            Action::Images(_) => observation::images::COMMAND_NAME,
+            Action::Table(_) => observation::table::COMMAND_NAME,
             Action::Url(_) => observation::url::COMMAND_NAME,
             Action::Viewport(_) => observation::viewport::COMMAND_NAME,
             Action::Html(_) => observation::html::COMMAND_NAME,
             Action::Text(_) => observation::text::COMMAND_NAME,
             Action::Value(_) => observation::value::COMMAND_NAME,
+            Action::Options(_) => observation::options::COMMAND_NAME,
             Action::Attr(_) => observation::attr::COMMAND_NAME,
             Action::Attrs(_) => observation::attrs::COMMAND_NAME,
             Action::Box(_) => observation::r#box::COMMAND_NAME,
@@ -230,40 +433,68 @@ impl Action {
             Action::Query(_) => observation::query::COMMAND_NAME,
             Action::InspectPoint(_) => observation::inspect_point::COMMAND_NAME,
             Action::Pdf(_) => observation::pdf::COMMAND_NAME,
+            Action::Save(_) => observation::save::COMMAND_NAME,
             Action::LogsConsole(_) => observation::logs_console::COMMAND_NAME,
             Action::LogsErrors(_) => observation::logs_errors::COMMAND_NAME,
             Action::NetworkRequests(_) => observation::network_requests::COMMAND_NAME,
             Action::NetworkRequestDetail(_) => observation::network_request_detail::COMMAND_NAME,
             Action::NetworkHarStart(_) => observation::network_har::START_COMMAND_NAME,
             Action::NetworkHarStop(_) => observation::network_har::STOP_COMMAND_NAME,
+            Action::NetworkRouteAdd(_) => observation::network_route::ADD_COMMAND_NAME,
+            Action::NetworkRouteList(_) => observation::network_route::LIST_COMMAND_NAME,
+            Action::NetworkRouteRemove(_) => observation::network_route::REMOVE_COMMAND_NAME,
             Action::CookiesList(_) => cookies::list::COMMAND_NAME,
             Action::CookiesGet(_) => cookies::get::COMMAND_NAME,
             Action::CookiesSet(_) => cookies::set::COMMAND_NAME,
             Action::CookiesDelete(_) => cookies::delete::COMMAND_NAME,
             Action::CookiesClear(_) => cookies::clear::COMMAND_NAME,
+            Action::CookiesExport(_) => cookies::export::COMMAND_NAME,
+            Action::CookiesImport(_) => cookies::import::COMMAND_NAME,
+            Action::DownloadsList(_) => downloads::list::COMMAND_NAME,
+            Action::DownloadsWait(_) => downloads::wait::COMMAND_NAME,
             Action::StorageList(cmd) => storage::list::command_name(cmd.kind),
             Action::StorageGet(cmd) => storage::get::command_name(cmd.kind),
             Action::StorageSet(cmd) => storage::set::command_name(cmd.kind),
             Action::StorageDelete(cmd) => storage::delete::command_name(cmd.kind),
             Action::StorageClear(cmd) => storage::clear::command_name(cmd.kind),
+            Action::IndexedDbList(_) => indexeddb::list::COMMAND_NAME,
+            Action::IndexedDbDump(_) => indexeddb::dump::COMMAND_NAME,
             Action::WaitElement(_) => wait::element::COMMAND_NAME,
             Action::WaitNavigation(_) => wait::navigation::COMMAND_NAME,
             Action::WaitNetworkIdle(_) => wait::network_idle::COMMAND_NAME,
             Action::WaitCondition(_) => wait::condition::COMMAND_NAME,
+            Action::WaitText(_) => wait::text::COMMAND_NAME,
+            Action::WaitUrl(_) => wait::url::COMMAND_NAME,
             Action::Eval(_) => interaction::eval::COMMAND_NAME,
             Action::Click(_) => interaction::click::COMMAND_NAME,
             Action::BatchClick(_) => interaction::batch_click::COMMAND_NAME,
             Action::Hover(_) => interaction::hover::COMMAND_NAME,
+            Action::Menu(_) => interaction::menu::COMMAND_NAME,
+            Action::Handoff(_) => interaction::handoff::COMMAND_NAME,
             Action::Focus(_) => interaction::focus::COMMAND_NAME,
+            Action::TabTo(_) => interaction::tabto::COMMAND_NAME,
             Action::Press(_) => interaction::press::COMMAND_NAME,
             Action::Type(_) => interaction::type_text::COMMAND_NAME,
             Action::Fill(_) => interaction::fill::COMMAND_NAME,
             Action::Select(_) => interaction::select::COMMAND_NAME,
+            Action::CanvasClickColor(_) => interaction::canvas::CLICK_COLOR_COMMAND_NAME,
+            Action::CanvasDraw(_) => interaction::canvas::DRAW_COMMAND_NAME,
+            Action::RecordStart(_) => interaction::record::START_COMMAND_NAME,
+            Action::RecordStop(_) => interaction::record::STOP_COMMAND_NAME,
+            Action::RecordVideoStart(_) => observation::record_video::START_COMMAND_NAME,
+            Action::RecordVideoStop(_) => observation::record_video::STOP_COMMAND_NAME,
+            Action::TraceStart(_) => observation::trace::START_COMMAND_NAME,
+            Action::TraceStop(_) => observation::trace::STOP_COMMAND_NAME,
+            Action::Date(_) => interaction::date::COMMAND_NAME,
+            Action::Slide(_) => interaction::slide::COMMAND_NAME,
             Action::Drag(_) => interaction::drag::COMMAND_NAME,
             Action::Upload(_) => interaction::upload::COMMAND_NAME,
             Action::MouseMove(_) => interaction::mouse_move::COMMAND_NAME,
             Action::CursorPosition(_) => interaction::cursor_position::COMMAND_NAME,
             Action::Scroll(_) => interaction::scroll::COMMAND_NAME,
+            Action::Tap(_) => interaction::touch::TAP_COMMAND_NAME,
+            Action::Swipe(_) => interaction::touch::SWIPE_COMMAND_NAME,
+            Action::Pinch(_) => interaction::touch::PINCH_COMMAND_NAME,
         }
     }
 }
@@ -75,10 +75,65 @@ impl ApiClient {
     }
 
     /// Get action by area ID (returns plain text)
-    pub async fn get_action_by_area_id(&self, area_id: &str) -> Result<String, CliError> {
+    ///
+    /// Servers that know `sections` and `max_tokens` trim the text
+    /// themselves; others ignore them and return the full document.
+    pub async fn get_action_by_area_id(
+        &self,
+        params: &GetActionParams,
+    ) -> Result<String, CliError> {
+        let mut query_params = vec![("area_id", params.area_id.clone())];
+
+        if !params.sections.is_empty() {
+            query_params.push(("sections", params.sections.join(",")));
+        }
+
+        if let Some(max_tokens) = params.max_tokens {
+            query_params.push(("max_tokens", max_tokens.to_string()));
+        }
+
         let response = self
             .request_text(reqwest::Method::GET, "/api/get_action_by_area_id")
-            .query(&[("area_id", area_id)])
+            .query(&query_params)
+            .send()
+            .await
+            .map_err(|e| CliError::ApiError(format!("Request failed: {}", e)))?;
+
+        self.handle_text_response(response).await
+    }
+
+    /// Report a run outcome for an action book (returns plain text)
+    pub async fn report_action(&self, report: &ActionReport) -> Result<String, CliError> {
+        let response = self
+            .request_text(reqwest::Method::POST, "/api/report_action")
+            .json(report)
+            .send()
+            .await
+            .map_err(|e| CliError::ApiError(format!("Request failed: {}", e)))?;
+
+        self.handle_text_response(response).await
+    }
+
+    /// Upload an action book draft (returns plain text)
+    pub async fn publish_action(&self, params: &PublishActionParams) -> Result<String, CliError> {
+        let response = self
+            .request_text(reqwest::Method::POST, "/api/publish_action")
+            .json(params)
+            .send()
+            .await
+            .map_err(|e| CliError::ApiError(format!("Request failed: {}", e)))?;
+
+        self.handle_text_response(response).await
+    }
+
+    /// Upload anonymous usage counters from `actionbook stats upload`
+    pub async fn upload_usage_stats(
+        &self,
+        payload: &serde_json::Value,
+    ) -> Result<String, CliError> {
+        let response = self
+            .request_text(reqwest::Method::POST, "/api/usage_stats")
+            .json(payload)
             .send()
             .await
             .map_err(|e| CliError::ApiError(format!("Request failed: {}", e)))?;
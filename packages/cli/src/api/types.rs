@@ -1,3 +1,5 @@
+use serde::Serialize;
+
 /// Parameters for searching actions (new text-based API)
 #[derive(Debug, Default)]
 pub struct SearchActionsParams {
@@ -8,3 +10,34 @@ pub struct SearchActionsParams {
     pub page: Option<u32>,
     pub page_size: Option<u32>,
 }
+
+/// Parameters for fetching an action book by area ID
+#[derive(Debug, Default)]
+pub struct GetActionParams {
+    pub area_id: String,
+    /// Only these `## ` sections (lower-case names)
+    pub sections: Vec<String>,
+    /// Approximate token budget for the returned text
+    pub max_tokens: Option<u32>,
+}
+
+/// A run outcome reported back for an action book (`actionbook report`)
+#[derive(Debug, Serialize)]
+pub struct ActionReport {
+    pub area_id: String,
+    /// `success` or `failure`
+    pub outcome: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub notes: Option<String>,
+    /// The `actionbook run` result the outcome came from
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub run_report: Option<serde_json::Value>,
+}
+
+/// An action book draft uploaded with `actionbook publish`
+#[derive(Debug, Serialize)]
+pub struct PublishActionParams {
+    pub action_book: serde_json::Value,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub source_id: Option<String>,
+}
@@ -0,0 +1,755 @@
+//! Append-only audit log of browser actions.
+//!
+//! The daemon appends one JSON line per routed action to
+//! `~/.actionbook/audit/audit.jsonl`, from a writer thread of its own so file
+//! IO never runs on the async workers. When the active file exceeds
+//! [`MAX_FILE_BYTES`] it is rotated to `audit.1.jsonl` (older files shift up)
+//! and at most [`MAX_ROTATED_FILES`] rotated files are kept.
+//!
+//! `actionbook audit tail` / `actionbook audit search` read the log directly —
+//! they do not need a running daemon.
+
+use std::fs::{self, OpenOptions};
+use std::io::{BufRead, BufReader, Write as _};
+use std::path::{Path, PathBuf};
+use std::sync::mpsc::{self, Sender};
+use std::sync::{Mutex, OnceLock};
+use std::time::Duration;
+
+use clap::{Args, ValueEnum};
+use serde::{Deserialize, Serialize};
+use serde_json::{Value, json};
+
+use crate::action::Action;
+use crate::action_result::ActionResult;
+use crate::config;
+use crate::daemon::guardrails;
+
+pub const COMMAND_NAME_TAIL: &str = "audit tail";
+pub const COMMAND_NAME_SEARCH: &str = "audit search";
+
+/// Rotate the active log once it grows past this size.
+pub const MAX_FILE_BYTES: u64 = 10 * 1024 * 1024;
+/// Number of rotated files kept next to the active log.
+pub const MAX_ROTATED_FILES: usize = 5;
+
+/// Serializes rotation + append across concurrent daemon connections.
+static WRITE_LOCK: Mutex<()> = Mutex::new(());
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize, ValueEnum)]
+#[serde(rename_all = "lowercase")]
+pub enum RiskLevel {
+    /// Read-only observation
+    Low,
+    /// Navigation and page interaction
+    Medium,
+    /// Script execution, uploads, state mutation, guardrail events
+    High,
+}
+
+/// One audit log line.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AuditRecord {
+    pub ts: String,
+    pub command: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub session_id: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tab_id: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub profile: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub backend: Option<String>,
+    /// Selector(s) or URL(s) the action targeted.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub target: Option<String>,
+    pub risk: RiskLevel,
+    /// `ok`, `fatal`, `retryable` or `user_action`.
+    pub result: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error_code: Option<String>,
+    pub duration_ms: u64,
+    /// The action carried a secret (`--secret` or a detected password field).
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub secret: bool,
+}
+
+/// Profile and backend of the session an action ran against.
+#[derive(Debug, Clone, Default)]
+pub struct SessionInfo {
+    pub profile: Option<String>,
+    pub backend: Option<String>,
+}
+
+pub fn audit_dir() -> PathBuf {
+    config::actionbook_home().join("audit")
+}
+
+fn active_log_path(dir: &Path) -> PathBuf {
+    dir.join("audit.jsonl")
+}
+
+fn rotated_log_path(dir: &Path, index: usize) -> PathBuf {
+    dir.join(format!("audit.{index}.jsonl"))
+}
+
+/// Log files oldest → newest.
+fn log_files_oldest_first(dir: &Path) -> Vec<PathBuf> {
+    let mut files: Vec<PathBuf> = (1..=MAX_ROTATED_FILES)
+        .rev()
+        .map(|i| rotated_log_path(dir, i))
+        .filter(|p| p.exists())
+        .collect();
+    let active = active_log_path(dir);
+    if active.exists() {
+        files.push(active);
+    }
+    files
+}
+
+// ── Recording ─────────────────────────────────────────────────────────
+
+/// Session / tab addressed by an action, from its log label (`s0/t1`, `s0`, `-`).
+pub fn action_address(action: &Action) -> (Option<String>, Option<String>) {
+    let label = action.session_tab_label();
+    if label == "-" {
+        return (None, None);
+    }
+    match label.split_once('/') {
+        Some((s, t)) => (Some(s.to_string()), Some(t.to_string())),
+        None => (Some(label), None),
+    }
+}
+
+/// Selector(s) / URL(s) an action targets, joined with spaces.
+///
+/// Only addressing fields are read — typed text, values and scripts are never
+/// copied into the audit log.
+pub fn action_target(action: &Action) -> Option<String> {
+    match action {
+        // `[selector] text`: only the selector (when present) is an address.
+        Action::Type(cmd) => return (cmd.args.len() == 2).then(|| cmd.args[0].clone()),
+        Action::Fill(cmd) => return (cmd.args.len() == 2).then(|| cmd.args[0].clone()),
+        Action::Drag(cmd) => return Some(format!("{} {}", cmd.source, cmd.destination)),
+        _ => {}
+    }
+    let value = serde_json::to_value(action).ok()?;
+    let mut parts: Vec<String> = Vec::new();
+    for key in ["url", "urls", "open_url", "selector", "selectors", "target"] {
+        match value.get(key) {
+            Some(Value::String(s)) if !s.is_empty() => parts.push(s.clone()),
+            Some(Value::Array(items)) => {
+                parts.extend(items.iter().filter_map(|v| v.as_str()).map(String::from))
+            }
+            _ => {}
+        }
+    }
+    (!parts.is_empty()).then(|| parts.join(" "))
+}
+
+/// Whether the action handled a secret value, either flagged by the caller or
+/// detected and redacted by the handler.
+pub fn is_secret(action: &Action, result: &ActionResult) -> bool {
+    let flagged = match action {
+        Action::Type(cmd) => cmd.secret,
+        Action::Fill(cmd) => cmd.secret,
+        Action::StateBundle(cmd) => cmd.password.is_some(),
+        Action::StateImport(cmd) => cmd.password.is_some(),
+        _ => false,
+    };
+    flagged
+        || matches!(result, ActionResult::Ok { data }
+            if data.pointer("/value_summary/redacted").is_some() || data.get("redacted").is_some())
+}
+
+/// Classify an action. Guardrail refusals and confirmed sensitive actions are
+/// always `high`.
+pub fn risk_level(action: &Action, result: &ActionResult) -> RiskLevel {
+    match result {
+        ActionResult::Fatal { code, .. }
+            if code == guardrails::DOMAIN_NOT_ALLOWED
+                || code == guardrails::CONFIRMATION_REQUIRED =>
+        {
+            return RiskLevel::High;
+        }
+        ActionResult::Ok { data } if data.get("sensitive").is_some() => return RiskLevel::High,
+        _ => {}
+    }
+    match action {
+        Action::Eval(_)
+        | Action::Upload(_)
+        | Action::CookiesSet(_)
+        | Action::CookiesDelete(_)
+        | Action::CookiesClear(_)
+        | Action::CookiesExport(_)
+        | Action::CookiesImport(_)
+        | Action::StateBundle(_)
+        | Action::StateImport(_)
+        | Action::StorageSet(_)
+        | Action::StorageDelete(_)
+        | Action::StorageClear(_) => RiskLevel::High,
+        // A non-GET fetch can change server state with the user's login.
+        Action::Fetch(c) if !matches!(c.method.to_ascii_uppercase().as_str(), "GET" | "HEAD") => {
+            RiskLevel::High
+        }
+        Action::StartSession(_)
+        | Action::Close(_)
+        | Action::Restart(_)
+        | Action::Emulate(_)
+        | Action::EmulateNetwork(_)
+        | Action::NewTab(_)
+        | Action::BatchOpen(_)
+        | Action::CloseTab(_)
+        | Action::Switch(_)
+        | Action::Window(_)
+        | Action::Goto(_)
+        | Action::Back(_)
+        | Action::Forward(_)
+        | Action::Reload(_)
+        | Action::Fetch(_)
+        | Action::TraceRedirects(_)
+        | Action::NetworkRouteAdd(_)
+        | Action::NetworkRouteRemove(_)
+        | Action::Click(_)
+        | Action::BatchClick(_)
+        | Action::Hover(_)
+        | Action::Menu(_)
+        | Action::Focus(_)
+        | Action::TabTo(_)
+        | Action::Press(_)
+        | Action::Type(_)
+        | Action::Fill(_)
+        | Action::Select(_)
+        | Action::Slide(_)
+        | Action::Date(_)
+        | Action::CanvasClickColor(_)
+        | Action::CanvasDraw(_)
+        | Action::Drag(_)
+        | Action::MouseMove(_)
+        | Action::Scroll(_)
+        | Action::Tap(_)
+        | Action::Swipe(_)
+        | Action::Pinch(_) => RiskLevel::Medium,
+        // Read-only actions. Listed one by one, with no catch-all, so a new
+        // action has to be classified here before it compiles.
+        Action::ListSessions(_)
+        | Action::SessionStatus(_)
+        | Action::ListTabs(_)
+        | Action::BatchSnapshot(_)
+        | Action::Snapshot(_)
+        | Action::Screenshot(_)
+        | Action::Title(_)
+        | Action::Meta(_)
+        | Action::Response(_)
+        | Action::Assert(_)
+        | Action::Feeds(_)
+        | Action::Images(_)
+        | Action::Table(_)
+        | Action::Url(_)
+        | Action::Viewport(_)
+        | Action::Html(_)
+        | Action::Text(_)
+        | Action::Value(_)
+        | Action::Options(_)
+        | Action::Attr(_)
+        | Action::Attrs(_)
+        | Action::Box(_)
+        | Action::Styles(_)
+        | Action::Describe(_)
+        | Action::State(_)
+        | Action::Query(_)
+        | Action::InspectPoint(_)
+        | Action::Pdf(_)
+        | Action::Save(_)
+        | Action::LogsConsole(_)
+        | Action::LogsErrors(_)
+        | Action::NetworkRequests(_)
+        | Action::NetworkRequestDetail(_)
+        | Action::NetworkHarStart(_)
+        | Action::NetworkHarStop(_)
+        | Action::NetworkRouteList(_)
+        | Action::CookiesList(_)
+        | Action::CookiesGet(_)
+        | Action::DownloadsList(_)
+        | Action::DownloadsWait(_)
+        | Action::StorageList(_)
+        | Action::StorageGet(_)
+        | Action::IndexedDbList(_)
+        | Action::IndexedDbDump(_)
+        | Action::WaitElement(_)
+        | Action::WaitNavigation(_)
+        | Action::WaitNetworkIdle(_)
+        | Action::WaitCondition(_)
+        | Action::WaitText(_)
+        | Action::WaitUrl(_)
+        | Action::ExtensionStatus(_)
+        | Action::ExtensionVersion(_)
+        | Action::Handoff(_)
+        | Action::RecordStart(_)
+        | Action::RecordStop(_)
+        | Action::RecordVideoStart(_)
+        | Action::RecordVideoStop(_)
+        | Action::TraceStart(_)
+        | Action::TraceStop(_)
+        | Action::CursorPosition(_) => RiskLevel::Low,
+    }
+}
+
+pub fn build_record(
+    action: &Action,
+    result: &ActionResult,
+    session: &SessionInfo,
+    elapsed: Duration,
+) -> AuditRecord {
+    let (mut session_id, tab_id) = action_address(action);
+    let mut backend = session.backend.clone();
+    if let (Action::StartSession(_), ActionResult::Ok { data }) = (action, result) {
+        session_id = data
+            .pointer("/session/session_id")
+            .and_then(|v| v.as_str())
+            .map(String::from)
+            .or(session_id);
+        backend = data
+            .pointer("/session/mode")
+            .and_then(|v| v.as_str())
+            .map(String::from)
+            .or(backend);
+    }
+    let (result_label, error_code) = match result {
+        ActionResult::Ok { .. } => ("ok", None),
+        ActionResult::Fatal { code, .. } => ("fatal", Some(code.clone())),
+        ActionResult::Retryable { .. } => ("retryable", None),
+        ActionResult::UserAction { .. } => ("user_action", None),
+    };
+    AuditRecord {
+        ts: crate::utils::time::now_rfc3339(),
+        command: action.command_name().to_string(),
+        session_id,
+        tab_id,
+        profile: session.profile.clone(),
+        backend,
+        target: action_target(action),
+        risk: risk_level(action, result),
+        result: result_label.to_string(),
+        error_code,
+        duration_ms: elapsed.as_millis() as u64,
+        secret: is_secret(action, result),
+    }
+}
+
+/// Append a record, rotating first if the active file is full.
+pub fn append(record: &AuditRecord) -> std::io::Result<()> {
+    append_in(&audit_dir(), record, MAX_FILE_BYTES)
+}
+
+enum WriterMsg {
+    Record(Box<AuditRecord>),
+    /// Answered once every record queued before it is on disk.
+    Flush(mpsc::SyncSender<()>),
+}
+
+/// Queue feeding the writer thread, started on first use.
+static WRITER: OnceLock<Sender<WriterMsg>> = OnceLock::new();
+
+fn writer() -> &'static Sender<WriterMsg> {
+    WRITER.get_or_init(|| {
+        let (tx, rx) = mpsc::channel::<WriterMsg>();
+        let spawned = std::thread::Builder::new()
+            .name("audit-writer".to_string())
+            .spawn(move || {
+                for msg in rx {
+                    match msg {
+                        WriterMsg::Record(record) => {
+                            if let Err(e) = append(&record) {
+                                tracing::warn!("failed to append audit record: {e}");
+                            }
+                        }
+                        WriterMsg::Flush(done) => {
+                            let _ = done.send(());
+                        }
+                    }
+                }
+            });
+        if let Err(e) = spawned {
+            tracing::warn!("failed to start the audit writer, records will be dropped: {e}");
+        }
+        tx
+    })
+}
+
+/// Queue `record` for the writer thread. Returns at once; records are
+/// appended in the order they were queued.
+pub fn submit(record: AuditRecord) {
+    let _ = writer().send(WriterMsg::Record(Box::new(record)));
+}
+
+/// Block until every queued record is written. The daemon calls this once on
+/// shutdown, off the async workers.
+pub fn flush() {
+    let (done_tx, done_rx) = mpsc::sync_channel(1);
+    if writer().send(WriterMsg::Flush(done_tx)).is_ok() {
+        let _ = done_rx.recv();
+    }
+}
+
+fn append_in(dir: &Path, record: &AuditRecord, max_bytes: u64) -> std::io::Result<()> {
+    let _guard = WRITE_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+    fs::create_dir_all(dir)?;
+    let active = active_log_path(dir);
+    if fs::metadata(&active).map(|m| m.len()).unwrap_or(0) >= max_bytes {
+        rotate(dir)?;
+    }
+    let mut line = serde_json::to_string(record).map_err(std::io::Error::other)?;
+    line.push('\n');
+    let mut file = OpenOptions::new().create(true).append(true).open(&active)?;
+    file.write_all(line.as_bytes())
+}
+
+fn rotate(dir: &Path) -> std::io::Result<()> {
+    let oldest = rotated_log_path(dir, MAX_ROTATED_FILES);
+    if oldest.exists() {
+        fs::remove_file(&oldest)?;
+    }
+    for index in (1..MAX_ROTATED_FILES).rev() {
+        let from = rotated_log_path(dir, index);
+        if from.exists() {
+            fs::rename(&from, rotated_log_path(dir, index + 1))?;
+        }
+    }
+    fs::rename(active_log_path(dir), rotated_log_path(dir, 1))
+}
+
+// ── Reading ───────────────────────────────────────────────────────────
+
+/// Audit search filters
+#[derive(Args, Debug, Clone, Default)]
+#[command(after_help = "\
+Examples:
+  actionbook audit search example.com
+  actionbook audit search --command \"browser click\" --risk high
+  actionbook audit search --session s1 --result fatal --limit 100
+
+QUERY matches case-insensitively against the whole record (command, target,
+error code, ...). Filters are combined with AND. Newest matches are returned.")]
+pub struct SearchArgs {
+    /// Free-text query
+    pub query: Option<String>,
+    /// Exact command name (e.g. "browser goto")
+    #[arg(long)]
+    pub command: Option<String>,
+    /// Session ID
+    #[arg(long)]
+    pub session: Option<String>,
+    /// Minimum risk level
+    #[arg(long, value_enum)]
+    pub risk: Option<RiskLevel>,
+    /// Result status (ok, fatal, retryable, user_action)
+    #[arg(long)]
+    pub result: Option<String>,
+    /// Maximum number of records to return
+    #[arg(long, default_value_t = 50)]
+    pub limit: usize,
+}
+
+impl SearchArgs {
+    fn matches(&self, raw: &str, record: &AuditRecord) -> bool {
+        if let Some(q) = &self.query
+            && !raw.to_lowercase().contains(&q.to_lowercase())
+        {
+            return false;
+        }
+        if let Some(c) = &self.command
+            && &record.command != c
+        {
+            return false;
+        }
+        if let Some(s) = &self.session
+            && record.session_id.as_ref() != Some(s)
+        {
+            return false;
+        }
+        if let Some(r) = self.risk
+            && record.risk < r
+        {
+            return false;
+        }
+        if let Some(r) = &self.result
+            && &record.result != r
+        {
+            return false;
+        }
+        true
+    }
+}
+
+/// Newest `limit` records matching `filter`, returned oldest first.
+pub fn read_records(filter: &SearchArgs) -> std::io::Result<Vec<AuditRecord>> {
+    read_records_in(&audit_dir(), filter)
+}
+
+fn read_records_in(dir: &Path, filter: &SearchArgs) -> std::io::Result<Vec<AuditRecord>> {
+    let mut matched = std::collections::VecDeque::new();
+    for path in log_files_oldest_first(dir) {
+        let reader = BufReader::new(fs::File::open(&path)?);
+        for line in reader.lines() {
+            let line = line?;
+            // Skip partial or foreign lines rather than failing the whole read.
+            let Ok(record) = serde_json::from_str::<AuditRecord>(&line) else {
+                continue;
+            };
+            if filter.matches(&line, &record) {
+                matched.push_back(record);
+                if matched.len() > filter.limit {
+                    matched.pop_front();
+                }
+            }
+        }
+    }
+    Ok(matched.into_iter().collect())
+}
+
+fn records_result(records: std::io::Result<Vec<AuditRecord>>) -> ActionResult {
+    match records {
+        Ok(records) => ActionResult::ok(json!({
+            "path": active_log_path(&audit_dir()).display().to_string(),
+            "count": records.len(),
+            "records": records,
+        })),
+        Err(e) => ActionResult::fatal_with_hint(
+            "IO_ERROR",
+            format!("failed to read audit log: {e}"),
+            format!("check permissions on {}", audit_dir().display()),
+        ),
+    }
+}
+
+pub fn execute_tail(lines: usize) -> ActionResult {
+    records_result(read_records(&SearchArgs {
+        limit: lines,
+        ..SearchArgs::default()
+    }))
+}
+
+pub fn execute_search(args: &SearchArgs) -> ActionResult {
+    records_result(read_records(args))
+}
+
+#[cfg(test)]
+mod tests {
+    use clap::Parser;
+
+    use super::*;
+    use crate::browser::{interaction, navigation};
+    use crate::cli::{Cli, Commands};
+
+    fn goto(url: &str) -> Action {
+        Action::Goto(navigation::goto::Cmd {
+            url: url.to_string(),
+            session: "s1".to_string(),
+            tab: "t1".to_string(),
+            wait_until: Default::default(),
+            timeout: None,
+            dismiss_consent: false,
+            max_download_bytes: None,
+        })
+    }
+
+    fn record(command: &str, risk: RiskLevel, result: &str) -> AuditRecord {
+        AuditRecord {
+            ts: "2026-01-01T00:00:00.000Z".to_string(),
+            command: command.to_string(),
+            session_id: Some("s1".to_string()),
+            tab_id: None,
+            profile: None,
+            backend: None,
+            target: None,
+            risk,
+            result: result.to_string(),
+            error_code: None,
+            duration_ms: 1,
+            secret: false,
+        }
+    }
+
+    #[test]
+    fn build_record_captures_address_target_and_risk() {
+        let action = goto("https://example.com");
+        let rec = build_record(
+            &action,
+            &ActionResult::ok(json!({})),
+            &SessionInfo {
+                profile: Some("work".to_string()),
+                backend: Some("local".to_string()),
+            },
+            Duration::from_millis(42),
+        );
+        assert_eq!(rec.command, "browser goto");
+        assert_eq!(rec.session_id.as_deref(), Some("s1"));
+        assert_eq!(rec.tab_id.as_deref(), Some("t1"));
+        assert_eq!(rec.target.as_deref(), Some("https://example.com"));
+        assert_eq!(rec.risk, RiskLevel::Medium);
+        assert_eq!(rec.result, "ok");
+        assert_eq!(rec.duration_ms, 42);
+    }
+
+    #[test]
+    fn target_never_includes_typed_text() {
+        let action = Action::Type(interaction::type_text::Cmd {
+            args: vec!["#password".to_string(), "hunter2".to_string()],
+            session: "s1".to_string(),
+            tab: "t1".to_string(),
+            secret: true,
+        });
+        assert_eq!(action_target(&action).as_deref(), Some("#password"));
+        let focused = Action::Type(interaction::type_text::Cmd {
+            args: vec!["hunter2".to_string()],
+            session: "s1".to_string(),
+            tab: "t1".to_string(),
+            secret: false,
+        });
+        assert_eq!(action_target(&focused), None);
+        assert!(is_secret(&action, &ActionResult::ok(json!({}))));
+        let detected = ActionResult::ok(json!({ "value_summary": { "redacted": true } }));
+        assert!(is_secret(&focused, &detected));
+    }
+
+    #[test]
+    fn guardrail_refusal_is_high_risk() {
+        let result = ActionResult::fatal(guardrails::DOMAIN_NOT_ALLOWED, "blocked");
+        assert_eq!(
+            risk_level(&goto("https://x.test"), &result),
+            RiskLevel::High
+        );
+    }
+
+    #[test]
+    fn every_mutating_action_is_at_least_medium() {
+        // One command per mutating action. Read-only actions are listed by
+        // name in `risk_level`, so a new variant cannot fall through to low.
+        let commands = [
+            "start",
+            "close --session s1",
+            "restart --session s1",
+            "emulate iPhone-14 --session s1",
+            "emulate network --preset slow-3g --session s1",
+            "state bundle --out p.bundle --session s1",
+            "state import p.bundle --session s1",
+            "new-tab https://x.test --session s1",
+            "batch-open --urls https://x.test --session s1",
+            "close-tab --session s1 --tab t1",
+            "switch t2 --session s1",
+            "window --session s1 --tab t1",
+            "goto https://x.test --session s1 --tab t1",
+            "back --session s1 --tab t1",
+            "forward --session s1 --tab t1",
+            "reload --session s1 --tab t1",
+            "fetch https://x.test --session s1 --tab t1",
+            "trace-redirects https://x.test --session s1 --tab t1",
+            "network route add *.ads.test/* --block --session s1 --tab t1",
+            "network route remove r1 --session s1 --tab t1",
+            "cookies set sid v --session s1",
+            "cookies delete sid --session s1",
+            "cookies clear --session s1",
+            "cookies export --out c.json --session s1",
+            "cookies import c.json --session s1",
+            "local-storage set k v --session s1 --tab t1",
+            "local-storage delete k --session s1 --tab t1",
+            "local-storage clear --session s1 --tab t1",
+            "eval 1 --session s1 --tab t1",
+            "click #b --session s1 --tab t1",
+            "batch-click #a #b --session s1 --tab t1",
+            "hover #m --session s1 --tab t1",
+            "menu #m #m-a --session s1 --tab t1",
+            "focus #e --session s1 --tab t1",
+            "tabto #b --session s1 --tab t1",
+            "press Enter --session s1 --tab t1",
+            "type #q hi --session s1 --tab t1",
+            "fill #q hi --session s1 --tab t1",
+            "select #c us --session s1 --tab t1",
+            "canvas click-color #c #ff0000 --session s1 --tab t1",
+            "canvas draw #c --path p.json --session s1 --tab t1",
+            "date #d 2026-01-01 --session s1 --tab t1",
+            "slide #v --to-value 5 --session s1 --tab t1",
+            "drag #a #b --session s1 --tab t1",
+            "upload #f /tmp/x.png --session s1 --tab t1",
+            "mouse-move 1,2 --session s1 --tab t1",
+            "scroll down 500 --session s1 --tab t1",
+            "tap #b --session s1 --tab t1",
+            "swipe up --session s1 --tab t1",
+            "pinch out --session s1 --tab t1",
+        ];
+        for command in commands {
+            let argv = ["actionbook", "browser"]
+                .into_iter()
+                .chain(command.split(' '));
+            let action = match Cli::try_parse_from(argv)
+                .unwrap_or_else(|e| panic!("`{command}` should parse: {e}"))
+                .command
+            {
+                Some(Commands::Browser { command }) => command.to_action().unwrap(),
+                other => panic!("expected browser command, got {other:?}"),
+            };
+            assert_ne!(
+                risk_level(&action, &ActionResult::ok(json!({}))),
+                RiskLevel::Low,
+                "{}",
+                action.command_name()
+            );
+        }
+    }
+
+    #[test]
+    fn search_filters_combine() {
+        let rec = record("browser click", RiskLevel::Medium, "fatal");
+        let raw = serde_json::to_string(&rec).unwrap();
+        let mut f = SearchArgs {
+            command: Some("browser click".to_string()),
+            risk: Some(RiskLevel::Medium),
+            result: Some("fatal".to_string()),
+            limit: 10,
+            ..SearchArgs::default()
+        };
+        assert!(f.matches(&raw, &rec));
+        f.risk = Some(RiskLevel::High);
+        assert!(!f.matches(&raw, &rec));
+        f.risk = None;
+        f.query = Some("CLICK".to_string());
+        assert!(f.matches(&raw, &rec));
+        f.session = Some("s2".to_string());
+        assert!(!f.matches(&raw, &rec));
+    }
+
+    #[test]
+    fn append_rotates_and_read_spans_files_in_order() {
+        let tmp = tempfile::tempdir().unwrap();
+        let dir = tmp.path();
+        for i in 0..4 {
+            let mut rec = record("browser goto", RiskLevel::Medium, "ok");
+            rec.duration_ms = i;
+            // Tiny cap forces a rotation before every append after the first.
+            append_in(dir, &rec, 1).unwrap();
+        }
+        assert!(rotated_log_path(dir, 3).exists());
+        let all = read_records_in(
+            dir,
+            &SearchArgs {
+                limit: 10,
+                ..SearchArgs::default()
+            },
+        )
+        .unwrap();
+        let order: Vec<u64> = all.iter().map(|r| r.duration_ms).collect();
+        assert_eq!(order, vec![0, 1, 2, 3]);
+
+        let tail = read_records_in(
+            dir,
+            &SearchArgs {
+                limit: 2,
+                ..SearchArgs::default()
+            },
+        )
+        .unwrap();
+        assert_eq!(tail.len(), 2);
+        assert_eq!(tail[1].duration_ms, 3);
+    }
+}
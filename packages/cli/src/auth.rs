@@ -0,0 +1,729 @@
+//! Per-domain login credentials.
+//!
+//! `actionbook auth add example.com --user alice --pass-env EX_PASS` keeps the
+//! username in `~/.actionbook/auth/credentials.json` and the password (and an
+//! optional TOTP secret) in the OS keyring: the macOS keychain through
+//! `security`, or the Secret Service through `secret-tool` on Linux. Without
+//! either, or with `ACTIONBOOK_CREDENTIAL_STORE=file`, secrets go to
+//! `~/.actionbook/auth/secrets.json`, readable by the owner only.
+//!
+//! Run scripts refer to a credential by domain with a `login` step, so flow
+//! files never carry the secrets themselves.
+
+use std::collections::BTreeMap;
+use std::io::Write as _;
+use std::path::PathBuf;
+use std::process::{Command, Stdio};
+
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use sha1::{Digest, Sha1};
+
+use crate::action_result::ActionResult;
+use crate::config;
+use crate::daemon::server::write_state_file;
+
+pub const COMMAND_NAME_ADD: &str = "auth add";
+pub const COMMAND_NAME_LIST: &str = "auth list";
+pub const COMMAND_NAME_REMOVE: &str = "auth remove";
+pub const COMMAND_NAME_TOTP: &str = "auth totp";
+
+/// Keyring service the secrets are stored under.
+const SERVICE: &str = "actionbook";
+const TOTP_PERIOD_SECS: u64 = 30;
+
+/// Why reading or writing the credential store failed.
+#[derive(Debug, thiserror::Error)]
+pub enum AuthError {
+    #[error("{}: {source}", path.display())]
+    Io {
+        path: PathBuf,
+        source: std::io::Error,
+    },
+    #[error("{}: {source}", path.display())]
+    Json {
+        path: PathBuf,
+        source: serde_json::Error,
+    },
+    /// The keyring tool could not be started or fed its input.
+    #[error("failed to run {program}: {source}")]
+    Spawn {
+        program: &'static str,
+        source: std::io::Error,
+    },
+    /// The keyring tool ran and reported an error.
+    #[error("{program} failed: {stderr}")]
+    Keyring {
+        program: &'static str,
+        stderr: String,
+    },
+    #[error("no secret for {account} in {store}")]
+    MissingSecret { account: String, store: String },
+    #[error("TOTP secret is not valid base32")]
+    InvalidTotpSecret,
+    /// `security -i` reads one command per line, so a newline in a value
+    /// would start a new command.
+    #[error("{field} must not contain control characters")]
+    ControlCharacter { field: &'static str },
+}
+
+impl AuthError {
+    pub fn error_code(&self) -> &'static str {
+        match self {
+            AuthError::Io { .. } | AuthError::Json { .. } => "IO_ERROR",
+            AuthError::Spawn { .. }
+            | AuthError::Keyring { .. }
+            | AuthError::MissingSecret { .. } => "KEYRING_ERROR",
+            AuthError::InvalidTotpSecret | AuthError::ControlCharacter { .. } => "INVALID_ARGUMENT",
+        }
+    }
+}
+
+/// Where a credential's secrets live.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Store {
+    Keyring,
+    File,
+}
+
+/// What `credentials.json` keeps per domain: nothing secret.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+struct Entry {
+    user: String,
+    #[serde(default)]
+    totp: bool,
+    store: Store,
+    added: String,
+}
+
+/// A stored login, secrets included.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Credential {
+    pub domain: String,
+    pub user: String,
+    pub password: String,
+    pub totp_secret: Option<String>,
+}
+
+impl Credential {
+    /// The current TOTP code, when the credential has a secret.
+    pub fn totp(&self) -> Option<Result<String, AuthError>> {
+        let secret = self.totp_secret.as_deref()?;
+        Some(totp(secret, now_secs()))
+    }
+}
+
+fn now_secs() -> u64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map_or(0, |d| d.as_secs())
+}
+
+fn auth_dir() -> PathBuf {
+    config::actionbook_home().join("auth")
+}
+
+fn index_path() -> PathBuf {
+    auth_dir().join("credentials.json")
+}
+
+fn secrets_path() -> PathBuf {
+    auth_dir().join("secrets.json")
+}
+
+fn read_json<T: Default + serde::de::DeserializeOwned>(
+    path: &std::path::Path,
+) -> Result<T, AuthError> {
+    match std::fs::read_to_string(path) {
+        Ok(text) => serde_json::from_str(&text).map_err(|source| AuthError::Json {
+            path: path.to_path_buf(),
+            source,
+        }),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(T::default()),
+        Err(source) => Err(AuthError::Io {
+            path: path.to_path_buf(),
+            source,
+        }),
+    }
+}
+
+fn write_json<T: Serialize>(path: &std::path::Path, value: &T) -> Result<(), AuthError> {
+    std::fs::create_dir_all(auth_dir()).map_err(|source| AuthError::Io {
+        path: auth_dir(),
+        source,
+    })?;
+    let text = serde_json::to_string_pretty(value).map_err(|source| AuthError::Json {
+        path: path.to_path_buf(),
+        source,
+    })?;
+    write_state_file(path, &text).map_err(|source| AuthError::Io {
+        path: path.to_path_buf(),
+        source,
+    })
+}
+
+/// `https://Example.com/login` -> `example.com`.
+pub fn normalize_domain(input: &str) -> String {
+    let rest = input.trim();
+    let rest = rest.split_once("://").map_or(rest, |(_, r)| r);
+    let host = rest.split(['/', '?', '#']).next().unwrap_or("");
+    let host = host.rsplit_once('@').map_or(host, |(_, h)| h);
+    let host = host.split(':').next().unwrap_or("");
+    host.trim_end_matches('.').to_ascii_lowercase()
+}
+
+/// The OS keyring tools the store can use.
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Keyring {
+    MacKeychain,
+    SecretService,
+}
+
+impl Keyring {
+    /// The keyring to store new secrets in, if there is one.
+    fn detect() -> Option<Self> {
+        if std::env::var("ACTIONBOOK_CREDENTIAL_STORE").as_deref() == Ok("file") {
+            return None;
+        }
+        if cfg!(target_os = "macos") && which::which("security").is_ok() {
+            Some(Self::MacKeychain)
+        } else if cfg!(target_os = "linux") && which::which("secret-tool").is_ok() {
+            Some(Self::SecretService)
+        } else {
+            None
+        }
+    }
+
+    fn default_for_store() -> Option<Self> {
+        if cfg!(target_os = "macos") {
+            Some(Self::MacKeychain)
+        } else if cfg!(target_os = "linux") {
+            Some(Self::SecretService)
+        } else {
+            None
+        }
+    }
+
+    fn run(self, args: &[&str], stdin: Option<&str>) -> Result<String, AuthError> {
+        let program = match self {
+            Self::MacKeychain => "security",
+            Self::SecretService => "secret-tool",
+        };
+        let mut child = Command::new(program)
+            .args(args)
+            .stdin(if stdin.is_some() {
+                Stdio::piped()
+            } else {
+                Stdio::null()
+            })
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .spawn()
+            .map_err(|source| AuthError::Spawn { program, source })?;
+        if let (Some(input), Some(mut pipe)) = (stdin, child.stdin.take()) {
+            pipe.write_all(input.as_bytes())
+                .map_err(|source| AuthError::Spawn { program, source })?;
+        }
+        let output = child
+            .wait_with_output()
+            .map_err(|source| AuthError::Spawn { program, source })?;
+        if !output.status.success() {
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            return Err(AuthError::Keyring {
+                program,
+                stderr: stderr.trim().to_string(),
+            });
+        }
+        Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+    }
+
+    fn set(self, account: &str, secret: &str) -> Result<(), AuthError> {
+        match self {
+            // `-i` reads the command from stdin, keeping the secret out of
+            // the process list.
+            Self::MacKeychain => self
+                .run(&["-i"], Some(&add_password_command(account, secret)?))
+                .map(drop),
+            Self::SecretService => self
+                .run(
+                    &[
+                        "store",
+                        "--label",
+                        &format!("{SERVICE} {account}"),
+                        "service",
+                        SERVICE,
+                        "account",
+                        account,
+                    ],
+                    Some(secret),
+                )
+                .map(drop),
+        }
+    }
+
+    fn get(self, account: &str) -> Result<String, AuthError> {
+        let out = match self {
+            Self::MacKeychain => self.run(
+                &["find-generic-password", "-s", SERVICE, "-a", account, "-w"],
+                None,
+            )?,
+            Self::SecretService => {
+                self.run(&["lookup", "service", SERVICE, "account", account], None)?
+            }
+        };
+        let secret = out.strip_suffix('\n').unwrap_or(&out);
+        if secret.is_empty() {
+            return Err(AuthError::MissingSecret {
+                account: account.to_string(),
+                store: "the keyring".to_string(),
+            });
+        }
+        Ok(secret.to_string())
+    }
+
+    fn delete(self, account: &str) -> Result<(), AuthError> {
+        match self {
+            Self::MacKeychain => self.run(
+                &["delete-generic-password", "-s", SERVICE, "-a", account],
+                None,
+            ),
+            Self::SecretService => {
+                self.run(&["clear", "service", SERVICE, "account", account], None)
+            }
+        }
+        .map(drop)
+    }
+}
+
+/// The `security -i` line storing `secret`. Values with control characters
+/// are refused rather than quoted: a newline would end the command early.
+fn add_password_command(account: &str, secret: &str) -> Result<String, AuthError> {
+    reject_control_chars("account", account)?;
+    reject_control_chars("secret", secret)?;
+    Ok(format!(
+        "add-generic-password -U -s {} -a {} -w {}\n",
+        quote(SERVICE),
+        quote(account),
+        quote(secret)
+    ))
+}
+
+fn reject_control_chars(field: &'static str, value: &str) -> Result<(), AuthError> {
+    if value.chars().any(|c| c.is_ascii_control()) {
+        return Err(AuthError::ControlCharacter { field });
+    }
+    Ok(())
+}
+
+/// A double-quoted word for `security -i`.
+fn quote(s: &str) -> String {
+    format!("\"{}\"", s.replace('\\', "\\\\").replace('"', "\\\""))
+}
+
+fn totp_account(domain: &str) -> String {
+    format!("{domain}:totp")
+}
+
+fn put_secret(store: Store, account: &str, secret: &str) -> Result<(), AuthError> {
+    match (store, Keyring::default_for_store()) {
+        (Store::Keyring, Some(keyring)) => keyring.set(account, secret),
+        _ => {
+            let mut secrets: BTreeMap<String, String> = read_json(&secrets_path())?;
+            secrets.insert(account.to_string(), secret.to_string());
+            write_json(&secrets_path(), &secrets)
+        }
+    }
+}
+
+fn get_secret(store: Store, account: &str) -> Result<String, AuthError> {
+    match (store, Keyring::default_for_store()) {
+        (Store::Keyring, Some(keyring)) => keyring.get(account),
+        _ => read_json::<BTreeMap<String, String>>(&secrets_path())?
+            .remove(account)
+            .ok_or_else(|| AuthError::MissingSecret {
+                account: account.to_string(),
+                store: secrets_path().display().to_string(),
+            }),
+    }
+}
+
+fn delete_secret(store: Store, account: &str) -> Result<(), AuthError> {
+    match (store, Keyring::default_for_store()) {
+        (Store::Keyring, Some(keyring)) => keyring.delete(account),
+        _ => {
+            let mut secrets: BTreeMap<String, String> = read_json(&secrets_path())?;
+            if secrets.remove(account).is_some() {
+                write_json(&secrets_path(), &secrets)?;
+            }
+            Ok(())
+        }
+    }
+}
+
+/// The credential for `domain`, or for the closest parent domain that has
+/// one (`login.example.com` falls back to `example.com`).
+pub fn lookup(domain: &str) -> Result<Option<Credential>, AuthError> {
+    let index: BTreeMap<String, Entry> = read_json(&index_path())?;
+    let mut candidate = normalize_domain(domain);
+    let (domain, entry) = loop {
+        if let Some(entry) = index.get(&candidate) {
+            break (candidate, entry);
+        }
+        match candidate.split_once('.') {
+            Some((_, parent)) if parent.contains('.') => candidate = parent.to_string(),
+            _ => return Ok(None),
+        }
+    };
+    let password = get_secret(entry.store, &domain)?;
+    let totp_secret = if entry.totp {
+        Some(get_secret(entry.store, &totp_account(&domain))?)
+    } else {
+        None
+    };
+    Ok(Some(Credential {
+        domain,
+        user: entry.user.clone(),
+        password,
+        totp_secret,
+    }))
+}
+
+/// Decode an RFC 4648 base32 TOTP secret, ignoring case, spaces and padding.
+fn base32_decode(s: &str) -> Option<Vec<u8>> {
+    let mut out = Vec::new();
+    let (mut buffer, mut bits) = (0u64, 0u32);
+    for c in s
+        .chars()
+        .filter(|c| !c.is_whitespace() && *c != '=' && *c != '-')
+    {
+        let value = match c.to_ascii_uppercase() {
+            c @ 'A'..='Z' => c as u64 - 'A' as u64,
+            c @ '2'..='7' => c as u64 - '2' as u64 + 26,
+            _ => return None,
+        };
+        buffer = (buffer << 5) | value;
+        bits += 5;
+        if bits >= 8 {
+            bits -= 8;
+            out.push((buffer >> bits) as u8);
+            buffer &= (1 << bits) - 1;
+        }
+    }
+    (!out.is_empty()).then_some(out)
+}
+
+fn hmac_sha1(key: &[u8], message: &[u8]) -> [u8; 20] {
+    const BLOCK: usize = 64;
+    let mut block = [0u8; BLOCK];
+    if key.len() > BLOCK {
+        block[..20].copy_from_slice(&Sha1::digest(key));
+    } else {
+        block[..key.len()].copy_from_slice(key);
+    }
+    let pad = |byte: u8| block.iter().map(|b| b ^ byte).collect::<Vec<u8>>();
+    let inner = Sha1::new()
+        .chain_update(pad(0x36))
+        .chain_update(message)
+        .finalize();
+    Sha1::new()
+        .chain_update(pad(0x5c))
+        .chain_update(inner)
+        .finalize()
+        .into()
+}
+
+/// The RFC 6238 code (SHA-1, 30 s, 6 digits) for `secret` at `unix_secs`.
+fn totp(secret: &str, unix_secs: u64) -> Result<String, AuthError> {
+    let key = base32_decode(secret).ok_or(AuthError::InvalidTotpSecret)?;
+    let mac = hmac_sha1(&key, &(unix_secs / TOTP_PERIOD_SECS).to_be_bytes());
+    let offset = (mac[19] & 0x0f) as usize;
+    let code = u32::from_be_bytes([
+        mac[offset],
+        mac[offset + 1],
+        mac[offset + 2],
+        mac[offset + 3],
+    ]) & 0x7fff_ffff;
+    Ok(format!("{:06}", code % 1_000_000))
+}
+
+fn env_secret(flag: &str, var: &str) -> Result<String, ActionResult> {
+    match std::env::var(var) {
+        Ok(v) if !v.is_empty() => Ok(v),
+        _ => Err(ActionResult::fatal_with_hint(
+            "INVALID_ARGUMENT",
+            format!("{flag} {var}: the variable is not set"),
+            format!("export {var} before running `actionbook auth add`"),
+        )),
+    }
+}
+
+/// Options of `actionbook auth add`.
+pub struct AddOptions<'a> {
+    pub domain: &'a str,
+    pub user: &'a str,
+    pub pass_env: Option<&'a str>,
+    pub pass_stdin: bool,
+    pub totp_secret: Option<&'a str>,
+    pub totp_env: Option<&'a str>,
+}
+
+pub fn execute_add(opts: &AddOptions) -> ActionResult {
+    let domain = normalize_domain(opts.domain);
+    if domain.is_empty() {
+        return ActionResult::fatal("INVALID_ARGUMENT", "a domain is required");
+    }
+    let password = match (opts.pass_env, opts.pass_stdin) {
+        (Some(var), _) => match env_secret("--pass-env", var) {
+            Ok(p) => p,
+            Err(e) => return e,
+        },
+        (None, true) => {
+            let mut line = String::new();
+            if let Err(e) = std::io::stdin().read_line(&mut line) {
+                return ActionResult::fatal("IO_ERROR", format!("failed to read stdin: {e}"));
+            }
+            line.trim_end_matches(['\r', '\n']).to_string()
+        }
+        (None, false) => String::new(),
+    };
+    if password.is_empty() {
+        return ActionResult::fatal_with_hint(
+            "INVALID_ARGUMENT",
+            "no password given",
+            "pass --pass-env <VAR> or pipe it in with --pass-stdin",
+        );
+    }
+    let totp_secret = match (opts.totp_secret, opts.totp_env) {
+        (Some(s), _) => Some(s.to_string()),
+        (None, Some(var)) => match env_secret("--totp-env", var) {
+            Ok(s) => Some(s),
+            Err(e) => return e,
+        },
+        (None, None) => None,
+    };
+    if let Some(secret) = &totp_secret
+        && base32_decode(secret).is_none()
+    {
+        let e = AuthError::InvalidTotpSecret;
+        return ActionResult::fatal(e.error_code(), e.to_string());
+    }
+    // Checked before anything is replaced, so a bad secret leaves the old
+    // credential intact.
+    if let Err(e) = reject_control_chars("domain", &domain)
+        .and_then(|()| reject_control_chars("password", &password))
+    {
+        return ActionResult::fatal(e.error_code(), e.to_string());
+    }
+
+    let mut index: BTreeMap<String, Entry> = match read_json(&index_path()) {
+        Ok(i) => i,
+        Err(e) => return ActionResult::fatal(e.error_code(), e.to_string()),
+    };
+    let store = if Keyring::detect().is_some() {
+        Store::Keyring
+    } else {
+        Store::File
+    };
+    // Replacing a credential drops the secrets of the old one first, in
+    // case it lived in the other store.
+    if let Some(old) = index.get(&domain) {
+        let _ = delete_secret(old.store, &domain);
+        let _ = delete_secret(old.store, &totp_account(&domain));
+    }
+    let mut saved = put_secret(store, &domain, &password);
+    if let (Ok(()), Some(secret)) = (&saved, &totp_secret) {
+        saved = put_secret(store, &totp_account(&domain), secret);
+    }
+    if let Err(e) = saved {
+        return ActionResult::fatal("KEYRING_ERROR", format!("failed to store the secret: {e}"));
+    }
+    index.insert(
+        domain.clone(),
+        Entry {
+            user: opts.user.to_string(),
+            totp: totp_secret.is_some(),
+            store,
+            added: crate::utils::time::now_rfc3339(),
+        },
+    );
+    if let Err(e) = write_json(&index_path(), &index) {
+        return ActionResult::fatal(e.error_code(), e.to_string());
+    }
+
+    let mut data = json!({
+        "domain": domain,
+        "user": opts.user,
+        "totp": totp_secret.is_some(),
+        "store": store,
+    });
+    if store == Store::File {
+        data["__warnings"] = json!([format!(
+            "no OS keyring found; secrets are in {} (owner-only)",
+            secrets_path().display()
+        )]);
+    }
+    ActionResult::ok(data)
+}
+
+pub fn execute_list() -> ActionResult {
+    let index: BTreeMap<String, Entry> = match read_json(&index_path()) {
+        Ok(i) => i,
+        Err(e) => return ActionResult::fatal(e.error_code(), e.to_string()),
+    };
+    let credentials: Vec<_> = index
+        .iter()
+        .map(|(domain, e)| {
+            json!({
+                "domain": domain,
+                "user": e.user,
+                "totp": e.totp,
+                "store": e.store,
+                "added": e.added,
+            })
+        })
+        .collect();
+    ActionResult::ok(json!({ "credentials": credentials }))
+}
+
+pub fn execute_remove(domain: &str) -> ActionResult {
+    let domain = normalize_domain(domain);
+    let mut index: BTreeMap<String, Entry> = match read_json(&index_path()) {
+        Ok(i) => i,
+        Err(e) => return ActionResult::fatal(e.error_code(), e.to_string()),
+    };
+    let Some(entry) = index.remove(&domain) else {
+        return not_found(&domain);
+    };
+    let mut warnings = Vec::new();
+    if let Err(e) = delete_secret(entry.store, &domain) {
+        warnings.push(e.to_string());
+    }
+    if entry.totp
+        && let Err(e) = delete_secret(entry.store, &totp_account(&domain))
+    {
+        warnings.push(e.to_string());
+    }
+    if let Err(e) = write_json(&index_path(), &index) {
+        return ActionResult::fatal(e.error_code(), e.to_string());
+    }
+    let mut data = json!({ "domain": domain, "removed": true });
+    if !warnings.is_empty() {
+        data["__warnings"] = json!(warnings);
+    }
+    ActionResult::ok(data)
+}
+
+pub fn execute_totp(domain: &str) -> ActionResult {
+    let credential = match lookup(domain) {
+        Ok(Some(c)) => c,
+        Ok(None) => return not_found(&normalize_domain(domain)),
+        Err(e) => return ActionResult::fatal(e.error_code(), e.to_string()),
+    };
+    match credential.totp() {
+        None => ActionResult::fatal_with_hint(
+            "INVALID_ARGUMENT",
+            format!("no TOTP secret stored for {}", credential.domain),
+            "add one with `actionbook auth add <domain> --totp-secret <base32>`",
+        ),
+        Some(Err(e)) => ActionResult::fatal(e.error_code(), e.to_string()),
+        Some(Ok(code)) => {
+            let now = now_secs();
+            ActionResult::ok(json!({
+                "domain": credential.domain,
+                "code": code,
+                "expires_in": TOTP_PERIOD_SECS - now % TOTP_PERIOD_SECS,
+            }))
+        }
+    }
+}
+
+/// Whether a page on `host` may receive the login stored for `domain`: the
+/// same host or one of its subdomains, never a parent or a lookalike.
+pub fn host_matches(host: &str, domain: &str) -> bool {
+    let host = normalize_domain(host);
+    let domain = normalize_domain(domain);
+    !host.is_empty()
+        && (host == domain
+            || host
+                .strip_suffix(domain.as_str())
+                .is_some_and(|rest| rest.ends_with('.')))
+}
+
+pub fn domain_mismatch(host: &str, domain: &str) -> ActionResult {
+    ActionResult::fatal_with_hint(
+        "CREDENTIAL_DOMAIN_MISMATCH",
+        format!("the page is on {host}, not {domain}; the login for {domain} was not filled"),
+        format!("navigate to {domain} before the login step"),
+    )
+}
+
+pub fn not_found(domain: &str) -> ActionResult {
+    ActionResult::fatal_with_hint(
+        "CREDENTIALS_NOT_FOUND",
+        format!("no credentials stored for {domain}"),
+        format!("add them with `actionbook auth add {domain} --user <name> --pass-env <VAR>`"),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn totp_matches_the_rfc_6238_vectors() {
+        // RFC 6238 appendix B, SHA-1 key "12345678901234567890", last 6 digits.
+        let secret = "GEZDGNBVGY3TQOJQGEZDGNBVGY3TQOJQ";
+        assert_eq!(totp(secret, 59).unwrap(), "287082");
+        assert_eq!(totp(secret, 1_111_111_109).unwrap(), "081804");
+        assert_eq!(totp(secret, 20_000_000_000).unwrap(), "353130");
+        assert_eq!(
+            totp("gezd gnbv gy3t qojq gezd gnbv gy3t qojq", 59).unwrap(),
+            "287082"
+        );
+        assert!(matches!(
+            totp("not base32!", 59),
+            Err(AuthError::InvalidTotpSecret)
+        ));
+    }
+
+    #[test]
+    fn keychain_command_refuses_control_characters() {
+        assert_eq!(
+            add_password_command("example.com", "pa\"ss").unwrap(),
+            "add-generic-password -U -s \"actionbook\" -a \"example.com\" -w \"pa\\\"ss\"\n"
+        );
+        let err =
+            add_password_command("example.com", "x\ndelete-keychain login.keychain").unwrap_err();
+        assert!(matches!(
+            err,
+            AuthError::ControlCharacter { field: "secret" }
+        ));
+        assert_eq!(err.error_code(), "INVALID_ARGUMENT");
+        assert!(add_password_command("a\rb", "secret").is_err());
+    }
+
+    #[test]
+    fn logins_only_go_to_their_domain_and_its_subdomains() {
+        assert!(host_matches("example.com", "example.com"));
+        assert!(host_matches("https://login.example.com/x", "example.com"));
+        assert!(!host_matches("example.com", "login.example.com"));
+        assert!(!host_matches("evilexample.com", "example.com"));
+        assert!(!host_matches("example.com.evil.test", "example.com"));
+        assert!(!host_matches("about:blank", "example.com"));
+        assert!(!host_matches("", "example.com"));
+    }
+
+    #[test]
+    fn domains_normalize_from_urls() {
+        assert_eq!(
+            normalize_domain("https://User@Example.com:8443/login?x=1"),
+            "example.com"
+        );
+        assert_eq!(normalize_domain("example.com."), "example.com");
+        assert_eq!(
+            normalize_domain(" app.example.com/path "),
+            "app.example.com"
+        );
+    }
+}
@@ -0,0 +1,187 @@
+use clap::Args;
+use serde::{Deserialize, Serialize};
+use serde_json::{Value, json};
+
+use crate::action_result::ActionResult;
+use crate::daemon::registry::SharedRegistry;
+use crate::output::ResponseContext;
+
+use super::normalize_domain;
+
+/// Export the session's cookies to a file
+#[derive(Args, Debug, Clone, Serialize, Deserialize)]
+#[command(after_help = "\
+Examples:
+  actionbook browser cookies export --out cookies.json --session s1
+  actionbook browser cookies export --out cookies.txt --session s1 --domain example.com
+
+--format json writes the CDP cookie objects (what Network.getAllCookies
+returns); netscape writes a cookies.txt that curl, wget and yt-dlp read.
+Without --format, a .txt file gets netscape and anything else json. The file
+holds live credentials, so it is created readable by the owner only.")]
+pub struct Cmd {
+    /// File to write
+    #[arg(long, value_name = "FILE")]
+    pub out: String,
+    /// File format (default: netscape for .txt, else json)
+    #[arg(long, value_parser = ["json", "netscape"])]
+    #[serde(default)]
+    pub format: Option<String>,
+    /// Only export cookies of this domain
+    #[arg(long)]
+    #[serde(default)]
+    pub domain: Option<String>,
+    /// Session ID
+    #[arg(long)]
+    #[serde(rename = "session_id")]
+    pub session: String,
+}
+
+pub const COMMAND_NAME: &str = "browser cookies export";
+
+pub fn context(cmd: &Cmd, result: &ActionResult) -> Option<ResponseContext> {
+    if let ActionResult::Fatal { code, .. } = result
+        && code == "SESSION_NOT_FOUND"
+    {
+        return None;
+    }
+    Some(ResponseContext {
+        session_id: cmd.session.clone(),
+        tab_id: None,
+        window_id: None,
+        url: None,
+        title: None,
+    })
+}
+
+fn netscape_bool(b: bool) -> &'static str {
+    if b { "TRUE" } else { "FALSE" }
+}
+
+/// cookies.txt lines for CDP cookie objects. HttpOnly cookies carry the
+/// `#HttpOnly_` domain prefix curl understands; session cookies expire at 0.
+pub(crate) fn to_netscape(cookies: &[Value]) -> String {
+    let mut out = String::from("# Netscape HTTP Cookie File\n");
+    for c in cookies {
+        let s = |k: &str| c.get(k).and_then(|v| v.as_str()).unwrap_or("");
+        let b = |k: &str| c.get(k).and_then(|v| v.as_bool()).unwrap_or(false);
+        let domain = s("domain");
+        let expires = c
+            .get("expires")
+            .and_then(|v| v.as_f64())
+            .filter(|e| *e > 0.0)
+            .map_or(0, |e| e as i64);
+        let path = if s("path").is_empty() { "/" } else { s("path") };
+        out.push_str(&format!(
+            "{}{domain}\t{}\t{path}\t{}\t{expires}\t{}\t{}\n",
+            if b("httpOnly") { "#HttpOnly_" } else { "" },
+            netscape_bool(domain.starts_with('.')),
+            netscape_bool(b("secure")),
+            s("name"),
+            s("value"),
+        ));
+    }
+    out
+}
+
+pub async fn execute(cmd: &Cmd, registry: &SharedRegistry) -> ActionResult {
+    let (cdp, target_id) = {
+        let reg = registry.lock().await;
+        let entry = match reg.get(&cmd.session) {
+            Some(e) => e,
+            None => {
+                return ActionResult::fatal_with_hint(
+                    "SESSION_NOT_FOUND",
+                    format!("session '{}' not found", cmd.session),
+                    "run `actionbook browser list-sessions` to see available sessions",
+                );
+            }
+        };
+        let cdp = match entry.cdp.clone() {
+            Some(c) => c,
+            None => {
+                return ActionResult::fatal(
+                    "INTERNAL_ERROR",
+                    format!("no CDP connection for session '{}'", cmd.session),
+                );
+            }
+        };
+        let target_id = match entry.tabs.first() {
+            Some(t) => t.native_id.clone(),
+            None => {
+                return ActionResult::fatal(
+                    "NO_TAB",
+                    format!("no active tab in session '{}'", cmd.session),
+                );
+            }
+        };
+        (cdp, target_id)
+    };
+
+    let resp = match cdp
+        .execute_on_tab(&target_id, "Network.getAllCookies", json!({}))
+        .await
+    {
+        Ok(v) => v,
+        Err(e) => return ActionResult::fatal("CDP_ERROR", e.to_string()),
+    };
+    let cookies: Vec<Value> = resp
+        .pointer("/result/cookies")
+        .and_then(|v| v.as_array())
+        .into_iter()
+        .flatten()
+        .filter(|c| match &cmd.domain {
+            Some(d) => {
+                let domain = c.get("domain").and_then(|v| v.as_str()).unwrap_or("");
+                normalize_domain(domain) == normalize_domain(d)
+            }
+            None => true,
+        })
+        .cloned()
+        .collect();
+
+    let format = cmd.format.clone().unwrap_or_else(|| {
+        if cmd.out.to_ascii_lowercase().ends_with(".txt") {
+            "netscape".to_string()
+        } else {
+            "json".to_string()
+        }
+    });
+    let contents = if format == "netscape" {
+        to_netscape(&cookies)
+    } else {
+        serde_json::to_string_pretty(&cookies).unwrap_or_default()
+    };
+    if let Err(e) =
+        crate::daemon::server::write_state_file(std::path::Path::new(&cmd.out), &contents)
+    {
+        return ActionResult::fatal("IO_ERROR", format!("failed to write {}: {e}", cmd.out));
+    }
+
+    ActionResult::ok(json!({
+        "path": cmd.out,
+        "format": format,
+        "count": cookies.len(),
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn to_netscape_marks_http_only_and_host_only_cookies() {
+        let text = to_netscape(&[
+            json!({ "name": "sid", "value": "abc", "domain": ".example.com", "path": "/",
+                    "expires": 1893456000.5, "httpOnly": true, "secure": true }),
+            json!({ "name": "pref", "value": "dark", "domain": "app.example.com", "path": "/ui",
+                    "expires": -1, "httpOnly": false, "secure": false }),
+        ]);
+        assert_eq!(
+            text,
+            "# Netscape HTTP Cookie File\n\
+             #HttpOnly_.example.com\tTRUE\t/\tTRUE\t1893456000\tsid\tabc\n\
+             app.example.com\tFALSE\t/ui\tFALSE\t0\tpref\tdark\n"
+        );
+    }
+}
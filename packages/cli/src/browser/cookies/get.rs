@@ -3,6 +3,7 @@ use serde::{Deserialize, Serialize};
 use serde_json::{Value, json};
 
 use crate::action_result::ActionResult;
+use crate::browser::redact;
 use crate::daemon::registry::SharedRegistry;
 use crate::output::ResponseContext;
 
@@ -12,7 +13,10 @@ use super::map_cookie;
 #[derive(Args, Debug, Clone, Serialize, Deserialize)]
 #[command(after_help = "\
 Examples:
-  actionbook browser cookies get session_id --session s1")]
+  actionbook browser cookies get session_id --session s1
+  actionbook browser cookies get session_id --session s1 --show-secrets
+
+Cookie values are masked unless --show-secrets is passed.")]
 pub struct Cmd {
     /// Cookie name
     #[arg()]
@@ -21,6 +25,10 @@ pub struct Cmd {
     #[arg(long)]
     #[serde(rename = "session_id")]
     pub session: String,
+    /// Show cookie values instead of masking them
+    #[arg(long)]
+    #[serde(default)]
+    pub show_secrets: bool,
 }
 
 pub const COMMAND_NAME: &str = "browser cookies get";
@@ -88,11 +96,14 @@ pub async fn execute(cmd: &Cmd, registry: &SharedRegistry) -> ActionResult {
         .and_then(|v| v.as_array())
         .unwrap_or(&empty);
 
-    let item: Value = raw
+    let mut item: Value = raw
         .iter()
         .find(|c| c.get("name").and_then(|v| v.as_str()) == Some(&cmd.name))
         .map(map_cookie)
         .unwrap_or(Value::Null);
+    if !cmd.show_secrets {
+        redact::mask_cookie(&mut item);
+    }
 
     ActionResult::ok(json!({ "item": item }))
 }
@@ -0,0 +1,331 @@
+use clap::Args;
+use serde::{Deserialize, Serialize};
+use serde_json::{Value, json};
+
+use crate::action_result::ActionResult;
+use crate::daemon::registry::SharedRegistry;
+use crate::output::ResponseContext;
+
+/// Import cookies from a file into the session
+#[derive(Args, Debug, Clone, Serialize, Deserialize)]
+#[command(after_help = "\
+Examples:
+  actionbook browser cookies import cookies.json --session s1
+  actionbook browser cookies import cookies.txt --session s1
+
+Accepts a JSON array of cookies (the CDP shape written by `cookies export`,
+or the shape printed by `cookies list --json`) and Netscape cookies.txt files
+from curl, wget or browser extensions. The format is detected from the file
+contents. Cookies that have already expired are skipped.")]
+pub struct Cmd {
+    /// File to read
+    #[arg(value_name = "FILE")]
+    pub file: String,
+    /// Session ID
+    #[arg(long)]
+    #[serde(rename = "session_id")]
+    pub session: String,
+}
+
+pub const COMMAND_NAME: &str = "browser cookies import";
+
+pub fn context(cmd: &Cmd, result: &ActionResult) -> Option<ResponseContext> {
+    if let ActionResult::Fatal { code, .. } = result
+        && code == "SESSION_NOT_FOUND"
+    {
+        return None;
+    }
+    Some(ResponseContext {
+        session_id: cmd.session.clone(),
+        tab_id: None,
+        window_id: None,
+        url: None,
+        title: None,
+    })
+}
+
+/// A cookie read from a file, before it becomes a CDP `CookieParam`.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct ImportedCookie {
+    pub name: String,
+    pub value: String,
+    pub domain: String,
+    pub path: String,
+    pub secure: bool,
+    pub http_only: bool,
+    pub same_site: Option<String>,
+    /// Unix seconds; `None` for a session cookie.
+    pub expires: Option<f64>,
+}
+
+impl ImportedCookie {
+    /// `Network.setCookies` parameter. Domain cookies (leading dot) keep the
+    /// domain; host-only cookies are set through a URL so Chrome does not
+    /// widen them to subdomains.
+    pub(crate) fn to_cookie_param(&self) -> Value {
+        let mut param = json!({
+            "name": self.name,
+            "value": self.value,
+            "path": self.path,
+            "secure": self.secure,
+            "httpOnly": self.http_only,
+        });
+        if self.domain.starts_with('.') {
+            param["domain"] = json!(self.domain);
+        } else {
+            let scheme = if self.secure { "https" } else { "http" };
+            param["url"] = json!(format!("{scheme}://{}{}", self.domain, self.path));
+        }
+        if let Some(ref ss) = self.same_site {
+            param["sameSite"] = json!(ss);
+        }
+        if let Some(exp) = self.expires {
+            param["expires"] = json!(exp);
+        }
+        param
+    }
+}
+
+/// CDP only accepts `Strict`, `Lax` and `None`; other tools write these in
+/// any case, or as `no_restriction` / `unspecified` (Chrome extensions).
+fn normalize_same_site(s: &str) -> Option<String> {
+    match s.to_ascii_lowercase().as_str() {
+        "strict" => Some("Strict".to_string()),
+        "lax" => Some("Lax".to_string()),
+        "none" | "no_restriction" => Some("None".to_string()),
+        _ => None,
+    }
+}
+
+pub(crate) fn from_json_cookie(c: &Value) -> Option<ImportedCookie> {
+    let s = |keys: &[&str]| {
+        keys.iter()
+            .find_map(|k| c.get(*k).and_then(|v| v.as_str()))
+            .map(str::to_string)
+    };
+    let b = |keys: &[&str]| {
+        keys.iter()
+            .find_map(|k| c.get(*k).and_then(|v| v.as_bool()))
+            .unwrap_or(false)
+    };
+    let name = s(&["name"]).filter(|n| !n.is_empty())?;
+    let domain = s(&["domain"]).filter(|d| !d.is_empty())?;
+    let expires = ["expires", "expirationDate"]
+        .iter()
+        .find_map(|k| c.get(*k).and_then(|v| v.as_f64()))
+        .filter(|e| *e > 0.0)
+        .filter(|_| !b(&["session"]));
+    Some(ImportedCookie {
+        name,
+        value: s(&["value"]).unwrap_or_default(),
+        domain,
+        path: s(&["path"]).unwrap_or_else(|| "/".to_string()),
+        secure: b(&["secure"]),
+        http_only: b(&["httpOnly", "http_only"]),
+        same_site: s(&["sameSite", "same_site"]).and_then(|ss| normalize_same_site(&ss)),
+        expires,
+    })
+}
+
+/// Parse a JSON cookie file: an array of cookies, `{"cookies": [...]}`, or
+/// the `cookies list --json` envelope (`data.items`).
+pub(crate) fn parse_json(text: &str) -> Result<Vec<ImportedCookie>, String> {
+    let value: Value = serde_json::from_str(text).map_err(|e| format!("invalid JSON: {e}"))?;
+    let list = match &value {
+        Value::Array(a) => a,
+        Value::Object(_) => ["/cookies", "/items", "/data/items"]
+            .iter()
+            .find_map(|p| value.pointer(p).and_then(|v| v.as_array()))
+            .ok_or("expected an array of cookies or an object with a \"cookies\" array")?,
+        _ => return Err("expected an array of cookies".to_string()),
+    };
+    list.iter()
+        .enumerate()
+        .map(|(i, c)| {
+            from_json_cookie(c).ok_or_else(|| format!("cookie #{}: missing name or domain", i + 1))
+        })
+        .collect()
+}
+
+/// Parse a Netscape cookies.txt file (domain, subdomains flag, path, secure,
+/// expiry, name, value — tab separated). `#HttpOnly_` lines are cookies;
+/// other `#` lines are comments.
+pub(crate) fn parse_netscape(text: &str) -> Result<Vec<ImportedCookie>, String> {
+    let mut cookies = Vec::new();
+    for (i, raw) in text.lines().enumerate() {
+        let line = raw.trim_end_matches('\r');
+        let (line, http_only) = match line.strip_prefix("#HttpOnly_") {
+            Some(rest) => (rest, true),
+            None => (line, false),
+        };
+        if line.trim().is_empty() || line.starts_with('#') {
+            continue;
+        }
+        let fields: Vec<&str> = line.split('\t').collect();
+        if fields.len() < 6 {
+            return Err(format!(
+                "line {}: expected 7 tab-separated fields, got {}",
+                i + 1,
+                fields.len()
+            ));
+        }
+        let expiry: f64 = fields[4]
+            .trim()
+            .parse()
+            .map_err(|_| format!("line {}: invalid expiry '{}'", i + 1, fields[4]))?;
+        let mut domain = fields[0].to_string();
+        if fields[1].eq_ignore_ascii_case("TRUE") && !domain.starts_with('.') {
+            domain.insert(0, '.');
+        }
+        cookies.push(ImportedCookie {
+            name: fields[5].to_string(),
+            value: fields.get(6).copied().unwrap_or("").to_string(),
+            domain,
+            path: fields[2].to_string(),
+            secure: fields[3].eq_ignore_ascii_case("TRUE"),
+            http_only,
+            same_site: None,
+            expires: (expiry > 0.0).then_some(expiry),
+        });
+    }
+    Ok(cookies)
+}
+
+pub async fn execute(cmd: &Cmd, registry: &SharedRegistry) -> ActionResult {
+    let text = match std::fs::read_to_string(&cmd.file) {
+        Ok(t) => t,
+        Err(e) => {
+            return ActionResult::fatal("IO_ERROR", format!("failed to read {}: {e}", cmd.file));
+        }
+    };
+    let is_json = matches!(text.trim_start().chars().next(), Some('[' | '{'));
+    let format = if is_json { "json" } else { "netscape" };
+    let parsed = if is_json {
+        parse_json(&text)
+    } else {
+        parse_netscape(&text)
+    };
+    let cookies = match parsed {
+        Ok(c) => c,
+        Err(e) => {
+            return ActionResult::fatal(
+                "INVALID_ARGUMENT",
+                format!("{}: not a valid {format} cookie file: {e}", cmd.file),
+            );
+        }
+    };
+
+    let (cdp, target_id) = {
+        let reg = registry.lock().await;
+        let entry = match reg.get(&cmd.session) {
+            Some(e) => e,
+            None => {
+                return ActionResult::fatal_with_hint(
+                    "SESSION_NOT_FOUND",
+                    format!("session '{}' not found", cmd.session),
+                    "run `actionbook browser list-sessions` to see available sessions",
+                );
+            }
+        };
+        let cdp = match entry.cdp.clone() {
+            Some(c) => c,
+            None => {
+                return ActionResult::fatal(
+                    "INTERNAL_ERROR",
+                    format!("no CDP connection for session '{}'", cmd.session),
+                );
+            }
+        };
+        let target_id = match entry.tabs.first() {
+            Some(t) => t.native_id.clone(),
+            None => {
+                return ActionResult::fatal(
+                    "NO_TAB",
+                    format!("no active tab in session '{}'", cmd.session),
+                );
+            }
+        };
+        (cdp, target_id)
+    };
+
+    let now = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.as_secs_f64())
+        .unwrap_or(0.0);
+    let (live, expired): (Vec<_>, Vec<_>) = cookies
+        .into_iter()
+        .partition(|c| c.expires.is_none_or(|e| e > now));
+    let params: Vec<Value> = live.iter().map(ImportedCookie::to_cookie_param).collect();
+
+    if !params.is_empty()
+        && let Err(e) = cdp
+            .execute_on_tab(
+                &target_id,
+                "Network.setCookies",
+                json!({ "cookies": params }),
+            )
+            .await
+    {
+        return ActionResult::fatal("CDP_ERROR", e.to_string());
+    }
+
+    ActionResult::ok(json!({
+        "path": cmd.file,
+        "format": format,
+        "imported": live.len(),
+        "skipped": expired.len(),
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_netscape_reads_http_only_and_subdomain_flags() {
+        let text = "# Netscape HTTP Cookie File\n\
+                    # comment\n\
+                    \n\
+                    #HttpOnly_.example.com\tTRUE\t/\tTRUE\t1893456000\tsid\tabc\n\
+                    app.example.com\tFALSE\t/ui\tFALSE\t0\tpref\t\n";
+        let cookies = parse_netscape(text).unwrap();
+        assert_eq!(cookies.len(), 2);
+        assert_eq!(cookies[0].domain, ".example.com");
+        assert!(cookies[0].http_only && cookies[0].secure);
+        assert_eq!(cookies[0].expires, Some(1893456000.0));
+        assert_eq!(cookies[1].domain, "app.example.com");
+        assert_eq!(cookies[1].value, "");
+        assert_eq!(cookies[1].expires, None);
+
+        assert!(parse_netscape("example.com\tTRUE\t/\n").is_err());
+    }
+
+    #[test]
+    fn parse_json_accepts_cdp_and_list_shapes() {
+        let cdp = r#"[{"name":"sid","value":"abc","domain":".example.com","path":"/",
+                      "expires":-1,"httpOnly":true,"secure":true,"sameSite":"Lax","session":true}]"#;
+        let listed = r#"{"ok":true,"data":{"items":[{"name":"sid","value":"abc","domain":".example.com","path":"/",
+                      "http_only":true,"secure":true,"same_site":"lax","expires":null}]}}"#;
+        let a = parse_json(cdp).unwrap();
+        let b = parse_json(listed).unwrap();
+        assert_eq!(a, b);
+        assert_eq!(a[0].same_site.as_deref(), Some("Lax"));
+        assert_eq!(a[0].expires, None);
+
+        assert!(parse_json(r#"[{"value":"x"}]"#).is_err());
+        assert!(parse_json("42").is_err());
+    }
+
+    #[test]
+    fn to_cookie_param_keeps_host_only_cookies_host_only() {
+        let mut c = parse_netscape("app.example.com\tFALSE\t/ui\tTRUE\t0\tpref\tdark\n").unwrap();
+        let param = c[0].to_cookie_param();
+        assert_eq!(param["url"], "https://app.example.com/ui");
+        assert!(param.get("domain").is_none());
+
+        c[0].domain = ".example.com".to_string();
+        let param = c[0].to_cookie_param();
+        assert_eq!(param["domain"], ".example.com");
+        assert!(param.get("url").is_none());
+    }
+}
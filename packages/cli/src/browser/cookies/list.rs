@@ -3,6 +3,7 @@ use serde::{Deserialize, Serialize};
 use serde_json::json;
 
 use crate::action_result::ActionResult;
+use crate::browser::redact;
 use crate::daemon::registry::SharedRegistry;
 use crate::output::ResponseContext;
 
@@ -13,12 +14,19 @@ use super::{map_cookie, normalize_domain};
 #[command(after_help = "\
 Examples:
   actionbook browser cookies list --session s1
-  actionbook browser cookies list --session s1 --domain example.com")]
+  actionbook browser cookies list --session s1 --domain example.com
+  actionbook browser cookies list --session s1 --show-secrets
+
+Cookie values are masked unless --show-secrets is passed.")]
 pub struct Cmd {
     /// Session ID
     #[arg(long)]
     #[serde(rename = "session_id")]
     pub session: String,
+    /// Show cookie values instead of masking them
+    #[arg(long)]
+    #[serde(default)]
+    pub show_secrets: bool,
     /// Filter cookies by domain
     #[arg(long)]
     pub domain: Option<String>,
@@ -89,7 +97,7 @@ pub async fn execute(cmd: &Cmd, registry: &SharedRegistry) -> ActionResult {
         .and_then(|v| v.as_array())
         .unwrap_or(&empty);
 
-    let items: Vec<_> = raw
+    let mut items: Vec<_> = raw
         .iter()
         .map(map_cookie)
         .filter(|c| {
@@ -101,6 +109,9 @@ pub async fn execute(cmd: &Cmd, registry: &SharedRegistry) -> ActionResult {
             }
         })
         .collect();
+    if !cmd.show_secrets {
+        items.iter_mut().for_each(redact::mask_cookie);
+    }
 
     ActionResult::ok(json!({ "items": items }))
 }
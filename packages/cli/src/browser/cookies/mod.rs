@@ -1,6 +1,8 @@
 pub mod clear;
 pub mod delete;
+pub mod export;
 pub mod get;
+pub mod import;
 pub mod list;
 pub mod set;
 
@@ -0,0 +1,43 @@
+use clap::Args;
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+
+use crate::action_result::ActionResult;
+use crate::daemon::registry::SharedRegistry;
+use crate::output::ResponseContext;
+
+/// List the session's downloads
+#[derive(Args, Debug, Clone, Serialize, Deserialize)]
+#[command(after_help = "\
+Examples:
+  actionbook browser downloads list --session s1
+
+Lists every download since the session started, with its state
+(in_progress, completed, canceled), bytes received and where it was saved.
+Start the session with --download-dir to choose the folder; otherwise files
+go to Chrome's default download folder and the path may be unknown.")]
+pub struct Cmd {
+    /// Session ID
+    #[arg(long)]
+    #[serde(rename = "session_id")]
+    pub session: String,
+}
+
+pub const COMMAND_NAME: &str = "browser downloads list";
+
+pub fn context(cmd: &Cmd, result: &ActionResult) -> Option<ResponseContext> {
+    super::session_context(&cmd.session, result)
+}
+
+pub async fn execute(cmd: &Cmd, registry: &SharedRegistry) -> ActionResult {
+    let tracker = match super::tracker(registry, &cmd.session).await {
+        Ok(t) => t,
+        Err(e) => return e,
+    };
+    let downloads = tracker.list();
+    ActionResult::ok(json!({
+        "dir": tracker.dir().map(|d| d.to_string_lossy().into_owned()),
+        "total": downloads.len(),
+        "downloads": downloads,
+    }))
+}
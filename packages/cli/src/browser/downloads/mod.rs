@@ -0,0 +1,48 @@
+pub mod list;
+pub mod wait;
+
+use crate::action_result::ActionResult;
+use crate::daemon::downloads::SharedDownloads;
+use crate::daemon::registry::SharedRegistry;
+use crate::output::ResponseContext;
+
+pub fn session_context(session: &str, result: &ActionResult) -> Option<ResponseContext> {
+    if let ActionResult::Fatal { code, .. } = result
+        && code == "SESSION_NOT_FOUND"
+    {
+        return None;
+    }
+    Some(ResponseContext {
+        session_id: session.to_string(),
+        tab_id: None,
+        window_id: None,
+        url: None,
+        title: None,
+    })
+}
+
+/// The session's download log; tracking is set up by `browser start`.
+async fn tracker(
+    registry: &SharedRegistry,
+    session: &str,
+) -> Result<SharedDownloads, ActionResult> {
+    let reg = registry.lock().await;
+    let Some(entry) = reg.get(session) else {
+        return Err(ActionResult::fatal_with_hint(
+            "SESSION_NOT_FOUND",
+            format!("session '{session}' not found"),
+            "run `actionbook browser list-sessions` to see available sessions",
+        ));
+    };
+    entry
+        .cdp
+        .as_ref()
+        .and_then(|cdp| cdp.downloads())
+        .ok_or_else(|| {
+            ActionResult::fatal_with_hint(
+                "DOWNLOADS_NOT_TRACKED",
+                format!("downloads are not tracked in session '{session}'"),
+                "download tracking needs a local or cloud session that allows Browser.setDownloadBehavior",
+            )
+        })
+}
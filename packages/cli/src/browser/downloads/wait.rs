@@ -0,0 +1,65 @@
+use std::time::Duration;
+
+use clap::Args;
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+
+use crate::action_result::ActionResult;
+use crate::daemon::registry::SharedRegistry;
+use crate::output::ResponseContext;
+
+/// Wait for a download to finish
+#[derive(Args, Debug, Clone, Serialize, Deserialize)]
+#[command(after_help = "\
+Examples:
+  actionbook browser click \"#export-csv\" --session s1 --tab t1
+  actionbook browser downloads wait --session s1 --timeout 60000
+
+Returns the oldest download that finished since the last wait, waiting for
+one if none has. A download that already completed while the click returned
+is not missed, and each is returned once. A canceled download fails with
+DOWNLOAD_CANCELED.")]
+pub struct Cmd {
+    /// Session ID
+    #[arg(long)]
+    #[serde(rename = "session_id")]
+    pub session: String,
+    /// Timeout in milliseconds
+    #[arg(long, default_value_t = 30000)]
+    #[serde(default = "default_timeout")]
+    pub timeout: u64,
+}
+
+fn default_timeout() -> u64 {
+    30000
+}
+
+pub const COMMAND_NAME: &str = "browser downloads wait";
+
+pub fn context(cmd: &Cmd, result: &ActionResult) -> Option<ResponseContext> {
+    super::session_context(&cmd.session, result)
+}
+
+pub async fn execute(cmd: &Cmd, registry: &SharedRegistry) -> ActionResult {
+    let tracker = match super::tracker(registry, &cmd.session).await {
+        Ok(t) => t,
+        Err(e) => return e,
+    };
+    match tracker
+        .wait_finished(Duration::from_millis(cmd.timeout))
+        .await
+    {
+        Some(Ok(download)) => ActionResult::ok(json!({ "download": download })),
+        Some(Err(download)) => ActionResult::fatal_with_details(
+            "DOWNLOAD_CANCELED",
+            format!("download of '{}' was canceled", download.filename),
+            "check that the site allows the download and that the folder is writable",
+            json!({ "download": download }),
+        ),
+        None => ActionResult::fatal_with_hint(
+            "TIMEOUT",
+            format!("no download finished within {}ms", cmd.timeout),
+            "raise --timeout, or check `browser downloads list` for one still in progress",
+        ),
+    }
+}
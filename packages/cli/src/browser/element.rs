@@ -4,13 +4,21 @@
 //! every command can resolve selectors with a single `ctx.resolve_node(sel)`.
 //!
 //! Selector dispatch:
-//! 1. **CSS selector** — default path, `DOM.querySelector`.
+//! 1. **CSS selector** — default path, `DOM.querySelector`. When that finds
+//!    nothing, open shadow roots are searched too, and `host >>> inner`
+//!    steps into a shadow root explicitly (see [`DEEP_QUERY_JS`]).
 //! 2. **XPath** — prefix `//` or `/`, `Runtime.evaluate`.
 //! 3. **Snapshot ref** — prefix `@e`, e.g. `@e5`, via RefCache + CDP.
+//! 4. **Text** — `text=Sign in` (case-insensitive substring) or
+//!    `text="Sign in"` (exact), the innermost element with that text.
+//! 5. **Role** — `role=button[name="Checkout"]`, via
+//!    `Accessibility.queryAXTree` on the same tree `snapshot` reads.
 //!
 //! iframe support: after resolving an `@eN` ref, `resolved_frame_id` is set
 //! so that subsequent `execute_on_element()` calls route to the correct CDP
-//! session for cross-origin iframes.
+//! session for cross-origin iframes. After [`TabContext::enter_frame`]
+//! (`--frame`), CSS and XPath selectors are looked up in that iframe's
+//! document instead of the top one.
 
 use serde_json::{Value, json};
 
@@ -63,6 +71,19 @@ pub struct TabContext {
     /// None = main frame. Some(frame_id) = iframe element.
     /// Used by execute_on_element() for subsequent CDP commands on the same element.
     resolved_frame_id: Option<String>,
+    /// Occlusion report from the most recent resolve_center call, when the
+    /// element's centre was covered by another element.
+    occlusion: Option<Value>,
+    /// iframe entered with `--frame`: CSS/XPath selectors and `evaluate`
+    /// run in its document.
+    frame: Option<FrameScope>,
+}
+
+/// An iframe entered with `--frame`, plus the isolated world created in it
+/// for selector lookups.
+struct FrameScope {
+    frame_id: String,
+    context_id: i64,
 }
 
 impl TabContext {
@@ -80,9 +101,72 @@ impl TabContext {
             session_id: session_id.to_string(),
             tab_id: tab_id.to_string(),
             resolved_frame_id: None,
+            occlusion: None,
+            frame: None,
         })
     }
 
+    /// Scope CSS/XPath selectors and [`Self::evaluate`] to the iframe `spec`
+    /// names: a frame name, a 1-based position among the tab's frames, or a
+    /// URL substring. `@eN` refs keep resolving in their own frame.
+    pub async fn enter_frame(&mut self, spec: &str) -> Result<(), ActionResult> {
+        let frames = list_frames(&self.cdp, &self.target_id).await?;
+        let frame_id = pick_frame(&frames, spec)?.frame_id.clone();
+        let world = execute_for_frame(
+            &self.cdp,
+            &self.target_id,
+            Some(&frame_id),
+            "Page.createIsolatedWorld",
+            json!({ "frameId": frame_id, "worldName": "actionbook-frame" }),
+        )
+        .await
+        .map_err(|e| cdp_error_to_result(e, "CDP_ERROR"))?;
+        let context_id = world
+            .pointer("/result/executionContextId")
+            .and_then(|v| v.as_i64())
+            .ok_or_else(|| {
+                ActionResult::fatal(
+                    "CDP_ERROR",
+                    format!("no execution context for frame {spec}"),
+                )
+            })?;
+        self.frame = Some(FrameScope {
+            frame_id,
+            context_id,
+        });
+        Ok(())
+    }
+
+    /// `Runtime.evaluate` (by value) in the entered frame, or the tab's top
+    /// document when no frame was entered.
+    pub async fn evaluate(&self, expression: &str) -> Result<Value, CliError> {
+        match &self.frame {
+            Some(frame) => {
+                execute_for_frame(
+                    &self.cdp,
+                    &self.target_id,
+                    Some(&frame.frame_id),
+                    "Runtime.evaluate",
+                    json!({
+                        "expression": expression,
+                        "returnByValue": true,
+                        "contextId": frame.context_id,
+                    }),
+                )
+                .await
+            }
+            None => {
+                self.cdp
+                    .execute_on_tab(
+                        &self.target_id,
+                        "Runtime.evaluate",
+                        json!({ "expression": expression, "returnByValue": true }),
+                    )
+                    .await
+            }
+        }
+    }
+
     /// Selector → CDP `nodeId`. Sets `resolved_frame_id` for @eN refs.
     pub async fn resolve_node(&mut self, selector: &str) -> Result<i64, ActionResult> {
         if selector.starts_with("@e") {
@@ -97,6 +181,29 @@ impl TabContext {
             .await?;
             self.resolved_frame_id = frame_id;
             Ok(node_id)
+        } else if selector.starts_with("text=") || selector.starts_with("role=") {
+            let frame = self.frame.as_ref();
+            let node_id = resolve_engine(
+                &self.cdp,
+                &self.target_id,
+                frame.map(|f| f.frame_id.as_str()),
+                frame.map(|f| f.context_id),
+                selector,
+            )
+            .await?;
+            self.resolved_frame_id = frame.map(|f| f.frame_id.clone());
+            Ok(node_id)
+        } else if let Some(frame) = &self.frame {
+            let node_id = resolve_in_frame(
+                &self.cdp,
+                &self.target_id,
+                &frame.frame_id,
+                frame.context_id,
+                selector,
+            )
+            .await?;
+            self.resolved_frame_id = Some(frame.frame_id.clone());
+            Ok(node_id)
         } else if selector.starts_with("//") || selector.starts_with('/') {
             self.resolved_frame_id = None;
             resolve_xpath(&self.cdp, &self.target_id, selector).await
@@ -111,19 +218,64 @@ impl TabContext {
     /// Returns the nodeId alongside the coordinates so callers (e.g. drag)
     /// can later call `get_center(node_id)` to refresh coordinates without
     /// re-scrolling.
+    ///
+    /// When the centre is covered (see `[interaction]` in the config), the
+    /// element is scrolled clear of the cover or an uncovered point inside it
+    /// is returned instead; [`Self::occlusion`] then describes what happened.
     pub async fn resolve_center(
         &mut self,
         selector: &str,
     ) -> Result<(i64, f64, f64), ActionResult> {
+        self.occlusion = None;
         let node_id = self.resolve_node(selector).await?;
         let frame_id = self.resolved_frame_id.as_deref();
         scroll_into_view_for_frame(&self.cdp, &self.target_id, node_id, frame_id).await?;
+        let occlusion =
+            avoid_occlusion_for_frame(&self.cdp, &self.target_id, node_id, frame_id).await;
         let (x, y) =
             get_element_center_for_frame(&self.cdp, &self.target_id, node_id, selector, frame_id)
                 .await?;
+        let offset = |key: &str| {
+            occlusion
+                .as_ref()
+                .and_then(|o| o.get(key))
+                .and_then(|v| v.as_f64())
+                .unwrap_or(0.0)
+        };
+        let (x, y) = (x + offset("dx"), y + offset("dy"));
+        self.occlusion = occlusion;
+        Ok((node_id, x, y))
+    }
+
+    /// Like `resolve_center`, but aims at `point` inside the element's border
+    /// box. An explicit point is taken as given: occlusion avoidance only
+    /// applies to the centre.
+    pub async fn resolve_point(
+        &mut self,
+        selector: &str,
+        point: &ElementPoint,
+    ) -> Result<(i64, f64, f64), ActionResult> {
+        if point.is_center() {
+            return self.resolve_center(selector).await;
+        }
+        self.occlusion = None;
+        let node_id = self.resolve_node(selector).await?;
+        let frame_id = self.resolved_frame_id.as_deref();
+        scroll_into_view_for_frame(&self.cdp, &self.target_id, node_id, frame_id).await?;
+        let (left, top, width, height) =
+            get_element_rect_for_frame(&self.cdp, &self.target_id, node_id, selector, frame_id)
+                .await?;
+        let (x, y) = point.apply(left, top, width, height);
         Ok((node_id, x, y))
     }
 
+    /// Occlusion report from the last `resolve_center`: `{ by, resolved }`,
+    /// where `by` describes the covering element and `resolved` is
+    /// `"scroll"`, `"point"` or null (still covered). None when uncovered.
+    pub fn occlusion(&self) -> Option<&Value> {
+        self.occlusion.as_ref()
+    }
+
     /// Like `resolve_center` but skips `scrollIntoView` (no JS main-thread call).
     /// Use for batch operations where elements are already in the DOM and the
     /// caller doesn't need them scrolled into the viewport.
@@ -285,6 +437,87 @@ async fn scroll_into_view_for_frame(
     Ok(())
 }
 
+/// Hit-test the element's centre and, if another element covers it, scroll
+/// it clear with a temporary `scroll-margin`, falling back to an uncovered
+/// point inside it. Returns `{ occluded, by, resolved, dx, dy }` where
+/// `(dx, dy)` offsets the click point from the centre.
+const AVOID_OCCLUSION_JS: &str = r#"function(margin) {
+    const self = this;
+    const root = self.getRootNode();
+    const doc = root.elementFromPoint ? root : document;
+    const cover = (x, y) => {
+        const hit = doc.elementFromPoint(x, y);
+        return !hit || hit === self || self.contains(hit) ? null : hit;
+    };
+    let r = self.getBoundingClientRect();
+    const occluder = cover(r.left + r.width / 2, r.top + r.height / 2);
+    if (!occluder) return { occluded: false };
+    const cls = typeof occluder.className === 'string' ? occluder.className.trim().split(/\s+/)[0] : '';
+    const by = occluder.tagName.toLowerCase() + (occluder.id ? '#' + occluder.id : '') + (cls ? '.' + cls : '');
+    const o = occluder.getBoundingClientRect();
+    const style = self.style;
+    const saved = [style.scrollMarginTop, style.scrollMarginBottom];
+    if (o.top + o.height / 2 < r.top + r.height / 2) {
+        style.scrollMarginTop = (o.bottom + margin) + 'px';
+        self.scrollIntoView({ block: 'start', inline: 'nearest', behavior: 'instant' });
+    } else {
+        style.scrollMarginBottom = (innerHeight - o.top + margin) + 'px';
+        self.scrollIntoView({ block: 'end', inline: 'nearest', behavior: 'instant' });
+    }
+    style.scrollMarginTop = saved[0];
+    style.scrollMarginBottom = saved[1];
+    r = self.getBoundingClientRect();
+    const cx = r.left + r.width / 2, cy = r.top + r.height / 2;
+    if (!cover(cx, cy)) return { occluded: true, by, resolved: 'scroll', dx: 0, dy: 0 };
+    for (const fy of [0.5, 0.25, 0.75, 0.1, 0.9]) {
+        for (const fx of [0.5, 0.25, 0.75, 0.1, 0.9]) {
+            const x = r.left + r.width * fx, y = r.top + r.height * fy;
+            if (!cover(x, y)) return { occluded: true, by, resolved: 'point', dx: x - cx, dy: y - cy };
+        }
+    }
+    return { occluded: true, by, resolved: null, dx: 0, dy: 0 };
+}"#;
+
+/// Run [`AVOID_OCCLUSION_JS`] on an element per the `[interaction]` config.
+/// Best effort: returns None when the element is uncovered, the strategy is
+/// `ignore`, or the check itself fails.
+async fn avoid_occlusion_for_frame(
+    cdp: &CdpSession,
+    target_id: &str,
+    node_id: i64,
+    frame_id: Option<&str>,
+) -> Option<Value> {
+    let cfg = crate::config::interaction_config();
+    if cfg.occlusion == crate::config::OcclusionStrategy::Ignore {
+        return None;
+    }
+    let margin = cfg
+        .scroll_margin
+        .unwrap_or(crate::config::DEFAULT_SCROLL_MARGIN_PX);
+    let object_id = resolve_object_id_for_frame(cdp, target_id, node_id, frame_id)
+        .await
+        .ok()?;
+    let resp = execute_for_frame(
+        cdp,
+        target_id,
+        frame_id,
+        "Runtime.callFunctionOn",
+        json!({
+            "objectId": object_id,
+            "functionDeclaration": AVOID_OCCLUSION_JS,
+            "arguments": [{ "value": margin }],
+            "returnByValue": true,
+        }),
+    )
+    .await
+    .ok()?;
+    let report = resp.pointer("/result/result/value")?;
+    if report.get("occluded").and_then(|v| v.as_bool()) != Some(true) {
+        return None;
+    }
+    Some(report.clone())
+}
+
 /// `nodeId` → remote JS object ID, routing to the correct frame session.
 async fn resolve_object_id_for_frame(
     cdp: &CdpSession,
@@ -350,6 +583,47 @@ async fn get_element_center_for_frame(
     Ok((cx, cy))
 }
 
+/// Border box `(left, top, width, height)` of an element in page coordinates,
+/// routing to the correct frame session.
+async fn get_element_rect_for_frame(
+    cdp: &CdpSession,
+    target_id: &str,
+    node_id: i64,
+    selector: &str,
+    frame_id: Option<&str>,
+) -> Result<(f64, f64, f64, f64), ActionResult> {
+    let bm = execute_for_frame(
+        cdp,
+        target_id,
+        frame_id,
+        "DOM.getBoxModel",
+        json!({ "nodeId": node_id }),
+    )
+    .await
+    .map_err(|e| cdp_error_to_result(e, "CDP_ERROR"))?;
+
+    let quad: Vec<f64> = bm
+        .pointer("/result/model/border")
+        .and_then(|v| v.as_array())
+        .map(|a| a.iter().map(|v| v.as_f64().unwrap_or(0.0)).collect())
+        .filter(|q: &Vec<f64>| q.len() == 8)
+        .ok_or_else(|| {
+            ActionResult::fatal("CDP_ERROR", format!("no box model for element: {selector}"))
+        })?;
+
+    let (mut left, mut top) = (quad[0], quad[1]);
+    let (width, height) = (quad[4] - quad[0], quad[5] - quad[1]);
+    // Same OOPIF adjustment as get_element_center_for_frame.
+    if let Some(fid) = frame_id
+        && let Ok((off_x, off_y)) = get_iframe_offset(cdp, target_id, fid).await
+    {
+        left += off_x;
+        top += off_y;
+    }
+
+    Ok((left, top, width, height))
+}
+
 /// Get the top-left offset of an iframe on the parent page.
 ///
 /// Uses `DOM.getFrameOwner(frameId)` to find the iframe element's
@@ -443,14 +717,45 @@ pub fn element_not_found(selector: &str) -> ActionResult {
     }
 }
 
+/// Called as `(DEEP_QUERY_JS)(selector, root)`: the first match for a CSS
+/// selector under `root`, looking inside open shadow roots when the light
+/// DOM has none. `a >>> b` finds `a`, then looks for `b` inside its shadow
+/// root. Throws on invalid syntax.
+pub const DEEP_QUERY_JS: &str = r#"(function(selector, root) {
+    const deep = (scope, sel) => {
+        const hit = scope.querySelector(sel);
+        if (hit) return hit;
+        for (const el of scope.querySelectorAll('*')) {
+            if (!el.shadowRoot) continue;
+            const found = deep(el.shadowRoot, sel);
+            if (found) return found;
+        }
+        return null;
+    };
+    let scope = root || document;
+    let el = null;
+    for (const part of selector.split('>>>')) {
+        const sel = part.trim();
+        if (!sel) throw new SyntaxError("empty selector around '>>>'");
+        el = deep(scope, sel);
+        if (!el) return null;
+        scope = el.shadowRoot || el;
+    }
+    return el;
+})"#;
+
 // ── Private resolvers ──────────────────────────────────────────────
 
-/// CSS selector → nodeId via `DOM.querySelector`.
+/// CSS selector → nodeId via `DOM.querySelector`, falling back to
+/// [`DEEP_QUERY_JS`] for shadow DOM.
 async fn resolve_css(
     cdp: &CdpSession,
     target_id: &str,
     selector: &str,
 ) -> Result<i64, ActionResult> {
+    if selector.contains(">>>") {
+        return resolve_deep(cdp, target_id, None, None, selector).await;
+    }
     let doc = cdp
         .execute_on_tab(target_id, "DOM.getDocument", json!({}))
         .await
@@ -495,7 +800,7 @@ async fn resolve_css(
         .unwrap_or(0);
 
     if node_id == 0 {
-        return Err(element_not_found(selector));
+        return resolve_deep(cdp, target_id, None, None, selector).await;
     }
     Ok(node_id)
 }
@@ -551,6 +856,359 @@ async fn resolve_xpath(
     Ok(node_id)
 }
 
+/// CSS selector or XPath → nodeId, looked up in one iframe's document
+/// through the isolated world `context_id` created in it.
+async fn resolve_in_frame(
+    cdp: &CdpSession,
+    target_id: &str,
+    frame_id: &str,
+    context_id: i64,
+    selector: &str,
+) -> Result<i64, ActionResult> {
+    resolve_deep(cdp, target_id, Some(frame_id), Some(context_id), selector).await
+}
+
+/// Selector → nodeId by evaluating it in the page: XPath via
+/// `document.evaluate`, CSS via [`DEEP_QUERY_JS`]. `frame_id` and
+/// `context_id` select an iframe's document; both `None` is the top one.
+async fn resolve_deep(
+    cdp: &CdpSession,
+    target_id: &str,
+    frame_id: Option<&str>,
+    context_id: Option<i64>,
+    selector: &str,
+) -> Result<i64, ActionResult> {
+    let selector_json = serde_json::to_string(selector).unwrap_or_default();
+    let expression = if selector.starts_with('/') {
+        format!(
+            "document.evaluate({selector_json}, document, null, XPathResult.FIRST_ORDERED_NODE_TYPE, null).singleNodeValue"
+        )
+    } else {
+        format!("({DEEP_QUERY_JS})({selector_json}, document)")
+    };
+    node_for_expression(cdp, target_id, frame_id, context_id, &expression, selector).await
+}
+
+/// Evaluate `expression` (which yields an element or null) in the page or
+/// frame and turn the element into a nodeId. Exceptions are reported as an
+/// invalid `selector`.
+async fn node_for_expression(
+    cdp: &CdpSession,
+    target_id: &str,
+    frame_id: Option<&str>,
+    context_id: Option<i64>,
+    expression: &str,
+    selector: &str,
+) -> Result<i64, ActionResult> {
+    execute_for_frame(cdp, target_id, frame_id, "DOM.getDocument", json!({}))
+        .await
+        .map_err(|e| cdp_error_to_result(e, "CDP_ERROR"))?;
+
+    let mut params = json!({ "expression": expression });
+    if let Some(id) = context_id {
+        params["contextId"] = json!(id);
+    }
+    let eval = execute_for_frame(cdp, target_id, frame_id, "Runtime.evaluate", params)
+        .await
+        .map_err(|e| cdp_error_to_result(e, "CDP_ERROR"))?;
+    if eval.pointer("/result/exceptionDetails").is_some() {
+        return Err(ActionResult::Fatal {
+            code: "INVALID_SELECTOR".to_string(),
+            message: format!("invalid selector: '{selector}'"),
+            hint: "check your selector syntax".to_string(),
+            details: Some(json!({ "selector": selector })),
+        });
+    }
+    let Some(object_id) = eval
+        .pointer("/result/result/objectId")
+        .and_then(|v| v.as_str())
+    else {
+        return Err(element_not_found(selector));
+    };
+
+    let node_resp = execute_for_frame(
+        cdp,
+        target_id,
+        frame_id,
+        "DOM.requestNode",
+        json!({ "objectId": object_id }),
+    )
+    .await
+    .map_err(|e| cdp_error_to_result(e, "CDP_ERROR"))?;
+    match node_resp.pointer("/result/nodeId").and_then(|v| v.as_i64()) {
+        Some(node_id) if node_id > 0 => Ok(node_id),
+        _ => Err(element_not_found(selector)),
+    }
+}
+
+// ── Text and role selectors ────────────────────────────────────────
+
+/// Called as `(TEXT_QUERY_JS)(text, exact)`: the innermost element whose
+/// whitespace-normalised text contains `text` (case-insensitive), or equals
+/// it when `exact`. Buttons made of `<input>` match on their value; open
+/// shadow roots are searched too.
+const TEXT_QUERY_JS: &str = r#"(function(text, exact) {
+    const norm = (s) => (s || '').replace(/\s+/g, ' ').trim();
+    const want = exact ? norm(text) : norm(text).toLowerCase();
+    const skip = new Set(['SCRIPT', 'STYLE', 'NOSCRIPT', 'TEMPLATE', 'HEAD']);
+    const textOf = (el) => {
+        if (el instanceof HTMLInputElement) return /^(button|submit|reset)$/.test(el.type) ? el.value : '';
+        return el instanceof HTMLElement ? el.innerText : el.textContent;
+    };
+    const contains = (el) => {
+        const t = norm(textOf(el));
+        return exact ? t.includes(want) : t.toLowerCase().includes(want);
+    };
+    const matches = (el) => !exact || norm(textOf(el)) === want;
+    // Descend through elements containing the text; the deepest one that
+    // matches wins.
+    const search = (el) => {
+        const kids = Array.from(el.children);
+        if (el.shadowRoot) kids.push(...el.shadowRoot.children);
+        for (const child of kids) {
+            if (skip.has(child.tagName) || !contains(child)) continue;
+            const found = search(child);
+            if (found) return found;
+            if (matches(child)) return child;
+        }
+        return null;
+    };
+    const top = document.body || document.documentElement;
+    return search(top) || (contains(top) && matches(top) ? top : null);
+})"#;
+
+/// A `text=` or `role=` selector.
+#[derive(Debug, PartialEq)]
+pub(crate) enum EngineSelector {
+    Text { text: String, exact: bool },
+    Role { role: String, name: Option<String> },
+}
+
+impl EngineSelector {
+    /// Parse `text=...`, `text="..."`, `role=R` or `role=R[name="N"]`.
+    pub(crate) fn parse(selector: &str) -> Result<Self, String> {
+        let unquote = |s: &str| -> Option<String> {
+            let s = s.trim();
+            let q = s.chars().next().filter(|c| *c == '"' || *c == '\'')?;
+            s.strip_prefix(q)?.strip_suffix(q).map(String::from)
+        };
+        if let Some(text) = selector.strip_prefix("text=") {
+            if text.trim().is_empty() {
+                return Err("text= needs some text".to_string());
+            }
+            return Ok(match unquote(text) {
+                Some(exact) => Self::Text {
+                    text: exact,
+                    exact: true,
+                },
+                None => Self::Text {
+                    text: text.trim().to_string(),
+                    exact: false,
+                },
+            });
+        }
+        let rest = selector
+            .strip_prefix("role=")
+            .ok_or_else(|| format!("unknown selector engine in '{selector}'"))?;
+        let (role, name) = match rest.split_once('[') {
+            None => (rest.trim(), None),
+            Some((role, attr)) => {
+                let attr = attr
+                    .strip_suffix(']')
+                    .and_then(|a| a.trim().strip_prefix("name"))
+                    .and_then(|a| a.trim_start().strip_prefix('='))
+                    .ok_or_else(|| {
+                        format!("expected role=ROLE[name=\"NAME\"], got '{selector}'")
+                    })?;
+                let name = unquote(attr).unwrap_or_else(|| attr.trim().to_string());
+                (role.trim(), Some(name))
+            }
+        };
+        if role.is_empty() || !role.chars().all(|c| c.is_ascii_alphanumeric()) {
+            return Err(format!("invalid role in '{selector}'"));
+        }
+        Ok(Self::Role {
+            role: role.to_string(),
+            name,
+        })
+    }
+}
+
+/// `text=` / `role=` selector → nodeId, in the top document or the frame
+/// given by `frame_id` and `context_id`.
+async fn resolve_engine(
+    cdp: &CdpSession,
+    target_id: &str,
+    frame_id: Option<&str>,
+    context_id: Option<i64>,
+    selector: &str,
+) -> Result<i64, ActionResult> {
+    let invalid = |message: String| ActionResult::Fatal {
+        code: "INVALID_SELECTOR".to_string(),
+        message,
+        hint: "use text=Sign in, text=\"Sign in\" or role=button[name=\"Sign in\"]".to_string(),
+        details: Some(json!({ "selector": selector })),
+    };
+    let (role, name) = match EngineSelector::parse(selector).map_err(invalid)? {
+        EngineSelector::Text { text, exact } => {
+            let expression = format!(
+                "({TEXT_QUERY_JS})({}, {exact})",
+                serde_json::to_string(&text).unwrap_or_default()
+            );
+            return node_for_expression(
+                cdp,
+                target_id,
+                frame_id,
+                context_id,
+                &expression,
+                selector,
+            )
+            .await;
+        }
+        EngineSelector::Role { role, name } => (role, name),
+    };
+
+    // queryAXTree needs a root: the (frame's) document.
+    let mut params = json!({ "expression": "document" });
+    if let Some(id) = context_id {
+        params["contextId"] = json!(id);
+    }
+    let doc = execute_for_frame(cdp, target_id, frame_id, "Runtime.evaluate", params)
+        .await
+        .map_err(|e| cdp_error_to_result(e, "CDP_ERROR"))?;
+    let Some(doc_object) = doc
+        .pointer("/result/result/objectId")
+        .and_then(|v| v.as_str())
+    else {
+        return Err(element_not_found(selector));
+    };
+    let mut query = json!({ "objectId": doc_object, "role": role });
+    if let Some(name) = &name {
+        query["accessibleName"] = json!(name);
+    }
+    let resp = execute_for_frame(cdp, target_id, frame_id, "Accessibility.queryAXTree", query)
+        .await
+        .map_err(|e| cdp_error_to_result(e, "CDP_ERROR"))?;
+    let nodes = resp
+        .pointer("/result/nodes")
+        .and_then(|v| v.as_array())
+        .cloned()
+        .unwrap_or_default();
+    for node in nodes {
+        if node["ignored"].as_bool().unwrap_or(false) {
+            continue;
+        }
+        let bid = node["backendDOMNodeId"].as_i64().unwrap_or(0);
+        if bid > 0
+            && let Some(node_id) = resolve_backend_node(cdp, target_id, bid, frame_id).await?
+        {
+            return Ok(node_id);
+        }
+    }
+    Err(element_not_found(selector))
+}
+
+// ── Frames (`--frame`) ─────────────────────────────────────────────
+
+/// A child frame of a tab.
+#[derive(Debug, Clone, PartialEq)]
+pub struct FrameInfo {
+    pub frame_id: String,
+    pub name: String,
+    pub url: String,
+}
+
+/// Every frame below the tab's top document, in document order.
+///
+/// Cross-origin frames with their own CDP session (see
+/// `Target.setAutoAttach`) are added from that session when the tab's frame
+/// tree does not list them.
+pub async fn list_frames(
+    cdp: &CdpSession,
+    target_id: &str,
+) -> Result<Vec<FrameInfo>, ActionResult> {
+    let tree = cdp
+        .execute_on_tab(target_id, "Page.getFrameTree", json!({}))
+        .await
+        .map_err(|e| cdp_error_to_result(e, "CDP_ERROR"))?;
+    let mut frames = Vec::new();
+    collect_frames(&tree["result"]["frameTree"]["childFrames"], &mut frames);
+
+    for (frame_id, iframe_sid) in cdp.iframe_sessions().await {
+        if frames.iter().any(|f| f.frame_id == frame_id) {
+            continue;
+        }
+        if let Ok(tree) = cdp
+            .execute("Page.getFrameTree", json!({}), Some(&iframe_sid))
+            .await
+        {
+            let frame = &tree["result"]["frameTree"]["frame"];
+            frames.push(FrameInfo {
+                frame_id,
+                name: frame["name"].as_str().unwrap_or("").to_string(),
+                url: frame["url"].as_str().unwrap_or("").to_string(),
+            });
+        }
+    }
+    Ok(frames)
+}
+
+fn collect_frames(children: &Value, out: &mut Vec<FrameInfo>) {
+    for child in children.as_array().into_iter().flatten() {
+        let frame = &child["frame"];
+        if let Some(frame_id) = frame["id"].as_str() {
+            out.push(FrameInfo {
+                frame_id: frame_id.to_string(),
+                name: frame["name"].as_str().unwrap_or("").to_string(),
+                url: frame["url"].as_str().unwrap_or("").to_string(),
+            });
+        }
+        collect_frames(&child["childFrames"], out);
+    }
+}
+
+/// Pick the frame `spec` names: a 1-based position, an exact frame name or
+/// ID, or else the one frame whose URL contains `spec`.
+pub fn pick_frame<'a>(frames: &'a [FrameInfo], spec: &str) -> Result<&'a FrameInfo, ActionResult> {
+    let not_found = || {
+        let listing = frames
+            .iter()
+            .enumerate()
+            .map(|(i, f)| format!("{} name={:?} url={}", i + 1, f.name, f.url))
+            .collect::<Vec<_>>()
+            .join("; ");
+        ActionResult::fatal_with_hint(
+            "FRAME_NOT_FOUND",
+            format!("no frame matches '{spec}'"),
+            if frames.is_empty() {
+                "the page has no iframes".to_string()
+            } else {
+                format!("frames: {listing}")
+            },
+        )
+    };
+
+    if let Ok(position) = spec.parse::<usize>() {
+        return position
+            .checked_sub(1)
+            .and_then(|i| frames.get(i))
+            .ok_or_else(not_found);
+    }
+    if let Some(frame) = frames.iter().find(|f| f.name == spec || f.frame_id == spec) {
+        return Ok(frame);
+    }
+    let matches: Vec<&FrameInfo> = frames.iter().filter(|f| f.url.contains(spec)).collect();
+    match matches.as_slice() {
+        [] => Err(not_found()),
+        [frame] => Ok(frame),
+        many => Err(ActionResult::fatal_with_hint(
+            "INVALID_ARGUMENT",
+            format!("--frame '{spec}' matches {} frames", many.len()),
+            "use the frame name or its position (1 = first frame)",
+        )),
+    }
+}
+
 /// Snapshot ref (`@eN`) → (nodeId, frame_id) via RefCache + CDP.
 ///
 /// Returns both the resolved nodeId and the frame_id from RefCache,
@@ -558,8 +1216,11 @@ async fn resolve_xpath(
 ///
 /// Strategy: try backendNodeId directly (> 0), then fall back to
 /// `Accessibility.queryAXTree` with role + name.
+///
+/// Accepts `e5` or `@e5`. Refs come from the last
+/// [`crate::browser::observation::snapshot::capture`] of the tab.
 #[allow(clippy::too_many_arguments)]
-async fn resolve_ref(
+pub async fn resolve_ref(
     cdp: &CdpSession,
     target_id: &str,
     selector: &str,
@@ -619,12 +1280,30 @@ async fn resolve_ref(
         return Ok((node_id, frame_id));
     }
 
-    // Fallback: use role + name via Accessibility.queryAXTree
-    if !name.is_empty()
-        && let Some(node_id) =
-            resolve_by_ax_query(cdp, target_id, &role, &name, frame_id.as_deref()).await?
-    {
-        return Ok((node_id, frame_id));
+    // Fallback: the node was replaced (typically a framework re-render), so
+    // look for its role + name via Accessibility.queryAXTree. Only a unique
+    // match is taken; the first of several could be a different element.
+    if !name.is_empty() {
+        match resolve_by_ax_query(cdp, target_id, &role, &name, frame_id.as_deref()).await? {
+            AxMatch::One(node_id, new_backend_id) => {
+                // Rebind so later commands find the new node directly.
+                let mut reg = registry.lock().await;
+                let mut cache = reg.take_ref_cache(session_id, tab_id);
+                cache.rebind(ref_id, new_backend_id);
+                reg.put_ref_cache(session_id, tab_id, cache);
+                return Ok((node_id, frame_id));
+            }
+            AxMatch::Many(count) => {
+                return Err(ActionResult::fatal_with_hint(
+                    "REF_STALE",
+                    format!(
+                        "snapshot ref '{selector}' points at a removed element; {count} elements now match role={role}, name={name}"
+                    ),
+                    "run 'browser snapshot' again to pick the right one",
+                ));
+            }
+            AxMatch::None => {}
+        }
     }
 
     Err(ActionResult::fatal_with_hint(
@@ -634,7 +1313,9 @@ async fn resolve_ref(
     ))
 }
 
-/// backendNodeId → nodeId. Returns `Ok(None)` if stale (-32000).
+/// backendNodeId → nodeId. Returns `Ok(None)` if stale (-32000) or if the
+/// node is no longer in the document: a removed node stays resolvable while
+/// the page's JS still holds it, and acting on it would silently do nothing.
 /// Routes to the correct frame session for cross-origin iframes.
 async fn resolve_backend_node(
     cdp: &CdpSession,
@@ -669,6 +1350,24 @@ async fn resolve_backend_node(
         None => return Ok(None),
     };
 
+    let connected = execute_for_frame(
+        cdp,
+        target_id,
+        frame_id,
+        "Runtime.callFunctionOn",
+        json!({
+            "objectId": object_id,
+            "functionDeclaration": "function() { return this.isConnected; }",
+            "returnByValue": true,
+        }),
+    )
+    .await
+    .ok()
+    .and_then(|r| r.pointer("/result/result/value").and_then(|v| v.as_bool()));
+    if connected == Some(false) {
+        return Ok(None);
+    }
+
     let node_resp = execute_for_frame(
         cdp,
         target_id,
@@ -691,6 +1390,15 @@ async fn resolve_backend_node(
     }
 }
 
+/// What a role + name lookup found.
+enum AxMatch {
+    None,
+    /// Exactly one live element: (nodeId, backendNodeId).
+    One(i64, i64),
+    /// Several live elements; none is taken.
+    Many(usize),
+}
+
 /// Find an element by ARIA role + name via `Accessibility.queryAXTree`.
 /// Routes to the correct frame session for cross-origin iframes.
 async fn resolve_by_ax_query(
@@ -699,7 +1407,7 @@ async fn resolve_by_ax_query(
     role: &str,
     name: &str,
     frame_id: Option<&str>,
-) -> Result<Option<i64>, ActionResult> {
+) -> Result<AxMatch, ActionResult> {
     let resp = match execute_for_frame(
         cdp,
         target_id,
@@ -710,25 +1418,35 @@ async fn resolve_by_ax_query(
     .await
     {
         Ok(r) => r,
-        Err(_) => return Ok(None),
+        Err(_) => return Ok(AxMatch::None),
     };
 
     let nodes = resp.pointer("/result/nodes").and_then(|v| v.as_array());
     let nodes = match nodes {
         Some(n) if !n.is_empty() => n,
-        _ => return Ok(None),
+        _ => return Ok(AxMatch::None),
     };
 
-    for node in nodes {
-        let bid = node["backendDOMNodeId"].as_i64().unwrap_or(0);
-        if bid > 0
-            && let Some(node_id) = resolve_backend_node(cdp, target_id, bid, frame_id).await?
-        {
-            return Ok(Some(node_id));
+    let mut backend_ids: Vec<i64> = nodes
+        .iter()
+        .filter_map(|n| n["backendDOMNodeId"].as_i64())
+        .filter(|&bid| bid > 0)
+        .collect();
+    backend_ids.dedup();
+    let mut found = None;
+    let mut count = 0;
+    for bid in backend_ids {
+        if let Some(node_id) = resolve_backend_node(cdp, target_id, bid, frame_id).await? {
+            count += 1;
+            found.get_or_insert((node_id, bid));
         }
     }
 
-    Ok(None)
+    Ok(match (count, found) {
+        (1, Some((node_id, bid))) => AxMatch::One(node_id, bid),
+        (0, _) | (_, None) => AxMatch::None,
+        (n, Some(_)) => AxMatch::Many(n),
+    })
 }
 
 // ── Target parsing (shared by click, fill, type) ──────────────────
@@ -779,3 +1497,209 @@ pub fn parse_target(input: &str) -> Result<ClickTarget, ActionResult> {
 
     Ok(ClickTarget::Coordinates(x, y))
 }
+
+// ── Point within an element (click/hover --position) ──────────────
+
+/// Where inside an element's border box to aim, as a fraction of its size
+/// plus a CSS-pixel offset: `x = left + width * fx + dx`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ElementPoint {
+    pub fx: f64,
+    pub fy: f64,
+    pub dx: f64,
+    pub dy: f64,
+}
+
+impl ElementPoint {
+    pub const CENTER: ElementPoint = ElementPoint {
+        fx: 0.5,
+        fy: 0.5,
+        dx: 0.0,
+        dy: 0.0,
+    };
+
+    /// Parse `center`, a corner (`top-left`, `top-right`, `bottom-left`,
+    /// `bottom-right`, each 1px inside the edge), `X,Y` pixels from the
+    /// top-left corner, or `X%,Y%` of the width and height.
+    pub fn parse(raw: &str) -> Result<Self, String> {
+        let corner = |fx: f64, fy: f64| ElementPoint {
+            fx,
+            fy,
+            dx: if fx == 0.0 { 1.0 } else { -1.0 },
+            dy: if fy == 0.0 { 1.0 } else { -1.0 },
+        };
+        let err = || {
+            format!(
+                "invalid position '{raw}', expected center, top-left, top-right, bottom-left, bottom-right, X,Y or X%,Y%"
+            )
+        };
+        match raw.trim().to_ascii_lowercase().as_str() {
+            "center" => return Ok(Self::CENTER),
+            "top-left" => return Ok(corner(0.0, 0.0)),
+            "top-right" => return Ok(corner(1.0, 0.0)),
+            "bottom-left" => return Ok(corner(0.0, 1.0)),
+            "bottom-right" => return Ok(corner(1.0, 1.0)),
+            _ => {}
+        }
+        let (x, y) = raw.split_once(',').ok_or_else(err)?;
+        let (x, y) = (x.trim(), y.trim());
+        match (x.strip_suffix('%'), y.strip_suffix('%')) {
+            (Some(px), Some(py)) => {
+                let fx: f64 = px.trim().parse().map_err(|_| err())?;
+                let fy: f64 = py.trim().parse().map_err(|_| err())?;
+                Ok(ElementPoint {
+                    fx: fx / 100.0,
+                    fy: fy / 100.0,
+                    dx: 0.0,
+                    dy: 0.0,
+                })
+            }
+            (None, None) => Ok(ElementPoint {
+                fx: 0.0,
+                fy: 0.0,
+                dx: x.parse().map_err(|_| err())?,
+                dy: y.parse().map_err(|_| err())?,
+            }),
+            _ => Err(err()),
+        }
+    }
+
+    pub fn is_center(&self) -> bool {
+        *self == Self::CENTER
+    }
+
+    /// The point for a box at `(left, top)` of `width` × `height`.
+    pub fn apply(&self, left: f64, top: f64, width: f64, height: f64) -> (f64, f64) {
+        (
+            left + width * self.fx + self.dx,
+            top + height * self.fy + self.dy,
+        )
+    }
+
+    /// Arguments for in-page JS that takes `(fx, fy, dx, dy)`.
+    pub fn js_args(&self) -> Value {
+        json!([
+            { "value": self.fx },
+            { "value": self.fy },
+            { "value": self.dx },
+            { "value": self.dy },
+        ])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn element_point_parses_names_pixels_and_percentages() {
+        assert!(ElementPoint::parse("center").unwrap().is_center());
+        assert_eq!(
+            ElementPoint::parse("Top-Left")
+                .unwrap()
+                .apply(10.0, 20.0, 100.0, 50.0),
+            (11.0, 21.0)
+        );
+        assert_eq!(
+            ElementPoint::parse("bottom-right")
+                .unwrap()
+                .apply(10.0, 20.0, 100.0, 50.0),
+            (109.0, 69.0)
+        );
+        assert_eq!(
+            ElementPoint::parse("30, 5")
+                .unwrap()
+                .apply(10.0, 20.0, 100.0, 50.0),
+            (40.0, 25.0)
+        );
+        assert_eq!(
+            ElementPoint::parse("25%,100%")
+                .unwrap()
+                .apply(10.0, 20.0, 100.0, 50.0),
+            (35.0, 70.0)
+        );
+    }
+
+    #[test]
+    fn element_point_rejects_mixed_and_malformed() {
+        assert!(ElementPoint::parse("25%,5").is_err());
+        assert!(ElementPoint::parse("middle").is_err());
+        assert!(ElementPoint::parse("1,x").is_err());
+    }
+
+    #[test]
+    fn engine_selector_parses_text_and_role_forms() {
+        assert_eq!(
+            EngineSelector::parse("text=Sign in"),
+            Ok(EngineSelector::Text {
+                text: "Sign in".to_string(),
+                exact: false
+            })
+        );
+        assert_eq!(
+            EngineSelector::parse("text=\"Sign in\""),
+            Ok(EngineSelector::Text {
+                text: "Sign in".to_string(),
+                exact: true
+            })
+        );
+        assert_eq!(
+            EngineSelector::parse("role=button[name='Checkout']"),
+            Ok(EngineSelector::Role {
+                role: "button".to_string(),
+                name: Some("Checkout".to_string())
+            })
+        );
+        assert_eq!(
+            EngineSelector::parse("role=link"),
+            Ok(EngineSelector::Role {
+                role: "link".to_string(),
+                name: None
+            })
+        );
+        assert!(EngineSelector::parse("text=").is_err());
+        assert!(EngineSelector::parse("role=button[label=x]").is_err());
+        assert!(EngineSelector::parse("role=but ton").is_err());
+    }
+
+    #[test]
+    fn collect_frames_flattens_nested_frames_in_order() {
+        let tree = json!([
+            { "frame": { "id": "F1", "name": "nav", "url": "https://a.test/nav" },
+              "childFrames": [
+                { "frame": { "id": "F1a", "name": "", "url": "https://ads.test/x" } }
+              ] },
+            { "frame": { "id": "F2", "name": "", "url": "https://pay.test/card" } }
+        ]);
+        let mut frames = Vec::new();
+        collect_frames(&tree, &mut frames);
+        let ids: Vec<&str> = frames.iter().map(|f| f.frame_id.as_str()).collect();
+        assert_eq!(ids, ["F1", "F1a", "F2"]);
+    }
+
+    #[test]
+    fn pick_frame_by_position_name_and_url() {
+        let frame = |id: &str, name: &str, url: &str| FrameInfo {
+            frame_id: id.to_string(),
+            name: name.to_string(),
+            url: url.to_string(),
+        };
+        let frames = [
+            frame("F1", "nav", "https://a.test/nav"),
+            frame("F2", "", "https://pay.test/card"),
+            frame("F3", "", "https://a.test/footer"),
+        ];
+        assert_eq!(pick_frame(&frames, "2").unwrap().frame_id, "F2");
+        assert_eq!(pick_frame(&frames, "nav").unwrap().frame_id, "F1");
+        assert_eq!(pick_frame(&frames, "pay.test").unwrap().frame_id, "F2");
+
+        let code = |r: Result<&FrameInfo, ActionResult>| match r {
+            Err(ActionResult::Fatal { code, .. }) => code,
+            other => panic!("expected an error, got {other:?}"),
+        };
+        assert_eq!(code(pick_frame(&frames, "0")), "FRAME_NOT_FOUND");
+        assert_eq!(code(pick_frame(&frames, "4")), "FRAME_NOT_FOUND");
+        assert_eq!(code(pick_frame(&frames, "nope")), "FRAME_NOT_FOUND");
+        assert_eq!(code(pick_frame(&frames, "a.test")), "INVALID_ARGUMENT");
+    }
+}
@@ -0,0 +1,159 @@
+use clap::Args;
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+
+use crate::action_result::ActionResult;
+use crate::daemon::cdp_session::get_cdp_and_target;
+use crate::daemon::registry::SharedRegistry;
+use crate::output::ResponseContext;
+
+use super::{page_origin, remote_value};
+
+pub const DEFAULT_LIMIT: u32 = 100;
+
+/// Dump the entries of an IndexedDB object store
+#[derive(Args, Debug, Clone, Serialize, Deserialize)]
+#[command(after_help = "\
+Examples:
+  actionbook browser indexeddb dump app-db todos --session s1 --tab t1
+  actionbook browser indexeddb dump app-db todos --index by_date --limit 10 --session s1 --tab t1
+  actionbook browser indexeddb dump keyval-store keyval --skip 100 --session s1 --tab t1 --json
+
+Entries come back in key order (index key order with --index), as
+{key, primary_key, value}. Values are converted to JSON: dates become ISO
+strings, Maps and Sets arrays, and binary data and Blobs a short description.
+`has_more` tells whether another --skip page exists.")]
+pub struct Cmd {
+    /// Database name (see `indexeddb list`)
+    pub database: String,
+    /// Object store name
+    pub store: String,
+    /// Read through this index instead of the primary key
+    #[arg(long)]
+    #[serde(default)]
+    pub index: Option<String>,
+    /// Entries to skip
+    #[arg(long, default_value_t = 0)]
+    #[serde(default)]
+    pub skip: u32,
+    /// Maximum entries to return
+    #[arg(long, default_value_t = DEFAULT_LIMIT, value_parser = clap::value_parser!(u32).range(1..))]
+    #[serde(default = "default_limit")]
+    pub limit: u32,
+    /// Session ID
+    #[arg(long)]
+    #[serde(rename = "session_id")]
+    pub session: String,
+    /// Tab ID
+    #[arg(long)]
+    #[serde(rename = "tab_id")]
+    pub tab: String,
+}
+
+fn default_limit() -> u32 {
+    DEFAULT_LIMIT
+}
+
+pub const COMMAND_NAME: &str = "browser indexeddb dump";
+
+pub fn context(cmd: &Cmd, result: &ActionResult) -> Option<ResponseContext> {
+    if let ActionResult::Fatal { code, .. } = result {
+        if code == "SESSION_NOT_FOUND" {
+            return None;
+        }
+        if code == "TAB_NOT_FOUND" {
+            return Some(ResponseContext {
+                session_id: cmd.session.clone(),
+                tab_id: None,
+                window_id: None,
+                url: None,
+                title: None,
+            });
+        }
+    }
+    let url = if let ActionResult::Ok { data } = result {
+        data.get("__url")
+            .and_then(|v| v.as_str())
+            .filter(|s| !s.is_empty())
+            .map(String::from)
+    } else {
+        None
+    };
+    Some(ResponseContext {
+        session_id: cmd.session.clone(),
+        tab_id: Some(cmd.tab.clone()),
+        window_id: None,
+        url,
+        title: None,
+    })
+}
+
+pub async fn execute(cmd: &Cmd, registry: &SharedRegistry) -> ActionResult {
+    let (cdp, target_id) = match get_cdp_and_target(registry, &cmd.session, &cmd.tab).await {
+        Ok(v) => v,
+        Err(e) => return e,
+    };
+    let origin = match page_origin(&cdp, &target_id).await {
+        Ok(o) => o,
+        Err(e) => return e,
+    };
+
+    let resp = match cdp
+        .execute_on_tab(
+            &target_id,
+            "IndexedDB.requestData",
+            json!({
+                "securityOrigin": origin,
+                "databaseName": cmd.database,
+                "objectStoreName": cmd.store,
+                "indexName": cmd.index.clone().unwrap_or_default(),
+                "skipCount": cmd.skip,
+                "pageSize": cmd.limit,
+            }),
+        )
+        .await
+    {
+        Ok(v) => v,
+        Err(e) => {
+            // Chrome answers an unknown database or store with a generic
+            // "Could not get data" / "Could not open database" error.
+            return ActionResult::fatal_with_hint(
+                "CDP_ERROR",
+                format!("failed to read {}/{}: {e}", cmd.database, cmd.store),
+                "run `actionbook browser indexeddb list` to see databases and stores",
+            );
+        }
+    };
+
+    let mut entries = Vec::new();
+    for entry in resp
+        .pointer("/result/objectStoreDataEntries")
+        .and_then(|v| v.as_array())
+        .into_iter()
+        .flatten()
+    {
+        entries.push(json!({
+            "key": remote_value(&cdp, &target_id, &entry["key"]).await,
+            "primary_key": remote_value(&cdp, &target_id, &entry["primaryKey"]).await,
+            "value": remote_value(&cdp, &target_id, &entry["value"]).await,
+        }));
+    }
+    let has_more = resp
+        .pointer("/result/hasMore")
+        .and_then(|v| v.as_bool())
+        .unwrap_or(false);
+
+    let url = crate::browser::navigation::get_tab_url(&cdp, &target_id).await;
+
+    ActionResult::ok(json!({
+        "origin": origin,
+        "database": cmd.database,
+        "store": cmd.store,
+        "index": cmd.index,
+        "skip": cmd.skip,
+        "count": entries.len(),
+        "has_more": has_more,
+        "entries": entries,
+        "__url": url,
+    }))
+}
@@ -0,0 +1,164 @@
+use clap::Args;
+use serde::{Deserialize, Serialize};
+use serde_json::{Value, json};
+
+use crate::action_result::ActionResult;
+use crate::daemon::cdp_session::get_cdp_and_target;
+use crate::daemon::registry::SharedRegistry;
+use crate::output::ResponseContext;
+
+use super::{key_path, page_origin};
+
+/// List the IndexedDB databases and object stores of the tab's origin
+#[derive(Args, Debug, Clone, Serialize, Deserialize)]
+#[command(after_help = "\
+Examples:
+  actionbook browser indexeddb list --session s1 --tab t1
+  actionbook browser indexeddb list --session s1 --tab t1 --json
+
+Lists every database of the page's origin with its version, and each object
+store with its key path, indexes and number of entries.")]
+pub struct Cmd {
+    /// Session ID
+    #[arg(long)]
+    #[serde(rename = "session_id")]
+    pub session: String,
+    /// Tab ID
+    #[arg(long)]
+    #[serde(rename = "tab_id")]
+    pub tab: String,
+}
+
+pub const COMMAND_NAME: &str = "browser indexeddb list";
+
+pub fn context(cmd: &Cmd, result: &ActionResult) -> Option<ResponseContext> {
+    if let ActionResult::Fatal { code, .. } = result {
+        if code == "SESSION_NOT_FOUND" {
+            return None;
+        }
+        if code == "TAB_NOT_FOUND" {
+            return Some(ResponseContext {
+                session_id: cmd.session.clone(),
+                tab_id: None,
+                window_id: None,
+                url: None,
+                title: None,
+            });
+        }
+    }
+    let url = if let ActionResult::Ok { data } = result {
+        data.get("__url")
+            .and_then(|v| v.as_str())
+            .filter(|s| !s.is_empty())
+            .map(String::from)
+    } else {
+        None
+    };
+    Some(ResponseContext {
+        session_id: cmd.session.clone(),
+        tab_id: Some(cmd.tab.clone()),
+        window_id: None,
+        url,
+        title: None,
+    })
+}
+
+pub async fn execute(cmd: &Cmd, registry: &SharedRegistry) -> ActionResult {
+    let (cdp, target_id) = match get_cdp_and_target(registry, &cmd.session, &cmd.tab).await {
+        Ok(v) => v,
+        Err(e) => return e,
+    };
+    let origin = match page_origin(&cdp, &target_id).await {
+        Ok(o) => o,
+        Err(e) => return e,
+    };
+    let call = |method: &'static str, params: Value| {
+        let cdp = cdp.clone();
+        let target_id = target_id.clone();
+        async move {
+            cdp.execute_on_tab(&target_id, method, params)
+                .await
+                .map_err(|e| ActionResult::fatal("CDP_ERROR", e.to_string()))
+        }
+    };
+
+    let names = match call(
+        "IndexedDB.requestDatabaseNames",
+        json!({ "securityOrigin": origin }),
+    )
+    .await
+    {
+        Ok(r) => r
+            .pointer("/result/databaseNames")
+            .and_then(|v| v.as_array())
+            .cloned()
+            .unwrap_or_default(),
+        Err(e) => return e,
+    };
+
+    let mut databases = Vec::with_capacity(names.len());
+    for name in names.iter().filter_map(|n| n.as_str()) {
+        let db = match call(
+            "IndexedDB.requestDatabase",
+            json!({ "securityOrigin": origin, "databaseName": name }),
+        )
+        .await
+        {
+            Ok(r) => r
+                .pointer("/result/databaseWithObjectStores")
+                .cloned()
+                .unwrap_or(Value::Null),
+            Err(e) => return e,
+        };
+        let mut stores = Vec::new();
+        for store in db["objectStores"].as_array().into_iter().flatten() {
+            let store_name = store["name"].as_str().unwrap_or_default();
+            // A store that cannot be counted is still listed.
+            let entries = call(
+                "IndexedDB.getMetadata",
+                json!({
+                    "securityOrigin": origin,
+                    "databaseName": name,
+                    "objectStoreName": store_name,
+                }),
+            )
+            .await
+            .ok()
+            .and_then(|r| r.pointer("/result/entriesCount").and_then(|v| v.as_f64()))
+            .map(|n| n as u64);
+            let indexes: Vec<Value> = store["indexes"]
+                .as_array()
+                .into_iter()
+                .flatten()
+                .map(|i| {
+                    json!({
+                        "name": i["name"],
+                        "key_path": key_path(&i["keyPath"]),
+                        "unique": i["unique"].as_bool().unwrap_or(false),
+                        "multi_entry": i["multiEntry"].as_bool().unwrap_or(false),
+                    })
+                })
+                .collect();
+            stores.push(json!({
+                "name": store_name,
+                "key_path": key_path(&store["keyPath"]),
+                "auto_increment": store["autoIncrement"].as_bool().unwrap_or(false),
+                "indexes": indexes,
+                "entries": entries,
+            }));
+        }
+        databases.push(json!({
+            "name": name,
+            "version": db["version"],
+            "stores": stores,
+        }));
+    }
+
+    let url = crate::browser::navigation::get_tab_url(&cdp, &target_id).await;
+
+    ActionResult::ok(json!({
+        "origin": origin,
+        "databases": databases,
+        "__url": url,
+    }))
+}
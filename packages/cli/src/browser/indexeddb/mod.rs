@@ -0,0 +1,115 @@
+pub mod dump;
+pub mod list;
+
+use serde_json::{Value, json};
+
+use crate::action_result::ActionResult;
+use crate::daemon::cdp_session::CdpSession;
+
+/// Origin of the tab's page, which scopes its IndexedDB databases.
+pub(crate) async fn page_origin(cdp: &CdpSession, target_id: &str) -> Result<String, ActionResult> {
+    let resp = cdp
+        .execute_on_tab(
+            target_id,
+            "Runtime.evaluate",
+            json!({ "expression": "location.origin", "returnByValue": true }),
+        )
+        .await
+        .map_err(|e| ActionResult::fatal("CDP_ERROR", e.to_string()))?;
+    match resp
+        .pointer("/result/result/value")
+        .and_then(|v| v.as_str())
+    {
+        Some(origin) if origin != "null" && !origin.is_empty() => Ok(origin.to_string()),
+        _ => Err(ActionResult::fatal_with_hint(
+            "UNSUPPORTED_OPERATION",
+            "the page has no origin with IndexedDB storage",
+            "navigate the tab to an http(s) page first",
+        )),
+    }
+}
+
+/// A CDP `IndexedDB.KeyPath` as JSON: null, a string, or an array of strings.
+pub(crate) fn key_path(kp: &Value) -> Value {
+    match kp.get("type").and_then(|v| v.as_str()) {
+        Some("string") => kp.get("string").cloned().unwrap_or(Value::Null),
+        Some("array") => kp.get("array").cloned().unwrap_or(json!([])),
+        _ => Value::Null,
+    }
+}
+
+/// Turns a structured-clone value into JSON. Dates become ISO strings, Maps
+/// and Sets arrays, and binary data and Blobs a short description.
+const TO_JSON_FN: &str = r#"function() {
+  const seen = new WeakSet();
+  return JSON.parse(JSON.stringify(this, function(k, v) {
+    const raw = this[k];
+    if (typeof raw === 'bigint') return raw.toString();
+    if (raw instanceof Date) return raw.toISOString();
+    if (raw instanceof Map) return Array.from(raw.entries());
+    if (raw instanceof Set) return Array.from(raw.values());
+    if (raw instanceof ArrayBuffer) return { type: 'ArrayBuffer', byteLength: raw.byteLength };
+    if (ArrayBuffer.isView(raw)) return { type: raw.constructor.name, byteLength: raw.byteLength };
+    if (typeof Blob !== 'undefined' && raw instanceof Blob) return { type: 'Blob', size: raw.size, mime: raw.type };
+    if (v && typeof v === 'object') {
+      if (seen.has(v)) return '[Circular]';
+      seen.add(v);
+    }
+    return v;
+  }) ?? null);
+}"#;
+
+/// The JSON value of a CDP `Runtime.RemoteObject` returned by the IndexedDB
+/// domain. Objects are read back through `Runtime.callFunctionOn` and then
+/// released.
+pub(crate) async fn remote_value(cdp: &CdpSession, target_id: &str, obj: &Value) -> Value {
+    if let Some(v) = obj.get("value") {
+        return v.clone();
+    }
+    if let Some(v) = obj.get("unserializableValue") {
+        return v.clone();
+    }
+    let Some(object_id) = obj.get("objectId").and_then(|v| v.as_str()) else {
+        return Value::Null;
+    };
+    let resp = cdp
+        .execute_on_tab(
+            target_id,
+            "Runtime.callFunctionOn",
+            json!({
+                "objectId": object_id,
+                "functionDeclaration": TO_JSON_FN,
+                "returnByValue": true,
+            }),
+        )
+        .await;
+    let _ = cdp
+        .execute_on_tab(
+            target_id,
+            "Runtime.releaseObject",
+            json!({ "objectId": object_id }),
+        )
+        .await;
+    match resp {
+        Ok(r) if r.pointer("/result/exceptionDetails").is_none() => r
+            .pointer("/result/result/value")
+            .cloned()
+            .unwrap_or(Value::Null),
+        _ => obj.get("description").cloned().unwrap_or(Value::Null),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn key_path_flattens_cdp_shape() {
+        assert_eq!(key_path(&json!({ "type": "null" })), Value::Null);
+        assert_eq!(key_path(&json!({ "type": "string", "string": "id" })), "id");
+        assert_eq!(
+            key_path(&json!({ "type": "array", "array": ["a", "b"] })),
+            json!(["a", "b"])
+        );
+    }
+}
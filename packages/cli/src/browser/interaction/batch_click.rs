@@ -4,6 +4,7 @@ use serde_json::json;
 
 use crate::action_result::ActionResult;
 use crate::browser::element::TabContext;
+use crate::daemon::guardrails::{self, SensitivePolicy};
 use crate::daemon::registry::SharedRegistry;
 use crate::output::ResponseContext;
 
@@ -22,7 +23,8 @@ Refs come from snapshot output (e.g. [ref=e5]).
 
 Unlike 'click', batch-click skips per-click state detection (URL/focus
 changes) for maximum throughput. Use 'click' when you need to know
-whether a click triggered navigation.")]
+whether a click triggered navigation.
+Sensitive-action guardrails apply as for 'click' (see --confirm-sensitive).")]
 pub struct Cmd {
     /// Snapshot refs or CSS selectors to click (2 or more)
     #[arg(num_args(2..))]
@@ -35,6 +37,10 @@ pub struct Cmd {
     #[arg(long)]
     #[serde(rename = "tab_id")]
     pub tab: String,
+    /// Allow clicks that the sensitive-action guardrail would refuse
+    #[arg(long)]
+    #[serde(default)]
+    pub confirm_sensitive: bool,
 }
 
 pub const COMMAND_NAME: &str = "browser batch-click";
@@ -62,6 +68,11 @@ pub async fn execute(cmd: &Cmd, registry: &SharedRegistry) -> ActionResult {
         );
     }
 
+    let policy = match SensitivePolicy::load() {
+        Ok(p) => p,
+        Err(e) => return e,
+    };
+
     let mut ctx = match TabContext::new(registry, &cmd.session, &cmd.tab).await {
         Ok(v) => v,
         Err(e) => return e,
@@ -69,10 +80,13 @@ pub async fn execute(cmd: &Cmd, registry: &SharedRegistry) -> ActionResult {
 
     let mut results = Vec::new();
     for (i, selector) in cmd.selectors.iter().enumerate() {
-        match click::execute_fast_click(selector, &mut ctx).await {
+        match click::execute_fast_click(selector, &mut ctx, policy.as_ref(), cmd.confirm_sensitive)
+            .await
+        {
             Ok(()) => {
                 results.push(json!({ "index": i, "selector": selector }));
             }
+            Err(err) if guardrails::is_confirmation_required(&err) => return err,
             Err(_) => {
                 return ActionResult::fatal_with_details(
                     "BATCH_CLICK_ERROR",
@@ -0,0 +1,524 @@
+//! `browser canvas click-color` / `browser canvas draw` commands.
+//!
+//! Canvas UIs (signature pads, charts, simple games) have no DOM to target,
+//! so these work in pixels: `click-color` reads the bitmap with
+//! `getImageData` to find where a colour is drawn, and `draw` replays a
+//! pointer path over the canvas.
+
+use std::path::PathBuf;
+
+use clap::Args;
+use serde::{Deserialize, Serialize};
+use serde_json::{Value, json};
+
+use crate::action_result::ActionResult;
+use crate::browser::element::TabContext;
+use crate::browser::navigation;
+use crate::daemon::cdp_session::cdp_error_to_result;
+use crate::daemon::registry::SharedRegistry;
+use crate::output::ResponseContext;
+
+// ── click-color ───────────────────────────────────────────────────────────────
+
+/// Click where a canvas shows a colour
+#[derive(Args, Debug, Clone, Serialize, Deserialize)]
+#[command(after_help = "\
+Examples:
+  actionbook browser canvas click-color \"#chart\" \"#ff0000\" --session s1 --tab t1
+  actionbook browser canvas click-color canvas \"rgb(0, 128, 255)\" --tolerance 24 --session s1 --tab t1
+
+Accepts a CSS selector, XPath, or snapshot ref (@eN) for a <canvas>.
+Colours are #rgb, #rrggbb or rgb(r, g, b). A pixel matches when every channel
+is within --tolerance of the colour (transparent pixels never match). The
+click lands on the matching pixel nearest the centre of all matches.
+Canvases tainted by cross-origin images cannot be read.")]
+pub struct ClickColorCmd {
+    /// Selector for the canvas (CSS, XPath, or @ref)
+    pub selector: String,
+    /// Colour to find
+    pub color: String,
+    /// Largest per-channel difference that still matches (0-255)
+    #[arg(long, default_value_t = 8)]
+    #[serde(default = "default_tolerance")]
+    pub tolerance: u8,
+    /// Session ID
+    #[arg(long)]
+    #[serde(rename = "session_id")]
+    pub session: String,
+    /// Tab ID
+    #[arg(long)]
+    #[serde(rename = "tab_id")]
+    pub tab: String,
+}
+
+fn default_tolerance() -> u8 {
+    8
+}
+
+pub const CLICK_COLOR_COMMAND_NAME: &str = "browser canvas click-color";
+
+fn tab_context(session: &str, tab: &str, result: &ActionResult) -> Option<ResponseContext> {
+    if let ActionResult::Fatal { code, .. } = result
+        && code == "SESSION_NOT_FOUND"
+    {
+        return None;
+    }
+    let (url, title) = match result {
+        ActionResult::Ok { data } => (
+            data.get("post_url")
+                .and_then(|v| v.as_str())
+                .filter(|s| !s.is_empty())
+                .map(String::from),
+            data.get("post_title")
+                .and_then(|v| v.as_str())
+                .filter(|s| !s.is_empty())
+                .map(String::from),
+        ),
+        _ => (None, None),
+    };
+    Some(ResponseContext {
+        session_id: session.to_string(),
+        tab_id: Some(tab.to_string()),
+        window_id: None,
+        url,
+        title,
+    })
+}
+
+pub fn click_color_context(cmd: &ClickColorCmd, result: &ActionResult) -> Option<ResponseContext> {
+    tab_context(&cmd.session, &cmd.tab, result)
+}
+
+/// Parse `#rgb`, `#rrggbb` or `rgb(r, g, b)`.
+fn parse_color(raw: &str) -> Option<[u8; 3]> {
+    let s = raw.trim();
+    if let Some(hex) = s.strip_prefix('#') {
+        let digits: Vec<u8> = hex
+            .chars()
+            .map(|c| c.to_digit(16).map(|d| d as u8))
+            .collect::<Option<_>>()?;
+        return match digits.as_slice() {
+            [r, g, b] => Some([r * 17, g * 17, b * 17]),
+            [r1, r2, g1, g2, b1, b2] => Some([r1 * 16 + r2, g1 * 16 + g2, b1 * 16 + b2]),
+            _ => None,
+        };
+    }
+    let inner = s
+        .strip_prefix("rgb(")
+        .or_else(|| s.strip_prefix("rgba("))?
+        .strip_suffix(')')?;
+    let parts: Vec<u8> = inner
+        .split(',')
+        .take(3)
+        .map(|p| p.trim().parse().ok())
+        .collect::<Option<_>>()?;
+    match parts.as_slice() {
+        [r, g, b] => Some([*r, *g, *b]),
+        _ => None,
+    }
+}
+
+/// Find the matching pixel nearest the centroid of all matches and map it to
+/// viewport coordinates (canvas bitmap size may differ from its CSS box).
+const FIND_COLOR_JS: &str = r#"function(r, g, b, tol) {
+    if (!(this instanceof HTMLCanvasElement)) {
+        return JSON.stringify({ error: 'not_canvas', tag: this.tagName.toLowerCase() });
+    }
+    const w = this.width, h = this.height;
+    if (!w || !h) return JSON.stringify({ error: 'empty' });
+    let data;
+    try {
+        // Copy onto a 2D canvas so WebGL canvases can be read too.
+        const copy = document.createElement('canvas');
+        copy.width = w;
+        copy.height = h;
+        const c2d = copy.getContext('2d');
+        c2d.drawImage(this, 0, 0);
+        data = c2d.getImageData(0, 0, w, h).data;
+    } catch (e) {
+        return JSON.stringify({ error: 'unreadable', message: String(e && e.message || e) });
+    }
+    const hit = (i) => data[i + 3] > 0 && Math.abs(data[i] - r) <= tol
+        && Math.abs(data[i + 1] - g) <= tol && Math.abs(data[i + 2] - b) <= tol;
+    let n = 0, sx = 0, sy = 0;
+    for (let y = 0; y < h; y++) for (let x = 0; x < w; x++) {
+        if (hit((y * w + x) * 4)) { n++; sx += x; sy += y; }
+    }
+    if (!n) return JSON.stringify({ error: 'no_match', width: w, height: h });
+    const cx = sx / n, cy = sy / n;
+    let best = null, bestD = Infinity;
+    for (let y = 0; y < h; y++) for (let x = 0; x < w; x++) {
+        if (!hit((y * w + x) * 4)) continue;
+        const d = (x - cx) ** 2 + (y - cy) ** 2;
+        if (d < bestD) { bestD = d; best = { x, y }; }
+    }
+    const rect = this.getBoundingClientRect();
+    const cs = getComputedStyle(this);
+    const px = (v) => parseFloat(v) || 0;
+    const left = rect.left + px(cs.borderLeftWidth) + px(cs.paddingLeft);
+    const top = rect.top + px(cs.borderTopWidth) + px(cs.paddingTop);
+    const cw = rect.width - px(cs.borderLeftWidth) - px(cs.borderRightWidth) - px(cs.paddingLeft) - px(cs.paddingRight);
+    const ch = rect.height - px(cs.borderTopWidth) - px(cs.borderBottomWidth) - px(cs.paddingTop) - px(cs.paddingBottom);
+    return JSON.stringify({
+        matches: n,
+        pixel: best,
+        x: left + (best.x + 0.5) * cw / w,
+        y: top + (best.y + 0.5) * ch / h,
+    });
+}"#;
+
+pub async fn execute_click_color(cmd: &ClickColorCmd, registry: &SharedRegistry) -> ActionResult {
+    let Some([r, g, b]) = parse_color(&cmd.color) else {
+        return ActionResult::fatal_with_hint(
+            "INVALID_ARGUMENT",
+            format!("invalid colour '{}'", cmd.color),
+            "use #rgb, #rrggbb or rgb(r, g, b)",
+        );
+    };
+
+    let mut ctx = match TabContext::new(registry, &cmd.session, &cmd.tab).await {
+        Ok(v) => v,
+        Err(e) => return e,
+    };
+    let (node_id, object_id) = match ctx.resolve_object(&cmd.selector).await {
+        Ok(v) => v,
+        Err(e) => return e,
+    };
+    if let Err(e) = ctx.scroll_into_view(node_id).await {
+        return e;
+    }
+
+    let found = match call_on(
+        &ctx,
+        &object_id,
+        FIND_COLOR_JS,
+        json!([{ "value": r }, { "value": g }, { "value": b }, { "value": cmd.tolerance }]),
+    )
+    .await
+    {
+        Ok(v) => v,
+        Err(e) => return e,
+    };
+    match found.get("error").and_then(|v| v.as_str()) {
+        Some("not_canvas") => {
+            return ActionResult::fatal(
+                "INVALID_ARGUMENT",
+                format!(
+                    "'{}' is a <{}>, not a <canvas>",
+                    cmd.selector,
+                    found["tag"].as_str().unwrap_or("?")
+                ),
+            );
+        }
+        Some("empty") => {
+            return ActionResult::fatal(
+                "INVALID_ARGUMENT",
+                format!("canvas '{}' has no pixels", cmd.selector),
+            );
+        }
+        Some("unreadable") => {
+            return ActionResult::fatal_with_hint(
+                "CANVAS_UNREADABLE",
+                format!(
+                    "canvas '{}' cannot be read: {}",
+                    cmd.selector,
+                    found["message"].as_str().unwrap_or("")
+                ),
+                "the canvas is tainted by cross-origin content",
+            );
+        }
+        Some(_) => {
+            return ActionResult::fatal_with_hint(
+                "COLOR_NOT_FOUND",
+                format!("no pixel in '{}' matches {}", cmd.selector, cmd.color),
+                "raise --tolerance, or check the colour with a screenshot",
+            );
+        }
+        None => {}
+    }
+
+    let (x, y) = (
+        found["x"].as_f64().unwrap_or(0.0),
+        found["y"].as_f64().unwrap_or(0.0),
+    );
+    if let Err(e) = mouse_event(&ctx, "mouseMoved", x, y, 0).await {
+        return e;
+    }
+    for event_type in ["mousePressed", "mouseReleased"] {
+        if let Err(e) = mouse_event(&ctx, event_type, x, y, 1).await {
+            return e;
+        }
+    }
+    {
+        let mut reg = ctx.registry().lock().await;
+        reg.set_cursor_position(ctx.session_id(), ctx.tab_id(), x, y);
+    }
+
+    let url = navigation::get_tab_url(&ctx.cdp, &ctx.target_id).await;
+    let title = navigation::get_tab_title(&ctx.cdp, &ctx.target_id).await;
+
+    ActionResult::ok(json!({
+        "action": "canvas click-color",
+        "target": { "selector": cmd.selector },
+        "color": format!("#{r:02x}{g:02x}{b:02x}"),
+        "x": x,
+        "y": y,
+        "pixel": found["pixel"],
+        "matches": found["matches"],
+        "post_url": url,
+        "post_title": title,
+    }))
+}
+
+// ── draw ──────────────────────────────────────────────────────────────────────
+
+/// Draw a pointer path over a canvas
+#[derive(Args, Debug, Clone, Serialize, Deserialize)]
+#[command(after_help = "\
+Examples:
+  actionbook browser canvas draw \"#signature\" --path points.json --session s1 --tab t1
+
+The --path file holds points in CSS pixels from the canvas's top-left corner,
+either one stroke or a list of strokes; a point is [x, y] or {\"x\": .., \"y\": ..}:
+  [[10, 10], [60, 40], [120, 15]]
+  [[[10, 10], [60, 40]], [[20, 60], [100, 60]]]
+Each stroke is a mouse press at its first point, moves through the rest, and
+a release at its last point.")]
+pub struct DrawCmd {
+    /// Selector for the canvas (CSS, XPath, or @ref)
+    pub selector: String,
+    /// JSON file with the points to draw through
+    #[arg(long)]
+    pub path: String,
+    /// Session ID
+    #[arg(long)]
+    #[serde(rename = "session_id")]
+    pub session: String,
+    /// Tab ID
+    #[arg(long)]
+    #[serde(rename = "tab_id")]
+    pub tab: String,
+}
+
+pub const DRAW_COMMAND_NAME: &str = "browser canvas draw";
+
+pub fn draw_context(cmd: &DrawCmd, result: &ActionResult) -> Option<ResponseContext> {
+    tab_context(&cmd.session, &cmd.tab, result)
+}
+
+type Stroke = Vec<(f64, f64)>;
+
+fn parse_point(v: &Value) -> Option<(f64, f64)> {
+    match v {
+        Value::Array(xy) if xy.len() == 2 => Some((xy[0].as_f64()?, xy[1].as_f64()?)),
+        Value::Object(o) => Some((o.get("x")?.as_f64()?, o.get("y")?.as_f64()?)),
+        _ => None,
+    }
+}
+
+/// Read one stroke (a list of points) or a list of strokes.
+fn parse_strokes(v: &Value) -> Result<Vec<Stroke>, String> {
+    let items = v
+        .as_array()
+        .filter(|a| !a.is_empty())
+        .ok_or("expected a non-empty array of points or strokes")?;
+    let is_point = |p: &Value| parse_point(p).is_some();
+    if items.iter().all(is_point) {
+        return Ok(vec![items.iter().filter_map(parse_point).collect()]);
+    }
+    items
+        .iter()
+        .enumerate()
+        .map(|(i, stroke)| {
+            let points = stroke
+                .as_array()
+                .filter(|a| !a.is_empty())
+                .ok_or(format!("stroke {i} is not a non-empty array of points"))?;
+            points
+                .iter()
+                .enumerate()
+                .map(|(j, p)| parse_point(p).ok_or(format!("stroke {i} point {j} is not [x, y]")))
+                .collect()
+        })
+        .collect()
+}
+
+/// Top-left of the canvas content box in viewport coordinates.
+const ORIGIN_JS: &str = r#"function() {
+    const rect = this.getBoundingClientRect();
+    const cs = getComputedStyle(this);
+    const px = (v) => parseFloat(v) || 0;
+    return JSON.stringify({
+        canvas: this instanceof HTMLCanvasElement,
+        tag: this.tagName.toLowerCase(),
+        left: rect.left + px(cs.borderLeftWidth) + px(cs.paddingLeft),
+        top: rect.top + px(cs.borderTopWidth) + px(cs.paddingTop),
+    });
+}"#;
+
+pub async fn execute_draw(cmd: &DrawCmd, registry: &SharedRegistry) -> ActionResult {
+    let path = PathBuf::from(&cmd.path);
+    let raw = match std::fs::read_to_string(&path) {
+        Ok(s) => s,
+        Err(e) => {
+            return ActionResult::fatal(
+                "INVALID_ARGUMENT",
+                format!("cannot read {}: {e}", path.display()),
+            );
+        }
+    };
+    let strokes = match serde_json::from_str::<Value>(&raw)
+        .map_err(|e| e.to_string())
+        .and_then(|v| parse_strokes(&v))
+    {
+        Ok(s) => s,
+        Err(e) => {
+            return ActionResult::fatal(
+                "INVALID_ARGUMENT",
+                format!("invalid --path {}: {e}", path.display()),
+            );
+        }
+    };
+
+    let mut ctx = match TabContext::new(registry, &cmd.session, &cmd.tab).await {
+        Ok(v) => v,
+        Err(e) => return e,
+    };
+    let (node_id, object_id) = match ctx.resolve_object(&cmd.selector).await {
+        Ok(v) => v,
+        Err(e) => return e,
+    };
+    if let Err(e) = ctx.scroll_into_view(node_id).await {
+        return e;
+    }
+    let origin = match call_on(&ctx, &object_id, ORIGIN_JS, json!([])).await {
+        Ok(v) => v,
+        Err(e) => return e,
+    };
+    if origin["canvas"].as_bool() != Some(true) {
+        return ActionResult::fatal(
+            "INVALID_ARGUMENT",
+            format!(
+                "'{}' is a <{}>, not a <canvas>",
+                cmd.selector,
+                origin["tag"].as_str().unwrap_or("?")
+            ),
+        );
+    }
+    let (left, top) = (
+        origin["left"].as_f64().unwrap_or(0.0),
+        origin["top"].as_f64().unwrap_or(0.0),
+    );
+
+    let mut last = (left, top);
+    for stroke in &strokes {
+        let points: Vec<(f64, f64)> = stroke.iter().map(|(x, y)| (left + x, top + y)).collect();
+        let (first, end) = (points[0], points[points.len() - 1]);
+        let sequence = std::iter::once(("mouseMoved", first, 0))
+            .chain(std::iter::once(("mousePressed", first, 1)))
+            .chain(points[1..].iter().map(|p| ("mouseMoved", *p, 1)))
+            .chain(std::iter::once(("mouseReleased", end, 1)));
+        for (event_type, (x, y), buttons) in sequence {
+            if let Err(e) = mouse_event(&ctx, event_type, x, y, buttons).await {
+                return e;
+            }
+        }
+        last = end;
+    }
+    {
+        let mut reg = ctx.registry().lock().await;
+        reg.set_cursor_position(ctx.session_id(), ctx.tab_id(), last.0, last.1);
+    }
+
+    let url = navigation::get_tab_url(&ctx.cdp, &ctx.target_id).await;
+    let title = navigation::get_tab_title(&ctx.cdp, &ctx.target_id).await;
+
+    ActionResult::ok(json!({
+        "action": "canvas draw",
+        "target": { "selector": cmd.selector },
+        "strokes": strokes.len(),
+        "points": strokes.iter().map(Vec::len).sum::<usize>(),
+        "post_url": url,
+        "post_title": title,
+    }))
+}
+
+// ── Shared ────────────────────────────────────────────────────────────────────
+
+async fn call_on(
+    ctx: &TabContext,
+    object_id: &str,
+    function: &str,
+    arguments: Value,
+) -> Result<Value, ActionResult> {
+    let resp = ctx
+        .execute_on_element(
+            "Runtime.callFunctionOn",
+            json!({
+                "objectId": object_id,
+                "functionDeclaration": function,
+                "arguments": arguments,
+                "returnByValue": true,
+            }),
+        )
+        .await
+        .map_err(|e| cdp_error_to_result(e, "CDP_ERROR"))?;
+    let raw = resp
+        .pointer("/result/result/value")
+        .and_then(|v| v.as_str())
+        .unwrap_or("{}");
+    Ok(serde_json::from_str(raw).unwrap_or_default())
+}
+
+async fn mouse_event(
+    ctx: &TabContext,
+    event_type: &str,
+    x: f64,
+    y: f64,
+    buttons: u8,
+) -> Result<(), ActionResult> {
+    let mut params = json!({
+        "type": event_type,
+        "x": x,
+        "y": y,
+        "button": if buttons == 0 { "none" } else { "left" },
+        "buttons": buttons,
+    });
+    if event_type != "mouseMoved" {
+        params["clickCount"] = json!(1);
+    }
+    ctx.cdp
+        .execute_on_tab(&ctx.target_id, "Input.dispatchMouseEvent", params)
+        .await
+        .map(|_| ())
+        .map_err(|e| cdp_error_to_result(e, "CDP_ERROR"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_color_accepts_hex_and_rgb() {
+        assert_eq!(parse_color("#ff0000"), Some([255, 0, 0]));
+        assert_eq!(parse_color("#0af"), Some([0, 170, 255]));
+        assert_eq!(parse_color(" rgb(0, 128, 255) "), Some([0, 128, 255]));
+        assert_eq!(parse_color("rgba(1,2,3,0.5)"), Some([1, 2, 3]));
+        assert_eq!(parse_color("#ff00"), None);
+        assert_eq!(parse_color("rgb(300, 0, 0)"), None);
+        assert_eq!(parse_color("red"), None);
+    }
+
+    #[test]
+    fn parse_strokes_accepts_one_stroke_or_many() {
+        let one = parse_strokes(&json!([[1, 2], { "x": 3, "y": 4.5 }])).unwrap();
+        assert_eq!(one, vec![vec![(1.0, 2.0), (3.0, 4.5)]]);
+
+        let many = parse_strokes(&json!([[[0, 0], [5, 5]], [[1, 1]]])).unwrap();
+        assert_eq!(many, vec![vec![(0.0, 0.0), (5.0, 5.0)], vec![(1.0, 1.0)]]);
+
+        assert!(parse_strokes(&json!([])).is_err());
+        assert!(parse_strokes(&json!([[[0, 0]], []])).is_err());
+        assert!(parse_strokes(&json!([[[0, "a"]]])).is_err());
+    }
+}
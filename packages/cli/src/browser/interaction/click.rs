@@ -5,9 +5,10 @@ use serde::{Deserialize, Serialize};
 use serde_json::{Value, json};
 
 use crate::action_result::ActionResult;
-use crate::browser::element::{ClickTarget, TabContext, parse_target};
+use crate::browser::element::{ClickTarget, ElementPoint, TabContext, parse_target};
 use crate::browser::navigation;
 use crate::daemon::cdp_session::{CdpSession, cdp_error_to_result};
+use crate::daemon::guardrails::{self, ElementProbe, SensitivePolicy};
 use crate::daemon::registry::SharedRegistry;
 use crate::output::ResponseContext;
 
@@ -19,6 +20,10 @@ fn default_count() -> u32 {
     1
 }
 
+fn default_position() -> String {
+    "center".to_string()
+}
+
 /// Click one or more elements or coordinates
 #[derive(Args, Debug, Clone, Serialize, Deserialize)]
 #[command(after_help = "\
@@ -28,12 +33,24 @@ Examples:
   actionbook browser click 420,310 --session s1 --tab t1
   actionbook browser click \"a.link\" --new-tab --session s1 --tab t1
   actionbook browser click \"#item\" --count 2 --session s1 --tab t1
+  actionbook browser click \"#slider\" --position 75%,50% --session s1 --tab t1
+  actionbook browser click \".stars\" --position 44,8 --session s1 --tab t1
   actionbook browser click \"#close-banner\" \"#main-btn\" \"#confirm\" --session s1 --tab t1
+  actionbook browser click \"button.pay\" --frame checkout --session s1 --tab t1
 
 Accepts a CSS selector, XPath, snapshot ref (@eN), or x,y coordinates.
 When multiple selectors are provided, they are clicked sequentially in order.
 Refs come from snapshot output (e.g. [ref=e5]).
-Use --count 2 for double-click. Use --new-tab to open links in a new tab.")]
+--frame looks CSS/XPath selectors up inside an iframe (name, URL substring or
+position, 1 = first); refs already know their frame.
+Use --count 2 for double-click. Use --new-tab to open links in a new tab.
+--position aims inside the element's box instead of at its centre: a corner
+(top-left, top-right, bottom-left, bottom-right), X,Y pixels from the
+top-left corner, or X%,Y% of its width and height.
+A target covered by a sticky header or overlay is scrolled clear of it before
+the click (configurable under [interaction] in config.toml).
+When [guardrails] confirm_sensitive_actions is enabled, clicks on payment or
+deletion controls are refused unless --confirm-sensitive is passed.")]
 pub struct Cmd {
     /// CSS selector, XPath, @ref, or x,y coordinates (one or more)
     #[arg(num_args(1..))]
@@ -58,6 +75,20 @@ pub struct Cmd {
     #[arg(long, default_value_t = 1)]
     #[serde(default = "default_count")]
     pub count: u32,
+    /// Point inside the element: center, top-left, top-right, bottom-left,
+    /// bottom-right, X,Y or X%,Y%
+    #[arg(long, default_value = "center")]
+    #[serde(default = "default_position")]
+    pub position: String,
+    /// Allow clicks that the sensitive-action guardrail would refuse
+    #[arg(long)]
+    #[serde(default)]
+    pub confirm_sensitive: bool,
+    /// Look selectors up inside this iframe: its name, a URL substring, or
+    /// its position (1 = first frame)
+    #[arg(long)]
+    #[serde(default)]
+    pub frame: Option<String>,
 }
 
 pub const COMMAND_NAME: &str = "browser click";
@@ -92,17 +123,34 @@ pub fn context(cmd: &Cmd, result: &ActionResult) -> Option<ResponseContext> {
 }
 
 /// Click a single selector with the given context and command params.
-async fn execute_single_click(selector: &str, cmd: &Cmd, ctx: &mut TabContext) -> ActionResult {
+async fn execute_single_click(
+    selector: &str,
+    cmd: &Cmd,
+    ctx: &mut TabContext,
+    policy: Option<&SensitivePolicy>,
+    point: &ElementPoint,
+) -> ActionResult {
     // Parse target
     let target = match parse_target(selector) {
         Ok(t) => t,
         Err(e) => return e,
     };
+    if matches!(target, ClickTarget::Coordinates(..)) && !point.is_center() {
+        return ActionResult::fatal(
+            "INVALID_ARGUMENT",
+            format!("--position needs an element target, got coordinates '{selector}'"),
+        );
+    }
+
+    let sensitive = match guard_sensitive(ctx, selector, policy, cmd.confirm_sensitive).await {
+        Ok(v) => v,
+        Err(e) => return e,
+    };
 
     // Resolve element to (x, y) coordinates
     let (x, y) = match &target {
         ClickTarget::Coordinates(cx, cy) => (*cx, *cy),
-        ClickTarget::Selector(sel) => match ctx.resolve_center(sel).await {
+        ClickTarget::Selector(sel) => match ctx.resolve_point(sel, point).await {
             Ok((_node_id, cx, cy)) => (cx, cy),
             Err(e) => return e,
         },
@@ -116,14 +164,12 @@ async fn execute_single_click(selector: &str, cmd: &Cmd, ctx: &mut TabContext) -
             Ok(()) => {
                 let url = navigation::get_tab_url(&ctx.cdp, &ctx.target_id).await;
                 let title = navigation::get_tab_title(&ctx.cdp, &ctx.target_id).await;
-                ActionResult::ok(build_response(
-                    selector,
-                    &target,
-                    false,
-                    false,
-                    Some(url),
-                    Some(title),
-                ))
+                let mut data =
+                    build_response(selector, &target, false, false, Some(url), Some(title));
+                if let Some(s) = sensitive {
+                    data["sensitive"] = s;
+                }
+                ActionResult::ok(data)
             }
             Err(e) => e,
         };
@@ -150,14 +196,27 @@ async fn execute_single_click(selector: &str, cmd: &Cmd, ctx: &mut TabContext) -
     let url_changed = !pre_url.is_empty() && pre_url != post_url;
     let focus_changed = pre_focus != post_focus;
 
-    ActionResult::ok(build_response(
+    let mut data = build_response(
         selector,
         &target,
         url_changed,
         focus_changed,
         Some(post_url),
         Some(post_title),
-    ))
+    );
+    if let Some(s) = sensitive {
+        data["sensitive"] = s;
+    }
+    if !point.is_center() {
+        data["point"] = json!({ "x": x, "y": y });
+    }
+    if let Some(occlusion) = ctx.occlusion() {
+        data["occlusion"] = json!({
+            "covered_by": occlusion["by"],
+            "resolved": occlusion["resolved"],
+        });
+    }
+    ActionResult::ok(data)
 }
 
 /// Fast click: resolve + scroll + dispatch, but no pre/post state detection.
@@ -166,8 +225,11 @@ async fn execute_single_click(selector: &str, cmd: &Cmd, ctx: &mut TabContext) -
 pub(crate) async fn execute_fast_click(
     selector: &str,
     ctx: &mut TabContext,
+    policy: Option<&SensitivePolicy>,
+    confirmed: bool,
 ) -> Result<(), ActionResult> {
     let target = parse_target(selector)?;
+    guard_sensitive(ctx, selector, policy, confirmed).await?;
 
     let (x, y) = match &target {
         ClickTarget::Coordinates(cx, cy) => (*cx, *cy),
@@ -211,21 +273,37 @@ pub async fn execute(cmd: &Cmd, registry: &SharedRegistry) -> ActionResult {
         );
     }
 
+    let point = match ElementPoint::parse(&cmd.position) {
+        Ok(p) => p,
+        Err(e) => return ActionResult::fatal("INVALID_ARGUMENT", e),
+    };
+
+    let policy = match SensitivePolicy::load() {
+        Ok(p) => p,
+        Err(e) => return e,
+    };
+
     // Get CDP session and verify tab
     let mut ctx = match TabContext::new(registry, &cmd.session, &cmd.tab).await {
         Ok(v) => v,
         Err(e) => return e,
     };
+    if let Some(frame) = &cmd.frame
+        && let Err(e) = ctx.enter_frame(frame).await
+    {
+        return e;
+    }
 
     // Single selector: same response shape as before (backwards compat)
     if cmd.selectors.len() == 1 {
-        return execute_single_click(&cmd.selectors[0], cmd, &mut ctx).await;
+        return execute_single_click(&cmd.selectors[0], cmd, &mut ctx, policy.as_ref(), &point)
+            .await;
     }
 
     // Batch: sequential, fail-fast
     let mut results = Vec::new();
     for (i, selector) in cmd.selectors.iter().enumerate() {
-        match execute_single_click(selector, cmd, &mut ctx).await {
+        match execute_single_click(selector, cmd, &mut ctx, policy.as_ref(), &point).await {
             ActionResult::Ok { data } => {
                 results.push(json!({
                     "index": i,
@@ -236,6 +314,8 @@ pub async fn execute(cmd: &Cmd, registry: &SharedRegistry) -> ActionResult {
                     "post_title": data.get("post_title"),
                 }));
             }
+            // Guardrail refusals keep their own code so callers can confirm and retry.
+            err if guardrails::is_confirmation_required(&err) => return err,
             _err => {
                 return ActionResult::fatal_with_details(
                     "BATCH_CLICK_ERROR",
@@ -271,6 +351,99 @@ pub async fn execute(cmd: &Cmd, registry: &SharedRegistry) -> ActionResult {
     ActionResult::ok(data)
 }
 
+// ── Sensitive-action guardrail ─────────────────────────────────────
+
+/// Page-side probe: accessible name, submit-ness and page host of `this`.
+const PROBE_FN: &str = r#"function() {
+    const el = (this.closest && this.closest('button, a, input, [role=button], [role=link], [role=menuitem]')) || this;
+    const labelled = (el.getAttribute('aria-labelledby') || '').split(/\s+/).filter(Boolean)
+        .map(id => { const n = document.getElementById(id); return n ? n.textContent : ''; }).join(' ');
+    const name = (el.getAttribute('aria-label') || labelled || el.innerText || el.value
+        || el.getAttribute('title') || el.getAttribute('alt') || '').replace(/\s+/g, ' ').trim().slice(0, 200);
+    const type = (el.getAttribute('type') || '').toLowerCase();
+    const submit = !!el.form && ((el.tagName === 'BUTTON' && (type === '' || type === 'submit'))
+        || (el.tagName === 'INPUT' && (type === 'submit' || type === 'image')));
+    return { name, submit, host: location.hostname };
+}"#;
+
+/// Refuse unconfirmed sensitive clicks, and unconfirmed clicks whose target
+/// cannot be inspected. Returns the override record to attach to the response
+/// when a sensitive click was confirmed.
+pub(crate) async fn guard_sensitive(
+    ctx: &mut TabContext,
+    selector: &str,
+    policy: Option<&SensitivePolicy>,
+    confirmed: bool,
+) -> Result<Option<Value>, ActionResult> {
+    let Some(policy) = policy else {
+        return Ok(None);
+    };
+    let probe = probe_element(ctx, selector).await;
+    let Some(reason) = policy.gate(selector, probe.as_ref(), confirmed)? else {
+        return Ok(None);
+    };
+    tracing::warn!(
+        "sensitive action confirmed: click '{selector}' on {} [{}/{}]: {reason}",
+        probe
+            .as_ref()
+            .and_then(|p| p.host.as_deref())
+            .unwrap_or("-"),
+        ctx.session_id(),
+        ctx.tab_id()
+    );
+    Ok(Some(json!({
+        "confirmed": true,
+        "accessible_name": probe.map(|p| p.name),
+        "reason": reason,
+    })))
+}
+
+async fn probe_element(ctx: &mut TabContext, selector: &str) -> Option<ElementProbe> {
+    let value = match parse_target(selector).ok()? {
+        ClickTarget::Selector(sel) => {
+            let node_id = ctx.resolve_node(&sel).await.ok()?;
+            let object_id = ctx.resolve_object_id(node_id).await.ok()?;
+            ctx.execute_on_element(
+                "Runtime.callFunctionOn",
+                json!({
+                    "objectId": object_id,
+                    "functionDeclaration": PROBE_FN,
+                    "returnByValue": true,
+                }),
+            )
+            .await
+            .ok()?
+        }
+        ClickTarget::Coordinates(x, y) => {
+            let js = format!(
+                "(() => {{ const el = document.elementFromPoint({x}, {y}); return el ? ({PROBE_FN}).call(el) : null; }})()"
+            );
+            ctx.cdp
+                .execute_on_tab(
+                    &ctx.target_id,
+                    "Runtime.evaluate",
+                    json!({ "expression": js, "returnByValue": true }),
+                )
+                .await
+                .ok()?
+        }
+    };
+    let v = value.pointer("/result/result/value")?;
+    Some(ElementProbe {
+        name: v
+            .get("name")
+            .and_then(|n| n.as_str())
+            .unwrap_or("")
+            .to_string(),
+        is_submit: v.get("submit").and_then(|b| b.as_bool()).unwrap_or(false),
+        host: v
+            .get("host")
+            .and_then(|h| h.as_str())
+            .filter(|h| !h.is_empty())
+            .map(str::to_ascii_lowercase),
+    })
+}
+
 // ── Response builder ───────────────────────────────────────────────
 
 fn build_response(
@@ -0,0 +1,525 @@
+use std::time::Duration;
+
+use clap::Args;
+use serde::{Deserialize, Serialize};
+use serde_json::{Value, json};
+
+use crate::action_result::ActionResult;
+use crate::browser::element::TabContext;
+use crate::browser::interaction::type_text::type_chars;
+use crate::browser::navigation;
+use crate::daemon::cdp_session::cdp_error_to_result;
+use crate::daemon::registry::SharedRegistry;
+use crate::output::ResponseContext;
+
+/// How `browser date` gets the date into the field.
+#[derive(Clone, Copy, Debug, Default, Serialize, Deserialize, clap::ValueEnum, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum DateStrategy {
+    /// native for <input type=date|datetime-local|month>, otherwise type.
+    #[default]
+    Auto,
+    /// Set the value of a native date input and fire input and change.
+    Native,
+    /// Focus the field, replace its text with the date in --format, then blur.
+    Type,
+    /// Open the calendar popup, page to the month and click the day.
+    Calendar,
+}
+
+impl DateStrategy {
+    fn as_str(self) -> &'static str {
+        match self {
+            DateStrategy::Auto => "auto",
+            DateStrategy::Native => "native",
+            DateStrategy::Type => "type",
+            DateStrategy::Calendar => "calendar",
+        }
+    }
+}
+
+fn default_format() -> String {
+    "YYYY-MM-DD".to_string()
+}
+
+/// Set a date field (native input or JS datepicker)
+#[derive(Args, Debug, Clone, Serialize, Deserialize)]
+#[command(after_help = "\
+Examples:
+  actionbook browser date \"#checkin\" 2025-03-14 --session s1 --tab t1
+  actionbook browser date @e9 2025-03-14 --format MM/DD/YYYY --session s1 --tab t1
+  actionbook browser date \"#depart\" 2025-03-14 --strategy calendar --session s1 --tab t1
+
+Accepts a CSS selector, XPath, or snapshot ref (@eN from snapshot output).
+The date is always given as YYYY-MM-DD; --format is how the field expects it
+typed, built from YYYY, YY, MMMM (March), MMM (Mar), MM, M, DD and D.
+
+Strategies:
+  auto      native for date inputs, type for anything else (default)
+  native    set <input type=date|datetime-local|month> directly
+  type      replace the field's text with the formatted date, then blur
+  calendar  click the field, page the popup to the month, click the day")]
+pub struct Cmd {
+    /// Selector for the date field (CSS, XPath, or @ref)
+    pub selector: String,
+    /// Date as YYYY-MM-DD
+    pub date: String,
+    /// How the field expects the date typed (type strategy)
+    #[arg(long, default_value = "YYYY-MM-DD")]
+    #[serde(default = "default_format")]
+    pub format: String,
+    /// How to enter the date
+    #[arg(long, value_enum, default_value = "auto")]
+    #[serde(default)]
+    pub strategy: DateStrategy,
+    /// Session ID
+    #[arg(long)]
+    #[serde(rename = "session_id")]
+    pub session: String,
+    /// Tab ID
+    #[arg(long)]
+    #[serde(rename = "tab_id")]
+    pub tab: String,
+}
+
+pub const COMMAND_NAME: &str = "browser date";
+
+pub fn context(cmd: &Cmd, result: &ActionResult) -> Option<ResponseContext> {
+    if let ActionResult::Fatal { code, .. } = result
+        && code == "SESSION_NOT_FOUND"
+    {
+        return None;
+    }
+    let (url, title) = match result {
+        ActionResult::Ok { data } => (
+            data.get("post_url")
+                .and_then(|v| v.as_str())
+                .filter(|s| !s.is_empty())
+                .map(String::from),
+            data.get("post_title")
+                .and_then(|v| v.as_str())
+                .filter(|s| !s.is_empty())
+                .map(String::from),
+        ),
+        _ => (None, None),
+    };
+    Some(ResponseContext {
+        session_id: cmd.session.clone(),
+        tab_id: Some(cmd.tab.clone()),
+        window_id: None,
+        url,
+        title,
+    })
+}
+
+// ── Dates ──────────────────────────────────────────────────────────
+
+const MONTHS: [&str; 12] = [
+    "January",
+    "February",
+    "March",
+    "April",
+    "May",
+    "June",
+    "July",
+    "August",
+    "September",
+    "October",
+    "November",
+    "December",
+];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct Date {
+    year: u32,
+    month: u32,
+    day: u32,
+}
+
+impl Date {
+    /// Parse a calendar date given as `YYYY-MM-DD`.
+    fn parse(raw: &str) -> Option<Self> {
+        let mut parts = raw.trim().splitn(3, '-');
+        let (y, m, d) = (parts.next()?, parts.next()?, parts.next()?);
+        if y.len() != 4 || m.len() != 2 || d.len() != 2 {
+            return None;
+        }
+        let date = Date {
+            year: y.parse().ok()?,
+            month: m.parse().ok()?,
+            day: d.parse().ok()?,
+        };
+        let y = date.year;
+        let leap = y.is_multiple_of(4) && (!y.is_multiple_of(100) || y.is_multiple_of(400));
+        let days = match date.month {
+            1 | 3 | 5 | 7 | 8 | 10 | 12 => 31,
+            4 | 6 | 9 | 11 => 30,
+            2 if leap => 29,
+            2 => 28,
+            _ => return None,
+        };
+        (1..=days).contains(&date.day).then_some(date)
+    }
+
+    fn iso(&self) -> String {
+        format!("{:04}-{:02}-{:02}", self.year, self.month, self.day)
+    }
+
+    /// Render with YYYY/YY/MMMM/MMM/MM/M/DD/D tokens; anything else is literal.
+    fn format(&self, fmt: &str) -> String {
+        let name = MONTHS[(self.month - 1) as usize];
+        let tokens: [(&str, String); 8] = [
+            ("YYYY", format!("{:04}", self.year)),
+            ("YY", format!("{:02}", self.year % 100)),
+            ("MMMM", name.to_string()),
+            ("MMM", name[..3].to_string()),
+            ("MM", format!("{:02}", self.month)),
+            ("M", self.month.to_string()),
+            ("DD", format!("{:02}", self.day)),
+            ("D", self.day.to_string()),
+        ];
+        let mut out = String::new();
+        let mut rest = fmt;
+        'outer: while !rest.is_empty() {
+            for (token, value) in &tokens {
+                if let Some(tail) = rest.strip_prefix(token) {
+                    out.push_str(value);
+                    rest = tail;
+                    continue 'outer;
+                }
+            }
+            let ch = rest.chars().next().unwrap_or_default();
+            out.push(ch);
+            rest = &rest[ch.len_utf8()..];
+        }
+        out
+    }
+}
+
+// ── Page scripts ───────────────────────────────────────────────────
+
+/// Set a native date input; returns 'unsupported:<type>' for anything else.
+const NATIVE_JS: &str = r#"function(iso) {
+    const type = this.tagName === 'INPUT' ? this.type : this.tagName.toLowerCase();
+    let value;
+    if (type === 'date') value = iso;
+    else if (type === 'month') value = iso.slice(0, 7);
+    else if (type === 'datetime-local') value = iso + 'T' + ((this.value.split('T')[1]) || '00:00');
+    else return 'unsupported:' + type;
+    const setter = Object.getOwnPropertyDescriptor(HTMLInputElement.prototype, 'value').set;
+    setter.call(this, value);
+    this.dispatchEvent(new Event('input', { bubbles: true }));
+    this.dispatchEvent(new Event('change', { bubbles: true }));
+    return 'ok:' + this.value;
+}"#;
+
+const IS_NATIVE_JS: &str = r#"function() {
+    return this.tagName === 'INPUT' && ['date', 'month', 'datetime-local'].includes(this.type);
+}"#;
+
+const SELECT_ALL_JS: &str = r#"function() {
+    if (typeof this.select === 'function') this.select();
+    else if (this.isContentEditable) document.execCommand('selectAll');
+}"#;
+
+const COMMIT_JS: &str = r#"function() {
+    this.dispatchEvent(new Event('change', { bubbles: true }));
+    this.blur();
+    return this.value ?? this.textContent;
+}"#;
+
+/// Look at the open calendar popup and decide the next click: the target
+/// day, or the prev/next control that moves towards its month.
+fn calendar_js(date: &Date) -> String {
+    let months = serde_json::to_string(&MONTHS).unwrap_or_default();
+    format!(
+        r#"(() => {{
+    const months = {months};
+    const year = {year}, month = {month}, day = {day}, iso = '{iso}';
+    const visible = (el) => {{
+        const r = el.getBoundingClientRect();
+        const s = getComputedStyle(el);
+        return r.width > 0 && r.height > 0 && s.visibility !== 'hidden' && s.display !== 'none';
+    }};
+    const point = (el) => {{ const r = el.getBoundingClientRect(); return {{ x: r.left + r.width / 2, y: r.top + r.height / 2 }}; }};
+    const popups = Array.from(document.querySelectorAll(
+        '[role="dialog"], [role="grid"], [class*="calendar" i], [class*="datepicker" i], [class*="picker" i]'
+    )).filter(visible);
+    // Outermost match, so a grid's surrounding header (month, arrows) is included.
+    const popup = popups.filter(p => !popups.some(o => o !== p && o.contains(p))).pop();
+    if (!popup) return {{ status: 'none', reason: 'no calendar popup is open' }};
+
+    const direct = popup.querySelector(`[data-date="${{iso}}"], [data-day="${{iso}}"], [data-value="${{iso}}"]`);
+    if (direct && visible(direct)) return {{ status: 'day', ...point(direct) }};
+
+    const text = popup.textContent;
+    const yearMatch = text.match(/\b(19|20)\d{{2}}\b/);
+    // The earliest month name in the popup is the (first) month shown.
+    let shown = null;
+    for (let i = 0; i < 12; i++) {{
+        const re = new RegExp('\\b(' + months[i] + '|' + months[i].slice(0, 3) + ')\\b', 'i');
+        const at = text.search(re);
+        if (at >= 0 && (!shown || at < shown.at)) shown = {{ index: i, at }};
+    }}
+    if (!yearMatch || !shown) return {{ status: 'none', reason: 'cannot read the month and year the calendar shows' }};
+    const shownKey = parseInt(yearMatch[0], 10) * 12 + shown.index;
+    const wantKey = year * 12 + (month - 1);
+
+    if (shownKey === wantKey) {{
+        const skip = /(disabled|outside|other|old|new|adjacent|prev|next)/i;
+        const cells = Array.from(popup.querySelectorAll('td, button, a, [role="gridcell"], span, div'))
+            .filter(el => el.children.length === 0 || el.matches('td, button, [role="gridcell"]'))
+            .filter(el => el.textContent.trim() === String(day) && visible(el))
+            .filter(el => !skip.test(el.className) && !skip.test(el.parentElement?.className || '')
+                && el.getAttribute('aria-disabled') !== 'true' && !el.disabled);
+        if (!cells.length) return {{ status: 'none', reason: `day ${{day}} is not selectable in the calendar` }};
+        return {{ status: 'day', ...point(cells[0]) }};
+    }}
+
+    const forward = wantKey > shownKey;
+    const words = forward ? /(next|forward|›|»|→|>)/i : /(prev|back|‹|«|←|<)/i;
+    const controls = Array.from(popup.querySelectorAll('button, a, [role="button"], span, div'))
+        .filter(visible)
+        .filter(el => words.test([el.getAttribute('aria-label'), el.title, el.className, el.children.length ? '' : el.textContent].join(' ')));
+    if (!controls.length) return {{ status: 'none', reason: `no ${{forward ? 'next' : 'previous'}} month control found` }};
+    return {{ status: 'nav', ...point(controls[0]) }};
+}})()"#,
+        year = date.year,
+        month = date.month,
+        day = date.day,
+        iso = date.iso(),
+    )
+}
+
+// ── Execute ────────────────────────────────────────────────────────
+
+/// Most calendars need well under two years of paging.
+const MAX_CALENDAR_STEPS: usize = 36;
+
+pub async fn execute(cmd: &Cmd, registry: &SharedRegistry) -> ActionResult {
+    let Some(date) = Date::parse(&cmd.date) else {
+        return ActionResult::fatal_with_hint(
+            "INVALID_ARGUMENT",
+            format!("invalid date '{}'", cmd.date),
+            "give the date as YYYY-MM-DD, e.g. 2025-03-14",
+        );
+    };
+
+    let mut ctx = match TabContext::new(registry, &cmd.session, &cmd.tab).await {
+        Ok(v) => v,
+        Err(e) => return e,
+    };
+    let (node_id, object_id) = match ctx.resolve_object(&cmd.selector).await {
+        Ok(v) => v,
+        Err(e) => return e,
+    };
+    if let Err(e) = ctx.scroll_into_view(node_id).await {
+        return e;
+    }
+
+    let strategy = match cmd.strategy {
+        DateStrategy::Auto => match call_on(&ctx, &object_id, IS_NATIVE_JS, json!([])).await {
+            Ok(Value::Bool(true)) => DateStrategy::Native,
+            Ok(_) => DateStrategy::Type,
+            Err(e) => return e,
+        },
+        other => other,
+    };
+
+    let value = match strategy {
+        DateStrategy::Native => set_native(&ctx, &object_id, &cmd.selector, &date).await,
+        DateStrategy::Type => type_date(&ctx, node_id, &object_id, &date.format(&cmd.format)).await,
+        DateStrategy::Calendar => {
+            pick_from_calendar(&mut ctx, &cmd.selector, &object_id, &date).await
+        }
+        DateStrategy::Auto => unreachable!("resolved above"),
+    };
+    let value = match value {
+        Ok(v) => v,
+        Err(e) => return e,
+    };
+
+    let url = navigation::get_tab_url(&ctx.cdp, &ctx.target_id).await;
+    let title = navigation::get_tab_title(&ctx.cdp, &ctx.target_id).await;
+
+    ActionResult::ok(json!({
+        "action": "date",
+        "target": { "selector": cmd.selector },
+        "date": date.iso(),
+        "strategy": strategy.as_str(),
+        "value": value,
+        "post_url": url,
+        "post_title": title,
+    }))
+}
+
+async fn call_on(
+    ctx: &TabContext,
+    object_id: &str,
+    function: &str,
+    arguments: Value,
+) -> Result<Value, ActionResult> {
+    let resp = ctx
+        .execute_on_element(
+            "Runtime.callFunctionOn",
+            json!({
+                "objectId": object_id,
+                "functionDeclaration": function,
+                "arguments": arguments,
+                "returnByValue": true,
+            }),
+        )
+        .await
+        .map_err(|e| cdp_error_to_result(e, "CDP_ERROR"))?;
+    Ok(resp
+        .pointer("/result/result/value")
+        .cloned()
+        .unwrap_or(Value::Null))
+}
+
+async fn set_native(
+    ctx: &TabContext,
+    object_id: &str,
+    selector: &str,
+    date: &Date,
+) -> Result<Value, ActionResult> {
+    let result = call_on(ctx, object_id, NATIVE_JS, json!([{ "value": date.iso() }])).await?;
+    let result = result.as_str().unwrap_or_default();
+    if let Some(value) = result.strip_prefix("ok:") {
+        return Ok(json!(value));
+    }
+    let kind = result.strip_prefix("unsupported:").unwrap_or(result);
+    Err(ActionResult::fatal_with_hint(
+        "INVALID_ARGUMENT",
+        format!("'{selector}' is a {kind}, not a native date input"),
+        "use --strategy type or --strategy calendar for JS datepickers",
+    ))
+}
+
+async fn type_date(
+    ctx: &TabContext,
+    node_id: i64,
+    object_id: &str,
+    text: &str,
+) -> Result<Value, ActionResult> {
+    ctx.focus_element(node_id).await?;
+    call_on(ctx, object_id, SELECT_ALL_JS, json!([])).await?;
+    type_chars(ctx, text).await?;
+    call_on(ctx, object_id, COMMIT_JS, json!([])).await
+}
+
+async fn pick_from_calendar(
+    ctx: &mut TabContext,
+    selector: &str,
+    object_id: &str,
+    date: &Date,
+) -> Result<Value, ActionResult> {
+    let (_, x, y) = ctx.resolve_center(selector).await?;
+    dispatch_mouse_click(ctx, x, y).await?;
+
+    let script = calendar_js(date);
+    for _ in 0..MAX_CALENDAR_STEPS {
+        tokio::time::sleep(Duration::from_millis(150)).await;
+        let resp = ctx
+            .execute_on_element(
+                "Runtime.evaluate",
+                json!({ "expression": script, "returnByValue": true }),
+            )
+            .await
+            .map_err(|e| cdp_error_to_result(e, "CDP_ERROR"))?;
+        let step = resp
+            .pointer("/result/result/value")
+            .cloned()
+            .unwrap_or(Value::Null);
+        let (x, y) = (
+            step["x"].as_f64().unwrap_or(0.0),
+            step["y"].as_f64().unwrap_or(0.0),
+        );
+        match step["status"].as_str() {
+            Some("day") => {
+                dispatch_mouse_click(ctx, x, y).await?;
+                tokio::time::sleep(Duration::from_millis(100)).await;
+                return call_on(
+                    ctx,
+                    object_id,
+                    "function() { return this.value ?? this.textContent; }",
+                    json!([]),
+                )
+                .await;
+            }
+            Some("nav") => dispatch_mouse_click(ctx, x, y).await?,
+            _ => {
+                return Err(ActionResult::fatal_with_hint(
+                    "DATEPICKER_UNSUPPORTED",
+                    format!(
+                        "could not pick {} from the calendar: {}",
+                        date.iso(),
+                        step["reason"].as_str().unwrap_or("unrecognised popup")
+                    ),
+                    "try --strategy type with the --format the field expects",
+                ));
+            }
+        }
+    }
+    Err(ActionResult::fatal_with_hint(
+        "DATEPICKER_UNSUPPORTED",
+        format!(
+            "the calendar did not reach {} after {MAX_CALENDAR_STEPS} clicks",
+            date.iso()
+        ),
+        "try --strategy type with the --format the field expects",
+    ))
+}
+
+/// Click at viewport coordinates.
+async fn dispatch_mouse_click(ctx: &TabContext, x: f64, y: f64) -> Result<(), ActionResult> {
+    for event_type in &["mousePressed", "mouseReleased"] {
+        ctx.cdp
+            .execute_on_tab(
+                &ctx.target_id,
+                "Input.dispatchMouseEvent",
+                json!({
+                    "type": event_type,
+                    "x": x,
+                    "y": y,
+                    "button": "left",
+                    "clickCount": 1,
+                    "buttons": 1,
+                }),
+            )
+            .await
+            .map_err(|e| cdp_error_to_result(e, "CDP_ERROR"))?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_accepts_real_dates_only() {
+        assert_eq!(
+            Date::parse("2025-03-14"),
+            Some(Date {
+                year: 2025,
+                month: 3,
+                day: 14
+            })
+        );
+        assert!(Date::parse("2024-02-29").is_some());
+        assert!(Date::parse("2025-02-29").is_none());
+        assert!(Date::parse("2025-13-01").is_none());
+        assert!(Date::parse("2025-3-14").is_none());
+        assert!(Date::parse("14/03/2025").is_none());
+    }
+
+    #[test]
+    fn format_expands_tokens_longest_first() {
+        let d = Date::parse("2025-03-04").unwrap();
+        assert_eq!(d.format("MM/DD/YYYY"), "03/04/2025");
+        assert_eq!(d.format("D.M.YY"), "4.3.25");
+        assert_eq!(d.format("MMMM D, YYYY"), "March 4, 2025");
+        assert_eq!(d.format("DD MMM"), "04 Mar");
+    }
+}
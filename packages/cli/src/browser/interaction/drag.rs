@@ -155,16 +155,21 @@ pub async fn execute(cmd: &Cmd, registry: &SharedRegistry) -> ActionResult {
 
     let result = execute_inner(&mut ctx, cmd, &destination).await;
 
-    // Always clear the viewport override
+    // Always clear the viewport override, back to the session's device if
+    // it emulates one.
     if viewport_set {
-        let _ = ctx
-            .cdp
-            .execute_on_tab(
-                &ctx.target_id,
-                "Emulation.clearDeviceMetricsOverride",
-                json!({}),
-            )
-            .await;
+        if let Some(emulation) = ctx.cdp.emulation() {
+            let _ = crate::daemon::emulation::apply(&ctx.cdp, &ctx.target_id, &emulation).await;
+        } else {
+            let _ = ctx
+                .cdp
+                .execute_on_tab(
+                    &ctx.target_id,
+                    "Emulation.clearDeviceMetricsOverride",
+                    json!({}),
+                )
+                .await;
+        }
     }
 
     result
@@ -340,7 +345,7 @@ async fn execute_inner(
 }
 
 /// Dispatch CDP mouse events for the drag gesture.
-async fn dispatch_drag(
+pub async fn dispatch_drag(
     cdp: &CdpSession,
     target_id: &str,
     src_x: f64,
@@ -4,7 +4,9 @@ use serde_json::json;
 
 use crate::action_result::ActionResult;
 use crate::browser::element::{ClickTarget, TabContext, parse_target};
+use crate::browser::interaction::press::key_definition;
 use crate::browser::navigation;
+use crate::browser::redact;
 use crate::daemon::cdp_session::cdp_error_to_result;
 use crate::daemon::registry::SharedRegistry;
 use crate::output::ResponseContext;
@@ -17,11 +19,22 @@ Examples:
   actionbook browser fill @e4 \"search query\" --session s1 --tab t1
   actionbook browser fill 420,310 \"hello\" --session s1 --tab t1
   actionbook browser fill \"hello\" --session s1 --tab t1
+  actionbook browser fill \"#card\" \"4242\" --frame pay.example --session s1 --tab t1
 
 Accepts a CSS selector, XPath, snapshot ref (@eN), or coordinates (x,y).
 If selector is omitted, fills the currently focused element (document.activeElement).
+--frame looks the selector up inside an iframe (name, URL substring or
+position, 1 = first).
 Sets the value instantly (no per-character events). Use for standard inputs.
-For fields that need keystroke events (autocomplete, validation), use type instead.")]
+For fields that need keystroke events (autocomplete, validation), use type instead.
+
+Rich text editors (ProseMirror, Quill, Slate, Lexical, ...) keep their own
+document model, so setting content directly corrupts it. With --rich, or
+whenever the target is contenteditable, the existing content is selected and
+replaced through the browser's input pipeline: each line is inserted as text
+(beforeinput/input insertText) and line breaks are trusted Enter presses, so
+the editor builds its own paragraphs.
+Use --secret for credentials; password fields are detected automatically.")]
 pub struct Cmd {
     /// Positional args: [selector] value — if one arg, it's the value; if two, first is selector.
     #[arg(num_args = 1..=2)]
@@ -34,6 +47,19 @@ pub struct Cmd {
     #[arg(long)]
     #[serde(rename = "tab_id")]
     pub tab: String,
+    /// Treat the value as a secret: never echo it or its length
+    #[arg(long)]
+    #[serde(default)]
+    pub secret: bool,
+    /// Replace the content through trusted input events instead of setting it
+    #[arg(long)]
+    #[serde(default)]
+    pub rich: bool,
+    /// Look selectors up inside this iframe: its name, a URL substring, or
+    /// its position (1 = first frame)
+    #[arg(long)]
+    #[serde(default)]
+    pub frame: Option<String>,
 }
 
 pub const COMMAND_NAME: &str = "browser fill";
@@ -83,6 +109,11 @@ pub async fn execute(cmd: &Cmd, registry: &SharedRegistry) -> ActionResult {
         Ok(v) => v,
         Err(e) => return e,
     };
+    if let Some(frame) = &cmd.frame
+        && let Err(e) = ctx.enter_frame(frame).await
+    {
+        return e;
+    }
 
     let target_json: serde_json::Value;
 
@@ -130,6 +161,25 @@ pub async fn execute(cmd: &Cmd, registry: &SharedRegistry) -> ActionResult {
         }
     };
 
+    let secret = cmd.secret || redact::object_is_secret(&ctx, &object_id).await;
+
+    let editable = is_contenteditable(&ctx, &object_id).await;
+    if cmd.rich || editable {
+        if let Err(e) = fill_rich(&ctx, &object_id, value, editable).await {
+            return e;
+        }
+        let url = navigation::get_tab_url(&ctx.cdp, &ctx.target_id).await;
+        let title = navigation::get_tab_title(&ctx.cdp, &ctx.target_id).await;
+        return ActionResult::ok(json!({
+            "action": "fill",
+            "target": target_json,
+            "mode": "rich",
+            "value_summary": redact::value_summary(value, secret),
+            "post_url": url,
+            "post_title": title,
+        }));
+    }
+
     // Set value directly via JS and dispatch an input event (no key events)
     let value_json = serde_json::to_string(&value).unwrap_or_default();
     let fill_fn = format!(
@@ -177,12 +227,138 @@ pub async fn execute(cmd: &Cmd, registry: &SharedRegistry) -> ActionResult {
     ActionResult::ok(json!({
         "action": "fill",
         "target": target_json,
-        "value_summary": { "text_length": value.chars().count() },
+        "value_summary": redact::value_summary(value, secret),
         "post_url": url,
         "post_title": title,
     }))
 }
 
+async fn is_contenteditable(ctx: &TabContext, object_id: &str) -> bool {
+    ctx.execute_on_element(
+        "Runtime.callFunctionOn",
+        json!({
+            "functionDeclaration": "function() { return this.isContentEditable === true; }",
+            "objectId": object_id,
+            "returnByValue": true,
+        }),
+    )
+    .await
+    .ok()
+    .and_then(|v| v.pointer("/result/result/value").and_then(|b| b.as_bool()))
+    .unwrap_or(false)
+}
+
+/// Focus the element and select everything in it, so the next input replaces it.
+const SELECT_CONTENT_JS: &str = r#"function() {
+    this.focus();
+    if (typeof this.select === 'function') {
+        this.select();
+        return 'ok';
+    }
+    if (!this.isContentEditable) return 'element is not editable';
+    const range = document.createRange();
+    range.selectNodeContents(this);
+    const sel = window.getSelection();
+    sel.removeAllRanges();
+    sel.addRange(range);
+    return 'ok';
+}"#;
+
+/// Replace the element's content the way a user would: select it all, then
+/// insert text with `Input.insertText` (which editors see as a trusted
+/// `beforeinput` of type `insertText`) and break lines with real Enter presses
+/// so the editor splits its own blocks.
+async fn fill_rich(
+    ctx: &TabContext,
+    object_id: &str,
+    value: &str,
+    editable: bool,
+) -> Result<(), ActionResult> {
+    let resp = ctx
+        .execute_on_element(
+            "Runtime.callFunctionOn",
+            json!({
+                "objectId": object_id,
+                "functionDeclaration": SELECT_CONTENT_JS,
+                "returnByValue": true,
+            }),
+        )
+        .await
+        .map_err(|e| cdp_error_to_result(e, "CDP_ERROR"))?;
+    let result_str = resp
+        .pointer("/result/result/value")
+        .and_then(|v| v.as_str())
+        .unwrap_or("");
+    if result_str != "ok" {
+        return Err(ActionResult::fatal(
+            "CDP_ERROR",
+            format!("fill failed: {result_str}"),
+        ));
+    }
+
+    // Input.* events go to the foreground tab regardless of session.
+    let _ = ctx
+        .cdp
+        .execute_browser(
+            "Target.activateTarget",
+            json!({ "targetId": ctx.target_id }),
+        )
+        .await;
+
+    if value.is_empty() {
+        return press_key(ctx, "Backspace").await;
+    }
+    if !editable {
+        // Plain inputs and textareas take newlines as text.
+        return insert_text(ctx, value).await;
+    }
+    for (i, line) in value.split('\n').enumerate() {
+        if i > 0 {
+            press_key(ctx, "Enter").await?;
+        }
+        if !line.is_empty() {
+            insert_text(ctx, line).await?;
+        }
+    }
+    Ok(())
+}
+
+async fn insert_text(ctx: &TabContext, text: &str) -> Result<(), ActionResult> {
+    ctx.cdp
+        .execute_on_tab(&ctx.target_id, "Input.insertText", json!({ "text": text }))
+        .await
+        .map(|_| ())
+        .map_err(|e| cdp_error_to_result(e, "CDP_ERROR"))
+}
+
+async fn press_key(ctx: &TabContext, key: &str) -> Result<(), ActionResult> {
+    let def = key_definition(key);
+    let text = def.as_ref().and_then(|d| d.text.clone());
+    let mut down = json!({
+        "type": if text.is_some() { "keyDown" } else { "rawKeyDown" },
+        "key": key,
+    });
+    let mut up = json!({ "type": "keyUp", "key": key });
+    if let Some(ref d) = def {
+        for ev in [&mut down, &mut up] {
+            ev["code"] = json!(d.code);
+            ev["windowsVirtualKeyCode"] = json!(d.key_code);
+            ev["nativeVirtualKeyCode"] = json!(d.key_code);
+        }
+    }
+    if let Some(text) = text {
+        down["text"] = json!(text);
+        down["unmodifiedText"] = json!(text);
+    }
+    for ev in [down, up] {
+        ctx.cdp
+            .execute_on_tab(&ctx.target_id, "Input.dispatchKeyEvent", ev)
+            .await
+            .map_err(|e| cdp_error_to_result(e, "CDP_ERROR"))?;
+    }
+    Ok(())
+}
+
 /// Click at coordinates to focus the element at that position.
 async fn dispatch_mouse_click(ctx: &TabContext, x: f64, y: f64) -> Result<(), ActionResult> {
     for event_type in &["mousePressed", "mouseReleased"] {
@@ -0,0 +1,311 @@
+use std::time::{Duration, Instant};
+
+use clap::Args;
+use serde::{Deserialize, Serialize};
+use serde_json::{Value, json};
+
+use crate::action::Action;
+use crate::action_result::ActionResult;
+use crate::browser::navigation;
+use crate::daemon::cdp_session::{CdpSession, cdp_error_to_result, get_cdp_and_target};
+use crate::daemon::registry::SharedRegistry;
+use crate::output::ResponseContext;
+
+const DEFAULT_TIMEOUT_MS: u64 = 10 * 60 * 1000;
+const POLL_INTERVAL_MS: u64 = 250;
+
+/// Hand the browser to a human until they finish a step (2FA, CAPTCHA, ...)
+#[derive(Args, Debug, Clone, Serialize, Deserialize)]
+#[command(after_help = "\
+Examples:
+  actionbook browser handoff --session s1 --tab t1 --message \"please complete 2FA\"
+  actionbook browser handoff --session s1 --tab t1 --until-url /dashboard
+  actionbook browser handoff --session s1 --tab t1 --until-selector '#account-menu'
+
+Brings the tab's window to the front and shows a banner with the message and
+a Done button. The command returns when the human clicks Done (or presses
+Ctrl+Shift+Enter in the page), when the URL contains --until-url, or when
+--until-selector matches. Other commands on the session are rejected as
+retryable until the handoff ends. Requires a visible (non-headless) session.")]
+pub struct Cmd {
+    /// Session ID
+    #[arg(long)]
+    #[serde(rename = "session_id")]
+    pub session: String,
+    /// Tab ID
+    #[arg(long)]
+    #[serde(rename = "tab_id")]
+    pub tab: String,
+    /// Instructions shown to the human in the page banner
+    #[arg(long)]
+    #[serde(default)]
+    pub message: Option<String>,
+    /// Finish once the tab URL contains this substring
+    #[arg(long)]
+    #[serde(default)]
+    pub until_url: Option<String>,
+    /// Finish once this CSS selector matches an element
+    #[arg(long)]
+    #[serde(default)]
+    pub until_selector: Option<String>,
+    /// Timeout in milliseconds (default 600000)
+    #[arg(long)]
+    pub timeout: Option<u64>,
+}
+
+pub const COMMAND_NAME: &str = "browser handoff";
+
+/// Banner injected into the page (and every page navigated to during the
+/// handoff). Called as `(BANNER_JS)(message)`.
+const BANNER_JS: &str = r#"(function(message) {
+    if (window.top !== window) return;
+    const mount = () => {
+        if (document.getElementById('__ab_handoff')) return;
+        const bar = document.createElement('div');
+        bar.id = '__ab_handoff';
+        bar.setAttribute('style', 'position:fixed;top:0;left:0;right:0;z-index:2147483647;display:flex;gap:12px;align-items:center;padding:10px 16px;background:#1f2937;color:#fff;font:14px system-ui,sans-serif;box-shadow:0 2px 8px rgba(0,0,0,.3)');
+        const text = document.createElement('span');
+        text.style.flex = '1';
+        text.textContent = message;
+        const done = document.createElement('button');
+        done.textContent = 'Done';
+        done.setAttribute('style', 'padding:4px 14px;border:0;border-radius:4px;background:#10b981;color:#fff;font:inherit;cursor:pointer');
+        done.addEventListener('click', () => { window.__ab_handoff_done = true; });
+        bar.append(text, done);
+        document.documentElement.appendChild(bar);
+    };
+    window.addEventListener('keydown', (e) => {
+        if (e.ctrlKey && e.shiftKey && e.key === 'Enter') window.__ab_handoff_done = true;
+    }, true);
+    if (document.documentElement) mount();
+    else document.addEventListener('DOMContentLoaded', mount);
+})"#;
+
+const POLL_JS: &str = r#"(function(selector) {
+    return {
+        url: location.href,
+        done: window.__ab_handoff_done === true,
+        matched: selector ? document.querySelector(selector) !== null : false,
+    };
+})"#;
+
+const REMOVE_JS: &str = r#"(function() {
+    const bar = document.getElementById('__ab_handoff');
+    if (bar) bar.remove();
+    delete window.__ab_handoff_done;
+})()"#;
+
+pub fn context(cmd: &Cmd, result: &ActionResult) -> Option<ResponseContext> {
+    if let ActionResult::Fatal { code, .. } = result
+        && code == "SESSION_NOT_FOUND"
+    {
+        return None;
+    }
+    let tab_id = if let ActionResult::Fatal { code, .. } = result
+        && code == "TAB_NOT_FOUND"
+    {
+        None
+    } else {
+        Some(cmd.tab.clone())
+    };
+    let (url, title) = match result {
+        ActionResult::Ok { data } => (
+            data.get("__ctx_url")
+                .and_then(|v| v.as_str())
+                .map(String::from),
+            data.get("__ctx_title")
+                .and_then(|v| v.as_str())
+                .map(String::from),
+        ),
+        _ => (None, None),
+    };
+    Some(ResponseContext {
+        session_id: cmd.session.clone(),
+        tab_id,
+        window_id: None,
+        url,
+        title,
+    })
+}
+
+/// Reject actions on a session while a human has it. Closing the session and
+/// read-only session listing stay available so a stuck handoff can be ended.
+pub async fn paused(action: &Action, registry: &SharedRegistry) -> Option<ActionResult> {
+    let session = match action {
+        Action::Handoff(_)
+        | Action::Close(_)
+        | Action::SessionStatus(_)
+        | Action::ListSessions(_) => return None,
+        other => crate::audit::action_address(other).0?,
+    };
+    if !registry.lock().await.in_handoff(&session) {
+        return None;
+    }
+    Some(ActionResult::Retryable {
+        reason: format!("session '{session}' is handed off to a human"),
+        hint: "wait for `browser handoff` to return, then retry".to_string(),
+    })
+}
+
+pub async fn execute(cmd: &Cmd, registry: &SharedRegistry) -> ActionResult {
+    let (cdp, target_id) = match get_cdp_and_target(registry, &cmd.session, &cmd.tab).await {
+        Ok(v) => v,
+        Err(e) => return e,
+    };
+    {
+        let mut reg = registry.lock().await;
+        if reg.get(&cmd.session).is_some_and(|e| e.headless) {
+            return ActionResult::fatal_with_hint(
+                "INVALID_ARGUMENT",
+                "handoff needs a visible browser window",
+                "restart the session with --headless false",
+            );
+        }
+        if !reg.begin_handoff(&cmd.session) {
+            return ActionResult::fatal(
+                "INVALID_ARGUMENT",
+                format!("session '{}' is already handed off", cmd.session),
+            );
+        }
+    }
+
+    let result = run(cmd, &cdp, &target_id).await;
+    registry.lock().await.end_handoff(&cmd.session);
+    result
+}
+
+async fn run(cmd: &Cmd, cdp: &CdpSession, target_id: &str) -> ActionResult {
+    bring_to_front(cdp, target_id).await;
+
+    let message = cmd
+        .message
+        .clone()
+        .unwrap_or_else(|| "Automation paused: finish this step, then click Done.".to_string());
+    let banner = format!(
+        "({BANNER_JS})({})",
+        serde_json::to_string(&message).unwrap_or_default()
+    );
+    // Re-inject on every navigation the human triggers (login redirects etc.).
+    let script_id = match cdp
+        .execute_on_tab(
+            target_id,
+            "Page.addScriptToEvaluateOnNewDocument",
+            json!({ "source": banner }),
+        )
+        .await
+    {
+        Ok(v) => v
+            .pointer("/result/identifier")
+            .and_then(|v| v.as_str())
+            .map(String::from),
+        Err(e) => return cdp_error_to_result(e, "CDP_ERROR"),
+    };
+    if let Err(e) = cdp
+        .execute_on_tab(
+            target_id,
+            "Runtime.evaluate",
+            json!({ "expression": banner, "returnByValue": true }),
+        )
+        .await
+    {
+        return cdp_error_to_result(e, "CDP_ERROR");
+    }
+
+    let outcome = wait_for_human(cmd, cdp, target_id).await;
+
+    if let Some(id) = script_id {
+        let _ = cdp
+            .execute_on_tab(
+                target_id,
+                "Page.removeScriptToEvaluateOnNewDocument",
+                json!({ "identifier": id }),
+            )
+            .await;
+    }
+    let _ = cdp
+        .execute_on_tab(
+            target_id,
+            "Runtime.evaluate",
+            json!({ "expression": REMOVE_JS, "returnByValue": true }),
+        )
+        .await;
+
+    outcome
+}
+
+async fn wait_for_human(cmd: &Cmd, cdp: &CdpSession, target_id: &str) -> ActionResult {
+    let timeout_ms = cmd.timeout.unwrap_or(DEFAULT_TIMEOUT_MS);
+    let selector = serde_json::to_string(&cmd.until_selector).unwrap_or_else(|_| "null".into());
+    let expression = format!("({POLL_JS})({selector})");
+    let start = Instant::now();
+
+    loop {
+        // Evaluation fails mid-navigation; just try again next tick.
+        if let Ok(v) = cdp
+            .execute_on_tab(
+                target_id,
+                "Runtime.evaluate",
+                json!({ "expression": expression, "returnByValue": true }),
+            )
+            .await
+            && let Some(state) = v.pointer("/result/result/value")
+        {
+            let url = state["url"].as_str().unwrap_or_default();
+            let finished_by = if state["done"] == Value::Bool(true) {
+                Some("done")
+            } else if cmd.until_url.as_deref().is_some_and(|u| url.contains(u)) {
+                Some("url")
+            } else if state["matched"] == Value::Bool(true) {
+                Some("selector")
+            } else {
+                None
+            };
+            if let Some(by) = finished_by {
+                let title = navigation::get_tab_title(cdp, target_id).await;
+                return ActionResult::ok(json!({
+                    "completed": true,
+                    "completed_by": by,
+                    "elapsed_ms": start.elapsed().as_millis() as u64,
+                    "url": url,
+                    "__ctx_url": url,
+                    "__ctx_title": title,
+                }));
+            }
+        }
+
+        if start.elapsed().as_millis() as u64 >= timeout_ms {
+            return ActionResult::fatal_with_hint(
+                "TIMEOUT",
+                format!("handoff was not completed within {timeout_ms}ms"),
+                "increase --timeout or run `browser handoff` again",
+            );
+        }
+        tokio::time::sleep(Duration::from_millis(POLL_INTERVAL_MS)).await;
+    }
+}
+
+/// Focus the tab and restore its window if minimized. Best-effort: some
+/// endpoints (extension bridge, remote browsers) lack the Browser domain.
+async fn bring_to_front(cdp: &CdpSession, target_id: &str) {
+    let _ = cdp
+        .execute_on_tab(target_id, "Page.bringToFront", json!({}))
+        .await;
+    if let Ok(v) = cdp
+        .execute_browser(
+            "Browser.getWindowForTarget",
+            json!({ "targetId": target_id }),
+        )
+        .await
+        && let Some(window_id) = v.pointer("/result/windowId").and_then(|v| v.as_i64())
+        && v.pointer("/result/bounds/windowState")
+            .and_then(|v| v.as_str())
+            == Some("minimized")
+    {
+        let _ = cdp
+            .execute_browser(
+                "Browser.setWindowBounds",
+                json!({ "windowId": window_id, "bounds": { "windowState": "normal" } }),
+            )
+            .await;
+    }
+}
@@ -3,7 +3,7 @@ use serde::{Deserialize, Serialize};
 use serde_json::json;
 
 use crate::action_result::ActionResult;
-use crate::browser::element::TabContext;
+use crate::browser::element::{ElementPoint, TabContext};
 use crate::browser::navigation;
 use crate::daemon::cdp_session::cdp_error_to_result;
 use crate::daemon::registry::SharedRegistry;
@@ -15,9 +15,11 @@ use crate::output::ResponseContext;
 Examples:
   actionbook browser hover \"#menu-item\" --session s1 --tab t1
   actionbook browser hover @e3 --session s1 --tab t1
+  actionbook browser hover \".rating\" --position 80%,50% --session s1 --tab t1
 
 Moves the mouse over the element to trigger hover states (tooltips, dropdowns, etc.).
-Accepts a CSS selector, XPath, or snapshot ref (@eN from snapshot output).")]
+Accepts a CSS selector, XPath, or snapshot ref (@eN from snapshot output).
+--position takes the same values as click --position.")]
 pub struct Cmd {
     /// Selector (CSS, XPath, or @ref)
     pub selector: String,
@@ -29,6 +31,15 @@ pub struct Cmd {
     #[arg(long)]
     #[serde(rename = "tab_id")]
     pub tab: String,
+    /// Point inside the element: center, top-left, top-right, bottom-left,
+    /// bottom-right, X,Y or X%,Y%
+    #[arg(long, default_value = "center")]
+    #[serde(default = "default_position")]
+    pub position: String,
+}
+
+fn default_position() -> String {
+    "center".to_string()
 }
 
 pub const COMMAND_NAME: &str = "browser hover";
@@ -62,6 +73,11 @@ pub fn context(cmd: &Cmd, result: &ActionResult) -> Option<ResponseContext> {
 }
 
 pub async fn execute(cmd: &Cmd, registry: &SharedRegistry) -> ActionResult {
+    let point = match ElementPoint::parse(&cmd.position) {
+        Ok(p) => p,
+        Err(e) => return ActionResult::fatal("INVALID_ARGUMENT", e),
+    };
+
     let mut ctx = match TabContext::new(registry, &cmd.session, &cmd.tab).await {
         Ok(v) => v,
         Err(e) => return e,
@@ -92,16 +108,17 @@ pub async fn execute(cmd: &Cmd, registry: &SharedRegistry) -> ActionResult {
             "Runtime.callFunctionOn",
             json!({
                 "objectId": object_id,
-                "functionDeclaration": r#"function() {
+                "functionDeclaration": r#"function(fx, fy, dx, dy) {
                     const rect = this.getBoundingClientRect();
-                    const cx = rect.left + rect.width / 2;
-                    const cy = rect.top + rect.height / 2;
+                    const cx = rect.left + rect.width * fx + dx;
+                    const cy = rect.top + rect.height * fy + dy;
                     const shared = { clientX: cx, clientY: cy, screenX: cx, screenY: cy, view: window };
                     this.dispatchEvent(new MouseEvent('mouseenter', { ...shared, bubbles: false }));
                     this.dispatchEvent(new MouseEvent('mouseover', { ...shared, bubbles: true }));
                     this.dispatchEvent(new MouseEvent('mousemove', { ...shared, bubbles: true }));
                     return JSON.stringify({ x: cx, y: cy });
                 }"#,
+                "arguments": point.js_args(),
                 "returnByValue": true,
             }),
         )
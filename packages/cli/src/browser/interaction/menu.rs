@@ -0,0 +1,212 @@
+use std::time::{Duration, Instant};
+
+use clap::Args;
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+
+use crate::action_result::ActionResult;
+use crate::browser::element::TabContext;
+use crate::browser::interaction::click::execute_fast_click;
+use crate::browser::navigation;
+use crate::daemon::cdp_session::cdp_error_to_result;
+use crate::daemon::guardrails::SensitivePolicy;
+use crate::daemon::registry::SharedRegistry;
+use crate::output::ResponseContext;
+
+const DEFAULT_TIMEOUT_MS: u64 = 5_000;
+const POLL_INTERVAL_MS: u64 = 50;
+
+/// Hover a menu trigger, then click an item in the submenu it opens
+#[derive(Args, Debug, Clone, Serialize, Deserialize)]
+#[command(after_help = "\
+Examples:
+  actionbook browser menu \"#nav-products\" \"#nav-products a.pricing\" --session s1 --tab t1
+  actionbook browser menu @e3 \"#account-menu .sign-out\" --delay 300 --session s1 --tab t1
+
+Hovers the first selector, waits until the second selector is visible, and
+clicks it — all inside one command, so the menu cannot close between the
+hover and the click the way it does with separate hover and click calls.
+--delay waits a fixed time after the hover (for menus with an open delay)
+before the visibility check starts; --timeout bounds the whole wait.")]
+pub struct Cmd {
+    /// Menu trigger to hover (CSS, XPath, or @ref)
+    pub hover_selector: String,
+    /// Submenu item to click once visible (CSS, XPath, or @ref)
+    pub click_selector: String,
+    /// Milliseconds to wait after hovering before looking for the item
+    #[arg(long, default_value_t = 0)]
+    #[serde(default)]
+    pub delay: u64,
+    /// Milliseconds to wait for the item to become visible (default 5000)
+    #[arg(long)]
+    pub timeout: Option<u64>,
+    /// Allow clicks that the sensitive-action guardrail would refuse
+    #[arg(long)]
+    #[serde(default)]
+    pub confirm_sensitive: bool,
+    /// Session ID
+    #[arg(long)]
+    #[serde(rename = "session_id")]
+    pub session: String,
+    /// Tab ID
+    #[arg(long)]
+    #[serde(rename = "tab_id")]
+    pub tab: String,
+}
+
+pub const COMMAND_NAME: &str = "browser menu";
+
+/// True when `this` is rendered and not hidden by CSS.
+const IS_VISIBLE_JS: &str = r#"function() {
+    const r = this.getBoundingClientRect();
+    if (r.width === 0 || r.height === 0) return false;
+    const s = getComputedStyle(this);
+    return s.visibility !== 'hidden' && s.display !== 'none' && parseFloat(s.opacity) > 0;
+}"#;
+
+/// Fire the DOM hover events on `this`, for menus driven by JS listeners.
+const HOVER_EVENTS_JS: &str = r#"function() {
+    const rect = this.getBoundingClientRect();
+    const cx = rect.left + rect.width / 2;
+    const cy = rect.top + rect.height / 2;
+    const shared = { clientX: cx, clientY: cy, screenX: cx, screenY: cy, view: window };
+    this.dispatchEvent(new MouseEvent('mouseenter', { ...shared, bubbles: false }));
+    this.dispatchEvent(new MouseEvent('mouseover', { ...shared, bubbles: true }));
+    this.dispatchEvent(new MouseEvent('mousemove', { ...shared, bubbles: true }));
+}"#;
+
+pub fn context(cmd: &Cmd, result: &ActionResult) -> Option<ResponseContext> {
+    if let ActionResult::Fatal { code, .. } = result
+        && code == "SESSION_NOT_FOUND"
+    {
+        return None;
+    }
+    let (url, title) = match result {
+        ActionResult::Ok { data } => (
+            data.get("post_url")
+                .and_then(|v| v.as_str())
+                .filter(|s| !s.is_empty())
+                .map(String::from),
+            data.get("post_title")
+                .and_then(|v| v.as_str())
+                .filter(|s| !s.is_empty())
+                .map(String::from),
+        ),
+        _ => (None, None),
+    };
+    Some(ResponseContext {
+        session_id: cmd.session.clone(),
+        tab_id: Some(cmd.tab.clone()),
+        window_id: None,
+        url,
+        title,
+    })
+}
+
+/// Hover the trigger with a real pointer move (so CSS `:hover` menus open)
+/// plus the JS hover events that headless Chrome does not always synthesise.
+async fn hover(ctx: &mut TabContext, selector: &str) -> Result<(), ActionResult> {
+    let (node_id, x, y) = ctx.resolve_center(selector).await?;
+    ctx.cdp
+        .execute_on_tab(
+            &ctx.target_id,
+            "Input.dispatchMouseEvent",
+            json!({ "type": "mouseMoved", "x": x, "y": y, "button": "none", "buttons": 0 }),
+        )
+        .await
+        .map_err(|e| cdp_error_to_result(e, "CDP_ERROR"))?;
+    let object_id = ctx.resolve_object_id(node_id).await?;
+    ctx.execute_on_element(
+        "Runtime.callFunctionOn",
+        json!({ "objectId": object_id, "functionDeclaration": HOVER_EVENTS_JS }),
+    )
+    .await
+    .map_err(|e| cdp_error_to_result(e, "CDP_ERROR"))?;
+
+    let mut reg = ctx.registry().lock().await;
+    reg.set_cursor_position(ctx.session_id(), ctx.tab_id(), x, y);
+    Ok(())
+}
+
+async fn is_visible(ctx: &mut TabContext, selector: &str) -> bool {
+    let Ok((_, object_id)) = ctx.resolve_object(selector).await else {
+        return false;
+    };
+    ctx.execute_on_element(
+        "Runtime.callFunctionOn",
+        json!({
+            "objectId": object_id,
+            "functionDeclaration": IS_VISIBLE_JS,
+            "returnByValue": true,
+        }),
+    )
+    .await
+    .ok()
+    .and_then(|v| v.pointer("/result/result/value").and_then(|v| v.as_bool()))
+    .unwrap_or(false)
+}
+
+pub async fn execute(cmd: &Cmd, registry: &SharedRegistry) -> ActionResult {
+    let policy = match SensitivePolicy::load() {
+        Ok(p) => p,
+        Err(e) => return e,
+    };
+
+    let mut ctx = match TabContext::new(registry, &cmd.session, &cmd.tab).await {
+        Ok(v) => v,
+        Err(e) => return e,
+    };
+
+    let pre_url = navigation::get_tab_url(&ctx.cdp, &ctx.target_id).await;
+
+    if let Err(e) = hover(&mut ctx, &cmd.hover_selector).await {
+        return e;
+    }
+    if cmd.delay > 0 {
+        tokio::time::sleep(Duration::from_millis(cmd.delay)).await;
+    }
+
+    let timeout_ms = cmd.timeout.unwrap_or(DEFAULT_TIMEOUT_MS);
+    let start = Instant::now();
+    while !is_visible(&mut ctx, &cmd.click_selector).await {
+        if start.elapsed().as_millis() as u64 >= timeout_ms {
+            return ActionResult::fatal_with_hint(
+                "TIMEOUT",
+                format!(
+                    "menu item '{}' did not become visible within {timeout_ms}ms of hovering '{}'",
+                    cmd.click_selector, cmd.hover_selector
+                ),
+                "check both selectors, or raise --delay/--timeout for slow menus",
+            );
+        }
+        tokio::time::sleep(Duration::from_millis(POLL_INTERVAL_MS)).await;
+    }
+    let waited_ms = start.elapsed().as_millis() as u64;
+
+    if let Err(e) = execute_fast_click(
+        &cmd.click_selector,
+        &mut ctx,
+        policy.as_ref(),
+        cmd.confirm_sensitive,
+    )
+    .await
+    {
+        return e;
+    }
+
+    let url = navigation::get_tab_url(&ctx.cdp, &ctx.target_id).await;
+    let title = navigation::get_tab_title(&ctx.cdp, &ctx.target_id).await;
+
+    ActionResult::ok(json!({
+        "action": "menu",
+        "target": { "selector": cmd.hover_selector },
+        "item": { "selector": cmd.click_selector },
+        "waited_ms": waited_ms,
+        "changed": {
+            "url_changed": !pre_url.is_empty() && pre_url != url,
+            "focus_changed": false,
+        },
+        "post_url": url,
+        "post_title": title,
+    }))
+}
@@ -1,14 +1,22 @@
 pub mod batch_click;
+pub mod canvas;
 pub mod click;
 pub mod cursor_position;
+pub mod date;
 pub mod drag;
 pub mod eval;
 pub mod fill;
 pub mod focus;
+pub mod handoff;
 pub mod hover;
+pub mod menu;
 pub mod mouse_move;
 pub mod press;
+pub mod record;
 pub mod scroll;
 pub mod select;
+pub mod slide;
+pub mod tabto;
+pub mod touch;
 pub mod type_text;
 pub mod upload;
@@ -1,10 +1,12 @@
+use std::collections::BTreeMap;
+
 use clap::Args;
 use serde::{Deserialize, Serialize};
 use serde_json::json;
 
 use crate::action_result::ActionResult;
 use crate::browser::navigation;
-use crate::daemon::cdp_session::{cdp_error_to_result, get_cdp_and_target};
+use crate::daemon::cdp_session::{CdpSession, cdp_error_to_result, get_cdp_and_target};
 use crate::daemon::registry::SharedRegistry;
 use crate::output::ResponseContext;
 
@@ -16,12 +18,19 @@ Examples:
   actionbook browser press Tab --session s1 --tab t1
   actionbook browser press Control+A --session s1 --tab t1
   actionbook browser press Shift+Tab --session s1 --tab t1
+  actionbook browser press \"Tab Tab Enter\" --session s1 --tab t1
+  actionbook browser press @save --session s1 --tab t1
 
 Sends a key press to the currently focused element.
 Use focus first to direct keys to a specific element.
-Key names follow CDP conventions: Enter, Tab, Escape, ArrowDown, Control, Shift, Alt, Meta.")]
+Key names follow CDP conventions: Enter, Tab, Escape, ArrowDown, Space, Control, Shift, Alt, Meta.
+Several keys separated by spaces are pressed one after another.
+@name presses a macro from the [macros] section of the config, e.g.
+  [macros]
+  save = \"Control+S\"
+  next_field = \"Tab Tab Enter\"")]
 pub struct Cmd {
-    /// Single key or key combination (e.g., Enter, Control+A, Shift+Tab)
+    /// Key, key combination or space-separated sequence (e.g., Enter, Control+A, \"Tab Enter\", @macro)
     pub key: String,
     /// Session ID
     #[arg(long)]
@@ -111,9 +120,80 @@ fn parse_chord(input: &str) -> Result<(u32, String), ActionResult> {
     Ok((modifiers, main_key))
 }
 
+/// Expand `@name` macros from `[macros]` and split the keys into the chords
+/// to press in turn. Keys made only of spaces are the space key; a lone `@`
+/// is the `@` key.
+fn key_sequence(keys: &str) -> Result<Vec<String>, ActionResult> {
+    if !keys.is_empty() && keys.trim().is_empty() {
+        return Ok(vec![" ".to_string()]);
+    }
+    let uses_macros = keys
+        .split_whitespace()
+        .any(|t| t.len() > 1 && t.starts_with('@'));
+    let macros = if uses_macros {
+        crate::config::key_macros()
+    } else {
+        BTreeMap::new()
+    };
+    expand_macros(keys, &macros)
+}
+
+fn expand_macros(
+    keys: &str,
+    macros: &BTreeMap<String, String>,
+) -> Result<Vec<String>, ActionResult> {
+    let mut chords = Vec::new();
+    for token in keys.split_whitespace() {
+        let Some(name) = token.strip_prefix('@').filter(|n| !n.is_empty()) else {
+            chords.push(token.to_string());
+            continue;
+        };
+        let Some(value) = macros.get(name) else {
+            let defined = if macros.is_empty() {
+                "none are defined".to_string()
+            } else {
+                let names: Vec<&str> = macros.keys().map(String::as_str).collect();
+                format!("defined: {}", names.join(", "))
+            };
+            return Err(ActionResult::fatal_with_hint(
+                "INVALID_ARGUMENT",
+                format!("unknown key macro '{token}'"),
+                format!(
+                    "add `{name} = \"...\"` under [macros] in {} ({defined})",
+                    crate::config::config_path().display()
+                ),
+            ));
+        };
+        let before = chords.len();
+        for step in value.split_whitespace() {
+            if step.len() > 1 && step.starts_with('@') {
+                return Err(ActionResult::fatal(
+                    "INVALID_ARGUMENT",
+                    format!("key macro '{token}' uses '{step}'; macros cannot use other macros"),
+                ));
+            }
+            chords.push(step.to_string());
+        }
+        if chords.len() == before {
+            return Err(ActionResult::fatal(
+                "INVALID_ARGUMENT",
+                format!("key macro '{token}' is empty"),
+            ));
+        }
+    }
+    if chords.is_empty() {
+        return Err(ActionResult::fatal("INVALID_ARGUMENT", "no key to press"));
+    }
+    Ok(chords)
+}
+
 /// Map a key name to the CDP `key` value.
 /// Single printable characters with modifiers are lowercased.
 fn cdp_key(key: &str, has_modifiers: bool) -> String {
+    // `Space` spells the space key inside a sequence
+    if key.eq_ignore_ascii_case("space") {
+        return " ".to_string();
+    }
     // Single character keys
     if key.len() == 1 {
         let ch = key.chars().next().unwrap();
@@ -268,14 +348,18 @@ pub async fn execute(cmd: &Cmd, registry: &SharedRegistry) -> ActionResult {
         Err(e) => return e,
     };
 
-    // Parse the key-or-chord
-    let (modifiers, main_key) = match parse_chord(&cmd.key) {
+    // Parse every chord first so a typo late in a sequence presses nothing
+    let sequence = match key_sequence(&cmd.key) {
         Ok(v) => v,
         Err(e) => return e,
     };
-
-    let key = cdp_key(&main_key, modifiers != 0);
-    let def = key_definition(&key);
+    let mut chords = Vec::with_capacity(sequence.len());
+    for chord in &sequence {
+        match parse_chord(chord) {
+            Ok(v) => chords.push(v),
+            Err(e) => return e,
+        }
+    }
 
     // Chrome routes Input.dispatchKeyEvent to the active (foreground) tab,
     // ignoring the CDP sessionId.  Activate our target tab first so key
@@ -284,6 +368,38 @@ pub async fn execute(cmd: &Cmd, registry: &SharedRegistry) -> ActionResult {
         .execute_browser("Target.activateTarget", json!({ "targetId": target_id }))
         .await;
 
+    for (modifiers, main_key) in &chords {
+        if let Err(e) = press_chord(&cdp, &target_id, *modifiers, main_key).await {
+            return e;
+        }
+    }
+
+    let url = navigation::get_tab_url(&cdp, &target_id).await;
+    let title = navigation::get_tab_title(&cdp, &target_id).await;
+
+    ActionResult::ok(json!({
+        "action": "press",
+        "keys": cmd.key,
+        "sequence": sequence,
+        "changed": {
+            "url_changed": false,
+            "focus_changed": false,
+        },
+        "post_url": url,
+        "post_title": title,
+    }))
+}
+
+/// Send keyDown and keyUp for one chord.
+async fn press_chord(
+    cdp: &CdpSession,
+    target_id: &str,
+    modifiers: u32,
+    main_key: &str,
+) -> Result<(), ActionResult> {
+    let key = cdp_key(main_key, modifiers != 0);
+    let def = key_definition(&key);
+
     // Chrome CDP dispatches key events with specific type semantics:
     //   - "keyDown" with `text`: generates both keydown + keypress DOM events
     //     and triggers native behaviours (form submit, focus switch, etc.)
@@ -323,12 +439,9 @@ pub async fn execute(cmd: &Cmd, registry: &SharedRegistry) -> ActionResult {
         key_down["unmodifiedText"] = json!(text);
     }
 
-    if let Err(e) = cdp
-        .execute_on_tab(&target_id, "Input.dispatchKeyEvent", key_down)
+    cdp.execute_on_tab(target_id, "Input.dispatchKeyEvent", key_down)
         .await
-    {
-        return cdp_error_to_result(e, "CDP_ERROR");
-    }
+        .map_err(|e| cdp_error_to_result(e, "CDP_ERROR"))?;
 
     // keyUp
     let mut key_up = json!({
@@ -342,24 +455,47 @@ pub async fn execute(cmd: &Cmd, registry: &SharedRegistry) -> ActionResult {
         key_up["nativeVirtualKeyCode"] = json!(d.key_code);
     }
 
-    if let Err(e) = cdp
-        .execute_on_tab(&target_id, "Input.dispatchKeyEvent", key_up)
+    cdp.execute_on_tab(target_id, "Input.dispatchKeyEvent", key_up)
         .await
-    {
-        return cdp_error_to_result(e, "CDP_ERROR");
-    }
+        .map_err(|e| cdp_error_to_result(e, "CDP_ERROR"))?;
 
-    let url = navigation::get_tab_url(&cdp, &target_id).await;
-    let title = navigation::get_tab_title(&cdp, &target_id).await;
+    Ok(())
+}
 
-    ActionResult::ok(json!({
-        "action": "press",
-        "keys": cmd.key,
-        "changed": {
-            "url_changed": false,
-            "focus_changed": false,
-        },
-        "post_url": url,
-        "post_title": title,
-    }))
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn expand_macros_splits_sequences_and_rejects_unknown_or_nested_macros() {
+        let macros = BTreeMap::from([
+            ("save".to_string(), "Control+S".to_string()),
+            ("next_field".to_string(), "Tab  Tab Enter".to_string()),
+            ("loop".to_string(), "@save".to_string()),
+            ("blank".to_string(), " ".to_string()),
+        ]);
+        let expand = |keys: &str| expand_macros(keys, &macros).map_err(|e| format!("{e:?}"));
+
+        assert_eq!(expand("Enter").unwrap(), vec!["Enter"]);
+        assert_eq!(expand("@save").unwrap(), vec!["Control+S"]);
+        assert_eq!(
+            expand("Escape @next_field").unwrap(),
+            vec!["Escape", "Tab", "Tab", "Enter"]
+        );
+        assert_eq!(expand("Shift+@ @").unwrap(), vec!["Shift+@", "@"]);
+
+        let err = expand("@missing").unwrap_err();
+        assert!(err.contains("unknown key macro '@missing'"), "{err}");
+        assert!(err.contains("blank, loop, next_field, save"), "{err}");
+        assert!(
+            expand("@loop")
+                .unwrap_err()
+                .contains("cannot use other macros")
+        );
+        assert!(expand("@blank").unwrap_err().contains("is empty"));
+        assert!(expand("").is_err());
+
+        assert_eq!(key_sequence("   ").unwrap(), vec![" "]);
+        assert_eq!(cdp_key("Space", false), " ");
+    }
 }
@@ -0,0 +1,251 @@
+//! `browser record start` / `browser record stop` commands.
+//!
+//! Records what a person does in the tab (clicks, field values, selections,
+//! Enter presses and navigations) and writes it as a script for
+//! `actionbook run`. Recording is per-tab, like HAR recording.
+
+use std::path::PathBuf;
+
+use clap::Args;
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+
+use crate::action_result::ActionResult;
+use crate::browser::navigation;
+use crate::daemon::cdp_session::{CdpSession, get_cdp_and_target};
+use crate::daemon::flow_recording::{self, FlowRecorder};
+use crate::daemon::registry::SharedRegistry;
+use crate::output::ResponseContext;
+use crate::types::Mode;
+
+// ── Start ─────────────────────────────────────────────────────────────────────
+
+/// Start recording user input in a tab as a run script.
+#[derive(Args, Debug, Clone, Serialize, Deserialize)]
+#[command(after_help = "\
+Examples:
+  actionbook browser record start --out flow.yaml --session s1 --tab t1
+  actionbook browser record stop --session s1 --tab t1
+  actionbook run flow.yaml --session s1 --tab t1
+
+Works with local, cloud and extension sessions; in an extension session the
+extension records the tab of your own Chrome. Until `record stop`, every
+click, committed field value, dropdown choice and Enter press in the tab is
+captured with a selector for its element, along with navigations that no
+input caused. Use the browser as usual in between; new pages are covered too.
+
+Password-like fields are recorded without their value: fill it in before
+running the script.")]
+pub struct StartCmd {
+    /// Script to write on stop (.yaml/.yml or .json)
+    #[arg(long)]
+    pub out: String,
+    /// Session ID
+    #[arg(long)]
+    #[serde(rename = "session_id")]
+    pub session: String,
+    /// Tab ID
+    #[arg(long)]
+    #[serde(rename = "tab_id")]
+    pub tab: String,
+}
+
+pub const START_COMMAND_NAME: &str = "browser record start";
+
+fn tab_context(session: &str, tab: &str, result: &ActionResult) -> Option<ResponseContext> {
+    if let ActionResult::Fatal { code, .. } = result
+        && code == "SESSION_NOT_FOUND"
+    {
+        return None;
+    }
+    let tab_id = if let ActionResult::Fatal { code, .. } = result
+        && code == "TAB_NOT_FOUND"
+    {
+        None
+    } else {
+        Some(tab.to_string())
+    };
+    Some(ResponseContext {
+        session_id: session.to_string(),
+        tab_id,
+        window_id: None,
+        url: None,
+        title: None,
+    })
+}
+
+pub fn start_context(cmd: &StartCmd, result: &ActionResult) -> Option<ResponseContext> {
+    tab_context(&cmd.session, &cmd.tab, result)
+}
+
+/// The tab's CDP session, target and flat-session key, and whether it is
+/// driven through the extension bridge.
+async fn tab_session(
+    registry: &SharedRegistry,
+    session: &str,
+    tab: &str,
+) -> Result<(CdpSession, String, String, bool), ActionResult> {
+    let (cdp, target_id) = get_cdp_and_target(registry, session, tab).await?;
+    let Some(cdp_session_id) = cdp.get_cdp_session_id(&target_id).await else {
+        return Err(ActionResult::fatal(
+            "INTERNAL_ERROR",
+            format!("no CDP session for tab '{tab}' (target {target_id})"),
+        ));
+    };
+    let extension = registry
+        .lock()
+        .await
+        .get(session)
+        .is_some_and(|e| e.mode == Mode::Extension);
+    Ok((cdp, target_id, cdp_session_id, extension))
+}
+
+pub async fn execute_start(cmd: &StartCmd, registry: &SharedRegistry) -> ActionResult {
+    let (cdp, target_id, cdp_session_id, extension) =
+        match tab_session(registry, &cmd.session, &cmd.tab).await {
+            Ok(v) => v,
+            Err(e) => return e,
+        };
+
+    let url = navigation::get_tab_url(&cdp, &target_id).await;
+    let recorder = std::sync::Arc::new(std::sync::Mutex::new(FlowRecorder::new(
+        PathBuf::from(&cmd.out),
+        url.clone(),
+    )));
+    if !cdp
+        .start_flow_recording(&cdp_session_id, recorder.clone())
+        .await
+    {
+        return ActionResult::fatal_with_hint(
+            "RECORDING_ACTIVE",
+            format!("tab '{}' is already being recorded", cmd.tab),
+            "run `actionbook browser record stop` first",
+        );
+    }
+    let installed = if extension {
+        flow_recording::install_extension(&cdp, &target_id, &cdp_session_id, recorder).await
+    } else {
+        flow_recording::install(&cdp, &target_id, &cdp_session_id, recorder).await
+    };
+    if let Err(e) = installed {
+        cdp.take_flow_recording(&cdp_session_id).await;
+        let message = format!("failed to start recording: {e}");
+        if extension && message.contains("Unknown extension method") {
+            return ActionResult::fatal_with_hint(
+                "UNSUPPORTED_OPERATION",
+                message,
+                "update the Actionbook extension (`actionbook extension version --update`)",
+            );
+        }
+        return ActionResult::fatal("CDP_ERROR", message);
+    }
+
+    ActionResult::ok(json!({
+        "recording": true,
+        "out": cmd.out,
+        "url": url,
+    }))
+}
+
+// ── Stop ──────────────────────────────────────────────────────────────────────
+
+/// Stop recording and write the run script.
+#[derive(Args, Debug, Clone, Serialize, Deserialize)]
+#[command(after_help = "\
+Examples:
+  actionbook browser record stop --session s1 --tab t1
+  actionbook browser record stop --out other.json --session s1 --tab t1
+
+Writes the script to the --out given at start unless another is passed here.")]
+pub struct StopCmd {
+    /// Write the script here instead
+    #[arg(long)]
+    pub out: Option<String>,
+    /// Session ID
+    #[arg(long)]
+    #[serde(rename = "session_id")]
+    pub session: String,
+    /// Tab ID
+    #[arg(long)]
+    #[serde(rename = "tab_id")]
+    pub tab: String,
+}
+
+pub const STOP_COMMAND_NAME: &str = "browser record stop";
+
+pub fn stop_context(cmd: &StopCmd, result: &ActionResult) -> Option<ResponseContext> {
+    tab_context(&cmd.session, &cmd.tab, result)
+}
+
+pub async fn execute_stop(cmd: &StopCmd, registry: &SharedRegistry) -> ActionResult {
+    let (cdp, target_id, cdp_session_id, extension) =
+        match tab_session(registry, &cmd.session, &cmd.tab).await {
+            Ok(v) => v,
+            Err(e) => return e,
+        };
+    let Some(recorder) = cdp.take_flow_recording(&cdp_session_id).await else {
+        return ActionResult::fatal_with_hint(
+            "RECORDING_NOT_ACTIVE",
+            format!("tab '{}' is not being recorded", cmd.tab),
+            "start one with `actionbook browser record start --out flow.yaml`",
+        );
+    };
+
+    let (out, steps, inputs, script_id) = {
+        let mut rec = recorder.lock().unwrap_or_else(|e| e.into_inner());
+        rec.stop();
+        let out = cmd
+            .out
+            .as_ref()
+            .map(PathBuf::from)
+            .unwrap_or(rec.out.clone());
+        (out, rec.steps(), rec.inputs(), rec.script_id.clone())
+    };
+
+    // Best effort: the page keeps its listeners until it navigates, but with
+    // the binding gone (or the extension told to stop) they report nowhere.
+    if extension {
+        let _ = flow_recording::uninstall_extension(&cdp, &target_id).await;
+    } else if let Some(identifier) = script_id {
+        let _ = cdp
+            .execute_on_tab(
+                &target_id,
+                "Page.removeScriptToEvaluateOnNewDocument",
+                json!({ "identifier": identifier }),
+            )
+            .await;
+    }
+    if !extension {
+        let _ = cdp
+            .execute_on_tab(
+                &target_id,
+                "Runtime.removeBinding",
+                json!({ "name": flow_recording::BINDING }),
+            )
+            .await;
+    }
+
+    let count = steps.len();
+    let script = flow_recording::render(steps, &out);
+    if let Some(parent) = out.parent()
+        && !parent.as_os_str().is_empty()
+        && let Err(e) = std::fs::create_dir_all(parent)
+    {
+        return ActionResult::fatal(
+            "IO_ERROR",
+            format!("failed to create {}: {e}", parent.display()),
+        );
+    }
+    if let Err(e) = std::fs::write(&out, script) {
+        return ActionResult::fatal(
+            "IO_ERROR",
+            format!("failed to write {}: {e}", out.display()),
+        );
+    }
+
+    ActionResult::ok(json!({
+        "path": out.to_string_lossy(),
+        "steps": count,
+        "inputs": inputs,
+    }))
+}
@@ -15,19 +15,31 @@ use crate::output::ResponseContext;
 Examples:
   actionbook browser scroll down 500 --session s1 --tab t1
   actionbook browser scroll up 200 --session s1 --tab t1
+  actionbook browser scroll page-down --session s1 --tab t1
   actionbook browser scroll top --session s1 --tab t1
   actionbook browser scroll bottom --session s1 --tab t1
   actionbook browser scroll into-view \"#footer\" --session s1 --tab t1
   actionbook browser scroll into-view @e8 --session s1 --tab t1
   actionbook browser scroll down 300 --container \"#sidebar\" --session s1 --tab t1
+  actionbook browser scroll --by 0,800 --session s1 --tab t1
+  actionbook browser scroll --bottom --session s1 --tab t1
 
-Directions: up, down, left, right (with pixel amount), top, bottom (jump to edge).
+Directions: up, down, left, right (with pixel amount), page-up, page-down (one
+viewport, keeping a little overlap), top, bottom (jump to edge).
 Use into-view with a selector or snapshot ref (@eN) to scroll an element into the viewport.
 Use --container to scroll within a specific scrollable element.
-Use --align with into-view to control alignment (start, center, end, nearest).")]
+Use --align with into-view to control alignment (start, center, end, nearest).
+--to, --by X,Y, --bottom and --page-down are flag forms of into-view, a pixel
+delta, bottom and page-down.
+
+Scrolling is done with mouse-wheel events over the scroller, in viewport-sized
+steps for top/bottom, so scroll listeners and lazy loaders fire as they would
+for a user; infinite-scroll pages load more on each `scroll bottom`. When the
+wheel cannot reach the scroller (it is covered, off-screen or inside an
+iframe) the position is set from script instead; data.method says which.")]
 pub struct Cmd {
-    /// Direction or action: up, down, left, right, top, bottom, into-view
-    pub direction: String,
+    /// Direction or action: up, down, left, right, page-up, page-down, top, bottom, into-view
+    pub direction: Option<String>,
     /// Pixels (for directional) or selector/ref (for into-view)
     pub value: Option<String>,
     /// Session ID
@@ -44,6 +56,22 @@ pub struct Cmd {
     /// Alignment for into-view (start, center, end, nearest)
     #[arg(long)]
     pub align: Option<String>,
+    /// Scroll this element into view (same as into-view)
+    #[arg(long, value_name = "SELECTOR")]
+    #[serde(default)]
+    pub to: Option<String>,
+    /// Scroll by X,Y pixels (negative scrolls up/left)
+    #[arg(long, value_name = "X,Y", allow_hyphen_values = true)]
+    #[serde(default)]
+    pub by: Option<String>,
+    /// Scroll to the bottom (same as bottom)
+    #[arg(long)]
+    #[serde(default)]
+    pub bottom: bool,
+    /// Scroll down one viewport (same as page-down)
+    #[arg(long)]
+    #[serde(default)]
+    pub page_down: bool,
 }
 
 pub const COMMAND_NAME: &str = "browser scroll";
@@ -78,17 +106,73 @@ pub fn context(cmd: &Cmd, result: &ActionResult) -> Option<ResponseContext> {
 
 enum ScrollMode {
     Directional { direction: String, pixels: i64 },
+    By { dx: i64, dy: i64 },
+    Page { direction: String },
     Edge { direction: String },
     IntoView { selector: String, align: String },
 }
 
+/// Give up wheeling towards an edge after this many viewport-sized steps and
+/// jump the rest of the way.
+const MAX_EDGE_STEPS: usize = 30;
+/// Longest wait for a wheel scroll to land.
+const WHEEL_SETTLE_MS: u64 = 1_000;
+
 fn parse_scroll_mode(cmd: &Cmd) -> Result<ScrollMode, ActionResult> {
-    match cmd.direction.as_str() {
+    let flags = [
+        cmd.to.is_some(),
+        cmd.by.is_some(),
+        cmd.bottom,
+        cmd.page_down,
+    ]
+    .iter()
+    .filter(|f| **f)
+    .count();
+    if flags > 1 || (flags == 1 && cmd.direction.is_some()) {
+        return Err(ActionResult::fatal(
+            "INVALID_ARGUMENT",
+            "pass one of a direction, --to, --by, --bottom or --page-down",
+        ));
+    }
+    if let Some(selector) = &cmd.to {
+        return Ok(ScrollMode::IntoView {
+            selector: selector.clone(),
+            align: parse_align(cmd)?,
+        });
+    }
+    if let Some(by) = &cmd.by {
+        let (dx, dy) = parse_delta(by).ok_or_else(|| {
+            ActionResult::fatal(
+                "INVALID_ARGUMENT",
+                format!("invalid --by value: '{by}', expected X,Y pixels"),
+            )
+        })?;
+        return Ok(ScrollMode::By { dx, dy });
+    }
+    if cmd.bottom {
+        return Ok(ScrollMode::Edge {
+            direction: "bottom".to_string(),
+        });
+    }
+    if cmd.page_down {
+        return Ok(ScrollMode::Page {
+            direction: "page-down".to_string(),
+        });
+    }
+
+    let Some(direction) = cmd.direction.as_deref() else {
+        return Err(ActionResult::fatal_with_hint(
+            "INVALID_ARGUMENT",
+            "missing scroll direction",
+            "pass up|down|left|right <px>, page-up, page-down, top, bottom, into-view <selector>, or --to/--by/--bottom/--page-down",
+        ));
+    };
+    match direction {
         "up" | "down" | "left" | "right" => {
             let pixels_str = cmd.value.as_deref().ok_or_else(|| {
                 ActionResult::fatal(
                     "INVALID_ARGUMENT",
-                    format!("'{}' requires a pixel amount", cmd.direction),
+                    format!("'{direction}' requires a pixel amount"),
                 )
             })?;
             let pixels = pixels_str.parse::<i64>().map_err(|_| {
@@ -98,38 +182,51 @@ fn parse_scroll_mode(cmd: &Cmd) -> Result<ScrollMode, ActionResult> {
                 )
             })?;
             Ok(ScrollMode::Directional {
-                direction: cmd.direction.clone(),
+                direction: direction.to_string(),
                 pixels,
             })
         }
+        "page-up" | "page-down" => Ok(ScrollMode::Page {
+            direction: direction.to_string(),
+        }),
         "top" | "bottom" => Ok(ScrollMode::Edge {
-            direction: cmd.direction.clone(),
+            direction: direction.to_string(),
         }),
         "into-view" => {
             let selector = cmd.value.as_deref().ok_or_else(|| {
                 ActionResult::fatal("INVALID_ARGUMENT", "into-view requires a selector")
             })?;
-            let align = cmd.align.as_deref().unwrap_or("nearest").to_string();
-            if !matches!(align.as_str(), "start" | "center" | "end" | "nearest") {
-                return Err(ActionResult::fatal(
-                    "INVALID_ARGUMENT",
-                    format!("invalid align value: '{align}', expected start|center|end|nearest"),
-                ));
-            }
             Ok(ScrollMode::IntoView {
                 selector: selector.to_string(),
-                align,
+                align: parse_align(cmd)?,
             })
         }
         other => Err(ActionResult::fatal(
             "INVALID_ARGUMENT",
             format!(
-                "invalid scroll direction: '{other}', expected up|down|left|right|top|bottom|into-view"
+                "invalid scroll direction: '{other}', expected up|down|left|right|page-up|page-down|top|bottom|into-view"
             ),
         )),
     }
 }
 
+fn parse_align(cmd: &Cmd) -> Result<String, ActionResult> {
+    let align = cmd.align.as_deref().unwrap_or("nearest").to_string();
+    if !matches!(align.as_str(), "start" | "center" | "end" | "nearest") {
+        return Err(ActionResult::fatal(
+            "INVALID_ARGUMENT",
+            format!("invalid align value: '{align}', expected start|center|end|nearest"),
+        ));
+    }
+    Ok(align)
+}
+
+/// `X,Y` → pixel delta.
+fn parse_delta(raw: &str) -> Option<(i64, i64)> {
+    let (x, y) = raw.split_once(',')?;
+    Some((x.trim().parse().ok()?, y.trim().parse().ok()?))
+}
+
 pub async fn execute(cmd: &Cmd, registry: &SharedRegistry) -> ActionResult {
     let mode = match parse_scroll_mode(cmd) {
         Ok(m) => m,
@@ -170,39 +267,69 @@ pub async fn execute(cmd: &Cmd, registry: &SharedRegistry) -> ActionResult {
     .await;
 
     // Execute scroll
-    match &mode {
-        ScrollMode::Directional { direction, pixels } => {
-            if let Err(e) = scroll_directional(
-                &ctx.cdp,
-                &ctx.target_id,
-                direction,
-                *pixels,
-                container_object_id.as_deref(),
-                container_frame_id.as_deref(),
-            )
-            .await
-            {
-                return e;
+    let scroller = Scroller {
+        cdp: &ctx.cdp,
+        target_id: &ctx.target_id,
+        object_id: container_object_id.as_deref(),
+        frame_id: container_frame_id.as_deref(),
+    };
+    let mut outcome = json!({});
+    let result =
+        match &mode {
+            ScrollMode::Directional { direction, pixels } => {
+                let (dx, dy) = match direction.as_str() {
+                    "up" => (0, -pixels),
+                    "down" => (0, *pixels),
+                    "left" => (-pixels, 0),
+                    "right" => (*pixels, 0),
+                    _ => unreachable!(),
+                };
+                scroller
+                    .scroll_by(dx as f64, dy as f64)
+                    .await
+                    .map(|method| {
+                        outcome["method"] = json!(method);
+                    })
             }
-        }
-        ScrollMode::Edge { direction } => {
-            if let Err(e) = scroll_edge(
-                &ctx.cdp,
-                &ctx.target_id,
-                direction,
-                container_object_id.as_deref(),
-                container_frame_id.as_deref(),
-            )
-            .await
-            {
-                return e;
+            ScrollMode::By { dx, dy } => {
+                scroller
+                    .scroll_by(*dx as f64, *dy as f64)
+                    .await
+                    .map(|method| {
+                        outcome["method"] = json!(method);
+                    })
             }
-        }
-        ScrollMode::IntoView { selector, align } => {
-            if let Err(e) = scroll_into_view(&mut ctx, selector, align).await {
-                return e;
+            ScrollMode::Page { direction } => match scroller.metrics().await {
+                Ok(m) => {
+                    // Like the PageDown key: a viewport, less a little overlap.
+                    let pixels = (m.client_h * 0.875).round();
+                    let dy = if direction == "page-up" {
+                        -pixels
+                    } else {
+                        pixels
+                    };
+                    scroller.scroll_by(0.0, dy).await.map(|method| {
+                        outcome["method"] = json!(method);
+                        outcome["pixels"] = json!(pixels as i64);
+                    })
+                }
+                Err(e) => Err(e),
+            },
+            ScrollMode::Edge { direction } => scroller
+                .scroll_to_edge(direction == "bottom")
+                .await
+                .map(|(method, steps)| {
+                    outcome["method"] = json!(method);
+                    outcome["steps"] = json!(steps);
+                }),
+            ScrollMode::IntoView { selector, align } => {
+                scroll_into_view(&mut ctx, selector, align).await.map(|()| {
+                    outcome["method"] = json!("script");
+                })
             }
-        }
+        };
+    if let Err(e) = result {
+        return e;
     }
 
     // Post-scroll state
@@ -237,7 +364,10 @@ pub async fn execute(cmd: &Cmd, registry: &SharedRegistry) -> ActionResult {
             data["direction"] = json!(direction);
             data["pixels"] = json!(pixels);
         }
-        ScrollMode::Edge { direction } => {
+        ScrollMode::By { dx, dy } => {
+            data["delta"] = json!({ "x": dx, "y": dy });
+        }
+        ScrollMode::Page { direction } | ScrollMode::Edge { direction } => {
             data["direction"] = json!(direction);
         }
         ScrollMode::IntoView { selector, align } => {
@@ -246,6 +376,12 @@ pub async fn execute(cmd: &Cmd, registry: &SharedRegistry) -> ActionResult {
         }
     }
 
+    if let Some(map) = outcome.as_object() {
+        for (k, v) in map {
+            data[k] = v.clone();
+        }
+    }
+
     if let Some(ref container_sel) = cmd.container {
         data["container"] = json!(container_sel);
     }
@@ -262,66 +398,245 @@ async fn resolve_to_object_id(
     Ok(object_id)
 }
 
-/// Scroll by pixel amount in a given direction.
-async fn scroll_directional(
-    cdp: &CdpSession,
-    target_id: &str,
-    direction: &str,
-    pixels: i64,
-    container_object_id: Option<&str>,
-    frame_id: Option<&str>,
-) -> Result<(), ActionResult> {
-    let (dx, dy) = match direction {
-        "up" => (0, -pixels),
-        "down" => (0, pixels),
-        "left" => (-pixels, 0),
-        "right" => (pixels, 0),
-        _ => unreachable!(),
+/// Reads the scroller's position and size, and where a wheel event would
+/// reach it. Called with `this` bound to the scroller (the container, or
+/// `document.scrollingElement` for the page).
+const SCROLL_METRICS_JS: &str = r#"function(isPage) {
+    var s = this;
+    var r = isPage ? { left: 0, top: 0, width: innerWidth, height: innerHeight } : s.getBoundingClientRect();
+    var left = Math.max(r.left, 0), top = Math.max(r.top, 0);
+    var right = Math.min(r.left + r.width, innerWidth), bottom = Math.min(r.top + r.height, innerHeight);
+    var px = (left + right) / 2, py = (top + bottom) / 2;
+    var wheel = right > left && bottom > top;
+    if (wheel) {
+        // The wheel scrolls the innermost scrollable element under the point,
+        // so anything scrollable (or an iframe) in between would steal it.
+        var el = document.elementFromPoint(px, py);
+        for (; el && el !== s && el !== document.documentElement && el !== document.body; el = el.parentElement) {
+            var st = getComputedStyle(el);
+            if (el.tagName === 'IFRAME'
+                || (/(auto|scroll)/.test(st.overflowY) && el.scrollHeight > el.clientHeight)
+                || (/(auto|scroll)/.test(st.overflowX) && el.scrollWidth > el.clientWidth)) {
+                wheel = false;
+                break;
+            }
+        }
+        if (!isPage && el !== s) wheel = false;
+    }
+    return {
+        x: s.scrollLeft, y: s.scrollTop,
+        cw: s.clientWidth, ch: s.clientHeight,
+        sw: s.scrollWidth, sh: s.scrollHeight,
+        px: px, py: py, wheel: wheel
     };
+}"#;
 
-    if let Some(object_id) = container_object_id {
-        call_fn_on(
-            cdp,
-            target_id,
-            object_id,
-            &format!("function() {{ this.scrollBy({dx}, {dy}); return 'ok'; }}"),
-            frame_id,
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct Metrics {
+    x: f64,
+    y: f64,
+    client_w: f64,
+    client_h: f64,
+    scroll_w: f64,
+    scroll_h: f64,
+    /// Viewport point a wheel event over the scroller would use, if any.
+    wheel_at: Option<(f64, f64)>,
+}
+
+impl Metrics {
+    fn max_x(&self) -> f64 {
+        (self.scroll_w - self.client_w).max(0.0)
+    }
+
+    fn max_y(&self) -> f64 {
+        (self.scroll_h - self.client_h).max(0.0)
+    }
+
+    /// Where scrolling by `(dx, dy)` ends up, clamped to the scroll range.
+    fn target(&self, dx: f64, dy: f64) -> (f64, f64) {
+        (
+            (self.x + dx).clamp(0.0, self.max_x()),
+            (self.y + dy).clamp(0.0, self.max_y()),
         )
-        .await
-    } else {
-        eval_js(
-            cdp,
-            target_id,
-            &format!("(() => {{ window.scrollBy({dx}, {dy}); return 'ok'; }})()"),
+    }
+}
+
+/// The page or a `--container` element being scrolled.
+struct Scroller<'a> {
+    cdp: &'a CdpSession,
+    target_id: &'a str,
+    object_id: Option<&'a str>,
+    frame_id: Option<&'a str>,
+}
+
+impl Scroller<'_> {
+    /// Run `function_declaration` with `this` bound to the scroller.
+    async fn call(
+        &self,
+        function_declaration: &str,
+        args: serde_json::Value,
+    ) -> Result<serde_json::Value, ActionResult> {
+        let resp = match self.object_id {
+            Some(object_id) => {
+                let arguments: Vec<_> = args
+                    .as_array()
+                    .into_iter()
+                    .flatten()
+                    .map(|a| json!({ "value": a }))
+                    .collect();
+                crate::browser::element::execute_for_frame(
+                    self.cdp,
+                    self.target_id,
+                    self.frame_id,
+                    "Runtime.callFunctionOn",
+                    json!({
+                        "objectId": object_id,
+                        "functionDeclaration": function_declaration,
+                        "arguments": arguments,
+                        "returnByValue": true,
+                    }),
+                )
+                .await
+            }
+            None => {
+                self.cdp
+                    .execute_on_tab(
+                        self.target_id,
+                        "Runtime.evaluate",
+                        json!({
+                            "expression": format!(
+                                "({function_declaration}).apply(document.scrollingElement || document.documentElement, {args})"
+                            ),
+                            "returnByValue": true,
+                        }),
+                    )
+                    .await
+            }
+        }
+        .map_err(|e| cdp_error_to_result(e, "CDP_ERROR"))?;
+        Ok(resp
+            .pointer("/result/result/value")
+            .cloned()
+            .unwrap_or_default())
+    }
+
+    async fn metrics(&self) -> Result<Metrics, ActionResult> {
+        let v = self
+            .call(SCROLL_METRICS_JS, json!([self.object_id.is_none()]))
+            .await?;
+        let f = |k: &str| v.get(k).and_then(|v| v.as_f64()).unwrap_or(0.0);
+        // Wheel coordinates are in the top-level viewport; a container
+        // inside an iframe reports frame coordinates, so script it instead.
+        let wheel =
+            v.get("wheel").and_then(|v| v.as_bool()).unwrap_or(false) && self.frame_id.is_none();
+        Ok(Metrics {
+            x: f("x"),
+            y: f("y"),
+            client_w: f("cw"),
+            client_h: f("ch"),
+            scroll_w: f("sw"),
+            scroll_h: f("sh"),
+            wheel_at: wheel.then(|| (f("px"), f("py"))),
+        })
+    }
+
+    async fn set_position(&self, x: f64, y: f64) -> Result<(), ActionResult> {
+        self.call(
+            "function(x, y) { this.scrollLeft = x; this.scrollTop = y; }",
+            json!([x, y]),
         )
         .await
+        .map(|_| ())
     }
-}
 
-/// Scroll to top or bottom edge.
-async fn scroll_edge(
-    cdp: &CdpSession,
-    target_id: &str,
-    direction: &str,
-    container_object_id: Option<&str>,
-    frame_id: Option<&str>,
-) -> Result<(), ActionResult> {
-    if let Some(object_id) = container_object_id {
-        let fn_body = match direction {
-            "top" => "function() { this.scrollTop = 0; this.scrollLeft = 0; return 'ok'; }",
-            "bottom" => "function() { this.scrollTop = this.scrollHeight; return 'ok'; }",
-            _ => unreachable!(),
+    async fn wheel(&self, (x, y): (f64, f64), dx: f64, dy: f64) -> Result<(), ActionResult> {
+        self.cdp
+            .execute_on_tab(
+                self.target_id,
+                "Input.dispatchMouseEvent",
+                json!({ "type": "mouseWheel", "x": x, "y": y, "deltaX": dx, "deltaY": dy }),
+            )
+            .await
+            .map_err(|e| cdp_error_to_result(e, "CDP_ERROR"))?;
+        Ok(())
+    }
+
+    /// Scroll by a delta with one wheel event, returning `"wheel"` or
+    /// `"script"`. The wheel is applied asynchronously (and may be smooth),
+    /// so wait for the position to land; if it stops short it is finished
+    /// from script, and if the wheel moved nothing the whole delta is.
+    async fn scroll_by(&self, dx: f64, dy: f64) -> Result<&'static str, ActionResult> {
+        let before = self.metrics().await?;
+        let target = before.target(dx, dy);
+        if target == (before.x, before.y) {
+            return Ok(if before.wheel_at.is_some() {
+                "wheel"
+            } else {
+                "script"
+            });
+        }
+        let Some(point) = before.wheel_at else {
+            self.set_position(target.0, target.1).await?;
+            return Ok("script");
         };
-        call_fn_on(cdp, target_id, object_id, fn_body, frame_id).await
-    } else {
-        let js = match direction {
-            "top" => "(() => { window.scrollTo(0, 0); return 'ok'; })()",
-            "bottom" => {
-                "(() => { window.scrollTo(0, document.documentElement.scrollHeight); return 'ok'; })()"
+
+        self.wheel(point, dx, dy).await?;
+        let deadline =
+            std::time::Instant::now() + std::time::Duration::from_millis(WHEEL_SETTLE_MS);
+        let mut last = (before.x, before.y);
+        let mut still = 0;
+        loop {
+            tokio::time::sleep(std::time::Duration::from_millis(25)).await;
+            let m = self.metrics().await?;
+            let pos = (m.x, m.y);
+            if (pos.0 - target.0).abs() < 1.0 && (pos.1 - target.1).abs() < 1.0 {
+                return Ok("wheel");
             }
-            _ => unreachable!(),
-        };
-        eval_js(cdp, target_id, js).await
+            still = if pos == last { still + 1 } else { 0 };
+            last = pos;
+            if std::time::Instant::now() >= deadline || (still >= 4 && pos != (before.x, before.y))
+            {
+                break;
+            }
+        }
+        let moved = last != (before.x, before.y);
+        self.set_position(target.0, target.1).await?;
+        Ok(if moved { "wheel" } else { "script" })
+    }
+
+    /// Wheel one viewport at a time to the top or bottom edge. The scroll
+    /// height is re-read every step, so content appended by lazy loaders
+    /// along the way is scrolled through too (up to `MAX_EDGE_STEPS`).
+    /// Returns the method and the number of steps.
+    async fn scroll_to_edge(&self, bottom: bool) -> Result<(&'static str, usize), ActionResult> {
+        let mut method = "script";
+        let mut steps = 0;
+        while steps < MAX_EDGE_STEPS {
+            let m = self.metrics().await?;
+            let at_edge = if bottom {
+                m.y >= m.max_y() - 1.0
+            } else {
+                m.y <= 0.0
+            };
+            if at_edge || m.wheel_at.is_none() {
+                break;
+            }
+            let dy = m.client_h.max(1.0);
+            method = self.scroll_by(0.0, if bottom { dy } else { -dy }).await?;
+            steps += 1;
+            if method == "script" {
+                break;
+            }
+        }
+        // Land exactly on the edge (and jump the rest when steps ran out or
+        // the wheel could not be used).
+        let m = self.metrics().await?;
+        let edge = if bottom { m.max_y() } else { 0.0 };
+        let x = if bottom { m.x } else { 0.0 };
+        if (m.y - edge).abs() >= 1.0 || x != m.x {
+            self.set_position(x, edge).await?;
+        }
+        Ok((method, steps))
     }
 }
 
@@ -376,21 +691,6 @@ async fn call_fn_on(
     Ok(())
 }
 
-/// Evaluate JS and check the result.
-async fn eval_js(cdp: &CdpSession, target_id: &str, expression: &str) -> Result<(), ActionResult> {
-    cdp.execute_on_tab(
-        target_id,
-        "Runtime.evaluate",
-        json!({
-            "expression": expression,
-            "returnByValue": true,
-        }),
-    )
-    .await
-    .map_err(|e| cdp_error_to_result(e, "CDP_ERROR"))?;
-    Ok(())
-}
-
 /// Get scroll position for change detection.
 async fn get_scroll_position(
     cdp: &CdpSession,
@@ -459,3 +759,32 @@ async fn get_active_element_id(cdp: &CdpSession, target_id: &str) -> String {
     })
     .unwrap_or_default()
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_delta_reads_signed_pairs() {
+        assert_eq!(parse_delta("0,800"), Some((0, 800)));
+        assert_eq!(parse_delta("-20, -300"), Some((-20, -300)));
+        assert_eq!(parse_delta("800"), None);
+        assert_eq!(parse_delta("a,1"), None);
+    }
+
+    #[test]
+    fn metrics_target_clamps_to_the_scroll_range() {
+        let m = Metrics {
+            x: 0.0,
+            y: 900.0,
+            client_w: 800.0,
+            client_h: 600.0,
+            scroll_w: 800.0,
+            scroll_h: 2000.0,
+            wheel_at: None,
+        };
+        assert_eq!(m.target(0.0, 300.0), (0.0, 1200.0));
+        assert_eq!(m.target(0.0, 900.0), (0.0, 1400.0));
+        assert_eq!(m.target(-50.0, -1000.0), (0.0, 0.0));
+    }
+}
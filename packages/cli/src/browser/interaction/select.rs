@@ -16,16 +16,21 @@ Examples:
   actionbook browser select \"#country\" \"us\" --session s1 --tab t1
   actionbook browser select @e7 \"United States\" --by-text --session s1 --tab t1
   actionbook browser select \"#country\" @e12 --by-ref --session s1 --tab t1
+  actionbook browser select \"#country\" --index 2 --session s1 --tab t1
 
 Accepts a CSS selector, XPath, or snapshot ref (@eN from snapshot output).
 Selects an option in a <select> element by its value attribute.
 Use --by-text to match the visible display text instead.
-Use --by-ref to select an option by its snapshot ref (@eN).")]
+Use --by-ref to select an option by its snapshot ref (@eN).
+Use --index to select the Nth option (0-based) when values are opaque ids;
+`browser options` lists them.")]
 pub struct Cmd {
     /// Selector for <select> element (CSS, XPath, or @ref)
     pub selector: String,
     /// Value to select (option value, display text with --by-text, or @ref with --by-ref)
-    pub value: String,
+    #[arg(required_unless_present = "index")]
+    #[serde(default)]
+    pub value: Option<String>,
     /// Session ID
     #[arg(long)]
     #[serde(rename = "session_id")]
@@ -42,6 +47,10 @@ pub struct Cmd {
     #[arg(long)]
     #[serde(default)]
     pub by_ref: bool,
+    /// Select the option at this 0-based position instead of matching a value
+    #[arg(long, conflicts_with_all = ["value", "by_text", "by_ref"])]
+    #[serde(default)]
+    pub index: Option<usize>,
 }
 
 pub const COMMAND_NAME: &str = "browser select";
@@ -81,6 +90,19 @@ pub async fn execute(cmd: &Cmd, registry: &SharedRegistry) -> ActionResult {
             "--by-text and --by-ref are mutually exclusive",
         );
     }
+    let value = match (&cmd.value, cmd.index) {
+        (Some(_), Some(_)) => {
+            return ActionResult::fatal(
+                "INVALID_ARGUMENT",
+                "a value and --index are mutually exclusive",
+            );
+        }
+        (Some(value), None) => value.as_str(),
+        (None, Some(_)) => "",
+        (None, None) => {
+            return ActionResult::fatal("INVALID_ARGUMENT", "a value or --index is required");
+        }
+    };
 
     let mut ctx = match TabContext::new(registry, &cmd.session, &cmd.tab).await {
         Ok(v) => v,
@@ -99,9 +121,24 @@ pub async fn execute(cmd: &Cmd, registry: &SharedRegistry) -> ActionResult {
     }
 
     // Build JS function + arguments based on mode
-    let (fn_decl, call_args) = if cmd.by_ref {
+    let (fn_decl, call_args) = if let Some(index) = cmd.index {
+        (
+            format!(
+                r#"function() {{
+                    if (this.tagName !== 'SELECT') return 'not a select element';
+                    const opt = this.options[{index}];
+                    if (!opt) return JSON.stringify({{ status: 'index out of range', total: this.options.length }});
+                    this.value = opt.value;
+                    this.dispatchEvent(new Event('input', {{ bubbles: true }}));
+                    this.dispatchEvent(new Event('change', {{ bubbles: true }}));
+                    return JSON.stringify({{ status: 'ok', value: opt.value }});
+                }}"#
+            ),
+            json!([]),
+        )
+    } else if cmd.by_ref {
         // Resolve option ref → pass the element directly via CDP arguments
-        let (_opt_node_id, opt_object_id) = match ctx.resolve_object(&cmd.value).await {
+        let (_opt_node_id, opt_object_id) = match ctx.resolve_object(value).await {
             Ok(v) => v,
             Err(e) => return e,
         };
@@ -119,7 +156,7 @@ pub async fn execute(cmd: &Cmd, registry: &SharedRegistry) -> ActionResult {
             json!([{ "objectId": opt_object_id }]),
         )
     } else {
-        let value_json = serde_json::to_string(&cmd.value).unwrap_or_default();
+        let value_json = serde_json::to_string(value).unwrap_or_default();
         let by_text = cmd.by_text;
         (
             format!(
@@ -166,55 +203,65 @@ pub async fn execute(cmd: &Cmd, registry: &SharedRegistry) -> ActionResult {
         .and_then(|v| v.as_str())
         .unwrap_or("");
 
+    let mut selected_value = value.to_string();
     match result_str {
         "ok" => {}
         "option not found" => {
             // Fallback: JS now returns JSON; this arm kept as defensive safety net.
-            return ActionResult::fatal(
-                "INVALID_ARGUMENT",
-                format!("option not found: '{}'", cmd.value),
-            );
+            return ActionResult::fatal("INVALID_ARGUMENT", format!("option not found: '{value}'"));
         }
         other if other.starts_with('{') => {
-            if let Ok(diag) = serde_json::from_str::<serde_json::Value>(other)
-                && diag["status"].as_str() == Some("option not found")
-            {
-                let mode = diag["mode"].as_str().unwrap_or("by-value").to_string();
-                let total = diag["total"].as_u64().unwrap_or(0);
-                let values = diag["values"].clone();
-                let texts = diag["texts"].clone();
-                let message = format!(
-                    "option not found: '{}'. Mode: {}. Total options: {}. Values: {}. Texts: {}",
-                    cmd.value, mode, total, values, texts,
-                );
-                return ActionResult::fatal_with_details(
-                    "INVALID_ARGUMENT",
-                    message,
-                    "check the available values and texts above, or use --by-text to match display text",
-                    json!({
-                        "status": "option not found",
-                        "mode": mode,
-                        "total": total,
-                        "values": values,
-                        "texts": texts,
-                    }),
-                );
+            let diag = serde_json::from_str::<serde_json::Value>(other).unwrap_or_default();
+            match diag["status"].as_str() {
+                Some("ok") => {
+                    selected_value = diag["value"].as_str().unwrap_or_default().to_string();
+                }
+                Some("index out of range") => {
+                    let total = diag["total"].as_u64().unwrap_or(0);
+                    return ActionResult::fatal_with_hint(
+                        "INVALID_ARGUMENT",
+                        format!(
+                            "option index {} is out of range: the <select> has {total} options",
+                            cmd.index.unwrap_or_default()
+                        ),
+                        "run `actionbook browser options` to list the available options",
+                    );
+                }
+                Some("option not found") => {
+                    let mode = diag["mode"].as_str().unwrap_or("by-value").to_string();
+                    let total = diag["total"].as_u64().unwrap_or(0);
+                    let values = diag["values"].clone();
+                    let texts = diag["texts"].clone();
+                    let message = format!(
+                        "option not found: '{}'. Mode: {}. Total options: {}. Values: {}. Texts: {}",
+                        value, mode, total, values, texts,
+                    );
+                    return ActionResult::fatal_with_details(
+                        "INVALID_ARGUMENT",
+                        message,
+                        "check the available values and texts above, or use --by-text to match display text",
+                        json!({
+                            "status": "option not found",
+                            "mode": mode,
+                            "total": total,
+                            "values": values,
+                            "texts": texts,
+                        }),
+                    );
+                }
+                _ => return ActionResult::fatal("CDP_ERROR", format!("select failed: {other}")),
             }
-            return ActionResult::fatal("CDP_ERROR", format!("select failed: {other}"));
         }
         "not an option element" => {
             return ActionResult::fatal(
                 "INVALID_ARGUMENT",
-                format!("ref '{}' does not point to an <option> element", cmd.value),
+                format!("ref '{value}' does not point to an <option> element"),
             );
         }
         "option not in this select" => {
             return ActionResult::fatal(
                 "INVALID_ARGUMENT",
-                format!(
-                    "option '{}' is not in the target <select> element",
-                    cmd.value
-                ),
+                format!("option '{value}' is not in the target <select> element"),
             );
         }
         other => {
@@ -225,15 +272,19 @@ pub async fn execute(cmd: &Cmd, registry: &SharedRegistry) -> ActionResult {
     let url = navigation::get_tab_url(&ctx.cdp, &ctx.target_id).await;
     let title = navigation::get_tab_title(&ctx.cdp, &ctx.target_id).await;
 
-    ActionResult::ok(json!({
+    let mut data = json!({
         "action": "select",
         "target": { "selector": cmd.selector },
         "value_summary": {
-            "value": cmd.value,
+            "value": selected_value,
             "by_text": cmd.by_text,
             "by_ref": cmd.by_ref,
         },
         "post_url": url,
         "post_title": title,
-    }))
+    });
+    if let Some(index) = cmd.index {
+        data["value_summary"]["index"] = json!(index);
+    }
+    ActionResult::ok(data)
 }
@@ -0,0 +1,351 @@
+use clap::Args;
+use serde::{Deserialize, Serialize};
+use serde_json::{Value, json};
+
+use crate::action_result::ActionResult;
+use crate::browser::element::TabContext;
+use crate::browser::interaction::drag::dispatch_drag;
+use crate::browser::navigation;
+use crate::daemon::cdp_session::cdp_error_to_result;
+use crate::daemon::registry::SharedRegistry;
+use crate::output::ResponseContext;
+
+/// Set a slider or range input to a value
+#[derive(Args, Debug, Clone, Serialize, Deserialize)]
+#[command(after_help = "\
+Examples:
+  actionbook browser slide \"#volume\" --to-value 75 --session s1 --tab t1
+  actionbook browser slide @e5 --percent 30 --session s1 --tab t1
+
+Accepts a CSS selector, XPath, or snapshot ref (@eN from snapshot output).
+An <input type=range> is set directly and fires input and change events; the
+value snaps to the input's step. Anything else must be (or contain) an
+element with role=slider: its thumb is dragged along its track with the
+mouse, then the drag is corrected once if aria-valuenow landed off target.
+--percent is relative to the slider's min and max.")]
+pub struct Cmd {
+    /// Selector for the slider (CSS, XPath, or @ref)
+    pub selector: String,
+    /// Value to set, between the slider's min and max
+    #[arg(long, required_unless_present = "percent", conflicts_with = "percent")]
+    #[serde(default)]
+    pub to_value: Option<f64>,
+    /// Position to set, from 0 (min) to 100 (max)
+    #[arg(long)]
+    #[serde(default)]
+    pub percent: Option<f64>,
+    /// Session ID
+    #[arg(long)]
+    #[serde(rename = "session_id")]
+    pub session: String,
+    /// Tab ID
+    #[arg(long)]
+    #[serde(rename = "tab_id")]
+    pub tab: String,
+}
+
+pub const COMMAND_NAME: &str = "browser slide";
+
+pub fn context(cmd: &Cmd, result: &ActionResult) -> Option<ResponseContext> {
+    if let ActionResult::Fatal { code, .. } = result
+        && code == "SESSION_NOT_FOUND"
+    {
+        return None;
+    }
+    let (url, title) = match result {
+        ActionResult::Ok { data } => (
+            data.get("post_url")
+                .and_then(|v| v.as_str())
+                .filter(|s| !s.is_empty())
+                .map(String::from),
+            data.get("post_title")
+                .and_then(|v| v.as_str())
+                .filter(|s| !s.is_empty())
+                .map(String::from),
+        ),
+        _ => (None, None),
+    };
+    Some(ResponseContext {
+        session_id: cmd.session.clone(),
+        tab_id: Some(cmd.tab.clone()),
+        window_id: None,
+        url,
+        title,
+    })
+}
+
+/// Describe the slider behind the resolved element: a range input, or the
+/// role=slider element (itself or its first descendant) with its thumb point
+/// and track box in viewport coordinates.
+const DESCRIBE_JS: &str = r#"function() {
+    const num = (v, d) => { const n = parseFloat(v); return Number.isFinite(n) ? n : d; };
+    if (this.tagName === 'INPUT' && this.type === 'range') {
+        return JSON.stringify({
+            kind: 'range',
+            min: num(this.min, 0),
+            max: num(this.max, 100),
+            step: this.step === 'any' ? 0 : num(this.step, 1),
+            value: num(this.value, 0),
+            disabled: this.disabled,
+        });
+    }
+    const slider = this.getAttribute('role') === 'slider' ? this : this.querySelector('[role="slider"]');
+    if (!slider) return JSON.stringify({ kind: 'none', tag: this.tagName.toLowerCase() });
+    const min = num(slider.getAttribute('aria-valuemin'), 0);
+    const max = num(slider.getAttribute('aria-valuemax'), 100);
+    const now = num(slider.getAttribute('aria-valuenow'), null);
+    const vertical = slider.getAttribute('aria-orientation') === 'vertical';
+    const box = slider.getBoundingClientRect();
+    const extent = (r) => vertical ? r.height : r.width;
+    const cross = vertical ? box.width : box.height;
+    // A slider much longer than it is thick is its own track; otherwise it is
+    // the thumb and the track is the nearest ancestor at least twice as long.
+    let track = box;
+    let thumb = { x: box.left + box.width / 2, y: box.top + box.height / 2 };
+    if (extent(box) > 3 * cross) {
+        const frac = now === null || max === min ? 0 : (now - min) / (max - min);
+        if (vertical) thumb.y = box.bottom - frac * box.height;
+        else thumb.x = box.left + frac * box.width;
+    } else {
+        for (let p = slider.parentElement; p; p = p.parentElement) {
+            const r = p.getBoundingClientRect();
+            if (extent(r) >= 2 * extent(box)) { track = r; break; }
+        }
+    }
+    return JSON.stringify({
+        kind: 'aria', min, max, now, vertical, thumb,
+        track: { left: track.left, top: track.top, width: track.width, height: track.height },
+        disabled: slider.getAttribute('aria-disabled') === 'true',
+    });
+}"#;
+
+const SET_RANGE_JS: &str = r#"function(value) {
+    const setter = Object.getOwnPropertyDescriptor(HTMLInputElement.prototype, 'value').set;
+    setter.call(this, value);
+    this.dispatchEvent(new Event('input', { bubbles: true }));
+    this.dispatchEvent(new Event('change', { bubbles: true }));
+    return this.value;
+}"#;
+
+/// Snap `value` to `step` counted from `min`, then clamp to the range.
+fn snap(value: f64, min: f64, max: f64, step: f64) -> f64 {
+    let snapped = if step > 0.0 {
+        min + ((value - min) / step).round() * step
+    } else {
+        value
+    };
+    snapped.clamp(min.min(max), max.max(min))
+}
+
+/// Work out the requested value from --to-value or --percent.
+fn requested_value(cmd: &Cmd, min: f64, max: f64) -> Result<f64, ActionResult> {
+    match (cmd.to_value, cmd.percent) {
+        (Some(value), None) => {
+            if value < min.min(max) || value > max.max(min) {
+                return Err(ActionResult::fatal_with_hint(
+                    "INVALID_ARGUMENT",
+                    format!("value {value} is outside the slider's range {min}..{max}"),
+                    "pass a value between min and max, or use --percent",
+                ));
+            }
+            Ok(value)
+        }
+        (None, Some(percent)) => {
+            if !(0.0..=100.0).contains(&percent) {
+                return Err(ActionResult::fatal(
+                    "INVALID_ARGUMENT",
+                    format!("--percent must be between 0 and 100, got {percent}"),
+                ));
+            }
+            Ok(min + (max - min) * percent / 100.0)
+        }
+        _ => Err(ActionResult::fatal(
+            "INVALID_ARGUMENT",
+            "pass exactly one of --to-value or --percent",
+        )),
+    }
+}
+
+/// Viewport point on the track for `value`; vertical sliders grow upwards.
+fn track_point(desc: &Value, value: f64) -> (f64, f64) {
+    let min = desc["min"].as_f64().unwrap_or(0.0);
+    let max = desc["max"].as_f64().unwrap_or(100.0);
+    let frac = if max == min {
+        0.0
+    } else {
+        ((value - min) / (max - min)).clamp(0.0, 1.0)
+    };
+    let t = &desc["track"];
+    let (left, top) = (
+        t["left"].as_f64().unwrap_or(0.0),
+        t["top"].as_f64().unwrap_or(0.0),
+    );
+    let (w, h) = (
+        t["width"].as_f64().unwrap_or(0.0),
+        t["height"].as_f64().unwrap_or(0.0),
+    );
+    if desc["vertical"].as_bool().unwrap_or(false) {
+        (left + w / 2.0, top + h - frac * h)
+    } else {
+        (left + frac * w, top + h / 2.0)
+    }
+}
+
+async fn describe(ctx: &TabContext, object_id: &str) -> Result<Value, ActionResult> {
+    let resp = ctx
+        .execute_on_element(
+            "Runtime.callFunctionOn",
+            json!({
+                "objectId": object_id,
+                "functionDeclaration": DESCRIBE_JS,
+                "returnByValue": true,
+            }),
+        )
+        .await
+        .map_err(|e| cdp_error_to_result(e, "CDP_ERROR"))?;
+    let raw = resp
+        .pointer("/result/result/value")
+        .and_then(|v| v.as_str())
+        .unwrap_or("{}");
+    Ok(serde_json::from_str(raw).unwrap_or_default())
+}
+
+async fn drag_thumb(
+    ctx: &TabContext,
+    from: (f64, f64),
+    to: (f64, f64),
+) -> Result<(), ActionResult> {
+    dispatch_drag(&ctx.cdp, &ctx.target_id, from.0, from.1, to.0, to.1, "left").await?;
+    let mut reg = ctx.registry().lock().await;
+    reg.set_cursor_position(ctx.session_id(), ctx.tab_id(), to.0, to.1);
+    Ok(())
+}
+
+pub async fn execute(cmd: &Cmd, registry: &SharedRegistry) -> ActionResult {
+    let mut ctx = match TabContext::new(registry, &cmd.session, &cmd.tab).await {
+        Ok(v) => v,
+        Err(e) => return e,
+    };
+
+    let (node_id, object_id) = match ctx.resolve_object(&cmd.selector).await {
+        Ok(v) => v,
+        Err(e) => return e,
+    };
+    if let Err(e) = ctx.scroll_into_view(node_id).await {
+        return e;
+    }
+
+    let desc = match describe(&ctx, &object_id).await {
+        Ok(v) => v,
+        Err(e) => return e,
+    };
+    let kind = desc["kind"].as_str().unwrap_or("none").to_string();
+    if kind == "none" {
+        return ActionResult::fatal_with_hint(
+            "INVALID_ARGUMENT",
+            format!(
+                "'{}' is a <{}>, not a range input or slider",
+                cmd.selector,
+                desc["tag"].as_str().unwrap_or("?")
+            ),
+            "target an <input type=range> or an element with role=slider",
+        );
+    }
+    if desc["disabled"].as_bool().unwrap_or(false) {
+        return ActionResult::fatal(
+            "INVALID_ARGUMENT",
+            format!("slider '{}' is disabled", cmd.selector),
+        );
+    }
+
+    let min = desc["min"].as_f64().unwrap_or(0.0);
+    let max = desc["max"].as_f64().unwrap_or(100.0);
+    let requested = match requested_value(cmd, min, max) {
+        Ok(v) => v,
+        Err(e) => return e,
+    };
+
+    let value = if kind == "range" {
+        let step = desc["step"].as_f64().unwrap_or(1.0);
+        let target = snap(requested, min, max, step);
+        let resp = match ctx
+            .execute_on_element(
+                "Runtime.callFunctionOn",
+                json!({
+                    "objectId": object_id,
+                    "functionDeclaration": SET_RANGE_JS,
+                    "arguments": [{ "value": target.to_string() }],
+                    "returnByValue": true,
+                }),
+            )
+            .await
+        {
+            Ok(v) => v,
+            Err(e) => return cdp_error_to_result(e, "CDP_ERROR"),
+        };
+        resp.pointer("/result/result/value")
+            .and_then(|v| v.as_str())
+            .and_then(|s| s.parse::<f64>().ok())
+    } else {
+        let thumb = (
+            desc.pointer("/thumb/x")
+                .and_then(|v| v.as_f64())
+                .unwrap_or(0.0),
+            desc.pointer("/thumb/y")
+                .and_then(|v| v.as_f64())
+                .unwrap_or(0.0),
+        );
+        let aim = track_point(&desc, requested);
+        if let Err(e) = drag_thumb(&ctx, thumb, aim).await {
+            return e;
+        }
+
+        // Thumbs rarely sit exactly under the pointer on release (padding,
+        // centring, snapping); nudge once by the measured error.
+        let mut after = match describe(&ctx, &object_id).await {
+            Ok(v) => v,
+            Err(e) => return e,
+        };
+        if let Some(now) = after["now"].as_f64() {
+            let tolerance = ((max - min) / 100.0).abs();
+            if (now - requested).abs() > tolerance {
+                let (ax, ay) = track_point(&after, requested);
+                let (nx, ny) = track_point(&after, now);
+                let thumb = (
+                    after
+                        .pointer("/thumb/x")
+                        .and_then(|v| v.as_f64())
+                        .unwrap_or(nx),
+                    after
+                        .pointer("/thumb/y")
+                        .and_then(|v| v.as_f64())
+                        .unwrap_or(ny),
+                );
+                let corrected = (thumb.0 + ax - nx, thumb.1 + ay - ny);
+                if let Err(e) = drag_thumb(&ctx, thumb, corrected).await {
+                    return e;
+                }
+                after = match describe(&ctx, &object_id).await {
+                    Ok(v) => v,
+                    Err(e) => return e,
+                };
+            }
+        }
+        after["now"].as_f64()
+    };
+
+    let url = navigation::get_tab_url(&ctx.cdp, &ctx.target_id).await;
+    let title = navigation::get_tab_title(&ctx.cdp, &ctx.target_id).await;
+
+    ActionResult::ok(json!({
+        "action": "slide",
+        "target": { "selector": cmd.selector },
+        "kind": kind,
+        "requested": requested,
+        "value": value,
+        "min": min,
+        "max": max,
+        "post_url": url,
+        "post_title": title,
+    }))
+}
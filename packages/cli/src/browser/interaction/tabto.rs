@@ -0,0 +1,189 @@
+use clap::Args;
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+
+use crate::action_result::ActionResult;
+use crate::browser::element::TabContext;
+use crate::browser::interaction::press::key_definition;
+use crate::browser::navigation;
+use crate::daemon::cdp_session::cdp_error_to_result;
+use crate::daemon::registry::SharedRegistry;
+use crate::output::ResponseContext;
+
+/// Press Tab until an element has keyboard focus
+#[derive(Args, Debug, Clone, Serialize, Deserialize)]
+#[command(after_help = "\
+Examples:
+  actionbook browser tabto \"#submit\" --session s1 --tab t1
+  actionbook browser tabto @e7 --max-tabs 20 --session s1 --tab t1
+  actionbook browser tabto \"#search\" --reverse --session s1 --tab t1
+
+Presses Tab (or Shift+Tab with --reverse) one key at a time and checks
+document.activeElement after each press, stopping as soon as the target is
+focused. Unlike focus, this walks the page's real tab order, so it exercises
+tabindex, focus traps and keyboard handlers the way a keyboard user would.
+Fails if the target is still not focused after --max-tabs presses.")]
+pub struct Cmd {
+    /// Selector (CSS, XPath, or @ref)
+    pub selector: String,
+    /// Maximum number of Tab presses before giving up
+    #[arg(long, default_value_t = 50)]
+    #[serde(default = "default_max_tabs")]
+    pub max_tabs: u32,
+    /// Press Shift+Tab to walk the tab order backwards
+    #[arg(long)]
+    #[serde(default)]
+    pub reverse: bool,
+    /// Session ID
+    #[arg(long)]
+    #[serde(rename = "session_id")]
+    pub session: String,
+    /// Tab ID
+    #[arg(long)]
+    #[serde(rename = "tab_id")]
+    pub tab: String,
+}
+
+fn default_max_tabs() -> u32 {
+    50
+}
+
+pub const COMMAND_NAME: &str = "browser tabto";
+
+/// True when the element (`this`) is the deepest active element, following
+/// focus into open shadow roots.
+const IS_FOCUSED_JS: &str = r#"function() {
+    let a = document.activeElement;
+    while (a && a.shadowRoot && a.shadowRoot.activeElement) a = a.shadowRoot.activeElement;
+    return a === this;
+}"#;
+
+pub fn context(cmd: &Cmd, result: &ActionResult) -> Option<ResponseContext> {
+    if let ActionResult::Fatal { code, .. } = result
+        && code == "SESSION_NOT_FOUND"
+    {
+        return None;
+    }
+    let (url, title) = match result {
+        ActionResult::Ok { data } => (
+            data.get("post_url")
+                .and_then(|v| v.as_str())
+                .filter(|s| !s.is_empty())
+                .map(String::from),
+            data.get("post_title")
+                .and_then(|v| v.as_str())
+                .filter(|s| !s.is_empty())
+                .map(String::from),
+        ),
+        _ => (None, None),
+    };
+    Some(ResponseContext {
+        session_id: cmd.session.clone(),
+        tab_id: Some(cmd.tab.clone()),
+        window_id: None,
+        url,
+        title,
+    })
+}
+
+async fn is_focused(ctx: &TabContext, object_id: &str) -> Result<bool, ActionResult> {
+    let resp = ctx
+        .execute_on_element(
+            "Runtime.callFunctionOn",
+            json!({
+                "functionDeclaration": IS_FOCUSED_JS,
+                "objectId": object_id,
+                "returnByValue": true,
+            }),
+        )
+        .await
+        .map_err(|e| cdp_error_to_result(e, "CDP_ERROR"))?;
+    Ok(resp
+        .pointer("/result/result/value")
+        .and_then(|v| v.as_bool())
+        .unwrap_or(false))
+}
+
+async fn press_tab(ctx: &TabContext, modifiers: u32) -> Result<(), ActionResult> {
+    let def = key_definition("Tab").expect("Tab has a key definition");
+    for kind in ["rawKeyDown", "keyUp"] {
+        ctx.cdp
+            .execute_on_tab(
+                &ctx.target_id,
+                "Input.dispatchKeyEvent",
+                json!({
+                    "type": kind,
+                    "key": "Tab",
+                    "code": def.code,
+                    "windowsVirtualKeyCode": def.key_code,
+                    "nativeVirtualKeyCode": def.key_code,
+                    "modifiers": modifiers,
+                }),
+            )
+            .await
+            .map_err(|e| cdp_error_to_result(e, "CDP_ERROR"))?;
+    }
+    Ok(())
+}
+
+pub async fn execute(cmd: &Cmd, registry: &SharedRegistry) -> ActionResult {
+    let mut ctx = match TabContext::new(registry, &cmd.session, &cmd.tab).await {
+        Ok(v) => v,
+        Err(e) => return e,
+    };
+
+    let (_, object_id) = match ctx.resolve_object(&cmd.selector).await {
+        Ok(v) => v,
+        Err(e) => return e,
+    };
+
+    // Key events go to the foreground tab regardless of the CDP session
+    // (see press), so bring ours to the front before the first Tab.
+    let _ = ctx
+        .cdp
+        .execute_browser(
+            "Target.activateTarget",
+            json!({ "targetId": ctx.target_id }),
+        )
+        .await;
+
+    let modifiers = if cmd.reverse { 8 } else { 0 };
+    let mut presses = 0u32;
+    loop {
+        match is_focused(&ctx, &object_id).await {
+            Ok(true) => break,
+            Ok(false) => {}
+            Err(e) => return e,
+        }
+        if presses >= cmd.max_tabs {
+            return ActionResult::fatal_with_hint(
+                "ELEMENT_NOT_REACHED",
+                format!(
+                    "'{}' was not focused after {presses} Tab presses",
+                    cmd.selector
+                ),
+                "the element may not be in the tab order; raise --max-tabs, try --reverse, or use browser focus",
+            );
+        }
+        if let Err(e) = press_tab(&ctx, modifiers).await {
+            return e;
+        }
+        presses += 1;
+    }
+
+    let url = navigation::get_tab_url(&ctx.cdp, &ctx.target_id).await;
+    let title = navigation::get_tab_title(&ctx.cdp, &ctx.target_id).await;
+
+    ActionResult::ok(json!({
+        "action": "tabto",
+        "target": { "selector": cmd.selector },
+        "presses": presses,
+        "reverse": cmd.reverse,
+        "changed": {
+            "url_changed": false,
+            "focus_changed": presses > 0,
+        },
+        "post_url": url,
+        "post_title": title,
+    }))
+}
@@ -0,0 +1,553 @@
+//! `browser tap`, `browser swipe` and `browser pinch`: touch gestures.
+//!
+//! Mobile pages often listen for touch events only, so a mouse click or drag
+//! does nothing on them. These commands touch the page instead, with
+//! `Input.dispatchTouchEvent`. They pair with a device from
+//! `browser start --device` or `browser emulate`, which makes the page see a
+//! touch screen in the first place.
+
+use std::time::Duration;
+
+use clap::Args;
+use serde::{Deserialize, Serialize};
+use serde_json::{Value, json};
+
+use crate::action_result::ActionResult;
+use crate::browser::element::{ClickTarget, TabContext, parse_target};
+use crate::browser::interaction::click::guard_sensitive;
+use crate::browser::navigation;
+use crate::daemon::cdp_session::cdp_error_to_result;
+use crate::daemon::guardrails::SensitivePolicy;
+use crate::daemon::registry::SharedRegistry;
+use crate::output::ResponseContext;
+
+type Point = (f64, f64);
+
+/// Touch-move events sent per gesture.
+const MOVE_STEPS: u32 = 10;
+/// Distance between the two fingers of a pinch at its narrow end, in CSS px.
+const PINCH_GAP: f64 = 100.0;
+
+fn default_duration() -> u64 {
+    300
+}
+
+// ── Tap ───────────────────────────────────────────────────────────────────────
+
+/// Tap an element or point with a touch
+#[derive(Args, Debug, Clone, Serialize, Deserialize)]
+#[command(after_help = "\
+Examples:
+  actionbook browser tap \"#menu-button\" --session s1 --tab t1
+  actionbook browser tap @e4 --session s1 --tab t1
+  actionbook browser tap 200,400 --session s1 --tab t1
+
+Accepts a CSS selector, XPath, snapshot ref (@eN), or x,y coordinates.
+Sends touchstart and touchend at the element's centre, which the browser
+turns into a click as it would for a finger. Emulate a touch device first
+(browser start --device, or browser emulate) so the page expects touch.
+
+When [guardrails] confirm_sensitive_actions is enabled, taps on payment or
+deletion controls are refused unless --confirm-sensitive is passed.")]
+pub struct TapCmd {
+    /// Target selector (CSS, XPath, @ref) or x,y coordinates
+    pub target: String,
+    /// Allow taps that the sensitive-action guardrail would refuse
+    #[arg(long)]
+    #[serde(default)]
+    pub confirm_sensitive: bool,
+    /// Session ID
+    #[arg(long)]
+    #[serde(rename = "session_id")]
+    pub session: String,
+    /// Tab ID
+    #[arg(long)]
+    #[serde(rename = "tab_id")]
+    pub tab: String,
+}
+
+pub const TAP_COMMAND_NAME: &str = "browser tap";
+
+pub fn tap_context(cmd: &TapCmd, result: &ActionResult) -> Option<ResponseContext> {
+    tab_context(&cmd.session, &cmd.tab, result)
+}
+
+pub async fn execute_tap(cmd: &TapCmd, registry: &SharedRegistry) -> ActionResult {
+    let target = match parse_target(&cmd.target) {
+        Ok(t) => t,
+        Err(e) => return e,
+    };
+    let policy = match SensitivePolicy::load() {
+        Ok(p) => p,
+        Err(e) => return e,
+    };
+    let mut ctx = match TabContext::new(registry, &cmd.session, &cmd.tab).await {
+        Ok(v) => v,
+        Err(e) => return e,
+    };
+    let sensitive = match guard_sensitive(
+        &mut ctx,
+        &cmd.target,
+        policy.as_ref(),
+        cmd.confirm_sensitive,
+    )
+    .await
+    {
+        Ok(s) => s,
+        Err(e) => return e,
+    };
+    let point = match resolve(&mut ctx, &target).await {
+        Ok(p) => p,
+        Err(e) => return e,
+    };
+    if let Err(e) = gesture(&ctx, &[point], &[point], 0).await {
+        return e;
+    }
+
+    let mut data = json!({
+        "action": "tap",
+        "target": target_json(&cmd.target, &target),
+        "point": point_json(point),
+    });
+    if let Some(s) = sensitive {
+        data["sensitive"] = s;
+    }
+    finish(&ctx, &mut data).await;
+    ActionResult::ok(data)
+}
+
+// ── Swipe ─────────────────────────────────────────────────────────────────────
+
+/// Which way the finger moves in a swipe.
+#[derive(Clone, Copy, Debug, Serialize, Deserialize, clap::ValueEnum, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum SwipeDirection {
+    Up,
+    Down,
+    Left,
+    Right,
+}
+
+impl SwipeDirection {
+    fn as_str(self) -> &'static str {
+        match self {
+            Self::Up => "up",
+            Self::Down => "down",
+            Self::Left => "left",
+            Self::Right => "right",
+        }
+    }
+
+    fn unit(self) -> Point {
+        match self {
+            Self::Up => (0.0, -1.0),
+            Self::Down => (0.0, 1.0),
+            Self::Left => (-1.0, 0.0),
+            Self::Right => (1.0, 0.0),
+        }
+    }
+}
+
+/// Swipe a finger across the page or an element
+#[derive(Args, Debug, Clone, Serialize, Deserialize)]
+#[command(after_help = "\
+Examples:
+  actionbook browser swipe up --session s1 --tab t1
+  actionbook browser swipe left --from \".carousel\" --session s1 --tab t1
+  actionbook browser swipe down --distance 400 --duration 600 --session s1 --tab t1
+
+The direction is the way the finger moves: swiping up scrolls the page down,
+swiping left advances a carousel. The swipe starts at the centre of --from
+(a selector, @ref or x,y) or of the viewport, and covers --distance pixels,
+half the viewport by default, kept inside the viewport.")]
+pub struct SwipeCmd {
+    /// Direction the finger moves: up, down, left, right
+    #[arg(value_enum)]
+    pub direction: SwipeDirection,
+    /// Start at this element (CSS, XPath, @ref) or x,y instead of the viewport centre
+    #[arg(long, value_name = "TARGET")]
+    #[serde(default)]
+    pub from: Option<String>,
+    /// Pixels to move (default: half the viewport)
+    #[arg(long)]
+    #[serde(default)]
+    pub distance: Option<f64>,
+    /// Gesture duration in milliseconds
+    #[arg(long, default_value_t = default_duration())]
+    #[serde(default = "default_duration")]
+    pub duration: u64,
+    /// Session ID
+    #[arg(long)]
+    #[serde(rename = "session_id")]
+    pub session: String,
+    /// Tab ID
+    #[arg(long)]
+    #[serde(rename = "tab_id")]
+    pub tab: String,
+}
+
+pub const SWIPE_COMMAND_NAME: &str = "browser swipe";
+
+pub fn swipe_context(cmd: &SwipeCmd, result: &ActionResult) -> Option<ResponseContext> {
+    tab_context(&cmd.session, &cmd.tab, result)
+}
+
+/// End of a swipe from `from`, clamped 1px inside the viewport.
+fn swipe_end(from: Point, direction: SwipeDirection, distance: f64, viewport: Point) -> Point {
+    let (ux, uy) = direction.unit();
+    let clamp = |v: f64, max: f64| v.clamp(1.0, (max - 1.0).max(1.0));
+    (
+        clamp(from.0 + ux * distance, viewport.0),
+        clamp(from.1 + uy * distance, viewport.1),
+    )
+}
+
+pub async fn execute_swipe(cmd: &SwipeCmd, registry: &SharedRegistry) -> ActionResult {
+    if cmd.distance.is_some_and(|d| d.is_nan() || d <= 0.0) {
+        return ActionResult::fatal("INVALID_ARGUMENT", "--distance must be greater than 0");
+    }
+    let origin = match cmd.from.as_deref().map(parse_target).transpose() {
+        Ok(t) => t,
+        Err(e) => return e,
+    };
+    let mut ctx = match TabContext::new(registry, &cmd.session, &cmd.tab).await {
+        Ok(v) => v,
+        Err(e) => return e,
+    };
+    let start = match &origin {
+        Some(target) => match resolve(&mut ctx, target).await {
+            Ok(p) => p,
+            Err(e) => return e,
+        },
+        None => centre(&ctx).await,
+    };
+    let viewport = viewport(&ctx).await;
+    let distance = cmd.distance.unwrap_or(match cmd.direction {
+        SwipeDirection::Up | SwipeDirection::Down => viewport.1 / 2.0,
+        SwipeDirection::Left | SwipeDirection::Right => viewport.0 / 2.0,
+    });
+    let end = swipe_end(start, cmd.direction, distance, viewport);
+    if let Err(e) = gesture(&ctx, &[start], &[end], cmd.duration).await {
+        return e;
+    }
+
+    let mut data = json!({
+        "action": "swipe",
+        "direction": cmd.direction.as_str(),
+        "from": point_json(start),
+        "to": point_json(end),
+        "duration_ms": cmd.duration,
+    });
+    if let (Some(raw), Some(target)) = (&cmd.from, &origin) {
+        data["target"] = target_json(raw, target);
+    }
+    finish(&ctx, &mut data).await;
+    ActionResult::ok(data)
+}
+
+// ── Pinch ─────────────────────────────────────────────────────────────────────
+
+/// Whether a pinch brings the fingers together or spreads them.
+#[derive(Clone, Copy, Debug, Serialize, Deserialize, clap::ValueEnum, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum PinchDirection {
+    /// Fingers move together (zoom out).
+    In,
+    /// Fingers move apart (zoom in).
+    Out,
+}
+
+impl PinchDirection {
+    fn as_str(self) -> &'static str {
+        match self {
+            Self::In => "in",
+            Self::Out => "out",
+        }
+    }
+}
+
+/// Pinch two fingers together or apart
+#[derive(Args, Debug, Clone, Serialize, Deserialize)]
+#[command(after_help = "\
+Examples:
+  actionbook browser pinch out --session s1 --tab t1
+  actionbook browser pinch in --at \"#map\" --scale 0.5 --session s1 --tab t1
+
+Two fingers move apart (out, to zoom in) or together (in, to zoom out),
+centred on --at (a selector, @ref or x,y) or the viewport. --scale is the
+ratio of the final distance between the fingers to the first: above 1 for
+out, below 1 for in. The page receives the touch events; whether it zooms is
+up to its own gesture handling.")]
+pub struct PinchCmd {
+    /// Fingers move together (in) or apart (out)
+    #[arg(value_enum)]
+    pub direction: PinchDirection,
+    /// Centre the pinch on this element (CSS, XPath, @ref) or x,y
+    #[arg(long, value_name = "TARGET")]
+    #[serde(default)]
+    pub at: Option<String>,
+    /// Final finger distance over the starting one (default: 2 out, 0.5 in)
+    #[arg(long)]
+    #[serde(default)]
+    pub scale: Option<f64>,
+    /// Gesture duration in milliseconds
+    #[arg(long, default_value_t = default_duration())]
+    #[serde(default = "default_duration")]
+    pub duration: u64,
+    /// Session ID
+    #[arg(long)]
+    #[serde(rename = "session_id")]
+    pub session: String,
+    /// Tab ID
+    #[arg(long)]
+    #[serde(rename = "tab_id")]
+    pub tab: String,
+}
+
+pub const PINCH_COMMAND_NAME: &str = "browser pinch";
+
+pub fn pinch_context(cmd: &PinchCmd, result: &ActionResult) -> Option<ResponseContext> {
+    tab_context(&cmd.session, &cmd.tab, result)
+}
+
+/// The scale a pinch uses, checked against its direction.
+fn pinch_scale(direction: PinchDirection, scale: Option<f64>) -> Result<f64, ActionResult> {
+    let scale = scale.unwrap_or(match direction {
+        PinchDirection::In => 0.5,
+        PinchDirection::Out => 2.0,
+    });
+    let fits = match direction {
+        PinchDirection::In => scale > 0.0 && scale < 1.0,
+        PinchDirection::Out => scale > 1.0,
+    };
+    if !fits {
+        return Err(ActionResult::fatal_with_hint(
+            "INVALID_ARGUMENT",
+            format!("--scale {scale} does not fit pinch {}", direction.as_str()),
+            "use a scale above 1 to pinch out and between 0 and 1 to pinch in",
+        ));
+    }
+    Ok(scale)
+}
+
+/// Start and end positions of the two fingers, side by side around `centre`.
+/// The narrower end of the gesture has them `PINCH_GAP` apart.
+fn pinch_fingers(centre: Point, scale: f64) -> ([Point; 2], [Point; 2]) {
+    let (from, to) = if scale >= 1.0 {
+        (PINCH_GAP, PINCH_GAP * scale)
+    } else {
+        (PINCH_GAP / scale, PINCH_GAP)
+    };
+    let fingers = |gap: f64| {
+        [
+            (centre.0 - gap / 2.0, centre.1),
+            (centre.0 + gap / 2.0, centre.1),
+        ]
+    };
+    (fingers(from), fingers(to))
+}
+
+pub async fn execute_pinch(cmd: &PinchCmd, registry: &SharedRegistry) -> ActionResult {
+    let scale = match pinch_scale(cmd.direction, cmd.scale) {
+        Ok(s) => s,
+        Err(e) => return e,
+    };
+    let origin = match cmd.at.as_deref().map(parse_target).transpose() {
+        Ok(t) => t,
+        Err(e) => return e,
+    };
+    let mut ctx = match TabContext::new(registry, &cmd.session, &cmd.tab).await {
+        Ok(v) => v,
+        Err(e) => return e,
+    };
+    let at = match &origin {
+        Some(target) => match resolve(&mut ctx, target).await {
+            Ok(p) => p,
+            Err(e) => return e,
+        },
+        None => centre(&ctx).await,
+    };
+    let (from, to) = pinch_fingers(at, scale);
+    if let Err(e) = gesture(&ctx, &from, &to, cmd.duration).await {
+        return e;
+    }
+
+    let mut data = json!({
+        "action": "pinch",
+        "direction": cmd.direction.as_str(),
+        "scale": scale,
+        "center": point_json(at),
+        "duration_ms": cmd.duration,
+    });
+    if let (Some(raw), Some(target)) = (&cmd.at, &origin) {
+        data["target"] = target_json(raw, target);
+    }
+    finish(&ctx, &mut data).await;
+    ActionResult::ok(data)
+}
+
+// ── Shared ────────────────────────────────────────────────────────────────────
+
+fn tab_context(session: &str, tab: &str, result: &ActionResult) -> Option<ResponseContext> {
+    if let ActionResult::Fatal { code, .. } = result
+        && code == "SESSION_NOT_FOUND"
+    {
+        return None;
+    }
+    let (url, title) = match result {
+        ActionResult::Ok { data } => (
+            data.get("post_url")
+                .and_then(|v| v.as_str())
+                .filter(|s| !s.is_empty())
+                .map(String::from),
+            data.get("post_title")
+                .and_then(|v| v.as_str())
+                .filter(|s| !s.is_empty())
+                .map(String::from),
+        ),
+        _ => (None, None),
+    };
+    Some(ResponseContext {
+        session_id: session.to_string(),
+        tab_id: Some(tab.to_string()),
+        window_id: None,
+        url,
+        title,
+    })
+}
+
+fn target_json(raw: &str, target: &ClickTarget) -> Value {
+    match target {
+        ClickTarget::Selector(_) => json!({ "selector": raw }),
+        ClickTarget::Coordinates(..) => json!({ "coordinates": raw }),
+    }
+}
+
+fn point_json((x, y): Point) -> Value {
+    json!({ "x": x, "y": y })
+}
+
+/// Viewport point for a target: coordinates as given, or the centre of the
+/// element after scrolling it into view.
+async fn resolve(ctx: &mut TabContext, target: &ClickTarget) -> Result<Point, ActionResult> {
+    match target {
+        ClickTarget::Coordinates(x, y) => Ok((*x, *y)),
+        ClickTarget::Selector(selector) => {
+            let (_, x, y) = ctx.resolve_center(selector).await?;
+            Ok((x, y))
+        }
+    }
+}
+
+/// Viewport size in CSS pixels.
+async fn viewport(ctx: &TabContext) -> Point {
+    let value = ctx
+        .evaluate("({w:window.innerWidth,h:window.innerHeight})")
+        .await
+        .ok();
+    let size = value
+        .as_ref()
+        .and_then(|v| v.pointer("/result/result/value"));
+    (
+        size.and_then(|s| s["w"].as_f64()).unwrap_or(800.0),
+        size.and_then(|s| s["h"].as_f64()).unwrap_or(600.0),
+    )
+}
+
+async fn centre(ctx: &TabContext) -> Point {
+    let (w, h) = viewport(ctx).await;
+    (w / 2.0, h / 2.0)
+}
+
+/// Fingers at `from`, moved in [`MOVE_STEPS`] over `duration_ms` to `to`,
+/// then lifted. Equal endpoints and no duration make a tap.
+async fn gesture(
+    ctx: &TabContext,
+    from: &[Point],
+    to: &[Point],
+    duration_ms: u64,
+) -> Result<(), ActionResult> {
+    touch(ctx, "touchStart", from).await?;
+    if from != to || duration_ms > 0 {
+        let pause = Duration::from_millis(duration_ms / u64::from(MOVE_STEPS));
+        for step in 1..=MOVE_STEPS {
+            let t = f64::from(step) / f64::from(MOVE_STEPS);
+            let points: Vec<Point> = from
+                .iter()
+                .zip(to)
+                .map(|(a, b)| (a.0 + (b.0 - a.0) * t, a.1 + (b.1 - a.1) * t))
+                .collect();
+            tokio::time::sleep(pause).await;
+            touch(ctx, "touchMove", &points).await?;
+        }
+    }
+    touch(ctx, "touchEnd", &[]).await
+}
+
+async fn touch(ctx: &TabContext, kind: &str, points: &[Point]) -> Result<(), ActionResult> {
+    let points: Vec<Value> = points
+        .iter()
+        .enumerate()
+        .map(|(id, (x, y))| json!({ "x": x, "y": y, "id": id }))
+        .collect();
+    ctx.cdp
+        .execute_on_tab(
+            &ctx.target_id,
+            "Input.dispatchTouchEvent",
+            json!({ "type": kind, "touchPoints": points }),
+        )
+        .await
+        .map_err(|e| cdp_error_to_result(e, "CDP_ERROR"))?;
+    Ok(())
+}
+
+/// Add the page the gesture left the tab on, and a warning when the session
+/// does not emulate a touch device.
+async fn finish(ctx: &TabContext, data: &mut Value) {
+    data["post_url"] = json!(navigation::get_tab_url(&ctx.cdp, &ctx.target_id).await);
+    data["post_title"] = json!(navigation::get_tab_title(&ctx.cdp, &ctx.target_id).await);
+    if !ctx.cdp.emulation().is_some_and(|e| e.device.touch) {
+        data["__warnings"] = json!([
+            "the session does not emulate a touch device, so the page may not expect touch input; \
+             emulate one with `actionbook browser emulate \"iPhone 14\"`"
+        ]);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn swipe_end_moves_the_finger_and_stays_in_the_viewport() {
+        let viewport = (400.0, 800.0);
+        assert_eq!(
+            swipe_end((200.0, 400.0), SwipeDirection::Up, 300.0, viewport),
+            (200.0, 100.0)
+        );
+        assert_eq!(
+            swipe_end((200.0, 400.0), SwipeDirection::Down, 600.0, viewport),
+            (200.0, 799.0)
+        );
+        assert_eq!(
+            swipe_end((50.0, 400.0), SwipeDirection::Left, 200.0, viewport),
+            (1.0, 400.0)
+        );
+    }
+
+    #[test]
+    fn pinch_scale_defaults_by_direction_and_must_agree_with_it() {
+        assert_eq!(pinch_scale(PinchDirection::Out, None).unwrap(), 2.0);
+        assert_eq!(pinch_scale(PinchDirection::In, None).unwrap(), 0.5);
+        assert!(pinch_scale(PinchDirection::Out, Some(0.5)).is_err());
+        assert!(pinch_scale(PinchDirection::In, Some(1.5)).is_err());
+        assert!(pinch_scale(PinchDirection::In, Some(0.0)).is_err());
+
+        let (from, to) = pinch_fingers((200.0, 300.0), 2.0);
+        assert_eq!(from, [(150.0, 300.0), (250.0, 300.0)]);
+        assert_eq!(to, [(100.0, 300.0), (300.0, 300.0)]);
+        let (from, to) = pinch_fingers((200.0, 300.0), 0.5);
+        assert_eq!(from, [(100.0, 300.0), (300.0, 300.0)]);
+        assert_eq!(to, [(150.0, 300.0), (250.0, 300.0)]);
+    }
+}
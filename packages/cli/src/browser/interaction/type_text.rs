@@ -6,6 +6,7 @@ use crate::action_result::ActionResult;
 use crate::browser::element::{ClickTarget, TabContext, parse_target};
 use crate::browser::interaction::press::key_definition;
 use crate::browser::navigation;
+use crate::browser::redact;
 use crate::daemon::cdp_session::cdp_error_to_result;
 use crate::daemon::registry::SharedRegistry;
 use crate::output::ResponseContext;
@@ -23,7 +24,8 @@ Accepts a CSS selector, XPath, snapshot ref (@eN), or coordinates (x,y).
 If selector is omitted, types into the currently focused element (document.activeElement).
 Types each character individually, firing keydown/keypress/keyup events.
 Use for fields with autocomplete, live validation, or input listeners.
-For simple value setting without events, use fill instead.")]
+For simple value setting without events, use fill instead.
+Use --secret for credentials; password fields are detected automatically.")]
 pub struct Cmd {
     /// Positional args: [selector] text — if one arg, it's the text; if two, first is selector.
     #[arg(num_args = 1..=2)]
@@ -36,6 +38,10 @@ pub struct Cmd {
     #[arg(long)]
     #[serde(rename = "tab_id")]
     pub tab: String,
+    /// Treat the value as a secret: never echo it or its length
+    #[arg(long)]
+    #[serde(default)]
+    pub secret: bool,
 }
 
 pub const COMMAND_NAME: &str = "browser type";
@@ -156,6 +162,8 @@ pub async fn execute(cmd: &Cmd, registry: &SharedRegistry) -> ActionResult {
         }
     }
 
+    let secret = cmd.secret || redact::active_element_is_secret(&ctx).await;
+
     // Move cursor to end of existing value so typed text appends.
     // For contentEditable elements, use Selection/Range API instead.
     let _ = ctx
@@ -167,6 +175,24 @@ pub async fn execute(cmd: &Cmd, registry: &SharedRegistry) -> ActionResult {
         )
         .await;
 
+    if let Err(e) = type_chars(&ctx, text).await {
+        return e;
+    }
+
+    let url = navigation::get_tab_url(&ctx.cdp, &ctx.target_id).await;
+    let title = navigation::get_tab_title(&ctx.cdp, &ctx.target_id).await;
+
+    ActionResult::ok(json!({
+        "action": "type",
+        "target": target_json,
+        "value_summary": redact::value_summary(text, secret),
+        "post_url": url,
+        "post_title": title,
+    }))
+}
+
+/// Type `text` into the focused element one keystroke at a time.
+pub(crate) async fn type_chars(ctx: &TabContext, text: &str) -> Result<(), ActionResult> {
     // Chrome routes Input.dispatchKeyEvent to the active (foreground) tab,
     // ignoring the CDP sessionId.  Activate our target tab first so key
     // events reach the right page.  Matches Playwright's Page.bringToFront().
@@ -201,7 +227,7 @@ pub async fn execute(cmd: &Cmd, registry: &SharedRegistry) -> ActionResult {
             .execute_on_tab(&ctx.target_id, "Input.dispatchKeyEvent", key_down)
             .await
         {
-            return cdp_error_to_result(e, "CDP_ERROR");
+            return Err(cdp_error_to_result(e, "CDP_ERROR"));
         }
 
         let mut key_up = json!({
@@ -219,20 +245,10 @@ pub async fn execute(cmd: &Cmd, registry: &SharedRegistry) -> ActionResult {
             .execute_on_tab(&ctx.target_id, "Input.dispatchKeyEvent", key_up)
             .await
         {
-            return cdp_error_to_result(e, "CDP_ERROR");
+            return Err(cdp_error_to_result(e, "CDP_ERROR"));
         }
     }
-
-    let url = navigation::get_tab_url(&ctx.cdp, &ctx.target_id).await;
-    let title = navigation::get_tab_title(&ctx.cdp, &ctx.target_id).await;
-
-    ActionResult::ok(json!({
-        "action": "type",
-        "target": target_json,
-        "value_summary": { "text_length": text.chars().count() },
-        "post_url": url,
-        "post_title": title,
-    }))
+    Ok(())
 }
 
 /// Check if a DOM node is contenteditable by resolving it to a JS object.
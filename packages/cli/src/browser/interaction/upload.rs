@@ -19,8 +19,10 @@ Examples:
   actionbook browser upload @e6 /tmp/a.pdf /tmp/b.pdf --session s1 --tab t1
 
 Accepts a CSS selector, XPath, or snapshot ref (@eN from snapshot output).
-Sets files on a <input type=\"file\"> element. Paths must be absolute.
-Pass multiple paths for multi-file inputs.")]
+Sets files on a <input type=\"file\"> element. Paths must be absolute and
+name existing files. Pass multiple paths for inputs with the multiple attribute.
+Works the same in extension mode, where the call goes through the extension's
+debugger connection. The page sees the usual input and change events.")]
 pub struct Cmd {
     /// Selector for file input (CSS, XPath, or @ref)
     pub selector: String,
@@ -84,12 +86,60 @@ pub async fn execute(cmd: &Cmd, registry: &SharedRegistry) -> ActionResult {
         Err(e) => return e,
     };
 
+    // Check the files exist here: the daemon hands the paths to Chrome as-is
+    let mut bytes = 0;
+    for file in &cmd.files {
+        match std::fs::metadata(file) {
+            Ok(meta) if meta.is_file() => bytes += meta.len(),
+            Ok(_) => {
+                return ActionResult::fatal("INVALID_ARGUMENT", format!("not a file: '{file}'"));
+            }
+            Err(e) => {
+                return ActionResult::fatal_with_hint(
+                    "INVALID_ARGUMENT",
+                    format!("cannot read '{file}': {e}"),
+                    "upload paths are read on the machine running the browser daemon",
+                );
+            }
+        }
+    }
+
     // Resolve the file input element
     let node_id = match ctx.resolve_node(&cmd.selector).await {
         Ok(id) => id,
         Err(e) => return e,
     };
 
+    let object_id = match ctx.resolve_object_id(node_id).await {
+        Ok(id) => id,
+        Err(e) => return e,
+    };
+    let input = match file_input_info(&ctx, &object_id).await {
+        Ok(v) => v,
+        Err(e) => return e,
+    };
+    if input["file"].as_bool() != Some(true) {
+        return ActionResult::fatal_with_hint(
+            "INVALID_ARGUMENT",
+            format!(
+                "'{}' is not a file input ({})",
+                cmd.selector,
+                input["desc"].as_str().unwrap_or("?")
+            ),
+            "target the <input type=\"file\"> itself; it may be hidden behind a styled button",
+        );
+    }
+    if cmd.files.len() > 1 && input["multiple"].as_bool() != Some(true) {
+        return ActionResult::fatal(
+            "INVALID_ARGUMENT",
+            format!(
+                "'{}' accepts one file ({} given): it has no multiple attribute",
+                cmd.selector,
+                cmd.files.len()
+            ),
+        );
+    }
+
     // Set files on the input via DOM.setFileInputFiles
     if let Err(e) = ctx
         .execute_on_element(
@@ -113,8 +163,31 @@ pub async fn execute(cmd: &Cmd, registry: &SharedRegistry) -> ActionResult {
         "value_summary": {
             "files": cmd.files,
             "count": cmd.files.len(),
+            "bytes": bytes,
         },
         "post_url": url,
         "post_title": title,
     }))
 }
+
+/// Whether the element is a file input, and whether it takes several files.
+async fn file_input_info(
+    ctx: &TabContext,
+    object_id: &str,
+) -> Result<serde_json::Value, ActionResult> {
+    let resp = ctx
+        .execute_on_element(
+            "Runtime.callFunctionOn",
+            json!({
+                "objectId": object_id,
+                "functionDeclaration": "function() { return { file: this instanceof HTMLInputElement && this.type === 'file', multiple: !!this.multiple, desc: this.tagName.toLowerCase() + (this.type ? '[type=' + this.type + ']' : '') }; }",
+                "returnByValue": true,
+            }),
+        )
+        .await
+        .map_err(|e| cdp_error_to_result(e, "CDP_ERROR"))?;
+    Ok(resp
+        .pointer("/result/result/value")
+        .cloned()
+        .unwrap_or_default())
+}
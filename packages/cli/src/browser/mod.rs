@@ -1,8 +1,11 @@
 pub mod cookies;
+pub mod downloads;
 pub mod element;
+pub mod indexeddb;
 pub mod interaction;
 pub mod navigation;
 pub mod observation;
+pub mod redact;
 pub mod session;
 pub mod stealth;
 pub mod storage;
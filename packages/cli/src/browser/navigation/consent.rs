@@ -0,0 +1,181 @@
+//! Cookie consent banner dismissal for `browser goto --dismiss-consent`.
+//!
+//! Known consent-management platforms (CMPs) are matched by their stable
+//! button selectors; anything else falls back to a text heuristic scoped to
+//! containers that look like a consent banner. "Reject"/"necessary only"
+//! buttons are preferred over "accept" so dismissing a banner never opts the
+//! session into more tracking than the site's default.
+
+use serde_json::{Value, json};
+
+use crate::daemon::cdp_session::CdpSession;
+
+/// How long to keep looking for a banner after navigation. CMP scripts are
+/// usually loaded async and render shortly after DOMContentLoaded.
+const CONSENT_WAIT_MS: u64 = 2000;
+const CONSENT_POLL_MS: u64 = 250;
+
+/// `[cmp, action, selector]` rules, tried in order. Each CMP lists its reject
+/// selector(s) before its accept selector(s).
+const CMP_RULES: &[(&str, &str, &str)] = &[
+    ("onetrust", "reject", "#onetrust-reject-all-handler"),
+    ("onetrust", "accept", "#onetrust-accept-btn-handler"),
+    (
+        "cookiebot",
+        "reject",
+        "#CybotCookiebotDialogBodyButtonDecline",
+    ),
+    (
+        "cookiebot",
+        "accept",
+        "#CybotCookiebotDialogBodyLevelButtonLevelOptinAllowAll",
+    ),
+    (
+        "cookiebot",
+        "accept",
+        "#CybotCookiebotDialogBodyButtonAccept",
+    ),
+    ("didomi", "reject", "#didomi-notice-disagree-button"),
+    ("didomi", "accept", "#didomi-notice-agree-button"),
+    (
+        "quantcast",
+        "reject",
+        ".qc-cmp2-summary-buttons button[mode=\"secondary\"]",
+    ),
+    (
+        "quantcast",
+        "accept",
+        ".qc-cmp2-summary-buttons button[mode=\"primary\"]",
+    ),
+    (
+        "usercentrics",
+        "reject",
+        "[data-testid=\"uc-deny-all-button\"]",
+    ),
+    (
+        "usercentrics",
+        "accept",
+        "[data-testid=\"uc-accept-all-button\"]",
+    ),
+    ("trustarc", "reject", "#truste-consent-required"),
+    ("trustarc", "accept", "#truste-consent-button"),
+    ("osano", "reject", ".osano-cm-denyAll"),
+    ("osano", "accept", ".osano-cm-accept-all"),
+    ("cookieyes", "reject", ".cky-btn-reject"),
+    ("cookieyes", "accept", ".cky-btn-accept"),
+    ("complianz", "reject", ".cmplz-btn.cmplz-deny"),
+    ("complianz", "accept", ".cmplz-btn.cmplz-accept"),
+    ("klaro", "reject", ".klaro .cm-btn-decline"),
+    ("klaro", "accept", ".klaro .cm-btn-success"),
+    (
+        "borlabs",
+        "accept",
+        "#BorlabsCookieBox a._brlbs-btn-accept-all",
+    ),
+    ("iubenda", "reject", ".iubenda-cs-reject-btn"),
+    ("iubenda", "accept", ".iubenda-cs-accept-btn"),
+];
+
+/// Polls the page until a rule (or the heuristic) clicks something or the
+/// deadline passes. Called as `(FN)(rules, waitMs, pollMs)`.
+const DISMISS_JS: &str = r#"(async function(rules, waitMs, pollMs) {
+    const visible = (el) => {
+        if (!el || !el.isConnected) return false;
+        const r = el.getBoundingClientRect();
+        if (r.width === 0 || r.height === 0) return false;
+        const s = getComputedStyle(el);
+        return s.visibility !== 'hidden' && s.display !== 'none' && s.opacity !== '0';
+    };
+    // Some CMPs (Usercentrics) render into an open shadow root.
+    const roots = () => {
+        const out = [document];
+        for (const host of document.querySelectorAll('#usercentrics-root, #cmpwrapper, [id*="consent" i]')) {
+            if (host.shadowRoot) out.push(host.shadowRoot);
+        }
+        return out;
+    };
+    const byRule = () => {
+        for (const [cmp, action, selector] of rules) {
+            for (const root of roots()) {
+                const el = root.querySelector(selector);
+                if (visible(el)) return { el, cmp, action, rule: selector };
+            }
+        }
+        return null;
+    };
+    const REJECT = /^(reject( all)?|decline( all)?|deny( all)?|refuse( all)?|(use )?(only )?(strictly )?necessary( cookies)?( only)?|continue without accepting|alle ablehnen|ablehnen|nur notwendige|tout refuser|refuser|rechazar( todo)?|rifiuta( tutto)?|weigeren)$/i;
+    const ACCEPT = /^(accept( all)?( cookies)?|allow( all)?( cookies)?|agree|i agree|i accept|ok(ay)?|got it|alle akzeptieren|akzeptieren|zustimmen|tout accepter|accepter|j'accepte|aceptar( todo)?|accetta( tutto)?|akkoord|accepteren)$/i;
+    const CONTAINER = /cookie|consent|gdpr|privacy|cmp/i;
+    const byHeuristic = () => {
+        const candidates = Array.from(document.querySelectorAll('button, [role="button"], a, input[type="button"], input[type="submit"]'))
+            .filter((el) => {
+                if (!visible(el)) return false;
+                for (let p = el.parentElement, depth = 0; p && depth < 8; p = p.parentElement, depth++) {
+                    const label = (p.id || '') + ' ' + (typeof p.className === 'string' ? p.className : '') + ' ' + (p.getAttribute('aria-label') || '');
+                    if (CONTAINER.test(label)) return true;
+                }
+                return false;
+            });
+        const text = (el) => (el.innerText || el.value || el.getAttribute('aria-label') || '').trim().replace(/\s+/g, ' ');
+        const reject = candidates.find((el) => REJECT.test(text(el)));
+        if (reject) return { el: reject, cmp: null, action: 'reject', rule: 'heuristic' };
+        const accept = candidates.find((el) => ACCEPT.test(text(el)));
+        if (accept) return { el: accept, cmp: null, action: 'accept', rule: 'heuristic' };
+        return null;
+    };
+
+    const deadline = Date.now() + waitMs;
+    for (;;) {
+        const hit = byRule() || byHeuristic();
+        if (hit) {
+            const label = (hit.el.innerText || hit.el.value || '').trim().slice(0, 80);
+            hit.el.click();
+            return { dismissed: true, cmp: hit.cmp, action: hit.action, rule: hit.rule, text: label };
+        }
+        if (Date.now() >= deadline) return { dismissed: false };
+        await new Promise((r) => setTimeout(r, pollMs));
+    }
+})"#;
+
+/// Try to dismiss a consent banner on the tab. Never fails the caller:
+/// evaluation errors come back as `{dismissed: false, error}`.
+pub async fn dismiss(cdp: &CdpSession, target_id: &str) -> Value {
+    let rules = serde_json::to_string(CMP_RULES).unwrap_or_else(|_| "[]".to_string());
+    let expression = format!("({DISMISS_JS})({rules}, {CONSENT_WAIT_MS}, {CONSENT_POLL_MS})");
+    let resp = cdp
+        .execute_on_tab(
+            target_id,
+            "Runtime.evaluate",
+            json!({ "expression": expression, "returnByValue": true, "awaitPromise": true }),
+        )
+        .await;
+    match resp {
+        Ok(v) => match v.pointer("/result/result/value") {
+            Some(value) if v.pointer("/result/exceptionDetails").is_none() => value.clone(),
+            _ => json!({ "dismissed": false, "error": "consent script raised an exception" }),
+        },
+        Err(e) => json!({ "dismissed": false, "error": e.to_string() }),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn every_cmp_prefers_reject_over_accept() {
+        let mut seen_accept = std::collections::HashSet::new();
+        for (cmp, action, _) in CMP_RULES {
+            match *action {
+                "accept" => {
+                    seen_accept.insert(*cmp);
+                }
+                "reject" => assert!(
+                    !seen_accept.contains(cmp),
+                    "{cmp}: reject rule listed after an accept rule"
+                ),
+                other => panic!("{cmp}: unknown action {other}"),
+            }
+        }
+    }
+}
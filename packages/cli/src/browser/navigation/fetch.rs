@@ -0,0 +1,289 @@
+use clap::Args;
+use serde::{Deserialize, Serialize};
+use serde_json::{Value, json};
+
+use crate::action_result::ActionResult;
+use crate::browser::navigation;
+use crate::daemon::cdp_session::{cdp_error_to_result, get_cdp_and_target};
+use crate::daemon::guardrails::{self, DomainPolicy};
+use crate::daemon::registry::SharedRegistry;
+use crate::output::ResponseContext;
+
+/// Fetch a URL from inside the page (cookies and auth apply)
+#[derive(Args, Debug, Clone, Serialize, Deserialize)]
+#[command(after_help = "\
+Examples:
+  actionbook browser fetch https://app.example.com/export.csv --output report.csv --session s1 --tab t1
+  actionbook browser fetch /api/me --session s1 --tab t1
+  actionbook browser fetch /api/items --method POST --header \"Content-Type: application/json\" --data '{\"name\":\"x\"}' --session s1 --tab t1
+
+The request runs as fetch() in the tab's page, so the session's cookies,
+HTTP auth and any headers the site's own scripts would send apply. Relative
+URLs resolve against the current page. Cross-origin URLs are subject to the
+site's CORS rules; navigate to the target origin first if the request is
+blocked.
+
+Without --output the body is returned as text when it is UTF-8 (binary
+bodies report only their size). A non-2xx status fails with HTTP_ERROR and
+nothing is written.")]
+pub struct Cmd {
+    /// URL to fetch (absolute, or relative to the current page)
+    pub url: String,
+    /// Write the response body to this file
+    #[arg(long, value_name = "FILE")]
+    #[serde(default)]
+    pub output: Option<String>,
+    /// HTTP method
+    #[arg(long, default_value = "GET")]
+    #[serde(default = "default_method")]
+    pub method: String,
+    /// Extra request header as "Name: value" (repeatable)
+    #[arg(long = "header", value_name = "NAME: VALUE")]
+    #[serde(default)]
+    pub headers: Vec<String>,
+    /// Request body
+    #[arg(long)]
+    #[serde(default)]
+    pub data: Option<String>,
+    /// Give up after this many milliseconds
+    #[arg(long, default_value_t = 30000)]
+    #[serde(default = "default_timeout")]
+    pub timeout: u64,
+    /// Session ID
+    #[arg(long)]
+    #[serde(rename = "session_id")]
+    pub session: String,
+    /// Tab ID
+    #[arg(long)]
+    #[serde(rename = "tab_id")]
+    pub tab: String,
+}
+
+fn default_method() -> String {
+    "GET".to_string()
+}
+
+fn default_timeout() -> u64 {
+    30000
+}
+
+pub const COMMAND_NAME: &str = "browser fetch";
+
+/// Called as `(FETCH_JS)(url, init, timeoutMs)`. The body comes back
+/// base64-encoded so binary downloads survive `returnByValue`.
+const FETCH_JS: &str = r#"(async function(url, init, timeoutMs) {
+    init.credentials = 'include';
+    init.signal = AbortSignal.timeout(timeoutMs);
+    const resp = await fetch(url, init);
+    const bytes = new Uint8Array(await resp.arrayBuffer());
+    let binary = '';
+    for (let i = 0; i < bytes.length; i += 0x8000) {
+        binary += String.fromCharCode.apply(null, bytes.subarray(i, i + 0x8000));
+    }
+    const headers = {};
+    resp.headers.forEach((v, k) => { headers[k] = v; });
+    return {
+        url: resp.url,
+        status: resp.status,
+        statusText: resp.statusText,
+        headers,
+        body: btoa(binary),
+    };
+})"#;
+
+pub fn context(cmd: &Cmd, result: &ActionResult) -> Option<ResponseContext> {
+    if let ActionResult::Fatal { code, .. } = result
+        && code == "SESSION_NOT_FOUND"
+    {
+        return None;
+    }
+    let tab_id = if let ActionResult::Fatal { code, .. } = result
+        && code == "TAB_NOT_FOUND"
+    {
+        None
+    } else {
+        Some(cmd.tab.clone())
+    };
+    let (url, title) = match result {
+        ActionResult::Ok { data } => (
+            data.get("__ctx_url")
+                .and_then(|v| v.as_str())
+                .map(String::from),
+            data.get("__ctx_title")
+                .and_then(|v| v.as_str())
+                .map(String::from),
+        ),
+        _ => (None, None),
+    };
+    Some(ResponseContext {
+        session_id: cmd.session.clone(),
+        tab_id,
+        window_id: None,
+        url,
+        title,
+    })
+}
+
+pub async fn execute(cmd: &Cmd, registry: &SharedRegistry) -> ActionResult {
+    use base64::Engine;
+
+    let mut headers = serde_json::Map::new();
+    for raw in &cmd.headers {
+        match parse_header(raw) {
+            Ok((name, value)) => {
+                headers.insert(name, json!(value));
+            }
+            Err(e) => return ActionResult::fatal("INVALID_ARGUMENT", e),
+        }
+    }
+    let method = cmd.method.to_ascii_uppercase();
+    if cmd.data.is_some() && matches!(method.as_str(), "GET" | "HEAD") {
+        return ActionResult::fatal_with_hint(
+            "INVALID_ARGUMENT",
+            format!("--data cannot be sent with {method}"),
+            "pass --method POST (or PUT/PATCH)",
+        );
+    }
+
+    // Relative URLs stay on the page's own origin; absolute ones go through
+    // the same guardrails as navigation.
+    if cmd.url.contains("://") {
+        let policy = match DomainPolicy::current() {
+            Ok(policy) => policy,
+            Err(e) => return e,
+        };
+        if let Err(v) = policy.check(&cmd.url) {
+            return guardrails::violation_to_result(v);
+        }
+    }
+
+    let (cdp, target_id) = match get_cdp_and_target(registry, &cmd.session, &cmd.tab).await {
+        Ok(v) => v,
+        Err(e) => return e,
+    };
+
+    let mut init = json!({ "method": method, "headers": headers });
+    if let Some(data) = &cmd.data {
+        init["body"] = json!(data);
+    }
+    let expression = format!(
+        "({FETCH_JS})({}, {init}, {})",
+        serde_json::to_string(&cmd.url).unwrap_or_default(),
+        cmd.timeout
+    );
+    let resp = match cdp
+        .execute_on_tab(
+            &target_id,
+            "Runtime.evaluate",
+            json!({ "expression": expression, "returnByValue": true, "awaitPromise": true }),
+        )
+        .await
+    {
+        Ok(v) => v,
+        Err(e) => return cdp_error_to_result(e, "CDP_ERROR"),
+    };
+    if let Some(exc) = resp.pointer("/result/exceptionDetails") {
+        let msg = exc
+            .pointer("/exception/description")
+            .and_then(|v| v.as_str())
+            .or_else(|| exc.get("text").and_then(|v| v.as_str()))
+            .unwrap_or("fetch failed");
+        let (code, hint) = if msg.contains("TimeoutError") {
+            (
+                "TIMEOUT",
+                format!("no response within {}ms; raise --timeout", cmd.timeout),
+            )
+        } else {
+            (
+                "FETCH_FAILED",
+                "the request may be blocked by CORS; navigate to the URL's origin first"
+                    .to_string(),
+            )
+        };
+        return ActionResult::fatal_with_hint(code, format!("fetch {}: {msg}", cmd.url), hint);
+    }
+    let value = resp
+        .pointer("/result/result/value")
+        .cloned()
+        .unwrap_or(Value::Null);
+
+    let final_url = value["url"].as_str().unwrap_or(&cmd.url).to_string();
+    let status = value["status"].as_u64().unwrap_or(0);
+    let body = match base64::engine::general_purpose::STANDARD
+        .decode(value["body"].as_str().unwrap_or_default())
+    {
+        Ok(b) => b,
+        Err(e) => return ActionResult::fatal("INTERNAL_ERROR", format!("bad body encoding: {e}")),
+    };
+    let text = String::from_utf8(body.clone()).ok();
+
+    if !(200..300).contains(&status) {
+        let snippet: String = text.as_deref().unwrap_or("").chars().take(500).collect();
+        return ActionResult::fatal_with_details(
+            "HTTP_ERROR",
+            format!(
+                "{final_url} returned {status} {}",
+                value["statusText"].as_str().unwrap_or("")
+            )
+            .trim_end()
+            .to_string(),
+            "check the session is logged in and the URL is correct",
+            json!({ "status": status, "body": snippet }),
+        );
+    }
+
+    let url = navigation::get_tab_url(&cdp, &target_id).await;
+    let title = navigation::get_tab_title(&cdp, &target_id).await;
+    let mut data = json!({
+        "url": final_url,
+        "status": status,
+        "content_type": value["headers"]["content-type"],
+        "bytes": body.len(),
+        "headers": value["headers"],
+        "__ctx_url": url,
+        "__ctx_title": title,
+    });
+    if let Some(path) = &cmd.output {
+        if let Err(e) = std::fs::write(path, &body) {
+            return ActionResult::fatal("IO_ERROR", format!("failed to write {path}: {e}"));
+        }
+        data["path"] = json!(path);
+    } else {
+        match text {
+            Some(text) => data["body"] = json!(text),
+            None => data["binary"] = json!(true),
+        }
+    }
+    ActionResult::ok(data)
+}
+
+/// Split `Name: value` into its trimmed parts.
+fn parse_header(raw: &str) -> Result<(String, String), String> {
+    match raw.split_once(':') {
+        Some((name, value)) if !name.trim().is_empty() => {
+            Ok((name.trim().to_string(), value.trim().to_string()))
+        }
+        _ => Err(format!(
+            "invalid --header '{raw}' (expected \"Name: value\")"
+        )),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_header_splits_on_first_colon() {
+        assert_eq!(
+            parse_header("X-Token: a:b"),
+            Ok(("X-Token".to_string(), "a:b".to_string()))
+        );
+        assert_eq!(
+            parse_header(" Accept :text/csv "),
+            Ok(("Accept".to_string(), "text/csv".to_string()))
+        );
+        assert!(parse_header("no-colon").is_err());
+        assert!(parse_header(": v").is_err());
+    }
+}
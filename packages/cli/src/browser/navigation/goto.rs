@@ -1,6 +1,9 @@
+use std::time::Duration;
+
 use clap::Args;
 use serde::{Deserialize, Serialize};
 use serde_json::json;
+use tokio::time::Instant;
 
 use crate::action_result::ActionResult;
 use crate::daemon::cdp::ensure_scheme_or_fatal;
@@ -21,6 +24,16 @@ pub enum WaitUntil {
     Load,
 }
 
+impl WaitUntil {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::None => "none",
+            Self::Domcontentloaded => "domcontentloaded",
+            Self::Load => "load",
+        }
+    }
+}
+
 /// Navigate to URL
 #[derive(Args, Debug, Clone, Serialize, Deserialize)]
 #[command(after_help = "\
@@ -28,6 +41,7 @@ Examples:
   actionbook browser goto https://google.com --session s1 --tab t1
   actionbook browser goto https://example.com/login --session s1 --tab t1 --wait-until domcontentloaded
   actionbook browser goto https://example.com --session s1 --tab t1 --wait-until none
+  actionbook browser goto https://example.com --session s1 --tab t1 --timeout 60000
 
 A scheme (https://) is added automatically if omitted.
 After navigation, context.url and context.title are updated.
@@ -35,7 +49,20 @@ After navigation, context.url and context.title are updated.
 --wait-until controls when the command returns:
   domcontentloaded (default) — wait for DOMContentLoaded (DOM ready, faster)
   load                       — wait for the page load event (all resources)
-  none                       — return immediately after navigation starts")]
+  none                       — return immediately after navigation starts
+
+--timeout fails the command with TIMEOUT when the page has not reached
+--wait-until in time (default 30000ms, or `[browser] navigation_timeout_ms`
+in config.toml). It is the global --timeout: for goto it bounds the
+navigation itself, and the client waits a little longer for the answer.
+
+--dismiss-consent clicks away a cookie consent banner after navigation,
+preferring reject/necessary-only over accept. Enable it for every goto
+with `[navigate] auto_dismiss_consent = true` in config.toml.
+
+--max-download-bytes stops the page and fails with DOWNLOAD_BUDGET_EXCEEDED
+once the tab has downloaded more than BYTES during the navigation. Set a
+default for every navigation and capture with `browser start --max-download-bytes`.")]
 pub struct Cmd {
     /// Target URL
     pub url: String,
@@ -51,6 +78,20 @@ pub struct Cmd {
     #[arg(long, value_enum, default_value = "domcontentloaded")]
     #[serde(default)]
     pub wait_until: WaitUntil,
+    /// Navigation timeout in milliseconds, taken from the global --timeout
+    #[arg(skip)]
+    #[serde(default)]
+    pub timeout: Option<u64>,
+
+    /// Dismiss a cookie consent banner after navigation
+    #[arg(long)]
+    #[serde(default)]
+    pub dismiss_consent: bool,
+
+    /// Abort when the tab downloads more than this many bytes (default: session budget)
+    #[arg(long, value_name = "BYTES")]
+    #[serde(default)]
+    pub max_download_bytes: Option<u64>,
 }
 
 pub const COMMAND_NAME: &str = "browser goto";
@@ -80,6 +121,14 @@ pub fn context(cmd: &Cmd, result: &ActionResult) -> Option<ResponseContext> {
     })
 }
 
+fn timed_out(url: &str, stage: &str, timeout_ms: u64) -> ActionResult {
+    ActionResult::fatal_with_hint(
+        "TIMEOUT",
+        format!("{url} did not {stage} within {timeout_ms}ms"),
+        "raise --timeout or [browser] navigation_timeout_ms in config.toml, or pass --wait-until none",
+    )
+}
+
 pub async fn execute(cmd: &Cmd, registry: &SharedRegistry) -> ActionResult {
     let final_url = match ensure_scheme_or_fatal(&cmd.url) {
         Ok(u) => u,
@@ -142,12 +191,18 @@ pub async fn execute(cmd: &Cmd, registry: &SharedRegistry) -> ActionResult {
             while rx.try_recv().is_ok() {}
         }
 
-        match cdp
-            .execute_on_tab(&target_id, "Page.navigate", json!({ "url": final_url }))
-            .await
-        {
-            Err(e) => return cdp_error_to_result(e, "NAVIGATION_FAILED"),
-            Ok(v) => {
+        // --timeout (or the configured navigation timeout) covers the whole
+        // navigation: the request itself and the wait for --wait-until.
+        let timeout_ms = cmd
+            .timeout
+            .unwrap_or_else(crate::config::navigation_timeout_ms);
+        let deadline = Instant::now() + Duration::from_millis(timeout_ms);
+
+        let navigate = cdp.execute_on_tab(&target_id, "Page.navigate", json!({ "url": final_url }));
+        match tokio::time::timeout_at(deadline, navigate).await {
+            Err(_) => return timed_out(&final_url, "start loading", timeout_ms),
+            Ok(Err(e)) => return cdp_error_to_result(e, "NAVIGATION_FAILED"),
+            Ok(Ok(v)) => {
                 if let Some(err_text) = v["result"]["errorText"].as_str()
                     && !err_text.is_empty()
                 {
@@ -156,16 +211,22 @@ pub async fn execute(cmd: &Cmd, registry: &SharedRegistry) -> ActionResult {
             }
         }
 
-        // Wait for the subscribed CDP event.
-        // No internal timeout — the global --timeout flag (set in main.rs) controls
-        // the overall request deadline. If the user didn't set --timeout, we wait
-        // until the browser fires the event (fast for local pages, may need --timeout
-        // for heavy external pages).
-        if let Some(ref mut rx) = event_rx {
-            let _ = rx.recv().await; // None = channel closed (session died), proceed best-effort
+        // Wait for the subscribed CDP event. A closed channel (session died)
+        // proceeds best-effort.
+        if let Some(ref mut rx) = event_rx
+            && tokio::time::timeout_at(deadline, rx.recv()).await.is_err()
+        {
+            let stage = format!("reach {}", cmd.wait_until.as_str());
+            return timed_out(&final_url, &stage, timeout_ms);
         }
     }
 
+    let consent = if cmd.dismiss_consent || crate::config::auto_dismiss_consent() {
+        Some(super::consent::dismiss(&cdp, &target_id).await)
+    } else {
+        None
+    };
+
     // Get to_url and title after navigation (+ wait)
     let to_url = super::get_tab_url(&cdp, &target_id).await;
     let title = super::get_tab_title(&cdp, &target_id).await;
@@ -187,11 +248,15 @@ pub async fn execute(cmd: &Cmd, registry: &SharedRegistry) -> ActionResult {
         }
     }
 
-    ActionResult::ok(json!({
+    let mut data = json!({
         "kind": "goto",
         "requested_url": cmd.url,
         "from_url": from_url,
         "to_url": to_url,
         "title": title,
-    }))
+    });
+    if let Some(consent) = consent {
+        data["consent"] = consent;
+    }
+    ActionResult::ok(data)
 }
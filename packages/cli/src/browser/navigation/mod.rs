@@ -1,9 +1,13 @@
 pub mod back;
+pub mod consent;
+pub mod fetch;
 pub mod forward;
 pub mod goto;
 pub mod reload;
+pub mod trace_redirects;
 
-use crate::daemon::cdp_session::CdpSession;
+use crate::action_result::ActionResult;
+use crate::daemon::cdp_session::{CdpSession, cdp_error_to_result};
 use serde_json::json;
 
 /// Get the current URL of a tab via Runtime.evaluate.
@@ -57,3 +61,86 @@ pub async fn get_tab_origin(cdp: &CdpSession, target_id: &str) -> String {
     .and_then(|v| v["result"]["result"]["value"].as_str().map(String::from))
     .unwrap_or_default()
 }
+
+/// Fetch `url` through the tab's network stack, so the tab's cookies and
+/// auth apply as they would for the page itself.
+pub async fn load_resource(
+    cdp: &CdpSession,
+    target_id: &str,
+    url: &str,
+) -> Result<Vec<u8>, ActionResult> {
+    use base64::Engine;
+
+    let resp = cdp
+        .execute_on_tab(
+            target_id,
+            "Network.loadNetworkResource",
+            json!({
+                "frameId": target_id,
+                "url": url,
+                "options": { "disableCache": false, "includeCredentials": true },
+            }),
+        )
+        .await
+        .map_err(|e| cdp_error_to_result(e, "CDP_ERROR"))?;
+    let resource = &resp["result"]["resource"];
+    let stream = match resource["stream"].as_str() {
+        Some(stream)
+            if resource["success"].as_bool() == Some(true)
+                && resource["httpStatusCode"].as_u64().is_none_or(|c| c < 400) =>
+        {
+            stream.to_string()
+        }
+        _ => {
+            let reason = resource["netErrorName"]
+                .as_str()
+                .map(String::from)
+                .or_else(|| {
+                    resource["httpStatusCode"]
+                        .as_u64()
+                        .map(|c| format!("HTTP {c}"))
+                })
+                .unwrap_or_else(|| "no response".to_string());
+            return Err(ActionResult::fatal(
+                "NAVIGATION_FAILED",
+                format!("failed to fetch {url}: {reason}"),
+            ));
+        }
+    };
+
+    let mut bytes = Vec::new();
+    let read = loop {
+        let chunk = match cdp
+            .execute_on_tab(
+                target_id,
+                "IO.read",
+                json!({ "handle": stream, "size": 1 << 20 }),
+            )
+            .await
+        {
+            Ok(chunk) => chunk,
+            Err(e) => break Err(cdp_error_to_result(e, "CDP_ERROR")),
+        };
+        let data = chunk["result"]["data"].as_str().unwrap_or("");
+        if chunk["result"]["base64Encoded"].as_bool() == Some(true) {
+            match base64::engine::general_purpose::STANDARD.decode(data) {
+                Ok(decoded) => bytes.extend(decoded),
+                Err(e) => {
+                    break Err(ActionResult::fatal(
+                        "INTERNAL_ERROR",
+                        format!("base64 decode failed: {e}"),
+                    ));
+                }
+            }
+        } else {
+            bytes.extend_from_slice(data.as_bytes());
+        }
+        if chunk["result"]["eof"].as_bool() != Some(false) {
+            break Ok(bytes);
+        }
+    };
+    let _ = cdp
+        .execute_on_tab(target_id, "IO.close", json!({ "handle": stream }))
+        .await;
+    read
+}
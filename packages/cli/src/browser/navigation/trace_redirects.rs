@@ -0,0 +1,388 @@
+use std::time::Duration;
+
+use clap::Args;
+use serde::{Deserialize, Serialize};
+use serde_json::{Value, json};
+use tokio::time::Instant;
+
+use crate::action_result::ActionResult;
+use crate::daemon::cdp::ensure_scheme_or_fatal;
+use crate::daemon::cdp_session::{cdp_error_to_result, get_cdp_and_target};
+use crate::daemon::registry::SharedRegistry;
+use crate::output::ResponseContext;
+
+/// Navigate to a URL and report every redirect hop
+#[derive(Args, Debug, Clone, Serialize, Deserialize)]
+#[command(after_help = "\
+Examples:
+  actionbook browser trace-redirects https://t.co/abc --session s1 --tab t1
+  actionbook browser trace-redirects https://example.com/login --settle 5000 --session s1 --tab t1
+
+Navigates the tab and lists each document it passed through with its HTTP
+status and how it moved on:
+  http          3xx response with a Location header
+  meta-refresh  <meta http-equiv=refresh> or a Refresh header
+  js            script navigation (location.href = ..., form.submit(), ...)
+The last hop has no type. After each page loads, the trace waits --settle
+ms for a client-side redirect before deciding the chain has ended; raise it
+for pages that redirect on a longer timer.")]
+pub struct Cmd {
+    /// URL to start from
+    pub url: String,
+    /// How long a loaded page must stay put before the chain counts as ended (ms)
+    #[arg(long, default_value_t = 2000)]
+    #[serde(default = "default_settle")]
+    pub settle: u64,
+    /// Give up after this many milliseconds (default 30000, or `[browser] navigation_timeout_ms`)
+    #[arg(long)]
+    #[serde(default)]
+    pub timeout: Option<u64>,
+    /// Session ID
+    #[arg(long)]
+    #[serde(rename = "session_id")]
+    pub session: String,
+    /// Tab ID
+    #[arg(long)]
+    #[serde(rename = "tab_id")]
+    pub tab: String,
+}
+
+fn default_settle() -> u64 {
+    2000
+}
+
+pub const COMMAND_NAME: &str = "browser trace-redirects";
+
+const EVENTS: [&str; 5] = [
+    "Network.requestWillBeSent",
+    "Network.responseReceived",
+    "Network.loadingFailed",
+    "Page.frameRequestedNavigation",
+    "Page.loadEventFired",
+];
+
+/// One document in the chain. `kind` is how it moved on to the next one.
+#[derive(Debug, Clone, PartialEq)]
+struct Hop {
+    url: String,
+    status: Option<u64>,
+    kind: Option<&'static str>,
+    error: Option<String>,
+}
+
+/// Folds the main frame's navigation events into hops.
+#[derive(Debug)]
+struct Tracer {
+    main_frame: String,
+    hops: Vec<Hop>,
+    request_id: Option<String>,
+    /// Why the main frame is about to navigate, from
+    /// `Page.frameRequestedNavigation`.
+    pending_kind: Option<&'static str>,
+}
+
+impl Tracer {
+    fn new(main_frame: String) -> Self {
+        Self {
+            main_frame,
+            hops: Vec::new(),
+            request_id: None,
+            pending_kind: None,
+        }
+    }
+
+    /// Fold one event. Returns `true` when the main frame started a new
+    /// document request, i.e. the chain is still moving.
+    fn apply(&mut self, method: &str, params: &Value) -> bool {
+        let s = |k: &str| params.get(k).and_then(|v| v.as_str()).unwrap_or("");
+        match method {
+            "Page.frameRequestedNavigation" if s("frameId") == self.main_frame => {
+                if !self.hops.is_empty() {
+                    self.pending_kind = Some(match s("reason") {
+                        "metaTagRefresh" | "httpHeaderRefresh" => "meta-refresh",
+                        _ => "js",
+                    });
+                }
+                false
+            }
+            "Network.requestWillBeSent"
+                if s("type") == "Document" && s("frameId") == self.main_frame =>
+            {
+                let url = params
+                    .pointer("/request/url")
+                    .and_then(|v| v.as_str())
+                    .unwrap_or("")
+                    .to_string();
+                let same_request = self.request_id.as_deref() == Some(s("requestId"));
+                if let Some(redirect) = params.get("redirectResponse")
+                    && same_request
+                    && let Some(last) = self.hops.last_mut()
+                {
+                    last.status = redirect.get("status").and_then(|v| v.as_u64());
+                    last.kind = Some("http");
+                } else if let Some(last) = self.hops.last_mut() {
+                    last.kind = Some(self.pending_kind.take().unwrap_or("js"));
+                }
+                self.pending_kind = None;
+                self.request_id = Some(s("requestId").to_string());
+                self.hops.push(Hop {
+                    url,
+                    status: None,
+                    kind: None,
+                    error: None,
+                });
+                true
+            }
+            "Network.responseReceived" if self.request_id.as_deref() == Some(s("requestId")) => {
+                if let Some(last) = self.hops.last_mut() {
+                    last.status = params.pointer("/response/status").and_then(|v| v.as_u64());
+                }
+                false
+            }
+            "Network.loadingFailed" if self.request_id.as_deref() == Some(s("requestId")) => {
+                if let Some(last) = self.hops.last_mut() {
+                    last.error = Some(s("errorText").to_string());
+                }
+                false
+            }
+            _ => false,
+        }
+    }
+
+    fn to_json(&self) -> Vec<Value> {
+        self.hops
+            .iter()
+            .map(|h| {
+                let mut hop = json!({ "url": h.url, "status": h.status, "type": h.kind });
+                if let Some(error) = &h.error {
+                    hop["error"] = json!(error);
+                }
+                hop
+            })
+            .collect()
+    }
+}
+
+pub fn context(cmd: &Cmd, result: &ActionResult) -> Option<ResponseContext> {
+    if let ActionResult::Fatal { code, .. } = result
+        && code == "SESSION_NOT_FOUND"
+    {
+        return None;
+    }
+    let tab_id = if let ActionResult::Fatal { code, .. } = result
+        && code == "TAB_NOT_FOUND"
+    {
+        None
+    } else {
+        Some(cmd.tab.clone())
+    };
+    let (url, title) = match result {
+        ActionResult::Ok { data } => (
+            data.get("__ctx_url")
+                .and_then(|v| v.as_str())
+                .map(String::from),
+            data.get("__ctx_title")
+                .and_then(|v| v.as_str())
+                .map(String::from),
+        ),
+        _ => (None, None),
+    };
+    Some(ResponseContext {
+        session_id: cmd.session.clone(),
+        tab_id,
+        window_id: None,
+        url,
+        title,
+    })
+}
+
+pub async fn execute(cmd: &Cmd, registry: &SharedRegistry) -> ActionResult {
+    let url = match ensure_scheme_or_fatal(&cmd.url) {
+        Ok(u) => u,
+        Err(e) => return e,
+    };
+    let (cdp, target_id) = match get_cdp_and_target(registry, &cmd.session, &cmd.tab).await {
+        Ok(v) => v,
+        Err(e) => return e,
+    };
+    let Some(cdp_session_id) = cdp.get_cdp_session_id(&target_id).await else {
+        return ActionResult::fatal(
+            "INTERNAL_ERROR",
+            format!("no CDP session for target '{target_id}'"),
+        );
+    };
+
+    let mut receivers = Vec::new();
+    for method in EVENTS {
+        receivers.push(cdp.subscribe_events(&cdp_session_id, method).await);
+    }
+    let _ = cdp
+        .execute_on_tab(&target_id, "Page.enable", json!({}))
+        .await;
+    for rx in &mut receivers {
+        while rx.try_recv().is_ok() {}
+    }
+    let main_frame = cdp
+        .execute_on_tab(&target_id, "Page.getFrameTree", json!({}))
+        .await
+        .ok()
+        .and_then(|v| {
+            v.pointer("/result/frameTree/frame/id")
+                .and_then(|v| v.as_str())
+                .map(String::from)
+        })
+        .unwrap_or_else(|| target_id.clone());
+
+    // One channel for all subscriptions, so the loop below can wait on it
+    // alongside the settle timer.
+    let (tx, mut events) = tokio::sync::mpsc::channel::<Value>(1024);
+    for mut rx in receivers {
+        let tx = tx.clone();
+        tokio::spawn(async move {
+            while let Some(event) = rx.recv().await {
+                if tx.send(event).await.is_err() {
+                    break;
+                }
+            }
+        });
+    }
+    drop(tx);
+
+    match cdp
+        .execute_on_tab(&target_id, "Page.navigate", json!({ "url": url }))
+        .await
+    {
+        Err(e) => return cdp_error_to_result(e, "NAVIGATION_FAILED"),
+        Ok(v) => {
+            if let Some(err_text) = v["result"]["errorText"].as_str()
+                && !err_text.is_empty()
+            {
+                return ActionResult::fatal("NAVIGATION_FAILED", err_text.to_string());
+            }
+        }
+    }
+
+    let mut tracer = Tracer::new(main_frame);
+    let timeout_ms = cmd
+        .timeout
+        .unwrap_or_else(crate::config::navigation_timeout_ms);
+    let deadline = Instant::now() + Duration::from_millis(timeout_ms);
+    let settle = Duration::from_millis(cmd.settle);
+    let mut quiet_until: Option<Instant> = None;
+    let settled = loop {
+        let wake = quiet_until.map_or(deadline, |q| q.min(deadline));
+        tokio::select! {
+            event = events.recv() => {
+                let Some(event) = event else { break false };
+                let method = event.get("method").and_then(|v| v.as_str()).unwrap_or("");
+                let params = &event["params"];
+                if tracer.apply(method, params) {
+                    quiet_until = None;
+                }
+                match method {
+                    "Page.loadEventFired" => quiet_until = Some(Instant::now() + settle),
+                    "Network.loadingFailed" if tracer.hops.last().is_some_and(|h| h.error.is_some()) => {
+                        quiet_until = Some(Instant::now() + settle);
+                    }
+                    "Page.frameRequestedNavigation" if tracer.pending_kind.is_some() => {
+                        quiet_until = None;
+                    }
+                    _ => {}
+                }
+            }
+            _ = tokio::time::sleep_until(wake) => break quiet_until.is_some_and(|q| q <= deadline),
+        }
+    };
+
+    if tracer.hops.is_empty() {
+        return ActionResult::fatal_with_hint(
+            "TIMEOUT",
+            format!("no navigation to {url} seen within {timeout_ms}ms"),
+            "raise --timeout",
+        );
+    }
+
+    let final_url = super::get_tab_url(&cdp, &target_id).await;
+    let title = super::get_tab_title(&cdp, &target_id).await;
+    {
+        let mut reg = registry.lock().await;
+        reg.clear_ref_cache(&cmd.session, &cmd.tab);
+        if let Some(entry) = reg.get_mut(&cmd.session)
+            && let Some(tab) = entry.tabs.iter_mut().find(|t| t.id.0 == cmd.tab)
+        {
+            tab.url = final_url.clone();
+            tab.title = title.clone();
+        }
+    }
+
+    ActionResult::ok(json!({
+        "requested_url": cmd.url,
+        "final_url": final_url,
+        "hops": tracer.to_json(),
+        "redirects": tracer.hops.len() - 1,
+        "settled": settled,
+        "__ctx_url": final_url,
+        "__ctx_title": title,
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn doc_request(id: &str, url: &str) -> Value {
+        json!({ "requestId": id, "frameId": "MAIN", "type": "Document", "request": { "url": url } })
+    }
+
+    #[test]
+    fn tracer_labels_http_meta_refresh_and_js_hops() {
+        let mut t = Tracer::new("MAIN".to_string());
+        assert!(t.apply(
+            "Network.requestWillBeSent",
+            &doc_request("1", "https://t.test/x")
+        ));
+        let mut redirect = doc_request("1", "https://a.test/");
+        redirect["redirectResponse"] = json!({ "status": 301 });
+        t.apply("Network.requestWillBeSent", &redirect);
+        t.apply(
+            "Network.responseReceived",
+            &json!({ "requestId": "1", "response": { "status": 200 } }),
+        );
+        t.apply(
+            "Page.frameRequestedNavigation",
+            &json!({ "frameId": "MAIN", "reason": "metaTagRefresh" }),
+        );
+        t.apply(
+            "Network.requestWillBeSent",
+            &doc_request("2", "https://b.test/"),
+        );
+        t.apply(
+            "Network.responseReceived",
+            &json!({ "requestId": "2", "response": { "status": 200 } }),
+        );
+        // Subresources and child frames are not hops.
+        t.apply(
+            "Network.requestWillBeSent",
+            &json!({ "requestId": "3", "frameId": "CHILD", "type": "Document", "request": { "url": "https://ads.test/" } }),
+        );
+        t.apply(
+            "Network.requestWillBeSent",
+            &doc_request("4", "https://c.test/"),
+        );
+        t.apply(
+            "Network.loadingFailed",
+            &json!({ "requestId": "4", "errorText": "net::ERR_NAME_NOT_RESOLVED" }),
+        );
+
+        assert_eq!(
+            t.to_json(),
+            vec![
+                json!({ "url": "https://t.test/x", "status": 301, "type": "http" }),
+                json!({ "url": "https://a.test/", "status": 200, "type": "meta-refresh" }),
+                json!({ "url": "https://b.test/", "status": 200, "type": "js" }),
+                json!({ "url": "https://c.test/", "status": null, "type": null,
+                        "error": "net::ERR_NAME_NOT_RESOLVED" }),
+            ]
+        );
+    }
+}
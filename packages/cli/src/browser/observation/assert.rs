@@ -0,0 +1,441 @@
+use clap::Args;
+use serde::{Deserialize, Serialize};
+use serde_json::{Value, json};
+
+use crate::action_result::ActionResult;
+use crate::browser::observation::logs_console::{
+    ENSURE_LOG_CAPTURE_JS, log_capture_on_new_document_js,
+};
+use crate::browser::{element::TabContext, navigation};
+use crate::daemon::cdp_session::cdp_error_to_result;
+use crate::daemon::registry::SharedRegistry;
+use crate::output::ResponseContext;
+
+/// Check the page and fail when a check does not hold
+#[derive(Args, Debug, Clone, Serialize, Deserialize)]
+#[command(after_help = "\
+Examples:
+  actionbook browser assert --text-contains \"Order confirmed\" --session s1 --tab t1
+  actionbook browser assert --url-matches '/dashboard(\\?|$)' --session s1 --tab t1
+  actionbook browser assert --element-visible \"#logout\" --session s1 --tab t1
+  actionbook browser assert --element-count \".cart-item\" --count \">=1\" --session s1 --tab t1
+  actionbook browser assert --no-console-errors --allow 'favicon' --session s1 --tab t1
+
+Every given check runs; the command fails with ASSERTION_FAILED (non-zero
+exit) when any of them does not hold, listing each check with its expected
+and actual value in error.details.checks (--json).
+
+--text-contains  the page's visible text contains TEXT (case-sensitive)
+--url-matches    the current URL matches the regular expression
+--element-visible  the element exists and is visible (CSS, XPath, @ref,
+                   text= or role=)
+--element-count  the number of elements matching a CSS selector or XPath
+                 compares with --count: N, >=N, <=N, >N or <N
+--no-console-errors  the page logged no uncaught exceptions, unhandled
+                 rejections or console.error calls; --allow REGEX (repeatable)
+                 ignores entries whose text matches
+
+Console entries come from the same capture hook as `browser logs`, which
+starts with a fresh buffer on every navigation. The first
+--no-console-errors on a tab also installs the hook for every later
+document, so from the next navigation on errors are caught from page start.
+Until then only errors logged after the hook was installed are seen, and the
+result carries a warning saying so.")]
+pub struct Cmd {
+    /// The page's visible text contains this
+    #[arg(long, value_name = "TEXT")]
+    #[serde(default)]
+    pub text_contains: Option<String>,
+    /// The current URL matches this regular expression
+    #[arg(long, value_name = "REGEX")]
+    #[serde(default)]
+    pub url_matches: Option<String>,
+    /// This element exists and is visible
+    #[arg(long, value_name = "SELECTOR")]
+    #[serde(default)]
+    pub element_visible: Option<String>,
+    /// Count the elements matching this CSS selector or XPath (use with --count)
+    #[arg(long, value_name = "SELECTOR", requires = "count")]
+    #[serde(default)]
+    pub element_count: Option<String>,
+    /// Expected --element-count: N, >=N, <=N, >N or <N
+    #[arg(long, value_name = "EXPR", requires = "element_count")]
+    #[serde(default)]
+    pub count: Option<String>,
+    /// The page logged no uncaught errors or console.error calls
+    #[arg(long)]
+    #[serde(default)]
+    pub no_console_errors: bool,
+    /// Ignore console errors whose text matches this regex (repeatable)
+    #[arg(long, value_name = "REGEX", requires = "no_console_errors")]
+    #[serde(default)]
+    pub allow: Vec<String>,
+    /// Session ID
+    #[arg(long)]
+    #[serde(rename = "session_id")]
+    pub session: String,
+    /// Tab ID
+    #[arg(long)]
+    #[serde(rename = "tab_id")]
+    pub tab: String,
+}
+
+pub const COMMAND_NAME: &str = "browser assert";
+
+/// A `--count` comparison.
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum CountOp {
+    Eq,
+    Ge,
+    Le,
+    Gt,
+    Lt,
+}
+
+fn parse_count(s: &str) -> Result<(CountOp, u64), String> {
+    let s = s.trim();
+    let (op, n) = if let Some(n) = s.strip_prefix(">=") {
+        (CountOp::Ge, n)
+    } else if let Some(n) = s.strip_prefix("<=") {
+        (CountOp::Le, n)
+    } else if let Some(n) = s.strip_prefix('>') {
+        (CountOp::Gt, n)
+    } else if let Some(n) = s.strip_prefix('<') {
+        (CountOp::Lt, n)
+    } else {
+        (CountOp::Eq, s.strip_prefix('=').unwrap_or(s))
+    };
+    let n = n
+        .trim()
+        .parse()
+        .map_err(|_| format!("invalid --count '{s}' (expected N, >=N, <=N, >N or <N)"))?;
+    Ok((op, n))
+}
+
+impl CountOp {
+    fn holds(self, actual: u64, expected: u64) -> bool {
+        match self {
+            CountOp::Eq => actual == expected,
+            CountOp::Ge => actual >= expected,
+            CountOp::Le => actual <= expected,
+            CountOp::Gt => actual > expected,
+            CountOp::Lt => actual < expected,
+        }
+    }
+}
+
+/// Error-level entries from the page's log capture buffers, and whether the
+/// hook has been there since the document started.
+const CONSOLE_ERRORS_JS: &str = r#"(function() {
+    var pick = function(e) { return { level: e.level, text: e.text, source: e.source }; };
+    var errors = (window.__ab_error_logs || []).map(pick);
+    var logged = (window.__ab_console_logs || [])
+        .filter(function(e) { return e.level === 'error'; })
+        .map(pick);
+    return { from_start: window.__ab_log_capture_from_start === true, entries: errors.concat(logged) };
+})()"#;
+
+/// Entries shown in a failed --no-console-errors check.
+const MAX_CONSOLE_ERRORS_SHOWN: usize = 10;
+
+/// Entries whose text matches none of the `--allow` patterns.
+fn disallowed_errors(entries: &[Value], allow: &[regex::Regex]) -> Vec<Value> {
+    entries
+        .iter()
+        .filter(|e| {
+            let text = e["text"].as_str().unwrap_or_default();
+            !allow.iter().any(|re| re.is_match(text))
+        })
+        .cloned()
+        .collect()
+}
+
+pub fn context(cmd: &Cmd, result: &ActionResult) -> Option<ResponseContext> {
+    if let ActionResult::Fatal { code, .. } = result
+        && code == "SESSION_NOT_FOUND"
+    {
+        return None;
+    }
+    let tab_id = if let ActionResult::Fatal { code, .. } = result
+        && code == "TAB_NOT_FOUND"
+    {
+        None
+    } else {
+        Some(cmd.tab.clone())
+    };
+    let (url, title) = match result {
+        ActionResult::Ok { data } => (
+            data.get("__ctx_url")
+                .and_then(|v| v.as_str())
+                .map(String::from),
+            data.get("__ctx_title")
+                .and_then(|v| v.as_str())
+                .map(String::from),
+        ),
+        _ => (None, None),
+    };
+    Some(ResponseContext {
+        session_id: cmd.session.clone(),
+        tab_id,
+        window_id: None,
+        url,
+        title,
+    })
+}
+
+pub async fn execute(cmd: &Cmd, registry: &SharedRegistry) -> ActionResult {
+    if cmd.text_contains.is_none()
+        && cmd.url_matches.is_none()
+        && cmd.element_visible.is_none()
+        && cmd.element_count.is_none()
+        && !cmd.no_console_errors
+    {
+        return ActionResult::fatal_with_hint(
+            "INVALID_ARGUMENT",
+            "browser assert needs at least one check",
+            "pass --text-contains, --url-matches, --element-visible, --element-count or --no-console-errors",
+        );
+    }
+    let allow = match cmd
+        .allow
+        .iter()
+        .map(|p| regex::Regex::new(p))
+        .collect::<Result<Vec<_>, _>>()
+    {
+        Ok(v) => v,
+        Err(e) => return ActionResult::fatal("INVALID_ARGUMENT", format!("invalid --allow: {e}")),
+    };
+    let url_re = match cmd
+        .url_matches
+        .as_deref()
+        .map(regex::Regex::new)
+        .transpose()
+    {
+        Ok(re) => re,
+        Err(e) => {
+            return ActionResult::fatal("INVALID_ARGUMENT", format!("invalid --url-matches: {e}"));
+        }
+    };
+    let count = match cmd.count.as_deref().map(parse_count).transpose() {
+        Ok(c) => c,
+        Err(e) => return ActionResult::fatal("INVALID_ARGUMENT", e),
+    };
+    let mut ctx = match TabContext::new(registry, &cmd.session, &cmd.tab).await {
+        Ok(v) => v,
+        Err(e) => return e,
+    };
+
+    let url = navigation::get_tab_url(&ctx.cdp, &ctx.target_id).await;
+    let title = navigation::get_tab_title(&ctx.cdp, &ctx.target_id).await;
+    let mut checks = Vec::new();
+
+    if let Some(text) = &cmd.text_contains {
+        let expression = format!(
+            "(document.body ? document.body.innerText : '').includes({})",
+            serde_json::to_string(text).unwrap_or_default()
+        );
+        let found = match ctx.evaluate(&expression).await {
+            Ok(v) => v
+                .pointer("/result/result/value")
+                .and_then(|v| v.as_bool())
+                .unwrap_or(false),
+            Err(e) => return cdp_error_to_result(e, "CDP_ERROR"),
+        };
+        checks.push(json!({
+            "check": "text-contains",
+            "expected": text,
+            "actual": found,
+            "passed": found,
+        }));
+    }
+
+    if let (Some(pattern), Some(re)) = (&cmd.url_matches, &url_re) {
+        checks.push(json!({
+            "check": "url-matches",
+            "expected": pattern,
+            "actual": url,
+            "passed": re.is_match(&url),
+        }));
+    }
+
+    if let Some(selector) = &cmd.element_visible {
+        let visible = match ctx.resolve_object(selector).await {
+            Ok((_, object_id)) => {
+                let resp = ctx
+                    .execute_on_element(
+                        "Runtime.callFunctionOn",
+                        json!({
+                            "objectId": object_id,
+                            "functionDeclaration": r#"function() {
+                                var rect = this.getBoundingClientRect();
+                                var style = window.getComputedStyle(this);
+                                return rect.width > 0 && rect.height > 0 && style.visibility !== 'hidden' && style.display !== 'none';
+                            }"#,
+                            "returnByValue": true,
+                        }),
+                    )
+                    .await;
+                match resp {
+                    Ok(v) => json!(
+                        v.pointer("/result/result/value")
+                            .and_then(|v| v.as_bool())
+                            .unwrap_or(false)
+                    ),
+                    Err(e) => return cdp_error_to_result(e, "CDP_ERROR"),
+                }
+            }
+            Err(ActionResult::Fatal { code, .. }) if code == "ELEMENT_NOT_FOUND" => {
+                json!("not found")
+            }
+            Err(e) => return e,
+        };
+        checks.push(json!({
+            "check": "element-visible",
+            "selector": selector,
+            "expected": true,
+            "actual": visible,
+            "passed": visible == json!(true),
+        }));
+    }
+
+    if let (Some(selector), Some((op, expected))) = (&cmd.element_count, count) {
+        let selector_json = serde_json::to_string(selector).unwrap_or_default();
+        let expression = if selector.starts_with('/') {
+            format!(
+                "document.evaluate({selector_json}, document, null, XPathResult.ORDERED_NODE_SNAPSHOT_TYPE, null).snapshotLength"
+            )
+        } else {
+            format!("document.querySelectorAll({selector_json}).length")
+        };
+        let resp = match ctx.evaluate(&expression).await {
+            Ok(v) => v,
+            Err(e) => return cdp_error_to_result(e, "CDP_ERROR"),
+        };
+        let actual = match resp
+            .pointer("/result/result/value")
+            .and_then(|v| v.as_u64())
+        {
+            Some(n) => n,
+            None => {
+                return ActionResult::Fatal {
+                    code: "INVALID_SELECTOR".to_string(),
+                    message: format!("invalid selector: '{selector}'"),
+                    hint: "--element-count takes a CSS selector or XPath".to_string(),
+                    details: Some(json!({ "selector": selector })),
+                };
+            }
+        };
+        checks.push(json!({
+            "check": "element-count",
+            "selector": selector,
+            "expected": cmd.count,
+            "actual": actual,
+            "passed": op.holds(actual, expected),
+        }));
+    }
+
+    let mut warnings: Vec<String> = Vec::new();
+    if cmd.no_console_errors {
+        if let Err(e) = ctx.evaluate(ENSURE_LOG_CAPTURE_JS).await {
+            return cdp_error_to_result(e, "CDP_ERROR");
+        }
+        let resp = match ctx.evaluate(CONSOLE_ERRORS_JS).await {
+            Ok(v) => v,
+            Err(e) => return cdp_error_to_result(e, "CDP_ERROR"),
+        };
+        let captured = resp
+            .pointer("/result/result/value")
+            .cloned()
+            .unwrap_or(Value::Null);
+        if captured["from_start"] != json!(true) {
+            // Best effort: extension tabs may not support new-document scripts.
+            let source = log_capture_on_new_document_js();
+            let _ = ctx
+                .cdp
+                .execute_on_tab(
+                    &ctx.target_id,
+                    "Page.addScriptToEvaluateOnNewDocument",
+                    json!({ "source": source }),
+                )
+                .await;
+            let _ = ctx
+                .evaluate("window.__ab_log_capture_from_start = true")
+                .await;
+            warnings.push(
+                "console capture was not active when this page loaded; errors logged \
+                 before now were not seen (later navigations are captured from the start)"
+                    .to_string(),
+            );
+        }
+        let entries = captured["entries"].as_array().cloned().unwrap_or_default();
+        let errors = disallowed_errors(&entries, &allow);
+        checks.push(json!({
+            "check": "no-console-errors",
+            "expected": 0,
+            "actual": errors.len(),
+            "allowed": entries.len() - errors.len(),
+            "errors": errors.iter().take(MAX_CONSOLE_ERRORS_SHOWN).collect::<Vec<_>>(),
+            "passed": errors.is_empty(),
+        }));
+    }
+
+    let failed: Vec<&Value> = checks
+        .iter()
+        .filter(|c| c["passed"] != json!(true))
+        .collect();
+    if let Some(first) = failed.first() {
+        let message = format!(
+            "{} of {} assertions failed; first: {} expected {}, got {}",
+            failed.len(),
+            checks.len(),
+            first["check"].as_str().unwrap_or(""),
+            first["expected"],
+            first["actual"],
+        );
+        return ActionResult::fatal_with_details(
+            "ASSERTION_FAILED",
+            message,
+            "",
+            json!({ "checks": checks }),
+        );
+    }
+
+    let mut data = json!({
+        "passed": true,
+        "checks": checks,
+        "__ctx_url": url,
+        "__ctx_title": title,
+    });
+    if !warnings.is_empty() {
+        data["__warnings"] = json!(warnings);
+    }
+    ActionResult::ok(data)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_count_reads_operators() {
+        assert_eq!(parse_count("3"), Ok((CountOp::Eq, 3)));
+        assert_eq!(parse_count(">= 1"), Ok((CountOp::Ge, 1)));
+        assert_eq!(parse_count("<5"), Ok((CountOp::Lt, 5)));
+        assert!(parse_count(">=x").is_err());
+        assert!(CountOp::Gt.holds(2, 1));
+        assert!(!CountOp::Le.holds(6, 5));
+    }
+
+    #[test]
+    fn disallowed_errors_skips_allowed_patterns() {
+        let entries = vec![
+            json!({ "level": "error", "text": "Failed to load favicon.ico" }),
+            json!({ "level": "error", "text": "TypeError: x is undefined" }),
+        ];
+        let allow = vec![regex::Regex::new("favicon").unwrap()];
+        let errors = disallowed_errors(&entries, &allow);
+        assert_eq!(errors.len(), 1);
+        assert_eq!(errors[0]["text"], "TypeError: x is undefined");
+        assert_eq!(disallowed_errors(&entries, &[]).len(), 2);
+    }
+}
@@ -7,6 +7,7 @@ use crate::daemon::registry::SharedRegistry;
 use crate::output::ResponseContext;
 
 use super::snapshot;
+use super::snapshot_transform::{SnapshotBackend, SnapshotFormat};
 
 fn cursor_default() -> bool {
     true
@@ -17,7 +18,8 @@ fn cursor_default() -> bool {
 #[command(after_help = "\
 Examples:
   actionbook browser batch-snapshot --session s1 --tabs t1 t2 t3
-  actionbook browser batch-snapshot --session s1 --tabs t1 t2 --interactive --compact")]
+  actionbook browser batch-snapshot --session s1 --tabs t1 t2 --interactive --compact
+  actionbook browser batch-snapshot --session s1 --tabs t1 t2 --format compact")]
 pub struct Cmd {
     /// Session ID
     #[arg(long)]
@@ -47,6 +49,14 @@ pub struct Cmd {
     #[arg(long, short = 's')]
     #[serde(default)]
     pub selector: Option<String>,
+    /// Snapshot file format (see `browser snapshot --help`)
+    #[arg(long, value_enum, default_value = "yaml")]
+    #[serde(default)]
+    pub format: SnapshotFormat,
+    /// Cursor-interactive detection (see `browser snapshot --help`)
+    #[arg(long, value_enum, default_value = "js")]
+    #[serde(default)]
+    pub backend: SnapshotBackend,
 }
 
 pub const COMMAND_NAME: &str = "browser batch-snapshot";
@@ -79,6 +89,9 @@ pub async fn execute(cmd: &Cmd, registry: &SharedRegistry) -> ActionResult {
             cursor: cmd.cursor,
             depth: cmd.depth,
             selector: cmd.selector.clone(),
+            frame: None,
+            format: cmd.format,
+            backend: cmd.backend,
         };
         match snapshot::execute(&tab_cmd, registry).await {
             ActionResult::Ok { data } => {
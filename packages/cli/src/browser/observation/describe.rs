@@ -4,23 +4,30 @@ use serde_json::{Value, json};
 
 use crate::action_result::ActionResult;
 use crate::browser::{element::TabContext, element::element_not_found, navigation};
-use crate::daemon::cdp_session::cdp_error_to_result;
+use crate::daemon::cdp_session::{cdp_error_to_result, get_cdp_and_target};
 use crate::daemon::registry::SharedRegistry;
 use crate::output::ResponseContext;
 
-/// Describe element properties and context.
+/// Describe element properties and context, or the whole page.
 #[derive(Args, Debug, Clone, Serialize, Deserialize)]
 #[command(after_help = "\
 Examples:
+  actionbook browser describe --session s1 --tab t1
   actionbook browser describe \"#submit\" --session s1 --tab t1
   actionbook browser describe @e5 --nearby --session s1 --tab t1
 
-Accepts a CSS selector, XPath, or snapshot ref (@eN from snapshot output).
-Returns element tag, role, name, bounding box, and attributes.
+Without a selector, returns a compact page digest sized for an LLM prompt:
+URL, title, headings, visible interactive element counts by role, a summary
+of each form and its fields, and pagination hints. Use it to orient before
+deciding whether a full snapshot is needed.
+
+With a selector (CSS, XPath, or snapshot ref @eN from snapshot output),
+returns element tag, role, name, bounding box, and attributes.
 Use --nearby to include parent, siblings, and children for context.")]
 pub struct Cmd {
-    /// Selector (CSS, XPath, or @ref)
-    pub selector: String,
+    /// Selector (CSS, XPath, or @ref); omit to describe the page
+    #[serde(default)]
+    pub selector: Option<String>,
     /// Include nearby context (parent, siblings, children)
     #[arg(long)]
     #[serde(default)]
@@ -70,13 +77,47 @@ pub fn context(cmd: &Cmd, result: &ActionResult) -> Option<ResponseContext> {
     })
 }
 
+/// Page digest. Caps keep the result small on large pages: headings, forms,
+/// fields per form and label lengths are all truncated.
+const PAGE_JS: &str = r#"(function() {
+var clip=function(s,n){ s=(s||'').replace(/\s+/g,' ').trim(); return s.length>n?s.substring(0,n-1)+'…':s; };
+var vis=function(e){ var r=e.getBoundingClientRect(); if(r.width<=0||r.height<=0) return false; var st=window.getComputedStyle(e); return st.visibility!=='hidden'&&st.display!=='none'; };
+var role=function(e){ var r=e.getAttribute('role'); if(r) return r; var t=e.tagName.toLowerCase(); if(t==='a') return 'link'; if(t==='button'||t==='summary') return 'button'; if(t==='select') return e.multiple?'listbox':'combobox'; if(t==='textarea') return 'textbox'; if(t==='input'){ var tp=(e.type||'text').toLowerCase(); if(tp==='checkbox'||tp==='radio') return tp; if(tp==='submit'||tp==='button'||tp==='reset'||tp==='image') return 'button'; if(tp==='range') return 'slider'; if(tp==='search') return 'searchbox'; return 'textbox'; } if(e.isContentEditable) return 'textbox'; return t; };
+var label=function(e){ var l=e.getAttribute('aria-label'); if(l) return clip(l,60); var lb=e.getAttribute('aria-labelledby'); if(lb){ var le=document.getElementById(lb); if(le) return clip(le.innerText,60); } if(e.id){ try{ var fl=document.querySelector('label[for="'+CSS.escape(e.id)+'"]'); if(fl) return clip(fl.innerText,60); }catch(_){} } var pl=e.closest('label'); if(pl) return clip(pl.innerText,60); if(e.placeholder) return clip(e.placeholder,60); if(e.title) return clip(e.title,60); if(e.tagName==='INPUT'&&e.value&&/^(submit|button|reset)$/i.test(e.type)) return clip(e.value,60); return clip(e.innerText,60); };
+var sel='a[href],button,input:not([type=hidden]),select,textarea,summary,[role=button],[role=link],[role=checkbox],[role=radio],[role=tab],[role=menuitem],[role=combobox],[role=switch],[role=option],[contenteditable=""],[contenteditable=true]';
+var counts={}; var total=0;
+document.querySelectorAll(sel).forEach(function(e){ if(!vis(e)) return; var r=role(e); counts[r]=(counts[r]||0)+1; total++; });
+var headings=[];
+document.querySelectorAll('h1,h2,h3').forEach(function(h){ if(headings.length>=20||!vis(h)) return; var t=clip(h.innerText,80); if(t) headings.push({level:+h.tagName[1],text:t}); });
+var forms=[];
+document.querySelectorAll('form').forEach(function(f){ if(forms.length>=5||!vis(f)) return; var fields=[]; var n=0; f.querySelectorAll('input:not([type=hidden]),select,textarea').forEach(function(e){ var tp=(e.type||e.tagName).toLowerCase(); if(/^(submit|button|reset|image)$/.test(tp)||!vis(e)) return; n++; if(fields.length>=15) return; var x={type:tp,label:label(e)}; if(e.name) x.name=e.name; if(e.required) x.required=true; fields.push(x); }); var sb=f.querySelector('button[type=submit],input[type=submit],button:not([type])'); forms.push({id:f.id||null,name:f.getAttribute('name')||null,action:f.getAttribute('action')||null,method:(f.getAttribute('method')||'get').toLowerCase(),field_count:n,fields:fields,submit:sb?label(sb):null}); });
+var linkText=function(re){ var out=null; document.querySelectorAll('a[href],button').forEach(function(e){ if(out||!vis(e)) return; var t=((e.getAttribute('aria-label')||'')+' '+(e.innerText||'')).trim(); if(re.test(t)) out=e.href||label(e); }); return out; };
+var rel=function(r){ var e=document.querySelector('link[rel='+r+'],a[rel~='+r+']'); return e?e.href:null; };
+var nav=document.querySelector('nav[aria-label*=agina i],[class*=paginat i],[role=navigation][aria-label*=page i]');
+var pages=nav?Array.from(nav.querySelectorAll('a,button')).filter(function(e){ return /^\d+$/.test((e.innerText||'').trim()); }).length:0;
+var pagination={next:rel('next')||linkText(/^(next|next page|older|more results)\b|^[›»→]$/i),prev:rel('prev')||linkText(/^(prev|previous|previous page|newer)\b|^[‹«←]$/i),pages:pages,load_more:!!linkText(/^(load|show|see) more\b/i),scroll_screens:Math.round(document.documentElement.scrollHeight/Math.max(window.innerHeight,1)*10)/10};
+var dialog=document.querySelector('dialog[open],[role=dialog],[role=alertdialog],[aria-modal=true]');
+return {lang:document.documentElement.lang||null,description:clip((document.querySelector('meta[name=description]')||{}).content,160)||null,headings:headings,interactive:{total:total,by_role:counts},forms:forms,pagination:pagination,dialog:dialog&&vis(dialog)?label(dialog)||true:null};
+})()"#;
+
 pub async fn execute(cmd: &Cmd, registry: &SharedRegistry) -> ActionResult {
+    let Some(selector) = cmd.selector.as_deref() else {
+        if cmd.nearby {
+            return ActionResult::fatal_with_hint(
+                "INVALID_ARGUMENT",
+                "--nearby requires a selector",
+                "pass a selector, or drop --nearby to describe the page",
+            );
+        }
+        return execute_page(cmd, registry).await;
+    };
+
     let mut ctx = match TabContext::new(registry, &cmd.session, &cmd.tab).await {
         Ok(v) => v,
         Err(e) => return e,
     };
 
-    let (_, object_id) = match ctx.resolve_object(&cmd.selector).await {
+    let (_, object_id) = match ctx.resolve_object(selector).await {
         Ok(v) => v,
         Err(e) => return e,
     };
@@ -130,7 +171,7 @@ return res;
         .unwrap_or(Value::Null);
 
     if val.is_null() {
-        return element_not_found(&cmd.selector);
+        return element_not_found(selector);
     }
 
     let summary = {
@@ -159,7 +200,7 @@ return res;
     };
 
     ActionResult::ok(json!({
-        "target": { "selector": cmd.selector },
+        "target": { "selector": selector },
         "summary": summary,
         "role": val["role"],
         "name": val["name"],
@@ -171,3 +212,51 @@ return res;
         "__ctx_title": title,
     }))
 }
+
+async fn execute_page(cmd: &Cmd, registry: &SharedRegistry) -> ActionResult {
+    let (cdp, target_id) = match get_cdp_and_target(registry, &cmd.session, &cmd.tab).await {
+        Ok(v) => v,
+        Err(e) => return e,
+    };
+
+    let resp = match cdp
+        .execute_on_tab(
+            &target_id,
+            "Runtime.evaluate",
+            json!({ "expression": PAGE_JS, "returnByValue": true }),
+        )
+        .await
+    {
+        Ok(v) => v,
+        Err(e) => return cdp_error_to_result(e, "CDP_ERROR"),
+    };
+    if resp.pointer("/result/exceptionDetails").is_some() {
+        let description = resp
+            .pointer("/result/exceptionDetails/exception/description")
+            .and_then(|v| v.as_str())
+            .unwrap_or("JS exception during describe");
+        return ActionResult::fatal("JS_EXCEPTION", description.to_string());
+    }
+    let digest = resp
+        .pointer("/result/result/value")
+        .cloned()
+        .unwrap_or(Value::Null);
+
+    let url = navigation::get_tab_url(&cdp, &target_id).await;
+    let title = navigation::get_tab_title(&cdp, &target_id).await;
+
+    ActionResult::ok(json!({
+        "target": { "page": true },
+        "url": url,
+        "title": title,
+        "lang": digest["lang"],
+        "description": digest["description"],
+        "headings": digest["headings"],
+        "interactive": digest["interactive"],
+        "forms": digest["forms"],
+        "pagination": digest["pagination"],
+        "dialog": digest["dialog"],
+        "__ctx_url": url,
+        "__ctx_title": title,
+    }))
+}
@@ -0,0 +1,347 @@
+use std::time::Duration;
+
+use clap::Args;
+use serde::{Deserialize, Serialize};
+use serde_json::{Value, json};
+
+use crate::action_result::ActionResult;
+use crate::browser::navigation;
+use crate::daemon::cdp_session::{CdpSession, cdp_error_to_result, get_cdp_and_target};
+use crate::daemon::guardrails::{self, DomainPolicy};
+use crate::daemon::registry::SharedRegistry;
+use crate::output::ResponseContext;
+
+/// Discover RSS/Atom/JSON feeds advertised by the page
+#[derive(Args, Debug, Clone, Serialize, Deserialize)]
+#[command(after_help = "\
+Examples:
+  actionbook browser feeds --session s1 --tab t1
+  actionbook browser feeds --session s1 --tab t1 --fetch --limit 5
+
+Discovery reads <link rel=\"alternate\"> tags with an RSS, Atom or JSON Feed type.
+--fetch downloads each feed from the daemon and normalizes it to
+{title, link, description, items: [{title, link, id, published, summary}]}.
+Feed URLs blocked by [guardrails] are reported per feed and not fetched.")]
+pub struct Cmd {
+    /// Session ID
+    #[arg(long)]
+    #[serde(rename = "session_id")]
+    pub session: String,
+    /// Tab ID
+    #[arg(long)]
+    #[serde(rename = "tab_id")]
+    pub tab: String,
+    /// Fetch and normalize each discovered feed
+    #[arg(long)]
+    #[serde(default)]
+    pub fetch: bool,
+    /// Maximum items per fetched feed
+    #[arg(long, default_value_t = 20)]
+    #[serde(default = "default_limit")]
+    pub limit: usize,
+}
+
+fn default_limit() -> usize {
+    20
+}
+
+pub const COMMAND_NAME: &str = "browser feeds";
+
+const FEED_HTTP_TIMEOUT: Duration = Duration::from_secs(15);
+
+const DISCOVER_JS: &str = r#"(function() {
+    const formats = {
+        'application/rss+xml': 'rss',
+        'application/rdf+xml': 'rss',
+        'application/atom+xml': 'atom',
+        'application/feed+json': 'json',
+    };
+    const seen = new Set();
+    const feeds = [];
+    for (const el of document.querySelectorAll('link[rel~="alternate"][href]')) {
+        const type = (el.getAttribute('type') || '').toLowerCase().split(';')[0].trim();
+        const format = formats[type];
+        if (!format || seen.has(el.href)) continue;
+        seen.add(el.href);
+        feeds.push({ url: el.href, type, format, title: el.getAttribute('title') || null });
+    }
+    return feeds;
+})()"#;
+
+/// Normalize an RSS 2.0 / RSS 1.0 (RDF) / Atom document using the page's
+/// DOMParser. Called as `(PARSE_XML_FN)(text, limit)`.
+const PARSE_XML_FN: &str = r#"(function(text, limit) {
+    const doc = new DOMParser().parseFromString(text, 'application/xml');
+    if (doc.querySelector('parsererror')) return { error: 'feed is not well-formed XML' };
+    const txt = (el, sel) => {
+        const n = el && el.querySelector(sel);
+        return n ? n.textContent.trim() : null;
+    };
+    const root = doc.documentElement;
+    if (root.localName === 'feed') {
+        const link = (el) => {
+            const l = el.querySelector(':scope > link[rel="alternate"], :scope > link:not([rel])');
+            return l ? l.getAttribute('href') : null;
+        };
+        const entries = Array.from(root.querySelectorAll(':scope > entry')).slice(0, limit);
+        return {
+            title: txt(root, ':scope > title'),
+            link: link(root),
+            description: txt(root, ':scope > subtitle'),
+            items: entries.map((e) => ({
+                title: txt(e, ':scope > title'),
+                link: link(e),
+                id: txt(e, ':scope > id'),
+                published: txt(e, ':scope > published') || txt(e, ':scope > updated'),
+                summary: txt(e, ':scope > summary') || txt(e, ':scope > content'),
+            })),
+        };
+    }
+    const channel = root.querySelector('channel') || root;
+    const items = Array.from(root.getElementsByTagName('item')).slice(0, limit);
+    return {
+        title: txt(channel, 'title'),
+        link: txt(channel, 'link'),
+        description: txt(channel, 'description'),
+        items: items.map((i) => ({
+            title: txt(i, 'title'),
+            link: txt(i, 'link') || i.getAttribute('rdf:about'),
+            id: txt(i, 'guid'),
+            published: txt(i, 'pubDate') || txt(i, 'date'),
+            summary: txt(i, 'description'),
+        })),
+    };
+})"#;
+
+pub fn context(cmd: &Cmd, result: &ActionResult) -> Option<ResponseContext> {
+    if let ActionResult::Fatal { code, .. } = result
+        && code == "SESSION_NOT_FOUND"
+    {
+        return None;
+    }
+    let tab_id = if let ActionResult::Fatal { code, .. } = result
+        && code == "TAB_NOT_FOUND"
+    {
+        None
+    } else {
+        Some(cmd.tab.clone())
+    };
+    let (url, title) = match result {
+        ActionResult::Ok { data } => (
+            data.get("__ctx_url")
+                .and_then(|v| v.as_str())
+                .map(String::from),
+            data.get("__ctx_title")
+                .and_then(|v| v.as_str())
+                .map(String::from),
+        ),
+        _ => (None, None),
+    };
+    Some(ResponseContext {
+        session_id: cmd.session.clone(),
+        tab_id,
+        window_id: None,
+        url,
+        title,
+    })
+}
+
+pub async fn execute(cmd: &Cmd, registry: &SharedRegistry) -> ActionResult {
+    let (cdp, target_id) = match get_cdp_and_target(registry, &cmd.session, &cmd.tab).await {
+        Ok(v) => v,
+        Err(e) => return e,
+    };
+
+    let mut feeds = match evaluate(&cdp, &target_id, DISCOVER_JS).await {
+        Ok(Value::Array(feeds)) => feeds,
+        Ok(_) => Vec::new(),
+        Err(e) => return e,
+    };
+
+    if cmd.fetch && !feeds.is_empty() {
+        let policy = match DomainPolicy::current() {
+            Ok(policy) => policy,
+            Err(e) => return e,
+        };
+        let client = match reqwest::Client::builder()
+            .timeout(FEED_HTTP_TIMEOUT)
+            .connect_timeout(Duration::from_secs(5))
+            .build()
+        {
+            Ok(c) => c,
+            Err(e) => return ActionResult::fatal("INTERNAL_ERROR", e.to_string()),
+        };
+        for feed in &mut feeds {
+            let url = feed["url"].as_str().unwrap_or_default().to_string();
+            let outcome = match policy.check(&url) {
+                Err(v) => {
+                    let host = v.host.unwrap_or_default();
+                    Err(json!({
+                        "code": guardrails::DOMAIN_NOT_ALLOWED,
+                        "message": format!("feed host '{host}' is not allowed by guardrails"),
+                    }))
+                }
+                Ok(()) => {
+                    fetch_feed(
+                        &client,
+                        &cdp,
+                        &target_id,
+                        &url,
+                        feed["format"].as_str(),
+                        cmd.limit,
+                    )
+                    .await
+                }
+            };
+            match outcome {
+                Ok(normalized) => feed["feed"] = normalized,
+                Err(error) => feed["error"] = error,
+            }
+        }
+    }
+
+    let url = navigation::get_tab_url(&cdp, &target_id).await;
+    let title = navigation::get_tab_title(&cdp, &target_id).await;
+    ActionResult::ok(json!({
+        "count": feeds.len(),
+        "feeds": feeds,
+        "__ctx_url": url,
+        "__ctx_title": title,
+    }))
+}
+
+async fn evaluate(
+    cdp: &CdpSession,
+    target_id: &str,
+    expression: &str,
+) -> Result<Value, ActionResult> {
+    let resp = cdp
+        .execute_on_tab(
+            target_id,
+            "Runtime.evaluate",
+            json!({ "expression": expression, "returnByValue": true }),
+        )
+        .await
+        .map_err(|e| cdp_error_to_result(e, "CDP_ERROR"))?;
+    if let Some(exc) = resp.pointer("/result/exceptionDetails") {
+        let msg = exc
+            .pointer("/exception/description")
+            .and_then(|v| v.as_str())
+            .or_else(|| exc.get("text").and_then(|v| v.as_str()))
+            .unwrap_or("feed evaluation failed");
+        return Err(ActionResult::fatal("JS_EXCEPTION", msg.to_string()));
+    }
+    Ok(resp
+        .pointer("/result/result/value")
+        .cloned()
+        .unwrap_or(Value::Null))
+}
+
+/// Download one feed and normalize it. Errors are returned as a per-feed
+/// `{code, message}` object so one broken feed does not fail the command.
+async fn fetch_feed(
+    client: &reqwest::Client,
+    cdp: &CdpSession,
+    target_id: &str,
+    url: &str,
+    format: Option<&str>,
+    limit: usize,
+) -> Result<Value, Value> {
+    let feed_error = |code: &str, message: String| json!({ "code": code, "message": message });
+    let resp = client
+        .get(url)
+        .send()
+        .await
+        .map_err(|e| feed_error("FEED_FETCH_FAILED", e.to_string()))?;
+    let status = resp.status();
+    if !status.is_success() {
+        return Err(feed_error("FEED_FETCH_FAILED", format!("HTTP {status}")));
+    }
+    let body = resp
+        .text()
+        .await
+        .map_err(|e| feed_error("FEED_FETCH_FAILED", e.to_string()))?;
+
+    if format == Some("json") {
+        let doc: Value = serde_json::from_str(&body)
+            .map_err(|e| feed_error("FEED_PARSE_FAILED", format!("invalid JSON feed: {e}")))?;
+        return Ok(normalize_json_feed(&doc, limit));
+    }
+
+    let arg = serde_json::to_string(&body).unwrap_or_default();
+    let expression = format!("({PARSE_XML_FN})({arg}, {limit})");
+    let parsed = evaluate(cdp, target_id, &expression).await.map_err(|_| {
+        feed_error(
+            "FEED_PARSE_FAILED",
+            "feed parser raised an exception".into(),
+        )
+    })?;
+    match parsed.get("error").and_then(|v| v.as_str()) {
+        Some(msg) => Err(feed_error("FEED_PARSE_FAILED", msg.to_string())),
+        None => Ok(parsed),
+    }
+}
+
+/// Map a JSON Feed (https://jsonfeed.org) document onto the shape the XML
+/// parser produces.
+fn normalize_json_feed(doc: &Value, limit: usize) -> Value {
+    let s = |v: &Value, k: &str| v.get(k).and_then(|x| x.as_str()).map(String::from);
+    let items: Vec<Value> = doc
+        .get("items")
+        .and_then(|v| v.as_array())
+        .into_iter()
+        .flatten()
+        .take(limit)
+        .map(|item| {
+            json!({
+                "title": s(item, "title"),
+                "link": s(item, "url").or_else(|| s(item, "external_url")),
+                "id": item.get("id").map(|id| match id {
+                    Value::String(v) => v.clone(),
+                    other => other.to_string(),
+                }),
+                "published": s(item, "date_published").or_else(|| s(item, "date_modified")),
+                "summary": s(item, "summary").or_else(|| s(item, "content_text")),
+            })
+        })
+        .collect();
+    json!({
+        "title": s(doc, "title"),
+        "link": s(doc, "home_page_url"),
+        "description": s(doc, "description"),
+        "items": items,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn json_feed_is_normalized_and_limited() {
+        let doc = json!({
+            "version": "https://jsonfeed.org/version/1.1",
+            "title": "Blog",
+            "home_page_url": "https://example.com/",
+            "items": [
+                { "id": 1, "url": "https://example.com/a", "title": "A", "content_text": "aaa",
+                  "date_published": "2026-01-01T00:00:00Z" },
+                { "id": "b", "external_url": "https://other.example/b", "summary": "bbb" },
+                { "id": "c" },
+            ],
+        });
+
+        let feed = normalize_json_feed(&doc, 2);
+
+        assert_eq!(feed["title"], "Blog");
+        assert_eq!(feed["link"], "https://example.com/");
+        assert!(feed["description"].is_null());
+        let items = feed["items"].as_array().unwrap();
+        assert_eq!(items.len(), 2);
+        assert_eq!(items[0]["id"], "1");
+        assert_eq!(items[0]["summary"], "aaa");
+        assert_eq!(items[0]["published"], "2026-01-01T00:00:00Z");
+        assert_eq!(items[1]["link"], "https://other.example/b");
+        assert!(items[1]["title"].is_null());
+    }
+}
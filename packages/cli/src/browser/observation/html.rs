@@ -15,10 +15,12 @@ Examples:
   actionbook browser html --session s1 --tab t1
   actionbook browser html \"#main\" --session s1 --tab t1
   actionbook browser html @e3 --session s1 --tab t1
+  actionbook browser html --frame checkout --session s1 --tab t1
 
 Accepts a CSS selector, XPath, or snapshot ref (@eN from snapshot output).
 Without a selector, returns the full page HTML.
-With a selector, returns the outer HTML of the matched element.")]
+With a selector, returns the outer HTML of the matched element.
+--frame reads inside an iframe instead of the top document.")]
 pub struct Cmd {
     /// Selector (CSS, XPath, or @ref). Omit to read the full page HTML.
     pub selector: Option<String>,
@@ -30,6 +32,11 @@ pub struct Cmd {
     #[arg(long)]
     #[serde(rename = "tab_id")]
     pub tab: String,
+    /// Look selectors up inside this iframe: its name, a URL substring, or
+    /// its position (1 = first frame)
+    #[arg(long)]
+    #[serde(default)]
+    pub frame: Option<String>,
 }
 
 pub const COMMAND_NAME: &str = "browser html";
@@ -72,6 +79,11 @@ pub async fn execute(cmd: &Cmd, registry: &SharedRegistry) -> ActionResult {
         Ok(v) => v,
         Err(e) => return e,
     };
+    if let Some(frame) = &cmd.frame
+        && let Err(e) = ctx.enter_frame(frame).await
+    {
+        return e;
+    }
 
     let value = match get_html(&mut ctx, cmd.selector.as_deref()).await {
         Ok(v) => v,
@@ -116,15 +128,7 @@ async fn get_html(ctx: &mut TabContext, selector: Option<&str>) -> Result<Value,
         }
         None => {
             let resp = ctx
-                .cdp
-                .execute_on_tab(
-                    &ctx.target_id,
-                    "Runtime.evaluate",
-                    json!({
-                        "expression": "document.documentElement.outerHTML",
-                        "returnByValue": true,
-                    }),
-                )
+                .evaluate("document.documentElement.outerHTML")
                 .await
                 .map_err(|e| cdp_error_to_result(e, "CDP_ERROR"))?;
 
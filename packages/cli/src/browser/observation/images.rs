@@ -0,0 +1,357 @@
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+
+use clap::Args;
+use serde::{Deserialize, Serialize};
+use serde_json::{Value, json};
+
+use crate::action_result::ActionResult;
+use crate::browser::{element, navigation};
+use crate::daemon::cdp_session::{CdpSession, cdp_error_to_result, get_cdp_and_target};
+use crate::daemon::guardrails::{self, DomainPolicy};
+use crate::daemon::registry::SharedRegistry;
+use crate::output::ResponseContext;
+
+/// List the page's images (and optionally download them)
+#[derive(Args, Debug, Clone, Serialize, Deserialize)]
+#[command(after_help = "\
+Examples:
+  actionbook browser images --session s1 --tab t1
+  actionbook browser images --selector \"#gallery\" --min-size 100x100 --session s1 --tab t1
+  actionbook browser images --download ./imgs --session s1 --tab t1
+
+Lists every <img> with its URL (the source the browser picked from srcset),
+alt text and natural size in pixels. --min-size drops images smaller than
+WxH in either dimension; images that have not loaded yet report 0x0.
+--download saves each image into the directory through the tab's own network
+stack, so cookies and auth apply. Failures are reported per image; images on
+hosts blocked by [guardrails] are not downloaded.")]
+pub struct Cmd {
+    /// Only images inside this element (CSS selector)
+    #[arg(long)]
+    #[serde(default)]
+    pub selector: Option<String>,
+    /// Save the images into this directory
+    #[arg(long, value_name = "DIR")]
+    #[serde(default)]
+    pub download: Option<String>,
+    /// Skip images smaller than WIDTHxHEIGHT
+    #[arg(long, value_name = "WxH")]
+    #[serde(default)]
+    pub min_size: Option<String>,
+    /// Session ID
+    #[arg(long)]
+    #[serde(rename = "session_id")]
+    pub session: String,
+    /// Tab ID
+    #[arg(long)]
+    #[serde(rename = "tab_id")]
+    pub tab: String,
+}
+
+pub const COMMAND_NAME: &str = "browser images";
+
+/// Called as `(COLLECT_JS)(selector)`; `null` when the selector matches
+/// nothing.
+const COLLECT_JS: &str = r#"(function(selector) {
+    const root = selector ? document.querySelector(selector) : document;
+    if (!root) return null;
+    const imgs = Array.from(root.querySelectorAll('img'));
+    if (root instanceof HTMLImageElement) imgs.unshift(root);
+    const seen = new Set();
+    const out = [];
+    for (const img of imgs) {
+        const url = img.currentSrc || img.src;
+        if (!url || seen.has(url)) continue;
+        seen.add(url);
+        out.push({
+            url,
+            alt: img.getAttribute('alt'),
+            width: img.naturalWidth,
+            height: img.naturalHeight,
+        });
+    }
+    return out;
+})"#;
+
+pub fn context(cmd: &Cmd, result: &ActionResult) -> Option<ResponseContext> {
+    if let ActionResult::Fatal { code, .. } = result
+        && code == "SESSION_NOT_FOUND"
+    {
+        return None;
+    }
+    let tab_id = if let ActionResult::Fatal { code, .. } = result
+        && code == "TAB_NOT_FOUND"
+    {
+        None
+    } else {
+        Some(cmd.tab.clone())
+    };
+    let (url, title) = match result {
+        ActionResult::Ok { data } => (
+            data.get("__ctx_url")
+                .and_then(|v| v.as_str())
+                .map(String::from),
+            data.get("__ctx_title")
+                .and_then(|v| v.as_str())
+                .map(String::from),
+        ),
+        _ => (None, None),
+    };
+    Some(ResponseContext {
+        session_id: cmd.session.clone(),
+        tab_id,
+        window_id: None,
+        url,
+        title,
+    })
+}
+
+pub async fn execute(cmd: &Cmd, registry: &SharedRegistry) -> ActionResult {
+    let min_size = match cmd.min_size.as_deref().map(parse_size).transpose() {
+        Ok(v) => v,
+        Err(e) => return ActionResult::fatal("INVALID_ARGUMENT", e),
+    };
+    let (cdp, target_id) = match get_cdp_and_target(registry, &cmd.session, &cmd.tab).await {
+        Ok(v) => v,
+        Err(e) => return e,
+    };
+
+    let selector = serde_json::to_string(&cmd.selector).unwrap_or_default();
+    let mut images = match evaluate(&cdp, &target_id, &format!("({COLLECT_JS})({selector})")).await
+    {
+        Ok(Value::Array(images)) => images,
+        Ok(_) => {
+            return element::element_not_found(cmd.selector.as_deref().unwrap_or_default());
+        }
+        Err(e) => return e,
+    };
+    if let Some((min_w, min_h)) = min_size {
+        images.retain(|img| {
+            img["width"].as_u64().unwrap_or(0) >= min_w
+                && img["height"].as_u64().unwrap_or(0) >= min_h
+        });
+    }
+
+    let mut downloaded = 0;
+    if let Some(dir) = &cmd.download {
+        let dir = PathBuf::from(dir);
+        if let Err(e) = std::fs::create_dir_all(&dir) {
+            return ActionResult::fatal(
+                "IO_ERROR",
+                format!("failed to create {}: {e}", dir.display()),
+            );
+        }
+        let policy = match DomainPolicy::current() {
+            Ok(policy) => policy,
+            Err(e) => return e,
+        };
+        let mut taken = HashSet::new();
+        for (i, img) in images.iter_mut().enumerate() {
+            let url = img["url"].as_str().unwrap_or_default().to_string();
+            if !url.starts_with("data:")
+                && let Err(v) = policy.check(&url)
+            {
+                let host = v.host.unwrap_or_default();
+                img["error"] = json!({
+                    "code": guardrails::DOMAIN_NOT_ALLOWED,
+                    "message": format!("image host '{host}' is not allowed by guardrails"),
+                });
+                continue;
+            }
+            let path = dir.join(file_name_for(&url, i + 1, &mut taken));
+            match save_image(&cdp, &target_id, &url, &path).await {
+                Ok(bytes) => {
+                    img["path"] = json!(path.to_string_lossy());
+                    img["bytes"] = json!(bytes);
+                    downloaded += 1;
+                }
+                Err(error) => img["error"] = error,
+            }
+        }
+    }
+
+    let url = navigation::get_tab_url(&cdp, &target_id).await;
+    let title = navigation::get_tab_title(&cdp, &target_id).await;
+    let mut data = json!({
+        "count": images.len(),
+        "images": images,
+        "__ctx_url": url,
+        "__ctx_title": title,
+    });
+    if let Some(dir) = &cmd.download {
+        data["dir"] = json!(dir);
+        data["downloaded"] = json!(downloaded);
+    }
+    ActionResult::ok(data)
+}
+
+async fn evaluate(
+    cdp: &CdpSession,
+    target_id: &str,
+    expression: &str,
+) -> Result<Value, ActionResult> {
+    let resp = cdp
+        .execute_on_tab(
+            target_id,
+            "Runtime.evaluate",
+            json!({ "expression": expression, "returnByValue": true }),
+        )
+        .await
+        .map_err(|e| cdp_error_to_result(e, "CDP_ERROR"))?;
+    if let Some(exc) = resp.pointer("/result/exceptionDetails") {
+        let msg = exc
+            .pointer("/exception/description")
+            .and_then(|v| v.as_str())
+            .or_else(|| exc.get("text").and_then(|v| v.as_str()))
+            .unwrap_or("image collection failed");
+        return Err(ActionResult::fatal("JS_EXCEPTION", msg.to_string()));
+    }
+    Ok(resp
+        .pointer("/result/result/value")
+        .cloned()
+        .unwrap_or(Value::Null))
+}
+
+/// Fetch one image and write it to `path`. Errors are returned as a
+/// per-image `{code, message}` object so one broken image does not fail the
+/// command.
+async fn save_image(
+    cdp: &CdpSession,
+    target_id: &str,
+    url: &str,
+    path: &Path,
+) -> Result<usize, Value> {
+    let image_error = |code: &str, message: String| json!({ "code": code, "message": message });
+    let bytes = if url.starts_with("data:") {
+        decode_data_url(url)
+            .ok_or_else(|| image_error("IMAGE_FETCH_FAILED", "malformed data: URL".to_string()))?
+    } else {
+        navigation::load_resource(cdp, target_id, url)
+            .await
+            .map_err(|e| match e {
+                ActionResult::Fatal { message, .. } => image_error("IMAGE_FETCH_FAILED", message),
+                _ => image_error("IMAGE_FETCH_FAILED", format!("failed to fetch {url}")),
+            })?
+    };
+    std::fs::write(path, &bytes).map_err(|e| {
+        image_error(
+            "IO_ERROR",
+            format!("failed to write {}: {e}", path.display()),
+        )
+    })?;
+    Ok(bytes.len())
+}
+
+/// Parse `WIDTHxHEIGHT` (e.g. `100x100`).
+fn parse_size(s: &str) -> Result<(u64, u64), String> {
+    let err = || format!("invalid --min-size '{s}' (expected WIDTHxHEIGHT, e.g. 100x100)");
+    let (w, h) = s.trim().split_once(['x', 'X']).ok_or_else(err)?;
+    Ok((
+        w.trim().parse().map_err(|_| err())?,
+        h.trim().parse().map_err(|_| err())?,
+    ))
+}
+
+/// A file name for the `index`-th image: the URL's last path segment when it
+/// has one, else `image-<index>`, made unique among `taken`.
+fn file_name_for(url: &str, index: usize, taken: &mut HashSet<String>) -> String {
+    let segment = if url.starts_with("data:") {
+        ""
+    } else {
+        url.split(['?', '#'])
+            .next()
+            .unwrap_or("")
+            .rsplit('/')
+            .next()
+            .unwrap_or("")
+    };
+    let decoded = urlencoding::decode(segment)
+        .map(|s| s.into_owned())
+        .unwrap_or_else(|_| segment.to_string());
+    let cleaned: String = decoded
+        .chars()
+        .map(|c| {
+            if c.is_alphanumeric() || matches!(c, '.' | '-' | '_') {
+                c
+            } else {
+                '_'
+            }
+        })
+        .collect();
+    let base = match cleaned.trim_matches('.') {
+        "" => format!("image-{index}"),
+        name => name.to_string(),
+    };
+
+    let mut name = base.clone();
+    let mut n = 2;
+    while !taken.insert(name.clone()) {
+        name = match base.rsplit_once('.') {
+            Some((stem, ext)) => format!("{stem}-{n}.{ext}"),
+            None => format!("{base}-{n}"),
+        };
+        n += 1;
+    }
+    name
+}
+
+/// Bytes of a `data:` URL (base64 or percent-encoded).
+fn decode_data_url(url: &str) -> Option<Vec<u8>> {
+    use base64::Engine;
+
+    let (meta, payload) = url.strip_prefix("data:")?.split_once(',')?;
+    if meta.ends_with(";base64") {
+        base64::engine::general_purpose::STANDARD
+            .decode(payload.trim())
+            .ok()
+    } else {
+        urlencoding::decode_binary(payload.as_bytes())
+            .into_owned()
+            .into()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_size_accepts_width_x_height() {
+        assert_eq!(parse_size("100x80"), Ok((100, 80)));
+        assert_eq!(parse_size(" 64X64 "), Ok((64, 64)));
+        assert!(parse_size("100").is_err());
+        assert!(parse_size("axb").is_err());
+    }
+
+    #[test]
+    fn file_name_for_uses_url_segment_and_stays_unique() {
+        let mut taken = HashSet::new();
+        assert_eq!(
+            file_name_for("https://x.test/a/cat%20photo.jpg?w=200", 1, &mut taken),
+            "cat_photo.jpg"
+        );
+        assert_eq!(
+            file_name_for("https://y.test/cat%20photo.jpg", 2, &mut taken),
+            "cat_photo-2.jpg"
+        );
+        assert_eq!(file_name_for("https://x.test/", 3, &mut taken), "image-3");
+        assert_eq!(
+            file_name_for("data:image/png;base64,AAAA", 4, &mut taken),
+            "image-4"
+        );
+    }
+
+    #[test]
+    fn decode_data_url_handles_base64_and_percent_encoding() {
+        assert_eq!(
+            decode_data_url("data:image/png;base64,aGk=").as_deref(),
+            Some(&b"hi"[..])
+        );
+        assert_eq!(
+            decode_data_url("data:image/svg+xml,%3Csvg%3E").as_deref(),
+            Some(&b"<svg>"[..])
+        );
+        assert!(decode_data_url("data:nocomma").is_none());
+    }
+}
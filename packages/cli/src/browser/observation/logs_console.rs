@@ -64,6 +64,13 @@ pub const ENSURE_LOG_CAPTURE_JS: &str = r#"(function() {
     return true;
 })()"#;
 
+/// Registered with `Page.addScriptToEvaluateOnNewDocument` so the capture
+/// hook is present from the start of every later document; the marker tells
+/// readers whether a page's buffers cover its whole lifetime.
+pub fn log_capture_on_new_document_js() -> String {
+    format!("window.__ab_log_capture_from_start = true;\n{ENSURE_LOG_CAPTURE_JS};")
+}
+
 /// Get console logs.
 #[derive(Args, Debug, Clone, Serialize, Deserialize)]
 #[command(after_help = "\
@@ -0,0 +1,141 @@
+use clap::Args;
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+
+use crate::action_result::ActionResult;
+use crate::browser::navigation;
+use crate::daemon::cdp_session::{cdp_error_to_result, get_cdp_and_target};
+use crate::daemon::registry::SharedRegistry;
+use crate::output::ResponseContext;
+
+/// Read page metadata (title, description, canonical, OpenGraph, Twitter, JSON-LD)
+#[derive(Args, Debug, Clone, Serialize, Deserialize)]
+#[command(after_help = "\
+Examples:
+  actionbook browser meta --session s1 --tab t1
+  actionbook browser meta --session s1 --tab t1 --json
+
+Returns one object with: title, description, canonical, lang, og {..},
+twitter {..}, meta {name: content} for all other named meta tags, and
+json_ld [..] with every parsed <script type=\"application/ld+json\"> block.")]
+pub struct Cmd {
+    /// Session ID
+    #[arg(long)]
+    #[serde(rename = "session_id")]
+    pub session: String,
+    /// Tab ID
+    #[arg(long)]
+    #[serde(rename = "tab_id")]
+    pub tab: String,
+}
+
+pub const COMMAND_NAME: &str = "browser meta";
+
+/// Collect metadata in a single pass. OpenGraph keys drop their `og:` prefix
+/// (`og:image:width` → `image:width`), likewise `twitter:`. Repeated keys
+/// (e.g. several `og:image`) become arrays. JSON-LD blocks that fail to parse
+/// are reported in `json_ld_errors` instead of aborting the whole read.
+const META_JS: &str = r#"(function() {
+    const put = (obj, key, value) => {
+        if (!(key in obj)) { obj[key] = value; return; }
+        if (!Array.isArray(obj[key])) obj[key] = [obj[key]];
+        obj[key].push(value);
+    };
+    const og = {}, twitter = {}, meta = {};
+    for (const el of document.querySelectorAll('meta')) {
+        const key = (el.getAttribute('property') || el.getAttribute('name') || '').trim();
+        const content = el.getAttribute('content');
+        if (!key || content === null) continue;
+        const lower = key.toLowerCase();
+        if (lower.startsWith('og:')) put(og, lower.slice(3), content);
+        else if (lower.startsWith('twitter:')) put(twitter, lower.slice(8), content);
+        else put(meta, lower, content);
+    }
+    const jsonLd = [], jsonLdErrors = [];
+    document.querySelectorAll('script[type="application/ld+json"]').forEach((el, i) => {
+        try {
+            jsonLd.push(JSON.parse(el.textContent));
+        } catch (e) {
+            jsonLdErrors.push({ index: i, error: String(e && e.message || e) });
+        }
+    });
+    const canonical = document.querySelector('link[rel~="canonical"]');
+    return {
+        title: document.title || null,
+        description: meta['description'] || null,
+        canonical: canonical ? canonical.href : null,
+        lang: document.documentElement.lang || null,
+        og, twitter, meta,
+        json_ld: jsonLd,
+        json_ld_errors: jsonLdErrors,
+    };
+})()"#;
+
+pub fn context(cmd: &Cmd, result: &ActionResult) -> Option<ResponseContext> {
+    if let ActionResult::Fatal { code, .. } = result
+        && code == "SESSION_NOT_FOUND"
+    {
+        return None;
+    }
+    let tab_id = if let ActionResult::Fatal { code, .. } = result
+        && code == "TAB_NOT_FOUND"
+    {
+        None
+    } else {
+        Some(cmd.tab.clone())
+    };
+    let (url, title) = match result {
+        ActionResult::Ok { data } => (
+            data.get("__ctx_url")
+                .and_then(|v| v.as_str())
+                .map(String::from),
+            data.get("__ctx_title")
+                .and_then(|v| v.as_str())
+                .map(String::from),
+        ),
+        _ => (None, None),
+    };
+    Some(ResponseContext {
+        session_id: cmd.session.clone(),
+        tab_id,
+        window_id: None,
+        url,
+        title,
+    })
+}
+
+pub async fn execute(cmd: &Cmd, registry: &SharedRegistry) -> ActionResult {
+    let (cdp, target_id) = match get_cdp_and_target(registry, &cmd.session, &cmd.tab).await {
+        Ok(v) => v,
+        Err(e) => return e,
+    };
+
+    let resp = match cdp
+        .execute_on_tab(
+            &target_id,
+            "Runtime.evaluate",
+            json!({ "expression": META_JS, "returnByValue": true }),
+        )
+        .await
+    {
+        Ok(v) => v,
+        Err(e) => return cdp_error_to_result(e, "CDP_ERROR"),
+    };
+    if let Some(exc) = resp.pointer("/result/exceptionDetails") {
+        let msg = exc
+            .pointer("/exception/description")
+            .and_then(|v| v.as_str())
+            .or_else(|| exc.get("text").and_then(|v| v.as_str()))
+            .unwrap_or("metadata extraction failed");
+        return ActionResult::fatal("JS_EXCEPTION", msg.to_string());
+    }
+    let Some(mut data) = resp.pointer("/result/result/value").cloned() else {
+        return ActionResult::fatal("CDP_ERROR", "metadata extraction returned no value");
+    };
+
+    let url = navigation::get_tab_url(&cdp, &target_id).await;
+    data["url"] = json!(url);
+    data["__ctx_url"] = json!(url);
+    data["__ctx_title"] = data["title"].clone();
+    ActionResult::ok(data)
+}
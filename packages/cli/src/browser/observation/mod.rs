@@ -1,24 +1,35 @@
+pub mod assert;
 pub mod attr;
 pub mod attrs;
 pub mod batch_snapshot;
 pub mod r#box;
 pub mod describe;
+pub mod feeds;
 pub mod html;
+pub mod images;
 pub mod inspect_point;
 pub mod logs_console;
 pub mod logs_errors;
+pub mod meta;
 pub mod network_har;
 pub mod network_request_detail;
 pub mod network_requests;
+pub mod network_route;
+pub mod options;
 pub mod pdf;
 pub mod query;
+pub mod record_video;
+pub mod response;
+pub mod save;
 pub mod screenshot;
 pub mod snapshot;
 pub mod snapshot_transform;
 pub mod state;
 pub mod styles;
+pub mod table;
 pub mod text;
 pub mod title;
+pub mod trace;
 pub mod url;
 pub mod value;
 pub mod viewport;
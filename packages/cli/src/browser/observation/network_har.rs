@@ -14,6 +14,7 @@ use crate::action_result::ActionResult;
 use crate::daemon::cdp_session::{HarEntry, get_cdp_and_target};
 use crate::daemon::registry::SharedRegistry;
 use crate::output::ResponseContext;
+use crate::utils::time::unix_secs_to_rfc3339;
 
 /// Default cap on per-response body size (bytes). Bodies larger than this are
 /// dropped; metadata is still recorded. Keeps HAR output bounded even on long
@@ -571,34 +572,6 @@ fn wall_time_to_rfc3339(wall_time: f64) -> String {
     unix_secs_to_rfc3339(secs, millis)
 }
 
-/// Format a Unix timestamp (seconds + milliseconds) as RFC 3339 / ISO 8601 UTC.
-/// Output: `YYYY-MM-DDTHH:MM:SS.mmmZ`
-fn unix_secs_to_rfc3339(secs: u64, millis: u64) -> String {
-    // Days since Unix epoch → calendar date (Gregorian proleptic)
-    let days = secs / 86400;
-    let time_of_day = secs % 86400;
-    let h = time_of_day / 3600;
-    let m = (time_of_day % 3600) / 60;
-    let s = time_of_day % 60;
-
-    // Algorithm from http://howardhinnant.github.io/date_algorithms.html
-    let z = days as i64 + 719468;
-    let era = if z >= 0 { z } else { z - 146096 } / 146097;
-    let doe = z - era * 146097;
-    let yoe = (doe - doe / 1460 + doe / 36524 - doe / 146096) / 365;
-    let y = yoe + era * 400;
-    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
-    let mp = (5 * doy + 2) / 153;
-    let d = doy - (153 * mp + 2) / 5 + 1;
-    let mo = if mp < 10 { mp + 3 } else { mp - 9 };
-    let y = if mo <= 2 { y + 1 } else { y };
-
-    format!(
-        "{:04}-{:02}-{:02}T{:02}:{:02}:{:02}.{:03}Z",
-        y, mo, d, h, m, s, millis
-    )
-}
-
 fn parse_request_cookies(cookie_header: &str) -> Vec<serde_json::Value> {
     cookie_header
         .split(';')
@@ -0,0 +1,360 @@
+//! `browser network route add` / `list` / `remove` commands.
+//!
+//! Routes are per-tab and checked in the order they were added; the first
+//! pattern that matches a request decides what happens to it. They last until
+//! removed or the tab closes.
+
+use clap::Args;
+use serde::{Deserialize, Serialize};
+use serde_json::{Value, json};
+
+use crate::action_result::ActionResult;
+use crate::daemon::cdp_session::{CdpSession, get_cdp_and_target};
+use crate::daemon::registry::SharedRegistry;
+use crate::daemon::routes::{self, RouteAction, SharedRoutes};
+use crate::output::ResponseContext;
+
+// ── Add ───────────────────────────────────────────────────────────────────────
+
+/// Block, mock, or rewrite requests matching a URL pattern.
+#[derive(Args, Debug, Clone, Serialize, Deserialize)]
+#[command(after_help = "\
+Examples:
+  actionbook browser network route add '*.doubleclick.net/*' --block --session s1 --tab t1
+  actionbook browser network route add '*/api/users*' --fulfill users.json --session s1 --tab t1
+  actionbook browser network route add '*/api/*' --fulfill err.json --status 503 --session s1 --tab t1
+  actionbook browser network route add '*/api/*' --header 'Authorization: Bearer t' --session s1 --tab t1
+
+The pattern is matched against the full request URL; `*` matches any run of
+characters and everything else is literal. Routes are checked in the order
+they were added and the first match wins.
+
+  --block            fail the request as blocked by the client
+  --fulfill <file>   answer with the file's contents; --status (default 200)
+                     and --content-type (default: from the file extension)
+                     shape the response, --header adds response headers
+  --header 'K: V'    on its own, set a request header and let the request
+                     continue; an empty value removes the header
+
+The fixture is read once, when the route is added.")]
+pub struct AddCmd {
+    /// URL pattern (`*` is the only wildcard)
+    pub pattern: String,
+    /// Fail matching requests
+    #[arg(long, conflicts_with_all = ["fulfill", "header"])]
+    #[serde(default)]
+    pub block: bool,
+    /// Answer matching requests from this file
+    #[arg(long)]
+    pub fulfill: Option<String>,
+    /// HTTP status for --fulfill
+    #[arg(long, requires = "fulfill")]
+    pub status: Option<u16>,
+    /// Content-Type for --fulfill (default: from the file extension)
+    #[arg(long, requires = "fulfill")]
+    pub content_type: Option<String>,
+    /// Header as 'Name: value' (repeatable)
+    #[arg(long = "header")]
+    #[serde(default)]
+    pub header: Vec<String>,
+    /// Session ID
+    #[arg(long)]
+    #[serde(rename = "session_id")]
+    pub session: String,
+    /// Tab ID
+    #[arg(long)]
+    #[serde(rename = "tab_id")]
+    pub tab: String,
+}
+
+pub const ADD_COMMAND_NAME: &str = "browser network route add";
+
+/// Split `Name: value` at the first colon.
+fn parse_header(raw: &str) -> Option<(String, String)> {
+    let (name, value) = raw.split_once(':')?;
+    let name = name.trim();
+    if name.is_empty() {
+        return None;
+    }
+    Some((name.to_string(), value.trim().to_string()))
+}
+
+fn route_action(cmd: &AddCmd) -> Result<RouteAction, ActionResult> {
+    let mut headers = Vec::with_capacity(cmd.header.len());
+    for raw in &cmd.header {
+        match parse_header(raw) {
+            Some(h) => headers.push(h),
+            None => {
+                return Err(ActionResult::fatal_with_hint(
+                    "INVALID_ARGUMENT",
+                    format!("invalid header '{raw}'"),
+                    "use --header 'Name: value'",
+                ));
+            }
+        }
+    }
+
+    if cmd.block {
+        return Ok(RouteAction::Block);
+    }
+    if let Some(path) = &cmd.fulfill {
+        let body = std::fs::read(path).map_err(|e| {
+            ActionResult::fatal("IO_ERROR", format!("failed to read fixture '{path}': {e}"))
+        })?;
+        return Ok(RouteAction::Fulfill {
+            status: cmd.status.unwrap_or(200),
+            content_type: cmd
+                .content_type
+                .clone()
+                .unwrap_or_else(|| routes::guess_content_type(path).to_string()),
+            headers,
+            body,
+            source: path.clone(),
+        });
+    }
+    if !headers.is_empty() {
+        return Ok(RouteAction::Headers(headers));
+    }
+    Err(ActionResult::fatal_with_hint(
+        "INVALID_ARGUMENT",
+        "route needs --block, --fulfill or --header",
+        "see `actionbook browser network route add --help`",
+    ))
+}
+
+async fn tab_routes(
+    registry: &SharedRegistry,
+    session: &str,
+    tab: &str,
+) -> Result<(CdpSession, String, String, SharedRoutes), ActionResult> {
+    let (cdp, target_id) = get_cdp_and_target(registry, session, tab).await?;
+    let Some(cdp_session_id) = cdp.get_cdp_session_id(&target_id).await else {
+        return Err(ActionResult::fatal(
+            "INTERNAL_ERROR",
+            format!("no CDP session for tab '{tab}' (target {target_id})"),
+        ));
+    };
+    let table = cdp.tab_routes(&cdp_session_id).await;
+    Ok((cdp, target_id, cdp_session_id, table))
+}
+
+fn tab_context(session: &str, tab: &str, result: &ActionResult) -> Option<ResponseContext> {
+    if let ActionResult::Fatal { code, .. } = result
+        && code == "SESSION_NOT_FOUND"
+    {
+        return None;
+    }
+    let tab_id = if let ActionResult::Fatal { code, .. } = result
+        && code == "TAB_NOT_FOUND"
+    {
+        None
+    } else {
+        Some(tab.to_string())
+    };
+    Some(ResponseContext {
+        session_id: session.to_string(),
+        tab_id,
+        window_id: None,
+        url: None,
+        title: None,
+    })
+}
+
+pub fn add_context(cmd: &AddCmd, result: &ActionResult) -> Option<ResponseContext> {
+    tab_context(&cmd.session, &cmd.tab, result)
+}
+
+pub async fn execute_add(cmd: &AddCmd, registry: &SharedRegistry) -> ActionResult {
+    let action = match route_action(cmd) {
+        Ok(a) => a,
+        Err(e) => return e,
+    };
+    let (cdp, target_id, cdp_session_id, table) =
+        match tab_routes(registry, &cmd.session, &cmd.tab).await {
+            Ok(v) => v,
+            Err(e) => return e,
+        };
+
+    // Sessions started with --adblock already intercept every tab; this is a
+    // no-op for them.
+    if let Err(e) = routes::install(
+        &cdp,
+        &target_id,
+        &cdp_session_id,
+        table.clone(),
+        cdp.adblock(),
+    )
+    .await
+    {
+        return ActionResult::fatal(
+            "CDP_ERROR",
+            format!("failed to enable request interception: {e}"),
+        );
+    }
+
+    let mut table = table.write().unwrap_or_else(|e| e.into_inner());
+    let id = table.add(cmd.pattern.clone(), action);
+    let route = table
+        .routes()
+        .iter()
+        .find(|r| r.id == id)
+        .map(|r| r.to_json())
+        .unwrap_or(Value::Null);
+    ActionResult::ok(json!({
+        "route": route,
+        "count": table.routes().len(),
+    }))
+}
+
+// ── List ──────────────────────────────────────────────────────────────────────
+
+/// List a tab's request routes.
+#[derive(Args, Debug, Clone, Serialize, Deserialize)]
+#[command(after_help = "\
+Examples:
+  actionbook browser network route list --session s1 --tab t1
+
+Routes are listed in match order, each with the number of requests it has
+handled so far.")]
+pub struct ListCmd {
+    /// Session ID
+    #[arg(long)]
+    #[serde(rename = "session_id")]
+    pub session: String,
+    /// Tab ID
+    #[arg(long)]
+    #[serde(rename = "tab_id")]
+    pub tab: String,
+}
+
+pub const LIST_COMMAND_NAME: &str = "browser network route list";
+
+pub fn list_context(cmd: &ListCmd, result: &ActionResult) -> Option<ResponseContext> {
+    tab_context(&cmd.session, &cmd.tab, result)
+}
+
+pub async fn execute_list(cmd: &ListCmd, registry: &SharedRegistry) -> ActionResult {
+    let (_, _, _, table) = match tab_routes(registry, &cmd.session, &cmd.tab).await {
+        Ok(v) => v,
+        Err(e) => return e,
+    };
+    let table = table.read().unwrap_or_else(|e| e.into_inner());
+    let routes: Vec<Value> = table.routes().iter().map(|r| r.to_json()).collect();
+    ActionResult::ok(json!({
+        "routes": routes,
+        "count": routes.len(),
+    }))
+}
+
+// ── Remove ────────────────────────────────────────────────────────────────────
+
+/// Remove one or all of a tab's request routes.
+#[derive(Args, Debug, Clone, Serialize, Deserialize)]
+#[command(after_help = "\
+Examples:
+  actionbook browser network route remove r2 --session s1 --tab t1
+  actionbook browser network route remove --all --session s1 --tab t1
+
+Requests that no longer match any route reach the network again.")]
+pub struct RemoveCmd {
+    /// Route ID (see route list)
+    #[arg(required_unless_present = "all", conflicts_with = "all")]
+    pub id: Option<String>,
+    /// Remove every route on the tab
+    #[arg(long)]
+    #[serde(default)]
+    pub all: bool,
+    /// Session ID
+    #[arg(long)]
+    #[serde(rename = "session_id")]
+    pub session: String,
+    /// Tab ID
+    #[arg(long)]
+    #[serde(rename = "tab_id")]
+    pub tab: String,
+}
+
+pub const REMOVE_COMMAND_NAME: &str = "browser network route remove";
+
+pub fn remove_context(cmd: &RemoveCmd, result: &ActionResult) -> Option<ResponseContext> {
+    tab_context(&cmd.session, &cmd.tab, result)
+}
+
+pub async fn execute_remove(cmd: &RemoveCmd, registry: &SharedRegistry) -> ActionResult {
+    let (_, _, _, table) = match tab_routes(registry, &cmd.session, &cmd.tab).await {
+        Ok(v) => v,
+        Err(e) => return e,
+    };
+    let mut table = table.write().unwrap_or_else(|e| e.into_inner());
+    let removed = match (&cmd.id, cmd.all) {
+        (_, true) => table.clear(),
+        (Some(id), false) => match table.remove(id) {
+            Some(_) => 1,
+            None => {
+                return ActionResult::fatal_with_hint(
+                    "ROUTE_NOT_FOUND",
+                    format!("no route '{id}' on tab '{}'", cmd.tab),
+                    "run `actionbook browser network route list` to see routes",
+                );
+            }
+        },
+        (None, false) => {
+            return ActionResult::fatal("INVALID_ARGUMENT", "give a route ID or --all");
+        }
+    };
+    ActionResult::ok(json!({
+        "removed": removed,
+        "count": table.routes().len(),
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn add(args: &[&str]) -> AddCmd {
+        AddCmd {
+            pattern: "*".into(),
+            block: args.contains(&"block"),
+            fulfill: None,
+            status: None,
+            content_type: None,
+            header: args
+                .iter()
+                .filter(|a| a.contains(':'))
+                .map(|a| a.to_string())
+                .collect(),
+            session: "s1".into(),
+            tab: "t1".into(),
+        }
+    }
+
+    #[test]
+    fn header_parses_at_first_colon() {
+        assert_eq!(
+            parse_header("Authorization: Bearer a:b"),
+            Some(("Authorization".into(), "Bearer a:b".into()))
+        );
+        assert_eq!(
+            parse_header("X-Drop:"),
+            Some(("X-Drop".into(), String::new()))
+        );
+        assert_eq!(parse_header(": v"), None);
+        assert_eq!(parse_header("novalue"), None);
+    }
+
+    #[test]
+    fn route_action_needs_exactly_a_mode() {
+        assert!(matches!(
+            route_action(&add(&["block"])),
+            Ok(RouteAction::Block)
+        ));
+        assert!(matches!(
+            route_action(&add(&["X-A: 1"])),
+            Ok(RouteAction::Headers(h)) if h.len() == 1
+        ));
+        assert!(
+            matches!(route_action(&add(&[])), Err(ActionResult::Fatal { code, .. }) if code == "INVALID_ARGUMENT")
+        );
+    }
+}
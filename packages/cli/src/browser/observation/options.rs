@@ -0,0 +1,135 @@
+use clap::Args;
+use serde::{Deserialize, Serialize};
+use serde_json::{Value, json};
+
+use crate::action_result::ActionResult;
+use crate::browser::{element::TabContext, navigation};
+use crate::daemon::cdp_session::cdp_error_to_result;
+use crate::daemon::registry::SharedRegistry;
+use crate::output::ResponseContext;
+
+/// List the options of a dropdown
+#[derive(Args, Debug, Clone, Serialize, Deserialize)]
+#[command(after_help = "\
+Examples:
+  actionbook browser options \"#country\" --session s1 --tab t1
+  actionbook browser options @e7 --session s1 --tab t1 --json
+
+Accepts a CSS selector, XPath, or snapshot ref (@eN from snapshot output).
+Lists every <option> of a <select> element with its index, value, label,
+selected and disabled state, and <optgroup> label. Pick one with
+`browser select <selector> --index N`.")]
+pub struct Cmd {
+    /// Selector for <select> element (CSS, XPath, or @ref)
+    pub selector: String,
+    /// Session ID
+    #[arg(long)]
+    #[serde(rename = "session_id")]
+    pub session: String,
+    /// Tab ID
+    #[arg(long)]
+    #[serde(rename = "tab_id")]
+    pub tab: String,
+}
+
+pub const COMMAND_NAME: &str = "browser options";
+
+const LIST_OPTIONS_JS: &str = r#"function() {
+    if (this.tagName !== 'SELECT') return null;
+    return {
+        multiple: this.multiple,
+        options: Array.from(this.options).map((o, index) => ({
+            index,
+            value: o.value,
+            label: o.label || o.textContent.trim(),
+            selected: o.selected,
+            disabled: o.disabled,
+            group: o.parentElement && o.parentElement.tagName === 'OPTGROUP'
+                ? o.parentElement.label
+                : null,
+        })),
+    };
+}"#;
+
+pub fn context(cmd: &Cmd, result: &ActionResult) -> Option<ResponseContext> {
+    if let ActionResult::Fatal { code, .. } = result
+        && code == "SESSION_NOT_FOUND"
+    {
+        return None;
+    }
+    let tab_id = if let ActionResult::Fatal { code, .. } = result
+        && code == "TAB_NOT_FOUND"
+    {
+        None
+    } else {
+        Some(cmd.tab.clone())
+    };
+    let (url, title) = match result {
+        ActionResult::Ok { data } => (
+            data.get("__ctx_url")
+                .and_then(|v| v.as_str())
+                .map(String::from),
+            data.get("__ctx_title")
+                .and_then(|v| v.as_str())
+                .map(String::from),
+        ),
+        _ => (None, None),
+    };
+    Some(ResponseContext {
+        session_id: cmd.session.clone(),
+        tab_id,
+        window_id: None,
+        url,
+        title,
+    })
+}
+
+pub async fn execute(cmd: &Cmd, registry: &SharedRegistry) -> ActionResult {
+    let mut ctx = match TabContext::new(registry, &cmd.session, &cmd.tab).await {
+        Ok(v) => v,
+        Err(e) => return e,
+    };
+
+    let (_, object_id) = match ctx.resolve_object(&cmd.selector).await {
+        Ok(v) => v,
+        Err(e) => return e,
+    };
+    let resp = match ctx
+        .execute_on_element(
+            "Runtime.callFunctionOn",
+            json!({
+                "objectId": object_id,
+                "functionDeclaration": LIST_OPTIONS_JS,
+                "returnByValue": true,
+            }),
+        )
+        .await
+    {
+        Ok(v) => v,
+        Err(e) => return cdp_error_to_result(e, "CDP_ERROR"),
+    };
+
+    let Some(listing) = resp
+        .pointer("/result/result/value")
+        .filter(|v| v.is_object())
+    else {
+        return ActionResult::fatal(
+            "INVALID_ARGUMENT",
+            format!("'{}' is not a <select> element", cmd.selector),
+        );
+    };
+    let options = listing.get("options").cloned().unwrap_or(json!([]));
+    let total = options.as_array().map(Vec::len).unwrap_or(0);
+
+    let url = navigation::get_tab_url(&ctx.cdp, &ctx.target_id).await;
+    let title = navigation::get_tab_title(&ctx.cdp, &ctx.target_id).await;
+
+    ActionResult::ok(json!({
+        "target": { "selector": cmd.selector },
+        "multiple": listing.get("multiple").cloned().unwrap_or(Value::Bool(false)),
+        "total": total,
+        "options": options,
+        "__ctx_url": url,
+        "__ctx_title": title,
+    }))
+}
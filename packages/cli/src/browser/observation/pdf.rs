@@ -23,6 +23,10 @@ pub struct Cmd {
     #[arg(long)]
     #[serde(rename = "tab_id")]
     pub tab: String,
+    /// Abort when the tab downloads more than this many bytes (default: session budget)
+    #[arg(long, value_name = "BYTES")]
+    #[serde(default)]
+    pub max_download_bytes: Option<u64>,
 }
 
 pub const COMMAND_NAME: &str = "browser pdf";
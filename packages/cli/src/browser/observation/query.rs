@@ -113,6 +113,15 @@ impl Cmd {
         }
     }
 
+    pub fn session_and_tab_mut(&mut self) -> (&str, &mut String) {
+        match &mut self.mode {
+            QueryMode::One { session, tab, .. }
+            | QueryMode::All { session, tab, .. }
+            | QueryMode::Nth { session, tab, .. }
+            | QueryMode::Count { session, tab, .. } => (session, tab),
+        }
+    }
+
     pub fn selector(&self) -> &str {
         match &self.mode {
             QueryMode::One { selector, .. }
@@ -0,0 +1,245 @@
+//! `browser record-video start` / `browser record-video stop` commands.
+//!
+//! Records what a tab shows as a video through `Page.startScreencast`, for
+//! looking back at what a run or an anti-bot challenge actually did.
+//! Recording is per-tab, like `browser record`.
+
+use std::path::PathBuf;
+
+use clap::Args;
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+
+use crate::action_result::ActionResult;
+use crate::daemon::cdp_session::get_cdp_and_target;
+use crate::daemon::registry::SharedRegistry;
+use crate::daemon::screencast::{self, Screencast};
+use crate::output::ResponseContext;
+
+// ── Start ─────────────────────────────────────────────────────────────────────
+
+/// Start recording a tab as a video.
+#[derive(Args, Debug, Clone, Serialize, Deserialize)]
+#[command(after_help = "\
+Examples:
+  actionbook browser record-video start --out session.webm --session s1 --tab t1
+  actionbook browser record-video stop --session s1 --tab t1
+
+Frames are written to a directory next to --out (session.frames/ for
+session.webm) as the page changes. On stop they are encoded with ffmpeg into
+--out (.webm as VP9, .mp4 as H.264) and the directory is removed. Without
+ffmpeg on PATH the frames are kept and the ffmpeg command to encode them is
+printed.")]
+pub struct StartCmd {
+    /// Video to write on stop (.webm, .mp4, ...)
+    #[arg(long)]
+    pub out: String,
+    /// JPEG quality of each frame (1-100)
+    #[arg(long, default_value_t = 80, value_parser = clap::value_parser!(u8).range(1..=100))]
+    pub quality: u8,
+    /// Session ID
+    #[arg(long)]
+    #[serde(rename = "session_id")]
+    pub session: String,
+    /// Tab ID
+    #[arg(long)]
+    #[serde(rename = "tab_id")]
+    pub tab: String,
+}
+
+pub const START_COMMAND_NAME: &str = "browser record-video start";
+
+fn tab_context(session: &str, tab: &str, result: &ActionResult) -> Option<ResponseContext> {
+    if let ActionResult::Fatal { code, .. } = result
+        && code == "SESSION_NOT_FOUND"
+    {
+        return None;
+    }
+    let tab_id = if let ActionResult::Fatal { code, .. } = result
+        && code == "TAB_NOT_FOUND"
+    {
+        None
+    } else {
+        Some(tab.to_string())
+    };
+    Some(ResponseContext {
+        session_id: session.to_string(),
+        tab_id,
+        window_id: None,
+        url: None,
+        title: None,
+    })
+}
+
+pub fn start_context(cmd: &StartCmd, result: &ActionResult) -> Option<ResponseContext> {
+    tab_context(&cmd.session, &cmd.tab, result)
+}
+
+pub async fn execute_start(cmd: &StartCmd, registry: &SharedRegistry) -> ActionResult {
+    let (cdp, target_id) = match get_cdp_and_target(registry, &cmd.session, &cmd.tab).await {
+        Ok(v) => v,
+        Err(e) => return e,
+    };
+    let Some(cdp_session_id) = cdp.get_cdp_session_id(&target_id).await else {
+        return ActionResult::fatal(
+            "INTERNAL_ERROR",
+            format!("no CDP session for tab '{}' (target {target_id})", cmd.tab),
+        );
+    };
+
+    let recording = std::sync::Arc::new(std::sync::Mutex::new(Screencast::new(PathBuf::from(
+        &cmd.out,
+    ))));
+    if !cdp
+        .start_screencast(&cdp_session_id, recording.clone())
+        .await
+    {
+        return ActionResult::fatal_with_hint(
+            "RECORDING_ACTIVE",
+            format!("tab '{}' is already being recorded", cmd.tab),
+            "run `actionbook browser record-video stop` first",
+        );
+    }
+    let frames_dir = screencast::frames_dir(std::path::Path::new(&cmd.out));
+    if let Err(e) =
+        screencast::install(&cdp, &target_id, &cdp_session_id, recording, cmd.quality).await
+    {
+        cdp.take_screencast(&cdp_session_id).await;
+        return ActionResult::fatal("CDP_ERROR", format!("failed to start recording: {e}"));
+    }
+
+    ActionResult::ok(json!({
+        "recording": true,
+        "out": cmd.out,
+        "frames_dir": frames_dir.to_string_lossy(),
+    }))
+}
+
+// ── Stop ──────────────────────────────────────────────────────────────────────
+
+/// Stop recording and encode the video.
+#[derive(Args, Debug, Clone, Serialize, Deserialize)]
+#[command(after_help = "\
+Examples:
+  actionbook browser record-video stop --session s1 --tab t1
+  actionbook browser record-video stop --out other.mp4 --session s1 --tab t1
+
+Writes the video to the --out given at start unless another is passed here.")]
+pub struct StopCmd {
+    /// Write the video here instead
+    #[arg(long)]
+    pub out: Option<String>,
+    /// Session ID
+    #[arg(long)]
+    #[serde(rename = "session_id")]
+    pub session: String,
+    /// Tab ID
+    #[arg(long)]
+    #[serde(rename = "tab_id")]
+    pub tab: String,
+}
+
+pub const STOP_COMMAND_NAME: &str = "browser record-video stop";
+
+pub fn stop_context(cmd: &StopCmd, result: &ActionResult) -> Option<ResponseContext> {
+    tab_context(&cmd.session, &cmd.tab, result)
+}
+
+pub async fn execute_stop(cmd: &StopCmd, registry: &SharedRegistry) -> ActionResult {
+    let (cdp, target_id) = match get_cdp_and_target(registry, &cmd.session, &cmd.tab).await {
+        Ok(v) => v,
+        Err(e) => return e,
+    };
+    let recording = match cdp.get_cdp_session_id(&target_id).await {
+        Some(id) => cdp.take_screencast(&id).await,
+        None => None,
+    };
+    let Some(recording) = recording else {
+        return ActionResult::fatal_with_hint(
+            "RECORDING_NOT_ACTIVE",
+            format!("tab '{}' is not being recorded", cmd.tab),
+            "start one with `actionbook browser record-video start --out session.webm`",
+        );
+    };
+
+    // Best effort: a closed or crashed tab has no screencast left to stop.
+    let _ = cdp
+        .execute_on_tab(&target_id, "Page.stopScreencast", json!({}))
+        .await;
+
+    let (out, dir, frames, duration_ms, list) = {
+        let mut rec = recording.lock().unwrap_or_else(|e| e.into_inner());
+        rec.stop();
+        let out = cmd
+            .out
+            .as_ref()
+            .map(PathBuf::from)
+            .unwrap_or(rec.out.clone());
+        let end = rec.elapsed_ms();
+        (
+            out,
+            rec.frames_dir.clone(),
+            rec.frames(),
+            end,
+            rec.concat_list(end),
+        )
+    };
+
+    if frames == 0 {
+        let _ = std::fs::remove_dir_all(&dir);
+        return ActionResult::fatal_with_hint(
+            "RECORDING_EMPTY",
+            "no frames were captured",
+            "the tab must be visible and painting; background tabs send no frames",
+        );
+    }
+    if let Err(e) = std::fs::write(dir.join(screencast::CONCAT_LIST), list) {
+        return ActionResult::fatal(
+            "IO_ERROR",
+            format!("failed to write {}: {e}", dir.display()),
+        );
+    }
+    if let Some(parent) = out.parent()
+        && !parent.as_os_str().is_empty()
+        && let Err(e) = std::fs::create_dir_all(parent)
+    {
+        return ActionResult::fatal(
+            "IO_ERROR",
+            format!("failed to create {}: {e}", parent.display()),
+        );
+    }
+
+    let mut data = json!({
+        "path": out.to_string_lossy(),
+        "frames": frames,
+        "duration_ms": duration_ms,
+    });
+    match screencast::encode(&dir, &out).await {
+        Ok(true) => {
+            let _ = std::fs::remove_dir_all(&dir);
+            data["encoded"] = json!(true);
+        }
+        Ok(false) => {
+            let command = screencast::ffmpeg_command_line(&screencast::ffmpeg_args(
+                &dir.join(screencast::CONCAT_LIST),
+                &out,
+            ));
+            data["encoded"] = json!(false);
+            data["frames_dir"] = json!(dir.to_string_lossy());
+            data["ffmpeg"] = json!(command);
+            data["__warnings"] = json!([format!(
+                "ffmpeg not found on PATH; frames kept in {}",
+                dir.display()
+            )]);
+        }
+        Err(e) => {
+            return ActionResult::fatal_with_details(
+                "FFMPEG_FAILED",
+                e,
+                "frames are kept for another try",
+                json!({ "frames_dir": dir.to_string_lossy() }),
+            );
+        }
+    }
+    ActionResult::ok(data)
+}
@@ -0,0 +1,116 @@
+use clap::Args;
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+
+use crate::action_result::ActionResult;
+use crate::browser::{navigation, redact};
+use crate::daemon::cdp_session::get_cdp_and_target;
+use crate::daemon::registry::SharedRegistry;
+use crate::output::ResponseContext;
+
+/// Show the HTTP response of the current page
+#[derive(Args, Debug, Clone, Serialize, Deserialize)]
+#[command(after_help = "\
+Examples:
+  actionbook browser response --session s1 --tab t1
+  actionbook browser response --session s1 --tab t1 --json
+  actionbook browser response --session s1 --tab t1 --show-secrets
+
+Reports the main document's final URL, HTTP status, response headers, the
+redirects that led to it and timing (ms from request to response headers
+and to the end of the body), captured from the tab's network events on
+every navigation — goto, clicks and reloads alike. Use it to branch on 403,
+429 or a redirect to a login page. Same-document navigations (hash changes,
+history.pushState) keep the previous document's response.
+
+Credential headers (Set-Cookie, Authorization, API keys and tokens) are
+masked unless --show-secrets is passed.")]
+pub struct Cmd {
+    /// Session ID
+    #[arg(long)]
+    #[serde(rename = "session_id")]
+    pub session: String,
+    /// Tab ID
+    #[arg(long)]
+    #[serde(rename = "tab_id")]
+    pub tab: String,
+    /// Show credential header values instead of masking them
+    #[arg(long)]
+    #[serde(default)]
+    pub show_secrets: bool,
+}
+
+pub const COMMAND_NAME: &str = "browser response";
+
+pub fn context(cmd: &Cmd, result: &ActionResult) -> Option<ResponseContext> {
+    if let ActionResult::Fatal { code, .. } = result
+        && code == "SESSION_NOT_FOUND"
+    {
+        return None;
+    }
+    let tab_id = if let ActionResult::Fatal { code, .. } = result
+        && code == "TAB_NOT_FOUND"
+    {
+        None
+    } else {
+        Some(cmd.tab.clone())
+    };
+    let (url, title) = match result {
+        ActionResult::Ok { data } => (
+            data.get("__ctx_url")
+                .and_then(|v| v.as_str())
+                .map(String::from),
+            data.get("__ctx_title")
+                .and_then(|v| v.as_str())
+                .map(String::from),
+        ),
+        _ => (None, None),
+    };
+    Some(ResponseContext {
+        session_id: cmd.session.clone(),
+        tab_id,
+        window_id: None,
+        url,
+        title,
+    })
+}
+
+pub async fn execute(cmd: &Cmd, registry: &SharedRegistry) -> ActionResult {
+    let (cdp, target_id) = match get_cdp_and_target(registry, &cmd.session, &cmd.tab).await {
+        Ok(v) => v,
+        Err(e) => return e,
+    };
+    let Some(cdp_session_id) = cdp.get_cdp_session_id(&target_id).await else {
+        return ActionResult::fatal(
+            "INTERNAL_ERROR",
+            format!("no CDP session for target '{target_id}'"),
+        );
+    };
+
+    // Network events name frames by frame ID; the main frame's usually equals
+    // the target ID, but ask rather than assume (extension tabs differ).
+    let frame_id = cdp
+        .execute_on_tab(&target_id, "Page.getFrameTree", json!({}))
+        .await
+        .ok()
+        .and_then(|v| {
+            v.pointer("/result/frameTree/frame/id")
+                .and_then(|v| v.as_str())
+                .map(String::from)
+        })
+        .unwrap_or_else(|| target_id.clone());
+
+    let Some(mut data) = cdp.document_response(&cdp_session_id, &frame_id).await else {
+        return ActionResult::fatal_with_hint(
+            "RESPONSE_NOT_FOUND",
+            "no document response recorded for this tab",
+            "navigate with `browser goto` first; about:blank and pages loaded before the tab was attached have none",
+        );
+    };
+    if !cmd.show_secrets {
+        redact::mask_headers(&mut data["headers"]);
+    }
+    data["__ctx_url"] = json!(navigation::get_tab_url(&cdp, &target_id).await);
+    data["__ctx_title"] = json!(navigation::get_tab_title(&cdp, &target_id).await);
+    ActionResult::ok(data)
+}
@@ -0,0 +1,260 @@
+use clap::Args;
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+
+use crate::action_result::ActionResult;
+use crate::browser::navigation;
+use crate::daemon::cdp_session::{cdp_error_to_result, get_cdp_and_target};
+use crate::daemon::registry::SharedRegistry;
+use crate::output::ResponseContext;
+
+/// Archive format for `browser save`.
+#[derive(Clone, Copy, Debug, Default, Serialize, Deserialize, clap::ValueEnum, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum SaveFormat {
+    /// MHTML archive captured by Chrome (Page.captureSnapshot) — byte-faithful.
+    #[default]
+    Mhtml,
+    /// Single HTML file with stylesheets and images inlined, scripts removed.
+    Html,
+}
+
+impl SaveFormat {
+    fn mime_type(self) -> &'static str {
+        match self {
+            SaveFormat::Mhtml => "multipart/related",
+            SaveFormat::Html => "text/html",
+        }
+    }
+}
+
+/// Archive the current page as a single file
+#[derive(Args, Debug, Clone, Serialize, Deserialize)]
+#[command(after_help = "\
+Examples:
+  actionbook browser save /tmp/page.mhtml --session s1 --tab t1
+  actionbook browser save /tmp/page.html --format html --session s1 --tab t1
+
+mhtml (default) is Chrome's own archive of the page and every subresource it
+loaded. html inlines stylesheets and images into one self-contained file and
+drops scripts, so it opens anywhere but is a reconstruction, not the original bytes.")]
+pub struct Cmd {
+    /// Output file path
+    pub path: String,
+    /// Archive format
+    #[arg(long, value_enum, default_value = "mhtml")]
+    #[serde(default)]
+    pub format: SaveFormat,
+    /// Session ID
+    #[arg(long)]
+    #[serde(rename = "session_id")]
+    pub session: String,
+    /// Tab ID
+    #[arg(long)]
+    #[serde(rename = "tab_id")]
+    pub tab: String,
+    /// Abort when the tab downloads more than this many bytes (default: session budget)
+    #[arg(long, value_name = "BYTES")]
+    #[serde(default)]
+    pub max_download_bytes: Option<u64>,
+}
+
+pub const COMMAND_NAME: &str = "browser save";
+
+/// Serialize the live DOM into a standalone HTML document. Stylesheets become
+/// `<style>` blocks, images become data URLs, and scripts are removed so the
+/// archive renders the captured state instead of re-running the page.
+const INLINE_HTML_JS: &str = r#"(async function() {
+    const toDataUrl = async (url) => {
+        try {
+            const resp = await fetch(url, { credentials: 'include' });
+            if (!resp.ok) return null;
+            const blob = await resp.blob();
+            return await new Promise((resolve) => {
+                const reader = new FileReader();
+                reader.onload = () => resolve(reader.result);
+                reader.onerror = () => resolve(null);
+                reader.readAsDataURL(blob);
+            });
+        } catch (_) {
+            return null;
+        }
+    };
+    const sheetText = async (sheet) => {
+        try {
+            return Array.from(sheet.cssRules).map((r) => r.cssText).join('\n');
+        } catch (_) {
+            if (!sheet.href) return null;
+            try {
+                const resp = await fetch(sheet.href);
+                return resp.ok ? await resp.text() : null;
+            } catch (_) {
+                return null;
+            }
+        }
+    };
+
+    const clone = document.documentElement.cloneNode(true);
+    let inlined = 0;
+    let failed = 0;
+
+    const liveLinks = Array.from(document.querySelectorAll('link[rel~="stylesheet"]'));
+    const cloneLinks = Array.from(clone.querySelectorAll('link[rel~="stylesheet"]'));
+    for (let i = 0; i < cloneLinks.length; i++) {
+        const sheet = liveLinks[i] && liveLinks[i].sheet;
+        const text = sheet ? await sheetText(sheet) : null;
+        if (text === null) { failed++; continue; }
+        const style = document.createElement('style');
+        style.textContent = text;
+        cloneLinks[i].replaceWith(style);
+        inlined++;
+    }
+
+    const liveImgs = Array.from(document.images);
+    const cloneImgs = Array.from(clone.querySelectorAll('img'));
+    for (let i = 0; i < cloneImgs.length; i++) {
+        const src = liveImgs[i] && (liveImgs[i].currentSrc || liveImgs[i].src);
+        if (!src || src.startsWith('data:')) continue;
+        const data = await toDataUrl(src);
+        if (data === null) { failed++; continue; }
+        cloneImgs[i].setAttribute('src', data);
+        cloneImgs[i].removeAttribute('srcset');
+        inlined++;
+    }
+
+    clone.querySelectorAll('script, noscript').forEach((el) => el.remove());
+    const head = clone.querySelector('head');
+    if (head && !head.querySelector('base')) {
+        const base = document.createElement('base');
+        base.href = document.baseURI;
+        head.prepend(base);
+    }
+
+    const doctype = document.doctype ? new XMLSerializer().serializeToString(document.doctype) : '<!DOCTYPE html>';
+    return { html: doctype + '\n' + clone.outerHTML, inlined, failed };
+})()"#;
+
+pub fn context(cmd: &Cmd, result: &ActionResult) -> Option<ResponseContext> {
+    if let ActionResult::Fatal { code, .. } = result
+        && code == "SESSION_NOT_FOUND"
+    {
+        return None;
+    }
+    let tab_id = if let ActionResult::Fatal { code, .. } = result
+        && code == "TAB_NOT_FOUND"
+    {
+        None
+    } else {
+        Some(cmd.tab.clone())
+    };
+    let (url, title) = match result {
+        ActionResult::Ok { data } => (
+            data.get("__ctx_url")
+                .and_then(|v| v.as_str())
+                .map(String::from),
+            data.get("__ctx_title")
+                .and_then(|v| v.as_str())
+                .map(String::from),
+        ),
+        _ => (None, None),
+    };
+    Some(ResponseContext {
+        session_id: cmd.session.clone(),
+        tab_id,
+        window_id: None,
+        url,
+        title,
+    })
+}
+
+pub async fn execute(cmd: &Cmd, registry: &SharedRegistry) -> ActionResult {
+    let (cdp, target_id) = match get_cdp_and_target(registry, &cmd.session, &cmd.tab).await {
+        Ok(v) => v,
+        Err(e) => return e,
+    };
+
+    let url = navigation::get_tab_url(&cdp, &target_id).await;
+    let title = navigation::get_tab_title(&cdp, &target_id).await;
+
+    let mut resources = None;
+    let content = match cmd.format {
+        SaveFormat::Mhtml => {
+            let resp = match cdp
+                .execute_on_tab(
+                    &target_id,
+                    "Page.captureSnapshot",
+                    json!({ "format": "mhtml" }),
+                )
+                .await
+            {
+                Ok(v) => v,
+                Err(e) => return cdp_error_to_result(e, "CDP_ERROR"),
+            };
+            match resp.pointer("/result/data").and_then(|v| v.as_str()) {
+                Some(data) => data.to_string(),
+                None => {
+                    return ActionResult::fatal(
+                        "CDP_ERROR",
+                        "Page.captureSnapshot returned no data",
+                    );
+                }
+            }
+        }
+        SaveFormat::Html => {
+            let resp = match cdp
+                .execute_on_tab(
+                    &target_id,
+                    "Runtime.evaluate",
+                    json!({
+                        "expression": INLINE_HTML_JS,
+                        "returnByValue": true,
+                        "awaitPromise": true,
+                    }),
+                )
+                .await
+            {
+                Ok(v) => v,
+                Err(e) => return cdp_error_to_result(e, "CDP_ERROR"),
+            };
+            if let Some(exc) = resp.pointer("/result/exceptionDetails") {
+                let msg = exc
+                    .pointer("/exception/description")
+                    .and_then(|v| v.as_str())
+                    .or_else(|| exc.get("text").and_then(|v| v.as_str()))
+                    .unwrap_or("page serialization failed");
+                return ActionResult::fatal("JS_EXCEPTION", msg.to_string());
+            }
+            let value = resp.pointer("/result/result/value");
+            let Some(html) = value.and_then(|v| v.get("html")).and_then(|v| v.as_str()) else {
+                return ActionResult::fatal("CDP_ERROR", "page serialization returned no HTML");
+            };
+            resources = Some(json!({
+                "inlined": value.and_then(|v| v.get("inlined")).cloned().unwrap_or(json!(0)),
+                "failed": value.and_then(|v| v.get("failed")).cloned().unwrap_or(json!(0)),
+            }));
+            html.to_string()
+        }
+    };
+
+    if let Err(e) = std::fs::write(&cmd.path, content.as_bytes()) {
+        return ActionResult::fatal(
+            "ARTIFACT_WRITE_FAILED",
+            format!("failed to write archive to '{}': {e}", cmd.path),
+        );
+    }
+
+    let mut data = json!({
+        "artifact": {
+            "path": cmd.path,
+            "mime_type": cmd.format.mime_type(),
+            "bytes": content.len() as u64,
+        },
+        "format": cmd.format,
+        "__ctx_url": url,
+        "__ctx_title": title,
+    });
+    if let Some(resources) = resources {
+        data["resources"] = resources;
+    }
+    ActionResult::ok(data)
+}
@@ -19,6 +19,10 @@ pub struct Cmd {
     #[arg(long)]
     #[serde(rename = "tab_id")]
     pub tab: String,
+    /// Abort when the tab downloads more than this many bytes (default: session budget)
+    #[arg(long, value_name = "BYTES")]
+    #[serde(default)]
+    pub max_download_bytes: Option<u64>,
     /// Capture full page (not just the current viewport)
     #[arg(long)]
     #[serde(default)]
@@ -33,7 +37,7 @@ pub struct Cmd {
     /// Image format (png or jpeg)
     #[arg(long)]
     pub screenshot_format: Option<String>,
-    /// CSS selector to limit capture region
+    /// Capture only this element (CSS, XPath, @ref, text= or role=)
     #[arg(long)]
     pub selector: Option<String>,
 }
@@ -202,6 +206,8 @@ pub async fn execute(cmd: &Cmd, registry: &SharedRegistry) -> ActionResult {
         match get_selector_rect(&mut ctx, sel).await {
             Ok((clip, _)) => {
                 params["clip"] = clip;
+                // Elements taller than the viewport are captured whole.
+                params["captureBeyondViewport"] = json!(true);
             }
             Err(e) => {
                 if overlay_injected {
@@ -572,9 +578,20 @@ async fn get_selector_rect(
             ActionResult::fatal("CDP_ERROR", format!("failed to get rect for: {selector}"))
         })?;
 
+    if rect.width <= 0.0 || rect.height <= 0.0 {
+        return Err(ActionResult::fatal_with_hint(
+            "ELEMENT_NOT_VISIBLE",
+            format!("'{selector}' has no size to capture"),
+            "the element is hidden or empty; check it with `browser state`",
+        ));
+    }
+
+    // `Page.captureScreenshot` clips in document coordinates, while the
+    // bounding rect is relative to the viewport the element was scrolled into.
+    let (scroll_x, scroll_y) = get_scroll_offsets(&ctx.cdp, &ctx.target_id).await?;
     let clip = json!({
-        "x": rect.x,
-        "y": rect.y,
+        "x": rect.x + scroll_x,
+        "y": rect.y + scroll_y,
         "width": rect.width,
         "height": rect.height,
         "scale": 1,
@@ -5,11 +5,14 @@ use serde::{Deserialize, Serialize};
 use serde_json::{Value, json};
 
 use crate::action_result::ActionResult;
+use crate::browser::element;
 use crate::daemon::cdp_session::{CdpSession, get_cdp_and_target};
 use crate::daemon::registry::SharedRegistry;
 use crate::output::ResponseContext;
 
-use super::snapshot_transform::{self, CursorInfo, SnapshotOptions};
+use super::snapshot_transform::{
+    self, AXNode, CursorInfo, SnapshotBackend, SnapshotFormat, SnapshotOptions,
+};
 
 fn cursor_default() -> bool {
     true
@@ -24,6 +27,9 @@ Examples:
   actionbook browser snapshot -i -c --session s1 --tab t1
   actionbook browser snapshot --depth 3 --session s1 --tab t1
   actionbook browser snapshot --selector \"#main\" --session s1 --tab t1
+  actionbook browser snapshot --format compact --session s1 --tab t1
+  actionbook browser snapshot --frame checkout --session s1 --tab t1
+  actionbook browser snapshot --backend cdp-ax --session s1 --tab t1
 
 The default snapshot contains all information including interactive elements,
 structural nodes, and cursor-interactive elements. Use additional flags as needed.
@@ -33,6 +39,27 @@ Elements are labeled with refs (e.g. @e8, @e9). Use @eN to target elements
 in other commands: click @e5, fill @e7 \"text\", hover @e3.
 Refs are stable across snapshots — if the DOM node stays the same, the ref
 stays the same. This lets agents chain commands without re-snapshotting.
+If the page re-renders an element, its ref is re-matched by role and name;
+when more than one element now matches, the ref fails with REF_STALE instead
+of guessing.
+
+The snapshot covers the page and one level of iframes. --frame captures a
+single iframe (any depth, same- or cross-origin) instead; its refs work in
+other commands like any other.
+
+The tree always comes from Chrome's accessibility tree. --backend picks how
+cursor-interactive elements are found: js (default) runs a scan in the page;
+cdp-ax reads styles and attributes from DOMSnapshot instead, which is faster
+on very large pages and leaves the page untouched, but does not see onclick
+handlers assigned from script.
+
+--format controls the saved file:
+  yaml (default) — Playwright-style tree, shown below
+  text           — flat `- role \"name\" [ref=eN]` lines
+  json           — nested tree of {ref, role, name, value, url, children}
+  compact        — yaml with unnamed wrappers collapsed, runs of similar
+                   siblings folded into \"×N similar items\", long URLs dropped;
+                   folded elements keep their refs but are not listed
 
 Sample output:
   - generic
@@ -72,6 +99,19 @@ pub struct Cmd {
     #[arg(long, short = 's')]
     #[serde(default)]
     pub selector: Option<String>,
+    /// Capture only this iframe: its name, a URL substring, or its position
+    /// (1 = first frame)
+    #[arg(long, conflicts_with = "selector")]
+    #[serde(default)]
+    pub frame: Option<String>,
+    /// Snapshot file format
+    #[arg(long, value_enum, default_value = "yaml")]
+    #[serde(default)]
+    pub format: SnapshotFormat,
+    /// How cursor-interactive elements are detected
+    #[arg(long, value_enum, default_value = "js")]
+    #[serde(default)]
+    pub backend: SnapshotBackend,
 }
 
 pub const COMMAND_NAME: &str = "browser snapshot";
@@ -111,29 +151,64 @@ pub fn context(cmd: &Cmd, result: &ActionResult) -> Option<ResponseContext> {
     })
 }
 
-pub async fn execute(cmd: &Cmd, registry: &SharedRegistry) -> ActionResult {
-    let (cdp, target_id) = match get_cdp_and_target(registry, &cmd.session, &cmd.tab).await {
-        Ok(v) => v,
-        Err(e) => return e,
+/// A captured accessibility snapshot of one tab, before rendering.
+///
+/// Refs on `nodes` are already stored in the tab's RefCache, so they resolve
+/// through [`crate::browser::element::resolve_ref`] (or any `@eN` selector)
+/// as soon as `capture` returns.
+#[derive(Debug, Clone)]
+pub struct Snapshot {
+    pub nodes: Vec<AXNode>,
+    /// Live tab URL/title at capture time (`None` when empty).
+    pub url: Option<String>,
+    pub title: Option<String>,
+    /// The tree hit the token budget and was cut short.
+    pub truncated: bool,
+    pub warnings: Vec<String>,
+}
+
+/// Capture a tab's accessibility tree (main frame plus one level of iframes,
+/// or only the `--frame` iframe), assign refs and store them in the
+/// registry's RefCache. `browser snapshot` is this plus rendering to a file;
+/// other callers that need refs without the artifact should use it directly.
+pub async fn capture(
+    registry: &SharedRegistry,
+    session_id: &str,
+    tab_id: &str,
+    options: &SnapshotOptions,
+    cursor: bool,
+    backend: SnapshotBackend,
+) -> Result<Snapshot, ActionResult> {
+    let (cdp, target_id) = get_cdp_and_target(registry, session_id, tab_id).await?;
+
+    // --frame: capture that one iframe's tree instead of the page
+    let frame_id = match options.frame {
+        Some(ref spec) => {
+            let frames = element::list_frames(&cdp, &target_id).await?;
+            Some(element::pick_frame(&frames, spec)?.frame_id.clone())
+        }
+        None => None,
     };
 
     // Resolve --selector to a set of backendNodeIds via CDP DOM queries
-    let scope_backend_ids = if let Some(ref selector) = cmd.selector {
-        match resolve_selector_scope(&cdp, &target_id, selector).await {
-            Ok(ids) => Some(ids),
-            Err(e) => return e,
-        }
-    } else {
-        None
+    let scope_backend_ids = match options.selector {
+        Some(ref selector) => Some(resolve_selector_scope(&cdp, &target_id, selector).await?),
+        None => None,
     };
 
     // Fetch the full accessibility tree via CDP
-    let cdp_response = match cdp
-        .execute_on_tab(&target_id, "Accessibility.getFullAXTree", json!({}))
-        .await
-    {
-        Ok(resp) => resp,
-        Err(e) => return crate::daemon::cdp_session::cdp_error_to_result(e, "INTERNAL_ERROR"),
+    let cdp_response = match frame_id {
+        Some(ref frame_id) => {
+            enable_iframe_sessions(&cdp).await;
+            let iframe_sessions = cdp.iframe_sessions().await;
+            fetch_iframe_ax_tree(&cdp, &target_id, frame_id, &iframe_sessions)
+                .await
+                .map_err(|e| ActionResult::fatal("INTERNAL_ERROR", e))?
+        }
+        None => cdp
+            .execute_on_tab(&target_id, "Accessibility.getFullAXTree", json!({}))
+            .await
+            .map_err(|e| crate::daemon::cdp_session::cdp_error_to_result(e, "INTERNAL_ERROR"))?,
     };
 
     // Query live url/title from CDP (not registry — avoids stale data after navigation)
@@ -145,56 +220,56 @@ pub async fn execute(cmd: &Cmd, registry: &SharedRegistry) -> ActionResult {
     // Get RefCache from registry
     let mut ref_cache = {
         let mut reg = registry.lock().await;
-        reg.take_ref_cache(&cmd.session, &cmd.tab)
-    };
-
-    let options = SnapshotOptions {
-        interactive: cmd.interactive,
-        compact: cmd.compact,
-        depth: cmd.depth.map(|d| d as usize),
-        selector: cmd.selector.clone(),
+        reg.take_ref_cache(session_id, tab_id)
     };
 
     // Parse and transform the AX tree
     // Detect cursor-interactive elements if --cursor flag set
-    let (cursor_elements, cursor_warning) = if cmd.cursor {
-        match detect_cursor_elements(&cdp, &target_id).await {
-            Ok(map) => (Some(map), None),
-            Err(e) => (
-                None,
-                Some(format!("cursor detection failed: {e}, proceeding without")),
-            ),
+    let mut warnings = Vec::new();
+    let cursor_elements = if cursor && frame_id.is_none() {
+        let detected = match backend {
+            SnapshotBackend::Js => detect_cursor_elements(&cdp, &target_id).await,
+            SnapshotBackend::CdpAx => detect_cursor_elements_from_dom(&cdp, &target_id).await,
+        };
+        match detected {
+            Ok(map) => Some(map),
+            Err(e) => {
+                warnings.push(format!("cursor detection failed: {e}, proceeding without"));
+                None
+            }
         }
     } else {
-        (None, None)
+        None
     };
 
     let mut nodes = snapshot_transform::parse_ax_tree(
         &cdp_response,
-        &options,
+        options,
         &mut ref_cache,
         scope_backend_ids.as_ref(),
         cursor_elements.as_ref(),
-        None, // main frame
+        frame_id.as_deref(),
     );
 
-    // Expand 1 level of iframe content (only from main frame, no recursion).
-    // Returns the set of frame_ids expanded in this pass.
-    let expanded_frames =
-        expand_iframes(&cdp, &target_id, &mut nodes, &mut ref_cache, &options).await;
-
-    // Expand OOPIF frames that weren't discovered via AX tree Iframe nodes
-    // (e.g., iframes inside closed shadow roots — invisible to DOM but
-    // Chrome still creates dedicated CDP sessions for them).
-    expand_undiscovered_oopifs(
-        &cdp,
-        &target_id,
-        &mut nodes,
-        &mut ref_cache,
-        &options,
-        &expanded_frames,
-    )
-    .await;
+    if frame_id.is_none() {
+        // Expand 1 level of iframe content (only from main frame, no recursion).
+        // Returns the set of frame_ids expanded in this pass.
+        let expanded_frames =
+            expand_iframes(&cdp, &target_id, &mut nodes, &mut ref_cache, options).await;
+
+        // Expand OOPIF frames that weren't discovered via AX tree Iframe nodes
+        // (e.g., iframes inside closed shadow roots — invisible to DOM but
+        // Chrome still creates dedicated CDP sessions for them).
+        expand_undiscovered_oopifs(
+            &cdp,
+            &target_id,
+            &mut nodes,
+            &mut ref_cache,
+            options,
+            &expanded_frames,
+        )
+        .await;
+    }
 
     // Apply token budget truncation (100K tokens max)
     const MAX_TOKENS: usize = 100_000;
@@ -212,11 +287,49 @@ pub async fn execute(cmd: &Cmd, registry: &SharedRegistry) -> ActionResult {
     // Store RefCache back (single lock)
     {
         let mut reg = registry.lock().await;
-        reg.put_ref_cache(&cmd.session, &cmd.tab, ref_cache);
+        reg.put_ref_cache(session_id, tab_id, ref_cache);
     }
 
+    Ok(Snapshot {
+        nodes,
+        url,
+        title,
+        truncated,
+        warnings,
+    })
+}
+
+pub async fn execute(cmd: &Cmd, registry: &SharedRegistry) -> ActionResult {
+    let options = SnapshotOptions {
+        interactive: cmd.interactive,
+        compact: cmd.compact,
+        depth: cmd.depth.map(|d| d as usize),
+        selector: cmd.selector.clone(),
+        frame: cmd.frame.clone(),
+    };
+    let snapshot = match capture(
+        registry,
+        &cmd.session,
+        &cmd.tab,
+        &options,
+        cmd.cursor,
+        cmd.backend,
+    )
+    .await
+    {
+        Ok(s) => s,
+        Err(e) => return e,
+    };
+    let Snapshot {
+        nodes,
+        url,
+        title,
+        truncated,
+        warnings,
+    } = snapshot;
+
     // Build output per §10.1
-    let output = snapshot_transform::build_output(nodes);
+    let output = snapshot_transform::build_output_as(nodes, cmd.format);
 
     // Write snapshot content to a file in the session data directory.
     let session_data_dir = crate::config::session_data_dir(&cmd.session);
@@ -224,7 +337,7 @@ pub async fn execute(cmd: &Cmd, registry: &SharedRegistry) -> ActionResult {
         .duration_since(std::time::UNIX_EPOCH)
         .map(|d| d.as_millis())
         .unwrap_or(0);
-    let snapshot_path = session_data_dir.join(format!("snapshot_{ts}.yaml"));
+    let snapshot_path = session_data_dir.join(format!("snapshot_{ts}.{}", cmd.format.extension()));
     let snapshot_path_str = snapshot_path.to_string_lossy().to_string();
 
     if let Err(e) = std::fs::write(&snapshot_path, &output.content) {
@@ -236,6 +349,8 @@ pub async fn execute(cmd: &Cmd, registry: &SharedRegistry) -> ActionResult {
 
     let mut data = json!({
         "format": "snapshot",
+        "content_format": cmd.format.as_str(),
+        "backend": cmd.backend.as_str(),
         "path": snapshot_path_str,
         "nodes": output.nodes,
         "stats": {
@@ -248,8 +363,8 @@ pub async fn execute(cmd: &Cmd, registry: &SharedRegistry) -> ActionResult {
     if truncated {
         data["__truncated"] = json!(true);
     }
-    if let Some(ref warning) = cursor_warning {
-        data["__warnings"] = json!([warning]);
+    if !warnings.is_empty() {
+        data["__warnings"] = json!(warnings);
     }
     ActionResult::ok(data)
 }
@@ -678,34 +793,9 @@ async fn detect_cursor_elements(
             let has_tabindex = elem["hasTabIndex"].as_bool().unwrap_or(false);
             let is_editable = elem["isEditable"].as_bool().unwrap_or(false);
 
-            let kind = if has_pointer || has_onclick {
-                "clickable"
-            } else if is_editable {
-                "editable"
-            } else {
-                "focusable"
-            };
-
-            let mut hints = Vec::new();
-            if has_pointer {
-                hints.push("cursor:pointer".to_string());
-            }
-            if has_onclick {
-                hints.push("onclick".to_string());
-            }
-            if has_tabindex {
-                hints.push("tabindex".to_string());
-            }
-            if is_editable {
-                hints.push("contenteditable".to_string());
-            }
-
             map.insert(
                 bid,
-                CursorInfo {
-                    kind: kind.to_string(),
-                    hints,
-                },
+                CursorInfo::from_signals(has_pointer, has_onclick, has_tabindex, is_editable),
             );
         }
     }
@@ -713,6 +803,24 @@ async fn detect_cursor_elements(
     Ok(map)
 }
 
+/// Like [`detect_cursor_elements`], but from `DOMSnapshot.captureSnapshot`:
+/// one CDP call, no script run in or attribute written to the page.
+async fn detect_cursor_elements_from_dom(
+    cdp: &CdpSession,
+    target_id: &str,
+) -> Result<std::collections::HashMap<i64, CursorInfo>, crate::error::CliError> {
+    let resp = cdp
+        .execute_on_tab(
+            target_id,
+            "DOMSnapshot.captureSnapshot",
+            json!({ "computedStyles": ["cursor"] }),
+        )
+        .await?;
+    Ok(snapshot_transform::cursor_elements_from_dom_snapshot(
+        &resp["result"],
+    ))
+}
+
 /// Resolve data-__ab-ci tagged elements to backendNodeIds via CDP DOM queries.
 /// Only matches attributes with our nonce prefix to avoid colliding with page-owned attrs.
 async fn resolve_cursor_backend_ids(
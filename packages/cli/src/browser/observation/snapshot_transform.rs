@@ -46,6 +46,64 @@ pub struct SnapshotOptions {
     pub depth: Option<usize>,
     /// CSS selector to limit subtree (None = whole page)
     pub selector: Option<String>,
+    /// Capture only this iframe (`--frame`: name, URL substring or position)
+    pub frame: Option<String>,
+}
+
+/// Rendering of the snapshot file written by `browser snapshot`.
+#[derive(Clone, Copy, Debug, Default, Serialize, Deserialize, clap::ValueEnum, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum SnapshotFormat {
+    /// Flat indented lines: `- role "name" [ref=eN] url=...`
+    Text,
+    /// Nested JSON tree of `{ref, role, name, value, url, children}`
+    Json,
+    /// Playwright-style YAML DSL (see `render_yaml`).
+    #[default]
+    Yaml,
+    /// YAML with wrappers collapsed, repeated siblings folded and long URLs dropped.
+    Compact,
+}
+
+impl SnapshotFormat {
+    pub fn extension(self) -> &'static str {
+        match self {
+            SnapshotFormat::Text => "txt",
+            SnapshotFormat::Json => "json",
+            SnapshotFormat::Yaml | SnapshotFormat::Compact => "yaml",
+        }
+    }
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            SnapshotFormat::Text => "text",
+            SnapshotFormat::Json => "json",
+            SnapshotFormat::Yaml => "yaml",
+            SnapshotFormat::Compact => "compact",
+        }
+    }
+}
+
+/// How `browser snapshot` finds cursor-interactive elements. The tree itself
+/// always comes from `Accessibility.getFullAXTree`.
+#[derive(Clone, Copy, Debug, Default, Serialize, Deserialize, clap::ValueEnum, PartialEq, Eq)]
+#[serde(rename_all = "kebab-case")]
+pub enum SnapshotBackend {
+    /// Scan the page with an injected script.
+    #[default]
+    Js,
+    /// Read computed styles and attributes from `DOMSnapshot.captureSnapshot`;
+    /// nothing runs in the page.
+    CdpAx,
+}
+
+impl SnapshotBackend {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            SnapshotBackend::Js => "js",
+            SnapshotBackend::CdpAx => "cdp-ax",
+        }
+    }
 }
 
 /// Snapshot output ready to serialise as §10.1 data.
@@ -106,6 +164,164 @@ pub struct CursorInfo {
     pub hints: Vec<String>,
 }
 
+impl CursorInfo {
+    /// Classify an element from what made it look interactive.
+    pub fn from_signals(pointer: bool, onclick: bool, tabindex: bool, editable: bool) -> Self {
+        let kind = if pointer || onclick {
+            "clickable"
+        } else if editable {
+            "editable"
+        } else {
+            "focusable"
+        };
+        let hints = [
+            (pointer, "cursor:pointer"),
+            (onclick, "onclick"),
+            (tabindex, "tabindex"),
+            (editable, "contenteditable"),
+        ]
+        .iter()
+        .filter(|(on, _)| *on)
+        .map(|(_, hint)| hint.to_string())
+        .collect();
+        CursorInfo {
+            kind: kind.to_string(),
+            hints,
+        }
+    }
+}
+
+/// Cursor-interactive elements of the main document in a
+/// `DOMSnapshot.captureSnapshot` result (taken with `computedStyles:
+/// ["cursor"]`), by backendNodeId. Applies the same rules as the injected
+/// scan: elements under `<body>` that are not hidden, not natively
+/// interactive, have a non-empty box, and show `cursor: pointer` (unless
+/// inherited from a pointer parent), an `onclick` attribute, a tabindex other
+/// than -1, or `contenteditable`. Handlers assigned from script
+/// (`el.onclick = ...`) are not visible here.
+pub fn cursor_elements_from_dom_snapshot(
+    snapshot: &Value,
+) -> std::collections::HashMap<i64, CursorInfo> {
+    const NATIVE_TAGS: &[&str] = &[
+        "a", "button", "input", "select", "textarea", "details", "summary", "iframe",
+    ];
+    const NATIVE_ROLES: &[&str] = &[
+        "button",
+        "link",
+        "textbox",
+        "checkbox",
+        "radio",
+        "combobox",
+        "listbox",
+        "menuitem",
+        "menuitemcheckbox",
+        "menuitemradio",
+        "option",
+        "searchbox",
+        "slider",
+        "spinbutton",
+        "switch",
+        "tab",
+        "treeitem",
+    ];
+
+    let mut found = std::collections::HashMap::new();
+    let strings: Vec<&str> = snapshot["strings"]
+        .as_array()
+        .map(|a| a.iter().map(|v| v.as_str().unwrap_or("")).collect())
+        .unwrap_or_default();
+    let string = |v: &Value| {
+        v.as_u64()
+            .and_then(|i| strings.get(i as usize).copied())
+            .unwrap_or("")
+    };
+    let doc = &snapshot["documents"][0];
+    let nodes = &doc["nodes"];
+    let ints = |v: &Value| -> Vec<i64> {
+        v.as_array()
+            .map(|a| a.iter().map(|x| x.as_i64().unwrap_or(-1)).collect())
+            .unwrap_or_default()
+    };
+    let parents = ints(&nodes["parentIndex"]);
+    let types = ints(&nodes["nodeType"]);
+    let backend_ids = ints(&nodes["backendNodeId"]);
+    let names = nodes["nodeName"].as_array().cloned().unwrap_or_default();
+    let attributes = nodes["attributes"].as_array().cloned().unwrap_or_default();
+    let count = parents.len();
+
+    // Layout: computed cursor and whether the box is non-empty, per node.
+    let mut pointer = vec![false; count];
+    let mut sized = vec![false; count];
+    let layout = &doc["layout"];
+    let layout_nodes = ints(&layout["nodeIndex"]);
+    for (i, &node) in layout_nodes.iter().enumerate() {
+        let Some(node) = usize::try_from(node).ok().filter(|&n| n < count) else {
+            continue;
+        };
+        pointer[node] = string(&layout["styles"][i][0]) == "pointer";
+        let bounds = &layout["bounds"][i];
+        sized[node] =
+            bounds[2].as_f64().unwrap_or(0.0) > 0.0 && bounds[3].as_f64().unwrap_or(0.0) > 0.0;
+    }
+
+    // Nodes come in document order, so a parent is always seen before its
+    // children and these flags can be inherited in one pass.
+    let mut in_body = vec![false; count];
+    let mut hidden = vec![false; count];
+    for i in 0..count {
+        let attrs: Vec<&str> = attributes
+            .get(i)
+            .and_then(|a| a.as_array())
+            .map(|a| a.iter().map(&string).collect())
+            .unwrap_or_default();
+        let attr = |name: &str| {
+            attrs
+                .chunks(2)
+                .find(|kv| kv[0].eq_ignore_ascii_case(name))
+                .map(|kv| kv.get(1).copied().unwrap_or(""))
+        };
+        let tag = names.get(i).map(&string).unwrap_or("").to_ascii_lowercase();
+        let parent = usize::try_from(parents[i]).ok().filter(|&p| p < i);
+        let parent_in_body = parent.is_some_and(|p| in_body[p]);
+        hidden[i] = parent.is_some_and(|p| hidden[p])
+            || attr("hidden").is_some()
+            || attr("aria-hidden") == Some("true");
+        in_body[i] = parent_in_body || tag == "body";
+
+        if types.get(i) != Some(&1) || !parent_in_body || hidden[i] || !sized[i] {
+            continue;
+        }
+        if NATIVE_TAGS.contains(&tag.as_str()) {
+            continue;
+        }
+        if attr("role").is_some_and(|r| NATIVE_ROLES.contains(&r.to_ascii_lowercase().as_str())) {
+            continue;
+        }
+        let has_pointer = pointer[i];
+        let has_onclick = attr("onclick").is_some();
+        let has_tabindex = attr("tabindex").is_some_and(|t| t != "-1");
+        let is_editable = matches!(attr("contenteditable"), Some("" | "true"));
+        if !has_pointer && !has_onclick && !has_tabindex && !is_editable {
+            continue;
+        }
+        if has_pointer
+            && !has_onclick
+            && !has_tabindex
+            && !is_editable
+            && parent.is_some_and(|p| pointer[p])
+        {
+            continue;
+        }
+        if let Some(&bid) = backend_ids.get(i) {
+            found.insert(
+                bid,
+                CursorInfo::from_signals(has_pointer, has_onclick, has_tabindex, is_editable),
+            );
+        }
+    }
+    found
+}
+
 /// Render a flat node list to `content` string per §10.1.
 /// Format: `- role "name" [ref=eN]` with depth-based indentation.
 /// Nodes without a ref omit the `[...]` bracket.
@@ -510,10 +726,175 @@ pub fn parse_ax_tree(
     result
 }
 
+/// Render a flat node list as a nested JSON tree.
+pub fn render_json(nodes: &[AXNode]) -> String {
+    fn build(nodes: &[AXNode], i: &mut usize) -> Value {
+        let node = &nodes[*i];
+        *i += 1;
+        let mut obj = serde_json::Map::new();
+        if !node.ref_id.is_empty() {
+            obj.insert("ref".into(), Value::from(node.ref_id.as_str()));
+        }
+        obj.insert("role".into(), Value::from(node.role.as_str()));
+        if !node.name.is_empty() {
+            obj.insert("name".into(), Value::from(node.name.as_str()));
+        }
+        if !node.value.is_empty() {
+            obj.insert("value".into(), Value::from(node.value.as_str()));
+        }
+        if !node.url.is_empty() {
+            obj.insert("url".into(), Value::from(node.url.as_str()));
+        }
+        if let Some(ref ci) = node.cursor_info {
+            obj.insert("cursor".into(), Value::from(ci.kind.as_str()));
+        }
+        let mut children = Vec::new();
+        while *i < nodes.len() && nodes[*i].depth > node.depth {
+            children.push(build(nodes, i));
+        }
+        if !children.is_empty() {
+            obj.insert("children".into(), Value::Array(children));
+        }
+        Value::Object(obj)
+    }
+
+    let mut roots = Vec::new();
+    let mut i = 0;
+    while i < nodes.len() {
+        roots.push(build(nodes, &mut i));
+    }
+    serde_json::to_string_pretty(&roots).unwrap_or_default()
+}
+
+/// URLs longer than this are dropped from compact output.
+const COMPACT_MAX_URL_LEN: usize = 100;
+/// Runs of at least this many similar siblings are folded.
+const COMPACT_FOLD_MIN_RUN: usize = 4;
+/// Siblings kept verbatim at the head of a folded run.
+const COMPACT_FOLD_KEEP: usize = 2;
+
+/// Token-optimised rewrite of a flat node list, rendered with `render_yaml`:
+///
+/// - unnamed, unreferenced structural wrappers are removed and their children
+///   promoted one level;
+/// - runs of [`COMPACT_FOLD_MIN_RUN`]+ siblings with the same subtree shape
+///   keep the first [`COMPACT_FOLD_KEEP`] and fold the rest into a single
+///   `- ×N similar items` line;
+/// - URLs longer than [`COMPACT_MAX_URL_LEN`] are dropped.
+///
+/// Folded siblings keep their refs in the RefCache — they are only hidden
+/// from the rendered text.
+pub fn compact_for_tokens(nodes: &[AXNode]) -> Vec<AXNode> {
+    fold_runs(&collapse_wrappers(nodes))
+}
+
+/// Drop unnamed structural wrappers, shifting their descendants up.
+fn collapse_wrappers(nodes: &[AXNode]) -> Vec<AXNode> {
+    let mut out: Vec<AXNode> = Vec::with_capacity(nodes.len());
+    // Depths of removed ancestors of the current node.
+    let mut removed: Vec<usize> = Vec::new();
+    for node in nodes {
+        while removed.last().is_some_and(|&d| d >= node.depth) {
+            removed.pop();
+        }
+        let is_wrapper = is_structural_role(&node.role)
+            && node.ref_id.is_empty()
+            && node.name.is_empty()
+            && node.value.is_empty()
+            && node.cursor_info.is_none();
+        if is_wrapper {
+            removed.push(node.depth);
+            continue;
+        }
+        let mut n = node.clone();
+        n.depth -= removed.len();
+        if n.url.len() > COMPACT_MAX_URL_LEN {
+            n.url.clear();
+        }
+        out.push(n);
+    }
+    out
+}
+
+/// Fold runs of similar siblings in a flat node list.
+fn fold_runs(nodes: &[AXNode]) -> Vec<AXNode> {
+    // Subtree end (exclusive) for each node.
+    let len = nodes.len();
+    let mut end = vec![len; len];
+    let mut open: Vec<usize> = Vec::new();
+    for (i, node) in nodes.iter().enumerate() {
+        while open
+            .last()
+            .is_some_and(|&top| nodes[top].depth >= node.depth)
+        {
+            end[open.pop().unwrap_or_default()] = i;
+        }
+        open.push(i);
+    }
+    // Roles by relative depth; names are ignored so list rows match.
+    let shape = |i: usize| -> Vec<(usize, &str)> {
+        nodes[i..end[i]]
+            .iter()
+            .take(16)
+            .map(|n| (n.depth - nodes[i].depth, n.role.as_str()))
+            .collect()
+    };
+
+    let mut out: Vec<AXNode> = Vec::with_capacity(len);
+    let mut i = 0;
+    while i < len {
+        let depth = nodes[i].depth;
+        let first = shape(i);
+        let mut run = vec![i];
+        let mut next = end[i];
+        while next < len && nodes[next].depth == depth && shape(next) == first {
+            run.push(next);
+            next = end[next];
+        }
+        if run.len() < COMPACT_FOLD_MIN_RUN {
+            // Children follow as the next entries and are checked in turn.
+            out.push(nodes[i].clone());
+            i += 1;
+            continue;
+        }
+        for &start in &run[..COMPACT_FOLD_KEEP] {
+            out.push(nodes[start].clone());
+            out.extend(fold_runs(&nodes[start + 1..end[start]]));
+        }
+        out.push(AXNode {
+            ref_id: String::new(),
+            role: format!("×{} similar items", run.len() - COMPACT_FOLD_KEEP),
+            name: String::new(),
+            value: String::new(),
+            url: String::new(),
+            interactive: false,
+            depth,
+            children: Vec::new(),
+            cursor_info: None,
+        });
+        i = next;
+    }
+    out
+}
+
 /// Build the full SnapshotOutput from a flat node list.
 /// `data.nodes` only contains nodes that have a ref (interactive + named content).
 pub fn build_output(nodes: Vec<AXNode>) -> SnapshotOutput {
-    let content = render_yaml(&nodes);
+    build_output_as(nodes, SnapshotFormat::Yaml)
+}
+
+/// [`build_output`] with an explicit file format. For `Compact`, `data.nodes`
+/// and the stats only cover the nodes left in the rendered text.
+pub fn build_output_as(nodes: Vec<AXNode>, format: SnapshotFormat) -> SnapshotOutput {
+    let nodes = match format {
+        SnapshotFormat::Compact => compact_for_tokens(&nodes),
+        _ => nodes,
+    };
+    let content = match format {
+        SnapshotFormat::Text => render_content(&nodes),
+        SnapshotFormat::Json => render_json(&nodes),
+        SnapshotFormat::Yaml | SnapshotFormat::Compact => render_yaml(&nodes),
+    };
     // Stats count all nodes with refs
     let ref_nodes: Vec<&AXNode> = nodes.iter().filter(|n| !n.ref_id.is_empty()).collect();
     let node_count = ref_nodes.len();
@@ -803,6 +1184,26 @@ impl RefCache {
             .and_then(|key| self.id_to_ref.get(key))
     }
 
+    /// Point `ref_id` at a new backendNodeId in the same frame, after the
+    /// element it named was replaced by an identical one. If the new node
+    /// already has a ref of its own, both refs resolve to it.
+    pub fn rebind(&mut self, ref_id: &str, backend_node_id: i64) {
+        let Some(old_key) = self.ref_to_id.get(ref_id).cloned() else {
+            return;
+        };
+        let new_key: RefKey = (old_key.0.clone(), backend_node_id);
+        if new_key == old_key {
+            return;
+        }
+        let entry = self.id_to_ref.remove(&old_key);
+        if !self.id_to_ref.contains_key(&new_key)
+            && let Some(entry) = entry
+        {
+            self.id_to_ref.insert(new_key.clone(), entry);
+        }
+        self.ref_to_id.insert(ref_id.to_string(), new_key);
+    }
+
     /// Remap refs that were parsed as main-frame (frame_id=None) to a
     /// specific frame_id.  This happens when Chrome's AX tree includes
     /// iframe content inline (e.g., closed shadow root iframes) but we
@@ -2220,6 +2621,27 @@ mod tests {
         assert_eq!(entry.name, "New");
     }
 
+    #[test]
+    fn test_ref_cache_rebind_follows_a_replaced_node() {
+        let mut cache = RefCache::new();
+        cache.get_or_assign(42, "button", "Save", Some("F1"));
+        cache.get_or_assign(55, "link", "Home", Some("F1"));
+
+        cache.rebind("e1", 77);
+        assert_eq!(cache.backend_node_id_for_ref("e1"), Some(77));
+        assert_eq!(cache.frame_id_for_ref("e1"), Some("F1"));
+        assert_eq!(cache.entry_for_ref("e1").unwrap().name, "Save");
+        // The next snapshot sees the new node under the same ref.
+        assert_eq!(cache.get_or_assign(77, "button", "Save", Some("F1")), "e1");
+
+        // Rebinding onto a node that already has a ref keeps that ref too.
+        cache.rebind("e1", 55);
+        assert_eq!(cache.backend_node_id_for_ref("e1"), Some(55));
+        assert_eq!(cache.get_or_assign(55, "link", "Home", Some("F1")), "e2");
+        cache.rebind("e9", 1);
+        assert_eq!(cache.backend_node_id_for_ref("e9"), None);
+    }
+
     #[test]
     fn test_ref_cache_entries() {
         let mut cache = RefCache::new();
@@ -2518,4 +2940,142 @@ mod tests {
             "content must show cursor hints: {content}"
         );
     }
+
+    // ── snapshot formats ─────────────────────────────────────────────
+
+    #[test]
+    fn test_render_json_nests_children() {
+        let mut link = make_node("e2", "link", "Home", true, 1);
+        link.url = "https://example.com/".to_string();
+        let nodes = vec![
+            make_node("", "navigation", "", false, 0),
+            link,
+            make_node("e3", "button", "Go", true, 0),
+        ];
+        let v: Value = serde_json::from_str(&render_json(&nodes)).unwrap();
+        assert_eq!(v.as_array().unwrap().len(), 2);
+        assert_eq!(v[0]["role"], "navigation");
+        assert!(v[0].get("ref").is_none());
+        assert_eq!(v[0]["children"][0]["ref"], "e2");
+        assert_eq!(v[0]["children"][0]["url"], "https://example.com/");
+        assert_eq!(v[1]["name"], "Go");
+    }
+
+    #[test]
+    fn test_compact_collapses_wrappers_and_folds_siblings() {
+        let mut nodes = vec![
+            make_node("", "generic", "", false, 0),
+            make_node("", "list", "", false, 1),
+        ];
+        for i in 0..6 {
+            nodes.push(make_node("", "listitem", "", false, 2));
+            nodes.push(make_node(
+                &format!("e{}", i + 1),
+                "link",
+                &format!("Item {i}"),
+                true,
+                3,
+            ));
+        }
+        let mut long = make_node("e9", "link", "Long", true, 1);
+        long.url = format!("https://example.com/{}", "x".repeat(200));
+        nodes.push(long);
+
+        let out = compact_for_tokens(&nodes);
+        let rendered = render_yaml(&out);
+        assert_eq!(
+            rendered,
+            "- listitem:\n  - link \"Item 0\" [ref=e1]\n- listitem:\n  - link \"Item 1\" [ref=e2]\n- ×4 similar items\n- link \"Long\" [ref=e9]"
+        );
+    }
+
+    #[test]
+    fn test_compact_keeps_short_runs_and_named_wrappers() {
+        let nodes = vec![
+            make_node("", "group", "Filters", false, 0),
+            make_node("e1", "checkbox", "A", true, 1),
+            make_node("e2", "checkbox", "B", true, 1),
+            make_node("e3", "checkbox", "C", true, 1),
+        ];
+        assert_eq!(compact_for_tokens(&nodes), nodes);
+    }
+
+    #[test]
+    fn test_build_output_as_compact_counts_visible_refs() {
+        let mut nodes = vec![make_node("", "list", "", false, 0)];
+        for i in 0..5 {
+            nodes.push(make_node(&format!("e{i}"), "button", "x", true, 1));
+        }
+        let out = build_output_as(nodes, SnapshotFormat::Compact);
+        assert_eq!(out.node_count, 2);
+        assert!(out.content.ends_with("- ×3 similar items"));
+    }
+
+    #[test]
+    fn test_cursor_elements_from_dom_snapshot_matches_the_script_rules() {
+        let strings = serde_json::json!([
+            "#document",
+            "HTML",
+            "BODY",
+            "DIV",
+            "SPAN",
+            "BUTTON",
+            "pointer",
+            "auto",
+            "tabindex",
+            "0",
+            "hidden",
+            "",
+            "onclick",
+            "go()",
+            "contenteditable",
+            "role",
+            "button"
+        ]);
+        let snapshot = serde_json::json!({
+            "strings": strings,
+            "documents": [{
+                "nodes": {
+                    "parentIndex": [-1, 0, 1, 2, 3, 2, 2, 2, 2, 2, 2],
+                    "nodeType": [9, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1],
+                    "nodeName": [0, 1, 2, 3, 4, 5, 3, 3, 3, 3, 3],
+                    "backendNodeId": [1, 2, 3, 13, 14, 15, 16, 17, 18, 19, 20],
+                    "attributes": [
+                        [], [], [], [], [], [],
+                        [8, 9],
+                        [10, 11, 12, 13],
+                        [14, 11],
+                        [12, 13],
+                        [15, 16]
+                    ],
+                },
+                "layout": {
+                    "nodeIndex": [2, 3, 4, 5, 6, 7, 8, 9, 10],
+                    "styles": [[7], [6], [6], [6], [7], [7], [7], [7], [6]],
+                    "bounds": [
+                        [0, 0, 800, 600], [0, 0, 100, 50], [0, 0, 20, 10], [0, 60, 80, 30],
+                        [0, 100, 80, 30], [0, 140, 80, 30], [0, 180, 80, 30], [0, 0, 0, 0],
+                        [0, 220, 80, 30]
+                    ],
+                },
+            }],
+        });
+
+        let found = cursor_elements_from_dom_snapshot(&snapshot);
+
+        let mut ids: Vec<i64> = found.keys().copied().collect();
+        ids.sort();
+        // 14 inherits its parent's pointer, 15 is a <button>, 17 is hidden,
+        // 19 has no box and 20 has an interactive role.
+        assert_eq!(ids, vec![13, 16, 18]);
+        assert_eq!(
+            found[&13],
+            CursorInfo::from_signals(true, false, false, false)
+        );
+        assert_eq!(found[&13].kind, "clickable");
+        assert_eq!(found[&16].kind, "focusable");
+        assert_eq!(found[&16].hints, vec!["tabindex"]);
+        assert_eq!(found[&18].kind, "editable");
+        assert!(cursor_elements_from_dom_snapshot(&serde_json::json!({})).is_empty());
+    }
 }
@@ -0,0 +1,618 @@
+use std::collections::{HashMap, HashSet};
+
+use clap::Args;
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value, json};
+
+use crate::action_result::ActionResult;
+use crate::browser::{element::TabContext, navigation};
+use crate::daemon::cdp_session::cdp_error_to_result;
+use crate::daemon::registry::SharedRegistry;
+use crate::output::ResponseContext;
+use crate::utils::parse::{self, ParseRule};
+use crate::utils::sink::{self, Frame, Sink, to_csv};
+use crate::utils::url;
+
+/// Extract a <table> as CSV or JSON
+#[derive(Args, Debug, Clone, Serialize, Deserialize)]
+#[command(after_help = "\
+Examples:
+  actionbook browser table \"#prices\" --session s1 --tab t1
+  actionbook browser table \"table.results\" --format json --session s1 --tab t1
+  actionbook browser table @e12 --out prices.csv --session s1 --tab t1
+  actionbook browser table \"#prices\" --format json --parse Price=currency:de-DE \\
+    --parse Date=date:%d.%m.%Y --session s1 --tab t1
+  actionbook browser table \"#results\" --normalize-urls --dedupe-by Link --session s1 --tab t1
+
+The selector (CSS, XPath, @ref, text= or role=) names the table, or an
+element containing it. Header rows come from <thead>, or from a first row of
+<th> cells; columns without one are named column_N. Cells spanning several
+rows or columns repeat their text in each position they cover, so every row
+has one value per column. Rows of nested tables are not included.
+
+--format json lists rows as objects keyed by header. Links inside cells are
+listed apart, with the row, column, text and href of each.
+
+--parse [COLUMN=]TYPE turns cell text into typed values. TYPE is number or
+number:LOCALE (\"1.234,56\" with de-DE → 1234.56), currency or currency:LOCALE
+({\"amount\": 1234.56, \"currency\": \"EUR\"}; CSV gets the amount), or
+date:FORMAT (strftime %Y %y %m %d %H %M %S %b %B %p → ISO 8601). Without a
+locale the decimal separator is inferred per value. A rule naming a column
+reports the cells it could not parse under parse_failures and leaves them as
+text; a rule without one applies to every cell that parses.
+
+--normalize-urls rewrites link hrefs and cells holding an http(s) URL to a
+canonical form: lowercase host, no default port, fragment or tracking
+parameters (utm_*, gclid, fbclid, …). --dedupe-by COLUMN keeps the first row
+for each value of the column (repeat it for a combined key; rows with an
+empty key are always kept); --unique drops rows identical to an earlier one.
+Both compare values after --parse and --normalize-urls.
+
+--sink KIND:PATH writes the rows into analyzable storage instead of the
+response, which then only reports the inferred schema and row count:
+sqlite:FILE[?table=NAME] appends to a table (default items), creating it or
+adding missing columns; csv:FILE appends under one header; parquet:FILE
+replaces the file. Add ?mode=replace to start a SQLite table or CSV afresh.
+Column types (integer, real, boolean, text) are inferred from the values, so
+combine it with --parse; currency cells add a COLUMN_currency column. The
+sqlite and parquet sinks are build features: install with
+`--features sink-sqlite,sink-parquet` to use them.")]
+pub struct Cmd {
+    /// The table, or an element containing it
+    pub selector: String,
+    /// Output format
+    #[arg(long, default_value = "csv", value_parser = ["csv", "json"])]
+    #[serde(default = "default_format")]
+    pub format: String,
+    /// Also write the output to this file
+    #[arg(long, value_name = "FILE")]
+    #[serde(default)]
+    pub out: Option<String>,
+    /// Parse cells into typed values: [COLUMN=]number[:LOCALE], currency[:LOCALE] or date:FORMAT (repeatable)
+    #[arg(long = "parse", value_name = "[COLUMN=]TYPE", value_parser = parse::parse_rule_arg)]
+    #[serde(default)]
+    pub parse: Vec<String>,
+    /// Canonicalize URLs in cells and links and strip tracking parameters
+    #[arg(long)]
+    #[serde(default)]
+    pub normalize_urls: bool,
+    /// Keep only the first row per value of this column (repeatable)
+    #[arg(long, value_name = "COLUMN")]
+    #[serde(default)]
+    pub dedupe_by: Vec<String>,
+    /// Drop rows identical to an earlier row
+    #[arg(long)]
+    #[serde(default)]
+    pub unique: bool,
+    /// Write the rows to sqlite:FILE[?table=NAME], csv:FILE or parquet:FILE
+    #[arg(long, value_name = "KIND:PATH", value_parser = sink::parse_sink_arg)]
+    #[serde(default)]
+    pub sink: Option<String>,
+    /// Session ID
+    #[arg(long)]
+    #[serde(rename = "session_id")]
+    pub session: String,
+    /// Tab ID
+    #[arg(long)]
+    #[serde(rename = "tab_id")]
+    pub tab: String,
+}
+
+fn default_format() -> String {
+    "csv".to_string()
+}
+
+pub const COMMAND_NAME: &str = "browser table";
+
+/// Called with `this` = the selected element. Returns `null` when it neither
+/// is nor contains a table, else `{ header_rows, rows, links }` with spans
+/// already expanded into a rectangular grid of cell text.
+const TABLE_JS: &str = r#"function() {
+    const table = this.tagName === 'TABLE' ? this : this.querySelector('table');
+    if (!table) return null;
+    const clean = (s) => (s || '').replace(/\s+/g, ' ').trim();
+    // table.rows and row.cells skip nested tables.
+    const rows = Array.from(table.rows);
+    let headerCount = table.tHead ? table.tHead.rows.length : 0;
+    if (headerCount === 0 && rows.length > 0 && rows[0].cells.length > 0
+        && Array.from(rows[0].cells).every(c => c.tagName === 'TH')) {
+        headerCount = 1;
+    }
+    // thead rows come first in table.rows regardless of source order.
+    const grid = [];
+    const links = [];
+    rows.forEach((row, r) => {
+        grid[r] = grid[r] || [];
+        let col = 0;
+        for (const cell of row.cells) {
+            while (grid[r][col] !== undefined) col++;
+            const text = clean(cell.innerText);
+            const rowSpan = Math.max(1, cell.rowSpan || 1);
+            const colSpan = Math.max(1, cell.colSpan || 1);
+            for (let dr = 0; dr < rowSpan && r + dr < rows.length; dr++) {
+                grid[r + dr] = grid[r + dr] || [];
+                for (let dc = 0; dc < colSpan; dc++) grid[r + dr][col + dc] = text;
+            }
+            for (const a of cell.querySelectorAll('a[href]')) {
+                if (a.closest('table') !== table) continue;
+                links.push({ row: r, column: col, text: clean(a.innerText), href: a.href });
+            }
+            col += colSpan;
+        }
+    });
+    const width = grid.reduce((w, row) => Math.max(w, row.length), 0);
+    const filled = grid.map(row => Array.from({ length: width }, (_, i) => row[i] ?? ''));
+    return {
+        header_rows: filled.slice(0, headerCount),
+        rows: filled.slice(headerCount),
+        links: links.filter(l => l.row >= headerCount).map(l => ({ ...l, row: l.row - headerCount })),
+    };
+}"#;
+
+pub fn context(cmd: &Cmd, result: &ActionResult) -> Option<ResponseContext> {
+    if let ActionResult::Fatal { code, .. } = result
+        && code == "SESSION_NOT_FOUND"
+    {
+        return None;
+    }
+    let tab_id = if let ActionResult::Fatal { code, .. } = result
+        && code == "TAB_NOT_FOUND"
+    {
+        None
+    } else {
+        Some(cmd.tab.clone())
+    };
+    let (url, title) = match result {
+        ActionResult::Ok { data } => (
+            data.get("__ctx_url")
+                .and_then(|v| v.as_str())
+                .map(String::from),
+            data.get("__ctx_title")
+                .and_then(|v| v.as_str())
+                .map(String::from),
+        ),
+        _ => (None, None),
+    };
+    Some(ResponseContext {
+        session_id: cmd.session.clone(),
+        tab_id,
+        window_id: None,
+        url,
+        title,
+    })
+}
+
+/// One name per column: header rows joined with " / " (a spanning header
+/// is not repeated), `column_N` when empty, and `_2`, `_3`… on duplicates.
+fn column_names(header_rows: &[Vec<String>], width: usize) -> Vec<String> {
+    let mut seen: HashMap<String, usize> = HashMap::new();
+    (0..width)
+        .map(|i| {
+            let mut parts: Vec<&str> = Vec::new();
+            for row in header_rows {
+                if let Some(text) = row.get(i).map(String::as_str)
+                    && !text.is_empty()
+                    && parts.last() != Some(&text)
+                {
+                    parts.push(text);
+                }
+            }
+            let base = if parts.is_empty() {
+                format!("column_{}", i + 1)
+            } else {
+                parts.join(" / ")
+            };
+            let n = seen.entry(base.clone()).or_insert(0);
+            *n += 1;
+            if *n == 1 { base } else { format!("{base}_{n}") }
+        })
+        .collect()
+}
+
+/// The CSV text of a typed cell: currency keeps only its amount.
+fn cell_text(v: &Value) -> String {
+    match v {
+        Value::String(s) => s.clone(),
+        Value::Null => String::new(),
+        Value::Object(o) => o.get("amount").map(cell_text).unwrap_or_default(),
+        other => other.to_string(),
+    }
+}
+
+/// Apply `--parse` rules to the grid. Returns the typed rows and, for rules
+/// naming a column, the non-empty cells that did not parse.
+fn typed_rows(
+    headers: &[String],
+    rows: &[Vec<String>],
+    rules: &[ParseRule],
+) -> Result<(Vec<Vec<Value>>, Vec<Value>), String> {
+    for rule in rules {
+        if let Some(column) = &rule.column
+            && !headers.contains(column)
+        {
+            return Err(format!(
+                "--parse names column '{column}', but the table has {}",
+                headers.join(", ")
+            ));
+        }
+    }
+    let global: Vec<&ParseRule> = rules.iter().filter(|r| r.column.is_none()).collect();
+    let mut failures = Vec::new();
+    let typed = rows
+        .iter()
+        .enumerate()
+        .map(|(r, row)| {
+            row.iter()
+                .enumerate()
+                .map(|(c, text)| {
+                    let header = &headers[c];
+                    if let Some(rule) = rules.iter().find(|r| r.column.as_ref() == Some(header)) {
+                        if text.is_empty() {
+                            return Value::Null;
+                        }
+                        return rule.parser.parse(text).unwrap_or_else(|| {
+                            failures.push(json!({ "row": r, "column": header, "text": text }));
+                            json!(text)
+                        });
+                    }
+                    global
+                        .iter()
+                        .find_map(|rule| rule.parser.parse(text))
+                        .unwrap_or_else(|| json!(text))
+                })
+                .collect()
+        })
+        .collect();
+    Ok((typed, failures))
+}
+
+/// Drop duplicate rows in place: by the `dedupe_by` columns (rows whose key
+/// cells are all empty are kept) and, with `unique`, whole-row repeats.
+/// Returns each original row's new index, `None` for removed rows.
+fn dedupe_rows(
+    headers: &[String],
+    rows: &mut Vec<Vec<Value>>,
+    dedupe_by: &[String],
+    unique: bool,
+) -> Result<Vec<Option<usize>>, String> {
+    let key_columns = dedupe_by
+        .iter()
+        .map(|name| {
+            headers.iter().position(|h| h == name).ok_or_else(|| {
+                format!(
+                    "--dedupe-by names column '{name}', but the table has {}",
+                    headers.join(", ")
+                )
+            })
+        })
+        .collect::<Result<Vec<usize>, String>>()?;
+    let is_empty = |v: &Value| v.is_null() || v.as_str() == Some("");
+    let mut seen_keys = HashSet::new();
+    let mut seen_rows = HashSet::new();
+    let mut mapping = Vec::with_capacity(rows.len());
+    let mut kept = Vec::with_capacity(rows.len());
+    for row in rows.drain(..) {
+        let key: Vec<&Value> = key_columns.iter().map(|&c| &row[c]).collect();
+        let duplicate_key = !key_columns.is_empty()
+            && !key.iter().all(|v| is_empty(v))
+            && !seen_keys.insert(json!(key).to_string());
+        let duplicate_row = unique && !seen_rows.insert(json!(row).to_string());
+        if duplicate_key || duplicate_row {
+            mapping.push(None);
+        } else {
+            mapping.push(Some(kept.len()));
+            kept.push(row);
+        }
+    }
+    *rows = kept;
+    Ok(mapping)
+}
+
+/// Re-point `{ "row": N, … }` entries at deduplicated rows, dropping those
+/// whose row was removed.
+fn remap_rows(entries: Vec<Value>, mapping: &[Option<usize>]) -> Vec<Value> {
+    entries
+        .into_iter()
+        .filter_map(|mut entry| {
+            let old = entry["row"].as_u64()? as usize;
+            entry["row"] = json!((*mapping.get(old)?)?);
+            Some(entry)
+        })
+        .collect()
+}
+
+fn string_rows(v: &Value) -> Vec<Vec<String>> {
+    v.as_array()
+        .into_iter()
+        .flatten()
+        .map(|row| {
+            row.as_array()
+                .into_iter()
+                .flatten()
+                .map(|c| c.as_str().unwrap_or_default().to_string())
+                .collect()
+        })
+        .collect()
+}
+
+pub async fn execute(cmd: &Cmd, registry: &SharedRegistry) -> ActionResult {
+    let mut ctx = match TabContext::new(registry, &cmd.session, &cmd.tab).await {
+        Ok(v) => v,
+        Err(e) => return e,
+    };
+    let (_, object_id) = match ctx.resolve_object(&cmd.selector).await {
+        Ok(v) => v,
+        Err(e) => return e,
+    };
+    let resp = match ctx
+        .execute_on_element(
+            "Runtime.callFunctionOn",
+            json!({
+                "objectId": object_id,
+                "functionDeclaration": TABLE_JS,
+                "returnByValue": true,
+            }),
+        )
+        .await
+    {
+        Ok(v) => v,
+        Err(e) => return cdp_error_to_result(e, "CDP_ERROR"),
+    };
+    let table = resp
+        .pointer("/result/result/value")
+        .cloned()
+        .unwrap_or(Value::Null);
+    if !table.is_object() {
+        return ActionResult::fatal_with_hint(
+            "ELEMENT_NOT_FOUND",
+            format!("'{}' is not a table and contains none", cmd.selector),
+            "select the <table> element or one of its ancestors",
+        );
+    }
+
+    let header_rows = string_rows(&table["header_rows"]);
+    let rows = string_rows(&table["rows"]);
+    let width = header_rows
+        .iter()
+        .chain(&rows)
+        .map(Vec::len)
+        .max()
+        .unwrap_or(0);
+    let headers = column_names(&header_rows, width);
+    let rules: Vec<ParseRule> = match cmd.parse.iter().map(|r| r.parse()).collect() {
+        Ok(rules) => rules,
+        Err(e) => return ActionResult::fatal("INVALID_ARGUMENT", e),
+    };
+    let (mut typed, parse_failures) = match typed_rows(&headers, &rows, &rules) {
+        Ok(v) => v,
+        Err(e) => {
+            return ActionResult::fatal_with_hint(
+                "INVALID_ARGUMENT",
+                e,
+                "name a column by its header as listed, or omit it to parse every cell",
+            );
+        }
+    };
+    if cmd.normalize_urls {
+        for cell in typed.iter_mut().flatten() {
+            if let Value::String(s) = cell
+                && let Some(normalized) = url::normalize_url(s)
+            {
+                *s = normalized;
+            }
+        }
+    }
+    let mapping = match dedupe_rows(&headers, &mut typed, &cmd.dedupe_by, cmd.unique) {
+        Ok(v) => v,
+        Err(e) => {
+            return ActionResult::fatal_with_hint(
+                "INVALID_ARGUMENT",
+                e,
+                "name a column by its header as listed",
+            );
+        }
+    };
+    let links: Vec<Value> = table["links"]
+        .as_array()
+        .into_iter()
+        .flatten()
+        .map(|l| {
+            let column = l["column"].as_u64().unwrap_or(0) as usize;
+            let href = match l["href"].as_str() {
+                Some(href) if cmd.normalize_urls => {
+                    json!(url::normalize_url(href).unwrap_or_else(|| href.to_string()))
+                }
+                _ => l["href"].clone(),
+            };
+            json!({
+                "row": l["row"],
+                "column": headers.get(column),
+                "text": l["text"],
+                "href": href,
+            })
+        })
+        .collect();
+    let links = remap_rows(links, &mapping);
+    let parse_failures = remap_rows(parse_failures, &mapping);
+    let row_count = typed.len();
+
+    let sink_report = match cmd.sink.as_deref().map(str::parse::<Sink>) {
+        None => None,
+        Some(Err(e)) => return ActionResult::fatal("INVALID_ARGUMENT", e),
+        Some(Ok(target)) if !target.kind.available() => {
+            return ActionResult::fatal_with_hint(
+                "UNSUPPORTED_OPERATION",
+                format!("this build cannot write {} sinks", target.kind.as_str()),
+                format!(
+                    "rebuild actionbook with `--features sink-{}`",
+                    target.kind.as_str()
+                ),
+            );
+        }
+        Some(Ok(target)) => {
+            let frame = Frame::infer(&headers, &typed);
+            let written = tokio::task::spawn_blocking(move || sink::write(&target, &frame)).await;
+            match written {
+                Ok(Ok(report)) => Some(report),
+                Ok(Err(e)) => return ActionResult::fatal("IO_ERROR", e),
+                Err(e) => return ActionResult::fatal("INTERNAL_ERROR", e.to_string()),
+            }
+        }
+    };
+
+    let (value, mut data) = if cmd.format == "json" {
+        let objects: Vec<Value> = typed
+            .into_iter()
+            .map(|row| {
+                headers
+                    .iter()
+                    .cloned()
+                    .zip(row)
+                    .collect::<Map<_, _>>()
+                    .into()
+            })
+            .collect();
+        (
+            serde_json::to_string_pretty(&objects).unwrap_or_default(),
+            json!({ "rows": objects }),
+        )
+    } else {
+        let text_rows: Vec<Vec<String>> = typed
+            .iter()
+            .map(|row| row.iter().map(cell_text).collect())
+            .collect();
+        let csv = to_csv(&headers, &text_rows);
+        (csv.clone(), json!({ "csv": csv }))
+    };
+
+    if let Some(out) = &cmd.out
+        && let Err(e) = std::fs::write(out, &value)
+    {
+        return ActionResult::fatal("IO_ERROR", format!("failed to write {out}: {e}"));
+    }
+
+    let url = navigation::get_tab_url(&ctx.cdp, &ctx.target_id).await;
+    let title = navigation::get_tab_title(&ctx.cdp, &ctx.target_id).await;
+    data["format"] = json!(cmd.format);
+    data["headers"] = json!(headers);
+    data["row_count"] = json!(row_count);
+    if !cmd.dedupe_by.is_empty() || cmd.unique {
+        data["duplicates_removed"] = json!(rows.len() - row_count);
+    }
+    data["column_count"] = json!(width);
+    data["links"] = json!(links);
+    if let Some(report) = sink_report {
+        // The rows went to the sink; keep the response small.
+        if let Some(obj) = data.as_object_mut() {
+            obj.remove("rows");
+            obj.remove("csv");
+            obj.remove("links");
+        }
+        data["sink"] = report;
+    }
+    data["path"] = json!(cmd.out);
+    if !rules.is_empty() {
+        data["parse_failures"] = json!(parse_failures);
+    }
+    data["__ctx_url"] = json!(url);
+    data["__ctx_title"] = json!(title);
+    ActionResult::ok(data)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rows(r: &[&[&str]]) -> Vec<Vec<String>> {
+        r.iter()
+            .map(|row| row.iter().map(|c| c.to_string()).collect())
+            .collect()
+    }
+
+    #[test]
+    fn column_names_join_header_rows_and_fill_gaps() {
+        // | Name | Price (colspan 2) |     |
+        // |      | Net     | Gross   |     |
+        let header = rows(&[
+            &["Name", "Price", "Price", ""],
+            &["Name", "Net", "Gross", ""],
+        ]);
+        assert_eq!(
+            column_names(&header, 5),
+            [
+                "Name",
+                "Price / Net",
+                "Price / Gross",
+                "column_4",
+                "column_5"
+            ]
+        );
+        assert_eq!(
+            column_names(&rows(&[&["A", "A", "B"]]), 3),
+            ["A", "A_2", "B"]
+        );
+    }
+
+    #[test]
+    fn typed_rows_apply_column_rules_before_global_ones() {
+        let headers = vec!["Item".to_string(), "Price".to_string(), "Qty".to_string()];
+        let grid = rows(&[&["Tea", "1.234,56 €", "3"], &["Cup", "n/a", ""]]);
+        let rules: Vec<ParseRule> = ["Price=currency:de-DE", "number"]
+            .iter()
+            .map(|r| r.parse().unwrap())
+            .collect();
+        let (typed, failures) = typed_rows(&headers, &grid, &rules).unwrap();
+        assert_eq!(
+            typed[0],
+            [
+                json!("Tea"),
+                json!({ "amount": 1234.56, "currency": "EUR" }),
+                json!(3)
+            ]
+        );
+        assert_eq!(typed[1], [json!("Cup"), json!("n/a"), json!("")]);
+        assert_eq!(
+            failures,
+            [json!({ "row": 1, "column": "Price", "text": "n/a" })]
+        );
+        assert_eq!(cell_text(&typed[0][1]), "1234.56");
+
+        let bad = ["Cost=number".parse().unwrap()];
+        assert!(typed_rows(&headers, &grid, &bad).is_err());
+    }
+
+    #[test]
+    fn dedupe_rows_by_key_and_whole_row() {
+        let headers = vec!["Name".to_string(), "Link".to_string()];
+        let mut grid = vec![
+            vec![json!("A"), json!("https://x.test/1")],
+            vec![json!("B"), json!("https://x.test/1")],
+            vec![json!("C"), json!("")],
+            vec![json!("D"), json!("")],
+            vec![json!("C"), json!("")],
+        ];
+        let mapping = dedupe_rows(&headers, &mut grid, &["Link".into()], true).unwrap();
+        assert_eq!(mapping, [Some(0), None, Some(1), Some(2), None]);
+        assert_eq!(grid.len(), 3);
+        assert_eq!(grid[2][0], "D");
+
+        let links = vec![json!({ "row": 1 }), json!({ "row": 3, "href": "h" })];
+        assert_eq!(
+            remap_rows(links, &mapping),
+            [json!({ "row": 2, "href": "h" })]
+        );
+        assert!(dedupe_rows(&headers, &mut grid, &["Url".into()], false).is_err());
+    }
+
+    #[test]
+    fn to_csv_quotes_fields_that_need_it() {
+        let csv = to_csv(
+            &["name".into(), "note".into()],
+            &rows(&[&["Ada", "says \"hi\", twice"], &["Bob", "line\nbreak"]]),
+        );
+        assert_eq!(
+            csv,
+            "name,note\r\nAda,\"says \"\"hi\"\", twice\"\r\nBob,\"line\nbreak\"\r\n"
+        );
+    }
+}
@@ -15,10 +15,16 @@ Examples:
   actionbook browser text --session s1 --tab t1
   actionbook browser text \"#article\" --session s1 --tab t1
   actionbook browser text @e3 --session s1 --tab t1
+  actionbook browser text --frame 1 --session s1 --tab t1
 
 Accepts a CSS selector, XPath, or snapshot ref (@eN from snapshot output).
 Without a selector, returns the full page innerText.
-With a selector, returns the innerText of the matched element.")]
+With a selector, returns the innerText of the matched element.
+
+--frame reads inside an iframe instead of the top document.
+On a tab showing Chrome's PDF viewer, returns the text of the PDF itself
+(fetched again with the tab's cookies). This needs a build with
+`--features pdf-text`.")]
 pub struct Cmd {
     /// Selector (CSS, XPath, or @ref). Omit to read the full page text.
     pub selector: Option<String>,
@@ -30,6 +36,11 @@ pub struct Cmd {
     #[arg(long)]
     #[serde(rename = "tab_id")]
     pub tab: String,
+    /// Look selectors up inside this iframe: its name, a URL substring, or
+    /// its position (1 = first frame)
+    #[arg(long)]
+    #[serde(default)]
+    pub frame: Option<String>,
 }
 
 pub const COMMAND_NAME: &str = "browser text";
@@ -72,6 +83,11 @@ pub async fn execute(cmd: &Cmd, registry: &SharedRegistry) -> ActionResult {
         Ok(v) => v,
         Err(e) => return e,
     };
+    if let Some(frame) = &cmd.frame
+        && let Err(e) = ctx.enter_frame(frame).await
+    {
+        return e;
+    }
 
     let value = match get_text(&mut ctx, cmd.selector.as_deref()).await {
         Ok(v) => v,
@@ -124,16 +140,11 @@ async fn get_text(ctx: &mut TabContext, selector: Option<&str>) -> Result<Value,
             }
         }
         None => {
+            if let Some(url) = pdf_viewer_url(ctx).await {
+                return pdf_text(ctx, &url).await.map(Value::String);
+            }
             let resp = ctx
-                .cdp
-                .execute_on_tab(
-                    &ctx.target_id,
-                    "Runtime.evaluate",
-                    json!({
-                        "expression": "document.body.innerText",
-                        "returnByValue": true,
-                    }),
-                )
+                .evaluate("document.body.innerText")
                 .await
                 .map_err(|e| cdp_error_to_result(e, "CDP_ERROR"))?;
 
@@ -144,3 +155,69 @@ async fn get_text(ctx: &mut TabContext, selector: Option<&str>) -> Result<Value,
         }
     }
 }
+
+/// URL of the PDF when the document is Chrome's PDF viewer, whose DOM holds
+/// only the viewer's embed and none of the document text.
+async fn pdf_viewer_url(ctx: &TabContext) -> Option<String> {
+    let resp = ctx
+        .evaluate("document.contentType === 'application/pdf' ? location.href : null")
+        .await
+        .ok()?;
+    resp.pointer("/result/result/value")
+        .and_then(|v| v.as_str())
+        .map(String::from)
+}
+
+#[cfg(feature = "pdf-text")]
+async fn pdf_text(ctx: &TabContext, url: &str) -> Result<String, ActionResult> {
+    let bytes = match url.strip_prefix("file://") {
+        Some(path) => {
+            let path = urlencoding::decode(path)
+                .map(|p| p.into_owned())
+                .unwrap_or_else(|_| path.to_string());
+            std::fs::read(&path).map_err(|e| {
+                ActionResult::fatal("IO_ERROR", format!("failed to read {path}: {e}"))
+            })?
+        }
+        None => navigation::load_resource(&ctx.cdp, &ctx.target_id, url).await?,
+    };
+    tokio::task::spawn_blocking(move || extract_pdf_text(&bytes))
+        .await
+        .map_err(|e| ActionResult::fatal("INTERNAL_ERROR", e.to_string()))?
+        .map_err(|e| {
+            ActionResult::fatal(
+                "PDF_UNREADABLE",
+                format!("cannot read text from {url}: {e}"),
+            )
+        })
+}
+
+#[cfg(not(feature = "pdf-text"))]
+async fn pdf_text(_ctx: &TabContext, url: &str) -> Result<String, ActionResult> {
+    Err(ActionResult::fatal_with_hint(
+        "UNSUPPORTED_OPERATION",
+        format!("{url} is a PDF and this build cannot read PDF text"),
+        "rebuild actionbook with `--features pdf-text`",
+    ))
+}
+
+#[cfg(feature = "pdf-text")]
+fn extract_pdf_text(bytes: &[u8]) -> Result<String, pdf_extract::OutputError> {
+    pdf_extract::extract_text_from_mem(bytes).map(|text| text.trim().to_string())
+}
+
+#[cfg(all(test, feature = "pdf-text"))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn extract_pdf_text_reads_page_text() {
+        let bytes = include_bytes!("../../../tests/fixtures/text.pdf");
+        assert_eq!(extract_pdf_text(bytes).unwrap(), "Actionbook PDF fixture");
+    }
+
+    #[test]
+    fn extract_pdf_text_rejects_non_pdf_bytes() {
+        assert!(extract_pdf_text(b"<html>not a pdf</html>").is_err());
+    }
+}
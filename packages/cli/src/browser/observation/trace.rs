@@ -0,0 +1,194 @@
+//! `browser trace start` / `browser trace stop` commands.
+//!
+//! Records a Chrome performance trace of the whole session, for finding out
+//! why an automated page is slow. The trace covers every tab, so these are
+//! session-level commands.
+
+use std::path::PathBuf;
+use std::time::Instant;
+
+use clap::Args;
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+
+use crate::action_result::ActionResult;
+use crate::daemon::cdp_session::CdpSession;
+use crate::daemon::registry::SharedRegistry;
+use crate::daemon::trace::{self, Trace};
+use crate::output::ResponseContext;
+
+// ── Start ─────────────────────────────────────────────────────────────────────
+
+/// Start a performance trace of the session
+#[derive(Args, Debug, Clone, Serialize, Deserialize)]
+#[command(after_help = "\
+Examples:
+  actionbook browser trace start --out trace.json --session s1
+  actionbook browser trace start --out trace.json --screenshots --session s1
+  actionbook browser trace start --out trace.json --categories \"-*,v8,blink\" --session s1
+  actionbook browser trace stop --session s1
+
+Records what every tab of the session does until `trace stop`, which writes
+a JSON trace that chrome://tracing, ui.perfetto.dev and the DevTools
+Performance panel open. By default the categories DevTools records are used;
+--categories replaces them (a leading - excludes one, -* everything not
+listed) and --screenshots adds a filmstrip.")]
+pub struct StartCmd {
+    /// Trace file to write on stop
+    #[arg(long)]
+    pub out: String,
+    /// Comma-separated trace categories instead of the DevTools set
+    #[arg(long)]
+    #[serde(default)]
+    pub categories: Option<String>,
+    /// Also capture screenshots as the page renders
+    #[arg(long)]
+    #[serde(default)]
+    pub screenshots: bool,
+    /// Session ID
+    #[arg(long)]
+    #[serde(rename = "session_id")]
+    pub session: String,
+}
+
+pub const START_COMMAND_NAME: &str = "browser trace start";
+
+fn session_context(session: &str, result: &ActionResult) -> Option<ResponseContext> {
+    if let ActionResult::Fatal { code, .. } = result
+        && code == "SESSION_NOT_FOUND"
+    {
+        return None;
+    }
+    Some(ResponseContext {
+        session_id: session.to_string(),
+        tab_id: None,
+        window_id: None,
+        url: None,
+        title: None,
+    })
+}
+
+pub fn start_context(cmd: &StartCmd, result: &ActionResult) -> Option<ResponseContext> {
+    session_context(&cmd.session, result)
+}
+
+async fn session_cdp(registry: &SharedRegistry, session: &str) -> Result<CdpSession, ActionResult> {
+    let reg = registry.lock().await;
+    let Some(entry) = reg.get(session) else {
+        return Err(ActionResult::fatal_with_hint(
+            "SESSION_NOT_FOUND",
+            format!("session '{session}' not found"),
+            "run `actionbook browser list-sessions` to see available sessions",
+        ));
+    };
+    entry.cdp.clone().ok_or_else(|| {
+        ActionResult::fatal(
+            "INTERNAL_ERROR",
+            format!("no CDP connection for session '{session}'"),
+        )
+    })
+}
+
+pub async fn execute_start(cmd: &StartCmd, registry: &SharedRegistry) -> ActionResult {
+    let cdp = match session_cdp(registry, &cmd.session).await {
+        Ok(c) => c,
+        Err(e) => return e,
+    };
+    let categories = trace::categories(cmd.categories.as_deref(), cmd.screenshots);
+    if categories.iter().all(|c| c.starts_with('-')) {
+        return ActionResult::fatal(
+            "INVALID_ARGUMENT",
+            "--categories includes no category to record",
+        );
+    }
+    let started = Trace {
+        out: PathBuf::from(&cmd.out),
+        started: Instant::now(),
+    };
+    if !cdp.start_trace(started) {
+        return ActionResult::fatal_with_hint(
+            "TRACE_ACTIVE",
+            format!("session '{}' is already being traced", cmd.session),
+            "run `actionbook browser trace stop` first",
+        );
+    }
+    if let Err(e) = cdp
+        .execute_browser("Tracing.start", trace::start_params(&categories))
+        .await
+    {
+        cdp.take_trace();
+        return ActionResult::fatal("CDP_ERROR", format!("failed to start tracing: {e}"));
+    }
+
+    ActionResult::ok(json!({
+        "tracing": true,
+        "out": cmd.out,
+        "categories": categories,
+    }))
+}
+
+// ── Stop ──────────────────────────────────────────────────────────────────────
+
+/// Stop the trace and write it out
+#[derive(Args, Debug, Clone, Serialize, Deserialize)]
+#[command(after_help = "\
+Examples:
+  actionbook browser trace stop --session s1
+  actionbook browser trace stop --out other.json --session s1
+
+Writes the trace to the --out given at start unless another is passed here.
+Large traces take a few seconds to flush.")]
+pub struct StopCmd {
+    /// Write the trace here instead
+    #[arg(long)]
+    #[serde(default)]
+    pub out: Option<String>,
+    /// Session ID
+    #[arg(long)]
+    #[serde(rename = "session_id")]
+    pub session: String,
+}
+
+pub const STOP_COMMAND_NAME: &str = "browser trace stop";
+
+pub fn stop_context(cmd: &StopCmd, result: &ActionResult) -> Option<ResponseContext> {
+    session_context(&cmd.session, result)
+}
+
+pub async fn execute_stop(cmd: &StopCmd, registry: &SharedRegistry) -> ActionResult {
+    let cdp = match session_cdp(registry, &cmd.session).await {
+        Ok(c) => c,
+        Err(e) => return e,
+    };
+    let Some(running) = cdp.take_trace() else {
+        return ActionResult::fatal_with_hint(
+            "TRACE_NOT_ACTIVE",
+            format!("session '{}' is not being traced", cmd.session),
+            "start one with `actionbook browser trace start --out trace.json`",
+        );
+    };
+    let out = cmd.out.as_ref().map(PathBuf::from).unwrap_or(running.out);
+    if let Some(parent) = out.parent()
+        && !parent.as_os_str().is_empty()
+        && let Err(e) = std::fs::create_dir_all(parent)
+    {
+        return ActionResult::fatal(
+            "IO_ERROR",
+            format!("failed to create {}: {e}", parent.display()),
+        );
+    }
+
+    let duration_ms = running.started.elapsed().as_millis() as u64;
+    match trace::finish(&cdp, &out).await {
+        Ok(bytes) => ActionResult::ok(json!({
+            "path": out.to_string_lossy(),
+            "bytes": bytes,
+            "duration_ms": duration_ms,
+        })),
+        Err(crate::error::CliError::Timeout) => ActionResult::fatal(
+            "TIMEOUT",
+            "Chrome did not finish writing the trace within 60s",
+        ),
+        Err(e) => ActionResult::fatal("CDP_ERROR", format!("failed to stop tracing: {e}")),
+    }
+}
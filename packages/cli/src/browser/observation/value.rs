@@ -3,7 +3,7 @@ use serde::{Deserialize, Serialize};
 use serde_json::{Value, json};
 
 use crate::action_result::ActionResult;
-use crate::browser::{element::TabContext, navigation};
+use crate::browser::{element::TabContext, navigation, redact};
 use crate::daemon::cdp_session::cdp_error_to_result;
 use crate::daemon::registry::SharedRegistry;
 use crate::output::ResponseContext;
@@ -16,7 +16,8 @@ Examples:
   actionbook browser value @e4 --session s1 --tab t1
 
 Accepts a CSS selector, XPath, or snapshot ref (@eN from snapshot output).
-Returns the current value of an input, textarea, or select element.")]
+Returns the current value of an input, textarea, or select element.
+Password and credential fields are masked unless --show-secrets is passed.")]
 pub struct Cmd {
     /// Selector (CSS, XPath, or @ref)
     pub selector: String,
@@ -28,6 +29,10 @@ pub struct Cmd {
     #[arg(long)]
     #[serde(rename = "tab_id")]
     pub tab: String,
+    /// Show the value of password/credential fields instead of masking it
+    #[arg(long)]
+    #[serde(default)]
+    pub show_secrets: bool,
 }
 
 pub const COMMAND_NAME: &str = "browser value";
@@ -71,37 +76,53 @@ pub async fn execute(cmd: &Cmd, registry: &SharedRegistry) -> ActionResult {
         Err(e) => return e,
     };
 
-    let value = match get_value(&mut ctx, &cmd.selector).await {
+    let (value, secret) = match get_value(&mut ctx, &cmd.selector).await {
         Ok(v) => v,
         Err(e) => return e,
     };
     let url = navigation::get_tab_url(&ctx.cdp, &ctx.target_id).await;
     let title = navigation::get_tab_title(&ctx.cdp, &ctx.target_id).await;
 
-    ActionResult::ok(json!({
+    let mut data = json!({
         "target": { "selector": cmd.selector },
         "value": value,
         "__ctx_url": url,
         "__ctx_title": title,
-    }))
+    });
+    if secret && !cmd.show_secrets {
+        data["value"] = json!(redact::REDACTED);
+        data["redacted"] = json!(true);
+    }
+    ActionResult::ok(data)
 }
 
-async fn get_value(ctx: &mut TabContext, selector: &str) -> Result<Value, ActionResult> {
+/// Read `(value, is_secret_field)` for the element.
+async fn get_value(ctx: &mut TabContext, selector: &str) -> Result<(Value, bool), ActionResult> {
     let (_, object_id) = ctx.resolve_object(selector).await?;
+    let function = format!(
+        "function() {{ return {{ value: this.value, secret: ({}).call(this) }}; }}",
+        redact::IS_SECRET_FIELD_FN
+    );
     let resp = ctx
         .execute_on_element(
             "Runtime.callFunctionOn",
             json!({
                 "objectId": object_id,
-                "functionDeclaration": r#"function() { return this.value; }"#,
+                "functionDeclaration": function,
                 "returnByValue": true,
             }),
         )
         .await
         .map_err(|e| cdp_error_to_result(e, "CDP_ERROR"))?;
 
-    Ok(resp
-        .pointer("/result/result/value")
+    let result = resp.pointer("/result/result/value");
+    let value = result
+        .and_then(|v| v.get("value"))
         .cloned()
-        .unwrap_or(Value::Null))
+        .unwrap_or(Value::Null);
+    let secret = result
+        .and_then(|v| v.get("secret"))
+        .and_then(|v| v.as_bool())
+        .unwrap_or(false);
+    Ok((value, secret))
 }
@@ -0,0 +1,149 @@
+//! Secret redaction for command output.
+//!
+//! Handlers mask secrets before building their result, so `--json` output,
+//! text output, daemon logs and the audit log all see the same masked data.
+//! Callers opt back in with `--show-secrets` where a command supports it.
+
+use serde_json::{Value, json};
+
+use crate::browser::element::TabContext;
+
+/// Placeholder written in place of a secret value.
+pub const REDACTED: &str = "[redacted]";
+
+/// Page-side predicate: is `this` an input whose value should be treated as a
+/// secret (password fields and credential/payment autocomplete hints)?
+pub const IS_SECRET_FIELD_FN: &str = r#"function() {
+    const type = (this.type || '').toLowerCase();
+    if (type === 'password') return true;
+    const ac = (this.getAttribute && this.getAttribute('autocomplete') || '').toLowerCase();
+    return /(^|\s)(current-password|new-password|one-time-code|cc-number|cc-csc)(\s|$)/.test(ac);
+}"#;
+
+/// Whether the focused element is a secret field. Errors count as "not secret"
+/// since the caller's explicit `--secret` flag still applies.
+pub async fn active_element_is_secret(ctx: &TabContext) -> bool {
+    let expr = format!(
+        "(() => {{ const el = document.activeElement; return !!el && ({IS_SECRET_FIELD_FN}).call(el); }})()"
+    );
+    ctx.execute_on_element(
+        "Runtime.evaluate",
+        json!({ "expression": expr, "returnByValue": true }),
+    )
+    .await
+    .ok()
+    .and_then(|v| v.pointer("/result/result/value").and_then(|b| b.as_bool()))
+    .unwrap_or(false)
+}
+
+/// Whether the element behind `object_id` is a secret field.
+pub async fn object_is_secret(ctx: &TabContext, object_id: &str) -> bool {
+    ctx.execute_on_element(
+        "Runtime.callFunctionOn",
+        json!({
+            "objectId": object_id,
+            "functionDeclaration": IS_SECRET_FIELD_FN,
+            "returnByValue": true,
+        }),
+    )
+    .await
+    .ok()
+    .and_then(|v| v.pointer("/result/result/value").and_then(|b| b.as_bool()))
+    .unwrap_or(false)
+}
+
+/// `value_summary` for an input command: the length of non-secret text, or a
+/// redaction marker for secrets.
+pub fn value_summary(text: &str, secret: bool) -> Value {
+    if secret {
+        json!({ "redacted": true })
+    } else {
+        json!({ "text_length": text.chars().count() })
+    }
+}
+
+/// Mask a cookie's `value` in place (canonical shape from `cookies::map_cookie`).
+pub fn mask_cookie(cookie: &mut Value) {
+    if let Some(obj) = cookie.as_object_mut()
+        && obj.contains_key("value")
+    {
+        obj.insert("value".to_string(), Value::from(REDACTED));
+        obj.insert("value_redacted".to_string(), Value::Bool(true));
+    }
+}
+
+/// Whether an HTTP header carries credentials: cookies, authorization and
+/// API-key or token headers. `name` is compared case-insensitively.
+pub fn is_secret_header(name: &str) -> bool {
+    let name = name.to_ascii_lowercase();
+    matches!(
+        name.as_str(),
+        "authorization" | "proxy-authorization" | "cookie" | "set-cookie" | "set-cookie2"
+    ) || name.contains("api-key")
+        || name.contains("token")
+        || name.contains("secret")
+}
+
+/// Mask the values of credential headers in a `{ name: value }` object.
+pub fn mask_headers(headers: &mut Value) {
+    if let Some(obj) = headers.as_object_mut() {
+        for (name, value) in obj.iter_mut() {
+            if is_secret_header(name) {
+                *value = Value::from(REDACTED);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn mask_cookie_replaces_value_and_flags_it() {
+        let mut cookie = json!({ "name": "sid", "value": "abc123", "domain": "a.test" });
+        mask_cookie(&mut cookie);
+        assert_eq!(cookie["value"], REDACTED);
+        assert_eq!(cookie["value_redacted"], true);
+        assert_eq!(cookie["name"], "sid");
+    }
+
+    #[test]
+    fn value_summary_hides_length_of_secrets() {
+        assert_eq!(value_summary("hunter2", false), json!({ "text_length": 7 }));
+        assert_eq!(value_summary("hunter2", true), json!({ "redacted": true }));
+    }
+
+    #[test]
+    fn mask_headers_hides_credential_headers_only() {
+        let mut headers = json!({
+            "set-cookie": "sid=abc123; HttpOnly",
+            "Authorization": "Bearer t",
+            "proxy-authorization": "Basic eA==",
+            "x-api-key": "k",
+            "x-csrf-token": "c",
+            "content-type": "text/html",
+            "server": "nginx",
+        });
+        mask_headers(&mut headers);
+        for name in [
+            "set-cookie",
+            "Authorization",
+            "proxy-authorization",
+            "x-api-key",
+            "x-csrf-token",
+        ] {
+            assert_eq!(headers[name], REDACTED, "{name}");
+        }
+        assert_eq!(headers["content-type"], "text/html");
+        assert_eq!(headers["server"], "nginx");
+    }
+
+    #[test]
+    fn mask_cookie_ignores_null() {
+        let mut cookie = Value::Null;
+        mask_cookie(&mut cookie);
+        assert!(cookie.is_null());
+    }
+}
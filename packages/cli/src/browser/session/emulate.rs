@@ -0,0 +1,154 @@
+use clap::Args;
+use serde::{Deserialize, Serialize};
+use serde_json::{Value, json};
+
+use crate::action_result::ActionResult;
+use crate::daemon::emulation::{self, DEVICES, Emulation};
+use crate::daemon::registry::SharedRegistry;
+use crate::output::ResponseContext;
+
+/// Emulate a mobile or tablet device on every tab of a session
+#[derive(Args, Debug, Clone, Serialize, Deserialize)]
+#[command(after_help = "\
+Examples:
+  actionbook browser emulate \"iPhone 14\" --session s1
+  actionbook browser emulate \"Pixel 7 landscape\" --session s1
+  actionbook browser emulate --reset --session s1
+  actionbook browser emulate --session s1          # current device and presets
+
+Sets the viewport, device scale factor, touch support and user agent of the
+session's tabs. Tabs opened later get the same device, and `browser restart`
+keeps it; `browser start --device NAME` sets it from the first tab on. Names
+ignore case and punctuation; append \"landscape\" to rotate.
+
+Devices: iPhone SE, iPhone 12 Pro, iPhone 14, iPhone 14 Pro Max, iPhone 15 Pro,
+Pixel 7, Galaxy S22 Ultra, iPad Mini, iPad Air, iPad Pro, Galaxy Tab S8,
+Desktop 1080p.
+
+The new user agent applies to the next request: reload pages that were
+already loaded (`browser reload`).")]
+pub struct Cmd {
+    /// Device preset, e.g. "iPhone 14"
+    #[arg(conflicts_with = "reset")]
+    #[serde(default)]
+    pub device: Option<String>,
+    /// Stop emulating and restore the browser's own metrics and user agent
+    #[arg(long)]
+    #[serde(default)]
+    pub reset: bool,
+    /// Session ID
+    #[arg(long)]
+    #[serde(rename = "session_id")]
+    pub session: String,
+}
+
+pub const COMMAND_NAME: &str = "browser emulate";
+
+pub fn context(cmd: &Cmd, result: &ActionResult) -> Option<ResponseContext> {
+    if let ActionResult::Fatal { code, .. } = result
+        && code == "SESSION_NOT_FOUND"
+    {
+        return None;
+    }
+    Some(ResponseContext {
+        session_id: cmd.session.clone(),
+        tab_id: None,
+        window_id: None,
+        url: None,
+        title: None,
+    })
+}
+
+pub async fn execute(cmd: &Cmd, registry: &SharedRegistry) -> ActionResult {
+    let target = match cmd.device.as_deref() {
+        Some(name) => match Emulation::resolve(name) {
+            Some(e) => Some(e),
+            None => {
+                return ActionResult::fatal_with_hint(
+                    "INVALID_ARGUMENT",
+                    format!("unknown device '{name}'"),
+                    format!("known devices: {}", emulation::device_names()),
+                );
+            }
+        },
+        None => None,
+    };
+
+    let (cdp, native_ids, stealth_ua) = {
+        let reg = registry.lock().await;
+        let Some(entry) = reg.get(&cmd.session) else {
+            return ActionResult::fatal_with_hint(
+                "SESSION_NOT_FOUND",
+                format!("session '{}' not found", cmd.session),
+                "run `actionbook browser list-sessions` to see available sessions",
+            );
+        };
+        let Some(cdp) = entry.cdp.clone() else {
+            return ActionResult::fatal(
+                "INTERNAL_ERROR",
+                format!("no CDP connection for session '{}'", cmd.session),
+            );
+        };
+        (
+            cdp,
+            entry
+                .tabs
+                .iter()
+                .map(|t| t.native_id.clone())
+                .collect::<Vec<_>>(),
+            entry.stealth_ua.clone(),
+        )
+    };
+
+    if target.is_none() && !cmd.reset {
+        let devices: Vec<Value> = DEVICES
+            .iter()
+            .map(|d| Emulation {
+                device: d,
+                landscape: false,
+            })
+            .map(|e| e.to_json())
+            .collect();
+        return ActionResult::ok(json!({
+            "device": cdp.emulation().map(|e| e.to_json()),
+            "devices": devices,
+        }));
+    }
+
+    cdp.set_emulation(target);
+    let mut warnings = Vec::new();
+    let mut applied = 0usize;
+    let mut first_error = None;
+    for native_id in &native_ids {
+        let result = match &target {
+            Some(e) => emulation::apply(&cdp, native_id, e).await,
+            None => emulation::clear(&cdp, native_id, stealth_ua.as_deref()).await,
+        };
+        match result {
+            Ok(()) => applied += 1,
+            Err(e) => {
+                warnings.push(format!("tab {native_id}: {e}"));
+                first_error.get_or_insert(e);
+            }
+        }
+    }
+    if applied == 0
+        && let Some(e) = first_error
+    {
+        cdp.set_emulation(None);
+        return ActionResult::fatal("CDP_ERROR", format!("failed to emulate device: {e}"));
+    }
+
+    if let Some(entry) = registry.lock().await.get_mut(&cmd.session) {
+        entry.device = target.map(|e| e.label());
+    }
+
+    let mut data = json!({
+        "device": target.map(|e| e.to_json()),
+        "tabs": applied,
+    });
+    if !warnings.is_empty() {
+        data["__warnings"] = json!(warnings);
+    }
+    ActionResult::ok(data)
+}
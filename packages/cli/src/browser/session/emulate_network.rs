@@ -0,0 +1,234 @@
+use clap::Args;
+use serde::{Deserialize, Serialize};
+use serde_json::{Value, json};
+
+use crate::action_result::ActionResult;
+use crate::daemon::emulation::{self, NETWORK_PRESETS, Network};
+use crate::daemon::registry::SharedRegistry;
+use crate::output::ResponseContext;
+
+/// Throttle or cut the network of every tab of a session
+#[derive(Args, Debug, Clone, Serialize, Deserialize)]
+#[command(after_help = "\
+Examples:
+  actionbook browser emulate network --preset slow-3g --session s1
+  actionbook browser emulate network --preset fast-3g --latency 1000 --session s1
+  actionbook browser emulate network --latency 300 --download 2000 --upload 500 --session s1
+  actionbook browser emulate network --preset offline --session s1
+  actionbook browser emulate network --reset --session s1
+  actionbook browser emulate network --session s1      # current conditions and presets
+
+Presets (latency, download/upload in kbit/s):
+  slow-3g   2000 ms, 400/400
+  fast-3g   563 ms, 1440/675
+  fast-4g   165 ms, 8100/1350
+  offline   no connection
+
+--latency, --download and --upload adjust a preset or, on their own, set
+custom conditions; a direction without a limit is left unthrottled. Tabs
+opened later get the same conditions.")]
+pub struct Cmd {
+    /// Network preset: slow-3g, fast-3g, fast-4g or offline
+    #[arg(long, conflicts_with = "reset")]
+    #[serde(default)]
+    pub preset: Option<String>,
+    /// Added round-trip latency in milliseconds
+    #[arg(long, value_name = "MS", conflicts_with = "reset")]
+    #[serde(default)]
+    pub latency: Option<u64>,
+    /// Download throughput limit in kbit/s
+    #[arg(long, value_name = "KBPS", conflicts_with = "reset")]
+    #[serde(default)]
+    pub download: Option<u64>,
+    /// Upload throughput limit in kbit/s
+    #[arg(long, value_name = "KBPS", conflicts_with = "reset")]
+    #[serde(default)]
+    pub upload: Option<u64>,
+    /// Stop throttling
+    #[arg(long)]
+    #[serde(default)]
+    pub reset: bool,
+    /// Session ID
+    #[arg(long)]
+    #[serde(rename = "session_id")]
+    pub session: String,
+}
+
+pub const COMMAND_NAME: &str = "browser emulate network";
+
+pub fn context(cmd: &Cmd, result: &ActionResult) -> Option<ResponseContext> {
+    if let ActionResult::Fatal { code, .. } = result
+        && code == "SESSION_NOT_FOUND"
+    {
+        return None;
+    }
+    Some(ResponseContext {
+        session_id: cmd.session.clone(),
+        tab_id: None,
+        window_id: None,
+        url: None,
+        title: None,
+    })
+}
+
+/// The conditions `cmd` asks for: `Ok(None)` when it only asks what is set.
+fn conditions(cmd: &Cmd) -> Result<Option<Network>, ActionResult> {
+    let mut network = match cmd.preset.as_deref() {
+        Some(name) => Network::preset(name).ok_or_else(|| {
+            ActionResult::fatal_with_hint(
+                "INVALID_ARGUMENT",
+                format!("unknown network preset '{name}'"),
+                format!("known presets: {}", emulation::network_preset_names()),
+            )
+        })?,
+        None if cmd.latency.is_none() && cmd.download.is_none() && cmd.upload.is_none() => {
+            return Ok(None);
+        }
+        None => Network {
+            preset: None,
+            offline: false,
+            latency_ms: 0,
+            download_kbps: None,
+            upload_kbps: None,
+        },
+    };
+    if network.offline && (cmd.latency.is_some() || cmd.download.is_some() || cmd.upload.is_some())
+    {
+        return Err(ActionResult::fatal(
+            "INVALID_ARGUMENT",
+            "--latency, --download and --upload do not apply offline",
+        ));
+    }
+    if let Some(ms) = cmd.latency {
+        network.latency_ms = ms;
+    }
+    if cmd.download.is_some() {
+        network.download_kbps = cmd.download;
+    }
+    if cmd.upload.is_some() {
+        network.upload_kbps = cmd.upload;
+    }
+    if matches!(network.download_kbps, Some(0)) || matches!(network.upload_kbps, Some(0)) {
+        return Err(ActionResult::fatal_with_hint(
+            "INVALID_ARGUMENT",
+            "throughput must be at least 1 kbit/s",
+            "use --preset offline to cut the connection",
+        ));
+    }
+    Ok(Some(network))
+}
+
+pub async fn execute(cmd: &Cmd, registry: &SharedRegistry) -> ActionResult {
+    let target = match conditions(cmd) {
+        Ok(t) => t,
+        Err(e) => return e,
+    };
+
+    let (cdp, native_ids) = {
+        let reg = registry.lock().await;
+        let Some(entry) = reg.get(&cmd.session) else {
+            return ActionResult::fatal_with_hint(
+                "SESSION_NOT_FOUND",
+                format!("session '{}' not found", cmd.session),
+                "run `actionbook browser list-sessions` to see available sessions",
+            );
+        };
+        let Some(cdp) = entry.cdp.clone() else {
+            return ActionResult::fatal(
+                "INTERNAL_ERROR",
+                format!("no CDP connection for session '{}'", cmd.session),
+            );
+        };
+        (
+            cdp,
+            entry
+                .tabs
+                .iter()
+                .map(|t| t.native_id.clone())
+                .collect::<Vec<_>>(),
+        )
+    };
+
+    if target.is_none() && !cmd.reset {
+        let mut presets: Vec<Value> = NETWORK_PRESETS
+            .iter()
+            .filter_map(|(name, ..)| Network::preset(name))
+            .map(|n| n.to_json())
+            .collect();
+        presets.extend(Network::preset("offline").map(|n| n.to_json()));
+        return ActionResult::ok(json!({
+            "network": cdp.network().map(|n| n.to_json()),
+            "presets": presets,
+        }));
+    }
+
+    cdp.set_network(target.clone());
+    let mut warnings = Vec::new();
+    let mut applied = 0usize;
+    let mut first_error = None;
+    for native_id in &native_ids {
+        match emulation::apply_network(&cdp, native_id, target.as_ref()).await {
+            Ok(()) => applied += 1,
+            Err(e) => {
+                warnings.push(format!("tab {native_id}: {e}"));
+                first_error.get_or_insert(e);
+            }
+        }
+    }
+    if applied == 0
+        && let Some(e) = first_error
+    {
+        cdp.set_network(None);
+        return ActionResult::fatal(
+            "CDP_ERROR",
+            format!("failed to emulate network conditions: {e}"),
+        );
+    }
+
+    let mut data = json!({
+        "network": target.map(|n| n.to_json()),
+        "tabs": applied,
+    });
+    if !warnings.is_empty() {
+        data["__warnings"] = json!(warnings);
+    }
+    ActionResult::ok(data)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn cmd(preset: Option<&str>, latency: Option<u64>, download: Option<u64>) -> Cmd {
+        Cmd {
+            preset: preset.map(String::from),
+            latency,
+            download,
+            upload: None,
+            reset: false,
+            session: "s1".into(),
+        }
+    }
+
+    #[test]
+    fn flags_adjust_a_preset_or_stand_alone() {
+        let n = conditions(&cmd(Some("fast-3g"), Some(1000), None))
+            .unwrap()
+            .unwrap();
+        assert_eq!(n.preset, Some("fast-3g"));
+        assert_eq!(n.latency_ms, 1000);
+        assert_eq!(n.download_kbps, Some(1440));
+
+        let n = conditions(&cmd(None, None, Some(2000))).unwrap().unwrap();
+        assert_eq!(n.preset, None);
+        assert_eq!(
+            (n.latency_ms, n.download_kbps, n.upload_kbps),
+            (0, Some(2000), None)
+        );
+
+        assert!(conditions(&cmd(None, None, None)).unwrap().is_none());
+        assert!(conditions(&cmd(Some("offline"), Some(10), None)).is_err());
+        assert!(conditions(&cmd(None, None, Some(0))).is_err());
+        assert!(conditions(&cmd(Some("dialup"), None, None)).is_err());
+    }
+}
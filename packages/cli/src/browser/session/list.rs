@@ -35,6 +35,7 @@ pub async fn execute(_cmd: &Cmd, registry: &SharedRegistry) -> ActionResult {
                 "headless": s.headless,
                 "tabs_count": s.tabs_count(),
                 "max_tracked_requests": s.max_tracked_requests,
+                "idle_secs": s.last_used.elapsed().as_secs(),
             });
             // Include cdp_endpoint for cloud sessions (redacted), never expose headers
             if let Some(ref ep) = s.cdp_endpoint {
@@ -1,6 +1,9 @@
 pub mod close;
+pub mod emulate;
+pub mod emulate_network;
 pub mod list;
 pub mod provider;
 pub mod restart;
 pub mod start;
+pub mod state_bundle;
 pub mod status;
@@ -56,6 +56,7 @@ pub async fn execute(cmd: &Cmd, registry: &SharedRegistry) -> ActionResult {
     let (
         mode,
         headless,
+        headless_mode,
         stealth,
         profile,
         mut open_url,
@@ -67,6 +68,14 @@ pub async fn execute(cmd: &Cmd, registry: &SharedRegistry) -> ActionResult {
         cdp,
         chrome_process,
         max_tracked_requests,
+        adblock_lists,
+        max_download_bytes,
+        download_dir,
+        window_position,
+        window_size,
+        gpu,
+        device,
+        proxy,
     );
     {
         let mut reg = registry.lock().await;
@@ -82,6 +91,7 @@ pub async fn execute(cmd: &Cmd, registry: &SharedRegistry) -> ActionResult {
         };
         mode = entry.mode;
         headless = entry.headless;
+        headless_mode = entry.headless_mode;
         stealth = entry.stealth;
         profile = entry.profile.clone();
         // tab.url is refreshed on `goto` (see browser/navigation/goto.rs)
@@ -124,6 +134,17 @@ pub async fn execute(cmd: &Cmd, registry: &SharedRegistry) -> ActionResult {
         cdp = entry.cdp.take();
         chrome_process = entry.chrome_process.take();
         max_tracked_requests = entry.max_tracked_requests;
+        adblock_lists = entry.adblock_lists.clone();
+        max_download_bytes = entry.max_download_bytes;
+        download_dir = entry.download_dir.clone();
+        window_position = entry.window_position.clone();
+        window_size = entry.window_size.clone();
+        gpu = entry.gpu;
+        device = entry.device.clone();
+        proxy = entry
+            .proxy
+            .as_ref()
+            .map(|p| (p.upstream_urls(), p.rotation()));
 
         reg.clear_session_ref_caches(&cmd.session);
     }
@@ -204,6 +225,7 @@ pub async fn execute(cmd: &Cmd, registry: &SharedRegistry) -> ActionResult {
         // Restart preserves the session's effective runtime settings and
         // intentionally does not re-run config/env resolution.
         headless: Some(headless),
+        headless_mode,
         profile: Some(profile),
         executable_path: None,
         open_url,
@@ -217,6 +239,23 @@ pub async fn execute(cmd: &Cmd, registry: &SharedRegistry) -> ActionResult {
         set_session_id: Some(cmd.session.clone()),
         stealth,
         max_tracked_requests,
+        // The recording directory is keyed by session id in the registry,
+        // so an active recording carries over the restart unchanged.
+        record_session: None,
+        adblock: adblock_lists.is_some(),
+        adblock_list: adblock_lists.unwrap_or_default(),
+        max_download_bytes,
+        download_dir,
+        window_position,
+        window_size,
+        gpu,
+        device,
+        proxy: proxy
+            .as_ref()
+            .map(|(urls, _)| urls.clone())
+            .unwrap_or_default(),
+        proxy_pool: false,
+        proxy_rotation: proxy.map(|(_, rotation)| rotation),
         provider_env: effective_provider_env,
     };
 
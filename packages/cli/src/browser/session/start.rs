@@ -1,4 +1,6 @@
+use std::path::PathBuf;
 use std::process::Child;
+use std::sync::Arc;
 
 use clap::Args;
 use serde::{Deserialize, Serialize};
@@ -8,14 +10,19 @@ use crate::action_result::ActionResult;
 use crate::browser::session::provider::{
     ProviderEnv, ProviderSession, connect_provider, normalize_provider_name, supported_providers,
 };
+use crate::browser::tab::window;
 use crate::config;
 use crate::config::DEFAULT_PROFILE;
+use crate::daemon::adblock::Adblock;
 use crate::daemon::browser;
 use crate::daemon::cdp::{cdp_navigate, ensure_scheme, ensure_scheme_or_fatal};
 use crate::daemon::cdp_session::{CdpSession, cdp_error_to_result};
+use crate::daemon::downloads::{self, DownloadTracker};
+use crate::daemon::emulation::{self, Emulation};
+use crate::daemon::proxy::{ProxyForwarder, Rotation, Upstream};
 use crate::daemon::registry::{SessionState, SharedRegistry};
 use crate::output::ResponseContext;
-use crate::types::{Mode, SessionId};
+use crate::types::{GpuMode, HeadlessMode, Mode, SessionId};
 
 /// Start or attach a browser session
 #[derive(Args, Debug, Clone, Serialize, Deserialize)]
@@ -25,7 +32,24 @@ Examples:
   actionbook browser start --session research
   actionbook browser start --session research --open-url https://google.com
   actionbook browser start --headless --profile scraper
+  actionbook browser start --headless-mode old --profile scraper
   actionbook browser start --mode cloud --cdp-endpoint wss://browser.example.com/ws
+  actionbook browser start --backend extension --open-url https://example.com
+
+--mode (alias --backend) overrides [browser] mode for this session only, so one
+workflow can mix local, extension and cloud sessions. Flags the chosen mode
+cannot honor are rejected: --headless/--window-position/--window-size with
+extension, --tab-id without it.
+
+--headless runs Chrome's new headless mode, the full browser without a
+window; --headless-mode old runs the lighter headless shell, which is much
+easier to fingerprint. Chrome 132 and later ship no old mode and run the new
+one instead; the mode actually running is checked after launch and reported
+as headless_mode here and in `browser status`.
+
+--gpu picks how WebGL renders, which headless Chrome otherwise leaves blank:
+swiftshader (software, works anywhere), hardware (the real GPU) or off.
+When set, start reports whether pages got a WebGL context.
 
 Cloud providers (-p / --provider):
   driver          requires DRIVER_API_KEY          # driver.dev
@@ -49,12 +73,16 @@ Provider examples:
 Reuse: if a session with the same profile already exists, it is reused.
 The returned session_id and tab_id are used to address all subsequent commands.")]
 pub struct Cmd {
-    /// Browser mode
-    #[arg(long, value_enum)]
+    /// Browser mode for this session, overriding [browser] mode in config (alias: --backend)
+    #[arg(long, value_enum, alias = "backend")]
     pub mode: Option<Mode>,
     /// Headless mode
     #[arg(long, default_missing_value = "true", num_args = 0..=1)]
     pub headless: Option<bool>,
+    /// Which headless implementation to run (implies --headless)
+    #[arg(long, value_enum)]
+    #[serde(default)]
+    pub headless_mode: Option<HeadlessMode>,
     /// Profile name
     #[arg(long)]
     pub profile: Option<String>,
@@ -107,6 +135,56 @@ pub struct Cmd {
     #[arg(long, default_value_t = 500)]
     #[serde(default = "default_max_tracked_requests")]
     pub max_tracked_requests: usize,
+    /// Record every snapshot, screenshot and navigation of this session into
+    /// the given directory. Inspect it later with `actionbook session replay <dir>`.
+    #[arg(long, value_name = "DIR")]
+    #[serde(default)]
+    pub record_session: Option<String>,
+    /// Block ads and trackers with the built-in filter list (local and cloud modes)
+    #[arg(long)]
+    #[serde(default)]
+    pub adblock: bool,
+    /// Extra EasyList-style filter list to enforce (repeatable, implies --adblock)
+    #[arg(long = "adblock-list", value_name = "PATH")]
+    #[serde(default)]
+    pub adblock_list: Vec<String>,
+    /// Download budget in bytes for every command that loads a page, and the
+    /// default for goto, screenshot, pdf and save
+    #[arg(long, value_name = "BYTES")]
+    #[serde(default)]
+    pub max_download_bytes: Option<u64>,
+    /// Save downloads into this directory (local sessions; see `browser downloads`)
+    #[arg(long, value_name = "DIR")]
+    #[serde(default)]
+    pub download_dir: Option<String>,
+    /// Initial window position as X,Y (local visible sessions; defaults to [browser] window_position)
+    #[arg(long, value_name = "X,Y", allow_hyphen_values = true)]
+    #[serde(default)]
+    pub window_position: Option<String>,
+    /// Initial window size as WIDTHxHEIGHT (local sessions; defaults to [browser] window_size)
+    #[arg(long, value_name = "WxH")]
+    #[serde(default)]
+    pub window_size: Option<String>,
+    /// WebGL rendering: swiftshader, hardware or off (local sessions; defaults to [browser] gpu)
+    #[arg(long, value_enum, value_name = "BACKEND")]
+    #[serde(default)]
+    pub gpu: Option<GpuMode>,
+    /// Emulate a device preset on every tab, e.g. "iPhone 14" or "Pixel 7 landscape" (see `browser emulate`)
+    #[arg(long, value_name = "NAME")]
+    #[serde(default)]
+    pub device: Option<String>,
+    /// Upstream HTTP proxy, http://[user:pass@]host:port (local sessions; repeat to rotate)
+    #[arg(long, value_name = "URL")]
+    #[serde(default)]
+    pub proxy: Vec<String>,
+    /// Rotate over the proxies listed in [proxy_pool] in config.toml (local sessions)
+    #[arg(long)]
+    #[serde(default)]
+    pub proxy_pool: bool,
+    /// How connections spread over the proxies (default: [proxy_pool] strategy, else round-robin)
+    #[arg(long, value_enum, value_name = "STRATEGY")]
+    #[serde(default)]
+    pub proxy_rotation: Option<Rotation>,
     /// Snapshot of provider env vars forwarded from the CLI client to the
     /// daemon (DRIVER_*, HYPERBROWSER_*, BROWSER_USE_*).
     /// The daemon must NOT read these from its own process env — its env was
@@ -159,7 +237,7 @@ struct ReuseTarget {
 }
 
 enum StartDisposition {
-    Reuse(ReuseTarget),
+    Reuse(Box<ReuseTarget>),
     Reserved(SessionId),
 }
 
@@ -211,6 +289,7 @@ pub async fn execute(cmd: &Cmd, registry: &SharedRegistry) -> ActionResult {
         })
     };
     let headless = cmd.headless.unwrap_or(false);
+    let requested_headless = headless.then(|| cmd.headless_mode.unwrap_or_default());
     let profile_name = cmd.profile.as_deref().unwrap_or(DEFAULT_PROFILE);
     let cdp_endpoint = cmd.cdp_endpoint.as_deref();
 
@@ -256,6 +335,38 @@ pub async fn execute(cmd: &Cmd, registry: &SharedRegistry) -> ActionResult {
         );
     }
 
+    let window = match launch_window(cmd) {
+        Ok(w) => w,
+        Err(e) => return e,
+    };
+
+    if cmd.max_download_bytes == Some(0) {
+        return ActionResult::fatal(
+            "INVALID_ARGUMENT",
+            "--max-download-bytes must be greater than 0",
+        );
+    }
+
+    if let Err(e) = load_emulation(cmd) {
+        return e;
+    }
+
+    let adblock = match load_adblock(cmd, mode) {
+        Ok(a) => a,
+        Err(e) => return e,
+    };
+    let proxy = match load_proxy(cmd, mode, cdp_endpoint.is_some()) {
+        Ok(p) => p,
+        Err(e) => return e,
+    };
+    if cmd.download_dir.is_some() && mode != Mode::Local {
+        return ActionResult::fatal_with_hint(
+            "INVALID_ARGUMENT",
+            format!("--download-dir is not supported in {mode} mode"),
+            "downloads are saved on the machine running the browser; use --mode local",
+        );
+    }
+
     // Parse headers from "KEY:VALUE" strings
     let headers = match parse_headers(&cmd.header) {
         Ok(h) => h,
@@ -445,6 +556,7 @@ pub async fn execute(cmd: &Cmd, registry: &SharedRegistry) -> ActionResult {
                 Some(provider_connection.provider.as_str()),
                 provider_connection.session.clone(),
                 cmd.max_tracked_requests,
+                adblock,
             )
             .await;
         }
@@ -459,6 +571,7 @@ pub async fn execute(cmd: &Cmd, registry: &SharedRegistry) -> ActionResult {
             None,
             None,
             cmd.max_tracked_requests,
+            adblock,
         )
         .await;
     }
@@ -496,7 +609,7 @@ pub async fn execute(cmd: &Cmd, registry: &SharedRegistry) -> ActionResult {
             && let Some(existing) = reg.find_local_session_by_profile(profile_name, mode)
         {
             match existing.status {
-                SessionState::Running => StartDisposition::Reuse(ReuseTarget {
+                SessionState::Running => StartDisposition::Reuse(Box::new(ReuseTarget {
                     session_id: existing.id.as_str().to_string(),
                     first_tab_id: existing
                         .tabs
@@ -510,7 +623,7 @@ pub async fn execute(cmd: &Cmd, registry: &SharedRegistry) -> ActionResult {
                         .unwrap_or_default(),
                     cdp: existing.cdp.clone(),
                     cdp_port: existing.cdp_port,
-                }),
+                })),
                 SessionState::Starting => {
                     return ActionResult::fatal_with_hint(
                         "SESSION_STARTING",
@@ -544,7 +657,7 @@ pub async fn execute(cmd: &Cmd, registry: &SharedRegistry) -> ActionResult {
 
     let session_id = match disposition {
         StartDisposition::Reuse(target) => {
-            return reuse_running_session(cmd, registry, target).await;
+            return reuse_running_session(cmd, registry, *target).await;
         }
         StartDisposition::Reserved(session_id) => session_id,
     };
@@ -625,6 +738,7 @@ pub async fn execute(cmd: &Cmd, registry: &SharedRegistry) -> ActionResult {
     #[cfg(windows)]
     let mut chrome_job: Option<crate::daemon::chrome_reaper::ChromeJobObject> = None;
 
+    let mut proxy_forwarder: Option<ProxyForwarder> = None;
     let (mut chrome_process, port, ws_url, mut targets) = if let Some(endpoint) = cdp_endpoint {
         let (ws_url, port) = match browser::resolve_cdp_endpoint(endpoint).await {
             Ok(value) => value,
@@ -674,12 +788,32 @@ pub async fn execute(cmd: &Cmd, registry: &SharedRegistry) -> ActionResult {
             }
         };
 
+        if let Some((upstreams, rotation)) = proxy.clone() {
+            match ProxyForwarder::start(upstreams, rotation).await {
+                Ok(forwarder) => proxy_forwarder = Some(forwarder),
+                Err(e) => {
+                    return fail_reserved_start(
+                        registry,
+                        &session_id,
+                        "PROXY_FAILED",
+                        format!("failed to start the proxy forwarder: {e}"),
+                    )
+                    .await;
+                }
+            }
+        }
         let (chrome, port) = match browser::launch_chrome(
             &executable,
-            headless,
+            requested_headless,
             &user_data_dir.to_string_lossy(),
             None,
             cmd.stealth,
+            window,
+            proxy_forwarder
+                .as_ref()
+                .map(ProxyForwarder::proxy_server)
+                .as_deref(),
+            cmd.gpu,
         )
         .await
         {
@@ -794,6 +928,26 @@ pub async fn execute(cmd: &Cmd, registry: &SharedRegistry) -> ActionResult {
             .await;
         }
     };
+    if let Some(adblock) = &adblock {
+        cdp.set_adblock(adblock.clone());
+    }
+    cdp.set_emulation(load_emulation(cmd).ok().flatten());
+    let tracker = Arc::new(DownloadTracker::new(
+        cmd.download_dir.as_ref().map(PathBuf::from),
+    ));
+    if let Err(e) = downloads::install(&cdp, tracker).await {
+        if cmd.download_dir.is_some() {
+            return fail_reserved_start_with_chrome(
+                registry,
+                &session_id,
+                chrome_process.take(),
+                "INVALID_ARGUMENT",
+                format!("cannot save downloads to --download-dir: {e}"),
+            )
+            .await;
+        }
+        tracing::warn!("download tracking unavailable: {e}");
+    }
     // Fetch real User-Agent from browser, strip Headless markers for stealth.
     // Only fetched when stealth is enabled; passed to attach() which gates injection on Some(ua).
     let user_agent: Option<String> = if cmd.stealth {
@@ -821,6 +975,38 @@ pub async fn execute(cmd: &Cmd, registry: &SharedRegistry) -> ActionResult {
 
     let first_native_id = native_tabs.first().map(|t| t.0.clone()).unwrap_or_default();
 
+    // Chrome drops headless flags it does not know, so check what launched.
+    let mut warnings: Vec<String> = Vec::new();
+    let headless_mode = if chrome_process.is_some() && !first_native_id.is_empty() {
+        let observed = observe_headless(&cdp, &first_native_id).await;
+        warnings.extend(browser::headless_mismatch(requested_headless, observed));
+        observed
+    } else {
+        requested_headless
+    };
+    // A blank canvas is the usual symptom of a missing GPU, so check --gpu
+    // actually gave pages WebGL.
+    let webgl = match cmd.gpu {
+        Some(gpu) if chrome_process.is_some() && !first_native_id.is_empty() => {
+            let available = cdp
+                .execute_on_tab(
+                    &first_native_id,
+                    "Runtime.evaluate",
+                    json!({ "expression": browser::WEBGL_PROBE, "returnByValue": true }),
+                )
+                .await
+                .ok()
+                .and_then(|r| r["result"]["result"]["value"].as_bool());
+            if available == Some(false) && gpu != GpuMode::Off {
+                warnings.push(format!(
+                    "WebGL is unavailable with --gpu {gpu}; try --gpu swiftshader or check the GPU drivers"
+                ));
+            }
+            available
+        }
+        _ => None,
+    };
+
     // Navigate to open_url after attach so the stealth script is already injected.
     if let Some(url) = &cmd.open_url
         && !first_native_id.is_empty()
@@ -863,12 +1049,22 @@ pub async fn execute(cmd: &Cmd, registry: &SharedRegistry) -> ActionResult {
         );
     };
     entry.mode = mode;
-    entry.headless = headless;
+    entry.headless = headless_mode.is_some();
+    entry.headless_mode = headless_mode;
     entry.profile = profile_name.to_string();
     entry.status = SessionState::Running;
     entry.cdp_port = port;
     entry.ws_url = ws_url.clone();
     entry.max_tracked_requests = cmd.max_tracked_requests;
+    entry.adblock_lists = adblock.as_ref().map(|_| cmd.adblock_list.clone());
+    entry.max_download_bytes = cmd.max_download_bytes;
+    entry.download_dir = cmd.download_dir.clone();
+    entry.window_position = cmd.window_position.clone();
+    entry.window_size = cmd.window_size.clone();
+    entry.gpu = cmd.gpu;
+    entry.device = load_emulation(cmd).ok().flatten().map(|e| e.label());
+    let proxy_summary = proxy_forwarder.as_ref().map(ProxyForwarder::summary);
+    entry.proxy = proxy_forwarder;
     for (native_id, url, title) in native_tabs {
         entry.push_tab(native_id, url, title);
     }
@@ -890,12 +1086,13 @@ pub async fn execute(cmd: &Cmd, registry: &SharedRegistry) -> ActionResult {
         .map(|t| t.id.0.clone())
         .unwrap_or_default();
 
-    ActionResult::ok(json!({
+    let mut data = json!({
         "session": {
             "session_id": session_id.as_str(),
             "mode": mode.to_string(),
             "status": "running",
-            "headless": headless,
+            "headless": headless_mode.is_some(),
+            "headless_mode": headless_mode,
             // Local loopback CDP URLs must be emitted verbatim so the caller
             // can actually connect (e.g. `chrome --remote-debugging-port` or
             // DevTools). `endpoint_for_mode` skips redaction for non-cloud
@@ -909,7 +1106,138 @@ pub async fn execute(cmd: &Cmd, registry: &SharedRegistry) -> ActionResult {
             "title": first_title,
         },
         "reused": false,
-    }))
+    });
+    if let Some(adblock) = &adblock {
+        data["adblock"] = adblock.summary();
+    }
+    if let Some(proxy) = proxy_summary {
+        data["proxy"] = proxy;
+    }
+    if let Some(gpu) = cmd.gpu {
+        data["session"]["gpu"] = json!(gpu);
+        data["session"]["webgl"] = json!(webgl);
+    }
+    if !warnings.is_empty() {
+        data["__warnings"] = json!(warnings);
+    }
+    ActionResult::ok(data)
+}
+
+/// The headless mode Chrome is actually running in, probed on `target_id`.
+async fn observe_headless(cdp: &CdpSession, target_id: &str) -> Option<HeadlessMode> {
+    let version = cdp
+        .execute("Browser.getVersion", json!({}), None)
+        .await
+        .ok()?;
+    let user_agent = version["result"]["userAgent"].as_str().unwrap_or("");
+    let pdf_viewer = cdp
+        .execute_on_tab(
+            target_id,
+            "Runtime.evaluate",
+            json!({ "expression": browser::PDF_VIEWER_PROBE, "returnByValue": true }),
+        )
+        .await
+        .ok()
+        .and_then(|r| r["result"]["result"]["value"].as_bool())
+        .unwrap_or(true);
+    browser::observed_headless(user_agent, pdf_viewer)
+}
+
+/// Upstreams and rotation for `--proxy` / `--proxy-pool`, validated. Only
+/// sessions that launch their own Chrome can be proxied.
+fn load_proxy(
+    cmd: &Cmd,
+    mode: Mode,
+    attaching: bool,
+) -> Result<Option<(Vec<Upstream>, Rotation)>, ActionResult> {
+    if cmd.proxy.is_empty() && !cmd.proxy_pool {
+        return Ok(None);
+    }
+    if mode != Mode::Local || attaching {
+        return Err(ActionResult::fatal_with_hint(
+            "INVALID_ARGUMENT",
+            "--proxy is only supported for browsers launched by actionbook",
+            "use --mode local without --cdp-endpoint, or configure the proxy on the remote browser",
+        ));
+    }
+    let mut urls = cmd.proxy.clone();
+    let mut rotation = cmd.proxy_rotation;
+    if cmd.proxy_pool {
+        let pool = config::proxy_pool();
+        if pool.proxies.is_empty() {
+            return Err(ActionResult::fatal_with_hint(
+                "INVALID_ARGUMENT",
+                "--proxy-pool is set but [proxy_pool] lists no proxies",
+                "add proxies = [\"http://host:port\", ...] under [proxy_pool] in config.toml",
+            ));
+        }
+        urls.extend(pool.proxies);
+        rotation = rotation.or(Some(pool.strategy));
+    }
+    let upstreams = urls
+        .iter()
+        .map(|u| Upstream::parse(u))
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|e| ActionResult::fatal("INVALID_ARGUMENT", e))?;
+    Ok(Some((upstreams, rotation.unwrap_or_default())))
+}
+
+/// Validated `--window-position` / `--window-size` for the Chrome launch.
+fn launch_window(cmd: &Cmd) -> Result<browser::LaunchWindow, ActionResult> {
+    let invalid = |e: String| ActionResult::fatal("INVALID_ARGUMENT", e);
+    Ok(browser::LaunchWindow {
+        position: cmd
+            .window_position
+            .as_deref()
+            .map(window::parse_position)
+            .transpose()
+            .map_err(invalid)?,
+        size: cmd
+            .window_size
+            .as_deref()
+            .map(window::parse_size)
+            .transpose()
+            .map_err(invalid)?,
+    })
+}
+
+/// Build the session's filter list when `--adblock`/`--adblock-list` is set.
+/// Extension mode is rejected: the bridge drives the user's everyday browser,
+/// and interception would route every one of its requests through the daemon.
+/// `--device`, resolved against the preset list.
+fn load_emulation(cmd: &Cmd) -> Result<Option<Emulation>, ActionResult> {
+    let Some(name) = cmd.device.as_deref() else {
+        return Ok(None);
+    };
+    Emulation::resolve(name).map(Some).ok_or_else(|| {
+        ActionResult::fatal_with_hint(
+            "INVALID_ARGUMENT",
+            format!("unknown device '{name}'"),
+            format!("known devices: {}", emulation::device_names()),
+        )
+    })
+}
+
+fn load_adblock(cmd: &Cmd, mode: Mode) -> Result<Option<Arc<Adblock>>, ActionResult> {
+    if !cmd.adblock && cmd.adblock_list.is_empty() {
+        return Ok(None);
+    }
+    if mode == Mode::Extension {
+        return Err(ActionResult::fatal_with_hint(
+            "INVALID_ARGUMENT",
+            "--adblock is not supported in extension mode",
+            "use the browser's own content blocker, or start with --mode local",
+        ));
+    }
+    Adblock::load(&cmd.adblock_list)
+        .map(|a| Some(Arc::new(a)))
+        .map_err(|e| {
+            ActionResult::fatal_with_hint(
+                "INVALID_ARGUMENT",
+                e,
+                "check the --adblock-list path and that the file is readable",
+            )
+        })
 }
 
 /// Extract url/title for a target_id from a targets list.
@@ -989,6 +1317,7 @@ async fn reuse_running_session(
             "mode": entry.mode.to_string(),
             "status": entry.status.to_string(),
             "headless": entry.headless,
+            "headless_mode": entry.headless_mode,
             // Cloud ws_urls embed tokens (e.g. Hyperbrowser JWT) as query
             // params and must be redacted; local loopback ws_urls must be
             // emitted verbatim so the caller can actually attach.
@@ -1123,6 +1452,7 @@ async fn execute_cloud(
     provider_name: Option<&str>,
     provider_session: Option<ProviderSession>,
     max_tracked_requests: usize,
+    adblock: Option<Arc<Adblock>>,
 ) -> ActionResult {
     let ws_url = match ensure_scheme_or_fatal(cdp_endpoint) {
         Ok(u) => u,
@@ -1207,6 +1537,14 @@ async fn execute_cloud(
             .await;
         }
     };
+    if let Some(adblock) = &adblock {
+        cdp.set_adblock(adblock.clone());
+    }
+    cdp.set_emulation(load_emulation(cmd).ok().flatten());
+    // Best effort: providers may not allow changing download behaviour.
+    if let Err(e) = downloads::install(&cdp, Arc::new(DownloadTracker::new(None))).await {
+        tracing::warn!("download tracking unavailable: {e}");
+    }
 
     // ── Discover tabs via Target.getTargets ──
     let tabs = match discover_tabs_via_cdp(&cdp).await {
@@ -1305,6 +1643,12 @@ async fn execute_cloud(
     entry.cdp_port = None;
     entry.ws_url = ws_url.clone();
     entry.max_tracked_requests = max_tracked_requests;
+    entry.adblock_lists = adblock.as_ref().map(|_| cmd.adblock_list.clone());
+    entry.max_download_bytes = cmd.max_download_bytes;
+    entry.download_dir = cmd.download_dir.clone();
+    entry.window_position = cmd.window_position.clone();
+    entry.window_size = cmd.window_size.clone();
+    entry.device = load_emulation(cmd).ok().flatten().map(|e| e.label());
     for (native_id, url, title) in tabs {
         entry.push_tab(native_id, url, title);
     }
@@ -1325,7 +1669,7 @@ async fn execute_cloud(
         .map(|t| t.id.0.clone())
         .unwrap_or_default();
 
-    ActionResult::ok(json!({
+    let mut data = json!({
         "session": {
             "session_id": session_id.as_str(),
             "mode": "cloud",
@@ -1341,7 +1685,11 @@ async fn execute_cloud(
             "title": first_title,
         },
         "reused": false,
-    }))
+    });
+    if let Some(adblock) = &adblock {
+        data["adblock"] = adblock.summary();
+    }
+    ActionResult::ok(data)
 }
 
 // ── Extension mode ────────────────────────────────────────────────────
@@ -1356,7 +1704,7 @@ async fn execute_extension(
     profile_name: &str,
     headless: bool,
 ) -> ActionResult {
-    use crate::daemon::bridge::{BRIDGE_PORT, BridgeError, ensure_bridge};
+    use crate::daemon::bridge::{BRIDGE_PORT, BridgeError, ensure_bridge, wait_for_extension};
 
     // Lazy bridge: bind 19222 on the first --mode extension call.
     // ensure_bridge is idempotent and recovers from a previous Failed state.
@@ -1403,27 +1751,25 @@ async fn execute_extension(
     // Bridge is bound; now wait for the Chrome extension's WS handshake to
     // complete (the extension uses exponential-backoff reconnect after a
     // daemon restart so it can take 100ms–2s).
-    let bridge_ws_url = {
-        let deadline = std::time::Instant::now() + std::time::Duration::from_secs(5);
-        loop {
-            {
-                let bs = bridge_state.lock().await;
-                if bs.is_extension_connected() {
-                    break;
-                }
-            }
-            if std::time::Instant::now() >= deadline {
-                return ActionResult::fatal_with_hint(
-                    "EXTENSION_NOT_CONNECTED",
-                    "no Chrome extension connected to the bridge within 5s",
-                    "open chrome://extensions, ensure the Actionbook extension is enabled and its popup shows Connected",
-                );
-            }
-            tokio::time::sleep(std::time::Duration::from_millis(100)).await;
-        }
-
-        format!("ws://127.0.0.1:{BRIDGE_PORT}")
-    };
+    let timeout = crate::config::extension_connect_timeout();
+    if let Err(rejection) = wait_for_extension(&bridge_state, timeout).await {
+        let secs = timeout.as_secs();
+        return match rejection {
+            Some(reason) => ActionResult::fatal_with_hint(
+                "EXTENSION_NOT_CONNECTED",
+                format!("the Chrome extension tried to connect but was refused: {reason}"),
+                "update or reload the Actionbook extension from chrome://extensions (`actionbook extension install` fetches the current build)",
+            ),
+            None => ActionResult::fatal_with_hint(
+                "EXTENSION_NOT_CONNECTED",
+                format!(
+                    "the bridge is listening on port {BRIDGE_PORT} but no Chrome extension connected within {secs}s"
+                ),
+                "make sure Chrome is running with the Actionbook extension enabled and its popup shows Connected; raise [extension] connect_timeout_secs in config.toml if Chrome starts slowly",
+            ),
+        };
+    }
+    let bridge_ws_url = format!("ws://127.0.0.1:{BRIDGE_PORT}");
 
     // Reserve a session placeholder.
     let effective_set_id = cmd.session.as_deref().or(cmd.set_session_id.as_deref());
@@ -1565,6 +1911,7 @@ async fn execute_extension(
 
     // Register extension tabs in CdpSession so execute_on_tab works.
     // Extension bridge ignores sessionId, so an empty string is fine.
+    cdp.set_emulation(load_emulation(cmd).ok().flatten());
     for (native_id, ..) in &tabs {
         cdp.register_extension_tab(native_id).await;
     }
@@ -1594,6 +1941,11 @@ async fn execute_extension(
     entry.cdp_port = None;
     entry.ws_url = bridge_ws_url.clone();
     entry.max_tracked_requests = cmd.max_tracked_requests;
+    entry.max_download_bytes = cmd.max_download_bytes;
+    entry.download_dir = cmd.download_dir.clone();
+    entry.window_position = cmd.window_position.clone();
+    entry.window_size = cmd.window_size.clone();
+    entry.device = load_emulation(cmd).ok().flatten().map(|e| e.label());
     for (native_id, url, title) in tabs {
         entry.push_tab(native_id, url, title);
     }
@@ -2051,6 +2403,7 @@ mod provider_start_tests {
             &Cmd {
                 mode: Some(Mode::Cloud),
                 headless: Some(false),
+                headless_mode: None,
                 profile: None,
                 executable_path: None,
                 open_url: None,
@@ -2062,6 +2415,18 @@ mod provider_start_tests {
                 set_session_id: Some("hyp3".to_string()),
                 stealth: true,
                 max_tracked_requests: 500,
+                record_session: None,
+                adblock: false,
+                adblock_list: Vec::new(),
+                max_download_bytes: None,
+                download_dir: None,
+                window_position: None,
+                window_size: None,
+                gpu: None,
+                device: None,
+                proxy: Vec::new(),
+                proxy_pool: false,
+                proxy_rotation: None,
                 provider_env: ProviderEnv::new(),
             },
             &registry,
@@ -2079,6 +2444,7 @@ mod provider_start_tests {
                 ]),
             }),
             500,
+            None,
         )
         .await;
 
@@ -2113,6 +2479,7 @@ mod provider_start_tests {
             &Cmd {
                 mode: Some(Mode::Cloud),
                 headless: Some(false),
+                headless_mode: None,
                 profile: None,
                 executable_path: None,
                 open_url: None,
@@ -2124,6 +2491,18 @@ mod provider_start_tests {
                 set_session_id: Some("hyp3".to_string()),
                 stealth: true,
                 max_tracked_requests: 500,
+                record_session: None,
+                adblock: false,
+                adblock_list: Vec::new(),
+                max_download_bytes: None,
+                download_dir: None,
+                window_position: None,
+                window_size: None,
+                gpu: None,
+                device: None,
+                proxy: Vec::new(),
+                proxy_pool: false,
+                proxy_rotation: None,
                 provider_env: ProviderEnv::from([
                     ("HYPERBROWSER_API_KEY".to_string(), "hb-key".to_string()),
                     (
@@ -2166,6 +2545,7 @@ mod provider_start_tests {
             &Cmd {
                 mode: Some(Mode::Cloud),
                 headless: Some(false),
+                headless_mode: None,
                 profile: None,
                 executable_path: None,
                 open_url: None,
@@ -2177,6 +2557,18 @@ mod provider_start_tests {
                 set_session_id: Some("bs1".to_string()),
                 stealth: true,
                 max_tracked_requests: 500,
+                record_session: None,
+                adblock: false,
+                adblock_list: Vec::new(),
+                max_download_bytes: None,
+                download_dir: None,
+                window_position: None,
+                window_size: None,
+                gpu: None,
+                device: None,
+                proxy: Vec::new(),
+                proxy_pool: false,
+                proxy_rotation: None,
                 provider_env: ProviderEnv::new(),
             },
             &registry,
@@ -2187,6 +2579,7 @@ mod provider_start_tests {
             Some("browseruse"),
             None,
             500,
+            None,
         )
         .await;
 
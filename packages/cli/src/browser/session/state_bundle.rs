@@ -0,0 +1,692 @@
+//! `browser state bundle` / `browser state import` commands.
+//!
+//! A bundle is a session's login state in one file: its cookies, the
+//! localStorage of the origins it has open or holds cookies for, and the
+//! fingerprint it presented (user agent, languages, time zone, emulated
+//! device). Importing it into a fresh session on another machine carries an
+//! authenticated persona over without signing in again.
+//!
+//! With `--encrypt` the state is sealed with AES-256-GCM under a key derived
+//! from a password with PBKDF2-HMAC-SHA256; the salt, nonce and iteration
+//! count are kept in the file's header.
+
+use std::num::NonZeroU32;
+
+use base64::Engine;
+use clap::Args;
+use ring::aead::{AES_256_GCM, Aad, LessSafeKey, NONCE_LEN, Nonce, UnboundKey};
+use ring::rand::{SecureRandom, SystemRandom};
+use serde::{Deserialize, Serialize};
+use serde_json::{Value, json};
+
+use crate::action_result::ActionResult;
+use crate::browser::cookies::import::ImportedCookie;
+use crate::daemon::cdp_session::CdpSession;
+use crate::daemon::registry::SharedRegistry;
+use crate::output::ResponseContext;
+
+/// Format tag and version at the top of every bundle.
+const FORMAT_KEY: &str = "actionbook_state_bundle";
+const FORMAT_VERSION: u64 = 1;
+
+/// Environment variable the password is read from unless `--password-env`
+/// names another.
+pub const DEFAULT_PASSWORD_ENV: &str = "ACTIONBOOK_BUNDLE_PASSWORD";
+
+/// PBKDF2 rounds for new bundles (OWASP's figure for PBKDF2-HMAC-SHA256).
+const PBKDF2_ITERATIONS: u32 = 600_000;
+/// Highest round count a bundle may ask for, so a crafted header cannot make
+/// an import spin for minutes.
+const MAX_PBKDF2_ITERATIONS: u32 = 5_000_000;
+const SALT_LEN: usize = 16;
+
+/// Page script reporting what the session presents to sites.
+const FINGERPRINT_JS: &str = "JSON.stringify({ user_agent: navigator.userAgent, \
+    languages: navigator.languages, platform: navigator.platform, \
+    timezone: Intl.DateTimeFormat().resolvedOptions().timeZone, \
+    screen: [screen.width, screen.height] })";
+
+// ── Bundle ────────────────────────────────────────────────────────────────────
+
+/// Write a session's cookies, localStorage and fingerprint to a bundle file
+#[derive(Args, Debug, Clone, Serialize, Deserialize)]
+#[command(after_help = "\
+Examples:
+  ACTIONBOOK_BUNDLE_PASSWORD=... actionbook browser state bundle --out persona.bundle --encrypt --session s1
+  actionbook browser state import persona.bundle --session s2
+
+Collects every cookie of the session, the localStorage of the origins its
+tabs show or it holds cookies for (plus any --origin), and the user agent,
+languages, time zone and emulated device it presents. --encrypt seals the
+bundle with a password read from $ACTIONBOOK_BUNDLE_PASSWORD (or the variable
+named by --password-env). Without it the bundle is plain JSON; either way it
+holds live logins and is created readable by the owner only.")]
+pub struct BundleCmd {
+    /// File to write
+    #[arg(long, value_name = "FILE")]
+    pub out: String,
+    /// Encrypt the bundle with a password
+    #[arg(long)]
+    #[serde(default)]
+    pub encrypt: bool,
+    /// Environment variable holding the password
+    #[arg(long, value_name = "VAR", default_value = DEFAULT_PASSWORD_ENV)]
+    pub password_env: String,
+    /// Also collect the localStorage of this origin (repeatable)
+    #[arg(long = "origin", value_name = "ORIGIN")]
+    #[serde(default)]
+    pub origins: Vec<String>,
+    /// Password, read on the client from --password-env
+    #[arg(skip)]
+    #[serde(default)]
+    pub password: Option<String>,
+    /// Session ID
+    #[arg(long)]
+    #[serde(rename = "session_id")]
+    pub session: String,
+}
+
+pub const BUNDLE_COMMAND_NAME: &str = "browser state bundle";
+
+fn session_context(session: &str, result: &ActionResult) -> Option<ResponseContext> {
+    if let ActionResult::Fatal { code, .. } = result
+        && code == "SESSION_NOT_FOUND"
+    {
+        return None;
+    }
+    Some(ResponseContext {
+        session_id: session.to_string(),
+        tab_id: None,
+        window_id: None,
+        url: None,
+        title: None,
+    })
+}
+
+pub fn bundle_context(cmd: &BundleCmd, result: &ActionResult) -> Option<ResponseContext> {
+    session_context(&cmd.session, result)
+}
+
+/// The session's CDP connection and its tabs' native ids.
+async fn session_tabs(
+    registry: &SharedRegistry,
+    session: &str,
+) -> Result<(CdpSession, Vec<String>, Option<String>), ActionResult> {
+    let reg = registry.lock().await;
+    let Some(entry) = reg.get(session) else {
+        return Err(ActionResult::fatal_with_hint(
+            "SESSION_NOT_FOUND",
+            format!("session '{session}' not found"),
+            "run `actionbook browser list-sessions` to see available sessions",
+        ));
+    };
+    let Some(cdp) = entry.cdp.clone() else {
+        return Err(ActionResult::fatal(
+            "INTERNAL_ERROR",
+            format!("no CDP connection for session '{session}'"),
+        ));
+    };
+    let tabs: Vec<String> = entry.tabs.iter().map(|t| t.native_id.clone()).collect();
+    if tabs.is_empty() {
+        return Err(ActionResult::fatal(
+            "NO_TAB",
+            format!("no active tab in session '{session}'"),
+        ));
+    }
+    Ok((cdp, tabs, entry.device.clone()))
+}
+
+/// `https://example.com` for an `http(s)` URL; `None` for other schemes.
+fn url_origin(url: &str) -> Option<String> {
+    let (scheme, rest) = url.split_once("://")?;
+    let scheme = scheme.to_ascii_lowercase();
+    if scheme != "http" && scheme != "https" {
+        return None;
+    }
+    let authority = rest.split(['/', '?', '#']).next().unwrap_or("");
+    let host = authority.rsplit_once('@').map_or(authority, |(_, h)| h);
+    (!host.is_empty()).then(|| format!("{scheme}://{}", host.to_ascii_lowercase()))
+}
+
+/// Origins whose localStorage goes into the bundle: the tabs' pages, the
+/// cookie domains (over https) and any asked for, each once.
+fn storage_origins(tab_urls: &[String], cookies: &[Value], extra: &[String]) -> Vec<String> {
+    let mut origins: Vec<String> = Vec::new();
+    let cookie_origins = cookies.iter().filter_map(|c| {
+        let domain = c.get("domain")?.as_str()?.trim_start_matches('.');
+        (!domain.is_empty()).then(|| format!("https://{domain}"))
+    });
+    for origin in tab_urls
+        .iter()
+        .filter_map(|u| url_origin(u))
+        .chain(cookie_origins)
+        .chain(extra.iter().filter_map(|o| url_origin(o)))
+    {
+        if !origins.contains(&origin) {
+            origins.push(origin);
+        }
+    }
+    origins
+}
+
+async fn local_storage(cdp: &CdpSession, target_id: &str, origin: &str) -> Vec<Value> {
+    let resp = cdp
+        .execute_on_tab(
+            target_id,
+            "DOMStorage.getDOMStorageItems",
+            json!({ "storageId": { "securityOrigin": origin, "isLocalStorage": true } }),
+        )
+        .await;
+    resp.ok()
+        .and_then(|r| {
+            r.pointer("/result/entries")
+                .and_then(|v| v.as_array())
+                .cloned()
+        })
+        .unwrap_or_default()
+}
+
+pub async fn execute_bundle(cmd: &BundleCmd, registry: &SharedRegistry) -> ActionResult {
+    let password = match (cmd.encrypt, cmd.password.as_deref()) {
+        (true, None | Some("")) => {
+            return ActionResult::fatal_with_hint(
+                "INVALID_ARGUMENT",
+                format!("--encrypt needs a password in ${}", cmd.password_env),
+                format!(
+                    "export {}=... (or name another variable with --password-env)",
+                    cmd.password_env
+                ),
+            );
+        }
+        (true, Some(p)) => Some(p),
+        (false, _) => None,
+    };
+    let (cdp, tabs, device) = match session_tabs(registry, &cmd.session).await {
+        Ok(v) => v,
+        Err(e) => return e,
+    };
+    let first = &tabs[0];
+
+    let cookies: Vec<Value> = match cdp
+        .execute_on_tab(first, "Network.getAllCookies", json!({}))
+        .await
+    {
+        Ok(resp) => resp
+            .pointer("/result/cookies")
+            .and_then(|v| v.as_array())
+            .cloned()
+            .unwrap_or_default(),
+        Err(e) => return ActionResult::fatal("CDP_ERROR", e.to_string()),
+    };
+    let mut tab_urls = Vec::new();
+    for tab in &tabs {
+        tab_urls.push(crate::browser::navigation::get_tab_url(&cdp, tab).await);
+    }
+    let mut storage = serde_json::Map::new();
+    for origin in storage_origins(&tab_urls, &cookies, &cmd.origins) {
+        let items = local_storage(&cdp, first, &origin).await;
+        if !items.is_empty() {
+            storage.insert(origin, json!(items));
+        }
+    }
+    let mut fingerprint = cdp
+        .execute_on_tab(
+            first,
+            "Runtime.evaluate",
+            json!({ "expression": FINGERPRINT_JS, "returnByValue": true }),
+        )
+        .await
+        .ok()
+        .and_then(|r| {
+            r.pointer("/result/result/value")
+                .and_then(|v| v.as_str())
+                .and_then(|s| serde_json::from_str::<Value>(s).ok())
+        })
+        .unwrap_or_else(|| json!({}));
+    fingerprint["device"] = json!(device);
+
+    let state = json!({
+        "created": crate::utils::time::now_rfc3339(),
+        "cookies": cookies,
+        "local_storage": storage,
+        "fingerprint": fingerprint,
+    });
+    let bundle = match password {
+        Some(p) => {
+            // Key derivation takes a good fraction of a second; keep it off
+            // the daemon's async workers.
+            let password = p.to_string();
+            match tokio::task::spawn_blocking(move || seal(&state, &password, PBKDF2_ITERATIONS))
+                .await
+            {
+                Ok(Ok(b)) => b,
+                Ok(Err(e)) => return ActionResult::fatal("INTERNAL_ERROR", e.to_string()),
+                Err(e) => return ActionResult::fatal("INTERNAL_ERROR", e.to_string()),
+            }
+        }
+        None => json!({ FORMAT_KEY: FORMAT_VERSION, "state": state }),
+    };
+    let contents = serde_json::to_string_pretty(&bundle).unwrap_or_default();
+    if let Err(e) =
+        crate::daemon::server::write_state_file(std::path::Path::new(&cmd.out), &contents)
+    {
+        return ActionResult::fatal("IO_ERROR", format!("failed to write {}: {e}", cmd.out));
+    }
+
+    let mut data = json!({
+        "path": cmd.out,
+        "encrypted": cmd.encrypt,
+        "cookies": cookies.len(),
+        "origins": storage.len(),
+    });
+    if !cmd.encrypt {
+        data["__warnings"] = json!([
+            "the bundle is not encrypted; pass --encrypt before copying it off this machine"
+        ]);
+    }
+    ActionResult::ok(data)
+}
+
+// ── Import ────────────────────────────────────────────────────────────────────
+
+/// Load a bundle's cookies, localStorage and device into a session
+#[derive(Args, Debug, Clone, Serialize, Deserialize)]
+#[command(after_help = "\
+Examples:
+  ACTIONBOOK_BUNDLE_PASSWORD=... actionbook browser state import persona.bundle --session s1
+  actionbook browser state import persona.bundle --password-env CI_BUNDLE_KEY --session s1
+
+Sets the bundle's cookies and localStorage in the session and, when it was
+taken from an emulated device and this session emulates none, emulates that
+device. A session whose user agent differs from the bundle's is reported:
+sites that tie a login to the user agent may still ask to sign in again.
+Reload open pages afterwards (`browser reload`).")]
+pub struct ImportCmd {
+    /// Bundle written by `browser state bundle`
+    #[arg(value_name = "FILE")]
+    pub file: String,
+    /// Environment variable holding the password of an encrypted bundle
+    #[arg(long, value_name = "VAR", default_value = DEFAULT_PASSWORD_ENV)]
+    pub password_env: String,
+    /// Password, read on the client from --password-env
+    #[arg(skip)]
+    #[serde(default)]
+    pub password: Option<String>,
+    /// Session ID
+    #[arg(long)]
+    #[serde(rename = "session_id")]
+    pub session: String,
+}
+
+pub const IMPORT_COMMAND_NAME: &str = "browser state import";
+
+pub fn import_context(cmd: &ImportCmd, result: &ActionResult) -> Option<ResponseContext> {
+    session_context(&cmd.session, result)
+}
+
+pub async fn execute_import(cmd: &ImportCmd, registry: &SharedRegistry) -> ActionResult {
+    let text = match std::fs::read_to_string(&cmd.file) {
+        Ok(t) => t,
+        Err(e) => {
+            return ActionResult::fatal("IO_ERROR", format!("failed to read {}: {e}", cmd.file));
+        }
+    };
+    let password = cmd.password.clone().filter(|p| !p.is_empty());
+    let opened = tokio::task::spawn_blocking(move || open_bundle(&text, password.as_deref())).await;
+    let opened = match opened {
+        Ok(result) => result,
+        Err(e) => return ActionResult::fatal("INTERNAL_ERROR", e.to_string()),
+    };
+    let state = match opened {
+        Ok(s) => s,
+        Err(BundleError::NeedsPassword) => {
+            return ActionResult::fatal_with_hint(
+                "INVALID_ARGUMENT",
+                format!(
+                    "{} is encrypted and ${} is not set",
+                    cmd.file, cmd.password_env
+                ),
+                format!(
+                    "export {}=... (or name another variable with --password-env)",
+                    cmd.password_env
+                ),
+            );
+        }
+        Err(BundleError::WrongPassword) => {
+            return ActionResult::fatal(
+                "INVALID_ARGUMENT",
+                format!("wrong password for {}, or the file is damaged", cmd.file),
+            );
+        }
+        Err(e @ BundleError::Iterations(_)) => {
+            return ActionResult::fatal("INVALID_ARGUMENT", format!("{}: {e}", cmd.file));
+        }
+        Err(BundleError::Invalid(e)) => {
+            return ActionResult::fatal(
+                "INVALID_ARGUMENT",
+                format!("{}: not a state bundle: {e}", cmd.file),
+            );
+        }
+        Err(e @ BundleError::Crypto(_)) => {
+            return ActionResult::fatal("INTERNAL_ERROR", e.to_string());
+        }
+    };
+    let (cdp, tabs, device) = match session_tabs(registry, &cmd.session).await {
+        Ok(v) => v,
+        Err(e) => return e,
+    };
+    let first = &tabs[0];
+
+    let params: Vec<Value> = state["cookies"]
+        .as_array()
+        .into_iter()
+        .flatten()
+        .filter_map(crate::browser::cookies::import::from_json_cookie)
+        .map(|c| ImportedCookie::to_cookie_param(&c))
+        .collect();
+    if !params.is_empty()
+        && let Err(e) = cdp
+            .execute_on_tab(first, "Network.setCookies", json!({ "cookies": params }))
+            .await
+    {
+        return ActionResult::fatal("CDP_ERROR", e.to_string());
+    }
+
+    let mut warnings = Vec::new();
+    let mut items = 0usize;
+    let origins = state["local_storage"]
+        .as_object()
+        .cloned()
+        .unwrap_or_default();
+    for (origin, entries) in &origins {
+        for entry in entries.as_array().into_iter().flatten() {
+            let (Some(key), Some(value)) = (entry[0].as_str(), entry[1].as_str()) else {
+                continue;
+            };
+            match cdp
+                .execute_on_tab(
+                    first,
+                    "DOMStorage.setDOMStorageItem",
+                    json!({
+                        "storageId": { "securityOrigin": origin, "isLocalStorage": true },
+                        "key": key,
+                        "value": value,
+                    }),
+                )
+                .await
+            {
+                Ok(_) => items += 1,
+                Err(e) => {
+                    warnings.push(format!("localStorage of {origin}: {e}"));
+                    break;
+                }
+            }
+        }
+    }
+
+    let fingerprint = &state["fingerprint"];
+    let mut emulated = None;
+    if device.is_none()
+        && let Some(name) = fingerprint["device"].as_str()
+    {
+        let emulate = super::emulate::Cmd {
+            device: Some(name.to_string()),
+            reset: false,
+            session: cmd.session.clone(),
+        };
+        match super::emulate::execute(&emulate, registry).await {
+            ActionResult::Ok { .. } => emulated = Some(name.to_string()),
+            ActionResult::Fatal { message, .. } => {
+                warnings.push(format!("could not emulate {name}: {message}"))
+            }
+            _ => warnings.push(format!("could not emulate {name}")),
+        }
+    }
+    if emulated.is_none()
+        && let Some(bundled) = fingerprint["user_agent"].as_str()
+    {
+        let current = cdp
+            .execute_on_tab(
+                first,
+                "Runtime.evaluate",
+                json!({ "expression": "navigator.userAgent", "returnByValue": true }),
+            )
+            .await
+            .ok()
+            .and_then(|r| {
+                r.pointer("/result/result/value")
+                    .and_then(|v| v.as_str())
+                    .map(String::from)
+            });
+        if current.as_deref().is_some_and(|ua| ua != bundled) {
+            warnings.push(format!(
+                "the bundle was taken with user agent \"{bundled}\"; sites that tie logins to it may ask to sign in again"
+            ));
+        }
+    }
+
+    let mut data = json!({
+        "path": cmd.file,
+        "cookies": params.len(),
+        "origins": origins.len(),
+        "local_storage_items": items,
+        "device": emulated,
+    });
+    if !warnings.is_empty() {
+        data["__warnings"] = json!(warnings);
+    }
+    ActionResult::ok(data)
+}
+
+// ── Format ────────────────────────────────────────────────────────────────────
+
+#[derive(Debug, PartialEq, thiserror::Error)]
+pub(crate) enum BundleError {
+    /// The bundle is encrypted and no password was given.
+    #[error("the bundle is encrypted and no password was given")]
+    NeedsPassword,
+    /// Decryption failed: a wrong password or a modified file.
+    #[error("wrong password, or the file is damaged")]
+    WrongPassword,
+    /// The header asks for no PBKDF2 rounds or more than we are willing to run.
+    #[error("encryption.iterations must be between 1 and {MAX_PBKDF2_ITERATIONS}, not {0}")]
+    Iterations(u64),
+    #[error("{0}")]
+    Invalid(String),
+    /// No system randomness, or the cipher rejected the derived key.
+    #[error("{0}")]
+    Crypto(&'static str),
+}
+
+fn b64() -> base64::engine::GeneralPurpose {
+    base64::engine::general_purpose::STANDARD
+}
+
+fn derive_key(password: &str, salt: &[u8], iterations: u32) -> Result<LessSafeKey, BundleError> {
+    let rounds = NonZeroU32::new(iterations)
+        .filter(|n| n.get() <= MAX_PBKDF2_ITERATIONS)
+        .ok_or(BundleError::Iterations(iterations.into()))?;
+    let mut key = [0u8; 32];
+    ring::pbkdf2::derive(
+        ring::pbkdf2::PBKDF2_HMAC_SHA256,
+        rounds,
+        salt,
+        password.as_bytes(),
+        &mut key,
+    );
+    let unbound =
+        UnboundKey::new(&AES_256_GCM, &key).map_err(|_| BundleError::Crypto("invalid key"))?;
+    Ok(LessSafeKey::new(unbound))
+}
+
+/// An encrypted bundle of `state`. The header is authenticated along with
+/// the ciphertext, so changing the iteration count breaks decryption too.
+pub(crate) fn seal(state: &Value, password: &str, iterations: u32) -> Result<Value, BundleError> {
+    let rng = SystemRandom::new();
+    let mut salt = [0u8; SALT_LEN];
+    let mut nonce = [0u8; NONCE_LEN];
+    rng.fill(&mut salt)
+        .and_then(|_| rng.fill(&mut nonce))
+        .map_err(|_| BundleError::Crypto("no system randomness"))?;
+    let header = json!({
+        "cipher": "aes-256-gcm",
+        "kdf": "pbkdf2-sha256",
+        "iterations": iterations,
+        "salt": b64().encode(salt),
+        "nonce": b64().encode(nonce),
+    });
+    let key = derive_key(password, &salt, iterations)?;
+    let mut data = serde_json::to_vec(state).map_err(|e| BundleError::Invalid(e.to_string()))?;
+    key.seal_in_place_append_tag(
+        Nonce::assume_unique_for_key(nonce),
+        Aad::from(header.to_string().as_bytes()),
+        &mut data,
+    )
+    .map_err(|_| BundleError::Crypto("encryption failed"))?;
+    Ok(json!({
+        FORMAT_KEY: FORMAT_VERSION,
+        "encryption": header,
+        "data": b64().encode(data),
+    }))
+}
+
+/// The state in a bundle file, decrypting it with `password` when sealed.
+pub(crate) fn open_bundle(text: &str, password: Option<&str>) -> Result<Value, BundleError> {
+    let invalid = |m: &str| BundleError::Invalid(m.to_string());
+    let bundle: Value =
+        serde_json::from_str(text).map_err(|e| BundleError::Invalid(e.to_string()))?;
+    match bundle[FORMAT_KEY].as_u64() {
+        Some(FORMAT_VERSION) => {}
+        Some(v) => return Err(BundleError::Invalid(format!("unsupported version {v}"))),
+        None => return Err(invalid(&format!("missing \"{FORMAT_KEY}\""))),
+    }
+    let Some(header) = bundle.get("encryption") else {
+        return bundle
+            .get("state")
+            .cloned()
+            .ok_or_else(|| invalid("missing \"state\""));
+    };
+    let password = password.ok_or(BundleError::NeedsPassword)?;
+    let field = |k: &str| {
+        header[k]
+            .as_str()
+            .and_then(|s| b64().decode(s).ok())
+            .ok_or_else(|| BundleError::Invalid(format!("bad encryption.{k}")))
+    };
+    if header["cipher"] != "aes-256-gcm" || header["kdf"] != "pbkdf2-sha256" {
+        return Err(invalid("unsupported cipher"));
+    }
+    let salt = field("salt")?;
+    let nonce: [u8; NONCE_LEN] = field("nonce")?
+        .try_into()
+        .map_err(|_| invalid("bad encryption.nonce"))?;
+    let iterations = header["iterations"]
+        .as_u64()
+        .ok_or_else(|| invalid("bad encryption.iterations"))?;
+    let iterations = u32::try_from(iterations)
+        .ok()
+        .filter(|n| (1..=MAX_PBKDF2_ITERATIONS).contains(n))
+        .ok_or(BundleError::Iterations(iterations))?;
+    let mut data = bundle["data"]
+        .as_str()
+        .and_then(|s| b64().decode(s).ok())
+        .ok_or_else(|| invalid("bad data"))?;
+    let key = derive_key(password, &salt, iterations)?;
+    let plain = key
+        .open_in_place(
+            Nonce::assume_unique_for_key(nonce),
+            Aad::from(header.to_string().as_bytes()),
+            &mut data,
+        )
+        .map_err(|_| BundleError::WrongPassword)?;
+    serde_json::from_slice(plain).map_err(|e| BundleError::Invalid(e.to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sealed_bundles_open_only_with_their_password() {
+        let state = json!({
+            "cookies": [{ "name": "sid", "value": "s3cret", "domain": ".example.com" }],
+            "local_storage": { "https://example.com": [["token", "abc"]] },
+        });
+        let bundle = seal(&state, "hunter2", 1000).unwrap();
+        let text = bundle.to_string();
+        assert!(!text.contains("s3cret"), "{text}");
+
+        assert_eq!(open_bundle(&text, Some("hunter2")), Ok(state.clone()));
+        assert_eq!(
+            open_bundle(&text, Some("hunter3")),
+            Err(BundleError::WrongPassword)
+        );
+        assert_eq!(open_bundle(&text, None), Err(BundleError::NeedsPassword));
+
+        let mut tampered = bundle.clone();
+        tampered["encryption"]["iterations"] = json!(999);
+        assert_eq!(
+            open_bundle(&tampered.to_string(), Some("hunter2")),
+            Err(BundleError::WrongPassword)
+        );
+
+        tampered["encryption"]["iterations"] = json!(u64::from(u32::MAX) + 1);
+        assert_eq!(
+            open_bundle(&tampered.to_string(), Some("hunter2")),
+            Err(BundleError::Iterations(u64::from(u32::MAX) + 1))
+        );
+        tampered["encryption"]["iterations"] = json!(0);
+        assert_eq!(
+            open_bundle(&tampered.to_string(), Some("hunter2")),
+            Err(BundleError::Iterations(0))
+        );
+
+        let plain = json!({ FORMAT_KEY: 1, "state": state }).to_string();
+        assert_eq!(open_bundle(&plain, None), Ok(state));
+        assert!(matches!(
+            open_bundle(r#"{"cookies": []}"#, None),
+            Err(BundleError::Invalid(_))
+        ));
+    }
+
+    #[test]
+    fn bundles_asking_for_too_many_rounds_are_refused_before_deriving() {
+        let state = json!({ "cookies": [] });
+        let mut bundle = seal(&state, "hunter2", 1000).unwrap();
+        let rounds = u64::from(MAX_PBKDF2_ITERATIONS) + 1;
+        bundle["encryption"]["iterations"] = json!(rounds);
+        let started = std::time::Instant::now();
+        assert_eq!(
+            open_bundle(&bundle.to_string(), Some("hunter2")),
+            Err(BundleError::Iterations(rounds))
+        );
+        assert!(started.elapsed() < std::time::Duration::from_secs(1));
+        assert_eq!(
+            seal(&state, "hunter2", MAX_PBKDF2_ITERATIONS + 1),
+            Err(BundleError::Iterations(rounds))
+        );
+    }
+
+    #[test]
+    fn storage_origins_cover_tabs_cookies_and_extras_once() {
+        let origins = storage_origins(
+            &[
+                "https://app.example.com/inbox?x=1".to_string(),
+                "about:blank".to_string(),
+            ],
+            &[
+                json!({ "domain": ".example.com" }),
+                json!({ "domain": "app.example.com" }),
+            ],
+            &["http://localhost:3000/".to_string()],
+        );
+        assert_eq!(
+            origins,
+            [
+                "https://app.example.com",
+                "https://example.com",
+                "http://localhost:3000"
+            ]
+        );
+    }
+}
@@ -1,10 +1,14 @@
+use std::time::Duration;
+
 use clap::Args;
 use serde::{Deserialize, Serialize};
 use serde_json::json;
 
 use crate::action_result::ActionResult;
+use crate::daemon::bridge::BridgeListenerStatus;
 use crate::daemon::registry::SharedRegistry;
 use crate::output::ResponseContext;
+use crate::types::Mode;
 
 /// Show session status
 #[derive(Args, Debug, Clone, Serialize, Deserialize)]
@@ -13,7 +17,9 @@ Examples:
   actionbook browser status --session my-session
   actionbook browser status --session my-session --json
 
-Returns mode, status, tab count, and lists all tabs with their URLs.")]
+Returns mode, status, tab count, and lists all tabs with their URLs.
+The `health` object (backend, CDP or bridge liveness, active page, session
+age, Chrome PID and memory) makes `--json` usable as a health probe.")]
 pub struct Cmd {
     /// Session ID
     #[arg(long)]
@@ -33,7 +39,19 @@ pub fn context(cmd: &Cmd, _result: &ActionResult) -> Option<ResponseContext> {
     })
 }
 
+/// How long the CDP liveness probe may take before the session counts as
+/// unresponsive.
+const CDP_PROBE_TIMEOUT: Duration = Duration::from_secs(2);
+
 pub async fn execute(cmd: &Cmd, registry: &SharedRegistry) -> ActionResult {
+    let mut result = session_status(cmd, registry).await;
+    if let ActionResult::Ok { data } = &mut result {
+        data["health"] = health(&cmd.session, registry).await;
+    }
+    result
+}
+
+async fn session_status(cmd: &Cmd, registry: &SharedRegistry) -> ActionResult {
     let reg = registry.lock().await;
     let entry = match reg.get(&cmd.session) {
         Some(e) => e,
@@ -62,6 +80,7 @@ pub async fn execute(cmd: &Cmd, registry: &SharedRegistry) -> ActionResult {
         "mode": entry.mode.to_string(),
         "status": entry.status.to_string(),
         "headless": entry.headless,
+        "profile": entry.profile,
         "tabs_count": entry.tabs_count(),
     });
     // Include cdp_endpoint for cloud sessions (redacted), never expose headers
@@ -71,6 +90,18 @@ pub async fn execute(cmd: &Cmd, registry: &SharedRegistry) -> ActionResult {
     if let Some(ref provider) = entry.provider {
         session["provider"] = json!(provider);
     }
+    if let Some(ref proxy) = entry.proxy {
+        session["proxy"] = proxy.summary();
+    }
+    if let Some(ref device) = entry.device {
+        session["device"] = json!(device);
+    }
+    if let Some(mode) = entry.headless_mode {
+        session["headless_mode"] = json!(mode);
+    }
+    if let Some(gpu) = entry.gpu {
+        session["gpu"] = json!(gpu);
+    }
     ActionResult::ok(json!({
         "session": session,
         "tabs": tabs,
@@ -81,3 +112,143 @@ pub async fn execute(cmd: &Cmd, registry: &SharedRegistry) -> ActionResult {
         },
     }))
 }
+
+/// Liveness details for orchestrators. The registry lock is released before
+/// probing CDP or the bridge so a wedged browser cannot stall the daemon.
+async fn health(session_id: &str, registry: &SharedRegistry) -> serde_json::Value {
+    let (mode, cdp, active_page, age, idle, pid, bridge_state) = {
+        let reg = registry.lock().await;
+        let Some(entry) = reg.get(session_id) else {
+            return serde_json::Value::Null;
+        };
+        let active_page = entry.tabs.first().map(|t| {
+            json!({
+                "tab_id": t.id.to_string(),
+                "url": t.url,
+                "title": t.title,
+            })
+        });
+        (
+            entry.mode,
+            entry.cdp.clone(),
+            active_page,
+            entry.created_at.elapsed(),
+            entry.last_used.elapsed(),
+            entry.chrome_process.as_ref().map(|c| c.id()),
+            reg.bridge_state().cloned(),
+        )
+    };
+
+    let mut health = json!({
+        "backend": mode.to_string(),
+        "active_page": active_page,
+        "age_secs": age.as_secs(),
+        "idle_secs": idle.as_secs(),
+    });
+
+    if mode == Mode::Extension {
+        let (listener, connected, protocol) = match bridge_state {
+            Some(state) => {
+                let state = state.lock().await;
+                let listener = match state.listener_status() {
+                    BridgeListenerStatus::Listening => "listening",
+                    BridgeListenerStatus::Failed => "failed",
+                    BridgeListenerStatus::Binding => "not_listening",
+                };
+                (
+                    listener,
+                    state.is_extension_connected(),
+                    state.extension_protocol().map(String::from),
+                )
+            }
+            None => ("not_listening", false, None),
+        };
+        health["bridge"] = json!({
+            "listener": listener,
+            "extension_connected": connected,
+            "protocol_version": protocol,
+        });
+    } else {
+        let started = std::time::Instant::now();
+        let responsive = match &cdp {
+            Some(cdp) => matches!(
+                tokio::time::timeout(
+                    CDP_PROBE_TIMEOUT,
+                    cdp.execute_browser("Browser.getVersion", json!({})),
+                )
+                .await,
+                Ok(Ok(_))
+            ),
+            None => false,
+        };
+        health["cdp"] = json!({
+            "connected": cdp.is_some(),
+            "responsive": responsive,
+            "latency_ms": responsive.then(|| started.elapsed().as_millis() as u64),
+        });
+    }
+
+    if let Some(pid) = pid {
+        health["chrome"] = json!({
+            "pid": pid,
+            "rss_bytes": process_rss_bytes(pid),
+        });
+    }
+    health
+}
+
+/// Resident memory of a process, when the platform makes it cheap to read.
+#[cfg(target_os = "linux")]
+fn process_rss_bytes(pid: u32) -> Option<u64> {
+    let status = std::fs::read_to_string(format!("/proc/{pid}/status")).ok()?;
+    parse_vm_rss(&status)
+}
+
+#[cfg(all(unix, not(target_os = "linux")))]
+fn process_rss_bytes(pid: u32) -> Option<u64> {
+    let output = std::process::Command::new("ps")
+        .args(["-o", "rss=", "-p", &pid.to_string()])
+        .output()
+        .ok()?;
+    let kib: u64 = String::from_utf8_lossy(&output.stdout)
+        .trim()
+        .parse()
+        .ok()?;
+    Some(kib * 1024)
+}
+
+#[cfg(not(unix))]
+fn process_rss_bytes(_pid: u32) -> Option<u64> {
+    None
+}
+
+/// `VmRSS:     123456 kB` from `/proc/<pid>/status`, in bytes.
+#[cfg(target_os = "linux")]
+fn parse_vm_rss(status: &str) -> Option<u64> {
+    let line = status.lines().find(|l| l.starts_with("VmRSS:"))?;
+    let kib: u64 = line
+        .trim_start_matches("VmRSS:")
+        .trim()
+        .trim_end_matches("kB")
+        .trim()
+        .parse()
+        .ok()?;
+    Some(kib * 1024)
+}
+
+#[cfg(all(test, target_os = "linux"))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_vm_rss_reads_kib() {
+        let status = "Name:\tchrome\nVmPeak:\t  900 kB\nVmRSS:\t  2048 kB\nThreads:\t12\n";
+        assert_eq!(parse_vm_rss(status), Some(2048 * 1024));
+        assert_eq!(parse_vm_rss("Name:\tchrome\n"), None);
+    }
+
+    #[test]
+    fn process_rss_bytes_reads_own_process() {
+        assert!(process_rss_bytes(std::process::id()).is_some_and(|b| b > 0));
+    }
+}
@@ -5,6 +5,7 @@ use serde::{Deserialize, Serialize};
 use serde_json::json;
 
 use crate::action_result::ActionResult;
+use crate::daemon::budget;
 use crate::daemon::cdp::ensure_scheme_or_fatal;
 use crate::daemon::cdp_session::cdp_error_to_result;
 use crate::daemon::registry::SharedRegistry;
@@ -92,11 +93,11 @@ pub async fn execute(cmd: &Cmd, registry: &SharedRegistry) -> ActionResult {
     }
 
     // Get CdpSession, stealth_ua, and mode from registry
-    let (cdp, stealth_ua, mode) = {
+    let (cdp, stealth_ua, mode, budget) = {
         let reg = registry.lock().await;
         match reg.get(&cmd.session) {
             Some(e) => match e.cdp.clone() {
-                Some(c) => (c, e.stealth_ua.clone(), e.mode),
+                Some(c) => (c, e.stealth_ua.clone(), e.mode, e.max_download_bytes),
                 None => {
                     return ActionResult::fatal_with_hint(
                         "INTERNAL_ERROR",
@@ -212,7 +213,10 @@ pub async fn execute(cmd: &Cmd, registry: &SharedRegistry) -> ActionResult {
 
         // Local / cloud / CDP-direct: raw CDP Target.createTarget.
         let resp = match cdp
-            .execute_browser("Target.createTarget", json!({ "url": final_url }))
+            .execute_browser(
+                "Target.createTarget",
+                json!({ "url": budget::initial_url(budget, final_url) }),
+            )
             .await
         {
             Ok(r) => r,
@@ -307,12 +311,20 @@ pub async fn execute(cmd: &Cmd, registry: &SharedRegistry) -> ActionResult {
             }
         };
 
-        results.push(json!({
+        let mut tab = json!({
             "tab_id": short_tab_id,
             "native_tab_id": target_id,
             "url": final_url,
             "title": "",
-        }));
+        });
+        // Created blank under a budget; load the page now that it is metered.
+        if let Some(limit) = budget {
+            match budget::load(&cdp, &target_id, final_url, limit).await {
+                ActionResult::Ok { mut data } => tab["download"] = data["download"].take(),
+                err => return batch_error(i, final_url, err, &results, cmd.urls.len()),
+            }
+        }
+        results.push(tab);
     }
 
     ActionResult::ok(json!({
@@ -15,7 +15,8 @@ Examples:
   actionbook browser list-tabs --session my-session
   actionbook browser list-tabs --session my-session --json
 
-Returns each tab's ID (t1, t2, ...), URL, and title.
+Returns each tab's ID (t1, t2, ...), URL, and title, and marks the active
+tab: the one `--tab active` stands for (see switch).
 
 Extension mode: only Actionbook-managed tabs are listed — tabs the extension
 has attached, plus any tab in the Chrome \"Actionbook\" tab group. Other
@@ -201,6 +202,10 @@ pub async fn execute(cmd: &Cmd, registry: &SharedRegistry) -> ActionResult {
                 to_attach_cdp.push(native_id.to_string());
             }
         }
+        let active = entry.active_tab_id().map(|t| t.0.clone());
+        for tab in &mut result {
+            tab["active"] = json!(tab["tab_id"].as_str() == active.as_deref());
+        }
         (result, to_attach_cdp, to_register_ext)
     };
 
@@ -2,3 +2,5 @@ pub mod batch_open;
 pub mod close;
 pub mod list;
 pub mod open;
+pub mod switch;
+pub mod window;
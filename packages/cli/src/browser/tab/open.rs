@@ -3,6 +3,7 @@ use serde::{Deserialize, Serialize};
 use serde_json::json;
 
 use crate::action_result::ActionResult;
+use crate::daemon::budget;
 use crate::daemon::cdp::{ensure_scheme, ensure_scheme_or_fatal};
 use crate::daemon::cdp_session::{CdpSession, cdp_error_to_result};
 use crate::daemon::registry::SharedRegistry;
@@ -87,7 +88,7 @@ pub async fn execute(cmd: &Cmd, registry: &SharedRegistry) -> ActionResult {
         );
     }
 
-    let (cdp, stealth_ua, mode) = match session_cdp(&cmd.session, registry).await {
+    let parts = match session_cdp(&cmd.session, registry).await {
         Ok(parts) => parts,
         Err(err) => return err,
     };
@@ -110,9 +111,7 @@ pub async fn execute(cmd: &Cmd, registry: &SharedRegistry) -> ActionResult {
 
         match open_one_tab(
             &cmd.session,
-            &cdp,
-            stealth_ua.as_deref(),
-            mode,
+            &parts,
             registry,
             &final_url,
             cmd.set_tab_id.get(index).map(String::as_str),
@@ -175,16 +174,14 @@ async fn execute_single(cmd: &Cmd, registry: &SharedRegistry) -> ActionResult {
         Err(e) => return e,
     };
 
-    let (cdp, stealth_ua, mode) = match session_cdp(&cmd.session, registry).await {
+    let parts = match session_cdp(&cmd.session, registry).await {
         Ok(parts) => parts,
         Err(err) => return err,
     };
 
     match open_one_tab(
         &cmd.session,
-        &cdp,
-        stealth_ua.as_deref(),
-        mode,
+        &parts,
         registry,
         &final_url,
         cmd.set_tab_id.first().map(String::as_str),
@@ -200,14 +197,28 @@ async fn execute_single(cmd: &Cmd, registry: &SharedRegistry) -> ActionResult {
     }
 }
 
+/// What opening a tab needs from the session entry.
+struct SessionParts {
+    cdp: CdpSession,
+    stealth_ua: Option<String>,
+    mode: Mode,
+    /// The session's `--max-download-bytes`.
+    budget: Option<u64>,
+}
+
 async fn session_cdp(
     session_id: &str,
     registry: &SharedRegistry,
-) -> Result<(CdpSession, Option<String>, Mode), ActionResult> {
+) -> Result<SessionParts, ActionResult> {
     let reg = registry.lock().await;
     match reg.get(session_id) {
         Some(entry) => match entry.cdp.clone() {
-            Some(cdp) => Ok((cdp, entry.stealth_ua.clone(), entry.mode)),
+            Some(cdp) => Ok(SessionParts {
+                cdp,
+                stealth_ua: entry.stealth_ua.clone(),
+                mode: entry.mode,
+                budget: entry.max_download_bytes,
+            }),
             None => Err(ActionResult::fatal_with_hint(
                 "INTERNAL_ERROR",
                 format!("no CDP connection for session '{session_id}'"),
@@ -224,13 +235,18 @@ async fn session_cdp(
 
 async fn open_one_tab(
     session_id: &str,
-    cdp: &CdpSession,
-    stealth_ua: Option<&str>,
-    mode: Mode,
+    parts: &SessionParts,
     registry: &SharedRegistry,
     final_url: &str,
     custom_tab_id: Option<&str>,
 ) -> Result<serde_json::Value, ActionResult> {
+    let SessionParts {
+        cdp,
+        stealth_ua,
+        mode,
+        budget,
+    } = parts;
+    let (stealth_ua, mode, budget) = (stealth_ua.as_deref(), *mode, *budget);
     // Extension mode: the bridge's CDP allowlist forbids `Target.createTarget`
     // (an extension must not spawn debugger-controlled targets behind the
     // user's back). Use the extension's custom `Extension.createTab` method,
@@ -305,8 +321,9 @@ async fn open_one_tab(
     }
 
     // Local / cloud / CDP-direct modes: use the standard CDP `Target.createTarget`.
+    let create_url = budget::initial_url(budget, final_url);
     let resp = match cdp
-        .execute_browser("Target.createTarget", json!({ "url": final_url }))
+        .execute_browser("Target.createTarget", json!({ "url": create_url }))
         .await
     {
         Ok(r) => r,
@@ -378,12 +395,20 @@ async fn open_one_tab(
         }
     };
 
-    Ok(json!({
+    let mut tab = json!({
         "tab_id": short_tab_id,
         "native_tab_id": target_id,
         "url": final_url,
         "title": "",
-    }))
+    });
+    // The tab was created blank; load the page now that it can be metered.
+    if let Some(limit) = budget {
+        match budget::load(cdp, &target_id, final_url, limit).await {
+            ActionResult::Ok { mut data } => tab["download"] = data["download"].take(),
+            err => return Err(err),
+        }
+    }
+    Ok(tab)
 }
 
 fn is_session_not_found(result: &ActionResult) -> bool {
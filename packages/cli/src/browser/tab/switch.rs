@@ -0,0 +1,185 @@
+use clap::Args;
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+
+use crate::action::Action;
+use crate::action_result::ActionResult;
+use crate::browser::navigation;
+use crate::daemon::registry::SharedRegistry;
+use crate::output::ResponseContext;
+use crate::types::{ACTIVE_TAB, TabId};
+
+/// Make a tab the session's active tab
+#[derive(Args, Debug, Clone, Serialize, Deserialize)]
+#[command(after_help = "\
+Examples:
+  actionbook browser switch t2 --session s1
+  actionbook browser click \"#buy\" --session s1 --tab active
+
+--tab active runs a command on the session's active tab; --tab is never
+optional. --tab also takes a tab's 1-based position in list-tabs or part of
+its URL (--tab 2, --tab checkout). Until the first switch the active tab is
+the session's first tab. The switched-to tab is also brought to the front in
+the browser window. Closing it makes the first tab active again.")]
+pub struct Cmd {
+    /// Tab ID to make active (see list-tabs)
+    #[serde(rename = "tab_id")]
+    pub tab: String,
+    /// Session ID
+    #[arg(long)]
+    #[serde(rename = "session_id")]
+    pub session: String,
+}
+
+pub const COMMAND_NAME: &str = "browser switch";
+
+pub fn context(cmd: &Cmd, result: &ActionResult) -> Option<ResponseContext> {
+    match result {
+        ActionResult::Ok { data } => Some(ResponseContext {
+            session_id: cmd.session.clone(),
+            tab_id: Some(cmd.tab.clone()),
+            window_id: None,
+            url: data
+                .get("url")
+                .and_then(|v| v.as_str())
+                .filter(|s| !s.is_empty())
+                .map(String::from),
+            title: data
+                .get("title")
+                .and_then(|v| v.as_str())
+                .filter(|s| !s.is_empty())
+                .map(String::from),
+        }),
+        ActionResult::Fatal { code, .. } if code == "TAB_NOT_FOUND" => Some(ResponseContext {
+            session_id: cmd.session.clone(),
+            tab_id: None,
+            window_id: None,
+            url: None,
+            title: None,
+        }),
+        _ => None,
+    }
+}
+
+pub async fn execute(cmd: &Cmd, registry: &SharedRegistry) -> ActionResult {
+    let (cdp, native_id, url, title, previous) = {
+        let mut reg = registry.lock().await;
+        let Some(entry) = reg.get_mut(&cmd.session) else {
+            return ActionResult::fatal_with_hint(
+                "SESSION_NOT_FOUND",
+                format!("session '{}' not found", cmd.session),
+                "run `actionbook browser list-sessions` to see available sessions",
+            );
+        };
+        let Some(tab) = entry.tabs.iter().find(|t| t.id.0 == cmd.tab) else {
+            return ActionResult::fatal_with_hint(
+                "TAB_NOT_FOUND",
+                format!("tab '{}' not found in session '{}'", cmd.tab, cmd.session),
+                "run `actionbook browser list-tabs` to see available tabs",
+            );
+        };
+        let found = (tab.native_id.clone(), tab.url.clone(), tab.title.clone());
+        let previous = entry.active_tab_id().map(|t| t.0.clone());
+        entry.active_tab = Some(TabId(cmd.tab.clone()));
+        (entry.cdp.clone(), found.0, found.1, found.2, previous)
+    };
+
+    // Best effort: headless sessions have no window to raise.
+    if let Some(cdp) = cdp {
+        let _ = cdp
+            .execute_browser("Target.activateTarget", json!({ "targetId": native_id }))
+            .await;
+    }
+
+    ActionResult::ok(json!({
+        "tab_id": cmd.tab,
+        "previous_tab_id": previous,
+        "url": url,
+        "title": title,
+    }))
+}
+
+/// Rewrite `--tab` to a concrete tab ID before the command runs, so ref
+/// caches, cursor positions and the response context all see the real tab.
+///
+/// `--tab` is matched in order as: `active` for the session's active tab, a
+/// tab ID, a 1-based position in `list-tabs`, then a substring of exactly one
+/// tab's current URL.
+///
+/// Returns the resolved ID when the value was rewritten. Unknown sessions,
+/// tab-less sessions and values matching no tab are left alone for the
+/// command itself to report; an out-of-range position or a URL substring
+/// matching several tabs is an error.
+pub(crate) async fn resolve_tab(
+    action: &mut Action,
+    registry: &SharedRegistry,
+) -> Result<Option<String>, ActionResult> {
+    let Some((session, tab)) = action.tab_slot() else {
+        return Ok(None);
+    };
+    let (cdp, candidates) = {
+        let reg = registry.lock().await;
+        let Some(entry) = reg.get(session) else {
+            return Ok(None);
+        };
+        if tab == ACTIVE_TAB {
+            let Some(active) = entry.active_tab_id() else {
+                return Ok(None);
+            };
+            *tab = active.0.clone();
+            return Ok(Some(tab.clone()));
+        }
+        if entry.tabs.iter().any(|t| t.id.0 == *tab) {
+            return Ok(None);
+        }
+        if let Ok(position) = tab.parse::<usize>() {
+            let Some(found) = position.checked_sub(1).and_then(|i| entry.tabs.get(i)) else {
+                return Err(ActionResult::fatal_with_hint(
+                    "TAB_NOT_FOUND",
+                    format!(
+                        "session '{session}' has {} tab(s), no tab #{position}",
+                        entry.tabs.len()
+                    ),
+                    "tab positions start at 1; run `actionbook browser list-tabs` to see them",
+                ));
+            };
+            *tab = found.id.0.clone();
+            return Ok(Some(tab.clone()));
+        }
+        let Some(cdp) = entry.cdp.clone() else {
+            return Ok(None);
+        };
+        let candidates: Vec<(String, String)> = entry
+            .tabs
+            .iter()
+            .map(|t| (t.id.0.clone(), t.native_id.clone()))
+            .collect();
+        (cdp, candidates)
+    };
+
+    // The registry's URLs are only refreshed by list-tabs; ask each tab.
+    let mut matches = Vec::new();
+    for (id, native_id) in candidates {
+        if navigation::get_tab_url(&cdp, &native_id)
+            .await
+            .contains(tab.as_str())
+        {
+            matches.push(id);
+        }
+    }
+    match matches.as_slice() {
+        [] => Ok(None),
+        [only] => {
+            *tab = only.clone();
+            Ok(Some(only.clone()))
+        }
+        _ => Err(ActionResult::fatal_with_hint(
+            "INVALID_ARGUMENT",
+            format!(
+                "--tab '{tab}' matches the URLs of several tabs: {}",
+                matches.join(", ")
+            ),
+            "pass a tab ID or a longer part of the URL",
+        )),
+    }
+}
@@ -0,0 +1,213 @@
+use clap::Args;
+use serde::{Deserialize, Serialize};
+use serde_json::{Value, json};
+
+use crate::action_result::ActionResult;
+use crate::daemon::cdp_session::{cdp_error_to_result, get_cdp_and_target};
+use crate::daemon::registry::SharedRegistry;
+use crate::output::ResponseContext;
+
+/// Window state accepted by `Browser.setWindowBounds`.
+#[derive(Clone, Copy, Debug, Serialize, Deserialize, clap::ValueEnum, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum WindowState {
+    Normal,
+    Minimized,
+    Maximized,
+    Fullscreen,
+}
+
+impl WindowState {
+    fn as_cdp(self) -> &'static str {
+        match self {
+            Self::Normal => "normal",
+            Self::Minimized => "minimized",
+            Self::Maximized => "maximized",
+            Self::Fullscreen => "fullscreen",
+        }
+    }
+}
+
+/// Move, resize or maximize the browser window that holds a tab
+#[derive(Args, Debug, Clone, Serialize, Deserialize)]
+#[command(after_help = "\
+Examples:
+  actionbook browser window --session s1 --tab t1
+  actionbook browser window --session s1 --tab t1 --position 1920,0 --size 1280x800
+  actionbook browser window --session s1 --tab t1 --state maximized
+
+Without options, reports the current window bounds. Coordinates are in
+screen pixels across all displays, so a window on a secondary monitor to the
+right of a 1920px-wide primary starts at x = 1920. Set launch defaults with
+`browser start --window-position/--window-size` or `window_position` /
+`window_size` under [browser] in config.toml.")]
+pub struct Cmd {
+    /// Session ID
+    #[arg(long)]
+    #[serde(rename = "session_id")]
+    pub session: String,
+    /// Tab ID
+    #[arg(long)]
+    #[serde(rename = "tab_id")]
+    pub tab: String,
+    /// Top-left corner as X,Y
+    #[arg(long, value_name = "X,Y", allow_hyphen_values = true)]
+    #[serde(default)]
+    pub position: Option<String>,
+    /// Window size as WIDTHxHEIGHT
+    #[arg(long, value_name = "WxH")]
+    #[serde(default)]
+    pub size: Option<String>,
+    /// Window state
+    #[arg(long, value_enum, conflicts_with_all = ["position", "size"])]
+    #[serde(default)]
+    pub state: Option<WindowState>,
+}
+
+pub const COMMAND_NAME: &str = "browser window";
+
+/// Parse `X,Y` (negative values allowed for displays left of/above the primary).
+pub fn parse_position(raw: &str) -> Result<(i64, i64), String> {
+    let err = || format!("invalid window position '{raw}', expected X,Y (e.g. 1920,0)");
+    let (x, y) = raw.split_once(',').ok_or_else(err)?;
+    Ok((
+        x.trim().parse().map_err(|_| err())?,
+        y.trim().parse().map_err(|_| err())?,
+    ))
+}
+
+/// Parse `WIDTHxHEIGHT`; both sides must be positive.
+pub fn parse_size(raw: &str) -> Result<(u32, u32), String> {
+    let err = || format!("invalid window size '{raw}', expected WIDTHxHEIGHT (e.g. 1280x800)");
+    let (w, h) = raw.split_once(['x', 'X']).ok_or_else(err)?;
+    let w: u32 = w.trim().parse().map_err(|_| err())?;
+    let h: u32 = h.trim().parse().map_err(|_| err())?;
+    if w == 0 || h == 0 {
+        return Err(err());
+    }
+    Ok((w, h))
+}
+
+pub fn context(cmd: &Cmd, result: &ActionResult) -> Option<ResponseContext> {
+    if let ActionResult::Fatal { code, .. } = result
+        && code == "SESSION_NOT_FOUND"
+    {
+        return None;
+    }
+    let tab_id = if let ActionResult::Fatal { code, .. } = result
+        && code == "TAB_NOT_FOUND"
+    {
+        None
+    } else {
+        Some(cmd.tab.clone())
+    };
+    Some(ResponseContext {
+        session_id: cmd.session.clone(),
+        tab_id,
+        window_id: None,
+        url: None,
+        title: None,
+    })
+}
+
+pub async fn execute(cmd: &Cmd, registry: &SharedRegistry) -> ActionResult {
+    let position = match cmd.position.as_deref().map(parse_position).transpose() {
+        Ok(p) => p,
+        Err(e) => return ActionResult::fatal("INVALID_ARGUMENT", e),
+    };
+    let size = match cmd.size.as_deref().map(parse_size).transpose() {
+        Ok(s) => s,
+        Err(e) => return ActionResult::fatal("INVALID_ARGUMENT", e),
+    };
+
+    let (cdp, target_id) = match get_cdp_and_target(registry, &cmd.session, &cmd.tab).await {
+        Ok(v) => v,
+        Err(e) => return e,
+    };
+
+    let window = match cdp
+        .execute_browser(
+            "Browser.getWindowForTarget",
+            json!({ "targetId": target_id }),
+        )
+        .await
+    {
+        Ok(v) => v,
+        Err(e) => return cdp_error_to_result(e, "CDP_ERROR"),
+    };
+    let Some(window_id) = window.pointer("/result/windowId").and_then(|v| v.as_i64()) else {
+        return ActionResult::fatal(
+            "CDP_ERROR",
+            "Browser.getWindowForTarget returned no windowId",
+        );
+    };
+    let current_state = window
+        .pointer("/result/bounds/windowState")
+        .and_then(|v| v.as_str())
+        .unwrap_or("normal");
+
+    let mut updates: Vec<Value> = Vec::new();
+    if let Some(state) = cmd.state {
+        updates.push(json!({ "windowState": state.as_cdp() }));
+    }
+    if position.is_some() || size.is_some() {
+        // Chrome rejects bounds on a maximized/minimized/fullscreen window;
+        // restore it first.
+        if current_state != "normal" {
+            updates.push(json!({ "windowState": "normal" }));
+        }
+        let mut bounds = json!({});
+        if let Some((x, y)) = position {
+            bounds["left"] = json!(x);
+            bounds["top"] = json!(y);
+        }
+        if let Some((w, h)) = size {
+            bounds["width"] = json!(w);
+            bounds["height"] = json!(h);
+        }
+        updates.push(bounds);
+    }
+    for bounds in updates {
+        if let Err(e) = cdp
+            .execute_browser(
+                "Browser.setWindowBounds",
+                json!({ "windowId": window_id, "bounds": bounds }),
+            )
+            .await
+        {
+            return cdp_error_to_result(e, "CDP_ERROR");
+        }
+    }
+
+    let bounds = match cdp
+        .execute_browser("Browser.getWindowBounds", json!({ "windowId": window_id }))
+        .await
+    {
+        Ok(v) => v.pointer("/result/bounds").cloned().unwrap_or(Value::Null),
+        Err(e) => return cdp_error_to_result(e, "CDP_ERROR"),
+    };
+    ActionResult::ok(json!({
+        "window_id": window_id,
+        "left": bounds.get("left"),
+        "top": bounds.get("top"),
+        "width": bounds.get("width"),
+        "height": bounds.get("height"),
+        "state": bounds.get("windowState"),
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_position_and_size() {
+        assert_eq!(parse_position("1920,0"), Ok((1920, 0)));
+        assert_eq!(parse_position("-1280, 40"), Ok((-1280, 40)));
+        assert!(parse_position("1920x0").is_err());
+        assert_eq!(parse_size("1280x800"), Ok((1280, 800)));
+        assert_eq!(parse_size("1280X800"), Ok((1280, 800)));
+        assert!(parse_size("0x800").is_err());
+        assert!(parse_size("-5x800").is_err());
+    }
+}
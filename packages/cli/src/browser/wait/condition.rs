@@ -1,4 +1,4 @@
-use std::time::{Duration, Instant};
+use std::time::Instant;
 
 use clap::Args;
 use serde::{Deserialize, Serialize};
@@ -7,11 +7,11 @@ use serde_json::json;
 use crate::action_result::ActionResult;
 use crate::browser::navigation;
 use crate::daemon::cdp_session::get_cdp_and_target;
+use crate::daemon::progress::Reporter;
 use crate::daemon::registry::SharedRegistry;
 use crate::output::ResponseContext;
 
 const DEFAULT_TIMEOUT_MS: u64 = 30_000;
-const POLL_INTERVAL_MS: u64 = 100;
 
 /// Wait for a JavaScript expression to become truthy
 #[derive(Args, Debug, Clone, Serialize, Deserialize)]
@@ -32,6 +32,10 @@ pub struct Cmd {
     /// Timeout in milliseconds (default 30000)
     #[arg(long)]
     pub timeout: Option<u64>,
+    /// Poll every this many milliseconds (default: back off from 100 to 1000)
+    #[arg(long, value_name = "MS", value_parser = clap::value_parser!(u64).range(1..))]
+    #[serde(default)]
+    pub poll_interval: Option<u64>,
 }
 
 pub const COMMAND_NAME: &str = "browser wait condition";
@@ -75,8 +79,13 @@ pub async fn execute(cmd: &Cmd, registry: &SharedRegistry) -> ActionResult {
         Err(e) => return e,
     };
 
-    let timeout_ms = cmd.timeout.unwrap_or(DEFAULT_TIMEOUT_MS);
+    let timeout_ms = cmd
+        .timeout
+        .unwrap_or_else(|| crate::utils::ci::scale_timeout(DEFAULT_TIMEOUT_MS));
     let start = Instant::now();
+    let mut reporter = Reporter::new(start);
+    let mut backoff = super::Backoff::new(cmd.poll_interval);
+    let mut last_value = serde_json::Value::Null;
 
     loop {
         let resp = cdp
@@ -89,6 +98,12 @@ pub async fn execute(cmd: &Cmd, registry: &SharedRegistry) -> ActionResult {
 
         if let Ok(v) = resp {
             let result_val = v.pointer("/result/result/value").cloned();
+            let value = result_val.clone().unwrap_or_default();
+            // A changing value means the page is moving; poll quickly again.
+            if value != last_value {
+                backoff.reset();
+            }
+            last_value = value;
             let truthy = result_val
                 .as_ref()
                 .map(|rv| match rv {
@@ -118,6 +133,12 @@ pub async fn execute(cmd: &Cmd, registry: &SharedRegistry) -> ActionResult {
             }
         }
 
+        if reporter.due() {
+            let mut probe = super::progress_probe(&cdp, &target_id, None).await;
+            probe["last_value"] = last_value.clone();
+            reporter.report("condition", probe);
+        }
+
         let elapsed = start.elapsed().as_millis() as u64;
         if elapsed >= timeout_ms {
             return ActionResult::fatal_with_hint(
@@ -130,6 +151,6 @@ pub async fn execute(cmd: &Cmd, registry: &SharedRegistry) -> ActionResult {
             );
         }
 
-        tokio::time::sleep(Duration::from_millis(POLL_INTERVAL_MS)).await;
+        backoff.sleep(start, timeout_ms).await;
     }
 }
@@ -1,27 +1,35 @@
-use std::time::{Duration, Instant};
+use std::time::Instant;
 
 use clap::Args;
 use serde::{Deserialize, Serialize};
 use serde_json::json;
 
 use crate::action_result::ActionResult;
-use crate::browser::navigation;
+use crate::browser::{element, navigation};
 use crate::daemon::cdp_session::get_cdp_and_target;
+use crate::daemon::progress::Reporter;
 use crate::daemon::registry::SharedRegistry;
 use crate::output::ResponseContext;
 
 const DEFAULT_TIMEOUT_MS: u64 = 30_000;
-const POLL_INTERVAL_MS: u64 = 100;
 
 /// Wait for a CSS selector to appear in the DOM
 #[derive(Args, Debug, Clone, Serialize, Deserialize)]
 #[command(after_help = "\
 Examples:
   actionbook browser wait element '#loaded' --session s1 --tab t1 --timeout 5000
-  actionbook browser wait element '.spinner[hidden]' --session s1 --tab t1")]
+  actionbook browser wait element '.spinner[hidden]' --session s1 --tab t1
+  actionbook browser wait element '.spinner' --hidden --session s1 --tab t1
+
+With --hidden, waits until nothing matches the selector or the match is not
+visible (zero size, display: none or visibility: hidden).")]
 pub struct Cmd {
     /// Selector to wait for (CSS, XPath, or @ref)
     pub selector: String,
+    /// Wait for the element to be gone or hidden instead
+    #[arg(long)]
+    #[serde(default)]
+    pub hidden: bool,
     /// Session ID
     #[arg(long)]
     #[serde(rename = "session_id")]
@@ -33,6 +41,10 @@ pub struct Cmd {
     /// Timeout in milliseconds (default 30000)
     #[arg(long)]
     pub timeout: Option<u64>,
+    /// Poll every this many milliseconds (default: back off from 100 to 1000)
+    #[arg(long, value_name = "MS", value_parser = clap::value_parser!(u64).range(1..))]
+    #[serde(default)]
+    pub poll_interval: Option<u64>,
 }
 
 pub const COMMAND_NAME: &str = "browser wait element";
@@ -76,10 +88,28 @@ pub async fn execute(cmd: &Cmd, registry: &SharedRegistry) -> ActionResult {
         Err(e) => return e,
     };
 
-    let timeout_ms = cmd.timeout.unwrap_or(DEFAULT_TIMEOUT_MS);
+    let timeout_ms = cmd
+        .timeout
+        .unwrap_or_else(|| crate::utils::ci::scale_timeout(DEFAULT_TIMEOUT_MS));
     let selector_json = serde_json::to_string(&cmd.selector).unwrap_or_default();
-    let js = format!("!!document.querySelector({selector_json})");
+    let js = if cmd.hidden {
+        format!(
+            "(() => {{ try {{ const el = ({})({selector_json}, document); if (!el) return true; \
+             const r = el.getBoundingClientRect(); const s = getComputedStyle(el); \
+             return (r.width === 0 && r.height === 0) || s.display === 'none' || s.visibility === 'hidden'; \
+             }} catch (e) {{ return false; }} }})()",
+            element::DEEP_QUERY_JS
+        )
+    } else {
+        format!(
+            "(() => {{ try {{ return !!({})({selector_json}, document); }} catch (e) {{ return false; }} }})()",
+            element::DEEP_QUERY_JS
+        )
+    };
+    let state = if cmd.hidden { "hidden" } else { "attached" };
     let start = Instant::now();
+    let mut reporter = Reporter::new(start);
+    let mut backoff = super::Backoff::new(cmd.poll_interval);
 
     loop {
         let resp = cdp
@@ -103,25 +133,38 @@ pub async fn execute(cmd: &Cmd, registry: &SharedRegistry) -> ActionResult {
                     "kind": "element",
                     "satisfied": true,
                     "elapsed_ms": elapsed_ms,
-                    "observed_value": { "selector": cmd.selector },
+                    "observed_value": { "selector": cmd.selector, "state": state },
                     "__ctx_url": url,
                     "__ctx_title": title,
                 }));
             }
         }
 
+        if reporter.due() {
+            let probe = super::progress_probe(&cdp, &target_id, Some(&cmd.selector)).await;
+            reporter.report("element", probe);
+        }
+
         let elapsed = start.elapsed().as_millis() as u64;
         if elapsed >= timeout_ms {
-            return ActionResult::fatal_with_hint(
-                "TIMEOUT",
+            let message = if cmd.hidden {
+                format!(
+                    "element '{}' still visible after {}ms",
+                    cmd.selector, timeout_ms
+                )
+            } else {
                 format!(
                     "element '{}' not found within {}ms",
                     cmd.selector, timeout_ms
-                ),
+                )
+            };
+            return ActionResult::fatal_with_hint(
+                "TIMEOUT",
+                message,
                 "check selector or increase --timeout",
             );
         }
 
-        tokio::time::sleep(Duration::from_millis(POLL_INTERVAL_MS)).await;
+        backoff.sleep(start, timeout_ms).await;
     }
 }
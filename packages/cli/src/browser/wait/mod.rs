@@ -2,3 +2,110 @@ pub mod condition;
 pub mod element;
 pub mod navigation;
 pub mod network_idle;
+pub mod text;
+pub mod url;
+
+use std::time::{Duration, Instant};
+
+use serde_json::{Value, json};
+
+use crate::daemon::cdp_session::CdpSession;
+
+/// Page snapshot for `--stream` progress events: readyState, URL and, given
+/// a selector, how many elements currently match it.
+pub(crate) async fn progress_probe(
+    cdp: &CdpSession,
+    target_id: &str,
+    selector: Option<&str>,
+) -> Value {
+    let count_js = match selector {
+        Some(sel) => format!(
+            "(function() {{ try {{ return document.querySelectorAll({}).length; }} catch (e) {{ return null; }} }})()",
+            serde_json::to_string(sel).unwrap_or_default()
+        ),
+        None => "undefined".to_string(),
+    };
+    let js = format!(
+        "({{ ready_state: document.readyState, url: location.href, match_count: {count_js} }})"
+    );
+    cdp.execute_on_tab(
+        target_id,
+        "Runtime.evaluate",
+        json!({ "expression": js, "returnByValue": true }),
+    )
+    .await
+    .ok()
+    .and_then(|v| v.pointer("/result/result/value").cloned())
+    .unwrap_or_else(|| json!({}))
+}
+
+/// First gap between wait-loop polls.
+const BACKOFF_MIN_MS: u64 = 100;
+/// Longest gap the backoff grows to.
+const BACKOFF_MAX_MS: u64 = 1_000;
+
+/// Delay between wait-loop polls. Starts at 100ms and grows by half each
+/// poll up to 1s, so a long wait stops evaluating JS ten times a second;
+/// `reset` drops back to 100ms when the page shows activity. An explicit
+/// `--poll-interval` pins the delay instead.
+#[derive(Debug)]
+pub(crate) struct Backoff {
+    next_ms: u64,
+    fixed: bool,
+}
+
+impl Backoff {
+    pub(crate) fn new(poll_interval: Option<u64>) -> Self {
+        match poll_interval {
+            Some(ms) => Self {
+                next_ms: ms.max(1),
+                fixed: true,
+            },
+            None => Self {
+                next_ms: BACKOFF_MIN_MS,
+                fixed: false,
+            },
+        }
+    }
+
+    /// The delay before the next poll; advances the backoff.
+    pub(crate) fn next_delay(&mut self) -> Duration {
+        let ms = self.next_ms;
+        if !self.fixed {
+            self.next_ms = (ms + ms / 2).min(BACKOFF_MAX_MS);
+        }
+        Duration::from_millis(ms)
+    }
+
+    pub(crate) fn reset(&mut self) {
+        if !self.fixed {
+            self.next_ms = BACKOFF_MIN_MS;
+        }
+    }
+
+    /// Sleep until the next poll, but never past `start + timeout_ms` so the
+    /// final poll still lands on the deadline.
+    pub(crate) async fn sleep(&mut self, start: Instant, timeout_ms: u64) {
+        let remaining = Duration::from_millis(timeout_ms).saturating_sub(start.elapsed());
+        tokio::time::sleep(self.next_delay().min(remaining)).await;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn backoff_grows_to_a_second_and_resets() {
+        let mut b = Backoff::new(None);
+        let delays: Vec<u64> = (0..8).map(|_| b.next_delay().as_millis() as u64).collect();
+        assert_eq!(delays, [100, 150, 225, 337, 505, 757, 1000, 1000]);
+        b.reset();
+        assert_eq!(b.next_delay(), Duration::from_millis(100));
+
+        let mut fixed = Backoff::new(Some(250));
+        fixed.next_delay();
+        fixed.reset();
+        assert_eq!(fixed.next_delay(), Duration::from_millis(250));
+    }
+}
@@ -1,4 +1,4 @@
-use std::time::{Duration, Instant};
+use std::time::Instant;
 
 use clap::Args;
 use serde::{Deserialize, Serialize};
@@ -7,12 +7,10 @@ use serde_json::json;
 use crate::action_result::ActionResult;
 use crate::browser::navigation as nav_helpers;
 use crate::daemon::cdp_session::get_cdp_and_target;
+use crate::daemon::progress::Reporter;
 use crate::daemon::registry::SharedRegistry;
 use crate::output::ResponseContext;
 
-const DEFAULT_TIMEOUT_MS: u64 = 30_000;
-const POLL_INTERVAL_MS: u64 = 100;
-
 /// After detecting `readyState=complete` with a URL that differs from the
 /// registry baseline, require the URL to remain stable (same value, still
 /// complete) for this many milliseconds before accepting.  Short because when
@@ -51,9 +49,13 @@ pub struct Cmd {
     #[arg(long)]
     #[serde(rename = "tab_id")]
     pub tab: String,
-    /// Timeout in milliseconds (default 30000)
+    /// Timeout in milliseconds (default 30000, or `[browser] navigation_timeout_ms`)
     #[arg(long)]
     pub timeout: Option<u64>,
+    /// Poll every this many milliseconds (default: back off from 100 to 1000)
+    #[arg(long, value_name = "MS", value_parser = clap::value_parser!(u64).range(1..))]
+    #[serde(default)]
+    pub poll_interval: Option<u64>,
 }
 
 pub const COMMAND_NAME: &str = "browser wait navigation";
@@ -140,7 +142,9 @@ pub async fn execute(cmd: &Cmd, registry: &SharedRegistry) -> ActionResult {
         Err(e) => return e,
     };
 
-    let timeout_ms = cmd.timeout.unwrap_or(DEFAULT_TIMEOUT_MS);
+    let timeout_ms = cmd
+        .timeout
+        .unwrap_or_else(crate::config::navigation_timeout_ms);
     let start = Instant::now();
 
     // Read the tab URL recorded when the previous command completed.
@@ -176,8 +180,7 @@ pub async fn execute(cmd: &Cmd, registry: &SharedRegistry) -> ActionResult {
     while event_rx.try_recv().is_ok() {}
 
     let mut detector = NavigationDetector::new();
-    let mut poll_interval = tokio::time::interval(Duration::from_millis(POLL_INTERVAL_MS));
-    poll_interval.tick().await; // consume the immediate first tick
+    let mut backoff = super::Backoff::new(cmd.poll_interval);
 
     // Time-based stability tracker.  When readyState first becomes "complete" we
     // start the clock.  The required stability window depends on whether the current
@@ -186,6 +189,7 @@ pub async fn execute(cmd: &Cmd, registry: &SharedRegistry) -> ActionResult {
     // short confirmation (URL_STABILITY_MS).  Any intervening frameNavigated event or
     // non-complete readyState resets this.
     let mut stable_since: Option<(Instant, String)> = None;
+    let mut reporter = Reporter::new(start);
 
     loop {
         let elapsed = start.elapsed().as_millis() as u64;
@@ -204,13 +208,15 @@ pub async fn execute(cmd: &Cmd, registry: &SharedRegistry) -> ActionResult {
                     // Channel closed — session died; fall through to timeout.
                     continue;
                 }
-                // A new navigation started — reset stability tracking.
+                // A new navigation started — reset stability tracking and
+                // poll quickly while it loads.
                 stable_since = None;
+                backoff.reset();
                 detector.observe(NavigationSignal::FrameNavigated);
             }
 
             // Path B: polling fallback.
-            _ = poll_interval.tick() => {
+            _ = backoff.sleep(start, timeout_ms) => {
                 let resp = cdp
                     .execute_on_tab(
                         &target_id,
@@ -233,6 +239,14 @@ pub async fn execute(cmd: &Cmd, registry: &SharedRegistry) -> ActionResult {
                     .unwrap_or("")
                     .to_string();
 
+                if reporter.due() {
+                    reporter.report("navigation", json!({
+                        "url": current_url,
+                        "ready_state": ready_state,
+                        "frame_navigated": detector.frame_navigated_seen,
+                    }));
+                }
+
                 // Strong-signal path: in-watch event or mid-load caught.
                 if detector.observe(NavigationSignal::Poll {
                     url: current_url.clone(),
@@ -8,11 +8,11 @@ use serde_json::json;
 use crate::action_result::ActionResult;
 use crate::browser::navigation;
 use crate::daemon::cdp_session::get_cdp_and_target;
+use crate::daemon::progress::Reporter;
 use crate::daemon::registry::SharedRegistry;
 use crate::output::ResponseContext;
 
 const DEFAULT_TIMEOUT_MS: u64 = 30_000;
-const POLL_INTERVAL_MS: u64 = 100;
 /// Strict idle: zero in-flight requests for this long.
 const STRICT_IDLE_QUIET_MS: u64 = 500;
 /// Relaxed idle: fewer than RELAXED_MAX_REQUESTS new requests in the sliding
@@ -42,6 +42,10 @@ pub struct Cmd {
     /// Timeout in milliseconds (default 30000)
     #[arg(long)]
     pub timeout: Option<u64>,
+    /// Poll every this many milliseconds (default: back off from 100 to 1000)
+    #[arg(long, value_name = "MS", value_parser = clap::value_parser!(u64).range(1..))]
+    #[serde(default)]
+    pub poll_interval: Option<u64>,
 }
 
 pub const COMMAND_NAME: &str = "browser wait network-idle";
@@ -85,7 +89,9 @@ pub async fn execute(cmd: &Cmd, registry: &SharedRegistry) -> ActionResult {
         Err(e) => return e,
     };
 
-    let timeout_ms = cmd.timeout.unwrap_or(DEFAULT_TIMEOUT_MS);
+    let timeout_ms = cmd
+        .timeout
+        .unwrap_or_else(|| crate::utils::ci::scale_timeout(DEFAULT_TIMEOUT_MS));
     let start = Instant::now();
 
     // Resolve the CDP flat-session ID.  `attach()` already called `Network.enable`
@@ -136,11 +142,19 @@ pub async fn execute(cmd: &Cmd, registry: &SharedRegistry) -> ActionResult {
     let mut quiet_start: Option<Instant> = None;
     // Whether the current quiet window is running in relaxed mode.
     let mut quiet_is_relaxed = false;
+    let mut reporter = Reporter::new(start);
+    let mut backoff = super::Backoff::new(cmd.poll_interval);
 
     loop {
         // Read the live in-flight counter maintained by reader_loop.
         let pending = cdp.network_pending(&cdp_session_id).await;
 
+        // Network activity: keep polling quickly so the quiet window is
+        // measured from close to the last request.
+        if pending != prev_pending {
+            backoff.reset();
+        }
+
         // Track new request starts: any increase in `pending` means at least that
         // many requests were initiated since the last poll.
         if pending > prev_pending {
@@ -230,6 +244,14 @@ pub async fn execute(cmd: &Cmd, registry: &SharedRegistry) -> ActionResult {
             quiet_is_relaxed = false;
         }
 
+        if reporter.due() {
+            let mut probe = super::progress_probe(&cdp, &target_id, None).await;
+            probe["pending"] = json!(pending);
+            probe["recent_requests_10s"] = json!(recent_requests);
+            probe["page_loaded"] = json!(js_idle);
+            reporter.report("network-idle", probe);
+        }
+
         let elapsed = start.elapsed().as_millis() as u64;
         if elapsed >= timeout_ms {
             return ActionResult::fatal_with_hint(
@@ -239,6 +261,6 @@ pub async fn execute(cmd: &Cmd, registry: &SharedRegistry) -> ActionResult {
             );
         }
 
-        tokio::time::sleep(Duration::from_millis(POLL_INTERVAL_MS)).await;
+        backoff.sleep(start, timeout_ms).await;
     }
 }
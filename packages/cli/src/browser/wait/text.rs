@@ -0,0 +1,150 @@
+use std::time::Instant;
+
+use clap::Args;
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+
+use crate::action_result::ActionResult;
+use crate::browser::{element, navigation};
+use crate::daemon::cdp_session::get_cdp_and_target;
+use crate::daemon::progress::Reporter;
+use crate::daemon::registry::SharedRegistry;
+use crate::output::ResponseContext;
+
+const DEFAULT_TIMEOUT_MS: u64 = 30_000;
+
+/// Wait for text to appear on the page
+#[derive(Args, Debug, Clone, Serialize, Deserialize)]
+#[command(after_help = "\
+Examples:
+  actionbook browser wait text 'Order confirmed' --session s1 --tab t1
+  actionbook browser wait text 'Saved' --selector '#status' --session s1 --tab t1 --timeout 5000
+
+Matches a case-sensitive substring of the visible text (innerText) of the
+page body, or of the first element matching --selector.")]
+pub struct Cmd {
+    /// Text to wait for
+    pub text: String,
+    /// Only look inside this element (CSS selector)
+    #[arg(long)]
+    #[serde(default)]
+    pub selector: Option<String>,
+    /// Session ID
+    #[arg(long)]
+    #[serde(rename = "session_id")]
+    pub session: String,
+    /// Tab ID
+    #[arg(long)]
+    #[serde(rename = "tab_id")]
+    pub tab: String,
+    /// Timeout in milliseconds (default 30000)
+    #[arg(long)]
+    pub timeout: Option<u64>,
+    /// Poll every this many milliseconds (default: back off from 100 to 1000)
+    #[arg(long, value_name = "MS", value_parser = clap::value_parser!(u64).range(1..))]
+    #[serde(default)]
+    pub poll_interval: Option<u64>,
+}
+
+pub const COMMAND_NAME: &str = "browser wait text";
+
+pub fn context(cmd: &Cmd, result: &ActionResult) -> Option<ResponseContext> {
+    if let ActionResult::Fatal { code, .. } = result
+        && code == "SESSION_NOT_FOUND"
+    {
+        return None;
+    }
+    let tab_id = if let ActionResult::Fatal { code, .. } = result
+        && code == "TAB_NOT_FOUND"
+    {
+        None
+    } else {
+        Some(cmd.tab.clone())
+    };
+    let (url, title) = match result {
+        ActionResult::Ok { data } => (
+            data.get("__ctx_url")
+                .and_then(|v| v.as_str())
+                .map(String::from),
+            data.get("__ctx_title")
+                .and_then(|v| v.as_str())
+                .map(String::from),
+        ),
+        _ => (None, None),
+    };
+    Some(ResponseContext {
+        session_id: cmd.session.clone(),
+        tab_id,
+        window_id: None,
+        url,
+        title,
+    })
+}
+
+pub async fn execute(cmd: &Cmd, registry: &SharedRegistry) -> ActionResult {
+    let (cdp, target_id) = match get_cdp_and_target(registry, &cmd.session, &cmd.tab).await {
+        Ok(v) => v,
+        Err(e) => return e,
+    };
+
+    let timeout_ms = cmd
+        .timeout
+        .unwrap_or_else(|| crate::utils::ci::scale_timeout(DEFAULT_TIMEOUT_MS));
+    let text_json = serde_json::to_string(&cmd.text).unwrap_or_default();
+    let root = match &cmd.selector {
+        Some(sel) => format!(
+            "({})({}, document)",
+            element::DEEP_QUERY_JS,
+            serde_json::to_string(sel).unwrap_or_default()
+        ),
+        None => "document.body".to_string(),
+    };
+    let js = format!(
+        "(() => {{ try {{ const root = {root}; return !!root && (root.innerText || '').includes({text_json}); }} catch (e) {{ return false; }} }})()"
+    );
+    let start = Instant::now();
+    let mut reporter = Reporter::new(start);
+    let mut backoff = super::Backoff::new(cmd.poll_interval);
+
+    loop {
+        let resp = cdp
+            .execute_on_tab(
+                &target_id,
+                "Runtime.evaluate",
+                json!({ "expression": js, "returnByValue": true }),
+            )
+            .await;
+
+        if let Ok(v) = resp
+            && v.pointer("/result/result/value").and_then(|v| v.as_bool()) == Some(true)
+        {
+            let elapsed_ms = start.elapsed().as_millis() as u64;
+            let url = navigation::get_tab_url(&cdp, &target_id).await;
+            let title = navigation::get_tab_title(&cdp, &target_id).await;
+            return ActionResult::ok(json!({
+                "kind": "text",
+                "satisfied": true,
+                "elapsed_ms": elapsed_ms,
+                "observed_value": { "text": cmd.text, "selector": cmd.selector },
+                "__ctx_url": url,
+                "__ctx_title": title,
+            }));
+        }
+
+        if reporter.due() {
+            let probe = super::progress_probe(&cdp, &target_id, cmd.selector.as_deref()).await;
+            reporter.report("text", probe);
+        }
+
+        let elapsed = start.elapsed().as_millis() as u64;
+        if elapsed >= timeout_ms {
+            return ActionResult::fatal_with_hint(
+                "TIMEOUT",
+                format!("text '{}' did not appear within {}ms", cmd.text, timeout_ms),
+                "check the text (matching is case-sensitive) or increase --timeout",
+            );
+        }
+
+        backoff.sleep(start, timeout_ms).await;
+    }
+}
@@ -0,0 +1,142 @@
+use std::time::Instant;
+
+use clap::Args;
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+
+use crate::action_result::ActionResult;
+use crate::browser::navigation;
+use crate::daemon::cdp_session::get_cdp_and_target;
+use crate::daemon::progress::Reporter;
+use crate::daemon::registry::SharedRegistry;
+use crate::output::ResponseContext;
+
+const DEFAULT_TIMEOUT_MS: u64 = 30_000;
+
+/// Wait for the tab's URL to match a pattern
+#[derive(Args, Debug, Clone, Serialize, Deserialize)]
+#[command(after_help = "\
+Examples:
+  actionbook browser wait url /dashboard --session s1 --tab t1
+  actionbook browser wait url '^https://shop\\.test/orders/\\d+$' --session s1 --tab t1 --timeout 10000
+
+The pattern is a regular expression searched anywhere in the URL, so a plain
+path fragment works as a substring match. Unlike wait navigation, this also
+returns at once when the URL already matches, and follows history.pushState
+changes in single-page apps.")]
+pub struct Cmd {
+    /// Regular expression the URL must match
+    pub pattern: String,
+    /// Session ID
+    #[arg(long)]
+    #[serde(rename = "session_id")]
+    pub session: String,
+    /// Tab ID
+    #[arg(long)]
+    #[serde(rename = "tab_id")]
+    pub tab: String,
+    /// Timeout in milliseconds (default 30000)
+    #[arg(long)]
+    pub timeout: Option<u64>,
+    /// Poll every this many milliseconds (default: back off from 100 to 1000)
+    #[arg(long, value_name = "MS", value_parser = clap::value_parser!(u64).range(1..))]
+    #[serde(default)]
+    pub poll_interval: Option<u64>,
+}
+
+pub const COMMAND_NAME: &str = "browser wait url";
+
+pub fn context(cmd: &Cmd, result: &ActionResult) -> Option<ResponseContext> {
+    if let ActionResult::Fatal { code, .. } = result
+        && code == "SESSION_NOT_FOUND"
+    {
+        return None;
+    }
+    let tab_id = if let ActionResult::Fatal { code, .. } = result
+        && code == "TAB_NOT_FOUND"
+    {
+        None
+    } else {
+        Some(cmd.tab.clone())
+    };
+    let (url, title) = match result {
+        ActionResult::Ok { data } => (
+            data.get("__ctx_url")
+                .and_then(|v| v.as_str())
+                .map(String::from),
+            data.get("__ctx_title")
+                .and_then(|v| v.as_str())
+                .map(String::from),
+        ),
+        _ => (None, None),
+    };
+    Some(ResponseContext {
+        session_id: cmd.session.clone(),
+        tab_id,
+        window_id: None,
+        url,
+        title,
+    })
+}
+
+pub async fn execute(cmd: &Cmd, registry: &SharedRegistry) -> ActionResult {
+    let re = match regex::Regex::new(&cmd.pattern) {
+        Ok(re) => re,
+        Err(e) => {
+            return ActionResult::fatal("INVALID_ARGUMENT", format!("invalid URL pattern: {e}"));
+        }
+    };
+    let (cdp, target_id) = match get_cdp_and_target(registry, &cmd.session, &cmd.tab).await {
+        Ok(v) => v,
+        Err(e) => return e,
+    };
+
+    let timeout_ms = cmd
+        .timeout
+        .unwrap_or_else(|| crate::utils::ci::scale_timeout(DEFAULT_TIMEOUT_MS));
+    let start = Instant::now();
+    let mut reporter = Reporter::new(start);
+    let mut backoff = super::Backoff::new(cmd.poll_interval);
+    let mut last_url = String::new();
+
+    loop {
+        let url = navigation::get_tab_url(&cdp, &target_id).await;
+        if re.is_match(&url) {
+            let elapsed_ms = start.elapsed().as_millis() as u64;
+            let title = navigation::get_tab_title(&cdp, &target_id).await;
+            return ActionResult::ok(json!({
+                "kind": "url",
+                "satisfied": true,
+                "elapsed_ms": elapsed_ms,
+                "observed_value": url,
+                "__ctx_url": url,
+                "__ctx_title": title,
+            }));
+        }
+        // A URL change means navigation is under way; check again soon.
+        if url != last_url {
+            backoff.reset();
+            last_url = url;
+        }
+
+        if reporter.due() {
+            let probe = super::progress_probe(&cdp, &target_id, None).await;
+            reporter.report("url", probe);
+        }
+
+        let elapsed = start.elapsed().as_millis() as u64;
+        if elapsed >= timeout_ms {
+            return ActionResult::fatal_with_details(
+                "TIMEOUT",
+                format!(
+                    "URL did not match '{}' within {}ms (last: {last_url})",
+                    cmd.pattern, timeout_ms
+                ),
+                "check the pattern or increase --timeout",
+                json!({ "last_url": last_url }),
+            );
+        }
+
+        backoff.sleep(start, timeout_ms).await;
+    }
+}
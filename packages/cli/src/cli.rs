@@ -2,9 +2,14 @@ use clap::{Args, Parser, Subcommand};
 
 use crate::action::Action;
 use crate::action_result::ActionResult;
-use crate::browser::{cookies, interaction, navigation, observation, session, storage, tab, wait};
+use crate::audit;
+use crate::browser::{
+    cookies, downloads, indexeddb, interaction, navigation, observation, session, storage, tab,
+    wait,
+};
 use crate::output::ResponseContext;
 use crate::setup;
+use crate::utils::sink::Sink;
 
 #[derive(Parser, Debug)]
 #[command(
@@ -21,6 +26,14 @@ pub struct Cli {
     #[arg(long, global = true)]
     pub timeout: Option<u64>,
 
+    /// With --json, print progress events as JSON lines while a wait runs
+    #[arg(long, global = true)]
+    pub stream: bool,
+
+    /// CI defaults: headless, no spinners or colour, longer waits (auto-detected)
+    #[arg(long, global = true)]
+    pub ci: bool,
+
     /// API key for authenticated access
     #[arg(
         long,
@@ -38,6 +51,8 @@ pub struct Cli {
     pub command: Option<Commands>,
 }
 
+// Parsed once per process; boxing `Browser` would only add noise at every match.
+#[allow(clippy::large_enum_variant)]
 #[derive(Subcommand, Debug)]
 #[command(disable_help_subcommand = true)]
 pub enum Commands {
@@ -45,6 +60,15 @@ pub enum Commands {
     Search {
         /// Search keyword
         keyword: String,
+        /// Rank sites matching the page open in --session and --tab first
+        #[arg(long, requires_all = ["session", "tab"])]
+        from_page: bool,
+        /// Session whose page is used with --from-page
+        #[arg(long, requires = "from_page")]
+        session: Option<String>,
+        /// Tab whose page is used with --from-page
+        #[arg(long, requires = "from_page")]
+        tab: Option<String>,
     },
 
     /// Get detailed manual information for a site, group, or action
@@ -62,6 +86,39 @@ pub enum Commands {
     Get {
         /// Area ID (e.g., "airbnb.com:/:default")
         area_id: String,
+        /// Only these sections, comma-separated (e.g. "steps,selectors")
+        #[arg(long, value_delimiter = ',')]
+        sections: Vec<String>,
+        /// Trim the output to about this many tokens
+        #[arg(long, value_parser = clap::value_parser!(u32).range(1..))]
+        max_tokens: Option<u32>,
+    },
+
+    /// Report how a run of an action book went, so its content can improve
+    Report {
+        /// Area ID (e.g., "airbnb.com:/:default")
+        area_id: String,
+        /// Whether the action book worked
+        #[arg(long, value_parser = ["success", "failure"])]
+        outcome: String,
+        /// What went wrong or what was missing
+        #[arg(long)]
+        notes: Option<String>,
+        /// Attach an `actionbook run --report` file
+        #[arg(long, value_name = "FILE")]
+        run_report: Option<String>,
+    },
+
+    /// Validate a drafted action book and upload it
+    Publish {
+        /// Draft file (.json, .yaml or .yml): a run script with an area_id
+        draft: String,
+        /// Source ID to publish under
+        #[arg(long)]
+        source: Option<String>,
+        /// Only validate the draft; print every problem found
+        #[arg(long)]
+        dry_run: bool,
     },
 
     /// Browser automation commands
@@ -79,6 +136,45 @@ pub enum Commands {
         #[command(subcommand)]
         command: ExtensionCommands,
     },
+    /// Inspect the audit log of performed browser actions
+    Audit {
+        #[command(subcommand)]
+        command: AuditCommands,
+    },
+    /// Show opt-in local usage stats, or share them with `stats upload`
+    Stats {
+        #[command(subcommand)]
+        command: Option<StatsCommands>,
+    },
+    /// Serve the browser commands as a local HTTP + JSON API
+    Serve {
+        /// Port to listen on
+        #[arg(long, default_value_t = crate::serve::DEFAULT_PORT)]
+        port: u16,
+        /// Address to bind; anything but loopback requires --token
+        #[arg(long, default_value = "127.0.0.1")]
+        host: String,
+        /// Require `Authorization: Bearer <token>` on every request
+        #[arg(long, env = "ACTIONBOOK_SERVE_TOKEN", hide_env_values = true)]
+        token: Option<String>,
+    },
+    /// Inspect recorded sessions
+    Session {
+        #[command(subcommand)]
+        command: SessionCommands,
+    },
+    /// Run a YAML or JSON script of browser steps
+    Run(crate::commands::run::Cmd),
+    /// Store per-domain logins for `login` steps
+    Auth {
+        #[command(subcommand)]
+        command: AuthCommands,
+    },
+    /// Convert between run scripts and other automation tools
+    Flow {
+        #[command(subcommand)]
+        command: FlowCommands,
+    },
     /// Interactive configuration wizard
     Setup(setup::Cmd),
     /// Show help
@@ -92,12 +188,24 @@ pub enum Commands {
 #[derive(Subcommand, Debug)]
 #[command(disable_help_subcommand = true)]
 pub enum DaemonCommands {
+    /// Start the daemon now instead of on the first browser command.
+    ///
+    /// The daemon keeps sessions and their CDP connections open between CLI
+    /// calls; starting it ahead of time takes its launch off the first
+    /// command's latency. A no-op when it is already running.
+    Start,
+    /// Show whether the daemon is running, its pid, build and session count
+    Status,
     /// Stop the running daemon. The next CLI call will auto-spawn a fresh one.
     ///
     /// Use this to recover from a stuck bridge (e.g. `BRIDGE_BIND_FAILED`
     /// after the holding process has been freed) without manually finding
     /// the daemon pid.
     Restart,
+    /// Stop the running daemon gracefully, closing its sessions.
+    ///
+    /// The extension bridge runs inside the daemon, so this also stops it.
+    Stop,
 }
 
 #[derive(Subcommand, Debug)]
@@ -107,6 +215,8 @@ pub enum ExtensionCommands {
     Status,
     /// Ping the extension bridge and measure RTT
     Ping,
+    /// Show the connected extension's version and protocol compatibility
+    Version(ExtensionVersionArgs),
     /// Show extension install path and installed status
     Path,
     /// Install the Actionbook extension
@@ -115,6 +225,129 @@ pub enum ExtensionCommands {
     Uninstall,
 }
 
+/// Audit log readers. Records are written by the daemon for every action.
+#[derive(Subcommand, Debug)]
+#[command(disable_help_subcommand = true)]
+pub enum AuditCommands {
+    /// Show the most recent audit records
+    Tail {
+        /// Number of records to show
+        #[arg(short = 'n', long, default_value_t = 20)]
+        lines: usize,
+    },
+    /// Search audit records by text and filters
+    Search(audit::SearchArgs),
+}
+
+#[derive(Subcommand, Debug)]
+#[command(disable_help_subcommand = true)]
+pub enum StatsCommands {
+    /// Start recording usage stats locally
+    Enable,
+    /// Stop recording usage stats (recorded counters are kept)
+    Disable,
+    /// Delete the recorded counters
+    Reset,
+    /// Send the recorded counters to the Actionbook API
+    Upload {
+        /// Clear the local counters after a successful upload
+        #[arg(long)]
+        reset: bool,
+    },
+}
+
+/// Per-domain credentials, kept in the OS keyring. Run scripts use them with
+/// a `login: <domain>` step.
+#[derive(Subcommand, Debug)]
+#[command(disable_help_subcommand = true)]
+pub enum AuthCommands {
+    /// Store the login for a domain (replacing any existing one)
+    #[command(after_help = "\
+Examples:
+  EX_PASS=... actionbook auth add example.com --user alice --pass-env EX_PASS
+  pass show example | actionbook auth add example.com --user alice --pass-stdin
+  actionbook auth add example.com --user alice --pass-env EX_PASS --totp-env EX_TOTP
+
+The password and TOTP secret go to the macOS keychain or the Secret Service
+(secret-tool). Without either, or with ACTIONBOOK_CREDENTIAL_STORE=file, they
+are kept in ~/.actionbook/auth/secrets.json, readable by the owner only.")]
+    Add {
+        /// Domain the login is for; subdomains fall back to it
+        domain: String,
+        /// Username or email
+        #[arg(long)]
+        user: String,
+        /// Environment variable holding the password
+        #[arg(long, value_name = "VAR", required_unless_present = "pass_stdin")]
+        pass_env: Option<String>,
+        /// Read the password from the first line of stdin
+        #[arg(long, conflicts_with = "pass_env")]
+        pass_stdin: bool,
+        /// Base32 TOTP secret for one-time codes
+        #[arg(long, value_name = "BASE32")]
+        totp_secret: Option<String>,
+        /// Environment variable holding the base32 TOTP secret
+        #[arg(long, value_name = "VAR", conflicts_with = "totp_secret")]
+        totp_env: Option<String>,
+    },
+    /// List stored logins (never their secrets)
+    List,
+    /// Delete the login for a domain
+    Remove {
+        /// Domain to forget
+        domain: String,
+    },
+    /// Print the current TOTP code for a domain
+    Totp {
+        /// Domain whose code to generate
+        domain: String,
+    },
+}
+
+/// Offline tools that convert run scripts to and from other tools' scripts.
+#[derive(Subcommand, Debug)]
+#[command(disable_help_subcommand = true)]
+pub enum FlowCommands {
+    /// Convert a Playwright or Puppeteer script into a run script
+    Import(crate::commands::flow_import::Cmd),
+    /// Convert a run script into a Playwright test or Puppeteer script
+    Export(crate::commands::flow_export::Cmd),
+}
+
+/// Offline tools for sessions recorded with `browser start --record-session`.
+#[derive(Subcommand, Debug)]
+#[command(disable_help_subcommand = true)]
+pub enum SessionCommands {
+    /// Step through a recorded session: list its events, or show one
+    #[command(after_help = "\
+Examples:
+  actionbook session replay ./run1
+  actionbook session replay ./run1 --event 3")]
+    Replay {
+        /// Recording directory
+        dir: String,
+        /// Show a single event (its seq number) with its artifact
+        #[arg(long)]
+        event: Option<u64>,
+    },
+}
+
+#[derive(Args, Debug, Clone)]
+#[command(after_help = "\
+Examples:
+  actionbook extension version
+  actionbook extension version --update
+
+Asks the connected extension for its version and capabilities and checks its
+protocol against the one this CLI requires. With --update, an incompatible or
+older extension is reinstalled from the copy bundled with this CLI; reload it
+at chrome://extensions/ afterwards.")]
+pub struct ExtensionVersionArgs {
+    /// Reinstall the bundled extension if the current one is incompatible or older
+    #[arg(long)]
+    pub update: bool,
+}
+
 #[derive(Args, Debug, Clone)]
 pub struct ExtensionInstallArgs {
     /// Force overwrite of an existing installation
@@ -133,6 +366,8 @@ pub struct TabArgs {
     pub tab: String,
 }
 
+// Parsed once per process, like `Commands`; `Start` is the large variant.
+#[allow(clippy::large_enum_variant)]
 #[derive(Subcommand, Debug)]
 #[command(disable_help_subcommand = true)]
 pub enum BrowserCommands {
@@ -151,6 +386,15 @@ pub enum BrowserCommands {
     Close(session::close::Cmd),
     /// Restart a session
     Restart(session::restart::Cmd),
+    /// Emulate a mobile or tablet device (or, with `network`, network
+    /// conditions) on every tab of a session
+    #[command(args_conflicts_with_subcommands = true, subcommand_negates_reqs = true)]
+    Emulate {
+        #[command(flatten)]
+        device: Option<session::emulate::Cmd>,
+        #[command(subcommand)]
+        command: Option<EmulateCommands>,
+    },
 
     // ── Tab management ─────────────────────────────────────────
     /// List tabs in a session
@@ -163,6 +407,10 @@ pub enum BrowserCommands {
     BatchNewTab(tab::batch_open::Cmd),
     /// Close a tab
     CloseTab(tab::close::Cmd),
+    /// Make a tab the session's active tab
+    Switch(tab::switch::Cmd),
+    /// Move, resize or maximize a tab's browser window
+    Window(tab::window::Cmd),
 
     // ── Navigation ─────────────────────────────────────────────
     /// Navigate to URL
@@ -182,6 +430,10 @@ Examples:
 Examples:
   actionbook browser reload --session s1 --tab t1")]
     Reload(TabArgs),
+    /// Fetch a URL from inside the page (cookies and auth apply)
+    Fetch(navigation::fetch::Cmd),
+    /// Navigate to a URL and report every redirect hop
+    TraceRedirects(navigation::trace_redirects::Cmd),
 
     // ── Observation ────────────────────────────────────────────
     /// Capture accessibility snapshots for multiple tabs
@@ -190,6 +442,18 @@ Examples:
     Snapshot(observation::snapshot::Cmd),
     /// Get current page title
     Title(observation::title::Cmd),
+    /// Read page metadata (description, canonical, OpenGraph, JSON-LD)
+    Meta(observation::meta::Cmd),
+    /// Show the main document's HTTP status, headers, redirects and timing
+    Response(observation::response::Cmd),
+    /// Check page text, URL or elements; fail (non-zero exit) when a check does not hold
+    Assert(observation::assert::Cmd),
+    /// Discover (and optionally fetch) RSS/Atom/JSON feeds
+    Feeds(observation::feeds::Cmd),
+    /// List page images with URL, alt and size (optionally download them)
+    Images(observation::images::Cmd),
+    /// Extract a <table> as CSV or JSON (spans expanded, links listed)
+    Table(observation::table::Cmd),
     /// Get current page URL
     Url(observation::url::Cmd),
     /// Get viewport dimensions
@@ -200,6 +464,8 @@ Examples:
     Text(observation::text::Cmd),
     /// Read element value
     Value(observation::value::Cmd),
+    /// List the options of a <select> element
+    Options(observation::options::Cmd),
     /// Read a named element attribute
     Attr(observation::attr::Cmd),
     /// Read all attributes on an element
@@ -210,8 +476,15 @@ Examples:
     Styles(observation::styles::Cmd),
     /// Describe element properties and context
     Describe(observation::describe::Cmd),
-    /// Get element state
-    State(observation::state::Cmd),
+    /// Get element state (or, with `bundle` / `import`, save and load a
+    /// session's login state)
+    #[command(args_conflicts_with_subcommands = true, subcommand_negates_reqs = true)]
+    State {
+        #[command(flatten)]
+        element: Option<observation::state::Cmd>,
+        #[command(subcommand)]
+        command: Option<StateCommands>,
+    },
     /// Query elements with cardinality constraints
     Query(observation::query::Cmd),
     /// Inspect element at coordinates
@@ -221,6 +494,8 @@ Examples:
 Examples:
   actionbook browser pdf /tmp/page.pdf --session s1 --tab t1")]
     Pdf(observation::pdf::Cmd),
+    /// Archive page as MHTML or self-contained HTML
+    Save(observation::save::Cmd),
     /// Get browser console or error logs
     Logs {
         #[command(subcommand)]
@@ -244,6 +519,13 @@ Examples:
         command: CookiesCommands,
     },
 
+    // ── Downloads ──────────────────────────────────────────────
+    /// List downloads or wait for one to finish
+    Downloads {
+        #[command(subcommand)]
+        command: DownloadsCommands,
+    },
+
     // ── Storage ────────────────────────────────────────────────
     /// Manage local storage (window.localStorage)
     #[command(name = "local-storage")]
@@ -257,6 +539,12 @@ Examples:
         #[command(subcommand)]
         command: StorageSubCommands,
     },
+    /// Inspect IndexedDB databases
+    #[command(name = "indexeddb")]
+    IndexedDb {
+        #[command(subcommand)]
+        command: IndexedDbCommands,
+    },
 
     // ── Wait ───────────────────────────────────────────────────
     /// Wait for a browser condition
@@ -274,8 +562,15 @@ Examples:
     BatchClick(interaction::batch_click::Cmd),
     /// Hover over an element
     Hover(interaction::hover::Cmd),
+    /// Hover a menu trigger, then click an item in the submenu it opens
+    Menu(interaction::menu::Cmd),
+    /// Pause automation while a human completes a step in the browser
+    Handoff(interaction::handoff::Cmd),
     /// Focus an element
     Focus(interaction::focus::Cmd),
+    /// Press Tab until an element has keyboard focus
+    #[command(name = "tabto")]
+    TabTo(interaction::tabto::Cmd),
     /// Press a key or key combination
     Press(interaction::press::Cmd),
     /// Fill an input field
@@ -284,6 +579,10 @@ Examples:
     Type(interaction::type_text::Cmd),
     /// Select a value from a dropdown
     Select(interaction::select::Cmd),
+    /// Set a date field (native input or JS datepicker)
+    Date(interaction::date::Cmd),
+    /// Set a slider or range input to a value
+    Slide(interaction::slide::Cmd),
     /// Drag an element to a target
     Drag(interaction::drag::Cmd),
     /// Upload files to a file input
@@ -294,6 +593,69 @@ Examples:
     CursorPosition(interaction::cursor_position::Cmd),
     /// Scroll the page or a container
     Scroll(interaction::scroll::Cmd),
+    /// Tap an element or point with a touch
+    Tap(interaction::touch::TapCmd),
+    /// Swipe a finger across the page or an element
+    Swipe(interaction::touch::SwipeCmd),
+    /// Pinch two fingers together or apart
+    Pinch(interaction::touch::PinchCmd),
+    /// Click by colour or draw paths on a canvas
+    Canvas {
+        #[command(subcommand)]
+        command: CanvasCommands,
+    },
+    /// Record user input as a run script (start / stop)
+    Record {
+        #[command(subcommand)]
+        command: RecordCommands,
+    },
+    /// Record the tab as a video (start / stop)
+    #[command(name = "record-video")]
+    RecordVideo {
+        #[command(subcommand)]
+        command: RecordVideoCommands,
+    },
+    /// Record a performance trace of the session (start / stop)
+    Trace {
+        #[command(subcommand)]
+        command: TraceCommands,
+    },
+}
+
+#[derive(Subcommand, Debug, Clone)]
+#[command(disable_help_subcommand = true)]
+pub enum CanvasCommands {
+    /// Click where the canvas shows a colour
+    ClickColor(interaction::canvas::ClickColorCmd),
+    /// Press, move through a path of points, and release
+    Draw(interaction::canvas::DrawCmd),
+}
+
+#[derive(Subcommand, Debug, Clone)]
+#[command(disable_help_subcommand = true)]
+pub enum RecordCommands {
+    /// Start recording the tab
+    Start(interaction::record::StartCmd),
+    /// Stop recording and write the script
+    Stop(interaction::record::StopCmd),
+}
+
+#[derive(Subcommand, Debug, Clone)]
+#[command(disable_help_subcommand = true)]
+pub enum RecordVideoCommands {
+    /// Start recording the tab as a video
+    Start(observation::record_video::StartCmd),
+    /// Stop recording and encode the video
+    Stop(observation::record_video::StopCmd),
+}
+
+#[derive(Subcommand, Debug, Clone)]
+#[command(disable_help_subcommand = true)]
+pub enum TraceCommands {
+    /// Start tracing the session
+    Start(observation::trace::StartCmd),
+    /// Stop tracing and write the trace file
+    Stop(observation::trace::StopCmd),
 }
 
 #[derive(Subcommand, Debug)]
@@ -307,7 +669,12 @@ pub enum WaitCommands {
     #[command(name = "network-idle")]
     NetworkIdle(wait::network_idle::Cmd),
     /// Wait for a JavaScript expression to become truthy
+    #[command(visible_alias = "fn")]
     Condition(wait::condition::Cmd),
+    /// Wait for text to appear on the page (or in an element)
+    Text(wait::text::Cmd),
+    /// Wait for the URL to match a regular expression
+    Url(wait::url::Cmd),
 }
 
 #[derive(Subcommand, Debug)]
@@ -331,6 +698,11 @@ pub enum NetworkCommands {
         #[command(subcommand)]
         command: HarCommands,
     },
+    /// Request routes: block, mock, or rewrite (add / list / remove)
+    Route {
+        #[command(subcommand)]
+        command: RouteCommands,
+    },
 }
 
 #[derive(Subcommand, Debug, Clone)]
@@ -342,6 +714,42 @@ pub enum HarCommands {
     Stop(observation::network_har::StopCmd),
 }
 
+#[derive(Subcommand, Debug, Clone)]
+#[command(disable_help_subcommand = true)]
+pub enum RouteCommands {
+    /// Block, mock, or rewrite requests matching a URL pattern
+    Add(observation::network_route::AddCmd),
+    /// List the tab's routes
+    List(observation::network_route::ListCmd),
+    /// Remove a route, or all of them
+    Remove(observation::network_route::RemoveCmd),
+}
+
+#[derive(Subcommand, Debug)]
+#[command(disable_help_subcommand = true)]
+pub enum DownloadsCommands {
+    /// List the session's downloads
+    List(downloads::list::Cmd),
+    /// Wait for a download to finish
+    Wait(downloads::wait::Cmd),
+}
+
+#[derive(Subcommand, Debug)]
+#[command(disable_help_subcommand = true)]
+pub enum EmulateCommands {
+    /// Throttle or cut the network of every tab of a session
+    Network(session::emulate_network::Cmd),
+}
+
+#[derive(Subcommand, Debug)]
+#[command(disable_help_subcommand = true)]
+pub enum StateCommands {
+    /// Write the session's cookies, localStorage and fingerprint to a file
+    Bundle(session::state_bundle::BundleCmd),
+    /// Load a bundle into the session
+    Import(session::state_bundle::ImportCmd),
+}
+
 #[derive(Subcommand, Debug)]
 #[command(disable_help_subcommand = true)]
 pub enum CookiesCommands {
@@ -355,6 +763,19 @@ pub enum CookiesCommands {
     Delete(cookies::delete::Cmd),
     /// Clear cookies (optionally filtered by domain)
     Clear(cookies::clear::Cmd),
+    /// Export cookies to a JSON or Netscape cookies.txt file
+    Export(cookies::export::Cmd),
+    /// Import cookies from a JSON or Netscape cookies.txt file
+    Import(cookies::import::Cmd),
+}
+
+#[derive(Subcommand, Debug)]
+#[command(disable_help_subcommand = true)]
+pub enum IndexedDbCommands {
+    /// List databases and object stores of the tab's origin
+    List(indexeddb::list::Cmd),
+    /// Dump the entries of an object store
+    Dump(indexeddb::dump::Cmd),
 }
 
 #[derive(Subcommand, Debug, Clone)]
@@ -392,14 +813,17 @@ Examples:
 
 Removes the key entirely. No-op if the key does not exist.")]
     Delete(StorageKeyArgs),
-    /// Clear the value for a key
+    /// Clear a key, or every entry when no key is given
     #[command(after_help = "\
 Examples:
   actionbook browser local-storage clear cache_key --session s1 --tab t1
   actionbook browser session-storage clear pref --session s1 --tab t1
+  actionbook browser local-storage clear --session s1 --tab t1
 
-Removes the key from storage. Returns affected count (1 if existed, 0 if not).")]
-    Clear(StorageKeyArgs),
+Removes the key from storage. Returns affected count (1 if existed, 0 if not).
+Without a key, empties the storage object of the tab's origin and returns the
+number of entries removed.")]
+    Clear(StorageClearArgs),
 }
 
 #[derive(Args, Debug, Clone)]
@@ -419,6 +843,15 @@ pub struct StorageKeyArgs {
     pub tab: String,
 }
 
+#[derive(Args, Debug, Clone)]
+pub struct StorageClearArgs {
+    pub key: Option<String>,
+    #[arg(long)]
+    pub session: String,
+    #[arg(long)]
+    pub tab: String,
+}
+
 #[derive(Args, Debug, Clone)]
 pub struct StorageSetArgs {
     pub key: String,
@@ -429,20 +862,63 @@ pub struct StorageSetArgs {
     pub tab: String,
 }
 
+/// How much longer than `goto`'s navigation timeout the client waits, so the
+/// daemon's TIMEOUT, which names the stage that stalled, arrives first.
+const NAVIGATION_REPLY_GRACE_MS: u64 = 2000;
+
 impl BrowserCommands {
+    /// Hand the global `--timeout` to commands that bound their own work with
+    /// it. Returns how long the client should wait for the daemon's reply.
+    pub fn apply_timeout(&mut self, timeout_ms: Option<u64>) -> Option<u64> {
+        match self {
+            Self::Goto(cmd) => {
+                cmd.timeout = timeout_ms;
+                timeout_ms.map(|ms| ms.saturating_add(NAVIGATION_REPLY_GRACE_MS))
+            }
+            _ => timeout_ms,
+        }
+    }
+
     /// Convert to wire Action. Returns None for unimplemented commands.
     pub fn to_action(&self) -> Option<Action> {
         Some(match self {
             Self::Help => return None,
-            Self::Start(cmd) => Action::StartSession(cmd.clone()),
+            Self::Start(cmd) => {
+                // Same CWD caveat as `network har stop --out`: resolve the
+                // recording directory here, not in the daemon.
+                let mut cmd = cmd.clone();
+                if let Some(ref p) = cmd.record_session
+                    && let Ok(abs) = std::path::absolute(std::path::Path::new(p))
+                {
+                    cmd.record_session = Some(abs.to_string_lossy().into_owned());
+                }
+                if let Some(ref p) = cmd.download_dir
+                    && let Ok(abs) = std::path::absolute(std::path::Path::new(p))
+                {
+                    cmd.download_dir = Some(abs.to_string_lossy().into_owned());
+                }
+                for p in &mut cmd.adblock_list {
+                    if let Ok(abs) = std::path::absolute(std::path::Path::new(p.as_str())) {
+                        *p = abs.to_string_lossy().into_owned();
+                    }
+                }
+                Action::StartSession(cmd)
+            }
             Self::ListSessions(cmd) => Action::ListSessions(cmd.clone()),
             Self::Status(cmd) => Action::SessionStatus(cmd.clone()),
             Self::Close(cmd) => Action::Close(cmd.clone()),
             Self::Restart(cmd) => Action::Restart(cmd.clone()),
+            Self::Emulate { device, command } => match (device, command) {
+                (_, Some(EmulateCommands::Network(cmd))) => Action::EmulateNetwork(cmd.clone()),
+                (Some(cmd), None) => Action::Emulate(cmd.clone()),
+                (None, None) => unreachable!("clap requires a device command or a subcommand"),
+            },
             Self::ListTabs(cmd) => Action::ListTabs(cmd.clone()),
             Self::NewTab(cmd) => Action::NewTab(cmd.clone()),
             Self::BatchNewTab(cmd) => Action::BatchOpen(cmd.clone()),
             Self::CloseTab(cmd) => Action::CloseTab(cmd.clone()),
+            Self::Switch(cmd) => Action::Switch(cmd.clone()),
+            Self::Window(cmd) => Action::Window(cmd.clone()),
             Self::Goto(cmd) => Action::Goto(cmd.clone()),
             Self::Back(a) => Action::Back(navigation::back::Cmd {
                 session: a.session.clone(),
@@ -456,29 +932,118 @@ impl BrowserCommands {
                 session: a.session.clone(),
                 tab: a.tab.clone(),
             }),
+            Self::Fetch(cmd) => {
+                // Same CWD caveat as `network har stop --out`.
+                let mut cmd = cmd.clone();
+                if let Some(out) = &cmd.output
+                    && let Ok(abs) = std::path::absolute(std::path::Path::new(out))
+                {
+                    cmd.output = Some(abs.to_string_lossy().into_owned());
+                }
+                Action::Fetch(cmd)
+            }
+            Self::TraceRedirects(cmd) => Action::TraceRedirects(cmd.clone()),
             Self::BatchSnapshot(cmd) => Action::BatchSnapshot(cmd.clone()),
             Self::Snapshot(cmd) => Action::Snapshot(cmd.clone()),
             Self::Title(cmd) => Action::Title(cmd.clone()),
+            Self::Meta(cmd) => Action::Meta(cmd.clone()),
+            Self::Response(cmd) => Action::Response(cmd.clone()),
+            Self::Assert(cmd) => Action::Assert(cmd.clone()),
+            Self::Feeds(cmd) => Action::Feeds(cmd.clone()),
+            Self::Images(cmd) => {
+                // Same CWD caveat as `network har stop --out`.
+                let mut cmd = cmd.clone();
+                if let Some(dir) = &cmd.download
+                    && let Ok(abs) = std::path::absolute(std::path::Path::new(dir))
+                {
+                    cmd.download = Some(abs.to_string_lossy().into_owned());
+                }
+                Action::Images(cmd)
+            }
+            Self::Table(cmd) => {
+                let mut cmd = cmd.clone();
+                if let Some(out) = &cmd.out
+                    && let Ok(abs) = std::path::absolute(std::path::Path::new(out))
+                {
+                    cmd.out = Some(abs.to_string_lossy().into_owned());
+                }
+                if let Some(Ok(mut target)) = cmd.sink.as_deref().map(str::parse::<Sink>)
+                    && let Ok(abs) = std::path::absolute(std::path::Path::new(&target.path))
+                {
+                    target.path = abs.to_string_lossy().into_owned();
+                    cmd.sink = Some(target.to_string());
+                }
+                Action::Table(cmd)
+            }
             Self::Url(cmd) => Action::Url(cmd.clone()),
             Self::Viewport(cmd) => Action::Viewport(cmd.clone()),
             Self::Html(cmd) => Action::Html(cmd.clone()),
             Self::Text(cmd) => Action::Text(cmd.clone()),
             Self::Value(cmd) => Action::Value(cmd.clone()),
+            Self::Options(cmd) => Action::Options(cmd.clone()),
             Self::Attr(cmd) => Action::Attr(cmd.clone()),
             Self::Attrs(cmd) => Action::Attrs(cmd.clone()),
             Self::Box(cmd) => Action::Box(cmd.clone()),
             Self::Styles(cmd) => Action::Styles(cmd.clone()),
             Self::Describe(cmd) => Action::Describe(cmd.clone()),
-            Self::State(cmd) => Action::State(cmd.clone()),
+            Self::State { element, command } => match (element, command) {
+                // The daemon reads and writes the bundle and has its own
+                // environment, so the path is made absolute and the password
+                // read here.
+                (_, Some(StateCommands::Bundle(cmd))) => {
+                    let mut cmd = cmd.clone();
+                    if let Ok(abs) = std::path::absolute(std::path::Path::new(&cmd.out)) {
+                        cmd.out = abs.to_string_lossy().into_owned();
+                    }
+                    cmd.password = std::env::var(&cmd.password_env).ok();
+                    Action::StateBundle(cmd)
+                }
+                (_, Some(StateCommands::Import(cmd))) => {
+                    let mut cmd = cmd.clone();
+                    if let Ok(abs) = std::path::absolute(std::path::Path::new(&cmd.file)) {
+                        cmd.file = abs.to_string_lossy().into_owned();
+                    }
+                    cmd.password = std::env::var(&cmd.password_env).ok();
+                    Action::StateImport(cmd)
+                }
+                (Some(cmd), None) => Action::State(cmd.clone()),
+                (None, None) => unreachable!("clap requires a selector or a subcommand"),
+            },
             Self::Query(cmd) => Action::Query(cmd.clone()),
             Self::InspectPoint(cmd) => Action::InspectPoint(cmd.clone()),
             Self::Pdf(cmd) => Action::Pdf(cmd.clone()),
+            Self::Save(cmd) => {
+                // Same CWD caveat as `network har stop --out`.
+                let mut cmd = cmd.clone();
+                if let Ok(abs) = std::path::absolute(std::path::Path::new(&cmd.path)) {
+                    cmd.path = abs.to_string_lossy().into_owned();
+                }
+                Action::Save(cmd)
+            }
             Self::Cookies { command } => match command {
                 CookiesCommands::List(cmd) => Action::CookiesList(cmd.clone()),
                 CookiesCommands::Get(cmd) => Action::CookiesGet(cmd.clone()),
                 CookiesCommands::Set(cmd) => Action::CookiesSet(cmd.clone()),
                 CookiesCommands::Delete(cmd) => Action::CookiesDelete(cmd.clone()),
                 CookiesCommands::Clear(cmd) => Action::CookiesClear(cmd.clone()),
+                CookiesCommands::Export(cmd) => {
+                    let mut cmd = cmd.clone();
+                    if let Ok(abs) = std::path::absolute(std::path::Path::new(&cmd.out)) {
+                        cmd.out = abs.to_string_lossy().into_owned();
+                    }
+                    Action::CookiesExport(cmd)
+                }
+                CookiesCommands::Import(cmd) => {
+                    let mut cmd = cmd.clone();
+                    if let Ok(abs) = std::path::absolute(std::path::Path::new(&cmd.file)) {
+                        cmd.file = abs.to_string_lossy().into_owned();
+                    }
+                    Action::CookiesImport(cmd)
+                }
+            },
+            Self::Downloads { command } => match command {
+                DownloadsCommands::List(cmd) => Action::DownloadsList(cmd.clone()),
+                DownloadsCommands::Wait(cmd) => Action::DownloadsWait(cmd.clone()),
             },
             Self::LocalStorage { command } => {
                 storage_to_action(command, storage::StorageKind::Local)
@@ -486,6 +1051,10 @@ impl BrowserCommands {
             Self::SessionStorage { command } => {
                 storage_to_action(command, storage::StorageKind::Session)
             }
+            Self::IndexedDb { command } => match command {
+                IndexedDbCommands::List(cmd) => Action::IndexedDbList(cmd.clone()),
+                IndexedDbCommands::Dump(cmd) => Action::IndexedDbDump(cmd.clone()),
+            },
             Self::Logs { command } => match command {
                 LogsCommands::Console(cmd) => Action::LogsConsole(cmd.clone()),
                 LogsCommands::Errors(cmd) => Action::LogsErrors(cmd.clone()),
@@ -511,28 +1080,121 @@ impl BrowserCommands {
                         Action::NetworkHarStop(cmd)
                     }
                 },
+                NetworkCommands::Route { command } => match command {
+                    RouteCommands::Add(cmd) => {
+                        // Same CWD caveat as `har stop --out`: the daemon reads
+                        // the fixture.
+                        let mut cmd = cmd.clone();
+                        if let Some(ref p) = cmd.fulfill
+                            && let Ok(abs) = std::path::absolute(std::path::Path::new(p))
+                        {
+                            cmd.fulfill = Some(abs.to_string_lossy().into_owned());
+                        }
+                        Action::NetworkRouteAdd(cmd)
+                    }
+                    RouteCommands::List(cmd) => Action::NetworkRouteList(cmd.clone()),
+                    RouteCommands::Remove(cmd) => Action::NetworkRouteRemove(cmd.clone()),
+                },
             },
             Self::Wait { command } => match command {
                 WaitCommands::Element(cmd) => Action::WaitElement(cmd.clone()),
                 WaitCommands::Navigation(cmd) => Action::WaitNavigation(cmd.clone()),
                 WaitCommands::NetworkIdle(cmd) => Action::WaitNetworkIdle(cmd.clone()),
                 WaitCommands::Condition(cmd) => Action::WaitCondition(cmd.clone()),
+                WaitCommands::Text(cmd) => Action::WaitText(cmd.clone()),
+                WaitCommands::Url(cmd) => Action::WaitUrl(cmd.clone()),
             },
             Self::Screenshot(cmd) => Action::Screenshot(cmd.clone()),
             Self::Eval(cmd) => Action::Eval(cmd.clone()),
             Self::Click(cmd) => Action::Click(cmd.clone()),
             Self::BatchClick(cmd) => Action::BatchClick(cmd.clone()),
             Self::Hover(cmd) => Action::Hover(cmd.clone()),
+            Self::Menu(cmd) => Action::Menu(cmd.clone()),
+            Self::Handoff(cmd) => Action::Handoff(cmd.clone()),
             Self::Focus(cmd) => Action::Focus(cmd.clone()),
+            Self::TabTo(cmd) => Action::TabTo(cmd.clone()),
             Self::Press(cmd) => Action::Press(cmd.clone()),
             Self::Type(cmd) => Action::Type(cmd.clone()),
             Self::Fill(cmd) => Action::Fill(cmd.clone()),
             Self::Select(cmd) => Action::Select(cmd.clone()),
+            Self::Canvas { command } => match command {
+                CanvasCommands::ClickColor(cmd) => Action::CanvasClickColor(cmd.clone()),
+                // Same CWD caveat as `har stop --out`: the daemon reads the file.
+                CanvasCommands::Draw(cmd) => {
+                    let mut cmd = cmd.clone();
+                    if let Ok(abs) = std::path::absolute(std::path::Path::new(&cmd.path)) {
+                        cmd.path = abs.to_string_lossy().into_owned();
+                    }
+                    Action::CanvasDraw(cmd)
+                }
+            },
+            Self::Record { command } => match command {
+                // Same CWD caveat as `har stop --out`: the daemon writes the script.
+                RecordCommands::Start(cmd) => {
+                    let mut cmd = cmd.clone();
+                    if let Ok(abs) = std::path::absolute(std::path::Path::new(&cmd.out)) {
+                        cmd.out = abs.to_string_lossy().into_owned();
+                    }
+                    Action::RecordStart(cmd)
+                }
+                RecordCommands::Stop(cmd) => {
+                    let mut cmd = cmd.clone();
+                    if let Some(ref p) = cmd.out
+                        && let Ok(abs) = std::path::absolute(std::path::Path::new(p))
+                    {
+                        cmd.out = Some(abs.to_string_lossy().into_owned());
+                    }
+                    Action::RecordStop(cmd)
+                }
+            },
+            Self::RecordVideo { command } => match command {
+                // The daemon writes the frames and the video, so make paths absolute.
+                RecordVideoCommands::Start(cmd) => {
+                    let mut cmd = cmd.clone();
+                    if let Ok(abs) = std::path::absolute(std::path::Path::new(&cmd.out)) {
+                        cmd.out = abs.to_string_lossy().into_owned();
+                    }
+                    Action::RecordVideoStart(cmd)
+                }
+                RecordVideoCommands::Stop(cmd) => {
+                    let mut cmd = cmd.clone();
+                    if let Some(ref p) = cmd.out
+                        && let Ok(abs) = std::path::absolute(std::path::Path::new(p))
+                    {
+                        cmd.out = Some(abs.to_string_lossy().into_owned());
+                    }
+                    Action::RecordVideoStop(cmd)
+                }
+            },
+            Self::Trace { command } => match command {
+                // The daemon writes the trace, so make paths absolute.
+                TraceCommands::Start(cmd) => {
+                    let mut cmd = cmd.clone();
+                    if let Ok(abs) = std::path::absolute(std::path::Path::new(&cmd.out)) {
+                        cmd.out = abs.to_string_lossy().into_owned();
+                    }
+                    Action::TraceStart(cmd)
+                }
+                TraceCommands::Stop(cmd) => {
+                    let mut cmd = cmd.clone();
+                    if let Some(ref p) = cmd.out
+                        && let Ok(abs) = std::path::absolute(std::path::Path::new(p))
+                    {
+                        cmd.out = Some(abs.to_string_lossy().into_owned());
+                    }
+                    Action::TraceStop(cmd)
+                }
+            },
+            Self::Date(cmd) => Action::Date(cmd.clone()),
+            Self::Slide(cmd) => Action::Slide(cmd.clone()),
             Self::Drag(cmd) => Action::Drag(cmd.clone()),
             Self::Upload(cmd) => Action::Upload(cmd.clone()),
             Self::MouseMove(cmd) => Action::MouseMove(cmd.clone()),
             Self::CursorPosition(cmd) => Action::CursorPosition(cmd.clone()),
             Self::Scroll(cmd) => Action::Scroll(cmd.clone()),
+            Self::Tap(cmd) => Action::Tap(cmd.clone()),
+            Self::Swipe(cmd) => Action::Swipe(cmd.clone()),
+            Self::Pinch(cmd) => Action::Pinch(cmd.clone()),
         })
     }
 
@@ -545,37 +1207,63 @@ impl BrowserCommands {
             Self::Status(_) => session::status::COMMAND_NAME,
             Self::Close(_) => session::close::COMMAND_NAME,
             Self::Restart(_) => session::restart::COMMAND_NAME,
+            Self::Emulate { command, .. } => match command {
+                None => session::emulate::COMMAND_NAME,
+                Some(EmulateCommands::Network(_)) => session::emulate_network::COMMAND_NAME,
+            },
             Self::ListTabs(_) => tab::list::COMMAND_NAME,
             Self::NewTab(_) => tab::open::COMMAND_NAME,
             Self::BatchNewTab(_) => tab::batch_open::COMMAND_NAME,
             Self::CloseTab(_) => tab::close::COMMAND_NAME,
+            Self::Switch(_) => tab::switch::COMMAND_NAME,
+            Self::Window(_) => tab::window::COMMAND_NAME,
             Self::Goto(_) => navigation::goto::COMMAND_NAME,
             Self::Back(_) => "browser back",
             Self::Forward(_) => "browser forward",
             Self::Reload(_) => "browser reload",
+            Self::Fetch(_) => navigation::fetch::COMMAND_NAME,
+            Self::TraceRedirects(_) => navigation::trace_redirects::COMMAND_NAME,
             Self::BatchSnapshot(_) => observation::batch_snapshot::COMMAND_NAME,
             Self::Snapshot(_) => observation::snapshot::COMMAND_NAME,
             Self::Title(_) => observation::title::COMMAND_NAME,
+            Self::Meta(_) => observation::meta::COMMAND_NAME,
+            Self::Response(_) => observation::response::COMMAND_NAME,
+            Self::Assert(_) => observation::assert::COMMAND_NAME,
+            Self::Feeds(_) => observation::feeds::COMMAND_NAME,
+            Self::Images(_) => observation::images::COMMAND_NAME,
+            Self::Table(_) => observation::table::COMMAND_NAME,
             Self::Url(_) => observation::url::COMMAND_NAME,
             Self::Viewport(_) => observation::viewport::COMMAND_NAME,
             Self::Html(_) => observation::html::COMMAND_NAME,
             Self::Text(_) => observation::text::COMMAND_NAME,
             Self::Value(_) => observation::value::COMMAND_NAME,
+            Self::Options(_) => observation::options::COMMAND_NAME,
             Self::Attr(_) => observation::attr::COMMAND_NAME,
             Self::Attrs(_) => observation::attrs::COMMAND_NAME,
             Self::Box(_) => observation::r#box::COMMAND_NAME,
             Self::Styles(_) => observation::styles::COMMAND_NAME,
             Self::Describe(_) => observation::describe::COMMAND_NAME,
-            Self::State(_) => observation::state::COMMAND_NAME,
+            Self::State { command, .. } => match command {
+                None => observation::state::COMMAND_NAME,
+                Some(StateCommands::Bundle(_)) => session::state_bundle::BUNDLE_COMMAND_NAME,
+                Some(StateCommands::Import(_)) => session::state_bundle::IMPORT_COMMAND_NAME,
+            },
             Self::Query(_) => observation::query::COMMAND_NAME,
             Self::InspectPoint(_) => observation::inspect_point::COMMAND_NAME,
             Self::Pdf(_) => observation::pdf::COMMAND_NAME,
+            Self::Save(_) => observation::save::COMMAND_NAME,
             Self::Cookies { command } => match command {
                 CookiesCommands::List(_) => cookies::list::COMMAND_NAME,
                 CookiesCommands::Get(_) => cookies::get::COMMAND_NAME,
                 CookiesCommands::Set(_) => cookies::set::COMMAND_NAME,
                 CookiesCommands::Delete(_) => cookies::delete::COMMAND_NAME,
                 CookiesCommands::Clear(_) => cookies::clear::COMMAND_NAME,
+                CookiesCommands::Export(_) => cookies::export::COMMAND_NAME,
+                CookiesCommands::Import(_) => cookies::import::COMMAND_NAME,
+            },
+            Self::Downloads { command } => match command {
+                DownloadsCommands::List(_) => downloads::list::COMMAND_NAME,
+                DownloadsCommands::Wait(_) => downloads::wait::COMMAND_NAME,
             },
             Self::LocalStorage { command } => {
                 storage_command_name(command, storage::StorageKind::Local)
@@ -583,6 +1271,10 @@ impl BrowserCommands {
             Self::SessionStorage { command } => {
                 storage_command_name(command, storage::StorageKind::Session)
             }
+            Self::IndexedDb { command } => match command {
+                IndexedDbCommands::List(_) => indexeddb::list::COMMAND_NAME,
+                IndexedDbCommands::Dump(_) => indexeddb::dump::COMMAND_NAME,
+            },
             Self::Logs { command } => match command {
                 LogsCommands::Console(_) => observation::logs_console::COMMAND_NAME,
                 LogsCommands::Errors(_) => observation::logs_errors::COMMAND_NAME,
@@ -594,33 +1286,77 @@ impl BrowserCommands {
                     HarCommands::Start(_) => observation::network_har::START_COMMAND_NAME,
                     HarCommands::Stop(_) => observation::network_har::STOP_COMMAND_NAME,
                 },
+                NetworkCommands::Route { command } => match command {
+                    RouteCommands::Add(_) => observation::network_route::ADD_COMMAND_NAME,
+                    RouteCommands::List(_) => observation::network_route::LIST_COMMAND_NAME,
+                    RouteCommands::Remove(_) => observation::network_route::REMOVE_COMMAND_NAME,
+                },
             },
             Self::Wait { command } => match command {
                 WaitCommands::Element(_) => wait::element::COMMAND_NAME,
                 WaitCommands::Navigation(_) => wait::navigation::COMMAND_NAME,
                 WaitCommands::NetworkIdle(_) => wait::network_idle::COMMAND_NAME,
                 WaitCommands::Condition(_) => wait::condition::COMMAND_NAME,
+                WaitCommands::Text(_) => wait::text::COMMAND_NAME,
+                WaitCommands::Url(_) => wait::url::COMMAND_NAME,
             },
             Self::Screenshot(_) => observation::screenshot::COMMAND_NAME,
             Self::Eval(_) => interaction::eval::COMMAND_NAME,
             Self::Click(_) => interaction::click::COMMAND_NAME,
             Self::BatchClick(_) => interaction::batch_click::COMMAND_NAME,
             Self::Hover(_) => interaction::hover::COMMAND_NAME,
+            Self::Menu(_) => interaction::menu::COMMAND_NAME,
+            Self::Handoff(_) => interaction::handoff::COMMAND_NAME,
             Self::Focus(_) => interaction::focus::COMMAND_NAME,
+            Self::TabTo(_) => interaction::tabto::COMMAND_NAME,
             Self::Press(_) => interaction::press::COMMAND_NAME,
             Self::Fill(_) => interaction::fill::COMMAND_NAME,
             Self::Type(_) => interaction::type_text::COMMAND_NAME,
             Self::Select(_) => interaction::select::COMMAND_NAME,
+            Self::Canvas { command } => match command {
+                CanvasCommands::ClickColor(_) => interaction::canvas::CLICK_COLOR_COMMAND_NAME,
+                CanvasCommands::Draw(_) => interaction::canvas::DRAW_COMMAND_NAME,
+            },
+            Self::Record { command } => match command {
+                RecordCommands::Start(_) => interaction::record::START_COMMAND_NAME,
+                RecordCommands::Stop(_) => interaction::record::STOP_COMMAND_NAME,
+            },
+            Self::RecordVideo { command } => match command {
+                RecordVideoCommands::Start(_) => observation::record_video::START_COMMAND_NAME,
+                RecordVideoCommands::Stop(_) => observation::record_video::STOP_COMMAND_NAME,
+            },
+            Self::Trace { command } => match command {
+                TraceCommands::Start(_) => observation::trace::START_COMMAND_NAME,
+                TraceCommands::Stop(_) => observation::trace::STOP_COMMAND_NAME,
+            },
+            Self::Date(_) => interaction::date::COMMAND_NAME,
+            Self::Slide(_) => interaction::slide::COMMAND_NAME,
             Self::Drag(_) => interaction::drag::COMMAND_NAME,
             Self::Upload(_) => interaction::upload::COMMAND_NAME,
             Self::MouseMove(_) => interaction::mouse_move::COMMAND_NAME,
             Self::CursorPosition(_) => interaction::cursor_position::COMMAND_NAME,
             Self::Scroll(_) => interaction::scroll::COMMAND_NAME,
+            Self::Tap(_) => interaction::touch::TAP_COMMAND_NAME,
+            Self::Swipe(_) => interaction::touch::SWIPE_COMMAND_NAME,
+            Self::Pinch(_) => interaction::touch::PINCH_COMMAND_NAME,
         }
     }
 
     /// Build response context from command args and result.
+    ///
+    /// `--tab` is reported as the tab the daemon resolved it to.
     pub fn context(&self, result: &ActionResult) -> Option<ResponseContext> {
+        let mut context = self.command_context(result)?;
+        if context.tab_id.is_some()
+            && let ActionResult::Ok { data } = result
+            && let Some(tab) = data.get("__ctx_tab").and_then(|v| v.as_str())
+        {
+            context.tab_id = Some(tab.to_string());
+        }
+        Some(context)
+    }
+
+    fn command_context(&self, result: &ActionResult) -> Option<ResponseContext> {
         match self {
             Self::Help => None,
             Self::Start(cmd) => session::start::context(cmd, result),
@@ -628,34 +1364,66 @@ impl BrowserCommands {
             Self::Status(cmd) => session::status::context(cmd, result),
             Self::Close(cmd) => session::close::context(cmd, result),
             Self::Restart(cmd) => session::restart::context(cmd, result),
+            Self::Emulate { device, command } => match (device, command) {
+                (_, Some(EmulateCommands::Network(cmd))) => {
+                    session::emulate_network::context(cmd, result)
+                }
+                (Some(cmd), None) => session::emulate::context(cmd, result),
+                (None, None) => None,
+            },
             Self::ListTabs(cmd) => tab::list::context(cmd, result),
             Self::NewTab(cmd) => tab::open::context(cmd, result),
             Self::BatchNewTab(cmd) => tab::batch_open::context(cmd, result),
             Self::CloseTab(cmd) => tab::close::context(cmd, result),
+            Self::Switch(cmd) => tab::switch::context(cmd, result),
+            Self::Window(cmd) => tab::window::context(cmd, result),
             Self::Goto(cmd) => navigation::goto::context(cmd, result),
             Self::BatchSnapshot(cmd) => observation::batch_snapshot::context(cmd, result),
             Self::Snapshot(cmd) => observation::snapshot::context(cmd, result),
             Self::Title(cmd) => observation::title::context(cmd, result),
+            Self::Meta(cmd) => observation::meta::context(cmd, result),
+            Self::Response(cmd) => observation::response::context(cmd, result),
+            Self::Assert(cmd) => observation::assert::context(cmd, result),
+            Self::Feeds(cmd) => observation::feeds::context(cmd, result),
+            Self::Images(cmd) => observation::images::context(cmd, result),
+            Self::Table(cmd) => observation::table::context(cmd, result),
             Self::Url(cmd) => observation::url::context(cmd, result),
             Self::Viewport(cmd) => observation::viewport::context(cmd, result),
             Self::Html(cmd) => observation::html::context(cmd, result),
             Self::Text(cmd) => observation::text::context(cmd, result),
             Self::Value(cmd) => observation::value::context(cmd, result),
+            Self::Options(cmd) => observation::options::context(cmd, result),
             Self::Attr(cmd) => observation::attr::context(cmd, result),
             Self::Attrs(cmd) => observation::attrs::context(cmd, result),
             Self::Box(cmd) => observation::r#box::context(cmd, result),
             Self::Styles(cmd) => observation::styles::context(cmd, result),
             Self::Describe(cmd) => observation::describe::context(cmd, result),
-            Self::State(cmd) => observation::state::context(cmd, result),
+            Self::State { element, command } => match (element, command) {
+                (_, Some(StateCommands::Bundle(cmd))) => {
+                    session::state_bundle::bundle_context(cmd, result)
+                }
+                (_, Some(StateCommands::Import(cmd))) => {
+                    session::state_bundle::import_context(cmd, result)
+                }
+                (Some(cmd), None) => observation::state::context(cmd, result),
+                (None, None) => None,
+            },
             Self::Query(cmd) => observation::query::context(cmd, result),
             Self::InspectPoint(cmd) => observation::inspect_point::context(cmd, result),
             Self::Pdf(cmd) => observation::pdf::context(cmd, result),
+            Self::Save(cmd) => observation::save::context(cmd, result),
             Self::Cookies { command } => match command {
                 CookiesCommands::List(cmd) => cookies::list::context(cmd, result),
                 CookiesCommands::Get(cmd) => cookies::get::context(cmd, result),
                 CookiesCommands::Set(cmd) => cookies::set::context(cmd, result),
                 CookiesCommands::Delete(cmd) => cookies::delete::context(cmd, result),
                 CookiesCommands::Clear(cmd) => cookies::clear::context(cmd, result),
+                CookiesCommands::Export(cmd) => cookies::export::context(cmd, result),
+                CookiesCommands::Import(cmd) => cookies::import::context(cmd, result),
+            },
+            Self::Downloads { command } => match command {
+                DownloadsCommands::List(cmd) => downloads::list::context(cmd, result),
+                DownloadsCommands::Wait(cmd) => downloads::wait::context(cmd, result),
             },
             Self::LocalStorage { command } => {
                 storage_context(command, storage::StorageKind::Local, result)
@@ -663,6 +1431,10 @@ impl BrowserCommands {
             Self::SessionStorage { command } => {
                 storage_context(command, storage::StorageKind::Session, result)
             }
+            Self::IndexedDb { command } => match command {
+                IndexedDbCommands::List(cmd) => indexeddb::list::context(cmd, result),
+                IndexedDbCommands::Dump(cmd) => indexeddb::dump::context(cmd, result),
+            },
             Self::Logs { command } => match command {
                 LogsCommands::Console(cmd) => observation::logs_console::context(cmd, result),
                 LogsCommands::Errors(cmd) => observation::logs_errors::context(cmd, result),
@@ -678,12 +1450,23 @@ impl BrowserCommands {
                     HarCommands::Start(cmd) => observation::network_har::start_context(cmd, result),
                     HarCommands::Stop(cmd) => observation::network_har::stop_context(cmd, result),
                 },
+                NetworkCommands::Route { command } => match command {
+                    RouteCommands::Add(cmd) => observation::network_route::add_context(cmd, result),
+                    RouteCommands::List(cmd) => {
+                        observation::network_route::list_context(cmd, result)
+                    }
+                    RouteCommands::Remove(cmd) => {
+                        observation::network_route::remove_context(cmd, result)
+                    }
+                },
             },
             Self::Wait { command } => match command {
                 WaitCommands::Element(cmd) => wait::element::context(cmd, result),
                 WaitCommands::Navigation(cmd) => wait::navigation::context(cmd, result),
                 WaitCommands::NetworkIdle(cmd) => wait::network_idle::context(cmd, result),
                 WaitCommands::Condition(cmd) => wait::condition::context(cmd, result),
+                WaitCommands::Text(cmd) => wait::text::context(cmd, result),
+                WaitCommands::Url(cmd) => wait::url::context(cmd, result),
             },
             Self::Eval(cmd) => interaction::eval::context(cmd, result),
             Self::Back(a) => navigation::back::context(
@@ -707,19 +1490,51 @@ impl BrowserCommands {
                 },
                 result,
             ),
+            Self::Fetch(cmd) => navigation::fetch::context(cmd, result),
+            Self::TraceRedirects(cmd) => navigation::trace_redirects::context(cmd, result),
             Self::Click(cmd) => interaction::click::context(cmd, result),
             Self::BatchClick(cmd) => interaction::batch_click::context(cmd, result),
             Self::Hover(cmd) => interaction::hover::context(cmd, result),
+            Self::Menu(cmd) => interaction::menu::context(cmd, result),
+            Self::Handoff(cmd) => interaction::handoff::context(cmd, result),
             Self::Focus(cmd) => interaction::focus::context(cmd, result),
+            Self::TabTo(cmd) => interaction::tabto::context(cmd, result),
             Self::Press(cmd) => interaction::press::context(cmd, result),
             Self::Type(cmd) => interaction::type_text::context(cmd, result),
             Self::Fill(cmd) => interaction::fill::context(cmd, result),
             Self::Select(cmd) => interaction::select::context(cmd, result),
+            Self::Canvas { command } => match command {
+                CanvasCommands::ClickColor(cmd) => {
+                    interaction::canvas::click_color_context(cmd, result)
+                }
+                CanvasCommands::Draw(cmd) => interaction::canvas::draw_context(cmd, result),
+            },
+            Self::Record { command } => match command {
+                RecordCommands::Start(cmd) => interaction::record::start_context(cmd, result),
+                RecordCommands::Stop(cmd) => interaction::record::stop_context(cmd, result),
+            },
+            Self::RecordVideo { command } => match command {
+                RecordVideoCommands::Start(cmd) => {
+                    observation::record_video::start_context(cmd, result)
+                }
+                RecordVideoCommands::Stop(cmd) => {
+                    observation::record_video::stop_context(cmd, result)
+                }
+            },
+            Self::Trace { command } => match command {
+                TraceCommands::Start(cmd) => observation::trace::start_context(cmd, result),
+                TraceCommands::Stop(cmd) => observation::trace::stop_context(cmd, result),
+            },
+            Self::Date(cmd) => interaction::date::context(cmd, result),
+            Self::Slide(cmd) => interaction::slide::context(cmd, result),
             Self::Drag(cmd) => interaction::drag::context(cmd, result),
             Self::Upload(cmd) => interaction::upload::context(cmd, result),
             Self::MouseMove(cmd) => interaction::mouse_move::context(cmd, result),
             Self::CursorPosition(cmd) => interaction::cursor_position::context(cmd, result),
             Self::Scroll(cmd) => interaction::scroll::context(cmd, result),
+            Self::Tap(cmd) => interaction::touch::tap_context(cmd, result),
+            Self::Swipe(cmd) => interaction::touch::swipe_context(cmd, result),
+            Self::Pinch(cmd) => interaction::touch::pinch_context(cmd, result),
             Self::Screenshot(cmd) => observation::screenshot::context(cmd, result),
         }
     }
@@ -908,8 +1723,11 @@ mod tests {
 
         assert!(!cli.json);
         match cli.command {
-            Some(Commands::Search { keyword }) => {
+            Some(Commands::Search {
+                keyword, from_page, ..
+            }) => {
                 assert_eq!(keyword, "query text");
+                assert!(!from_page);
             }
             other => panic!("expected search command, got {other:?}"),
         }
@@ -922,13 +1740,54 @@ mod tests {
 
         assert!(cli.json);
         match cli.command {
-            Some(Commands::Search { keyword }) => {
+            Some(Commands::Search { keyword, .. }) => {
                 assert_eq!(keyword, "query");
             }
             other => panic!("expected search command, got {other:?}"),
         }
     }
 
+    #[test]
+    fn try_parse_from_parses_search_from_page() {
+        let cli = Cli::try_parse_from([
+            "actionbook",
+            "search",
+            "book a room",
+            "--from-page",
+            "--session",
+            "s1",
+            "--tab",
+            "t1",
+        ])
+        .expect("parse search --from-page");
+        match cli.command {
+            Some(Commands::Search {
+                from_page,
+                session,
+                tab,
+                ..
+            }) => {
+                assert!(from_page);
+                assert_eq!(session.as_deref(), Some("s1"));
+                assert_eq!(tab.as_deref(), Some("t1"));
+            }
+            other => panic!("expected search command, got {other:?}"),
+        }
+
+        assert!(Cli::try_parse_from(["actionbook", "search", "q", "--from-page"]).is_err());
+        assert!(
+            Cli::try_parse_from([
+                "actionbook",
+                "search",
+                "q",
+                "--from-page",
+                "--session",
+                "s1"
+            ])
+            .is_err()
+        );
+    }
+
     #[test]
     fn try_parse_from_parses_manual_site_only() {
         let cli = Cli::try_parse_from(["actionbook", "manual", "notion"]).expect("parse manual");
@@ -1050,6 +1909,104 @@ mod tests {
         assert!(cli.is_ok(), "browser hover command should parse");
     }
 
+    #[test]
+    fn try_parse_from_parses_click_confirm_sensitive_flag() {
+        let cli = Cli::try_parse_from([
+            "actionbook",
+            "browser",
+            "click",
+            "#pay",
+            "--session",
+            "session-1",
+            "--tab",
+            "tab-1",
+            "--confirm-sensitive",
+        ])
+        .expect("browser click --confirm-sensitive should parse");
+
+        match cli.command {
+            Some(Commands::Browser {
+                command: BrowserCommands::Click(cmd),
+            }) => assert!(cmd.confirm_sensitive),
+            other => panic!("expected browser click, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn try_parse_from_parses_tap_confirm_sensitive_flag() {
+        let cli = Cli::try_parse_from([
+            "actionbook",
+            "browser",
+            "tap",
+            "#pay",
+            "--session",
+            "session-1",
+            "--tab",
+            "tab-1",
+            "--confirm-sensitive",
+        ])
+        .expect("browser tap --confirm-sensitive should parse");
+
+        match cli.command {
+            Some(Commands::Browser {
+                command: BrowserCommands::Tap(cmd),
+            }) => assert!(cmd.confirm_sensitive),
+            other => panic!("expected browser tap, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn try_parse_from_hands_the_global_timeout_to_goto() {
+        let mut cli = Cli::try_parse_from([
+            "actionbook",
+            "browser",
+            "goto",
+            "https://example.com",
+            "--session",
+            "session-1",
+            "--tab",
+            "tab-1",
+            "--timeout",
+            "60000",
+        ])
+        .expect("browser goto --timeout should parse");
+
+        assert_eq!(cli.timeout, Some(60000));
+        match cli.command.take() {
+            Some(Commands::Browser {
+                command: BrowserCommands::Goto(cmd),
+            }) => {
+                let mut command = BrowserCommands::Goto(cmd);
+                let client_ms = command.apply_timeout(cli.timeout);
+                assert_eq!(client_ms, Some(60000 + NAVIGATION_REPLY_GRACE_MS));
+                match command {
+                    BrowserCommands::Goto(cmd) => assert_eq!(cmd.timeout, Some(60000)),
+                    _ => unreachable!(),
+                }
+            }
+            other => panic!("expected browser goto, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn start_record_session_is_resolved_to_absolute_path() {
+        let cli =
+            Cli::try_parse_from(["actionbook", "browser", "start", "--record-session", "run1"])
+                .expect("browser start --record-session should parse");
+
+        let Some(Commands::Browser { command }) = cli.command else {
+            panic!("expected browser command");
+        };
+        match command.to_action() {
+            Some(Action::StartSession(cmd)) => {
+                let dir = cmd.record_session.expect("record_session set");
+                assert!(std::path::Path::new(&dir).is_absolute());
+                assert!(dir.ends_with("run1"));
+            }
+            other => panic!("expected start session action, got {other:?}"),
+        }
+    }
+
     #[test]
     fn try_parse_from_accepts_browser_focus_command() {
         let cli = Cli::try_parse_from([
@@ -1217,6 +2174,51 @@ mod tests {
         assert!(cli.is_ok(), "browser scroll edge command should parse");
     }
 
+    #[test]
+    fn try_parse_from_accepts_browser_scroll_flag_forms() {
+        for args in [
+            &["--by", "0,-400"][..],
+            &["--bottom"][..],
+            &["--page-down"][..],
+            &["--to", "#footer"][..],
+        ] {
+            let cli = Cli::try_parse_from(
+                [
+                    "actionbook",
+                    "browser",
+                    "scroll",
+                    "--session",
+                    "s1",
+                    "--tab",
+                    "t1",
+                ]
+                .iter()
+                .chain(args),
+            );
+            assert!(cli.is_ok(), "browser scroll {args:?} should parse");
+        }
+    }
+
+    #[test]
+    fn try_parse_from_accepts_wait_fn_alias_for_condition() {
+        let cli = Cli::try_parse_from([
+            "actionbook",
+            "browser",
+            "wait",
+            "fn",
+            "window.ready",
+            "--session",
+            "s1",
+            "--tab",
+            "t1",
+        ])
+        .expect("parse wait fn");
+        let Some(Commands::Browser { command }) = cli.command else {
+            panic!("expected browser command");
+        };
+        assert_eq!(command.command_name(), "browser wait condition");
+    }
+
     #[test]
     fn try_parse_from_accepts_browser_scroll_into_view_command() {
         let cli = Cli::try_parse_from([
@@ -1518,4 +2520,89 @@ mod tests {
             other => panic!("expected browser new-tab command, got {other:?}"),
         }
     }
+
+    #[test]
+    fn try_parse_from_splits_browser_emulate_device_and_network() {
+        let action = |args: &[&str]| {
+            let mut argv = vec!["actionbook", "browser", "emulate"];
+            argv.extend(args);
+            match Cli::try_parse_from(argv)
+                .expect("browser emulate should parse")
+                .command
+            {
+                Some(Commands::Browser { command }) => command.to_action(),
+                other => panic!("expected browser command, got {other:?}"),
+            }
+        };
+
+        match action(&["iPhone 14", "--session", "s1"]) {
+            Some(Action::Emulate(cmd)) => assert_eq!(cmd.device.as_deref(), Some("iPhone 14")),
+            other => panic!("expected emulate, got {other:?}"),
+        }
+        match action(&["network", "--preset", "slow-3g", "--session", "s1"]) {
+            Some(Action::EmulateNetwork(cmd)) => {
+                assert_eq!(cmd.preset.as_deref(), Some("slow-3g"));
+                assert_eq!(cmd.session, "s1");
+            }
+            other => panic!("expected emulate network, got {other:?}"),
+        }
+        assert!(
+            Cli::try_parse_from([
+                "actionbook",
+                "browser",
+                "emulate",
+                "network",
+                "--reset",
+                "--latency",
+                "5",
+                "--session",
+                "s1",
+            ])
+            .is_err()
+        );
+    }
+
+    #[test]
+    fn try_parse_from_splits_browser_state_element_and_bundle() {
+        let action = |args: &[&str]| {
+            let mut argv = vec!["actionbook", "browser", "state"];
+            argv.extend(args);
+            match Cli::try_parse_from(argv)
+                .expect("browser state should parse")
+                .command
+            {
+                Some(Commands::Browser { command }) => command.to_action(),
+                other => panic!("expected browser command, got {other:?}"),
+            }
+        };
+
+        match action(&["#agree", "--session", "s1", "--tab", "t1"]) {
+            Some(Action::State(cmd)) => assert_eq!(cmd.selector, "#agree"),
+            other => panic!("expected state, got {other:?}"),
+        }
+        // Any variable that is set will do to check it is read on the client.
+        match action(&[
+            "bundle",
+            "--out",
+            "p.bundle",
+            "--encrypt",
+            "--password-env",
+            "PATH",
+            "--session",
+            "s1",
+        ]) {
+            Some(Action::StateBundle(cmd)) => {
+                assert!(cmd.encrypt);
+                assert!(std::path::Path::new(&cmd.out).is_absolute());
+                assert_eq!(cmd.password, std::env::var("PATH").ok());
+            }
+            other => panic!("expected state bundle, got {other:?}"),
+        }
+        match action(&["import", "p.bundle", "--session", "s1"]) {
+            Some(Action::StateImport(cmd)) => {
+                assert_eq!(cmd.password_env, "ACTIONBOOK_BUNDLE_PASSWORD");
+            }
+            other => panic!("expected state import, got {other:?}"),
+        }
+    }
 }
@@ -0,0 +1,216 @@
+//! Breakpoints for `actionbook run`.
+//!
+//! `--pause-at` and `--step` stop the run before a step and read commands
+//! from stdin: any `browser` subcommand runs against the live session (its
+//! `--session`/`--tab` filled in), and `continue`, `next` or `quit` decide
+//! how the run goes on.
+
+use std::io::Write as _;
+
+use super::run::{browser_command, send};
+use crate::utils::client::DaemonClient;
+
+const HELP: &str = "\
+  continue, c         run to the next breakpoint
+  next, n             run this step and pause before the next one
+  quit, q             stop the run here
+  <browser command>   e.g. snapshot, eval \"document.title\", click \"#ok\"";
+
+/// Where the run pauses.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub(crate) struct Breakpoints {
+    /// Pause before every step (`--step`).
+    pub every: bool,
+    /// 1-based step numbers or step names (`--pause-at`).
+    pub at: Vec<String>,
+}
+
+impl Breakpoints {
+    /// Whether to pause before step `index` (1-based) named `name`.
+    pub fn hits(&self, index: usize, name: Option<&str>) -> bool {
+        self.every || self.at.iter().any(|b| matches(b, index, name))
+    }
+
+    /// `--pause-at` values that name none of the steps, given their names.
+    pub fn unmatched<'a>(&'a self, names: &[Option<&str>]) -> Vec<&'a str> {
+        self.at
+            .iter()
+            .filter(|b| {
+                !names
+                    .iter()
+                    .enumerate()
+                    .any(|(i, name)| matches(b, i + 1, *name))
+            })
+            .map(String::as_str)
+            .collect()
+    }
+}
+
+fn matches(breakpoint: &str, index: usize, name: Option<&str>) -> bool {
+    breakpoint.parse::<usize>().ok() == Some(index) || Some(breakpoint) == name
+}
+
+/// What the user asked for at a breakpoint.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) enum Resume {
+    Continue,
+    Next,
+    Quit,
+}
+
+/// Split a command line into words, honouring single and double quotes and
+/// backslash escapes.
+fn words(line: &str) -> Result<Vec<String>, String> {
+    let mut out = Vec::new();
+    let mut word = String::new();
+    let mut in_word = false;
+    let mut quote: Option<char> = None;
+    let mut chars = line.chars();
+    while let Some(c) = chars.next() {
+        match (quote, c) {
+            (Some(q), c) if c == q => quote = None,
+            (Some('"') | None, '\\') => match chars.next() {
+                Some(next) => {
+                    word.push(next);
+                    in_word = true;
+                }
+                None => return Err("trailing backslash".into()),
+            },
+            (Some(_), c) => word.push(c),
+            (None, '"' | '\'') => {
+                quote = Some(c);
+                in_word = true;
+            }
+            (None, c) if c.is_whitespace() => {
+                if in_word {
+                    out.push(std::mem::take(&mut word));
+                    in_word = false;
+                }
+            }
+            (None, c) => {
+                word.push(c);
+                in_word = true;
+            }
+        }
+    }
+    if quote.is_some() {
+        return Err("unterminated quote".into());
+    }
+    if in_word {
+        out.push(word);
+    }
+    Ok(out)
+}
+
+/// One line from stdin; `None` at end of input.
+async fn read_line() -> Option<String> {
+    tokio::task::spawn_blocking(|| {
+        let mut line = String::new();
+        match std::io::stdin().read_line(&mut line) {
+            Ok(0) | Err(_) => None,
+            Ok(_) => Some(line),
+        }
+    })
+    .await
+    .ok()
+    .flatten()
+}
+
+/// Pause before a step until the user resumes. `label` describes the step.
+/// The end of stdin continues the run.
+pub(crate) async fn pause(
+    client: &mut DaemonClient,
+    label: &str,
+    session: &str,
+    tab: Option<&str>,
+) -> Resume {
+    eprintln!("paused before {label} (continue, next, quit, help or a browser command)");
+    loop {
+        eprint!("run> ");
+        let _ = std::io::stderr().flush();
+        let Some(line) = read_line().await else {
+            eprintln!();
+            return Resume::Continue;
+        };
+        let args = match words(line.trim()) {
+            Ok(args) => args,
+            Err(e) => {
+                eprintln!("error: {e}");
+                continue;
+            }
+        };
+        match args.first().map(String::as_str) {
+            None => {}
+            Some("continue" | "c") => return Resume::Continue,
+            Some("next" | "n") => return Resume::Next,
+            Some("quit" | "q") => return Resume::Quit,
+            Some("help" | "?") => eprintln!("{HELP}"),
+            Some(_) => run_command(client, &args, session, tab).await,
+        }
+    }
+}
+
+/// Run one `browser` subcommand and print its result the way the CLI does.
+async fn run_command(client: &mut DaemonClient, args: &[String], session: &str, tab: Option<&str>) {
+    let command = match browser_command(args, session, tab) {
+        Ok(c) => c,
+        Err(e) => {
+            eprintln!("error: {e}");
+            return;
+        }
+    };
+    let Some(action) = command.to_action() else {
+        eprintln!("error: '{}' cannot run at a breakpoint", args[0]);
+        return;
+    };
+    let result = send(client, &action).await;
+    let context = command.context(&result);
+    // stderr, so `--json` keeps stdout to the run's own envelope.
+    eprintln!(
+        "{}",
+        crate::output::format_text(command.command_name(), &context, &result)
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn breakpoints_match_step_numbers_and_names() {
+        let b = Breakpoints {
+            every: false,
+            at: vec!["3".into(), "checkout".into()],
+        };
+        assert!(b.hits(3, None));
+        assert!(b.hits(5, Some("checkout")));
+        assert!(!b.hits(4, Some("login")));
+        assert_eq!(b.unmatched(&[None, Some("checkout")]), ["3"]);
+        assert!(
+            b.unmatched(&[None, None, None, Some("checkout")])
+                .is_empty()
+        );
+        assert!(
+            Breakpoints {
+                every: true,
+                at: vec![],
+            }
+            .hits(1, None)
+        );
+    }
+
+    #[test]
+    fn words_honour_quotes_and_escapes() {
+        assert_eq!(
+            words(r#"eval "document.title" --no-isolate"#).unwrap(),
+            ["eval", "document.title", "--no-isolate"]
+        );
+        assert_eq!(
+            words(r#"fill '#q' "say \"hi\"""#).unwrap(),
+            ["fill", "#q", r#"say "hi""#]
+        );
+        assert_eq!(words("  ").unwrap(), Vec::<String>::new());
+        assert_eq!(words("click ''").unwrap(), ["click", ""]);
+        assert!(words("eval \"x").is_err());
+    }
+}
@@ -0,0 +1,623 @@
+//! `actionbook flow export <flow>`: turn a run script into a Playwright test
+//! or a Puppeteer script.
+//!
+//! Steps are parsed the way `actionbook run` parses them and written as the
+//! closest API calls. Selectors keep their meaning: `text=` and `role=`
+//! become `getByText`/`getByRole` (Playwright) or `::-p-text`/`::-p-aria`
+//! (Puppeteer), XPath stays XPath. Steps with no equivalent, such as
+//! snapshot refs or `browser` commands outside the common set, are written
+//! as comments and reported.
+
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+
+use clap::{Args, ValueEnum};
+use serde_json::json;
+
+use super::run::{Assertion, Step, StepKind, load_steps};
+use crate::action_result::ActionResult;
+use crate::browser::element::EngineSelector;
+
+pub const COMMAND_NAME: &str = "flow export";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum Format {
+    Playwright,
+    Puppeteer,
+}
+
+impl Format {
+    fn name(self) -> &'static str {
+        match self {
+            Self::Playwright => "playwright",
+            Self::Puppeteer => "puppeteer",
+        }
+    }
+}
+
+/// Convert a run script into a Playwright test or a Puppeteer script
+#[derive(Args, Debug, Clone)]
+#[command(after_help = "\
+Examples:
+  actionbook flow export checkout.yaml
+  actionbook flow export checkout.yaml --format puppeteer --out scripts/checkout.js
+
+Playwright output is a @playwright/test spec (<flow>.spec.ts); Puppeteer
+output is a Node script (<flow>.js). goto, click, fill, wait, assert,
+screenshot and extract steps are exported, as are browser press, type,
+select, hover, focus, upload, back, forward and reload. Other steps are
+left as comments and listed. Retries, captures and anchors are not carried
+over.")]
+pub struct Cmd {
+    /// Run script (.yaml or .json)
+    pub flow: String,
+    /// Code to write
+    #[arg(long, value_enum, default_value_t = Format::Playwright)]
+    pub format: Format,
+    /// Where to write the code (default: next to the flow)
+    #[arg(long, short = 'o')]
+    pub out: Option<String>,
+    /// Overwrite the output file if it exists
+    #[arg(long)]
+    pub force: bool,
+}
+
+/// A single-quoted JavaScript string literal.
+fn js(s: &str) -> String {
+    let mut out = String::from("'");
+    for c in s.chars() {
+        match c {
+            '\\' => out.push_str("\\\\"),
+            '\'' => out.push_str("\\'"),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            '\t' => out.push_str("\\t"),
+            c => out.push(c),
+        }
+    }
+    out.push('\'');
+    out
+}
+
+/// A regex literal matching `s` anywhere, the way `assert: {url}` does.
+fn js_regex(s: &str) -> String {
+    let mut out = String::from("/");
+    for c in s.chars() {
+        if "\\^$.*+?()[]{}|/".contains(c) {
+            out.push('\\');
+        }
+        out.push(c);
+    }
+    out.push('/');
+    out
+}
+
+/// The argument of a Puppeteer `::-p-*` selector.
+fn p_arg(s: &str) -> String {
+    format!("\"{}\"", s.replace('\\', "\\\\").replace('"', "\\\""))
+}
+
+enum Selector {
+    Css(String),
+    XPath(String),
+    Engine(EngineSelector),
+}
+
+fn selector(s: &str) -> Result<Selector, String> {
+    if s.starts_with("@e") {
+        return Err(format!(
+            "snapshot ref {s} only exists in the session that took the snapshot"
+        ));
+    }
+    if s.starts_with("text=") || s.starts_with("role=") {
+        return EngineSelector::parse(s).map(Selector::Engine);
+    }
+    if s.starts_with('/') {
+        return Ok(Selector::XPath(s.to_string()));
+    }
+    Ok(Selector::Css(s.to_string()))
+}
+
+/// A Playwright locator expression for a run selector.
+fn locator(s: &str) -> Result<String, String> {
+    Ok(match selector(s)? {
+        Selector::Css(css) => format!("page.locator({})", js(&css)),
+        Selector::XPath(xpath) => format!("page.locator({})", js(&format!("xpath={xpath}"))),
+        Selector::Engine(EngineSelector::Text { text, exact }) => {
+            if exact {
+                format!("page.getByText({}, {{ exact: true }})", js(&text))
+            } else {
+                format!("page.getByText({})", js(&text))
+            }
+        }
+        Selector::Engine(EngineSelector::Role { role, name }) => match name {
+            Some(name) => format!("page.getByRole({}, {{ name: {} }})", js(&role), js(&name)),
+            None => format!("page.getByRole({})", js(&role)),
+        },
+    })
+}
+
+/// A Puppeteer selector string literal for a run selector.
+fn p_selector(s: &str) -> Result<String, String> {
+    Ok(js(&match selector(s)? {
+        Selector::Css(css) => css,
+        Selector::XPath(xpath) => format!("::-p-xpath({})", p_arg(&xpath)),
+        // Puppeteer has no exact text match; `::-p-text` matches a substring.
+        Selector::Engine(EngineSelector::Text { text, .. }) => {
+            format!("::-p-text({})", p_arg(&text))
+        }
+        Selector::Engine(EngineSelector::Role { role, name }) => {
+            let name = name.map_or(String::new(), |n| format!("[name={}]", p_arg(&n)));
+            format!("::-p-aria({name}[role={}])", p_arg(&role))
+        }
+    }))
+}
+
+/// The `browser` steps that have an equivalent.
+enum Browser<'a> {
+    Press(&'a str),
+    Type {
+        selector: Option<&'a str>,
+        text: &'a str,
+    },
+    Select {
+        selector: &'a str,
+        value: &'a str,
+    },
+    Hover(&'a str),
+    Focus(&'a str),
+    Upload {
+        selector: &'a str,
+        files: &'a [String],
+    },
+    Back,
+    Forward,
+    Reload,
+}
+
+fn browser(args: &[String]) -> Result<Browser<'_>, String> {
+    let unsupported = || format!("`browser {}` has no equivalent", args.join(" "));
+    if args.iter().any(|a| a.starts_with("--")) {
+        return Err(unsupported());
+    }
+    let words: Vec<&str> = args.iter().map(String::as_str).collect();
+    Ok(match words.as_slice() {
+        ["press", key] => Browser::Press(key),
+        ["type", text] => Browser::Type {
+            selector: None,
+            text,
+        },
+        ["type", selector, text] => Browser::Type {
+            selector: Some(selector),
+            text,
+        },
+        ["select", selector, value] => Browser::Select { selector, value },
+        ["hover", selector] => Browser::Hover(selector),
+        ["focus", selector] => Browser::Focus(selector),
+        ["upload", selector, ..] => Browser::Upload {
+            selector,
+            files: &args[2..],
+        },
+        ["back"] => Browser::Back,
+        ["forward"] => Browser::Forward,
+        ["reload"] => Browser::Reload,
+        _ => return Err(unsupported()),
+    })
+}
+
+/// A JavaScript identifier for an extracted value, unique within the file.
+fn variable(name: &str, used: &mut HashSet<String>) -> String {
+    let mut base: String = name
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() { c } else { '_' })
+        .collect();
+    if base.is_empty() || base.starts_with(|c: char| c.is_ascii_digit()) {
+        base.insert(0, '_');
+    }
+    let mut ident = base.clone();
+    let mut n = 2;
+    while !used.insert(ident.clone()) {
+        ident = format!("{base}_{n}");
+        n += 1;
+    }
+    ident
+}
+
+fn playwright(step: &Step, used: &mut HashSet<String>) -> Result<String, String> {
+    let line = match &step.kind {
+        StepKind::Login { domain, .. } => {
+            return Err(format!(
+                "login {domain} reads the actionbook credential store"
+            ));
+        }
+        StepKind::Goto(url) => format!("await page.goto({});", js(url)),
+        StepKind::Wait { selector, timeout } => match timeout {
+            Some(ms) => format!("await {}.waitFor({{ timeout: {ms} }});", locator(selector)?),
+            None => format!("await {}.waitFor();", locator(selector)?),
+        },
+        StepKind::Sleep(ms) => format!("await page.waitForTimeout({ms});"),
+        StepKind::Click(selector) => format!("await {}.click();", locator(selector)?),
+        StepKind::Fill { selector, text } => {
+            format!("await {}.fill({});", locator(selector)?, js(text))
+        }
+        StepKind::Assert(Assertion::Url(url)) => {
+            format!("await expect(page).toHaveURL({});", js_regex(url))
+        }
+        StepKind::Assert(Assertion::Text { selector, contains }) => {
+            let target = locator(selector.as_deref().unwrap_or("body"))?;
+            match contains {
+                Some(text) => format!("await expect({target}).toContainText({});", js(text)),
+                None => format!("await expect({target}).toBeAttached();"),
+            }
+        }
+        StepKind::Screenshot {
+            path,
+            selector: Some(selector),
+            ..
+        } => format!(
+            "await {}.screenshot({{ path: {} }});",
+            locator(selector)?,
+            js(path)
+        ),
+        StepKind::Screenshot { path, full, .. } => {
+            let full = if *full { ", fullPage: true" } else { "" };
+            format!("await page.screenshot({{ path: {}{full} }});", js(path))
+        }
+        StepKind::Extract {
+            selector,
+            attr,
+            name,
+        } => {
+            let target = locator(selector.as_deref().unwrap_or("body"))?;
+            let value = match attr {
+                Some(attr) => format!("getAttribute({})", js(attr)),
+                None => "innerText()".to_string(),
+            };
+            format!("const {} = await {target}.{value};", variable(name, used))
+        }
+        StepKind::Browser(args) => match browser(args)? {
+            Browser::Press(key) => format!("await page.keyboard.press({});", js(key)),
+            Browser::Type {
+                selector: None,
+                text,
+            } => format!("await page.keyboard.type({});", js(text)),
+            Browser::Type {
+                selector: Some(selector),
+                text,
+            } => format!(
+                "await {}.pressSequentially({});",
+                locator(selector)?,
+                js(text)
+            ),
+            Browser::Select { selector, value } => {
+                format!("await {}.selectOption({});", locator(selector)?, js(value))
+            }
+            Browser::Hover(selector) => format!("await {}.hover();", locator(selector)?),
+            Browser::Focus(selector) => format!("await {}.focus();", locator(selector)?),
+            Browser::Upload { selector, files } => {
+                let files = match files {
+                    [file] => js(file),
+                    _ => format!(
+                        "[{}]",
+                        files.iter().map(|f| js(f)).collect::<Vec<_>>().join(", ")
+                    ),
+                };
+                format!("await {}.setInputFiles({files});", locator(selector)?)
+            }
+            Browser::Back => "await page.goBack();".to_string(),
+            Browser::Forward => "await page.goForward();".to_string(),
+            Browser::Reload => "await page.reload();".to_string(),
+        },
+    };
+    Ok(line)
+}
+
+fn puppeteer(step: &Step, used: &mut HashSet<String>) -> Result<String, String> {
+    let line = match &step.kind {
+        StepKind::Login { domain, .. } => {
+            return Err(format!(
+                "login {domain} reads the actionbook credential store"
+            ));
+        }
+        StepKind::Goto(url) => format!("await page.goto({});", js(url)),
+        StepKind::Wait { selector, timeout } => match timeout {
+            Some(ms) => format!(
+                "await page.waitForSelector({}, {{ timeout: {ms} }});",
+                p_selector(selector)?
+            ),
+            None => format!("await page.waitForSelector({});", p_selector(selector)?),
+        },
+        StepKind::Sleep(ms) => {
+            format!("await new Promise((resolve) => setTimeout(resolve, {ms}));")
+        }
+        StepKind::Click(selector) => format!("await page.click({});", p_selector(selector)?),
+        StepKind::Fill { selector, text } => format!(
+            "await page.locator({}).fill({});",
+            p_selector(selector)?,
+            js(text)
+        ),
+        StepKind::Assert(Assertion::Url(url)) => {
+            format!("assert.ok(page.url().includes({}));", js(url))
+        }
+        StepKind::Assert(Assertion::Text { selector, contains }) => {
+            let target = p_selector(selector.as_deref().unwrap_or("body"))?;
+            match contains {
+                Some(text) => format!(
+                    "assert.ok((await page.$eval({target}, (e) => e.innerText)).includes({}));",
+                    js(text)
+                ),
+                None => format!("assert.ok(await page.$({target}));"),
+            }
+        }
+        StepKind::Screenshot {
+            path,
+            selector: Some(selector),
+            ..
+        } => format!(
+            "await (await page.waitForSelector({})).screenshot({{ path: {} }});",
+            p_selector(selector)?,
+            js(path)
+        ),
+        StepKind::Screenshot { path, full, .. } => {
+            let full = if *full { ", fullPage: true" } else { "" };
+            format!("await page.screenshot({{ path: {}{full} }});", js(path))
+        }
+        StepKind::Extract {
+            selector,
+            attr,
+            name,
+        } => {
+            let target = p_selector(selector.as_deref().unwrap_or("body"))?;
+            let value = match attr {
+                Some(attr) => format!("(e) => e.getAttribute({})", js(attr)),
+                None => "(e) => e.innerText".to_string(),
+            };
+            format!(
+                "const {} = await page.$eval({target}, {value});",
+                variable(name, used)
+            )
+        }
+        StepKind::Browser(args) => match browser(args)? {
+            Browser::Press(key) => format!("await page.keyboard.press({});", js(key)),
+            Browser::Type {
+                selector: None,
+                text,
+            } => format!("await page.keyboard.type({});", js(text)),
+            Browser::Type {
+                selector: Some(selector),
+                text,
+            } => format!("await page.type({}, {});", p_selector(selector)?, js(text)),
+            Browser::Select { selector, value } => {
+                format!(
+                    "await page.select({}, {});",
+                    p_selector(selector)?,
+                    js(value)
+                )
+            }
+            Browser::Hover(selector) => format!("await page.hover({});", p_selector(selector)?),
+            Browser::Focus(selector) => format!("await page.focus({});", p_selector(selector)?),
+            Browser::Upload { selector, files } => format!(
+                "await (await page.waitForSelector({})).uploadFile({});",
+                p_selector(selector)?,
+                files.iter().map(|f| js(f)).collect::<Vec<_>>().join(", ")
+            ),
+            Browser::Back => "await page.goBack();".to_string(),
+            Browser::Forward => "await page.goForward();".to_string(),
+            Browser::Reload => "await page.reload();".to_string(),
+        },
+    };
+    Ok(line)
+}
+
+/// A step that was written as a comment.
+#[derive(Debug, Clone, PartialEq)]
+struct Skipped {
+    step: usize,
+    reason: String,
+}
+
+/// The code for `steps`, and the steps left out of it.
+fn export(steps: &[Step], format: Format, title: &str) -> (String, Vec<Skipped>) {
+    let mut used = HashSet::new();
+    let mut body = Vec::new();
+    let mut skipped = Vec::new();
+    for (i, step) in steps.iter().enumerate() {
+        if let Some(name) = &step.name {
+            body.push(format!("// {name}"));
+        }
+        let line = match format {
+            Format::Playwright => playwright(step, &mut used),
+            Format::Puppeteer => puppeteer(step, &mut used),
+        };
+        match line {
+            Ok(line) => body.push(line),
+            Err(reason) => {
+                body.push(format!("// step {} not exported: {reason}", i + 1));
+                skipped.push(Skipped {
+                    step: i + 1,
+                    reason,
+                });
+            }
+        }
+    }
+
+    let indent = |lines: &[String], depth: usize| -> String {
+        lines
+            .iter()
+            .map(|l| format!("{}{l}\n", "  ".repeat(depth)))
+            .collect()
+    };
+    let code = match format {
+        Format::Playwright => format!(
+            "import {{ test, expect }} from '@playwright/test';\n\n\
+             test({}, async ({{ page }}) => {{\n{}}});\n",
+            js(title),
+            indent(&body, 1)
+        ),
+        Format::Puppeteer => {
+            let uses_assert = body.iter().any(|l| l.starts_with("assert."));
+            format!(
+                "{}const puppeteer = require('puppeteer');\n\n\
+                 (async () => {{\n  \
+                 const browser = await puppeteer.launch();\n  \
+                 const page = await browser.newPage();\n  \
+                 try {{\n{}  }} finally {{\n    \
+                 await browser.close();\n  \
+                 }}\n\
+                 }})();\n",
+                if uses_assert {
+                    "const assert = require('node:assert/strict');\n"
+                } else {
+                    ""
+                },
+                indent(&body, 2)
+            )
+        }
+    };
+    (code, skipped)
+}
+
+/// `checkout.yaml` -> `checkout.spec.ts` or `checkout.js`, next to the flow.
+fn default_out(flow: &Path, format: Format) -> PathBuf {
+    let stem = flow
+        .file_stem()
+        .map(|s| s.to_string_lossy().into_owned())
+        .unwrap_or_else(|| "flow".to_string());
+    let ext = match format {
+        Format::Playwright => "spec.ts",
+        Format::Puppeteer => "js",
+    };
+    flow.with_file_name(format!("{stem}.{ext}"))
+}
+
+pub fn execute(cmd: &Cmd) -> ActionResult {
+    let flow = Path::new(&cmd.flow);
+    let steps = match load_steps(flow) {
+        Ok(steps) => steps,
+        Err(e) => return ActionResult::fatal("INVALID_SCRIPT", e),
+    };
+    let out = cmd
+        .out
+        .as_ref()
+        .map(PathBuf::from)
+        .unwrap_or_else(|| default_out(flow, cmd.format));
+    if out.exists() && !cmd.force {
+        return ActionResult::fatal_with_hint(
+            "INVALID_ARGUMENT",
+            format!("{} already exists", out.display()),
+            "pass --force to overwrite it, or --out to write elsewhere",
+        );
+    }
+
+    let name = flow
+        .file_name()
+        .map(|n| n.to_string_lossy().into_owned())
+        .unwrap_or_default();
+    let title = flow
+        .file_stem()
+        .map(|n| n.to_string_lossy().into_owned())
+        .unwrap_or_default();
+    let (code, skipped) = export(&steps, cmd.format, &title);
+    if skipped.len() == steps.len() {
+        return ActionResult::fatal(
+            "INVALID_SCRIPT",
+            format!("{} has no steps that can be exported", cmd.flow),
+        );
+    }
+    let text = format!("// Exported from {name} by `actionbook flow export`.\n{code}");
+    if let Err(e) = std::fs::write(&out, text) {
+        return ActionResult::fatal(
+            "IO_ERROR",
+            format!("failed to write {}: {e}", out.display()),
+        );
+    }
+
+    ActionResult::ok(json!({
+        "flow": cmd.flow,
+        "format": cmd.format.name(),
+        "path": out.display().to_string(),
+        "steps": steps.len() - skipped.len(),
+        "skipped": skipped
+            .iter()
+            .map(|s| json!({ "step": s.step, "reason": s.reason }))
+            .collect::<Vec<_>>(),
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const FLOW: &str = r##"
+steps:
+  - goto: https://shop.test/
+  - name: open the sale
+    click: role=link[name="Sale"]
+  - fill: { selector: "#search", text: "it's red" }
+  - browser: [press, Enter]
+  - assert: { url: /search?q= }
+  - assert: { selector: text=1 result }
+  - extract: { selector: '//h1', as: page-title }
+  - click: "@e12"
+  - screenshot: { path: results.png, full: true }
+"##;
+
+    fn steps() -> Vec<Step> {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("shop.yaml");
+        std::fs::write(&path, FLOW).unwrap();
+        load_steps(&path).unwrap()
+    }
+
+    #[test]
+    fn playwright_export_uses_locators_and_web_first_assertions() {
+        let (code, skipped) = export(&steps(), Format::Playwright, "shop");
+        assert_eq!(
+            code,
+            r#"import { test, expect } from '@playwright/test';
+
+test('shop', async ({ page }) => {
+  await page.goto('https://shop.test/');
+  // open the sale
+  await page.getByRole('link', { name: 'Sale' }).click();
+  await page.locator('#search').fill('it\'s red');
+  await page.keyboard.press('Enter');
+  await expect(page).toHaveURL(/\/search\?q=/);
+  await expect(page.getByText('1 result')).toBeAttached();
+  const page_title = await page.locator('xpath=//h1').innerText();
+  // step 8 not exported: snapshot ref @e12 only exists in the session that took the snapshot
+  await page.screenshot({ path: 'results.png', fullPage: true });
+});
+"#
+        );
+        assert_eq!(skipped.iter().map(|s| s.step).collect::<Vec<_>>(), [8]);
+    }
+
+    #[test]
+    fn puppeteer_export_uses_p_selectors_and_node_assert() {
+        let (code, skipped) = export(&steps(), Format::Puppeteer, "shop");
+        assert!(code.starts_with("const assert = require('node:assert/strict');\n"));
+        for line in [
+            r#"    await page.click('::-p-aria([name="Sale"][role="link"])');"#,
+            "    await page.locator('#search').fill('it\\'s red');",
+            "    assert.ok(page.url().includes('/search?q='));",
+            r#"    assert.ok(await page.$('::-p-text("1 result")'));"#,
+            r#"    const page_title = await page.$eval('::-p-xpath("//h1")', (e) => e.innerText);"#,
+            "  } finally {\n    await browser.close();\n  }\n})();\n",
+        ] {
+            assert!(code.contains(line), "missing {line:?} in\n{code}");
+        }
+        assert_eq!(skipped.len(), 1);
+    }
+
+    #[test]
+    fn browser_steps_outside_the_common_set_are_skipped() {
+        assert!(browser(&["press".into(), "Enter".into()]).is_ok());
+        assert!(browser(&["scroll".into(), "down".into()]).is_err());
+        assert!(browser(&["select".into(), "#s".into(), "--index".into(), "2".into()]).is_err());
+        let mut used = HashSet::new();
+        assert_eq!(variable("total", &mut used), "total");
+        assert_eq!(variable("total", &mut used), "total_2");
+        assert_eq!(variable("2nd", &mut used), "_2nd");
+    }
+}
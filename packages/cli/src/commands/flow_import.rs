@@ -0,0 +1,824 @@
+//! `actionbook flow import <script>`: turn a Playwright or Puppeteer script
+//! into a run script.
+//!
+//! The script is tokenized, not executed: every call chain such as
+//! `page.getByRole('button', { name: 'Buy' }).click()` or
+//! `expect(page).toHaveURL(/checkout/)` is matched against the API calls a
+//! run step can stand for. Awaited calls that match nothing are reported, so
+//! the user knows what to port by hand.
+
+use std::path::{Path, PathBuf};
+
+use clap::Args;
+use serde_json::{Value, json};
+
+use super::run::validate_steps;
+use crate::action_result::ActionResult;
+
+pub const COMMAND_NAME: &str = "flow import";
+
+/// Browser and page lifecycle calls: the session stands in for them, so
+/// they are dropped without a report.
+const LIFECYCLE: &[&str] = &[
+    "launch",
+    "connect",
+    "newPage",
+    "newContext",
+    "close",
+    "setViewport",
+    "setViewportSize",
+];
+
+/// Convert a Playwright or Puppeteer script into a run script
+#[derive(Args, Debug, Clone)]
+#[command(after_help = "\
+Examples:
+  actionbook flow import tests/login.spec.ts
+  actionbook flow import checkout.js --out flows/checkout.yaml --force
+
+Understands page.goto, click, fill, type, press, hover, focus,
+selectOption/select, setInputFiles, waitForSelector, waitForTimeout,
+screenshot and keyboard.press/type, on the page or on a locator (locator,
+getByRole, getByText, getByTestId, getByPlaceholder, getByAltText,
+getByTitle), and expect(...) with toHaveURL, toHaveTitle, toBeVisible,
+toHaveText and toContainText. Awaited calls it cannot convert are listed
+with their line numbers. Run the result with `actionbook run`.")]
+pub struct Cmd {
+    /// Playwright or Puppeteer script (.js, .ts, .mjs, ...)
+    pub script: String,
+    /// Where to write the flow (default: <script name>.yaml next to it)
+    #[arg(long, short = 'o')]
+    pub out: Option<String>,
+    /// Overwrite the output file if it exists
+    #[arg(long)]
+    pub force: bool,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Tok {
+    Ident(String),
+    Str(String),
+    /// A template literal with `${...}` in it.
+    Template,
+    Num(f64),
+    Regex(String),
+    Punct(char),
+}
+
+#[derive(Debug, Clone)]
+struct Token {
+    tok: Tok,
+    line: usize,
+    start: usize,
+    end: usize,
+}
+
+fn tokenize(src: &str) -> Vec<Token> {
+    let chars: Vec<(usize, char)> = src.char_indices().collect();
+    let at = |i: usize| chars.get(i).map(|&(_, c)| c);
+    let offset = |i: usize| chars.get(i).map_or(src.len(), |&(o, _)| o);
+    let mut out: Vec<Token> = Vec::new();
+    let mut line = 1;
+    let mut i = 0;
+    while let Some(c) = at(i) {
+        let start = i;
+        let start_line = line;
+        let tok = match c {
+            '\n' => {
+                line += 1;
+                i += 1;
+                continue;
+            }
+            c if c.is_whitespace() => {
+                i += 1;
+                continue;
+            }
+            '/' if at(i + 1) == Some('/') => {
+                while at(i).is_some_and(|c| c != '\n') {
+                    i += 1;
+                }
+                continue;
+            }
+            '/' if at(i + 1) == Some('*') => {
+                i += 2;
+                while at(i).is_some() && !(at(i) == Some('*') && at(i + 1) == Some('/')) {
+                    line += usize::from(at(i) == Some('\n'));
+                    i += 1;
+                }
+                i += 2;
+                continue;
+            }
+            '/' if regex_allowed(out.last().map(|t| &t.tok)) => {
+                let mut source = String::new();
+                let mut class = false;
+                i += 1;
+                while let Some(c) = at(i) {
+                    match c {
+                        '\\' => {
+                            source.push(c);
+                            source.extend(at(i + 1));
+                            i += 2;
+                            continue;
+                        }
+                        '[' => class = true,
+                        ']' => class = false,
+                        '/' if !class => break,
+                        '\n' => break,
+                        _ => {}
+                    }
+                    source.push(c);
+                    i += 1;
+                }
+                i += 1;
+                while at(i).is_some_and(|c| c.is_ascii_alphabetic()) {
+                    i += 1;
+                }
+                Tok::Regex(source)
+            }
+            '\'' | '"' | '`' => {
+                let quote = c;
+                let mut text = String::new();
+                let mut interpolated = false;
+                i += 1;
+                while let Some(c) = at(i) {
+                    match c {
+                        '\\' => {
+                            match at(i + 1) {
+                                Some('n') => text.push('\n'),
+                                Some('t') => text.push('\t'),
+                                Some(other) => text.push(other),
+                                None => {}
+                            }
+                            i += 2;
+                            continue;
+                        }
+                        '$' if quote == '`' && at(i + 1) == Some('{') => interpolated = true,
+                        '\n' => line += 1,
+                        c if c == quote => break,
+                        _ => {}
+                    }
+                    text.push(c);
+                    i += 1;
+                }
+                i += 1;
+                if interpolated {
+                    Tok::Template
+                } else {
+                    Tok::Str(text)
+                }
+            }
+            c if c.is_ascii_digit() => {
+                while at(i).is_some_and(|c| c.is_ascii_alphanumeric() || c == '.' || c == '_') {
+                    i += 1;
+                }
+                let text: String = chars[start..i].iter().map(|&(_, c)| c).collect();
+                Tok::Num(text.replace('_', "").parse().unwrap_or(f64::NAN))
+            }
+            c if c.is_alphabetic() || c == '_' || c == '$' => {
+                while at(i).is_some_and(|c| c.is_alphanumeric() || c == '_' || c == '$') {
+                    i += 1;
+                }
+                Tok::Ident(chars[start..i].iter().map(|&(_, c)| c).collect())
+            }
+            c => {
+                i += 1;
+                Tok::Punct(c)
+            }
+        };
+        out.push(Token {
+            tok,
+            line: start_line,
+            start: offset(start),
+            end: offset(i),
+        });
+    }
+    out
+}
+
+/// Whether a `/` after `prev` starts a regex literal rather than a division.
+fn regex_allowed(prev: Option<&Tok>) -> bool {
+    match prev {
+        None => true,
+        Some(Tok::Punct(c)) => "(,=:[!&|?{};".contains(*c),
+        Some(Tok::Ident(word)) => matches!(word.as_str(), "return" | "await" | "typeof"),
+        _ => false,
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Arg {
+    Str(String),
+    Num(f64),
+    Regex(String),
+    Object(Vec<(String, Arg)>),
+    Chain(Vec<Item>),
+    Other,
+}
+
+/// One link of a call chain: `name` or `name(args)`.
+#[derive(Debug, Clone, PartialEq)]
+struct Item {
+    name: String,
+    args: Option<Vec<Arg>>,
+}
+
+/// Index of the bracket that closes the one at `open`.
+fn matching(toks: &[Token], open: usize) -> Option<usize> {
+    let mut depth = 0usize;
+    for (i, t) in toks.iter().enumerate().skip(open) {
+        match t.tok {
+            Tok::Punct('(' | '[' | '{') => depth += 1,
+            Tok::Punct(')' | ']' | '}') => {
+                depth -= 1;
+                if depth == 0 {
+                    return Some(i);
+                }
+            }
+            _ => {}
+        }
+    }
+    None
+}
+
+/// Split `toks` at the commas outside brackets.
+fn split_commas(toks: &[Token]) -> Vec<&[Token]> {
+    let mut parts = Vec::new();
+    let mut depth = 0i32;
+    let mut from = 0;
+    for (i, t) in toks.iter().enumerate() {
+        match t.tok {
+            Tok::Punct('(' | '[' | '{') => depth += 1,
+            Tok::Punct(')' | ']' | '}') => depth -= 1,
+            Tok::Punct(',') if depth == 0 => {
+                parts.push(&toks[from..i]);
+                from = i + 1;
+            }
+            _ => {}
+        }
+    }
+    if from < toks.len() {
+        parts.push(&toks[from..]);
+    }
+    parts
+}
+
+fn parse_arg(toks: &[Token]) -> Arg {
+    match toks {
+        [] => Arg::Other,
+        [t] => match &t.tok {
+            Tok::Str(s) => Arg::Str(s.clone()),
+            Tok::Num(n) => Arg::Num(*n),
+            Tok::Regex(r) => Arg::Regex(r.clone()),
+            Tok::Ident(_) => Arg::Chain(chain(toks, 0).map(|(c, _)| c).unwrap_or_default()),
+            _ => Arg::Other,
+        },
+        [first, .., last]
+            if first.tok == Tok::Punct('{')
+                && last.tok == Tok::Punct('}')
+                && matching(toks, 0) == Some(toks.len() - 1) =>
+        {
+            let mut fields = Vec::new();
+            for field in split_commas(&toks[1..toks.len() - 1]) {
+                let key = match field.first().map(|t| &t.tok) {
+                    Some(Tok::Ident(k) | Tok::Str(k)) => k.clone(),
+                    _ => return Arg::Other,
+                };
+                let value = match field.get(1).map(|t| &t.tok) {
+                    Some(Tok::Punct(':')) => parse_arg(&field[2..]),
+                    _ => Arg::Other,
+                };
+                fields.push((key, value));
+            }
+            Arg::Object(fields)
+        }
+        _ => match chain(toks, 0) {
+            Some((items, end)) if end == toks.len() => Arg::Chain(items),
+            _ => Arg::Other,
+        },
+    }
+}
+
+/// The call chain starting at the identifier `toks[i]`, and the index just
+/// past it.
+fn chain(toks: &[Token], i: usize) -> Option<(Vec<Item>, usize)> {
+    let Tok::Ident(name) = &toks.get(i)?.tok else {
+        return None;
+    };
+    let mut items = vec![Item {
+        name: name.clone(),
+        args: None,
+    }];
+    let mut j = i + 1;
+    loop {
+        match toks.get(j).map(|t| &t.tok) {
+            Some(Tok::Punct('.')) => match toks.get(j + 1).map(|t| &t.tok) {
+                Some(Tok::Ident(name)) => {
+                    items.push(Item {
+                        name: name.clone(),
+                        args: None,
+                    });
+                    j += 2;
+                }
+                _ => break,
+            },
+            Some(Tok::Punct('(')) => {
+                let close = matching(toks, j)?;
+                let args = split_commas(&toks[j + 1..close])
+                    .into_iter()
+                    .map(parse_arg)
+                    .collect();
+                let last = items.last_mut().expect("chain starts with an item");
+                if last.args.is_some() {
+                    items.push(Item {
+                        name: String::new(),
+                        args: Some(args),
+                    });
+                } else {
+                    last.args = Some(args);
+                }
+                j = close + 1;
+            }
+            _ => break,
+        }
+    }
+    Some((items, j))
+}
+
+fn str_arg(args: &[Arg], i: usize) -> Option<String> {
+    match args.get(i)? {
+        Arg::Str(s) => Some(s.clone()),
+        _ => None,
+    }
+}
+
+fn field<'a>(arg: Option<&'a Arg>, key: &str) -> Option<&'a Arg> {
+    match arg? {
+        Arg::Object(fields) => fields.iter().find(|(k, _)| k == key).map(|(_, v)| v),
+        _ => None,
+    }
+}
+
+/// A regex with no special characters, as the plain text it matches.
+fn regex_text(source: &str) -> Option<String> {
+    let mut out = String::new();
+    let mut chars = source.chars();
+    while let Some(c) = chars.next() {
+        match c {
+            '\\' => match chars.next()? {
+                c if c.is_ascii_alphanumeric() => return None,
+                c => out.push(c),
+            },
+            '^' | '$' | '*' | '+' | '?' | '(' | ')' | '[' | ']' | '{' | '}' | '|' | '.' => {
+                return None;
+            }
+            c => out.push(c),
+        }
+    }
+    Some(out)
+}
+
+fn attr_selector(attr: &str, value: &str) -> String {
+    format!(
+        "[{attr}=\"{}\"]",
+        value.replace('\\', "\\\\").replace('"', "\\\"")
+    )
+}
+
+/// The selector a chain of locator calls stands for.
+fn locator(items: &[Item]) -> Option<String> {
+    let mut css: Vec<String> = Vec::new();
+    let mut engine: Option<String> = None;
+    for item in items {
+        let args = item.args.as_deref()?;
+        let part = match item.name.as_str() {
+            "first" if args.is_empty() => continue,
+            "locator" | "$" => str_arg(args, 0)?,
+            "getByTestId" => attr_selector("data-testid", &str_arg(args, 0)?),
+            "getByPlaceholder" => attr_selector("placeholder", &str_arg(args, 0)?),
+            "getByAltText" => attr_selector("alt", &str_arg(args, 0)?),
+            "getByTitle" => attr_selector("title", &str_arg(args, 0)?),
+            "getByText" => {
+                let text = str_arg(args, 0)?;
+                let exact = matches!(field(args.get(1), "exact"), Some(Arg::Chain(c)) if c[0].name == "true");
+                engine = Some(if exact {
+                    format!("text=\"{text}\"")
+                } else {
+                    format!("text={text}")
+                });
+                continue;
+            }
+            "getByRole" => {
+                let role = str_arg(args, 0)?;
+                engine = Some(match field(args.get(1), "name") {
+                    Some(Arg::Str(name)) => format!("role={role}[name=\"{name}\"]"),
+                    None => format!("role={role}"),
+                    Some(_) => return None,
+                });
+                continue;
+            }
+            _ => return None,
+        };
+        // Engine selectors cannot be scoped by CSS, or the other way round.
+        if engine.is_some() {
+            return None;
+        }
+        css.push(part);
+    }
+    match engine {
+        Some(e) if css.is_empty() => Some(e),
+        Some(_) => None,
+        None if css.is_empty() => None,
+        None => Some(css.join(" ")),
+    }
+}
+
+fn browser(args: &[&str]) -> Value {
+    json!({ "browser": args })
+}
+
+/// The run steps for `expect(target).<matcher>`.
+fn assertion(items: &[Item]) -> Option<Vec<Value>> {
+    let [target] = items[0].args.as_deref()? else {
+        return None;
+    };
+    let Arg::Chain(target) = target else {
+        return None;
+    };
+    if items.iter().any(|i| i.name == "not") {
+        return None;
+    }
+    let matcher = items
+        .last()
+        .filter(|m| m.args.is_some() && items.len() > 1)?;
+    let args = matcher.args.as_deref()?;
+    let step = if target.len() == 1 {
+        match matcher.name.as_str() {
+            "toHaveURL" => match args.first()? {
+                Arg::Str(url) => json!({ "assert": { "url": url } }),
+                Arg::Regex(r) => json!({ "assert": { "url": regex_text(r)? } }),
+                _ => return None,
+            },
+            "toHaveTitle" => {
+                json!({ "assert": { "selector": "title", "text": str_arg(args, 0)? } })
+            }
+            _ => return None,
+        }
+    } else {
+        let selector = locator(&target[1..])?;
+        match matcher.name.as_str() {
+            "toBeVisible" | "toBeAttached" => json!({ "assert": { "selector": selector } }),
+            "toHaveText" | "toContainText" => {
+                json!({ "assert": { "selector": selector, "text": str_arg(args, 0)? } })
+            }
+            _ => return None,
+        }
+    };
+    Some(vec![step])
+}
+
+/// The run steps a page call such as `page.click('#go')` stands for.
+fn page_call(name: &str, args: &[Arg]) -> Option<Vec<Value>> {
+    let s = |i| str_arg(args, i);
+    let step = match name {
+        "goto" => json!({ "goto": s(0)? }),
+        "click" => json!({ "click": s(0)? }),
+        "fill" => json!({ "fill": { "selector": s(0)?, "text": s(1)? } }),
+        "type" => browser(&["type", &s(0)?, &s(1)?]),
+        "press" => {
+            return Some(vec![
+                browser(&["focus", &s(0)?]),
+                browser(&["press", &s(1)?]),
+            ]);
+        }
+        "hover" => browser(&["hover", &s(0)?]),
+        "focus" => browser(&["focus", &s(0)?]),
+        "selectOption" | "select" => browser(&["select", &s(0)?, &s(1)?]),
+        "setInputFiles" | "uploadFile" => browser(&["upload", &s(0)?, &s(1)?]),
+        "waitForSelector" => json!({ "wait": s(0)? }),
+        "waitForTimeout" => match args.first()? {
+            Arg::Num(ms) if *ms >= 0.0 => json!({ "wait": *ms as u64 }),
+            _ => return None,
+        },
+        "screenshot" => screenshot(args.first(), None)?,
+        _ => return None,
+    };
+    Some(vec![step])
+}
+
+/// The run steps `<locator>.<name>(args)` stands for.
+fn locator_call(selector: String, name: &str, args: &[Arg]) -> Option<Vec<Value>> {
+    let s = |i| str_arg(args, i);
+    let step = match name {
+        "click" => json!({ "click": selector }),
+        "fill" => json!({ "fill": { "selector": selector, "text": s(0)? } }),
+        "type" | "pressSequentially" => browser(&["type", &selector, &s(0)?]),
+        "press" => {
+            return Some(vec![
+                browser(&["focus", &selector]),
+                browser(&["press", &s(0)?]),
+            ]);
+        }
+        "hover" => browser(&["hover", &selector]),
+        "focus" => browser(&["focus", &selector]),
+        "selectOption" => browser(&["select", &selector, &s(0)?]),
+        "setInputFiles" => browser(&["upload", &selector, &s(0)?]),
+        "waitFor" => json!({ "wait": selector }),
+        "screenshot" => screenshot(args.first(), Some(selector))?,
+        _ => return None,
+    };
+    Some(vec![step])
+}
+
+fn screenshot(options: Option<&Arg>, selector: Option<String>) -> Option<Value> {
+    let Some(Arg::Str(path)) = field(options, "path") else {
+        return None;
+    };
+    let full = matches!(field(options, "fullPage"), Some(Arg::Chain(c)) if c[0].name == "true");
+    let mut step = json!({ "path": path });
+    if full {
+        step["full"] = json!(true);
+    }
+    if let Some(selector) = selector {
+        step["selector"] = json!(selector);
+    }
+    Some(json!({ "screenshot": step }))
+}
+
+/// The run steps a call chain stands for, if any.
+fn convert(items: &[Item]) -> Option<Vec<Value>> {
+    if items[0].name == "expect" {
+        return assertion(items);
+    }
+    if items[0].args.is_some() || items.len() < 2 {
+        return None;
+    }
+    let rest = &items[1..];
+    let last = rest.last()?;
+    let args = last.args.as_deref()?;
+    match rest {
+        [only] => page_call(&only.name, args),
+        [keyboard, key] if keyboard.name == "keyboard" && keyboard.args.is_none() => {
+            match (key.name.as_str(), str_arg(args, 0)) {
+                ("press", Some(k)) => Some(vec![browser(&["press", &k])]),
+                ("type" | "insertText", Some(text)) => Some(vec![browser(&["type", &text])]),
+                _ => None,
+            }
+        }
+        _ => locator_call(locator(&rest[..rest.len() - 1])?, &last.name, args),
+    }
+}
+
+/// A statement the importer could not convert.
+#[derive(Debug, Clone, PartialEq)]
+struct Skipped {
+    line: usize,
+    code: String,
+}
+
+/// Steps for every convertible call in `src`, and the awaited calls and
+/// assertions that were left out.
+fn import(src: &str) -> (Vec<Value>, Vec<Skipped>) {
+    let toks = tokenize(src);
+    let mut steps = Vec::new();
+    let mut converted: Vec<usize> = Vec::new();
+    let mut unconverted: Vec<(usize, usize)> = Vec::new();
+    let mut i = 0;
+    while i < toks.len() {
+        let after_dot = i > 0 && toks[i - 1].tok == Tok::Punct('.');
+        let chained = match &toks[i].tok {
+            Tok::Ident(_) if !after_dot => chain(&toks, i),
+            _ => None,
+        };
+        if let Some((items, end)) = chained {
+            if let Some(found) = convert(&items) {
+                steps.extend(found);
+                converted.push(i);
+                i = end;
+                continue;
+            }
+            let awaited = i > 0 && toks[i - 1].tok == Tok::Ident("await".into());
+            let lifecycle = items.iter().any(|it| LIFECYCLE.contains(&it.name.as_str()));
+            if (awaited || items[0].name == "expect") && !lifecycle && items.len() > 1 {
+                unconverted.push((i, end));
+            }
+        }
+        i += 1;
+    }
+    // A skipped statement that had convertible calls inside is not reported.
+    let skipped = unconverted
+        .into_iter()
+        .filter(|&(start, end)| !converted.iter().any(|&c| start <= c && c < end))
+        .map(|(start, end)| {
+            let code = src[toks[start].start..toks[end - 1].end].trim();
+            let code = code.lines().next().unwrap_or(code);
+            Skipped {
+                line: toks[start].line,
+                code: if code.chars().count() > 120 {
+                    format!("{}...", code.chars().take(117).collect::<String>())
+                } else {
+                    code.to_string()
+                },
+            }
+        })
+        .collect();
+    (steps, skipped)
+}
+
+/// `login.spec.ts` -> `login.yaml`, next to the script.
+fn default_out(script: &Path) -> PathBuf {
+    let name = script
+        .file_name()
+        .map(|n| n.to_string_lossy().into_owned())
+        .unwrap_or_default();
+    let mut stem = name.as_str();
+    for ext in [".ts", ".tsx", ".js", ".mjs", ".cjs", ".jsx"] {
+        if let Some(rest) = stem.strip_suffix(ext) {
+            stem = rest;
+            break;
+        }
+    }
+    for suffix in [".spec", ".test"] {
+        if let Some(rest) = stem.strip_suffix(suffix) {
+            stem = rest;
+            break;
+        }
+    }
+    let stem = if stem.is_empty() { "flow" } else { stem };
+    script.with_file_name(format!("{stem}.yaml"))
+}
+
+pub fn execute(cmd: &Cmd) -> ActionResult {
+    let script = Path::new(&cmd.script);
+    let src = match std::fs::read_to_string(script) {
+        Ok(s) => s,
+        Err(e) => {
+            return ActionResult::fatal("IO_ERROR", format!("failed to read {}: {e}", cmd.script));
+        }
+    };
+    let out = cmd
+        .out
+        .as_ref()
+        .map(PathBuf::from)
+        .unwrap_or_else(|| default_out(script));
+    if out.exists() && !cmd.force {
+        return ActionResult::fatal_with_hint(
+            "INVALID_ARGUMENT",
+            format!("{} already exists", out.display()),
+            "pass --force to overwrite it, or --out to write elsewhere",
+        );
+    }
+
+    let (steps, skipped) = import(&src);
+    if steps.is_empty() {
+        return ActionResult::fatal_with_hint(
+            "INVALID_SCRIPT",
+            format!("found no Playwright or Puppeteer steps in {}", cmd.script),
+            "see `actionbook flow import --help` for the calls it understands",
+        );
+    }
+    // The importer only writes steps `run` accepts; anything else is a bug.
+    let errors = validate_steps(&steps);
+    if !errors.is_empty() {
+        return ActionResult::fatal("INTERNAL_ERROR", errors.join("; "));
+    }
+
+    let mut text = format!(
+        "# Imported from {} by `actionbook flow import`.\n",
+        script
+            .file_name()
+            .map(|n| n.to_string_lossy().into_owned())
+            .unwrap_or_default()
+    );
+    for s in &skipped {
+        text.push_str(&format!("# Not converted (line {}): {}\n", s.line, s.code));
+    }
+    match serde_yaml::to_string(&json!({ "steps": steps })) {
+        Ok(yaml) => text.push_str(&yaml),
+        Err(e) => return ActionResult::fatal("INTERNAL_ERROR", e.to_string()),
+    }
+    if let Err(e) = std::fs::write(&out, text) {
+        return ActionResult::fatal(
+            "IO_ERROR",
+            format!("failed to write {}: {e}", out.display()),
+        );
+    }
+
+    ActionResult::ok(json!({
+        "script": cmd.script,
+        "path": out.display().to_string(),
+        "steps": steps.len(),
+        "skipped": skipped
+            .iter()
+            .map(|s| json!({ "line": s.line, "code": s.code }))
+            .collect::<Vec<_>>(),
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PLAYWRIGHT: &str = r#"
+import { test, expect } from '@playwright/test';
+
+test('checkout', async ({ page }) => {
+  await page.goto('https://shop.test/');
+  await page.getByRole('link', { name: 'Sale' }).click();
+  await page.locator('#search').fill("red shoes");
+  await page.getByPlaceholder('Search').press('Enter');
+  // await page.click('#commented-out');
+  await expect(page).toHaveURL(/search/);
+  await expect(page.getByTestId('results')).toContainText('1 result');
+  await page.route('**/api/**', route => route.abort());
+  await page.screenshot({ path: 'results.png', fullPage: true });
+});
+"#;
+
+    #[test]
+    fn playwright_calls_become_run_steps() {
+        let (steps, skipped) = import(PLAYWRIGHT);
+        assert_eq!(
+            steps,
+            vec![
+                json!({ "goto": "https://shop.test/" }),
+                json!({ "click": "role=link[name=\"Sale\"]" }),
+                json!({ "fill": { "selector": "#search", "text": "red shoes" } }),
+                json!({ "browser": ["focus", "[placeholder=\"Search\"]"] }),
+                json!({ "browser": ["press", "Enter"] }),
+                json!({ "assert": { "url": "search" } }),
+                json!({ "assert": { "selector": "[data-testid=\"results\"]", "text": "1 result" } }),
+                json!({ "screenshot": { "path": "results.png", "full": true } }),
+            ]
+        );
+        assert_eq!(
+            skipped,
+            vec![Skipped {
+                line: 12,
+                code: "page.route('**/api/**', route => route.abort())".into(),
+            }]
+        );
+        assert!(validate_steps(&steps).is_empty());
+    }
+
+    #[test]
+    fn puppeteer_calls_become_run_steps() {
+        let src = r#"
+const puppeteer = require('puppeteer');
+(async () => {
+  const browser = await puppeteer.launch();
+  const page = await browser.newPage();
+  await page.goto(`https://example.com/login`);
+  await page.type('#user', 'ada');
+  await page.waitForSelector('.ready');
+  await Promise.all([page.waitForNavigation(), page.click('button[type=submit]')]);
+  await page.keyboard.press('Tab');
+  await page.waitForTimeout(500);
+  const title = await page.title();
+  await browser.close();
+})();
+"#;
+        let (steps, skipped) = import(src);
+        assert_eq!(
+            steps,
+            vec![
+                json!({ "goto": "https://example.com/login" }),
+                json!({ "browser": ["type", "#user", "ada"] }),
+                json!({ "wait": ".ready" }),
+                json!({ "click": "button[type=submit]" }),
+                json!({ "browser": ["press", "Tab"] }),
+                json!({ "wait": 500 }),
+            ]
+        );
+        assert_eq!(
+            skipped.iter().map(|s| s.line).collect::<Vec<_>>(),
+            [12],
+            "{skipped:?}"
+        );
+    }
+
+    #[test]
+    fn unsupported_matchers_and_locators_are_not_guessed() {
+        let (steps, skipped) = import(
+            "await expect(page.locator('li')).not.toBeVisible();\n\
+             await page.getByLabel('Email').fill('a@b.c');\n\
+             await expect(page).toHaveURL(/order\\/\\d+/);\n",
+        );
+        assert!(steps.is_empty(), "{steps:?}");
+        assert_eq!(
+            skipped.iter().map(|s| s.line).collect::<Vec<_>>(),
+            [1, 2, 3]
+        );
+    }
+
+    #[test]
+    fn default_out_drops_test_suffixes() {
+        assert_eq!(
+            default_out(Path::new("tests/login.spec.ts")),
+            Path::new("tests/login.yaml")
+        );
+        assert_eq!(
+            default_out(Path::new("checkout.js")),
+            Path::new("checkout.yaml")
+        );
+    }
+}
@@ -1,29 +1,155 @@
-use crate::api::ApiClient;
+use crate::api::{ApiClient, GetActionParams};
 use crate::cli::Cli;
 use crate::config;
 use crate::error::CliError;
 
-pub async fn run(cli: &Cli, area_id: &str) -> Result<(), CliError> {
+/// Rough characters-per-token ratio used for `--max-tokens`.
+const CHARS_PER_TOKEN: usize = 4;
+
+pub async fn run(
+    cli: &Cli,
+    area_id: &str,
+    sections: &[String],
+    max_tokens: Option<u32>,
+) -> Result<(), CliError> {
     let mut config = config::load_config()?;
     if let Some(ref key) = cli.api_key {
         config.api.api_key = Some(key.clone());
     }
     let client = ApiClient::from_config(&config)?;
 
-    let result = client.get_action_by_area_id(area_id).await?;
+    let sections: Vec<String> = sections
+        .iter()
+        .map(|s| s.trim().to_lowercase())
+        .filter(|s| !s.is_empty())
+        .collect();
+    let document = client
+        .get_action_by_area_id(&GetActionParams {
+            area_id: area_id.to_string(),
+            sections: sections.clone(),
+            max_tokens,
+        })
+        .await?;
+
+    // Trim here too, for servers that return the full document.
+    let mut result = if sections.is_empty() {
+        document
+    } else {
+        select_sections(&document, &sections)
+    };
+    let mut truncated = false;
+    if let Some(max) = max_tokens {
+        (result, truncated) = truncate_to_tokens(&result, max as usize);
+    }
 
     if cli.json {
-        println!(
-            "{}",
-            serde_json::json!({
-                "success": true,
-                "area_id": area_id,
-                "result": result,
-            })
-        );
+        let mut out = serde_json::json!({
+            "success": true,
+            "area_id": area_id,
+            "result": result,
+        });
+        if !sections.is_empty() {
+            out["sections"] = serde_json::json!(section_names(&result));
+        }
+        if max_tokens.is_some() {
+            out["truncated"] = serde_json::json!(truncated);
+        }
+        println!("{out}");
     } else {
         println!("{}", result);
     }
 
     Ok(())
 }
+
+/// `## Heading` → `heading`, for matching against `--sections`.
+fn heading_key(line: &str) -> Option<String> {
+    let title = line.strip_prefix("## ")?;
+    Some(title.trim().to_lowercase())
+}
+
+/// `wanted` matches a heading by name or first word, so `steps` selects
+/// `## Steps` and `## Steps to run` alike.
+fn wanted_section(key: &str, wanted: &[String]) -> bool {
+    wanted
+        .iter()
+        .any(|w| key == w || key.split_whitespace().next() == Some(w.as_str()))
+}
+
+/// Keep the text before the first `## ` heading plus the requested
+/// sections, in document order.
+fn select_sections(document: &str, wanted: &[String]) -> String {
+    let mut out = Vec::new();
+    let mut keep = true;
+    for line in document.lines() {
+        if let Some(key) = heading_key(line) {
+            keep = wanted_section(&key, wanted);
+        }
+        if keep {
+            out.push(line);
+        }
+    }
+    out.join("\n").trim_end().to_string()
+}
+
+fn section_names(document: &str) -> Vec<String> {
+    document
+        .lines()
+        .filter_map(|l| l.strip_prefix("## "))
+        .map(|t| t.trim().to_string())
+        .collect()
+}
+
+/// Cut at a line boundary to about `max_tokens`, marking the cut.
+fn truncate_to_tokens(text: &str, max_tokens: usize) -> (String, bool) {
+    let budget = max_tokens * CHARS_PER_TOKEN;
+    if text.len() <= budget {
+        return (text.to_string(), false);
+    }
+    let mut out = String::new();
+    for line in text.lines() {
+        if out.len() + line.len() + 1 > budget {
+            break;
+        }
+        out.push_str(line);
+        out.push('\n');
+    }
+    out.push_str(&format!(
+        "\n[truncated to ~{max_tokens} tokens; use --sections or a larger --max-tokens]"
+    ));
+    (out, true)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const DOC: &str = "Action: airbnb.com:/:default\n\n## Overview\nSearch stays.\n\n## Steps to run\n1. Fill location\n\n## Selectors\n#q\n\n## Notes\nSlow on mobile.";
+
+    #[test]
+    fn select_sections_keeps_the_preamble_and_named_sections() {
+        let wanted = vec!["steps".to_string(), "selectors".to_string()];
+        assert_eq!(
+            select_sections(DOC, &wanted),
+            "Action: airbnb.com:/:default\n\n## Steps to run\n1. Fill location\n\n## Selectors\n#q"
+        );
+        assert_eq!(
+            section_names(&select_sections(DOC, &wanted)),
+            ["Steps to run", "Selectors"]
+        );
+    }
+
+    #[test]
+    fn truncate_to_tokens_cuts_at_a_line() {
+        let (out, truncated) = truncate_to_tokens(DOC, 10);
+        assert!(truncated);
+        assert!(out.starts_with("Action: airbnb.com:/:default\n"));
+        assert!(out.ends_with("larger --max-tokens]"));
+        assert!(!out.contains("Search stays"));
+
+        assert_eq!(
+            truncate_to_tokens("short", 10),
+            ("short".to_string(), false)
+        );
+    }
+}
@@ -0,0 +1,266 @@
+//! Self-healing selectors for `actionbook run`.
+//!
+//! Recorded steps carry an `anchor`: the element's fingerprint at recording
+//! time (see `flow_recording::FINGERPRINT_JS`). When such a step still cannot
+//! find its element after its retries, every visible element of the same tag
+//! or role is scored against the anchor in the page, and the step is tried
+//! once more on the best match if it clearly wins. `--save-healed` writes the
+//! new selectors back into the script.
+
+use std::path::Path;
+
+use serde_json::Value;
+
+use super::run::META_KEYS;
+use crate::daemon::flow_recording::{FINGERPRINT_JS, SELECTOR_JS};
+
+/// Lowest score a candidate needs. A name match alone (4) is not enough; it
+/// also has to share the tag, role or surroundings.
+const MIN_SCORE: u64 = 5;
+
+/// Scores the page against `want`. Only candidates that match on name, text
+/// or an attribute count at all: role, tag and nearby text just break ties.
+const HEAL_SRC: &str = r#"(() => {
+  const selectorFor = /*SELECTOR*/;
+  const fingerprint = /*FINGERPRINT*/;
+  const want = /*ANCHOR*/;
+  const old = /*OLD*/;
+  const weight = (k) => (k === 'id' || k === 'data-testid' ? 3 : 2);
+  const overlaps = (a, b) => !!a && !!b && (a.includes(b) || b.includes(a));
+  const score = (fp) => {
+    let s = 0;
+    const why = [];
+    const add = (n, k) => { s += n; why.push(k); };
+    if (want.name && fp.name === want.name) add(4, 'name');
+    else if (overlaps(want.name, fp.name)) add(2, 'name~');
+    if (want.text && fp.text === want.text) add(3, 'text');
+    for (const [k, v] of Object.entries(want.attrs || {})) if (fp.attrs[k] === v) add(weight(k), k);
+    if (!why.length) return { s: 0, why };
+    if (want.near && fp.near === want.near) add(2, 'near');
+    else if (overlaps(want.near, fp.near)) add(1, 'near~');
+    if (want.role && fp.role === want.role) add(2, 'role');
+    if (fp.tag === want.tag) add(1, 'tag');
+    return { s, why };
+  };
+  let max = (want.name ? 4 : 0) + (want.text ? 3 : 0) + (want.near ? 2 : 0) + (want.role ? 2 : 0) + 1;
+  for (const k of Object.keys(want.attrs || {})) max += weight(k);
+  const scored = [];
+  for (const el of document.querySelectorAll('body *')) {
+    if (!el.getClientRects().length) continue;
+    const fp = fingerprint(el);
+    if (fp.tag !== want.tag && (!want.role || fp.role !== want.role)) continue;
+    const { s, why } = score(fp);
+    if (s >= /*MIN*/) scored.push({ el, s, why });
+  }
+  scored.sort((a, b) => b.s - a.s);
+  if (!scored.length) return { candidates: 0, max };
+  const best = scored[0];
+  if (scored.length > 1 && scored[1].s === best.s) return { candidates: scored.length, tied: true, score: best.s, max };
+  const selector = selectorFor(best.el);
+  return { selector, score: best.s, max, matched: best.why, candidates: scored.length, same: selector === old };
+})()"#;
+
+/// The `browser eval` expression that looks for the element `anchor`
+/// describes. `selector` is the one that stopped working.
+pub(crate) fn script(anchor: &Value, selector: &str) -> String {
+    HEAL_SRC
+        .replace("/*SELECTOR*/", SELECTOR_JS)
+        .replace("/*FINGERPRINT*/", FINGERPRINT_JS)
+        .replace("/*ANCHOR*/", &anchor.to_string())
+        .replace("/*OLD*/", &Value::from(selector).to_string())
+        .replace("/*MIN*/", &MIN_SCORE.to_string())
+}
+
+/// The element the page script settled on.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct Candidate {
+    pub selector: String,
+    pub score: u64,
+    pub max: u64,
+    pub matched: Vec<String>,
+}
+
+/// Read the page script's answer; `Err` says why nothing was picked.
+pub(crate) fn parse(found: &Value) -> Result<Candidate, String> {
+    let n = |k: &str| found.get(k).and_then(|v| v.as_u64()).unwrap_or(0);
+    if found.get("tied").and_then(|v| v.as_bool()) == Some(true) {
+        return Err(format!(
+            "{} elements match the anchor equally well",
+            n("candidates")
+        ));
+    }
+    let Some(selector) = found.get("selector").and_then(|v| v.as_str()) else {
+        return Err("no element matches the anchor".into());
+    };
+    if found.get("same").and_then(|v| v.as_bool()) == Some(true) {
+        return Err("the best match is the element the selector already names".into());
+    }
+    Ok(Candidate {
+        selector: selector.to_string(),
+        score: n("score"),
+        max: n("max"),
+        matched: found
+            .get("matched")
+            .and_then(|v| v.as_array())
+            .map(|a| {
+                a.iter()
+                    .filter_map(|v| v.as_str().map(String::from))
+                    .collect()
+            })
+            .unwrap_or_default(),
+    })
+}
+
+/// Whether a step of `action` that failed with `code` lost its element.
+pub(crate) fn applies(action: &str, code: &str) -> bool {
+    match code {
+        "ELEMENT_NOT_FOUND" | "ELEMENT_NOT_VISIBLE" => true,
+        // `wait element` times out instead of reporting a missing element.
+        "TIMEOUT" => action == "wait",
+        _ => false,
+    }
+}
+
+/// Replace the selector of one raw script step in place.
+fn set_selector(step: &mut serde_yaml::Value, selector: &str) -> bool {
+    let Some(map) = step.as_mapping_mut() else {
+        return false;
+    };
+    let Some((key, arg)) = map
+        .iter_mut()
+        .find(|(k, _)| k.as_str().is_some_and(|k| !META_KEYS.contains(&k)))
+    else {
+        return false;
+    };
+    let new = serde_yaml::Value::from(selector);
+    if key.as_str() == Some("browser") {
+        // `browser: [select, <selector>, ...]`, or the same as one string.
+        let mut args: Vec<serde_yaml::Value> = match arg {
+            serde_yaml::Value::Sequence(seq) => seq.clone(),
+            serde_yaml::Value::String(s) => s.split_whitespace().map(Into::into).collect(),
+            _ => return false,
+        };
+        if args.len() < 2 {
+            return false;
+        }
+        args[1] = new;
+        *arg = serde_yaml::Value::Sequence(args);
+        return true;
+    }
+    match arg {
+        // The short forms `click: <selector>` and `wait: <selector>`.
+        serde_yaml::Value::String(_) if matches!(key.as_str(), Some("click" | "wait")) => {
+            *arg = new
+        }
+        serde_yaml::Value::Mapping(m) if m.contains_key("selector") => {
+            m.insert("selector".into(), new);
+        }
+        _ => return false,
+    }
+    true
+}
+
+/// Write healed selectors (by 0-based step index) back into the script,
+/// keeping its format and key order. Comments do not survive the rewrite.
+pub(crate) fn save(path: &Path, healed: &[(usize, String)]) -> Result<(), String> {
+    let text = std::fs::read_to_string(path).map_err(|e| e.to_string())?;
+    // JSON is YAML, and YAML mappings keep their key order.
+    let mut script: serde_yaml::Value = serde_yaml::from_str(&text).map_err(|e| e.to_string())?;
+    let steps = script
+        .get_mut("steps")
+        .and_then(|s| s.as_sequence_mut())
+        .ok_or("the script has no steps")?;
+    for (i, selector) in healed {
+        let step = steps
+            .get_mut(*i)
+            .ok_or_else(|| format!("the script has no step {}", i + 1))?;
+        if !set_selector(step, selector) {
+            return Err(format!("step {} has no selector to replace", i + 1));
+        }
+    }
+    let is_json = path
+        .extension()
+        .is_some_and(|e| e.eq_ignore_ascii_case("json"));
+    let out = if is_json {
+        serde_json::to_string_pretty(&script).map_err(|e| e.to_string())? + "\n"
+    } else {
+        serde_yaml::to_string(&script).map_err(|e| e.to_string())?
+    };
+    std::fs::write(path, out).map_err(|e| e.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn parse_reports_why_nothing_was_picked() {
+        let found = json!({
+            "selector": "button[data-testid=\"buy\"]",
+            "score": 9,
+            "max": 12,
+            "matched": ["name", "data-testid"],
+            "candidates": 2,
+            "same": false,
+        });
+        assert_eq!(
+            parse(&found).unwrap(),
+            Candidate {
+                selector: "button[data-testid=\"buy\"]".into(),
+                score: 9,
+                max: 12,
+                matched: vec!["name".into(), "data-testid".into()],
+            }
+        );
+        assert!(
+            parse(&json!({ "candidates": 3, "tied": true, "score": 6 }))
+                .unwrap_err()
+                .contains("3 elements")
+        );
+        assert!(parse(&json!({ "candidates": 0 })).is_err());
+        assert!(parse(&json!({ "selector": "#a", "same": true })).is_err());
+    }
+
+    #[test]
+    fn save_rewrites_only_healed_selectors() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("flow.yaml");
+        std::fs::write(
+            &path,
+            "session: s1\nsteps:\n- goto: https://a.test/\n- click: '#old'\n  anchor: { tag: button }\n- fill: { selector: '#q', text: hi }\n- browser: 'select #size L'\n",
+        )
+        .unwrap();
+
+        save(
+            &path,
+            &[
+                (1, "#new".into()),
+                (2, "input[name=\"q\"]".into()),
+                (3, "#sz".into()),
+            ],
+        )
+        .unwrap();
+
+        let script: Value = serde_yaml::from_str(&std::fs::read_to_string(&path).unwrap()).unwrap();
+        assert_eq!(script["session"], "s1");
+        assert_eq!(script["steps"][0]["goto"], "https://a.test/");
+        assert_eq!(script["steps"][1]["click"], "#new");
+        assert_eq!(script["steps"][1]["anchor"]["tag"], "button");
+        assert_eq!(script["steps"][2]["fill"]["selector"], "input[name=\"q\"]");
+        assert_eq!(script["steps"][2]["fill"]["text"], "hi");
+        assert_eq!(script["steps"][3]["browser"], json!(["select", "#sz", "L"]));
+
+        let json_path = dir.path().join("flow.json");
+        std::fs::write(&json_path, r#"{"steps":[{"goto":"https://a.test/"}]}"#).unwrap();
+        assert!(save(&json_path, &[(0, "#x".into())]).is_err());
+    }
+
+    #[test]
+    fn healing_applies_to_lost_elements_only() {
+        assert!(applies("click", "ELEMENT_NOT_FOUND"));
+        assert!(applies("wait", "TIMEOUT"));
+        assert!(!applies("goto", "TIMEOUT"));
+        assert!(!applies("assert", "ASSERTION_FAILED"));
+    }
+}
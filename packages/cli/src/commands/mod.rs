@@ -1,3 +1,10 @@
+pub(crate) mod breakpoint;
+pub mod flow_export;
+pub mod flow_import;
 pub mod get;
+pub(crate) mod heal;
 pub mod manual;
+pub mod publish;
+pub mod report;
+pub mod run;
 pub mod search;
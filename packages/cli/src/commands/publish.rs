@@ -0,0 +1,222 @@
+//! `actionbook publish <draft>`: validate a locally drafted action book and
+//! upload it.
+//!
+//! A draft is a script written by `browser record stop` (or by hand) with the
+//! action book's identity added on top:
+//!
+//! ```yaml
+//! area_id: airbnb.com:/:default
+//! title: Search stays
+//! url: https://www.airbnb.com/
+//! steps:
+//!   - fill: { selector: "#bigsearch-query-location-input", text: Paris }
+//!   - click: "[data-testid=structured-search-input-search-button]"
+//! ```
+//!
+//! Steps are checked with the same parser `actionbook run` uses, so a draft
+//! that publishes is a draft that runs.
+
+use std::path::Path;
+
+use serde_json::{Value, json};
+
+use crate::api::{ApiClient, PublishActionParams};
+use crate::cli::Cli;
+use crate::config;
+use crate::error::CliError;
+
+/// Keys a draft may carry; `session` and friends come from recorded scripts.
+const DRAFT_KEYS: &[&str] = &[
+    "area_id",
+    "title",
+    "description",
+    "url",
+    "steps",
+    "session",
+    "tab",
+    "retries",
+    "retry_delay_ms",
+    "capture",
+];
+
+pub async fn run(
+    cli: &Cli,
+    draft_path: &str,
+    source: Option<&str>,
+    dry_run: bool,
+) -> Result<(), CliError> {
+    let draft = load_draft(Path::new(draft_path))
+        .map_err(|e| CliError::InvalidArgument(format!("failed to load {draft_path}: {e}")))?;
+    let errors = validate_draft(&draft);
+    let area_id = draft
+        .get("area_id")
+        .and_then(|v| v.as_str())
+        .unwrap_or_default()
+        .to_string();
+    let steps = draft
+        .get("steps")
+        .and_then(|v| v.as_array())
+        .map_or(0, Vec::len);
+
+    if dry_run {
+        if cli.json {
+            println!(
+                "{}",
+                json!({
+                    "success": errors.is_empty(),
+                    "valid": errors.is_empty(),
+                    "area_id": area_id,
+                    "steps": steps,
+                    "errors": errors,
+                })
+            );
+        } else if errors.is_empty() {
+            println!("{draft_path} is valid: {area_id} ({steps} steps)");
+        } else {
+            for e in &errors {
+                println!("  {e}");
+            }
+        }
+    }
+    if !errors.is_empty() {
+        return Err(CliError::InvalidArgument(format!(
+            "{draft_path} is not a valid action book ({} problems; first: {})",
+            errors.len(),
+            errors[0]
+        )));
+    }
+    if dry_run {
+        return Ok(());
+    }
+
+    let mut config = config::load_config()?;
+    if let Some(ref key) = cli.api_key {
+        config.api.api_key = Some(key.clone());
+    }
+    let client = ApiClient::from_config(&config)?;
+    let result = client
+        .publish_action(&PublishActionParams {
+            action_book: draft,
+            source_id: source.map(String::from),
+        })
+        .await?;
+
+    if cli.json {
+        println!(
+            "{}",
+            json!({
+                "success": true,
+                "area_id": area_id,
+                "steps": steps,
+                "result": result,
+            })
+        );
+    } else {
+        println!("Published {area_id} ({steps} steps)");
+        if !result.trim().is_empty() {
+            println!("{}", result.trim());
+        }
+    }
+
+    Ok(())
+}
+
+fn load_draft(path: &Path) -> Result<Value, String> {
+    let text = std::fs::read_to_string(path).map_err(|e| e.to_string())?;
+    let is_json = path
+        .extension()
+        .is_some_and(|e| e.eq_ignore_ascii_case("json"));
+    if is_json {
+        serde_json::from_str(&text).map_err(|e| e.to_string())
+    } else {
+        serde_yaml::from_str(&text).map_err(|e| e.to_string())
+    }
+}
+
+/// `domain:path:name`, e.g. `airbnb.com:/:default`.
+fn valid_area_id(id: &str) -> bool {
+    let mut parts = id.splitn(3, ':');
+    match (parts.next(), parts.next(), parts.next()) {
+        (Some(domain), Some(path), Some(name)) => {
+            !domain.is_empty() && path.starts_with('/') && !name.is_empty()
+        }
+        _ => false,
+    }
+}
+
+/// Every problem with the draft; empty when it is valid.
+fn validate_draft(draft: &Value) -> Vec<String> {
+    let Some(obj) = draft.as_object() else {
+        return vec!["the draft must be an object".to_string()];
+    };
+    let mut errors = Vec::new();
+    for key in obj.keys() {
+        if !DRAFT_KEYS.contains(&key.as_str()) {
+            errors.push(format!("unknown key '{key}'"));
+        }
+    }
+    match obj.get("area_id") {
+        None => errors.push("missing area_id".to_string()),
+        Some(Value::String(id)) if valid_area_id(id) => {}
+        Some(v) => errors.push(format!("area_id must look like domain:/path:name, got {v}")),
+    }
+    for key in ["title", "description"] {
+        if let Some(v) = obj.get(key)
+            && !v.is_string()
+        {
+            errors.push(format!("{key} must be a string"));
+        }
+    }
+    if let Some(v) = obj.get("url") {
+        let host = v.as_str().and_then(|u| {
+            u.strip_prefix("https://")
+                .or_else(|| u.strip_prefix("http://"))
+        });
+        if host.is_none_or(str::is_empty) {
+            errors.push(format!("url must be an http(s) URL, got {v}"));
+        }
+    }
+    match obj.get("steps").and_then(|v| v.as_array()) {
+        None => errors.push("missing steps".to_string()),
+        Some(steps) if steps.is_empty() => errors.push("steps is empty".to_string()),
+        Some(steps) => errors.extend(super::run::validate_steps(steps)),
+    }
+    errors
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn validate_draft_accepts_a_recorded_script_with_an_area_id() {
+        let draft = json!({
+            "area_id": "airbnb.com:/:default",
+            "url": "https://www.airbnb.com/",
+            "session": "s1",
+            "steps": [{ "click": "#search" }, { "fill": { "selector": "#q", "text": "Paris" } }],
+        });
+        assert_eq!(validate_draft(&draft), Vec::<String>::new());
+    }
+
+    #[test]
+    fn validate_draft_lists_every_problem() {
+        let draft = json!({
+            "area_id": "airbnb.com",
+            "tittle": "typo",
+            "url": "ftp://x",
+            "steps": [{ "click": "#ok" }, { "teleport": "#nope" }],
+        });
+        let errors = validate_draft(&draft);
+        assert_eq!(errors.len(), 4, "{errors:?}");
+        assert!(errors[0].contains("tittle"));
+        assert!(errors[1].contains("area_id"));
+        assert!(errors[2].contains("url"));
+        assert!(errors[3].starts_with("step 2:"), "{errors:?}");
+
+        assert_eq!(
+            validate_draft(&json!({ "area_id": "a.com:/:x" })),
+            vec!["missing steps"]
+        );
+    }
+}
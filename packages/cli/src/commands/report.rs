@@ -0,0 +1,120 @@
+use serde_json::Value;
+
+use crate::action_result::ActionResult;
+use crate::api::{ActionReport, ApiClient};
+use crate::cli::Cli;
+use crate::config;
+use crate::error::CliError;
+
+pub async fn run(
+    cli: &Cli,
+    area_id: &str,
+    outcome: &str,
+    notes: Option<&str>,
+    run_report: Option<&str>,
+) -> Result<(), CliError> {
+    let run_report = match run_report {
+        Some(path) => {
+            let text = std::fs::read_to_string(path).map_err(|e| {
+                CliError::InvalidArgument(format!("failed to read run report {path}: {e}"))
+            })?;
+            Some(serde_json::from_str::<Value>(&text).map_err(|e| {
+                CliError::InvalidArgument(format!("run report {path} is not JSON: {e}"))
+            })?)
+        }
+        None => None,
+    };
+    let report = ActionReport {
+        area_id: area_id.to_string(),
+        outcome: outcome.to_string(),
+        notes: notes.map(String::from),
+        run_report,
+    };
+
+    let result = submit(cli.api_key.as_deref(), &report).await?;
+
+    if cli.json {
+        println!(
+            "{}",
+            serde_json::json!({
+                "success": true,
+                "area_id": area_id,
+                "outcome": outcome,
+                "result": result,
+            })
+        );
+    } else {
+        println!("Reported {outcome} for {area_id}");
+        if !result.trim().is_empty() {
+            println!("{}", result.trim());
+        }
+    }
+
+    Ok(())
+}
+
+/// Post a report with the configured API key (or `api_key`, when given).
+pub async fn submit(api_key: Option<&str>, report: &ActionReport) -> Result<String, CliError> {
+    let mut config = config::load_config()?;
+    if let Some(key) = api_key {
+        config.api.api_key = Some(key.to_string());
+    }
+    let client = ApiClient::from_config(&config)?;
+    client.report_action(report).await
+}
+
+/// The report for an `actionbook run` result: the run summary is attached,
+/// and a run that stopped early carries its error as the notes.
+pub fn from_run(area_id: &str, result: &ActionResult) -> ActionReport {
+    let (outcome, notes, run_report) = match result {
+        ActionResult::Ok { data } => ("success", None, Some(data.clone())),
+        ActionResult::Fatal {
+            code,
+            message,
+            details,
+            ..
+        } => (
+            "failure",
+            Some(format!("{code}: {message}")),
+            details.clone(),
+        ),
+        ActionResult::Retryable { reason, .. } => ("failure", Some(reason.clone()), None),
+        ActionResult::UserAction { action, .. } => ("failure", Some(action.clone()), None),
+    };
+    ActionReport {
+        area_id: area_id.to_string(),
+        outcome: outcome.to_string(),
+        notes,
+        run_report,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn from_run_attaches_the_summary_and_failure_cause() {
+        let ok = from_run("x.com:/:default", &ActionResult::ok(json!({ "passed": 2 })));
+        assert_eq!(ok.outcome, "success");
+        assert_eq!(ok.notes, None);
+        assert_eq!(ok.run_report, Some(json!({ "passed": 2 })));
+
+        let failed = from_run(
+            "x.com:/:default",
+            &ActionResult::fatal_with_details(
+                "STEP_FAILED",
+                "step 2 (click #go) failed",
+                "",
+                json!({ "failed": 1 }),
+            ),
+        );
+        assert_eq!(failed.outcome, "failure");
+        assert_eq!(
+            failed.notes.as_deref(),
+            Some("STEP_FAILED: step 2 (click #go) failed")
+        );
+        assert_eq!(failed.run_report, Some(json!({ "failed": 1 })));
+    }
+}
@@ -0,0 +1,1459 @@
+//! `actionbook run <script>`: execute a YAML or JSON script of browser steps
+//! over one daemon connection.
+//!
+//! Each step is turned into the argument list of the equivalent
+//! `actionbook browser …` command and parsed with the real CLI definition, so
+//! steps get exactly the validation, defaults and path handling of the command
+//! they stand for.
+
+use std::collections::HashMap;
+use std::path::Path;
+use std::time::{Duration, Instant};
+
+use clap::{Args, Parser};
+use serde::Deserialize;
+use serde_json::{Map, Value, json};
+
+use super::breakpoint::{self, Breakpoints, Resume};
+use super::heal;
+use crate::action::Action;
+use crate::action_result::ActionResult;
+use crate::auth::{self, Credential};
+use crate::cli::{BrowserCommands, Cli, Commands};
+use crate::utils::artifacts::{self, RunDir, TemplateVars};
+use crate::utils::client::DaemonClient;
+
+pub const COMMAND_NAME: &str = "run";
+
+const DEFAULT_RETRY_DELAY_MS: u64 = 500;
+
+/// Keys a step may carry besides its action.
+pub(crate) const META_KEYS: &[&str] =
+    &["name", "retries", "continue_on_error", "anchor", "capture"];
+
+/// Directory, next to the step files, that step captures are written to.
+const CAPTURE_DIR: &str = "captures";
+
+/// `browser` subcommands whose first argument is a selector, so their steps
+/// can be healed.
+const SELECTOR_COMMANDS: &[&str] = &["select", "hover", "focus", "upload"];
+
+/// Fields a `login` step fills when it names none.
+const LOGIN_USER_SELECTOR: &str = "input[autocomplete=username], input[type=email], \
+     input[name*=user i], input[name*=email i], input[name*=login i]";
+const LOGIN_PASS_SELECTOR: &str = "input[type=password]";
+
+/// Run a script of browser steps
+#[derive(Args, Debug, Clone)]
+#[command(after_help = "\
+Examples:
+  actionbook run login.yaml --session s1 --tab t1
+  actionbook run checkout.json --session s1 --tab t2 --report report.json
+  actionbook run search.yaml --session s1 --tab t1 --area-id airbnb.com:/:default
+
+Script (YAML or JSON):
+  session: s1            # or pass --session
+  tab: t1                # or pass --tab (`active` for the active tab)
+  retries: 1             # default retries per step
+  capture: none          # after each step: screenshot, snapshot, both or none
+  steps:
+    - goto: https://example.com/login
+    - fill: { selector: \"#email\", text: ada@example.com }
+    - click: \"#submit\"
+    - wait: \"#dashboard\"            # or { selector: ..., timeout: 5000 }, or a number of ms
+    - assert: { selector: h1, text: Welcome }
+    - assert: { url: /dashboard }
+    - extract: { selector: .balance, as: balance }   # add attr: href for an attribute
+    - screenshot: dashboard.png      # or { path: ..., selector: ..., full: true }
+    - browser: [press, Enter]        # any other browser subcommand
+      name: submit search
+      retries: 3
+      continue_on_error: true
+      capture: both
+    - click: \"#buy\"
+      anchor: { tag: button, role: button, name: Buy now }   # written by `browser record`
+    - login: example.com             # fill and submit the login stored with `actionbook auth add`
+    - login: { domain: example.com, totp: \"#otp\", submit: \"#verify\" }   # only the fields named
+
+Steps run in order on the same session and tab. A failing step is retried
+(retries, retry_delay_ms) and then stops the run unless it sets
+continue_on_error. The result lists every step with its attempts, timing and
+data, plus the values collected by extract steps. With --area-id the outcome
+and this result are posted back to the action book when the run ends.
+
+When config.toml has a [delivery] section, the result and every file the
+steps wrote (screenshots, PDFs, table --out/--sink files, downloads) are
+uploaded to [delivery.s3] and/or summarized to [delivery.webhook] when the
+run ends; --no-deliver skips that.
+
+With --artifacts-dir (or [artifacts] dir in config.toml), each run gets its
+own directory from a template such as ~/runs/{profile}/{flow}/{timestamp}
+({profile}, {flow} = script name, {session}, {timestamp} = UTC), and relative
+step paths are written inside it. Concurrent runs never share a directory.
+Afterwards older runs next to it are pruned to [artifacts] keep_last and
+max_total_mb; runs still in progress are left alone.
+
+Steps with an anchor (recorded steps have one) heal themselves: when the
+selector still finds nothing after the retries, the page's elements are
+scored against the anchor's role, name, text, surroundings and attributes,
+and the step is tried once more on the single best match. The step's `heal`
+entry names both selectors. --save-healed writes the new selectors back into
+the script (comments in it are lost); --no-heal turns healing off.
+
+A step's `capture` (or the script's, or --capture) saves a screenshot and/or
+an accessibility snapshot of the page after the step, pass or fail, as
+captures/step-<n>.png and .yaml next to the step files. Each step lists its
+captures; they are delivered with the other files.
+
+--pause-at N (a step number or name, repeatable) or --step stops before the
+step and reads commands from stdin: browser subcommands run against the live
+session (snapshot, eval \"document.title\", click \"#ok\"), `continue` runs to
+the next breakpoint, `next` runs one step, `quit` stops the run.")]
+pub struct Cmd {
+    /// Script file (.yaml, .yml or .json)
+    pub script: String,
+    /// Session ID (overrides the script's `session`)
+    #[arg(long)]
+    pub session: Option<String>,
+    /// Tab ID (overrides the script's `tab`)
+    #[arg(long)]
+    pub tab: Option<String>,
+    /// Default retries per step (overrides the script's `retries`)
+    #[arg(long)]
+    pub retries: Option<u32>,
+    /// Also write the JSON result to this file
+    #[arg(long)]
+    pub report: Option<String>,
+    /// Report the outcome to this action book (see `actionbook report`)
+    #[arg(long, value_name = "AREA_ID")]
+    pub area_id: Option<String>,
+    /// Skip the [delivery] configured in config.toml
+    #[arg(long)]
+    pub no_deliver: bool,
+    /// Per-run artifact directory template (overrides [artifacts] dir)
+    #[arg(long, value_name = "TEMPLATE")]
+    pub artifacts_dir: Option<String>,
+    /// Fail steps whose selector broke instead of healing them from their anchor
+    #[arg(long, conflicts_with = "save_healed")]
+    pub no_heal: bool,
+    /// Write healed selectors back into the script
+    #[arg(long)]
+    pub save_healed: bool,
+    /// What to capture after steps without their own `capture` (overrides the script's)
+    #[arg(long, value_enum)]
+    pub capture: Option<Capture>,
+    /// Pause before this step (number or name) for commands from stdin; repeatable
+    #[arg(long, value_name = "STEP")]
+    pub pause_at: Vec<String>,
+    /// Pause before every step
+    #[arg(long)]
+    pub step: bool,
+}
+
+/// Artifacts saved after a step.
+#[derive(clap::ValueEnum, Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Capture {
+    #[default]
+    None,
+    Screenshot,
+    Snapshot,
+    Both,
+}
+
+impl Capture {
+    fn screenshot(self) -> bool {
+        matches!(self, Self::Screenshot | Self::Both)
+    }
+
+    fn snapshot(self) -> bool {
+        matches!(self, Self::Snapshot | Self::Both)
+    }
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct Script {
+    session: Option<String>,
+    tab: Option<String>,
+    #[serde(default)]
+    retries: u32,
+    retry_delay_ms: Option<u64>,
+    #[serde(default)]
+    capture: Capture,
+    steps: Vec<Value>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub(super) enum Assertion {
+    /// Element exists; with `contains`, its text (or the page's) contains it.
+    Text {
+        selector: Option<String>,
+        contains: Option<String>,
+    },
+    Url(String),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub(super) enum StepKind {
+    Goto(String),
+    Wait {
+        selector: String,
+        timeout: Option<u64>,
+    },
+    Sleep(u64),
+    Click(String),
+    Fill {
+        selector: String,
+        text: String,
+    },
+    Assert(Assertion),
+    Screenshot {
+        path: String,
+        selector: Option<String>,
+        full: bool,
+    },
+    Extract {
+        selector: Option<String>,
+        attr: Option<String>,
+        name: String,
+    },
+    Browser(Vec<String>),
+    /// Fill a login form from the credential stored for `domain`.
+    Login {
+        domain: String,
+        user: Option<String>,
+        pass: Option<String>,
+        totp: Option<String>,
+        submit: Option<String>,
+    },
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub(super) struct Step {
+    pub name: Option<String>,
+    pub kind: StepKind,
+    retries: Option<u32>,
+    continue_on_error: bool,
+    /// Fingerprint of the step's element, for healing its selector.
+    anchor: Option<Value>,
+    capture: Option<Capture>,
+}
+
+/// Strings as-is; numbers and booleans in their YAML spelling.
+fn scalar(v: &Value) -> Option<String> {
+    match v {
+        Value::String(s) => Some(s.clone()),
+        Value::Number(n) => Some(n.to_string()),
+        Value::Bool(b) => Some(b.to_string()),
+        _ => None,
+    }
+}
+
+/// `value` itself when it is a scalar, else `value[field]`.
+fn scalar_or_field(value: &Value, field: &str) -> Option<String> {
+    scalar(value).or_else(|| value.get(field).and_then(scalar))
+}
+
+fn parse_step(value: &Value) -> Result<Step, String> {
+    let obj = value
+        .as_object()
+        .ok_or("must be a mapping like `click: \"#id\"`")?;
+    let mut actions = obj.iter().filter(|(k, _)| !META_KEYS.contains(&k.as_str()));
+    let (Some((action, arg)), None) = (actions.next(), actions.next()) else {
+        return Err(
+            "must have exactly one action: goto, wait, click, fill, assert, screenshot, extract, login or browser"
+                .into(),
+        );
+    };
+    let field = |name: &str| arg.get(name).and_then(scalar);
+    let need = |v: Option<String>, what: &str| v.ok_or_else(|| format!("{action} needs {what}"));
+
+    let kind = match action.as_str() {
+        "goto" => StepKind::Goto(need(scalar_or_field(arg, "url"), "a URL")?),
+        "wait" => match arg {
+            Value::Number(n) => StepKind::Sleep(n.as_u64().ok_or("wait needs whole milliseconds")?),
+            _ => StepKind::Wait {
+                selector: need(
+                    scalar_or_field(arg, "selector"),
+                    "a selector or milliseconds",
+                )?,
+                timeout: arg.get("timeout").and_then(|v| v.as_u64()),
+            },
+        },
+        "click" => StepKind::Click(need(scalar_or_field(arg, "selector"), "a selector")?),
+        "fill" => StepKind::Fill {
+            selector: need(field("selector"), "selector")?,
+            text: need(field("text"), "text")?,
+        },
+        "assert" => match (field("url"), field("selector"), field("text")) {
+            (Some(url), None, None) => StepKind::Assert(Assertion::Url(url)),
+            (None, None, None) => return Err("assert needs selector, text or url".into()),
+            (None, selector, contains) => StepKind::Assert(Assertion::Text { selector, contains }),
+            (Some(_), _, _) => {
+                return Err("assert url cannot be combined with selector or text".into());
+            }
+        },
+        "screenshot" => StepKind::Screenshot {
+            path: need(scalar_or_field(arg, "path"), "a path")?,
+            selector: field("selector"),
+            full: arg.get("full").and_then(|v| v.as_bool()).unwrap_or(false),
+        },
+        "extract" => StepKind::Extract {
+            selector: field("selector"),
+            attr: field("attr"),
+            name: need(field("as"), "`as` (the name to store the value under)")?,
+        },
+        "browser" => {
+            let args: Option<Vec<String>> = arg
+                .as_array()
+                .map(|a| a.iter().map(scalar).collect::<Option<_>>())
+                .unwrap_or_else(|| {
+                    arg.as_str()
+                        .map(|s| s.split_whitespace().map(String::from).collect())
+                });
+            match args {
+                Some(args) if !args.is_empty() => StepKind::Browser(args),
+                _ => return Err("browser needs a subcommand and its arguments".into()),
+            }
+        }
+        "login" => {
+            let domain = auth::normalize_domain(&need(scalar_or_field(arg, "domain"), "a domain")?);
+            let (user, pass, totp) = (field("user"), field("pass"), field("totp"));
+            let (user, pass) = if user.is_none() && pass.is_none() && totp.is_none() {
+                (
+                    Some(LOGIN_USER_SELECTOR.to_string()),
+                    Some(LOGIN_PASS_SELECTOR.to_string()),
+                )
+            } else {
+                (user, pass)
+            };
+            StepKind::Login {
+                domain,
+                user,
+                pass,
+                totp,
+                submit: field("submit"),
+            }
+        }
+        other => return Err(format!("unknown action '{other}'")),
+    };
+    if let StepKind::Extract {
+        selector: None,
+        attr: Some(_),
+        ..
+    } = kind
+    {
+        return Err("extract attr needs a selector".into());
+    }
+
+    Ok(Step {
+        name: obj.get("name").and_then(scalar),
+        kind,
+        retries: obj
+            .get("retries")
+            .map(|v| {
+                v.as_u64()
+                    .map(|n| n as u32)
+                    .ok_or("retries must be a whole number")
+            })
+            .transpose()?,
+        continue_on_error: obj
+            .get("continue_on_error")
+            .and_then(|v| v.as_bool())
+            .unwrap_or(false),
+        anchor: match obj.get("anchor") {
+            None => None,
+            Some(a) if a.get("tag").is_some_and(|t| t.is_string()) => Some(a.clone()),
+            Some(_) => return Err("anchor must be a mapping with a tag".into()),
+        },
+        capture: obj
+            .get("capture")
+            .map(|v| {
+                Capture::deserialize(v)
+                    .map_err(|_| "capture must be screenshot, snapshot, both or none")
+            })
+            .transpose()?,
+    })
+}
+
+impl Step {
+    fn action(&self) -> &'static str {
+        match self.kind {
+            StepKind::Goto(_) => "goto",
+            StepKind::Wait { .. } | StepKind::Sleep(_) => "wait",
+            StepKind::Click(_) => "click",
+            StepKind::Fill { .. } => "fill",
+            StepKind::Assert(_) => "assert",
+            StepKind::Screenshot { .. } => "screenshot",
+            StepKind::Extract { .. } => "extract",
+            StepKind::Browser(_) => "browser",
+            StepKind::Login { .. } => "login",
+        }
+    }
+
+    /// Short description for progress lines and errors.
+    fn target(&self) -> String {
+        match &self.kind {
+            StepKind::Goto(url) => url.clone(),
+            StepKind::Wait { selector, .. } => selector.clone(),
+            StepKind::Sleep(ms) => format!("{ms}ms"),
+            StepKind::Click(sel) | StepKind::Fill { selector: sel, .. } => sel.clone(),
+            StepKind::Assert(Assertion::Url(url)) => format!("url ~ {url}"),
+            StepKind::Assert(Assertion::Text { selector, contains }) => {
+                let sel = selector.as_deref().unwrap_or("page");
+                match contains {
+                    Some(text) => format!("{sel} ~ {text}"),
+                    None => sel.to_string(),
+                }
+            }
+            StepKind::Screenshot { path, .. } => path.clone(),
+            StepKind::Extract { name, .. } => name.clone(),
+            StepKind::Browser(args) => args.join(" "),
+            StepKind::Login { domain, .. } => domain.clone(),
+        }
+    }
+
+    /// The selector the step acts on, if it has one.
+    fn selector(&self) -> Option<&str> {
+        match &self.kind {
+            StepKind::Wait { selector, .. }
+            | StepKind::Click(selector)
+            | StepKind::Fill { selector, .. } => Some(selector),
+            StepKind::Assert(Assertion::Text { selector, .. })
+            | StepKind::Screenshot { selector, .. }
+            | StepKind::Extract { selector, .. } => selector.as_deref(),
+            StepKind::Browser(args) if SELECTOR_COMMANDS.contains(&args[0].as_str()) => {
+                args.get(1).map(String::as_str)
+            }
+            _ => None,
+        }
+    }
+
+    /// The same step acting on `new` instead of its selector.
+    fn with_selector(&self, new: &str) -> Step {
+        let mut step = self.clone();
+        match &mut step.kind {
+            StepKind::Wait { selector, .. }
+            | StepKind::Click(selector)
+            | StepKind::Fill { selector, .. } => *selector = new.to_string(),
+            StepKind::Assert(Assertion::Text { selector, .. })
+            | StepKind::Screenshot { selector, .. }
+            | StepKind::Extract { selector, .. } => *selector = Some(new.to_string()),
+            StepKind::Browser(args) if args.len() > 1 => args[1] = new.to_string(),
+            _ => {}
+        }
+        step
+    }
+
+    /// `actionbook browser …` arguments, without --session/--tab. None for
+    /// steps that run without the daemon, and for `login`, which sends
+    /// several commands built when it runs.
+    fn browser_args(&self) -> Option<Vec<String>> {
+        let s = |v: &str| v.to_string();
+        Some(match &self.kind {
+            StepKind::Sleep(_) | StepKind::Login { .. } => return None,
+            StepKind::Goto(url) => vec![s("goto"), url.clone()],
+            StepKind::Wait { selector, timeout } => {
+                let mut args = vec![s("wait"), s("element"), selector.clone()];
+                if let Some(ms) = timeout {
+                    args.extend([s("--timeout"), ms.to_string()]);
+                }
+                args
+            }
+            StepKind::Click(sel) => vec![s("click"), sel.clone()],
+            StepKind::Fill { selector, text } => vec![s("fill"), selector.clone(), text.clone()],
+            StepKind::Assert(Assertion::Url(_)) => vec![s("url")],
+            StepKind::Assert(Assertion::Text { selector, .. })
+            | StepKind::Extract {
+                selector,
+                attr: None,
+                ..
+            } => std::iter::once(s("text")).chain(selector.clone()).collect(),
+            StepKind::Extract {
+                selector: Some(sel),
+                attr: Some(attr),
+                ..
+            } => vec![s("attr"), sel.clone(), attr.clone()],
+            StepKind::Extract { .. } => unreachable!("rejected by parse_step"),
+            StepKind::Screenshot {
+                path,
+                selector,
+                full,
+            } => {
+                let mut args = vec![s("screenshot"), path.clone()];
+                if let Some(sel) = selector {
+                    args.extend([s("--selector"), sel.clone()]);
+                }
+                if *full {
+                    args.push(s("--full"));
+                }
+                args
+            }
+            StepKind::Browser(args) => args.clone(),
+        })
+    }
+
+    /// Check an assertion against a successful result.
+    fn check(&self, data: &Value) -> Result<(), ActionResult> {
+        let (what, expected) = match &self.kind {
+            StepKind::Assert(Assertion::Url(expected)) => ("url", expected),
+            StepKind::Assert(Assertion::Text {
+                selector,
+                contains: Some(expected),
+            }) => (selector.as_deref().unwrap_or("page text"), expected),
+            _ => return Ok(()),
+        };
+        let actual = data.get("value").and_then(|v| v.as_str()).unwrap_or("");
+        if actual.contains(expected.as_str()) {
+            return Ok(());
+        }
+        let shown: String = actual.chars().take(200).collect();
+        Err(ActionResult::fatal(
+            "ASSERTION_FAILED",
+            format!("expected {what} to contain '{expected}', got '{shown}'"),
+        ))
+    }
+}
+
+/// Parse `actionbook browser <args> --session … [--tab …]` with the CLI's own
+/// definition.
+pub(super) fn browser_command(
+    args: &[String],
+    session: &str,
+    tab: Option<&str>,
+) -> Result<BrowserCommands, String> {
+    let mut argv: Vec<&str> = vec!["actionbook", "browser"];
+    argv.extend(args.iter().map(String::as_str));
+    let has = |flag: &str| args.iter().any(|a| a == flag);
+    if !has("--session") {
+        argv.extend(["--session", session]);
+    }
+    if let Some(tab) = tab
+        && !has("--tab")
+    {
+        argv.extend(["--tab", tab]);
+    }
+    let cli = Cli::try_parse_from(argv).map_err(|e| {
+        e.to_string()
+            .lines()
+            .next()
+            .unwrap_or("invalid arguments")
+            .trim_start_matches("error: ")
+            .to_string()
+    })?;
+    match cli.command {
+        Some(Commands::Browser { command }) => Ok(command),
+        _ => Err("not a browser command".into()),
+    }
+}
+
+/// Check script steps the way `execute` does before running them, returning
+/// one message per bad step.
+pub(crate) fn validate_steps(steps: &[Value]) -> Vec<String> {
+    let mut errors = Vec::new();
+    for (i, raw) in steps.iter().enumerate() {
+        let step = match parse_step(raw) {
+            Ok(step) => step,
+            Err(e) => {
+                errors.push(format!("step {}: {e}", i + 1));
+                continue;
+            }
+        };
+        if let Some(args) = step.browser_args() {
+            match browser_command(&args, "draft", Some("draft")) {
+                Ok(command) if command.to_action().is_none() => errors.push(format!(
+                    "step {}: '{}' cannot run from a script",
+                    i + 1,
+                    args[0]
+                )),
+                Ok(_) => {}
+                Err(e) => errors.push(format!("step {}: {e}", i + 1)),
+            }
+        }
+    }
+    errors
+}
+
+fn load_script(path: &Path) -> Result<Script, String> {
+    let text = std::fs::read_to_string(path).map_err(|e| e.to_string())?;
+    let is_json = path
+        .extension()
+        .is_some_and(|e| e.eq_ignore_ascii_case("json"));
+    if is_json {
+        serde_json::from_str(&text).map_err(|e| e.to_string())
+    } else {
+        serde_yaml::from_str(&text).map_err(|e| e.to_string())
+    }
+}
+
+fn parse_steps(raw: &[Value]) -> Result<Vec<Step>, String> {
+    raw.iter()
+        .enumerate()
+        .map(|(i, raw)| parse_step(raw).map_err(|e| format!("step {}: {e}", i + 1)))
+        .collect()
+}
+
+/// The parsed steps of the script at `path`, for tools that read scripts
+/// without running them.
+pub(super) fn load_steps(path: &Path) -> Result<Vec<Step>, String> {
+    let script =
+        load_script(path).map_err(|e| format!("failed to load {}: {e}", path.display()))?;
+    parse_steps(&script.steps)
+}
+
+/// Everything in `data` except the `__`-prefixed internal keys.
+fn public_data(data: &Value) -> Value {
+    match data.as_object() {
+        Some(obj) => obj
+            .iter()
+            .filter(|(k, _)| !k.starts_with("__"))
+            .map(|(k, v)| (k.clone(), v.clone()))
+            .collect::<Map<_, _>>()
+            .into(),
+        None => data.clone(),
+    }
+}
+
+/// Browser actions for the steps (None for steps that run without the
+/// daemon). Relative paths in the steps resolve against the current directory.
+fn resolve_actions(
+    steps: &[Step],
+    session: &str,
+    tab: Option<&str>,
+) -> Result<Vec<Option<Action>>, ActionResult> {
+    let mut actions = Vec::with_capacity(steps.len());
+    for (i, step) in steps.iter().enumerate() {
+        let action = match step.browser_args() {
+            None => None,
+            Some(args) => match browser_command(&args, session, tab) {
+                Ok(command) => match command.to_action() {
+                    Some(action) => Some(action),
+                    None => {
+                        return Err(ActionResult::fatal(
+                            "INVALID_SCRIPT",
+                            format!("step {}: '{}' cannot run from a script", i + 1, args[0]),
+                        ));
+                    }
+                },
+                Err(e) => {
+                    return Err(ActionResult::fatal(
+                        "INVALID_SCRIPT",
+                        format!("step {}: {e}", i + 1),
+                    ));
+                }
+            },
+        };
+        actions.push(action);
+    }
+    Ok(actions)
+}
+
+/// Send one action, folding transport errors into the result.
+pub(super) async fn send(client: &mut DaemonClient, action: &Action) -> ActionResult {
+    match client.send_action(action).await {
+        Ok(result) => result,
+        Err(e) => ActionResult::fatal(e.error_code(), e.to_string()),
+    }
+}
+
+/// Run a step once and check its result.
+async fn attempt(client: &mut DaemonClient, step: &Step, action: Option<&Action>) -> ActionResult {
+    match action {
+        None => {
+            if let StepKind::Sleep(ms) = step.kind {
+                tokio::time::sleep(Duration::from_millis(ms)).await;
+            }
+            ActionResult::ok(json!({}))
+        }
+        Some(action) => match send(client, action).await {
+            ActionResult::Ok { data } => match step.check(&data) {
+                Ok(()) => ActionResult::Ok { data },
+                Err(e) => e,
+            },
+            other => other,
+        },
+    }
+}
+
+/// Fill the login form a `login` step describes from its stored credential,
+/// with a fresh TOTP code on every attempt, then submit it.
+async fn login(
+    client: &mut DaemonClient,
+    step: &Step,
+    credentials: &HashMap<String, Credential>,
+    session: &str,
+    tab: Option<&str>,
+) -> ActionResult {
+    let StepKind::Login {
+        domain,
+        user,
+        pass,
+        totp,
+        submit,
+    } = &step.kind
+    else {
+        return ActionResult::fatal("INTERNAL_ERROR", "not a login step");
+    };
+    let Some(credential) = credentials.get(domain) else {
+        return auth::not_found(domain);
+    };
+    // A redirect or a lookalike page must not receive the password.
+    let url = match browser_command(&["url".to_string()], session, tab).map(|c| c.to_action()) {
+        Ok(Some(action)) => match send(client, &action).await {
+            ActionResult::Ok { data } => data["value"].as_str().unwrap_or_default().to_string(),
+            other => return other,
+        },
+        _ => return ActionResult::fatal("INTERNAL_ERROR", "cannot read the page URL"),
+    };
+    if !auth::host_matches(&url, &credential.domain) {
+        return auth::domain_mismatch(&auth::normalize_domain(&url), &credential.domain);
+    }
+    let code = match (totp, credential.totp()) {
+        (None, _) => None,
+        (Some(_), Some(Ok(code))) => Some(code),
+        (Some(_), Some(Err(e))) => return ActionResult::fatal(e.error_code(), e.to_string()),
+        (Some(_), None) => {
+            return ActionResult::fatal(
+                "INVALID_SCRIPT",
+                format!("no TOTP secret stored for {}", credential.domain),
+            );
+        }
+    };
+    let fills = [
+        (user, Some(&credential.user), false),
+        (pass, Some(&credential.password), true),
+        (totp, code.as_ref(), true),
+    ];
+
+    // Options before `--`, so a value starting with `-` stays a value.
+    let mut scope = vec!["--session".to_string(), session.to_string()];
+    if let Some(tab) = tab {
+        scope.extend(["--tab".to_string(), tab.to_string()]);
+    }
+    let mut commands: Vec<Vec<String>> = Vec::new();
+    let mut last_field = None;
+    for (selector, value, secret) in fills {
+        if let (Some(selector), Some(value)) = (selector, value) {
+            let mut args = vec!["fill".to_string()];
+            if secret {
+                args.push("--secret".to_string());
+            }
+            args.extend(scope.iter().cloned());
+            args.extend(["--".to_string(), selector.clone(), value.clone()]);
+            commands.push(args);
+            last_field = Some(selector);
+        }
+    }
+    match (submit, last_field) {
+        (Some(button), _) => commands.push(vec!["click".to_string(), button.clone()]),
+        (None, Some(field)) => {
+            commands.push(vec!["focus".to_string(), field.clone()]);
+            commands.push(vec!["press".to_string(), "Enter".to_string()]);
+        }
+        (None, None) => {}
+    }
+
+    let mut result = ActionResult::ok(json!({}));
+    for args in &commands {
+        // Never echo a parse error: the arguments hold the secrets.
+        let Some(action) = browser_command(args, session, tab)
+            .ok()
+            .and_then(|c| c.to_action())
+        else {
+            return ActionResult::fatal(
+                "INVALID_SCRIPT",
+                format!("login {domain}: invalid {} step", args[0]),
+            );
+        };
+        result = send(client, &action).await;
+        if !result.is_ok() {
+            return result;
+        }
+    }
+    result
+}
+
+/// The message of a result that is not `Ok`.
+fn failure(result: ActionResult) -> String {
+    match result {
+        ActionResult::Ok { .. } => String::new(),
+        ActionResult::Fatal { message, .. } => message,
+        ActionResult::Retryable { reason, .. } => reason,
+        ActionResult::UserAction { action, .. } => action,
+    }
+}
+
+/// Look for the element a failed step's anchor describes and build the step
+/// and action that act on it instead.
+async fn find_healed(
+    client: &mut DaemonClient,
+    step: &Step,
+    anchor: &Value,
+    selector: &str,
+    session: &str,
+    tab: Option<&str>,
+    run_dir: Option<&Path>,
+) -> Result<(heal::Candidate, Step, Option<Action>), String> {
+    let args = ["eval".to_string(), heal::script(anchor, selector)];
+    let eval = browser_command(&args, session, tab)?
+        .to_action()
+        .ok_or("eval cannot run from a script")?;
+    let found = match send(client, &eval).await {
+        ActionResult::Ok { data } => data["value"].clone(),
+        other => return Err(failure(other)),
+    };
+    let candidate = heal::parse(&found)?;
+    let healed = step.with_selector(&candidate.selector);
+    let resolved = {
+        let _cwd = match run_dir {
+            Some(dir) => Some(CwdGuard::enter(dir).map_err(|e| e.to_string())?),
+            None => None,
+        };
+        resolve_actions(std::slice::from_ref(&healed), session, tab)
+    };
+    let action = resolved.map_err(failure)?.pop().flatten();
+    Ok((candidate, healed, action))
+}
+
+/// Save what `mode` asks for after step `index` (1-based) into `dir`. A
+/// capture that fails is reported in place of its path and never fails the
+/// step.
+async fn capture(
+    client: &mut DaemonClient,
+    mode: Capture,
+    index: usize,
+    dir: &Path,
+    session: &str,
+    tab: Option<&str>,
+) -> Vec<Value> {
+    let mut out = Vec::new();
+    if let Err(e) = std::fs::create_dir_all(dir) {
+        let error = format!("failed to create {}: {e}", dir.display());
+        return vec![json!({ "kind": "capture", "error": error })];
+    }
+    let stem = format!("step-{index:02}");
+    if mode.screenshot() {
+        let path = dir.join(format!("{stem}.png"));
+        let args = [
+            "screenshot".to_string(),
+            path.to_string_lossy().into_owned(),
+        ];
+        let saved = match browser_command(&args, session, tab).map(|c| c.to_action()) {
+            Ok(Some(action)) => match send(client, &action).await {
+                ActionResult::Ok { .. } => Ok(path),
+                other => Err(failure(other)),
+            },
+            Ok(None) => Err("screenshot cannot run from a script".to_string()),
+            Err(e) => Err(e),
+        };
+        out.push(capture_entry("screenshot", saved));
+    }
+    if mode.snapshot() {
+        let args = ["snapshot".to_string()];
+        let saved = match browser_command(&args, session, tab).map(|c| c.to_action()) {
+            Ok(Some(action)) => match send(client, &action).await {
+                // The snapshot lands in the session's data directory; keep a copy.
+                ActionResult::Ok { data } => match data["path"].as_str().map(Path::new) {
+                    Some(src) => {
+                        let ext = src.extension().and_then(|e| e.to_str()).unwrap_or("yaml");
+                        let path = dir.join(format!("{stem}.{ext}"));
+                        std::fs::copy(src, &path)
+                            .map(|_| path)
+                            .map_err(|e| format!("failed to copy {}: {e}", src.display()))
+                    }
+                    None => Err("snapshot returned no file".to_string()),
+                },
+                other => Err(failure(other)),
+            },
+            Ok(None) => Err("snapshot cannot run from a script".to_string()),
+            Err(e) => Err(e),
+        };
+        out.push(capture_entry("snapshot", saved));
+    }
+    out
+}
+
+fn capture_entry(kind: &str, saved: Result<std::path::PathBuf, String>) -> Value {
+    match saved {
+        Ok(path) => json!({ "kind": kind, "path": path.display().to_string() }),
+        Err(error) => json!({ "kind": kind, "error": error }),
+    }
+}
+
+/// Changes the process's working directory and restores it on drop.
+struct CwdGuard(std::path::PathBuf);
+
+impl CwdGuard {
+    fn enter(dir: &Path) -> std::io::Result<Self> {
+        let previous = std::env::current_dir()?;
+        std::env::set_current_dir(dir)?;
+        Ok(Self(previous))
+    }
+}
+
+impl Drop for CwdGuard {
+    fn drop(&mut self) {
+        let _ = std::env::set_current_dir(&self.0);
+    }
+}
+
+/// Expand the artifacts template for this run and create its directory.
+async fn open_run_dir(
+    template: &str,
+    script: &Path,
+    session: &str,
+    client: &mut DaemonClient,
+) -> Result<RunDir, ActionResult> {
+    let profile = if artifacts::uses(template, "profile") {
+        let status = Action::SessionStatus(crate::browser::session::status::Cmd {
+            session: session.to_string(),
+        });
+        match client.send_action(&status).await {
+            Ok(ActionResult::Ok { data }) => data["session"]["profile"]
+                .as_str()
+                .unwrap_or_default()
+                .to_string(),
+            Ok(other) => return Err(other),
+            Err(e) => return Err(ActionResult::fatal(e.error_code(), e.to_string())),
+        }
+    } else {
+        String::new()
+    };
+    let now = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0);
+    let vars = TemplateVars {
+        profile,
+        flow: script
+            .file_stem()
+            .map(|s| s.to_string_lossy().into_owned())
+            .unwrap_or_default(),
+        session: session.to_string(),
+        timestamp: crate::utils::time::unix_secs_to_compact(now),
+    };
+    let path = artifacts::expand(template, &vars)
+        .map_err(|e| ActionResult::fatal("INVALID_ARGUMENT", e))?;
+    artifacts::create_run_dir(&path).map_err(|e| {
+        ActionResult::fatal(
+            "IO_ERROR",
+            format!("failed to create {}: {e}", path.display()),
+        )
+    })
+}
+
+/// Run the script, calling `on_step` with each step's report as it finishes.
+pub async fn execute(cmd: &Cmd, mut on_step: impl FnMut(&Value)) -> ActionResult {
+    let path = Path::new(&cmd.script);
+    let script = match load_script(path) {
+        Ok(s) => s,
+        Err(e) => {
+            return ActionResult::fatal(
+                "INVALID_SCRIPT",
+                format!("failed to load {}: {e}", cmd.script),
+            );
+        }
+    };
+    let steps = match parse_steps(&script.steps) {
+        Ok(steps) => steps,
+        Err(e) => return ActionResult::fatal("INVALID_SCRIPT", e),
+    };
+    let Some(session) = cmd.session.clone().or(script.session) else {
+        return ActionResult::fatal_with_hint(
+            "INVALID_ARGUMENT",
+            "no session to run against",
+            "pass --session or set `session:` in the script",
+        );
+    };
+    let Some(tab) = cmd.tab.clone().or(script.tab) else {
+        return ActionResult::fatal_with_hint(
+            "INVALID_ARGUMENT",
+            "no tab to run against",
+            "pass --tab or set `tab:` in the script (`active` for the session's active tab)",
+        );
+    };
+    let tab = Some(tab);
+    let default_retries = cmd.retries.unwrap_or(script.retries);
+    let retry_delay =
+        Duration::from_millis(script.retry_delay_ms.unwrap_or(DEFAULT_RETRY_DELAY_MS));
+    let default_capture = cmd.capture.unwrap_or(script.capture);
+    let breakpoints = Breakpoints {
+        every: cmd.step,
+        at: cmd.pause_at.clone(),
+    };
+    let names: Vec<Option<&str>> = steps.iter().map(|s| s.name.as_deref()).collect();
+    let unmatched = breakpoints.unmatched(&names);
+    if !unmatched.is_empty() {
+        return ActionResult::fatal_with_hint(
+            "INVALID_ARGUMENT",
+            format!("--pause-at {} matches no step", unmatched.join(", ")),
+            format!("use a step number (1-{}) or a step's name", steps.len()),
+        );
+    }
+
+    // Resolve every step up front so a typo in step 9 fails before step 1 runs.
+    let mut actions = match resolve_actions(&steps, &session, tab.as_deref()) {
+        Ok(actions) => actions,
+        Err(e) => return e,
+    };
+    // Likewise a login step without a stored credential.
+    let mut credentials = HashMap::new();
+    for step in &steps {
+        if let StepKind::Login { domain, .. } = &step.kind
+            && !credentials.contains_key(domain)
+        {
+            match auth::lookup(domain) {
+                Ok(Some(credential)) => {
+                    credentials.insert(domain.clone(), credential);
+                }
+                Ok(None) => return auth::not_found(domain),
+                Err(e) => {
+                    return ActionResult::fatal(
+                        e.error_code(),
+                        format!("failed to read the login for {domain}: {e}"),
+                    );
+                }
+            }
+        }
+    }
+
+    let mut client = match DaemonClient::connect().await {
+        Ok(c) => c,
+        Err(e) => return ActionResult::fatal(e.error_code(), e.to_string()),
+    };
+
+    let artifacts = crate::config::artifacts();
+    let run_dir = match cmd.artifacts_dir.clone().or(artifacts.dir) {
+        None => None,
+        Some(template) => {
+            let run_dir = match open_run_dir(&template, path, &session, &mut client).await {
+                Ok(d) => d,
+                Err(e) => return e,
+            };
+            // Steps resolve relative paths against the cwd; resolve them again
+            // from inside the run directory so their files land there.
+            let resolved = {
+                let _cwd = match CwdGuard::enter(&run_dir.path) {
+                    Ok(g) => g,
+                    Err(e) => return ActionResult::fatal("IO_ERROR", e.to_string()),
+                };
+                resolve_actions(&steps, &session, tab.as_deref())
+            };
+            match resolved {
+                Ok(a) => actions = a,
+                Err(e) => return e,
+            }
+            Some(run_dir)
+        }
+    };
+
+    let started = Instant::now();
+    let mut reports = Vec::with_capacity(steps.len());
+    let mut extracted = Map::new();
+    let mut failed = 0usize;
+    let mut stopped: Option<usize> = None;
+    let mut healed_selectors: Vec<(usize, String)> = Vec::new();
+    let mut stepping = false;
+    let mut quit: Option<usize> = None;
+
+    for (i, (step, action)) in steps.iter().zip(&actions).enumerate() {
+        if stepping || breakpoints.hits(i + 1, step.name.as_deref()) {
+            let label = format!("step {} ({} {})", i + 1, step.action(), step.target());
+            match breakpoint::pause(&mut client, &label, &session, tab.as_deref()).await {
+                Resume::Continue => stepping = false,
+                Resume::Next => stepping = true,
+                Resume::Quit => {
+                    quit = Some(i);
+                    break;
+                }
+            }
+        }
+        let retries = step.retries.unwrap_or(default_retries);
+        let step_start = Instant::now();
+        let mut attempts = 0;
+        let mut result = loop {
+            attempts += 1;
+            let result = match step.kind {
+                StepKind::Login { .. } => {
+                    login(&mut client, step, &credentials, &session, tab.as_deref()).await
+                }
+                _ => attempt(&mut client, step, action.as_ref()).await,
+            };
+            if result.is_ok() || attempts > retries {
+                break result;
+            }
+            tokio::time::sleep(retry_delay).await;
+        };
+
+        let mut heal_report = None;
+        if let ActionResult::Fatal { code, .. } = &result
+            && !cmd.no_heal
+            && heal::applies(step.action(), code)
+            && let (Some(anchor), Some(selector)) = (&step.anchor, step.selector())
+        {
+            let dir = run_dir.as_ref().map(|d| d.path.as_path());
+            heal_report = Some(
+                match find_healed(
+                    &mut client,
+                    step,
+                    anchor,
+                    selector,
+                    &session,
+                    tab.as_deref(),
+                    dir,
+                )
+                .await
+                {
+                    Ok((candidate, healed, healed_action)) => {
+                        attempts += 1;
+                        let retried = attempt(&mut client, &healed, healed_action.as_ref()).await;
+                        let ok = retried.is_ok();
+                        if ok {
+                            result = retried;
+                            healed_selectors.push((i, candidate.selector.clone()));
+                        }
+                        json!({
+                            "from": selector,
+                            "to": candidate.selector,
+                            "score": candidate.score,
+                            "max": candidate.max,
+                            "matched": candidate.matched,
+                            "ok": ok,
+                        })
+                    }
+                    Err(reason) => json!({ "from": selector, "ok": false, "reason": reason }),
+                },
+            );
+        }
+
+        let mut report = json!({
+            "index": i + 1,
+            "name": step.name,
+            "action": step.action(),
+            "target": step.target(),
+            "ok": result.is_ok(),
+            "attempts": attempts,
+            "elapsed_ms": step_start.elapsed().as_millis() as u64,
+        });
+        if let Some(heal) = heal_report {
+            report["heal"] = heal;
+        }
+        let mode = step.capture.unwrap_or(default_capture);
+        if mode != Capture::None {
+            let dir = match &run_dir {
+                Some(d) => d.path.join(CAPTURE_DIR),
+                None => std::env::current_dir()
+                    .unwrap_or_default()
+                    .join(CAPTURE_DIR),
+            };
+            report["captures"] =
+                json!(capture(&mut client, mode, i + 1, &dir, &session, tab.as_deref()).await);
+        }
+        match &result {
+            ActionResult::Ok { data } => {
+                if let StepKind::Extract { name, .. } = &step.kind {
+                    extracted.insert(
+                        name.clone(),
+                        data.get("value").cloned().unwrap_or(Value::Null),
+                    );
+                }
+                report["data"] = public_data(data);
+            }
+            ActionResult::Fatal {
+                code,
+                message,
+                hint,
+                ..
+            } => {
+                report["error"] = json!({ "code": code, "message": message, "hint": hint });
+            }
+            ActionResult::Retryable { reason, hint } => {
+                report["error"] = json!({ "code": "RETRYABLE", "message": reason, "hint": hint });
+            }
+            ActionResult::UserAction { action, hint } => {
+                report["error"] = json!({ "code": "USER_ACTION", "message": action, "hint": hint });
+            }
+        }
+        on_step(&report);
+        reports.push(report);
+
+        if !result.is_ok() {
+            failed += 1;
+            if !step.continue_on_error {
+                stopped = Some(i);
+                break;
+            }
+        }
+    }
+
+    let mut summary = json!({
+        "script": cmd.script,
+        "session_id": session,
+        "tab_id": tab,
+        "total": steps.len(),
+        "passed": reports.len() - failed,
+        "failed": failed,
+        "skipped": steps.len() - reports.len(),
+        "elapsed_ms": started.elapsed().as_millis() as u64,
+        "extracted": extracted,
+        "steps": reports,
+    });
+    if !healed_selectors.is_empty() {
+        summary["healed"] = json!(healed_selectors.len());
+        if cmd.save_healed {
+            summary["heal_saved"] = match heal::save(path, &healed_selectors) {
+                Ok(()) => json!(true),
+                Err(e) => json!(format!("failed to update {}: {e}", cmd.script)),
+            };
+        }
+    }
+    if let Some(run_dir) = &run_dir {
+        summary["artifacts_dir"] = json!(run_dir.path.display().to_string());
+        summary["retention"] = artifacts::apply_retention(
+            &run_dir.path,
+            artifacts.keep_last,
+            artifacts.max_total_mb.map(|mb| mb * 1024 * 1024),
+        );
+    }
+    if let Some(out) = &cmd.report
+        && let Err(e) = std::fs::write(
+            out,
+            serde_json::to_string_pretty(&summary).unwrap_or_default(),
+        )
+    {
+        return ActionResult::fatal("IO_ERROR", format!("failed to write report {out}: {e}"));
+    }
+
+    if let Some(i) = quit {
+        return ActionResult::fatal_with_details(
+            "RUN_ABORTED",
+            format!("run stopped before step {} at a breakpoint", i + 1),
+            "`continue` at the breakpoint runs the remaining steps",
+            summary,
+        );
+    }
+    match stopped {
+        None => ActionResult::ok(summary),
+        Some(i) => {
+            let step = &steps[i];
+            let cause = summary["steps"][i]["error"]["message"]
+                .as_str()
+                .unwrap_or("")
+                .to_string();
+            let code = summary["steps"][i]["error"]["code"]
+                .as_str()
+                .unwrap_or("")
+                .to_string();
+            ActionResult::fatal_with_details(
+                "STEP_FAILED",
+                format!(
+                    "step {} ({} {}) failed: {code}: {cause}",
+                    i + 1,
+                    step.action(),
+                    step.target()
+                ),
+                "fix the step, raise its retries, or set continue_on_error",
+                summary,
+            )
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn step(yaml: &str) -> Result<Step, String> {
+        parse_step(&serde_yaml::from_str::<Value>(yaml).unwrap())
+    }
+
+    #[test]
+    fn steps_accept_short_and_long_forms() {
+        assert_eq!(
+            step("click: '#go'").unwrap().kind,
+            StepKind::Click("#go".into())
+        );
+        assert_eq!(
+            step("wait: { selector: '#x', timeout: 500 }").unwrap().kind,
+            StepKind::Wait {
+                selector: "#x".into(),
+                timeout: Some(500)
+            }
+        );
+        assert_eq!(step("wait: 250").unwrap().kind, StepKind::Sleep(250));
+        assert_eq!(
+            step("fill: { selector: '#zip', text: 94107 }")
+                .unwrap()
+                .kind,
+            StepKind::Fill {
+                selector: "#zip".into(),
+                text: "94107".into()
+            }
+        );
+        assert_eq!(
+            step("browser: press Enter").unwrap().kind,
+            StepKind::Browser(vec!["press".into(), "Enter".into()])
+        );
+    }
+
+    #[test]
+    fn login_steps_name_a_domain_and_default_their_fields() {
+        let s = step("login: https://App.Example.com/signin").unwrap();
+        assert_eq!(
+            s.kind,
+            StepKind::Login {
+                domain: "app.example.com".into(),
+                user: Some(LOGIN_USER_SELECTOR.into()),
+                pass: Some(LOGIN_PASS_SELECTOR.into()),
+                totp: None,
+                submit: None,
+            }
+        );
+        assert_eq!(s.browser_args(), None);
+        assert_eq!(s.target(), "app.example.com");
+
+        // Naming any field fills only the fields named.
+        let s = step("login: { domain: example.com, totp: '#otp', submit: '#verify' }").unwrap();
+        assert_eq!(
+            s.kind,
+            StepKind::Login {
+                domain: "example.com".into(),
+                user: None,
+                pass: None,
+                totp: Some("#otp".into()),
+                submit: Some("#verify".into()),
+            }
+        );
+        assert!(step("login: { user: '#u' }").is_err());
+
+        // The form `login` sends its fills in: values may start with `-`.
+        let fill = [
+            "fill",
+            "--secret",
+            "--session",
+            "s1",
+            "--tab",
+            "t1",
+            "--",
+            "#pw",
+            "-pw-",
+        ]
+        .map(String::from);
+        assert!(browser_command(&fill, "s1", Some("t1")).is_ok());
+    }
+
+    #[test]
+    fn step_meta_keys_are_not_actions() {
+        let s = step("{ click: '#a', name: go, retries: 2, continue_on_error: true }").unwrap();
+        assert_eq!(s.name.as_deref(), Some("go"));
+        assert_eq!(s.retries, Some(2));
+        assert!(s.continue_on_error);
+    }
+
+    #[test]
+    fn anchored_steps_swap_their_selector() {
+        let s =
+            step("{ fill: { selector: '#q', text: hi }, anchor: { tag: input, name: Search } }")
+                .unwrap();
+        assert_eq!(s.anchor.as_ref().unwrap()["name"], "Search");
+        assert_eq!(s.selector(), Some("#q"));
+        let healed = s.with_selector("input[name=\"q\"]");
+        assert_eq!(
+            healed.kind,
+            StepKind::Fill {
+                selector: "input[name=\"q\"]".into(),
+                text: "hi".into()
+            }
+        );
+
+        let s = step("browser: [select, '#size', L]").unwrap();
+        assert_eq!(s.with_selector("#sz").browser_args().unwrap()[1], "#sz");
+        assert_eq!(step("goto: https://a.test/").unwrap().selector(), None);
+        assert_eq!(step("browser: [press, Enter]").unwrap().selector(), None);
+        assert!(step("{ click: '#a', anchor: x }").is_err());
+    }
+
+    #[test]
+    fn capture_is_per_step_with_a_script_default() {
+        assert_eq!(step("click: '#a'").unwrap().capture, None);
+        let s = step("{ click: '#a', capture: both }").unwrap();
+        assert_eq!(s.capture, Some(Capture::Both));
+        assert!(s.capture.unwrap().screenshot() && s.capture.unwrap().snapshot());
+        assert!(
+            step("{ click: '#a', capture: video }")
+                .unwrap_err()
+                .contains("capture must be")
+        );
+
+        let script: Script =
+            serde_yaml::from_str("capture: snapshot\nsteps: [ { click: '#a' } ]").unwrap();
+        assert_eq!(script.capture, Capture::Snapshot);
+        assert!(!script.capture.screenshot());
+    }
+
+    #[test]
+    fn invalid_steps_explain_themselves() {
+        assert!(
+            step("{ click: '#a', goto: x }")
+                .unwrap_err()
+                .contains("exactly one")
+        );
+        assert!(step("tap: '#a'").unwrap_err().contains("unknown action"));
+        assert!(
+            step("extract: { selector: a }")
+                .unwrap_err()
+                .contains("`as`")
+        );
+        assert!(step("assert: { url: /x, text: y }").is_err());
+    }
+
+    #[test]
+    fn steps_parse_with_the_cli_definition() {
+        let s = step("screenshot: { path: out.png, full: true }").unwrap();
+        let cmd = browser_command(&s.browser_args().unwrap(), "s1", Some("t2")).unwrap();
+        assert_eq!(cmd.command_name(), "browser screenshot");
+
+        let s = step("extract: { selector: a, attr: href, as: link }").unwrap();
+        let cmd = browser_command(&s.browser_args().unwrap(), "s1", Some("active")).unwrap();
+        assert_eq!(cmd.command_name(), "browser attr");
+        // Tab-scoped steps never fall back to an implicit tab.
+        assert!(browser_command(&s.browser_args().unwrap(), "s1", None).is_err());
+
+        let err = browser_command(&["goto".into()], "s1", None).unwrap_err();
+        assert!(err.contains("required"), "{err}");
+    }
+
+    #[test]
+    fn assertions_compare_the_value() {
+        let s = step("assert: { selector: h1, text: Welcome }").unwrap();
+        assert!(s.check(&json!({ "value": "Welcome back" })).is_ok());
+        assert!(matches!(
+            s.check(&json!({ "value": "Sign in" })),
+            Err(ActionResult::Fatal { code, .. }) if code == "ASSERTION_FAILED"
+        ));
+        // Existence-only assertions pass on any successful read.
+        assert!(
+            step("assert: { selector: h1 }")
+                .unwrap()
+                .check(&json!({}))
+                .is_ok()
+        );
+    }
+
+    #[test]
+    fn script_rejects_unknown_top_level_keys() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("s.yaml");
+        std::fs::write(&path, "sesion: s1\nsteps: []\n").unwrap();
+        assert!(load_script(&path).unwrap_err().contains("sesion"));
+
+        let path = dir.path().join("s.json");
+        std::fs::write(
+            &path,
+            r#"{"session":"s1","steps":[{"goto":"https://a.test"}]}"#,
+        )
+        .unwrap();
+        assert_eq!(load_script(&path).unwrap().steps.len(), 1);
+    }
+}
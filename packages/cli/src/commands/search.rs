@@ -1,21 +1,49 @@
-use std::collections::HashSet;
+use std::collections::{BTreeMap, HashSet};
 
 use serde::Deserialize;
+use serde_json::{Value, json};
 
+use crate::action::Action;
+use crate::action_result::ActionResult;
 use crate::api_response;
+use crate::browser::observation;
 use crate::config;
+use crate::error::CliError;
 use crate::formatter;
+use crate::utils::client::DaemonClient;
+
+/// Added to a site that matches the domain of the `--from-page` page.
+const DOMAIN_SCORE: f64 = 4.0;
+
+/// `page` is the (session, tab) of `--from-page`: its URL is sent along with
+/// the query and sites on its domain rank first.
+pub async fn run(
+    keyword: &str,
+    page: Option<(String, String)>,
+    json: bool,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let page_url = match page {
+        Some((session, tab)) => Some(page_url(session, tab).await?),
+        None => None,
+    };
+    let domain = page_url.as_deref().and_then(host_of);
 
-pub async fn run(keyword: &str, json: bool) -> Result<(), Box<dyn std::error::Error>> {
     let client = reqwest::Client::builder()
         .timeout(std::time::Duration::from_secs(30))
         .build()?;
 
-    let url = format!(
+    let mut url = format!(
         "{}/api/search?q={}",
         config::api_base(),
         urlencoding(keyword)
     );
+    if let (Some(page_url), Some(domain)) = (&page_url, &domain) {
+        url.push_str(&format!(
+            "&url={}&domain={}",
+            urlencoding(page_url),
+            urlencoding(domain)
+        ));
+    }
 
     let response = match client.get(&url).send().await {
         Ok(resp) => resp,
@@ -35,16 +63,105 @@ pub async fn run(keyword: &str, json: bool) -> Result<(), Box<dyn std::error::Er
     let data = api_response::unwrap_data(serde_json::from_str(&body_text)?);
 
     if json {
+        let mut data = data;
+        annotate_scores(&mut data, &query_words(keyword), domain.as_deref());
         println!("{}", serde_json::to_string_pretty(&data)?);
         return Ok(());
     }
 
     let sites: Vec<SearchSite> = serde_json::from_value(data)?;
-    let output = format_search_results(&sites, keyword);
+    let output = format_search_results(&sites, keyword, domain.as_deref());
     println!("{output}");
     Ok(())
 }
 
+/// The URL of the page open in `session`/`tab`.
+async fn page_url(session: String, tab: String) -> Result<String, CliError> {
+    let action = Action::Url(observation::url::Cmd { session, tab });
+    let mut client = DaemonClient::connect().await?;
+    match client.send_action(&action).await? {
+        ActionResult::Ok { data } => Ok(data
+            .get("value")
+            .and_then(|v| v.as_str())
+            .unwrap_or_default()
+            .to_string()),
+        ActionResult::Fatal { code, message, .. } => Err(CliError::InvalidArgument(format!(
+            "--from-page: {code}: {message}"
+        ))),
+        ActionResult::Retryable { reason, .. } => {
+            Err(CliError::InvalidArgument(format!("--from-page: {reason}")))
+        }
+        ActionResult::UserAction { action, .. } => {
+            Err(CliError::InvalidArgument(format!("--from-page: {action}")))
+        }
+    }
+}
+
+/// Lower-cased host of an http(s) URL, without a leading `www.`.
+fn host_of(url: &str) -> Option<String> {
+    let rest = url
+        .strip_prefix("https://")
+        .or_else(|| url.strip_prefix("http://"))?;
+    let authority = rest.split(['/', '?', '#']).next()?;
+    let host = authority.rsplit('@').next()?.split(':').next()?;
+    let host = host.to_lowercase();
+    let host = host.strip_prefix("www.").unwrap_or(&host);
+    (!host.is_empty()).then(|| host.to_string())
+}
+
+/// A site named `airbnb` or `airbnb.com` matches `airbnb.com` and its
+/// subdomains.
+fn site_matches_domain(site: &str, domain: &str) -> bool {
+    let site = site.to_lowercase();
+    let site = site.strip_prefix("www.").unwrap_or(&site);
+    if site.contains('.') && (domain == site || domain.ends_with(&format!(".{site}"))) {
+        return true;
+    }
+    let Some(label) = site.split('.').next().filter(|l| !l.is_empty()) else {
+        return false;
+    };
+    // Skip the TLD so a site called `com` matches nothing.
+    let labels: Vec<&str> = domain.split('.').collect();
+    labels[..labels.len().saturating_sub(1)].contains(&label)
+}
+
+/// Add `score` and `matches` (matched query words per field) to every action
+/// of the search data, in place.
+fn annotate_scores(data: &mut Value, query_words: &[String], domain: Option<&str>) {
+    let Some(sites) = data.as_array_mut() else {
+        return;
+    };
+    for site in sites {
+        let site_name = site["name"].as_str().unwrap_or_default().to_string();
+        let Some(groups) = site.get_mut("groups").and_then(|g| g.as_array_mut()) else {
+            continue;
+        };
+        for group in groups {
+            let group_name = group["name"].as_str().unwrap_or_default().to_string();
+            let Some(actions) = group.get_mut("actions").and_then(|a| a.as_array_mut()) else {
+                continue;
+            };
+            for action in actions {
+                let scored = score_action(
+                    query_words,
+                    &site_name,
+                    &group_name,
+                    action["name"].as_str().unwrap_or_default(),
+                    action["summary"].as_str().unwrap_or_default(),
+                    domain,
+                );
+                if let Some(obj) = action.as_object_mut() {
+                    obj.insert(
+                        "score".into(),
+                        json!((scored.value * 100.0).round() / 100.0),
+                    );
+                    obj.insert("matches".into(), json!(scored.matches));
+                }
+            }
+        }
+    }
+}
+
 #[derive(Deserialize)]
 struct SearchAction {
     name: String,
@@ -77,14 +194,25 @@ struct FlatRow {
     score: f64,
 }
 
+/// A relevance score and the query words each field matched.
+struct Scored {
+    value: f64,
+    matches: BTreeMap<&'static str, Vec<String>>,
+}
+
 fn score_action(
     query_words: &[String],
     site: &str,
     group: &str,
     action: &str,
     summary: &str,
-) -> f64 {
+    domain: Option<&str>,
+) -> Scored {
     let mut score = 0.0;
+    let mut matches: BTreeMap<&'static str, Vec<String>> = BTreeMap::new();
+    let mut matched = |field: &'static str, word: &str| {
+        matches.entry(field).or_default().push(word.to_string());
+    };
     let site_lower = site.to_lowercase();
     let group_lower = group.to_lowercase();
     let action_lower = action.to_lowercase();
@@ -94,30 +222,46 @@ fn score_action(
     for word in query_words {
         if site_lower == *word {
             score += 3.0;
+            matched("site", word);
         } else if site_lower.contains(word.as_str()) {
             score += 1.5;
+            matched("site", word);
         }
 
         if action_parts.iter().any(|p| p == word) {
             score += 2.0;
+            matched("action", word);
         } else if action_lower.contains(word.as_str()) {
             score += 1.0;
+            matched("action", word);
         }
 
         if group_lower.contains(word.as_str()) {
             score += 1.0;
+            matched("group", word);
         }
 
         if summary_lower.contains(word.as_str()) {
             score += 0.5;
+            matched("summary", word);
         }
     }
 
-    score
+    if let Some(domain) = domain
+        && site_matches_domain(site, domain)
+    {
+        score += DOMAIN_SCORE;
+        matched("domain", domain);
+    }
+
+    Scored {
+        value: score,
+        matches,
+    }
 }
 
-fn format_search_results(sites: &[SearchSite], query: &str) -> String {
-    let query_words: Vec<String> = query
+fn query_words(query: &str) -> Vec<String> {
+    query
         .to_lowercase()
         .split_whitespace()
         .filter(|w| {
@@ -127,7 +271,11 @@ fn format_search_results(sites: &[SearchSite], query: &str) -> String {
             .contains(w)
         })
         .map(String::from)
-        .collect();
+        .collect()
+}
+
+fn format_search_results(sites: &[SearchSite], query: &str, domain: Option<&str>) -> String {
+    let query_words = query_words(query);
 
     let mut rows: Vec<FlatRow> = Vec::new();
     for site in sites {
@@ -139,7 +287,9 @@ fn format_search_results(sites: &[SearchSite], query: &str) -> String {
                     &group.name,
                     &action.name,
                     &action.summary,
-                );
+                    domain,
+                )
+                .value;
                 rows.push(FlatRow {
                     site: site.name.clone(),
                     group: group.name.clone(),
@@ -309,7 +459,7 @@ mod tests {
     fn format_search_empty() {
         let sites: Vec<SearchSite> = vec![];
         assert_eq!(
-            format_search_results(&sites, "create page"),
+            format_search_results(&sites, "create page", None),
             "No results found."
         );
     }
@@ -341,7 +491,7 @@ mod tests {
             ],
         }];
 
-        let output = format_search_results(&sites, "create page notion");
+        let output = format_search_results(&sites, "create page notion", None);
         assert!(output.contains("  notion\n"));
         assert!(output.contains("    pages\n"));
         assert!(output.contains("    databases\n"));
@@ -381,7 +531,7 @@ mod tests {
             },
         ];
 
-        let output = format_search_results(&sites, "create page");
+        let output = format_search_results(&sites, "create page", None);
         assert!(output.contains("  notion\n"));
         assert!(output.contains("  coda\n"));
         assert!(output.contains("actions from 2 sites"));
@@ -411,7 +561,7 @@ mod tests {
             }],
         }];
 
-        let output = format_search_results(&sites, "create page");
+        let output = format_search_results(&sites, "create page", None);
         assert!(output.contains("1 actions from 1 sites"));
     }
 
@@ -432,17 +582,73 @@ mod tests {
             }],
         }];
 
-        let output = format_search_results(&sites, "create");
+        let output = format_search_results(&sites, "create", None);
         assert!(output.contains(long_summary.trim()));
     }
 
     #[test]
     fn score_action_basic() {
         let words = vec!["create".into(), "page".into(), "notion".into()];
-        let score = score_action(&words, "notion", "pages", "create_page", "Create a page");
+        let score = score_action(
+            &words,
+            "notion",
+            "pages",
+            "create_page",
+            "Create a page",
+            None,
+        )
+        .value;
         assert!(score > 0.0);
 
-        let low_score = score_action(&words, "stripe", "charges", "list_charges", "List charges");
+        let low_score = score_action(
+            &words,
+            "stripe",
+            "charges",
+            "list_charges",
+            "List charges",
+            None,
+        )
+        .value;
         assert!(score > low_score);
     }
+
+    #[test]
+    fn score_action_records_matches_and_the_page_domain() {
+        let words = vec!["create".into(), "page".into()];
+        let scored = score_action(
+            &words,
+            "notion",
+            "pages",
+            "create_page",
+            "Create a page",
+            Some("notion.so"),
+        );
+        assert_eq!(scored.matches["action"], vec!["create", "page"]);
+        assert_eq!(scored.matches["group"], vec!["page"]);
+        assert_eq!(scored.matches["domain"], vec!["notion.so"]);
+
+        let off_page = score_action(
+            &words,
+            "coda",
+            "pages",
+            "create_page",
+            "Create a page",
+            Some("notion.so"),
+        );
+        assert_eq!(scored.value - off_page.value, DOMAIN_SCORE);
+        assert!(!off_page.matches.contains_key("domain"));
+    }
+
+    #[test]
+    fn host_of_and_site_matches_domain() {
+        assert_eq!(
+            host_of("https://www.Airbnb.com:443/rooms?x=1").as_deref(),
+            Some("airbnb.com")
+        );
+        assert_eq!(host_of("about:blank"), None);
+        assert!(site_matches_domain("airbnb", "airbnb.com"));
+        assert!(site_matches_domain("github.com", "docs.github.com"));
+        assert!(!site_matches_domain("com", "airbnb.com"));
+        assert!(!site_matches_domain("stripe", "airbnb.com"));
+    }
 }
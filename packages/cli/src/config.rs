@@ -6,7 +6,7 @@ use serde::{Deserialize, Serialize};
 
 use crate::browser::session::start::Cmd as StartCmd;
 use crate::error::CliError;
-use crate::types::Mode;
+use crate::types::{GpuMode, HeadlessMode, Mode};
 
 pub(crate) const DEFAULT_PROFILE: &str = "actionbook";
 pub(crate) const CURRENT_CONFIG_VERSION: u32 = 1;
@@ -18,6 +18,16 @@ pub(crate) struct ConfigFile {
     pub(crate) version: Option<u32>,
     pub(crate) api: ApiConfig,
     pub(crate) browser: BrowserConfig,
+    pub(crate) guardrails: GuardrailsConfig,
+    pub(crate) navigate: NavigateConfig,
+    pub(crate) limits: LimitsConfig,
+    pub(crate) interaction: InteractionConfig,
+    pub(crate) extension: ExtensionConfig,
+    pub(crate) telemetry: TelemetryConfig,
+    pub(crate) proxy_pool: ProxyPoolConfig,
+    pub(crate) delivery: DeliveryConfig,
+    pub(crate) artifacts: ArtifactsConfig,
+    pub(crate) macros: std::collections::BTreeMap<String, String>,
 }
 
 impl Default for ConfigFile {
@@ -26,6 +36,16 @@ impl Default for ConfigFile {
             version: Some(CURRENT_CONFIG_VERSION),
             api: ApiConfig::default(),
             browser: BrowserConfig::default(),
+            guardrails: GuardrailsConfig::default(),
+            navigate: NavigateConfig::default(),
+            limits: LimitsConfig::default(),
+            interaction: InteractionConfig::default(),
+            extension: ExtensionConfig::default(),
+            telemetry: TelemetryConfig::default(),
+            proxy_pool: ProxyPoolConfig::default(),
+            delivery: DeliveryConfig::default(),
+            artifacts: ArtifactsConfig::default(),
+            macros: std::collections::BTreeMap::new(),
         }
     }
 }
@@ -41,7 +61,9 @@ pub(crate) struct ApiConfig {
 #[serde(default)]
 pub(crate) struct BrowserConfig {
     pub(crate) mode: Mode,
-    pub(crate) headless: bool,
+    /// `false`, `true` (the new mode), `"new"` or `"old"`.
+    #[serde(with = "headless_setting")]
+    pub(crate) headless: Option<HeadlessMode>,
     #[serde(default = "default_profile_name", alias = "default_profile")]
     pub(crate) profile_name: String,
     #[serde(alias = "executable")]
@@ -49,21 +71,349 @@ pub(crate) struct BrowserConfig {
     pub(crate) provider: Option<String>,
     #[serde(alias = "cdp-endpoint", alias = "cdp_endpoint")]
     pub(crate) cdp_endpoint: Option<String>,
+    /// Default `--window-position` (X,Y) for local sessions.
+    pub(crate) window_position: Option<String>,
+    /// Default `--window-size` (WIDTHxHEIGHT) for local sessions.
+    pub(crate) window_size: Option<String>,
+    /// Default `--gpu` for local sessions.
+    pub(crate) gpu: Option<GpuMode>,
+    /// How long `goto` and the other navigation waits give a page to load.
+    pub(crate) navigation_timeout_ms: Option<u64>,
 }
 
 impl Default for BrowserConfig {
     fn default() -> Self {
         Self {
             mode: Mode::Local,
-            headless: false,
+            headless: None,
             profile_name: default_profile_name(),
             executable_path: None,
             provider: None,
             cdp_endpoint: None,
+            window_position: None,
+            window_size: None,
+            gpu: None,
+            navigation_timeout_ms: None,
+        }
+    }
+}
+
+/// Navigation and action guardrails.
+///
+/// Domain entries are bare hosts (`example.com`, which also matches
+/// subdomains) or wildcard patterns (`*.example.com`, subdomains only).
+/// `blocked_domains` wins over `allowed_domains`; an empty allowlist allows all.
+/// The lists cover every page a tab loads, including ones reached through a
+/// redirect, a click or a script, not just the URLs commands are given.
+///
+/// When `confirm_sensitive_actions` is set, clicks and taps on elements whose
+/// accessible name matches `sensitive_patterns` (case-insensitive regexes; a
+/// built-in payment/deletion list when empty), or on submit controls while the
+/// page is on one of `sensitive_form_domains`, require `--confirm-sensitive`.
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[serde(default)]
+pub(crate) struct GuardrailsConfig {
+    pub(crate) allowed_domains: Vec<String>,
+    pub(crate) blocked_domains: Vec<String>,
+    pub(crate) confirm_sensitive_actions: bool,
+    pub(crate) sensitive_patterns: Vec<String>,
+    pub(crate) sensitive_form_domains: Vec<String>,
+}
+
+/// Defaults applied to `browser goto`.
+///
+/// `auto_dismiss_consent` behaves as if `--dismiss-consent` were passed to
+/// every goto.
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[serde(default)]
+pub(crate) struct NavigateConfig {
+    pub(crate) auto_dismiss_consent: bool,
+}
+
+/// Resource limits the daemon enforces on running sessions.
+///
+/// Checked on the daemon's housekeeping tick (every 60s). A session idle for
+/// longer than `max_session_idle_minutes`, or whose local browser process tree
+/// uses more than `max_session_memory_mb` of resident memory, is closed.
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[serde(default)]
+pub(crate) struct LimitsConfig {
+    pub(crate) max_session_idle_minutes: Option<u64>,
+    pub(crate) max_session_memory_mb: Option<u64>,
+}
+
+impl LimitsConfig {
+    pub(crate) fn is_empty(&self) -> bool {
+        self.max_session_idle_minutes.is_none() && self.max_session_memory_mb.is_none()
+    }
+}
+
+/// Pointer-interaction settings for click, drag and menu.
+///
+/// The target is scrolled to the viewport centre and hit-tested with
+/// `elementFromPoint` before any mouse event. With `occlusion = "scroll"` (the
+/// default), a target covered by another element — typically a sticky header
+/// or footer — is scrolled clear of it using a `scroll-margin` of the cover's
+/// extent plus `scroll_margin` pixels (default 8). If it is still covered, the
+/// first uncovered point inside it is used instead of its centre.
+/// `occlusion = "ignore"` always aims at the centre.
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[serde(default)]
+pub(crate) struct InteractionConfig {
+    pub(crate) occlusion: OcclusionStrategy,
+    pub(crate) scroll_margin: Option<u32>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+#[serde(rename_all = "lowercase")]
+pub(crate) enum OcclusionStrategy {
+    #[default]
+    Scroll,
+    Ignore,
+}
+
+pub(crate) const DEFAULT_SCROLL_MARGIN_PX: u32 = 8;
+
+/// Extension-mode settings.
+///
+/// `connect_timeout_secs` is how long `browser start --mode extension` waits
+/// for the Chrome extension to complete its handshake with the bridge.
+/// `request_timeout_secs` bounds each command relayed to the extension, not
+/// counting time spent on an L3 confirmation prompt; PDF rendering and awaited
+/// scripts are bounded by their caller instead.
+/// `allowed_ids` lists extension IDs the bridge accepts besides the published
+/// Actionbook builds (self-built or enterprise force-installed variants).
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[serde(default)]
+pub(crate) struct ExtensionConfig {
+    pub(crate) connect_timeout_secs: Option<u64>,
+    pub(crate) request_timeout_secs: Option<u64>,
+    pub(crate) allowed_ids: Vec<String>,
+}
+
+/// Local usage metrics, off unless the user opts in.
+///
+/// With `enabled = true`, every command adds to the counters in
+/// `~/.actionbook/stats/stats.json` (see `actionbook stats`). Nothing leaves
+/// the machine unless the user runs `actionbook stats upload`.
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[serde(default)]
+pub(crate) struct TelemetryConfig {
+    pub(crate) enabled: bool,
+}
+
+/// Upstream proxies for `browser start --proxy-pool`.
+///
+/// `proxies` are `http://[user:pass@]host:port` URLs; `strategy` picks one
+/// per connection: `round-robin`, `random` or `sticky-per-domain`.
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[serde(default)]
+pub(crate) struct ProxyPoolConfig {
+    pub(crate) proxies: Vec<String>,
+    pub(crate) strategy: crate::daemon::proxy::Rotation,
+}
+
+/// Where `actionbook run` sends its result and artifacts when it ends.
+///
+/// `on` picks which runs are delivered: `always`, `success` or `failure`.
+/// `[delivery.s3]` uploads `result.json` and every file the steps wrote to
+/// an S3-compatible bucket under `prefix/<run id>/`. `[delivery.webhook]`
+/// POSTs a JSON summary to `url`.
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[serde(default)]
+pub(crate) struct DeliveryConfig {
+    pub(crate) on: DeliveryTrigger,
+    pub(crate) s3: Option<S3DeliveryConfig>,
+    pub(crate) webhook: Option<WebhookDeliveryConfig>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+#[serde(rename_all = "lowercase")]
+pub(crate) enum DeliveryTrigger {
+    #[default]
+    Always,
+    Success,
+    Failure,
+}
+
+/// An S3-compatible bucket (AWS, Cloudflare R2, MinIO, …).
+///
+/// `endpoint` defaults to AWS for `region`; custom endpoints use path-style
+/// URLs unless `path_style = false`. Credentials missing here come from
+/// `AWS_ACCESS_KEY_ID`, `AWS_SECRET_ACCESS_KEY` and `AWS_SESSION_TOKEN`.
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[serde(default)]
+pub(crate) struct S3DeliveryConfig {
+    pub(crate) bucket: String,
+    pub(crate) region: Option<String>,
+    pub(crate) endpoint: Option<String>,
+    pub(crate) prefix: String,
+    pub(crate) path_style: Option<bool>,
+    pub(crate) access_key_id: Option<String>,
+    pub(crate) secret_access_key: Option<String>,
+    pub(crate) session_token: Option<String>,
+}
+
+/// A webhook receiving the run summary. With `secret`, the body is signed
+/// (`X-Actionbook-Signature: sha256=<hex HMAC>`); `include_files` inlines
+/// the artifacts as base64.
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[serde(default)]
+pub(crate) struct WebhookDeliveryConfig {
+    pub(crate) url: String,
+    pub(crate) secret: Option<String>,
+    pub(crate) headers: std::collections::BTreeMap<String, String>,
+    pub(crate) include_files: bool,
+}
+
+/// Where `actionbook run` puts the files its steps write, and how many old
+/// runs it keeps.
+///
+/// `dir` is a path template such as `~/runs/{profile}/{flow}/{timestamp}`;
+/// each run gets a fresh directory and relative step paths (screenshots,
+/// PDFs, table --out) land in it. After the run, sibling run directories
+/// beyond `keep_last`, or beyond `max_total_mb` in total, are removed
+/// oldest first.
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[serde(default)]
+pub(crate) struct ArtifactsConfig {
+    pub(crate) dir: Option<String>,
+    pub(crate) keep_last: Option<usize>,
+    pub(crate) max_total_mb: Option<u64>,
+}
+
+pub(crate) const DEFAULT_NAVIGATION_TIMEOUT_MS: u64 = 30_000;
+pub(crate) const DEFAULT_EXTENSION_CONNECT_TIMEOUT_SECS: u64 = 5;
+pub(crate) const DEFAULT_EXTENSION_REQUEST_TIMEOUT_SECS: u64 = 30;
+
+/// How long navigation waits give a page when no `--timeout` is passed:
+/// `[browser] navigation_timeout_ms`, else the default stretched for CI. An
+/// unreadable config uses the default.
+pub(crate) fn navigation_timeout_ms() -> u64 {
+    load_config()
+        .ok()
+        .and_then(|cfg| cfg.browser.navigation_timeout_ms)
+        .filter(|&ms| ms > 0)
+        .unwrap_or_else(|| crate::utils::ci::scale_timeout(DEFAULT_NAVIGATION_TIMEOUT_MS))
+}
+
+/// Extension handshake wait. An unreadable config uses the default.
+pub(crate) fn extension_connect_timeout() -> std::time::Duration {
+    let secs = load_config()
+        .ok()
+        .and_then(|cfg| cfg.extension.connect_timeout_secs)
+        .unwrap_or(DEFAULT_EXTENSION_CONNECT_TIMEOUT_SECS);
+    std::time::Duration::from_secs(secs)
+}
+
+/// Extra extension IDs from `[extension] allowed_ids`, unvalidated. An
+/// unreadable config allows none.
+pub(crate) fn extension_allowed_ids() -> Vec<String> {
+    load_config()
+        .map(|cfg| cfg.extension.allowed_ids)
+        .unwrap_or_default()
+}
+
+/// Per-request timeout for commands relayed to the extension. An unreadable
+/// config uses the default.
+pub(crate) fn extension_request_timeout() -> std::time::Duration {
+    let secs = load_config()
+        .ok()
+        .and_then(|cfg| cfg.extension.request_timeout_secs)
+        .filter(|&s| s > 0)
+        .unwrap_or(DEFAULT_EXTENSION_REQUEST_TIMEOUT_SECS);
+    std::time::Duration::from_secs(secs)
+}
+
+/// Current session limits. An unreadable config disables them.
+pub(crate) fn session_limits() -> LimitsConfig {
+    load_config().map(|cfg| cfg.limits).unwrap_or_default()
+}
+
+/// Current pointer-interaction settings. An unreadable config uses the defaults.
+pub(crate) fn interaction_config() -> InteractionConfig {
+    load_config().map(|cfg| cfg.interaction).unwrap_or_default()
+}
+
+/// The `[proxy_pool]` section. An unreadable config has no pool.
+pub(crate) fn proxy_pool() -> ProxyPoolConfig {
+    load_config().map(|cfg| cfg.proxy_pool).unwrap_or_default()
+}
+
+/// The `[delivery]` section. An unreadable config delivers nowhere.
+pub(crate) fn delivery() -> DeliveryConfig {
+    load_config().map(|cfg| cfg.delivery).unwrap_or_default()
+}
+
+/// The `[artifacts]` section. An unreadable config keeps artifacts where the
+/// steps put them.
+pub(crate) fn artifacts() -> ArtifactsConfig {
+    load_config().map(|cfg| cfg.artifacts).unwrap_or_default()
+}
+
+/// Named key sequences from `[macros]` (`save = "Control+S"`), pressed with
+/// `browser press @save`. An unreadable config has none.
+pub(crate) fn key_macros() -> std::collections::BTreeMap<String, String> {
+    load_config().map(|cfg| cfg.macros).unwrap_or_default()
+}
+
+/// Whether usage metrics are recorded. An unreadable config leaves them off.
+pub(crate) fn telemetry_enabled() -> bool {
+    load_config().is_ok_and(|cfg| cfg.telemetry.enabled)
+}
+
+/// Whether `browser goto` should dismiss cookie consent banners by default.
+/// An unreadable config leaves the feature off.
+pub(crate) fn auto_dismiss_consent() -> bool {
+    load_config().is_ok_and(|cfg| cfg.navigate.auto_dismiss_consent)
+}
+
+/// `[browser] headless` as a boolean or a headless mode name. The new mode
+/// is written back as `true` so older CLIs still read the file.
+mod headless_setting {
+    use serde::{Deserialize, Deserializer, Serializer};
+
+    use crate::types::HeadlessMode;
+
+    pub(super) fn serialize<S: Serializer>(
+        value: &Option<HeadlessMode>,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        match value {
+            None => serializer.serialize_bool(false),
+            Some(HeadlessMode::New) => serializer.serialize_bool(true),
+            Some(HeadlessMode::Old) => serializer.serialize_str("old"),
+        }
+    }
+
+    pub(super) fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<Option<HeadlessMode>, D::Error> {
+        #[derive(Deserialize)]
+        #[serde(untagged)]
+        enum Raw {
+            Bool(bool),
+            Name(String),
+        }
+        match Raw::deserialize(deserializer)? {
+            Raw::Bool(on) => Ok(on.then_some(HeadlessMode::New)),
+            Raw::Name(name) => super::parse_headless(&name).map_err(serde::de::Error::custom),
         }
     }
 }
 
+/// A headless setting: a boolean, `new` or `old`.
+fn parse_headless(value: &str) -> Result<Option<HeadlessMode>, String> {
+    match value.trim().to_ascii_lowercase().as_str() {
+        "1" | "true" | "yes" | "on" | "new" => Ok(Some(HeadlessMode::New)),
+        "old" => Ok(Some(HeadlessMode::Old)),
+        "0" | "false" | "no" | "off" => Ok(None),
+        _ => Err(format!(
+            "invalid headless value '{value}' (expected true, false, new or old)"
+        )),
+    }
+}
+
 fn default_profile_name() -> String {
     DEFAULT_PROFILE.to_string()
 }
@@ -209,8 +559,14 @@ fn migrate_config(path: &std::path::Path, raw: &toml::Value) -> Result<ConfigFil
 
     // Copy browser fields; mode is forced to the current default (Local)
     if let Some(browser) = raw.get("browser").and_then(|v| v.as_table()) {
-        if let Some(headless) = browser.get("headless").and_then(|v| v.as_bool()) {
-            config.browser.headless = headless;
+        match browser.get("headless") {
+            Some(toml::Value::Boolean(headless)) => {
+                config.browser.headless = headless.then_some(HeadlessMode::New);
+            }
+            Some(toml::Value::String(name)) => {
+                config.browser.headless = parse_headless(name).unwrap_or_default();
+            }
+            _ => {}
         }
         if let Some(profile) = browser
             .get("profile_name")
@@ -236,6 +592,72 @@ fn migrate_config(path: &std::path::Path, raw: &toml::Value) -> Result<ConfigFil
         {
             config.browser.cdp_endpoint = Some(cdp.to_string());
         }
+        if let Some(position) = browser.get("window_position").and_then(|v| v.as_str()) {
+            config.browser.window_position = Some(position.to_string());
+        }
+        if let Some(size) = browser.get("window_size").and_then(|v| v.as_str()) {
+            config.browser.window_size = Some(size.to_string());
+        }
+        if let Some(ms) = browser
+            .get("navigation_timeout_ms")
+            .and_then(|v| v.as_integer())
+            .and_then(|ms| u64::try_from(ms).ok())
+        {
+            config.browser.navigation_timeout_ms = Some(ms);
+        }
+    }
+
+    // Guardrails are copied verbatim; dropping them on migration would silently
+    // lift a sandbox the user configured.
+    if let Some(guardrails) = raw.get("guardrails").cloned()
+        && let Ok(guardrails) = guardrails.try_into::<GuardrailsConfig>()
+    {
+        config.guardrails = guardrails;
+    }
+    if let Some(navigate) = raw.get("navigate").cloned()
+        && let Ok(navigate) = navigate.try_into::<NavigateConfig>()
+    {
+        config.navigate = navigate;
+    }
+    if let Some(limits) = raw.get("limits").cloned()
+        && let Ok(limits) = limits.try_into::<LimitsConfig>()
+    {
+        config.limits = limits;
+    }
+    if let Some(interaction) = raw.get("interaction").cloned()
+        && let Ok(interaction) = interaction.try_into::<InteractionConfig>()
+    {
+        config.interaction = interaction;
+    }
+    if let Some(extension) = raw.get("extension").cloned()
+        && let Ok(extension) = extension.try_into::<ExtensionConfig>()
+    {
+        config.extension = extension;
+    }
+    if let Some(telemetry) = raw.get("telemetry").cloned()
+        && let Ok(telemetry) = telemetry.try_into::<TelemetryConfig>()
+    {
+        config.telemetry = telemetry;
+    }
+    if let Some(proxy_pool) = raw.get("proxy_pool").cloned()
+        && let Ok(proxy_pool) = proxy_pool.try_into::<ProxyPoolConfig>()
+    {
+        config.proxy_pool = proxy_pool;
+    }
+    if let Some(delivery) = raw.get("delivery").cloned()
+        && let Ok(delivery) = delivery.try_into::<DeliveryConfig>()
+    {
+        config.delivery = delivery;
+    }
+    if let Some(artifacts) = raw.get("artifacts").cloned()
+        && let Ok(artifacts) = artifacts.try_into::<ArtifactsConfig>()
+    {
+        config.artifacts = artifacts;
+    }
+    if let Some(macros) = raw.get("macros").cloned()
+        && let Ok(macros) = macros.try_into()
+    {
+        config.macros = macros;
     }
 
     save_config(&config)?;
@@ -271,19 +693,13 @@ fn read_trimmed_env(name: &str) -> Option<String> {
         .filter(|value| !value.is_empty())
 }
 
-fn parse_env_bool(name: &str) -> Result<Option<bool>, CliError> {
+fn parse_env_headless(name: &str) -> Result<Option<Option<HeadlessMode>>, CliError> {
     let Some(value) = read_trimmed_env(name) else {
         return Ok(None);
     };
-
-    let normalized = value.to_ascii_lowercase();
-    match normalized.as_str() {
-        "1" | "true" | "yes" | "on" => Ok(Some(true)),
-        "0" | "false" | "no" | "off" => Ok(Some(false)),
-        _ => Err(CliError::InvalidArgument(format!(
-            "invalid boolean in {name}: {value}"
-        ))),
-    }
+    parse_headless(&value)
+        .map(Some)
+        .map_err(|e| CliError::InvalidArgument(format!("{name}: {e}")))
 }
 
 fn parse_env_mode(name: &str) -> Result<Option<Mode>, CliError> {
@@ -302,6 +718,36 @@ fn normalize_optional(value: Option<String>) -> Option<String> {
         .filter(|v| !v.is_empty())
 }
 
+/// Reject explicit `browser start` flags the selected mode would ignore.
+/// Only flags given on the command line are checked (this runs before config
+/// and env defaults are merged in), so e.g. `headless = true` in config.toml
+/// does not block a one-off `--mode extension` start.
+fn check_mode_flags(cmd: &StartCmd, mode: Mode) -> Result<(), CliError> {
+    let unsupported = |flag: &str| {
+        CliError::InvalidArgument(format!("{flag} is not supported with --mode {mode}"))
+    };
+    if mode == Mode::Extension {
+        if cmd.headless == Some(true) {
+            return Err(unsupported("--headless"));
+        }
+        if cmd.headless_mode.is_some() {
+            return Err(unsupported("--headless-mode"));
+        }
+        if cmd.window_position.is_some() {
+            return Err(unsupported("--window-position"));
+        }
+        if cmd.window_size.is_some() {
+            return Err(unsupported("--window-size"));
+        }
+        if cmd.gpu.is_some() {
+            return Err(unsupported("--gpu"));
+        }
+    } else if cmd.tab_id.is_some() {
+        return Err(unsupported("--tab-id"));
+    }
+    Ok(())
+}
+
 pub fn resolve_start_command(mut cmd: StartCmd) -> Result<StartCmd, CliError> {
     let config = load_config()?;
 
@@ -309,7 +755,7 @@ pub fn resolve_start_command(mut cmd: StartCmd) -> Result<StartCmd, CliError> {
     let env_mode = parse_env_mode("ACTIONBOOK_BROWSER_MODE")?;
     let env_mode_explicit = env_mode.is_some();
     let env_profile = read_trimmed_env("ACTIONBOOK_BROWSER_PROFILE_NAME");
-    let env_headless = parse_env_bool("ACTIONBOOK_BROWSER_HEADLESS")?;
+    let env_headless = parse_env_headless("ACTIONBOOK_BROWSER_HEADLESS")?;
     let env_executable = read_trimmed_env("ACTIONBOOK_BROWSER_EXECUTABLE_PATH");
     let env_provider = read_trimmed_env("ACTIONBOOK_BROWSER_PROVIDER");
     let env_cdp = read_trimmed_env("ACTIONBOOK_BROWSER_CDP_ENDPOINT");
@@ -323,9 +769,33 @@ pub fn resolve_start_command(mut cmd: StartCmd) -> Result<StartCmd, CliError> {
         .or(config_provider);
 
     let resolved_mode = cmd.mode.or(env_mode).unwrap_or(config.browser.mode);
-    let resolved_headless = cmd
-        .headless
-        .unwrap_or_else(|| env_headless.unwrap_or(config.browser.headless));
+    let effective_mode = if resolved_provider.is_some() && !cli_mode_explicit && !env_mode_explicit
+    {
+        Mode::Cloud
+    } else {
+        resolved_mode
+    };
+    check_mode_flags(&cmd, effective_mode)?;
+    // CI machines have no display, so a local browser goes headless there
+    // unless the flag or env var asks otherwise.
+    let ci_headless =
+        (effective_mode == Mode::Local && crate::utils::ci::enabled()).then_some(HeadlessMode::New);
+    // A bare --headless keeps the mode env or config names.
+    let named_mode = env_headless.flatten().or(config.browser.headless);
+    let cli_headless = match (cmd.headless, cmd.headless_mode) {
+        (Some(false), Some(_)) => {
+            return Err(CliError::InvalidArgument(
+                "--headless-mode cannot be combined with --headless false".to_string(),
+            ));
+        }
+        (Some(false), None) => Some(None),
+        (_, Some(mode)) => Some(Some(mode)),
+        (Some(true), None) => Some(Some(named_mode.unwrap_or_default())),
+        (None, None) => None,
+    };
+    let resolved_headless = cli_headless
+        .or(env_headless)
+        .unwrap_or(config.browser.headless.or(ci_headless));
 
     let cli_profile = normalize_optional(cmd.profile.clone());
     let resolved_profile = cli_profile
@@ -338,13 +808,19 @@ pub fn resolve_start_command(mut cmd: StartCmd) -> Result<StartCmd, CliError> {
         || config_profile.as_deref() != Some(DEFAULT_PROFILE);
 
     cmd.mode = Some(resolved_mode);
-    cmd.headless = Some(resolved_headless);
+    cmd.headless = Some(resolved_headless.is_some());
+    cmd.headless_mode = resolved_headless;
     cmd.profile = explicit_profile.then_some(resolved_profile);
     cmd.executable_path = env_executable.or(config_executable);
     cmd.provider = resolved_provider.clone();
     cmd.cdp_endpoint = normalize_optional(cmd.cdp_endpoint)
         .or(env_cdp)
         .or(config_cdp);
+    cmd.window_position = normalize_optional(cmd.window_position)
+        .or_else(|| normalize_optional(config.browser.window_position.clone()));
+    cmd.window_size = normalize_optional(cmd.window_size)
+        .or_else(|| normalize_optional(config.browser.window_size.clone()));
+    cmd.gpu = cmd.gpu.or(config.browser.gpu);
 
     if cmd.provider.is_some()
         && !matches!(cmd.mode, Some(Mode::Cloud))
@@ -408,6 +884,7 @@ mod tests {
             ("ACTIONBOOK_BROWSER_EXECUTABLE_PATH", None),
             ("ACTIONBOOK_BROWSER_PROVIDER", None),
             ("ACTIONBOOK_BROWSER_CDP_ENDPOINT", None),
+            ("ACTIONBOOK_CI", Some("0")),
         ]);
         (tmp, guard)
     }
@@ -416,6 +893,7 @@ mod tests {
         StartCmd {
             mode: None,
             headless: None,
+            headless_mode: None,
             profile: None,
             executable_path: None,
             open_url: None,
@@ -427,6 +905,18 @@ mod tests {
             set_session_id: None,
             stealth: true,
             max_tracked_requests: 500,
+            record_session: None,
+            adblock: false,
+            adblock_list: Vec::new(),
+            max_download_bytes: None,
+            download_dir: None,
+            window_position: None,
+            window_size: None,
+            gpu: None,
+            device: None,
+            proxy: Vec::new(),
+            proxy_pool: false,
+            proxy_rotation: None,
             provider_env: Default::default(),
         }
     }
@@ -494,6 +984,200 @@ cdp_endpoint = "ws://127.0.0.1:9333/devtools/browser/config"
         );
     }
 
+    #[test]
+    fn window_placement_defaults_come_from_config_unless_given() {
+        let _lock = test_lock();
+        let (_tmp, _guard) = make_home();
+        fs::create_dir_all(actionbook_home()).expect("home");
+        fs::write(
+            config_path(),
+            r#"[browser]
+window_position = "1920,0"
+window_size = "1280x800"
+"#,
+        )
+        .expect("write config");
+
+        let resolved = resolve_start_command(base_cmd()).expect("resolve");
+        assert_eq!(resolved.window_position.as_deref(), Some("1920,0"));
+        assert_eq!(resolved.window_size.as_deref(), Some("1280x800"));
+
+        let mut cmd = base_cmd();
+        cmd.window_size = Some("800x600".to_string());
+        let resolved = resolve_start_command(cmd).expect("resolve");
+        assert_eq!(resolved.window_size.as_deref(), Some("800x600"));
+    }
+
+    #[test]
+    fn extension_connect_timeout_reads_config_and_survives_migration() {
+        let _lock = test_lock();
+        let (_tmp, _guard) = make_home();
+        fs::create_dir_all(actionbook_home()).expect("home");
+        assert_eq!(
+            extension_connect_timeout(),
+            std::time::Duration::from_secs(DEFAULT_EXTENSION_CONNECT_TIMEOUT_SECS)
+        );
+
+        // No `version` key → migrated on load.
+        fs::write(config_path(), "[extension]\nconnect_timeout_secs = 30\n").expect("write");
+        assert_eq!(
+            extension_connect_timeout(),
+            std::time::Duration::from_secs(30)
+        );
+    }
+
+    #[test]
+    fn proxy_pool_reads_proxies_and_strategy_through_migration() {
+        let _lock = test_lock();
+        let (_tmp, _guard) = make_home();
+        fs::create_dir_all(actionbook_home()).expect("home");
+        assert!(proxy_pool().proxies.is_empty());
+
+        // No `version` key → migrated on load.
+        fs::write(
+            config_path(),
+            "[proxy_pool]\nproxies = [\"http://a:1\", \"http://b:2\"]\nstrategy = \"sticky-per-domain\"\n",
+        )
+        .expect("write");
+        let pool = proxy_pool();
+        assert_eq!(pool.proxies, ["http://a:1", "http://b:2"]);
+        assert_eq!(
+            pool.strategy,
+            crate::daemon::proxy::Rotation::StickyPerDomain
+        );
+    }
+
+    #[test]
+    fn delivery_reads_s3_and_webhook_sections() {
+        let _lock = test_lock();
+        let (_tmp, _guard) = make_home();
+        fs::create_dir_all(actionbook_home()).expect("home");
+        let cfg = delivery();
+        assert!(cfg.s3.is_none() && cfg.webhook.is_none());
+
+        fs::write(
+            config_path(),
+            "[delivery]\non = \"failure\"\n\
+             [delivery.s3]\nbucket = \"runs\"\nendpoint = \"http://127.0.0.1:9000\"\nprefix = \"nightly/\"\n\
+             [delivery.webhook]\nurl = \"https://hooks.example.com/runs\"\nheaders = { X-Team = \"qa\" }\n",
+        )
+        .expect("write");
+        let cfg = delivery();
+        assert_eq!(cfg.on, DeliveryTrigger::Failure);
+        let s3 = cfg.s3.expect("s3");
+        assert_eq!(s3.bucket, "runs");
+        assert_eq!(s3.prefix, "nightly/");
+        let webhook = cfg.webhook.expect("webhook");
+        assert_eq!(webhook.url, "https://hooks.example.com/runs");
+        assert_eq!(webhook.headers["X-Team"], "qa");
+    }
+
+    #[test]
+    fn artifacts_reads_template_and_retention() {
+        let _lock = test_lock();
+        let (_tmp, _guard) = make_home();
+        fs::create_dir_all(actionbook_home()).expect("home");
+        assert!(artifacts().dir.is_none());
+
+        fs::write(
+            config_path(),
+            "[artifacts]\ndir = \"~/runs/{profile}/{flow}/{timestamp}\"\nkeep_last = 20\nmax_total_mb = 500\n",
+        )
+        .expect("write");
+        let cfg = artifacts();
+        assert_eq!(
+            cfg.dir.as_deref(),
+            Some("~/runs/{profile}/{flow}/{timestamp}")
+        );
+        assert_eq!(cfg.keep_last, Some(20));
+        assert_eq!(cfg.max_total_mb, Some(500));
+    }
+
+    #[test]
+    fn interaction_config_reads_occlusion_strategy_and_margin() {
+        let _lock = test_lock();
+        let (_tmp, _guard) = make_home();
+        fs::create_dir_all(actionbook_home()).expect("home");
+        let cfg = interaction_config();
+        assert_eq!(cfg.occlusion, OcclusionStrategy::Scroll);
+        assert_eq!(cfg.scroll_margin, None);
+
+        fs::write(
+            config_path(),
+            "[interaction]\nocclusion = \"ignore\"\nscroll_margin = 24\n",
+        )
+        .expect("write");
+        let cfg = interaction_config();
+        assert_eq!(cfg.occlusion, OcclusionStrategy::Ignore);
+        assert_eq!(cfg.scroll_margin, Some(24));
+    }
+
+    #[test]
+    fn extension_allowed_ids_survive_migration() {
+        let _lock = test_lock();
+        let (_tmp, _guard) = make_home();
+        fs::create_dir_all(actionbook_home()).expect("home");
+        assert!(extension_allowed_ids().is_empty());
+
+        fs::write(
+            config_path(),
+            "[extension]\nallowed_ids = [\"abcdefghijklmnopabcdefghijklmnop\"]\n",
+        )
+        .expect("write");
+        assert_eq!(
+            extension_allowed_ids(),
+            vec!["abcdefghijklmnopabcdefghijklmnop".to_string()]
+        );
+    }
+
+    #[test]
+    fn navigation_timeout_is_read_and_survives_migration() {
+        let _lock = test_lock();
+        let (_tmp, _guard) = make_home();
+        let _env = EnvGuard::set(&[("ACTIONBOOK_CI", Some("0"))]);
+        fs::create_dir_all(actionbook_home()).expect("home");
+        assert_eq!(navigation_timeout_ms(), DEFAULT_NAVIGATION_TIMEOUT_MS);
+
+        fs::write(config_path(), "[browser]\nnavigation_timeout_ms = 90000\n").expect("write");
+        assert_eq!(navigation_timeout_ms(), 90_000);
+        assert!(
+            fs::read_to_string(config_path())
+                .unwrap()
+                .contains("navigation_timeout_ms = 90000")
+        );
+
+        fs::write(
+            config_path(),
+            "version = 1\n[browser]\nnavigation_timeout_ms = 0\n",
+        )
+        .expect("write");
+        assert_eq!(navigation_timeout_ms(), DEFAULT_NAVIGATION_TIMEOUT_MS);
+    }
+
+    #[test]
+    fn key_macros_are_read_and_survive_migration() {
+        let _lock = test_lock();
+        let (_tmp, _guard) = make_home();
+        fs::create_dir_all(actionbook_home()).expect("home");
+        assert!(key_macros().is_empty());
+
+        fs::write(
+            config_path(),
+            "[macros]\nsave = \"Control+S\"\nnext_field = \"Tab Tab Enter\"\n",
+        )
+        .expect("write");
+        let macros = key_macros();
+        assert_eq!(macros["save"], "Control+S");
+        assert_eq!(macros["next_field"], "Tab Tab Enter");
+        // The file had no version, so it was migrated and rewritten.
+        assert!(
+            fs::read_to_string(config_path())
+                .unwrap()
+                .contains("version = 1")
+        );
+        assert_eq!(key_macros(), macros);
+    }
+
     #[test]
     fn cli_overrides_env_for_mode_profile_headless_and_cdp_endpoint() {
         let _lock = test_lock();
@@ -541,6 +1225,37 @@ cdp_endpoint = "ws://127.0.0.1:9333/devtools/browser/config"
         assert_eq!(resolved.mode, Some(Mode::Cloud));
     }
 
+    #[test]
+    fn explicit_flags_must_fit_the_selected_mode() {
+        let _lock = test_lock();
+        let (_tmp, _guard) = make_home();
+        fs::create_dir_all(actionbook_home()).expect("home");
+        fs::write(
+            config_path(),
+            "version = 1\n[browser]\nheadless = true\nwindow_size = \"1280x800\"\n",
+        )
+        .expect("write");
+
+        // Config defaults do not conflict with a one-off extension start.
+        let mut cmd = base_cmd();
+        cmd.mode = Some(Mode::Extension);
+        assert!(resolve_start_command(cmd).is_ok());
+
+        let mut cmd = base_cmd();
+        cmd.mode = Some(Mode::Extension);
+        cmd.headless = Some(true);
+        let err = resolve_start_command(cmd).unwrap_err().to_string();
+        assert!(err.contains("--headless"), "{err}");
+
+        let mut cmd = base_cmd();
+        cmd.tab_id = Some(42);
+        let err = resolve_start_command(cmd).unwrap_err().to_string();
+        assert!(
+            err.contains("--tab-id is not supported with --mode local"),
+            "{err}"
+        );
+    }
+
     #[test]
     fn cli_false_headless_overrides_env_true() {
         let _lock = test_lock();
@@ -555,6 +1270,110 @@ cdp_endpoint = "ws://127.0.0.1:9333/devtools/browser/config"
         assert_eq!(resolved.headless, Some(false));
     }
 
+    #[test]
+    fn ci_mode_defaults_local_sessions_to_headless() {
+        let _lock = test_lock();
+        let (_tmp, _guard) = make_home();
+        let _env = EnvGuard::set(&[("ACTIONBOOK_CI", Some("1"))]);
+
+        let resolved = resolve_start_command(base_cmd()).expect("resolve");
+        assert_eq!(resolved.headless, Some(true));
+
+        let mut cmd = base_cmd();
+        cmd.mode = Some(Mode::Extension);
+        let resolved = resolve_start_command(cmd).expect("extension has no headless");
+        assert_eq!(resolved.headless, Some(false));
+
+        let _env = EnvGuard::set(&[("ACTIONBOOK_BROWSER_HEADLESS", Some("0"))]);
+        let resolved = resolve_start_command(base_cmd()).expect("resolve");
+        assert_eq!(resolved.headless, Some(false));
+    }
+
+    #[test]
+    fn headless_mode_comes_from_config_env_or_cli() {
+        let _lock = test_lock();
+        let (_tmp, _guard) = make_home();
+        fs::create_dir_all(actionbook_home()).expect("home");
+        fs::write(
+            config_path(),
+            "version = 1\n[browser]\nheadless = \"old\"\n",
+        )
+        .expect("write config");
+
+        let resolved = resolve_start_command(base_cmd()).expect("resolve");
+        assert_eq!(resolved.headless, Some(true));
+        assert_eq!(resolved.headless_mode, Some(HeadlessMode::Old));
+
+        // A bare --headless keeps the configured mode; --headless-mode wins.
+        let mut cmd = base_cmd();
+        cmd.headless = Some(true);
+        let resolved = resolve_start_command(cmd).expect("resolve");
+        assert_eq!(resolved.headless_mode, Some(HeadlessMode::Old));
+        let mut cmd = base_cmd();
+        cmd.headless_mode = Some(HeadlessMode::New);
+        let resolved = resolve_start_command(cmd).expect("resolve");
+        assert_eq!(resolved.headless_mode, Some(HeadlessMode::New));
+
+        let _env = EnvGuard::set(&[("ACTIONBOOK_BROWSER_HEADLESS", Some("false"))]);
+        let resolved = resolve_start_command(base_cmd()).expect("resolve");
+        assert_eq!(resolved.headless, Some(false));
+        assert_eq!(resolved.headless_mode, None);
+
+        let mut cmd = base_cmd();
+        cmd.headless = Some(false);
+        cmd.headless_mode = Some(HeadlessMode::Old);
+        let err = resolve_start_command(cmd).unwrap_err().to_string();
+        assert!(err.contains("--headless-mode"), "{err}");
+
+        let _env = EnvGuard::set(&[("ACTIONBOOK_BROWSER_HEADLESS", Some("sideways"))]);
+        let err = resolve_start_command(base_cmd()).unwrap_err().to_string();
+        assert!(err.contains("ACTIONBOOK_BROWSER_HEADLESS"), "{err}");
+    }
+
+    #[test]
+    fn gpu_defaults_from_config_and_is_rejected_in_extension_mode() {
+        let _lock = test_lock();
+        let (_tmp, _guard) = make_home();
+        fs::create_dir_all(actionbook_home()).expect("home");
+        fs::write(
+            config_path(),
+            "version = 1\n[browser]\ngpu = \"swiftshader\"\n",
+        )
+        .expect("write config");
+
+        let resolved = resolve_start_command(base_cmd()).expect("resolve");
+        assert_eq!(resolved.gpu, Some(GpuMode::Swiftshader));
+        let mut cmd = base_cmd();
+        cmd.gpu = Some(GpuMode::Off);
+        let resolved = resolve_start_command(cmd).expect("resolve");
+        assert_eq!(resolved.gpu, Some(GpuMode::Off));
+
+        let mut cmd = base_cmd();
+        cmd.mode = Some(Mode::Extension);
+        cmd.gpu = Some(GpuMode::Hardware);
+        let err = resolve_start_command(cmd).unwrap_err().to_string();
+        assert!(err.contains("--gpu"), "{err}");
+    }
+
+    #[test]
+    fn headless_setting_round_trips_through_the_config_file() {
+        let mut config = ConfigFile::default();
+        for (setting, written) in [
+            (None, "headless = false"),
+            (Some(HeadlessMode::New), "headless = true"),
+            (Some(HeadlessMode::Old), "headless = \"old\""),
+        ] {
+            config.browser.headless = setting;
+            let text = toml::to_string(&config).expect("serialize");
+            assert!(text.contains(written), "{text}");
+            let back: ConfigFile = toml::from_str(&text).expect("parse");
+            assert_eq!(back.browser.headless, setting);
+        }
+        let parsed: ConfigFile = toml::from_str("[browser]\nheadless = \"new\"\n").expect("parse");
+        assert_eq!(parsed.browser.headless, Some(HeadlessMode::New));
+        assert!(toml::from_str::<ConfigFile>("[browser]\nheadless = \"maybe\"\n").is_err());
+    }
+
     #[test]
     fn migrate_old_config_without_version() {
         let _lock = test_lock();
@@ -586,7 +1405,7 @@ executable_path = "/usr/bin/chrome"
             config.api.base_url.as_deref(),
             Some("https://api.example.com")
         );
-        assert!(config.browser.headless);
+        assert_eq!(config.browser.headless, Some(HeadlessMode::New));
         assert_eq!(config.browser.profile_name, "my-profile");
         assert_eq!(
             config.browser.executable_path.as_deref(),
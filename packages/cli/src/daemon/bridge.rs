@@ -17,14 +17,15 @@
 //! use of 19222) does not permanently break extension mode. If every attempt
 //! fails the daemon still starts — only extension mode is unavailable.
 
+use std::collections::HashMap;
 use std::sync::Arc;
-use std::time::Instant;
+use std::time::{Duration, Instant};
 
 use futures_util::{SinkExt, StreamExt};
 use serde_json::json;
 use tokio::io::{AsyncReadExt, AsyncWriteExt};
 use tokio::net::{TcpListener, TcpStream};
-use tokio::sync::{Mutex, mpsc};
+use tokio::sync::{Mutex, mpsc, oneshot};
 use tokio_tungstenite::tungstenite::Message;
 use tokio_tungstenite::tungstenite::http::StatusCode;
 use tracing::{error, info, warn};
@@ -55,7 +56,8 @@ const BIND_RETRY_DELAYS_MS: &[u64] = &[100, 500, 1_000, 2_000, 5_000];
 ///   single-attach protocol cannot be mixed with the multi-attach client.
 const PROTOCOL_VERSION: &str = crate::EXTENSION_PROTOCOL_MIN_VERSION;
 
-/// Known Actionbook Chrome extension IDs.
+/// Known Actionbook Chrome extension IDs. More can be allowed with
+/// `[extension] allowed_ids` in config.toml.
 const EXTENSION_ID_CWS: &str = "bebchpafpemheedhcdabookaifcijmfo";
 const EXTENSION_ID_DEV: &str = "dpfioflkmnkklgjldmaggkodhlidkdcd";
 const EXTENSION_IDS: &[&str] = &[EXTENSION_ID_CWS, EXTENSION_ID_DEV];
@@ -65,6 +67,23 @@ const EXTENSION_IDS: &[&str] = &[EXTENSION_ID_CWS, EXTENSION_ID_DEV];
 const HEALTH_CHECK_PATH: &str = "/healthz";
 const HEALTH_CHECK_RESPONSE: &[u8] = b"HTTP/1.1 204 No Content\r\nContent-Length: 0\r\nConnection: close\r\nAccess-Control-Allow-Origin: *\r\nCache-Control: no-store\r\n\r\n";
 
+/// JSON-RPC error code for requests the bridge answers itself (timeout,
+/// extension gone). Matches the extension's own generic server error.
+const BRIDGE_ERROR_CODE: i64 = -32000;
+
+/// First id used for requests the daemon itself sends to the extension
+/// (see [`request`]). Far above anything a CdpSession allocates, and still
+/// exact as a JS number.
+const BRIDGE_REQUEST_ID_BASE: u64 = 1 << 48;
+
+/// How long the extension keeps an L3 confirmation prompt open
+/// (`L3_CONFIRM_TIMEOUT_MS` in background.js).
+const CONFIRMATION_WINDOW: Duration = Duration::from_secs(30);
+
+/// Event the extension sends when a relayed request starts waiting on the
+/// user's L3 confirmation.
+const CONFIRMATION_PENDING_EVENT: &str = "Extension.confirmationPending";
+
 // ─── Shared State ───────────────────────────────────────────────────────
 
 /// Observable state of the bridge TCP listener.
@@ -94,6 +113,16 @@ pub struct BridgeState {
     last_activity: Instant,
     /// Listener bind state (updated by the background bind task).
     listener_status: BridgeListenerStatus,
+    /// Why the most recent extension hello was refused, if it was.
+    last_rejection: Option<String>,
+    /// CDP client requests relayed to the extension and not yet answered, with
+    /// the time the bridge gives up on each (`None`: no bridge deadline).
+    in_flight: HashMap<u64, Option<Instant>>,
+    /// Protocol version from the connected extension's hello.
+    extension_protocol: Option<String>,
+    /// Waiters for requests the daemon sent to the extension itself.
+    bridge_requests: HashMap<u64, oneshot::Sender<serde_json::Value>>,
+    next_bridge_request_id: u64,
 }
 
 impl BridgeState {
@@ -104,6 +133,11 @@ impl BridgeState {
             connection_id: 0,
             last_activity: Instant::now(),
             listener_status: BridgeListenerStatus::Binding,
+            last_rejection: None,
+            in_flight: HashMap::new(),
+            extension_protocol: None,
+            bridge_requests: HashMap::new(),
+            next_bridge_request_id: BRIDGE_REQUEST_ID_BASE,
         }
     }
 
@@ -127,6 +161,93 @@ impl BridgeState {
     fn set_listener_status(&mut self, status: BridgeListenerStatus) {
         self.listener_status = status;
     }
+
+    /// Why the most recent extension handshake was refused (old protocol
+    /// version, unknown extension ID), cleared once an extension connects.
+    pub fn last_rejection(&self) -> Option<&str> {
+        self.last_rejection.as_deref()
+    }
+
+    /// Protocol version the connected extension announced in its hello.
+    pub fn extension_protocol(&self) -> Option<&str> {
+        self.extension_protocol.as_deref()
+    }
+}
+
+/// Send a request from the daemon itself (not a CDP client) to the connected
+/// extension and wait for its `result`. Errors carry a readable reason: not
+/// connected, a JSON-RPC error from the extension, disconnect or timeout.
+pub async fn request(
+    state: &SharedBridgeState,
+    method: &str,
+    params: serde_json::Value,
+    timeout: Duration,
+) -> Result<serde_json::Value, String> {
+    let (tx, rx) = oneshot::channel();
+    let id = {
+        let mut s = state.lock().await;
+        let id = s.next_bridge_request_id;
+        s.next_bridge_request_id += 1;
+        let message = json!({ "id": id, "method": method, "params": params });
+        let sent = s
+            .extension_tx
+            .as_ref()
+            .is_some_and(|ext_tx| ext_tx.send(message.to_string()).is_ok());
+        if !sent {
+            return Err("no Chrome extension connected".to_string());
+        }
+        s.bridge_requests.insert(id, tx);
+        s.touch();
+        id
+    };
+
+    let response = match tokio::time::timeout(timeout, rx).await {
+        Ok(Ok(response)) => response,
+        Ok(Err(_)) => return Err("extension disconnected before responding".to_string()),
+        Err(_) => {
+            state.lock().await.bridge_requests.remove(&id);
+            return Err(format!(
+                "extension did not respond to {method} within {}s",
+                timeout.as_secs_f64()
+            ));
+        }
+    };
+    if let Some(err) = response.get("error") {
+        return Err(err
+            .get("message")
+            .and_then(|m| m.as_str())
+            .unwrap_or("extension returned an error")
+            .to_string());
+    }
+    Ok(response
+        .get("result")
+        .cloned()
+        .unwrap_or(serde_json::Value::Null))
+}
+
+/// Wait until a Chrome extension completes the hello handshake.
+///
+/// The extension reconnects with exponential backoff (100ms–2s after a daemon
+/// restart, longer once Chrome has throttled the service worker), so callers
+/// should allow a few seconds. On timeout the error carries the last
+/// handshake rejection, if any, so the CLI can say *why* nothing connected.
+pub async fn wait_for_extension(
+    state: &SharedBridgeState,
+    timeout: std::time::Duration,
+) -> Result<(), Option<String>> {
+    let deadline = Instant::now() + timeout;
+    loop {
+        {
+            let s = state.lock().await;
+            if s.is_extension_connected() {
+                return Ok(());
+            }
+            if Instant::now() >= deadline {
+                return Err(s.last_rejection.clone());
+            }
+        }
+        tokio::time::sleep(std::time::Duration::from_millis(100)).await;
+    }
 }
 
 pub type SharedBridgeState = Arc<Mutex<BridgeState>>;
@@ -489,6 +610,9 @@ async fn handle_extension(
 
     // Validate protocol version against `EXTENSION_PROTOCOL_MIN_VERSION`.
     if !is_version_ok(client_version) {
+        state.lock().await.last_rejection = Some(format!(
+            "extension protocol {client_version} is older than the required {PROTOCOL_VERSION}"
+        ));
         let err = json!({
             "type": "hello_error",
             "error": "version_mismatch",
@@ -499,15 +623,27 @@ async fn handle_extension(
         return;
     }
 
-    // Validate extension origin.
-    let origin_ok = EXTENSION_IDS.iter().any(|id| {
-        let expected = format!("chrome-extension://{id}");
-        origin
-            .as_deref()
-            .map(|o| o.eq_ignore_ascii_case(&expected))
-            .unwrap_or(false)
-    });
-    if !origin_ok {
+    // Validate extension origin, and the ID the extension reports in its hello
+    // (sent since 0.4.x; older builds are checked by origin alone).
+    let origin_id = origin.as_deref().and_then(origin_extension_id);
+    let hello_id = hello.get("extensionId").and_then(|v| v.as_str());
+    let configured = configured_extension_ids();
+    let source = origin_id
+        .as_deref()
+        .and_then(|id| extension_id_source(id, &configured));
+    let rejection = match (&origin_id, source, hello_id) {
+        (_, None, _) => Some(format!(
+            "extension origin {} is not a known Actionbook extension ID \
+             (allow it with [extension] allowed_ids in config.toml)",
+            origin.as_deref().unwrap_or("<none>")
+        )),
+        (Some(o), _, Some(h)) if !o.eq_ignore_ascii_case(h) => Some(format!(
+            "extension reported ID {h} but connected from origin chrome-extension://{o}"
+        )),
+        _ => None,
+    };
+    if let Some(reason) = rejection {
+        state.lock().await.last_rejection = Some(reason);
         let err = json!({
             "type": "hello_error",
             "error": "invalid_origin",
@@ -516,6 +652,12 @@ async fn handle_extension(
         let _ = write.send(Message::Text(err.to_string().into())).await;
         return;
     }
+    if source == Some(ExtensionIdSource::Config) {
+        warn!(
+            "bridge: accepting extension {} from [extension] allowed_ids — not a published Actionbook build",
+            origin_id.as_deref().unwrap_or_default()
+        );
+    }
 
     // Reject if another extension is already connected.
     {
@@ -550,6 +692,8 @@ async fn handle_extension(
         let mut s = state.lock().await;
         s.connection_id += 1;
         s.extension_tx = Some(ext_tx);
+        s.extension_protocol = Some(client_version.to_string());
+        s.last_rejection = None;
         s.touch();
         s.connection_id
     };
@@ -570,15 +714,9 @@ async fn handle_extension(
     while let Some(frame) = read.next().await {
         match frame {
             Ok(Message::Text(text)) => {
-                let text_str = text.to_string();
                 let mut s = state.lock().await;
                 s.touch();
-                if let Some(ref cdp_tx) = s.cdp_tx
-                    && cdp_tx.send(text_str).is_err()
-                {
-                    warn!("bridge: failed to forward extension message to CDP client");
-                }
-                // If no CDP client, message is dropped (events before session start).
+                route_extension_message(&mut s, text.to_string());
             }
             Ok(Message::Close(_)) => break,
             Err(_) => break,
@@ -588,11 +726,24 @@ async fn handle_extension(
 
     info!("bridge: extension disconnected");
 
-    // Cleanup: only clear if we own the current connection.
+    // Cleanup: only clear if we own the current connection, and fail
+    // whatever it was still working on instead of letting callers time out.
     {
         let mut s = state.lock().await;
         if s.connection_id == my_conn_id {
             s.extension_tx = None;
+            s.extension_protocol = None;
+            // Dropping the senders wakes their `request` callers.
+            s.bridge_requests.clear();
+            let ids: Vec<u64> = s.in_flight.drain().map(|(id, _)| id).collect();
+            if let Some(ref cdp_tx) = s.cdp_tx {
+                for id in ids {
+                    let _ = cdp_tx.send(error_response(
+                        id,
+                        "extension disconnected before responding",
+                    ));
+                }
+            }
         }
     }
 
@@ -624,19 +775,14 @@ async fn handle_cdp_client(
     {
         let mut s = state.lock().await;
         s.cdp_tx = Some(cdp_tx);
+        s.in_flight.clear();
         s.touch();
     }
 
+    let timeout = crate::config::extension_request_timeout();
+
     // Forward the first CDP message (already read) to extension.
-    {
-        let mut s = state.lock().await;
-        s.touch();
-        if let Some(ref ext_tx) = s.extension_tx
-            && ext_tx.send(first_message).is_err()
-        {
-            warn!("bridge: failed to forward first CDP message to extension");
-        }
-    }
+    relay_request(&state, first_message, timeout).await;
 
     // Writer task: channel → CDP client WS.
     let write = Arc::new(Mutex::new(write));
@@ -653,16 +799,7 @@ async fn handle_cdp_client(
     // Reader: CDP client WS → forward to extension.
     while let Some(frame) = read.next().await {
         match frame {
-            Ok(Message::Text(text)) => {
-                let text_str = text.to_string();
-                let mut s = state.lock().await;
-                s.touch();
-                if let Some(ref ext_tx) = s.extension_tx
-                    && ext_tx.send(text_str).is_err()
-                {
-                    warn!("bridge: failed to forward CDP message to extension");
-                }
-            }
+            Ok(Message::Text(text)) => relay_request(&state, text.to_string(), timeout).await,
             Ok(Message::Close(_)) => break,
             Err(_) => break,
             _ => {}
@@ -673,11 +810,155 @@ async fn handle_cdp_client(
     {
         let mut s = state.lock().await;
         s.cdp_tx = None;
+        s.in_flight.clear();
     }
 
     write_handle.abort();
 }
 
+/// Forward one CDP client request to the extension and arm its timeout.
+///
+/// Requests are answered with an error immediately when no extension is
+/// connected. Otherwise, if the extension has not replied after `timeout`,
+/// the bridge answers with an error itself, forgets the id (a late reply is
+/// dropped) and sends `Extension.cancel` so the extension can abandon work
+/// such as a pending L3 confirmation. A request the extension reports as
+/// waiting on the user's confirmation gets the prompt window on top, and
+/// long-running calls (see [`runs_unbounded`]) are left to their caller's
+/// own deadline.
+async fn relay_request(state: &SharedBridgeState, message: String, timeout: Duration) {
+    let parsed = serde_json::from_str::<serde_json::Value>(&message).ok();
+    let id = parsed.as_ref().and_then(|m| m.get("id")?.as_u64());
+    let unbounded = parsed.as_ref().is_some_and(runs_unbounded);
+    let mut s = state.lock().await;
+    s.touch();
+    let sent = s
+        .extension_tx
+        .as_ref()
+        .is_some_and(|ext_tx| ext_tx.send(message).is_ok());
+    let Some(id) = id else {
+        if !sent {
+            warn!("bridge: failed to forward CDP message to extension");
+        }
+        return;
+    };
+    if !sent {
+        if let Some(ref cdp_tx) = s.cdp_tx {
+            let _ = cdp_tx.send(error_response(id, "no Chrome extension connected"));
+        }
+        return;
+    }
+    if unbounded {
+        s.in_flight.insert(id, None);
+        return;
+    }
+    let mut deadline = Instant::now() + timeout;
+    s.in_flight.insert(id, Some(deadline));
+    drop(s);
+
+    let state = state.clone();
+    tokio::spawn(async move {
+        let mut s = loop {
+            tokio::time::sleep_until(deadline.into()).await;
+            let s = state.lock().await;
+            match s.in_flight.get(&id) {
+                None => return,
+                // Pushed back while we slept (pending confirmation).
+                Some(Some(later)) if *later > Instant::now() => deadline = *later,
+                Some(_) => break s,
+            }
+        };
+        s.in_flight.remove(&id);
+        warn!("bridge: extension did not answer request {id} within {timeout:?}");
+        if let Some(ref cdp_tx) = s.cdp_tx {
+            let _ = cdp_tx.send(error_response(
+                id,
+                &format!(
+                    "extension did not respond within {}s",
+                    timeout.as_secs_f64()
+                ),
+            ));
+        }
+        if let Some(ref ext_tx) = s.extension_tx {
+            let cancel = json!({
+                "id": 0,
+                "method": "Extension.cancel",
+                "params": { "id": id },
+            });
+            let _ = ext_tx.send(cancel.to_string());
+        }
+        s.touch();
+    });
+}
+
+/// Deliver one message from the extension: to the daemon's own waiter, to
+/// the CDP client, or to the timers of the requests still in flight.
+fn route_extension_message(s: &mut BridgeState, text: String) {
+    if let Some(id) = message_id(&text) {
+        if let Some(waiter) = s.bridge_requests.remove(&id) {
+            if let Ok(response) = serde_json::from_str(&text) {
+                let _ = waiter.send(response);
+            }
+            return;
+        }
+        // A response whose request already timed out has no waiter.
+        if s.in_flight.remove(&id).is_none() {
+            return;
+        }
+    } else if let Some(id) = confirmation_pending_id(&text) {
+        // A human is deciding: give them the whole prompt window, then the
+        // usual budget for the command itself.
+        if let Some(Some(deadline)) = s.in_flight.get_mut(&id) {
+            let timeout = crate::config::extension_request_timeout();
+            *deadline = Instant::now() + CONFIRMATION_WINDOW + timeout;
+        }
+        return;
+    }
+    if let Some(ref cdp_tx) = s.cdp_tx
+        && cdp_tx.send(text).is_err()
+    {
+        warn!("bridge: failed to forward extension message to CDP client");
+    }
+    // If no CDP client, message is dropped (events before session start).
+}
+
+/// Calls that may legitimately outlast the relay timeout: PDF rendering and
+/// scripts whose promise the caller awaits.
+fn runs_unbounded(message: &serde_json::Value) -> bool {
+    match message.get("method").and_then(|m| m.as_str()) {
+        Some("Page.printToPDF") => true,
+        Some("Runtime.evaluate" | "Runtime.callFunctionOn") => {
+            message.pointer("/params/awaitPromise") == Some(&json!(true))
+        }
+        _ => false,
+    }
+}
+
+/// Request id named by an `Extension.confirmationPending` event.
+fn confirmation_pending_id(message: &str) -> Option<u64> {
+    let event = serde_json::from_str::<serde_json::Value>(message).ok()?;
+    if event.get("method")?.as_str()? != CONFIRMATION_PENDING_EVENT {
+        return None;
+    }
+    event.pointer("/params/id")?.as_u64()
+}
+
+/// Top-level numeric `id` of a JSON-RPC message (events have none).
+fn message_id(message: &str) -> Option<u64> {
+    serde_json::from_str::<serde_json::Value>(message)
+        .ok()?
+        .get("id")?
+        .as_u64()
+}
+
+fn error_response(id: u64, message: &str) -> String {
+    json!({
+        "id": id,
+        "error": { "code": BRIDGE_ERROR_CODE, "message": message },
+    })
+    .to_string()
+}
+
 // ─── Helpers ────────────────────────────────────────────────────────────
 
 fn is_health_check_request(buf: &[u8]) -> bool {
@@ -707,6 +988,59 @@ async fn maybe_serve_health_check(stream: &mut TcpStream) -> std::io::Result<boo
     Ok(true)
 }
 
+/// Where an accepted extension ID comes from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ExtensionIdSource {
+    /// One of the published Actionbook builds (`EXTENSION_IDS`).
+    Builtin,
+    /// Listed under `[extension] allowed_ids`.
+    Config,
+}
+
+fn extension_id_source(id: &str, configured: &[String]) -> Option<ExtensionIdSource> {
+    if EXTENSION_IDS
+        .iter()
+        .any(|known| known.eq_ignore_ascii_case(id))
+    {
+        Some(ExtensionIdSource::Builtin)
+    } else if configured.iter().any(|c| c.eq_ignore_ascii_case(id)) {
+        Some(ExtensionIdSource::Config)
+    } else {
+        None
+    }
+}
+
+/// `[extension] allowed_ids`, lowercased. Malformed entries are skipped with
+/// a warning rather than silently widening what the bridge accepts.
+fn configured_extension_ids() -> Vec<String> {
+    crate::config::extension_allowed_ids()
+        .into_iter()
+        .filter_map(|id| {
+            let id = id.trim().to_ascii_lowercase();
+            if is_valid_extension_id(&id) {
+                Some(id)
+            } else {
+                warn!("bridge: ignoring malformed extension ID {id:?} in [extension] allowed_ids");
+                None
+            }
+        })
+        .collect()
+}
+
+/// Chrome extension IDs are 32 characters in `a`–`p`.
+fn is_valid_extension_id(id: &str) -> bool {
+    id.len() == 32 && id.bytes().all(|b| (b'a'..=b'p').contains(&b))
+}
+
+/// Extension ID from a `chrome-extension://<id>` origin.
+fn origin_extension_id(origin: &str) -> Option<String> {
+    let lower = origin.to_ascii_lowercase();
+    let id = lower
+        .strip_prefix("chrome-extension://")?
+        .trim_end_matches('/');
+    (!id.is_empty()).then(|| id.to_string())
+}
+
 /// Validate WS origin: allow chrome-extension:// and loopback HTTP.
 fn is_origin_allowed(origin: Option<&str>) -> bool {
     let Some(o) = origin else { return true };
@@ -726,7 +1060,7 @@ fn is_origin_allowed(origin: Option<&str>) -> bool {
 }
 
 /// Check protocol version >= 0.4.0 (simple major.minor comparison).
-fn is_version_ok(version: &str) -> bool {
+pub(crate) fn is_version_ok(version: &str) -> bool {
     let parts: Vec<u32> = version.split('.').filter_map(|p| p.parse().ok()).collect();
     if parts.len() < 2 {
         return false;
@@ -758,6 +1092,31 @@ mod tests {
         assert!(!is_origin_allowed(Some("http://192.168.1.1")));
     }
 
+    #[test]
+    fn test_extension_id_source() {
+        let configured = vec!["abcdefghijklmnopabcdefghijklmnop".to_string()];
+        assert_eq!(
+            extension_id_source(EXTENSION_ID_CWS, &configured),
+            Some(ExtensionIdSource::Builtin)
+        );
+        assert_eq!(
+            extension_id_source("ABCDEFGHIJKLMNOPABCDEFGHIJKLMNOP", &configured),
+            Some(ExtensionIdSource::Config)
+        );
+        assert_eq!(
+            extension_id_source("pppppppppppppppppppppppppppppppp", &configured),
+            None
+        );
+        assert!(is_valid_extension_id("abcdefghijklmnopabcdefghijklmnop"));
+        assert!(!is_valid_extension_id("abcdefghijklmnopabcdefghijklmnoz"));
+        assert!(!is_valid_extension_id("abc"));
+        assert_eq!(
+            origin_extension_id("chrome-extension://BEBCHPAFPEMHEEDHCDABOOKAIFCIJMFO/").as_deref(),
+            Some(EXTENSION_ID_CWS)
+        );
+        assert_eq!(origin_extension_id("http://localhost"), None);
+    }
+
     #[test]
     fn test_is_version_ok() {
         assert!(is_version_ok("0.4.0"));
@@ -814,6 +1173,175 @@ mod tests {
         format!("{addr}")
     }
 
+    #[tokio::test]
+    async fn wait_for_extension_reports_last_rejection_on_timeout() {
+        let state = new_bridge_state();
+        let timeout = std::time::Duration::from_millis(150);
+        assert_eq!(wait_for_extension(&state, timeout).await, Err(None));
+
+        state.lock().await.last_rejection = Some("extension protocol 0.3.0 is older".into());
+        let err = wait_for_extension(&state, timeout).await.unwrap_err();
+        assert_eq!(err.as_deref(), Some("extension protocol 0.3.0 is older"));
+
+        let (tx, _rx) = mpsc::unbounded_channel();
+        state.lock().await.extension_tx = Some(tx);
+        assert_eq!(wait_for_extension(&state, timeout).await, Ok(()));
+    }
+
+    #[tokio::test]
+    async fn request_returns_result_or_error_from_extension() {
+        let state = new_bridge_state();
+        let timeout = Duration::from_millis(500);
+        assert!(
+            request(&state, "Extension.getVersion", json!({}), timeout)
+                .await
+                .unwrap_err()
+                .contains("no Chrome extension")
+        );
+
+        let (ext_tx, mut ext_rx) = mpsc::unbounded_channel::<String>();
+        state.lock().await.extension_tx = Some(ext_tx);
+        let responder = {
+            let state = state.clone();
+            tokio::spawn(async move {
+                for reply in [
+                    json!({ "result": { "version": "0.4.1" } }),
+                    json!({ "error": { "code": -32601, "message": "Unknown extension method" } }),
+                ] {
+                    let sent: serde_json::Value =
+                        serde_json::from_str(&ext_rx.recv().await.unwrap()).unwrap();
+                    let id = sent["id"].as_u64().unwrap();
+                    assert!(id >= BRIDGE_REQUEST_ID_BASE);
+                    let mut reply = reply;
+                    reply["id"] = json!(id);
+                    let waiter = state.lock().await.bridge_requests.remove(&id).unwrap();
+                    waiter.send(reply).unwrap();
+                }
+            })
+        };
+
+        let ok = request(&state, "Extension.getVersion", json!({}), timeout).await;
+        assert_eq!(ok.unwrap()["version"], "0.4.1");
+        let err = request(&state, "Extension.nope", json!({}), timeout).await;
+        assert_eq!(err.unwrap_err(), "Unknown extension method");
+        responder.await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn relay_request_times_out_and_cancels() {
+        let state = new_bridge_state();
+        let (ext_tx, mut ext_rx) = mpsc::unbounded_channel();
+        let (cdp_tx, mut cdp_rx) = mpsc::unbounded_channel();
+        {
+            let mut s = state.lock().await;
+            s.extension_tx = Some(ext_tx);
+            s.cdp_tx = Some(cdp_tx);
+        }
+        let timeout = Duration::from_millis(50);
+
+        relay_request(&state, r#"{"id":7,"method":"Page.reload"}"#.into(), timeout).await;
+        assert!(ext_rx.recv().await.unwrap().contains("Page.reload"));
+
+        let err: serde_json::Value = serde_json::from_str(&cdp_rx.recv().await.unwrap()).unwrap();
+        assert_eq!(err["id"], 7);
+        assert!(
+            err["error"]["message"]
+                .as_str()
+                .unwrap()
+                .contains("did not respond")
+        );
+        let cancel: serde_json::Value =
+            serde_json::from_str(&ext_rx.recv().await.unwrap()).unwrap();
+        assert_eq!(cancel["method"], "Extension.cancel");
+        assert_eq!(cancel["params"]["id"], 7);
+        assert!(state.lock().await.in_flight.is_empty());
+
+        // Answered in time: no error, no cancel.
+        relay_request(&state, r#"{"id":8,"method":"Page.reload"}"#.into(), timeout).await;
+        ext_rx.recv().await.unwrap();
+        assert!(state.lock().await.in_flight.remove(&8).is_some());
+        tokio::time::sleep(timeout * 2).await;
+        assert!(cdp_rx.try_recv().is_err());
+        assert!(ext_rx.try_recv().is_err());
+    }
+
+    #[tokio::test]
+    async fn relay_request_waits_for_a_pending_confirmation() {
+        let state = new_bridge_state();
+        let (ext_tx, mut ext_rx) = mpsc::unbounded_channel();
+        let (cdp_tx, mut cdp_rx) = mpsc::unbounded_channel();
+        {
+            let mut s = state.lock().await;
+            s.extension_tx = Some(ext_tx);
+            s.cdp_tx = Some(cdp_tx);
+        }
+        let timeout = Duration::from_millis(50);
+
+        relay_request(
+            &state,
+            r#"{"id":9,"method":"Network.setCookie"}"#.into(),
+            timeout,
+        )
+        .await;
+        ext_rx.recv().await.unwrap();
+        route_extension_message(
+            &mut *state.lock().await,
+            r#"{"method":"Extension.confirmationPending","params":{"id":9}}"#.into(),
+        );
+        // The user takes longer than the relay timeout to approve.
+        tokio::time::sleep(timeout * 3).await;
+        assert!(
+            cdp_rx.try_recv().is_err(),
+            "confirmation event is not relayed"
+        );
+        assert!(
+            ext_rx.try_recv().is_err(),
+            "no cancel while the user decides"
+        );
+
+        route_extension_message(&mut *state.lock().await, r#"{"id":9,"result":{}}"#.into());
+        let reply: serde_json::Value = serde_json::from_str(&cdp_rx.recv().await.unwrap()).unwrap();
+        assert_eq!(reply["id"], 9);
+        assert!(reply.get("error").is_none());
+        assert!(state.lock().await.in_flight.is_empty());
+    }
+
+    #[tokio::test]
+    async fn relay_request_leaves_long_running_calls_to_their_caller() {
+        let state = new_bridge_state();
+        let (ext_tx, mut ext_rx) = mpsc::unbounded_channel();
+        let (cdp_tx, mut cdp_rx) = mpsc::unbounded_channel();
+        {
+            let mut s = state.lock().await;
+            s.extension_tx = Some(ext_tx);
+            s.cdp_tx = Some(cdp_tx);
+        }
+        let timeout = Duration::from_millis(50);
+
+        for message in [
+            r#"{"id":1,"method":"Page.printToPDF"}"#,
+            r#"{"id":2,"method":"Runtime.evaluate","params":{"awaitPromise":true}}"#,
+        ] {
+            relay_request(&state, message.into(), timeout).await;
+            ext_rx.recv().await.unwrap();
+        }
+        // Without awaitPromise an evaluate is still bounded.
+        relay_request(
+            &state,
+            r#"{"id":3,"method":"Runtime.evaluate","params":{}}"#.into(),
+            timeout,
+        )
+        .await;
+        ext_rx.recv().await.unwrap();
+
+        tokio::time::sleep(timeout * 3).await;
+        let err: serde_json::Value = serde_json::from_str(&cdp_rx.recv().await.unwrap()).unwrap();
+        assert_eq!(err["id"], 3);
+        assert!(cdp_rx.try_recv().is_err());
+        let s = state.lock().await;
+        assert!(s.in_flight.contains_key(&1) && s.in_flight.contains_key(&2));
+    }
+
     #[tokio::test]
     async fn bind_with_retry_succeeds_immediately_when_port_is_free() {
         let addr = ephemeral_addr().await;
@@ -3,6 +3,7 @@ use std::process::{Child, Stdio};
 use std::time::Duration;
 
 use crate::error::CliError;
+use crate::types::{GpuMode, HeadlessMode};
 
 /// Find Chrome executable.
 pub fn find_chrome() -> Result<String, CliError> {
@@ -57,16 +58,27 @@ pub fn find_chrome() -> Result<String, CliError> {
     Err(CliError::BrowserNotFound)
 }
 
+/// Initial window placement for a launched browser.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct LaunchWindow {
+    pub position: Option<(i64, i64)>,
+    pub size: Option<(u32, u32)>,
+}
+
 /// Launch Chrome with CDP enabled.
 /// Returns (Child, actual_cdp_port).
 /// Uses --remote-debugging-port=0 so Chrome picks a free port itself,
 /// then reads the actual port from stderr ("DevTools listening on ws://...").
+#[allow(clippy::too_many_arguments)]
 pub async fn launch_chrome(
     executable: &str,
-    headless: bool,
+    headless: Option<HeadlessMode>,
     user_data_dir: &str,
     open_url: Option<&str>,
     stealth: bool,
+    window: LaunchWindow,
+    proxy_server: Option<&str>,
+    gpu: Option<GpuMode>,
 ) -> Result<(Child, u16), CliError> {
     let mut args = vec![
         "--remote-debugging-port=0".to_string(),
@@ -96,8 +108,29 @@ pub async fn launch_chrome(
         args.push("--disable-background-timer-throttling".to_string());
         args.push("--disable-backgrounding-occluded-windows".to_string());
     }
-    if headless {
-        args.push("--headless=new".to_string());
+    if crate::utils::ci::needs_no_sandbox() {
+        args.push("--no-sandbox".to_string());
+        // Container /dev/shm is usually 64MB, too small for Chrome's renderers.
+        if !stealth {
+            args.push("--disable-dev-shm-usage".to_string());
+        }
+    }
+    if let Some(mode) = headless {
+        args.push(format!("--headless={mode}"));
+    }
+    // --window-size also sets the headless viewport; a position is meaningless
+    // without a visible window.
+    if let Some((w, h)) = window.size {
+        args.push(format!("--window-size={w},{h}"));
+    }
+    if let (Some((x, y)), None) = (window.position, headless) {
+        args.push(format!("--window-position={x},{y}"));
+    }
+    if let Some(proxy) = proxy_server {
+        args.push(format!("--proxy-server={proxy}"));
+    }
+    if let Some(gpu) = gpu {
+        args.extend(gpu_args(gpu).iter().map(|a| a.to_string()));
     }
     // open_url is NOT passed as a Chrome launch arg — Chrome starts on about:blank.
     // The caller navigates after attach() so the stealth script is already injected.
@@ -105,63 +138,166 @@ pub async fn launch_chrome(
 
     let exe = executable.to_string();
     // Spawn Chrome and read stderr in a blocking thread to avoid blocking tokio
-
     tokio::task::spawn_blocking(move || -> Result<(Child, u16), CliError> {
-        let mut child = std::process::Command::new(&exe)
-            .args(&args)
-            .stdin(Stdio::null())
-            .stdout(Stdio::null())
-            .stderr(Stdio::piped())
-            .spawn()
-            .map_err(|e| CliError::BrowserLaunchFailed(e.to_string()))?;
-
-        let stderr = child.stderr.take().ok_or_else(|| {
-            CliError::BrowserLaunchFailed("failed to capture Chrome stderr".to_string())
-        })?;
-
-        // Read stderr to find "DevTools listening on ws://HOST:PORT/..."
-        let (tx, rx) = std::sync::mpsc::channel::<u16>();
-        std::thread::spawn(move || {
-            let reader = BufReader::new(stderr);
-            for line in reader.lines() {
-                let line = match line {
-                    Ok(l) => l,
-                    Err(_) => break,
-                };
-                if line.contains("DevTools listening on")
-                    && let Some(ws_start) = line.find("ws://")
-                {
-                    let after_ws = &line[ws_start + 5..];
-                    if let Some(colon) = after_ws.find(':') {
-                        let after_colon = &after_ws[colon + 1..];
-                        let port_str: String = after_colon
-                            .chars()
-                            .take_while(|c| c.is_ascii_digit())
-                            .collect();
-                        if let Ok(p) = port_str.parse::<u16>() {
-                            let _ = tx.send(p);
-                            return;
-                        }
-                    }
+        let mut attempt = 1;
+        loop {
+            match spawn_chrome_once(&exe, &args) {
+                Err(LaunchFailure::PortCollision(detail)) if attempt < LAUNCH_ATTEMPTS => {
+                    tracing::warn!(
+                        "Chrome could not bind its DevTools port (attempt {attempt}/{LAUNCH_ATTEMPTS}): {detail}"
+                    );
+                    attempt += 1;
+                    std::thread::sleep(Duration::from_millis(200 * attempt as u64));
                 }
+                Err(LaunchFailure::PortCollision(detail)) => {
+                    return Err(CliError::BrowserLaunchFailed(format!(
+                        "Chrome could not bind a DevTools port after {LAUNCH_ATTEMPTS} attempts: {detail}"
+                    )));
+                }
+                Err(LaunchFailure::Other(e)) => return Err(e),
+                Ok(launched) => return Ok(launched),
             }
-        });
-
-        let port = rx
-            .recv_timeout(std::time::Duration::from_secs(30))
-            .map_err(|_| {
-                crate::daemon::chrome_reaper::kill_and_reap(&mut child);
-                CliError::CdpConnectionFailed(
-                    "Chrome did not print DevTools listening URL within 30s".to_string(),
-                )
-            })?;
-
-        Ok((child, port))
+        }
     })
     .await
     .map_err(|e| CliError::Internal(format!("spawn_blocking failed: {e}")))?
 }
 
+/// Chrome flags for a `--gpu` choice.
+pub fn gpu_args(gpu: GpuMode) -> &'static [&'static str] {
+    match gpu {
+        // Chrome 137+ only falls back to SwiftShader for WebGL when told the
+        // (JIT-compiling) software renderer is acceptable.
+        GpuMode::Swiftshader => &[
+            "--use-gl=angle",
+            "--use-angle=swiftshader",
+            "--enable-unsafe-swiftshader",
+        ],
+        GpuMode::Hardware => &[
+            "--use-gl=angle",
+            "--use-angle=default",
+            "--enable-gpu",
+            "--ignore-gpu-blocklist",
+            "--enable-gpu-rasterization",
+        ],
+        GpuMode::Off => &["--disable-gpu", "--disable-webgl", "--disable-3d-apis"],
+    }
+}
+
+/// Page expression reporting whether a WebGL context can be created.
+pub const WEBGL_PROBE: &str = "(() => { try { const c = document.createElement('canvas'); return !!(c.getContext('webgl2') || c.getContext('webgl')); } catch (e) { return false; } })()";
+
+/// How many times a launch is retried when Chrome fails to bind its DevTools
+/// port. With `--remote-debugging-port=0` the OS hands out the port, so this
+/// only covers the rare case of concurrent launches racing on the same one.
+const LAUNCH_ATTEMPTS: u32 = 3;
+
+/// How long a launched Chrome has to print its DevTools URL.
+const LISTEN_TIMEOUT_MS: u64 = 30_000;
+
+/// Number of trailing stderr lines kept for launch error messages.
+const STDERR_TAIL_LINES: usize = 10;
+
+enum LaunchFailure {
+    /// Chrome exited because its DevTools server could not bind; worth a retry.
+    PortCollision(String),
+    Other(CliError),
+}
+
+enum StderrEvent {
+    Listening(u16),
+    /// stderr closed before the DevTools URL was printed; carries the tail.
+    Closed(Vec<String>),
+}
+
+fn spawn_chrome_once(exe: &str, args: &[String]) -> Result<(Child, u16), LaunchFailure> {
+    let mut child = std::process::Command::new(exe)
+        .args(args)
+        .stdin(Stdio::null())
+        .stdout(Stdio::null())
+        .stderr(Stdio::piped())
+        .spawn()
+        .map_err(|e| LaunchFailure::Other(CliError::BrowserLaunchFailed(e.to_string())))?;
+
+    let stderr = child.stderr.take().ok_or_else(|| {
+        LaunchFailure::Other(CliError::BrowserLaunchFailed(
+            "failed to capture Chrome stderr".to_string(),
+        ))
+    })?;
+
+    // Read stderr to find "DevTools listening on ws://HOST:PORT/..."
+    let (tx, rx) = std::sync::mpsc::channel::<StderrEvent>();
+    std::thread::spawn(move || {
+        let reader = BufReader::new(stderr);
+        let mut tail = std::collections::VecDeque::with_capacity(STDERR_TAIL_LINES);
+        for line in reader.lines() {
+            let line = match line {
+                Ok(l) => l,
+                Err(_) => break,
+            };
+            if let Some(p) = parse_devtools_port(&line) {
+                let _ = tx.send(StderrEvent::Listening(p));
+                return;
+            }
+            if tail.len() == STDERR_TAIL_LINES {
+                tail.pop_front();
+            }
+            tail.push_back(line);
+        }
+        let _ = tx.send(StderrEvent::Closed(tail.into()));
+    });
+
+    let wait = Duration::from_millis(crate::utils::ci::scale_timeout(LISTEN_TIMEOUT_MS));
+    match rx.recv_timeout(wait) {
+        Ok(StderrEvent::Listening(port)) => Ok((child, port)),
+        Ok(StderrEvent::Closed(tail)) => {
+            crate::daemon::chrome_reaper::kill_and_reap(&mut child);
+            let detail = tail.join("\n");
+            if is_port_collision(&detail) {
+                Err(LaunchFailure::PortCollision(detail))
+            } else if detail.is_empty() {
+                Err(LaunchFailure::Other(CliError::BrowserLaunchFailed(
+                    "Chrome exited before printing the DevTools listening URL".to_string(),
+                )))
+            } else {
+                Err(LaunchFailure::Other(CliError::BrowserLaunchFailed(
+                    format!("Chrome exited before printing the DevTools listening URL:\n{detail}"),
+                )))
+            }
+        }
+        Err(_) => {
+            crate::daemon::chrome_reaper::kill_and_reap(&mut child);
+            Err(LaunchFailure::Other(CliError::CdpConnectionFailed(
+                format!(
+                    "Chrome did not print DevTools listening URL within {}s",
+                    wait.as_secs()
+                ),
+            )))
+        }
+    }
+}
+
+/// Extract the port from Chrome's "DevTools listening on ws://HOST:PORT/..." line.
+fn parse_devtools_port(line: &str) -> Option<u16> {
+    if !line.contains("DevTools listening on") {
+        return None;
+    }
+    let after_ws = &line[line.find("ws://")? + 5..];
+    let after_colon = &after_ws[after_ws.find(':')? + 1..];
+    let port_str: String = after_colon
+        .chars()
+        .take_while(|c| c.is_ascii_digit())
+        .collect();
+    port_str.parse::<u16>().ok()
+}
+
+/// Whether Chrome's stderr says the DevTools server lost a bind race.
+fn is_port_collision(stderr: &str) -> bool {
+    stderr.contains("Address already in use")
+        || stderr.contains("Cannot start http server for devtools")
+        || (stderr.contains("bind() failed") && stderr.contains("DevTools"))
+}
+
 /// Discover the WebSocket debugger URL from Chrome's /json/version endpoint.
 pub async fn discover_ws_url(port: u16) -> Result<String, CliError> {
     discover_ws_url_from_base(&format!("http://127.0.0.1:{port}")).await
@@ -191,6 +327,20 @@ pub async fn discover_ws_url_from_base(base_url: &str) -> Result<String, CliErro
     )))
 }
 
+/// Read the current browser WebSocket URL from a running Chrome, once and
+/// quickly. `None` when nothing answers on the port.
+pub async fn probe_ws_url(port: u16) -> Option<String> {
+    let url = format!("http://127.0.0.1:{port}/json/version");
+    let client = reqwest::Client::builder()
+        .timeout(Duration::from_secs(2))
+        .build()
+        .ok()?;
+    let json: serde_json::Value = client.get(&url).send().await.ok()?.json().await.ok()?;
+    json.get("webSocketDebuggerUrl")
+        .and_then(|v| v.as_str())
+        .map(String::from)
+}
+
 /// Get list of targets (tabs) from Chrome.
 pub async fn list_targets(port: u16) -> Result<Vec<serde_json::Value>, CliError> {
     list_targets_from_base(&format!("http://127.0.0.1:{port}")).await
@@ -258,6 +408,50 @@ fn endpoint_origin(endpoint: &str) -> Result<String, CliError> {
     Ok(format!("{}://{}", &endpoint[..scheme_end], authority))
 }
 
+/// Page expression reporting whether the built-in PDF viewer is present,
+/// which the old headless shell lacks.
+pub const PDF_VIEWER_PROBE: &str = "navigator.pdfViewerEnabled === true";
+
+/// The headless mode a running Chrome is in, from its own `Browser.getVersion`
+/// user agent and whether a page sees a PDF viewer. Headless builds report
+/// `HeadlessChrome` whatever the stealth override shows pages; of the two,
+/// only the new mode ships the PDF viewer.
+pub fn observed_headless(browser_user_agent: &str, pdf_viewer: bool) -> Option<HeadlessMode> {
+    if !browser_user_agent.contains("HeadlessChrome") {
+        return None;
+    }
+    Some(if pdf_viewer {
+        HeadlessMode::New
+    } else {
+        HeadlessMode::Old
+    })
+}
+
+/// Warning for a Chrome that did not run the headless mode it was asked for.
+pub fn headless_mismatch(
+    requested: Option<HeadlessMode>,
+    observed: Option<HeadlessMode>,
+) -> Option<String> {
+    let name = |m: Option<HeadlessMode>| match m {
+        Some(mode) => format!("the {mode} headless mode"),
+        None => "a visible window".to_string(),
+    };
+    if requested == observed {
+        return None;
+    }
+    let mut warning = format!(
+        "Chrome was asked for {} but runs in {}",
+        name(requested),
+        name(observed)
+    );
+    if requested == Some(HeadlessMode::Old) {
+        warning.push_str(
+            "; Chrome 132+ has no old mode, point executable_path at chrome-headless-shell",
+        );
+    }
+    Some(warning)
+}
+
 fn parse_endpoint_port(endpoint: &str) -> Result<u16, CliError> {
     let scheme_end = endpoint
         .find("://")
@@ -276,3 +470,66 @@ fn parse_endpoint_port(endpoint: &str) -> Result<u16, CliError> {
         CliError::InvalidArgument(format!("invalid endpoint port in {endpoint}: {port_str}"))
     })
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_devtools_port_reads_listening_line() {
+        assert_eq!(
+            parse_devtools_port(
+                "DevTools listening on ws://127.0.0.1:41235/devtools/browser/0b6c0e3a-77f1"
+            ),
+            Some(41235)
+        );
+        assert_eq!(
+            parse_devtools_port("[1234:5678:ERROR] something else"),
+            None
+        );
+        assert_eq!(parse_devtools_port("DevTools listening on nowhere"), None);
+    }
+
+    #[test]
+    fn port_collision_is_recognised() {
+        assert!(is_port_collision(
+            "[0101/000000.000:ERROR:socket_posix.cc(147)] bind() failed: Address already in use (98)\n\
+             Cannot start http server for devtools."
+        ));
+        assert!(!is_port_collision("Opening in existing browser session."));
+    }
+
+    #[test]
+    fn gpu_args_enable_or_disable_webgl() {
+        assert!(gpu_args(GpuMode::Swiftshader).contains(&"--enable-unsafe-swiftshader"));
+        assert!(gpu_args(GpuMode::Swiftshader).contains(&"--use-angle=swiftshader"));
+        assert!(gpu_args(GpuMode::Hardware).contains(&"--ignore-gpu-blocklist"));
+        assert!(!gpu_args(GpuMode::Hardware).contains(&"--enable-unsafe-swiftshader"));
+        assert!(gpu_args(GpuMode::Off).contains(&"--disable-gpu"));
+    }
+
+    #[test]
+    fn observed_headless_tells_the_modes_apart() {
+        let headless = "Mozilla/5.0 (X11; Linux x86_64) AppleWebKit/537.36 (KHTML, like Gecko) HeadlessChrome/131.0.0.0 Safari/537.36";
+        let headed = headless.replace("HeadlessChrome", "Chrome");
+        assert_eq!(observed_headless(headless, true), Some(HeadlessMode::New));
+        assert_eq!(observed_headless(headless, false), Some(HeadlessMode::Old));
+        assert_eq!(observed_headless(&headed, true), None);
+
+        assert_eq!(
+            headless_mismatch(Some(HeadlessMode::New), Some(HeadlessMode::New)),
+            None
+        );
+        let warning = headless_mismatch(Some(HeadlessMode::Old), Some(HeadlessMode::New)).unwrap();
+        assert!(
+            warning.starts_with(
+                "Chrome was asked for the old headless mode but runs in the new headless mode;"
+            ),
+            "{warning}"
+        );
+        assert_eq!(
+            headless_mismatch(None, Some(HeadlessMode::New)).unwrap(),
+            "Chrome was asked for a visible window but runs in the new headless mode"
+        );
+    }
+}
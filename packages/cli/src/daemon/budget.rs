@@ -0,0 +1,275 @@
+//! Download-size budget for navigation and capture commands.
+//!
+//! `--max-download-bytes` (per command, or session-wide via `browser start`)
+//! caps the bytes a tab may pull over the network while the command runs.
+//! The session-wide value also covers every command that can load a page —
+//! reload, back, forward, clicks, new tabs — and a load such a command starts
+//! is counted until it finishes. Bytes are counted from Network events; once
+//! the budget is crossed the tab stops loading and the command fails with
+//! `DOWNLOAD_BUDGET_EXCEEDED` instead of waiting for the rest of the page.
+
+use std::collections::HashMap;
+use std::future::Future;
+use std::time::Duration;
+
+use serde_json::{Value, json};
+use tokio::sync::oneshot;
+
+use crate::action_result::ActionResult;
+
+use super::cdp_session::{CdpSession, cdp_error_to_result, get_cdp_and_target};
+use super::registry::SharedRegistry;
+
+pub const DOWNLOAD_BUDGET_EXCEEDED: &str = "DOWNLOAD_BUDGET_EXCEEDED";
+
+/// Per-request byte accounting. `dataReceived` reports chunks and
+/// `loadingFinished` the final total; Chrome often reports `0` for chunks
+/// coming from the network service, so each request keeps the larger of the
+/// two rather than adding them.
+#[derive(Debug, Default)]
+struct ByteCounter {
+    per_request: HashMap<String, u64>,
+    total: u64,
+}
+
+impl ByteCounter {
+    /// Feed one Network event; returns the running total.
+    fn observe(&mut self, method: &str, params: &Value) -> u64 {
+        let Some(id) = params["requestId"].as_str() else {
+            return self.total;
+        };
+        let len = params["encodedDataLength"].as_f64().unwrap_or(0.0).max(0.0) as u64;
+        let seen = self.per_request.entry(id.to_string()).or_insert(0);
+        let next = match method {
+            "Network.dataReceived" => *seen + len,
+            "Network.loadingFinished" => (*seen).max(len),
+            _ => *seen,
+        };
+        self.total += next - *seen;
+        *seen = next;
+        self.total
+    }
+}
+
+/// Run `fut` under the download budget for `session`/`tab`.
+///
+/// `limit` is the per-command value; `None` falls back to the session's
+/// `--max-download-bytes`. Without either, `fut` runs untouched. Lookup
+/// failures (unknown session/tab) also run `fut` directly so the command
+/// reports its own error.
+pub async fn guard<F>(
+    registry: &SharedRegistry,
+    session: &str,
+    tab: &str,
+    limit: Option<u64>,
+    fut: F,
+) -> ActionResult
+where
+    F: Future<Output = ActionResult>,
+{
+    let limit = match limit {
+        Some(l) => Some(l),
+        None => session_limit(registry, session).await,
+    };
+    let Some(limit) = limit else {
+        return fut.await;
+    };
+    if limit == 0 {
+        return ActionResult::fatal(
+            "INVALID_ARGUMENT",
+            "--max-download-bytes must be greater than 0",
+        );
+    }
+    let Ok((cdp, target_id)) = get_cdp_and_target(registry, session, tab).await else {
+        return fut.await;
+    };
+    guard_target(&cdp, &target_id, limit, fut).await
+}
+
+/// The session's `--max-download-bytes`, if it set one.
+pub async fn session_limit(registry: &SharedRegistry, session: &str) -> Option<u64> {
+    registry
+        .lock()
+        .await
+        .get(session)
+        .and_then(|e| e.max_download_bytes)
+}
+
+/// URL to create a new tab with. Under a budget the tab starts blank, so its
+/// first load is only requested once it is attached and can be counted.
+pub fn initial_url(limit: Option<u64>, url: &str) -> &str {
+    if limit.is_some() { "about:blank" } else { url }
+}
+
+/// Load `url` in a tab created with [`initial_url`], under `limit`.
+pub async fn load(cdp: &CdpSession, target_id: &str, url: &str, limit: u64) -> ActionResult {
+    let navigate = async {
+        match cdp
+            .execute_on_tab(target_id, "Page.navigate", json!({ "url": url }))
+            .await
+        {
+            Ok(_) => ActionResult::ok(json!({})),
+            Err(e) => cdp_error_to_result(e, "NAVIGATION_FAILED"),
+        }
+    };
+    guard_target(cdp, target_id, limit, navigate).await
+}
+
+/// Count the bytes one tab pulls while `fut` runs. A page load the command
+/// starts (a reload, a click on a link, a new tab) keeps counting until the
+/// tab stops loading, bounded by the navigation timeout.
+async fn guard_target<F>(cdp: &CdpSession, target_id: &str, limit: u64, fut: F) -> ActionResult
+where
+    F: Future<Output = ActionResult>,
+{
+    let Some(cdp_session_id) = cdp.get_cdp_session_id(target_id).await else {
+        return fut.await;
+    };
+
+    // Subscribe before the command starts so its first response is counted.
+    let mut data_rx = cdp
+        .subscribe_events(&cdp_session_id, "Network.dataReceived")
+        .await;
+    let mut done_rx = cdp
+        .subscribe_events(&cdp_session_id, "Network.loadingFinished")
+        .await;
+    let mut sent_rx = cdp
+        .subscribe_events(&cdp_session_id, "Network.requestWillBeSent")
+        .await;
+    let mut stopped_rx = cdp
+        .subscribe_events(&cdp_session_id, "Page.frameStoppedLoading")
+        .await;
+    // Page events are only sent once the domain is on; enabling is idempotent.
+    let _ = cdp
+        .execute_on_tab(target_id, "Page.enable", json!({}))
+        .await;
+    let (exceeded_tx, mut exceeded_rx) = oneshot::channel::<u64>();
+    let (total_tx, total_rx) = tokio::sync::watch::channel(0u64);
+    let (loading_tx, mut loading_rx) = tokio::sync::watch::channel(false);
+    let frame_id = target_id.to_string();
+    let counter = tokio::spawn(async move {
+        let mut bytes = ByteCounter::default();
+        let mut exceeded_tx = Some(exceeded_tx);
+        loop {
+            let event = tokio::select! {
+                Some(ev) = data_rx.recv() => ev,
+                Some(ev) = done_rx.recv() => ev,
+                Some(ev) = sent_rx.recv() => {
+                    if starts_page_load(&ev["params"], &frame_id) {
+                        let _ = loading_tx.send(true);
+                    }
+                    continue;
+                }
+                Some(ev) = stopped_rx.recv() => {
+                    if ev["params"]["frameId"].as_str() == Some(frame_id.as_str()) {
+                        let _ = loading_tx.send(false);
+                    }
+                    continue;
+                }
+                else => break,
+            };
+            let method = event["method"].as_str().unwrap_or_default();
+            let total = bytes.observe(method, &event["params"]);
+            let _ = total_tx.send(total);
+            if total > limit
+                && let Some(tx) = exceeded_tx.take()
+            {
+                let _ = tx.send(total);
+            }
+        }
+    });
+
+    let mut outcome = tokio::select! {
+        result = fut => Ok(result),
+        Ok(used) = &mut exceeded_rx => Err(used),
+    };
+    if matches!(outcome, Ok(ActionResult::Ok { .. })) && *loading_rx.borrow() {
+        let timeout = Duration::from_millis(crate::config::navigation_timeout_ms());
+        tokio::select! {
+            Ok(used) = &mut exceeded_rx => outcome = Err(used),
+            _ = loading_rx.wait_for(|loading| !loading) => {}
+            _ = tokio::time::sleep(timeout) => {}
+        }
+    }
+    counter.abort();
+
+    match outcome {
+        Ok(mut result) => {
+            let used = *total_rx.borrow();
+            if let ActionResult::Ok { data } = &mut result
+                && data.is_object()
+            {
+                data["download"] = json!({ "bytes": used, "limit": limit });
+            }
+            result
+        }
+        Err(used) => {
+            // Dropping `fut` abandons the command; stop the page too so the
+            // tab does not keep downloading after we return.
+            let _ = cdp
+                .execute_on_tab(target_id, "Page.stopLoading", json!({}))
+                .await;
+            ActionResult::fatal_with_hint(
+                DOWNLOAD_BUDGET_EXCEEDED,
+                format!("page downloaded {used} bytes, over the budget of {limit} bytes"),
+                "raise --max-download-bytes, or start the session with --adblock to skip heavy third-party resources",
+            )
+        }
+    }
+}
+
+/// Whether a `Network.requestWillBeSent` is the tab's own next page: a
+/// document request for the main frame that starts a new loader.
+fn starts_page_load(params: &Value, frame_id: &str) -> bool {
+    params["type"].as_str() == Some("Document")
+        && params["frameId"].as_str() == Some(frame_id)
+        && params["requestId"] == params["loaderId"]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn counter_takes_larger_of_chunks_and_final_total() {
+        let mut c = ByteCounter::default();
+        c.observe(
+            "Network.dataReceived",
+            &json!({ "requestId": "a", "encodedDataLength": 100 }),
+        );
+        c.observe(
+            "Network.dataReceived",
+            &json!({ "requestId": "a", "encodedDataLength": 50 }),
+        );
+        // Final total below the chunk sum must not shrink the count.
+        assert_eq!(
+            c.observe(
+                "Network.loadingFinished",
+                &json!({ "requestId": "a", "encodedDataLength": 120 }),
+            ),
+            150
+        );
+        // Chunks reported as 0 are covered by loadingFinished.
+        c.observe(
+            "Network.dataReceived",
+            &json!({ "requestId": "b", "encodedDataLength": 0 }),
+        );
+        assert_eq!(
+            c.observe(
+                "Network.loadingFinished",
+                &json!({ "requestId": "b", "encodedDataLength": 1000.0 }),
+            ),
+            1150
+        );
+    }
+
+    #[test]
+    fn only_a_main_frame_document_starts_a_page_load() {
+        let sent = |ty: &str, frame: &str, loader: &str| json!({ "type": ty, "frameId": frame, "requestId": "r1", "loaderId": loader });
+        assert!(starts_page_load(&sent("Document", "T1", "r1"), "T1"));
+        // An iframe's document, a subresource, a request within the current load.
+        assert!(!starts_page_load(&sent("Document", "F2", "r1"), "T1"));
+        assert!(!starts_page_load(&sent("Script", "T1", "r1"), "T1"));
+        assert!(!starts_page_load(&sent("Document", "T1", "l9"), "T1"));
+    }
+}
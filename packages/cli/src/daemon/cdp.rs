@@ -106,30 +106,6 @@ pub async fn cdp_navigate(ws_url: &str, url: &str) -> Result<(), CliError> {
     Err(CliError::CdpError("no response from CDP".to_string()))
 }
 
-/// Get accessibility tree via CDP.
-pub async fn cdp_get_ax_tree(ws_url: &str) -> Result<String, CliError> {
-    let (mut ws, _) = connect_async(ws_url)
-        .await
-        .map_err(|e| CliError::CdpConnectionFailed(e.to_string()))?;
-
-    let msg = json!({ "id": 1, "method": "Accessibility.getFullAXTree", "params": {} });
-    ws.send(ws_text(msg.to_string()))
-        .await
-        .map_err(|e| CliError::CdpError(e.to_string()))?;
-
-    while let Some(raw) = ws.next().await {
-        let raw = raw.map_err(|e| CliError::CdpError(e.to_string()))?;
-        if let Some(text) = msg_to_string(&raw) {
-            let resp: serde_json::Value = serde_json::from_str(&text).unwrap_or_default();
-            if resp.get("id").and_then(|v| v.as_u64()) == Some(1) {
-                let _ = ws.close(None).await;
-                return Ok(text);
-            }
-        }
-    }
-    Err(CliError::CdpError("no response".to_string()))
-}
-
 /// Ensure a URL has a scheme prefix. Rejects dangerous protocols.
 pub fn ensure_scheme(url: &str) -> Result<String, crate::error::CliError> {
     // Block dangerous protocols (L3 CDP security level, case-insensitive)
@@ -3,6 +3,10 @@
 //! One browser-level WebSocket connection per session. Commands target specific
 //! tabs via CDP flat sessions (Target.attachToTarget + sessionId). Concurrent
 //! requests are multiplexed using incrementing message IDs.
+//!
+//! Browser-wide domains (Target, Browser, browser contexts, permissions) go
+//! over the same connection without a sessionId: `execute_browser` and
+//! `subscribe_browser_events`.
 
 use std::collections::{HashMap, HashSet, VecDeque};
 use std::sync::Arc;
@@ -54,6 +58,7 @@ pub struct TrackedRequest {
 }
 
 type TabNetRequests = Arc<Mutex<HashMap<String, VecDeque<TrackedRequest>>>>;
+type TabDocuments = Arc<Mutex<HashMap<String, super::documents::DocumentLog>>>;
 
 // ─── HAR recording ────────────────────────────────────────────────────────────
 
@@ -688,6 +693,9 @@ pub struct CdpSession {
     /// Per-tab ring buffer of tracked network requests, keyed by CDP session ID.
     /// Populated by reader_loop from Network events; capacity capped at MAX_TRACKED_REQUESTS.
     tab_net_requests: TabNetRequests,
+    /// Per-tab document responses (status, headers, redirects) for
+    /// `browser response`, keyed by CDP session ID. Fed by reader_loop.
+    tab_documents: TabDocuments,
     /// `true` when this session speaks the extension-bridge protocol (0.3.0+).
     /// Flipped by `register_extension_tab`. In extension mode every per-tab
     /// command injects a root-level `tabId` instead of a CDP `sessionId`, and
@@ -697,6 +705,30 @@ pub struct CdpSession {
     /// Per-tab HAR recorders, keyed by CDP flat-session ID.
     /// Present in the map only while `har_start` is active for that tab.
     tab_har_recorders: TabHarRecorders,
+    /// Filter list enforced on every tab attached after `set_adblock`, for
+    /// sessions started with `--adblock`.
+    adblock: Arc<std::sync::RwLock<Option<Arc<super::adblock::Adblock>>>>,
+    /// Per-tab `browser network route` tables, keyed by CDP flat-session ID.
+    /// Created on first use and shared with the tab's request interceptor.
+    tab_routes: Arc<Mutex<HashMap<String, super::routes::SharedRoutes>>>,
+    /// Per-tab `browser record` recordings, keyed by CDP flat-session ID.
+    /// Present only between `record start` and `record stop`.
+    tab_flow_recorders: Arc<Mutex<HashMap<String, super::flow_recording::SharedFlowRecorder>>>,
+    /// Per-tab `browser record-video` screencasts, keyed by CDP flat-session
+    /// ID. Present only between `record-video start` and `record-video stop`.
+    tab_screencasts: Arc<Mutex<HashMap<String, super::screencast::SharedScreencast>>>,
+    /// Download log fed by `Browser.download*` events, once
+    /// `downloads::install` has turned them on.
+    downloads: Arc<std::sync::RwLock<Option<super::downloads::SharedDownloads>>>,
+    /// Device preset applied to every tab attached after `set_emulation`,
+    /// for `--device` sessions and `browser emulate`.
+    emulation: Arc<std::sync::RwLock<Option<super::emulation::Emulation>>>,
+    /// Network conditions applied to every tab attached after `set_network`,
+    /// for `browser emulate network`.
+    network: Arc<std::sync::RwLock<Option<super::emulation::Network>>>,
+    /// The browser-wide `browser trace` in progress, between `trace start`
+    /// and `trace stop`.
+    trace: Arc<std::sync::RwLock<Option<super::trace::Trace>>>,
 }
 
 impl CdpSession {
@@ -751,6 +783,7 @@ impl CdpSession {
         let tab_sessions: Arc<Mutex<HashMap<String, String>>> =
             Arc::new(Mutex::new(HashMap::new()));
         let tab_net_requests: TabNetRequests = Arc::new(Mutex::new(HashMap::new()));
+        let tab_documents: TabDocuments = Arc::new(Mutex::new(HashMap::new()));
         let is_extension_bridge = Arc::new(std::sync::atomic::AtomicBool::new(false));
         let tab_har_recorders: TabHarRecorders = Arc::new(Mutex::new(HashMap::new()));
 
@@ -764,6 +797,7 @@ impl CdpSession {
             pending_iframe_enables.clone(),
             tab_sessions.clone(),
             tab_net_requests.clone(),
+            tab_documents.clone(),
             max_tracked_requests,
             is_extension_bridge.clone(),
             tab_har_recorders.clone(),
@@ -783,11 +817,151 @@ impl CdpSession {
             iframe_sessions,
             pending_iframe_enables,
             tab_net_requests,
+            tab_documents,
             is_extension_bridge,
             tab_har_recorders,
+            adblock: Arc::new(std::sync::RwLock::new(None)),
+            tab_routes: Arc::new(Mutex::new(HashMap::new())),
+            tab_flow_recorders: Arc::new(Mutex::new(HashMap::new())),
+            tab_screencasts: Arc::new(Mutex::new(HashMap::new())),
+            downloads: Arc::new(std::sync::RwLock::new(None)),
+            emulation: Arc::new(std::sync::RwLock::new(None)),
+            network: Arc::new(std::sync::RwLock::new(None)),
+            trace: Arc::new(std::sync::RwLock::new(None)),
         })
     }
 
+    /// Whether the WebSocket reader has stopped: the peer dropped the
+    /// connection or `close()` ran. A dead connection never recovers; see
+    /// [`get_cdp_and_target`] for the reconnect.
+    pub async fn is_disconnected(&self) -> bool {
+        match self.reader_handle.lock().await.as_ref() {
+            Some(handle) => handle.is_finished(),
+            None => true,
+        }
+    }
+
+    /// Whether two handles share one underlying connection.
+    pub fn same_connection(&self, other: &CdpSession) -> bool {
+        Arc::ptr_eq(&self.pending, &other.pending)
+    }
+
+    /// Enforce `adblock` on tabs attached from now on. Call before the first
+    /// `attach()` so the initial tab is covered too.
+    pub fn set_adblock(&self, adblock: Arc<super::adblock::Adblock>) {
+        *self.adblock.write().unwrap_or_else(|e| e.into_inner()) = Some(adblock);
+    }
+
+    pub fn set_downloads(&self, downloads: super::downloads::SharedDownloads) {
+        *self.downloads.write().unwrap_or_else(|e| e.into_inner()) = Some(downloads);
+    }
+
+    pub fn downloads(&self) -> Option<super::downloads::SharedDownloads> {
+        self.downloads
+            .read()
+            .unwrap_or_else(|e| e.into_inner())
+            .clone()
+    }
+
+    /// Emulate `emulation` on tabs attached from now on (`None` stops).
+    /// Tabs already attached are left to the caller.
+    pub fn set_emulation(&self, emulation: Option<super::emulation::Emulation>) {
+        *self.emulation.write().unwrap_or_else(|e| e.into_inner()) = emulation;
+    }
+
+    pub fn emulation(&self) -> Option<super::emulation::Emulation> {
+        *self.emulation.read().unwrap_or_else(|e| e.into_inner())
+    }
+
+    /// Throttle tabs attached from now on to `network` (`None` stops).
+    /// Tabs already attached are left to the caller.
+    pub fn set_network(&self, network: Option<super::emulation::Network>) {
+        *self.network.write().unwrap_or_else(|e| e.into_inner()) = network;
+    }
+
+    pub fn network(&self) -> Option<super::emulation::Network> {
+        self.network
+            .read()
+            .unwrap_or_else(|e| e.into_inner())
+            .clone()
+    }
+
+    /// Record that a trace is running; `false` if one already is.
+    pub fn start_trace(&self, trace: super::trace::Trace) -> bool {
+        let mut current = self.trace.write().unwrap_or_else(|e| e.into_inner());
+        if current.is_some() {
+            return false;
+        }
+        *current = Some(trace);
+        true
+    }
+
+    /// Remove and return the running trace.
+    pub fn take_trace(&self) -> Option<super::trace::Trace> {
+        self.trace.write().unwrap_or_else(|e| e.into_inner()).take()
+    }
+
+    pub fn adblock(&self) -> Option<Arc<super::adblock::Adblock>> {
+        self.adblock
+            .read()
+            .unwrap_or_else(|e| e.into_inner())
+            .clone()
+    }
+
+    /// The route table for one tab, created empty on first use.
+    pub async fn tab_routes(&self, cdp_session_id: &str) -> super::routes::SharedRoutes {
+        self.tab_routes
+            .lock()
+            .await
+            .entry(cdp_session_id.to_string())
+            .or_default()
+            .clone()
+    }
+
+    /// Register a tab's recording; `false` if one is already running.
+    pub async fn start_flow_recording(
+        &self,
+        cdp_session_id: &str,
+        recorder: super::flow_recording::SharedFlowRecorder,
+    ) -> bool {
+        let mut recorders = self.tab_flow_recorders.lock().await;
+        if recorders.contains_key(cdp_session_id) {
+            return false;
+        }
+        recorders.insert(cdp_session_id.to_string(), recorder);
+        true
+    }
+
+    /// Remove and return a tab's recording.
+    pub async fn take_flow_recording(
+        &self,
+        cdp_session_id: &str,
+    ) -> Option<super::flow_recording::SharedFlowRecorder> {
+        self.tab_flow_recorders.lock().await.remove(cdp_session_id)
+    }
+
+    /// Register a tab's video recording; `false` if one is already running.
+    pub async fn start_screencast(
+        &self,
+        cdp_session_id: &str,
+        recording: super::screencast::SharedScreencast,
+    ) -> bool {
+        let mut recordings = self.tab_screencasts.lock().await;
+        if recordings.contains_key(cdp_session_id) {
+            return false;
+        }
+        recordings.insert(cdp_session_id.to_string(), recording);
+        true
+    }
+
+    /// Remove and return a tab's video recording.
+    pub async fn take_screencast(
+        &self,
+        cdp_session_id: &str,
+    ) -> Option<super::screencast::SharedScreencast> {
+        self.tab_screencasts.lock().await.remove(cdp_session_id)
+    }
+
     /// Attach to a CDP target (tab) using flat session mode.
     ///
     /// Sends `Target.attachToTarget` with `flatten: true` and stores the
@@ -809,6 +983,7 @@ impl CdpSession {
                 // Tab already attached but stealth not yet applied.
                 // Apply stealth to this existing session.
                 self.apply_stealth(&existing, user_agent).await;
+                self.apply_emulation(target_id).await;
             }
             return Ok(existing);
         }
@@ -874,6 +1049,26 @@ impl CdpSession {
 
         // Apply stealth when user_agent is provided (stealth mode enabled).
         self.apply_stealth(&session_id, user_agent).await;
+        // After stealth, so a device user agent wins over the stealth one.
+        self.apply_emulation(target_id).await;
+
+        // Best-effort like auto-attach: a tab without interception still works,
+        // it just loads everything. Domain guardrails need it to stop pages a
+        // redirect or click leads to; the router re-checks where tabs land.
+        let adblock = self.adblock();
+        let guarded = super::guardrails::DomainPolicy::current().map_or(true, |p| !p.is_empty());
+        if (adblock.is_some() || guarded)
+            && let Err(e) = super::routes::install(
+                self,
+                target_id,
+                &session_id,
+                self.tab_routes(&session_id).await,
+                adblock,
+            )
+            .await
+        {
+            warn!("failed to enable request interception on {target_id}: {e}");
+        }
 
         Ok(session_id)
     }
@@ -921,6 +1116,24 @@ impl CdpSession {
         // (real users have 1366x768, 2560x1440, 3440x1440, etc.).
     }
 
+    /// Best-effort: emulate the session's device preset and network
+    /// conditions, if any, on a tab.
+    async fn apply_emulation(&self, target_id: &str) {
+        if let Some(emulation) = self.emulation()
+            && let Err(e) = super::emulation::apply(self, target_id, &emulation).await
+        {
+            warn!(
+                "emulation: failed to emulate {} on {target_id}: {e}",
+                emulation.label()
+            );
+        }
+        if let Some(network) = self.network()
+            && let Err(e) = super::emulation::apply_network(self, target_id, Some(&network)).await
+        {
+            warn!("emulation: failed to throttle {target_id}: {e}");
+        }
+    }
+
     /// Register a tab for extension mode (protocol 0.3.0+).
     ///
     /// Flips this session into "extension bridge" mode (one-way; local/cloud
@@ -978,6 +1191,7 @@ impl CdpSession {
                 "register_extension_tab: Network.enable failed for tab {native_id}: {e}"
             );
         }
+        self.apply_emulation(native_id).await;
     }
 
     /// Detach from a CDP target (tab).
@@ -1001,10 +1215,18 @@ impl CdpSession {
 
         // Clean up tracked network requests for this session.
         self.tab_net_requests.lock().await.remove(&session_id);
+        self.tab_documents.lock().await.remove(&session_id);
 
         // Clean up any active HAR recorder for this session.
         self.tab_har_recorders.lock().await.remove(&session_id);
 
+        // Drop the tab's routes; the interceptor ends with its subscription.
+        self.tab_routes.lock().await.remove(&session_id);
+
+        // Abandon any unfinished `browser record` or `record-video` for the tab.
+        self.tab_flow_recorders.lock().await.remove(&session_id);
+        self.tab_screencasts.lock().await.remove(&session_id);
+
         // Clean up all event subscriptions for this session.
         self.unsubscribe_all(&session_id).await;
 
@@ -1152,6 +1374,13 @@ impl CdpSession {
         self.execute(method, params, None).await
     }
 
+    /// Subscribe to a browser-level CDP event (no sessionId), such as
+    /// `Target.targetCreated` or `Browser.downloadProgress`. Same delivery
+    /// rules as [`Self::subscribe_events`].
+    pub async fn subscribe_browser_events(&self, method: &str) -> mpsc::Receiver<Value> {
+        self.subscribe_events("", method).await
+    }
+
     /// Return the CDP flat-session ID for a target, or `None` if not attached.
     pub async fn get_cdp_session_id(&self, target_id: &str) -> Option<String> {
         self.tab_sessions.lock().await.get(target_id).cloned()
@@ -1223,6 +1452,17 @@ impl CdpSession {
             .and_then(|requests| tracked_request_detail(requests, request_id))
     }
 
+    /// The latest document response recorded for `frame_id` in a tab's CDP
+    /// session, as `browser response` reports it.
+    pub async fn document_response(&self, cdp_session_id: &str, frame_id: &str) -> Option<Value> {
+        self.tab_documents
+            .lock()
+            .await
+            .get(cdp_session_id)
+            .and_then(|log| log.get(frame_id))
+            .map(|doc| doc.to_json())
+    }
+
     /// Subscribe to a CDP event for a specific flat-session.
     ///
     /// Returns a channel receiver that yields each matching event message.
@@ -1488,6 +1728,7 @@ impl CdpSession {
         pending_iframe_enables: PendingIframeEnables,
         _tab_sessions: Arc<Mutex<HashMap<String, String>>>,
         tab_net_requests: TabNetRequests,
+        tab_documents: TabDocuments,
         max_tracked_requests: usize,
         is_extension_bridge: Arc<std::sync::atomic::AtomicBool>,
         tab_har_recorders: TabHarRecorders,
@@ -1637,6 +1878,22 @@ impl CdpSession {
                         }
                         _ => {}
                     }
+                    if let Some(params) = resp.get("params")
+                        && matches!(
+                            method,
+                            "Network.requestWillBeSent"
+                                | "Network.responseReceived"
+                                | "Network.loadingFinished"
+                                | "Network.loadingFailed"
+                        )
+                    {
+                        tab_documents
+                            .lock()
+                            .await
+                            .entry(session_id.to_string())
+                            .or_default()
+                            .apply(method, params);
+                    }
 
                     // HAR recording: feed network events into any active recorder
                     // for this CDP session. Independent of the ring-buffer path above.
@@ -1860,10 +2117,32 @@ impl CdpSession {
 /// is Chrome's native CDP target ID needed for `execute_on_tab`.
 /// Returns `ActionResult` errors for SESSION_NOT_FOUND, TAB_NOT_FOUND,
 /// or missing CDP connection.
+///
+/// The daemon keeps one CDP connection per session and reuses it for every
+/// command. If that connection has died while the browser lives on, it is
+/// replaced here before the command runs (see [`reconnect_session`]).
 pub async fn get_cdp_and_target(
     registry: &crate::daemon::registry::SharedRegistry,
     session_id: &str,
     tab_id: &str,
+) -> Result<(CdpSession, String), crate::action_result::ActionResult> {
+    let (cdp, native_id) = lookup_cdp_and_target(registry, session_id, tab_id).await?;
+    if !cdp.is_disconnected().await {
+        return Ok((cdp, native_id));
+    }
+    // Boxed: the reconnect future is large, and inlining it into every
+    // command's future overflows the daemon's worker stack in debug builds.
+    match Box::pin(reconnect_session(registry, session_id, &cdp)).await {
+        Some(fresh) => Ok((fresh, native_id)),
+        // Nothing to redial; the command reports the closed connection.
+        None => Ok((cdp, native_id)),
+    }
+}
+
+async fn lookup_cdp_and_target(
+    registry: &crate::daemon::registry::SharedRegistry,
+    session_id: &str,
+    tab_id: &str,
 ) -> Result<(CdpSession, String), crate::action_result::ActionResult> {
     let reg = registry.lock().await;
     let entry = reg.get(session_id).ok_or_else(|| {
@@ -1892,6 +2171,124 @@ pub async fn get_cdp_and_target(
     Ok((cdp, native_id))
 }
 
+/// Replace a session's dead CDP connection and re-attach its tabs.
+///
+/// Local sessions rediscover the browser WebSocket URL from the debug port,
+/// so a URL that went stale with a browser-side restart is not redialled.
+/// Cloud sessions redial their endpoint with the launch headers. Extension
+/// sessions are left alone: the bridge owns that connection. Returns `None`
+/// when there is nothing to reconnect to.
+async fn reconnect_session(
+    registry: &crate::daemon::registry::SharedRegistry,
+    session_id: &str,
+    dead: &CdpSession,
+) -> Option<CdpSession> {
+    use crate::types::Mode;
+
+    let (mode, cdp_port, ws_url, headers, max_tracked_requests, user_agent, native_ids) = {
+        let reg = registry.lock().await;
+        let entry = reg.get(session_id)?;
+        // A session being closed dropped its connection on purpose.
+        if entry.status != crate::daemon::registry::SessionState::Running {
+            return None;
+        }
+        (
+            entry.mode,
+            entry.cdp_port,
+            match entry.mode {
+                Mode::Cloud => entry.cdp_endpoint.clone()?,
+                _ => entry.ws_url.clone(),
+            },
+            entry.headers.clone(),
+            entry.max_tracked_requests,
+            entry.stealth_ua.clone(),
+            entry
+                .tabs
+                .iter()
+                .map(|t| t.native_id.clone())
+                .collect::<Vec<_>>(),
+        )
+    };
+    let ws_url = match (mode, cdp_port) {
+        (Mode::Extension, _) => return None,
+        (Mode::Local, Some(port)) => crate::daemon::browser::probe_ws_url(port).await?,
+        _ => ws_url,
+    };
+
+    let fresh = match CdpSession::connect_with_config(&ws_url, &headers, max_tracked_requests).await
+    {
+        Ok(cdp) => cdp,
+        Err(e) => {
+            warn!("session {session_id}: CDP reconnect failed: {e}");
+            return None;
+        }
+    };
+    // Route tables are keyed by the tab's CDP session, which re-attaching
+    // replaces, so look them up by target now.
+    let mut routes = Vec::new();
+    for native_id in &native_ids {
+        if let Some(old_sid) = dead.get_cdp_session_id(native_id).await
+            && let Some(table) = dead.tab_routes.lock().await.get(&old_sid).cloned()
+        {
+            routes.push((native_id.clone(), table));
+        }
+    }
+    if let Some(adblock) = dead.adblock() {
+        fresh.set_adblock(adblock);
+    }
+    fresh.set_emulation(dead.emulation());
+    fresh.set_network(dead.network());
+    if let Some(downloads) = dead.downloads()
+        && let Err(e) = super::downloads::install(&fresh, downloads).await
+    {
+        warn!("session {session_id}: failed to re-enable download tracking: {e}");
+    }
+    for native_id in &native_ids {
+        if let Err(e) = fresh.attach(native_id, user_agent.as_deref()).await {
+            warn!("session {session_id}: failed to re-attach tab {native_id}: {e}");
+        }
+    }
+    for (native_id, old) in routes {
+        let Some(sid) = fresh.get_cdp_session_id(&native_id).await else {
+            continue;
+        };
+        let table = fresh.tab_routes(&sid).await;
+        {
+            let old = old.read().unwrap_or_else(|e| e.into_inner());
+            if old.routes().is_empty() {
+                continue;
+            }
+            table
+                .write()
+                .unwrap_or_else(|e| e.into_inner())
+                .restore(&old);
+        }
+        if let Err(e) =
+            super::routes::install(&fresh, &native_id, &sid, table, fresh.adblock()).await
+        {
+            warn!("session {session_id}: failed to re-install routes on tab {native_id}: {e}");
+        }
+    }
+
+    let mut reg = registry.lock().await;
+    let entry = reg.get_mut(session_id)?;
+    match &entry.cdp {
+        // A concurrent command won the race; keep its connection.
+        Some(current) if !current.same_connection(dead) => {
+            let current = current.clone();
+            drop(reg);
+            fresh.close().await;
+            Some(current)
+        }
+        _ => {
+            tracing::info!("session {session_id}: reconnected CDP to {ws_url}");
+            entry.ws_url = ws_url;
+            entry.cdp = Some(fresh.clone());
+            Some(fresh)
+        }
+    }
+}
+
 /// Convert a CliError from CDP operations into an ActionResult.
 /// For cloud sessions, connection drops are surfaced as CLOUD_CONNECTION_LOST.
 /// For local sessions, they use the default_code.
@@ -2002,6 +2399,178 @@ mod tests {
             .unwrap();
     }
 
+    #[tokio::test]
+    async fn get_cdp_and_target_reconnects_a_dropped_cloud_connection() {
+        use crate::daemon::registry::{SessionEntry, SessionState, new_shared_registry};
+        use crate::types::{Mode, SessionId};
+
+        let (url, mut conns) = mock_ws_server().await;
+        let dead = CdpSession::connect(&url).await.unwrap();
+        let (reader, writer) = conns.recv().await.unwrap();
+        drop((reader, writer));
+        for _ in 0..50 {
+            if dead.is_disconnected().await {
+                break;
+            }
+            tokio::time::sleep(std::time::Duration::from_millis(10)).await;
+        }
+        assert!(dead.is_disconnected().await);
+
+        let registry = new_shared_registry();
+        {
+            let mut entry = SessionEntry::starting(
+                SessionId::new("cloud-1").unwrap(),
+                Mode::Cloud,
+                true,
+                false,
+                "profile".to_string(),
+            );
+            entry.status = SessionState::Running;
+            entry.cdp_endpoint = Some(url.clone());
+            entry.push_tab("TARGET-1".into(), "about:blank".into(), String::new());
+            entry.cdp = Some(dead.clone());
+            registry.lock().await.insert(entry);
+        }
+
+        // Answer everything the re-attach sends on the new connection.
+        tokio::spawn(async move {
+            let (mut reader, mut writer) = conns.recv().await.unwrap();
+            loop {
+                let msg = read_json(&mut reader).await;
+                let reply = if msg["method"] == "Target.attachToTarget" {
+                    json!({"id": msg["id"], "result": {"sessionId": "S-1"}})
+                } else {
+                    json!({"id": msg["id"], "result": {}})
+                };
+                send_json(&mut writer, reply).await;
+            }
+        });
+
+        let (cdp, target) = get_cdp_and_target(&registry, "cloud-1", "t1")
+            .await
+            .unwrap();
+        assert_eq!(target, "TARGET-1");
+        assert!(!cdp.same_connection(&dead));
+        assert!(!cdp.is_disconnected().await);
+        assert_eq!(
+            cdp.get_cdp_session_id("TARGET-1").await.as_deref(),
+            Some("S-1")
+        );
+        let reg = registry.lock().await;
+        assert!(
+            reg.get("cloud-1")
+                .unwrap()
+                .cdp
+                .as_ref()
+                .unwrap()
+                .same_connection(&cdp)
+        );
+    }
+
+    #[tokio::test]
+    async fn reconnect_reinstalls_network_routes_on_the_new_tab_session() {
+        use crate::daemon::registry::{SessionEntry, SessionState, new_shared_registry};
+        use crate::daemon::routes::RouteAction;
+        use crate::types::{Mode, SessionId};
+
+        let (url, mut conns) = mock_ws_server().await;
+        let dead = CdpSession::connect(&url).await.unwrap();
+        let (reader, writer) = conns.recv().await.unwrap();
+        // The dead connection had the tab attached as S-0 with one route.
+        dead.tab_sessions
+            .lock()
+            .await
+            .insert("TARGET-1".into(), "S-0".into());
+        dead.tab_routes("S-0")
+            .await
+            .write()
+            .unwrap()
+            .add("*/api/*".into(), RouteAction::Block);
+        drop((reader, writer));
+        for _ in 0..50 {
+            if dead.is_disconnected().await {
+                break;
+            }
+            tokio::time::sleep(std::time::Duration::from_millis(10)).await;
+        }
+
+        let registry = new_shared_registry();
+        {
+            let mut entry = SessionEntry::starting(
+                SessionId::new("cloud-1").unwrap(),
+                Mode::Cloud,
+                true,
+                false,
+                "profile".to_string(),
+            );
+            entry.status = SessionState::Running;
+            entry.cdp_endpoint = Some(url.clone());
+            entry.push_tab("TARGET-1".into(), "about:blank".into(), String::new());
+            entry.cdp = Some(dead.clone());
+            registry.lock().await.insert(entry);
+        }
+
+        // Answer everything on the new connection and report what was sent.
+        let (seen_tx, mut seen) = mpsc::unbounded_channel::<Value>();
+        let (pause_tx, mut pause_rx) = mpsc::unbounded_channel::<Value>();
+        tokio::spawn(async move {
+            let (mut reader, mut writer) = conns.recv().await.unwrap();
+            loop {
+                tokio::select! {
+                    msg = read_json(&mut reader) => {
+                        let reply = if msg["method"] == "Target.attachToTarget" {
+                            json!({"id": msg["id"], "result": {"sessionId": "S-1"}})
+                        } else {
+                            json!({"id": msg["id"], "result": {}})
+                        };
+                        let _ = seen_tx.send(msg);
+                        send_json(&mut writer, reply).await;
+                    }
+                    Some(event) = pause_rx.recv() => send_json(&mut writer, event).await,
+                }
+            }
+        });
+
+        let (cdp, _) = get_cdp_and_target(&registry, "cloud-1", "t1")
+            .await
+            .unwrap();
+        assert!(!cdp.same_connection(&dead));
+        assert_eq!(
+            cdp.tab_routes("S-1").await.read().unwrap().routes().len(),
+            1
+        );
+
+        async fn wait_for(seen: &mut mpsc::UnboundedReceiver<Value>, method: &str) -> Value {
+            loop {
+                let msg = tokio::time::timeout(std::time::Duration::from_secs(5), seen.recv())
+                    .await
+                    .unwrap_or_else(|_| panic!("{method} never sent"))
+                    .unwrap();
+                if msg["method"] == method {
+                    return msg;
+                }
+            }
+        }
+        let enable = wait_for(&mut seen, "Fetch.enable").await;
+        assert_eq!(enable["sessionId"], "S-1");
+
+        pause_tx
+            .send(json!({
+                "method": "Fetch.requestPaused",
+                "sessionId": "S-1",
+                "params": {
+                    "requestId": "R-1",
+                    "frameId": "TARGET-1",
+                    "resourceType": "XHR",
+                    "request": { "url": "https://example.com/api/items", "headers": {} }
+                }
+            }))
+            .unwrap();
+        let fail = wait_for(&mut seen, "Fetch.failRequest").await;
+        assert_eq!(fail["params"]["requestId"], "R-1");
+        assert_eq!(fail["sessionId"], "S-1");
+    }
+
     // ── 1. test_message_id_increment ─────────────────────────────────
 
     #[tokio::test]
@@ -3294,4 +3863,36 @@ mod tests {
         );
         assert_eq!(req.response_body.as_deref(), Some(r#"{"ok":true}"#));
     }
+
+    // ── 20. test_browser_events_reach_browser_subscribers ──────────
+
+    /// Events without a sessionId are browser-level: they go to
+    /// `subscribe_browser_events`, not to tab subscribers of the same method.
+    #[tokio::test]
+    async fn test_browser_events_reach_browser_subscribers() {
+        let (url, mut conns) = mock_ws_server().await;
+        let cdp = CdpSession::connect(&url).await.unwrap();
+        let (_reader, mut writer) = conns.recv().await.unwrap();
+
+        let mut browser_rx = cdp.subscribe_browser_events("Target.targetCreated").await;
+        let mut tab_rx = cdp
+            .subscribe_events("SESS_TAB", "Target.targetCreated")
+            .await;
+
+        send_json(
+            &mut writer,
+            json!({
+                "method": "Target.targetCreated",
+                "params": { "targetInfo": { "targetId": "T_NEW", "type": "page" } }
+            }),
+        )
+        .await;
+
+        let event = tokio::time::timeout(std::time::Duration::from_secs(2), browser_rx.recv())
+            .await
+            .expect("browser event delivered")
+            .unwrap();
+        assert_eq!(event["params"]["targetInfo"]["targetId"], "T_NEW");
+        assert!(tab_rx.try_recv().is_err());
+    }
 }
@@ -0,0 +1,127 @@
+//! Detect Chrome DevTools open in a session's browser.
+//!
+//! DevTools is a second debugger client on the page. It can pause scripts,
+//! hold focus and swallow or reorder the input events we dispatch over CDP,
+//! which shows up as clicks and typing that silently do nothing. Its
+//! frontend is listed by `Target.getTargets` as a `devtools://` target, so
+//! after an input command the daemon checks for one and tells the user.
+
+use serde_json::{Value, json};
+use tracing::warn;
+
+use crate::action::Action;
+use crate::action_result::ActionResult;
+use crate::daemon::registry::SharedRegistry;
+
+const WARNING: &str = "DEVTOOLS_OPEN: Chrome DevTools is open in this browser; it can swallow or reorder dispatched input";
+const HINT: &str = "Chrome DevTools is open in this browser and may be interfering; close DevTools (or undock it from this tab) and retry";
+
+/// Number of DevTools frontends in a `Target.getTargets` response.
+fn devtools_targets(resp: &Value) -> usize {
+    resp.pointer("/result/targetInfos")
+        .and_then(|v| v.as_array())
+        .into_iter()
+        .flatten()
+        .filter(|t| {
+            t.get("url")
+                .and_then(|v| v.as_str())
+                .is_some_and(|u| u.starts_with("devtools://"))
+        })
+        .count()
+}
+
+/// Commands whose effect depends on CDP input reaching the page.
+fn dispatches_input(action: &Action) -> bool {
+    matches!(
+        action,
+        Action::Click(_)
+            | Action::BatchClick(_)
+            | Action::Hover(_)
+            | Action::Menu(_)
+            | Action::Focus(_)
+            | Action::TabTo(_)
+            | Action::Press(_)
+            | Action::Type(_)
+            | Action::Fill(_)
+            | Action::Select(_)
+            | Action::CanvasClickColor(_)
+            | Action::CanvasDraw(_)
+            | Action::Date(_)
+            | Action::Slide(_)
+            | Action::Drag(_)
+            | Action::Upload(_)
+            | Action::MouseMove(_)
+            | Action::Scroll(_)
+            | Action::Tap(_)
+            | Action::Swipe(_)
+            | Action::Pinch(_)
+    )
+}
+
+/// After an input command, warn (on success) or extend the hint (on
+/// failure) when DevTools is open in the session's browser.
+pub async fn annotate(action: &Action, result: &mut ActionResult, registry: &SharedRegistry) {
+    if !dispatches_input(action) {
+        return;
+    }
+    let (Some(session_id), _) = crate::audit::action_address(action) else {
+        return;
+    };
+    let Some(cdp) = registry
+        .lock()
+        .await
+        .get(&session_id)
+        .and_then(|e| e.cdp.clone())
+    else {
+        return;
+    };
+    // The extension bridge may not forward Target.getTargets; no answer
+    // means nothing to report.
+    let Ok(resp) = cdp.execute_browser("Target.getTargets", json!({})).await else {
+        return;
+    };
+    if devtools_targets(&resp) == 0 {
+        return;
+    }
+    warn!(
+        "{session_id}: Chrome DevTools is open during {}",
+        action.command_name()
+    );
+    match result {
+        ActionResult::Ok { data } => {
+            if let Some(obj) = data.as_object_mut() {
+                let warnings = obj.entry("__warnings").or_insert_with(|| json!([]));
+                if let Some(list) = warnings.as_array_mut() {
+                    list.push(json!(WARNING));
+                }
+            }
+        }
+        ActionResult::Fatal { code, hint, .. }
+            if !matches!(code.as_str(), "SESSION_NOT_FOUND" | "TAB_NOT_FOUND") =>
+        {
+            *hint = if hint.is_empty() {
+                HINT.to_string()
+            } else {
+                format!("{hint}; {HINT}")
+            };
+        }
+        _ => {}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn devtools_targets_counts_frontend_urls() {
+        let resp = json!({ "result": { "targetInfos": [
+            { "targetId": "A", "type": "page", "url": "https://example.com/" },
+            { "targetId": "B", "type": "other",
+              "url": "devtools://devtools/bundled/devtools_app.html?remoteBase=x" },
+            { "targetId": "C", "type": "service_worker", "url": "https://example.com/sw.js" },
+        ] } });
+        assert_eq!(devtools_targets(&resp), 1);
+        assert_eq!(devtools_targets(&json!({ "result": {} })), 0);
+    }
+}
@@ -0,0 +1,289 @@
+//! Document responses for `browser response`.
+//!
+//! `reader_loop` folds every tab's `Network.*` events for `Document`
+//! requests into a [`DocumentLog`], keeping the latest navigation of each
+//! frame: final status and headers, the redirects that led there, and timing.
+//! The command then reads back the entry for the tab's main frame.
+
+use std::collections::{BTreeMap, HashMap};
+
+use serde_json::{Value, json};
+
+/// Frames remembered per tab; the oldest navigation is dropped past this.
+const MAX_FRAMES: usize = 32;
+
+/// One hop of a redirect chain.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Redirect {
+    pub url: String,
+    pub status: u16,
+    pub location: Option<String>,
+}
+
+/// The latest document request of one frame.
+#[derive(Debug, Clone)]
+pub struct DocumentResponse {
+    pub request_id: String,
+    pub url: String,
+    pub method: String,
+    pub redirects: Vec<Redirect>,
+    pub status: Option<u16>,
+    pub status_text: String,
+    pub mime_type: Option<String>,
+    /// Lower-cased header names.
+    pub headers: BTreeMap<String, String>,
+    pub remote_address: Option<String>,
+    pub protocol: Option<String>,
+    pub from_cache: bool,
+    /// `Network.loadingFailed` error text (e.g. `net::ERR_NAME_NOT_RESOLVED`).
+    pub error: Option<String>,
+    /// CDP monotonic timestamps, in seconds.
+    started: f64,
+    responded: Option<f64>,
+    finished: Option<f64>,
+}
+
+impl DocumentResponse {
+    pub fn to_json(&self) -> Value {
+        let ms = |t: Option<f64>| t.map(|t| ((t - self.started) * 1000.0).round().max(0.0) as u64);
+        json!({
+            "url": self.url,
+            "method": self.method,
+            "status": self.status,
+            "status_text": self.status_text,
+            "mime_type": self.mime_type,
+            "headers": self.headers,
+            "redirects": self.redirects.iter().map(|r| json!({
+                "url": r.url,
+                "status": r.status,
+                "location": r.location,
+            })).collect::<Vec<_>>(),
+            "remote_address": self.remote_address,
+            "protocol": self.protocol,
+            "from_cache": self.from_cache,
+            "error": self.error,
+            "timing": {
+                "response_ms": ms(self.responded),
+                "total_ms": ms(self.finished),
+            },
+        })
+    }
+}
+
+fn headers_of(v: Option<&Value>) -> BTreeMap<String, String> {
+    v.and_then(|h| h.as_object())
+        .map(|obj| {
+            obj.iter()
+                .filter_map(|(k, v)| v.as_str().map(|s| (k.to_lowercase(), s.to_string())))
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// Per-tab document responses, keyed by frame ID.
+#[derive(Debug, Default)]
+pub struct DocumentLog {
+    frames: HashMap<String, DocumentResponse>,
+}
+
+impl DocumentLog {
+    pub fn get(&self, frame_id: &str) -> Option<&DocumentResponse> {
+        self.frames.get(frame_id)
+    }
+
+    /// Fold one `Network.*` event into the log. Non-document requests are
+    /// ignored.
+    pub fn apply(&mut self, method: &str, params: &Value) {
+        let s = |k: &str| params.get(k).and_then(|v| v.as_str()).unwrap_or("");
+        let request_id = s("requestId");
+        if request_id.is_empty() {
+            return;
+        }
+        match method {
+            "Network.requestWillBeSent" => {
+                if s("type") != "Document" || s("frameId").is_empty() {
+                    return;
+                }
+                let url = params
+                    .pointer("/request/url")
+                    .and_then(|v| v.as_str())
+                    .unwrap_or("")
+                    .to_string();
+                let method = params
+                    .pointer("/request/method")
+                    .and_then(|v| v.as_str())
+                    .unwrap_or("GET")
+                    .to_string();
+                let started = params
+                    .get("timestamp")
+                    .and_then(|v| v.as_f64())
+                    .unwrap_or(0.0);
+                let frame_id = s("frameId").to_string();
+
+                // A redirect reuses the request ID: record the hop and keep
+                // the original start time.
+                if let Some(redirect) = params.get("redirectResponse")
+                    && let Some(doc) = self.frames.get_mut(&frame_id)
+                    && doc.request_id == request_id
+                {
+                    doc.redirects.push(Redirect {
+                        url: std::mem::replace(&mut doc.url, url),
+                        status: redirect.get("status").and_then(|v| v.as_u64()).unwrap_or(0) as u16,
+                        location: headers_of(redirect.get("headers")).remove("location"),
+                    });
+                    doc.method = method;
+                    return;
+                }
+
+                if !self.frames.contains_key(&frame_id)
+                    && self.frames.len() >= MAX_FRAMES
+                    && let Some(oldest) = self
+                        .frames
+                        .iter()
+                        .min_by(|a, b| a.1.started.total_cmp(&b.1.started))
+                        .map(|(k, _)| k.clone())
+                {
+                    self.frames.remove(&oldest);
+                }
+                self.frames.insert(
+                    frame_id,
+                    DocumentResponse {
+                        request_id: request_id.to_string(),
+                        url,
+                        method,
+                        redirects: Vec::new(),
+                        status: None,
+                        status_text: String::new(),
+                        mime_type: None,
+                        headers: BTreeMap::new(),
+                        remote_address: None,
+                        protocol: None,
+                        from_cache: false,
+                        error: None,
+                        started,
+                        responded: None,
+                        finished: None,
+                    },
+                );
+            }
+            "Network.responseReceived" => {
+                let Some(doc) = self.by_request(request_id) else {
+                    return;
+                };
+                let r = &params["response"];
+                let rs = |k: &str| r.get(k).and_then(|v| v.as_str()).map(String::from);
+                doc.url = rs("url").unwrap_or_else(|| doc.url.clone());
+                doc.status = r.get("status").and_then(|v| v.as_u64()).map(|v| v as u16);
+                doc.status_text = rs("statusText").unwrap_or_default();
+                doc.mime_type = rs("mimeType");
+                doc.headers = headers_of(r.get("headers"));
+                doc.remote_address = rs("remoteIPAddress").map(|ip| {
+                    match r.get("remotePort").and_then(|v| v.as_u64()) {
+                        Some(port) => format!("{ip}:{port}"),
+                        None => ip,
+                    }
+                });
+                doc.protocol = rs("protocol");
+                doc.from_cache = ["fromDiskCache", "fromPrefetchCache", "fromServiceWorker"]
+                    .iter()
+                    .any(|k| r.get(*k).and_then(|v| v.as_bool()).unwrap_or(false));
+                doc.responded = params.get("timestamp").and_then(|v| v.as_f64());
+            }
+            "Network.loadingFinished" | "Network.loadingFailed" => {
+                let error = params
+                    .get("errorText")
+                    .and_then(|v| v.as_str())
+                    .map(String::from);
+                let Some(doc) = self.by_request(request_id) else {
+                    return;
+                };
+                doc.finished = params.get("timestamp").and_then(|v| v.as_f64());
+                if method == "Network.loadingFailed" {
+                    doc.error = error;
+                }
+            }
+            _ => {}
+        }
+    }
+
+    fn by_request(&mut self, request_id: &str) -> Option<&mut DocumentResponse> {
+        self.frames
+            .values_mut()
+            .find(|d| d.request_id == request_id)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn apply_follows_redirects_to_the_final_response() {
+        let mut log = DocumentLog::default();
+        log.apply(
+            "Network.requestWillBeSent",
+            &json!({ "requestId": "L1", "frameId": "F", "type": "Document", "timestamp": 10.0,
+                     "request": { "url": "https://x.test/account", "method": "GET" } }),
+        );
+        log.apply(
+            "Network.requestWillBeSent",
+            &json!({ "requestId": "L1", "frameId": "F", "type": "Document", "timestamp": 10.1,
+                     "request": { "url": "https://x.test/login", "method": "GET" },
+                     "redirectResponse": { "status": 302, "headers": { "Location": "/login" } } }),
+        );
+        // Subresources do not touch the entry.
+        log.apply(
+            "Network.requestWillBeSent",
+            &json!({ "requestId": "R2", "frameId": "F", "type": "Script", "timestamp": 10.2,
+                     "request": { "url": "https://x.test/app.js" } }),
+        );
+        log.apply(
+            "Network.responseReceived",
+            &json!({ "requestId": "L1", "frameId": "F", "type": "Document", "timestamp": 10.25,
+                     "response": { "url": "https://x.test/login", "status": 200, "statusText": "OK",
+                                   "mimeType": "text/html", "headers": { "Content-Type": "text/html" },
+                                   "remoteIPAddress": "10.0.0.1", "remotePort": 443,
+                                   "protocol": "h2" } }),
+        );
+        log.apply(
+            "Network.loadingFinished",
+            &json!({ "requestId": "L1", "timestamp": 10.5 }),
+        );
+
+        let v = log.get("F").unwrap().to_json();
+        assert_eq!(v["url"], "https://x.test/login");
+        assert_eq!(v["status"], 200);
+        assert_eq!(v["headers"]["content-type"], "text/html");
+        assert_eq!(v["remote_address"], "10.0.0.1:443");
+        assert_eq!(
+            v["redirects"],
+            json!([{ "url": "https://x.test/account", "status": 302, "location": "/login" }])
+        );
+        assert_eq!(v["timing"]["response_ms"], 250);
+        assert_eq!(v["timing"]["total_ms"], 500);
+    }
+
+    #[test]
+    fn apply_replaces_the_entry_on_a_new_navigation_and_records_failures() {
+        let mut log = DocumentLog::default();
+        let begin = |id: &str, url: &str| {
+            json!({ "requestId": id, "frameId": "F", "type": "Document", "timestamp": 1.0,
+                    "request": { "url": url } })
+        };
+        log.apply("Network.requestWillBeSent", &begin("L1", "https://a.test/"));
+        log.apply(
+            "Network.requestWillBeSent",
+            &begin("L2", "https://b.invalid/"),
+        );
+        log.apply(
+            "Network.loadingFailed",
+            &json!({ "requestId": "L2", "timestamp": 1.2, "errorText": "net::ERR_NAME_NOT_RESOLVED" }),
+        );
+
+        let doc = log.get("F").unwrap();
+        assert_eq!(doc.url, "https://b.invalid/");
+        assert_eq!(doc.status, None);
+        assert_eq!(doc.error.as_deref(), Some("net::ERR_NAME_NOT_RESOLVED"));
+        assert!(doc.redirects.is_empty());
+    }
+}
@@ -0,0 +1,237 @@
+//! Per-session download tracking for `browser downloads`.
+//!
+//! `browser start` turns on download events with `Browser.setDownloadBehavior`
+//! (saving into `--download-dir` when given, else Chrome's default folder),
+//! and a background task folds `downloadWillBegin` / `downloadProgress` into
+//! a [`DownloadTracker`] that `downloads list` reads and `downloads wait`
+//! blocks on.
+
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+use serde::Serialize;
+use serde_json::{Value, json};
+use tokio::sync::Notify;
+
+use super::cdp_session::CdpSession;
+use crate::error::CliError;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DownloadState {
+    InProgress,
+    Completed,
+    Canceled,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct Download {
+    pub guid: String,
+    pub url: String,
+    pub filename: String,
+    pub state: DownloadState,
+    pub received_bytes: u64,
+    pub total_bytes: u64,
+    /// Where the file lands: reported by Chrome when it says, otherwise the
+    /// suggested filename inside `--download-dir`.
+    pub path: Option<String>,
+    /// Already returned by a `downloads wait`.
+    #[serde(skip)]
+    pub reported: bool,
+}
+
+pub struct DownloadTracker {
+    dir: Option<PathBuf>,
+    log: std::sync::Mutex<Vec<Download>>,
+    changed: Notify,
+}
+
+pub type SharedDownloads = Arc<DownloadTracker>;
+
+impl DownloadTracker {
+    pub fn new(dir: Option<PathBuf>) -> Self {
+        Self {
+            dir,
+            log: std::sync::Mutex::new(Vec::new()),
+            changed: Notify::new(),
+        }
+    }
+
+    pub fn dir(&self) -> Option<&Path> {
+        self.dir.as_deref()
+    }
+
+    pub fn list(&self) -> Vec<Download> {
+        self.log.lock().unwrap_or_else(|e| e.into_inner()).clone()
+    }
+
+    /// Fold one `downloadWillBegin` / `downloadProgress` event into the log.
+    pub fn apply(&self, method: &str, params: &Value) {
+        let s = |k: &str| params.get(k).and_then(|v| v.as_str()).unwrap_or("");
+        let Some(guid) = params.get("guid").and_then(|v| v.as_str()) else {
+            return;
+        };
+        let mut log = self.log.lock().unwrap_or_else(|e| e.into_inner());
+        if method.ends_with(".downloadWillBegin") {
+            if log.iter().any(|d| d.guid == guid) {
+                return;
+            }
+            let filename = s("suggestedFilename").to_string();
+            log.push(Download {
+                guid: guid.to_string(),
+                url: s("url").to_string(),
+                path: self
+                    .dir
+                    .as_ref()
+                    .filter(|_| !filename.is_empty())
+                    .map(|d| d.join(&filename).to_string_lossy().into_owned()),
+                filename,
+                state: DownloadState::InProgress,
+                received_bytes: 0,
+                total_bytes: 0,
+                reported: false,
+            });
+        } else if method.ends_with(".downloadProgress") {
+            let Some(d) = log.iter_mut().find(|d| d.guid == guid) else {
+                return;
+            };
+            let n = |k: &str| params.get(k).and_then(|v| v.as_f64()).unwrap_or(0.0) as u64;
+            d.received_bytes = n("receivedBytes");
+            d.total_bytes = n("totalBytes");
+            d.state = match s("state") {
+                "completed" => DownloadState::Completed,
+                "canceled" => DownloadState::Canceled,
+                _ => DownloadState::InProgress,
+            };
+            if !s("filePath").is_empty() {
+                d.path = Some(s("filePath").to_string());
+            }
+        } else {
+            return;
+        }
+        drop(log);
+        self.changed.notify_waiters();
+    }
+
+    /// The oldest finished download no `wait` has returned yet, marked as
+    /// returned: `Ok` when it completed, `Err` when it was canceled.
+    fn take_finished(&self) -> Option<Result<Download, Download>> {
+        let mut log = self.log.lock().unwrap_or_else(|e| e.into_inner());
+        let d = log
+            .iter_mut()
+            .find(|d| !d.reported && d.state != DownloadState::InProgress)?;
+        d.reported = true;
+        Some(if d.state == DownloadState::Completed {
+            Ok(d.clone())
+        } else {
+            Err(d.clone())
+        })
+    }
+
+    /// Wait until a download not yet returned by an earlier wait finishes.
+    /// `None` on timeout.
+    pub async fn wait_finished(
+        &self,
+        timeout: std::time::Duration,
+    ) -> Option<Result<Download, Download>> {
+        let deadline = tokio::time::Instant::now() + timeout;
+        loop {
+            let changed = self.changed.notified();
+            if let Some(found) = self.take_finished() {
+                return Some(found);
+            }
+            if tokio::time::timeout_at(deadline, changed).await.is_err() {
+                return None;
+            }
+        }
+    }
+}
+
+const EVENTS: [&str; 2] = ["Browser.downloadWillBegin", "Browser.downloadProgress"];
+
+/// Turn on download events for the whole browser and feed them to `tracker`.
+/// Also called again on reconnect with the existing tracker.
+pub async fn install(cdp: &CdpSession, tracker: SharedDownloads) -> Result<(), CliError> {
+    // Subscribe before enabling so the first events are not missed. These
+    // are browser-level events, routed under the empty session ID.
+    let mut receivers = Vec::new();
+    for method in EVENTS {
+        receivers.push(cdp.subscribe_events("", method).await);
+    }
+
+    let mut params = json!({ "behavior": "default", "eventsEnabled": true });
+    if let Some(dir) = tracker.dir() {
+        std::fs::create_dir_all(dir).map_err(|e| {
+            CliError::InvalidArgument(format!("cannot create {}: {e}", dir.display()))
+        })?;
+        params["behavior"] = json!("allow");
+        params["downloadPath"] = json!(dir.to_string_lossy());
+    }
+    cdp.execute_browser("Browser.setDownloadBehavior", params)
+        .await?;
+    cdp.set_downloads(tracker.clone());
+
+    for mut rx in receivers {
+        let tracker = tracker.clone();
+        tokio::spawn(async move {
+            while let Some(event) = rx.recv().await {
+                let method = event.get("method").and_then(|v| v.as_str()).unwrap_or("");
+                tracker.apply(method, &event["params"]);
+            }
+        });
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn apply_tracks_a_download_to_completion() {
+        let t = DownloadTracker::new(Some(PathBuf::from("/tmp/dl")));
+        t.apply(
+            "Browser.downloadWillBegin",
+            &json!({ "guid": "g1", "url": "https://x.test/r.csv", "suggestedFilename": "r.csv" }),
+        );
+        t.apply(
+            "Browser.downloadProgress",
+            &json!({ "guid": "g1", "receivedBytes": 5, "totalBytes": 10, "state": "inProgress" }),
+        );
+        let d = &t.list()[0];
+        assert_eq!(d.state, DownloadState::InProgress);
+        assert_eq!(d.path.as_deref(), Some("/tmp/dl/r.csv"));
+        assert!(t.take_finished().is_none());
+
+        t.apply(
+            "Browser.downloadProgress",
+            &json!({ "guid": "g1", "receivedBytes": 10, "totalBytes": 10, "state": "completed",
+                     "filePath": "/tmp/dl/r (1).csv" }),
+        );
+        let done = t.take_finished().unwrap().unwrap();
+        assert_eq!(done.received_bytes, 10);
+        assert_eq!(done.path.as_deref(), Some("/tmp/dl/r (1).csv"));
+        // Each finished download is returned once.
+        assert!(t.take_finished().is_none());
+    }
+
+    #[test]
+    fn apply_reports_canceled_and_ignores_duplicates_and_unknown_guids() {
+        let t = DownloadTracker::new(None);
+        let begin = json!({ "guid": "g1", "url": "u", "suggestedFilename": "a.bin" });
+        t.apply("Browser.downloadWillBegin", &begin);
+        t.apply("Browser.downloadWillBegin", &begin);
+        t.apply(
+            "Browser.downloadProgress",
+            &json!({ "guid": "other", "state": "completed" }),
+        );
+        assert_eq!(t.list().len(), 1);
+        assert_eq!(t.list()[0].path, None);
+
+        t.apply(
+            "Browser.downloadProgress",
+            &json!({ "guid": "g1", "state": "canceled" }),
+        );
+        assert!(t.take_finished().unwrap().is_err());
+    }
+}
@@ -0,0 +1,443 @@
+//! Device and network emulation for `browser start --device`,
+//! `browser emulate` and `browser emulate network`.
+//!
+//! A device preset sets the viewport, device scale factor, touch support and
+//! user agent of a tab (`Emulation.setDeviceMetricsOverride`,
+//! `setTouchEmulationEnabled`, `setUserAgentOverride`); network conditions
+//! set its latency and throughput (`Network.emulateNetworkConditions`). The
+//! session's settings live on its `CdpSession` and are applied to every tab
+//! attached afterwards, so tabs opened later behave the same.
+//!
+//! Metrics, user agents and network presets follow Chrome DevTools.
+
+use serde_json::{Value, json};
+
+use super::cdp_session::CdpSession;
+use crate::error::CliError;
+
+const IOS_16: &str = "Mozilla/5.0 (iPhone; CPU iPhone OS 16_6 like Mac OS X) AppleWebKit/605.1.15 (KHTML, like Gecko) Version/16.6 Mobile/15E148 Safari/604.1";
+const IOS_17: &str = "Mozilla/5.0 (iPhone; CPU iPhone OS 17_0 like Mac OS X) AppleWebKit/605.1.15 (KHTML, like Gecko) Version/17.0 Mobile/15E148 Safari/604.1";
+const IPADOS_16: &str = "Mozilla/5.0 (iPad; CPU OS 16_6 like Mac OS X) AppleWebKit/605.1.15 (KHTML, like Gecko) Version/16.6 Mobile/15E148 Safari/604.1";
+const PIXEL_7: &str = "Mozilla/5.0 (Linux; Android 13; Pixel 7) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/116.0.0.0 Mobile Safari/537.36";
+const GALAXY_S22: &str = "Mozilla/5.0 (Linux; Android 13; SM-S908B) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/116.0.0.0 Mobile Safari/537.36";
+const GALAXY_TAB: &str = "Mozilla/5.0 (Linux; Android 13; SM-X906C) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/116.0.0.0 Safari/537.36";
+
+/// A device preset. Width and height are CSS pixels in portrait orientation.
+#[derive(Debug, PartialEq)]
+pub struct Device {
+    pub name: &'static str,
+    pub width: u32,
+    pub height: u32,
+    pub scale: f64,
+    pub mobile: bool,
+    pub touch: bool,
+    /// Replaces the browser's user agent; `None` keeps it.
+    pub user_agent: Option<&'static str>,
+    pub platform: &'static str,
+}
+
+pub const DEVICES: &[Device] = &[
+    Device {
+        name: "iPhone SE",
+        width: 375,
+        height: 667,
+        scale: 2.0,
+        mobile: true,
+        touch: true,
+        user_agent: Some(IOS_16),
+        platform: "iPhone",
+    },
+    Device {
+        name: "iPhone 12 Pro",
+        width: 390,
+        height: 844,
+        scale: 3.0,
+        mobile: true,
+        touch: true,
+        user_agent: Some(IOS_16),
+        platform: "iPhone",
+    },
+    Device {
+        name: "iPhone 14",
+        width: 390,
+        height: 844,
+        scale: 3.0,
+        mobile: true,
+        touch: true,
+        user_agent: Some(IOS_16),
+        platform: "iPhone",
+    },
+    Device {
+        name: "iPhone 14 Pro Max",
+        width: 430,
+        height: 932,
+        scale: 3.0,
+        mobile: true,
+        touch: true,
+        user_agent: Some(IOS_16),
+        platform: "iPhone",
+    },
+    Device {
+        name: "iPhone 15 Pro",
+        width: 393,
+        height: 852,
+        scale: 3.0,
+        mobile: true,
+        touch: true,
+        user_agent: Some(IOS_17),
+        platform: "iPhone",
+    },
+    Device {
+        name: "Pixel 7",
+        width: 412,
+        height: 915,
+        scale: 2.625,
+        mobile: true,
+        touch: true,
+        user_agent: Some(PIXEL_7),
+        platform: "Linux armv8l",
+    },
+    Device {
+        name: "Galaxy S22 Ultra",
+        width: 384,
+        height: 824,
+        scale: 3.75,
+        mobile: true,
+        touch: true,
+        user_agent: Some(GALAXY_S22),
+        platform: "Linux armv8l",
+    },
+    Device {
+        name: "iPad Mini",
+        width: 768,
+        height: 1024,
+        scale: 2.0,
+        mobile: true,
+        touch: true,
+        user_agent: Some(IPADOS_16),
+        platform: "iPad",
+    },
+    Device {
+        name: "iPad Air",
+        width: 820,
+        height: 1180,
+        scale: 2.0,
+        mobile: true,
+        touch: true,
+        user_agent: Some(IPADOS_16),
+        platform: "iPad",
+    },
+    Device {
+        name: "iPad Pro",
+        width: 1024,
+        height: 1366,
+        scale: 2.0,
+        mobile: true,
+        touch: true,
+        user_agent: Some(IPADOS_16),
+        platform: "iPad",
+    },
+    Device {
+        name: "Galaxy Tab S8",
+        width: 753,
+        height: 1205,
+        scale: 2.25,
+        mobile: true,
+        touch: true,
+        user_agent: Some(GALAXY_TAB),
+        platform: "Linux armv8l",
+    },
+    Device {
+        name: "Desktop 1080p",
+        width: 1920,
+        height: 1080,
+        scale: 1.0,
+        mobile: false,
+        touch: false,
+        user_agent: None,
+        platform: "",
+    },
+];
+
+/// A resolved `--device` value: a preset and its orientation.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Emulation {
+    pub device: &'static Device,
+    pub landscape: bool,
+}
+
+impl Emulation {
+    /// Look up a device by name, ignoring case, spaces and punctuation
+    /// ("iPhone 14", "iphone-14"). A trailing "landscape" rotates it.
+    pub fn resolve(name: &str) -> Option<Self> {
+        let key = normalize(name);
+        let (key, landscape) = match key.strip_suffix("landscape") {
+            Some(rest) => (rest.to_string(), true),
+            None => (key, false),
+        };
+        if key.is_empty() {
+            return None;
+        }
+        DEVICES
+            .iter()
+            .find(|d| normalize(d.name) == key)
+            .map(|device| Self { device, landscape })
+    }
+
+    /// The `--device` value that resolves back to this emulation.
+    pub fn label(&self) -> String {
+        if self.landscape {
+            format!("{} landscape", self.device.name)
+        } else {
+            self.device.name.to_string()
+        }
+    }
+
+    /// Viewport as (width, height) in the emulated orientation.
+    pub fn viewport(&self) -> (u32, u32) {
+        let d = self.device;
+        if self.landscape {
+            (d.height, d.width)
+        } else {
+            (d.width, d.height)
+        }
+    }
+
+    pub fn to_json(&self) -> Value {
+        let (width, height) = self.viewport();
+        json!({
+            "device": self.label(),
+            "width": width,
+            "height": height,
+            "device_scale_factor": self.device.scale,
+            "mobile": self.device.mobile,
+            "touch": self.device.touch,
+            "user_agent": self.device.user_agent,
+        })
+    }
+}
+
+fn normalize(name: &str) -> String {
+    name.chars()
+        .filter(|c| c.is_ascii_alphanumeric())
+        .map(|c| c.to_ascii_lowercase())
+        .collect()
+}
+
+/// Network conditions. Throughput is in kilobits per second; `None` leaves
+/// that direction unthrottled.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Network {
+    /// The preset these conditions started from, if any.
+    pub preset: Option<&'static str>,
+    pub offline: bool,
+    pub latency_ms: u64,
+    pub download_kbps: Option<u64>,
+    pub upload_kbps: Option<u64>,
+}
+
+/// (name, latency ms, download kbps, upload kbps)
+pub const NETWORK_PRESETS: &[(&str, u64, u64, u64)] = &[
+    ("slow-3g", 2000, 400, 400),
+    ("fast-3g", 563, 1440, 675),
+    ("fast-4g", 165, 8100, 1350),
+];
+
+impl Network {
+    /// A preset by name (case-insensitive); "offline" cuts the connection.
+    pub fn preset(name: &str) -> Option<Self> {
+        let name = name.to_ascii_lowercase();
+        if name == "offline" {
+            return Some(Self {
+                preset: Some("offline"),
+                offline: true,
+                latency_ms: 0,
+                download_kbps: None,
+                upload_kbps: None,
+            });
+        }
+        NETWORK_PRESETS
+            .iter()
+            .find(|(n, ..)| *n == name)
+            .map(|&(preset, latency_ms, down, up)| Self {
+                preset: Some(preset),
+                offline: false,
+                latency_ms,
+                download_kbps: Some(down),
+                upload_kbps: Some(up),
+            })
+    }
+
+    pub fn to_json(&self) -> Value {
+        json!({
+            "preset": self.preset,
+            "offline": self.offline,
+            "latency_ms": self.latency_ms,
+            "download_kbps": self.download_kbps,
+            "upload_kbps": self.upload_kbps,
+        })
+    }
+
+    fn params(&self) -> Value {
+        // CDP wants bytes per second, with -1 for no limit.
+        let rate = |kbps: Option<u64>| kbps.map_or(-1.0, |k| k as f64 * 125.0);
+        json!({
+            "offline": self.offline,
+            "latency": self.latency_ms,
+            "downloadThroughput": rate(self.download_kbps),
+            "uploadThroughput": rate(self.upload_kbps),
+        })
+    }
+}
+
+/// Comma-separated network preset names, for error hints and help.
+pub fn network_preset_names() -> String {
+    let mut names: Vec<&str> = NETWORK_PRESETS.iter().map(|(n, ..)| *n).collect();
+    names.push("offline");
+    names.join(", ")
+}
+
+/// Comma-separated preset names, for error hints and help.
+pub fn device_names() -> String {
+    DEVICES
+        .iter()
+        .map(|d| d.name)
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+/// Apply `emulation` to one tab.
+pub async fn apply(
+    cdp: &CdpSession,
+    target_id: &str,
+    emulation: &Emulation,
+) -> Result<(), CliError> {
+    let d = emulation.device;
+    let (width, height) = emulation.viewport();
+    let orientation = if emulation.landscape {
+        json!({ "type": "landscapePrimary", "angle": 90 })
+    } else {
+        json!({ "type": "portraitPrimary", "angle": 0 })
+    };
+    cdp.execute_on_tab(
+        target_id,
+        "Emulation.setDeviceMetricsOverride",
+        json!({
+            "width": width,
+            "height": height,
+            "deviceScaleFactor": d.scale,
+            "mobile": d.mobile,
+            "screenWidth": width,
+            "screenHeight": height,
+            "screenOrientation": orientation,
+        }),
+    )
+    .await?;
+    cdp.execute_on_tab(
+        target_id,
+        "Emulation.setTouchEmulationEnabled",
+        json!({ "enabled": d.touch, "maxTouchPoints": if d.touch { 5 } else { 1 } }),
+    )
+    .await?;
+    if let Some(ua) = d.user_agent {
+        cdp.execute_on_tab(
+            target_id,
+            "Emulation.setUserAgentOverride",
+            json!({ "userAgent": ua, "platform": d.platform }),
+        )
+        .await?;
+    }
+    Ok(())
+}
+
+/// Undo [`apply`] on one tab. `user_agent` is the session's stealth user
+/// agent, restored in place of the device's; without one the override is
+/// cleared.
+pub async fn clear(
+    cdp: &CdpSession,
+    target_id: &str,
+    user_agent: Option<&str>,
+) -> Result<(), CliError> {
+    cdp.execute_on_tab(target_id, "Emulation.clearDeviceMetricsOverride", json!({}))
+        .await?;
+    cdp.execute_on_tab(
+        target_id,
+        "Emulation.setTouchEmulationEnabled",
+        json!({ "enabled": false }),
+    )
+    .await?;
+    // An empty user agent removes the override.
+    cdp.execute_on_tab(
+        target_id,
+        "Emulation.setUserAgentOverride",
+        json!({ "userAgent": user_agent.unwrap_or_default() }),
+    )
+    .await?;
+    Ok(())
+}
+
+/// Throttle one tab to `network`, or lift the throttling with `None`.
+pub async fn apply_network(
+    cdp: &CdpSession,
+    target_id: &str,
+    network: Option<&Network>,
+) -> Result<(), CliError> {
+    let params = match network {
+        Some(n) => n.params(),
+        None => json!({
+            "offline": false,
+            "latency": 0,
+            "downloadThroughput": -1,
+            "uploadThroughput": -1,
+        }),
+    };
+    cdp.execute_on_tab(target_id, "Network.emulateNetworkConditions", params)
+        .await?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn resolve_ignores_case_punctuation_and_reads_orientation() {
+        let e = Emulation::resolve("iphone-14").unwrap();
+        assert_eq!(e.device.name, "iPhone 14");
+        assert!(!e.landscape);
+        assert_eq!(e.viewport(), (390, 844));
+
+        let e = Emulation::resolve("Pixel 7 Landscape").unwrap();
+        assert_eq!(e.device.name, "Pixel 7");
+        assert_eq!(e.viewport(), (915, 412));
+        assert_eq!(e.label(), "Pixel 7 landscape");
+        assert_eq!(Emulation::resolve(&e.label()), Some(e));
+
+        assert!(Emulation::resolve("iPhone 14 Pro").is_none());
+        assert!(Emulation::resolve("landscape").is_none());
+        assert!(Emulation::resolve("").is_none());
+    }
+
+    #[test]
+    fn network_presets_convert_to_bytes_per_second() {
+        let slow = Network::preset("Slow-3G").unwrap();
+        assert_eq!(slow.preset, Some("slow-3g"));
+        let params = slow.params();
+        assert_eq!(params["latency"], 2000);
+        assert_eq!(params["downloadThroughput"], 50000.0);
+        assert_eq!(params["offline"], false);
+
+        let offline = Network::preset("offline").unwrap();
+        assert!(offline.offline);
+        assert_eq!(offline.params()["uploadThroughput"], -1.0);
+        assert!(Network::preset("5g").is_none());
+    }
+
+    #[test]
+    fn device_names_are_unique_after_normalizing() {
+        let mut keys: Vec<String> = DEVICES.iter().map(|d| normalize(d.name)).collect();
+        keys.sort();
+        keys.dedup();
+        assert_eq!(keys.len(), DEVICES.len());
+    }
+}
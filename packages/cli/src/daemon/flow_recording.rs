@@ -0,0 +1,614 @@
+//! Recording a user's clicks, typing and navigation as an `actionbook run`
+//! script.
+//!
+//! `browser record start` installs a page script that reports trusted input
+//! events through a CDP binding (`Runtime.addBinding`, so `Runtime.enable` is
+//! never needed) and re-installs itself on every new document. The tab's main
+//! frame navigations come from `Page.frameNavigated`. `browser record stop`
+//! turns what was captured into `run` steps and writes them out.
+//!
+//! Extension sessions cannot add bindings or new-document scripts through
+//! the bridge's CDP allowlist. There `Extension.startRecording` makes the
+//! extension inject an equivalent content script (`recorder.js`), and the
+//! events arrive as `Extension.recordingEvent` frames routed like the tab's
+//! CDP events, navigations included.
+
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, LazyLock, Mutex};
+use std::time::Instant;
+
+use serde_json::{Value, json};
+
+use super::cdp_session::CdpSession;
+use crate::error::CliError;
+
+/// Name of the binding the page script reports events through.
+pub const BINDING: &str = "__actionbookRecord";
+
+/// Event the extension sends for each input or navigation in a recorded tab.
+pub const EXTENSION_EVENT: &str = "Extension.recordingEvent";
+
+/// A navigation this soon after a click, key press or selection is taken to
+/// be caused by it and is not recorded as its own `goto`.
+const NAV_GRACE_MS: u64 = 3000;
+
+/// `(el) => selector`: a unique id, then test ids, name, aria-label and
+/// placeholder, then a tag path from the nearest id.
+pub const SELECTOR_JS: &str = r#"(el) => {
+  const unique = (sel) => { try { return document.querySelectorAll(sel).length === 1; } catch (_) { return false; } };
+  if (el.id && unique('#' + CSS.escape(el.id))) return '#' + CSS.escape(el.id);
+  const tag = el.tagName.toLowerCase();
+  for (const attr of ['data-testid', 'data-test', 'data-qa', 'name', 'aria-label', 'placeholder']) {
+    const v = el.getAttribute(attr);
+    if (!v) continue;
+    const sel = `${tag}[${attr}="${v.replace(/\\/g, '\\\\').replace(/"/g, '\\"')}"]`;
+    if (unique(sel)) return sel;
+  }
+  const parts = [];
+  for (let node = el; node && node.nodeType === 1 && node !== document.documentElement; node = node.parentElement) {
+    if (node.id) { parts.unshift('#' + CSS.escape(node.id)); break; }
+    const name = node.tagName.toLowerCase();
+    const same = node.parentElement ? Array.from(node.parentElement.children).filter(c => c.tagName === node.tagName) : [];
+    parts.unshift(same.length > 1 ? `${name}:nth-of-type(${same.indexOf(node) + 1})` : name);
+  }
+  return parts.join(' > ');
+}"#;
+
+/// `(el) => anchor`: what identifies an element besides its selector — tag,
+/// role, accessible name, own text, the text around it and its stable
+/// attributes. Recorded steps keep it so `actionbook run` can find the
+/// element again when the selector stops matching.
+pub const FINGERPRINT_JS: &str = r#"(el) => {
+  const clip = (s) => (s || '').replace(/\s+/g, ' ').trim().slice(0, 80);
+  const tag = el.tagName.toLowerCase();
+  const IMPLICIT = { a: 'link', button: 'button', select: 'combobox', textarea: 'textbox', summary: 'button', img: 'img', option: 'option' };
+  const INPUT = { checkbox: 'checkbox', radio: 'radio', submit: 'button', button: 'button', reset: 'button', image: 'button', range: 'slider', search: 'searchbox' };
+  const role = el.getAttribute('role') || (tag === 'input' ? INPUT[el.type] || 'textbox' : IMPLICIT[tag]) || '';
+  const field = tag === 'input' || tag === 'select' || tag === 'textarea';
+  const labelledBy = (el.getAttribute('aria-labelledby') || '').split(' ')[0];
+  const byId = labelledBy && document.getElementById(labelledBy);
+  const label = (el.labels && el.labels[0]) || el.closest('label');
+  const name = clip(el.getAttribute('aria-label') || (byId && byId.innerText) || (label && label.innerText)
+    || el.getAttribute('alt') || el.getAttribute('title')
+    || (field ? el.getAttribute('placeholder') || (['submit', 'button', 'reset'].includes(el.type) ? el.value : '') : el.innerText));
+  const text = field ? '' : clip(el.innerText);
+  let near = '';
+  for (let node = el.parentElement, i = 0; node && i < 3 && !near; node = node.parentElement, i++) {
+    near = clip((node.innerText || '').replace(el.innerText || '', ''));
+  }
+  const attrs = {};
+  for (const a of ['id', 'name', 'type', 'data-testid', 'data-test', 'data-qa', 'aria-label', 'placeholder', 'href']) {
+    const v = el.getAttribute(a);
+    if (v) attrs[a] = v.slice(0, 200);
+  }
+  const fp = { tag, role, name, text, near, attrs };
+  for (const k of ['role', 'name', 'text', 'near']) if (!fp[k]) delete fp[k];
+  return fp;
+}"#;
+
+/// Reports clicks, committed field values, selections and Enter presses,
+/// each with its selector (see [`SELECTOR_JS`]) and anchor (see
+/// [`FINGERPRINT_JS`]).
+const LISTENER_SRC: &str = r#"(() => {
+  if (window.__actionbookRecorder) return;
+  window.__actionbookRecorder = true;
+  const send = (event) => { try { window.__actionbookRecord(JSON.stringify(event)); } catch (_) {} };
+  const selectorFor = /*SELECTOR*/;
+  const anchorFor = /*FINGERPRINT*/;
+  const NOT_TEXT = ['checkbox', 'radio', 'submit', 'button', 'reset', 'file', 'image', 'range', 'color'];
+  const isText = (el) => el.tagName === 'TEXTAREA' || (el.tagName === 'INPUT' && !NOT_TEXT.includes(el.type));
+  const sent = new WeakMap();
+  const sendFill = (el) => {
+    if (sent.get(el) === el.value) return;
+    sent.set(el, el.value);
+    const secret = el.type === 'password' || /pass|secret|token|otp/i.test(el.name + ' ' + el.autocomplete);
+    send({ type: 'fill', selector: selectorFor(el), anchor: anchorFor(el), text: secret ? '' : el.value, secret });
+  };
+  document.addEventListener('click', (e) => {
+    if (!e.isTrusted) return;
+    const el = e.target.closest('a, button, input, select, textarea, label, summary, [role="button"], [role="link"], [role="menuitem"], [role="tab"], [role="checkbox"], [onclick]') || e.target;
+    if (isText(el) || el.tagName === 'SELECT') return;
+    send({ type: 'click', selector: selectorFor(el), anchor: anchorFor(el) });
+  }, true);
+  document.addEventListener('change', (e) => {
+    if (!e.isTrusted) return;
+    const el = e.target;
+    if (el.tagName === 'SELECT') send({ type: 'select', selector: selectorFor(el), anchor: anchorFor(el), value: el.value });
+    else if (isText(el)) sendFill(el);
+  }, true);
+  document.addEventListener('keydown', (e) => {
+    if (!e.isTrusted || e.key !== 'Enter' || !isText(e.target) || e.target.tagName === 'TEXTAREA') return;
+    sendFill(e.target);
+    send({ type: 'press', key: 'Enter' });
+  }, true);
+})()"#;
+
+pub static LISTENER_JS: LazyLock<String> = LazyLock::new(|| {
+    LISTENER_SRC
+        .replace("/*SELECTOR*/", SELECTOR_JS)
+        .replace("/*FINGERPRINT*/", FINGERPRINT_JS)
+});
+
+/// One captured user input. `anchor` is the element's fingerprint, when the
+/// page script sent one.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Input {
+    Click {
+        selector: String,
+        anchor: Option<Value>,
+    },
+    Fill {
+        selector: String,
+        text: String,
+        secret: bool,
+        anchor: Option<Value>,
+    },
+    Select {
+        selector: String,
+        value: String,
+        anchor: Option<Value>,
+    },
+    Press {
+        key: String,
+    },
+    Navigate {
+        url: String,
+    },
+}
+
+impl Input {
+    /// Parse a payload the page script sent through the binding.
+    pub fn from_payload(payload: &str) -> Option<Self> {
+        Self::from_value(&serde_json::from_str(payload).ok()?)
+    }
+
+    /// Parse one event object, as sent by the page script or the extension.
+    pub fn from_value(v: &Value) -> Option<Self> {
+        let s = |k: &str| v.get(k).and_then(|x| x.as_str()).map(String::from);
+        let anchor = v.get("anchor").filter(|a| a.is_object()).cloned();
+        Some(match v.get("type")?.as_str()? {
+            "click" => Input::Click {
+                selector: s("selector")?,
+                anchor,
+            },
+            "fill" => Input::Fill {
+                selector: s("selector")?,
+                text: s("text").unwrap_or_default(),
+                secret: v.get("secret").and_then(|x| x.as_bool()).unwrap_or(false),
+                anchor,
+            },
+            "select" => Input::Select {
+                selector: s("selector")?,
+                value: s("value")?,
+                anchor,
+            },
+            "press" => Input::Press { key: s("key")? },
+            "navigate" => Input::Navigate { url: s("url")? },
+            _ => return None,
+        })
+    }
+}
+
+/// A tab's recording in progress.
+#[derive(Debug)]
+pub struct FlowRecorder {
+    /// Where `record stop` writes the script unless given another path.
+    pub out: PathBuf,
+    /// The page the recording started on; becomes the first `goto`.
+    pub start_url: String,
+    /// `Page.addScriptToEvaluateOnNewDocument` identifier, removed on stop.
+    pub script_id: Option<String>,
+    started: Instant,
+    inputs: Vec<(u64, Input)>,
+    stopped: bool,
+}
+
+pub type SharedFlowRecorder = Arc<Mutex<FlowRecorder>>;
+
+impl FlowRecorder {
+    pub fn new(out: PathBuf, start_url: String) -> Self {
+        Self {
+            out,
+            start_url,
+            script_id: None,
+            started: Instant::now(),
+            inputs: Vec::new(),
+            stopped: false,
+        }
+    }
+
+    pub fn push(&mut self, input: Input) {
+        let at = self.started.elapsed().as_millis() as u64;
+        self.inputs.push((at, input));
+    }
+
+    pub fn inputs(&self) -> usize {
+        self.inputs.len()
+    }
+
+    /// Stop accepting events; the listener task exits on its next event.
+    pub fn stop(&mut self) {
+        self.stopped = true;
+    }
+
+    pub fn steps(&self) -> Vec<Value> {
+        steps(&self.start_url, &self.inputs)
+    }
+}
+
+/// Turn timestamped inputs into `run` steps: a leading `goto`, consecutive
+/// fills of one field collapsed to the last value, and navigations dropped
+/// when an input just caused them or they go nowhere new. Element steps keep
+/// their anchor for selector healing.
+pub fn steps(start_url: &str, inputs: &[(u64, Input)]) -> Vec<Value> {
+    let mut steps = vec![json!({ "goto": start_url })];
+    let mut url = start_url.to_string();
+    let mut last_action_at: Option<u64> = None;
+    let mut last_fill: Option<String> = None;
+
+    for (at, input) in inputs {
+        if let Input::Navigate { url: to } = input {
+            let caused = last_action_at.is_some_and(|t| at.saturating_sub(t) <= NAV_GRACE_MS);
+            if !caused && *to != url {
+                steps.push(json!({ "goto": to }));
+                last_fill = None;
+            }
+            url = to.clone();
+            continue;
+        }
+
+        let with_anchor = |mut step: Value, anchor: &Option<Value>| {
+            if let Some(anchor) = anchor {
+                step["anchor"] = anchor.clone();
+            }
+            step
+        };
+        let step = match input {
+            Input::Click { selector, anchor } => with_anchor(json!({ "click": selector }), anchor),
+            Input::Fill {
+                selector,
+                text,
+                secret,
+                anchor,
+            } => {
+                let mut step = with_anchor(
+                    json!({ "fill": { "selector": selector, "text": text } }),
+                    anchor,
+                );
+                if *secret {
+                    step["name"] = json!("secret value not recorded: fill it in before running");
+                }
+                if last_fill.as_deref() == Some(selector.as_str()) {
+                    steps.pop();
+                }
+                last_fill = Some(selector.clone());
+                steps.push(step);
+                continue;
+            }
+            Input::Select {
+                selector,
+                value,
+                anchor,
+            } => with_anchor(json!({ "browser": ["select", selector, value] }), anchor),
+            Input::Press { key } => json!({ "browser": ["press", key] }),
+            Input::Navigate { .. } => unreachable!("handled above"),
+        };
+        last_action_at = Some(*at);
+        last_fill = None;
+        steps.push(step);
+    }
+    steps
+}
+
+/// Render a script as JSON for `.json` paths and YAML otherwise, matching
+/// what `actionbook run` loads.
+pub fn render(steps: Vec<Value>, path: &Path) -> String {
+    let script = json!({ "steps": steps });
+    let is_json = path
+        .extension()
+        .and_then(|e| e.to_str())
+        .is_some_and(|e| e.eq_ignore_ascii_case("json"));
+    if is_json {
+        serde_json::to_string_pretty(&script).unwrap_or_default() + "\n"
+    } else {
+        serde_yaml::to_string(&script).unwrap_or_default()
+    }
+}
+
+/// Start feeding a tab's input events and navigations into `recorder`.
+pub async fn install(
+    cdp: &CdpSession,
+    target_id: &str,
+    cdp_session_id: &str,
+    recorder: SharedFlowRecorder,
+) -> Result<(), CliError> {
+    // Subscribe before enabling anything so no early event is missed.
+    let mut bindings = cdp
+        .subscribe_events(cdp_session_id, "Runtime.bindingCalled")
+        .await;
+    let mut navigations = cdp
+        .subscribe_events(cdp_session_id, "Page.frameNavigated")
+        .await;
+
+    cdp.execute_on_tab(target_id, "Runtime.addBinding", json!({ "name": BINDING }))
+        .await?;
+    // Page is already enabled for local and cloud tabs; extension tabs may
+    // need it for frameNavigated.
+    let _ = cdp
+        .execute_on_tab(target_id, "Page.enable", json!({}))
+        .await;
+    let added = cdp
+        .execute_on_tab(
+            target_id,
+            "Page.addScriptToEvaluateOnNewDocument",
+            json!({ "source": &*LISTENER_JS }),
+        )
+        .await?;
+    let script_id = added
+        .pointer("/result/identifier")
+        .and_then(|v| v.as_str())
+        .map(String::from);
+    cdp.execute_on_tab(
+        target_id,
+        "Runtime.evaluate",
+        json!({ "expression": &*LISTENER_JS }),
+    )
+    .await?;
+    recorder.lock().unwrap_or_else(|e| e.into_inner()).script_id = script_id;
+
+    tokio::spawn(async move {
+        loop {
+            let input = tokio::select! {
+                Some(event) = bindings.recv() => {
+                    let params = &event["params"];
+                    if params["name"].as_str() != Some(BINDING) {
+                        continue;
+                    }
+                    params["payload"].as_str().and_then(Input::from_payload)
+                }
+                Some(event) = navigations.recv() => {
+                    let frame = &event["params"]["frame"];
+                    if frame.get("parentId").is_some() {
+                        continue;
+                    }
+                    frame["url"].as_str().map(|url| Input::Navigate { url: url.to_string() })
+                }
+                else => break,
+            };
+            let mut rec = recorder.lock().unwrap_or_else(|e| e.into_inner());
+            if rec.stopped {
+                break;
+            }
+            if let Some(input) = input {
+                rec.push(input);
+            }
+        }
+    });
+    Ok(())
+}
+
+/// Start an extension-side recording of `target_id` (a Chrome tab id) and
+/// feed its events into `recorder`.
+pub async fn install_extension(
+    cdp: &CdpSession,
+    target_id: &str,
+    cdp_session_id: &str,
+    recorder: SharedFlowRecorder,
+) -> Result<(), CliError> {
+    let tab_id: u64 = target_id
+        .parse()
+        .map_err(|_| CliError::CdpError(format!("non-numeric extension tab id '{target_id}'")))?;
+    let mut events = cdp.subscribe_events(cdp_session_id, EXTENSION_EVENT).await;
+    cdp.execute_browser("Extension.startRecording", json!({ "tabId": tab_id }))
+        .await?;
+
+    tokio::spawn(async move {
+        while let Some(event) = events.recv().await {
+            let input = Input::from_value(&event["params"]["event"]);
+            let mut rec = recorder.lock().unwrap_or_else(|e| e.into_inner());
+            if rec.stopped {
+                break;
+            }
+            if let Some(input) = input {
+                rec.push(input);
+            }
+        }
+    });
+    Ok(())
+}
+
+/// Stop the extension-side recording of `target_id`.
+pub async fn uninstall_extension(cdp: &CdpSession, target_id: &str) -> Result<(), CliError> {
+    let tab_id: u64 = target_id
+        .parse()
+        .map_err(|_| CliError::CdpError(format!("non-numeric extension tab id '{target_id}'")))?;
+    cdp.execute_browser("Extension.stopRecording", json!({ "tabId": tab_id }))
+        .await?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn click(sel: &str) -> Input {
+        Input::Click {
+            selector: sel.into(),
+            anchor: None,
+        }
+    }
+
+    fn fill(sel: &str, text: &str) -> Input {
+        Input::Fill {
+            selector: sel.into(),
+            text: text.into(),
+            secret: false,
+            anchor: None,
+        }
+    }
+
+    fn nav(url: &str) -> Input {
+        Input::Navigate { url: url.into() }
+    }
+
+    #[test]
+    fn payloads_parse_into_inputs() {
+        assert_eq!(
+            Input::from_payload(r##"{"type":"click","selector":"#go"}"##),
+            Some(click("#go"))
+        );
+        assert_eq!(
+            Input::from_payload(r#"{"type":"press","key":"Enter"}"#),
+            Some(Input::Press {
+                key: "Enter".into()
+            })
+        );
+        assert_eq!(
+            Input::from_value(&json!({ "type": "navigate", "url": "https://a.test/b" })),
+            Some(nav("https://a.test/b"))
+        );
+        assert_eq!(Input::from_payload(r#"{"type":"scroll"}"#), None);
+        assert_eq!(Input::from_payload("not json"), None);
+    }
+
+    #[test]
+    fn steps_collapse_fills_and_drop_caused_navigations() {
+        let inputs = vec![
+            (100, fill("#q", "act")),
+            (200, fill("#q", "actionbook")),
+            (300, click("#search")),
+            (900, nav("https://a.test/results")),
+            (10_000, nav("https://a.test/other")),
+        ];
+
+        let steps = steps("https://a.test/", &inputs);
+
+        assert_eq!(
+            steps,
+            vec![
+                json!({ "goto": "https://a.test/" }),
+                json!({ "fill": { "selector": "#q", "text": "actionbook" } }),
+                json!({ "click": "#search" }),
+                json!({ "goto": "https://a.test/other" }),
+            ]
+        );
+    }
+
+    #[test]
+    fn element_steps_keep_their_anchor() {
+        let anchor = json!({ "tag": "button", "role": "button", "name": "Sign in" });
+        let input = Input::from_value(&json!({
+            "type": "click",
+            "selector": "#login",
+            "anchor": anchor,
+        }))
+        .unwrap();
+
+        let steps = steps("https://a.test/", &[(10, input)]);
+
+        assert_eq!(steps[1], json!({ "click": "#login", "anchor": anchor }));
+    }
+
+    #[test]
+    fn secret_fills_keep_no_value() {
+        let inputs = vec![(
+            50,
+            Input::Fill {
+                selector: "#pw".into(),
+                text: String::new(),
+                secret: true,
+                anchor: None,
+            },
+        )];
+
+        let steps = steps("https://a.test/", &inputs);
+
+        assert_eq!(steps[1]["fill"]["text"], "");
+        assert!(steps[1]["name"].as_str().unwrap().contains("secret"));
+    }
+
+    #[tokio::test]
+    async fn extension_recording_events_reach_the_recorder() {
+        use futures_util::{SinkExt, StreamExt};
+        use tokio_tungstenite::tungstenite::Message;
+
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("ws://{}", listener.local_addr().unwrap());
+        // Fake extension: answer every request, then emit two recording
+        // events for tab 42 and one for another tab.
+        let extension = tokio::spawn(async move {
+            let (stream, _) = listener.accept().await.unwrap();
+            let mut ws = tokio_tungstenite::accept_async(stream).await.unwrap();
+            let mut methods = Vec::new();
+            while let Some(Ok(Message::Text(t))) = ws.next().await {
+                let msg: Value = serde_json::from_str(t.as_ref()).unwrap();
+                let method = msg["method"].as_str().unwrap().to_string();
+                let reply = json!({ "id": msg["id"], "result": {} });
+                ws.send(Message::Text(reply.to_string().into()))
+                    .await
+                    .unwrap();
+                methods.push(method.clone());
+                if method == "Extension.startRecording" {
+                    assert_eq!(msg["params"]["tabId"], 42);
+                    for (tab, event) in [
+                        (42, json!({ "type": "click", "selector": "#go" })),
+                        (7, json!({ "type": "click", "selector": "#other-tab" })),
+                        (42, json!({ "type": "navigate", "url": "https://a.test/b" })),
+                    ] {
+                        let frame = json!({ "method": EXTENSION_EVENT, "params": { "event": event }, "tabId": tab });
+                        ws.send(Message::Text(frame.to_string().into()))
+                            .await
+                            .unwrap();
+                    }
+                }
+            }
+            methods
+        });
+
+        let cdp = CdpSession::connect(&url).await.unwrap();
+        cdp.register_extension_tab("42").await;
+        let key = cdp.get_cdp_session_id("42").await.unwrap();
+        let recorder = Arc::new(Mutex::new(FlowRecorder::new(
+            PathBuf::from("flow.yaml"),
+            "https://a.test/".into(),
+        )));
+        install_extension(&cdp, "42", &key, recorder.clone())
+            .await
+            .unwrap();
+
+        for _ in 0..50 {
+            if recorder.lock().unwrap().inputs() == 2 {
+                break;
+            }
+            tokio::time::sleep(std::time::Duration::from_millis(10)).await;
+        }
+        let inputs: Vec<Input> = recorder
+            .lock()
+            .unwrap()
+            .inputs
+            .iter()
+            .map(|(_, input)| input.clone())
+            .collect();
+        assert_eq!(inputs, vec![click("#go"), nav("https://a.test/b")]);
+        uninstall_extension(&cdp, "42").await.unwrap();
+        cdp.close().await;
+        let methods = extension.await.unwrap();
+        assert_eq!(
+            methods,
+            [
+                "Network.enable",
+                "Extension.startRecording",
+                "Extension.stopRecording"
+            ]
+        );
+    }
+
+    #[test]
+    fn render_follows_the_extension() {
+        let steps = vec![json!({ "goto": "https://a.test/" })];
+        let yaml = render(steps.clone(), Path::new("flow.yaml"));
+        assert!(yaml.contains("- goto: https://a.test/"), "{yaml}");
+        let json = render(steps, Path::new("flow.json"));
+        assert!(json.trim_start().starts_with('{'), "{json}");
+    }
+}
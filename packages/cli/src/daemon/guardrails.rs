@@ -0,0 +1,705 @@
+//! Guardrails configured in the `[guardrails]` section of `config.toml`.
+//!
+//! - Domain allowlist/denylist: checked in the router before any handler runs,
+//!   so every backend (local CDP, extension bridge, cloud) shares one rule set.
+//!   Navigations no command names directly (redirects, clicks, scripts) are
+//!   stopped by the tab's request interceptor, which fails document requests
+//!   to rejected hosts, and the router re-checks the URL each tab lands on.
+//!   Tabs attached before a policy was written get their interceptor from
+//!   the first command that addresses them afterwards.
+//! - Sensitive-action confirmation: clicks on payment/deletion-like controls
+//!   are refused unless the caller passes `--confirm-sensitive`.
+
+use std::sync::{Arc, Mutex};
+use std::time::SystemTime;
+
+use regex::Regex;
+use serde_json::json;
+
+use crate::action::Action;
+use crate::action_result::ActionResult;
+use crate::audit;
+use crate::config::{self, GuardrailsConfig};
+use crate::daemon::cdp::ensure_scheme;
+use crate::daemon::cdp_session::get_cdp_and_target;
+use crate::daemon::registry::SharedRegistry;
+use crate::daemon::routes;
+
+pub const DOMAIN_NOT_ALLOWED: &str = "DOMAIN_NOT_ALLOWED";
+pub const CONFIRMATION_REQUIRED: &str = "CONFIRMATION_REQUIRED";
+
+/// Accessible-name patterns used when `sensitive_patterns` is empty.
+const DEFAULT_SENSITIVE_PATTERNS: &[&str] = &[
+    r"\bpay\b",
+    r"\bpay now\b",
+    r"\bpurchase\b",
+    r"\bbuy\b",
+    r"\bcheckout\b",
+    r"\bplace (your )?order\b",
+    r"\bdelete\b",
+    r"\bremove\b",
+    r"\btransfer\b",
+    r"\bwithdraw\b",
+    r"\bsend money\b",
+    r"\bclose (my )?account\b",
+];
+
+/// Which list rejected a URL.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PolicyKind {
+    Blocked,
+    NotAllowed,
+}
+
+impl PolicyKind {
+    fn as_str(self) -> &'static str {
+        match self {
+            PolicyKind::Blocked => "blocked_domains",
+            PolicyKind::NotAllowed => "allowed_domains",
+        }
+    }
+}
+
+/// A rejected navigation target.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Violation {
+    pub url: String,
+    pub host: Option<String>,
+    pub policy: PolicyKind,
+    /// The blocklist entry that matched (only for `PolicyKind::Blocked`).
+    pub rule: Option<String>,
+}
+
+/// Compiled domain policy.
+#[derive(Debug, Clone, Default)]
+pub struct DomainPolicy {
+    allowed: Vec<String>,
+    blocked: Vec<String>,
+}
+
+/// `config.toml` modification time and length a cached policy was read at.
+type ConfigStamp = (SystemTime, u64);
+
+/// Last policy read from `config.toml`, reused until the file changes.
+static CURRENT_POLICY: Mutex<Option<(ConfigStamp, Arc<DomainPolicy>)>> = Mutex::new(None);
+
+impl DomainPolicy {
+    pub(crate) fn from_config(cfg: &GuardrailsConfig) -> Self {
+        Self {
+            allowed: normalize_rules(&cfg.allowed_domains),
+            blocked: normalize_rules(&cfg.blocked_domains),
+        }
+    }
+
+    /// The policy in `config.toml`. Parsed once and again only when the file
+    /// changes, since every intercepted document request checks it. Fails
+    /// closed: an unreadable config must not disable the sandbox.
+    pub fn current() -> Result<Arc<Self>, ActionResult> {
+        let stamp = std::fs::metadata(config::config_path())
+            .ok()
+            .and_then(|m| Some((m.modified().ok()?, m.len())));
+        let mut cached = CURRENT_POLICY.lock().unwrap_or_else(|e| e.into_inner());
+        if let (Some(stamp), Some((at, policy))) = (stamp, cached.as_ref())
+            && stamp == *at
+        {
+            return Ok(policy.clone());
+        }
+        let cfg = config::load_config().map_err(|e| {
+            ActionResult::fatal_with_hint(
+                "INVALID_ARGUMENT",
+                format!("cannot evaluate navigation guardrails: {e}"),
+                "fix config.toml or remove the [guardrails] section",
+            )
+        })?;
+        let policy = Arc::new(Self::from_config(&cfg.guardrails));
+        *cached = stamp.map(|stamp| (stamp, policy.clone()));
+        Ok(policy)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.allowed.is_empty() && self.blocked.is_empty()
+    }
+
+    /// Check a single URL (scheme optional, as accepted by `goto`).
+    pub fn check(&self, raw_url: &str) -> Result<(), Violation> {
+        if self.is_empty() {
+            return Ok(());
+        }
+        let url = ensure_scheme(raw_url).unwrap_or_else(|_| raw_url.to_string());
+        // about:blank is the neutral starting page for every backend.
+        if url.eq_ignore_ascii_case("about:blank") {
+            return Ok(());
+        }
+        let host = url_host(&url);
+
+        if let Some(h) = host.as_deref()
+            && let Some(rule) = self.blocked.iter().find(|r| rule_matches(r, h))
+        {
+            return Err(Violation {
+                url,
+                host,
+                policy: PolicyKind::Blocked,
+                rule: Some(rule.clone()),
+            });
+        }
+
+        if self.allowed.is_empty() {
+            return Ok(());
+        }
+        // With an allowlist active, host-less URLs (file:, data:, chrome:)
+        // cannot be matched and are rejected.
+        match host.as_deref() {
+            Some(h) if self.allowed.iter().any(|r| rule_matches(r, h)) => Ok(()),
+            _ => Err(Violation {
+                url,
+                host,
+                policy: PolicyKind::NotAllowed,
+                rule: None,
+            }),
+        }
+    }
+}
+
+/// URLs an action would navigate to. Empty for non-navigating actions.
+pub fn navigation_targets(action: &Action) -> Vec<&str> {
+    match action {
+        Action::Goto(cmd) => vec![cmd.url.as_str()],
+        Action::TraceRedirects(cmd) => vec![cmd.url.as_str()],
+        Action::NewTab(cmd) => cmd.urls.iter().map(String::as_str).collect(),
+        Action::BatchOpen(cmd) => cmd.urls.iter().map(String::as_str).collect(),
+        Action::StartSession(cmd) => cmd.open_url.as_deref().into_iter().collect(),
+        _ => Vec::new(),
+    }
+}
+
+/// Enforce the configured policy on `action`. Returns `Some(fatal)` when the
+/// action must not run.
+pub fn enforce(action: &Action) -> Option<ActionResult> {
+    let targets = navigation_targets(action);
+    if targets.is_empty() {
+        return None;
+    }
+    let policy = match DomainPolicy::current() {
+        Ok(policy) => policy,
+        Err(e) => return Some(e),
+    };
+    targets
+        .into_iter()
+        .find_map(|url| policy.check(url).err())
+        .map(violation_to_result)
+}
+
+/// Make sure the tab an action addresses runs the request interceptor while
+/// a domain policy is in force. Tabs attach it only when a policy exists at
+/// attach time, so one added to config.toml mid-session is armed here,
+/// before the action can click or script its way off the allowed domains.
+pub async fn arm_interceptor(action: &Action, registry: &SharedRegistry) {
+    if DomainPolicy::current().is_ok_and(|p| p.is_empty()) {
+        return;
+    }
+    let (Some(session), Some(tab)) = audit::action_address(action) else {
+        return;
+    };
+    let Ok((cdp, target_id)) = get_cdp_and_target(registry, &session, &tab).await else {
+        return;
+    };
+    let Some(cdp_session_id) = cdp.get_cdp_session_id(&target_id).await else {
+        return;
+    };
+    let routes = cdp.tab_routes(&cdp_session_id).await;
+    if let Err(e) = routes::install(&cdp, &target_id, &cdp_session_id, routes, cdp.adblock()).await
+    {
+        tracing::warn!("guardrails: failed to enable request interception on {target_id}: {e}");
+    }
+}
+
+/// Whether the tab's request interceptor must fail this paused request:
+/// a page or frame document on a host the policy rejects. Also catches
+/// each hop of a redirect chain, which the browser pauses one by one.
+pub fn blocks_document(policy: &DomainPolicy, resource_type: &str, url: &str) -> bool {
+    resource_type == "Document" && policy.check(url).is_err()
+}
+
+/// Re-check the page a tab-addressed action left its tab on. A click, a
+/// script or a history move can land somewhere no target named; off-policy
+/// landings send the tab to `about:blank` and fail the action.
+pub async fn check_landed(
+    action: &Action,
+    result: ActionResult,
+    registry: &SharedRegistry,
+) -> ActionResult {
+    let ActionResult::Ok { data } = &result else {
+        return result;
+    };
+    let Some(url) = data["__ctx_url"].as_str() else {
+        return result;
+    };
+    // Browser error pages (a blocked request) and blank pages are neutral.
+    if url.is_empty() || url.starts_with("chrome-error:") || url.starts_with("about:") {
+        return result;
+    }
+    let (Some(session), Some(tab)) = audit::action_address(action) else {
+        return result;
+    };
+    let violation = match DomainPolicy::current() {
+        Ok(policy) => match policy.check(url) {
+            Ok(()) => return result,
+            Err(v) => v,
+        },
+        Err(e) => return e,
+    };
+    if let Ok((cdp, target_id)) = get_cdp_and_target(registry, &session, &tab).await {
+        let _ = cdp
+            .execute_on_tab(&target_id, "Page.navigate", json!({ "url": "about:blank" }))
+            .await;
+    }
+    violation_to_result(violation)
+}
+
+pub fn violation_to_result(v: Violation) -> ActionResult {
+    let host_label = v.host.as_deref().unwrap_or("<none>");
+    let message = match v.policy {
+        PolicyKind::Blocked => format!(
+            "navigation to {} blocked by guardrails (host '{host_label}' matches blocked_domains)",
+            v.url
+        ),
+        PolicyKind::NotAllowed => format!(
+            "navigation to {} blocked by guardrails (host '{host_label}' is not in allowed_domains)",
+            v.url
+        ),
+    };
+    ActionResult::fatal_with_details(
+        DOMAIN_NOT_ALLOWED,
+        message,
+        "this session is restricted by [guardrails] in config.toml; choose an approved site or update the policy",
+        json!({
+            "url": v.url,
+            "host": v.host,
+            "policy": v.policy.as_str(),
+            "rule": v.rule,
+        }),
+    )
+}
+
+/// What the page reports about a click target before it is clicked.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ElementProbe {
+    /// Approximate accessible name (aria-label, labelledby, text, value, title).
+    pub name: String,
+    /// The element is (or sits inside) a form submit control.
+    pub is_submit: bool,
+    /// Host of the page the element lives on.
+    pub host: Option<String>,
+}
+
+/// Compiled sensitive-action policy. Only built when the guardrail is enabled.
+#[derive(Debug, Clone)]
+pub struct SensitivePolicy {
+    patterns: Vec<Regex>,
+    form_domains: Vec<String>,
+}
+
+impl SensitivePolicy {
+    /// Returns `Ok(None)` when `confirm_sensitive_actions` is off.
+    pub(crate) fn from_config(cfg: &GuardrailsConfig) -> Result<Option<Self>, String> {
+        if !cfg.confirm_sensitive_actions {
+            return Ok(None);
+        }
+        let sources: Vec<String> = if cfg.sensitive_patterns.is_empty() {
+            DEFAULT_SENSITIVE_PATTERNS
+                .iter()
+                .map(|p| p.to_string())
+                .collect()
+        } else {
+            cfg.sensitive_patterns.clone()
+        };
+        let patterns = sources
+            .iter()
+            .map(|p| {
+                Regex::new(&format!("(?i){p}"))
+                    .map_err(|e| format!("invalid guardrails.sensitive_patterns entry '{p}': {e}"))
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(Some(Self {
+            patterns,
+            form_domains: normalize_rules(&cfg.sensitive_form_domains),
+        }))
+    }
+
+    /// Load from `config.toml`. Config or pattern errors surface as a fatal
+    /// result so a broken policy never silently disables the guardrail.
+    pub fn load() -> Result<Option<Self>, ActionResult> {
+        let cfg = config::load_config().map_err(|e| {
+            ActionResult::fatal_with_hint(
+                "INVALID_ARGUMENT",
+                format!("cannot evaluate sensitive-action guardrails: {e}"),
+                "fix config.toml or remove the [guardrails] section",
+            )
+        })?;
+        Self::from_config(&cfg.guardrails).map_err(|e| {
+            ActionResult::fatal_with_hint("INVALID_ARGUMENT", e, "fix [guardrails] in config.toml")
+        })
+    }
+
+    /// Why `probe` counts as a sensitive action, or `None` if it does not.
+    pub fn classify(&self, probe: &ElementProbe) -> Option<String> {
+        let name = probe.name.trim();
+        if !name.is_empty()
+            && let Some(p) = self.patterns.iter().find(|p| p.is_match(name))
+        {
+            return Some(format!(
+                "accessible name '{name}' matches sensitive pattern '{}'",
+                p.as_str().trim_start_matches("(?i)")
+            ));
+        }
+        if probe.is_submit
+            && let Some(host) = probe.host.as_deref()
+            && let Some(rule) = self.form_domains.iter().find(|r| rule_matches(r, host))
+        {
+            return Some(format!(
+                "form submission on '{host}' (matches sensitive_form_domains '{rule}')"
+            ));
+        }
+        None
+    }
+
+    /// Decide whether a click on `selector` may proceed. Returns the reason to
+    /// record when a sensitive click goes ahead on `confirmed`. A target that
+    /// could not be probed is treated as sensitive: the guardrail cannot tell
+    /// what it is, so it must not wave the click through.
+    pub fn gate(
+        &self,
+        selector: &str,
+        probe: Option<&ElementProbe>,
+        confirmed: bool,
+    ) -> Result<Option<String>, ActionResult> {
+        let Some(probe) = probe else {
+            if !confirmed {
+                return Err(unverifiable_target(selector));
+            }
+            return Ok(Some(UNVERIFIED_REASON.to_string()));
+        };
+        let Some(reason) = self.classify(probe) else {
+            return Ok(None);
+        };
+        if !confirmed {
+            return Err(confirmation_required(selector, probe, &reason));
+        }
+        Ok(Some(reason))
+    }
+}
+
+const UNVERIFIED_REASON: &str = "click target could not be inspected";
+
+/// Fatal returned when the guardrail cannot inspect a click target. Shares
+/// `CONFIRMATION_REQUIRED` so interactive callers can confirm and retry.
+pub fn unverifiable_target(selector: &str) -> ActionResult {
+    ActionResult::fatal_with_details(
+        CONFIRMATION_REQUIRED,
+        format!("cannot verify target '{selector}': {UNVERIFIED_REASON}"),
+        "check the selector, or re-run with --confirm-sensitive if the click is intended",
+        json!({
+            "selector": selector,
+            "reason": UNVERIFIED_REASON,
+        }),
+    )
+}
+
+/// Fatal returned when a sensitive action is attempted without confirmation.
+pub fn confirmation_required(selector: &str, probe: &ElementProbe, reason: &str) -> ActionResult {
+    ActionResult::fatal_with_details(
+        CONFIRMATION_REQUIRED,
+        format!("refusing sensitive action on '{selector}': {reason}"),
+        "verify the action is intended, then re-run with --confirm-sensitive",
+        json!({
+            "selector": selector,
+            "accessible_name": probe.name,
+            "host": probe.host,
+            "reason": reason,
+        }),
+    )
+}
+
+pub fn is_confirmation_required(result: &ActionResult) -> bool {
+    matches!(result, ActionResult::Fatal { code, .. } if code == CONFIRMATION_REQUIRED)
+}
+
+/// Mark a blocked action as confirmed so it can be re-sent after interactive
+/// approval. Returns `false` for actions that do not support confirmation.
+pub fn confirm_sensitive(action: &mut Action) -> bool {
+    match action {
+        Action::Click(cmd) => {
+            cmd.confirm_sensitive = true;
+            true
+        }
+        Action::BatchClick(cmd) => {
+            cmd.confirm_sensitive = true;
+            true
+        }
+        Action::Menu(cmd) => {
+            cmd.confirm_sensitive = true;
+            true
+        }
+        Action::Tap(cmd) => {
+            cmd.confirm_sensitive = true;
+            true
+        }
+        _ => false,
+    }
+}
+
+fn normalize_rules(rules: &[String]) -> Vec<String> {
+    rules
+        .iter()
+        .map(|r| r.trim().trim_end_matches('.').to_ascii_lowercase())
+        .filter(|r| !r.is_empty())
+        .collect()
+}
+
+/// `example.com` matches the host and any subdomain; `*.example.com` matches
+/// subdomains only; `*` matches everything.
+fn rule_matches(rule: &str, host: &str) -> bool {
+    if rule == "*" {
+        return true;
+    }
+    if let Some(suffix) = rule.strip_prefix("*.") {
+        return host.len() > suffix.len()
+            && host.ends_with(suffix)
+            && host.as_bytes()[host.len() - suffix.len() - 1] == b'.';
+    }
+    host == rule
+        || (host.len() > rule.len()
+            && host.ends_with(rule)
+            && host.as_bytes()[host.len() - rule.len() - 1] == b'.')
+}
+
+/// Extract the lowercase host from `scheme://[userinfo@]host[:port][/...]`.
+/// Returns `None` for URLs without an authority or with an empty host.
+pub fn url_host(url: &str) -> Option<String> {
+    let (_, rest) = url.split_once("://")?;
+    let authority = rest.split(['/', '?', '#']).next().unwrap_or("");
+    let host_port = authority.rsplit_once('@').map_or(authority, |(_, h)| h);
+    let host = if let Some(v6) = host_port.strip_prefix('[') {
+        v6.split(']').next().unwrap_or("")
+    } else {
+        host_port.split(':').next().unwrap_or("")
+    };
+    let host = host.trim_end_matches('.').to_ascii_lowercase();
+    (!host.is_empty()).then_some(host)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn strings(items: &[&str]) -> Vec<String> {
+        items.iter().map(|s| s.to_string()).collect()
+    }
+
+    fn policy(allowed: &[&str], blocked: &[&str]) -> DomainPolicy {
+        DomainPolicy::from_config(&GuardrailsConfig {
+            allowed_domains: strings(allowed),
+            blocked_domains: strings(blocked),
+            ..GuardrailsConfig::default()
+        })
+    }
+
+    fn sensitive(patterns: &[&str], form_domains: &[&str]) -> SensitivePolicy {
+        SensitivePolicy::from_config(&GuardrailsConfig {
+            confirm_sensitive_actions: true,
+            sensitive_patterns: strings(patterns),
+            sensitive_form_domains: strings(form_domains),
+            ..GuardrailsConfig::default()
+        })
+        .unwrap()
+        .unwrap()
+    }
+
+    fn probe(name: &str, is_submit: bool, host: &str) -> ElementProbe {
+        ElementProbe {
+            name: name.to_string(),
+            is_submit,
+            host: Some(host.to_string()),
+        }
+    }
+
+    #[test]
+    fn url_host_handles_userinfo_port_and_ipv6() {
+        assert_eq!(
+            url_host("https://Example.COM/path").as_deref(),
+            Some("example.com")
+        );
+        assert_eq!(
+            url_host("http://u:p@a.b.c:8080?q").as_deref(),
+            Some("a.b.c")
+        );
+        assert_eq!(url_host("http://[::1]:9222/json").as_deref(), Some("::1"));
+        assert_eq!(
+            url_host("https://example.com./").as_deref(),
+            Some("example.com")
+        );
+        assert_eq!(url_host("file:///etc/passwd"), None);
+        assert_eq!(url_host("about:blank"), None);
+    }
+
+    #[test]
+    fn interceptor_fails_only_off_policy_documents() {
+        let p = policy(&["example.com"], &["ads.example.com"]);
+        assert!(!blocks_document(&p, "Document", "https://www.example.com/"));
+        assert!(blocks_document(&p, "Document", "https://evil.test/landing"));
+        assert!(blocks_document(&p, "Document", "https://ads.example.com/"));
+        // Subresources are the router's and adblock's business, not this check.
+        assert!(!blocks_document(&p, "Script", "https://evil.test/x.js"));
+        assert!(!blocks_document(
+            &policy(&[], &[]),
+            "Document",
+            "https://evil.test/"
+        ));
+    }
+
+    #[test]
+    fn empty_policy_allows_everything() {
+        let p = policy(&[], &[]);
+        assert!(p.check("https://anything.test").is_ok());
+        assert!(p.check("file:///tmp/x.html").is_ok());
+    }
+
+    #[test]
+    fn bare_rule_matches_host_and_subdomains_only() {
+        let p = policy(&["example.com"], &[]);
+        assert!(p.check("example.com").is_ok());
+        assert!(p.check("https://docs.example.com/a").is_ok());
+        let err = p.check("https://badexample.com").unwrap_err();
+        assert_eq!(err.policy, PolicyKind::NotAllowed);
+        assert_eq!(err.host.as_deref(), Some("badexample.com"));
+    }
+
+    #[test]
+    fn wildcard_rule_matches_subdomains_only() {
+        let p = policy(&["*.example.com"], &[]);
+        assert!(p.check("https://a.example.com").is_ok());
+        assert!(p.check("https://example.com").is_err());
+    }
+
+    #[test]
+    fn blocked_takes_precedence_over_allowed() {
+        let p = policy(&["example.com"], &["admin.example.com"]);
+        assert!(p.check("https://www.example.com").is_ok());
+        let err = p.check("https://admin.example.com/users").unwrap_err();
+        assert_eq!(err.policy, PolicyKind::Blocked);
+        assert_eq!(err.rule.as_deref(), Some("admin.example.com"));
+    }
+
+    #[test]
+    fn blocklist_only_allows_unlisted_hosts() {
+        let p = policy(&[], &["evil.test"]);
+        assert!(p.check("https://good.test").is_ok());
+        assert!(p.check("https://x.evil.test").is_err());
+    }
+
+    #[test]
+    fn allowlist_rejects_hostless_urls_but_keeps_about_blank() {
+        let p = policy(&["example.com"], &[]);
+        assert!(p.check("about:blank").is_ok());
+        assert!(p.check("file:///etc/passwd").is_err());
+        assert!(p.check("data:text/plain,hi").is_err());
+    }
+
+    #[test]
+    fn violation_maps_to_structured_fatal() {
+        let p = policy(&["example.com"], &[]);
+        let result = violation_to_result(p.check("https://other.test").unwrap_err());
+        match result {
+            ActionResult::Fatal { code, details, .. } => {
+                assert_eq!(code, DOMAIN_NOT_ALLOWED);
+                let details = details.unwrap();
+                assert_eq!(details["host"], "other.test");
+                assert_eq!(details["policy"], "allowed_domains");
+            }
+            other => panic!("expected fatal, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn sensitive_policy_is_disabled_by_default() {
+        let cfg = GuardrailsConfig::default();
+        assert!(SensitivePolicy::from_config(&cfg).unwrap().is_none());
+    }
+
+    #[test]
+    fn default_patterns_flag_payment_and_deletion_names() {
+        let p = sensitive(&[], &[]);
+        assert!(p.classify(&probe("Pay now", false, "shop.test")).is_some());
+        assert!(
+            p.classify(&probe("DELETE repository", false, "x.test"))
+                .is_some()
+        );
+        assert!(
+            p.classify(&probe("Transfer funds", false, "bank.test"))
+                .is_some()
+        );
+        assert!(
+            p.classify(&probe("Payment history", false, "bank.test"))
+                .is_none()
+        );
+        assert!(p.classify(&probe("Search", true, "shop.test")).is_none());
+    }
+
+    #[test]
+    fn custom_patterns_replace_defaults() {
+        let p = sensitive(&["^archive$"], &[]);
+        assert!(p.classify(&probe("Archive", false, "a.test")).is_some());
+        assert!(p.classify(&probe("Delete", false, "a.test")).is_none());
+    }
+
+    #[test]
+    fn submit_on_configured_domain_is_sensitive() {
+        let p = sensitive(&[], &["bank.test"]);
+        let reason = p
+            .classify(&probe("Continue", true, "www.bank.test"))
+            .unwrap();
+        assert!(reason.contains("form submission"));
+        assert!(
+            p.classify(&probe("Continue", false, "www.bank.test"))
+                .is_none()
+        );
+        assert!(p.classify(&probe("Continue", true, "other.test")).is_none());
+    }
+
+    #[test]
+    fn invalid_sensitive_pattern_is_an_error() {
+        let cfg = GuardrailsConfig {
+            confirm_sensitive_actions: true,
+            sensitive_patterns: strings(&["(unclosed"]),
+            ..GuardrailsConfig::default()
+        };
+        assert!(SensitivePolicy::from_config(&cfg).is_err());
+    }
+
+    #[test]
+    fn unprobed_target_needs_confirmation() {
+        let p = sensitive(&[], &[]);
+        let err = p.gate("#gone", None, false).unwrap_err();
+        assert!(is_confirmation_required(&err));
+        match err {
+            ActionResult::Fatal { details, .. } => {
+                assert_eq!(details.unwrap()["selector"], "#gone");
+            }
+            other => panic!("expected fatal, got {other:?}"),
+        }
+        let reason = p.gate("#gone", None, true).unwrap();
+        assert_eq!(reason.as_deref(), Some(UNVERIFIED_REASON));
+    }
+
+    #[test]
+    fn gate_passes_ordinary_targets_and_holds_sensitive_ones() {
+        let p = sensitive(&[], &[]);
+        let search = probe("Search", false, "shop.test");
+        assert_eq!(p.gate("#q", Some(&search), false).unwrap(), None);
+        let pay = probe("Pay now", false, "shop.test");
+        assert!(is_confirmation_required(
+            &p.gate("#pay", Some(&pay), false).unwrap_err()
+        ));
+        assert!(p.gate("#pay", Some(&pay), true).unwrap().is_some());
+    }
+}
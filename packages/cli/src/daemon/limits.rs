@@ -0,0 +1,170 @@
+//! Session resource limits from the `[limits]` config section.
+//!
+//! Runs on the daemon housekeeping tick. Sessions idle past
+//! `max_session_idle_minutes`, or whose local browser process tree exceeds
+//! `max_session_memory_mb` of resident memory, are closed through the normal
+//! `browser close` path so profiles and artifacts are cleaned up the same way.
+
+use std::collections::HashMap;
+use std::time::Duration;
+
+use tracing::{info, warn};
+
+use crate::action_result::ActionResult;
+use crate::browser::session::close;
+use crate::config::{self, LimitsConfig};
+
+use super::registry::{SessionState, SharedRegistry};
+
+/// Close every session that breaks the configured limits.
+pub async fn enforce(registry: &SharedRegistry) {
+    let limits = config::session_limits();
+    if limits.is_empty() {
+        return;
+    }
+
+    let candidates: Vec<(String, Duration, Option<u32>)> = {
+        let reg = registry.lock().await;
+        reg.list()
+            .into_iter()
+            .filter(|e| e.status == SessionState::Running && !reg.in_handoff(e.id.as_str()))
+            .map(|e| {
+                (
+                    e.id.as_str().to_string(),
+                    e.last_used.elapsed(),
+                    e.chrome_process.as_ref().map(|c| c.id()),
+                )
+            })
+            .collect()
+    };
+    if candidates.is_empty() {
+        return;
+    }
+
+    let rss = if limits.max_session_memory_mb.is_some()
+        && candidates.iter().any(|(_, _, pid)| pid.is_some())
+    {
+        tokio::task::spawn_blocking(process_table)
+            .await
+            .ok()
+            .flatten()
+    } else {
+        None
+    };
+
+    for (session_id, idle, pid) in candidates {
+        let memory_kb = match (&rss, pid) {
+            (Some(table), Some(pid)) => Some(tree_rss_kb(table, pid)),
+            _ => None,
+        };
+        let Some(reason) = violation(&limits, idle, memory_kb) else {
+            continue;
+        };
+        info!("closing session {session_id}: {reason}");
+        let cmd = close::Cmd {
+            session: session_id.clone(),
+        };
+        if let ActionResult::Fatal { code, message, .. } = close::execute(&cmd, registry).await {
+            warn!("failed to close session {session_id} ({code}): {message}");
+        }
+    }
+}
+
+/// Why a session should be closed, or `None` if it is within limits.
+fn violation(limits: &LimitsConfig, idle: Duration, memory_kb: Option<u64>) -> Option<String> {
+    if let Some(max) = limits.max_session_idle_minutes
+        && idle > Duration::from_secs(max * 60)
+    {
+        return Some(format!(
+            "idle for {}m, over max_session_idle_minutes = {max}",
+            idle.as_secs() / 60
+        ));
+    }
+    if let (Some(max), Some(kb)) = (limits.max_session_memory_mb, memory_kb)
+        && kb / 1024 > max
+    {
+        return Some(format!(
+            "browser uses {}MB, over max_session_memory_mb = {max}",
+            kb / 1024
+        ));
+    }
+    None
+}
+
+/// `pid → (ppid, rss_kb)` for every process, read from `ps`.
+#[cfg(unix)]
+fn process_table() -> Option<HashMap<u32, (u32, u64)>> {
+    let out = std::process::Command::new("ps")
+        .args(["-A", "-o", "pid=", "-o", "ppid=", "-o", "rss="])
+        .output()
+        .ok()?;
+    out.status
+        .success()
+        .then(|| parse_ps(&String::from_utf8_lossy(&out.stdout)))
+}
+
+/// Memory ceilings need a process table; Windows sessions are only subject to
+/// the idle limit.
+#[cfg(not(unix))]
+fn process_table() -> Option<HashMap<u32, (u32, u64)>> {
+    None
+}
+
+fn parse_ps(text: &str) -> HashMap<u32, (u32, u64)> {
+    text.lines()
+        .filter_map(|line| {
+            let mut cols = line.split_whitespace().map(str::parse::<u64>);
+            let pid = cols.next()?.ok()?;
+            let ppid = cols.next()?.ok()?;
+            let rss = cols.next()?.ok()?;
+            Some((pid as u32, (ppid as u32, rss)))
+        })
+        .collect()
+}
+
+/// Resident memory of `root` plus all its descendants (Chrome renderers,
+/// GPU and utility processes are children of the browser process).
+fn tree_rss_kb(table: &HashMap<u32, (u32, u64)>, root: u32) -> u64 {
+    let mut children: HashMap<u32, Vec<u32>> = HashMap::new();
+    for (&pid, &(ppid, _)) in table {
+        children.entry(ppid).or_default().push(pid);
+    }
+    let mut total = 0;
+    let mut stack = vec![root];
+    while let Some(pid) = stack.pop() {
+        if let Some(&(_, rss)) = table.get(&pid) {
+            total += rss;
+        }
+        if let Some(kids) = children.get(&pid) {
+            stack.extend(kids.iter().copied().filter(|&k| k != pid));
+        }
+    }
+    total
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn tree_rss_sums_descendants_only() {
+        let table = parse_ps(
+            "    1     0   100\n  500     1  2048\n  501   500  1024\n  502   501   512\n  600     1  9999\n",
+        );
+        assert_eq!(tree_rss_kb(&table, 500), 2048 + 1024 + 512);
+        assert_eq!(tree_rss_kb(&table, 4242), 0);
+    }
+
+    #[test]
+    fn violation_checks_idle_then_memory() {
+        let limits = LimitsConfig {
+            max_session_idle_minutes: Some(10),
+            max_session_memory_mb: Some(1),
+        };
+        assert!(violation(&limits, Duration::from_secs(60), Some(512)).is_none());
+        let idle = violation(&limits, Duration::from_secs(11 * 60), None).unwrap();
+        assert!(idle.contains("idle for 11m"), "{idle}");
+        let mem = violation(&limits, Duration::from_secs(0), Some(4096)).unwrap();
+        assert!(mem.contains("4MB"), "{mem}");
+    }
+}
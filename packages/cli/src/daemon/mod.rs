@@ -1,9 +1,24 @@
+pub mod adblock;
 pub mod bridge;
 pub mod browser;
+pub mod budget;
 pub mod cdp;
 pub mod cdp_session;
 pub mod chrome_reaper;
+pub mod devtools;
+pub mod documents;
+pub mod downloads;
+pub mod emulation;
+pub mod flow_recording;
+pub mod guardrails;
+pub mod limits;
+pub mod progress;
+pub mod proxy;
+pub mod recording;
 pub mod registry;
 pub mod router;
+pub mod routes;
+pub mod screencast;
 pub mod server;
 pub mod session;
+pub mod trace;
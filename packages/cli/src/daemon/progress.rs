@@ -0,0 +1,101 @@
+//! Progress events for long-running commands.
+//!
+//! A request sent with `stream: true` is routed inside [`scope`]. Poll loops
+//! (the `wait` commands) report through a [`Reporter`], and each event is
+//! written to the client as an `{"id", "event"}` frame ahead of the final
+//! response. Outside a scope every call is a no-op.
+
+use std::future::Future;
+use std::time::{Duration, Instant};
+
+use serde_json::{Value, json};
+
+/// Minimum gap between two progress events from the same command.
+pub const PROGRESS_INTERVAL: Duration = Duration::from_secs(1);
+
+type Sink = Box<dyn Fn(Value) + Send + Sync>;
+
+tokio::task_local! {
+    static SINK: Sink;
+}
+
+/// Run `f` with progress events delivered to `sink`.
+pub async fn scope<F: Future>(sink: impl Fn(Value) + Send + Sync + 'static, f: F) -> F::Output {
+    SINK.scope(Box::new(sink), f).await
+}
+
+/// Whether the current request asked for progress events.
+pub fn is_streaming() -> bool {
+    SINK.try_with(|_| ()).is_ok()
+}
+
+/// Rate-limits progress events for one command invocation.
+pub struct Reporter {
+    start: Instant,
+    next_at: Duration,
+}
+
+impl Reporter {
+    pub fn new(start: Instant) -> Self {
+        Self {
+            start,
+            next_at: PROGRESS_INTERVAL,
+        }
+    }
+
+    /// True at most once per [`PROGRESS_INTERVAL`], and never when the request
+    /// is not streaming — callers gate any extra probing on this.
+    pub fn due(&mut self) -> bool {
+        if !is_streaming() {
+            return false;
+        }
+        let elapsed = self.start.elapsed();
+        if elapsed < self.next_at {
+            return false;
+        }
+        self.next_at = elapsed + PROGRESS_INTERVAL;
+        true
+    }
+
+    /// Emit a `progress` event; `elapsed_ms` is filled in.
+    pub fn report(&self, kind: &str, mut fields: Value) {
+        fields["kind"] = json!(kind);
+        fields["elapsed_ms"] = json!(self.start.elapsed().as_millis() as u64);
+        let event = json!({ "type": "progress", "data": fields });
+        let _ = SINK.try_with(|sink| sink(event));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::{Arc, Mutex};
+
+    use super::*;
+
+    #[tokio::test]
+    async fn reporter_emits_only_inside_a_scope() {
+        let mut outside = Reporter::new(Instant::now() - PROGRESS_INTERVAL);
+        assert!(!outside.due());
+
+        let events = Arc::new(Mutex::new(Vec::new()));
+        let sink_events = events.clone();
+        scope(
+            move |event| sink_events.lock().unwrap().push(event),
+            async {
+                let mut reporter = Reporter::new(Instant::now() - PROGRESS_INTERVAL);
+                assert!(reporter.due());
+                reporter.report("element", json!({ "match_count": 0 }));
+                // The next event is not due for another interval.
+                assert!(!reporter.due());
+            },
+        )
+        .await;
+
+        let events = events.lock().unwrap();
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0]["type"], "progress");
+        assert_eq!(events[0]["data"]["kind"], "element");
+        assert_eq!(events[0]["data"]["match_count"], 0);
+        assert!(events[0]["data"]["elapsed_ms"].as_u64().unwrap() >= 1000);
+    }
+}
@@ -0,0 +1,346 @@
+//! Session recording for offline replay.
+//!
+//! `browser start --record-session <dir>` registers a recording directory for
+//! the new session. After every routed action the daemon appends snapshot,
+//! screenshot and navigation events to `<dir>/events.jsonl` and copies the
+//! artifacts they produced next to it, so a run can be inspected later with
+//! `actionbook session replay <dir>` without touching the live site.
+
+use std::fs::{self, OpenOptions};
+use std::io::{BufRead, BufReader, Write as _};
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+use serde_json::{Value, json};
+
+use crate::action::Action;
+use crate::action_result::ActionResult;
+use crate::audit;
+
+pub const COMMAND_NAME_REPLAY: &str = "session replay";
+
+pub const EVENTS_FILE: &str = "events.jsonl";
+pub const MANIFEST_FILE: &str = "session.json";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum EventKind {
+    Snapshot,
+    Screenshot,
+    Navigation,
+}
+
+/// One recorded event (a line of `events.jsonl`).
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RecordedEvent {
+    pub seq: u64,
+    pub ts: String,
+    pub kind: EventKind,
+    pub command: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub session_id: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tab_id: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub url: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub title: Option<String>,
+    /// Artifact file name, relative to the recording directory.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub artifact: Option<String>,
+    pub ok: bool,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error_code: Option<String>,
+}
+
+/// Which event (if any) an action/result pair produces, and the artifact file
+/// it wrote that should be copied into the recording.
+pub fn classify(action: &Action, result: &ActionResult) -> Option<(EventKind, Option<PathBuf>)> {
+    let data = match result {
+        ActionResult::Ok { data } => Some(data),
+        _ => None,
+    };
+    let path_at = |pointer: &str| {
+        data.and_then(|d| d.pointer(pointer))
+            .and_then(|v| v.as_str())
+            .map(PathBuf::from)
+    };
+    match action {
+        Action::Snapshot(_) => Some((EventKind::Snapshot, path_at("/path"))),
+        Action::Screenshot(_) => Some((EventKind::Screenshot, path_at("/artifact/path"))),
+        Action::Goto(_)
+        | Action::Back(_)
+        | Action::Forward(_)
+        | Action::Reload(_)
+        | Action::TraceRedirects(_)
+        | Action::NewTab(_)
+        | Action::WaitNavigation(_) => Some((EventKind::Navigation, None)),
+        // A click only counts as navigation when it changed the URL.
+        Action::Click(_)
+            if data
+                .and_then(|d| d.pointer("/changed/url_changed"))
+                .and_then(|v| v.as_bool())
+                == Some(true) =>
+        {
+            Some((EventKind::Navigation, None))
+        }
+        _ => None,
+    }
+}
+
+fn page_url_title(data: Option<&Value>) -> (Option<String>, Option<String>) {
+    let Some(data) = data else {
+        return (None, None);
+    };
+    let first = |keys: &[&str]| {
+        keys.iter()
+            .find_map(|k| data.pointer(k).and_then(|v| v.as_str()))
+            .filter(|s| !s.is_empty())
+            .map(String::from)
+    };
+    (
+        first(&["/__ctx_url", "/to_url", "/post_url", "/tab/url", "/url"]),
+        first(&["/__ctx_title", "/title", "/post_title", "/tab/title"]),
+    )
+}
+
+fn next_seq(dir: &Path) -> u64 {
+    fs::File::open(dir.join(EVENTS_FILE))
+        .map(|f| BufReader::new(f).lines().count() as u64)
+        .unwrap_or(0)
+        + 1
+}
+
+/// Create the recording directory and write its manifest.
+pub fn begin(dir: &Path, session_id: &str, mode: Option<&str>) -> std::io::Result<()> {
+    fs::create_dir_all(dir)?;
+    let manifest = json!({
+        "session_id": session_id,
+        "mode": mode,
+        "started_at": crate::utils::time::now_rfc3339(),
+        "version": crate::BUILD_VERSION,
+    });
+    fs::write(
+        dir.join(MANIFEST_FILE),
+        serde_json::to_vec_pretty(&manifest).map_err(std::io::Error::other)?,
+    )
+}
+
+/// Record the event produced by `action` (if any). Returns the event written.
+pub fn record(
+    dir: &Path,
+    action: &Action,
+    result: &ActionResult,
+) -> std::io::Result<Option<RecordedEvent>> {
+    let Some((kind, source)) = classify(action, result) else {
+        return Ok(None);
+    };
+    fs::create_dir_all(dir)?;
+    let seq = next_seq(dir);
+
+    let artifact = match source {
+        Some(src) if src.is_file() => {
+            let ext = src.extension().and_then(|e| e.to_str()).unwrap_or("bin");
+            let kind_label = match kind {
+                EventKind::Snapshot => "snapshot",
+                EventKind::Screenshot => "screenshot",
+                EventKind::Navigation => "navigation",
+            };
+            let name = format!("{seq:04}-{kind_label}.{ext}");
+            fs::copy(&src, dir.join(&name))?;
+            Some(name)
+        }
+        _ => None,
+    };
+
+    let data = match result {
+        ActionResult::Ok { data } => Some(data),
+        _ => None,
+    };
+    let (url, title) = page_url_title(data);
+    let (session_id, tab_id) = audit::action_address(action);
+    let event = RecordedEvent {
+        seq,
+        ts: crate::utils::time::now_rfc3339(),
+        kind,
+        command: action.command_name().to_string(),
+        session_id,
+        tab_id,
+        url,
+        title,
+        artifact,
+        ok: result.is_ok(),
+        error_code: match result {
+            ActionResult::Fatal { code, .. } => Some(code.clone()),
+            _ => None,
+        },
+    };
+
+    let mut line = serde_json::to_string(&event).map_err(std::io::Error::other)?;
+    line.push('\n');
+    OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(dir.join(EVENTS_FILE))?
+        .write_all(line.as_bytes())?;
+    Ok(Some(event))
+}
+
+/// Read all events from a recording directory, in order.
+pub fn load(dir: &Path) -> std::io::Result<Vec<RecordedEvent>> {
+    let reader = BufReader::new(fs::File::open(dir.join(EVENTS_FILE))?);
+    let mut events = Vec::new();
+    for line in reader.lines() {
+        let line = line?;
+        if let Ok(event) = serde_json::from_str::<RecordedEvent>(&line) {
+            events.push(event);
+        }
+    }
+    Ok(events)
+}
+
+/// `actionbook session replay <dir>`: the event timeline, or a single event
+/// (with its artifact path and, for snapshots, the snapshot text) when `event`
+/// is given.
+pub fn execute_replay(dir: &Path, event: Option<u64>) -> ActionResult {
+    let events = match load(dir) {
+        Ok(events) => events,
+        Err(e) => {
+            return ActionResult::fatal_with_hint(
+                "RECORDING_NOT_FOUND",
+                format!("failed to read recording at {}: {e}", dir.display()),
+                "pass a directory created with `browser start --record-session <dir>`",
+            );
+        }
+    };
+    let manifest = fs::read(dir.join(MANIFEST_FILE))
+        .ok()
+        .and_then(|b| serde_json::from_slice::<Value>(&b).ok());
+
+    let Some(seq) = event else {
+        return ActionResult::ok(json!({
+            "dir": dir.display().to_string(),
+            "session": manifest,
+            "count": events.len(),
+            "events": events,
+        }));
+    };
+
+    let Some(index) = events.iter().position(|e| e.seq == seq) else {
+        return ActionResult::fatal_with_hint(
+            "EVENT_NOT_FOUND",
+            format!("recording has no event {seq}"),
+            format!("choose an event between 1 and {}", events.len()),
+        );
+    };
+    let ev = &events[index];
+    let artifact_path = ev.artifact.as_ref().map(|a| dir.join(a));
+    let content = match (ev.kind, &artifact_path) {
+        (EventKind::Snapshot, Some(path)) => fs::read_to_string(path).ok(),
+        _ => None,
+    };
+    ActionResult::ok(json!({
+        "dir": dir.display().to_string(),
+        "count": events.len(),
+        "event": ev,
+        "artifact_path": artifact_path.map(|p| p.display().to_string()),
+        "content": content,
+        "prev": index.checked_sub(1).map(|i| events[i].seq),
+        "next": events.get(index + 1).map(|e| e.seq),
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::browser::{interaction, navigation, observation};
+
+    fn goto() -> Action {
+        Action::Goto(navigation::goto::Cmd {
+            url: "https://example.com".to_string(),
+            session: "s1".to_string(),
+            tab: "t1".to_string(),
+            wait_until: Default::default(),
+            timeout: None,
+            dismiss_consent: false,
+            max_download_bytes: None,
+        })
+    }
+
+    #[test]
+    fn records_navigation_and_skips_other_actions() {
+        let tmp = tempfile::tempdir().unwrap();
+        let ok = ActionResult::ok(json!({ "to_url": "https://example.com/", "title": "Example" }));
+        let ev = record(tmp.path(), &goto(), &ok).unwrap().unwrap();
+        assert_eq!(ev.seq, 1);
+        assert_eq!(ev.kind, EventKind::Navigation);
+        assert_eq!(ev.url.as_deref(), Some("https://example.com/"));
+        assert_eq!(ev.title.as_deref(), Some("Example"));
+
+        let hover = Action::Hover(interaction::hover::Cmd {
+            selector: "#a".to_string(),
+            session: "s1".to_string(),
+            tab: "t1".to_string(),
+            position: "center".to_string(),
+        });
+        assert!(record(tmp.path(), &hover, &ok).unwrap().is_none());
+
+        let failed = ActionResult::fatal("NAVIGATION_FAILED", "boom");
+        let ev = record(tmp.path(), &goto(), &failed).unwrap().unwrap();
+        assert_eq!(ev.seq, 2);
+        assert!(!ev.ok);
+        assert_eq!(ev.error_code.as_deref(), Some("NAVIGATION_FAILED"));
+
+        assert_eq!(load(tmp.path()).unwrap().len(), 2);
+
+        let ActionResult::Ok { data } = execute_replay(tmp.path(), Some(2)) else {
+            panic!("expected ok");
+        };
+        assert_eq!(data["event"]["seq"], 2);
+        assert_eq!(data["prev"], 1);
+        assert!(data["next"].is_null());
+        assert!(matches!(
+            execute_replay(tmp.path(), Some(9)),
+            ActionResult::Fatal { ref code, .. } if code == "EVENT_NOT_FOUND"
+        ));
+    }
+
+    #[test]
+    fn copies_snapshot_artifact_into_recording() {
+        let tmp = tempfile::tempdir().unwrap();
+        let src = tmp.path().join("snapshot_1.yaml");
+        fs::write(&src, "- button \"Go\"").unwrap();
+        let rec_dir = tmp.path().join("rec");
+
+        let action = Action::Snapshot(
+            serde_json::from_value::<observation::snapshot::Cmd>(
+                json!({ "session_id": "s1", "tab_id": "t1" }),
+            )
+            .unwrap(),
+        );
+        let result = ActionResult::ok(json!({ "path": src.to_string_lossy() }));
+        let ev = record(&rec_dir, &action, &result).unwrap().unwrap();
+        assert_eq!(ev.artifact.as_deref(), Some("0001-snapshot.yaml"));
+        assert_eq!(
+            fs::read_to_string(rec_dir.join("0001-snapshot.yaml")).unwrap(),
+            "- button \"Go\""
+        );
+    }
+
+    #[test]
+    fn click_is_navigation_only_when_url_changed() {
+        let click = Action::Click(
+            serde_json::from_value::<interaction::click::Cmd>(
+                json!({ "selectors": ["a"], "session_id": "s1", "tab_id": "t1" }),
+            )
+            .unwrap(),
+        );
+        let same = ActionResult::ok(json!({ "changed": { "url_changed": false } }));
+        let moved = ActionResult::ok(json!({ "changed": { "url_changed": true } }));
+        assert!(classify(&click, &same).is_none());
+        assert_eq!(
+            classify(&click, &moved).map(|(k, _)| k),
+            Some(EventKind::Navigation)
+        );
+    }
+}
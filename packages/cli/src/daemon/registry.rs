@@ -1,5 +1,6 @@
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::fmt;
+use std::path::PathBuf;
 use std::process::Child;
 use std::sync::Arc;
 use tokio::sync::Mutex;
@@ -59,6 +60,9 @@ pub struct SessionEntry {
     pub id: SessionId,
     pub mode: Mode,
     pub headless: bool,
+    /// Headless implementation Chrome was seen running after launch; `None`
+    /// for a headed or unverified session.
+    pub headless_mode: Option<crate::types::HeadlessMode>,
     pub stealth: bool,
     /// Stealth user-agent string — needed when attaching new tabs so they get the same stealth injection.
     pub stealth_ua: Option<String>,
@@ -67,6 +71,8 @@ pub struct SessionEntry {
     pub cdp_port: Option<u16>,
     pub ws_url: String,
     pub tabs: Vec<TabEntry>,
+    /// Tab selected with `browser switch`; `--tab active` runs on it.
+    pub active_tab: Option<TabId>,
     pub chrome_process: Option<Child>,
     /// Win32 Job Object that owns Chrome's main process and all its helpers.
     /// `TerminateJobObject` kills the entire process group atomically on close.
@@ -86,6 +92,27 @@ pub struct SessionEntry {
     pub next_tab_id: u32,
     /// Maximum number of network requests tracked per tab (ring buffer cap).
     pub max_tracked_requests: usize,
+    /// User filter lists for `--adblock` sessions (`None` when adblock is off).
+    pub adblock_lists: Option<Vec<String>>,
+    /// Session-wide `--max-download-bytes` for navigation and capture commands.
+    pub max_download_bytes: Option<u64>,
+    /// `--download-dir` the session was started with.
+    pub download_dir: Option<String>,
+    /// When the session was reserved; reported as its age by `browser status`.
+    pub created_at: std::time::Instant,
+    /// When the last command addressed to this session finished. Drives the
+    /// `[limits] max_session_idle_minutes` reaper.
+    pub last_used: std::time::Instant,
+    /// Launch-time `--window-position` / `--window-size`, carried over restarts.
+    pub window_position: Option<String>,
+    pub window_size: Option<String>,
+    /// Launch-time `--gpu`, carried over restarts.
+    pub gpu: Option<crate::types::GpuMode>,
+    /// Device preset emulated on every tab (`--device` / `browser emulate`),
+    /// carried over restarts.
+    pub device: Option<String>,
+    /// Forwarder rotating the session's `--proxy` upstreams; stops on drop.
+    pub proxy: Option<crate::daemon::proxy::ProxyForwarder>,
 }
 
 impl Drop for SessionEntry {
@@ -110,6 +137,7 @@ impl SessionEntry {
             id,
             mode,
             headless,
+            headless_mode: None,
             stealth,
             stealth_ua: None,
             profile,
@@ -117,6 +145,7 @@ impl SessionEntry {
             cdp_port: None,
             ws_url: String::new(),
             tabs: Vec::new(),
+            active_tab: None,
             chrome_process: None,
             #[cfg(windows)]
             job_object: None,
@@ -127,6 +156,16 @@ impl SessionEntry {
             provider_session: None,
             next_tab_id: 1,
             max_tracked_requests: crate::daemon::cdp_session::MAX_TRACKED_REQUESTS,
+            adblock_lists: None,
+            max_download_bytes: None,
+            download_dir: None,
+            created_at: std::time::Instant::now(),
+            last_used: std::time::Instant::now(),
+            window_position: None,
+            window_size: None,
+            gpu: None,
+            device: None,
+            proxy: None,
         }
     }
 
@@ -134,6 +173,16 @@ impl SessionEntry {
         self.tabs.len()
     }
 
+    /// The tab that `--tab active` stands for: the switched-to tab while it
+    /// is still open, otherwise the first tab.
+    pub fn active_tab_id(&self) -> Option<&TabId> {
+        self.active_tab
+            .as_ref()
+            .and_then(|id| self.tabs.iter().find(|t| t.id == *id))
+            .or_else(|| self.tabs.first())
+            .map(|t| &t.id)
+    }
+
     /// Append a tab with an auto-assigned short ID (t1, t2, ...).
     pub fn push_tab(&mut self, native_id: String, url: String, title: String) {
         let short_id = format!("t{}", self.next_tab_id);
@@ -155,6 +204,13 @@ impl SessionEntry {
         url: String,
         title: String,
     ) -> Result<String, ActionResult> {
+        if custom_id == crate::types::ACTIVE_TAB {
+            return Err(ActionResult::fatal_with_hint(
+                "INVALID_ARGUMENT",
+                format!("tab ID '{custom_id}' is reserved for the session's active tab"),
+                "choose a different --tab (--set-tab-id) value or omit it for auto-assignment",
+            ));
+        }
         if self.tabs.iter().any(|t| t.id.0 == custom_id) {
             return Err(ActionResult::fatal_with_hint(
                 "TAB_ID_CONFLICT",
@@ -179,6 +235,10 @@ pub struct SessionRegistry {
     ref_caches: HashMap<String, RefCache>,
     /// Last known cursor position per tab. Key: "session_id\0tab_id"
     cursor_positions: HashMap<String, (f64, f64)>,
+    /// `--record-session` directory per session. Key: session_id
+    recordings: HashMap<String, PathBuf>,
+    /// Sessions currently handed off to a human via `browser handoff`.
+    handoffs: HashSet<String>,
     /// Extension bridge state. `None` until first lazy `ensure_bridge` call;
     /// stays `Some` afterward (status field within tracks Listening/Failed).
     bridge_state: Option<SharedBridgeState>,
@@ -199,6 +259,8 @@ impl SessionRegistry {
             sessions: HashMap::new(),
             ref_caches: HashMap::new(),
             cursor_positions: HashMap::new(),
+            recordings: HashMap::new(),
+            handoffs: HashSet::new(),
             bridge_state: None,
             bridge_init_lock: Arc::new(Mutex::new(())),
         }
@@ -378,6 +440,26 @@ impl SessionRegistry {
         self.sessions.values().collect()
     }
 
+    /// Mark a session as just used.
+    pub fn touch(&mut self, session_id: &str) {
+        if let Some(entry) = self.sessions.get_mut(session_id) {
+            entry.last_used = std::time::Instant::now();
+        }
+    }
+
+    /// Mark a session as handed off; `false` if it already was.
+    pub fn begin_handoff(&mut self, session_id: &str) -> bool {
+        self.handoffs.insert(session_id.to_string())
+    }
+
+    pub fn end_handoff(&mut self, session_id: &str) {
+        self.handoffs.remove(session_id);
+    }
+
+    pub fn in_handoff(&self, session_id: &str) -> bool {
+        self.handoffs.contains(session_id)
+    }
+
     /// Returns `true` if any session is in Starting or Running state.
     pub fn has_active_sessions(&self) -> bool {
         self.sessions.values().any(|entry| entry.status.is_active())
@@ -437,6 +519,21 @@ impl SessionRegistry {
         let key = format!("{}\0{}", session_id, tab_id);
         self.cursor_positions.get(&key).copied()
     }
+
+    /// Register the `--record-session` directory for a session.
+    pub fn set_recording_dir(&mut self, session_id: &str, dir: PathBuf) {
+        self.recordings.insert(session_id.to_string(), dir);
+    }
+
+    /// Recording directory of a session, if it was started with `--record-session`.
+    pub fn recording_dir(&self, session_id: &str) -> Option<&PathBuf> {
+        self.recordings.get(session_id)
+    }
+
+    /// Stop recording a session (call on session close).
+    pub fn clear_recording_dir(&mut self, session_id: &str) {
+        self.recordings.remove(session_id);
+    }
 }
 
 pub type SharedRegistry = Arc<Mutex<SessionRegistry>>;
@@ -715,6 +812,29 @@ mod tests {
         assert_eq!(entry.tabs.len(), 1, "no tab should be added on conflict");
     }
 
+    #[test]
+    fn active_tab_id_follows_switch_and_falls_back_to_first_tab() {
+        let mut entry = SessionEntry::starting(
+            SessionId::new("test-session").unwrap(),
+            Mode::Local,
+            true,
+            true,
+            "profile".to_string(),
+        );
+        assert!(entry.active_tab_id().is_none());
+
+        entry.push_tab("n1".to_string(), String::new(), String::new());
+        entry.push_tab("n2".to_string(), String::new(), String::new());
+        assert_eq!(entry.active_tab_id().unwrap().0, "t1");
+
+        entry.active_tab = Some(TabId("t2".to_string()));
+        assert_eq!(entry.active_tab_id().unwrap().0, "t2");
+
+        // A switched-to tab that was closed no longer counts.
+        entry.tabs.retain(|t| t.id.0 != "t2");
+        assert_eq!(entry.active_tab_id().unwrap().0, "t1");
+    }
+
     #[test]
     fn push_tab_with_id_does_not_affect_auto_counter() {
         let mut entry = SessionEntry::starting(
@@ -3,35 +3,117 @@ use crate::action_result::ActionResult;
 use crate::browser;
 use crate::extension;
 
+use super::budget;
+use super::guardrails;
 use super::registry::SharedRegistry;
 
 /// Route an action to the appropriate handler.
 pub async fn route(action: &Action, registry: &SharedRegistry) -> ActionResult {
+    if let Some(denied) = guardrails::enforce(action) {
+        return denied;
+    }
+    if let Some(paused) = browser::interaction::handoff::paused(action, registry).await {
+        return paused;
+    }
+    guardrails::arm_interceptor(action, registry).await;
+    let result = dispatch(action, registry).await;
+    guardrails::check_landed(action, result, registry).await
+}
+
+async fn dispatch(action: &Action, registry: &SharedRegistry) -> ActionResult {
     match action {
         Action::StartSession(cmd) => browser::session::start::execute(cmd, registry).await,
         Action::ListSessions(cmd) => browser::session::list::execute(cmd, registry).await,
         Action::SessionStatus(cmd) => browser::session::status::execute(cmd, registry).await,
         Action::Close(cmd) => browser::session::close::execute(cmd, registry).await,
         Action::Restart(cmd) => browser::session::restart::execute(cmd, registry).await,
-        Action::Goto(cmd) => browser::navigation::goto::execute(cmd, registry).await,
-        Action::Back(cmd) => browser::navigation::back::execute(cmd, registry).await,
-        Action::Forward(cmd) => browser::navigation::forward::execute(cmd, registry).await,
-        Action::Reload(cmd) => browser::navigation::reload::execute(cmd, registry).await,
+        Action::Emulate(cmd) => browser::session::emulate::execute(cmd, registry).await,
+        Action::EmulateNetwork(cmd) => {
+            browser::session::emulate_network::execute(cmd, registry).await
+        }
+        Action::StateBundle(cmd) => {
+            browser::session::state_bundle::execute_bundle(cmd, registry).await
+        }
+        Action::StateImport(cmd) => {
+            browser::session::state_bundle::execute_import(cmd, registry).await
+        }
+        Action::Goto(cmd) => {
+            budget::guard(
+                registry,
+                &cmd.session,
+                &cmd.tab,
+                cmd.max_download_bytes,
+                browser::navigation::goto::execute(cmd, registry),
+            )
+            .await
+        }
+        Action::Back(cmd) => {
+            budget::guard(
+                registry,
+                &cmd.session,
+                &cmd.tab,
+                None,
+                browser::navigation::back::execute(cmd, registry),
+            )
+            .await
+        }
+        Action::Forward(cmd) => {
+            budget::guard(
+                registry,
+                &cmd.session,
+                &cmd.tab,
+                None,
+                browser::navigation::forward::execute(cmd, registry),
+            )
+            .await
+        }
+        Action::Reload(cmd) => {
+            budget::guard(
+                registry,
+                &cmd.session,
+                &cmd.tab,
+                None,
+                browser::navigation::reload::execute(cmd, registry),
+            )
+            .await
+        }
+        Action::Fetch(cmd) => browser::navigation::fetch::execute(cmd, registry).await,
+        Action::TraceRedirects(cmd) => {
+            browser::navigation::trace_redirects::execute(cmd, registry).await
+        }
         Action::ListTabs(cmd) => browser::tab::list::execute(cmd, registry).await,
         Action::NewTab(cmd) => browser::tab::open::execute(cmd, registry).await,
         Action::BatchOpen(cmd) => browser::tab::batch_open::execute(cmd, registry).await,
         Action::CloseTab(cmd) => browser::tab::close::execute(cmd, registry).await,
+        Action::Switch(cmd) => browser::tab::switch::execute(cmd, registry).await,
+        Action::Window(cmd) => browser::tab::window::execute(cmd, registry).await,
         Action::BatchSnapshot(cmd) => {
             browser::observation::batch_snapshot::execute(cmd, registry).await
         }
         Action::Snapshot(cmd) => browser::observation::snapshot::execute(cmd, registry).await,
-        Action::Screenshot(cmd) => browser::observation::screenshot::execute(cmd, registry).await,
+        Action::Screenshot(cmd) => {
+            budget::guard(
+                registry,
+                &cmd.session,
+                &cmd.tab,
+                cmd.max_download_bytes,
+                browser::observation::screenshot::execute(cmd, registry),
+            )
+            .await
+        }
         Action::Title(cmd) => browser::observation::title::execute(cmd, registry).await,
+        Action::Meta(cmd) => browser::observation::meta::execute(cmd, registry).await,
+        Action::Response(cmd) => browser::observation::response::execute(cmd, registry).await,
+        Action::Assert(cmd) => browser::observation::assert::execute(cmd, registry).await,
+        Action::Feeds(cmd) => browser::observation::feeds::execute(cmd, registry).await,
+        Action::Images(cmd) => browser::observation::images::execute(cmd, registry).await,
+        Action::Table(cmd) => browser::observation::table::execute(cmd, registry).await,
         Action::Url(cmd) => browser::observation::url::execute(cmd, registry).await,
         Action::Viewport(cmd) => browser::observation::viewport::execute(cmd, registry).await,
         Action::Html(cmd) => browser::observation::html::execute(cmd, registry).await,
         Action::Text(cmd) => browser::observation::text::execute(cmd, registry).await,
         Action::Value(cmd) => browser::observation::value::execute(cmd, registry).await,
+        Action::Options(cmd) => browser::observation::options::execute(cmd, registry).await,
         Action::Attr(cmd) => browser::observation::attr::execute(cmd, registry).await,
         Action::Attrs(cmd) => browser::observation::attrs::execute(cmd, registry).await,
         Action::Box(cmd) => browser::observation::r#box::execute(cmd, registry).await,
@@ -42,7 +124,26 @@ pub async fn route(action: &Action, registry: &SharedRegistry) -> ActionResult {
         Action::InspectPoint(cmd) => {
             browser::observation::inspect_point::execute(cmd, registry).await
         }
-        Action::Pdf(cmd) => browser::observation::pdf::execute(cmd, registry).await,
+        Action::Pdf(cmd) => {
+            budget::guard(
+                registry,
+                &cmd.session,
+                &cmd.tab,
+                cmd.max_download_bytes,
+                browser::observation::pdf::execute(cmd, registry),
+            )
+            .await
+        }
+        Action::Save(cmd) => {
+            budget::guard(
+                registry,
+                &cmd.session,
+                &cmd.tab,
+                cmd.max_download_bytes,
+                browser::observation::save::execute(cmd, registry),
+            )
+            .await
+        }
         Action::LogsConsole(cmd) => {
             browser::observation::logs_console::execute(cmd, registry).await
         }
@@ -59,29 +160,112 @@ pub async fn route(action: &Action, registry: &SharedRegistry) -> ActionResult {
         Action::NetworkHarStop(cmd) => {
             browser::observation::network_har::execute_stop(cmd, registry).await
         }
+        Action::NetworkRouteAdd(cmd) => {
+            browser::observation::network_route::execute_add(cmd, registry).await
+        }
+        Action::NetworkRouteList(cmd) => {
+            browser::observation::network_route::execute_list(cmd, registry).await
+        }
+        Action::NetworkRouteRemove(cmd) => {
+            browser::observation::network_route::execute_remove(cmd, registry).await
+        }
         Action::CookiesList(cmd) => browser::cookies::list::execute(cmd, registry).await,
         Action::CookiesGet(cmd) => browser::cookies::get::execute(cmd, registry).await,
         Action::CookiesSet(cmd) => browser::cookies::set::execute(cmd, registry).await,
         Action::CookiesDelete(cmd) => browser::cookies::delete::execute(cmd, registry).await,
         Action::CookiesClear(cmd) => browser::cookies::clear::execute(cmd, registry).await,
+        Action::CookiesExport(cmd) => browser::cookies::export::execute(cmd, registry).await,
+        Action::CookiesImport(cmd) => browser::cookies::import::execute(cmd, registry).await,
+        Action::DownloadsList(cmd) => browser::downloads::list::execute(cmd, registry).await,
+        Action::DownloadsWait(cmd) => browser::downloads::wait::execute(cmd, registry).await,
         Action::StorageList(cmd) => browser::storage::list::execute(cmd, registry).await,
         Action::StorageGet(cmd) => browser::storage::get::execute(cmd, registry).await,
         Action::StorageSet(cmd) => browser::storage::set::execute(cmd, registry).await,
         Action::StorageDelete(cmd) => browser::storage::delete::execute(cmd, registry).await,
         Action::StorageClear(cmd) => browser::storage::clear::execute(cmd, registry).await,
+        Action::IndexedDbList(cmd) => browser::indexeddb::list::execute(cmd, registry).await,
+        Action::IndexedDbDump(cmd) => browser::indexeddb::dump::execute(cmd, registry).await,
         Action::WaitElement(cmd) => browser::wait::element::execute(cmd, registry).await,
         Action::WaitNavigation(cmd) => browser::wait::navigation::execute(cmd, registry).await,
         Action::WaitNetworkIdle(cmd) => browser::wait::network_idle::execute(cmd, registry).await,
         Action::WaitCondition(cmd) => browser::wait::condition::execute(cmd, registry).await,
-        Action::Eval(cmd) => browser::interaction::eval::execute(cmd, registry).await,
-        Action::Click(cmd) => browser::interaction::click::execute(cmd, registry).await,
-        Action::BatchClick(cmd) => browser::interaction::batch_click::execute(cmd, registry).await,
+        Action::WaitText(cmd) => browser::wait::text::execute(cmd, registry).await,
+        Action::WaitUrl(cmd) => browser::wait::url::execute(cmd, registry).await,
+        Action::Eval(cmd) => {
+            budget::guard(
+                registry,
+                &cmd.session,
+                &cmd.tab,
+                None,
+                browser::interaction::eval::execute(cmd, registry),
+            )
+            .await
+        }
+        Action::Click(cmd) => {
+            budget::guard(
+                registry,
+                &cmd.session,
+                &cmd.tab,
+                None,
+                browser::interaction::click::execute(cmd, registry),
+            )
+            .await
+        }
+        Action::BatchClick(cmd) => {
+            budget::guard(
+                registry,
+                &cmd.session,
+                &cmd.tab,
+                None,
+                browser::interaction::batch_click::execute(cmd, registry),
+            )
+            .await
+        }
         Action::Hover(cmd) => browser::interaction::hover::execute(cmd, registry).await,
+        Action::Menu(cmd) => {
+            budget::guard(
+                registry,
+                &cmd.session,
+                &cmd.tab,
+                None,
+                browser::interaction::menu::execute(cmd, registry),
+            )
+            .await
+        }
+        Action::Handoff(cmd) => browser::interaction::handoff::execute(cmd, registry).await,
         Action::Focus(cmd) => browser::interaction::focus::execute(cmd, registry).await,
-        Action::Press(cmd) => browser::interaction::press::execute(cmd, registry).await,
+        Action::TabTo(cmd) => browser::interaction::tabto::execute(cmd, registry).await,
+        Action::Press(cmd) => {
+            budget::guard(
+                registry,
+                &cmd.session,
+                &cmd.tab,
+                None,
+                browser::interaction::press::execute(cmd, registry),
+            )
+            .await
+        }
         Action::Type(cmd) => browser::interaction::type_text::execute(cmd, registry).await,
         Action::Fill(cmd) => browser::interaction::fill::execute(cmd, registry).await,
         Action::Select(cmd) => browser::interaction::select::execute(cmd, registry).await,
+        Action::CanvasClickColor(cmd) => {
+            browser::interaction::canvas::execute_click_color(cmd, registry).await
+        }
+        Action::CanvasDraw(cmd) => browser::interaction::canvas::execute_draw(cmd, registry).await,
+        Action::RecordStart(cmd) => {
+            browser::interaction::record::execute_start(cmd, registry).await
+        }
+        Action::RecordStop(cmd) => browser::interaction::record::execute_stop(cmd, registry).await,
+        Action::RecordVideoStart(cmd) => {
+            browser::observation::record_video::execute_start(cmd, registry).await
+        }
+        Action::RecordVideoStop(cmd) => {
+            browser::observation::record_video::execute_stop(cmd, registry).await
+        }
+        Action::TraceStart(cmd) => browser::observation::trace::execute_start(cmd, registry).await,
+        Action::TraceStop(cmd) => browser::observation::trace::execute_stop(cmd, registry).await,
+        Action::Date(cmd) => browser::interaction::date::execute(cmd, registry).await,
+        Action::Slide(cmd) => browser::interaction::slide::execute(cmd, registry).await,
         Action::Drag(cmd) => browser::interaction::drag::execute(cmd, registry).await,
         Action::Upload(cmd) => browser::interaction::upload::execute(cmd, registry).await,
         Action::MouseMove(cmd) => browser::interaction::mouse_move::execute(cmd, registry).await,
@@ -89,6 +273,19 @@ pub async fn route(action: &Action, registry: &SharedRegistry) -> ActionResult {
             browser::interaction::cursor_position::execute(cmd, registry).await
         }
         Action::Scroll(cmd) => browser::interaction::scroll::execute(cmd, registry).await,
+        Action::Tap(cmd) => {
+            budget::guard(
+                registry,
+                &cmd.session,
+                &cmd.tab,
+                None,
+                browser::interaction::touch::execute_tap(cmd, registry),
+            )
+            .await
+        }
+        Action::Swipe(cmd) => browser::interaction::touch::execute_swipe(cmd, registry).await,
+        Action::Pinch(cmd) => browser::interaction::touch::execute_pinch(cmd, registry).await,
         Action::ExtensionStatus(cmd) => extension::status::execute_daemon(cmd, registry).await,
+        Action::ExtensionVersion(cmd) => extension::version::execute_daemon(cmd, registry).await,
     }
 }
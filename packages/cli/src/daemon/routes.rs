@@ -0,0 +1,484 @@
+//! Request interception for `browser network route`, `--adblock` and the
+//! `[guardrails]` domain lists.
+//!
+//! Each tab has one Fetch-domain interceptor at most: every request is paused,
+//! document requests to hosts the domain policy rejects are failed, the rest
+//! are checked against the tab's routes (first match wins), then against the
+//! session's adblock list if one is loaded, and finally continued untouched.
+//! A route either blocks the request, answers it from a local fixture, or
+//! rewrites its request headers before letting it through.
+//!
+//! Route patterns are URL globs: `*` matches any run of characters and
+//! everything else is literal, so `?` in a query string needs no escaping.
+
+use std::sync::{Arc, RwLock};
+
+use base64::Engine as _;
+use serde_json::{Value, json};
+
+use super::adblock::{Adblock, Request};
+use super::cdp_session::CdpSession;
+use super::guardrails::{self, DomainPolicy, url_host};
+
+/// What a matching route does with the paused request.
+#[derive(Debug, Clone)]
+pub enum RouteAction {
+    /// Fail the request with `BlockedByClient`.
+    Block,
+    /// Answer without touching the network.
+    Fulfill {
+        status: u16,
+        content_type: String,
+        headers: Vec<(String, String)>,
+        body: Vec<u8>,
+        /// Fixture path, kept for `route list`.
+        source: String,
+    },
+    /// Continue with these request headers set; an empty value removes the
+    /// header.
+    Headers(Vec<(String, String)>),
+}
+
+impl RouteAction {
+    pub fn kind(&self) -> &'static str {
+        match self {
+            RouteAction::Block => "block",
+            RouteAction::Fulfill { .. } => "fulfill",
+            RouteAction::Headers(_) => "headers",
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Route {
+    pub id: String,
+    pub pattern: String,
+    pub action: RouteAction,
+    pub hits: u64,
+}
+
+impl Route {
+    pub fn to_json(&self) -> Value {
+        let mut v = json!({
+            "id": self.id,
+            "pattern": self.pattern,
+            "action": self.action.kind(),
+            "hits": self.hits,
+        });
+        match &self.action {
+            RouteAction::Block => {}
+            RouteAction::Fulfill {
+                status,
+                content_type,
+                body,
+                source,
+                ..
+            } => {
+                v["status"] = json!(status);
+                v["content_type"] = json!(content_type);
+                v["file"] = json!(source);
+                v["bytes"] = json!(body.len());
+            }
+            RouteAction::Headers(headers) => {
+                v["headers"] = headers
+                    .iter()
+                    .map(|(k, val)| (k.clone(), json!(val)))
+                    .collect::<serde_json::Map<_, _>>()
+                    .into();
+            }
+        }
+        v
+    }
+}
+
+/// One tab's routes plus whether its interceptor is already running.
+#[derive(Debug, Default)]
+pub struct RouteTable {
+    routes: Vec<Route>,
+    next_id: u64,
+    intercepting: bool,
+}
+
+pub type SharedRoutes = Arc<RwLock<RouteTable>>;
+
+impl RouteTable {
+    /// Append a route and return its ID (`r1`, `r2`, …).
+    pub fn add(&mut self, pattern: String, action: RouteAction) -> String {
+        self.next_id += 1;
+        let id = format!("r{}", self.next_id);
+        self.routes.push(Route {
+            id: id.clone(),
+            pattern,
+            action,
+            hits: 0,
+        });
+        id
+    }
+
+    pub fn remove(&mut self, id: &str) -> Option<Route> {
+        let pos = self.routes.iter().position(|r| r.id == id)?;
+        Some(self.routes.remove(pos))
+    }
+
+    /// Remove every route and return how many there were.
+    pub fn clear(&mut self) -> usize {
+        std::mem::take(&mut self.routes).len()
+    }
+
+    pub fn routes(&self) -> &[Route] {
+        &self.routes
+    }
+
+    /// Take over another table's routes and ID counter, as when a tab is
+    /// re-attached on a new connection. Whether this table intercepts is
+    /// left alone: the old interceptor died with its connection.
+    pub fn restore(&mut self, from: &RouteTable) {
+        self.routes = from.routes.clone();
+        self.next_id = self.next_id.max(from.next_id);
+    }
+
+    /// First route whose pattern matches `url`, with its hit counter bumped.
+    fn hit(&mut self, url: &str) -> Option<&Route> {
+        let route = self
+            .routes
+            .iter_mut()
+            .find(|r| glob_match(&r.pattern, url))?;
+        route.hits += 1;
+        Some(route)
+    }
+}
+
+/// Match `text` against a glob where `*` is the only wildcard.
+pub fn glob_match(pattern: &str, text: &str) -> bool {
+    let mut parts = pattern.split('*');
+    let first = parts.next().unwrap_or("");
+    let Some(mut rest) = text.strip_prefix(first) else {
+        return false;
+    };
+    let tail: Vec<&str> = parts.collect();
+    let Some((last, middle)) = tail.split_last() else {
+        // No `*` at all: the pattern must be the whole URL.
+        return rest.is_empty();
+    };
+    for part in middle {
+        match rest.find(part) {
+            Some(i) => rest = &rest[i + part.len()..],
+            None => return false,
+        }
+    }
+    rest.len() >= last.len() && rest.ends_with(last)
+}
+
+/// Content type for a fixture, from its extension.
+pub fn guess_content_type(path: &str) -> &'static str {
+    let ext = std::path::Path::new(path)
+        .extension()
+        .and_then(|e| e.to_str())
+        .map(|e| e.to_ascii_lowercase())
+        .unwrap_or_default();
+    match ext.as_str() {
+        "json" => "application/json",
+        "html" | "htm" => "text/html; charset=utf-8",
+        "js" | "mjs" => "text/javascript",
+        "css" => "text/css",
+        "txt" => "text/plain; charset=utf-8",
+        "xml" => "application/xml",
+        "svg" => "image/svg+xml",
+        "png" => "image/png",
+        "jpg" | "jpeg" => "image/jpeg",
+        "gif" => "image/gif",
+        "webp" => "image/webp",
+        _ => "application/octet-stream",
+    }
+}
+
+/// Overlay `overrides` on the paused request's headers, in CDP's
+/// `[{name, value}]` form. Header names compare case-insensitively.
+fn merge_headers(original: Option<&Value>, overrides: &[(String, String)]) -> Value {
+    let mut headers: Vec<(String, String)> = original
+        .and_then(|v| v.as_object())
+        .map(|obj| {
+            obj.iter()
+                .map(|(k, v)| (k.clone(), v.as_str().unwrap_or("").to_string()))
+                .collect()
+        })
+        .unwrap_or_default();
+    for (name, value) in overrides {
+        headers.retain(|(k, _)| !k.eq_ignore_ascii_case(name));
+        if !value.is_empty() {
+            headers.push((name.clone(), value.clone()));
+        }
+    }
+    headers
+        .into_iter()
+        .map(|(name, value)| json!({ "name": name, "value": value }))
+        .collect()
+}
+
+/// CDP reply for a request that matched `route`.
+fn route_reply(route: &Route, request_id: &str, params: &Value) -> (&'static str, Value) {
+    match &route.action {
+        RouteAction::Block => (
+            "Fetch.failRequest",
+            json!({ "requestId": request_id, "errorReason": "BlockedByClient" }),
+        ),
+        RouteAction::Fulfill {
+            status,
+            content_type,
+            headers,
+            body,
+            ..
+        } => {
+            let mut response_headers =
+                vec![json!({ "name": "Content-Type", "value": content_type })];
+            response_headers.extend(
+                headers
+                    .iter()
+                    .filter(|(k, _)| !k.eq_ignore_ascii_case("content-type"))
+                    .map(|(name, value)| json!({ "name": name, "value": value })),
+            );
+            (
+                "Fetch.fulfillRequest",
+                json!({
+                    "requestId": request_id,
+                    "responseCode": status,
+                    "responseHeaders": response_headers,
+                    "body": base64::engine::general_purpose::STANDARD.encode(body),
+                }),
+            )
+        }
+        RouteAction::Headers(overrides) => (
+            "Fetch.continueRequest",
+            json!({
+                "requestId": request_id,
+                "headers": merge_headers(params.pointer("/request/headers"), overrides),
+            }),
+        ),
+    }
+}
+
+/// Start intercepting requests on one attached tab, unless its interceptor is
+/// already running. `adblock` is consulted for requests no route matched.
+///
+/// The event loop never awaits CDP round-trips itself: each paused request is
+/// answered from its own task so the subscriber channel keeps draining (a full
+/// channel would drop the subscription and leave requests paused forever).
+pub async fn install(
+    cdp: &CdpSession,
+    target_id: &str,
+    session_id: &str,
+    routes: SharedRoutes,
+    adblock: Option<Arc<Adblock>>,
+) -> Result<(), crate::error::CliError> {
+    {
+        let mut table = routes.write().unwrap_or_else(|e| e.into_inner());
+        if table.intercepting {
+            return Ok(());
+        }
+        table.intercepting = true;
+    }
+    let mut rx = cdp
+        .subscribe_events(session_id, "Fetch.requestPaused")
+        .await;
+    if let Err(e) = cdp
+        .execute(
+            "Fetch.enable",
+            json!({ "patterns": [{ "urlPattern": "*", "requestStage": "Request" }] }),
+            Some(session_id),
+        )
+        .await
+    {
+        routes
+            .write()
+            .unwrap_or_else(|e| e.into_inner())
+            .intercepting = false;
+        return Err(e);
+    }
+
+    let cdp = cdp.clone();
+    let target_id = target_id.to_string();
+    let session_id = session_id.to_string();
+    tokio::spawn(async move {
+        let mut page_host: Option<String> = None;
+        while let Some(event) = rx.recv().await {
+            let params = &event["params"];
+            let Some(request_id) = params["requestId"].as_str().map(String::from) else {
+                continue;
+            };
+            let url = params
+                .pointer("/request/url")
+                .and_then(|v| v.as_str())
+                .unwrap_or("");
+            let resource_type = params["resourceType"].as_str().unwrap_or("Other");
+            let is_document = resource_type == "Document";
+            let is_main_frame = params["frameId"].as_str() == Some(target_id.as_str());
+            if is_document && is_main_frame {
+                page_host = url_host(url);
+            }
+
+            // Fails closed like the router: an unreadable policy blocks pages.
+            let denied = is_document
+                && DomainPolicy::current().map_or(true, |p| {
+                    guardrails::blocks_document(&p, resource_type, url)
+                });
+            if denied {
+                tracing::debug!("guardrails: blocked document {url}");
+                let cdp = cdp.clone();
+                let session_id = session_id.clone();
+                let reply = json!({ "requestId": request_id, "errorReason": "BlockedByClient" });
+                tokio::spawn(async move {
+                    let _ = cdp
+                        .execute("Fetch.failRequest", reply, Some(&session_id))
+                        .await;
+                });
+                continue;
+            }
+
+            let routed = {
+                let mut table = routes.write().unwrap_or_else(|e| e.into_inner());
+                table.hit(url).map(|route| {
+                    tracing::debug!("route {}: {} {url}", route.id, route.action.kind());
+                    route_reply(route, &request_id, params)
+                })
+            };
+
+            // The tab's own top-level document is never ad-blocked.
+            let blocked = match (&routed, &adblock) {
+                (None, Some(adblock)) if !(is_document && is_main_frame) => {
+                    let referer_host = params
+                        .pointer("/request/headers/Referer")
+                        .and_then(|v| v.as_str())
+                        .and_then(url_host);
+                    let source_host = page_host.clone().or(referer_host);
+                    adblock
+                        .list
+                        .matches(&Request {
+                            url,
+                            resource_type,
+                            source_host: source_host.as_deref(),
+                            is_subframe: is_document,
+                        })
+                        .map(|rule| {
+                            adblock.note_blocked();
+                            tracing::debug!("adblock: blocked {url} ({rule})");
+                        })
+                        .is_some()
+                }
+                _ => false,
+            };
+
+            let (method, reply) = match routed {
+                Some(reply) => reply,
+                None if blocked => (
+                    "Fetch.failRequest",
+                    json!({ "requestId": request_id, "errorReason": "BlockedByClient" }),
+                ),
+                None => ("Fetch.continueRequest", json!({ "requestId": request_id })),
+            };
+            let cdp = cdp.clone();
+            let session_id = session_id.clone();
+            tokio::spawn(async move {
+                let _ = cdp.execute(method, reply, Some(&session_id)).await;
+            });
+        }
+    });
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn glob_star_matches_any_run() {
+        assert!(glob_match(
+            "*/api/users*",
+            "https://x.test/api/users?page=2"
+        ));
+        assert!(glob_match("https://x.test/*.png", "https://x.test/a/b.png"));
+        assert!(glob_match("*", "https://anything"));
+        assert!(!glob_match("*/api/users*", "https://x.test/api/posts"));
+        assert!(!glob_match(
+            "https://x.test/*.png",
+            "https://x.test/a.png.js"
+        ));
+    }
+
+    #[test]
+    fn glob_without_star_is_exact_and_question_mark_is_literal() {
+        assert!(glob_match("https://x.test/a?b=1", "https://x.test/a?b=1"));
+        assert!(!glob_match("https://x.test/a?b=1", "https://x.test/aXb=1"));
+        assert!(!glob_match("https://x.test/a", "https://x.test/ab"));
+    }
+
+    #[test]
+    fn glob_does_not_reuse_prefix_for_suffix() {
+        assert!(!glob_match("ab*ba", "aba"));
+        assert!(glob_match("ab*ba", "abba"));
+    }
+
+    #[test]
+    fn first_matching_route_wins_and_counts_hits() {
+        let mut table = RouteTable::default();
+        let first = table.add("*/api/*".into(), RouteAction::Block);
+        table.add("*".into(), RouteAction::Headers(vec![]));
+        assert_eq!(table.hit("https://x.test/api/a").unwrap().id, first);
+        assert_eq!(table.hit("https://x.test/index").unwrap().id, "r2");
+        assert_eq!(table.routes()[0].hits, 1);
+
+        assert!(table.remove(&first).is_some());
+        assert_eq!(table.hit("https://x.test/api/a").unwrap().id, "r2");
+        // IDs are never reused after a removal.
+        assert_eq!(table.add("*".into(), RouteAction::Block), "r3");
+        assert_eq!(table.clear(), 2);
+    }
+
+    #[test]
+    fn header_overrides_replace_case_insensitively_and_empty_removes() {
+        let original = json!({ "Accept": "*/*", "X-Debug": "1" });
+        let merged = merge_headers(
+            Some(&original),
+            &[
+                ("accept".into(), "application/json".into()),
+                ("x-debug".into(), String::new()),
+                ("Authorization".into(), "Bearer t".into()),
+            ],
+        );
+        assert_eq!(
+            merged,
+            json!([
+                { "name": "accept", "value": "application/json" },
+                { "name": "Authorization", "value": "Bearer t" },
+            ])
+        );
+    }
+
+    #[test]
+    fn fulfill_reply_sets_status_type_and_base64_body() {
+        let route = Route {
+            id: "r1".into(),
+            pattern: "*".into(),
+            action: RouteAction::Fulfill {
+                status: 201,
+                content_type: "application/json".into(),
+                headers: vec![("X-Mock".into(), "yes".into())],
+                body: b"{}".to_vec(),
+                source: "/tmp/a.json".into(),
+            },
+            hits: 0,
+        };
+        let (method, reply) = route_reply(&route, "req-1", &json!({}));
+        assert_eq!(method, "Fetch.fulfillRequest");
+        assert_eq!(reply["responseCode"], 201);
+        assert_eq!(reply["body"], "e30=");
+        assert_eq!(reply["responseHeaders"][0]["value"], "application/json");
+        assert_eq!(reply["responseHeaders"][1]["name"], "X-Mock");
+    }
+
+    #[test]
+    fn content_type_guess_by_extension() {
+        assert_eq!(guess_content_type("users.JSON"), "application/json");
+        assert_eq!(guess_content_type("page.html"), "text/html; charset=utf-8");
+        assert_eq!(guess_content_type("blob"), "application/octet-stream");
+    }
+}
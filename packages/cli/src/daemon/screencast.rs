@@ -0,0 +1,287 @@
+//! Recording a tab as a video.
+//!
+//! `browser record-video start` turns on `Page.startScreencast` and writes
+//! every `Page.screencastFrame` it receives to a frame directory next to the
+//! output, acknowledging each one so Chrome keeps sending. Chrome only sends
+//! a frame when the page changes, so each frame's arrival time is kept too.
+//! `browser record-video stop` writes an ffmpeg concat list that shows every
+//! frame until the next one arrived and, when `ffmpeg` is on PATH, encodes
+//! it into the output video. Without ffmpeg the frames stay on disk and the
+//! command to encode them is returned instead.
+
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+use std::time::Instant;
+
+use base64::Engine;
+use serde_json::json;
+
+use super::cdp_session::CdpSession;
+use crate::error::CliError;
+
+/// Event carrying one screencast frame.
+pub const FRAME_EVENT: &str = "Page.screencastFrame";
+
+/// Name of the concat list written into the frame directory on stop.
+pub const CONCAT_LIST: &str = "frames.txt";
+
+/// A tab's video recording in progress.
+#[derive(Debug)]
+pub struct Screencast {
+    /// The video `record-video stop` encodes to.
+    pub out: PathBuf,
+    /// Where the JPEG frames are written.
+    pub frames_dir: PathBuf,
+    started: Instant,
+    /// Frame file names and the milliseconds since start each arrived at.
+    frames: Vec<(String, u64)>,
+    stopped: bool,
+}
+
+pub type SharedScreencast = Arc<Mutex<Screencast>>;
+
+impl Screencast {
+    pub fn new(out: PathBuf) -> Self {
+        let frames_dir = frames_dir(&out);
+        Self {
+            out,
+            frames_dir,
+            started: Instant::now(),
+            frames: Vec::new(),
+            stopped: false,
+        }
+    }
+
+    pub fn frames(&self) -> usize {
+        self.frames.len()
+    }
+
+    pub fn elapsed_ms(&self) -> u64 {
+        self.started.elapsed().as_millis() as u64
+    }
+
+    /// Stop accepting frames; the listener task exits on its next event.
+    pub fn stop(&mut self) {
+        self.stopped = true;
+    }
+
+    /// The concat list for the frames captured so far, ending at `end_ms`.
+    pub fn concat_list(&self, end_ms: u64) -> String {
+        concat_list(&self.frames, end_ms)
+    }
+}
+
+/// `session.webm` → `session.frames/`, beside the video.
+pub fn frames_dir(out: &Path) -> PathBuf {
+    out.with_extension("frames")
+}
+
+/// An ffmpeg concat-demuxer script showing each frame until the next one
+/// arrived and the last one until `end_ms`. The last file is listed twice:
+/// the demuxer ignores the final `duration` otherwise.
+pub fn concat_list(frames: &[(String, u64)], end_ms: u64) -> String {
+    let mut list = String::from("ffconcat version 1.0\n");
+    for (i, (file, at)) in frames.iter().enumerate() {
+        let next = frames.get(i + 1).map_or(end_ms, |(_, t)| *t);
+        // A frame shorter than one millisecond still needs a positive
+        // duration or ffmpeg drops it.
+        let ms = next.saturating_sub(*at).max(1);
+        list.push_str(&format!(
+            "file '{file}'\nduration {}.{:03}\n",
+            ms / 1000,
+            ms % 1000
+        ));
+    }
+    if let Some((file, _)) = frames.last() {
+        list.push_str(&format!("file '{file}'\n"));
+    }
+    list
+}
+
+/// ffmpeg arguments encoding the concat list `list` to `out`, with a codec
+/// picked from the output's extension. Screencast frames can have odd
+/// dimensions, which yuv420p does not allow, so they are rounded down.
+pub fn ffmpeg_args(list: &Path, out: &Path) -> Vec<String> {
+    let mut args: Vec<String> = [
+        "-y",
+        "-loglevel",
+        "error",
+        "-f",
+        "concat",
+        "-safe",
+        "0",
+        "-i",
+    ]
+    .iter()
+    .map(|s| s.to_string())
+    .collect();
+    args.push(list.to_string_lossy().into_owned());
+    args.extend(
+        [
+            "-vf",
+            "scale=trunc(iw/2)*2:trunc(ih/2)*2",
+            "-pix_fmt",
+            "yuv420p",
+        ]
+        .iter()
+        .map(|s| s.to_string()),
+    );
+    let ext = out
+        .extension()
+        .and_then(|e| e.to_str())
+        .map(|e| e.to_ascii_lowercase());
+    match ext.as_deref() {
+        Some("webm") => args.extend(
+            ["-c:v", "libvpx-vp9", "-b:v", "0", "-crf", "32"]
+                .iter()
+                .map(|s| s.to_string()),
+        ),
+        Some("mp4") | Some("mov") => args.extend(["-c:v", "libx264"].iter().map(|s| s.to_string())),
+        _ => {}
+    }
+    args.push(out.to_string_lossy().into_owned());
+    args
+}
+
+/// The ffmpeg invocation as a shell command line, for when it has to be run
+/// by hand.
+pub fn ffmpeg_command_line(args: &[String]) -> String {
+    let mut line = String::from("ffmpeg");
+    for arg in args {
+        line.push(' ');
+        if arg
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || "-_./:=".contains(c))
+        {
+            line.push_str(arg);
+        } else {
+            line.push('\'');
+            line.push_str(&arg.replace('\'', r"'\''"));
+            line.push('\'');
+        }
+    }
+    line
+}
+
+/// Start the tab's screencast and feed its frames into `recording`.
+pub async fn install(
+    cdp: &CdpSession,
+    target_id: &str,
+    cdp_session_id: &str,
+    recording: SharedScreencast,
+    quality: u8,
+) -> Result<(), CliError> {
+    let dir = recording
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .frames_dir
+        .clone();
+    std::fs::create_dir_all(&dir)
+        .map_err(|e| CliError::Internal(format!("failed to create {}: {e}", dir.display())))?;
+
+    // Subscribe before starting so the first frame is not missed.
+    let mut frames = cdp.subscribe_events(cdp_session_id, FRAME_EVENT).await;
+    cdp.execute_on_tab(
+        target_id,
+        "Page.startScreencast",
+        json!({ "format": "jpeg", "quality": quality, "everyNthFrame": 1 }),
+    )
+    .await?;
+
+    let cdp = cdp.clone();
+    let target_id = target_id.to_string();
+    tokio::spawn(async move {
+        while let Some(event) = frames.recv().await {
+            let params = &event["params"];
+            // Chrome stops sending until the previous frame is acknowledged.
+            if let Some(session_id) = params["sessionId"].as_i64() {
+                let _ = cdp
+                    .execute_on_tab(
+                        &target_id,
+                        "Page.screencastFrameAck",
+                        json!({ "sessionId": session_id }),
+                    )
+                    .await;
+            }
+            let Some(bytes) = params["data"]
+                .as_str()
+                .and_then(|d| base64::engine::general_purpose::STANDARD.decode(d).ok())
+            else {
+                continue;
+            };
+            let mut rec = recording.lock().unwrap_or_else(|e| e.into_inner());
+            if rec.stopped {
+                break;
+            }
+            let file = format!("frame-{:06}.jpg", rec.frames.len() + 1);
+            if std::fs::write(rec.frames_dir.join(&file), bytes).is_ok() {
+                let at = rec.elapsed_ms();
+                rec.frames.push((file, at));
+            }
+        }
+    });
+    Ok(())
+}
+
+/// Encode the concat list in `dir` to `out` with ffmpeg. `Ok(false)` when
+/// ffmpeg is not installed.
+pub async fn encode(dir: &Path, out: &Path) -> Result<bool, String> {
+    let Ok(ffmpeg) = which::which("ffmpeg") else {
+        return Ok(false);
+    };
+    let output = tokio::process::Command::new(ffmpeg)
+        .args(ffmpeg_args(&dir.join(CONCAT_LIST), out))
+        .output()
+        .await
+        .map_err(|e| format!("failed to run ffmpeg: {e}"))?;
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(format!("ffmpeg failed: {}", stderr.trim()));
+    }
+    Ok(true)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn concat_list_holds_each_frame_until_the_next() {
+        let frames = vec![
+            ("frame-000001.jpg".to_string(), 0),
+            ("frame-000002.jpg".to_string(), 1250),
+            ("frame-000003.jpg".to_string(), 1250),
+        ];
+        assert_eq!(
+            concat_list(&frames, 4000),
+            "ffconcat version 1.0\n\
+             file 'frame-000001.jpg'\nduration 1.250\n\
+             file 'frame-000002.jpg'\nduration 0.001\n\
+             file 'frame-000003.jpg'\nduration 2.750\n\
+             file 'frame-000003.jpg'\n"
+        );
+        assert_eq!(concat_list(&[], 10), "ffconcat version 1.0\n");
+    }
+
+    #[test]
+    fn ffmpeg_args_pick_a_codec_for_the_container() {
+        let list = Path::new("/tmp/s.frames/frames.txt");
+        let webm = ffmpeg_args(list, Path::new("/tmp/s.webm"));
+        assert!(webm.windows(2).any(|w| w == ["-c:v", "libvpx-vp9"]));
+        assert_eq!(webm.last().unwrap(), "/tmp/s.webm");
+        let mp4 = ffmpeg_args(list, Path::new("/tmp/s.MP4"));
+        assert!(mp4.windows(2).any(|w| w == ["-c:v", "libx264"]));
+        let gif = ffmpeg_args(list, Path::new("/tmp/s.gif"));
+        assert!(!gif.iter().any(|a| a == "-c:v"));
+
+        assert_eq!(
+            frames_dir(Path::new("/tmp/s.webm")),
+            Path::new("/tmp/s.frames")
+        );
+        let line = ffmpeg_command_line(&webm);
+        assert!(
+            line.starts_with("ffmpeg -y -loglevel error -f concat -safe 0 -i /tmp/s.frames/frames.txt -vf 'scale=trunc(iw/2)*2:trunc(ih/2)*2'"),
+            "{line}"
+        );
+    }
+}
@@ -1,5 +1,5 @@
 use std::fs::OpenOptions;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::time::{Duration, Instant};
 #[cfg(windows)]
 use tokio::net::TcpListener;
@@ -7,9 +7,16 @@ use tokio::net::TcpListener;
 use tokio::net::UnixListener;
 use tracing::{error, info, warn};
 
+use super::devtools;
+use super::limits;
+use super::progress;
+use super::recording;
 use super::registry::{SharedRegistry, new_shared_registry};
 use super::router;
+use crate::action::Action;
 use crate::action_result::ActionResult;
+use crate::audit;
+use crate::browser::tab::switch::resolve_tab;
 use crate::config;
 use crate::utils::wire;
 
@@ -143,6 +150,127 @@ pub fn send_sigterm(_pid: i32) -> bool {
     false
 }
 
+/// Ask the daemon `pid` to shut down gracefully (close sessions, remove its
+/// state files). Returns `true` if the request was delivered.
+#[cfg(unix)]
+pub fn request_shutdown(pid: i32) -> bool {
+    send_sigterm(pid)
+}
+
+/// Windows has no SIGTERM: signal the daemon's named stop event instead.
+/// `send_sigterm` (taskkill /F) stays the fallback for a daemon that does not
+/// respond, e.g. one built before the event existed.
+#[cfg(windows)]
+pub fn request_shutdown(pid: i32) -> bool {
+    use windows_sys::Win32::Foundation::{CloseHandle, FALSE};
+    use windows_sys::Win32::System::Threading::{OpenEventW, SetEvent};
+    let Ok(pid) = u32::try_from(pid) else {
+        return false;
+    };
+    let name = stop_event_name(pid);
+    unsafe {
+        let handle = OpenEventW(EVENT_MODIFY_STATE, FALSE, name.as_ptr());
+        if handle.is_null() {
+            return false;
+        }
+        let ok = SetEvent(handle) != FALSE;
+        CloseHandle(handle);
+        ok
+    }
+}
+
+#[cfg(not(any(unix, windows)))]
+pub fn request_shutdown(_pid: i32) -> bool {
+    false
+}
+
+/// `EVENT_MODIFY_STATE` access right (0x0002) — required by `SetEvent`.
+#[cfg(windows)]
+const EVENT_MODIFY_STATE: u32 = 0x0002;
+
+/// Name of the event a Windows daemon waits on for graceful shutdown.
+///
+/// Named format: "Local\actionbook-daemon-stop-{pid}" — per-pid so a CLI
+/// stopping an old daemon never signals its replacement.
+#[cfg(windows)]
+fn stop_event_name(pid: u32) -> Vec<u16> {
+    format!("Local\\actionbook-daemon-stop-{pid}")
+        .encode_utf16()
+        .chain(std::iter::once(0))
+        .collect()
+}
+
+/// Named auto-reset event owned by a running Windows daemon; see
+/// [`request_shutdown`].
+#[cfg(windows)]
+struct StopEvent {
+    handle: windows_sys::Win32::Foundation::HANDLE,
+}
+
+#[cfg(windows)]
+unsafe impl Send for StopEvent {}
+
+#[cfg(windows)]
+impl StopEvent {
+    fn create(pid: u32) -> Option<Self> {
+        use windows_sys::Win32::Foundation::FALSE;
+        use windows_sys::Win32::System::Threading::CreateEventW;
+        let name = stop_event_name(pid);
+        let handle = unsafe { CreateEventW(std::ptr::null(), FALSE, FALSE, name.as_ptr()) };
+        if handle.is_null() {
+            warn!("failed to create daemon stop event");
+            return None;
+        }
+        Some(Self { handle })
+    }
+
+    /// Block the calling thread until the event is signalled.
+    fn wait(&self) {
+        use windows_sys::Win32::System::Threading::WaitForSingleObject;
+        const INFINITE: u32 = 0xFFFF_FFFF;
+        unsafe {
+            WaitForSingleObject(self.handle, INFINITE);
+        }
+    }
+}
+
+#[cfg(windows)]
+impl Drop for StopEvent {
+    fn drop(&mut self) {
+        unsafe {
+            windows_sys::Win32::Foundation::CloseHandle(self.handle);
+        }
+    }
+}
+
+/// Write a daemon state file (port, version, ready, Windows PID) atomically:
+/// the contents go to a sibling temp file that is then renamed over `path`,
+/// so a CLI polling for the file never reads it half-written. On Unix the
+/// file is created 0600; on Windows the rename replaces an existing file.
+pub(crate) fn write_state_file(path: &Path, contents: &str) -> std::io::Result<()> {
+    use std::io::Write;
+    let mut tmp = path.as_os_str().to_owned();
+    tmp.push(format!(".tmp-{}", std::process::id()));
+    let tmp = PathBuf::from(tmp);
+
+    let mut options = OpenOptions::new();
+    options.write(true).create(true).truncate(true);
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::OpenOptionsExt;
+        options.mode(0o600);
+    }
+    let result = options.open(&tmp).and_then(|mut f| {
+        f.write_all(contents.as_bytes())?;
+        f.sync_all()
+    });
+    if let Err(e) = result.and_then(|()| std::fs::rename(&tmp, path)) {
+        std::fs::remove_file(&tmp).ok();
+        return Err(e);
+    }
+    Ok(())
+}
+
 /// Check if a specific process is still alive (kill -0).
 /// Returns `true` if the process exists (including EPERM — the process is
 /// alive but we cannot signal it).  Only ESRCH means definitely dead.
@@ -243,12 +371,16 @@ pub async fn run_daemon() -> Result<(), Box<dyn std::error::Error>> {
     // Open or create PID file, then acquire an exclusive flock.
     // The flock is held for the entire daemon lifetime — the kernel releases it
     // automatically when the process exits (even on SIGKILL / panic = "abort").
-    let pid_file_fd = OpenOptions::new()
-        .read(true)
-        .write(true)
-        .create(true)
-        .truncate(false)
-        .open(&pid_file)?;
+    let pid_file_fd = {
+        use std::os::unix::fs::OpenOptionsExt;
+        OpenOptions::new()
+            .read(true)
+            .write(true)
+            .create(true)
+            .truncate(false)
+            .mode(0o600)
+            .open(&pid_file)?
+    };
 
     let mut locked = try_lock_exclusive(&pid_file_fd);
     if !locked {
@@ -300,12 +432,13 @@ pub async fn run_daemon() -> Result<(), Box<dyn std::error::Error>> {
 
     // Write version file for version mismatch detection
     let ver_path = version_path();
-    std::fs::write(&ver_path, crate::BUILD_VERSION)?;
+    write_state_file(&ver_path, crate::BUILD_VERSION)?;
 
     // Write ready signal with build version for CLI version check
-    std::fs::write(&ready_path, crate::BUILD_VERSION)?;
+    write_state_file(&ready_path, crate::BUILD_VERSION)?;
 
     let registry = new_shared_registry();
+    crate::stats::start_daemon_recorder();
 
     // Bridge is no longer spawned at daemon boot — it lazy-binds on the first
     // `--mode extension` call via `bridge::ensure_bridge`. Non-extension users
@@ -354,6 +487,9 @@ pub async fn run_daemon() -> Result<(), Box<dyn std::error::Error>> {
                 break;
             }
             _ = housekeeping.tick() => {
+                // Off the accept loop: closing a session can take seconds.
+                let reg = registry.clone();
+                tokio::spawn(async move { limits::enforce(&reg).await });
                 if let Some(timeout) = idle_timeout_duration
                     && last_activity.elapsed() > timeout {
                         let has_active = registry.lock().await.has_active_sessions();
@@ -416,6 +552,13 @@ pub async fn run_daemon() -> Result<(), Box<dyn std::error::Error>> {
     std::fs::remove_file(version_path()).ok();
     std::fs::remove_file(&pid_file).ok();
 
+    // Write out audit records and usage counts left from the last requests.
+    let _ = tokio::task::spawn_blocking(|| {
+        audit::flush();
+        crate::stats::flush_pending();
+    })
+    .await;
+
     info!("daemon shutdown complete (pid={})", std::process::id());
 
     // `pid_file_fd` is dropped here → kernel releases flock
@@ -475,8 +618,8 @@ pub async fn run_daemon() -> Result<(), Box<dyn std::error::Error>> {
     }
 
     // Write our PID to a separate (unlocked) file so the client can target us
-    // with taskkill on version mismatch.
-    std::fs::write(&pid_file, std::process::id().to_string())?;
+    // with the stop event (or taskkill) on version mismatch and `daemon stop`.
+    write_state_file(&pid_file, &std::process::id().to_string())?;
 
     // Bind TCP listener; OS assigns an ephemeral port in the dynamic range.
     let listener = TcpListener::bind("127.0.0.1:0").await?;
@@ -487,16 +630,29 @@ pub async fn run_daemon() -> Result<(), Box<dyn std::error::Error>> {
     );
 
     // Write port file for client discovery
-    std::fs::write(&port_file, port.to_string())?;
+    write_state_file(&port_file, &port.to_string())?;
 
     // Write version and ready files
-    std::fs::write(version_path(), crate::BUILD_VERSION)?;
-    std::fs::write(&ready_path, crate::BUILD_VERSION)?;
+    write_state_file(&version_path(), crate::BUILD_VERSION)?;
+    write_state_file(&ready_path, crate::BUILD_VERSION)?;
 
     let registry = new_shared_registry();
+    crate::stats::start_daemon_recorder();
 
     // Bridge is lazy: see `bridge::ensure_bridge`. No bind at daemon boot.
 
+    // Graceful stop requests (`daemon stop`, restart) arrive on a named event;
+    // a dedicated thread blocks on it. If the event cannot be created the
+    // sender drops and the select arm below is disabled.
+    let (stop_tx, mut stop_rx) = tokio::sync::oneshot::channel::<()>();
+    if let Some(event) = StopEvent::create(std::process::id()) {
+        std::thread::spawn(move || {
+            event.wait();
+            let _ = stop_tx.send(());
+        });
+    }
+    let mut ctrl_break = tokio::signal::windows::ctrl_break()?;
+
     let mut last_activity = Instant::now();
     let idle_timeout_duration = idle_timeout();
     let mut housekeeping = tokio::time::interval(housekeeping_interval());
@@ -524,7 +680,18 @@ pub async fn run_daemon() -> Result<(), Box<dyn std::error::Error>> {
                 info!("received Ctrl+C, shutting down");
                 break;
             }
+            _ = ctrl_break.recv() => {
+                info!("received Ctrl+Break, shutting down");
+                break;
+            }
+            Ok(()) = &mut stop_rx => {
+                info!("received stop request, shutting down");
+                break;
+            }
             _ = housekeeping.tick() => {
+                // Off the accept loop: closing a session can take seconds.
+                let reg = registry.clone();
+                tokio::spawn(async move { limits::enforce(&reg).await });
                 if let Some(timeout) = idle_timeout_duration
                     && last_activity.elapsed() > timeout {
                         let has_active = registry.lock().await.has_active_sessions();
@@ -582,6 +749,13 @@ pub async fn run_daemon() -> Result<(), Box<dyn std::error::Error>> {
     std::fs::remove_file(&pid_file).ok();
     std::fs::remove_file(&lock_file).ok();
 
+    // Write out audit records and usage counts left from the last requests.
+    let _ = tokio::task::spawn_blocking(|| {
+        audit::flush();
+        crate::stats::flush_pending();
+    })
+    .await;
+
     info!("daemon shutdown complete (pid={})", std::process::id());
     // Drop the lock fd — Windows releases the byte-range lock when the fd closes.
     drop(lock_file_fd);
@@ -595,6 +769,12 @@ pub async fn run_daemon() -> Result<(), Box<dyn std::error::Error>> {
 
 /// Generic connection handler — works with any `AsyncRead + AsyncWrite` stream
 /// (UnixStream on Unix, TcpStream on Windows).
+///
+/// A connection may carry any number of requests. Each one runs in its own
+/// task, so a slow command (a long `wait`, a `handoff`) does not hold up the
+/// others; responses are written as they complete and matched by request id.
+/// When the client hangs up (Ctrl-C, `--timeout`), in-flight requests are
+/// cancelled; see [`process_request`].
 async fn handle_connection_inner<R, W>(
     mut reader: R,
     mut writer: W,
@@ -602,43 +782,234 @@ async fn handle_connection_inner<R, W>(
 ) -> Result<(), Box<dyn std::error::Error>>
 where
     R: tokio::io::AsyncRead + Unpin,
-    W: tokio::io::AsyncWrite + Unpin,
+    W: tokio::io::AsyncWrite + Unpin + Send + 'static,
 {
-    loop {
+    let (resp_tx, mut resp_rx) = tokio::sync::mpsc::unbounded_channel::<Vec<u8>>();
+    let (hangup_tx, hangup_rx) = tokio::sync::watch::channel(false);
+    let writer_task = tokio::spawn(async move {
+        while let Some(payload) = resp_rx.recv().await {
+            wire::write_frame(&mut writer, &payload).await?;
+        }
+        Ok::<(), std::io::Error>(())
+    });
+
+    let read_result: std::io::Result<()> = loop {
         let payload = match wire::read_frame(&mut reader).await {
             Ok(p) => p,
-            Err(e) if e.kind() == std::io::ErrorKind::UnexpectedEof => break,
-            Err(e) => return Err(e.into()),
+            Err(e) if e.kind() == std::io::ErrorKind::UnexpectedEof => break Ok(()),
+            Err(e) => break Err(e),
+        };
+        let request: wire::Request = match serde_json::from_slice(&payload) {
+            Ok(r) => r,
+            Err(e) => break Err(e.into()),
         };
 
-        let request: wire::Request = serde_json::from_slice(&payload)?;
-        let cmd_name = request.action.command_name().to_owned();
-        let addr = request.action.session_tab_label();
-        let start = std::time::Instant::now();
+        let registry = registry.clone();
+        let resp_tx = resp_tx.clone();
+        let hangup = hangup_rx.clone();
+        tokio::spawn(async move {
+            let processing = process_request(request.action, &registry, hangup);
+            let result = if request.stream {
+                // Events share the response channel, so they reach the client
+                // in order and before the final response.
+                let (id, event_tx) = (request.id, resp_tx.clone());
+                let sink = move |event| match wire::serialize_event(id, &event) {
+                    Ok(payload) => {
+                        let _ = event_tx.send(payload);
+                    }
+                    Err(e) => warn!("failed to serialize event for {id}: {e}"),
+                };
+                progress::scope(sink, processing).await
+            } else {
+                processing.await
+            };
+            match wire::serialize_response(request.id, &result) {
+                // The writer is gone only if the client hung up.
+                Ok(payload) => {
+                    let _ = resp_tx.send(payload);
+                }
+                Err(e) => warn!("failed to serialize response {}: {e}", request.id),
+            }
+        });
+    };
 
-        let result = router::route(&request.action, registry).await;
-        let elapsed = start.elapsed();
+    let _ = hangup_tx.send(true);
+    drop(resp_tx);
+    let write_result = writer_task.await;
+    read_result?;
+    write_result??;
+    Ok(())
+}
 
-        match &result {
-            ActionResult::Ok { .. } => {
-                info!("{cmd_name} [{addr}] ok ({elapsed:.0?})");
-            }
-            ActionResult::Retryable { reason, .. } => {
-                warn!("{cmd_name} [{addr}] retryable: {reason} ({elapsed:.0?})");
-            }
-            ActionResult::UserAction { action, .. } => {
-                warn!("{cmd_name} [{addr}] user_action: {action} ({elapsed:.0?})");
+/// Route one request and do the daemon-side bookkeeping (audit log, session
+/// recording, idle tracking, log line).
+///
+/// `hangup` flips to true once the client disconnects. The request is then
+/// dropped mid-flight — pending CDP calls and wait loops stop — and audited
+/// as `INTERRUPTED`. `browser start` is the exception: aborting it would leak
+/// a half-launched Chrome, so it runs to completion and the session it
+/// launched is closed again, since the caller never learned about it.
+///
+/// `--tab` is resolved first (`active` to the session's active tab, a
+/// position or URL substring to the tab it names), so everything below sees
+/// the concrete tab ID.
+async fn process_request(
+    mut action: Action,
+    registry: &SharedRegistry,
+    mut hangup: tokio::sync::watch::Receiver<bool>,
+) -> ActionResult {
+    let resolved_tab = resolve_tab(&mut action, registry).await;
+    let action = &action;
+    let cmd_name = action.command_name().to_owned();
+    let addr = action.session_tab_label();
+    let start = std::time::Instant::now();
+
+    // Captured before routing: `close` removes the session from the registry.
+    let session_info = audit_session_info(action, None, registry).await;
+    let mut result = if let Err(e) = &resolved_tab {
+        e.clone()
+    } else if matches!(action, Action::StartSession(_)) {
+        let result = router::route(action, registry).await;
+        if *hangup.borrow() {
+            close_interrupted_start(&result, registry).await;
+        }
+        result
+    } else {
+        tokio::select! {
+            result = router::route(action, registry) => result,
+            _ = hangup.wait_for(|gone| *gone) => ActionResult::fatal(
+                "INTERRUPTED",
+                format!("{cmd_name} was interrupted by the client"),
+            ),
+        }
+    };
+    devtools::annotate(action, &mut result, registry).await;
+    // Tell the client which tab its `--tab` meant; `__` keys are stripped
+    // from the printed envelope.
+    if let (Ok(Some(tab)), ActionResult::Ok { data }) = (resolved_tab, &mut result)
+        && let Some(obj) = data.as_object_mut()
+    {
+        obj.insert("__ctx_tab".to_string(), serde_json::json!(tab));
+    }
+    let elapsed = start.elapsed();
+
+    let session_info = match session_info {
+        Some(info) => info,
+        None => audit_session_info(action, Some(&result), registry)
+            .await
+            .unwrap_or_default(),
+    };
+    audit::submit(audit::build_record(action, &result, &session_info, elapsed));
+    crate::stats::record_result(action.command_name(), &result, elapsed);
+    update_recording(action, &result, registry).await;
+    if let (Some(session_id), _) = audit::action_address(action) {
+        registry.lock().await.touch(&session_id);
+    }
+
+    match &result {
+        ActionResult::Ok { .. } => {
+            info!("{cmd_name} [{addr}] ok ({elapsed:.0?})");
+        }
+        ActionResult::Retryable { reason, .. } => {
+            warn!("{cmd_name} [{addr}] retryable: {reason} ({elapsed:.0?})");
+        }
+        ActionResult::UserAction { action, .. } => {
+            warn!("{cmd_name} [{addr}] user_action: {action} ({elapsed:.0?})");
+        }
+        ActionResult::Fatal { code, message, .. } => {
+            error!("{cmd_name} [{addr}] fatal({code}): {message} ({elapsed:.0?})");
+        }
+    }
+    result
+}
+
+/// Close a session that `browser start` launched for a client that has since
+/// hung up. Reused sessions were already known to the caller and stay open.
+async fn close_interrupted_start(result: &ActionResult, registry: &SharedRegistry) {
+    let ActionResult::Ok { data } = result else {
+        return;
+    };
+    if data["reused"].as_bool().unwrap_or(false) {
+        return;
+    }
+    let Some(session_id) = data.pointer("/session/session_id").and_then(|v| v.as_str()) else {
+        return;
+    };
+    info!("closing session {session_id}: its start was interrupted");
+    let close = Action::Close(crate::browser::session::close::Cmd {
+        session: session_id.to_string(),
+    });
+    let _ = router::route(&close, registry).await;
+}
+
+/// Feed `--record-session` recordings: register the directory when a session
+/// starts, append events for its actions, and stop recording on close.
+async fn update_recording(action: &Action, result: &ActionResult, registry: &SharedRegistry) {
+    let session_id = match (action, result) {
+        (Action::StartSession(_), ActionResult::Ok { data }) => data
+            .pointer("/session/session_id")
+            .and_then(|v| v.as_str())
+            .map(String::from),
+        _ => audit::action_address(action).0,
+    };
+    let Some(session_id) = session_id else {
+        return;
+    };
+
+    let dir = {
+        let mut reg = registry.lock().await;
+        match action {
+            Action::StartSession(cmd) if result.is_ok() => {
+                if let Some(dir) = cmd.record_session.as_deref() {
+                    let dir = PathBuf::from(dir);
+                    let mode = reg.get(&session_id).map(|e| e.mode.to_string());
+                    if let Err(e) = recording::begin(&dir, &session_id, mode.as_deref()) {
+                        warn!(
+                            "failed to start session recording in {}: {e}",
+                            dir.display()
+                        );
+                        return;
+                    }
+                    reg.set_recording_dir(&session_id, dir);
+                }
+                return;
             }
-            ActionResult::Fatal { code, message, .. } => {
-                error!("{cmd_name} [{addr}] fatal({code}): {message} ({elapsed:.0?})");
+            Action::Close(_) if result.is_ok() => {
+                reg.clear_recording_dir(&session_id);
+                return;
             }
+            _ => match reg.recording_dir(&session_id) {
+                Some(dir) => dir.clone(),
+                None => return,
+            },
         }
-
-        let response_payload = wire::serialize_response(request.id, &result)?;
-        wire::write_frame(&mut writer, &response_payload).await?;
+    };
+    if let Err(e) = recording::record(&dir, action, result) {
+        warn!("failed to record event in {}: {e}", dir.display());
     }
+}
 
-    Ok(())
+/// Profile/backend of the session an action addresses. `result` lets
+/// `browser start` resolve the session it has just created.
+async fn audit_session_info(
+    action: &Action,
+    result: Option<&ActionResult>,
+    registry: &SharedRegistry,
+) -> Option<audit::SessionInfo> {
+    let session_id = match result {
+        Some(ActionResult::Ok { data }) => data
+            .pointer("/session/session_id")
+            .and_then(|v| v.as_str())
+            .map(String::from),
+        Some(_) => None,
+        None => audit::action_address(action).0,
+    }?;
+    let reg = registry.lock().await;
+    let entry = reg.get(&session_id)?;
+    Some(audit::SessionInfo {
+        profile: Some(entry.profile.clone()),
+        backend: Some(entry.mode.to_string()),
+    })
 }
 
 #[cfg(unix)]
@@ -770,6 +1141,20 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_write_state_file_replaces_atomically_with_private_mode() {
+        use std::os::unix::fs::PermissionsExt;
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("daemon.version");
+        write_state_file(&path, "1.0.0").unwrap();
+        write_state_file(&path, "1.0.1").unwrap();
+        assert_eq!(std::fs::read_to_string(&path).unwrap(), "1.0.1");
+        let mode = std::fs::metadata(&path).unwrap().permissions().mode();
+        assert_eq!(mode & 0o777, 0o600);
+        let leftovers: Vec<_> = std::fs::read_dir(dir.path()).unwrap().collect();
+        assert_eq!(leftovers.len(), 1, "temp file must not be left behind");
+    }
+
     // Test parse_idle_timeout directly (pure function, no env var mutation).
 
     #[test]
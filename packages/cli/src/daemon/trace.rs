@@ -0,0 +1,177 @@
+//! Performance traces of a whole session through the CDP `Tracing` domain.
+//!
+//! `browser trace start` calls `Tracing.start` on the browser target with
+//! `transferMode: "ReturnAsStream"`, so nothing is buffered in the daemon
+//! while the trace runs. `browser trace stop` ends it, waits for
+//! `Tracing.tracingComplete` and copies the returned IO stream to the output
+//! a chunk at a time. The file is Chrome's JSON trace format, which
+//! chrome://tracing, Perfetto and the DevTools Performance panel load.
+
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+
+use base64::Engine;
+use serde_json::json;
+
+use super::cdp_session::CdpSession;
+use crate::error::CliError;
+
+/// Categories DevTools records for a Performance panel profile.
+pub const DEFAULT_CATEGORIES: &[&str] = &[
+    "-*",
+    "devtools.timeline",
+    "disabled-by-default-devtools.timeline",
+    "disabled-by-default-devtools.timeline.frame",
+    "disabled-by-default-devtools.timeline.stack",
+    "toplevel",
+    "v8.execute",
+    "disabled-by-default-v8.cpu_profiler",
+    "blink.console",
+    "blink.user_timing",
+    "latencyInfo",
+    "loading",
+];
+
+/// Category that adds filmstrip screenshots to a trace.
+pub const SCREENSHOT_CATEGORY: &str = "disabled-by-default-devtools.screenshot";
+
+/// How long `Tracing.end` may take to flush before `trace stop` gives up.
+const COMPLETE_TIMEOUT: Duration = Duration::from_secs(60);
+
+/// Bytes asked for per `IO.read`.
+const READ_CHUNK: u64 = 1 << 20;
+
+/// A session's trace in progress.
+#[derive(Debug, Clone)]
+pub struct Trace {
+    /// Where `trace stop` writes the trace unless given another path.
+    pub out: PathBuf,
+    pub started: Instant,
+}
+
+/// The categories to record: `custom` (comma separated) or the DevTools
+/// set, plus screenshots when asked for.
+pub fn categories(custom: Option<&str>, screenshots: bool) -> Vec<String> {
+    let mut list: Vec<String> = match custom {
+        Some(c) => c
+            .split(',')
+            .map(str::trim)
+            .filter(|s| !s.is_empty())
+            .map(String::from)
+            .collect(),
+        None => DEFAULT_CATEGORIES.iter().map(|s| s.to_string()).collect(),
+    };
+    if screenshots && !list.iter().any(|c| c == SCREENSHOT_CATEGORY) {
+        list.push(SCREENSHOT_CATEGORY.to_string());
+    }
+    list
+}
+
+/// `Tracing.start` parameters for `categories`. A leading `-name` excludes a
+/// category; `-*` excludes everything not listed.
+pub fn start_params(categories: &[String]) -> serde_json::Value {
+    let (excluded, included): (Vec<&String>, Vec<&String>) =
+        categories.iter().partition(|c| c.starts_with('-'));
+    let excluded: Vec<&str> = excluded
+        .iter()
+        .map(|c| &c[1..])
+        .filter(|c| *c != "*")
+        .collect();
+    let mut config = json!({
+        "recordMode": "recordContinuously",
+        "includedCategories": included,
+    });
+    if !excluded.is_empty() {
+        config["excludedCategories"] = json!(excluded);
+    }
+    json!({
+        "transferMode": "ReturnAsStream",
+        "streamFormat": "json",
+        "traceConfig": config,
+    })
+}
+
+/// End the browser's trace and write it to `out`; the number of bytes written.
+pub async fn finish(cdp: &CdpSession, out: &Path) -> Result<u64, CliError> {
+    // Subscribe before ending so a fast completion is not missed.
+    let mut complete = cdp
+        .subscribe_browser_events("Tracing.tracingComplete")
+        .await;
+    cdp.execute_browser("Tracing.end", json!({})).await?;
+    let event = tokio::time::timeout(COMPLETE_TIMEOUT, complete.recv())
+        .await
+        .map_err(|_| CliError::Timeout)?
+        .ok_or_else(|| CliError::CdpError("connection closed while tracing".to_string()))?;
+    let Some(handle) = event["params"]["stream"].as_str().map(String::from) else {
+        return Err(CliError::CdpError(
+            "Tracing.tracingComplete carried no stream".to_string(),
+        ));
+    };
+
+    let mut file = std::fs::File::create(out)
+        .map_err(|e| CliError::Internal(format!("failed to create {}: {e}", out.display())))?;
+    let mut written = 0u64;
+    loop {
+        let resp = cdp
+            .execute_browser("IO.read", json!({ "handle": handle, "size": READ_CHUNK }))
+            .await?;
+        let result = &resp["result"];
+        let data = result["data"].as_str().unwrap_or("");
+        let bytes = if result["base64Encoded"].as_bool() == Some(true) {
+            base64::engine::general_purpose::STANDARD
+                .decode(data)
+                .map_err(|e| CliError::CdpError(format!("bad trace chunk: {e}")))?
+        } else {
+            data.as_bytes().to_vec()
+        };
+        file.write_all(&bytes)
+            .map_err(|e| CliError::Internal(format!("failed to write {}: {e}", out.display())))?;
+        written += bytes.len() as u64;
+        if result["eof"].as_bool() != Some(false) {
+            break;
+        }
+    }
+    let _ = cdp
+        .execute_browser("IO.close", json!({ "handle": handle }))
+        .await;
+    Ok(written)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn categories_default_to_devtools_and_add_screenshots_once() {
+        let all = categories(None, false);
+        assert_eq!(all[0], "-*");
+        assert!(all.iter().any(|c| c == "devtools.timeline"));
+        assert!(!all.iter().any(|c| c == SCREENSHOT_CATEGORY));
+
+        assert_eq!(
+            categories(Some(" v8, blink ,,"), true),
+            ["v8", "blink", SCREENSHOT_CATEGORY]
+        );
+        assert_eq!(
+            categories(Some(SCREENSHOT_CATEGORY), true),
+            [SCREENSHOT_CATEGORY]
+        );
+    }
+
+    #[test]
+    fn start_params_split_included_and_excluded_categories() {
+        let params = start_params(&categories(Some("-*,toplevel,-v8.gc,loading"), false));
+        assert_eq!(params["transferMode"], "ReturnAsStream");
+        assert_eq!(
+            params["traceConfig"]["includedCategories"],
+            json!(["toplevel", "loading"])
+        );
+        assert_eq!(
+            params["traceConfig"]["excludedCategories"],
+            json!(["v8.gc"])
+        );
+        let params = start_params(&categories(Some("-*,toplevel"), false));
+        assert!(params["traceConfig"].get("excludedCategories").is_none());
+    }
+}
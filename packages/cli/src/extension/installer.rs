@@ -143,6 +143,20 @@ fn read_version(dir: &Path) -> Option<String> {
     v["version"].as_str().map(String::from)
 }
 
+/// Version of the extension bundled into this CLI binary.
+pub fn bundled_version() -> Option<String> {
+    let (_, bytes) = BUNDLED_EXTENSION
+        .iter()
+        .find(|(path, _)| *path == "manifest.json")?;
+    let v: serde_json::Value = serde_json::from_slice(bytes).ok()?;
+    v["version"].as_str().map(String::from)
+}
+
+/// Version of the unpacked extension on disk, if installed.
+pub fn installed_version() -> Option<String> {
+    installed_extension_dir().and_then(|dir| read_version(&dir))
+}
+
 pub fn execute_path() -> ActionResult {
     let dir = extension_dir();
     let installed = dir.join("manifest.json").exists();
@@ -1,3 +1,4 @@
 pub mod installer;
 pub mod ping;
 pub mod status;
+pub mod version;
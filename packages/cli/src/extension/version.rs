@@ -0,0 +1,132 @@
+use std::time::Duration;
+
+use clap::Args;
+use serde::{Deserialize, Serialize};
+use serde_json::{Value, json};
+
+use crate::action_result::ActionResult;
+use crate::daemon::bridge;
+use crate::daemon::registry::SharedRegistry;
+use crate::extension::installer;
+
+/// Report the connected extension's version and compatibility.
+#[derive(Args, Debug, Clone, Serialize, Deserialize, Default)]
+pub struct Cmd {}
+
+pub const COMMAND_NAME: &str = "extension version";
+
+const QUERY_TIMEOUT: Duration = Duration::from_secs(5);
+
+pub async fn execute_daemon(_cmd: &Cmd, registry: &SharedRegistry) -> ActionResult {
+    let bridge_state = registry.lock().await.bridge_state().cloned();
+    let required = crate::EXTENSION_PROTOCOL_MIN_VERSION;
+    let installed = installer::installed_version();
+    let bundled = installer::bundled_version();
+
+    let mut connected = false;
+    let mut protocol = None;
+    let mut reported = Value::Null;
+    if let Some(state) = bridge_state {
+        {
+            let s = state.lock().await;
+            connected = s.is_extension_connected();
+            protocol = s.extension_protocol().map(String::from);
+        }
+        if connected {
+            // Extensions before Extension.getVersion only report the protocol
+            // from their hello; that is enough for the compatibility verdict.
+            reported = bridge::request(&state, "Extension.getVersion", json!({}), QUERY_TIMEOUT)
+                .await
+                .unwrap_or(Value::Null);
+        }
+    }
+
+    let extension_version = reported
+        .get("version")
+        .and_then(|v| v.as_str())
+        .map(String::from);
+    let protocol = reported
+        .get("protocolVersion")
+        .and_then(|v| v.as_str())
+        .map(String::from)
+        .or(protocol);
+    let compatible = protocol.as_deref().map(bridge::is_version_ok);
+    // Compare whichever extension we know about against the bundled build.
+    let current = extension_version.as_deref().or(installed.as_deref());
+    let update_available = match (current, bundled.as_deref()) {
+        (Some(current), Some(bundled)) => is_older(current, bundled),
+        _ => false,
+    };
+
+    ActionResult::ok(json!({
+        "connected": connected,
+        "extension_version": extension_version,
+        "protocol_version": protocol,
+        "capabilities": reported.get("capabilities").cloned().unwrap_or(json!([])),
+        "required_protocol_version": required,
+        "compatible": compatible,
+        "installed_version": installed,
+        "bundled_version": bundled,
+        "update_available": update_available,
+    }))
+}
+
+/// Whether the extension described by `execute_daemon` data should be
+/// reinstalled from the bundled copy.
+pub fn needs_update(data: &Value) -> bool {
+    data["compatible"] == Value::Bool(false) || data["update_available"] == Value::Bool(true)
+}
+
+/// Numeric `major.minor.patch` comparison; missing parts count as 0 and
+/// pre-release suffixes are ignored.
+fn is_older(current: &str, other: &str) -> bool {
+    fn parts(v: &str) -> Vec<u64> {
+        v.split(['-', '+'])
+            .next()
+            .unwrap_or_default()
+            .split('.')
+            .map(|p| p.parse().unwrap_or(0))
+            .collect()
+    }
+    let (a, b) = (parts(current), parts(other));
+    for i in 0..a.len().max(b.len()) {
+        let (x, y) = (
+            a.get(i).copied().unwrap_or(0),
+            b.get(i).copied().unwrap_or(0),
+        );
+        if x != y {
+            return x < y;
+        }
+    }
+    false
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn compares_versions_numerically() {
+        assert!(is_older("0.4.0", "0.4.1"));
+        assert!(is_older("0.9.9", "0.10.0"));
+        assert!(is_older("0.4", "0.4.1"));
+        assert!(!is_older("0.4.1", "0.4.1"));
+        assert!(!is_older("1.0.0-beta", "0.9.0"));
+    }
+
+    #[test]
+    fn needs_update_on_incompatible_or_outdated() {
+        assert!(needs_update(
+            &json!({ "compatible": false, "update_available": false })
+        ));
+        assert!(needs_update(
+            &json!({ "compatible": null, "update_available": true })
+        ));
+        assert!(!needs_update(
+            &json!({ "compatible": true, "update_available": false })
+        ));
+        assert!(!needs_update(
+            &json!({ "compatible": null, "update_available": false })
+        ));
+    }
+}
@@ -8,15 +8,20 @@ pub mod action;
 pub mod action_result;
 pub mod api;
 pub mod api_response;
+pub mod audit;
+pub mod auth;
 pub mod browser;
 pub mod cli;
 pub mod commands;
 pub mod config;
 pub mod daemon;
+pub mod delivery;
 pub mod error;
 pub mod extension;
 pub mod formatter;
 pub mod output;
+pub mod serve;
 pub mod setup;
+pub mod stats;
 pub mod types;
 pub mod utils;
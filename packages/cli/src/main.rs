@@ -1,4 +1,5 @@
 use std::io::Write as _;
+use std::path::Path;
 use std::time::{Duration, Instant};
 
 use clap::Parser;
@@ -6,8 +7,12 @@ use serde_json::json;
 
 use actionbook_cli::action::Action;
 use actionbook_cli::action_result::ActionResult;
-use actionbook_cli::cli::{BrowserCommands, Cli, Commands, DaemonCommands, ExtensionCommands};
+use actionbook_cli::cli::{
+    AuditCommands, AuthCommands, BrowserCommands, Cli, Commands, DaemonCommands, ExtensionCommands,
+    FlowCommands, SessionCommands, StatsCommands,
+};
 use actionbook_cli::config;
+use actionbook_cli::daemon::guardrails;
 use actionbook_cli::output::{self, JsonEnvelope};
 use actionbook_cli::utils::client::DaemonClient;
 
@@ -92,6 +97,13 @@ async fn main() {
     }
 
     let cli = Cli::parse();
+    if cli.ci {
+        actionbook_cli::utils::ci::force();
+    }
+    if actionbook_cli::utils::ci::enabled() {
+        console::set_colors_enabled(false);
+        console::set_colors_enabled_stderr(false);
+    }
     let json_output = cli.json;
     let is_setup_command = matches!(cli.command.as_ref(), Some(Commands::Setup(_)));
 
@@ -106,7 +118,16 @@ async fn main() {
         return;
     }
 
+    let started = Instant::now();
+    let stats_name = cli.command.as_ref().and_then(stats_command_name);
     let result = run(cli).await;
+    if let Some(name) = stats_name {
+        let code = result.as_ref().err().map(|e| {
+            e.downcast_ref::<actionbook_cli::error::CliError>()
+                .map_or("INTERNAL_ERROR", |e| e.error_code())
+        });
+        actionbook_cli::stats::record(name, code, started.elapsed());
+    }
 
     match result {
         Ok(()) => {}
@@ -138,14 +159,34 @@ async fn main() {
     }
 }
 
+/// Usage-stats name of a top-level command recorded by this process. Browser
+/// actions are counted by the daemon instead, and `run` counts itself.
+fn stats_command_name(command: &Commands) -> Option<&'static str> {
+    match command {
+        Commands::Search { .. } => Some("search"),
+        Commands::Manual { .. } => Some("manual"),
+        Commands::Get { .. } => Some("get"),
+        Commands::Report { .. } => Some("report"),
+        Commands::Publish { .. } => Some("publish"),
+        Commands::Setup(_) => Some("setup"),
+        _ => None,
+    }
+}
+
 async fn run(mut cli: Cli) -> Result<(), Box<dyn std::error::Error>> {
     let json_mode = cli.json;
     let timeout_ms = cli.timeout;
 
     let command = cli.command.take().unwrap();
     match command {
-        Commands::Search { keyword } => {
-            actionbook_cli::commands::search::run(&keyword, json_mode).await?;
+        Commands::Search {
+            keyword,
+            from_page: _,
+            session,
+            tab,
+        } => {
+            let page = session.zip(tab);
+            actionbook_cli::commands::search::run(&keyword, page, json_mode).await?;
         }
         Commands::Manual {
             site,
@@ -160,11 +201,39 @@ async fn run(mut cli: Cli) -> Result<(), Box<dyn std::error::Error>> {
             )
             .await?;
         }
-        Commands::Get { area_id } => {
-            actionbook_cli::commands::get::run(&cli, &area_id).await?;
+        Commands::Get {
+            area_id,
+            sections,
+            max_tokens,
+        } => {
+            actionbook_cli::commands::get::run(&cli, &area_id, &sections, max_tokens).await?;
+        }
+        Commands::Report {
+            area_id,
+            outcome,
+            notes,
+            run_report,
+        } => {
+            actionbook_cli::commands::report::run(
+                &cli,
+                &area_id,
+                &outcome,
+                notes.as_deref(),
+                run_report.as_deref(),
+            )
+            .await?;
+        }
+        Commands::Publish {
+            draft,
+            source,
+            dry_run,
+        } => {
+            actionbook_cli::commands::publish::run(&cli, &draft, source.as_deref(), dry_run)
+                .await?;
         }
         Commands::Browser { command } => {
-            handle_browser(command, json_mode, timeout_ms).await?;
+            let stream = json_mode && cli.stream;
+            handle_browser(command, json_mode, stream, timeout_ms).await?;
         }
         Commands::Daemon { command } => {
             handle_daemon(command, json_mode, timeout_ms).await?;
@@ -172,6 +241,28 @@ async fn run(mut cli: Cli) -> Result<(), Box<dyn std::error::Error>> {
         Commands::Extension { command } => {
             handle_extension(command, json_mode).await?;
         }
+        Commands::Audit { command } => {
+            handle_audit(command, json_mode)?;
+        }
+        Commands::Session { command } => {
+            handle_session(command, json_mode)?;
+        }
+        Commands::Auth { command } => {
+            handle_auth(command, json_mode)?;
+        }
+        Commands::Flow { command } => {
+            handle_flow(command, json_mode)?;
+        }
+        Commands::Stats { command } => {
+            handle_stats(command, cli.api_key.as_deref(), json_mode).await?;
+        }
+        Commands::Serve { port, host, token } => {
+            actionbook_cli::serve::run(actionbook_cli::serve::ServeConfig { host, port, token })
+                .await?;
+        }
+        Commands::Run(cmd) => {
+            handle_run(cmd, cli.api_key.as_deref(), json_mode).await?;
+        }
         Commands::Setup(cmd) => {
             actionbook_cli::setup::execute(&cmd, json_mode).await?;
         }
@@ -188,6 +279,7 @@ async fn run(mut cli: Cli) -> Result<(), Box<dyn std::error::Error>> {
 async fn handle_browser(
     command: BrowserCommands,
     json_mode: bool,
+    stream: bool,
     timeout_ms: Option<u64>,
 ) -> Result<(), Box<dyn std::error::Error>> {
     if matches!(command, BrowserCommands::Help) {
@@ -196,7 +288,7 @@ async fn handle_browser(
     }
 
     let start = Instant::now();
-    let command = match command {
+    let mut command = match command {
         BrowserCommands::Start(cmd) => match config::resolve_start_command(cmd) {
             Ok(mut cmd) => {
                 // Forward provider env vars from the CLI client's process env
@@ -211,6 +303,7 @@ async fn handle_browser(
                     BrowserCommands::Start(actionbook_cli::browser::session::start::Cmd {
                         mode: None,
                         headless: None,
+                        headless_mode: None,
                         profile: None,
                         executable_path: None,
                         open_url: None,
@@ -222,6 +315,18 @@ async fn handle_browser(
                         set_session_id: None,
                         stealth: true,
                         max_tracked_requests: 500,
+                        record_session: None,
+                        adblock: false,
+                        adblock_list: Vec::new(),
+                        max_download_bytes: None,
+                        download_dir: None,
+                        window_position: None,
+                        window_size: None,
+                        gpu: None,
+                        device: None,
+                        proxy: Vec::new(),
+                        proxy_pool: false,
+                        proxy_rotation: None,
                         provider_env: Default::default(),
                     });
                 let result = ActionResult::fatal(err.error_code(), err.to_string());
@@ -248,10 +353,11 @@ async fn handle_browser(
         other => other,
     };
 
+    let timeout_ms = command.apply_timeout(timeout_ms);
     let command_name = command.command_name().to_string();
 
     // Build action from CLI args
-    let action = match command.to_action() {
+    let mut action = match command.to_action() {
         Some(a) => a,
         None => {
             let result = ActionResult::fatal(
@@ -273,10 +379,7 @@ async fn handle_browser(
 
     // Connect to daemon and execute, with optional global timeout across the whole request.
     let result = if let Some(timeout_ms) = timeout_ms {
-        let execution = async {
-            let mut client = DaemonClient::connect().await?;
-            client.send_action(&action).await
-        };
+        let execution = send_interruptible(&action, stream);
         match tokio::time::timeout(Duration::from_millis(timeout_ms), execution).await {
             Ok(result) => result?,
             Err(_) => {
@@ -299,8 +402,19 @@ async fn handle_browser(
             }
         }
     } else {
-        let mut client = DaemonClient::connect().await?;
-        client.send_action(&action).await?
+        send_interruptible(&action, stream).await?
+    };
+
+    // Sensitive-action guardrail: offer interactive approval on a terminal,
+    // then re-send the same action with the confirmation flag set.
+    let result = if !json_mode
+        && guardrails::is_confirmation_required(&result)
+        && approve_sensitive_action(&result)
+        && guardrails::confirm_sensitive(&mut action)
+    {
+        send_interruptible(&action, stream).await?
+    } else {
+        result
     };
     let duration = start.elapsed();
 
@@ -320,12 +434,62 @@ async fn handle_browser(
     }
 
     if !result.is_ok() {
-        flush_and_exit(1);
+        flush_and_exit(if is_interrupted(&result) { 130 } else { 1 });
     }
 
     Ok(())
 }
 
+/// Send one action to the daemon, giving up on Ctrl-C. Exiting closes the
+/// connection, which makes the daemon cancel the request (or close the
+/// session an interrupted `browser start` launched). With `stream`, each
+/// progress event is printed as a JSON line ahead of the final envelope.
+async fn send_interruptible(
+    action: &Action,
+    stream: bool,
+) -> Result<ActionResult, actionbook_cli::error::CliError> {
+    let command_name = action.command_name();
+    let execution = async {
+        let mut client = DaemonClient::connect().await?;
+        client
+            .send_action_streaming(action, stream, |mut event| {
+                event["command"] = json!(command_name);
+                println!("{event}");
+                let _ = std::io::stdout().flush();
+            })
+            .await
+    };
+    tokio::select! {
+        result = execution => result,
+        _ = tokio::signal::ctrl_c() => Ok(ActionResult::fatal(
+            "INTERRUPTED",
+            format!("{command_name} was interrupted"),
+        )),
+    }
+}
+
+fn is_interrupted(result: &ActionResult) -> bool {
+    matches!(result, ActionResult::Fatal { code, .. } if code == "INTERRUPTED")
+}
+
+/// Ask the user to approve a guarded action. Only prompts when both stdin and
+/// stderr are terminals; agents and pipelines get the structured error instead.
+fn approve_sensitive_action(result: &ActionResult) -> bool {
+    use std::io::IsTerminal as _;
+    if !std::io::stdin().is_terminal() || !std::io::stderr().is_terminal() {
+        return false;
+    }
+    let ActionResult::Fatal { message, .. } = result else {
+        return false;
+    };
+    eprintln!("{message}");
+    dialoguer::Confirm::new()
+        .with_prompt("Proceed with this sensitive action?")
+        .default(false)
+        .interact()
+        .unwrap_or(false)
+}
+
 async fn handle_daemon(
     command: DaemonCommands,
     json_mode: bool,
@@ -333,6 +497,74 @@ async fn handle_daemon(
 ) -> Result<(), Box<dyn std::error::Error>> {
     let start = Instant::now();
     match command {
+        DaemonCommands::Start => {
+            let outcome = actionbook_cli::utils::client::start_daemon_now().await;
+            let duration = start.elapsed();
+            match outcome {
+                Ok(started) => {
+                    let status = if started {
+                        "started"
+                    } else {
+                        "already_running"
+                    };
+                    let pid = actionbook_cli::daemon::server::read_daemon_pid();
+                    if json_mode {
+                        let envelope = JsonEnvelope::success(
+                            "daemon start",
+                            None,
+                            json!({ "status": status, "pid": pid }),
+                            duration,
+                        );
+                        println!("{}", serde_json::to_string(&envelope)?);
+                    } else {
+                        let pid = pid.map(|p| format!(" (pid {p})")).unwrap_or_default();
+                        if started {
+                            println!("daemon started{pid}");
+                        } else {
+                            println!("daemon already running{pid}");
+                        }
+                    }
+                }
+                Err(e) => {
+                    if json_mode {
+                        let result = ActionResult::fatal("DAEMON_START_FAILED", e.to_string());
+                        let envelope =
+                            JsonEnvelope::from_result("daemon start", None, &result, duration);
+                        println!("{}", serde_json::to_string(&envelope)?);
+                    } else {
+                        eprintln!("error DAEMON_START_FAILED: {e}");
+                    }
+                    flush_and_exit(1);
+                }
+            }
+        }
+        DaemonCommands::Status => {
+            let status = actionbook_cli::utils::client::daemon_status().await;
+            let duration = start.elapsed();
+            if json_mode {
+                let envelope = JsonEnvelope::success("daemon status", None, status, duration);
+                println!("{}", serde_json::to_string(&envelope)?);
+            } else if status["running"] == true {
+                let mut parts = Vec::new();
+                if let Some(pid) = status["pid"].as_i64() {
+                    parts.push(format!("pid {pid}"));
+                }
+                if let Some(version) = status["version"].as_str() {
+                    parts.push(format!("version {version}"));
+                }
+                if let Some(n) = status["sessions"].as_u64() {
+                    parts.push(format!("{n} sessions"));
+                }
+                println!("daemon running ({})", parts.join(", "));
+                if status["version"] != status["cli_version"] {
+                    println!(
+                        "  built from another version; the next browser command will restart it"
+                    );
+                }
+            } else {
+                println!("daemon not running; the next browser command starts it");
+            }
+        }
         DaemonCommands::Restart => {
             // Honor --timeout: restart_daemon_now can block up to ~15s
             // (5s SIGTERM wait + 10s readiness wait). Without an outer
@@ -377,6 +609,49 @@ async fn handle_daemon(
                 }
             }
         }
+        DaemonCommands::Stop => {
+            let stop_call = actionbook_cli::utils::client::stop_daemon_now();
+            let outcome = if let Some(ms) = timeout_ms {
+                match tokio::time::timeout(Duration::from_millis(ms), stop_call).await {
+                    Ok(r) => r,
+                    Err(_) => Err(actionbook_cli::error::CliError::Internal(format!(
+                        "daemon stop timed out after {ms}ms"
+                    ))),
+                }
+            } else {
+                stop_call.await
+            };
+            let duration = start.elapsed();
+            match outcome {
+                Ok(stopped) => {
+                    let status = if stopped { "stopped" } else { "not_running" };
+                    if json_mode {
+                        let envelope = JsonEnvelope::success(
+                            "daemon stop",
+                            None,
+                            json!({ "status": status }),
+                            duration,
+                        );
+                        println!("{}", serde_json::to_string(&envelope)?);
+                    } else if stopped {
+                        println!("daemon stopped");
+                    } else {
+                        println!("daemon not running");
+                    }
+                }
+                Err(e) => {
+                    if json_mode {
+                        let result = ActionResult::fatal("DAEMON_STOP_FAILED", e.to_string());
+                        let envelope =
+                            JsonEnvelope::from_result("daemon stop", None, &result, duration);
+                        println!("{}", serde_json::to_string(&envelope)?);
+                    } else {
+                        eprintln!("error DAEMON_STOP_FAILED: {e}");
+                    }
+                    flush_and_exit(1);
+                }
+            }
+        }
     }
     Ok(())
 }
@@ -394,6 +669,24 @@ async fn handle_extension(
             let result = client.send_action(&action).await?;
             (actionbook_cli::extension::status::COMMAND_NAME, result)
         }
+        ExtensionCommands::Version(args) => {
+            use actionbook_cli::extension::{installer, version};
+            let action = Action::ExtensionVersion(version::Cmd::default());
+            let mut client = DaemonClient::connect().await?;
+            let mut result = client.send_action(&action).await?;
+            if args.update
+                && let ActionResult::Ok { data } = &mut result
+                && version::needs_update(data)
+            {
+                // Reinstall on this side: the unpacked copy lives in the
+                // user's Actionbook home, next to the CLI that bundles it.
+                match installer::execute_install(true) {
+                    ActionResult::Ok { data: installed } => data["updated"] = installed,
+                    failed => result = failed,
+                }
+            }
+            (version::COMMAND_NAME, result)
+        }
         ExtensionCommands::Ping => {
             let result = actionbook_cli::extension::ping::execute().await;
             (actionbook_cli::extension::ping::COMMAND_NAME, result)
@@ -453,6 +746,242 @@ fn handle_version(json_mode: bool) {
     }
 }
 
+fn handle_audit(command: AuditCommands, json_mode: bool) -> Result<(), Box<dyn std::error::Error>> {
+    let start = Instant::now();
+    let (command_name, result) = match command {
+        AuditCommands::Tail { lines } => (
+            actionbook_cli::audit::COMMAND_NAME_TAIL,
+            actionbook_cli::audit::execute_tail(lines),
+        ),
+        AuditCommands::Search(args) => (
+            actionbook_cli::audit::COMMAND_NAME_SEARCH,
+            actionbook_cli::audit::execute_search(&args),
+        ),
+    };
+    let duration = start.elapsed();
+
+    if json_mode {
+        let envelope = JsonEnvelope::from_result(command_name, None, &result, duration);
+        println!("{}", serde_json::to_string(&envelope)?);
+    } else {
+        let text = output::format_text(command_name, &None, &result);
+        if result.is_ok() {
+            println!("{text}");
+        } else {
+            eprintln!("{text}");
+        }
+    }
+
+    if !result.is_ok() {
+        flush_and_exit(1);
+    }
+
+    Ok(())
+}
+
+async fn handle_stats(
+    command: Option<StatsCommands>,
+    api_key: Option<&str>,
+    json_mode: bool,
+) -> Result<(), Box<dyn std::error::Error>> {
+    use actionbook_cli::stats;
+    let start = Instant::now();
+    let (command_name, result) = match command {
+        None => (stats::COMMAND_NAME_SHOW, stats::execute_show()),
+        Some(StatsCommands::Enable) => {
+            (stats::COMMAND_NAME_ENABLE, stats::execute_set_enabled(true))
+        }
+        Some(StatsCommands::Disable) => (
+            stats::COMMAND_NAME_DISABLE,
+            stats::execute_set_enabled(false),
+        ),
+        Some(StatsCommands::Reset) => (stats::COMMAND_NAME_RESET, stats::execute_reset()),
+        Some(StatsCommands::Upload { reset }) => (
+            stats::COMMAND_NAME_UPLOAD,
+            stats::execute_upload(api_key, reset).await,
+        ),
+    };
+    let duration = start.elapsed();
+
+    if json_mode {
+        let envelope = JsonEnvelope::from_result(command_name, None, &result, duration);
+        println!("{}", serde_json::to_string(&envelope)?);
+    } else {
+        let text = output::format_text(command_name, &None, &result);
+        if result.is_ok() {
+            println!("{text}");
+        } else {
+            eprintln!("{text}");
+        }
+    }
+
+    if !result.is_ok() {
+        flush_and_exit(1);
+    }
+
+    Ok(())
+}
+
+fn handle_session(
+    command: SessionCommands,
+    json_mode: bool,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let start = Instant::now();
+    let SessionCommands::Replay { dir, event } = command;
+    let command_name = actionbook_cli::daemon::recording::COMMAND_NAME_REPLAY;
+    let result = actionbook_cli::daemon::recording::execute_replay(Path::new(&dir), event);
+    let duration = start.elapsed();
+
+    if json_mode {
+        let envelope = JsonEnvelope::from_result(command_name, None, &result, duration);
+        println!("{}", serde_json::to_string(&envelope)?);
+    } else {
+        let text = output::format_text(command_name, &None, &result);
+        if result.is_ok() {
+            println!("{text}");
+        } else {
+            eprintln!("{text}");
+        }
+    }
+
+    if !result.is_ok() {
+        flush_and_exit(1);
+    }
+
+    Ok(())
+}
+
+fn handle_auth(command: AuthCommands, json_mode: bool) -> Result<(), Box<dyn std::error::Error>> {
+    use actionbook_cli::auth;
+    let start = Instant::now();
+    let (command_name, result) = match command {
+        AuthCommands::Add {
+            domain,
+            user,
+            pass_env,
+            pass_stdin,
+            totp_secret,
+            totp_env,
+        } => (
+            auth::COMMAND_NAME_ADD,
+            auth::execute_add(&auth::AddOptions {
+                domain: &domain,
+                user: &user,
+                pass_env: pass_env.as_deref(),
+                pass_stdin,
+                totp_secret: totp_secret.as_deref(),
+                totp_env: totp_env.as_deref(),
+            }),
+        ),
+        AuthCommands::List => (auth::COMMAND_NAME_LIST, auth::execute_list()),
+        AuthCommands::Remove { domain } => {
+            (auth::COMMAND_NAME_REMOVE, auth::execute_remove(&domain))
+        }
+        AuthCommands::Totp { domain } => (auth::COMMAND_NAME_TOTP, auth::execute_totp(&domain)),
+    };
+    let duration = start.elapsed();
+
+    if json_mode {
+        let envelope = JsonEnvelope::from_result(command_name, None, &result, duration);
+        println!("{}", serde_json::to_string(&envelope)?);
+    } else {
+        let text = output::format_text(command_name, &None, &result);
+        if result.is_ok() {
+            println!("{text}");
+        } else {
+            eprintln!("{text}");
+        }
+    }
+
+    if !result.is_ok() {
+        flush_and_exit(1);
+    }
+
+    Ok(())
+}
+
+fn handle_flow(command: FlowCommands, json_mode: bool) -> Result<(), Box<dyn std::error::Error>> {
+    let start = Instant::now();
+    let (command_name, result) = match command {
+        FlowCommands::Import(cmd) => (
+            actionbook_cli::commands::flow_import::COMMAND_NAME,
+            actionbook_cli::commands::flow_import::execute(&cmd),
+        ),
+        FlowCommands::Export(cmd) => (
+            actionbook_cli::commands::flow_export::COMMAND_NAME,
+            actionbook_cli::commands::flow_export::execute(&cmd),
+        ),
+    };
+    let duration = start.elapsed();
+
+    if json_mode {
+        let envelope = JsonEnvelope::from_result(command_name, None, &result, duration);
+        println!("{}", serde_json::to_string(&envelope)?);
+    } else {
+        let text = output::format_text(command_name, &None, &result);
+        if result.is_ok() {
+            println!("{text}");
+        } else {
+            eprintln!("{text}");
+        }
+    }
+
+    if !result.is_ok() {
+        flush_and_exit(1);
+    }
+
+    Ok(())
+}
+
+async fn handle_run(
+    cmd: actionbook_cli::commands::run::Cmd,
+    api_key: Option<&str>,
+    json_mode: bool,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let start = Instant::now();
+    let command_name = actionbook_cli::commands::run::COMMAND_NAME;
+    let mut result = actionbook_cli::commands::run::execute(&cmd, |step| {
+        if !json_mode {
+            println!("{}", output::format_run_step(step));
+            let _ = std::io::stdout().flush();
+        }
+    })
+    .await;
+    let duration = start.elapsed();
+    actionbook_cli::stats::record_result(command_name, &result, duration);
+
+    // Neither a failed delivery nor a failed report changes how the run went.
+    if !cmd.no_deliver {
+        for warning in actionbook_cli::delivery::deliver_run(&cmd.script, &mut result).await {
+            eprintln!("warning: {warning}");
+        }
+    }
+    if let Some(area_id) = &cmd.area_id {
+        let report = actionbook_cli::commands::report::from_run(area_id, &result);
+        if let Err(e) = actionbook_cli::commands::report::submit(api_key, &report).await {
+            eprintln!("warning: failed to report the run for {area_id}: {e}");
+        }
+    }
+
+    if json_mode {
+        let envelope = JsonEnvelope::from_result(command_name, None, &result, duration);
+        println!("{}", serde_json::to_string(&envelope)?);
+    } else {
+        let text = output::format_text(command_name, &None, &result);
+        if result.is_ok() {
+            println!("{text}");
+        } else {
+            eprintln!("{text}");
+        }
+    }
+
+    if !result.is_ok() {
+        flush_and_exit(1);
+    }
+
+    Ok(())
+}
+
 fn handle_help(json_mode: bool) {
     let help_text = "\
 Actionbook — browser automation for AI agents
@@ -463,11 +992,24 @@ No \"current tab\" — run commands on any session/tab in parallel.
 Usage: actionbook <command> [options]
 
 Commands:
-  search            Search for action manuals by keyword
+  search            Search for action manuals by keyword (--from-page to rank by the open page)
   manual            Get detailed manual for a site, group, or action (alias: man)
   browser           Control browser sessions, tabs, and page interactions
-  extension         Manage the Chrome extension (status, ping, install, uninstall, path)
+  extension         Manage the Chrome extension (status, ping, version, install, uninstall, path)
+  audit             Inspect the audit log of browser actions (tail, search)
+  session replay    Step through a session recorded with --record-session
+  stats             Show opt-in local usage stats (enable, disable, reset, upload)
+  report <area_id>  Report a run outcome for an action book (--outcome success|failure)
+  publish <draft>   Validate a drafted action book and upload it (--dry-run to only validate)
+  run <script>      Run a YAML or JSON script of browser steps (results go to [delivery] if set)
+  auth add <domain> Store a login for `login` steps in run scripts (list, remove, totp)
+  flow import <f>   Convert a Playwright or Puppeteer script into a run script
+  flow export <f>   Convert a run script into Playwright or Puppeteer code (--format)
+  serve             Serve the browser commands as a local HTTP API (--port 8088)
+  daemon start      Start the daemon now instead of on the first browser command
+  daemon status     Show whether the daemon is running, its pid and session count
   daemon restart    Stop the running daemon (next CLI call auto-respawns one)
+  daemon stop       Stop the running daemon and close its sessions
   setup             Configure actionbook (or --target <agent> for quick skills install)
   help       Show this help
   --version  Show version
@@ -475,6 +1017,8 @@ Commands:
 Global flags:
   --json          Output as JSON envelope
   --timeout <ms>  Set command timeout
+  --stream        With --json, stream wait progress as JSON lines
+  --ci            CI defaults: headless, no colour, longer waits (auto-detected)
 
 Quick start:
   actionbook browser start --set-session-id s1
@@ -499,7 +1043,9 @@ fn handle_browser_help(json_mode: bool) {
     let help_text = "\
 Usage: actionbook browser <subcommand> [options]
 
-Most commands require --session <SID> and --tab <TID>.
+Most commands require --session <SID> and --tab <TID>. --tab active means the
+session's active tab (its first tab, or the one picked with switch). --tab also
+takes a 1-based position in list-tabs (--tab 2) or part of a tab's URL.
 Session-level commands need only --session. Start and list-sessions need neither.
 
 Session:
@@ -508,32 +1054,48 @@ Session:
   status              --session      Show session status
   close               --session      Close a session (alias: stop)
   restart             --session      Restart a session
+  emulate [device]    --session      Emulate a phone or tablet on every tab (--reset)
+  emulate network     --session      Throttle or cut the network (--preset slow-3g|fast-3g|offline)
 
 Tab:
   list-tabs           --session      List tabs in a session
   new-tab <url>...    --session      Open one or more tabs (alias: open)
   close-tab           --session --tab  Close a tab
+  switch <tab>        --session      Make a tab active (used by --tab active)
+  window              --session --tab  Move/resize the tab's window (--position, --size, --state)
 
 Navigation:
   goto <url>          --session --tab  Navigate to a URL
   back                --session --tab  Go back
   forward             --session --tab  Go forward
   reload              --session --tab  Reload the page
+  fetch <url>         --session --tab  Fetch a URL with the page's cookies (--output, --method)
+  trace-redirects <url>  --session --tab  Navigate and list each redirect hop (http/js/meta-refresh)
 
 Observation:
-  snapshot            --session --tab  Capture accessibility snapshot
+  snapshot            --session --tab  Capture accessibility snapshot (--frame for one iframe, --backend cdp-ax)
   screenshot <path>   --session --tab  Take a screenshot
+  record-video start --out <file>  --session --tab  Record the tab as a video (stop: record-video stop)
+  trace start --out <file>  --session  Record a performance trace for Perfetto (stop: trace stop)
+  save <path>         --session --tab  Archive page as MHTML (--format html to inline)
   title               --session --tab  Get page title
+  meta                --session --tab  Get page metadata (OpenGraph, JSON-LD, ...)
+  response            --session --tab  Main document HTTP status, headers, redirects, timing
+  assert              --session --tab  Check text/URL/elements/console errors; non-zero exit on failure
+  feeds [--fetch]     --session --tab  Discover RSS/Atom/JSON feeds on the page
+  images [--download <dir>]  --session --tab  List page images (URL, alt, size)
+  table <selector> [--format csv|json]  --session --tab  Extract a table (spans expanded, --parse, --dedupe-by, --sink)
   url                 --session --tab  Get current URL
   viewport            --session --tab  Get viewport size
   html [<selector>]   --session --tab  Read element/page HTML
   text [<selector>]   --session --tab  Read element/page text
   value <selector>    --session --tab  Read input value
+  options <selector>  --session --tab  List dropdown options
   attr <selector> <name>  --session --tab  Read element attribute
   attrs <selector>        --session --tab  Read all element attributes
   box <selector>          --session --tab  Read element bounding box
   styles <selector> [names...]  --session --tab  Read computed styles
-  describe <selector>     --session --tab  Describe element properties
+  describe [selector]     --session --tab  Describe the page, or an element's properties
   state <selector>        --session --tab  Get element state flags
   inspect-point <x,y>    --session --tab  Inspect element at coordinates
   query one|all|count <selector>  --session --tab  Query elements
@@ -548,12 +1110,21 @@ Network:
   network request <id>  --session --tab  Get detail for a single request (incl. body)
   network har start   --session --tab  Start HAR recording for a tab
   network har stop    --session --tab  Stop recording and export HAR 1.2 file
+  network route add <pattern>  --session --tab  Block, mock (--fulfill) or rewrite requests
+  network route list  --session --tab  List the tab's routes with hit counts
+  network route remove <id>|--all  --session --tab  Remove routes
 
 Wait:
-  wait element <selector>  --session --tab  Wait for element to appear
+  wait element <selector>  --session --tab  Wait for element to appear (--hidden: to go away)
   wait navigation          --session --tab  Wait for navigation to complete
   wait network-idle        --session --tab  Wait for network to become idle
-  wait condition <expr>    --session --tab  Wait for JS expression to be truthy
+  wait condition <expr>    --session --tab  Wait for JS expression to be truthy (alias: fn)
+  wait text <text>         --session --tab  Wait for text to appear (--selector to scope)
+  wait url <regex>         --session --tab  Wait for the URL to match
+
+Downloads:
+  downloads list      --session      List downloads (start with --download-dir to pick the folder)
+  downloads wait      --session      Wait for a download to finish (--timeout ms)
 
 Cookies:
   cookies list        --session      List all cookies
@@ -561,28 +1132,46 @@ Cookies:
   cookies set <name> <value>  --session  Set a cookie
   cookies delete <name>  --session   Delete a cookie
   cookies clear       --session      Clear cookies
+  cookies export --out <file>  --session  Export cookies (JSON or Netscape cookies.txt)
+  cookies import <file>  --session   Import cookies (JSON or Netscape cookies.txt)
+  state bundle --out <file> [--encrypt]  --session  Save cookies, localStorage and fingerprint to one file
+  state import <file>  --session     Load a state bundle (password from $ACTIONBOOK_BUNDLE_PASSWORD)
 
 Storage (local-storage | session-storage):
   <storage> list      --session --tab  List all key-value entries
   <storage> get <key> --session --tab  Get a value by key
   <storage> set <key> <value>  --session --tab  Set a key-value entry
   <storage> delete <key>  --session --tab  Delete a key
-  <storage> clear <key>   --session --tab  Clear a key
+  <storage> clear [key]   --session --tab  Clear a key (all keys when omitted)
+  indexeddb list          --session --tab  List IndexedDB databases and object stores
+  indexeddb dump <db> <store>  --session --tab  Dump an object store's entries (--index, --skip, --limit)
 
 Interaction:
-  click <selector|x,y>   --session --tab  Click element or coordinates
-  hover <selector>        --session --tab  Hover over an element
+  click <selector|x,y>   --session --tab  Click element or coordinates (--position to aim inside it)
+  hover <selector>        --session --tab  Hover over an element (--position to aim inside it)
+  menu <hover> <click>    --session --tab  Hover a menu trigger, then click a submenu item
   focus <selector>        --session --tab  Focus an element
-  press <key>             --session --tab  Press a key or key combo
+  tabto <selector>        --session --tab  Press Tab until an element is focused
+  press <keys>            --session --tab  Press a key, combo, sequence or @macro
   type <text>             --session --tab  Type text keystroke by keystroke
-  fill <selector> <text>  --session --tab  Fill an input field directly
-  select <selector> <value>  --session --tab  Select from a dropdown
+  fill <selector> <text>  --session --tab  Fill an input field directly (--rich for text editors)
+  select <selector> <value>  --session --tab  Select from a dropdown (or --index N)
+  slide <selector>        --session --tab  Set a slider (--to-value N or --percent P)
+  date <selector> <YYYY-MM-DD>  --session --tab  Set a date field or datepicker
+  canvas click-color <selector> <color>  --session --tab  Click where a canvas shows a colour
+  canvas draw <selector> --path <file>  --session --tab  Draw a pointer path on a canvas
   drag <source> <target>  --session --tab  Drag element to a target
   upload <selector> <file...>  --session --tab  Upload files to a file input
   eval <code>             --session --tab  Evaluate JavaScript
   mouse-move <x,y>       --session --tab  Move mouse to coordinates
   cursor-position         --session --tab  Get current cursor position
-  scroll <direction|edge|into-view>  --session --tab  Scroll page or container
+  scroll <direction|edge|page-down|into-view>  --session --tab  Scroll page or container (wheel events)
+  tap <selector|x,y>     --session --tab  Tap with a touch (for touch-only mobile pages)
+  swipe <up|down|left|right>  --session --tab  Swipe a finger (--from, --distance, --duration)
+  pinch <in|out>          --session --tab  Pinch two fingers (--at, --scale)
+  handoff [--message <text>]  --session --tab  Let a human finish a step, then resume
+  record start --out <file>   --session --tab  Record clicks and typing as a run script
+  record stop             --session --tab  Stop recording and write the script
 
 Batch:
   batch-new-tab --urls <url...>  --session  Open multiple tabs (alias: batch-open)
@@ -592,6 +1181,8 @@ Batch:
 Global flags (apply to all subcommands):
   --json          Output as JSON envelope
   --timeout <ms>  Set command timeout
+  --stream        With --json, stream wait progress as JSON lines
+  --ci            CI defaults: headless, no colour, longer waits (auto-detected)
 
 Quick start:
   actionbook browser start --set-session-id s1
@@ -192,6 +192,8 @@ pub fn format_text(
                 "browser start"
                     | "browser close"
                     | "browser restart"
+                    | "browser emulate"
+                    | "browser emulate network"
                     | "browser goto"
                     | "browser back"
                     | "browser forward"
@@ -200,27 +202,47 @@ pub fn format_text(
                     | "browser batch-click"
                     | "browser batch-new-tab"
                     | "browser hover"
+                    | "browser menu"
                     | "browser focus"
+                    | "browser tabto"
                     | "browser press"
                     | "browser type"
                     | "browser fill"
                     | "browser screenshot"
                     | "browser select"
+                    | "browser slide"
+                    | "browser date"
+                    | "browser canvas click-color"
+                    | "browser canvas draw"
                     | "browser drag"
                     | "browser upload"
                     | "browser mouse-move"
                     | "browser cursor-position"
                     | "browser scroll"
+                    | "browser tap"
+                    | "browser swipe"
+                    | "browser pinch"
+                    | "browser handoff"
                     | "browser new-tab"
                     | "browser close-tab"
+                    | "browser switch"
+                    | "browser window"
                     | "browser pdf"
+                    | "browser save"
                     | "browser wait element"
                     | "browser wait navigation"
                     | "browser wait network-idle"
                     | "browser wait condition"
+                    | "browser wait text"
+                    | "browser wait url"
+                    | "browser downloads wait"
                     | "browser cookies set"
                     | "browser cookies delete"
                     | "browser cookies clear"
+                    | "browser cookies export"
+                    | "browser state bundle"
+                    | "browser state import"
+                    | "browser cookies import"
                     | "browser local-storage set"
                     | "browser local-storage delete"
                     | "browser local-storage clear"
@@ -231,6 +253,15 @@ pub fn format_text(
                     | "browser network request"
                     | "browser network har start"
                     | "browser network har stop"
+                    | "browser record start"
+                    | "browser record stop"
+                    | "browser record-video start"
+                    | "browser record-video stop"
+                    | "browser trace start"
+                    | "browser trace stop"
+                    | "browser network route add"
+                    | "browser network route list"
+                    | "browser network route remove"
                     | "extension install"
                     | "extension uninstall"
             );
@@ -262,6 +293,12 @@ pub fn format_text(
             } else {
                 lines.push(format!("error {code}: {message}"));
             }
+            if command == "browser assert"
+                && code == "ASSERTION_FAILED"
+                && let Some(details) = result_details(result)
+            {
+                format_assert_checks(details, &mut lines);
+            }
             if !hint.is_empty() {
                 lines.push(format!("hint: {hint}"));
             }
@@ -307,6 +344,9 @@ fn format_data_fields(command: &str, data: &Value, lines: &mut Vec<String>) {
             {
                 lines.push(format!("provider: {provider}"));
             }
+            if let Some(rules) = data.pointer("/adblock/rules").and_then(|v| v.as_u64()) {
+                lines.push(format!("adblock: {rules} rules"));
+            }
             if let Some(title) = data
                 .get("tab")
                 .and_then(|t| t.get("title"))
@@ -345,10 +385,50 @@ fn format_data_fields(command: &str, data: &Value, lines: &mut Vec<String>) {
                 if let Some(provider) = s.get("provider").and_then(|v| v.as_str()) {
                     lines.push(format!("provider: {provider}"));
                 }
+                if let Some(mode) = s.get("headless_mode").and_then(|v| v.as_str()) {
+                    lines.push(format!("headless: {mode}"));
+                }
+                if let Some(device) = s.get("device").and_then(|v| v.as_str()) {
+                    lines.push(format!("device: {device}"));
+                }
                 if let Some(tabs) = s.get("tabs_count").and_then(|v| v.as_u64()) {
                     lines.push(format!("tabs: {tabs}"));
                 }
             }
+            if let Some(h) = data.get("health") {
+                if let Some(age) = h.get("age_secs").and_then(|v| v.as_u64()) {
+                    lines.push(format!("age: {age}s"));
+                }
+                if let Some(cdp) = h.get("cdp") {
+                    let responsive = cdp.get("responsive").and_then(|v| v.as_bool()) == Some(true);
+                    lines.push(format!(
+                        "cdp: {}",
+                        if responsive {
+                            "responsive"
+                        } else {
+                            "unresponsive"
+                        }
+                    ));
+                }
+                if let Some(bridge) = h.get("bridge") {
+                    let connected =
+                        bridge.get("extension_connected").and_then(|v| v.as_bool()) == Some(true);
+                    lines.push(format!(
+                        "extension: {}",
+                        if connected {
+                            "connected"
+                        } else {
+                            "not connected"
+                        }
+                    ));
+                }
+                if let Some(pid) = h.pointer("/chrome/pid").and_then(|v| v.as_u64()) {
+                    match h.pointer("/chrome/rss_bytes").and_then(|v| v.as_u64()) {
+                        Some(rss) => lines.push(format!("chrome: pid {pid}, {} MiB", rss >> 20)),
+                        None => lines.push(format!("chrome: pid {pid}")),
+                    }
+                }
+            }
         }
         "extension status" => {
             if let Some(bridge) = data.get("bridge").and_then(|v| v.as_str()) {
@@ -365,6 +445,289 @@ fn format_data_fields(command: &str, data: &Value, lines: &mut Vec<String>) {
             ));
             lines.push("  (check version at chrome://extensions/)".to_string());
         }
+        "extension version" => {
+            let str_of = |key: &str| data.get(key).and_then(|v| v.as_str());
+            if data.get("connected").and_then(|v| v.as_bool()) == Some(true) {
+                let version = str_of("extension_version").unwrap_or("unknown");
+                let protocol = str_of("protocol_version").unwrap_or("unknown");
+                lines.push(format!("extension: {version} (protocol {protocol})"));
+            } else {
+                lines.push("extension: not connected".to_string());
+            }
+            if let Some(required) = str_of("required_protocol_version") {
+                lines.push(format!("required_protocol: >= {required}"));
+            }
+            if let Some(compatible) = data.get("compatible").and_then(|v| v.as_bool()) {
+                lines.push(format!("compatible: {compatible}"));
+            }
+            if let Some(installed) = str_of("installed_version") {
+                lines.push(format!("installed: {installed}"));
+            }
+            if let Some(bundled) = str_of("bundled_version") {
+                lines.push(format!("bundled: {bundled}"));
+            }
+            if let Some(path) = data.pointer("/updated/path").and_then(|v| v.as_str()) {
+                lines.push(format!("updated: {path}"));
+                lines.push("  (reload the extension at chrome://extensions/)".to_string());
+            } else if data["compatible"] == Value::Bool(false)
+                || data["update_available"] == Value::Bool(true)
+            {
+                lines.push(
+                    "  (run `actionbook extension version --update` to install the bundled build)"
+                        .to_string(),
+                );
+            }
+        }
+        "audit tail" | "audit search" => {
+            let records = data.get("records").and_then(|v| v.as_array());
+            let count = records.map_or(0, |r| r.len());
+            lines.push(format!(
+                "{count} {}",
+                if count == 1 { "record" } else { "records" }
+            ));
+            for r in records.into_iter().flatten() {
+                let s = |k: &str| r.get(k).and_then(|v| v.as_str()).unwrap_or("");
+                let addr = match (s("session_id"), s("tab_id")) {
+                    ("", _) => String::new(),
+                    (sid, "") => format!(" [{sid}]"),
+                    (sid, tid) => format!(" [{sid} {tid}]"),
+                };
+                let outcome = match s("error_code") {
+                    "" => s("result").to_string(),
+                    code => format!("{} {code}", s("result")),
+                };
+                let target = match s("target") {
+                    "" => String::new(),
+                    t => format!(" {t}"),
+                };
+                lines.push(format!(
+                    "{} {} {}{addr}{target} risk={}",
+                    s("ts"),
+                    outcome,
+                    s("command"),
+                    s("risk")
+                ));
+            }
+        }
+        "stats" => {
+            let total = data.get("total").and_then(|v| v.as_u64()).unwrap_or(0);
+            let enabled = data.get("enabled").and_then(|v| v.as_bool()) == Some(true);
+            if total == 0 {
+                lines.push(if enabled {
+                    "Usage stats are on; nothing recorded yet".to_string()
+                } else {
+                    "Usage stats are off; turn them on with `actionbook stats enable`".to_string()
+                });
+            } else {
+                lines.push(format!(
+                    "Usage stats ({}) since {}: {total} runs",
+                    if enabled { "on" } else { "off" },
+                    data.get("since").and_then(|v| v.as_str()).unwrap_or("?")
+                ));
+            }
+            let bound = |v: Option<&Value>| match v.and_then(|v| v.as_u64()) {
+                Some(ms) => format!("<={ms}ms"),
+                None => ">30000ms".to_string(),
+            };
+            for c in data
+                .get("commands")
+                .and_then(|v| v.as_array())
+                .into_iter()
+                .flatten()
+            {
+                let n = |k: &str| c.get(k).and_then(|v| v.as_u64()).unwrap_or(0);
+                let mut line = format!(
+                    "  {} {} runs, p50 {}, p95 {}",
+                    c.get("command").and_then(|v| v.as_str()).unwrap_or(""),
+                    n("count"),
+                    bound(c.get("p50_ms")),
+                    bound(c.get("p95_ms")),
+                );
+                if n("failed") > 0 {
+                    let codes: Vec<String> = c
+                        .get("failures")
+                        .and_then(|v| v.as_object())
+                        .into_iter()
+                        .flatten()
+                        .map(|(code, count)| format!("{code} {count}"))
+                        .collect();
+                    line.push_str(&format!(", {} failed ({})", n("failed"), codes.join(", ")));
+                }
+                lines.push(line);
+            }
+        }
+        "stats enable" | "stats disable" => {
+            if data.get("enabled").and_then(|v| v.as_bool()) == Some(true) {
+                lines.push(format!(
+                    "Usage stats on; recording to {}",
+                    data.get("path").and_then(|v| v.as_str()).unwrap_or("")
+                ));
+            } else {
+                lines.push("Usage stats off; recorded counters are kept".to_string());
+            }
+        }
+        "stats reset" => {
+            lines.push(
+                if data.get("reset").and_then(|v| v.as_bool()) == Some(true) {
+                    "Usage stats cleared".to_string()
+                } else {
+                    "No usage stats recorded".to_string()
+                },
+            );
+        }
+        "stats upload" => {
+            let commands = data
+                .pointer("/uploaded/commands")
+                .and_then(|v| v.as_object())
+                .map_or(0, |m| m.len());
+            let mut line = format!("Uploaded usage stats for {commands} commands");
+            if data.get("reset").and_then(|v| v.as_bool()) == Some(true) {
+                line.push_str("; local counters cleared");
+            }
+            lines.push(line);
+        }
+        "auth add" => {
+            let s = |k: &str| data.get(k).and_then(|v| v.as_str()).unwrap_or("");
+            let totp = if data.get("totp").and_then(|v| v.as_bool()) == Some(true) {
+                ", TOTP"
+            } else {
+                ""
+            };
+            let store = match s("store") {
+                "keyring" => "the OS keyring",
+                _ => "the owner-only credential file (no OS keyring found)",
+            };
+            lines.push(format!(
+                "Stored login for {} ({}{totp}) in {store}",
+                s("domain"),
+                s("user")
+            ));
+        }
+        "auth list" => {
+            let credentials = data
+                .get("credentials")
+                .and_then(|v| v.as_array())
+                .cloned()
+                .unwrap_or_default();
+            if credentials.is_empty() {
+                lines.push("No logins stored; add one with `actionbook auth add`".to_string());
+            }
+            for c in &credentials {
+                let s = |k: &str| c.get(k).and_then(|v| v.as_str()).unwrap_or("");
+                let totp = if c.get("totp").and_then(|v| v.as_bool()) == Some(true) {
+                    " +totp"
+                } else {
+                    ""
+                };
+                lines.push(format!(
+                    "{}  {}{totp} ({})",
+                    s("domain"),
+                    s("user"),
+                    s("store")
+                ));
+            }
+        }
+        "auth remove" => {
+            let domain = data.get("domain").and_then(|v| v.as_str()).unwrap_or("");
+            lines.push(format!("Removed login for {domain}"));
+        }
+        "auth totp" => {
+            // Just the code, so `$(actionbook auth totp example.com)` works.
+            lines.push(
+                data.get("code")
+                    .and_then(|v| v.as_str())
+                    .unwrap_or("")
+                    .to_string(),
+            );
+        }
+        "flow import" => {
+            let path = data.get("path").and_then(|v| v.as_str()).unwrap_or("");
+            let steps = data.get("steps").and_then(|v| v.as_u64()).unwrap_or(0);
+            lines.push(format!("Wrote {path}: {steps} step(s)"));
+            for s in data
+                .get("skipped")
+                .and_then(|v| v.as_array())
+                .into_iter()
+                .flatten()
+            {
+                lines.push(format!(
+                    "  skipped line {}: {}",
+                    s.get("line").and_then(|v| v.as_u64()).unwrap_or(0),
+                    s.get("code").and_then(|v| v.as_str()).unwrap_or("")
+                ));
+            }
+        }
+        "flow export" => {
+            let path = data.get("path").and_then(|v| v.as_str()).unwrap_or("");
+            let steps = data.get("steps").and_then(|v| v.as_u64()).unwrap_or(0);
+            lines.push(format!("Wrote {path}: {steps} step(s)"));
+            for s in data
+                .get("skipped")
+                .and_then(|v| v.as_array())
+                .into_iter()
+                .flatten()
+            {
+                lines.push(format!(
+                    "  skipped step {}: {}",
+                    s.get("step").and_then(|v| v.as_u64()).unwrap_or(0),
+                    s.get("reason").and_then(|v| v.as_str()).unwrap_or("")
+                ));
+            }
+        }
+        "session replay" => {
+            let s =
+                |v: &Value, k: &str| v.get(k).and_then(|v| v.as_str()).unwrap_or("").to_string();
+            let event_line = |e: &Value| {
+                let seq = e.get("seq").and_then(|v| v.as_u64()).unwrap_or(0);
+                let outcome = match s(e, "error_code").as_str() {
+                    "" => String::new(),
+                    code => format!(" {code}"),
+                };
+                let artifact = match s(e, "artifact").as_str() {
+                    "" => String::new(),
+                    a => format!(" -> {a}"),
+                };
+                format!(
+                    "{seq:>4} {} {:<10} {} {}{outcome}{artifact}",
+                    s(e, "ts"),
+                    s(e, "kind"),
+                    s(e, "command"),
+                    s(e, "url")
+                )
+            };
+            if let Some(event) = data.get("event") {
+                lines.push(event_line(event));
+                let title = s(event, "title");
+                if !title.is_empty() {
+                    lines.push(format!("title: {title}"));
+                }
+                if let Some(path) = data.get("artifact_path").and_then(|v| v.as_str()) {
+                    lines.push(format!("artifact: {path}"));
+                }
+                let nav = |k: &str| data.get(k).and_then(|v| v.as_u64());
+                match (nav("prev"), nav("next")) {
+                    (None, None) => {}
+                    (prev, next) => lines.push(format!(
+                        "prev: {}  next: {}",
+                        prev.map_or("-".to_string(), |p| p.to_string()),
+                        next.map_or("-".to_string(), |n| n.to_string())
+                    )),
+                }
+                if let Some(content) = data.get("content").and_then(|v| v.as_str()) {
+                    lines.push(content.trim_end().to_string());
+                }
+            } else {
+                let events = data.get("events").and_then(|v| v.as_array());
+                let count = events.map_or(0, |e| e.len());
+                lines.push(format!(
+                    "{count} {}",
+                    if count == 1 { "event" } else { "events" }
+                ));
+                for e in events.into_iter().flatten() {
+                    lines.push(event_line(e));
+                }
+            }
+        }
         "extension ping" => {
             if let Some(bridge) = data.get("bridge").and_then(|v| v.as_str()) {
                 lines.push(format!("bridge: {bridge}"));
@@ -416,6 +779,36 @@ fn format_data_fields(command: &str, data: &Value, lines: &mut Vec<String>) {
                 lines.push(format!("closed_tabs: {tabs}"));
             }
         }
+        "browser emulate" => {
+            match data.get("device").filter(|d| !d.is_null()) {
+                Some(d) => lines.push(format!("device: {}", describe_device(d))),
+                None => lines.push("device: none".to_string()),
+            }
+            if let Some(tabs) = data.get("tabs").and_then(|v| v.as_u64()) {
+                lines.push(format!("tabs: {tabs}"));
+            }
+            if let Some(devices) = data.get("devices").and_then(|v| v.as_array()) {
+                lines.push("devices:".to_string());
+                for d in devices {
+                    lines.push(format!("  {}", describe_device(d)));
+                }
+            }
+        }
+        "browser emulate network" => {
+            match data.get("network").filter(|n| !n.is_null()) {
+                Some(n) => lines.push(format!("network: {}", describe_network(n))),
+                None => lines.push("network: unthrottled".to_string()),
+            }
+            if let Some(tabs) = data.get("tabs").and_then(|v| v.as_u64()) {
+                lines.push(format!("tabs: {tabs}"));
+            }
+            if let Some(presets) = data.get("presets").and_then(|v| v.as_array()) {
+                lines.push("presets:".to_string());
+                for n in presets {
+                    lines.push(format!("  {}", describe_network(n)));
+                }
+            }
+        }
         "browser restart" => {
             if let Some(status) = data
                 .get("session")
@@ -434,10 +827,15 @@ fn format_data_fields(command: &str, data: &Value, lines: &mut Vec<String>) {
                     let tid = t.get("tab_id").and_then(|v| v.as_str()).unwrap_or("?");
                     let title = t.get("title").and_then(|v| v.as_str()).unwrap_or("");
                     let url = t.get("url").and_then(|v| v.as_str()).unwrap_or("");
+                    let active = if t.get("active").and_then(|v| v.as_bool()) == Some(true) {
+                        " (active)"
+                    } else {
+                        ""
+                    };
                     if title.is_empty() {
-                        lines.push(format!("[{tid}]"));
+                        lines.push(format!("[{tid}]{active}"));
                     } else {
-                        lines.push(format!("[{tid}] {title}"));
+                        lines.push(format!("[{tid}] {title}{active}"));
                     }
                     lines.push(url.to_string());
                 }
@@ -460,10 +858,51 @@ fn format_data_fields(command: &str, data: &Value, lines: &mut Vec<String>) {
         "browser close-tab" => {
             // No additional fields per §8.3 text format
         }
+        "browser switch" => {
+            if let Some(prev) = data.get("previous_tab_id").and_then(|v| v.as_str())
+                && Some(prev) != data.get("tab_id").and_then(|v| v.as_str())
+            {
+                lines.push(format!("previous: {prev}"));
+            }
+        }
+        "browser window" => {
+            let n = |k: &str| data.get(k).and_then(|v| v.as_i64());
+            if let (Some(w), Some(h)) = (n("width"), n("height")) {
+                let at = match (n("left"), n("top")) {
+                    (Some(x), Some(y)) => format!(" at {x},{y}"),
+                    _ => String::new(),
+                };
+                let state = data
+                    .get("state")
+                    .and_then(|v| v.as_str())
+                    .unwrap_or("normal");
+                lines.push(format!("{w}x{h}{at} ({state})"));
+            }
+        }
+        "browser handoff" => {
+            let by = data
+                .get("completed_by")
+                .and_then(|v| v.as_str())
+                .unwrap_or("done");
+            let secs = data.get("elapsed_ms").and_then(|v| v.as_u64()).unwrap_or(0) / 1000;
+            lines.push(format!("resumed after {secs}s (completed by {by})"));
+        }
         "browser goto" | "browser back" | "browser forward" | "browser reload" => {
             if let Some(title) = data.get("title").and_then(|v| v.as_str()) {
                 lines.push(format!("title: {title}"));
             }
+            if let Some(consent) = data.get("consent") {
+                let s = |k: &str| consent.get(k).and_then(|v| v.as_str());
+                if consent.get("dismissed").and_then(|v| v.as_bool()) == Some(true) {
+                    let via = s("cmp").unwrap_or("heuristic");
+                    lines.push(format!(
+                        "consent: {} ({via})",
+                        s("action").unwrap_or("dismissed")
+                    ));
+                } else {
+                    lines.push("consent: no banner found".to_string());
+                }
+            }
         }
         "browser type" | "browser fill" => {
             if let Some(sel) = data.pointer("/target/selector").and_then(|v| v.as_str()) {
@@ -498,6 +937,12 @@ fn format_data_fields(command: &str, data: &Value, lines: &mut Vec<String>) {
             {
                 lines.push(format!("by_ref: {by_ref}"));
             }
+            if let Some(index) = data
+                .pointer("/value_summary/index")
+                .and_then(|v| v.as_u64())
+            {
+                lines.push(format!("index: {index}"));
+            }
         }
         "browser click" | "browser batch-click" => {
             // Batch response has "clicks" + "results" array
@@ -519,6 +964,12 @@ fn format_data_fields(command: &str, data: &Value, lines: &mut Vec<String>) {
                 {
                     lines.push(format!("target: {coords}"));
                 }
+                if let (Some(x), Some(y)) = (
+                    data.pointer("/point/x").and_then(|v| v.as_f64()),
+                    data.pointer("/point/y").and_then(|v| v.as_f64()),
+                ) {
+                    lines.push(format!("point: {},{}", x.round(), y.round()));
+                }
             }
         }
         "browser hover" | "browser focus" => {
@@ -526,6 +977,27 @@ fn format_data_fields(command: &str, data: &Value, lines: &mut Vec<String>) {
                 lines.push(format!("target: {sel}"));
             }
         }
+        "browser tabto" => {
+            if let Some(sel) = data.pointer("/target/selector").and_then(|v| v.as_str()) {
+                lines.push(format!("target: {sel}"));
+            }
+            if let Some(n) = data.get("presses").and_then(|v| v.as_u64()) {
+                let key = if data.get("reverse").and_then(|v| v.as_bool()) == Some(true) {
+                    "Shift+Tab"
+                } else {
+                    "Tab"
+                };
+                lines.push(format!("presses: {n} ({key})"));
+            }
+        }
+        "browser menu" => {
+            if let Some(sel) = data.pointer("/target/selector").and_then(|v| v.as_str()) {
+                lines.push(format!("target: {sel}"));
+            }
+            if let Some(sel) = data.pointer("/item/selector").and_then(|v| v.as_str()) {
+                lines.push(format!("clicked: {sel}"));
+            }
+        }
         "browser mouse-move" => {
             if let Some(coords) = data.pointer("/target/coordinates").and_then(|v| v.as_str()) {
                 lines.push(format!("target: {coords}"));
@@ -543,6 +1015,9 @@ fn format_data_fields(command: &str, data: &Value, lines: &mut Vec<String>) {
             if let Some(dir) = data.get("direction").and_then(|v| v.as_str()) {
                 lines.push(format!("direction: {dir}"));
             }
+            if let Some(delta) = data.get("delta") {
+                lines.push(format!("delta: {},{}", delta["x"], delta["y"]));
+            }
             if let Some(sel) = data.pointer("/target/selector").and_then(|v| v.as_str()) {
                 lines.push(format!("target: {sel}"));
             }
@@ -550,6 +1025,67 @@ fn format_data_fields(command: &str, data: &Value, lines: &mut Vec<String>) {
                 lines.push(format!("container: {container}"));
             }
         }
+        "browser tap" => {
+            if let Some(target) = data
+                .pointer("/target/selector")
+                .or_else(|| data.pointer("/target/coordinates"))
+                .and_then(|v| v.as_str())
+            {
+                lines.push(format!("target: {target}"));
+            }
+        }
+        "browser swipe" => {
+            if let Some(dir) = data.get("direction").and_then(|v| v.as_str()) {
+                lines.push(format!("direction: {dir}"));
+            }
+            if let (Some(from), Some(to)) = (data.get("from"), data.get("to")) {
+                let xy = |p: &Value| {
+                    format!(
+                        "{},{}",
+                        p["x"].as_f64().unwrap_or(0.0) as i64,
+                        p["y"].as_f64().unwrap_or(0.0) as i64
+                    )
+                };
+                lines.push(format!("path: {} -> {}", xy(from), xy(to)));
+            }
+        }
+        "browser pinch" => {
+            if let Some(dir) = data.get("direction").and_then(|v| v.as_str()) {
+                let scale = data.get("scale").and_then(|v| v.as_f64()).unwrap_or(1.0);
+                lines.push(format!("pinch: {dir} (scale {scale})"));
+            }
+            if let Some(sel) = data.pointer("/target/selector").and_then(|v| v.as_str()) {
+                lines.push(format!("target: {sel}"));
+            }
+        }
+        "browser slide" => {
+            if let Some(sel) = data.pointer("/target/selector").and_then(|v| v.as_str()) {
+                lines.push(format!("target: {sel}"));
+            }
+            let range = match (data["min"].as_f64(), data["max"].as_f64()) {
+                (Some(min), Some(max)) => format!(" (range {min}..{max})"),
+                _ => String::new(),
+            };
+            match data["value"].as_f64() {
+                Some(value) => lines.push(format!("value: {value}{range}")),
+                None => lines.push("value: unknown (slider has no aria-valuenow)".to_string()),
+            }
+        }
+        "browser date" => {
+            if let Some(sel) = data.pointer("/target/selector").and_then(|v| v.as_str()) {
+                lines.push(format!("target: {sel}"));
+            }
+            if let Some(date) = data.get("date").and_then(|v| v.as_str()) {
+                let via = data
+                    .get("strategy")
+                    .and_then(|v| v.as_str())
+                    .unwrap_or("auto");
+                lines.push(format!("date: {date} (via {via})"));
+            }
+            if let Some(value) = data.get("value").and_then(|v| v.as_str()) {
+                lines.push(format!("value: {value}"));
+            }
+        }
         "browser drag" => {
             if let Some(sel) = data.pointer("/target/selector").and_then(|v| v.as_str()) {
                 lines.push(format!("target: {sel}"));
@@ -580,6 +1116,13 @@ fn format_data_fields(command: &str, data: &Value, lines: &mut Vec<String>) {
         "browser press" => {
             if let Some(keys) = data.get("keys").and_then(|v| v.as_str()) {
                 lines.push(format!("keys: {keys}"));
+                let sequence: Vec<&str> = data["sequence"]
+                    .as_array()
+                    .map(|a| a.iter().filter_map(|v| v.as_str()).collect())
+                    .unwrap_or_default();
+                if sequence.len() > 1 || sequence.first().is_some_and(|k| *k != keys) {
+                    lines.push(format!("pressed: {}", sequence.join(", ")));
+                }
             }
         }
         "browser screenshot" => {
@@ -600,11 +1143,244 @@ fn format_data_fields(command: &str, data: &Value, lines: &mut Vec<String>) {
                 lines.push(text_scalar(val));
             }
         }
+        "browser options" => {
+            for opt in data
+                .get("options")
+                .and_then(|v| v.as_array())
+                .into_iter()
+                .flatten()
+            {
+                let index = opt.get("index").and_then(|v| v.as_u64()).unwrap_or(0);
+                let value = opt.get("value").and_then(|v| v.as_str()).unwrap_or("");
+                let label = opt.get("label").and_then(|v| v.as_str()).unwrap_or("");
+                let mut line = format!("[{index}] {value} \"{label}\"");
+                if let Some(group) = opt.get("group").and_then(|v| v.as_str()) {
+                    line.push_str(&format!(" ({group})"));
+                }
+                if opt.get("selected").and_then(|v| v.as_bool()) == Some(true) {
+                    line.push_str(" selected");
+                }
+                if opt.get("disabled").and_then(|v| v.as_bool()) == Some(true) {
+                    line.push_str(" disabled");
+                }
+                lines.push(line);
+            }
+        }
         "browser title" | "browser url" => {
             if let Some(val) = data.get("value").and_then(|v| v.as_str()) {
                 lines.push(val.to_string());
             }
         }
+        "browser meta" => {
+            for key in ["title", "description", "canonical", "lang"] {
+                if let Some(val) = data.get(key).and_then(|v| v.as_str()) {
+                    lines.push(format!("{key}: {val}"));
+                }
+            }
+            for group in ["og", "twitter"] {
+                if let Some(tags) = data.get(group).and_then(|v| v.as_object()) {
+                    for (name, val) in tags {
+                        lines.push(format!("{group}:{name}: {}", text_scalar(val)));
+                    }
+                }
+            }
+            if let Some(items) = data.get("json_ld").and_then(|v| v.as_array())
+                && !items.is_empty()
+            {
+                let types: Vec<String> = items
+                    .iter()
+                    .filter_map(|item| item.get("@type"))
+                    .map(text_scalar)
+                    .collect();
+                lines.push(format!("json_ld: {} ({})", items.len(), types.join(", ")));
+            }
+        }
+        "browser images" => {
+            let images = data.get("images").and_then(|v| v.as_array());
+            let count = images.map_or(0, |i| i.len());
+            lines.push(format!(
+                "{count} {}",
+                if count == 1 { "image" } else { "images" }
+            ));
+            for img in images.into_iter().flatten() {
+                let s = |v: &Value, k: &str| {
+                    v.get(k).and_then(|x| x.as_str()).unwrap_or("").to_string()
+                };
+                let n = |k: &str| img.get(k).and_then(|v| v.as_u64()).unwrap_or(0);
+                let alt = match s(img, "alt").as_str() {
+                    "" => String::new(),
+                    a => format!(" \"{a}\""),
+                };
+                lines.push(format!(
+                    "{}x{} {}{alt}",
+                    n("width"),
+                    n("height"),
+                    s(img, "url")
+                ));
+                if let Some(err) = img.get("error") {
+                    lines.push(format!("  error {}: {}", s(err, "code"), s(err, "message")));
+                } else if img.get("path").is_some() {
+                    lines.push(format!("  -> {}", s(img, "path")));
+                }
+            }
+        }
+        "browser table" => {
+            let n = |k: &str| data.get(k).and_then(|v| v.as_u64()).unwrap_or(0);
+            if let Some(sink) = data.get("sink") {
+                let s = |k: &str| sink.get(k).and_then(|v| v.as_str()).unwrap_or("");
+                let mut line = format!(
+                    "{} rows -> {}:{}",
+                    sink.get("rows_written")
+                        .and_then(|v| v.as_u64())
+                        .unwrap_or(0),
+                    s("kind"),
+                    s("path")
+                );
+                if !s("table").is_empty() {
+                    line.push_str(&format!(" (table {})", s("table")));
+                }
+                lines.push(line);
+                let columns: Vec<String> = sink
+                    .get("columns")
+                    .and_then(|v| v.as_array())
+                    .into_iter()
+                    .flatten()
+                    .map(|c| {
+                        let f = |k: &str| c.get(k).and_then(|v| v.as_str()).unwrap_or("");
+                        format!("{} {}", f("name"), f("type"))
+                    })
+                    .collect();
+                lines.push(format!("columns: {}", columns.join(", ")));
+            } else if let Some(path) = data.get("path").and_then(|v| v.as_str()) {
+                lines.push(format!(
+                    "{} rows x {} columns -> {path}",
+                    n("row_count"),
+                    n("column_count")
+                ));
+            } else if let Some(csv) = data.get("csv").and_then(|v| v.as_str()) {
+                lines.push(csv.trim_end().replace("\r\n", "\n"));
+            } else if let Some(rows) = data.get("rows") {
+                lines.push(serde_json::to_string_pretty(rows).unwrap_or_default());
+            }
+            let failures = data
+                .get("parse_failures")
+                .and_then(|v| v.as_array())
+                .map_or(0, Vec::len);
+            if failures > 0 {
+                lines.push(format!("{failures} cells did not parse (see --json)"));
+            }
+        }
+        "browser fetch" => {
+            let s = |k: &str| data.get(k).and_then(|v| v.as_str()).unwrap_or("");
+            let mut head = format!(
+                "{} {}",
+                data.get("status").and_then(|v| v.as_u64()).unwrap_or(0),
+                s("url")
+            );
+            if !s("content_type").is_empty() {
+                head.push_str(&format!(" ({})", s("content_type")));
+            }
+            let bytes = data.get("bytes").and_then(|v| v.as_u64()).unwrap_or(0);
+            head.push_str(&format!(" {bytes} bytes"));
+            if data.get("path").is_some() {
+                head.push_str(&format!(" -> {}", s("path")));
+            }
+            lines.push(head);
+            if data.get("body").is_some() {
+                lines.push(s("body").to_string());
+            }
+        }
+        "browser trace-redirects" => {
+            for (i, hop) in data
+                .get("hops")
+                .and_then(|v| v.as_array())
+                .into_iter()
+                .flatten()
+                .enumerate()
+            {
+                let status = hop
+                    .get("status")
+                    .and_then(|v| v.as_u64())
+                    .map_or("---".to_string(), |s| s.to_string());
+                let mut line = format!(
+                    "{}. {status} {}",
+                    i + 1,
+                    hop.get("url").and_then(|v| v.as_str()).unwrap_or("")
+                );
+                if let Some(kind) = hop.get("type").and_then(|v| v.as_str()) {
+                    line.push_str(&format!(" ({kind})"));
+                }
+                if let Some(err) = hop.get("error").and_then(|v| v.as_str()) {
+                    line.push_str(&format!(" error: {err}"));
+                }
+                lines.push(line);
+            }
+            if data.get("settled").and_then(|v| v.as_bool()) == Some(false) {
+                lines.push("still redirecting when --timeout ran out".to_string());
+            }
+        }
+        "browser assert" => format_assert_checks(data, lines),
+        "browser response" => {
+            let s =
+                |v: &Value, k: &str| v.get(k).and_then(|x| x.as_str()).unwrap_or("").to_string();
+            for hop in data
+                .get("redirects")
+                .and_then(|v| v.as_array())
+                .into_iter()
+                .flatten()
+            {
+                lines.push(format!(
+                    "{} {} -> {}",
+                    hop.get("status").and_then(|v| v.as_u64()).unwrap_or(0),
+                    s(hop, "url"),
+                    s(hop, "location")
+                ));
+            }
+            let url = s(data, "url");
+            if let Some(status) = data.get("status").and_then(|v| v.as_u64()) {
+                let mut line = format!("{status} {}", s(data, "status_text"))
+                    .trim_end()
+                    .to_string();
+                line.push_str(&format!(" {url}"));
+                if let Some(ms) = data.pointer("/timing/response_ms").and_then(|v| v.as_u64()) {
+                    line.push_str(&format!(" ({ms} ms)"));
+                }
+                lines.push(line);
+            } else {
+                lines.push(format!("failed {url}: {}", s(data, "error")));
+            }
+            if let Some(headers) = data.get("headers").and_then(|v| v.as_object()) {
+                for (name, value) in headers {
+                    lines.push(format!("{name}: {}", text_scalar(value)));
+                }
+            }
+        }
+        "browser feeds" => {
+            let feeds = data.get("feeds").and_then(|v| v.as_array());
+            let count = feeds.map_or(0, |f| f.len());
+            lines.push(format!(
+                "{count} {}",
+                if count == 1 { "feed" } else { "feeds" }
+            ));
+            for feed in feeds.into_iter().flatten() {
+                let s = |v: &Value, k: &str| {
+                    v.get(k).and_then(|x| x.as_str()).unwrap_or("").to_string()
+                };
+                let title = match s(feed, "title").as_str() {
+                    "" => String::new(),
+                    t => format!(" {t}"),
+                };
+                lines.push(format!("[{}]{title} {}", s(feed, "format"), s(feed, "url")));
+                if let Some(err) = feed.get("error") {
+                    lines.push(format!("  error {}: {}", s(err, "code"), s(err, "message")));
+                }
+                if let Some(items) = feed.pointer("/feed/items").and_then(|v| v.as_array()) {
+                    for item in items {
+                        lines.push(format!("  - {} {}", s(item, "title"), s(item, "link")));
+                    }
+                }
+            }
+        }
         "browser viewport" => {
             let width = data.get("width").and_then(|v| v.as_u64());
             let height = data.get("height").and_then(|v| v.as_u64());
@@ -670,6 +1446,9 @@ fn format_data_fields(command: &str, data: &Value, lines: &mut Vec<String>) {
                 }
             }
         }
+        "browser describe" if data.pointer("/target/page").is_some() => {
+            format_describe_page(data, lines);
+        }
         "browser describe" => {
             let summary = data
                 .get("summary")
@@ -776,7 +1555,7 @@ fn format_data_fields(command: &str, data: &Value, lines: &mut Vec<String>) {
                 }
             }
         }
-        "browser pdf" => {
+        "browser pdf" | "browser save" => {
             // §10.3: path line
             if let Some(path) = data
                 .get("artifact")
@@ -892,59 +1671,267 @@ fn format_data_fields(command: &str, data: &Value, lines: &mut Vec<String>) {
                 lines.push(format!("dropped: {n}"));
             }
         }
-        "browser logs console" | "browser logs errors" => {
-            // §10.12-§10.13: N log(s) then level timestamp source text per item
-            if let Some(items) = data.get("items").and_then(|v| v.as_array()) {
-                let count = items.len();
-                let label = if count == 1 { "log" } else { "logs" };
-                lines.push(format!("{count} {label}"));
-                for item in items {
-                    let level = item.get("level").and_then(|v| v.as_str()).unwrap_or("log");
-                    let ts = item
-                        .get("timestamp_ms")
-                        .and_then(|v| v.as_u64())
-                        .unwrap_or(0);
-                    let source = item.get("source").and_then(|v| v.as_str()).unwrap_or("");
-                    let text = item.get("text").and_then(|v| v.as_str()).unwrap_or("");
-                    lines.push(format!("{level} {ts} {source} {text}"));
-                }
+        "browser canvas click-color" => {
+            if let Some(sel) = data.pointer("/target/selector").and_then(|v| v.as_str()) {
+                lines.push(format!("target: {sel}"));
             }
-        }
-        "browser wait element" => {
-            if let Some(ms) = data.get("elapsed_ms").and_then(|v| v.as_u64()) {
-                lines.push(format!("elapsed_ms: {ms}"));
+            if let (Some(x), Some(y)) = (
+                data.get("x").and_then(|v| v.as_f64()),
+                data.get("y").and_then(|v| v.as_f64()),
+            ) {
+                let color = data.get("color").and_then(|v| v.as_str()).unwrap_or("");
+                lines.push(format!("clicked {color} at ({x:.0}, {y:.0})"));
             }
-            if let Some(sel) = data
-                .pointer("/observed_value/selector")
-                .and_then(|v| v.as_str())
-            {
+            if let Some(n) = data.get("matches").and_then(|v| v.as_u64()) {
+                lines.push(format!("matches: {n} px"));
+            }
+        }
+        "browser canvas draw" => {
+            if let Some(sel) = data.pointer("/target/selector").and_then(|v| v.as_str()) {
                 lines.push(format!("target: {sel}"));
             }
+            let n = |k: &str| data.get(k).and_then(|v| v.as_u64()).unwrap_or(0);
+            lines.push(format!(
+                "drew {} stroke(s) through {} point(s)",
+                n("strokes"),
+                n("points")
+            ));
         }
-        "browser wait navigation" | "browser wait network-idle" => {
-            if let Some(ms) = data.get("elapsed_ms").and_then(|v| v.as_u64()) {
-                lines.push(format!("elapsed_ms: {ms}"));
+        "browser record start" => {
+            if let Some(out) = data.get("out").and_then(|v| v.as_str()) {
+                lines.push(format!(
+                    "recording to {out} (stop with `browser record stop`)"
+                ));
             }
         }
-        "browser wait condition" => {
-            if let Some(ms) = data.get("elapsed_ms").and_then(|v| v.as_u64()) {
-                lines.push(format!("elapsed_ms: {ms}"));
+        "browser record stop" => {
+            if let Some(path) = data.get("path").and_then(|v| v.as_str()) {
+                lines.push(format!("path: {path}"));
             }
-            if let Some(val) = data.get("observed_value") {
-                lines.push(format!("observed_value: {}", text_scalar(val)));
+            if let Some(n) = data.get("steps").and_then(|v| v.as_u64()) {
+                lines.push(format!("steps: {n}"));
             }
         }
-        "browser eval" => {
-            if let Some(val) = data.get("value") {
-                lines.push(text_scalar(val));
+        "browser record-video start" => {
+            if let Some(out) = data.get("out").and_then(|v| v.as_str()) {
+                lines.push(format!(
+                    "recording video to {out} (stop with `browser record-video stop`)"
+                ));
             }
         }
-        "browser cookies list" => {
-            let items = data.get("items").and_then(|v| v.as_array());
-            let count = items.map(|v| v.len()).unwrap_or(0);
-            let label = if count == 1 { "cookie" } else { "cookies" };
-            lines.push(format!("{count} {label}"));
-            if let Some(items) = items {
+        "browser trace start" => {
+            if let Some(out) = data.get("out").and_then(|v| v.as_str()) {
+                lines.push(format!("tracing to {out} (stop with `browser trace stop`)"));
+            }
+        }
+        "browser trace stop" => {
+            let n = |k: &str| data.get(k).and_then(|v| v.as_u64()).unwrap_or(0);
+            if let Some(path) = data.get("path").and_then(|v| v.as_str()) {
+                lines.push(format!("path: {path}"));
+            }
+            lines.push(format!(
+                "{} KB, {:.1}s traced",
+                n("bytes").div_ceil(1024),
+                n("duration_ms") as f64 / 1000.0
+            ));
+        }
+        "browser record-video stop" => {
+            let n = |k: &str| data.get(k).and_then(|v| v.as_u64()).unwrap_or(0);
+            let seconds = n("duration_ms") as f64 / 1000.0;
+            if data.get("encoded").and_then(|v| v.as_bool()) == Some(true) {
+                if let Some(path) = data.get("path").and_then(|v| v.as_str()) {
+                    lines.push(format!("path: {path}"));
+                }
+                lines.push(format!("{} frame(s), {seconds:.1}s", n("frames")));
+            } else {
+                let dir = data
+                    .get("frames_dir")
+                    .and_then(|v| v.as_str())
+                    .unwrap_or("");
+                lines.push(format!(
+                    "ffmpeg not found: kept {} frame(s) ({seconds:.1}s) in {dir}",
+                    n("frames")
+                ));
+                if let Some(cmd) = data.get("ffmpeg").and_then(|v| v.as_str()) {
+                    lines.push(format!("encode with: {cmd}"));
+                }
+            }
+        }
+        "run" => {
+            let n = |k: &str| data.get(k).and_then(|v| v.as_u64()).unwrap_or(0);
+            let mut line = format!("{}/{} steps passed", n("passed"), n("total"));
+            if n("failed") > 0 {
+                line.push_str(&format!(", {} failed", n("failed")));
+            }
+            lines.push(line);
+            if let Some(extracted) = data.get("extracted").and_then(|v| v.as_object()) {
+                for (name, value) in extracted {
+                    lines.push(format!("{name}: {}", text_scalar(value)));
+                }
+            }
+            if n("healed") > 0 {
+                let noun = if n("healed") == 1 { "step" } else { "steps" };
+                let mut line = format!("healed: {} {noun}", n("healed"));
+                match data.get("heal_saved") {
+                    Some(Value::Bool(true)) => line.push_str(&format!(
+                        " (saved to {})",
+                        data.get("script").and_then(|v| v.as_str()).unwrap_or("")
+                    )),
+                    Some(Value::String(e)) => line.push_str(&format!(" ({e})")),
+                    _ => {}
+                }
+                lines.push(line);
+            }
+            if let Some(dir) = data.get("artifacts_dir").and_then(|v| v.as_str()) {
+                let removed = data
+                    .pointer("/retention/removed")
+                    .and_then(|v| v.as_array())
+                    .map_or(0, |a| a.len());
+                if removed > 0 {
+                    lines.push(format!("artifacts: {dir} (pruned {removed} old runs)"));
+                } else {
+                    lines.push(format!("artifacts: {dir}"));
+                }
+            }
+            if let Some(delivery) = data.get("delivery") {
+                let mut parts = Vec::new();
+                if let Some(s3) = delivery.get("s3") {
+                    let prefix = s3.get("prefix").and_then(|v| v.as_str()).unwrap_or("");
+                    let bucket = s3.get("bucket").and_then(|v| v.as_str()).unwrap_or("");
+                    match s3.get("uploaded").and_then(|v| v.as_u64()) {
+                        Some(n) => parts.push(format!("s3://{bucket}/{prefix} ({n} files)")),
+                        None => parts.push(format!("s3://{bucket}/{prefix} failed")),
+                    }
+                }
+                if let Some(webhook) = delivery.get("webhook") {
+                    match webhook.get("status").and_then(|v| v.as_u64()) {
+                        Some(status) => parts.push(format!("webhook {status}")),
+                        None => parts.push("webhook failed".to_string()),
+                    }
+                }
+                lines.push(format!("delivered: {}", parts.join(", ")));
+            }
+        }
+        "browser network route add" => {
+            if let Some(route) = data.get("route") {
+                lines.push(format!("route: {}", route_line(route)));
+            }
+        }
+        "browser network route list" => {
+            if let Some(routes) = data.get("routes").and_then(|v| v.as_array()) {
+                let label = if routes.len() == 1 { "route" } else { "routes" };
+                lines.push(format!("{} {label}", routes.len()));
+                lines.extend(routes.iter().map(route_line));
+            }
+        }
+        "browser network route remove" => {
+            if let Some(n) = data.get("removed").and_then(|v| v.as_u64()) {
+                lines.push(format!("removed: {n}"));
+            }
+        }
+        "browser logs console" | "browser logs errors" => {
+            // §10.12-§10.13: N log(s) then level timestamp source text per item
+            if let Some(items) = data.get("items").and_then(|v| v.as_array()) {
+                let count = items.len();
+                let label = if count == 1 { "log" } else { "logs" };
+                lines.push(format!("{count} {label}"));
+                for item in items {
+                    let level = item.get("level").and_then(|v| v.as_str()).unwrap_or("log");
+                    let ts = item
+                        .get("timestamp_ms")
+                        .and_then(|v| v.as_u64())
+                        .unwrap_or(0);
+                    let source = item.get("source").and_then(|v| v.as_str()).unwrap_or("");
+                    let text = item.get("text").and_then(|v| v.as_str()).unwrap_or("");
+                    lines.push(format!("{level} {ts} {source} {text}"));
+                }
+            }
+        }
+        "browser wait element" => {
+            if let Some(ms) = data.get("elapsed_ms").and_then(|v| v.as_u64()) {
+                lines.push(format!("elapsed_ms: {ms}"));
+            }
+            if let Some(sel) = data
+                .pointer("/observed_value/selector")
+                .and_then(|v| v.as_str())
+            {
+                lines.push(format!("target: {sel}"));
+            }
+        }
+        "browser wait text" => {
+            if let Some(ms) = data.get("elapsed_ms").and_then(|v| v.as_u64()) {
+                lines.push(format!("elapsed_ms: {ms}"));
+            }
+            if let Some(text) = data
+                .pointer("/observed_value/text")
+                .and_then(|v| v.as_str())
+            {
+                lines.push(format!("text: {text}"));
+            }
+        }
+        "browser wait url" => {
+            if let Some(ms) = data.get("elapsed_ms").and_then(|v| v.as_u64()) {
+                lines.push(format!("elapsed_ms: {ms}"));
+            }
+            if let Some(url) = data.get("observed_value").and_then(|v| v.as_str()) {
+                lines.push(format!("url: {url}"));
+            }
+        }
+        "browser wait navigation" | "browser wait network-idle" => {
+            if let Some(ms) = data.get("elapsed_ms").and_then(|v| v.as_u64()) {
+                lines.push(format!("elapsed_ms: {ms}"));
+            }
+        }
+        "browser wait condition" => {
+            if let Some(ms) = data.get("elapsed_ms").and_then(|v| v.as_u64()) {
+                lines.push(format!("elapsed_ms: {ms}"));
+            }
+            if let Some(val) = data.get("observed_value") {
+                lines.push(format!("observed_value: {}", text_scalar(val)));
+            }
+        }
+        "browser eval" => {
+            if let Some(val) = data.get("value") {
+                lines.push(text_scalar(val));
+            }
+        }
+        "browser downloads list" => {
+            let items = data.get("downloads").and_then(|v| v.as_array());
+            let count = items.map_or(0, |v| v.len());
+            let label = if count == 1 { "download" } else { "downloads" };
+            lines.push(format!("{count} {label}"));
+            for item in items.into_iter().flatten() {
+                let s = |k: &str| item.get(k).and_then(|v| v.as_str()).unwrap_or("");
+                let bytes = item
+                    .get("received_bytes")
+                    .and_then(|v| v.as_u64())
+                    .unwrap_or(0);
+                let mut line = format!("{} {} {bytes} bytes", s("state"), s("filename"));
+                if !s("path").is_empty() {
+                    line.push_str(&format!(" -> {}", s("path")));
+                }
+                lines.push(line);
+            }
+        }
+        "browser downloads wait" => {
+            if let Some(d) = data.get("download") {
+                let s = |k: &str| d.get(k).and_then(|v| v.as_str()).unwrap_or("");
+                let bytes = d
+                    .get("received_bytes")
+                    .and_then(|v| v.as_u64())
+                    .unwrap_or(0);
+                lines.push(format!("filename: {} ({bytes} bytes)", s("filename")));
+                if !s("path").is_empty() {
+                    lines.push(format!("path: {}", s("path")));
+                }
+            }
+        }
+        "browser cookies list" => {
+            let items = data.get("items").and_then(|v| v.as_array());
+            let count = items.map(|v| v.len()).unwrap_or(0);
+            let label = if count == 1 { "cookie" } else { "cookies" };
+            lines.push(format!("{count} {label}"));
+            if let Some(items) = items {
                 for item in items {
                     let name = item.get("name").and_then(|v| v.as_str()).unwrap_or("");
                     let domain = item.get("domain").and_then(|v| v.as_str()).unwrap_or("");
@@ -968,6 +1955,57 @@ fn format_data_fields(command: &str, data: &Value, lines: &mut Vec<String>) {
         "browser cookies set" | "browser cookies delete" | "browser cookies clear" => {
             // is_action already emits "ok {command}"; no additional text fields needed
         }
+        "browser cookies export" => {
+            let count = data.get("count").and_then(|v| v.as_u64()).unwrap_or(0);
+            let format = data.get("format").and_then(|v| v.as_str()).unwrap_or("");
+            let path = data.get("path").and_then(|v| v.as_str()).unwrap_or("");
+            lines.push(format!("{count} cookies ({format}) -> {path}"));
+        }
+        "browser cookies import" => {
+            let imported = data.get("imported").and_then(|v| v.as_u64()).unwrap_or(0);
+            let skipped = data.get("skipped").and_then(|v| v.as_u64()).unwrap_or(0);
+            let format = data.get("format").and_then(|v| v.as_str()).unwrap_or("");
+            let mut line = format!("{imported} cookies ({format})");
+            if skipped > 0 {
+                line.push_str(&format!(", {skipped} expired skipped"));
+            }
+            lines.push(line);
+        }
+        "browser state bundle" => {
+            let n = |k: &str| data.get(k).and_then(|v| v.as_u64()).unwrap_or(0);
+            let path = data.get("path").and_then(|v| v.as_str()).unwrap_or("");
+            let sealed = if data.get("encrypted").and_then(|v| v.as_bool()) == Some(true) {
+                "encrypted"
+            } else {
+                "not encrypted"
+            };
+            lines.push(format!(
+                "{} cookies, localStorage of {} origin(s) -> {path} ({sealed})",
+                n("cookies"),
+                n("origins")
+            ));
+        }
+        "browser state import" => {
+            let n = |k: &str| data.get(k).and_then(|v| v.as_u64()).unwrap_or(0);
+            lines.push(format!(
+                "{} cookies, {} localStorage item(s) in {} origin(s)",
+                n("cookies"),
+                n("local_storage_items"),
+                n("origins")
+            ));
+            if let Some(device) = data.get("device").and_then(|v| v.as_str()) {
+                lines.push(format!("device: {device}"));
+            }
+            for w in data
+                .get("__warnings")
+                .and_then(|v| v.as_array())
+                .into_iter()
+                .flatten()
+                .filter_map(|v| v.as_str())
+            {
+                lines.push(format!("warning: {w}"));
+            }
+        }
         "browser local-storage list" | "browser session-storage list" => {
             let items = data.get("items").and_then(|v| v.as_array());
             let count = items.map(|v| v.len()).unwrap_or(0);
@@ -981,6 +2019,47 @@ fn format_data_fields(command: &str, data: &Value, lines: &mut Vec<String>) {
                 }
             }
         }
+        "browser indexeddb list" => {
+            let dbs = data.get("databases").and_then(|v| v.as_array());
+            let count = dbs.map_or(0, |d| d.len());
+            let label = if count == 1 { "database" } else { "databases" };
+            lines.push(format!("{count} {label}"));
+            for db in dbs.into_iter().flatten() {
+                lines.push(format!(
+                    "{} v{}",
+                    db["name"].as_str().unwrap_or(""),
+                    text_scalar(&db["version"])
+                ));
+                for store in db["stores"].as_array().into_iter().flatten() {
+                    let mut line = format!("  {}", store["name"].as_str().unwrap_or(""));
+                    if !store["key_path"].is_null() {
+                        line.push_str(&format!(" key={}", text_scalar(&store["key_path"])));
+                    }
+                    if let Some(n) = store["entries"].as_u64() {
+                        line.push_str(&format!(" ({n} entries)"));
+                    }
+                    lines.push(line);
+                }
+            }
+        }
+        "browser indexeddb dump" => {
+            let entries = data.get("entries").and_then(|v| v.as_array());
+            let count = entries.map_or(0, |e| e.len());
+            let label = if count == 1 { "entry" } else { "entries" };
+            let more = if data["has_more"].as_bool().unwrap_or(false) {
+                " (more)"
+            } else {
+                ""
+            };
+            lines.push(format!("{count} {label}{more}"));
+            for entry in entries.into_iter().flatten() {
+                lines.push(format!(
+                    "{}={}",
+                    text_scalar(&entry["key"]),
+                    serde_json::to_string(&entry["value"]).unwrap_or_default()
+                ));
+            }
+        }
         "browser local-storage get" | "browser session-storage get" => {
             if let Some(item) = data.get("item") {
                 if item.is_null() {
@@ -1009,6 +2088,97 @@ fn format_data_fields(command: &str, data: &Value, lines: &mut Vec<String>) {
     }
 }
 
+/// One `actionbook run` step as it finishes:
+/// `ok 2 click #submit 120ms` or `fail 3 assert h1 ~ Hi ASSERTION_FAILED: … (2 attempts)`.
+pub fn format_run_step(step: &Value) -> String {
+    let s = |k: &str| step.get(k).and_then(|v| v.as_str()).unwrap_or("");
+    let n = |k: &str| step.get(k).and_then(|v| v.as_u64()).unwrap_or(0);
+    let label = match step.get("name").and_then(|v| v.as_str()) {
+        Some(name) => format!("{} [{name}]", s("target")),
+        None => s("target").to_string(),
+    };
+    let mut line = if step.get("ok").and_then(|v| v.as_bool()) == Some(true) {
+        format!(
+            "ok {} {} {label} {}ms",
+            n("index"),
+            s("action"),
+            n("elapsed_ms")
+        )
+    } else {
+        let code = step
+            .pointer("/error/code")
+            .and_then(|v| v.as_str())
+            .unwrap_or("");
+        let message = step
+            .pointer("/error/message")
+            .and_then(|v| v.as_str())
+            .unwrap_or("");
+        format!(
+            "fail {} {} {label} {code}: {message}",
+            n("index"),
+            s("action")
+        )
+    };
+    if n("attempts") > 1 {
+        line.push_str(&format!(" ({} attempts)", n("attempts")));
+    }
+    if let Some(heal) = step.get("heal") {
+        let from = heal.get("from").and_then(|v| v.as_str()).unwrap_or("");
+        match heal.get("to").and_then(|v| v.as_str()) {
+            Some(to) if heal.get("ok").and_then(|v| v.as_bool()) == Some(true) => {
+                line.push_str(&format!(" (healed: {from} -> {to})"));
+            }
+            Some(to) => line.push_str(&format!(" (heal to {to} failed)")),
+            None => {
+                let reason = heal.get("reason").and_then(|v| v.as_str()).unwrap_or("");
+                line.push_str(&format!(" (not healed: {reason})"));
+            }
+        }
+    }
+    for capture in step
+        .get("captures")
+        .and_then(|v| v.as_array())
+        .into_iter()
+        .flatten()
+    {
+        let kind = capture.get("kind").and_then(|v| v.as_str()).unwrap_or("");
+        match capture.get("path").and_then(|v| v.as_str()) {
+            Some(path) => line.push_str(&format!(" [{kind}: {path}]")),
+            None => {
+                let error = capture.get("error").and_then(|v| v.as_str()).unwrap_or("");
+                line.push_str(&format!(" [{kind} failed: {error}]"));
+            }
+        }
+    }
+    line
+}
+
+/// `r1 fulfill */api/* -> users.json (200 application/json) hits=3`
+fn route_line(route: &Value) -> String {
+    let s = |k: &str| route.get(k).and_then(|v| v.as_str()).unwrap_or("");
+    let detail = match s("action") {
+        "fulfill" => format!(
+            " -> {} ({} {})",
+            s("file"),
+            route.get("status").and_then(|v| v.as_u64()).unwrap_or(200),
+            s("content_type")
+        ),
+        "headers" => route
+            .get("headers")
+            .and_then(|v| v.as_object())
+            .map(|h| format!(" {}", h.keys().cloned().collect::<Vec<_>>().join(",")))
+            .unwrap_or_default(),
+        _ => String::new(),
+    };
+    format!(
+        "{} {} {}{detail} hits={}",
+        s("id"),
+        s("action"),
+        s("pattern"),
+        route.get("hits").and_then(|v| v.as_u64()).unwrap_or(0)
+    )
+}
+
 fn text_scalar(value: &Value) -> String {
     match value {
         Value::Null => "null".to_string(),
@@ -1035,6 +2205,41 @@ fn is_batch_new_tab_result(result: &ActionResult) -> bool {
     }
 }
 
+/// "iPhone 14 (390x844 @3x, touch)" for a `browser emulate` device.
+fn describe_device(d: &Value) -> String {
+    let name = d.get("device").and_then(|v| v.as_str()).unwrap_or("?");
+    let width = d.get("width").and_then(|v| v.as_u64()).unwrap_or(0);
+    let height = d.get("height").and_then(|v| v.as_u64()).unwrap_or(0);
+    let scale = d
+        .get("device_scale_factor")
+        .and_then(|v| v.as_f64())
+        .unwrap_or(1.0);
+    let touch = if d.get("touch").and_then(|v| v.as_bool()) == Some(true) {
+        ", touch"
+    } else {
+        ""
+    };
+    format!("{name} ({width}x{height} @{scale}x{touch})")
+}
+
+/// `fast-3g (563ms, down 1440 kbit/s, up 675 kbit/s)`
+fn describe_network(n: &Value) -> String {
+    let name = n.get("preset").and_then(|v| v.as_str()).unwrap_or("custom");
+    if n.get("offline").and_then(|v| v.as_bool()) == Some(true) {
+        return format!("{name} (no connection)");
+    }
+    let rate = |k: &str| match n.get(k).and_then(|v| v.as_u64()) {
+        Some(kbps) => format!("{kbps} kbit/s"),
+        None => "unlimited".to_string(),
+    };
+    let latency = n.get("latency_ms").and_then(|v| v.as_u64()).unwrap_or(0);
+    format!(
+        "{name} ({latency}ms, down {}, up {})",
+        rate("download_kbps"),
+        rate("upload_kbps")
+    )
+}
+
 fn is_batch_new_tab_data(data: &Value) -> bool {
     data.get("requested_urls")
         .and_then(|v| v.as_u64())
@@ -1052,6 +2257,124 @@ fn result_details(result: &ActionResult) -> Option<&Value> {
     }
 }
 
+/// One line per `browser assert` check: `pass|FAIL <check> [selector]`, with
+/// expected and actual values on failures.
+fn format_assert_checks(data: &Value, lines: &mut Vec<String>) {
+    for check in data
+        .get("checks")
+        .and_then(|v| v.as_array())
+        .into_iter()
+        .flatten()
+    {
+        let passed = check.get("passed").and_then(|v| v.as_bool()) == Some(true);
+        let mut line = format!(
+            "{} {}",
+            if passed { "pass" } else { "FAIL" },
+            check.get("check").and_then(|v| v.as_str()).unwrap_or("")
+        );
+        match check.get("selector").and_then(|v| v.as_str()) {
+            Some(selector) => line.push_str(&format!(" {selector}")),
+            None => line.push_str(&format!(" {}", check["expected"])),
+        }
+        if !passed {
+            line.push_str(&format!(
+                ": expected {}, got {}",
+                check["expected"], check["actual"]
+            ));
+        }
+        lines.push(line);
+        // --no-console-errors lists what was logged.
+        for entry in check
+            .get("errors")
+            .and_then(|v| v.as_array())
+            .into_iter()
+            .flatten()
+        {
+            let s = |k: &str| entry.get(k).and_then(|v| v.as_str()).unwrap_or("");
+            match s("source") {
+                "" => lines.push(format!("  {}", s("text"))),
+                source => lines.push(format!("  {} ({source})", s("text"))),
+            }
+        }
+    }
+}
+
+fn format_describe_page(data: &Value, lines: &mut Vec<String>) {
+    if let Some(headings) = data.get("headings").and_then(|v| v.as_array()) {
+        for h in headings {
+            let level = h.get("level").and_then(|v| v.as_u64()).unwrap_or(1) as usize;
+            let text = h.get("text").and_then(|v| v.as_str()).unwrap_or("");
+            lines.push(format!("{} {text}", "#".repeat(level)));
+        }
+    }
+    if let Some(by_role) = data
+        .pointer("/interactive/by_role")
+        .and_then(|v| v.as_object())
+    {
+        let mut counts: Vec<(&String, u64)> = by_role
+            .iter()
+            .map(|(role, n)| (role, n.as_u64().unwrap_or(0)))
+            .collect();
+        counts.sort_by(|a, b| b.1.cmp(&a.1).then(a.0.cmp(b.0)));
+        let parts: Vec<String> = counts.iter().map(|(r, n)| format!("{n} {r}")).collect();
+        if !parts.is_empty() {
+            lines.push(format!("interactive: {}", parts.join(", ")));
+        }
+    }
+    if let Some(forms) = data.get("forms").and_then(|v| v.as_array()) {
+        for form in forms {
+            let name = ["id", "name", "action"]
+                .iter()
+                .find_map(|k| form.get(*k).and_then(|v| v.as_str()))
+                .unwrap_or("(unnamed)");
+            let fields: Vec<String> = form
+                .get("fields")
+                .and_then(|v| v.as_array())
+                .map(|fs| {
+                    fs.iter()
+                        .map(|f| {
+                            let label = f.get("label").and_then(|v| v.as_str()).unwrap_or("");
+                            let ty = f.get("type").and_then(|v| v.as_str()).unwrap_or("");
+                            let req = if f.get("required").is_some() { "*" } else { "" };
+                            format!("{label}{req} ({ty})")
+                        })
+                        .collect()
+                })
+                .unwrap_or_default();
+            let submit = form
+                .get("submit")
+                .and_then(|v| v.as_str())
+                .map(|s| format!(" -> \"{s}\""))
+                .unwrap_or_default();
+            lines.push(format!("form {name}: {}{submit}", fields.join(", ")));
+        }
+    }
+    if let Some(p) = data.get("pagination") {
+        let mut hints: Vec<String> = Vec::new();
+        if let Some(next) = p.get("next").and_then(|v| v.as_str()) {
+            hints.push(format!("next {next}"));
+        }
+        if let Some(prev) = p.get("prev").and_then(|v| v.as_str()) {
+            hints.push(format!("prev {prev}"));
+        }
+        if let Some(n) = p.get("pages").and_then(|v| v.as_u64()).filter(|n| *n > 0) {
+            hints.push(format!("{n} page links"));
+        }
+        if p.get("load_more").and_then(|v| v.as_bool()) == Some(true) {
+            hints.push("load more".to_string());
+        }
+        if !hints.is_empty() {
+            lines.push(format!("pagination: {}", hints.join(", ")));
+        }
+    }
+    if let Some(dialog) = data.get("dialog").filter(|v| !v.is_null()) {
+        match dialog.as_str() {
+            Some(label) => lines.push(format!("dialog open: {label}")),
+            None => lines.push("dialog open".to_string()),
+        }
+    }
+}
+
 fn format_new_tab_batch_success(data: &Value, lines: &mut Vec<String>) {
     let requested = data
         .get("requested_urls")
@@ -1126,7 +2449,7 @@ fn format_new_tab_opened_tabs(
 
 #[cfg(test)]
 mod tests {
-    use super::{ResponseContext, format_text};
+    use super::{ResponseContext, format_run_step, format_text};
     use crate::action_result::ActionResult;
     use serde_json::json;
 
@@ -1229,6 +2552,25 @@ mod tests {
         );
     }
 
+    #[test]
+    fn browser_meta_text_renders_tags_and_json_ld_types() {
+        let result = ActionResult::ok(json!({
+            "title": "Example",
+            "description": null,
+            "canonical": "https://example.com/",
+            "og": { "title": "OG", "image": ["a.png", "b.png"] },
+            "twitter": {},
+            "json_ld": [{ "@type": "Article" }, { "@type": "Organization" }],
+        }));
+
+        let text = format_text("browser meta", &None, &result);
+
+        assert_eq!(
+            text,
+            "title: Example\ncanonical: https://example.com/\nog:image: [\"a.png\",\"b.png\"]\nog:title: OG\njson_ld: 2 (Article, Organization)"
+        );
+    }
+
     #[test]
     fn extension_install_text_renders_action_header_and_fields() {
         let result = ActionResult::ok(json!({
@@ -1246,17 +2588,905 @@ mod tests {
     }
 
     #[test]
-    fn extension_status_text_renders_bridge_state() {
+    fn browser_menu_text_shows_trigger_and_item() {
         let result = ActionResult::ok(json!({
-            "bridge": "listening",
-            "extension_connected": true,
+            "action": "menu",
+            "target": { "selector": "#nav" },
+            "item": { "selector": "#nav .pricing" },
+            "post_url": "https://example.com/pricing",
+            "post_title": "Pricing",
         }));
 
-        let text = format_text("extension status", &None, &result);
+        let text = format_text("browser menu", &None, &result);
+
+        assert!(text.starts_with("ok browser menu\n"), "got {text}");
+        assert!(
+            text.contains("target: #nav\nclicked: #nav .pricing"),
+            "got {text}"
+        );
+    }
+
+    #[test]
+    fn list_tabs_text_marks_active_tab() {
+        let result = ActionResult::ok(json!({
+            "total_tabs": 2,
+            "tabs": [
+                {"tab_id": "t1", "title": "A", "url": "https://a.com", "active": false},
+                {"tab_id": "t2", "title": "", "url": "https://b.com", "active": true},
+            ],
+        }));
+
+        let text = format_text("browser list-tabs", &None, &result);
 
         assert_eq!(
             text,
-            "bridge: listening\nextension_connected: true\nrequired_version: >= 0.4.0\n  (check version at chrome://extensions/)"
+            "2 tabs\n[t1] A\nhttps://a.com\n[t2] (active)\nhttps://b.com"
+        );
+    }
+
+    #[test]
+    fn browser_switch_text_shows_previous_tab() {
+        let result = ActionResult::ok(json!({
+            "tab_id": "t2",
+            "previous_tab_id": "t1",
+        }));
+
+        let text = format_text("browser switch", &None, &result);
+
+        assert_eq!(text, "ok browser switch\nprevious: t1");
+    }
+
+    #[test]
+    fn run_text_reports_delivery() {
+        let result = ActionResult::ok(json!({
+            "total": 2,
+            "passed": 2,
+            "failed": 0,
+            "extracted": {},
+            "delivery": {
+                "run_id": "login-20260101T000000Z",
+                "s3": { "bucket": "runs", "prefix": "nightly/login-20260101T000000Z/", "uploaded": 3 },
+                "webhook": { "url": "https://hooks.example.com", "error": "timeout" },
+            },
+        }));
+
+        let text = format_text("run", &None, &result);
+
+        assert_eq!(
+            text,
+            "2/2 steps passed\ndelivered: s3://runs/nightly/login-20260101T000000Z/ (3 files), webhook failed"
+        );
+    }
+
+    #[test]
+    fn browser_indexeddb_text_lists_stores_and_entries() {
+        let result = ActionResult::ok(json!({
+            "origin": "https://app.test",
+            "databases": [{
+                "name": "app-db",
+                "version": 3,
+                "stores": [
+                    { "name": "todos", "key_path": "id", "entries": 2 },
+                    { "name": "kv", "key_path": null, "entries": null },
+                ],
+            }],
+        }));
+        let text = format_text("browser indexeddb list", &None, &result);
+        assert_eq!(
+            text,
+            "1 database\napp-db v3\n  todos key=id (2 entries)\n  kv"
+        );
+
+        let result = ActionResult::ok(json!({
+            "count": 1,
+            "has_more": true,
+            "entries": [{ "key": 1, "primary_key": 1, "value": { "title": "a" } }],
+        }));
+        let text = format_text("browser indexeddb dump", &None, &result);
+        assert_eq!(text, "1 entry (more)\n1={\"title\":\"a\"}");
+    }
+
+    #[test]
+    fn browser_emulate_text_describes_device() {
+        let result = ActionResult::ok(json!({
+            "device": {
+                "device": "iPhone 14",
+                "width": 390,
+                "height": 844,
+                "device_scale_factor": 3.0,
+                "mobile": true,
+                "touch": true,
+            },
+            "tabs": 2,
+        }));
+        let text = format_text("browser emulate", &None, &result);
+        assert_eq!(
+            text,
+            "ok browser emulate\ndevice: iPhone 14 (390x844 @3x, touch)\ntabs: 2"
+        );
+
+        let result = ActionResult::ok(json!({ "device": null, "tabs": 1 }));
+        let text = format_text("browser emulate", &None, &result);
+        assert_eq!(text, "ok browser emulate\ndevice: none\ntabs: 1");
+    }
+
+    #[test]
+    fn browser_emulate_network_text_describes_conditions() {
+        let result = ActionResult::ok(json!({
+            "network": {
+                "preset": "fast-3g",
+                "offline": false,
+                "latency_ms": 1000,
+                "download_kbps": 1440,
+                "upload_kbps": null,
+            },
+            "tabs": 2,
+        }));
+        let text = format_text("browser emulate network", &None, &result);
+        assert_eq!(
+            text,
+            "ok browser emulate network\nnetwork: fast-3g (1000ms, down 1440 kbit/s, up unlimited)\ntabs: 2"
+        );
+
+        let result = ActionResult::ok(json!({
+            "network": { "preset": "offline", "offline": true },
+            "tabs": 1,
+        }));
+        let text = format_text("browser emulate network", &None, &result);
+        assert_eq!(
+            text,
+            "ok browser emulate network\nnetwork: offline (no connection)\ntabs: 1"
+        );
+    }
+
+    #[test]
+    fn run_text_reports_artifacts_dir_and_pruned_runs() {
+        let result = ActionResult::ok(json!({
+            "total": 1,
+            "passed": 1,
+            "failed": 0,
+            "extracted": {},
+            "artifacts_dir": "/runs/login/20261014T093000Z",
+            "retention": { "removed": ["/runs/login/a", "/runs/login/b"], "freed_bytes": 10, "kept": 20 },
+        }));
+
+        let text = format_text("run", &None, &result);
+
+        assert_eq!(
+            text,
+            "1/1 steps passed\nartifacts: /runs/login/20261014T093000Z (pruned 2 old runs)"
+        );
+    }
+
+    #[test]
+    fn browser_table_text_reports_sink_schema() {
+        let result = ActionResult::ok(json!({
+            "format": "csv",
+            "row_count": 2,
+            "column_count": 2,
+            "path": null,
+            "sink": {
+                "kind": "sqlite",
+                "path": "/tmp/prices.db",
+                "table": "prices",
+                "rows_written": 2,
+                "columns": [
+                    { "name": "Item", "type": "text" },
+                    { "name": "Qty", "type": "integer" },
+                ],
+            },
+        }));
+
+        let text = format_text("browser table", &None, &result);
+
+        assert_eq!(
+            text,
+            "2 rows -> sqlite:/tmp/prices.db (table prices)\ncolumns: Item text, Qty integer"
+        );
+    }
+
+    #[test]
+    fn browser_cookies_import_text_shows_count_and_skipped() {
+        let result = ActionResult::ok(json!({
+            "path": "/tmp/cookies.txt",
+            "format": "netscape",
+            "imported": 12,
+            "skipped": 2,
+        }));
+
+        let text = format_text("browser cookies import", &None, &result);
+
+        assert_eq!(
+            text,
+            "ok browser cookies import\n12 cookies (netscape), 2 expired skipped"
+        );
+    }
+
+    #[test]
+    fn browser_state_text_shows_counts_and_fingerprint_warnings() {
+        let bundled = ActionResult::ok(json!({
+            "path": "/tmp/persona.bundle",
+            "encrypted": true,
+            "cookies": 31,
+            "origins": 2,
+        }));
+        assert_eq!(
+            format_text("browser state bundle", &None, &bundled),
+            "ok browser state bundle\n31 cookies, localStorage of 2 origin(s) -> /tmp/persona.bundle (encrypted)"
+        );
+
+        let imported = ActionResult::ok(json!({
+            "path": "/tmp/persona.bundle",
+            "cookies": 31,
+            "origins": 2,
+            "local_storage_items": 5,
+            "device": null,
+            "__warnings": ["the bundle was taken with user agent \"UA\""],
+        }));
+        assert_eq!(
+            format_text("browser state import", &None, &imported),
+            "ok browser state import\n31 cookies, 5 localStorage item(s) in 2 origin(s)\nwarning: the bundle was taken with user agent \"UA\""
+        );
+    }
+
+    #[test]
+    fn browser_network_route_list_text_shows_each_route() {
+        let result = ActionResult::ok(json!({
+            "routes": [
+                {"id": "r1", "pattern": "*/ads/*", "action": "block", "hits": 4},
+                {"id": "r2", "pattern": "*/api/users*", "action": "fulfill", "hits": 1,
+                 "status": 200, "content_type": "application/json", "file": "/tmp/users.json", "bytes": 2},
+                {"id": "r3", "pattern": "*", "action": "headers", "hits": 0,
+                 "headers": {"Authorization": "Bearer t"}},
+            ],
+            "count": 3,
+        }));
+
+        let text = format_text("browser network route list", &None, &result);
+
+        assert_eq!(
+            text,
+            "ok browser network route list\n3 routes\n\
+             r1 block */ads/* hits=4\n\
+             r2 fulfill */api/users* -> /tmp/users.json (200 application/json) hits=1\n\
+             r3 headers * Authorization hits=0"
+        );
+    }
+
+    #[test]
+    fn browser_click_text_shows_point_for_explicit_position() {
+        let result = ActionResult::ok(json!({
+            "action": "click",
+            "target": { "selector": "#slider" },
+            "point": { "x": 172.4, "y": 40.0 },
+        }));
+
+        let text = format_text("browser click", &None, &result);
+
+        assert_eq!(text, "ok browser click\ntarget: #slider\npoint: 172,40");
+    }
+
+    #[test]
+    fn browser_press_text_shows_an_expanded_macro() {
+        let single = ActionResult::ok(json!({
+            "action": "press",
+            "keys": "Enter",
+            "sequence": ["Enter"],
+        }));
+        assert_eq!(
+            format_text("browser press", &None, &single),
+            "ok browser press\nkeys: Enter"
+        );
+
+        let expanded = ActionResult::ok(json!({
+            "action": "press",
+            "keys": "@next_field",
+            "sequence": ["Tab", "Tab", "Enter"],
+        }));
+        assert_eq!(
+            format_text("browser press", &None, &expanded),
+            "ok browser press\nkeys: @next_field\npressed: Tab, Tab, Enter"
+        );
+    }
+
+    #[test]
+    fn browser_touch_text_shows_gesture() {
+        let swipe = ActionResult::ok(json!({
+            "action": "swipe",
+            "direction": "up",
+            "from": { "x": 195.0, "y": 422.0 },
+            "to": { "x": 195.0, "y": 0.5 },
+            "duration_ms": 300,
+        }));
+        assert_eq!(
+            format_text("browser swipe", &None, &swipe),
+            "ok browser swipe\ndirection: up\npath: 195,422 -> 195,0"
+        );
+
+        let pinch = ActionResult::ok(json!({
+            "action": "pinch",
+            "direction": "out",
+            "scale": 2.0,
+            "center": { "x": 195.0, "y": 422.0 },
+            "target": { "selector": "#map" },
+        }));
+        assert_eq!(
+            format_text("browser pinch", &None, &pinch),
+            "ok browser pinch\npinch: out (scale 2)\ntarget: #map"
+        );
+    }
+
+    #[test]
+    fn browser_slide_text_shows_value_and_range() {
+        let result = ActionResult::ok(json!({
+            "action": "slide",
+            "target": { "selector": "#volume" },
+            "kind": "range",
+            "requested": 75.0,
+            "value": 75.0,
+            "min": 0.0,
+            "max": 100.0,
+        }));
+
+        let text = format_text("browser slide", &None, &result);
+
+        assert_eq!(
+            text,
+            "ok browser slide\ntarget: #volume\nvalue: 75 (range 0..100)"
+        );
+    }
+
+    #[test]
+    fn browser_date_text_shows_strategy_and_field_value() {
+        let result = ActionResult::ok(json!({
+            "action": "date",
+            "target": { "selector": "#checkin" },
+            "date": "2025-03-14",
+            "strategy": "type",
+            "value": "03/14/2025",
+        }));
+
+        let text = format_text("browser date", &None, &result);
+
+        assert_eq!(
+            text,
+            "ok browser date\ntarget: #checkin\ndate: 2025-03-14 (via type)\nvalue: 03/14/2025"
+        );
+    }
+
+    #[test]
+    fn browser_record_stop_text_shows_script_path_and_steps() {
+        let result = ActionResult::ok(json!({
+            "path": "/tmp/flow.yaml",
+            "steps": 4,
+            "inputs": 6,
+        }));
+
+        let text = format_text("browser record stop", &None, &result);
+
+        assert_eq!(
+            text,
+            "ok browser record stop\npath: /tmp/flow.yaml\nsteps: 4"
+        );
+    }
+
+    #[test]
+    fn browser_trace_stop_text_shows_path_size_and_duration() {
+        let result = ActionResult::ok(json!({
+            "path": "/tmp/trace.json",
+            "bytes": 2_500_000,
+            "duration_ms": 4200,
+        }));
+        assert_eq!(
+            format_text("browser trace stop", &None, &result),
+            "ok browser trace stop\npath: /tmp/trace.json\n2442 KB, 4.2s traced"
+        );
+    }
+
+    #[test]
+    fn browser_record_video_stop_text_shows_video_or_ffmpeg_command() {
+        let encoded = ActionResult::ok(json!({
+            "path": "/tmp/s.webm",
+            "frames": 42,
+            "duration_ms": 5300,
+            "encoded": true,
+        }));
+        assert_eq!(
+            format_text("browser record-video stop", &None, &encoded),
+            "ok browser record-video stop\npath: /tmp/s.webm\n42 frame(s), 5.3s"
+        );
+
+        let kept = ActionResult::ok(json!({
+            "path": "/tmp/s.webm",
+            "frames": 3,
+            "duration_ms": 900,
+            "encoded": false,
+            "frames_dir": "/tmp/s.frames",
+            "ffmpeg": "ffmpeg -i /tmp/s.frames/frames.txt /tmp/s.webm",
+        }));
+        let text = format_text("browser record-video stop", &None, &kept);
+        assert!(
+            text.contains("kept 3 frame(s) (0.9s) in /tmp/s.frames"),
+            "{text}"
+        );
+        assert!(
+            text.contains("encode with: ffmpeg -i /tmp/s.frames/frames.txt /tmp/s.webm"),
+            "{text}"
+        );
+    }
+
+    #[test]
+    fn browser_canvas_click_color_text_shows_point_and_matches() {
+        let result = ActionResult::ok(json!({
+            "action": "canvas click-color",
+            "target": { "selector": "#chart" },
+            "color": "#ff0000",
+            "x": 120.5,
+            "y": 64.5,
+            "pixel": { "x": 120, "y": 64 },
+            "matches": 400,
+        }));
+
+        let text = format_text("browser canvas click-color", &None, &result);
+
+        assert_eq!(
+            text,
+            "ok browser canvas click-color\ntarget: #chart\nclicked #ff0000 at (120, 64)\nmatches: 400 px"
+        );
+    }
+
+    #[test]
+    fn browser_downloads_list_text_shows_state_size_and_path() {
+        let result = ActionResult::ok(json!({
+            "dir": "/tmp/dl",
+            "total": 2,
+            "downloads": [
+                { "filename": "report.csv", "state": "completed", "received_bytes": 2048,
+                  "total_bytes": 2048, "path": "/tmp/dl/report.csv" },
+                { "filename": "big.zip", "state": "in_progress", "received_bytes": 10,
+                  "total_bytes": 0, "path": null },
+            ],
+        }));
+
+        let text = format_text("browser downloads list", &None, &result);
+
+        assert_eq!(
+            text,
+            "2 downloads\ncompleted report.csv 2048 bytes -> /tmp/dl/report.csv\nin_progress big.zip 10 bytes"
+        );
+    }
+
+    #[test]
+    fn browser_images_text_shows_size_url_alt_and_download() {
+        let result = ActionResult::ok(json!({
+            "count": 2,
+            "images": [
+                { "url": "https://x.test/cat.jpg", "alt": "A cat", "width": 640, "height": 480,
+                  "path": "/tmp/imgs/cat.jpg", "bytes": 5120 },
+                { "url": "https://x.test/gone.png", "alt": null, "width": 0, "height": 0,
+                  "error": { "code": "IMAGE_FETCH_FAILED", "message": "HTTP 404" } },
+            ],
+            "dir": "/tmp/imgs",
+            "downloaded": 1,
+        }));
+
+        let text = format_text("browser images", &None, &result);
+
+        assert_eq!(
+            text,
+            "2 images\n640x480 https://x.test/cat.jpg \"A cat\"\n  -> /tmp/imgs/cat.jpg\n0x0 https://x.test/gone.png\n  error IMAGE_FETCH_FAILED: HTTP 404"
+        );
+    }
+
+    #[test]
+    fn browser_table_text_prints_the_table_or_the_saved_path() {
+        let csv = ActionResult::ok(json!({
+            "format": "csv",
+            "csv": "name,price\r\nTea,3\r\n",
+            "row_count": 1,
+            "column_count": 2,
+            "path": null,
+        }));
+        assert_eq!(
+            format_text("browser table", &None, &csv),
+            "name,price\nTea,3"
+        );
+
+        let saved = ActionResult::ok(json!({
+            "format": "json",
+            "rows": [{ "name": "Tea", "price": "3" }],
+            "row_count": 1,
+            "column_count": 2,
+            "path": "/tmp/prices.json",
+        }));
+        assert_eq!(
+            format_text("browser table", &None, &saved),
+            "1 rows x 2 columns -> /tmp/prices.json"
+        );
+
+        let parsed = ActionResult::ok(json!({
+            "format": "csv",
+            "csv": "name,price\r\nTea,n/a\r\n",
+            "path": null,
+            "parse_failures": [{ "row": 0, "column": "price", "text": "n/a" }],
+        }));
+        assert_eq!(
+            format_text("browser table", &None, &parsed),
+            "name,price\nTea,n/a\n1 cells did not parse (see --json)"
+        );
+    }
+
+    #[test]
+    fn browser_wait_text_and_url_show_what_matched() {
+        let text = ActionResult::ok(json!({
+            "kind": "text",
+            "satisfied": true,
+            "elapsed_ms": 120,
+            "observed_value": { "text": "Saved", "selector": null },
+        }));
+        assert_eq!(
+            format_text("browser wait text", &None, &text),
+            "ok browser wait text\nelapsed_ms: 120\ntext: Saved"
+        );
+        let url = ActionResult::ok(json!({
+            "kind": "url",
+            "satisfied": true,
+            "elapsed_ms": 40,
+            "observed_value": "https://x.test/dashboard",
+        }));
+        assert_eq!(
+            format_text("browser wait url", &None, &url),
+            "ok browser wait url\nelapsed_ms: 40\nurl: https://x.test/dashboard"
+        );
+    }
+
+    #[test]
+    fn stats_text_lists_commands_with_latency_and_failures() {
+        let result = ActionResult::ok(json!({
+            "enabled": true,
+            "since": "2026-10-01T00:00:00.000Z",
+            "total": 5,
+            "commands": [
+                { "command": "browser click", "count": 4, "failed": 1,
+                  "failures": { "ELEMENT_NOT_FOUND": 1 }, "p50_ms": 250, "p95_ms": null },
+                { "command": "search", "count": 1, "failed": 0,
+                  "failures": {}, "p50_ms": 500, "p95_ms": 500 },
+            ],
+        }));
+        assert_eq!(
+            format_text("stats", &None, &result),
+            "Usage stats (on) since 2026-10-01T00:00:00.000Z: 5 runs\n  \
+             browser click 4 runs, p50 <=250ms, p95 >30000ms, 1 failed (ELEMENT_NOT_FOUND 1)\n  \
+             search 1 runs, p50 <=500ms, p95 <=500ms"
+        );
+        let off = ActionResult::ok(json!({ "enabled": false, "total": 0, "commands": [] }));
+        assert_eq!(
+            format_text("stats", &None, &off),
+            "Usage stats are off; turn them on with `actionbook stats enable`"
+        );
+    }
+
+    #[test]
+    fn auth_text_never_shows_secrets() {
+        let added = ActionResult::ok(json!({
+            "domain": "example.com", "user": "alice", "totp": true, "store": "keyring",
+        }));
+        assert_eq!(
+            format_text("auth add", &None, &added),
+            "Stored login for example.com (alice, TOTP) in the OS keyring"
+        );
+        let list = ActionResult::ok(json!({ "credentials": [
+            { "domain": "example.com", "user": "alice", "totp": true, "store": "keyring" },
+            { "domain": "shop.test", "user": "bob", "totp": false, "store": "file" },
+        ] }));
+        assert_eq!(
+            format_text("auth list", &None, &list),
+            "example.com  alice +totp (keyring)\nshop.test  bob (file)"
+        );
+        let code = ActionResult::ok(json!({ "domain": "example.com", "code": "081804" }));
+        assert_eq!(format_text("auth totp", &None, &code), "081804");
+    }
+
+    #[test]
+    fn flow_import_text_lists_skipped_lines() {
+        let result = ActionResult::ok(json!({
+            "script": "login.spec.ts",
+            "path": "login.yaml",
+            "steps": 4,
+            "skipped": [{ "line": 9, "code": "page.route('**', r => r.abort())" }],
+        }));
+        assert_eq!(
+            format_text("flow import", &None, &result),
+            "Wrote login.yaml: 4 step(s)\n  skipped line 9: page.route('**', r => r.abort())"
+        );
+    }
+
+    #[test]
+    fn flow_export_text_lists_skipped_steps() {
+        let result = ActionResult::ok(json!({
+            "flow": "shop.yaml",
+            "format": "playwright",
+            "path": "shop.spec.ts",
+            "steps": 5,
+            "skipped": [{ "step": 3, "reason": "`browser scroll down` has no equivalent" }],
+        }));
+        assert_eq!(
+            format_text("flow export", &None, &result),
+            "Wrote shop.spec.ts: 5 step(s)\n  skipped step 3: `browser scroll down` has no equivalent"
+        );
+    }
+
+    #[test]
+    fn browser_fetch_text_shows_status_and_body_or_path() {
+        let saved = ActionResult::ok(json!({
+            "url": "https://x.test/export.csv",
+            "status": 200,
+            "content_type": "text/csv",
+            "bytes": 42,
+            "path": "/tmp/export.csv",
+        }));
+        assert_eq!(
+            format_text("browser fetch", &None, &saved),
+            "200 https://x.test/export.csv (text/csv) 42 bytes -> /tmp/export.csv"
+        );
+
+        let inline = ActionResult::ok(json!({
+            "url": "https://x.test/api/me",
+            "status": 200,
+            "content_type": null,
+            "bytes": 11,
+            "body": "{\"id\":7}",
+        }));
+        assert_eq!(
+            format_text("browser fetch", &None, &inline),
+            "200 https://x.test/api/me 11 bytes\n{\"id\":7}"
+        );
+    }
+
+    #[test]
+    fn browser_trace_redirects_text_numbers_hops_with_their_type() {
+        let result = ActionResult::ok(json!({
+            "hops": [
+                { "url": "https://t.test/x", "status": 301, "type": "http" },
+                { "url": "https://a.test/", "status": 200, "type": "meta-refresh" },
+                { "url": "https://b.test/", "status": 200, "type": null },
+            ],
+            "redirects": 2,
+            "settled": true,
+        }));
+
+        let text = format_text("browser trace-redirects", &None, &result);
+
+        assert_eq!(
+            text,
+            "1. 301 https://t.test/x (http)\n2. 200 https://a.test/ (meta-refresh)\n3. 200 https://b.test/"
+        );
+    }
+
+    #[test]
+    fn browser_response_text_shows_redirects_status_and_headers() {
+        let result = ActionResult::ok(json!({
+            "url": "https://x.test/login",
+            "status": 200,
+            "status_text": "OK",
+            "headers": { "content-type": "text/html", "server": "nginx" },
+            "redirects": [{ "url": "https://x.test/account", "status": 302, "location": "/login" }],
+            "timing": { "response_ms": 84, "total_ms": 120 },
+        }));
+
+        let text = format_text("browser response", &None, &result);
+
+        assert_eq!(
+            text,
+            "302 https://x.test/account -> /login\n200 OK https://x.test/login (84 ms)\ncontent-type: text/html\nserver: nginx"
+        );
+    }
+
+    #[test]
+    fn browser_assert_text_lists_each_check() {
+        let result = ActionResult::fatal_with_details(
+            "ASSERTION_FAILED",
+            "1 of 2 assertions failed; first: element-count expected \">=1\", got 0",
+            "",
+            json!({ "checks": [
+                { "check": "url-matches", "expected": "/cart", "actual": "https://x.test/cart", "passed": true },
+                { "check": "element-count", "selector": ".item", "expected": ">=1", "actual": 0, "passed": false },
+            ] }),
+        );
+
+        let text = format_text("browser assert", &None, &result);
+
+        assert_eq!(
+            text,
+            "error ASSERTION_FAILED: 1 of 2 assertions failed; first: element-count expected \">=1\", got 0\npass url-matches \"/cart\"\nFAIL element-count .item: expected \">=1\", got 0"
+        );
+    }
+
+    #[test]
+    fn browser_assert_text_lists_console_errors() {
+        let result = ActionResult::fatal_with_details(
+            "ASSERTION_FAILED",
+            "1 of 1 assertions failed; first: no-console-errors expected 0, got 1",
+            "",
+            json!({ "checks": [
+                { "check": "no-console-errors", "expected": 0, "actual": 1, "allowed": 1, "passed": false,
+                  "errors": [{ "level": "error", "text": "TypeError: x is undefined", "source": "https://x.test/app.js" }] },
+            ] }),
+        );
+
+        assert_eq!(
+            format_text("browser assert", &None, &result),
+            "error ASSERTION_FAILED: 1 of 1 assertions failed; first: no-console-errors expected 0, got 1\n\
+             FAIL no-console-errors 0: expected 0, got 1\n  \
+             TypeError: x is undefined (https://x.test/app.js)"
+        );
+    }
+
+    #[test]
+    fn run_text_summarises_steps_and_extracted_values() {
+        let result = ActionResult::ok(json!({
+            "total": 3,
+            "passed": 2,
+            "failed": 1,
+            "extracted": { "balance": "$12.00" },
+        }));
+
+        let text = format_text("run", &None, &result);
+
+        assert_eq!(text, "2/3 steps passed, 1 failed\nbalance: $12.00");
+    }
+
+    #[test]
+    fn run_step_lines_show_outcome_and_attempts() {
+        let ok = json!({"index": 1, "action": "goto", "target": "https://a.test", "ok": true, "attempts": 1, "elapsed_ms": 80});
+        assert_eq!(format_run_step(&ok), "ok 1 goto https://a.test 80ms");
+
+        let failed = json!({
+            "index": 2, "name": "login", "action": "click", "target": "#go", "ok": false, "attempts": 3,
+            "error": {"code": "ELEMENT_NOT_FOUND", "message": "no element"},
+        });
+        assert_eq!(
+            format_run_step(&failed),
+            "fail 2 click #go [login] ELEMENT_NOT_FOUND: no element (3 attempts)"
+        );
+    }
+
+    #[test]
+    fn run_step_lines_list_captures() {
+        let step = json!({
+            "index": 4, "action": "click", "target": "#go", "ok": false, "attempts": 1,
+            "error": {"code": "ELEMENT_NOT_FOUND", "message": "no element"},
+            "captures": [
+                {"kind": "screenshot", "path": "captures/step-04.png"},
+                {"kind": "snapshot", "error": "tab closed"},
+            ],
+        });
+        assert_eq!(
+            format_run_step(&step),
+            "fail 4 click #go ELEMENT_NOT_FOUND: no element [screenshot: captures/step-04.png] [snapshot failed: tab closed]"
+        );
+    }
+
+    #[test]
+    fn run_text_reports_healed_selectors() {
+        let step = json!({
+            "index": 3, "action": "click", "target": "#buy", "ok": true, "attempts": 3, "elapsed_ms": 900,
+            "heal": {"from": "#buy", "to": "button[data-testid=\"buy\"]", "score": 9, "max": 12, "ok": true},
+        });
+        assert_eq!(
+            format_run_step(&step),
+            "ok 3 click #buy 900ms (3 attempts) (healed: #buy -> button[data-testid=\"buy\"])"
+        );
+
+        let result = ActionResult::ok(json!({
+            "script": "flow.yaml", "total": 3, "passed": 3, "failed": 0,
+            "healed": 1, "heal_saved": true,
+        }));
+        assert_eq!(
+            format_text("run", &None, &result),
+            "3/3 steps passed\nhealed: 1 step (saved to flow.yaml)"
+        );
+    }
+
+    #[test]
+    fn browser_tabto_text_reports_presses() {
+        let result = ActionResult::ok(json!({
+            "action": "tabto",
+            "target": { "selector": "#submit" },
+            "presses": 3,
+            "reverse": true,
+        }));
+
+        let text = format_text("browser tabto", &None, &result);
+
+        assert_eq!(
+            text,
+            "ok browser tabto\ntarget: #submit\npresses: 3 (Shift+Tab)"
+        );
+    }
+
+    #[test]
+    fn browser_options_text_lists_each_option() {
+        let result = ActionResult::ok(json!({
+            "total": 2,
+            "options": [
+                {"index": 0, "value": "", "label": "Choose…", "selected": true, "disabled": true, "group": null},
+                {"index": 1, "value": "c_81f", "label": "Canada", "selected": false, "disabled": false, "group": "Americas"},
+            ],
+        }));
+
+        let text = format_text("browser options", &None, &result);
+
+        assert_eq!(
+            text,
+            "[0]  \"Choose…\" selected disabled\n[1] c_81f \"Canada\" (Americas)"
+        );
+    }
+
+    #[test]
+    fn browser_status_text_renders_health() {
+        let result = ActionResult::ok(json!({
+            "session": {"status": "running", "mode": "local", "tabs_count": 1},
+            "health": {
+                "backend": "local",
+                "age_secs": 42,
+                "cdp": {"connected": true, "responsive": true, "latency_ms": 3},
+                "chrome": {"pid": 4321, "rss_bytes": 200u64 << 20},
+            },
+        }));
+
+        let text = format_text("browser status", &None, &result);
+
+        assert_eq!(
+            text,
+            "status: running\nmode: local\ntabs: 1\nage: 42s\ncdp: responsive\nchrome: pid 4321, 200 MiB"
+        );
+    }
+
+    #[test]
+    fn browser_status_text_shows_the_headless_mode() {
+        let result = ActionResult::ok(json!({
+            "session": {"status": "running", "mode": "local", "headless_mode": "old", "tabs_count": 1},
+        }));
+
+        let text = format_text("browser status", &None, &result);
+
+        assert_eq!(text, "status: running\nmode: local\nheadless: old\ntabs: 1");
+    }
+
+    #[test]
+    fn extension_status_text_renders_bridge_state() {
+        let result = ActionResult::ok(json!({
+            "bridge": "listening",
+            "extension_connected": true,
+        }));
+
+        let text = format_text("extension status", &None, &result);
+
+        assert_eq!(
+            text,
+            "bridge: listening\nextension_connected: true\nrequired_version: >= 0.4.0\n  (check version at chrome://extensions/)"
+        );
+    }
+
+    #[test]
+    fn extension_version_text_suggests_update_when_outdated() {
+        let result = ActionResult::ok(json!({
+            "connected": true,
+            "extension_version": "0.4.0",
+            "protocol_version": "0.4.0",
+            "required_protocol_version": "0.4.0",
+            "compatible": true,
+            "installed_version": "0.4.0",
+            "bundled_version": "0.4.1",
+            "update_available": true,
+        }));
+
+        let text = format_text("extension version", &None, &result);
+
+        assert_eq!(
+            text,
+            "extension: 0.4.0 (protocol 0.4.0)\nrequired_protocol: >= 0.4.0\ncompatible: true\ninstalled: 0.4.0\nbundled: 0.4.1\n  (run `actionbook extension version --update` to install the bundled build)"
         );
     }
 
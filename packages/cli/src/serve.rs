@@ -0,0 +1,669 @@
+//! `actionbook serve`: the browser command surface over local HTTP + JSON.
+//!
+//! Every `actionbook browser …` command is reachable as
+//! `POST /v1/browser/<command words>`, e.g. `/v1/browser/goto` or
+//! `/v1/browser/wait/element`. The JSON body holds the command's flags by
+//! name (`"session": "s1"`, `"full_page": true`, arrays for repeatable flags)
+//! and its positional arguments under `"args"`. The request is parsed by the
+//! same clap definitions as the CLI, sent to the daemon, and answered with
+//! the `--json` envelope, so sessions are shared with CLI callers.
+//!
+//! The server binds to loopback by default. Binding anywhere else needs a
+//! `--token`. Browser pages cannot drive it: requests must carry
+//! `Content-Type: application/json` (which a cross-origin page cannot send
+//! without a preflight this server never approves), and on loopback the
+//! `Host` header must name the server itself, which defeats DNS rebinding.
+//! A server bound beyond loopback is reached under whatever LAN or public
+//! name its clients use, so there the `--token` alone keeps such pages out.
+
+use std::net::{IpAddr, SocketAddr};
+use std::rc::Rc;
+use std::time::{Duration, Instant};
+
+use clap::Parser;
+use serde_json::{Value, json};
+use subtle::ConstantTimeEq;
+use tokio::io::{AsyncBufReadExt, AsyncReadExt, AsyncWriteExt, BufReader};
+use tokio::net::{TcpListener, TcpStream};
+
+use crate::action_result::ActionResult;
+use crate::cli::{BrowserCommands, Cli, Commands};
+use crate::error::CliError;
+use crate::output::JsonEnvelope;
+use crate::utils::client::DaemonClient;
+
+pub const DEFAULT_PORT: u16 = 8088;
+
+/// Largest request head (request line + headers) accepted.
+const MAX_HEAD_BYTES: usize = 64 * 1024;
+/// Longest single request or header line accepted, CRLF included.
+const MAX_LINE_BYTES: usize = 8 * 1024;
+/// Largest request body accepted.
+const MAX_BODY_BYTES: usize = 10 * 1024 * 1024;
+/// How long a client may take to send its request.
+const READ_TIMEOUT: Duration = Duration::from_secs(30);
+
+#[derive(Debug, Clone)]
+pub struct ServeConfig {
+    pub host: String,
+    pub port: u16,
+    pub token: Option<String>,
+}
+
+#[derive(Debug, PartialEq)]
+struct Request {
+    method: String,
+    path: String,
+    headers: Vec<(String, String)>,
+    body: Vec<u8>,
+}
+
+impl Request {
+    fn header(&self, name: &str) -> Option<&str> {
+        self.headers
+            .iter()
+            .find(|(k, _)| k.eq_ignore_ascii_case(name))
+            .map(|(_, v)| v.as_str())
+    }
+}
+
+#[derive(Debug, PartialEq)]
+struct Response {
+    status: u16,
+    body: Value,
+}
+
+fn reason(status: u16) -> &'static str {
+    match status {
+        200 => "OK",
+        400 => "Bad Request",
+        401 => "Unauthorized",
+        403 => "Forbidden",
+        404 => "Not Found",
+        405 => "Method Not Allowed",
+        413 => "Payload Too Large",
+        415 => "Unsupported Media Type",
+        422 => "Unprocessable Entity",
+        431 => "Request Header Fields Too Large",
+        503 => "Service Unavailable",
+        _ => "Internal Server Error",
+    }
+}
+
+/// An error envelope for requests that never reached a command.
+fn error_response(status: u16, command: &str, code: &str, message: &str, hint: &str) -> Response {
+    let envelope = JsonEnvelope::error(
+        command,
+        None,
+        code,
+        message,
+        false,
+        Value::Null,
+        hint,
+        Duration::ZERO,
+    );
+    Response {
+        status,
+        body: serde_json::to_value(&envelope).unwrap_or(Value::Null),
+    }
+}
+
+/// Serve until Ctrl-C.
+pub async fn run(config: ServeConfig) -> Result<(), CliError> {
+    let ip: IpAddr = config
+        .host
+        .parse()
+        .map_err(|_| CliError::InvalidArgument(format!("invalid --host '{}'", config.host)))?;
+    if !ip.is_loopback() && config.token.is_none() {
+        return Err(CliError::InvalidArgument(format!(
+            "refusing to serve on {ip} without --token (anyone who can reach it could drive your browser)"
+        )));
+    }
+    let listener = TcpListener::bind(SocketAddr::new(ip, config.port)).await?;
+    let addr = listener.local_addr()?;
+    eprintln!("actionbook serve listening on http://{addr}");
+    // `DaemonClient` futures are not `Send`, so connections run as local tasks.
+    let config = Rc::new(config);
+    let local = tokio::task::LocalSet::new();
+    local
+        .run_until(async move {
+            loop {
+                tokio::select! {
+                    accepted = listener.accept() => {
+                        let Ok((stream, _)) = accepted else { continue };
+                        let config = config.clone();
+                        tokio::task::spawn_local(async move {
+                            if let Err(e) = handle_connection(stream, &config).await {
+                                tracing::debug!("serve connection error: {e}");
+                            }
+                        });
+                    }
+                    _ = tokio::signal::ctrl_c() => return Ok(()),
+                }
+            }
+        })
+        .await
+}
+
+async fn handle_connection(stream: TcpStream, config: &ServeConfig) -> std::io::Result<()> {
+    let (reader, mut writer) = stream.into_split();
+    let mut reader = BufReader::new(reader);
+    let response = match tokio::time::timeout(READ_TIMEOUT, read_request(&mut reader)).await {
+        Ok(Ok(request)) => handle_request(request, config).await,
+        Ok(Err(response)) => response,
+        Err(_) => return Ok(()),
+    };
+    let body = serde_json::to_vec(&response.body).unwrap_or_default();
+    let head = format!(
+        "HTTP/1.1 {} {}\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n",
+        response.status,
+        reason(response.status),
+        body.len()
+    );
+    writer.write_all(head.as_bytes()).await?;
+    writer.write_all(&body).await?;
+    writer.shutdown().await
+}
+
+async fn read_request<R: tokio::io::AsyncRead + Unpin>(
+    reader: &mut BufReader<R>,
+) -> Result<Request, Response> {
+    let bad = |message: &str| error_response(400, "serve", "INVALID_ARGUMENT", message, "");
+    let mut head_bytes = 0;
+    let mut lines = Vec::new();
+    loop {
+        let mut line = String::new();
+        let n = (&mut *reader)
+            .take(MAX_LINE_BYTES as u64 + 1)
+            .read_line(&mut line)
+            .await
+            .map_err(|_| bad("malformed request"))?;
+        if n > MAX_LINE_BYTES {
+            return Err(error_response(
+                431,
+                "serve",
+                "INVALID_ARGUMENT",
+                &format!("request line or header longer than {MAX_LINE_BYTES} bytes"),
+                "",
+            ));
+        }
+        head_bytes += n;
+        if n == 0 || head_bytes > MAX_HEAD_BYTES {
+            return Err(bad("malformed or oversized request head"));
+        }
+        let line = line.trim_end_matches(['\r', '\n']).to_string();
+        if line.is_empty() {
+            break;
+        }
+        lines.push(line);
+    }
+    let mut parts = lines
+        .first()
+        .map(|l| l.split(' '))
+        .ok_or_else(|| bad("empty request"))?;
+    let (Some(method), Some(target)) = (parts.next(), parts.next()) else {
+        return Err(bad("malformed request line"));
+    };
+    let headers: Vec<(String, String)> = lines[1..]
+        .iter()
+        .filter_map(|l| l.split_once(':'))
+        .map(|(k, v)| (k.trim().to_string(), v.trim().to_string()))
+        .collect();
+    let mut request = Request {
+        method: method.to_string(),
+        path: target.split('?').next().unwrap_or("").to_string(),
+        headers,
+        body: Vec::new(),
+    };
+    let length: usize = match request.header("content-length") {
+        Some(v) => v.parse().map_err(|_| bad("invalid Content-Length"))?,
+        None => 0,
+    };
+    if length > MAX_BODY_BYTES {
+        return Err(error_response(
+            413,
+            "serve",
+            "INVALID_ARGUMENT",
+            "request body too large",
+            "",
+        ));
+    }
+    request.body = vec![0; length];
+    reader
+        .read_exact(&mut request.body)
+        .await
+        .map_err(|_| bad("request body shorter than Content-Length"))?;
+    Ok(request)
+}
+
+/// `localhost`, `127.0.0.1` or `[::1]` (with any port), or the bound host.
+/// Any host when the server is bound beyond loopback behind a token: remote
+/// clients name it by addresses and names it cannot know in advance.
+fn host_allowed(host_header: Option<&str>, config: &ServeConfig) -> bool {
+    let remote = config
+        .host
+        .parse::<IpAddr>()
+        .is_ok_and(|ip| !ip.is_loopback());
+    if remote && config.token.is_some() {
+        return true;
+    }
+    let Some(host) = host_header else {
+        return false;
+    };
+    let name = match host.strip_prefix('[') {
+        Some(rest) => rest.split(']').next().unwrap_or(""),
+        None => host.rsplit_once(':').map_or(host, |(h, _)| h),
+    };
+    name.eq_ignore_ascii_case("localhost")
+        || name == "127.0.0.1"
+        || name == "::1"
+        || name == config.host
+}
+
+async fn handle_request(request: Request, config: &ServeConfig) -> Response {
+    if !host_allowed(request.header("host"), config) {
+        return error_response(
+            403,
+            "serve",
+            "FORBIDDEN",
+            "Host header does not name this server",
+            "",
+        );
+    }
+    if let Some(token) = &config.token {
+        let presented = request
+            .header("authorization")
+            .and_then(|v| v.strip_prefix("Bearer "))
+            .unwrap_or("");
+        if !bool::from(presented.as_bytes().ct_eq(token.as_bytes())) {
+            return error_response(
+                401,
+                "serve",
+                "UNAUTHORIZED",
+                "missing or wrong bearer token",
+                "send Authorization: Bearer <token>",
+            );
+        }
+    }
+
+    if request.path == "/v1/health" {
+        if request.method != "GET" {
+            return error_response(405, "serve", "INVALID_ARGUMENT", "use GET", "");
+        }
+        return Response {
+            status: 200,
+            body: json!({
+                "ok": true,
+                "version": crate::BUILD_VERSION,
+                "daemon_running": crate::daemon::server::is_daemon_running(),
+            }),
+        };
+    }
+    let Some(words) = request.path.strip_prefix("/v1/browser/") else {
+        return error_response(
+            404,
+            "serve",
+            "NOT_FOUND",
+            &format!("no route for {}", request.path),
+            "browser commands are at POST /v1/browser/<command>",
+        );
+    };
+    if request.method != "POST" {
+        return error_response(405, "serve", "INVALID_ARGUMENT", "use POST", "");
+    }
+    let is_json = request
+        .header("content-type")
+        .is_some_and(|v| v.to_ascii_lowercase().starts_with("application/json"));
+    if !is_json {
+        return error_response(
+            415,
+            "serve",
+            "INVALID_ARGUMENT",
+            "requests must be Content-Type: application/json",
+            "",
+        );
+    }
+
+    let body: Value = if request.body.is_empty() {
+        json!({})
+    } else {
+        match serde_json::from_slice(&request.body) {
+            Ok(v) => v,
+            Err(e) => {
+                return error_response(
+                    400,
+                    "serve",
+                    "INVALID_ARGUMENT",
+                    &format!("invalid JSON body: {e}"),
+                    "",
+                );
+            }
+        }
+    };
+    let argv = match browser_argv(words, &body) {
+        Ok(argv) => argv,
+        Err(message) => return error_response(400, "serve", "INVALID_ARGUMENT", &message, ""),
+    };
+    let cli = match Cli::try_parse_from(&argv) {
+        Ok(cli) => cli,
+        Err(e) => {
+            let label = format!("browser {}", words.replace('/', " "));
+            return error_response(
+                400,
+                &label,
+                "INVALID_ARGUMENT",
+                e.to_string().trim(),
+                "flags go in the JSON body by name, positionals under \"args\"",
+            );
+        }
+    };
+    match cli.command {
+        Some(Commands::Browser { command }) => execute_browser(command, cli.timeout).await,
+        _ => error_response(
+            400,
+            "serve",
+            "INVALID_ARGUMENT",
+            "not a browser command",
+            "",
+        ),
+    }
+}
+
+/// `actionbook browser <words…> <args…> --flag value…` from a request.
+fn browser_argv(words: &str, body: &Value) -> Result<Vec<String>, String> {
+    let Some(fields) = body.as_object() else {
+        return Err("the body must be a JSON object".to_string());
+    };
+    let scalar = |key: &str, v: &Value| match v {
+        Value::String(s) => Ok(s.clone()),
+        Value::Number(n) => Ok(n.to_string()),
+        Value::Bool(b) => Ok(b.to_string()),
+        _ => Err(format!("'{key}' must hold strings, numbers or booleans")),
+    };
+    let mut argv = vec!["actionbook".to_string(), "browser".to_string()];
+    argv.extend(words.split('/').filter(|w| !w.is_empty()).map(String::from));
+    if let Some(args) = fields.get("args") {
+        for v in args.as_array().ok_or("'args' must be an array")? {
+            argv.push(scalar("args", v)?);
+        }
+    }
+    for (key, value) in fields {
+        if key == "args" {
+            continue;
+        }
+        let flag = format!("--{}", key.replace('_', "-"));
+        match value {
+            Value::Null | Value::Bool(false) => {}
+            Value::Bool(true) => argv.push(flag),
+            Value::Array(items) => {
+                for v in items {
+                    argv.push(flag.clone());
+                    argv.push(scalar(key, v)?);
+                }
+            }
+            v => {
+                argv.push(flag);
+                argv.push(scalar(key, v)?);
+            }
+        }
+    }
+    Ok(argv)
+}
+
+/// Run one browser command through the daemon, as `actionbook browser … --json`
+/// would, and wrap the outcome in its envelope.
+async fn execute_browser(command: BrowserCommands, timeout_ms: Option<u64>) -> Response {
+    let start = Instant::now();
+    let mut command = match command {
+        BrowserCommands::Start(cmd) => match crate::config::resolve_start_command(cmd) {
+            Ok(mut cmd) => {
+                cmd.provider_env =
+                    crate::browser::session::provider::collect_provider_env_from_process();
+                BrowserCommands::Start(cmd)
+            }
+            Err(e) => {
+                return error_response(400, "browser start", e.error_code(), &e.to_string(), "");
+            }
+        },
+        BrowserCommands::Restart(mut cmd) => {
+            cmd.provider_env =
+                crate::browser::session::provider::collect_provider_env_from_process();
+            BrowserCommands::Restart(cmd)
+        }
+        other => other,
+    };
+    let timeout_ms = command.apply_timeout(timeout_ms);
+    let command_name = command.command_name().to_string();
+    let Some(action) = command.to_action() else {
+        return error_response(
+            400,
+            &command_name,
+            "UNSUPPORTED_OPERATION",
+            &format!("{command_name} is not available over HTTP"),
+            "",
+        );
+    };
+
+    let send = async {
+        let mut client = DaemonClient::connect().await?;
+        client.send_action(&action).await
+    };
+    let outcome = match timeout_ms {
+        Some(ms) => tokio::time::timeout(Duration::from_millis(ms), send)
+            .await
+            .unwrap_or_else(|_| {
+                Ok(ActionResult::fatal_with_hint(
+                    "TIMEOUT",
+                    format!("{command_name} timed out after {ms}ms"),
+                    "increase timeout or retry the command",
+                ))
+            }),
+        None => send.await,
+    };
+    let result = match outcome {
+        Ok(result) => result,
+        Err(e) => {
+            return error_response(
+                503,
+                &command_name,
+                e.error_code(),
+                &e.to_string(),
+                &e.hint(),
+            );
+        }
+    };
+    let context = command.context(&result);
+    let envelope = JsonEnvelope::from_result(&command_name, context, &result, start.elapsed());
+    Response {
+        status: if result.is_ok() { 200 } else { 422 },
+        body: serde_json::to_value(&envelope).unwrap_or(Value::Null),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn config(token: Option<&str>) -> ServeConfig {
+        ServeConfig {
+            host: "127.0.0.1".to_string(),
+            port: DEFAULT_PORT,
+            token: token.map(String::from),
+        }
+    }
+
+    fn request(method: &str, path: &str, headers: &[(&str, &str)], body: &str) -> Request {
+        Request {
+            method: method.to_string(),
+            path: path.to_string(),
+            headers: headers
+                .iter()
+                .map(|(k, v)| (k.to_string(), v.to_string()))
+                .collect(),
+            body: body.as_bytes().to_vec(),
+        }
+    }
+
+    #[test]
+    fn browser_argv_maps_body_fields_to_flags() {
+        let argv = browser_argv(
+            "wait/element",
+            &json!({ "args": ["#done"], "session": "s1", "hidden": true, "stream": false,
+                     "timeout_ms": 500, "header": ["a: 1", "b: 2"] }),
+        )
+        .unwrap();
+        assert_eq!(
+            argv[..5],
+            ["actionbook", "browser", "wait", "element", "#done"]
+        );
+        let flags = argv[5..].join(" ");
+        assert!(flags.contains("--session s1"), "{flags}");
+        assert!(flags.contains("--hidden"), "{flags}");
+        assert!(!flags.contains("--stream"), "{flags}");
+        assert!(flags.contains("--timeout-ms 500"), "{flags}");
+        assert!(flags.contains("--header a: 1 --header b: 2"), "{flags}");
+
+        assert!(browser_argv("goto", &json!(["x"])).is_err());
+        assert!(browser_argv("goto", &json!({ "session": { "a": 1 } })).is_err());
+    }
+
+    #[test]
+    fn host_allowed_accepts_loopback_names_only() {
+        let cfg = config(None);
+        assert!(host_allowed(Some("localhost:8088"), &cfg));
+        assert!(host_allowed(Some("127.0.0.1:8088"), &cfg));
+        assert!(host_allowed(Some("[::1]:8088"), &cfg));
+        assert!(!host_allowed(Some("evil.example:8088"), &cfg));
+        assert!(!host_allowed(None, &cfg));
+    }
+
+    #[test]
+    fn host_allowed_accepts_any_host_on_a_token_protected_remote_bind() {
+        let remote = ServeConfig {
+            host: "0.0.0.0".to_string(),
+            ..config(Some("secret"))
+        };
+        assert!(host_allowed(Some("192.168.1.5:8787"), &remote));
+        assert!(host_allowed(Some("box.lan"), &remote));
+
+        let loopback = config(Some("secret"));
+        assert!(!host_allowed(Some("192.168.1.5:8787"), &loopback));
+    }
+
+    #[tokio::test]
+    async fn remote_bind_serves_lan_host_headers_with_the_token() {
+        let listener = TcpListener::bind("0.0.0.0:0").await.unwrap();
+        let port = listener.local_addr().unwrap().port();
+        let cfg = ServeConfig {
+            host: "0.0.0.0".to_string(),
+            port,
+            token: Some("secret".to_string()),
+        };
+        let exchange = |auth: &'static str| {
+            let raw = format!("GET /v1/health HTTP/1.1\r\nHost: 192.168.1.5:{port}\r\n{auth}\r\n");
+            async move {
+                let mut client = TcpStream::connect(("127.0.0.1", port)).await.unwrap();
+                client.write_all(raw.as_bytes()).await.unwrap();
+                let mut reply = String::new();
+                client.read_to_string(&mut reply).await.unwrap();
+                reply
+            }
+        };
+
+        for (auth, status) in [
+            ("Authorization: Bearer secret\r\n", "200"),
+            ("Authorization: Bearer secreT\r\n", "401"),
+            ("", "401"),
+        ] {
+            let client = tokio::spawn(exchange(auth));
+            let (stream, _) = listener.accept().await.unwrap();
+            handle_connection(stream, &cfg).await.unwrap();
+            let reply = client.await.unwrap();
+            assert!(
+                reply.starts_with(&format!("HTTP/1.1 {status} ")),
+                "{auth:?}: {reply}"
+            );
+        }
+    }
+
+    #[tokio::test]
+    async fn handle_request_checks_host_token_and_content_type() {
+        let with_token = config(Some("secret"));
+        let host = ("Host", "localhost:8088");
+        let json_type = ("Content-Type", "application/json");
+
+        let r = handle_request(
+            request(
+                "POST",
+                "/v1/browser/list-sessions",
+                &[host, json_type],
+                "{}",
+            ),
+            &with_token,
+        )
+        .await;
+        assert_eq!(r.status, 401);
+
+        let r = handle_request(
+            request(
+                "POST",
+                "/v1/browser/list-sessions",
+                &[("Host", "rebound.example"), json_type],
+                "{}",
+            ),
+            &config(None),
+        )
+        .await;
+        assert_eq!(r.status, 403);
+
+        let r = handle_request(
+            request(
+                "POST",
+                "/v1/browser/list-sessions",
+                &[host, ("Content-Type", "text/plain")],
+                "{}",
+            ),
+            &config(None),
+        )
+        .await;
+        assert_eq!(r.status, 415);
+
+        let r = handle_request(
+            request("POST", "/v1/browser/goto", &[host, json_type], "{}"),
+            &config(None),
+        )
+        .await;
+        assert_eq!(r.status, 400);
+        assert_eq!(r.body["error"]["code"], "INVALID_ARGUMENT");
+
+        let r = handle_request(request("GET", "/v1/nope", &[host], ""), &config(None)).await;
+        assert_eq!(r.status, 404);
+    }
+
+    #[tokio::test]
+    async fn read_request_parses_head_and_body() {
+        let raw =
+            "POST /v1/browser/goto?x=1 HTTP/1.1\r\nHost: localhost\r\nContent-Length: 2\r\n\r\n{}";
+        let mut reader = BufReader::new(raw.as_bytes());
+        let req = read_request(&mut reader).await.unwrap();
+        assert_eq!(req.method, "POST");
+        assert_eq!(req.path, "/v1/browser/goto");
+        assert_eq!(req.header("host"), Some("localhost"));
+        assert_eq!(req.body, b"{}");
+    }
+
+    #[tokio::test]
+    async fn read_request_rejects_overlong_lines() {
+        let raw = format!(
+            "GET /v1/health HTTP/1.1\r\nX-Filler: {}\r\n\r\n",
+            "a".repeat(MAX_LINE_BYTES)
+        );
+        let mut reader = BufReader::new(raw.as_bytes());
+        let err = read_request(&mut reader).await.unwrap_err();
+        assert_eq!(err.status, 431);
+
+        let raw = format!("GET /{} HTTP/1.1\r\n\r\n", "a".repeat(MAX_LINE_BYTES));
+        let mut reader = BufReader::new(raw.as_bytes());
+        assert_eq!(read_request(&mut reader).await.unwrap_err().status, 431);
+    }
+}
@@ -58,7 +58,7 @@ pub(crate) async fn configure_browser(
 /// install is unavailable (region-blocked, offline, corporate policy, etc.).
 fn configure_extension(json: bool, config: &mut ConfigFile) -> Result<(), CliError> {
     config.browser.executable_path = None;
-    config.browser.headless = false;
+    config.browser.headless = None;
     config.browser.cdp_endpoint = None;
 
     if json {
@@ -146,7 +146,7 @@ fn select_yes_no(prompt: &str, default_yes: bool) -> Result<bool, CliError> {
 
 fn configure_cloud(json: bool, config: &mut ConfigFile) -> Result<(), CliError> {
     config.browser.executable_path = None;
-    config.browser.headless = false;
+    config.browser.headless = None;
 
     if json {
         println!(
@@ -207,7 +207,7 @@ fn apply_existing_browser_mode(
                         "step": "browser",
                         "mode": "local",
                         "executable": config.browser.executable_path,
-                        "headless": config.browser.headless,
+                        "headless": config.browser.headless.is_some(),
                     })
                 );
             } else {
@@ -216,7 +216,7 @@ fn apply_existing_browser_mode(
         }
         Mode::Cloud => {
             config.browser.executable_path = None;
-            config.browser.headless = false;
+            config.browser.headless = None;
             if json {
                 println!(
                     "{}",
@@ -232,7 +232,7 @@ fn apply_existing_browser_mode(
         }
         Mode::Extension => {
             config.browser.executable_path = None;
-            config.browser.headless = false;
+            config.browser.headless = None;
             config.browser.cdp_endpoint = None;
             if json {
                 println!(
@@ -304,7 +304,7 @@ fn configure_local(
     };
 
     let headless_options = vec!["visible".to_string(), "headless".to_string()];
-    let headless_default = usize::from(config.browser.headless);
+    let headless_default = usize::from(config.browser.headless.is_some());
     let headless_selection = Select::with_theme(&setup_theme())
         .with_prompt("Display mode")
         .items(&headless_options)
@@ -312,7 +312,9 @@ fn configure_local(
         .interact()
         .map_err(|e| CliError::Internal(format!("Prompt failed: {e}")))?;
 
-    config.browser.headless = headless_selection == 1;
+    // Keep an `old` mode the user set by hand if they stay headless.
+    config.browser.headless =
+        (headless_selection == 1).then(|| config.browser.headless.unwrap_or_default());
     config.browser.cdp_endpoint = None;
 
     if json {
@@ -322,7 +324,7 @@ fn configure_local(
                 "step": "browser",
                 "mode": "local",
                 "executable": config.browser.executable_path,
-                "headless": config.browser.headless,
+                "headless": config.browser.headless.is_some(),
             })
         );
     } else {
@@ -392,11 +394,11 @@ fn apply_browser_mode(
         }
         Mode::Cloud => {
             config.browser.executable_path = None;
-            config.browser.headless = false;
+            config.browser.headless = None;
         }
         Mode::Extension => {
             config.browser.executable_path = None;
-            config.browser.headless = false;
+            config.browser.headless = None;
             config.browser.cdp_endpoint = None;
         }
     }
@@ -406,7 +408,7 @@ fn apply_browser_mode(
             "step": "browser",
             "mode": format!("{}", mode),
             "executable": config.browser.executable_path,
-            "headless": config.browser.headless,
+            "headless": config.browser.headless.is_some(),
             "cdp_endpoint": config.browser.cdp_endpoint,
         });
         if mode == Mode::Extension {
@@ -489,14 +491,14 @@ mod tests {
         let env = make_env_with_browsers(vec![]);
         let mut config = ConfigFile::default();
         config.browser.executable_path = Some("/usr/bin/chrome".to_string());
-        config.browser.headless = true;
+        config.browser.headless = Some(crate::types::HeadlessMode::New);
         config.browser.cdp_endpoint = Some("wss://browser.example.com".to_string());
 
         let result = apply_browser_mode(false, &env, Mode::Cloud, &mut config);
         assert!(result.is_ok());
         assert_eq!(config.browser.mode, Mode::Cloud);
         assert!(config.browser.executable_path.is_none());
-        assert!(!config.browser.headless);
+        assert!(config.browser.headless.is_none());
         assert_eq!(
             config.browser.cdp_endpoint.as_deref(),
             Some("wss://browser.example.com")
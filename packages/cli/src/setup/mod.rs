@@ -120,7 +120,7 @@ pub async fn execute(cmd: &Cmd, json: bool) -> Result<(), CliError> {
                     .executable_path
                     .as_deref()
                     .unwrap_or("auto-detect");
-                let headless_label = if config.browser.headless {
+                let headless_label = if config.browser.headless.is_some() {
                     "headless"
                 } else {
                     "visible"
@@ -277,7 +277,7 @@ fn print_step_connector() {
 
 /// Create a spinner with the given message. Returns `None` if in json or non-interactive mode.
 fn create_spinner(json: bool, non_interactive: bool, message: &str) -> Option<ProgressBar> {
-    if json || non_interactive {
+    if json || non_interactive || crate::utils::ci::enabled() {
         return None;
     }
     let pb = ProgressBar::new_spinner();
@@ -425,7 +425,7 @@ fn print_completion(json: bool, config: &ConfigFile, skills_result: &SkillsResul
                     .unwrap_or("endpoint not configured"),
                 Mode::Extension => "extension (bridge)",
             },
-            "headless": config.browser.headless,
+            "headless": config.browser.headless.is_some(),
             "skills": {
                 "npx_available": skills_result.npx_available,
                 "action": format!("{}", skills_result.action),
@@ -464,7 +464,7 @@ fn print_completion(json: bool, config: &ConfigFile, skills_result: &SkillsResul
                 .as_deref()
                 .map(shorten_browser_path)
                 .unwrap_or_else(|| "built-in".to_string());
-            let headless_str = if config.browser.headless {
+            let headless_str = if config.browser.headless.is_some() {
                 "headless"
             } else {
                 "visible"
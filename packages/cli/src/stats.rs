@@ -0,0 +1,418 @@
+//! Opt-in, local usage metrics.
+//!
+//! With `[telemetry] enabled = true` (or `actionbook stats enable`), each
+//! command adds to per-command counters in `~/.actionbook/stats/stats.json`:
+//! how often it ran, how it failed (by error code) and a latency histogram.
+//! Browser actions are counted by the daemon as they are routed, in memory,
+//! and written out every [`FLUSH_INTERVAL`] and on shutdown; top-level
+//! commands (`search`, `get`, `run`, …) by the CLI process. The daemon reads
+//! the setting once at startup, so `stats enable` / `stats disable` reach
+//! browser actions after `actionbook daemon restart`.
+//!
+//! Only command names, error codes and durations are kept — no session IDs,
+//! URLs, selectors or typed text. Nothing is sent anywhere until the user
+//! runs `actionbook stats upload`.
+
+use std::collections::BTreeMap;
+use std::fs::{self, OpenOptions};
+use std::path::PathBuf;
+use std::sync::{Mutex, OnceLock};
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+use serde_json::{Value, json};
+
+use crate::action_result::ActionResult;
+use crate::api::ApiClient;
+use crate::config;
+
+pub const COMMAND_NAME_SHOW: &str = "stats";
+pub const COMMAND_NAME_ENABLE: &str = "stats enable";
+pub const COMMAND_NAME_DISABLE: &str = "stats disable";
+pub const COMMAND_NAME_RESET: &str = "stats reset";
+pub const COMMAND_NAME_UPLOAD: &str = "stats upload";
+
+/// Upper bounds of the latency buckets; one more bucket catches the rest.
+pub const LATENCY_BUCKETS_MS: [u64; 9] = [50, 100, 250, 500, 1_000, 2_500, 5_000, 10_000, 30_000];
+
+/// How often the daemon adds its in-memory counts to the stats file.
+pub const FLUSH_INTERVAL: Duration = Duration::from_secs(30);
+
+/// Counts the daemon has not written out yet. Only set when telemetry was
+/// enabled at daemon startup.
+static PENDING: OnceLock<Mutex<UsageStats>> = OnceLock::new();
+
+/// Counters for one command.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct CommandStats {
+    pub count: u64,
+    /// Failures by error code (`RETRYABLE` / `USER_ACTION` for those results).
+    pub failures: BTreeMap<String, u64>,
+    /// Runs per [`LATENCY_BUCKETS_MS`] bucket, plus a final overflow bucket.
+    pub latency_ms: Vec<u64>,
+    pub total_ms: u64,
+}
+
+impl CommandStats {
+    fn add(&mut self, failure: Option<&str>, duration: Duration) {
+        let ms = duration.as_millis() as u64;
+        self.count += 1;
+        self.total_ms += ms;
+        if let Some(code) = failure {
+            *self.failures.entry(code.to_string()).or_insert(0) += 1;
+        }
+        self.latency_ms.resize(LATENCY_BUCKETS_MS.len() + 1, 0);
+        let bucket = LATENCY_BUCKETS_MS
+            .iter()
+            .position(|&le| ms <= le)
+            .unwrap_or(LATENCY_BUCKETS_MS.len());
+        self.latency_ms[bucket] += 1;
+    }
+
+    fn merge(&mut self, other: &CommandStats) {
+        self.count += other.count;
+        self.total_ms += other.total_ms;
+        for (code, n) in &other.failures {
+            *self.failures.entry(code.clone()).or_insert(0) += n;
+        }
+        if self.latency_ms.len() < other.latency_ms.len() {
+            self.latency_ms.resize(other.latency_ms.len(), 0);
+        }
+        for (bucket, n) in self.latency_ms.iter_mut().zip(&other.latency_ms) {
+            *bucket += n;
+        }
+    }
+
+    /// Upper bound of the bucket holding the `p`th percentile; `None` when
+    /// it falls in the overflow bucket or nothing was recorded.
+    pub fn percentile_ms(&self, p: f64) -> Option<u64> {
+        let rank = (self.count as f64 * p).ceil().max(1.0) as u64;
+        let mut seen = 0;
+        for (i, n) in self.latency_ms.iter().enumerate() {
+            seen += n;
+            if seen >= rank {
+                return LATENCY_BUCKETS_MS.get(i).copied();
+            }
+        }
+        None
+    }
+}
+
+/// The whole stats file.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct UsageStats {
+    /// When counting started (first record after a reset).
+    pub since: Option<String>,
+    pub commands: BTreeMap<String, CommandStats>,
+}
+
+impl UsageStats {
+    pub fn add(&mut self, command: &str, failure: Option<&str>, duration: Duration) {
+        if self.since.is_none() {
+            self.since = Some(crate::utils::time::now_rfc3339());
+        }
+        self.commands
+            .entry(command.to_string())
+            .or_default()
+            .add(failure, duration);
+    }
+
+    /// Add `other`'s counts to these, keeping the earlier `since`.
+    pub fn merge(&mut self, other: &UsageStats) {
+        if self.since.is_none() {
+            self.since.clone_from(&other.since);
+        }
+        for (command, stats) in &other.commands {
+            self.commands
+                .entry(command.clone())
+                .or_default()
+                .merge(stats);
+        }
+    }
+}
+
+pub fn stats_dir() -> PathBuf {
+    config::actionbook_home().join("stats")
+}
+
+fn stats_path() -> PathBuf {
+    stats_dir().join("stats.json")
+}
+
+/// Failure category of a result; `None` when it succeeded.
+pub fn failure_category(result: &ActionResult) -> Option<&str> {
+    match result {
+        ActionResult::Ok { .. } => None,
+        ActionResult::Fatal { code, .. } => Some(code),
+        ActionResult::Retryable { .. } => Some("RETRYABLE"),
+        ActionResult::UserAction { .. } => Some("USER_ACTION"),
+    }
+}
+
+pub fn read_stats() -> std::io::Result<UsageStats> {
+    match fs::read_to_string(stats_path()) {
+        Ok(text) => serde_json::from_str(&text)
+            .map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidData, e)),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(UsageStats::default()),
+        Err(e) => Err(e),
+    }
+}
+
+/// Read-modify-write the stats file under an exclusive lock, since the
+/// daemon and any number of CLI processes record concurrently.
+fn update_stats(f: impl FnOnce(&mut UsageStats)) -> std::io::Result<()> {
+    use fs2::FileExt;
+    let dir = stats_dir();
+    fs::create_dir_all(&dir)?;
+    let lock = OpenOptions::new()
+        .read(true)
+        .write(true)
+        .create(true)
+        .truncate(false)
+        .open(dir.join("stats.lock"))?;
+    lock.lock_exclusive()?;
+    // A corrupt file starts over rather than blocking every command.
+    let mut stats = read_stats().unwrap_or_default();
+    f(&mut stats);
+    let tmp = dir.join("stats.json.tmp");
+    fs::write(&tmp, serde_json::to_vec_pretty(&stats)?)?;
+    let renamed = fs::rename(&tmp, stats_path());
+    let _ = FileExt::unlock(&lock);
+    renamed
+}
+
+/// Count one run of `command`. A no-op unless telemetry is enabled; errors
+/// are swallowed so metrics never fail a command.
+pub fn record(command: &str, failure: Option<&str>, duration: Duration) {
+    if !config::telemetry_enabled() {
+        return;
+    }
+    if let Err(e) = update_stats(|s| s.add(command, failure, duration)) {
+        tracing::debug!("failed to record usage stats: {e}");
+    }
+}
+
+/// Count one action routed by the daemon. Only touches memory; a no-op unless
+/// [`start_daemon_recorder`] found telemetry enabled.
+pub fn record_result(command: &str, result: &ActionResult, duration: Duration) {
+    if let Some(pending) = PENDING.get() {
+        pending.lock().unwrap_or_else(|e| e.into_inner()).add(
+            command,
+            failure_category(result),
+            duration,
+        );
+    }
+}
+
+/// Called once at daemon startup: when telemetry is enabled, count routed
+/// actions in memory and flush them every [`FLUSH_INTERVAL`].
+pub fn start_daemon_recorder() {
+    if !config::telemetry_enabled() || PENDING.set(Mutex::new(UsageStats::default())).is_err() {
+        return;
+    }
+    tokio::spawn(async {
+        let mut tick = tokio::time::interval(FLUSH_INTERVAL);
+        tick.tick().await;
+        loop {
+            tick.tick().await;
+            let _ = tokio::task::spawn_blocking(flush_pending).await;
+        }
+    });
+}
+
+/// Add the daemon's pending counts to the stats file. Blocking; counts that
+/// fail to write stay pending for the next flush.
+pub fn flush_pending() {
+    let Some(pending) = PENDING.get() else {
+        return;
+    };
+    let delta = std::mem::take(&mut *pending.lock().unwrap_or_else(|e| e.into_inner()));
+    if delta.commands.is_empty() {
+        return;
+    }
+    if let Err(e) = update_stats(|s| s.merge(&delta)) {
+        tracing::debug!("failed to record usage stats: {e}");
+        let mut pending = pending.lock().unwrap_or_else(|e| e.into_inner());
+        let newer = std::mem::replace(&mut *pending, delta);
+        pending.merge(&newer);
+    }
+}
+
+fn io_failure(action: &str, e: impl std::fmt::Display) -> ActionResult {
+    ActionResult::fatal_with_hint(
+        "IO_ERROR",
+        format!("failed to {action} usage stats: {e}"),
+        format!("check permissions on {}", stats_dir().display()),
+    )
+}
+
+/// One row per command, most used first.
+fn command_rows(stats: &UsageStats) -> Vec<Value> {
+    let mut rows: Vec<(&String, &CommandStats)> = stats.commands.iter().collect();
+    rows.sort_by(|a, b| b.1.count.cmp(&a.1.count).then(a.0.cmp(b.0)));
+    rows.into_iter()
+        .map(|(name, c)| {
+            let failed: u64 = c.failures.values().sum();
+            json!({
+                "command": name,
+                "count": c.count,
+                "failed": failed,
+                "failures": c.failures,
+                "mean_ms": c.total_ms.checked_div(c.count),
+                "p50_ms": c.percentile_ms(0.5),
+                "p95_ms": c.percentile_ms(0.95),
+                "latency_ms": c.latency_ms,
+            })
+        })
+        .collect()
+}
+
+pub fn execute_show() -> ActionResult {
+    match read_stats() {
+        Ok(stats) => ActionResult::ok(json!({
+            "enabled": config::telemetry_enabled(),
+            "path": stats_path().display().to_string(),
+            "since": stats.since,
+            "total": stats.commands.values().map(|c| c.count).sum::<u64>(),
+            "buckets_ms": LATENCY_BUCKETS_MS,
+            "commands": command_rows(&stats),
+        })),
+        Err(e) => io_failure("read", e),
+    }
+}
+
+pub fn execute_set_enabled(enabled: bool) -> ActionResult {
+    let mut cfg = match config::load_config() {
+        Ok(cfg) => cfg,
+        Err(e) => return ActionResult::fatal(e.error_code(), e.to_string()),
+    };
+    cfg.telemetry.enabled = enabled;
+    match config::save_config(&cfg) {
+        Ok(path) => ActionResult::ok(json!({
+            "enabled": enabled,
+            "config": path.display().to_string(),
+            "path": stats_path().display().to_string(),
+        })),
+        Err(e) => ActionResult::fatal(e.error_code(), e.to_string()),
+    }
+}
+
+pub fn execute_reset() -> ActionResult {
+    match fs::remove_file(stats_path()) {
+        Ok(()) => ActionResult::ok(json!({ "reset": true })),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => {
+            ActionResult::ok(json!({ "reset": false }))
+        }
+        Err(e) => io_failure("reset", e),
+    }
+}
+
+/// What `stats upload` sends: the counters plus the CLI version and OS.
+pub fn upload_payload(stats: &UsageStats) -> Value {
+    json!({
+        "cli_version": env!("CARGO_PKG_VERSION"),
+        "os": std::env::consts::OS,
+        "since": stats.since,
+        "commands": stats.commands,
+    })
+}
+
+/// Send the local counters to the Actionbook API. With `reset`, clear them
+/// once the upload succeeds so the next upload does not repeat them.
+pub async fn execute_upload(api_key: Option<&str>, reset: bool) -> ActionResult {
+    let stats = match read_stats() {
+        Ok(stats) => stats,
+        Err(e) => return io_failure("read", e),
+    };
+    if stats.commands.is_empty() {
+        return ActionResult::fatal_with_hint(
+            "INVALID_ARGUMENT",
+            "no usage stats to upload",
+            "enable them with `actionbook stats enable`",
+        );
+    }
+    let payload = upload_payload(&stats);
+    let client = match config::load_config().and_then(|mut cfg| {
+        if let Some(key) = api_key {
+            cfg.api.api_key = Some(key.to_string());
+        }
+        ApiClient::from_config(&cfg)
+    }) {
+        Ok(client) => client,
+        Err(e) => return ActionResult::fatal(e.error_code(), e.to_string()),
+    };
+    if let Err(e) = client.upload_usage_stats(&payload).await {
+        return ActionResult::fatal_with_hint(e.error_code(), e.to_string(), e.hint());
+    }
+    if reset && let Err(e) = update_stats(|s| *s = UsageStats::default()) {
+        return io_failure("reset", e);
+    }
+    ActionResult::ok(json!({
+        "uploaded": payload,
+        "reset": reset,
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn add_counts_failures_and_buckets_latency() {
+        let mut stats = UsageStats::default();
+        stats.add("browser click", None, Duration::from_millis(40));
+        stats.add("browser click", None, Duration::from_millis(300));
+        stats.add(
+            "browser click",
+            Some("ELEMENT_NOT_FOUND"),
+            Duration::from_secs(60),
+        );
+        assert!(stats.since.is_some());
+
+        let click = &stats.commands["browser click"];
+        assert_eq!(click.count, 3);
+        assert_eq!(click.total_ms, 60_340);
+        assert_eq!(click.failures["ELEMENT_NOT_FOUND"], 1);
+        assert_eq!(click.latency_ms, [1, 0, 0, 1, 0, 0, 0, 0, 0, 1]);
+        assert_eq!(click.percentile_ms(0.3), Some(50));
+        assert_eq!(click.percentile_ms(0.5), Some(500));
+        assert_eq!(click.percentile_ms(0.95), None);
+    }
+
+    #[test]
+    fn merge_adds_counts_and_keeps_the_earlier_since() {
+        let mut file = UsageStats::default();
+        file.add("browser click", None, Duration::from_millis(40));
+        file.since = Some("2026-01-01T00:00:00.000Z".to_string());
+        let mut delta = UsageStats::default();
+        delta.add("browser click", Some("TIMEOUT"), Duration::from_secs(60));
+        delta.add("browser goto", None, Duration::from_millis(300));
+
+        file.merge(&delta);
+        assert_eq!(file.since.as_deref(), Some("2026-01-01T00:00:00.000Z"));
+        let click = &file.commands["browser click"];
+        assert_eq!(click.count, 2);
+        assert_eq!(click.total_ms, 60_040);
+        assert_eq!(click.failures["TIMEOUT"], 1);
+        assert_eq!(click.latency_ms, [1, 0, 0, 0, 0, 0, 0, 0, 0, 1]);
+        assert_eq!(file.commands["browser goto"].count, 1);
+    }
+
+    #[test]
+    fn failure_category_names_non_fatal_failures() {
+        assert_eq!(failure_category(&ActionResult::ok(json!({}))), None);
+        assert_eq!(
+            failure_category(&ActionResult::fatal("TIMEOUT", "slow")),
+            Some("TIMEOUT")
+        );
+        assert_eq!(
+            failure_category(&ActionResult::Retryable {
+                reason: "busy".into(),
+                hint: String::new(),
+            }),
+            Some("RETRYABLE")
+        );
+    }
+}
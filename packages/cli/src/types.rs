@@ -119,6 +119,12 @@ impl FromStr for SessionId {
     }
 }
 
+/// `--tab` value that stands for the session's active tab: the one picked
+/// with `browser switch`, or its first tab before any switch. `--tab` is
+/// still required; a command only runs on the active tab when given
+/// `--tab active`.
+pub const ACTIVE_TAB: &str = "active";
+
 /// Tab ID — Chrome's native CDP target ID (opaque string).
 #[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub struct TabId(pub String);
@@ -164,6 +170,8 @@ impl FromStr for WindowId {
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, clap::ValueEnum)]
 #[serde(rename_all = "snake_case")]
 pub enum Mode {
+    /// Local Chrome driven over CDP (`--backend cdp` is accepted as an alias).
+    #[value(alias = "cdp")]
     Local,
     Extension,
     Cloud,
@@ -191,6 +199,52 @@ impl FromStr for Mode {
     }
 }
 
+/// Which of Chrome's two headless implementations a session runs in. `New`
+/// is the full browser without a window; `Old` is the separate headless
+/// shell, which is lighter but easy to fingerprint.
+#[derive(
+    Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize, clap::ValueEnum,
+)]
+#[serde(rename_all = "snake_case")]
+pub enum HeadlessMode {
+    #[default]
+    New,
+    Old,
+}
+
+impl fmt::Display for HeadlessMode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            HeadlessMode::New => write!(f, "new"),
+            HeadlessMode::Old => write!(f, "old"),
+        }
+    }
+}
+
+/// How a launched Chrome renders WebGL. Headless Chrome has no GPU by
+/// default and recent versions no longer fall back to software rendering,
+/// so WebGL canvases come out blank unless one of these is chosen.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, clap::ValueEnum)]
+#[serde(rename_all = "snake_case")]
+pub enum GpuMode {
+    /// SwiftShader software rendering through ANGLE; works everywhere.
+    Swiftshader,
+    /// The machine's real GPU, ignoring Chrome's driver blocklist.
+    Hardware,
+    /// No GPU and no WebGL.
+    Off,
+}
+
+impl fmt::Display for GpuMode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            GpuMode::Swiftshader => write!(f, "swiftshader"),
+            GpuMode::Hardware => write!(f, "hardware"),
+            GpuMode::Off => write!(f, "off"),
+        }
+    }
+}
+
 #[derive(Debug, Clone)]
 pub enum ParseIdError {
     MissingPrefix(char),
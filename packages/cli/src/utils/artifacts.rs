@@ -0,0 +1,325 @@
+//! Per-run artifact directories for `actionbook run`: path templates, safe
+//! creation when runs start concurrently, and the retention policy that keeps
+//! long-lived installations from filling the disk.
+//!
+//! A run directory is the expanded `[artifacts] dir` template. It carries a
+//! `.actionbook-run` marker that the run holds an exclusive lock on until it
+//! ends; retention only ever removes marked, unlocked siblings, so it never
+//! touches unrelated directories or a run still in progress.
+
+use std::fs::{self, File, OpenOptions};
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+
+use fs2::FileExt;
+use serde_json::{Value, json};
+
+pub const MARKER: &str = ".actionbook-run";
+
+/// Values for the template placeholders.
+#[derive(Debug, Clone, Default)]
+pub struct TemplateVars {
+    pub profile: String,
+    pub flow: String,
+    pub session: String,
+    /// Compact UTC (`YYYYMMDDTHHMMSSZ`).
+    pub timestamp: String,
+}
+
+impl TemplateVars {
+    fn get(&self, name: &str) -> Option<&str> {
+        Some(match name {
+            "profile" => &self.profile,
+            "flow" => &self.flow,
+            "session" => &self.session,
+            "timestamp" => &self.timestamp,
+            _ => return None,
+        })
+    }
+}
+
+/// Whether `template` mentions `{name}`.
+pub fn uses(template: &str, name: &str) -> bool {
+    template.contains(&format!("{{{name}}}"))
+}
+
+/// A placeholder value as one path component: separators and anything a file
+/// system might reject become `_`.
+fn component(value: &str) -> String {
+    let cleaned: String = value
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.') {
+                c
+            } else {
+                '_'
+            }
+        })
+        .collect();
+    match cleaned.trim_matches('.') {
+        "" => "_".to_string(),
+        s => s.to_string(),
+    }
+}
+
+/// Expand `{profile}`, `{flow}`, `{session}` and `{timestamp}` in `template`,
+/// and a leading `~` to the home directory.
+pub fn expand(template: &str, vars: &TemplateVars) -> Result<PathBuf, String> {
+    let mut out = String::with_capacity(template.len());
+    let mut rest = template;
+    while let Some(start) = rest.find('{') {
+        out.push_str(&rest[..start]);
+        let after = &rest[start + 1..];
+        let end = after
+            .find('}')
+            .ok_or_else(|| format!("unclosed '{{' in artifacts template '{template}'"))?;
+        let name = &after[..end];
+        let value = vars.get(name).ok_or_else(|| {
+            format!(
+                "unknown placeholder {{{name}}} in artifacts template \
+                 (use {{profile}}, {{flow}}, {{session}} or {{timestamp}})"
+            )
+        })?;
+        out.push_str(&component(value));
+        rest = &after[end + 1..];
+    }
+    out.push_str(rest);
+
+    let path = match out.strip_prefix('~') {
+        Some(tail) if tail.is_empty() || tail.starts_with(['/', '\\']) => dirs::home_dir()
+            .ok_or("cannot expand '~': no home directory")?
+            .join(tail.trim_start_matches(['/', '\\'])),
+        _ => PathBuf::from(out),
+    };
+    if path.as_os_str().is_empty() {
+        return Err("artifacts template is empty".to_string());
+    }
+    std::path::absolute(&path).map_err(|e| e.to_string())
+}
+
+/// A run directory, locked for as long as this value lives.
+#[derive(Debug)]
+pub struct RunDir {
+    pub path: PathBuf,
+    _lock: File,
+}
+
+/// Create a fresh directory at `path`, or at `path-2`, `path-3`, … when two
+/// runs expand to the same name, and lock its marker.
+pub fn create_run_dir(path: &Path) -> std::io::Result<RunDir> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    let name = path
+        .file_name()
+        .map(|n| n.to_string_lossy().into_owned())
+        .unwrap_or_else(|| "run".to_string());
+    for n in 1u32.. {
+        let candidate = if n == 1 {
+            path.to_path_buf()
+        } else {
+            path.with_file_name(format!("{name}-{n}"))
+        };
+        // create_dir is atomic: exactly one concurrent caller wins a name.
+        match fs::create_dir(&candidate) {
+            Ok(()) => {
+                let lock = OpenOptions::new()
+                    .read(true)
+                    .write(true)
+                    .create(true)
+                    .truncate(false)
+                    .open(candidate.join(MARKER))?;
+                lock.lock_exclusive()?;
+                return Ok(RunDir {
+                    path: candidate,
+                    _lock: lock,
+                });
+            }
+            Err(e) if e.kind() == std::io::ErrorKind::AlreadyExists => continue,
+            Err(e) => return Err(e),
+        }
+    }
+    unreachable!("ran out of run directory suffixes")
+}
+
+fn dir_size(path: &Path) -> u64 {
+    let Ok(entries) = fs::read_dir(path) else {
+        return 0;
+    };
+    entries
+        .flatten()
+        .map(|e| match e.file_type() {
+            Ok(t) if t.is_dir() => dir_size(&e.path()),
+            Ok(_) => e.metadata().map(|m| m.len()).unwrap_or(0),
+            Err(_) => 0,
+        })
+        .sum()
+}
+
+/// A completed sibling run: path, start time (marker mtime) and size.
+struct PastRun {
+    path: PathBuf,
+    started: SystemTime,
+    bytes: u64,
+}
+
+/// Marked sibling runs in `parent` other than `current`. Runs whose marker
+/// is still locked are in progress and left out.
+fn past_runs(parent: &Path, current: &Path) -> Vec<PastRun> {
+    let Ok(entries) = fs::read_dir(parent) else {
+        return Vec::new();
+    };
+    entries
+        .flatten()
+        .filter_map(|e| {
+            let path = e.path();
+            if path == current || !e.file_type().ok()?.is_dir() {
+                return None;
+            }
+            let marker = OpenOptions::new().read(true).open(path.join(MARKER)).ok()?;
+            marker.try_lock_exclusive().ok()?;
+            let started = marker.metadata().and_then(|m| m.modified()).ok()?;
+            let _ = FileExt::unlock(&marker);
+            let bytes = dir_size(&path);
+            Some(PastRun {
+                path,
+                started,
+                bytes,
+            })
+        })
+        .collect()
+}
+
+/// Remove old runs next to `current`: beyond the newest `keep_last` (the
+/// current run counts as one), then oldest first until everything together
+/// fits in `max_total_bytes`. The current run is never removed.
+pub fn apply_retention(
+    current: &Path,
+    keep_last: Option<usize>,
+    max_total_bytes: Option<u64>,
+) -> Value {
+    let Some(parent) = current.parent() else {
+        return json!({ "removed": [], "freed_bytes": 0 });
+    };
+    let mut runs = past_runs(parent, current);
+    runs.sort_by_key(|r| std::cmp::Reverse(r.started));
+
+    let mut total = dir_size(current);
+    let mut kept = 1usize;
+    let mut drop = Vec::new();
+    for run in runs {
+        // Once one run is dropped, every older one goes too.
+        let full = !drop.is_empty()
+            || keep_last.is_some_and(|k| kept >= k)
+            || max_total_bytes.is_some_and(|max| total + run.bytes > max);
+        if full {
+            drop.push(run);
+        } else {
+            total += run.bytes;
+            kept += 1;
+        }
+    }
+
+    let mut removed = Vec::new();
+    let mut freed = 0u64;
+    for run in drop {
+        match fs::remove_dir_all(&run.path) {
+            Ok(()) => {
+                freed += run.bytes;
+                removed.push(run.path.display().to_string());
+            }
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => {}
+            Err(e) => tracing::warn!("failed to remove {}: {e}", run.path.display()),
+        }
+    }
+    json!({ "removed": removed, "freed_bytes": freed, "kept": kept })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn vars() -> TemplateVars {
+        TemplateVars {
+            profile: "actionbook".into(),
+            flow: "check out".into(),
+            session: "s1".into(),
+            timestamp: "20261014T093000Z".into(),
+        }
+    }
+
+    #[test]
+    fn expand_fills_placeholders_as_single_components() {
+        let dir = tempfile::tempdir().unwrap();
+        let template = format!(
+            "{}/{{profile}}/{{flow}}/{{timestamp}}",
+            dir.path().display()
+        );
+        assert_eq!(
+            expand(&template, &vars()).unwrap(),
+            dir.path()
+                .join("actionbook")
+                .join("check_out")
+                .join("20261014T093000Z")
+        );
+
+        let sneaky = TemplateVars {
+            flow: "../../etc".into(),
+            ..vars()
+        };
+        let path = expand(&format!("{}/{{flow}}", dir.path().display()), &sneaky).unwrap();
+        assert_eq!(path.parent().unwrap(), dir.path());
+
+        assert!(
+            expand("runs/{date}", &vars())
+                .unwrap_err()
+                .contains("{date}")
+        );
+        assert!(expand("runs/{flow", &vars()).is_err());
+        assert!(expand("~/runs", &vars()).unwrap().ends_with("runs"));
+    }
+
+    #[test]
+    fn create_run_dir_never_reuses_a_directory() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("flow").join("20261014T093000Z");
+        let a = create_run_dir(&path).unwrap();
+        let b = create_run_dir(&path).unwrap();
+        assert_eq!(a.path, path);
+        assert_eq!(b.path, dir.path().join("flow").join("20261014T093000Z-2"));
+        assert!(b.path.join(MARKER).is_file());
+    }
+
+    #[test]
+    fn retention_keeps_newest_runs_and_skips_active_or_foreign_dirs() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut past = Vec::new();
+        for i in 0..4 {
+            let run = create_run_dir(&dir.path().join(format!("run{i}"))).unwrap();
+            fs::write(run.path.join("shot.png"), vec![0u8; 100]).unwrap();
+            let when = SystemTime::UNIX_EPOCH + std::time::Duration::from_secs(1_000 + i);
+            File::options()
+                .write(true)
+                .open(run.path.join(MARKER))
+                .unwrap()
+                .set_modified(when)
+                .unwrap();
+            past.push(run.path.clone());
+            drop(run);
+        }
+        let active = create_run_dir(&dir.path().join("active")).unwrap();
+        fs::create_dir(dir.path().join("notes")).unwrap();
+        let current = create_run_dir(&dir.path().join("current")).unwrap();
+
+        let report = apply_retention(&current.path, Some(3), None);
+        assert_eq!(report["removed"].as_array().unwrap().len(), 2);
+        assert!(!past[0].exists() && !past[1].exists());
+        assert!(past[2].exists() && past[3].exists());
+        assert!(active.path.exists() && dir.path().join("notes").exists());
+
+        let report = apply_retention(&current.path, None, Some(50));
+        assert_eq!(report["freed_bytes"], 200);
+        assert!(!past[2].exists() && !past[3].exists());
+        assert!(current.path.exists());
+    }
+}
@@ -0,0 +1,201 @@
+//! CI and container detection.
+//!
+//! Pipelines and containers have no display, are slower than a laptop and
+//! often run everything as root, so the same commands need different
+//! defaults there: a headless browser, no spinners or colour, longer waits,
+//! and `--no-sandbox` because Chrome refuses to sandbox itself as root.
+//! CI mode is on when `--ci` is passed, when `ACTIONBOOK_CI` is true, or when
+//! a known CI system or a display-less container is detected.
+//! `ACTIONBOOK_CI=0` turns detection off.
+
+use std::path::Path;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+/// Environment variable that forces CI mode on (`1`) or off (`0`). `--ci`
+/// sets it for the daemon it starts.
+pub const CI_ENV: &str = "ACTIONBOOK_CI";
+
+/// How much longer default waits are in CI mode.
+pub const TIMEOUT_FACTOR: u64 = 2;
+
+/// Variables set by CI systems, and the name each one reports.
+const CI_VARS: &[(&str, &str)] = &[
+    ("GITHUB_ACTIONS", "GitHub Actions"),
+    ("GITLAB_CI", "GitLab CI"),
+    ("BUILDKITE", "Buildkite"),
+    ("CIRCLECI", "CircleCI"),
+    ("TRAVIS", "Travis CI"),
+    ("JENKINS_URL", "Jenkins"),
+    ("TF_BUILD", "Azure Pipelines"),
+    ("TEAMCITY_VERSION", "TeamCity"),
+    ("BITBUCKET_BUILD_NUMBER", "Bitbucket Pipelines"),
+    ("CODEBUILD_BUILD_ID", "AWS CodeBuild"),
+    ("CI", "CI"),
+];
+
+static FORCED: AtomicBool = AtomicBool::new(false);
+
+/// Turn CI mode on for this process, as `--ci` does.
+pub fn force() {
+    FORCED.store(true, Ordering::Relaxed);
+}
+
+/// Whether `--ci` was passed to this process.
+pub fn forced() -> bool {
+    FORCED.load(Ordering::Relaxed)
+}
+
+/// Why CI mode is on, if it is.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Reason {
+    /// `--ci` or `ACTIONBOOK_CI=1`.
+    Explicit,
+    /// A CI system, by name.
+    Ci(&'static str),
+    /// A container with no display.
+    Container,
+}
+
+/// What `detect` looks at, so it can be tested without touching the process.
+pub struct Probe<'a> {
+    pub env: &'a dyn Fn(&str) -> Option<String>,
+    pub container: bool,
+}
+
+/// The reason CI mode is on for `probe`, or `None` when it is off.
+pub fn detect(probe: &Probe) -> Option<Reason> {
+    let env = |name: &str| {
+        (probe.env)(name)
+            .map(|v| v.trim().to_ascii_lowercase())
+            .filter(|v| !v.is_empty())
+    };
+    match env(CI_ENV).as_deref() {
+        Some("1" | "true" | "yes" | "on") => return Some(Reason::Explicit),
+        Some("0" | "false" | "no" | "off") => return None,
+        _ => {}
+    }
+    for (var, name) in CI_VARS {
+        if env(var).is_some_and(|v| v != "false" && v != "0") {
+            return Some(Reason::Ci(name));
+        }
+    }
+    let display = env("DISPLAY").is_some() || env("WAYLAND_DISPLAY").is_some();
+    (probe.container && !display).then_some(Reason::Container)
+}
+
+/// The reason CI mode is on for this process, or `None` when it is off.
+pub fn reason() -> Option<Reason> {
+    if forced() {
+        return Some(Reason::Explicit);
+    }
+    detect(&Probe {
+        env: &|name| std::env::var(name).ok(),
+        container: in_container(),
+    })
+}
+
+/// Whether CI mode is on for this process.
+pub fn enabled() -> bool {
+    reason().is_some()
+}
+
+/// `default_ms` stretched for CI mode; unchanged otherwise. Only defaults
+/// are scaled: a timeout the user passed is used as given.
+pub fn scale_timeout(default_ms: u64) -> u64 {
+    if enabled() {
+        default_ms.saturating_mul(TIMEOUT_FACTOR)
+    } else {
+        default_ms
+    }
+}
+
+/// Whether this process runs inside a Docker, Podman or Kubernetes container.
+pub fn in_container() -> bool {
+    if Path::new("/.dockerenv").exists()
+        || Path::new("/run/.containerenv").exists()
+        || std::env::var_os("KUBERNETES_SERVICE_HOST").is_some()
+    {
+        return true;
+    }
+    std::fs::read_to_string("/proc/1/cgroup").is_ok_and(|c| cgroup_is_container(&c))
+}
+
+fn cgroup_is_container(cgroup: &str) -> bool {
+    ["docker", "kubepods", "containerd", "libpod", "lxc"]
+        .iter()
+        .any(|k| cgroup.contains(k))
+}
+
+/// Whether this process runs as root. Read from `/proc` so no libc binding
+/// is needed; false where there is no `/proc`.
+pub fn is_root() -> bool {
+    std::fs::read_to_string("/proc/self/status").is_ok_and(|s| status_euid(&s) == Some(0))
+}
+
+/// The effective uid from a `/proc/<pid>/status` file.
+fn status_euid(status: &str) -> Option<u32> {
+    let line = status.lines().find(|l| l.starts_with("Uid:"))?;
+    line.split_whitespace().nth(2)?.parse().ok()
+}
+
+/// Chrome will not start its sandbox as root, which is how most containers
+/// run, so it has to be launched with `--no-sandbox` there.
+pub fn needs_no_sandbox() -> bool {
+    is_root() && in_container()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn detect_with(vars: &[(&str, &str)], container: bool) -> Option<Reason> {
+        let env = |name: &str| {
+            vars.iter()
+                .find(|(k, _)| *k == name)
+                .map(|(_, v)| v.to_string())
+        };
+        detect(&Probe {
+            env: &env,
+            container,
+        })
+    }
+
+    #[test]
+    fn detect_names_the_ci_system_and_honors_the_override() {
+        assert_eq!(detect_with(&[], false), None);
+        assert_eq!(
+            detect_with(&[("CI", "true"), ("GITHUB_ACTIONS", "true")], false),
+            Some(Reason::Ci("GitHub Actions"))
+        );
+        assert_eq!(detect_with(&[("CI", "1")], false), Some(Reason::Ci("CI")));
+        assert_eq!(detect_with(&[("CI", "false")], false), None);
+        assert_eq!(
+            detect_with(&[("ACTIONBOOK_CI", "1")], false),
+            Some(Reason::Explicit)
+        );
+        assert_eq!(
+            detect_with(&[("ACTIONBOOK_CI", "0"), ("GITLAB_CI", "true")], true),
+            None
+        );
+    }
+
+    #[test]
+    fn detect_treats_only_display_less_containers_as_ci() {
+        assert_eq!(detect_with(&[], true), Some(Reason::Container));
+        assert_eq!(detect_with(&[("DISPLAY", ":0")], true), None);
+        assert_eq!(detect_with(&[("WAYLAND_DISPLAY", "wayland-0")], true), None);
+    }
+
+    #[test]
+    fn proc_files_are_parsed_for_containers_and_root() {
+        assert!(cgroup_is_container(
+            "0::/kubepods/besteffort/pod1234/abcd\n"
+        ));
+        assert!(cgroup_is_container("12:pids:/docker/0123abcd\n"));
+        assert!(!cgroup_is_container("0::/init.scope\n"));
+
+        let status = "Name:\tactionbook\nUid:\t1000\t0\t0\t0\nGid:\t1000\t1000\t1000\t1000\n";
+        assert_eq!(status_euid(status), Some(0));
+        assert_eq!(status_euid("Name:\tx\n"), None);
+    }
+}
@@ -95,13 +95,21 @@ impl DaemonClient {
 
     /// Send an action and receive the result.
     pub async fn send_action(&mut self, action: &Action) -> Result<ActionResult, CliError> {
+        self.send_action_streaming(action, false, |_| {}).await
+    }
+
+    /// Send an action; with `stream`, the daemon's progress events are passed
+    /// to `on_event` until the result arrives.
+    pub async fn send_action_streaming(
+        &mut self,
+        action: &Action,
+        stream: bool,
+        mut on_event: impl FnMut(serde_json::Value),
+    ) -> Result<ActionResult, CliError> {
         let id = REQUEST_ID.fetch_add(1, Ordering::Relaxed);
-        let payload = wire::serialize_request(id, action)?;
+        let payload = wire::serialize_request(id, action, stream)?;
         wire::write_frame(&mut self.writer, &payload).await?;
-
-        let response_payload = wire::read_frame(&mut self.reader).await?;
-        let response: wire::Response = serde_json::from_slice(&response_payload)?;
-        Ok(response.result)
+        read_response(&mut self.reader, id, &mut on_event).await
     }
 }
 
@@ -171,12 +179,21 @@ impl DaemonClient {
 
     /// Send an action and receive the result.
     pub async fn send_action(&mut self, action: &Action) -> Result<ActionResult, CliError> {
+        self.send_action_streaming(action, false, |_| {}).await
+    }
+
+    /// Send an action; with `stream`, the daemon's progress events are passed
+    /// to `on_event` until the result arrives.
+    pub async fn send_action_streaming(
+        &mut self,
+        action: &Action,
+        stream: bool,
+        mut on_event: impl FnMut(serde_json::Value),
+    ) -> Result<ActionResult, CliError> {
         let id = REQUEST_ID.fetch_add(1, Ordering::Relaxed);
-        let payload = wire::serialize_request(id, action)?;
+        let payload = wire::serialize_request(id, action, stream)?;
         wire::write_frame(&mut self.writer, &payload).await?;
-        let response_payload = wire::read_frame(&mut self.reader).await?;
-        let response: wire::Response = serde_json::from_slice(&response_payload)?;
-        Ok(response.result)
+        read_response(&mut self.reader, id, &mut on_event).await
     }
 }
 
@@ -193,6 +210,37 @@ impl DaemonClient {
             "daemon is not supported on this platform".to_string(),
         ))
     }
+
+    pub async fn send_action_streaming(
+        &mut self,
+        _action: &Action,
+        _stream: bool,
+        _on_event: impl FnMut(serde_json::Value),
+    ) -> Result<ActionResult, CliError> {
+        Err(CliError::Internal(
+            "daemon is not supported on this platform".to_string(),
+        ))
+    }
+}
+
+/// Read frames until the response to request `id` arrives. The daemon answers
+/// requests on a connection in completion order, so a response to an earlier
+/// request whose caller gave up waiting can arrive first; it is skipped.
+/// Progress events for `id` go to `on_event`.
+#[cfg(any(unix, windows))]
+async fn read_response<R: tokio::io::AsyncRead + Unpin>(
+    reader: &mut R,
+    id: u64,
+    on_event: &mut dyn FnMut(serde_json::Value),
+) -> Result<ActionResult, CliError> {
+    loop {
+        let payload = wire::read_frame(reader).await?;
+        match serde_json::from_slice(&payload)? {
+            wire::Frame::Response(response) if response.id == id => return Ok(response.result),
+            wire::Frame::Event(event) if event.id == id => on_event(event.event),
+            _ => {}
+        }
+    }
 }
 
 /// Check if the running daemon's version matches the CLI binary exactly.
@@ -241,6 +289,101 @@ pub async fn restart_daemon_now() -> Result<(), CliError> {
     }
 }
 
+/// Public wrapper for `actionbook daemon start`. Connects to the daemon,
+/// spawning it (or replacing one from another build) when needed, so the
+/// first browser command of a session does not pay the startup cost.
+/// Returns `Ok(true)` if this call started it.
+pub async fn start_daemon_now() -> Result<bool, CliError> {
+    let was_running = server::is_daemon_running()
+        && versions_match(&server::socket_path().with_extension("version"));
+    DaemonClient::connect().await?;
+    Ok(!was_running)
+}
+
+/// What `actionbook daemon status` reports: whether a daemon is running, its
+/// pid and build, and how many sessions it holds. Never spawns or restarts
+/// one — sessions are only counted when the running daemon is this build.
+pub async fn daemon_status() -> serde_json::Value {
+    let socket = server::socket_path();
+    let version_path = socket.with_extension("version");
+    let running = server::is_daemon_running();
+    let version = running
+        .then(|| std::fs::read_to_string(&version_path).ok())
+        .flatten()
+        .map(|v| v.trim().to_string())
+        .filter(|v| !v.is_empty());
+    let mut status = serde_json::json!({
+        "running": running,
+        "pid": running.then(server::read_daemon_pid).flatten(),
+        "version": version,
+        "cli_version": crate::BUILD_VERSION,
+        "socket": socket.display().to_string(),
+    });
+    if running && versions_match(&version_path) {
+        let list = Action::ListSessions(crate::browser::session::list::Cmd {});
+        if let Ok(mut client) = DaemonClient::connect().await
+            && let Ok(ActionResult::Ok { data }) = client.send_action(&list).await
+        {
+            status["sessions"] = data["total_sessions"].clone();
+        }
+    }
+    status
+}
+
+/// How long `daemon stop` waits for a graceful shutdown. Closing sessions
+/// (CDP close + Chrome exit) can take a few seconds each.
+const DAEMON_STOP_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Public wrapper for `actionbook daemon stop`. Asks the running daemon to
+/// shut down gracefully (SIGTERM on Unix, the named stop event on Windows)
+/// and waits for it to exit, so its sessions are closed and its state files
+/// removed. Returns `Ok(false)` if no daemon was running.
+pub async fn stop_daemon_now() -> Result<bool, CliError> {
+    #[cfg(any(unix, windows))]
+    {
+        if !server::is_daemon_running() {
+            return Ok(false);
+        }
+        let Some(pid) = server::read_daemon_pid().filter(|&p| p > 0) else {
+            return Err(CliError::Internal(
+                "daemon PID file missing/corrupt but daemon is still running".to_string(),
+            ));
+        };
+        if server::request_shutdown(pid) && wait_for_pid_exit(pid, DAEMON_STOP_TIMEOUT).await {
+            return Ok(true);
+        }
+        // A daemon that predates the stop event only yields to taskkill.
+        #[cfg(windows)]
+        if server::send_sigterm(pid) {
+            cleanup_stale_files_windows();
+            return Ok(true);
+        }
+        Err(CliError::Internal(format!(
+            "daemon (pid={pid}) did not exit within {}s",
+            DAEMON_STOP_TIMEOUT.as_secs()
+        )))
+    }
+    #[cfg(not(any(unix, windows)))]
+    {
+        Err(CliError::Internal(
+            "daemon stop is not supported on this platform".to_string(),
+        ))
+    }
+}
+
+/// Poll until `pid` has exited, up to `timeout`. Returns `true` if it did.
+#[cfg(any(unix, windows))]
+async fn wait_for_pid_exit(pid: i32, timeout: Duration) -> bool {
+    let start = std::time::Instant::now();
+    while start.elapsed() < timeout {
+        if !server::is_pid_alive(pid) {
+            return true;
+        }
+        tokio::time::sleep(Duration::from_millis(100)).await;
+    }
+    !server::is_pid_alive(pid)
+}
+
 /// Stop the running daemon and start a fresh one with the current binary.
 /// `reason` controls the user-facing log line so "version mismatch" doesn't
 /// leak into a user-initiated `daemon restart`. When `force` is true the
@@ -357,7 +500,12 @@ fn auto_start_daemon() -> Result<(), CliError> {
         .map(std::process::Stdio::from)
         .unwrap_or_else(|_| std::process::Stdio::null());
 
-    std::process::Command::new(&exe)
+    let mut command = std::process::Command::new(&exe);
+    // The daemon inherits CI variables from this shell, but not `--ci`.
+    if crate::utils::ci::forced() {
+        command.env(crate::utils::ci::CI_ENV, "1");
+    }
+    command
         .arg("__daemon")
         .stdin(std::process::Stdio::null())
         .stdout(std::process::Stdio::null())
@@ -399,7 +547,11 @@ async fn restart_daemon_windows(reason: &str, force: bool) -> Result<(), CliErro
 
     eprintln!("{reason}, restarting daemon (pid={pid})...");
 
-    if server::send_sigterm(pid) {
+    // Graceful first so sessions are closed and state files removed; force
+    // kill only a daemon that ignores (or predates) the stop event.
+    let stopped =
+        server::request_shutdown(pid) && wait_for_pid_exit(pid, Duration::from_secs(5)).await;
+    if !stopped && server::send_sigterm(pid) {
         // On Windows, is_pid_alive() checks the TCP port via daemon.port
         // rather than actual PID liveness.  Remove daemon.port immediately
         // after the force-kill so the liveness check returns false without
@@ -490,7 +642,11 @@ fn auto_start_daemon_windows() -> Result<(), CliError> {
         .map(std::process::Stdio::from)
         .unwrap_or_else(|_| std::process::Stdio::null());
 
-    std::process::Command::new(&exe)
+    let mut command = std::process::Command::new(&exe);
+    if crate::utils::ci::forced() {
+        command.env(crate::utils::ci::CI_ENV, "1");
+    }
+    command
         .arg("__daemon")
         .stdin(std::process::Stdio::null())
         .stdout(std::process::Stdio::null())
@@ -575,4 +731,28 @@ mod tests {
             "different minor version must NOT match"
         );
     }
+
+    #[tokio::test]
+    async fn read_response_skips_other_request_ids() {
+        let (mut daemon, mut cli) = tokio::io::duplex(4096);
+        for (id, reason) in [(3, "stale"), (4, "ours")] {
+            let event = wire::serialize_event(id, &serde_json::json!({ "for": id })).unwrap();
+            wire::write_frame(&mut daemon, &event).await.unwrap();
+            let result = ActionResult::Retryable {
+                reason: reason.to_string(),
+                hint: String::new(),
+            };
+            let payload = wire::serialize_response(id, &result).unwrap();
+            wire::write_frame(&mut daemon, &payload).await.unwrap();
+        }
+        let mut events = Vec::new();
+        match read_response(&mut cli, 4, &mut |e| events.push(e))
+            .await
+            .unwrap()
+        {
+            ActionResult::Retryable { reason, .. } => assert_eq!(reason, "ours"),
+            other => panic!("unexpected result: {other:?}"),
+        }
+        assert_eq!(events, vec![serde_json::json!({ "for": 4 })]);
+    }
 }
@@ -1,2 +1,8 @@
+pub mod artifacts;
+pub mod ci;
 pub mod client;
+pub mod parse;
+pub mod sink;
+pub mod time;
+pub mod url;
 pub mod wire;
@@ -0,0 +1,467 @@
+//! Typed parsing of scraped cell text: locale-aware numbers, currency
+//! amounts and dates (no chrono dependency).
+//!
+//! A rule is `[COLUMN=]TYPE`, where TYPE is one of
+//! - `number` / `number:<locale>` → JSON number (`"1.234,56"` → `1234.56` for `de-DE`)
+//! - `currency` / `currency:<locale>` → `{ "amount": 1234.56, "currency": "EUR" }`
+//! - `date:<format>` → ISO 8601 string, using a strftime subset
+//!   (`%Y %y %m %d %H %M %S %b %B %p %%`)
+//!
+//! Without a locale, the last `.` or `,` is the decimal separator when the
+//! other one also appears or when it is not followed by exactly three digits;
+//! otherwise it is a thousands separator (`1.5` → 1.5, `1,234` → 1234).
+
+use std::str::FromStr;
+
+use serde_json::{Value, json};
+
+/// Languages writing a decimal comma (`1.234,56`); all others use a point.
+const DECIMAL_COMMA_LANGUAGES: &[&str] = &[
+    "bg", "ca", "cs", "da", "de", "el", "es", "et", "fi", "fr", "hr", "hu", "id", "it", "lt", "lv",
+    "nb", "nl", "nn", "no", "pl", "pt", "ro", "ru", "sk", "sl", "sr", "sv", "tr", "uk", "vi",
+];
+/// Regions whose language would say comma but that write a decimal point.
+const DECIMAL_POINT_LOCALES: &[&str] = &["de-CH", "de-LI", "it-CH", "es-MX", "es-US"];
+
+/// Currency symbols and the ISO 4217 code each stands for. Longer symbols
+/// come first so `US$` wins over `$`.
+const CURRENCY_SYMBOLS: &[(&str, &str)] = &[
+    ("US$", "USD"),
+    ("CA$", "CAD"),
+    ("A$", "AUD"),
+    ("R$", "BRL"),
+    ("zł", "PLN"),
+    ("Kč", "CZK"),
+    ("kr", "SEK"),
+    ("€", "EUR"),
+    ("$", "USD"),
+    ("£", "GBP"),
+    ("¥", "JPY"),
+    ("₹", "INR"),
+    ("₩", "KRW"),
+    ("₽", "RUB"),
+    ("₺", "TRY"),
+    ("₪", "ILS"),
+    ("₫", "VND"),
+    ("฿", "THB"),
+];
+
+const MONTHS: [&str; 12] = [
+    "january",
+    "february",
+    "march",
+    "april",
+    "may",
+    "june",
+    "july",
+    "august",
+    "september",
+    "october",
+    "november",
+    "december",
+];
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum ValueParser {
+    /// Decimal separator, or `None` to infer it per value.
+    Number(Option<char>),
+    Currency(Option<char>),
+    /// strftime-style format.
+    Date(String),
+}
+
+/// One `--parse` rule: a parser, optionally limited to one column.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ParseRule {
+    pub column: Option<String>,
+    pub parser: ValueParser,
+}
+
+fn decimal_separator(locale: &str) -> Result<char, String> {
+    let valid = !locale.is_empty()
+        && locale
+            .split(['-', '_'])
+            .all(|part| !part.is_empty() && part.chars().all(|c| c.is_ascii_alphanumeric()));
+    if !valid {
+        return Err(format!("invalid locale '{locale}' (expected e.g. de-DE)"));
+    }
+    let locale = locale.replace('_', "-");
+    if DECIMAL_POINT_LOCALES
+        .iter()
+        .any(|l| l.eq_ignore_ascii_case(&locale))
+    {
+        return Ok('.');
+    }
+    let language = locale.split('-').next().unwrap_or("").to_ascii_lowercase();
+    Ok(if DECIMAL_COMMA_LANGUAGES.contains(&language.as_str()) {
+        ','
+    } else {
+        '.'
+    })
+}
+
+impl FromStr for ValueParser {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, String> {
+        let (kind, arg) = match s.split_once(':') {
+            Some((k, a)) => (k, Some(a)),
+            None => (s, None),
+        };
+        let separator = |arg: Option<&str>| arg.map(decimal_separator).transpose();
+        match kind {
+            "number" => Ok(Self::Number(separator(arg)?)),
+            "currency" => Ok(Self::Currency(separator(arg)?)),
+            "date" => match arg {
+                Some(format) if !format.is_empty() => {
+                    validate_date_format(format)?;
+                    Ok(Self::Date(format.to_string()))
+                }
+                _ => Err("date needs a format, e.g. date:%d.%m.%Y".to_string()),
+            },
+            other => Err(format!(
+                "unknown parse type '{other}' (expected number, currency or date)"
+            )),
+        }
+    }
+}
+
+impl FromStr for ParseRule {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, String> {
+        let (column, spec) = match s.split_once('=') {
+            Some((column, spec)) if !column.is_empty() => (Some(column.to_string()), spec),
+            Some(_) => return Err(format!("empty column name in '{s}'")),
+            None => (None, s),
+        };
+        Ok(Self {
+            column,
+            parser: spec.parse()?,
+        })
+    }
+}
+
+/// Clap value parser for `--parse`: checks the rule, keeps the text.
+pub fn parse_rule_arg(s: &str) -> Result<String, String> {
+    s.parse::<ParseRule>().map(|_| s.to_string())
+}
+
+impl ValueParser {
+    /// The typed value of `text`, or `None` when it does not parse.
+    pub fn parse(&self, text: &str) -> Option<Value> {
+        match self {
+            Self::Number(decimal) => parse_number(text, *decimal),
+            Self::Currency(decimal) => {
+                let (code, rest) = strip_currency(text.trim());
+                let amount = parse_number(rest, *decimal)?;
+                Some(json!({ "amount": amount, "currency": code }))
+            }
+            Self::Date(format) => parse_date(text.trim(), format).map(Value::String),
+        }
+    }
+}
+
+/// Integer when there is no fractional part and it fits, else `f64`.
+pub fn parse_number(text: &str, decimal: Option<char>) -> Option<Value> {
+    let mut s = text.trim();
+    let mut negative = false;
+    if let Some(inner) = s.strip_prefix('(').and_then(|r| r.strip_suffix(')')) {
+        // Accounting style: (1,234.00)
+        negative = true;
+        s = inner.trim();
+    }
+    if let Some(rest) = s.strip_prefix(['-', '\u{2212}']) {
+        negative = !negative;
+        s = rest.trim_start();
+    } else if let Some(rest) = s.strip_prefix('+') {
+        s = rest.trim_start();
+    }
+    if s.is_empty() || !s.chars().all(|c| c.is_ascii_digit() || is_separator(c)) {
+        return None;
+    }
+    let decimal = decimal.unwrap_or_else(|| infer_decimal(s));
+    let mut normalized = String::with_capacity(s.len());
+    let mut seen_decimal = false;
+    for c in s.chars() {
+        if c.is_ascii_digit() {
+            normalized.push(c);
+        } else if c == decimal {
+            if seen_decimal {
+                return None;
+            }
+            seen_decimal = true;
+            normalized.push('.');
+        }
+        // Any other separator groups digits and is dropped.
+    }
+    if normalized.trim_matches('.').is_empty() {
+        return None;
+    }
+    if !seen_decimal && let Ok(n) = normalized.parse::<i64>() {
+        return Some(json!(if negative { -n } else { n }));
+    }
+    let n: f64 = normalized.parse().ok()?;
+    Some(json!(if negative { -n } else { n }))
+}
+
+fn is_separator(c: char) -> bool {
+    matches!(
+        c,
+        '.' | ',' | ' ' | '\'' | '\u{a0}' | '\u{202f}' | '\u{2019}'
+    )
+}
+
+/// Decimal separator of a number written without a known locale.
+fn infer_decimal(s: &str) -> char {
+    let Some(pos) = s.rfind(['.', ',']) else {
+        return '.';
+    };
+    let last = s[pos..].chars().next().unwrap_or('.');
+    let other = if last == '.' { ',' } else { '.' };
+    let digits_after = s[pos + 1..].chars().filter(char::is_ascii_digit).count();
+    let repeated = s.matches(last).count() > 1;
+    if s.contains(other) || (digits_after != 3 && !repeated) {
+        last
+    } else {
+        other
+    }
+}
+
+/// Split a currency symbol or ISO code off either end of `s`.
+fn strip_currency(s: &str) -> (Option<String>, &str) {
+    for (symbol, code) in CURRENCY_SYMBOLS {
+        if let Some(rest) = s.strip_prefix(symbol).or_else(|| s.strip_suffix(symbol)) {
+            return (Some(code.to_string()), rest.trim());
+        }
+    }
+    let is_code = |w: &str| w.len() == 3 && w.chars().all(|c| c.is_ascii_uppercase());
+    if let Some((code, rest)) = s.split_once(char::is_whitespace)
+        && is_code(code)
+    {
+        return (Some(code.to_string()), rest.trim());
+    }
+    if let Some((rest, code)) = s.rsplit_once(char::is_whitespace)
+        && is_code(code)
+    {
+        return (Some(code.to_string()), rest.trim());
+    }
+    (None, s)
+}
+
+fn validate_date_format(format: &str) -> Result<(), String> {
+    let mut chars = format.chars();
+    let mut has_date = false;
+    while let Some(c) = chars.next() {
+        if c != '%' {
+            continue;
+        }
+        match chars.next() {
+            Some('Y' | 'y' | 'm' | 'd' | 'b' | 'B') => has_date = true,
+            Some('H' | 'M' | 'S' | 'p' | '%') => {}
+            Some(other) => return Err(format!("unsupported date directive %{other}")),
+            None => return Err("date format ends with a lone %".to_string()),
+        }
+    }
+    if has_date {
+        Ok(())
+    } else {
+        Err(format!("date format '{format}' has no date fields"))
+    }
+}
+
+fn take_digits(s: &str, max: usize) -> Option<(u32, &str)> {
+    let len = s
+        .char_indices()
+        .take(max)
+        .take_while(|(_, c)| c.is_ascii_digit())
+        .count();
+    if len == 0 {
+        return None;
+    }
+    Some((s[..len].parse().ok()?, &s[len..]))
+}
+
+fn days_in_month(year: i32, month: u32) -> u32 {
+    match month {
+        4 | 6 | 9 | 11 => 30,
+        2 if (year % 4 == 0 && year % 100 != 0) || year % 400 == 0 => 29,
+        2 => 28,
+        _ => 31,
+    }
+}
+
+/// `text` read with strftime-style `format`, as `YYYY-MM-DD`, plus
+/// `THH:MM:SS` when the format has time fields. `None` on any mismatch.
+pub fn parse_date(text: &str, format: &str) -> Option<String> {
+    let (mut year, mut month, mut day) = (None, None, None);
+    let (mut hour, mut minute, mut second, mut pm) = (None, None, None, None);
+    let mut s = text;
+    let mut f = format.chars();
+    while let Some(c) = f.next() {
+        if c != '%' {
+            if c.is_whitespace() {
+                s = s.trim_start();
+            } else {
+                s = s.strip_prefix(c)?;
+            }
+            continue;
+        }
+        let directive = f.next()?;
+        let (value, rest) = match directive {
+            'Y' => take_digits(s, 4)?,
+            'y' => {
+                let (y, rest) = take_digits(s, 2)?;
+                // POSIX pivot: 69–99 → 1900s, 00–68 → 2000s.
+                (if y >= 69 { 1900 + y } else { 2000 + y }, rest)
+            }
+            'm' | 'd' | 'H' | 'M' | 'S' => take_digits(s, 2)?,
+            'b' | 'B' => {
+                let word_len = s
+                    .char_indices()
+                    .find(|(_, c)| !c.is_alphabetic())
+                    .map_or(s.len(), |(i, _)| i);
+                let word = s[..word_len].to_lowercase();
+                let index = MONTHS
+                    .iter()
+                    .position(|m| word.len() >= 3 && m.starts_with(word.trim_end_matches('.')))?;
+                (index as u32 + 1, &s[word_len..])
+            }
+            'p' => {
+                let upper = s.get(..2)?.to_ascii_uppercase();
+                pm = Some(match upper.as_str() {
+                    "AM" => false,
+                    "PM" => true,
+                    _ => return None,
+                });
+                s = &s[2..];
+                continue;
+            }
+            '%' => {
+                s = s.strip_prefix('%')?;
+                continue;
+            }
+            _ => return None,
+        };
+        match directive {
+            'Y' | 'y' => year = Some(value as i32),
+            'm' | 'b' | 'B' => month = Some(value),
+            'd' => day = Some(value),
+            'H' => hour = Some(value),
+            'M' => minute = Some(value),
+            _ => second = Some(value),
+        }
+        s = rest;
+    }
+    if !s.trim().is_empty() {
+        return None;
+    }
+    let (year, month, day) = (year?, month?, day.unwrap_or(1));
+    if !(1..=12).contains(&month) || day == 0 || day > days_in_month(year, month) {
+        return None;
+    }
+    let mut date = format!("{year:04}-{month:02}-{day:02}");
+    if hour.is_some() || minute.is_some() || second.is_some() {
+        let mut h = hour.unwrap_or(0);
+        if let Some(pm) = pm {
+            if !(1..=12).contains(&h) {
+                return None;
+            }
+            h = h % 12 + if pm { 12 } else { 0 };
+        }
+        let (m, sec) = (minute.unwrap_or(0), second.unwrap_or(0));
+        if h > 23 || m > 59 || sec > 60 {
+            return None;
+        }
+        date.push_str(&format!("T{h:02}:{m:02}:{sec:02}"));
+    }
+    Some(date)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_number_honours_locale_and_infers_without_one() {
+        let de = Some(',');
+        assert_eq!(parse_number("1.234,56", de), Some(json!(1234.56)));
+        assert_eq!(parse_number("1 234 567", Some(',')), Some(json!(1234567)));
+        assert_eq!(parse_number("1,234.5", Some('.')), Some(json!(1234.5)));
+        assert_eq!(parse_number("-12", None), Some(json!(-12)));
+        assert_eq!(parse_number("(1,000.00)", None), Some(json!(-1000.0)));
+
+        assert_eq!(parse_number("1.5", None), Some(json!(1.5)));
+        assert_eq!(parse_number("1,234", None), Some(json!(1234)));
+        assert_eq!(parse_number("1.234.567", None), Some(json!(1234567)));
+        assert_eq!(parse_number("1.234,56", None), Some(json!(1234.56)));
+
+        assert_eq!(parse_number("12 apples", None), None);
+        assert_eq!(parse_number("1,2,3", Some(',')), None);
+        assert_eq!(parse_number("", None), None);
+    }
+
+    #[test]
+    fn currency_reads_symbol_or_code_on_either_side() {
+        let de: ValueParser = "currency:de-DE".parse().unwrap();
+        assert_eq!(
+            de.parse("1.234,56 €"),
+            Some(json!({ "amount": 1234.56, "currency": "EUR" }))
+        );
+        let auto: ValueParser = "currency".parse().unwrap();
+        assert_eq!(
+            auto.parse("US$ 9.99"),
+            Some(json!({ "amount": 9.99, "currency": "USD" }))
+        );
+        assert_eq!(
+            auto.parse("1'250.00 CHF"),
+            Some(json!({ "amount": 1250.0, "currency": "CHF" }))
+        );
+        assert_eq!(
+            auto.parse("42"),
+            Some(json!({ "amount": 42, "currency": null }))
+        );
+        assert_eq!(auto.parse("free"), None);
+    }
+
+    #[test]
+    fn parse_date_follows_the_format() {
+        assert_eq!(
+            parse_date("01.03.2024", "%d.%m.%Y").as_deref(),
+            Some("2024-03-01")
+        );
+        assert_eq!(
+            parse_date("Mar 5, 99 3:07 PM", "%b %d, %y %H:%M %p").as_deref(),
+            Some("1999-03-05T15:07:00")
+        );
+        assert_eq!(
+            parse_date("29 February 2023", "%d %B %Y"),
+            None,
+            "not a leap year"
+        );
+        assert_eq!(parse_date("2024-03-01 extra", "%Y-%m-%d"), None);
+    }
+
+    #[test]
+    fn parse_rule_reads_column_type_and_argument() {
+        assert_eq!(
+            "Price=number:de-DE".parse::<ParseRule>(),
+            Ok(ParseRule {
+                column: Some("Price".into()),
+                parser: ValueParser::Number(Some(',')),
+            })
+        );
+        assert_eq!(
+            "date:%H:%M %d/%m/%Y".parse::<ParseRule>().unwrap().parser,
+            ValueParser::Date("%H:%M %d/%m/%Y".into())
+        );
+        assert_eq!(
+            "number:de-CH".parse::<ValueParser>(),
+            Ok(ValueParser::Number(Some('.')))
+        );
+        assert!("date".parse::<ParseRule>().is_err());
+        assert!("date:%H:%M".parse::<ParseRule>().is_err());
+        assert!("=number".parse::<ParseRule>().is_err());
+        assert!("money".parse::<ParseRule>().is_err());
+    }
+}
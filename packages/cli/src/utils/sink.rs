@@ -0,0 +1,673 @@
+//! Row sinks for extraction commands: write typed rows straight into SQLite,
+//! CSV or Parquet instead of returning them inline.
+//!
+//! A sink is `KIND:PATH[?key=value&…]`, where KIND is one of
+//! - `sqlite:out.db?table=items` → rows appended to `items` (default table
+//!   name); the table is created, or widened with columns it lacks
+//! - `csv:out.csv` → rows appended; the header is written when the file is new
+//! - `parquet:out.parquet` → the file is replaced with a single row group
+//!
+//! `mode=replace` drops an existing SQLite table or truncates a CSV first.
+//! Column types are inferred from the values: integer, real, boolean, else
+//! text; empty cells are NULL. Currency cells (`{amount, currency}` from
+//! `--parse currency`) become the amount column plus `<column>_currency`.
+
+use std::fmt;
+use std::str::FromStr;
+
+use serde_json::{Value, json};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SinkKind {
+    Sqlite,
+    Csv,
+    Parquet,
+}
+
+impl SinkKind {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Sqlite => "sqlite",
+            Self::Csv => "csv",
+            Self::Parquet => "parquet",
+        }
+    }
+
+    /// Whether this build can write the sink (`sink-*` cargo features).
+    pub fn available(self) -> bool {
+        match self {
+            Self::Sqlite => cfg!(feature = "sink-sqlite"),
+            Self::Csv => true,
+            Self::Parquet => cfg!(feature = "sink-parquet"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Sink {
+    pub kind: SinkKind,
+    pub path: String,
+    /// SQLite table; `None` means [`DEFAULT_TABLE`].
+    pub table: Option<String>,
+    pub replace: bool,
+}
+
+pub const DEFAULT_TABLE: &str = "items";
+
+impl FromStr for Sink {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, String> {
+        let (kind, rest) = s
+            .split_once(':')
+            .ok_or_else(|| format!("invalid sink '{s}': expected KIND:PATH, e.g. sqlite:out.db"))?;
+        let kind = match kind.to_ascii_lowercase().as_str() {
+            "sqlite" => SinkKind::Sqlite,
+            "csv" => SinkKind::Csv,
+            "parquet" => SinkKind::Parquet,
+            other => {
+                return Err(format!(
+                    "unknown sink '{other}': expected sqlite, csv or parquet"
+                ));
+            }
+        };
+        let (path, query) = match rest.split_once('?') {
+            Some((path, query)) => (path, query),
+            None => (rest, ""),
+        };
+        if path.is_empty() {
+            return Err(format!("invalid sink '{s}': missing file path"));
+        }
+        let mut sink = Sink {
+            kind,
+            path: path.to_string(),
+            table: None,
+            replace: false,
+        };
+        for pair in query.split('&').filter(|p| !p.is_empty()) {
+            let (key, value) = pair.split_once('=').unwrap_or((pair, ""));
+            match (key, kind) {
+                ("table", SinkKind::Sqlite) if !value.is_empty() => {
+                    sink.table = Some(value.to_string())
+                }
+                ("mode", SinkKind::Parquet) if value == "append" => {
+                    return Err("parquet sinks cannot append; omit mode".to_string());
+                }
+                ("mode", _) if value == "append" || value == "replace" => {
+                    sink.replace = value == "replace"
+                }
+                ("mode", _) => {
+                    return Err(format!(
+                        "invalid sink mode '{value}': expected append or replace"
+                    ));
+                }
+                _ => {
+                    return Err(format!(
+                        "unknown option '{key}' for a {} sink",
+                        kind.as_str()
+                    ));
+                }
+            }
+        }
+        Ok(sink)
+    }
+}
+
+impl fmt::Display for Sink {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}:{}", self.kind.as_str(), self.path)?;
+        let mut options = Vec::new();
+        if let Some(table) = &self.table {
+            options.push(format!("table={table}"));
+        }
+        if self.replace {
+            options.push("mode=replace".to_string());
+        }
+        if !options.is_empty() {
+            write!(f, "?{}", options.join("&"))?;
+        }
+        Ok(())
+    }
+}
+
+/// clap value parser for `--sink`: validates, keeps the raw string.
+pub fn parse_sink_arg(s: &str) -> Result<String, String> {
+    s.parse::<Sink>().map(|_| s.to_string())
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ColumnType {
+    Integer,
+    Real,
+    Boolean,
+    Text,
+}
+
+impl ColumnType {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Integer => "integer",
+            Self::Real => "real",
+            Self::Boolean => "boolean",
+            Self::Text => "text",
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Cell {
+    Null,
+    Integer(i64),
+    Real(f64),
+    Boolean(bool),
+    Text(String),
+}
+
+impl Cell {
+    fn text(&self) -> String {
+        match self {
+            Cell::Null => String::new(),
+            Cell::Integer(i) => i.to_string(),
+            Cell::Real(r) => r.to_string(),
+            Cell::Boolean(b) => b.to_string(),
+            Cell::Text(s) => s.clone(),
+        }
+    }
+}
+
+/// Rows with an inferred schema, ready for any sink.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Frame {
+    pub columns: Vec<(String, ColumnType)>,
+    pub rows: Vec<Vec<Cell>>,
+}
+
+fn is_null(v: &Value) -> bool {
+    v.is_null() || v.as_str().is_some_and(|s| s.is_empty())
+}
+
+fn infer_type(values: &[&Value]) -> ColumnType {
+    let present: Vec<&&Value> = values.iter().filter(|v| !is_null(v)).collect();
+    if present.is_empty() {
+        ColumnType::Text
+    } else if present.iter().all(|v| v.is_i64() || v.is_u64()) {
+        ColumnType::Integer
+    } else if present.iter().all(|v| v.is_number()) {
+        ColumnType::Real
+    } else if present.iter().all(|v| v.is_boolean()) {
+        ColumnType::Boolean
+    } else {
+        ColumnType::Text
+    }
+}
+
+fn to_cell(v: &Value, ty: ColumnType) -> Cell {
+    if is_null(v) {
+        return Cell::Null;
+    }
+    match (ty, v) {
+        (ColumnType::Integer, _) => v.as_i64().map_or(Cell::Null, Cell::Integer),
+        (ColumnType::Real, _) => v.as_f64().map_or(Cell::Null, Cell::Real),
+        (ColumnType::Boolean, _) => v.as_bool().map_or(Cell::Null, Cell::Boolean),
+        (ColumnType::Text, Value::String(s)) => Cell::Text(s.clone()),
+        (ColumnType::Text, Value::Object(o)) if o.contains_key("amount") => {
+            Cell::Text(o["amount"].to_string())
+        }
+        (ColumnType::Text, other) => Cell::Text(other.to_string()),
+    }
+}
+
+impl Frame {
+    /// Infer a schema for `rows` (one value per header, as produced by
+    /// `--parse`) and convert every cell to it.
+    pub fn infer(headers: &[String], rows: &[Vec<Value>]) -> Frame {
+        let null = Value::Null;
+        let mut columns: Vec<(String, Vec<&Value>)> = Vec::new();
+        for (c, header) in headers.iter().enumerate() {
+            let values: Vec<&Value> = rows.iter().map(|r| r.get(c).unwrap_or(&null)).collect();
+            let currency = values.iter().any(|v| v.get("amount").is_some());
+            if currency {
+                let amounts = values.iter().map(|v| v.get("amount").unwrap_or(v));
+                let codes = values.iter().map(|v| v.get("currency").unwrap_or(&null));
+                columns.push((header.clone(), amounts.collect()));
+                columns.push((format!("{header}_currency"), codes.collect()));
+            } else {
+                columns.push((header.clone(), values));
+            }
+        }
+        let types: Vec<ColumnType> = columns.iter().map(|(_, v)| infer_type(v)).collect();
+        let rows = (0..rows.len())
+            .map(|r| {
+                columns
+                    .iter()
+                    .zip(&types)
+                    .map(|((_, values), ty)| to_cell(values[r], *ty))
+                    .collect()
+            })
+            .collect();
+        Frame {
+            columns: columns
+                .into_iter()
+                .map(|(name, _)| name)
+                .zip(types)
+                .collect(),
+            rows,
+        }
+    }
+
+    fn names(&self) -> Vec<String> {
+        self.columns.iter().map(|(n, _)| n.clone()).collect()
+    }
+}
+
+/// RFC 4180 field: quoted when it holds a comma, quote or line break.
+pub fn csv_field(s: &str) -> String {
+    if s.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", s.replace('"', "\"\""))
+    } else {
+        s.to_string()
+    }
+}
+
+fn csv_line(row: &[String]) -> String {
+    row.iter()
+        .map(|c| csv_field(c))
+        .collect::<Vec<_>>()
+        .join(",")
+        + "\r\n"
+}
+
+pub fn to_csv(headers: &[String], rows: &[Vec<String>]) -> String {
+    std::iter::once(headers)
+        .chain(rows.iter().map(Vec::as_slice))
+        .map(csv_line)
+        .collect()
+}
+
+/// Write `frame` to `sink`. Returns the summary reported under `data.sink`.
+pub fn write(sink: &Sink, frame: &Frame) -> Result<Value, String> {
+    match sink.kind {
+        SinkKind::Csv => write_csv(sink, frame)?,
+        SinkKind::Sqlite => write_sqlite(sink, frame)?,
+        SinkKind::Parquet => write_parquet(sink, frame)?,
+    }
+    let columns: Vec<Value> = frame
+        .columns
+        .iter()
+        .map(|(name, ty)| json!({ "name": name, "type": ty.as_str() }))
+        .collect();
+    let mut report = json!({
+        "kind": sink.kind.as_str(),
+        "path": sink.path,
+        "rows_written": frame.rows.len(),
+        "columns": columns,
+    });
+    if sink.kind == SinkKind::Sqlite {
+        report["table"] = json!(sink.table.as_deref().unwrap_or(DEFAULT_TABLE));
+    }
+    Ok(report)
+}
+
+fn write_csv(sink: &Sink, frame: &Frame) -> Result<(), String> {
+    use std::io::Write;
+
+    let header = frame.names();
+    let existing = if sink.replace {
+        None
+    } else {
+        std::fs::read_to_string(&sink.path)
+            .ok()
+            .filter(|s| !s.is_empty())
+    };
+    if let Some(existing) = &existing {
+        let first = existing.lines().next().unwrap_or("");
+        if first != csv_line(&header).trim_end() {
+            return Err(format!(
+                "{} already has columns {first}; this table has {}",
+                sink.path,
+                header.join(",")
+            ));
+        }
+    }
+    let mut out = String::new();
+    if existing.is_none() {
+        out.push_str(&csv_line(&header));
+    }
+    for row in &frame.rows {
+        out.push_str(&csv_line(&row.iter().map(Cell::text).collect::<Vec<_>>()));
+    }
+    let mut file = std::fs::OpenOptions::new()
+        .create(true)
+        .write(true)
+        .append(existing.is_some())
+        .truncate(existing.is_none())
+        .open(&sink.path)
+        .map_err(|e| format!("failed to open {}: {e}", sink.path))?;
+    file.write_all(out.as_bytes())
+        .map_err(|e| format!("failed to write {}: {e}", sink.path))
+}
+
+#[cfg(feature = "sink-sqlite")]
+fn write_sqlite(sink: &Sink, frame: &Frame) -> Result<(), String> {
+    use rusqlite::types::Value as SqlValue;
+
+    let quote = |ident: &str| format!("\"{}\"", ident.replace('"', "\"\""));
+    let sql_type = |ty: ColumnType| match ty {
+        ColumnType::Integer => "INTEGER",
+        ColumnType::Real => "REAL",
+        ColumnType::Boolean => "BOOLEAN",
+        ColumnType::Text => "TEXT",
+    };
+    let err = |e: rusqlite::Error| format!("{}: {e}", sink.path);
+    let table = quote(sink.table.as_deref().unwrap_or(DEFAULT_TABLE));
+
+    let mut conn = rusqlite::Connection::open(&sink.path).map_err(err)?;
+    let tx = conn.transaction().map_err(err)?;
+    if sink.replace {
+        tx.execute(&format!("DROP TABLE IF EXISTS {table}"), [])
+            .map_err(err)?;
+    }
+    let defs: Vec<String> = frame
+        .columns
+        .iter()
+        .map(|(name, ty)| format!("{} {}", quote(name), sql_type(*ty)))
+        .collect();
+    tx.execute(
+        &format!("CREATE TABLE IF NOT EXISTS {table} ({})", defs.join(", ")),
+        [],
+    )
+    .map_err(err)?;
+    let existing: Vec<String> = {
+        let mut stmt = tx
+            .prepare(&format!("PRAGMA table_info({table})"))
+            .map_err(err)?;
+        stmt.query_map([], |row| row.get::<_, String>(1))
+            .map_err(err)?
+            .collect::<Result<_, _>>()
+            .map_err(err)?
+    };
+    for (name, ty) in &frame.columns {
+        if !existing.contains(name) {
+            tx.execute(
+                &format!(
+                    "ALTER TABLE {table} ADD COLUMN {} {}",
+                    quote(name),
+                    sql_type(*ty)
+                ),
+                [],
+            )
+            .map_err(err)?;
+        }
+    }
+    {
+        let names: Vec<String> = frame.names().iter().map(|n| quote(n)).collect();
+        let placeholders: Vec<String> = (1..=names.len()).map(|i| format!("?{i}")).collect();
+        let mut stmt = tx
+            .prepare(&format!(
+                "INSERT INTO {table} ({}) VALUES ({})",
+                names.join(", "),
+                placeholders.join(", ")
+            ))
+            .map_err(err)?;
+        for row in &frame.rows {
+            let values = row.iter().map(|c| match c {
+                Cell::Null => SqlValue::Null,
+                Cell::Integer(i) => SqlValue::Integer(*i),
+                Cell::Real(r) => SqlValue::Real(*r),
+                Cell::Boolean(b) => SqlValue::Integer(i64::from(*b)),
+                Cell::Text(s) => SqlValue::Text(s.clone()),
+            });
+            stmt.execute(rusqlite::params_from_iter(values))
+                .map_err(err)?;
+        }
+    }
+    tx.commit().map_err(err)
+}
+
+#[cfg(not(feature = "sink-sqlite"))]
+fn write_sqlite(_sink: &Sink, _frame: &Frame) -> Result<(), String> {
+    Err("this build cannot write SQLite; rebuild actionbook with `--features sink-sqlite`".into())
+}
+
+#[cfg(feature = "sink-parquet")]
+fn write_parquet(sink: &Sink, frame: &Frame) -> Result<(), String> {
+    use std::sync::Arc;
+
+    use parquet::basic::{LogicalType, Repetition, Type as PhysicalType};
+    use parquet::column::writer::ColumnWriter;
+    use parquet::data_type::ByteArray;
+    use parquet::file::writer::SerializedFileWriter;
+    use parquet::schema::types::Type;
+
+    let err = |e: parquet::errors::ParquetError| format!("{}: {e}", sink.path);
+    let fields = frame
+        .columns
+        .iter()
+        .map(|(name, ty)| {
+            let (physical, logical) = match ty {
+                ColumnType::Integer => (PhysicalType::INT64, None),
+                ColumnType::Real => (PhysicalType::DOUBLE, None),
+                ColumnType::Boolean => (PhysicalType::BOOLEAN, None),
+                ColumnType::Text => (PhysicalType::BYTE_ARRAY, Some(LogicalType::String)),
+            };
+            Type::primitive_type_builder(name, physical)
+                .with_repetition(Repetition::OPTIONAL)
+                .with_logical_type(logical)
+                .build()
+                .map(Arc::new)
+        })
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(err)?;
+    let schema = Type::group_type_builder("schema")
+        .with_fields(fields)
+        .build()
+        .map_err(err)?;
+
+    let file = std::fs::File::create(&sink.path)
+        .map_err(|e| format!("failed to create {}: {e}", sink.path))?;
+    let mut writer =
+        SerializedFileWriter::new(file, Arc::new(schema), Default::default()).map_err(err)?;
+    let mut row_group = writer.next_row_group().map_err(err)?;
+    let mut c = 0;
+    while let Some(mut column) = row_group.next_column().map_err(err)? {
+        let cells: Vec<&Cell> = frame.rows.iter().map(|r| &r[c]).collect();
+        let defs: Vec<i16> = cells.iter().map(|v| i16::from(**v != Cell::Null)).collect();
+        match column.untyped() {
+            ColumnWriter::Int64ColumnWriter(w) => {
+                let v: Vec<i64> = cells
+                    .iter()
+                    .filter_map(|c| match c {
+                        Cell::Integer(i) => Some(*i),
+                        _ => None,
+                    })
+                    .collect();
+                w.write_batch(&v, Some(&defs), None).map_err(err)?;
+            }
+            ColumnWriter::DoubleColumnWriter(w) => {
+                let v: Vec<f64> = cells
+                    .iter()
+                    .filter_map(|c| match c {
+                        Cell::Real(r) => Some(*r),
+                        _ => None,
+                    })
+                    .collect();
+                w.write_batch(&v, Some(&defs), None).map_err(err)?;
+            }
+            ColumnWriter::BoolColumnWriter(w) => {
+                let v: Vec<bool> = cells
+                    .iter()
+                    .filter_map(|c| match c {
+                        Cell::Boolean(b) => Some(*b),
+                        _ => None,
+                    })
+                    .collect();
+                w.write_batch(&v, Some(&defs), None).map_err(err)?;
+            }
+            ColumnWriter::ByteArrayColumnWriter(w) => {
+                let v: Vec<ByteArray> = cells
+                    .iter()
+                    .filter_map(|c| match c {
+                        Cell::Text(s) => Some(ByteArray::from(s.as_str())),
+                        _ => None,
+                    })
+                    .collect();
+                w.write_batch(&v, Some(&defs), None).map_err(err)?;
+            }
+            _ => unreachable!("sink schemas only use INT64, DOUBLE, BOOLEAN and BYTE_ARRAY"),
+        }
+        column.close().map_err(err)?;
+        c += 1;
+    }
+    row_group.close().map_err(err)?;
+    writer.close().map_err(err)?;
+    Ok(())
+}
+
+#[cfg(not(feature = "sink-parquet"))]
+fn write_parquet(_sink: &Sink, _frame: &Frame) -> Result<(), String> {
+    Err("this build cannot write Parquet; rebuild actionbook with `--features sink-parquet`".into())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample() -> Frame {
+        let headers = vec!["Item".to_string(), "Price".to_string(), "Qty".to_string()];
+        Frame::infer(
+            &headers,
+            &[
+                vec![
+                    json!("Tea"),
+                    json!({ "amount": 4.5, "currency": "EUR" }),
+                    json!(3),
+                ],
+                vec![json!("Cup"), json!("n/a"), json!("")],
+            ],
+        )
+    }
+
+    #[test]
+    fn sink_spec_round_trips() {
+        let sink: Sink = "sqlite:out.db?table=products&mode=replace".parse().unwrap();
+        assert_eq!(sink.kind, SinkKind::Sqlite);
+        assert_eq!(sink.path, "out.db");
+        assert_eq!(sink.table.as_deref(), Some("products"));
+        assert!(sink.replace);
+        assert_eq!(
+            sink.to_string(),
+            "sqlite:out.db?table=products&mode=replace"
+        );
+
+        assert!("parquet:out.parquet?mode=append".parse::<Sink>().is_err());
+        assert!("csv:out.csv?table=x".parse::<Sink>().is_err());
+        assert!("xlsx:out.xlsx".parse::<Sink>().is_err());
+        assert!("out.db".parse::<Sink>().is_err());
+    }
+
+    #[test]
+    fn infer_splits_currency_and_keeps_failures_as_text() {
+        let frame = sample();
+        assert_eq!(
+            frame.columns,
+            [
+                ("Item".to_string(), ColumnType::Text),
+                ("Price".to_string(), ColumnType::Text),
+                ("Price_currency".to_string(), ColumnType::Text),
+                ("Qty".to_string(), ColumnType::Integer),
+            ]
+        );
+        assert_eq!(frame.rows[0][1], Cell::Text("4.5".to_string()));
+        assert_eq!(frame.rows[1][1], Cell::Text("n/a".to_string()));
+        assert_eq!(frame.rows[1][2], Cell::Null);
+        assert_eq!(frame.rows[1][3], Cell::Null);
+
+        let frame = Frame::infer(&["n".to_string()], &[vec![json!(1)], vec![json!(2.5)]]);
+        assert_eq!(frame.columns[0].1, ColumnType::Real);
+        assert_eq!(frame.rows[0][0], Cell::Real(1.0));
+    }
+
+    #[test]
+    fn csv_sink_appends_under_a_single_header() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("rows.csv");
+        let sink: Sink = format!("csv:{}", path.display()).parse().unwrap();
+        write(&sink, &sample()).unwrap();
+        write(&sink, &sample()).unwrap();
+        let text = std::fs::read_to_string(&path).unwrap();
+        assert_eq!(text.matches("Item,Price,Price_currency,Qty").count(), 1);
+        assert_eq!(text.lines().count(), 5);
+
+        let other = Frame::infer(&["Other".to_string()], &[vec![json!("x")]]);
+        assert!(write(&sink, &other).is_err());
+    }
+
+    #[cfg(not(feature = "sink-sqlite"))]
+    #[test]
+    fn sqlite_sink_is_refused_without_the_feature() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("rows.db");
+        let sink: Sink = format!("sqlite:{}", path.display()).parse().unwrap();
+        assert!(!sink.kind.available());
+        let err = write(&sink, &sample()).unwrap_err();
+        assert!(err.contains("--features sink-sqlite"), "{err}");
+        assert!(!path.exists());
+    }
+
+    #[cfg(feature = "sink-sqlite")]
+    #[test]
+    fn sqlite_sink_creates_and_widens_the_table() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("rows.db");
+        let sink: Sink = format!("sqlite:{}?table=items", path.display())
+            .parse()
+            .unwrap();
+        let report = write(&sink, &sample()).unwrap();
+        assert_eq!(report["rows_written"], 2);
+        assert_eq!(report["table"], "items");
+
+        let wider = Frame::infer(
+            &["Item".to_string(), "Rating".to_string()],
+            &[vec![json!("Pot"), json!(4.8)]],
+        );
+        write(&sink, &wider).unwrap();
+
+        let conn = rusqlite::Connection::open(&path).unwrap();
+        let count: i64 = conn
+            .query_row("SELECT COUNT(*) FROM items", [], |r| r.get(0))
+            .unwrap();
+        assert_eq!(count, 3);
+        let qty: i64 = conn
+            .query_row("SELECT Qty FROM items WHERE Item = 'Tea'", [], |r| r.get(0))
+            .unwrap();
+        assert_eq!(qty, 3);
+        let rating: f64 = conn
+            .query_row("SELECT Rating FROM items WHERE Item = 'Pot'", [], |r| {
+                r.get(0)
+            })
+            .unwrap();
+        assert_eq!(rating, 4.8);
+    }
+
+    #[cfg(feature = "sink-parquet")]
+    #[test]
+    fn parquet_sink_writes_typed_optional_columns() {
+        use parquet::file::reader::{FileReader, SerializedFileReader};
+
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("rows.parquet");
+        let sink: Sink = format!("parquet:{}", path.display()).parse().unwrap();
+        write(&sink, &sample()).unwrap();
+
+        let reader = SerializedFileReader::new(std::fs::File::open(&path).unwrap()).unwrap();
+        let meta = reader.metadata();
+        assert_eq!(meta.file_metadata().num_rows(), 2);
+        let schema = meta.file_metadata().schema_descr();
+        assert_eq!(schema.num_columns(), 4);
+        assert_eq!(schema.column(3).name(), "Qty");
+        assert_eq!(
+            schema.column(3).physical_type(),
+            parquet::basic::Type::INT64
+        );
+    }
+}
@@ -0,0 +1,50 @@
+//! Wall-clock formatting helpers (no chrono dependency).
+
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// Current wall-clock time as RFC 3339 UTC with millisecond precision.
+pub fn now_rfc3339() -> String {
+    let now = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default();
+    unix_secs_to_rfc3339(now.as_secs(), u64::from(now.subsec_millis()))
+}
+
+/// Format a Unix timestamp as ISO 8601 basic UTC, safe in file names and
+/// sortable. Output: `YYYYMMDDTHHMMSSZ`
+pub fn unix_secs_to_compact(secs: u64) -> String {
+    let iso = unix_secs_to_rfc3339(secs, 0);
+    let digits: String = iso[..19]
+        .chars()
+        .filter(|c| *c != '-' && *c != ':')
+        .collect();
+    format!("{digits}Z")
+}
+
+/// Format a Unix timestamp (seconds + milliseconds) as RFC 3339 / ISO 8601 UTC.
+/// Output: `YYYY-MM-DDTHH:MM:SS.mmmZ`
+pub fn unix_secs_to_rfc3339(secs: u64, millis: u64) -> String {
+    // Days since Unix epoch → calendar date (Gregorian proleptic)
+    let days = secs / 86400;
+    let time_of_day = secs % 86400;
+    let h = time_of_day / 3600;
+    let m = (time_of_day % 3600) / 60;
+    let s = time_of_day % 60;
+
+    // Algorithm from http://howardhinnant.github.io/date_algorithms.html
+    let z = days as i64 + 719468;
+    let era = if z >= 0 { z } else { z - 146096 } / 146097;
+    let doe = z - era * 146097;
+    let yoe = (doe - doe / 1460 + doe / 36524 - doe / 146096) / 365;
+    let y = yoe + era * 400;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let d = doy - (153 * mp + 2) / 5 + 1;
+    let mo = if mp < 10 { mp + 3 } else { mp - 9 };
+    let y = if mo <= 2 { y + 1 } else { y };
+
+    format!(
+        "{:04}-{:02}-{:02}T{:02}:{:02}:{:02}.{:03}Z",
+        y, mo, d, h, m, s, millis
+    )
+}
@@ -0,0 +1,87 @@
+//! URL canonicalization for extracted data (no url-crate dependency).
+
+/// Query parameters that only track the click, never select content.
+const TRACKING_PARAMS: &[&str] = &[
+    "dclid", "fbclid", "gclid", "gbraid", "wbraid", "msclkid", "yclid", "igshid", "mc_cid",
+    "mc_eid", "_ga", "_gl", "_hsenc", "_hsmi", "mkt_tok", "ref_src", "spm",
+];
+
+fn is_tracking_param(key: &str) -> bool {
+    let key = key.to_ascii_lowercase();
+    key.starts_with("utm_") || TRACKING_PARAMS.contains(&key.as_str())
+}
+
+/// Canonical form of an absolute http(s) URL: lowercase scheme and host, no
+/// default port, `/` for an empty path, no fragment and no tracking query
+/// parameters (`utm_*`, `gclid`, `fbclid`, …). Other parameters keep their
+/// order and encoding. `None` when `s` is not an http(s) URL.
+pub fn normalize_url(s: &str) -> Option<String> {
+    let s = s.trim();
+    let (scheme, rest) = s.split_once("://")?;
+    let scheme = scheme.to_ascii_lowercase();
+    if scheme != "http" && scheme != "https" {
+        return None;
+    }
+    let rest = rest.split('#').next().unwrap_or("");
+    let authority_end = rest.find(['/', '?']).unwrap_or(rest.len());
+    let (authority, tail) = rest.split_at(authority_end);
+    let (userinfo, host_port) = match authority.rsplit_once('@') {
+        Some((user, host)) => (Some(user), host),
+        None => (None, authority),
+    };
+    if host_port.is_empty() || host_port.contains(char::is_whitespace) {
+        return None;
+    }
+    let mut host = host_port.to_ascii_lowercase();
+    let default_port = if scheme == "http" { ":80" } else { ":443" };
+    if let Some(stripped) = host.strip_suffix(default_port) {
+        host = stripped.to_string();
+    }
+    let (path, query) = match tail.split_once('?') {
+        Some((path, query)) => (path, Some(query)),
+        None => (tail, None),
+    };
+
+    let mut out = format!("{scheme}://");
+    if let Some(user) = userinfo {
+        out.push_str(user);
+        out.push('@');
+    }
+    out.push_str(&host);
+    out.push_str(if path.is_empty() { "/" } else { path });
+    let kept: Vec<&str> = query
+        .into_iter()
+        .flat_map(|q| q.split('&'))
+        .filter(|pair| !pair.is_empty())
+        .filter(|pair| !is_tracking_param(pair.split('=').next().unwrap_or("")))
+        .collect();
+    if !kept.is_empty() {
+        out.push('?');
+        out.push_str(&kept.join("&"));
+    }
+    Some(out)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn normalize_url_strips_tracking_and_canonicalizes() {
+        assert_eq!(
+            normalize_url("HTTPS://Shop.Example.com:443?utm_source=x&id=7&gclid=abc#reviews")
+                .as_deref(),
+            Some("https://shop.example.com/?id=7")
+        );
+        assert_eq!(
+            normalize_url("http://example.com:8080/a/B?q=1&UTM_medium=mail").as_deref(),
+            Some("http://example.com:8080/a/B?q=1")
+        );
+        assert_eq!(
+            normalize_url("https://example.com/p?fbclid=1").as_deref(),
+            Some("https://example.com/p")
+        );
+        assert_eq!(normalize_url("mailto:a@example.com"), None);
+        assert_eq!(normalize_url("not a url"), None);
+    }
+}
@@ -12,6 +12,9 @@ pub struct Request {
     pub v: u32,
     pub id: u64,
     pub action: Action,
+    /// Ask for progress [`Event`]s while the action runs (`--json --stream`).
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub stream: bool,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -20,6 +23,22 @@ pub struct Response {
     pub result: ActionResult,
 }
 
+/// Intermediate event for a streaming request; zero or more precede its
+/// [`Response`].
+#[derive(Debug, Serialize, Deserialize)]
+pub struct Event {
+    pub id: u64,
+    pub event: serde_json::Value,
+}
+
+/// Any frame the daemon sends to a client.
+#[derive(Debug, Deserialize)]
+#[serde(untagged)]
+pub enum Frame {
+    Response(Response),
+    Event(Event),
+}
+
 /// Encode a frame: 4-byte LE length prefix + JSON payload.
 pub fn encode_frame(payload: &[u8]) -> Vec<u8> {
     let len = payload.len() as u32;
@@ -57,11 +76,12 @@ pub async fn read_frame<R: AsyncReadExt + Unpin>(reader: &mut R) -> std::io::Res
     Ok(payload)
 }
 
-pub fn serialize_request(id: u64, action: &Action) -> serde_json::Result<Vec<u8>> {
+pub fn serialize_request(id: u64, action: &Action, stream: bool) -> serde_json::Result<Vec<u8>> {
     let req = Request {
         v: PROTOCOL_VERSION,
         id,
         action: action.clone(),
+        stream,
     };
     serde_json::to_vec(&req)
 }
@@ -81,3 +101,10 @@ pub fn serialize_response(id: u64, result: &ActionResult) -> serde_json::Result<
     };
     serde_json::to_vec(&resp)
 }
+
+pub fn serialize_event(id: u64, event: &serde_json::Value) -> serde_json::Result<Vec<u8>> {
+    serde_json::to_vec(&Event {
+        id,
+        event: event.clone(),
+    })
+}
@@ -0,0 +1,125 @@
+//! Integration tests for `actionbook auth`.
+//!
+//! Each test gets its own `ACTIONBOOK_HOME` and the file store, so nothing
+//! touches the OS keyring.
+
+use assert_cmd::Command;
+use serde_json::Value;
+
+fn auth(home: &std::path::Path, args: &[&str], env: &[(&str, &str)]) -> std::process::Output {
+    let mut cmd = Command::cargo_bin("actionbook").expect("binary exists");
+    cmd.env("ACTIONBOOK_HOME", home)
+        .env("ACTIONBOOK_CREDENTIAL_STORE", "file")
+        .arg("--json")
+        .arg("auth")
+        .args(args);
+    for (k, v) in env {
+        cmd.env(k, v);
+    }
+    cmd.output().expect("run actionbook auth")
+}
+
+fn json(output: &std::process::Output) -> Value {
+    serde_json::from_slice(&output.stdout).unwrap_or_else(|e| {
+        panic!(
+            "{e}\nstdout:\n{}\nstderr:\n{}",
+            String::from_utf8_lossy(&output.stdout),
+            String::from_utf8_lossy(&output.stderr)
+        )
+    })
+}
+
+#[test]
+fn auth_add_list_totp_and_remove() {
+    let home = tempfile::tempdir().unwrap();
+    let out = auth(
+        home.path(),
+        &[
+            "add",
+            "https://Example.com/login",
+            "--user",
+            "alice",
+            "--pass-env",
+            "EX_PASS",
+            "--totp-secret",
+            "GEZDGNBVGY3TQOJQGEZDGNBVGY3TQOJQ",
+        ],
+        &[("EX_PASS", "hunter2")],
+    );
+    let v = json(&out);
+    assert_eq!(v["ok"], true, "{v}");
+    assert_eq!(v["data"]["domain"], "example.com");
+    assert_eq!(v["data"]["store"], "file");
+    assert!(!String::from_utf8_lossy(&out.stdout).contains("hunter2"));
+
+    // Only the secrets file holds the password.
+    let index = std::fs::read_to_string(home.path().join("auth/credentials.json")).unwrap();
+    assert!(!index.contains("hunter2"), "{index}");
+    assert!(
+        std::fs::read_to_string(home.path().join("auth/secrets.json"))
+            .unwrap()
+            .contains("hunter2")
+    );
+
+    let v = json(&auth(home.path(), &["list"], &[]));
+    assert_eq!(v["data"]["credentials"][0]["user"], "alice");
+    assert_eq!(v["data"]["credentials"][0]["totp"], true);
+
+    let v = json(&auth(home.path(), &["totp", "app.example.com"], &[]));
+    let code = v["data"]["code"].as_str().unwrap();
+    assert!(
+        code.len() == 6 && code.chars().all(|c| c.is_ascii_digit()),
+        "{v}"
+    );
+
+    let v = json(&auth(home.path(), &["remove", "example.com"], &[]));
+    assert_eq!(v["data"]["removed"], true);
+    assert_eq!(
+        std::fs::read_to_string(home.path().join("auth/secrets.json"))
+            .unwrap()
+            .trim(),
+        "{}"
+    );
+    let v = json(&auth(home.path(), &["totp", "example.com"], &[]));
+    assert_eq!(v["error"]["code"], "CREDENTIALS_NOT_FOUND");
+}
+
+#[test]
+fn auth_add_needs_the_password_variable_set() {
+    let home = tempfile::tempdir().unwrap();
+    let out = auth(
+        home.path(),
+        &[
+            "add",
+            "example.com",
+            "--user",
+            "alice",
+            "--pass-env",
+            "UNSET_PASS_VAR",
+        ],
+        &[],
+    );
+    assert!(!out.status.success());
+    assert_eq!(json(&out)["error"]["code"], "INVALID_ARGUMENT");
+    assert!(!home.path().join("auth/credentials.json").exists());
+}
+
+#[test]
+fn auth_add_refuses_a_password_with_a_newline() {
+    let home = tempfile::tempdir().unwrap();
+    let out = auth(
+        home.path(),
+        &[
+            "add",
+            "example.com",
+            "--user",
+            "alice",
+            "--pass-env",
+            "TEST_PASS",
+        ],
+        &[("TEST_PASS", "hunter2\ndelete-keychain")],
+    );
+    assert!(!out.status.success());
+    assert_eq!(json(&out)["error"]["code"], "INVALID_ARGUMENT");
+    assert!(!home.path().join("auth/credentials.json").exists());
+}
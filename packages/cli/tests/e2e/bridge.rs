@@ -1,5 +1,5 @@
 //! E2E tests for extension bridge lazy startup, port-holder diagnosis, and
-//! the `actionbook daemon restart` recovery path.
+//! the `actionbook daemon restart` recovery path and `daemon stop`.
 //!
 //! Bridge port 19222 is process-global and not isolatable per-test. These
 //! tests therefore:
@@ -437,3 +437,76 @@ fn daemon_restart_recovers_bridge_after_failed() {
         "after daemon restart, 19222 must be held by NEW daemon pid {new_pid} — got holder={holder:?}"
     );
 }
+
+// ===========================================================================
+// 7. daemon_stop_shuts_down_gracefully
+//    `actionbook daemon stop` exits the daemon and removes its state files;
+//    a second stop reports that nothing is running.
+// ===========================================================================
+
+#[test]
+fn daemon_stop_shuts_down_gracefully() {
+    if skip() {
+        return;
+    }
+    let _g = BRIDGE_PORT_LOCK.lock().unwrap();
+    let env = SoloEnv::new();
+
+    let out = env.headless_json(&["browser", "list-sessions"], 10);
+    assert!(out.status.success(), "warm-up list-sessions failed");
+    let pid = wait_for_daemon_up(&env, Duration::from_secs(5)).expect("daemon up");
+
+    let out = env.headless_json(&["daemon", "stop"], 15);
+    assert!(
+        out.status.success(),
+        "daemon stop failed: stdout={} stderr={}",
+        stdout_str(&out),
+        stderr_str(&out)
+    );
+    assert_eq!(parse_json(&out)["data"]["status"], "stopped");
+    assert!(!pid_alive(pid), "daemon pid {pid} still alive after stop");
+    let home = std::path::Path::new(&env.actionbook_home);
+    assert!(
+        !home.join("daemon.ready").exists(),
+        "graceful stop must remove daemon.ready"
+    );
+
+    let out = env.headless_json(&["daemon", "stop"], 10);
+    assert!(out.status.success(), "second daemon stop failed");
+    assert_eq!(parse_json(&out)["data"]["status"], "not_running");
+}
+
+#[test]
+fn daemon_start_and_status_report_the_running_daemon() {
+    if skip() {
+        return;
+    }
+    let _g = BRIDGE_PORT_LOCK.lock().unwrap();
+    let env = SoloEnv::new();
+
+    let out = env.headless_json(&["daemon", "status"], 10);
+    assert!(out.status.success(), "daemon status failed");
+    assert_eq!(parse_json(&out)["data"]["running"], false);
+
+    let out = env.headless_json(&["daemon", "start"], 15);
+    assert!(
+        out.status.success(),
+        "daemon start failed: stdout={} stderr={}",
+        stdout_str(&out),
+        stderr_str(&out)
+    );
+    assert_eq!(parse_json(&out)["data"]["status"], "started");
+    let pid = wait_for_daemon_up(&env, Duration::from_secs(5)).expect("daemon up");
+
+    let out = env.headless_json(&["daemon", "start"], 10);
+    assert_eq!(parse_json(&out)["data"]["status"], "already_running");
+
+    let out = env.headless_json(&["daemon", "status"], 10);
+    let data = &parse_json(&out)["data"];
+    assert_eq!(data["running"], true);
+    assert_eq!(data["pid"], pid);
+    assert_eq!(data["sessions"], 0);
+
+    let out = env.headless_json(&["daemon", "stop"], 15);
+    assert_eq!(parse_json(&out)["data"]["status"], "stopped");
+}
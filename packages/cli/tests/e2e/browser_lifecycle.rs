@@ -143,6 +143,73 @@ fn lifecycle_open_headless_json() {
     );
 }
 
+#[test]
+fn lifecycle_headless_mode_is_verified_and_shown_in_status() {
+    if skip() {
+        return;
+    }
+    let env = SoloEnv::new();
+
+    let out = env.headless_json(
+        &[
+            "browser",
+            "start",
+            "--mode",
+            "local",
+            "--headless-mode",
+            "new",
+        ],
+        30,
+    );
+    assert_success(&out, "start headless-mode new");
+    let v = parse_json(&out);
+    assert_eq!(v["data"]["session"]["headless"], true);
+    assert_eq!(v["data"]["session"]["headless_mode"], "new");
+
+    let out = env.headless_json(
+        &["browser", "status", "--session", DEFAULT_LOCAL_SESSION_ID],
+        30,
+    );
+    assert_success(&out, "status");
+    let v = parse_json(&out);
+    assert_eq!(v["data"]["session"]["headless_mode"], "new");
+
+    env.headless(
+        &["browser", "close", "--session", DEFAULT_LOCAL_SESSION_ID],
+        30,
+    );
+}
+
+#[test]
+fn lifecycle_gpu_swiftshader_gives_headless_pages_webgl() {
+    if skip() {
+        return;
+    }
+    let env = SoloEnv::new();
+
+    let out = env.headless_json(
+        &[
+            "browser",
+            "start",
+            "--mode",
+            "local",
+            "--headless",
+            "--gpu",
+            "swiftshader",
+        ],
+        30,
+    );
+    assert_success(&out, "start gpu swiftshader");
+    let v = parse_json(&out);
+    assert_eq!(v["data"]["session"]["gpu"], "swiftshader");
+    assert_eq!(v["data"]["session"]["webgl"], true);
+
+    env.headless(
+        &["browser", "close", "--session", DEFAULT_LOCAL_SESSION_ID],
+        30,
+    );
+}
+
 // ===========================================================================
 // 4. lifecycle_open_with_url — isolated
 // ===========================================================================
@@ -238,6 +305,16 @@ fn lifecycle_status_json() {
     assert!(caps["snapshot"].is_boolean());
     assert!(caps["pdf"].is_boolean());
     assert!(caps["upload"].is_boolean());
+    let health = &v["data"]["health"];
+    assert_eq!(health["backend"], "local");
+    assert_eq!(health["cdp"]["connected"], true);
+    assert_eq!(health["cdp"]["responsive"], true);
+    assert!(health["cdp"]["latency_ms"].is_number());
+    assert!(health["age_secs"].is_number());
+    assert!(health["idle_secs"].is_number());
+    assert_tab_id(&health["active_page"]["tab_id"]);
+    assert!(health["active_page"]["url"].is_string());
+    assert!(health["chrome"]["pid"].as_u64().is_some_and(|pid| pid > 0));
     assert_eq!(v["context"]["session_id"], sid);
     assert!(v["meta"]["duration_ms"].is_number());
 }
@@ -257,6 +334,7 @@ fn lifecycle_status_text() {
     assert!(text.contains("status: running"));
     assert!(text.contains("mode: local"));
     assert!(text.contains("tabs:"));
+    assert!(text.contains("cdp: responsive"));
 }
 
 // ===========================================================================
@@ -2120,3 +2198,50 @@ fn kill_chrome_for_dir(profiles_dir: &std::path::Path) {
         let _ = profiles_dir; // suppress unused warning
     }
 }
+
+#[test]
+fn start_rejects_proxy_options_it_cannot_apply() {
+    if skip() {
+        return;
+    }
+    let env = SoloEnv::new();
+
+    // An attached browser already has its proxy settings.
+    let out = env.headless_json(
+        &[
+            "browser",
+            "start",
+            "--mode",
+            "local",
+            "--cdp-endpoint",
+            "ws://127.0.0.1:9/devtools/browser/x",
+            "--proxy",
+            "http://127.0.0.1:3128",
+        ],
+        15,
+    );
+    assert_failure(&out, "proxy with cdp endpoint");
+    assert_eq!(parse_json(&out)["error"]["code"], "INVALID_ARGUMENT");
+
+    let out = env.headless_json(
+        &["browser", "start", "--proxy", "socks5://127.0.0.1:1080"],
+        15,
+    );
+    assert_failure(&out, "socks proxy");
+    let v = parse_json(&out);
+    assert!(
+        v["error"]["message"].as_str().unwrap().contains("socks5"),
+        "{v}"
+    );
+
+    let out = env.headless_json(&["browser", "start", "--proxy-pool"], 15);
+    assert_failure(&out, "empty proxy pool");
+    let v = parse_json(&out);
+    assert!(
+        v["error"]["message"]
+            .as_str()
+            .unwrap()
+            .contains("[proxy_pool]"),
+        "{v}"
+    );
+}
@@ -147,7 +147,17 @@ fn cookies_list_json_happy_path() {
     assert_eq!(set_v["data"]["action"], "set");
     assert_eq!(set_v["data"]["affected"], 1);
 
-    let out = headless_json(&["browser", "cookies", "list", "--session", &sid], 10);
+    let out = headless_json(
+        &[
+            "browser",
+            "cookies",
+            "list",
+            "--session",
+            &sid,
+            "--show-secrets",
+        ],
+        10,
+    );
     assert_success(&out, "cookies list json");
     let v = parse_json(&out);
 
@@ -324,6 +334,7 @@ fn cookies_get_json_happy_path() {
             PRIMARY_COOKIE,
             "--session",
             &sid,
+            "--show-secrets",
         ],
         10,
     );
@@ -347,6 +358,42 @@ fn cookies_get_json_happy_path() {
     );
 }
 
+#[test]
+fn cookies_get_masks_value_by_default() {
+    if skip() {
+        return;
+    }
+
+    let base_url = url_a();
+    let (sid, _tid) = start_session(&base_url);
+    let _guard = SessionGuard::new(&sid);
+
+    set_cookie(
+        &sid,
+        PRIMARY_COOKIE,
+        "alpha",
+        &["--domain", "127.0.0.1", "--path", "/"],
+        10,
+    );
+
+    let out = headless_json(
+        &[
+            "browser",
+            "cookies",
+            "get",
+            PRIMARY_COOKIE,
+            "--session",
+            &sid,
+        ],
+        10,
+    );
+    assert_success(&out, "cookies get masked");
+    let v = parse_json(&out);
+    assert_eq!(v["data"]["item"]["name"], PRIMARY_COOKIE);
+    assert_eq!(v["data"]["item"]["value"], "[redacted]");
+    assert_eq!(v["data"]["item"]["value_redacted"], true);
+}
+
 #[test]
 fn cookies_get_missing_json() {
     if skip() {
@@ -642,3 +689,232 @@ fn cookies_session_not_found_json() {
     assert!(v["context"].is_null());
     assert_error_envelope(&v, "SESSION_NOT_FOUND");
 }
+
+#[test]
+fn cookies_export_import_round_trip_netscape() {
+    if skip() {
+        return;
+    }
+
+    let base_url = url_a();
+    let (sid, _tid) = start_session(&base_url);
+    let _guard = SessionGuard::new(&sid);
+    let dir = tempfile::tempdir().expect("create export dir");
+    let file = dir.path().join("cookies.txt");
+    let file = file.to_str().unwrap();
+
+    set_cookie(
+        &sid,
+        PRIMARY_COOKIE,
+        "alpha",
+        &[
+            "--domain",
+            "127.0.0.1",
+            "--path",
+            "/",
+            "--http-only",
+            "--expires",
+            EXPIRES_TS,
+        ],
+        10,
+    );
+
+    let out = headless_json(
+        &[
+            "browser",
+            "cookies",
+            "export",
+            "--out",
+            file,
+            "--session",
+            &sid,
+        ],
+        10,
+    );
+    assert_success(&out, "cookies export");
+    let v = parse_json(&out);
+    assert_eq!(v["command"], "browser cookies export");
+    assert_session_context(&v, &sid);
+    assert_eq!(v["data"]["format"], "netscape");
+    assert!(v["data"]["count"].as_u64().unwrap_or(0) >= 1);
+    let text = std::fs::read_to_string(file).expect("read exported cookies");
+    assert!(text.starts_with("# Netscape HTTP Cookie File"), "{text}");
+    let line = text
+        .lines()
+        .find(|l| l.ends_with(&format!("\t{PRIMARY_COOKIE}\talpha")))
+        .unwrap_or_else(|| panic!("exported cookie missing: {text}"));
+    assert!(line.starts_with("#HttpOnly_"), "{line}");
+    assert!(
+        line.contains(&format!("\t/\tFALSE\t{EXPIRES_TS}\t")),
+        "{line}"
+    );
+
+    let clear_out = headless_json(&["browser", "cookies", "clear", "--session", &sid], 10);
+    assert_success(&clear_out, "cookies clear before import");
+
+    let out = headless_json(
+        &["browser", "cookies", "import", file, "--session", &sid],
+        10,
+    );
+    assert_success(&out, "cookies import");
+    let v = parse_json(&out);
+    assert_eq!(v["command"], "browser cookies import");
+    assert_eq!(v["data"]["format"], "netscape");
+    assert!(v["data"]["imported"].as_u64().unwrap_or(0) >= 1);
+    assert_eq!(v["data"]["skipped"], 0);
+
+    let get_out = headless_json(
+        &[
+            "browser",
+            "cookies",
+            "get",
+            PRIMARY_COOKIE,
+            "--session",
+            &sid,
+            "--show-secrets",
+        ],
+        10,
+    );
+    assert_success(&get_out, "cookies get after import");
+    let get_v = parse_json(&get_out);
+    assert_eq!(get_v["data"]["item"]["value"], "alpha");
+    assert_eq!(get_v["data"]["item"]["http_only"], true);
+}
+
+#[test]
+fn cookies_import_rejects_malformed_file() {
+    if skip() {
+        return;
+    }
+
+    let dir = tempfile::tempdir().expect("create import dir");
+    let file = dir.path().join("cookies.txt");
+    std::fs::write(&file, "example.com\tTRUE\t/\n").unwrap();
+
+    let out = headless_json(
+        &[
+            "browser",
+            "cookies",
+            "import",
+            file.to_str().unwrap(),
+            "--session",
+            "missing-session",
+        ],
+        10,
+    );
+    assert_failure(&out, "cookies import malformed file");
+    let v = parse_json(&out);
+    assert_eq!(v["command"], "browser cookies import");
+    assert_error_envelope(&v, "INVALID_ARGUMENT");
+}
+
+#[test]
+fn state_bundle_moves_cookies_and_local_storage_encrypted() {
+    if skip() {
+        return;
+    }
+
+    let base_url = url_a();
+    let (sid, tid) = start_session(&base_url);
+    let _guard = SessionGuard::new(&sid);
+    let dir = tempfile::tempdir().expect("create bundle dir");
+    let file = dir.path().join("persona.bundle");
+    let file = file.to_str().unwrap();
+    let password = [("ACTIONBOOK_BUNDLE_PASSWORD", "correct horse")];
+
+    set_cookie(
+        &sid,
+        PRIMARY_COOKIE,
+        "alpha",
+        &["--domain", "127.0.0.1", "--expires", EXPIRES_TS],
+        10,
+    );
+    let js = "(() => { localStorage.setItem('bundle_token', 'beta'); return 'ok'; })()";
+    let out = headless_json(
+        &["browser", "eval", js, "--session", &sid, "--tab", &tid],
+        10,
+    );
+    assert_success(&out, "set localStorage");
+
+    let out = crate::harness::headless_json_with_env(
+        &[
+            "browser",
+            "state",
+            "bundle",
+            "--out",
+            file,
+            "--encrypt",
+            "--session",
+            &sid,
+        ],
+        &password,
+        20,
+    );
+    assert_success(&out, "state bundle");
+    let v = parse_json(&out);
+    assert_eq!(v["command"], "browser state bundle");
+    assert_eq!(v["data"]["encrypted"], true);
+    assert!(v["data"]["origins"].as_u64().unwrap_or(0) >= 1);
+    let text = std::fs::read_to_string(file).expect("read bundle");
+    assert!(!text.contains("alpha") && !text.contains("beta"), "{text}");
+
+    assert_success(
+        &headless_json(&["browser", "cookies", "clear", "--session", &sid], 10),
+        "cookies clear",
+    );
+    let js = "(() => { localStorage.clear(); return 'ok'; })()";
+    assert_success(
+        &headless_json(
+            &["browser", "eval", js, "--session", &sid, "--tab", &tid],
+            10,
+        ),
+        "clear localStorage",
+    );
+
+    let out = crate::harness::headless_json_with_env(
+        &["browser", "state", "import", file, "--session", &sid],
+        &[("ACTIONBOOK_BUNDLE_PASSWORD", "wrong")],
+        20,
+    );
+    assert_failure(&out, "state import with a wrong password");
+    assert_error_envelope(&parse_json(&out), "INVALID_ARGUMENT");
+
+    let out = crate::harness::headless_json_with_env(
+        &["browser", "state", "import", file, "--session", &sid],
+        &password,
+        20,
+    );
+    assert_success(&out, "state import");
+    let v = parse_json(&out);
+    assert!(v["data"]["cookies"].as_u64().unwrap_or(0) >= 1);
+    assert!(v["data"]["local_storage_items"].as_u64().unwrap_or(0) >= 1);
+
+    let get_out = headless_json(
+        &[
+            "browser",
+            "cookies",
+            "get",
+            PRIMARY_COOKIE,
+            "--session",
+            &sid,
+            "--show-secrets",
+        ],
+        10,
+    );
+    assert_success(&get_out, "cookies get after import");
+    assert_eq!(parse_json(&get_out)["data"]["item"]["value"], "alpha");
+    let out = headless_json(
+        &[
+            "browser",
+            "eval",
+            "localStorage.getItem('bundle_token')",
+            "--session",
+            &sid,
+            "--tab",
+            &tid,
+        ],
+        10,
+    );
+    assert_success(&out, "read localStorage after import");
+    assert_eq!(parse_json(&out)["data"]["value"], "beta");
+}
@@ -2,7 +2,7 @@
 
 use crate::harness::{
     SessionGuard, assert_failure, assert_success, headless, headless_json, parse_json, skip,
-    stdout_str, unique_session, wait_page_ready,
+    stderr_str, stdout_str, unique_session, wait_page_ready,
 };
 
 const DESCRIBE_SELECTOR: &str = "#describe-target";
@@ -599,6 +599,89 @@ fn describe_text_output_with_qualifier() {
     assert_eq!(lines.get(1), Some(&"button \"Edit\" [disabled]"));
 }
 
+fn inject_page_fixture(sid: &str, tid: &str) {
+    let js = r#"document.body.innerHTML = `
+  <h1>Sign in</h1>
+  <form id="login" action="/session" method="post">
+    <label>Email <input name="email" type="email" required></label>
+    <label>Password <input name="password" type="password" required></label>
+    <button type="submit">Log in</button>
+  </form>
+  <h2>Results</h2>
+  <a href="?page=1">Prev</a>
+  <nav aria-label="Pagination"><a href="?page=1">1</a><a href="?page=2">2</a><a href="?page=3">3</a></nav>
+  <a rel="next" href="?page=3">Next</a>
+`;
+document.title = 'Describe Page Fixture';
+void(0)"#;
+    let out = headless_json(&["browser", "eval", js, "--session", sid, "--tab", tid], 10);
+    assert_success(&out, "inject page fixture");
+}
+
+#[test]
+fn describe_page_digest_json() {
+    if skip() {
+        return;
+    }
+
+    let (sid, tid) = start_session();
+    let _guard = SessionGuard::new(&sid);
+    inject_page_fixture(&sid, &tid);
+
+    let out = headless_json(
+        &["browser", "describe", "--session", &sid, "--tab", &tid],
+        10,
+    );
+    assert_success(&out, "describe page json");
+    let v = parse_json(&out);
+    assert_eq!(v["command"], "browser describe");
+    assert_eq!(v["data"]["target"]["page"], true);
+    assert_eq!(v["data"]["title"], "Describe Page Fixture");
+    assert_eq!(v["data"]["headings"][0]["text"], "Sign in");
+    assert_eq!(v["data"]["headings"][1]["level"], 2);
+    assert_eq!(v["data"]["interactive"]["by_role"]["textbox"], 2);
+    assert_eq!(v["data"]["interactive"]["by_role"]["link"], 5);
+
+    let form = &v["data"]["forms"][0];
+    assert_eq!(form["id"], "login");
+    assert_eq!(form["method"], "post");
+    assert_eq!(form["field_count"], 2);
+    assert_eq!(form["fields"][0]["name"], "email");
+    assert_eq!(form["fields"][0]["required"], true);
+    assert_eq!(form["submit"], "Log in");
+
+    let pagination = &v["data"]["pagination"];
+    assert!(pagination["next"].as_str().unwrap().ends_with("?page=3"));
+    assert!(pagination["prev"].as_str().unwrap().ends_with("?page=1"));
+    assert_eq!(pagination["pages"], 3);
+}
+
+#[test]
+fn describe_page_nearby_requires_selector() {
+    if skip() {
+        return;
+    }
+
+    let (sid, tid) = start_session();
+    let _guard = SessionGuard::new(&sid);
+
+    let out = headless_json(
+        &[
+            "browser",
+            "describe",
+            "--nearby",
+            "--session",
+            &sid,
+            "--tab",
+            &tid,
+        ],
+        10,
+    );
+    assert_failure(&out, "describe --nearby without selector");
+    let v = parse_json(&out);
+    assert_error_envelope(&v, "INVALID_ARGUMENT");
+}
+
 #[test]
 fn state_json_happy_path() {
     if skip() {
@@ -1057,3 +1140,156 @@ fn state_js_exception_returns_error() {
     assert_eq!(v["command"], "browser state");
     assert_error_envelope(&v, "JS_EXCEPTION");
 }
+
+#[test]
+fn assert_passes_when_every_check_holds() {
+    if skip() {
+        return;
+    }
+
+    let (sid, tid) = start_session();
+    let _guard = SessionGuard::new(&sid);
+    inject_fixture(&sid, &tid);
+
+    let out = headless_json(
+        &[
+            "browser",
+            "assert",
+            "--text-contains",
+            "John Smith",
+            "--url-matches",
+            "^about:",
+            "--element-visible",
+            DESCRIBE_SELECTOR,
+            "--element-count",
+            "input[type=checkbox]",
+            "--count",
+            ">=3",
+            "--session",
+            &sid,
+            "--tab",
+            &tid,
+        ],
+        10,
+    );
+    assert_success(&out, "assert all pass");
+    let v = parse_json(&out);
+    assert_eq!(v["command"], "browser assert");
+    assert_eq!(v["data"]["passed"], true);
+    let checks = v["data"]["checks"].as_array().unwrap();
+    assert_eq!(checks.len(), 4);
+    assert!(checks.iter().all(|c| c["passed"] == true));
+}
+
+#[test]
+fn assert_fails_with_each_check_in_details() {
+    if skip() {
+        return;
+    }
+
+    let (sid, tid) = start_session();
+    let _guard = SessionGuard::new(&sid);
+    inject_fixture(&sid, &tid);
+
+    let out = headless_json(
+        &[
+            "browser",
+            "assert",
+            "--text-contains",
+            "John Smith",
+            "--element-visible",
+            STATE_HIDDEN_SELECTOR,
+            "--element-count",
+            "#person-row",
+            "--count",
+            "2",
+            "--session",
+            &sid,
+            "--tab",
+            &tid,
+        ],
+        10,
+    );
+    assert_failure(&out, "assert with failing checks");
+    let v = parse_json(&out);
+    assert_error_envelope(&v, "ASSERTION_FAILED");
+    let checks = v["error"]["details"]["checks"].as_array().unwrap();
+    assert_eq!(checks[0]["passed"], true);
+    assert_eq!(checks[1]["check"], "element-visible");
+    assert_eq!(checks[1]["actual"], false);
+    assert_eq!(checks[2]["actual"], 1);
+    assert_eq!(checks[2]["passed"], false);
+
+    let out = headless(
+        &[
+            "browser",
+            "assert",
+            "--element-visible",
+            "#no-such-element",
+            "--session",
+            &sid,
+            "--tab",
+            &tid,
+        ],
+        10,
+    );
+    assert_failure(&out, "assert missing element text");
+    let text = stderr_str(&out);
+    assert!(
+        text.contains("FAIL element-visible #no-such-element: expected true, got \"not found\""),
+        "unexpected output: {text}"
+    );
+}
+
+#[test]
+fn assert_no_console_errors_fails_on_logged_errors_not_allowed() {
+    if skip() {
+        return;
+    }
+
+    let (sid, tid) = start_session();
+    let _guard = SessionGuard::new(&sid);
+
+    let assert_clean = |extra: &[&str]| {
+        let mut args = vec!["browser", "assert", "--no-console-errors"];
+        args.extend_from_slice(extra);
+        args.extend_from_slice(&["--session", &sid, "--tab", &tid]);
+        headless_json(&args, 10)
+    };
+
+    // Nothing logged yet; the hook was not there at load, so a warning says so.
+    let out = assert_clean(&[]);
+    assert_success(&out, "assert no console errors on a clean page");
+    let v = parse_json(&out);
+    assert_eq!(v["data"]["checks"][0]["actual"], 0);
+    assert!(
+        !v["meta"]["warnings"].as_array().unwrap().is_empty(),
+        "expected a capture warning: {v}"
+    );
+
+    let out = headless_json(
+        &[
+            "browser",
+            "eval",
+            "console.error('expected: ad blocked'); console.error('TypeError: boom'); 1",
+            "--session",
+            &sid,
+            "--tab",
+            &tid,
+        ],
+        10,
+    );
+    assert_success(&out, "log console errors");
+
+    let out = assert_clean(&["--allow", "^expected:"]);
+    assert_failure(&out, "assert with a console error");
+    let v = parse_json(&out);
+    assert_error_envelope(&v, "ASSERTION_FAILED");
+    let check = &v["error"]["details"]["checks"][0];
+    assert_eq!(check["actual"], 1);
+    assert_eq!(check["allowed"], 1);
+    assert_eq!(check["errors"][0]["text"], "TypeError: boom");
+
+    let out = assert_clean(&["--allow", "^expected:", "--allow", "boom"]);
+    assert_success(&out, "assert with every error allowed");
+}
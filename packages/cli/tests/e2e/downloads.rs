@@ -0,0 +1,114 @@
+//! E2E tests for `browser downloads` and `browser start --download-dir`.
+
+use crate::harness::{
+    SessionGuard, assert_error_envelope, assert_failure, assert_success, headless_json, parse_json,
+    skip, unique_session, url_a, url_download, wait_page_ready,
+};
+
+#[test]
+fn downloads_wait_returns_file_saved_to_download_dir() {
+    if skip() {
+        return;
+    }
+
+    let dir = tempfile::tempdir().expect("create download dir");
+    let dir_str = dir.path().to_string_lossy().to_string();
+    let (sid, profile) = unique_session("dl");
+    let out = headless_json(
+        &[
+            "browser",
+            "start",
+            "--mode",
+            "local",
+            "--headless",
+            "--set-session-id",
+            &sid,
+            "--profile",
+            &profile,
+            "--open-url",
+            &url_a(),
+            "--download-dir",
+            &dir_str,
+        ],
+        30,
+    );
+    let _guard = SessionGuard::new(&sid);
+    assert_success(&out, "start with --download-dir");
+    let tid = parse_json(&out)["data"]["tab"]["tab_id"]
+        .as_str()
+        .unwrap()
+        .to_string();
+    wait_page_ready(&sid, &tid);
+
+    let click = format!(
+        "(() => {{ const a = document.createElement('a'); a.href = '{}'; a.download = ''; \
+         document.body.appendChild(a); a.click(); return true; }})()",
+        url_download()
+    );
+    let out = headless_json(
+        &["browser", "eval", &click, "--session", &sid, "--tab", &tid],
+        15,
+    );
+    assert_success(&out, "eval click download link");
+
+    let out = headless_json(
+        &[
+            "browser",
+            "downloads",
+            "wait",
+            "--session",
+            &sid,
+            "--timeout",
+            "15000",
+        ],
+        30,
+    );
+    assert_success(&out, "downloads wait");
+    let v = parse_json(&out);
+    let d = &v["data"]["download"];
+    assert_eq!(d["state"], "completed");
+    assert_eq!(d["filename"], "report.csv");
+    let path = d["path"].as_str().expect("download path");
+    assert!(
+        path.starts_with(&dir_str),
+        "saved outside --download-dir: {path}"
+    );
+    let body = std::fs::read_to_string(path).expect("read downloaded file");
+    assert!(body.contains("alpha"));
+
+    let out = headless_json(&["browser", "downloads", "list", "--session", &sid], 15);
+    assert_success(&out, "downloads list");
+    let v = parse_json(&out);
+    assert_eq!(v["data"]["total"], 1);
+    assert_eq!(v["data"]["downloads"][0]["state"], "completed");
+
+    // The finished download was already returned, so a second wait times out.
+    let out = headless_json(
+        &[
+            "browser",
+            "downloads",
+            "wait",
+            "--session",
+            &sid,
+            "--timeout",
+            "500",
+        ],
+        15,
+    );
+    assert_failure(&out, "second downloads wait");
+    assert_error_envelope(&parse_json(&out), "TIMEOUT");
+}
+
+#[test]
+fn downloads_on_unknown_session_fails() {
+    if skip() {
+        return;
+    }
+
+    let out = headless_json(
+        &["browser", "downloads", "list", "--session", "no-such-dl"],
+        15,
+    );
+    assert_failure(&out, "downloads list unknown session");
+    assert_error_envelope(&parse_json(&out), "SESSION_NOT_FOUND");
+}
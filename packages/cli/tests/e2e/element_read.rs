@@ -267,6 +267,35 @@ fn text_json_full_page_happy_path() {
     assert!(text.contains("Primary article copy."));
 }
 
+#[cfg(feature = "pdf-text")]
+#[test]
+fn text_json_reads_pdf_viewer_document() {
+    if skip() {
+        return;
+    }
+
+    let (sid, tid) = start_session();
+    let _guard = SessionGuard::new(&sid);
+    let out = headless_json(
+        &[
+            "browser",
+            "goto",
+            &crate::harness::url_pdf(),
+            "--session",
+            &sid,
+            "--tab",
+            &tid,
+        ],
+        30,
+    );
+    assert_success(&out, "goto pdf");
+
+    let out = headless_json(&["browser", "text", "--session", &sid, "--tab", &tid], 15);
+    assert_success(&out, "text pdf json");
+    let v = parse_json(&out);
+    assert_eq!(v["data"]["value"], "Actionbook PDF fixture");
+}
+
 #[test]
 fn value_and_attr_json_and_text_happy_path() {
     if skip() {
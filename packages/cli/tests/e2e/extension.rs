@@ -141,6 +141,30 @@ fn extension_status_with_running_daemon_does_not_crash() {
     );
 }
 
+#[test]
+fn extension_version_reports_not_connected_and_bundled_build() {
+    if skip() {
+        return;
+    }
+
+    let env = SoloEnv::new();
+    let out = env.headless_json(&["extension", "version"], 10);
+    assert_success(&out, "extension version without an extension");
+    let v = parse_json(&out);
+
+    assert_eq!(v["command"], "extension version");
+    assert_eq!(v["data"]["connected"], false);
+    assert!(v["data"]["compatible"].is_null());
+    assert_eq!(
+        v["data"]["bundled_version"].as_str(),
+        Some(extension_fixture_version().as_str())
+    );
+    assert_eq!(
+        v["data"]["required_protocol_version"],
+        actionbook_cli::EXTENSION_PROTOCOL_MIN_VERSION
+    );
+}
+
 #[test]
 fn extension_ping_returns_bridge_not_listening() {
     if skip() {
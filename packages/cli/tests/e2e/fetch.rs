@@ -0,0 +1,157 @@
+//! E2E tests for `browser fetch`.
+
+use crate::harness::{
+    SessionGuard, assert_error_envelope, assert_failure, assert_success, headless_json, parse_json,
+    skip, unique_session, url_a, url_images, wait_page_ready,
+};
+
+fn start_on_images() -> (String, String) {
+    let (sid, profile) = unique_session("fetch");
+    let out = headless_json(
+        &[
+            "browser",
+            "start",
+            "--mode",
+            "local",
+            "--headless",
+            "--set-session-id",
+            &sid,
+            "--profile",
+            &profile,
+            "--open-url",
+            &url_images(),
+        ],
+        30,
+    );
+    assert_success(&out, "start on images fixture");
+    let tid = parse_json(&out)["data"]["tab"]["tab_id"]
+        .as_str()
+        .unwrap()
+        .to_string();
+    wait_page_ready(&sid, &tid);
+    (sid, tid)
+}
+
+#[test]
+fn fetch_sends_session_cookies_and_saves_output() {
+    if skip() {
+        return;
+    }
+
+    // /images sets the auth cookie that /img/private.svg requires.
+    let (sid, tid) = start_on_images();
+    let _guard = SessionGuard::new(&sid);
+
+    let dir = tempfile::tempdir().expect("create output dir");
+    let file = dir.path().join("private.svg");
+    let file_str = file.to_string_lossy().to_string();
+    let out = headless_json(
+        &[
+            "browser",
+            "fetch",
+            "/img/private.svg",
+            "--output",
+            &file_str,
+            "--session",
+            &sid,
+            "--tab",
+            &tid,
+        ],
+        30,
+    );
+    assert_success(&out, "fetch private image");
+    let v = parse_json(&out);
+    assert_eq!(v["command"], "browser fetch");
+    assert_eq!(v["data"]["status"], 200);
+    assert_eq!(v["data"]["content_type"], "image/svg+xml");
+    assert_eq!(v["data"]["path"], file_str.as_str());
+    let saved = std::fs::read_to_string(&file).expect("read fetched file");
+    assert!(saved.contains("<svg"));
+    assert_eq!(v["data"]["bytes"], saved.len());
+}
+
+#[test]
+fn fetch_forwards_method_headers_and_body() {
+    if skip() {
+        return;
+    }
+
+    let (sid, tid) = start_on_images();
+    let _guard = SessionGuard::new(&sid);
+
+    let out = headless_json(
+        &[
+            "browser",
+            "fetch",
+            "/api/echo",
+            "--method",
+            "POST",
+            "--header",
+            "X-Test: yes",
+            "--data",
+            "hello",
+            "--session",
+            &sid,
+            "--tab",
+            &tid,
+        ],
+        30,
+    );
+    assert_success(&out, "fetch echo");
+    let v = parse_json(&out);
+    let echo: serde_json::Value =
+        serde_json::from_str(v["data"]["body"].as_str().expect("text body")).expect("echo json");
+    assert_eq!(echo["method"], "POST");
+    assert_eq!(echo["headers"]["x-test"], "yes");
+    assert_eq!(echo["body"], "hello");
+}
+
+#[test]
+fn fetch_without_cookie_fails_with_http_error() {
+    if skip() {
+        return;
+    }
+
+    // Same origin as the images fixture, but the auth cookie was never set.
+    let (sid, profile) = unique_session("fetch");
+    let out = headless_json(
+        &[
+            "browser",
+            "start",
+            "--mode",
+            "local",
+            "--headless",
+            "--set-session-id",
+            &sid,
+            "--profile",
+            &profile,
+            "--open-url",
+            &url_a(),
+        ],
+        30,
+    );
+    let _guard = SessionGuard::new(&sid);
+    assert_success(&out, "start on page a");
+    let tid = parse_json(&out)["data"]["tab"]["tab_id"]
+        .as_str()
+        .unwrap()
+        .to_string();
+    wait_page_ready(&sid, &tid);
+
+    let out = headless_json(
+        &[
+            "browser",
+            "fetch",
+            "/img/private.svg",
+            "--session",
+            &sid,
+            "--tab",
+            &tid,
+        ],
+        30,
+    );
+    assert_failure(&out, "fetch without cookie");
+    let v = parse_json(&out);
+    assert_error_envelope(&v, "HTTP_ERROR");
+    assert_eq!(v["error"]["details"]["status"], 403);
+}
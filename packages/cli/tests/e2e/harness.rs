@@ -543,6 +543,29 @@ fn handle_http(mut stream: std::net::TcpStream) {
         return;
     }
 
+    // Redirect chain for `browser trace-redirects`:
+    // /redirect-chain --302--> /redirect-meta --meta refresh--> /redirect-js --script--> /page-b
+    if path == "/redirect-chain" {
+        let _ = stream.write_all(
+            b"HTTP/1.1 302 Found\r\nLocation: /redirect-meta\r\nConnection: close\r\nContent-Length: 0\r\n\r\n",
+        );
+        return;
+    }
+    if path == "/redirect-meta" || path == "/redirect-js" {
+        let body = if path == "/redirect-meta" {
+            r#"<!DOCTYPE html><html><head><meta http-equiv="refresh" content="0; url=/redirect-js"><title>Meta</title></head><body></body></html>"#
+        } else {
+            r#"<!DOCTYPE html><html><head><title>JS</title></head><body><script>setTimeout(() => location.replace('/page-b'), 50);</script></body></html>"#
+        };
+        let response = format!(
+            "HTTP/1.1 200 OK\r\nContent-Type: text/html\r\nConnection: close\r\nContent-Length: {}\r\n\r\n{}",
+            body.len(),
+            body
+        );
+        let _ = stream.write_all(response.as_bytes());
+        return;
+    }
+
     if path == "/redirect-fast" {
         let response = format!(
             "HTTP/1.1 302 Found\r\nLocation: http://127.0.0.1:{}/page-b\r\nConnection: close\r\nContent-Length: 0\r\n\r\n",
@@ -694,6 +717,65 @@ setTimeout(() => {{
         return;
     }
 
+    // Image fixtures for `browser images`: /img/private.svg is only served
+    // with an `auth=1` cookie, to check downloads go through the session.
+    if path == "/images" {
+        let body = r#"<!DOCTYPE html><html><head><title>Images</title></head>
+<body>
+<div id="gallery">
+<img src="/img/block.svg" alt="Block">
+<img src="/img/private.svg" alt="Private">
+</div>
+<img id="icon" src="data:image/svg+xml,%3Csvg xmlns='http://www.w3.org/2000/svg' width='8' height='8'/%3E" alt="">
+</body></html>"#;
+        let response = format!(
+            "HTTP/1.1 200 OK\r\nContent-Type: text/html\r\nSet-Cookie: auth=1; Path=/\r\nConnection: close\r\nContent-Length: {}\r\n\r\n{}",
+            body.len(),
+            body
+        );
+        let _ = stream.write_all(response.as_bytes());
+        return;
+    }
+    if path == "/img/block.svg" || path == "/img/private.svg" {
+        let authed = request
+            .lines()
+            .any(|l| l.to_ascii_lowercase().starts_with("cookie:") && l.contains("auth=1"));
+        let response = if path == "/img/private.svg" && !authed {
+            "HTTP/1.1 403 Forbidden\r\nConnection: close\r\nContent-Length: 0\r\n\r\n".to_string()
+        } else {
+            let body = r#"<svg xmlns="http://www.w3.org/2000/svg" width="120" height="80"><rect width="120" height="80" fill="teal"/></svg>"#;
+            format!(
+                "HTTP/1.1 200 OK\r\nContent-Type: image/svg+xml\r\nConnection: close\r\nContent-Length: {}\r\n\r\n{}",
+                body.len(),
+                body
+            )
+        };
+        let _ = stream.write_all(response.as_bytes());
+        return;
+    }
+
+    if path == "/doc.pdf" {
+        let body: &[u8] = include_bytes!("../fixtures/text.pdf");
+        let head = format!(
+            "HTTP/1.1 200 OK\r\nContent-Type: application/pdf\r\nConnection: close\r\nContent-Length: {}\r\n\r\n",
+            body.len()
+        );
+        let _ = stream.write_all(head.as_bytes());
+        let _ = stream.write_all(body);
+        return;
+    }
+
+    if path == "/download/report.csv" {
+        let body = "id,name\n1,alpha\n2,beta\n";
+        let response = format!(
+            "HTTP/1.1 200 OK\r\nContent-Type: text/csv\r\nContent-Disposition: attachment; filename=\"report.csv\"\r\nConnection: close\r\nContent-Length: {}\r\n\r\n{}",
+            body.len(),
+            body
+        );
+        let _ = stream.write_all(response.as_bytes());
+        return;
+    }
+
     if path == "/network-fixture.css" {
         let body = "body { background: rgb(245, 248, 255); }";
         let response = format!(
@@ -1012,6 +1094,11 @@ pub fn url_c() -> String {
     format!("http://127.0.0.1:{}/page-c", local_server().port)
 }
 
+/// Start of a 302 → meta refresh → script redirect chain ending on page B.
+pub fn url_redirect_chain() -> String {
+    format!("http://127.0.0.1:{}/redirect-chain", local_server().port)
+}
+
 /// URL that immediately redirects to page B via HTTP 302.
 pub fn url_fast_redirect() -> String {
     format!("http://127.0.0.1:{}/redirect-fast", local_server().port)
@@ -1035,6 +1122,26 @@ pub fn url_home_no_trailing_slash() -> String {
     format!("http://127.0.0.1:{}", local_server().port)
 }
 
+/// Page with two 120x80 images in `#gallery` (one requiring the cookie the
+/// page sets) and an 8x8 data: URL icon.
+pub fn url_images() -> String {
+    format!("http://127.0.0.1:{}/images", local_server().port)
+}
+
+/// URL of a one-page PDF reading "Actionbook PDF fixture".
+#[cfg(feature = "pdf-text")]
+pub fn url_pdf() -> String {
+    format!("http://127.0.0.1:{}/doc.pdf", local_server().port)
+}
+
+/// URL served as an attachment (`report.csv`), so navigating to it downloads.
+pub fn url_download() -> String {
+    format!(
+        "http://127.0.0.1:{}/download/report.csv",
+        local_server().port
+    )
+}
+
 /// URL for a slow page used to verify CLI-level timeouts.
 pub fn url_slow() -> String {
     format!("http://127.0.0.1:{}/slow", local_server().port)
@@ -1355,23 +1462,27 @@ pub fn assert_context_with_tab(v: &serde_json::Value, expected_sid: &str, expect
 
 /// Start a headless session with a unique session ID and profile, return (session_id, tab_id).
 pub fn start_session(url: &str) -> (String, String) {
+    start_session_with(url, &[])
+}
+
+/// [`start_session`] with extra `browser start` flags.
+pub fn start_session_with(url: &str, extra: &[&str]) -> (String, String) {
     let (sid, profile) = unique_session("s");
-    let out = headless_json(
-        &[
-            "browser",
-            "start",
-            "--mode",
-            "local",
-            "--headless",
-            "--set-session-id",
-            &sid,
-            "--profile",
-            &profile,
-            "--open-url",
-            url,
-        ],
-        30,
-    );
+    let mut args = vec![
+        "browser",
+        "start",
+        "--mode",
+        "local",
+        "--headless",
+        "--set-session-id",
+        &sid,
+        "--profile",
+        &profile,
+        "--open-url",
+        url,
+    ];
+    args.extend_from_slice(extra);
+    let out = headless_json(&args, 30);
     assert_success(&out, &format!("start session {sid}"));
     let v = parse_json(&out);
     let actual_sid = v["data"]["session"]["session_id"]
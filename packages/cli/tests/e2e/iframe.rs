@@ -2,8 +2,9 @@
 //! Covers both same-origin iframes and cross-origin (OOPIF) iframes.
 
 use crate::harness::{
-    SessionGuard, assert_success, headless, headless_json, parse_json, skip, stdout_str,
-    unique_session, url_iframe_cross_origin_parent, url_iframe_parent, wait_page_ready,
+    SessionGuard, assert_error_envelope, assert_failure, assert_success, headless, headless_json,
+    parse_json, skip, stdout_str, unique_session, url_iframe_cross_origin_parent,
+    url_iframe_parent, wait_page_ready,
 };
 use serde_json::Value;
 
@@ -708,6 +709,82 @@ fn xo_iframe_click_works_on_cross_origin_element() {
     assert_success(&click_out, "click on xo iframe element");
 }
 
+// ── --frame ───────────────────────────────────────────────────────
+
+#[test]
+fn frame_flag_scopes_selectors_to_same_origin_iframe() {
+    if skip() {
+        return;
+    }
+    let (sid, tid, _guard) = start_iframe_session();
+    let scoped = |args: &[&str]| {
+        let mut all = vec!["browser"];
+        all.extend_from_slice(args);
+        all.extend_from_slice(&["--session", &sid, "--tab", &tid]);
+        headless_json(&all, 15)
+    };
+
+    // #child-input only exists inside the iframe.
+    let out = scoped(&["fill", "#child-input", "framed", "--frame", "iframe-child"]);
+    assert_success(&out, "fill --frame");
+    let out = scoped(&["html", "#child-input", "--frame", "1"]);
+    assert_success(&out, "html --frame");
+    let out = scoped(&["click", "#child-btn", "--frame", "1"]);
+    assert_success(&out, "click --frame");
+
+    let out = scoped(&["text", "--frame", "1"]);
+    assert_success(&out, "text --frame");
+    let text = parse_json(&out)["data"]["value"]
+        .as_str()
+        .unwrap_or("")
+        .to_string();
+    assert!(text.contains("Child Content"), "got: {text}");
+    assert!(!text.contains("Main Page"), "got: {text}");
+
+    let out = scoped(&["snapshot", "--frame", "1"]);
+    assert_success(&out, "snapshot --frame");
+    let v = parse_json(&out);
+    assert!(snapshot_nodes_contain(&v, "Child Input"));
+    assert!(!snapshot_nodes_contain(&v, "Main Input"));
+    let child_ref = find_ref_for_name(&v, "Child Input");
+    let out = scoped(&["value", &format!("@{child_ref}")]);
+    assert_success(&out, "value via framed ref");
+    assert_eq!(parse_json(&out)["data"]["value"], "framed");
+
+    let out = scoped(&["text", "--frame", "5"]);
+    assert_failure(&out, "text --frame out of range");
+    assert_error_envelope(&parse_json(&out), "FRAME_NOT_FOUND");
+}
+
+#[test]
+fn frame_flag_reaches_cross_origin_iframe() {
+    if skip() {
+        return;
+    }
+    let (sid, tid, _guard) = start_xo_iframe_session();
+
+    let out = headless_json(
+        &[
+            "browser",
+            "html",
+            "#xo-input",
+            "--frame",
+            "1",
+            "--session",
+            &sid,
+            "--tab",
+            &tid,
+        ],
+        15,
+    );
+    assert_success(&out, "html --frame on cross-origin iframe");
+    let html = parse_json(&out)["data"]["value"]
+        .as_str()
+        .unwrap_or("")
+        .to_string();
+    assert!(html.contains("xo-value"), "got: {html}");
+}
+
 // ── Helper ────────────────────────────────────────────────────────
 
 /// Find the ref ID (e.g. "e42") for an element with the given name in snapshot data.
@@ -6,8 +6,8 @@
 //! per api-reference.md §11.
 
 use crate::harness::{
-    SessionGuard, assert_failure, assert_success, headless, headless_json, parse_json, skip,
-    stdout_str, unique_session, wait_page_ready,
+    SessionGuard, SoloEnv, assert_failure, assert_success, headless, headless_json, parse_json,
+    skip, stdout_str, unique_session, wait_page_ready,
 };
 
 const TEST_URL: &str = "https://example.com";
@@ -3548,6 +3548,220 @@ fn select_missing_selector_text() {
     close_session(&sid);
 }
 
+// ========================================================================
+// Group 11: select --index and options listing
+// ========================================================================
+
+#[test]
+fn select_by_index_json() {
+    if skip() {
+        return;
+    }
+    let (sid, tid) = start_session(TEST_URL);
+    let _guard = SessionGuard::new(&sid);
+    install_select_fixture(&sid, &tid);
+
+    let out = headless_json(
+        &[
+            "browser",
+            "select",
+            "#ab-select",
+            "--index",
+            "2",
+            "--session",
+            &sid,
+            "--tab",
+            &tid,
+        ],
+        15,
+    );
+    assert_success(&out, "select by index");
+    let v = parse_json(&out);
+    assert_eq!(v["data"]["value_summary"]["value"], "citrus");
+    assert_eq!(v["data"]["value_summary"]["index"], 2);
+    assert_eq!(
+        eval_value(&sid, &tid, "document.querySelector('#ab-select').value"),
+        "citrus"
+    );
+
+    let out = headless_json(
+        &[
+            "browser",
+            "select",
+            "#ab-select",
+            "--index",
+            "9",
+            "--session",
+            &sid,
+            "--tab",
+            &tid,
+        ],
+        15,
+    );
+    assert_failure(&out, "select index out of range");
+    assert_error_envelope(&parse_json(&out), "INVALID_ARGUMENT");
+
+    close_session(&sid);
+}
+
+#[test]
+fn options_lists_values_labels_and_selection() {
+    if skip() {
+        return;
+    }
+    let (sid, tid) = start_session(TEST_URL);
+    let _guard = SessionGuard::new(&sid);
+    install_select_fixture(&sid, &tid);
+
+    let out = headless_json(
+        &[
+            "browser",
+            "options",
+            "#ab-select",
+            "--session",
+            &sid,
+            "--tab",
+            &tid,
+        ],
+        15,
+    );
+    assert_success(&out, "options json");
+    let v = parse_json(&out);
+    assert_eq!(v["command"], "browser options");
+    assert_eq!(v["data"]["total"], 3);
+    assert_eq!(v["data"]["multiple"], false);
+    let options = v["data"]["options"].as_array().expect("options array");
+    assert_eq!(options[0]["value"], "apple");
+    assert_eq!(options[0]["selected"], true);
+    assert_eq!(options[2]["index"], 2);
+    assert_eq!(options[2]["label"], "Citrus Fruit");
+    assert_eq!(options[2]["selected"], false);
+
+    let out = headless(
+        &[
+            "browser",
+            "options",
+            "#ab-select",
+            "--session",
+            &sid,
+            "--tab",
+            &tid,
+        ],
+        15,
+    );
+    assert_success(&out, "options text");
+    let text = stdout_str(&out);
+    assert!(text.contains("[0] apple \"Apple\" selected"), "got {text}");
+    assert!(text.contains("[2] citrus \"Citrus Fruit\""), "got {text}");
+
+    close_session(&sid);
+}
+
+// ========================================================================
+// Group 12: tabto — walk the real tab order to an element
+// ========================================================================
+
+/// Three focusable fields in tab order, plus one that is skipped
+/// (`tabindex="-1"`) so it can never be reached by Tab.
+fn install_tab_order_fixture(session_id: &str, tab_id: &str) {
+    let expression = r#"
+(() => {
+  const existing = document.getElementById('ab-tab-fixture');
+  if (existing) existing.remove();
+  const root = document.createElement('div');
+  root.id = 'ab-tab-fixture';
+  root.innerHTML = `
+    <input id="ab-tab-a">
+    <input id="ab-tab-b">
+    <input id="ab-tab-skip" tabindex="-1">
+    <button id="ab-tab-c">Go</button>
+  `;
+  document.body.prepend(root);
+  document.getElementById('ab-tab-a').focus();
+  return 'ok';
+})()
+"#;
+    assert_eq!(eval_value(session_id, tab_id, expression), "ok");
+}
+
+#[test]
+fn tabto_reaches_element_in_tab_order() {
+    if skip() {
+        return;
+    }
+    let (sid, tid) = start_session(TEST_URL);
+    let _guard = SessionGuard::new(&sid);
+    install_tab_order_fixture(&sid, &tid);
+
+    let out = headless_json(
+        &[
+            "browser",
+            "tabto",
+            "#ab-tab-c",
+            "--session",
+            &sid,
+            "--tab",
+            &tid,
+        ],
+        15,
+    );
+    assert_success(&out, "tabto forward");
+    let v = parse_json(&out);
+    assert_eq!(v["command"], "browser tabto");
+    assert_eq!(v["data"]["presses"], 2);
+    assert_eq!(
+        eval_value(&sid, &tid, "document.activeElement.id"),
+        "ab-tab-c"
+    );
+
+    let out = headless_json(
+        &[
+            "browser",
+            "tabto",
+            "#ab-tab-a",
+            "--reverse",
+            "--session",
+            &sid,
+            "--tab",
+            &tid,
+        ],
+        15,
+    );
+    assert_success(&out, "tabto reverse");
+    assert_eq!(parse_json(&out)["data"]["presses"], 2);
+
+    close_session(&sid);
+}
+
+#[test]
+fn tabto_fails_for_element_outside_tab_order() {
+    if skip() {
+        return;
+    }
+    let (sid, tid) = start_session(TEST_URL);
+    let _guard = SessionGuard::new(&sid);
+    install_tab_order_fixture(&sid, &tid);
+
+    let out = headless_json(
+        &[
+            "browser",
+            "tabto",
+            "#ab-tab-skip",
+            "--max-tabs",
+            "5",
+            "--session",
+            &sid,
+            "--tab",
+            &tid,
+        ],
+        15,
+    );
+    assert_failure(&out, "tabto unreachable");
+    assert_error_envelope(&parse_json(&out), "ELEMENT_NOT_REACHED");
+
+    close_session(&sid);
+}
+
 // ========================================================================
 // Group 13: hover — command wiring, success path, and error path
 // ========================================================================
@@ -3827,6 +4041,105 @@ fn hover_missing_selector_text() {
     close_session(&sid);
 }
 
+// ========================================================================
+// Group 13b: menu — hover a trigger and click the submenu it opens
+// ========================================================================
+
+/// A CSS `:hover` menu: the submenu only renders while the pointer is over
+/// the trigger, so a separate hover + click would lose it.
+fn install_hover_menu_fixture(session_id: &str, tab_id: &str) {
+    let expression = r#"
+(() => {
+  const existing = document.getElementById('ab-menu-fixture');
+  if (existing) existing.remove();
+  window.__ab_menu_clicked = '';
+  const root = document.createElement('div');
+  root.id = 'ab-menu-fixture';
+  root.innerHTML = `
+    <style>
+      #ab-menu { position: fixed; top: 20px; left: 20px; z-index: 2147483647; background: #fff; }
+      #ab-menu ul { display: none; margin: 0; padding: 0; }
+      #ab-menu:hover ul { display: block; }
+    </style>
+    <div id="ab-menu">
+      <span id="ab-menu-trigger">Products</span>
+      <ul><li><a id="ab-menu-item" href="javascript:void 0">Pricing</a></li></ul>
+    </div>
+  `;
+  document.body.appendChild(root);
+  document.getElementById('ab-menu-item').addEventListener('click', () => {
+    window.__ab_menu_clicked = 'pricing';
+  });
+  return 'ok';
+})()
+"#;
+    assert_eq!(eval_value(session_id, tab_id, expression), "ok");
+}
+
+#[test]
+fn menu_hovers_then_clicks_submenu_item() {
+    if skip() {
+        return;
+    }
+    let (sid, tid) = start_session(TEST_URL);
+    let _guard = SessionGuard::new(&sid);
+    install_hover_menu_fixture(&sid, &tid);
+
+    let out = headless_json(
+        &[
+            "browser",
+            "menu",
+            "#ab-menu-trigger",
+            "#ab-menu-item",
+            "--session",
+            &sid,
+            "--tab",
+            &tid,
+        ],
+        15,
+    );
+    assert_success(&out, "menu");
+    let v = parse_json(&out);
+    assert_eq!(v["command"], "browser menu");
+    assert_eq!(v["data"]["item"]["selector"], "#ab-menu-item");
+    assert_eq!(
+        eval_value(&sid, &tid, "window.__ab_menu_clicked"),
+        "pricing"
+    );
+
+    close_session(&sid);
+}
+
+#[test]
+fn menu_times_out_when_item_never_appears() {
+    if skip() {
+        return;
+    }
+    let (sid, tid) = start_session(TEST_URL);
+    let _guard = SessionGuard::new(&sid);
+    install_hover_menu_fixture(&sid, &tid);
+
+    let out = headless_json(
+        &[
+            "browser",
+            "menu",
+            "#ab-menu-trigger",
+            "#ab-menu-missing",
+            "--timeout",
+            "500",
+            "--session",
+            &sid,
+            "--tab",
+            &tid,
+        ],
+        15,
+    );
+    assert_failure(&out, "menu missing item");
+    assert_error_envelope(&parse_json(&out), "TIMEOUT");
+
+    close_session(&sid);
+}
+
 // ========================================================================
 // Group 14: focus — command wiring, success path, and error path
 // ========================================================================
@@ -5301,6 +5614,54 @@ fn upload_relative_path_text() {
     close_session(&sid);
 }
 
+#[test]
+fn upload_validates_files_and_target_input() {
+    if skip() {
+        return;
+    }
+    let (tmp, files) = create_upload_files(&["upload-a.txt", "upload-b.txt"]);
+    let (sid, tid) = start_session(TEST_URL);
+    let _guard = SessionGuard::new(&sid);
+    install_upload_fixture(&sid, &tid);
+    eval_value(
+        &sid,
+        &tid,
+        "(() => { const i = document.createElement('input'); i.type = 'file'; i.id = 'ab-upload-single'; document.body.appendChild(i); return 'ok'; })()",
+    );
+
+    let upload = |selector: &str, paths: &[&str]| {
+        let mut args = vec!["browser", "upload", selector];
+        args.extend_from_slice(paths);
+        args.extend_from_slice(&["--session", &sid, "--tab", &tid]);
+        headless_json(&args, 10)
+    };
+
+    let missing = tmp.path().join("missing.txt");
+    let out = upload("#ab-upload-input", &[missing.to_str().unwrap()]);
+    assert_failure(&out, "upload missing file");
+    assert_error_envelope(&parse_json(&out), "INVALID_ARGUMENT");
+
+    let out = upload("#ab-upload-input", &[tmp.path().to_str().unwrap()]);
+    assert_failure(&out, "upload a directory");
+    assert_error_envelope(&parse_json(&out), "INVALID_ARGUMENT");
+
+    let out = upload("body", &[&files[0]]);
+    assert_failure(&out, "upload to a non-file element");
+    assert_error_envelope(&parse_json(&out), "INVALID_ARGUMENT");
+
+    let out = upload("#ab-upload-single", &[&files[0], &files[1]]);
+    assert_failure(&out, "two files to a single-file input");
+    assert_error_envelope(&parse_json(&out), "INVALID_ARGUMENT");
+
+    let out = upload("#ab-upload-single", &[&files[0]]);
+    assert_success(&out, "one file to a single-file input");
+    let v = parse_json(&out);
+    assert_eq!(
+        v["data"]["value_summary"]["bytes"],
+        std::fs::metadata(&files[0]).unwrap().len()
+    );
+}
+
 // ========================================================================
 // Group 18: eval — command wiring, success path, and error path
 // ========================================================================
@@ -6887,18 +7248,109 @@ fn scroll_into_view_missing_target_text() {
     close_session(&sid);
 }
 
-// ========================================================================
-// Group: scroll-to-center — off-screen element operations
-// ========================================================================
+#[test]
+fn scroll_bottom_wheels_through_an_infinite_feed() {
+    if skip() {
+        return;
+    }
+    let (sid, tid) = start_session(TEST_URL);
+    let _guard = SessionGuard::new(&sid);
 
-/// Inject a page with a sticky header, 2000px spacer, and interactive
-/// elements far below the fold. This tests that commands auto-scroll
-/// elements to viewport center before operating.
-fn install_offscreen_fixture(session_id: &str, tab_id: &str) {
-    let expression = r#"
-(() => {
-  const existing = document.getElementById('ab-offscreen-fixture');
-  if (existing) existing.remove();
+    // A feed that appends ten rows whenever the viewport nears its end, and
+    // counts wheel events so the test can tell wheel from script scrolling.
+    let fixture = r#"(() => {
+  document.body.innerHTML = '<div id="feed"></div>';
+  document.body.style.margin = '0';
+  window.__wheels = 0;
+  window.addEventListener('wheel', () => { window.__wheels += 1; });
+  const feed = document.getElementById('feed');
+  const more = () => {
+    if (feed.children.length >= 60) return;
+    for (let i = 0; i < 10; i++) {
+      const row = document.createElement('div');
+      row.style.height = '100px';
+      row.textContent = 'row ' + feed.children.length;
+      feed.appendChild(row);
+    }
+  };
+  more();
+  window.addEventListener('scroll', () => {
+    if (window.scrollY + window.innerHeight >= document.body.scrollHeight - 200) more();
+  });
+  return 'ok';
+})()"#;
+    assert_eq!(eval_value(&sid, &tid, fixture), "ok");
+
+    let out = headless_json(
+        &[
+            "browser",
+            "scroll",
+            "--bottom",
+            "--session",
+            &sid,
+            "--tab",
+            &tid,
+        ],
+        30,
+    );
+    assert_success(&out, "scroll --bottom");
+    let v = parse_json(&out);
+    assert_eq!(v["data"]["direction"], "bottom");
+    assert_eq!(v["data"]["method"], "wheel");
+    assert!(v["data"]["steps"].as_u64().unwrap() > 1);
+
+    assert_ne!(eval_value(&sid, &tid, "String(window.__wheels)"), "0");
+    // More rows loaded while wheeling, and the page ended at its bottom.
+    assert_eq!(
+        eval_value(
+            &sid,
+            &tid,
+            "String(document.getElementById('feed').children.length > 10)"
+        ),
+        "true"
+    );
+    assert_eq!(
+        eval_value(
+            &sid,
+            &tid,
+            "String(Math.abs(window.scrollY + window.innerHeight - document.documentElement.scrollHeight) < 2)"
+        ),
+        "true"
+    );
+
+    let out = headless_json(
+        &[
+            "browser",
+            "scroll",
+            "--by",
+            "0,-250",
+            "--session",
+            &sid,
+            "--tab",
+            &tid,
+        ],
+        15,
+    );
+    assert_success(&out, "scroll --by");
+    let v = parse_json(&out);
+    assert_eq!(v["data"]["delta"], serde_json::json!({ "x": 0, "y": -250 }));
+    assert_eq!(v["data"]["changed"]["scroll_changed"], true);
+
+    close_session(&sid);
+}
+
+// ========================================================================
+// Group: scroll-to-center — off-screen element operations
+// ========================================================================
+
+/// Inject a page with a sticky header, 2000px spacer, and interactive
+/// elements far below the fold. This tests that commands auto-scroll
+/// elements to viewport center before operating.
+fn install_offscreen_fixture(session_id: &str, tab_id: &str) {
+    let expression = r#"
+(() => {
+  const existing = document.getElementById('ab-offscreen-fixture');
+  if (existing) existing.remove();
 
   window.scrollTo(0, 0);
   document.body.innerHTML = '';
@@ -7286,6 +7738,141 @@ fn click_offscreen_avoids_sticky_header() {
     close_session(&sid);
 }
 
+/// A fixed bottom panel covers the lower 60% of the viewport, so
+/// centre-scrolling alone leaves the button underneath it.
+fn install_occluding_panel_fixture(session_id: &str, tab_id: &str) {
+    let expression = r#"
+(() => {
+  const existing = document.getElementById('ab-occlusion-fixture');
+  if (existing) existing.remove();
+  window.__ab_occluded_clicked = false;
+  const root = document.createElement('div');
+  root.id = 'ab-occlusion-fixture';
+  root.innerHTML = `
+    <div style="height: 150vh"></div>
+    <button id="ab-occluded-btn" style="display: block; height: 40px">Buy</button>
+    <div style="height: 150vh"></div>
+    <div id="ab-panel" style="position: fixed; left: 0; right: 0; top: 40vh; bottom: 0; background: #eee; z-index: 2147483647"></div>
+  `;
+  document.body.appendChild(root);
+  document.getElementById('ab-occluded-btn').addEventListener('click', () => {
+    window.__ab_occluded_clicked = true;
+  });
+  return 'ok';
+})()
+"#;
+    assert_eq!(eval_value(session_id, tab_id, expression), "ok");
+}
+
+#[test]
+fn click_scrolls_clear_of_fixed_panel() {
+    if skip() {
+        return;
+    }
+    let (sid, tid) = start_session(TEST_URL);
+    let _guard = SessionGuard::new(&sid);
+    install_occluding_panel_fixture(&sid, &tid);
+
+    let out = headless_json(
+        &[
+            "browser",
+            "click",
+            "#ab-occluded-btn",
+            "--session",
+            &sid,
+            "--tab",
+            &tid,
+        ],
+        15,
+    );
+    assert_success(&out, "click under fixed panel");
+    let v = parse_json(&out);
+    assert_eq!(v["data"]["occlusion"]["covered_by"], "div#ab-panel");
+    assert_eq!(v["data"]["occlusion"]["resolved"], "scroll");
+    assert_eq!(
+        eval_value(&sid, &tid, "String(window.__ab_occluded_clicked)"),
+        "true",
+        "click must reach the button, not the panel"
+    );
+
+    close_session(&sid);
+}
+
+/// A 200px-wide fixed track that records the offsetX of each click.
+fn install_click_track_fixture(session_id: &str, tab_id: &str) {
+    let expression = r#"
+(() => {
+  const existing = document.getElementById('ab-track');
+  if (existing) existing.remove();
+  window.__ab_track_x = null;
+  const track = document.createElement('div');
+  track.id = 'ab-track';
+  track.style.cssText = 'position: fixed; left: 50px; top: 100px; width: 200px; height: 20px; background: #ccc; z-index: 2147483647';
+  track.addEventListener('click', (e) => { window.__ab_track_x = Math.round(e.clientX - track.getBoundingClientRect().left); });
+  document.body.appendChild(track);
+  const plain = document.createElement('span');
+  plain.id = 'ab-not-slider';
+  plain.textContent = 'not a slider';
+  document.body.appendChild(plain);
+  return 'ok';
+})()
+"#;
+    assert_eq!(eval_value(session_id, tab_id, expression), "ok");
+}
+
+#[test]
+fn click_position_aims_inside_element() {
+    if skip() {
+        return;
+    }
+    let (sid, tid) = start_session(TEST_URL);
+    let _guard = SessionGuard::new(&sid);
+    install_click_track_fixture(&sid, &tid);
+
+    for (position, expected) in [("75%,50%", "150"), ("30,10", "30"), ("top-left", "1")] {
+        let out = headless_json(
+            &[
+                "browser",
+                "click",
+                "#ab-track",
+                "--position",
+                position,
+                "--session",
+                &sid,
+                "--tab",
+                &tid,
+            ],
+            15,
+        );
+        assert_success(&out, &format!("click --position {position}"));
+        assert!(parse_json(&out)["data"]["point"]["x"].is_number());
+        assert_eq!(
+            eval_value(&sid, &tid, "String(window.__ab_track_x)"),
+            expected,
+            "offset for --position {position}"
+        );
+    }
+
+    let out = headless_json(
+        &[
+            "browser",
+            "click",
+            "10,10",
+            "--position",
+            "top-left",
+            "--session",
+            &sid,
+            "--tab",
+            &tid,
+        ],
+        15,
+    );
+    assert_failure(&out, "--position with coordinates");
+    assert_error_envelope(&parse_json(&out), "INVALID_ARGUMENT");
+
+    close_session(&sid);
+}
+
 // ========================================================================
 // Group: contenteditable support — focus fallback + click-to-place-cursor
 // ========================================================================
@@ -7556,3 +8143,1002 @@ void(0)
 
     close_session(&sid);
 }
+
+// ── handoff ───────────────────────────────────────────────────────────
+
+#[test]
+fn contract_handoff_rejects_headless_session() {
+    if skip() {
+        return;
+    }
+    let (sid, tid) = start_session(TEST_URL);
+    let _guard = SessionGuard::new(&sid);
+
+    let out = headless_json(
+        &[
+            "browser",
+            "handoff",
+            "--session",
+            &sid,
+            "--tab",
+            &tid,
+            "--message",
+            "please complete 2FA",
+        ],
+        15,
+    );
+    assert_failure(&out, "handoff on headless session");
+    let v = parse_json(&out);
+    assert_error_envelope(&v, "INVALID_ARGUMENT");
+
+    // The failed handoff must not leave the session paused.
+    let out = headless_json(
+        &["browser", "eval", "1 + 1", "--session", &sid, "--tab", &tid],
+        10,
+    );
+    assert_success(&out, "eval after rejected handoff");
+}
+
+fn install_slider_fixture(session_id: &str, tab_id: &str) {
+    let expression = r#"
+(() => {
+  for (const id of ['ab-range', 'ab-aria-track', 'ab-not-slider']) document.getElementById(id)?.remove();
+  window.__ab_range_events = [];
+  const range = document.createElement('input');
+  range.type = 'range';
+  range.id = 'ab-range';
+  range.min = '0'; range.max = '200'; range.step = '10'; range.value = '0';
+  range.style.cssText = 'position: fixed; left: 50px; top: 40px; width: 200px';
+  for (const t of ['input', 'change']) range.addEventListener(t, () => window.__ab_range_events.push(t));
+  document.body.appendChild(range);
+
+  const track = document.createElement('div');
+  track.id = 'ab-aria-track';
+  track.style.cssText = 'position: fixed; left: 50px; top: 100px; width: 200px; height: 10px; background: #ccc; z-index: 2147483647';
+  const thumb = document.createElement('div');
+  thumb.id = 'ab-aria-thumb';
+  thumb.setAttribute('role', 'slider');
+  thumb.setAttribute('aria-valuemin', '0');
+  thumb.setAttribute('aria-valuemax', '100');
+  thumb.setAttribute('aria-valuenow', '0');
+  thumb.style.cssText = 'position: absolute; left: -8px; top: -3px; width: 16px; height: 16px; background: #333';
+  track.appendChild(thumb);
+  let dragging = false;
+  const moveTo = (x) => {
+    const r = track.getBoundingClientRect();
+    const v = Math.round(Math.min(Math.max((x - r.left) / r.width, 0), 1) * 100);
+    thumb.setAttribute('aria-valuenow', String(v));
+    thumb.style.left = (v * r.width / 100 - 8) + 'px';
+  };
+  thumb.addEventListener('mousedown', () => { dragging = true; });
+  document.addEventListener('mousemove', (e) => { if (dragging) moveTo(e.clientX); });
+  document.addEventListener('mouseup', () => { dragging = false; });
+  document.body.appendChild(track);
+  const plain = document.createElement('span');
+  plain.id = 'ab-not-slider';
+  plain.textContent = 'not a slider';
+  document.body.appendChild(plain);
+  return 'ok';
+})()
+"#;
+    assert_eq!(eval_value(session_id, tab_id, expression), "ok");
+}
+
+#[test]
+fn slide_sets_range_input_and_drags_aria_slider() {
+    if skip() {
+        return;
+    }
+    let (sid, tid) = start_session(TEST_URL);
+    let _guard = SessionGuard::new(&sid);
+    install_slider_fixture(&sid, &tid);
+
+    let slide = |selector: &str, flag: &str, value: &str| {
+        headless_json(
+            &[
+                "browser",
+                "slide",
+                selector,
+                flag,
+                value,
+                "--session",
+                &sid,
+                "--tab",
+                &tid,
+            ],
+            15,
+        )
+    };
+
+    let out = slide("#ab-range", "--to-value", "73");
+    assert_success(&out, "slide range");
+    let v = parse_json(&out);
+    assert_eq!(v["data"]["kind"], "range");
+    assert_eq!(v["data"]["value"], 70.0, "snapped to step 10");
+    assert_eq!(
+        eval_value(&sid, &tid, "window.__ab_range_events.join(',')"),
+        "input,change"
+    );
+
+    let out = slide("#ab-aria-track", "--percent", "60");
+    assert_success(&out, "slide aria");
+    let v = parse_json(&out);
+    assert_eq!(v["data"]["kind"], "aria");
+    let now: f64 = eval_value(
+        &sid,
+        &tid,
+        "document.getElementById('ab-aria-thumb').getAttribute('aria-valuenow')",
+    )
+    .parse()
+    .unwrap();
+    assert!((now - 60.0).abs() <= 1.0, "aria-valuenow {now}");
+
+    let out = slide("#ab-range", "--to-value", "500");
+    assert_failure(&out, "out of range");
+    assert_error_envelope(&parse_json(&out), "INVALID_ARGUMENT");
+
+    let out = slide("#ab-not-slider", "--percent", "10");
+    assert_failure(&out, "not a slider");
+    assert_error_envelope(&parse_json(&out), "INVALID_ARGUMENT");
+}
+
+fn install_date_fixture(session_id: &str, tab_id: &str) {
+    let expression = r#"
+(() => {
+  document.getElementById('ab-dates')?.remove();
+  const box = document.createElement('div');
+  box.id = 'ab-dates';
+  box.innerHTML = '<input type="date" id="ab-native"><input type="text" id="ab-typed"><input type="text" id="ab-cal" readonly>';
+  document.body.prepend(box);
+  window.__ab_date_events = [];
+  document.getElementById('ab-native').addEventListener('change', () => window.__ab_date_events.push('change'));
+
+  const months = ['January','February','March','April','May','June','July','August','September','October','November','December'];
+  const cal = document.getElementById('ab-cal');
+  let shown = { y: 2025, m: 0 };
+  const render = () => {
+    document.querySelector('.ab-calendar')?.remove();
+    const pop = document.createElement('div');
+    pop.className = 'ab-calendar';
+    const days = new Date(shown.y, shown.m + 1, 0).getDate();
+    pop.innerHTML = '<button aria-label="Previous month">‹</button><span>' + months[shown.m] + ' ' + shown.y +
+      '</span><button aria-label="Next month">›</button><div class="days">' +
+      Array.from({ length: days }, (_, i) => '<button class="day">' + (i + 1) + '</button>').join('') + '</div>';
+    pop.querySelectorAll('button')[0].onclick = () => { shown.m--; if (shown.m < 0) { shown.m = 11; shown.y--; } render(); };
+    pop.querySelectorAll('button')[1].onclick = () => { shown.m++; if (shown.m > 11) { shown.m = 0; shown.y++; } render(); };
+    pop.querySelectorAll('.day').forEach(b => b.onclick = () => {
+      cal.value = shown.y + '-' + String(shown.m + 1).padStart(2, '0') + '-' + b.textContent.padStart(2, '0');
+      pop.remove();
+    });
+    box.appendChild(pop);
+  };
+  cal.addEventListener('click', render);
+  return 'ok';
+})()
+"#;
+    assert_eq!(eval_value(session_id, tab_id, expression), "ok");
+}
+
+#[test]
+fn date_sets_native_typed_and_calendar_fields() {
+    if skip() {
+        return;
+    }
+    let (sid, tid) = start_session(TEST_URL);
+    let _guard = SessionGuard::new(&sid);
+    install_date_fixture(&sid, &tid);
+
+    let date = |args: &[&str]| {
+        let mut full = vec!["browser", "date"];
+        full.extend_from_slice(args);
+        full.extend_from_slice(&["--session", &sid, "--tab", &tid]);
+        headless_json(&full, 30)
+    };
+
+    let out = date(&["#ab-native", "2025-03-14"]);
+    assert_success(&out, "date native");
+    let v = parse_json(&out);
+    assert_eq!(v["data"]["strategy"], "native");
+    assert_eq!(v["data"]["value"], "2025-03-14");
+    assert_eq!(
+        eval_value(&sid, &tid, "window.__ab_date_events.join(',')"),
+        "change"
+    );
+
+    let out = date(&["#ab-typed", "2025-03-14", "--format", "MM/DD/YYYY"]);
+    assert_success(&out, "date typed");
+    let v = parse_json(&out);
+    assert_eq!(v["data"]["strategy"], "type");
+    assert_eq!(v["data"]["value"], "03/14/2025");
+
+    let out = date(&["#ab-cal", "2025-03-14", "--strategy", "calendar"]);
+    assert_success(&out, "date calendar");
+    assert_eq!(parse_json(&out)["data"]["value"], "2025-03-14");
+
+    let out = date(&["#ab-native", "2025-02-30"]);
+    assert_failure(&out, "impossible date");
+    assert_error_envelope(&parse_json(&out), "INVALID_ARGUMENT");
+
+    let out = date(&["#ab-typed", "2025-03-14", "--strategy", "native"]);
+    assert_failure(&out, "native on a text field");
+    assert_error_envelope(&parse_json(&out), "INVALID_ARGUMENT");
+}
+
+/// A tiny model-backed editor in the style of ProseMirror/Slate: it cancels
+/// every `beforeinput` and re-renders its paragraphs from its own state, so
+/// anything that bypasses the input pipeline is lost on the next render.
+fn install_rich_editor_fixture(session_id: &str, tab_id: &str) {
+    let expression = r#"
+(() => {
+  document.getElementById('ab-rich')?.remove();
+  const ed = document.createElement('div');
+  ed.id = 'ab-rich';
+  ed.contentEditable = 'true';
+  document.body.prepend(ed);
+  window.__ab_rich_model = ['seed'];
+  window.__ab_rich_inputs = [];
+  const render = () => {
+    ed.replaceChildren(...window.__ab_rich_model.map(t => {
+      const p = document.createElement('p');
+      p.textContent = t;
+      if (!t) p.appendChild(document.createElement('br'));
+      return p;
+    }));
+    const range = document.createRange();
+    range.selectNodeContents(ed.lastChild);
+    range.collapse(false);
+    const sel = window.getSelection();
+    sel.removeAllRanges();
+    sel.addRange(range);
+  };
+  ed.addEventListener('beforeinput', (e) => {
+    e.preventDefault();
+    window.__ab_rich_inputs.push(e.inputType);
+    const model = window.__ab_rich_model;
+    if (!window.getSelection().isCollapsed) model.splice(0, model.length, '');
+    if (e.inputType === 'insertText') model[model.length - 1] += e.data;
+    if (e.inputType === 'insertParagraph') model.push('');
+    render();
+  });
+  render();
+  return 'ok';
+})()
+"#;
+    assert_eq!(eval_value(session_id, tab_id, expression), "ok");
+}
+
+#[test]
+fn fill_rich_builds_paragraphs_through_editor_input() {
+    if skip() {
+        return;
+    }
+    let (sid, tid) = start_session(TEST_URL);
+    let _guard = SessionGuard::new(&sid);
+    install_rich_editor_fixture(&sid, &tid);
+
+    let out = headless_json(
+        &[
+            "browser",
+            "fill",
+            "#ab-rich",
+            "Hello\nworld",
+            "--session",
+            &sid,
+            "--tab",
+            &tid,
+        ],
+        15,
+    );
+    assert_success(&out, "fill contenteditable");
+    assert_eq!(parse_json(&out)["data"]["mode"], "rich");
+    assert_eq!(
+        eval_value(&sid, &tid, "JSON.stringify(window.__ab_rich_model)"),
+        r#"["Hello","world"]"#
+    );
+    assert_eq!(
+        eval_value(
+            &sid,
+            &tid,
+            "[...document.querySelectorAll('#ab-rich p')].map(p => p.textContent).join('|')"
+        ),
+        "Hello|world"
+    );
+    assert_eq!(
+        eval_value(&sid, &tid, "window.__ab_rich_inputs.join(',')"),
+        "insertText,insertParagraph,insertText"
+    );
+
+    // --rich on a plain input goes through insertText too: input events fire
+    // without a direct value write.
+    install_fill_fixture(&sid, &tid);
+    let out = headless_json(
+        &[
+            "browser",
+            "fill",
+            "#ab-fill-input",
+            "abc",
+            "--rich",
+            "--session",
+            &sid,
+            "--tab",
+            &tid,
+        ],
+        15,
+    );
+    assert_success(&out, "fill --rich input");
+    assert_eq!(
+        eval_value(&sid, &tid, "document.querySelector('#ab-fill-input').value"),
+        "abc"
+    );
+    assert_eq!(
+        eval_value(&sid, &tid, "String(window.__ab_fill_input_count)"),
+        "1"
+    );
+}
+
+fn install_canvas_fixture(session_id: &str, tab_id: &str) {
+    let expression = r#"
+(() => {
+  document.getElementById('ab-canvas')?.remove();
+  const c = document.createElement('canvas');
+  c.id = 'ab-canvas';
+  c.width = 400;
+  c.height = 200;
+  c.style.cssText = 'display:block;width:200px;height:100px;border:5px solid #000';
+  document.body.prepend(c);
+  const g = c.getContext('2d');
+  g.fillStyle = '#ffffff';
+  g.fillRect(0, 0, 400, 200);
+  g.fillStyle = '#ff0000';
+  g.fillRect(300, 40, 40, 40);
+  window.__ab_canvas_events = [];
+  const local = (e) => {
+    const r = c.getBoundingClientRect();
+    return Math.round(e.clientX - r.left - 5) + ',' + Math.round(e.clientY - r.top - 5);
+  };
+  c.addEventListener('click', (e) => window.__ab_canvas_events.push('click:' + local(e)));
+  c.addEventListener('mousedown', (e) => window.__ab_canvas_events.push('down:' + local(e)));
+  c.addEventListener('mouseup', (e) => window.__ab_canvas_events.push('up:' + local(e)));
+  return 'ok';
+})()
+"#;
+    assert_eq!(eval_value(session_id, tab_id, expression), "ok");
+}
+
+#[test]
+fn canvas_click_color_and_draw_hit_canvas_pixels() {
+    if skip() {
+        return;
+    }
+    let (sid, tid) = start_session(TEST_URL);
+    let _guard = SessionGuard::new(&sid);
+    install_canvas_fixture(&sid, &tid);
+
+    // The red square covers bitmap 300..340 x 40..80, i.e. CSS 150..170 x 20..40.
+    let out = headless_json(
+        &[
+            "browser",
+            "canvas",
+            "click-color",
+            "#ab-canvas",
+            "#ff0000",
+            "--session",
+            &sid,
+            "--tab",
+            &tid,
+        ],
+        15,
+    );
+    assert_success(&out, "canvas click-color");
+    let v = parse_json(&out);
+    assert_eq!(v["data"]["matches"], 1600);
+    let events = eval_value(&sid, &tid, "window.__ab_canvas_events.join(' ')");
+    let click = events
+        .split(' ')
+        .find_map(|e| e.strip_prefix("click:"))
+        .unwrap_or_else(|| panic!("no click recorded: {events}"));
+    let (x, y) = click.split_once(',').unwrap();
+    let (x, y): (i64, i64) = (x.parse().unwrap(), y.parse().unwrap());
+    assert!(
+        (150..=170).contains(&x) && (20..=40).contains(&y),
+        "{click}"
+    );
+
+    let out = headless_json(
+        &[
+            "browser",
+            "canvas",
+            "click-color",
+            "#ab-canvas",
+            "#00ff00",
+            "--session",
+            &sid,
+            "--tab",
+            &tid,
+        ],
+        15,
+    );
+    assert_failure(&out, "colour not drawn");
+    assert_error_envelope(&parse_json(&out), "COLOR_NOT_FOUND");
+
+    let dir = tempfile::tempdir().expect("temp dir");
+    let path = dir.path().join("points.json");
+    std::fs::write(
+        &path,
+        "[[[10, 10], [50, 30], [90, 10]], [[20, 80], [180, 80]]]",
+    )
+    .unwrap();
+    eval_value(&sid, &tid, "window.__ab_canvas_events = []; 'ok'");
+    let out = headless_json(
+        &[
+            "browser",
+            "canvas",
+            "draw",
+            "#ab-canvas",
+            "--path",
+            path.to_str().unwrap(),
+            "--session",
+            &sid,
+            "--tab",
+            &tid,
+        ],
+        15,
+    );
+    assert_success(&out, "canvas draw");
+    let v = parse_json(&out);
+    assert_eq!(v["data"]["strokes"], 2);
+    assert_eq!(v["data"]["points"], 5);
+    let events = eval_value(
+        &sid,
+        &tid,
+        "window.__ab_canvas_events.filter(e => !e.startsWith('click')).join(' ')",
+    );
+    assert_eq!(events, "down:10,10 up:90,10 down:20,80 up:180,80");
+}
+
+fn install_shadow_fixture(session_id: &str, tab_id: &str) {
+    let expression = r#"
+(() => {
+  document.getElementById('ab-shadow-host')?.remove();
+  window.__ab_shadow_clicks = 0;
+  const host = document.createElement('div');
+  host.id = 'ab-shadow-host';
+  document.body.prepend(host);
+  const outer = host.attachShadow({ mode: 'open' });
+  outer.innerHTML = '<input id="ab-shadow-input"><div class="inner-host"></div>';
+  const inner = outer.querySelector('.inner-host').attachShadow({ mode: 'open' });
+  inner.innerHTML = '<button class="ab-shadow-btn">Deep</button>';
+  inner.querySelector('button').addEventListener('click', () => { window.__ab_shadow_clicks++; });
+  return 'ok';
+})()
+"#;
+    assert_eq!(eval_value(session_id, tab_id, expression), "ok");
+}
+
+#[test]
+fn selectors_pierce_open_shadow_roots() {
+    if skip() {
+        return;
+    }
+    let (sid, tid) = start_session(TEST_URL);
+    let _guard = SessionGuard::new(&sid);
+    install_shadow_fixture(&sid, &tid);
+
+    // A plain selector finds the button two shadow roots down.
+    let out = headless_json(
+        &[
+            "browser",
+            "click",
+            ".ab-shadow-btn",
+            "--session",
+            &sid,
+            "--tab",
+            &tid,
+        ],
+        15,
+    );
+    assert_success(&out, "click inside nested shadow root");
+
+    // `>>>` walks the shadow roots explicitly.
+    let out = headless_json(
+        &[
+            "browser",
+            "click",
+            "#ab-shadow-host >>> .inner-host >>> button",
+            "--session",
+            &sid,
+            "--tab",
+            &tid,
+        ],
+        15,
+    );
+    assert_success(&out, "click through >>> path");
+    assert_eq!(
+        eval_value(&sid, &tid, "String(window.__ab_shadow_clicks)"),
+        "2"
+    );
+
+    let out = headless_json(
+        &[
+            "browser",
+            "fill",
+            "#ab-shadow-input",
+            "inside",
+            "--session",
+            &sid,
+            "--tab",
+            &tid,
+        ],
+        15,
+    );
+    assert_success(&out, "fill inside shadow root");
+    assert_eq!(
+        eval_value(
+            &sid,
+            &tid,
+            "document.querySelector('#ab-shadow-host').shadowRoot.querySelector('input').value"
+        ),
+        "inside"
+    );
+
+    let out = headless_json(
+        &[
+            "browser",
+            "wait",
+            "element",
+            "#ab-shadow-host >>> .inner-host >>> button",
+            "--timeout",
+            "2000",
+            "--session",
+            &sid,
+            "--tab",
+            &tid,
+        ],
+        15,
+    );
+    assert_success(&out, "wait element inside shadow root");
+
+    // A path that matches nothing is reported like any other selector.
+    let out = headless_json(
+        &[
+            "browser",
+            "click",
+            "#ab-shadow-host >>> button.missing",
+            "--session",
+            &sid,
+            "--tab",
+            &tid,
+        ],
+        15,
+    );
+    assert_failure(&out, "click missing shadow element");
+    assert_eq!(parse_json(&out)["error"]["code"], "ELEMENT_NOT_FOUND");
+}
+
+fn install_text_role_fixture(session_id: &str, tab_id: &str) {
+    let expression = r#"
+(() => {
+  document.getElementById('ab-engines')?.remove();
+  window.__ab_engine_clicks = [];
+  const box = document.createElement('div');
+  box.id = 'ab-engines';
+  box.innerHTML = '<p><span>Sign in to continue</span></p>'
+    + '<button id="b-sign">Sign in</button>'
+    + '<div role="button" id="b-checkout" aria-label="Checkout">Pay</div>'
+    + '<input type="submit" id="b-send" value="Send now">';
+  box.addEventListener('click', (e) => { window.__ab_engine_clicks.push(e.target.id || e.target.tagName); });
+  document.body.prepend(box);
+  return 'ok';
+})()
+"#;
+    assert_eq!(eval_value(session_id, tab_id, expression), "ok");
+}
+
+#[test]
+fn text_and_role_selectors_resolve_elements() {
+    if skip() {
+        return;
+    }
+    let (sid, tid) = start_session(TEST_URL);
+    let _guard = SessionGuard::new(&sid);
+    install_text_role_fixture(&sid, &tid);
+
+    for selector in [
+        "text=\"Sign in\"",
+        "role=button[name=\"Checkout\"]",
+        "text=send NOW",
+        "text=to continue",
+    ] {
+        let out = headless_json(
+            &[
+                "browser",
+                "click",
+                selector,
+                "--session",
+                &sid,
+                "--tab",
+                &tid,
+            ],
+            15,
+        );
+        assert_success(&out, &format!("click {selector}"));
+    }
+    assert_eq!(
+        eval_value(&sid, &tid, "window.__ab_engine_clicks.join(',')"),
+        "b-sign,b-checkout,b-send,SPAN"
+    );
+
+    let out = headless_json(
+        &[
+            "browser",
+            "click",
+            "role=button[name=\"Nope\"]",
+            "--session",
+            &sid,
+            "--tab",
+            &tid,
+        ],
+        15,
+    );
+    assert_failure(&out, "click missing role");
+    assert_eq!(parse_json(&out)["error"]["code"], "ELEMENT_NOT_FOUND");
+
+    let out = headless_json(
+        &[
+            "browser",
+            "click",
+            "role=button[label=x]",
+            "--session",
+            &sid,
+            "--tab",
+            &tid,
+        ],
+        15,
+    );
+    assert_failure(&out, "click malformed role selector");
+    assert_eq!(parse_json(&out)["error"]["code"], "INVALID_SELECTOR");
+}
+
+fn install_rerender_fixture(session_id: &str, tab_id: &str, labels: &[&str]) {
+    let labels = serde_json::to_string(labels).unwrap();
+    let expression = format!(
+        r#"
+(() => {{
+  const existing = document.getElementById('ab-rerender-fixture');
+  if (existing) existing.remove();
+  window.__ab_rerender_clicks = 0;
+  const root = document.createElement('div');
+  root.id = 'ab-rerender-fixture';
+  window.__ab_render = (labels) => {{
+    root.replaceChildren();
+    labels.forEach((label) => {{
+      const button = document.createElement('button');
+      button.textContent = label;
+      button.addEventListener('click', () => {{ window.__ab_rerender_clicks += 1; }});
+      root.appendChild(button);
+    }});
+  }};
+  window.__ab_render({labels});
+  document.body.prepend(root);
+  return true;
+}})()
+"#
+    );
+    let _ = eval_json_with_flags(session_id, tab_id, &expression, &[]);
+}
+
+fn snapshot_button_ref(session_id: &str, tab_id: &str, button_name: &str) -> String {
+    let out = headless_json(
+        &[
+            "browser",
+            "snapshot",
+            "--session",
+            session_id,
+            "--tab",
+            tab_id,
+        ],
+        15,
+    );
+    assert_success(&out, "snapshot for button ref");
+    let v = parse_json(&out);
+    let nodes = v["data"]["nodes"]
+        .as_array()
+        .expect("snapshot nodes must be an array");
+    for node in nodes {
+        if node["role"] == "button" && node["name"] == button_name {
+            let r = node["ref"].as_str().unwrap();
+            return format!("@{r}");
+        }
+    }
+    panic!("button ref not found for name='{button_name}' in snapshot nodes");
+}
+
+#[test]
+fn ref_follows_an_element_the_page_re_rendered() {
+    if skip() {
+        return;
+    }
+    let (sid, tid) = start_session(TEST_URL);
+    let _guard = SessionGuard::new(&sid);
+    install_rerender_fixture(&sid, &tid, &["Save draft"]);
+    let save_ref = snapshot_button_ref(&sid, &tid, "Save draft");
+
+    // Replace the button with an identical new node, as a framework re-render does.
+    eval_value(&sid, &tid, "window.__ab_render(['Save draft']), ''");
+    let out = headless_json(
+        &[
+            "browser",
+            "click",
+            &save_ref,
+            "--session",
+            &sid,
+            "--tab",
+            &tid,
+        ],
+        15,
+    );
+    assert_success(&out, "click re-rendered ref");
+    assert_eq!(
+        eval_value(&sid, &tid, "String(window.__ab_rerender_clicks)"),
+        "1"
+    );
+
+    // Two buttons now match role and name: the ref must not pick one.
+    eval_value(
+        &sid,
+        &tid,
+        "window.__ab_render(['Save draft', 'Save draft']), ''",
+    );
+    let out = headless_json(
+        &[
+            "browser",
+            "click",
+            &save_ref,
+            "--session",
+            &sid,
+            "--tab",
+            &tid,
+        ],
+        15,
+    );
+    assert_failure(&out, "click ambiguous ref");
+    let v = parse_json(&out);
+    assert_eq!(v["error"]["code"], "REF_STALE");
+    assert_eq!(
+        eval_value(&sid, &tid, "String(window.__ab_rerender_clicks)"),
+        "1"
+    );
+
+    close_session(&sid);
+}
+
+fn install_touch_fixture(session_id: &str, tab_id: &str) {
+    let expression = r#"
+(() => {
+  const existing = document.getElementById('ab-touch-fixture');
+  if (existing) existing.remove();
+  window.__ab_touch = { taps: 0, clicks: 0, moves: [], fingers: 0 };
+  const root = document.createElement('div');
+  root.id = 'ab-touch-fixture';
+  root.style.cssText = 'position: fixed; top: 100px; left: 40px; width: 300px; height: 300px; background: #ddd; touch-action: none; z-index: 2147483647;';
+  root.addEventListener('touchstart', (e) => {
+    window.__ab_touch.taps += 1;
+    window.__ab_touch.fingers = Math.max(window.__ab_touch.fingers, e.touches.length);
+  });
+  root.addEventListener('touchmove', (e) => {
+    window.__ab_touch.moves.push(e.touches[0].clientX + ',' + e.touches[0].clientY);
+  });
+  root.addEventListener('click', () => { window.__ab_touch.clicks += 1; });
+  document.body.appendChild(root);
+  return true;
+})()
+"#;
+    let _ = eval_json_with_flags(session_id, tab_id, expression, &[]);
+}
+
+#[test]
+fn touch_tap_swipe_and_pinch_reach_a_touch_only_page() {
+    if skip() {
+        return;
+    }
+    let (sid, tid) = start_session(TEST_URL);
+    let _guard = SessionGuard::new(&sid);
+    let out = headless_json(&["browser", "emulate", "iphone-14", "--session", &sid], 10);
+    assert_success(&out, "emulate iphone-14");
+    install_touch_fixture(&sid, &tid);
+
+    let out = headless_json(
+        &[
+            "browser",
+            "tap",
+            "#ab-touch-fixture",
+            "--session",
+            &sid,
+            "--tab",
+            &tid,
+        ],
+        15,
+    );
+    assert_success(&out, "tap");
+    let v = parse_json(&out);
+    assert_eq!(v["command"], "browser tap");
+    assert_eq!(v["data"]["target"]["selector"], "#ab-touch-fixture");
+    assert!(
+        v["meta"]["warnings"]
+            .as_array()
+            .is_none_or(|w| w.is_empty())
+    );
+    assert_eq!(
+        eval_value(&sid, &tid, "String(window.__ab_touch.taps)"),
+        "1"
+    );
+    assert_eq!(
+        eval_value(&sid, &tid, "String(window.__ab_touch.clicks)"),
+        "1"
+    );
+
+    let out = headless_json(
+        &[
+            "browser",
+            "swipe",
+            "left",
+            "--from",
+            "#ab-touch-fixture",
+            "--distance",
+            "100",
+            "--session",
+            &sid,
+            "--tab",
+            &tid,
+        ],
+        15,
+    );
+    assert_success(&out, "swipe left");
+    let v = parse_json(&out);
+    let from_x = v["data"]["from"]["x"].as_f64().unwrap();
+    assert_eq!(v["data"]["to"]["x"].as_f64().unwrap(), from_x - 100.0);
+    let last_move = eval_value(&sid, &tid, "window.__ab_touch.moves.at(-1) || ''");
+    let last_x: f64 = last_move.split(',').next().unwrap().parse().unwrap();
+    assert!(
+        (last_x - (from_x - 100.0)).abs() < 2.0,
+        "last touchmove at {last_move}"
+    );
+
+    let out = headless_json(
+        &[
+            "browser",
+            "pinch",
+            "out",
+            "--at",
+            "#ab-touch-fixture",
+            "--session",
+            &sid,
+            "--tab",
+            &tid,
+        ],
+        15,
+    );
+    assert_success(&out, "pinch out");
+    assert_eq!(parse_json(&out)["data"]["scale"], 2.0);
+    assert_eq!(
+        eval_value(&sid, &tid, "String(window.__ab_touch.fingers)"),
+        "2"
+    );
+
+    let out = headless_json(
+        &[
+            "browser",
+            "pinch",
+            "in",
+            "--scale",
+            "2",
+            "--session",
+            &sid,
+            "--tab",
+            &tid,
+        ],
+        15,
+    );
+    assert_failure(&out, "pinch in with a growing scale");
+    assert_eq!(parse_json(&out)["error"]["code"], "INVALID_ARGUMENT");
+
+    close_session(&sid);
+}
+
+#[test]
+fn press_runs_a_config_macro_as_a_key_sequence() {
+    if skip() {
+        return;
+    }
+    let env = SoloEnv::new();
+    std::fs::write(
+        env.config_path(),
+        "version = 1\n\n[macros]\nnext_field = \"Tab Tab\"\n",
+    )
+    .expect("write config");
+
+    let out = env.headless_json(
+        &[
+            "browser",
+            "start",
+            "--mode",
+            "local",
+            "--headless",
+            "--open-url",
+            TEST_URL,
+        ],
+        30,
+    );
+    assert_success(&out, "start");
+    let v = parse_json(&out);
+    let sid = v["data"]["session"]["session_id"]
+        .as_str()
+        .unwrap()
+        .to_string();
+    let tid = v["data"]["tab"]["tab_id"].as_str().unwrap().to_string();
+    let eval = |expression: &str| {
+        let out = env.headless_json(
+            &[
+                "browser",
+                "eval",
+                expression,
+                "--session",
+                &sid,
+                "--tab",
+                &tid,
+            ],
+            15,
+        );
+        assert_success(&out, "eval");
+        parse_json(&out)["data"]["value"].clone()
+    };
+    eval(
+        "['a', 'b', 'c'].forEach((id) => { const i = document.createElement('input'); \
+         i.id = 'ab-macro-' + id; document.body.prepend(i); }); \
+         document.getElementById('ab-macro-c').focus(); 'ok'",
+    );
+
+    let out = env.headless_json(
+        &[
+            "browser",
+            "press",
+            "@next_field",
+            "--session",
+            &sid,
+            "--tab",
+            &tid,
+        ],
+        15,
+    );
+    assert_success(&out, "press @next_field");
+    let v = parse_json(&out);
+    assert_eq!(v["data"]["keys"], "@next_field");
+    assert_eq!(v["data"]["sequence"], serde_json::json!(["Tab", "Tab"]));
+    assert_eq!(eval("document.activeElement.id"), "ab-macro-a");
+
+    let out = env.headless_json(
+        &[
+            "browser",
+            "press",
+            "@missing",
+            "--session",
+            &sid,
+            "--tab",
+            &tid,
+        ],
+        15,
+    );
+    assert_failure(&out, "press unknown macro");
+    let v = parse_json(&out);
+    assert_eq!(v["error"]["code"], "INVALID_ARGUMENT");
+    assert!(
+        v["error"]["hint"]
+            .as_str()
+            .unwrap_or("")
+            .contains("next_field"),
+        "hint should list the defined macros: {}",
+        v["error"]
+    );
+
+    env.headless(&["browser", "close", "--session", &sid], 30);
+}
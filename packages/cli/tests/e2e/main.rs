@@ -15,26 +15,35 @@ mod browser_lifecycle;
 mod cloud_mode;
 mod cookies;
 mod describe_state;
+mod downloads;
 mod element_details;
 mod element_read;
 mod extension;
+mod fetch;
 mod harness;
 mod iframe;
 mod inspect_point;
 mod interaction;
 mod logs;
 mod manual;
+mod meta;
 mod navigation;
 mod network;
 mod network_har;
+mod network_route;
 mod page_info;
 mod pdf;
 mod query;
+mod record;
+mod run;
+mod save;
 mod screenshot;
 mod search;
+mod serve;
 mod snapshot;
 mod storage;
 mod tab_management;
 mod timeout;
+mod trace;
 mod wait;
 mod windows_daemon;
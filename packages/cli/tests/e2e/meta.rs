@@ -0,0 +1,495 @@
+//! E2E tests for `browser meta`, `browser feeds`, `browser images` and `browser table`.
+
+use crate::harness::{
+    SessionGuard, assert_failure, assert_success, headless, headless_json, parse_json, skip,
+    stdout_str, unique_session, url_images, wait_page_ready,
+};
+
+fn start_session() -> (String, String) {
+    let (sid, profile) = unique_session("s");
+    let out = headless_json(
+        &[
+            "browser",
+            "start",
+            "--mode",
+            "local",
+            "--headless",
+            "--set-session-id",
+            &sid,
+            "--profile",
+            &profile,
+            "--open-url",
+            "about:blank",
+        ],
+        30,
+    );
+    assert_success(&out, "start session");
+    let v = parse_json(&out);
+    let sid = v["data"]["session"]["session_id"]
+        .as_str()
+        .unwrap()
+        .to_string();
+    let tid = v["data"]["tab"]["tab_id"].as_str().unwrap().to_string();
+
+    let goto_out = headless_json(
+        &[
+            "browser",
+            "goto",
+            "about:blank",
+            "--session",
+            &sid,
+            "--tab",
+            &tid,
+        ],
+        30,
+    );
+    assert_success(&goto_out, "goto about:blank");
+    wait_page_ready(&sid, &tid);
+
+    (sid, tid)
+}
+
+fn inject_fixture(sid: &str, tid: &str) {
+    let js = r#"document.head.innerHTML = `
+  <title>Meta Fixture</title>
+  <meta name="description" content="A page with metadata">
+  <meta property="og:title" content="OG Title">
+  <meta property="og:image" content="https://example.com/a.png">
+  <meta property="og:image" content="https://example.com/b.png">
+  <meta name="twitter:card" content="summary">
+  <link rel="canonical" href="https://example.com/canonical">
+  <script type="application/ld+json">{"@type": "Article", "headline": "Hello"}</script>
+  <script type="application/ld+json">{not json</script>
+`;
+void(0)"#;
+    let out = headless_json(&["browser", "eval", js, "--session", sid, "--tab", tid], 10);
+    assert_success(&out, "inject fixture");
+}
+
+fn assert_meta(v: &serde_json::Value) {
+    assert!(v["meta"]["duration_ms"].is_number());
+    assert!(v["meta"]["warnings"].is_array());
+    assert!(v["meta"]["pagination"].is_null());
+    assert!(v["meta"]["truncated"].is_boolean());
+}
+
+#[test]
+fn meta_json_happy_path() {
+    if skip() {
+        return;
+    }
+
+    let (sid, tid) = start_session();
+    let _guard = SessionGuard::new(&sid);
+    inject_fixture(&sid, &tid);
+
+    let out = headless_json(&["browser", "meta", "--session", &sid, "--tab", &tid], 10);
+    assert_success(&out, "meta json");
+    let v = parse_json(&out);
+
+    assert_eq!(v["command"], "browser meta");
+    assert_eq!(v["ok"], true);
+    assert_meta(&v);
+    assert_eq!(v["context"]["title"], "Meta Fixture");
+    let data = &v["data"];
+    assert_eq!(data["title"], "Meta Fixture");
+    assert_eq!(data["description"], "A page with metadata");
+    assert_eq!(data["canonical"], "https://example.com/canonical");
+    assert_eq!(data["og"]["title"], "OG Title");
+    assert_eq!(data["og"]["image"].as_array().map(|a| a.len()), Some(2));
+    assert_eq!(data["twitter"]["card"], "summary");
+    assert_eq!(data["json_ld"][0]["@type"], "Article");
+    assert_eq!(data["json_ld_errors"][0]["index"], 1);
+}
+
+#[test]
+fn meta_text_output() {
+    if skip() {
+        return;
+    }
+
+    let (sid, tid) = start_session();
+    let _guard = SessionGuard::new(&sid);
+    inject_fixture(&sid, &tid);
+
+    let out = headless(&["browser", "meta", "--session", &sid, "--tab", &tid], 10);
+    assert_success(&out, "meta text");
+    let text = stdout_str(&out);
+    assert!(
+        text.contains("description: A page with metadata"),
+        "got: {text}"
+    );
+    assert!(text.contains("og:title: OG Title"), "got: {text}");
+    assert!(text.contains("json_ld: 1 (Article)"), "got: {text}");
+}
+
+#[test]
+fn meta_missing_session_fails() {
+    if skip() {
+        return;
+    }
+
+    let out = headless_json(
+        &[
+            "browser",
+            "meta",
+            "--session",
+            "nope-session",
+            "--tab",
+            "t1",
+        ],
+        10,
+    );
+    assert_failure(&out, "meta missing session");
+    let v = parse_json(&out);
+    assert_eq!(v["error"]["code"], "SESSION_NOT_FOUND");
+    assert!(v["context"].is_null());
+}
+
+#[test]
+fn feeds_discovers_alternate_links() {
+    if skip() {
+        return;
+    }
+
+    let (sid, tid) = start_session();
+    let _guard = SessionGuard::new(&sid);
+    let js = r#"document.head.innerHTML = `
+  <link rel="alternate" type="application/rss+xml" title="Posts" href="https://example.com/rss.xml">
+  <link rel="alternate" type="application/atom+xml" href="https://example.com/atom.xml">
+  <link rel="alternate" type="application/rss+xml" href="https://example.com/rss.xml">
+  <link rel="alternate" hreflang="de" href="https://example.com/de/">
+`;
+void(0)"#;
+    let out = headless_json(
+        &["browser", "eval", js, "--session", &sid, "--tab", &tid],
+        10,
+    );
+    assert_success(&out, "inject feeds fixture");
+
+    let out = headless_json(&["browser", "feeds", "--session", &sid, "--tab", &tid], 10);
+    assert_success(&out, "feeds json");
+    let v = parse_json(&out);
+    assert_eq!(v["command"], "browser feeds");
+    assert_eq!(v["data"]["count"], 2);
+    assert_eq!(v["data"]["feeds"][0]["format"], "rss");
+    assert_eq!(v["data"]["feeds"][0]["title"], "Posts");
+    assert_eq!(v["data"]["feeds"][1]["format"], "atom");
+    assert!(v["data"]["feeds"][0]["feed"].is_null());
+}
+
+#[test]
+fn images_lists_filters_and_downloads_with_session_cookies() {
+    if skip() {
+        return;
+    }
+
+    let (sid, tid) = start_session();
+    let _guard = SessionGuard::new(&sid);
+    let out = headless_json(
+        &[
+            "browser",
+            "goto",
+            &url_images(),
+            "--session",
+            &sid,
+            "--tab",
+            &tid,
+        ],
+        30,
+    );
+    assert_success(&out, "goto images fixture");
+    wait_page_ready(&sid, &tid);
+
+    let out = headless_json(&["browser", "images", "--session", &sid, "--tab", &tid], 10);
+    assert_success(&out, "images json");
+    let v = parse_json(&out);
+    assert_eq!(v["command"], "browser images");
+    assert_eq!(v["data"]["count"], 3);
+    assert_eq!(v["data"]["images"][0]["alt"], "Block");
+    assert_eq!(v["data"]["images"][0]["width"], 120);
+    assert_eq!(v["data"]["images"][0]["height"], 80);
+
+    let dir = tempfile::tempdir().expect("create image dir");
+    let dir_str = dir.path().to_string_lossy().to_string();
+    let out = headless_json(
+        &[
+            "browser",
+            "images",
+            "--min-size",
+            "100x50",
+            "--download",
+            &dir_str,
+            "--session",
+            &sid,
+            "--tab",
+            &tid,
+        ],
+        30,
+    );
+    assert_success(&out, "images --download");
+    let v = parse_json(&out);
+    assert_eq!(v["data"]["count"], 2, "8x8 icon is below --min-size");
+    assert_eq!(v["data"]["downloaded"], 2);
+    for img in v["data"]["images"].as_array().unwrap() {
+        let path = img["path"].as_str().expect("downloaded path");
+        let body = std::fs::read_to_string(path).expect("read downloaded image");
+        assert!(body.contains("<svg"), "{path}: {body}");
+    }
+
+    let out = headless_json(
+        &[
+            "browser",
+            "images",
+            "--selector",
+            "#missing",
+            "--session",
+            &sid,
+            "--tab",
+            &tid,
+        ],
+        10,
+    );
+    assert_failure(&out, "images with missing --selector");
+    assert_eq!(parse_json(&out)["error"]["code"], "ELEMENT_NOT_FOUND");
+}
+
+fn inject_table(sid: &str, tid: &str) {
+    let js = r#"document.body.innerHTML = `
+  <table id="prices">
+    <thead>
+      <tr><th rowspan="2">Item</th><th colspan="2">Price</th></tr>
+      <tr><th>Net</th><th>Gross</th></tr>
+    </thead>
+    <tbody>
+      <tr><td><a href="/tea">Tea</a></td><td>3</td><td rowspan="2">4</td></tr>
+      <tr><td>Coffee, black</td><td>3</td></tr>
+      <tr><td colspan="3"><table><tr><td>nested</td></tr></table></td></tr>
+    </tbody>
+  </table>`;
+void(0)"#;
+    let out = headless_json(&["browser", "eval", js, "--session", sid, "--tab", tid], 10);
+    assert_success(&out, "inject table");
+}
+
+#[test]
+fn table_json_expands_spans_and_lists_links() {
+    if skip() {
+        return;
+    }
+
+    let (sid, tid) = start_session();
+    let _guard = SessionGuard::new(&sid);
+    inject_table(&sid, &tid);
+
+    let out = headless_json(
+        &[
+            "browser",
+            "table",
+            "#prices",
+            "--format",
+            "json",
+            "--session",
+            &sid,
+            "--tab",
+            &tid,
+        ],
+        10,
+    );
+    assert_success(&out, "table json");
+    let data = &parse_json(&out)["data"];
+    assert_eq!(
+        data["headers"],
+        serde_json::json!(["Item", "Price / Net", "Price / Gross"])
+    );
+    assert_eq!(data["row_count"], 3);
+    assert_eq!(data["rows"][1]["Item"], "Coffee, black");
+    // The rowspan repeats its value into the next row.
+    assert_eq!(data["rows"][1]["Price / Gross"], "4");
+    assert_eq!(data["links"][0]["column"], "Item");
+    assert!(
+        data["links"][0]["href"].as_str().unwrap().ends_with("/tea"),
+        "{}",
+        data["links"]
+    );
+}
+
+#[test]
+fn table_text_prints_csv() {
+    if skip() {
+        return;
+    }
+
+    let (sid, tid) = start_session();
+    let _guard = SessionGuard::new(&sid);
+    inject_table(&sid, &tid);
+
+    let out = headless(
+        &[
+            "browser",
+            "table",
+            "#prices",
+            "--session",
+            &sid,
+            "--tab",
+            &tid,
+        ],
+        10,
+    );
+    assert_success(&out, "table text");
+    let text = stdout_str(&out);
+    assert!(
+        text.contains("Item,Price / Net,Price / Gross\nTea,3,4\n\"Coffee, black\",3,4\n"),
+        "{text}"
+    );
+
+    let out = headless(
+        &["browser", "table", "h1", "--session", &sid, "--tab", &tid],
+        10,
+    );
+    assert_failure(&out, "table on a non-table");
+}
+
+#[test]
+fn table_parse_types_cells_and_reports_failures() {
+    if skip() {
+        return;
+    }
+
+    let (sid, tid) = start_session();
+    let _guard = SessionGuard::new(&sid);
+    inject_table(&sid, &tid);
+
+    let out = headless_json(
+        &[
+            "browser",
+            "table",
+            "#prices",
+            "--format",
+            "json",
+            "--parse",
+            "Price / Net=number:de-DE",
+            "--parse",
+            "number",
+            "--session",
+            &sid,
+            "--tab",
+            &tid,
+        ],
+        10,
+    );
+    assert_success(&out, "table --parse");
+    let data = &parse_json(&out)["data"];
+    assert_eq!(data["rows"][0]["Price / Net"], 3);
+    assert_eq!(data["rows"][0]["Price / Gross"], 4);
+    assert_eq!(data["rows"][0]["Item"], "Tea");
+    // The nested-table row spans every column with text that is no number.
+    assert_eq!(data["parse_failures"][0]["column"], "Price / Net");
+    assert_eq!(data["parse_failures"].as_array().unwrap().len(), 1);
+}
+
+#[test]
+fn table_dedupe_by_keeps_the_first_row_per_value() {
+    if skip() {
+        return;
+    }
+
+    let (sid, tid) = start_session();
+    let _guard = SessionGuard::new(&sid);
+    inject_table(&sid, &tid);
+
+    let out = headless_json(
+        &[
+            "browser",
+            "table",
+            "#prices",
+            "--format",
+            "json",
+            "--dedupe-by",
+            "Price / Net",
+            "--normalize-urls",
+            "--session",
+            &sid,
+            "--tab",
+            &tid,
+        ],
+        10,
+    );
+    assert_success(&out, "table --dedupe-by");
+    let data = &parse_json(&out)["data"];
+    // Tea and Coffee share a net price of 3; the nested-table row differs.
+    assert_eq!(data["row_count"], 2);
+    assert_eq!(data["duplicates_removed"], 1);
+    assert_eq!(data["rows"][0]["Item"], "Tea");
+    assert_eq!(data["links"][0]["row"], 0);
+}
+
+#[cfg(feature = "sink-sqlite")]
+#[test]
+fn table_sink_writes_rows_to_sqlite() {
+    if skip() {
+        return;
+    }
+
+    let (sid, tid) = start_session();
+    let _guard = SessionGuard::new(&sid);
+    inject_table(&sid, &tid);
+    let dir = tempfile::tempdir().expect("create sink dir");
+    let db = dir.path().join("prices.db");
+    let sink = format!("sqlite:{}?table=prices", db.display());
+
+    let out = headless_json(
+        &[
+            "browser",
+            "table",
+            "#prices",
+            "--parse",
+            "number",
+            "--sink",
+            &sink,
+            "--session",
+            &sid,
+            "--tab",
+            &tid,
+        ],
+        10,
+    );
+    assert_success(&out, "table --sink");
+    let data = &parse_json(&out)["data"];
+    assert!(
+        data.get("csv").is_none(),
+        "rows must not be inlined: {data}"
+    );
+    assert_eq!(data["sink"]["kind"], "sqlite");
+    assert_eq!(data["sink"]["table"], "prices");
+    assert_eq!(data["sink"]["rows_written"], data["row_count"]);
+    assert!(db.exists(), "sqlite file must be written");
+}
+
+#[test]
+fn table_rejects_unknown_sink_kind() {
+    if skip() {
+        return;
+    }
+
+    let out = headless(
+        &[
+            "browser",
+            "table",
+            "#prices",
+            "--sink",
+            "xlsx:prices.xlsx",
+            "--session",
+            "s1",
+            "--tab",
+            "t1",
+        ],
+        10,
+    );
+    assert_failure(&out, "table --sink xlsx");
+    assert!(
+        String::from_utf8_lossy(&out.stderr).contains("unknown sink 'xlsx'"),
+        "{}",
+        String::from_utf8_lossy(&out.stderr)
+    );
+}
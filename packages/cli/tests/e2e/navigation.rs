@@ -1,4 +1,4 @@
-//! Browser navigation E2E tests: goto, back, forward, reload.
+//! Browser navigation E2E tests: goto, back, forward, reload, response.
 //!
 //! All navigation commands are Tab-level: require `--session <SID> --tab <TID>`.
 //! Tests are strict per api-reference.md section 9.
@@ -6,8 +6,9 @@
 //! Uses local HTTP server from harness — no external network dependency.
 
 use crate::harness::{
-    SessionGuard, assert_error_envelope, assert_failure, assert_meta, assert_success, headless,
-    headless_json, parse_json, skip, start_session, stdout_str, url_a, url_b,
+    SessionGuard, SoloEnv, assert_error_envelope, assert_failure, assert_meta, assert_success,
+    headless, headless_json, local_server, parse_json, skip, start_session, start_session_with,
+    stdout_str, url_a, url_b, url_fast_redirect, url_redirect_chain,
 };
 
 // ── Helpers ───────────────────────────────────────────────────────────
@@ -183,6 +184,81 @@ fn nav_goto_navigation_failed_json() {
     assert_eq!(v["context"]["tab_id"], tid);
 }
 
+#[test]
+fn nav_goto_download_budget_exceeded_json() {
+    if skip() {
+        return;
+    }
+    let (sid, tid) = start_session(&url_a());
+    let _guard = SessionGuard::new(&sid);
+
+    let out = headless_json(
+        &[
+            "browser",
+            "goto",
+            &url_b(),
+            "--session",
+            &sid,
+            "--tab",
+            &tid,
+            "--max-download-bytes",
+            "1",
+        ],
+        15,
+    );
+    assert_failure(&out, "goto over download budget");
+    let v = parse_json(&out);
+
+    assert_eq!(v["command"], "browser goto");
+    assert_error_envelope(&v, "DOWNLOAD_BUDGET_EXCEEDED");
+}
+
+#[test]
+fn nav_goto_download_budget_reports_bytes() {
+    if skip() {
+        return;
+    }
+    let (sid, tid) = start_session(&url_a());
+    let _guard = SessionGuard::new(&sid);
+
+    let out = headless_json(
+        &[
+            "browser",
+            "goto",
+            &url_b(),
+            "--session",
+            &sid,
+            "--tab",
+            &tid,
+            "--max-download-bytes",
+            "10000000",
+        ],
+        15,
+    );
+    assert_success(&out, "goto within download budget");
+    let v = parse_json(&out);
+
+    assert_eq!(v["data"]["download"]["limit"], 10_000_000);
+    assert!(v["data"]["download"]["bytes"].as_u64().unwrap_or(0) > 0);
+}
+
+#[test]
+fn nav_reload_over_session_download_budget_json() {
+    if skip() {
+        return;
+    }
+    // The session-wide budget also covers what a reload pulls in.
+    let (sid, tid) = start_session_with(&url_a(), &["--max-download-bytes", "1"]);
+    let _guard = SessionGuard::new(&sid);
+
+    let out = headless_json(&["browser", "reload", "--session", &sid, "--tab", &tid], 15);
+    assert_failure(&out, "reload over session download budget");
+    let v = parse_json(&out);
+
+    assert_eq!(v["command"], "browser reload");
+    assert_error_envelope(&v, "DOWNLOAD_BUDGET_EXCEEDED");
+}
+
 #[test]
 fn nav_goto_session_not_found_json() {
     if skip() {
@@ -1049,3 +1125,189 @@ fn nav_goto_wait_then_snapshot_refs_stable() {
     );
     assert_success(&out, "hover @e1 after goto+wait should not be REF_STALE");
 }
+
+#[test]
+fn response_reports_status_headers_and_redirects() {
+    if skip() {
+        return;
+    }
+    let (sid, tid) = start_session(&url_a());
+    let _guard = SessionGuard::new(&sid);
+
+    let out = headless_json(
+        &[
+            "browser",
+            "goto",
+            &url_fast_redirect(),
+            "--session",
+            &sid,
+            "--tab",
+            &tid,
+        ],
+        30,
+    );
+    assert_success(&out, "goto redirect");
+
+    let out = headless_json(
+        &["browser", "response", "--session", &sid, "--tab", &tid],
+        10,
+    );
+    assert_success(&out, "response after redirect");
+    let v = parse_json(&out);
+    assert_eq!(v["command"], "browser response");
+    assert_eq!(v["data"]["status"], 200);
+    assert!(v["data"]["url"].as_str().unwrap().contains("page-b"));
+    assert_eq!(v["data"]["headers"]["content-type"], "text/html");
+    let redirects = v["data"]["redirects"].as_array().unwrap();
+    assert_eq!(redirects.len(), 1);
+    assert_eq!(redirects[0]["status"], 302);
+    assert!(
+        redirects[0]["url"]
+            .as_str()
+            .unwrap()
+            .contains("redirect-fast")
+    );
+    assert!(v["data"]["timing"]["response_ms"].is_u64());
+
+    // The private image answers 403 without the fixture's cookie.
+    let forbidden = url_a().replace("/page-a", "/img/private.svg");
+    let out = headless_json(
+        &[
+            "browser",
+            "goto",
+            &forbidden,
+            "--session",
+            &sid,
+            "--tab",
+            &tid,
+        ],
+        30,
+    );
+    assert_success(&out, "goto forbidden");
+    let out = headless_json(
+        &["browser", "response", "--session", &sid, "--tab", &tid],
+        10,
+    );
+    assert_success(&out, "response after 403");
+    let v = parse_json(&out);
+    assert_eq!(v["data"]["status"], 403);
+    assert_eq!(v["data"]["redirects"], serde_json::json!([]));
+}
+
+#[test]
+fn trace_redirects_labels_http_meta_refresh_and_js_hops() {
+    if skip() {
+        return;
+    }
+    let (sid, tid) = start_session(&url_a());
+    let _guard = SessionGuard::new(&sid);
+
+    let out = headless_json(
+        &[
+            "browser",
+            "trace-redirects",
+            &url_redirect_chain(),
+            "--settle",
+            "1000",
+            "--session",
+            &sid,
+            "--tab",
+            &tid,
+        ],
+        40,
+    );
+    assert_success(&out, "trace-redirects");
+    let v = parse_json(&out);
+    assert_eq!(v["command"], "browser trace-redirects");
+    assert_eq!(v["data"]["settled"], true);
+    let hops = v["data"]["hops"].as_array().unwrap();
+    let summary: Vec<(String, serde_json::Value, serde_json::Value)> = hops
+        .iter()
+        .map(|h| {
+            let url = h["url"].as_str().unwrap();
+            let path = url[url.rfind('/').unwrap()..].to_string();
+            (path, h["status"].clone(), h["type"].clone())
+        })
+        .collect();
+    assert_eq!(
+        summary,
+        vec![
+            ("/redirect-chain".to_string(), 302.into(), "http".into()),
+            (
+                "/redirect-meta".to_string(),
+                200.into(),
+                "meta-refresh".into()
+            ),
+            ("/redirect-js".to_string(), 200.into(), "js".into()),
+            ("/page-b".to_string(), 200.into(), serde_json::Value::Null),
+        ]
+    );
+    assert!(v["data"]["final_url"].as_str().unwrap().contains("page-b"));
+    assert_eq!(v["data"]["redirects"], 3);
+}
+
+#[test]
+fn guardrails_added_mid_session_stop_script_navigation() {
+    if skip() {
+        return;
+    }
+    let env = SoloEnv::new();
+    let out = env.headless_json(
+        &[
+            "browser",
+            "start",
+            "--mode",
+            "local",
+            "--headless",
+            "--open-url",
+            &url_a(),
+        ],
+        30,
+    );
+    assert_success(&out, "start");
+    let v = parse_json(&out);
+    let sid = v["data"]["session"]["session_id"]
+        .as_str()
+        .unwrap()
+        .to_string();
+    let tid = v["data"]["tab"]["tab_id"].as_str().unwrap().to_string();
+
+    // The tab attached without a policy, so it has no interceptor yet.
+    std::fs::write(
+        env.config_path(),
+        "version = 1\n\n[guardrails]\nblocked_domains = [\"localhost\"]\n",
+    )
+    .expect("write config");
+
+    let off_policy = format!("http://localhost:{}/page-b", local_server().port);
+    let script = format!("location.href = '{off_policy}'; 1");
+    let out = env.headless_json(
+        &["browser", "eval", &script, "--session", &sid, "--tab", &tid],
+        30,
+    );
+    assert_success(&out, "eval navigating off-policy");
+    std::thread::sleep(std::time::Duration::from_millis(1000));
+
+    // Blocked by the interceptor, the tab never reached the page; had it
+    // got there, the router would fail this eval with DOMAIN_NOT_ALLOWED.
+    let out = env.headless_json(
+        &[
+            "browser",
+            "eval",
+            "location.href",
+            "--session",
+            &sid,
+            "--tab",
+            &tid,
+        ],
+        30,
+    );
+    assert_success(&out, "eval after blocked navigation");
+    let href = parse_json(&out)["data"]["value"]
+        .as_str()
+        .unwrap_or("")
+        .to_string();
+    assert!(!href.contains("localhost"), "tab left the policy: {href}");
+
+    env.headless(&["browser", "close", "--session", &sid], 30);
+}
@@ -460,6 +460,65 @@ fn start_invalid_buffer_size_rejected() {
     assert_error_envelope(&v, "INVALID_ARGUMENT");
 }
 
+#[test]
+fn start_with_adblock_reports_filter_list() {
+    if skip() {
+        return;
+    }
+
+    let (sid, profile) = unique_session("net-adblock");
+    let out = headless_json(
+        &[
+            "browser",
+            "start",
+            "--mode",
+            "local",
+            "--headless",
+            "--set-session-id",
+            &sid,
+            "--profile",
+            &profile,
+            "--open-url",
+            &url_network_load(),
+            "--adblock",
+        ],
+        30,
+    );
+    let _guard = SessionGuard::new(&sid);
+    assert_success(&out, "start with --adblock");
+    let v = parse_json(&out);
+    assert!(v["data"]["adblock"]["rules"].as_u64().unwrap_or(0) > 0);
+    assert_eq!(v["data"]["adblock"]["unsupported"], 0);
+}
+
+#[test]
+fn start_with_missing_adblock_list_rejected() {
+    if skip() {
+        return;
+    }
+
+    let (sid, profile) = unique_session("net-adblock-missing");
+    let out = headless_json(
+        &[
+            "browser",
+            "start",
+            "--mode",
+            "local",
+            "--headless",
+            "--set-session-id",
+            &sid,
+            "--profile",
+            &profile,
+            "--adblock-list",
+            "/nonexistent/actionbook-easylist.txt",
+        ],
+        30,
+    );
+    assert_failure(&out, "start with missing --adblock-list");
+    let v = parse_json(&out);
+    assert_error_envelope(&v, "INVALID_ARGUMENT");
+}
+
 #[test]
 fn network_requests_dump_writes_file() {
     if skip() {
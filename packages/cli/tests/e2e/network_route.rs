@@ -0,0 +1,130 @@
+//! Browser network route E2E tests: block, fulfill and header rewrite.
+
+use std::fs;
+
+use crate::harness::{
+    SessionGuard, assert_error_envelope, assert_failure, assert_success, headless_json, parse_json,
+    skip, start_session, url_a, url_b,
+};
+
+fn route(session_id: &str, tab_id: &str, args: &[&str]) -> std::process::Output {
+    let mut full = vec!["browser", "network", "route"];
+    full.extend_from_slice(args);
+    full.extend_from_slice(&["--session", session_id, "--tab", tab_id]);
+    headless_json(&full, 15)
+}
+
+fn eval(session_id: &str, tab_id: &str, expr: &str) -> serde_json::Value {
+    let out = headless_json(
+        &[
+            "browser",
+            "eval",
+            expr,
+            "--session",
+            session_id,
+            "--tab",
+            tab_id,
+        ],
+        15,
+    );
+    assert_success(&out, "eval");
+    parse_json(&out)["data"]["value"].clone()
+}
+
+#[test]
+fn route_fulfill_serves_fixture_and_counts_hits() {
+    if skip() {
+        return;
+    }
+
+    let (sid, tid) = start_session(&url_a());
+    let _guard = SessionGuard::new(&sid);
+
+    let dir = tempfile::tempdir().unwrap();
+    let fixture = dir.path().join("users.json");
+    fs::write(&fixture, r#"{"users":["ada"]}"#).unwrap();
+    let fixture = fixture.to_string_lossy().to_string();
+
+    let out = route(&sid, &tid, &["add", "*/mock/users*", "--fulfill", &fixture]);
+    assert_success(&out, "route add --fulfill");
+    let v = parse_json(&out);
+    assert_eq!(v["data"]["route"]["id"], "r1");
+    assert_eq!(v["data"]["route"]["content_type"], "application/json");
+
+    let body = eval(
+        &sid,
+        &tid,
+        "fetch('/mock/users?page=1').then(r => r.status + ' ' + r.headers.get('content-type'))",
+    );
+    assert!(
+        body.as_str()
+            .unwrap_or("")
+            .starts_with("200 application/json"),
+        "fulfilled response: {body}"
+    );
+    let users = eval(
+        &sid,
+        &tid,
+        "fetch('/mock/users').then(r => r.json()).then(j => j.users[0])",
+    );
+    assert_eq!(users, "ada");
+
+    let out = route(&sid, &tid, &["list"]);
+    assert_success(&out, "route list");
+    let v = parse_json(&out);
+    assert_eq!(v["data"]["count"], 1);
+    assert_eq!(v["data"]["routes"][0]["hits"], 2);
+}
+
+#[test]
+fn route_block_fails_request_until_removed() {
+    if skip() {
+        return;
+    }
+
+    let (sid, tid) = start_session(&url_a());
+    let _guard = SessionGuard::new(&sid);
+
+    let out = route(&sid, &tid, &["add", "*/page-b", "--block"]);
+    assert_success(&out, "route add --block");
+
+    let probe = format!("fetch('{}').then(() => 'loaded', () => 'blocked')", url_b());
+    assert_eq!(eval(&sid, &tid, &probe), "blocked");
+
+    let out = route(&sid, &tid, &["remove", "r1"]);
+    assert_success(&out, "route remove");
+    assert_eq!(parse_json(&out)["data"]["removed"], 1);
+    assert_eq!(eval(&sid, &tid, &probe), "loaded");
+
+    let out = route(&sid, &tid, &["remove", "r1"]);
+    assert_failure(&out, "remove missing route");
+    assert_error_envelope(&parse_json(&out), "ROUTE_NOT_FOUND");
+}
+
+#[test]
+fn route_header_rewrites_request_headers() {
+    if skip() {
+        return;
+    }
+
+    let (sid, tid) = start_session(&url_a());
+    let _guard = SessionGuard::new(&sid);
+
+    let out = route(&sid, &tid, &["add", "*/api/echo", "--header", "X-Test: 1"]);
+    assert_success(&out, "route add --header");
+    assert_eq!(parse_json(&out)["data"]["route"]["action"], "headers");
+
+    let seen = eval(
+        &sid,
+        &tid,
+        "fetch('/api/echo').then(r => r.json()).then(j => j.headers['x-test'] || 'missing')",
+    );
+    assert_eq!(seen, "1", "the server must see the rewritten header");
+
+    let v = parse_json(&route(&sid, &tid, &["list"]));
+    assert_eq!(v["data"]["routes"][0]["hits"], 1);
+
+    let out = route(&sid, &tid, &["add", "*", "--header", "no-colon"]);
+    assert_failure(&out, "invalid header");
+    assert_error_envelope(&parse_json(&out), "INVALID_ARGUMENT");
+}
@@ -751,3 +751,150 @@ fn viewport_missing_tab_arg() {
     let out = headless_json(&["browser", "viewport", "--session", "any-sid"], 10);
     assert_failure(&out, "viewport missing --tab");
 }
+
+// ===========================================================================
+// Group 4: browser emulate
+// ===========================================================================
+
+#[test]
+fn emulate_device_applies_to_open_and_new_tabs() {
+    if skip() {
+        return;
+    }
+    let (sid, tid) = start_session(URL_A);
+    let _guard = SessionGuard::new(&sid);
+
+    let out = headless_json(&["browser", "emulate", "nokia 3310", "--session", &sid], 10);
+    assert_failure(&out, "unknown device");
+    assert_error_envelope(&parse_json(&out), "INVALID_ARGUMENT");
+
+    let out = headless_json(&["browser", "emulate", "iphone-14", "--session", &sid], 10);
+    assert_success(&out, "emulate iPhone 14");
+    let v = parse_json(&out);
+    assert_eq!(v["data"]["device"]["device"], "iPhone 14");
+    assert_eq!(v["data"]["tabs"], 1);
+
+    let eval = |tab: &str| {
+        let out = headless_json(
+            &[
+                "browser",
+                "eval",
+                "[innerWidth, navigator.maxTouchPoints, navigator.userAgent.includes('iPhone')]",
+                "--session",
+                &sid,
+                "--tab",
+                tab,
+            ],
+            10,
+        );
+        assert_success(&out, "eval emulated metrics");
+        parse_json(&out)["data"]["value"].clone()
+    };
+    assert_eq!(eval(&tid), serde_json::json!([390, 5, true]));
+
+    // Tabs opened afterwards look like the same device.
+    let out = headless_json(&["browser", "new-tab", URL_A, "--session", &sid], 30);
+    assert_success(&out, "new tab");
+    let new_tid = parse_json(&out)["data"]["tab"]["tab_id"]
+        .as_str()
+        .unwrap()
+        .to_string();
+    wait_page_ready(&sid, &new_tid);
+    assert_eq!(eval(&new_tid), serde_json::json!([390, 5, true]));
+
+    let out = headless_json(&["browser", "status", "--session", &sid], 10);
+    assert_success(&out, "status");
+    assert_eq!(parse_json(&out)["data"]["session"]["device"], "iPhone 14");
+
+    let out = headless_json(&["browser", "emulate", "--reset", "--session", &sid], 10);
+    assert_success(&out, "emulate reset");
+    let v = parse_json(&out);
+    assert!(v["data"]["device"].is_null());
+    assert_eq!(v["data"]["tabs"], 2);
+    assert_ne!(eval(&tid)[0], 390);
+}
+
+#[test]
+fn emulate_network_offline_applies_to_open_and_new_tabs() {
+    if skip() {
+        return;
+    }
+    let (sid, tid) = start_session(URL_A);
+    let _guard = SessionGuard::new(&sid);
+
+    let online = |tab: &str| {
+        let out = headless_json(
+            &[
+                "browser",
+                "eval",
+                "navigator.onLine",
+                "--session",
+                &sid,
+                "--tab",
+                tab,
+            ],
+            10,
+        );
+        assert_success(&out, "eval navigator.onLine");
+        parse_json(&out)["data"]["value"].clone()
+    };
+
+    let out = headless_json(
+        &[
+            "browser",
+            "emulate",
+            "network",
+            "--preset",
+            "dialup",
+            "--session",
+            &sid,
+        ],
+        10,
+    );
+    assert_failure(&out, "unknown preset");
+    assert_error_envelope(&parse_json(&out), "INVALID_ARGUMENT");
+
+    let out = headless_json(
+        &[
+            "browser",
+            "emulate",
+            "network",
+            "--preset",
+            "offline",
+            "--session",
+            &sid,
+        ],
+        10,
+    );
+    assert_success(&out, "emulate offline");
+    let v = parse_json(&out);
+    assert_eq!(v["data"]["network"]["offline"], true);
+    assert_eq!(v["data"]["tabs"], 1);
+    assert_eq!(online(&tid), false);
+
+    let out = headless_json(
+        &["browser", "new-tab", "about:blank", "--session", &sid],
+        30,
+    );
+    assert_success(&out, "new tab");
+    let new_tid = parse_json(&out)["data"]["tab"]["tab_id"]
+        .as_str()
+        .unwrap()
+        .to_string();
+    assert_eq!(online(&new_tid), false);
+
+    let out = headless_json(
+        &[
+            "browser",
+            "emulate",
+            "network",
+            "--reset",
+            "--session",
+            &sid,
+        ],
+        10,
+    );
+    assert_success(&out, "emulate network reset");
+    assert!(parse_json(&out)["data"]["network"].is_null());
+    assert_eq!(online(&tid), true);
+}
@@ -0,0 +1,103 @@
+//! `browser record` E2E tests: capture input as a script `actionbook run` accepts.
+//! `browser record-video` tests: capture the tab as screencast frames or a video.
+
+use std::fs;
+
+use crate::harness::{
+    SessionGuard, assert_error_envelope, assert_failure, assert_success, headless_json, parse_json,
+    skip, start_session, url_a,
+};
+
+fn browser(session_id: &str, tab_id: &str, args: &[&str]) -> std::process::Output {
+    let mut full = vec!["browser"];
+    full.extend_from_slice(args);
+    full.extend_from_slice(&["--session", session_id, "--tab", tab_id]);
+    headless_json(&full, 20)
+}
+
+#[test]
+fn record_captures_typing_and_clicks_as_run_script() {
+    if skip() {
+        return;
+    }
+    let (sid, tid) = start_session(&url_a());
+    let _guard = SessionGuard::new(&sid);
+
+    let fixture = "(() => { document.body.insertAdjacentHTML('beforeend', \
+        '<input id=\"q\"><button id=\"go\" type=\"button\">Go</button>'); return 'ok'; })()";
+    assert_success(&browser(&sid, &tid, &["eval", fixture]), "install fixture");
+
+    let dir = tempfile::tempdir().unwrap();
+    let out = dir.path().join("flow.yaml");
+    let out_path = out.to_string_lossy().to_string();
+
+    let started = browser(&sid, &tid, &["record", "start", "--out", &out_path]);
+    assert_success(&started, "record start");
+    let again = browser(&sid, &tid, &["record", "start", "--out", &out_path]);
+    assert_failure(&again, "second record start");
+    assert_error_envelope(&parse_json(&again), "RECORDING_ACTIVE");
+
+    assert_success(&browser(&sid, &tid, &["type", "#q", "hello"]), "type");
+    assert_success(&browser(&sid, &tid, &["press", "Enter"]), "press");
+    assert_success(&browser(&sid, &tid, &["click", "#go"]), "click");
+
+    let stopped = browser(&sid, &tid, &["record", "stop"]);
+    assert_success(&stopped, "record stop");
+    assert_eq!(parse_json(&stopped)["data"]["steps"], 4);
+
+    let script: serde_json::Value =
+        serde_yaml::from_str(&fs::read_to_string(&out).unwrap()).unwrap();
+    let steps = script["steps"].as_array().unwrap();
+    assert_eq!(steps[0]["goto"], url_a());
+    assert_eq!(steps[1]["fill"]["selector"], "#q");
+    assert_eq!(steps[1]["fill"]["text"], "hello");
+    assert_eq!(steps[2]["browser"], serde_json::json!(["press", "Enter"]));
+    assert_eq!(steps[3]["click"], "#go");
+
+    let out = browser(&sid, &tid, &["record", "stop"]);
+    assert_failure(&out, "stop without recording");
+    assert_error_envelope(&parse_json(&out), "RECORDING_NOT_ACTIVE");
+}
+
+#[test]
+fn record_video_captures_frames_of_a_changing_page() {
+    if skip() {
+        return;
+    }
+    let (sid, tid) = start_session(&url_a());
+    let _guard = SessionGuard::new(&sid);
+
+    let dir = tempfile::tempdir().unwrap();
+    let out = dir.path().join("session.webm");
+    let out_path = out.to_string_lossy().to_string();
+
+    let started = browser(&sid, &tid, &["record-video", "start", "--out", &out_path]);
+    assert_success(&started, "record-video start");
+    let again = browser(&sid, &tid, &["record-video", "start", "--out", &out_path]);
+    assert_failure(&again, "second record-video start");
+    assert_error_envelope(&parse_json(&again), "RECORDING_ACTIVE");
+
+    for colour in ["red", "green", "blue"] {
+        let paint =
+            format!("(() => {{ document.body.style.background = '{colour}'; return 'ok'; }})()");
+        assert_success(&browser(&sid, &tid, &["eval", &paint]), "repaint");
+        std::thread::sleep(std::time::Duration::from_millis(300));
+    }
+
+    let stopped = browser(&sid, &tid, &["record-video", "stop"]);
+    assert_success(&stopped, "record-video stop");
+    let data = &parse_json(&stopped)["data"];
+    assert!(data["frames"].as_u64().unwrap() > 0, "{data}");
+    if data["encoded"] == true {
+        assert!(fs::metadata(&out).unwrap().len() > 0);
+        assert!(!dir.path().join("session.frames").exists());
+    } else {
+        let list = fs::read_to_string(dir.path().join("session.frames/frames.txt")).unwrap();
+        assert!(list.contains("file 'frame-000001.jpg'"), "{list}");
+        assert!(data["ffmpeg"].as_str().unwrap().starts_with("ffmpeg "));
+    }
+
+    let out = browser(&sid, &tid, &["record-video", "stop"]);
+    assert_failure(&out, "stop without recording");
+    assert_error_envelope(&parse_json(&out), "RECORDING_NOT_ACTIVE");
+}
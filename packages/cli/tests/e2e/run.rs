@@ -0,0 +1,410 @@
+//! `actionbook run` E2E tests: scripted steps, retries and the result report.
+
+use std::fs;
+
+use crate::harness::{
+    SessionGuard, assert_failure, assert_success, headless_json, headless_json_with_env,
+    parse_json, skip, start_session, stderr_str, url_a, url_b,
+};
+
+fn run_script(script: &std::path::Path, session_id: &str) -> std::process::Output {
+    let path = script.to_string_lossy().to_string();
+    headless_json(
+        &["run", &path, "--session", session_id, "--tab", "active"],
+        60,
+    )
+}
+
+#[test]
+fn run_executes_steps_and_collects_extracted_values() {
+    if skip() {
+        return;
+    }
+    let (sid, _tid) = start_session(&url_a());
+    let _guard = SessionGuard::new(&sid);
+
+    let dir = tempfile::tempdir().unwrap();
+    let script = dir.path().join("flow.yaml");
+    let report = dir.path().join("report.json");
+    fs::write(
+        &script,
+        format!(
+            "steps:\n  - goto: {}\n  - assert: {{ url: /page-b }}\n  - extract: {{ selector: title, as: title }}\n  - wait: 50\n",
+            url_b()
+        ),
+    )
+    .unwrap();
+
+    let path = script.to_string_lossy().to_string();
+    let report_path = report.to_string_lossy().to_string();
+    let out = headless_json(
+        &[
+            "run",
+            &path,
+            "--session",
+            &sid,
+            "--tab",
+            "active",
+            "--report",
+            &report_path,
+        ],
+        60,
+    );
+    assert_success(&out, "run flow.yaml");
+    let v = parse_json(&out);
+    assert_eq!(v["data"]["passed"], 4);
+    assert_eq!(v["data"]["extracted"]["title"], "Page B");
+    assert_eq!(v["data"]["steps"][1]["action"], "assert");
+
+    let written: serde_json::Value = serde_json::from_slice(&fs::read(&report).unwrap()).unwrap();
+    assert_eq!(written["total"], 4);
+}
+
+#[test]
+fn run_stops_at_failed_step_after_retries() {
+    if skip() {
+        return;
+    }
+    let (sid, _tid) = start_session(&url_a());
+    let _guard = SessionGuard::new(&sid);
+
+    let dir = tempfile::tempdir().unwrap();
+    let script = dir.path().join("flow.json");
+    fs::write(
+        &script,
+        r##"{"retry_delay_ms": 10, "steps": [
+            {"assert": {"selector": "title", "text": "Nope"}, "retries": 2},
+            {"click": "#never-runs"}
+        ]}"##,
+    )
+    .unwrap();
+
+    let out = run_script(&script, &sid);
+    assert_failure(&out, "failing assert");
+    let v = parse_json(&out);
+    assert_eq!(v["error"]["code"], "STEP_FAILED");
+    let report = &v["error"]["details"];
+    assert_eq!(report["steps"][0]["attempts"], 3);
+    assert_eq!(report["steps"][0]["error"]["code"], "ASSERTION_FAILED");
+    assert_eq!(report["skipped"], 1);
+}
+
+#[test]
+fn run_rejects_invalid_step_before_running_anything() {
+    if skip() {
+        return;
+    }
+    let dir = tempfile::tempdir().unwrap();
+    let script = dir.path().join("bad.yaml");
+    fs::write(&script, "steps:\n  - tap: '#a'\n").unwrap();
+
+    let out = run_script(&script, "unused");
+    assert_failure(&out, "unknown step");
+    assert_eq!(parse_json(&out)["error"]["code"], "INVALID_SCRIPT");
+}
+
+#[test]
+fn run_writes_relative_step_files_into_a_fresh_artifacts_dir() {
+    if skip() {
+        return;
+    }
+    let (sid, _tid) = start_session(&url_a());
+    let _guard = SessionGuard::new(&sid);
+
+    let dir = tempfile::tempdir().unwrap();
+    let script = dir.path().join("shots.yaml");
+    fs::write(&script, "steps:\n  - screenshot: page.png\n").unwrap();
+    let template = format!("{}/{{flow}}/{{timestamp}}", dir.path().display());
+    let path = script.to_string_lossy().to_string();
+    let args = [
+        "run",
+        &path,
+        "--session",
+        &sid,
+        "--tab",
+        "active",
+        "--artifacts-dir",
+        &template,
+    ];
+
+    let first = headless_json(&args, 60);
+    assert_success(&first, "run shots.yaml");
+    let second = headless_json(&args, 60);
+    assert_success(&second, "run shots.yaml again");
+
+    let a = parse_json(&first)["data"]["artifacts_dir"]
+        .as_str()
+        .unwrap()
+        .to_string();
+    let b = parse_json(&second)["data"]["artifacts_dir"]
+        .as_str()
+        .unwrap()
+        .to_string();
+    assert_ne!(a, b);
+    assert!(std::path::Path::new(&a).join("page.png").is_file());
+    assert!(std::path::Path::new(&b).join("page.png").is_file());
+    assert!(std::path::Path::new(&a).starts_with(dir.path().join("shots")));
+}
+
+#[test]
+fn run_heals_a_moved_selector_from_its_anchor() {
+    if skip() {
+        return;
+    }
+    let (sid, _tid) = start_session(&url_a());
+    let _guard = SessionGuard::new(&sid);
+
+    let dir = tempfile::tempdir().unwrap();
+    let script = dir.path().join("flow.yaml");
+    fs::write(
+        &script,
+        r##"steps:
+  - browser: [eval, "document.body.innerHTML = '<p>Basket</p><button data-testid=buy>Buy now</button>'"]
+  - click: "#buy"
+    anchor: { tag: button, role: button, name: Buy now, near: Basket, attrs: { data-testid: buy } }
+"##,
+    )
+    .unwrap();
+
+    let path = script.to_string_lossy().to_string();
+    let out = headless_json(
+        &[
+            "run",
+            &path,
+            "--session",
+            &sid,
+            "--tab",
+            "active",
+            "--save-healed",
+        ],
+        60,
+    );
+    assert_success(&out, "run with a moved selector");
+    let v = parse_json(&out);
+    let heal = &v["data"]["steps"][1]["heal"];
+    assert_eq!(heal["ok"], true, "heal: {heal}");
+    assert_eq!(heal["from"], "#buy");
+    assert_eq!(v["data"]["healed"], 1);
+    assert_eq!(v["data"]["heal_saved"], true);
+
+    let saved: serde_json::Value =
+        serde_yaml::from_str(&fs::read_to_string(&script).unwrap()).unwrap();
+    assert_eq!(saved["steps"][1]["click"], heal["to"]);
+    assert_eq!(saved["steps"][1]["anchor"]["name"], "Buy now");
+}
+
+#[test]
+fn run_captures_artifacts_after_the_steps_that_ask() {
+    if skip() {
+        return;
+    }
+    let (sid, _tid) = start_session(&url_a());
+    let _guard = SessionGuard::new(&sid);
+
+    let dir = tempfile::tempdir().unwrap();
+    let script = dir.path().join("flow.yaml");
+    fs::write(
+        &script,
+        format!(
+            "capture: snapshot\nsteps:\n  - goto: {}\n  - wait: 10\n    capture: none\n  - assert: {{ url: /page-b }}\n    capture: both\n",
+            url_b()
+        ),
+    )
+    .unwrap();
+    let template = dir.path().join("runs").join("{flow}");
+    let template = template.to_string_lossy().to_string();
+
+    let path = script.to_string_lossy().to_string();
+    let out = headless_json(
+        &[
+            "run",
+            &path,
+            "--session",
+            &sid,
+            "--tab",
+            "active",
+            "--artifacts-dir",
+            &template,
+        ],
+        60,
+    );
+    assert_success(&out, "run with captures");
+    let steps = parse_json(&out)["data"]["steps"].clone();
+    assert_eq!(steps[0]["captures"].as_array().unwrap().len(), 1);
+    assert!(steps[1].get("captures").is_none());
+    let captures = steps[2]["captures"].as_array().unwrap();
+    assert_eq!(captures.len(), 2);
+    for capture in captures {
+        let file = capture["path"]
+            .as_str()
+            .unwrap_or_else(|| panic!("{capture}"));
+        assert!(std::path::Path::new(file).is_file(), "{file}");
+        assert!(file.contains("captures"), "{file}");
+    }
+}
+
+#[test]
+fn run_pauses_at_breakpoints_and_continues_at_end_of_input() {
+    if skip() {
+        return;
+    }
+    let (sid, _tid) = start_session(&url_a());
+    let _guard = SessionGuard::new(&sid);
+
+    let dir = tempfile::tempdir().unwrap();
+    let script = dir.path().join("flow.yaml");
+    fs::write(
+        &script,
+        format!(
+            "steps:\n  - goto: {}\n  - assert: {{ url: /page-b }}\n    name: check\n",
+            url_b()
+        ),
+    )
+    .unwrap();
+    let path = script.to_string_lossy().to_string();
+
+    let out = headless_json(
+        &[
+            "run",
+            &path,
+            "--session",
+            &sid,
+            "--tab",
+            "active",
+            "--pause-at",
+            "7",
+        ],
+        60,
+    );
+    assert_failure(&out, "unknown breakpoint");
+    assert_eq!(parse_json(&out)["error"]["code"], "INVALID_ARGUMENT");
+
+    // The harness gives the CLI no stdin, so the breakpoint reads end of
+    // input and the run goes on.
+    let out = headless_json(
+        &[
+            "run",
+            &path,
+            "--session",
+            &sid,
+            "--tab",
+            "active",
+            "--pause-at",
+            "check",
+        ],
+        60,
+    );
+    assert_success(&out, "run with a breakpoint");
+    assert_eq!(parse_json(&out)["data"]["passed"], 2);
+    assert!(
+        stderr_str(&out).contains("paused before step 2 (assert"),
+        "stderr: {}",
+        stderr_str(&out)
+    );
+}
+
+/// The login form the credential tests fill, with `#out` showing what was submitted.
+const LOGIN_FORM_STEP: &str = r##"  - browser:
+      - eval
+      - >-
+        document.body.innerHTML = '<form id=f><input name=username><input type=password name=pw><button>Go</button></form><p id=out></p>';
+        f.onsubmit = (e) => { e.preventDefault(); out.textContent = f.username.value + '/' + f.pw.value.length; };
+"##;
+
+fn add_login(domain: &str) {
+    let out = headless_json_with_env(
+        &[
+            "auth",
+            "add",
+            domain,
+            "--user",
+            "alice",
+            "--pass-env",
+            "E2E_LOGIN_PASS",
+        ],
+        &[
+            ("E2E_LOGIN_PASS", "s3cret-pw"),
+            ("ACTIONBOOK_CREDENTIAL_STORE", "file"),
+        ],
+        30,
+    );
+    assert_success(&out, "auth add");
+}
+
+#[test]
+fn run_logs_in_from_the_credential_store() {
+    if skip() {
+        return;
+    }
+    // The test server's pages are on 127.0.0.1.
+    add_login("127.0.0.1");
+
+    let (sid, _tid) = start_session(&url_a());
+    let _guard = SessionGuard::new(&sid);
+
+    let dir = tempfile::tempdir().unwrap();
+    let script = dir.path().join("flow.yaml");
+    fs::write(
+        &script,
+        format!(
+            "steps:\n{LOGIN_FORM_STEP}  - login: {{ domain: 127.0.0.1, submit: button }}\n  - assert: {{ selector: \"#out\", text: alice/9 }}\n"
+        ),
+    )
+    .unwrap();
+
+    let out = run_script(&script, &sid);
+    assert_success(&out, "run with a login step");
+    let stdout = String::from_utf8_lossy(&out.stdout);
+    assert!(!stdout.contains("s3cret-pw"), "password leaked: {stdout}");
+    let v = parse_json(&out);
+    assert_eq!(v["data"]["steps"][1]["action"], "login");
+    assert_eq!(v["data"]["steps"][1]["target"], "127.0.0.1");
+}
+
+#[test]
+fn run_login_refuses_to_fill_a_page_on_another_domain() {
+    if skip() {
+        return;
+    }
+    add_login("login-e2e.test");
+
+    let (sid, _tid) = start_session(&url_a());
+    let _guard = SessionGuard::new(&sid);
+
+    let dir = tempfile::tempdir().unwrap();
+    let script = dir.path().join("flow.yaml");
+    fs::write(
+        &script,
+        format!(
+            "steps:\n{LOGIN_FORM_STEP}  - login: {{ domain: https://app.login-e2e.test/signin, submit: button }}\n"
+        ),
+    )
+    .unwrap();
+
+    let out = run_script(&script, &sid);
+    assert_failure(&out, "login on the wrong host");
+    let stdout = String::from_utf8_lossy(&out.stdout);
+    assert!(!stdout.contains("s3cret-pw"), "password leaked: {stdout}");
+    let v = parse_json(&out);
+    let step = &v["error"]["details"]["steps"][1];
+    assert_eq!(
+        step["error"]["code"], "CREDENTIAL_DOMAIN_MISMATCH",
+        "{step}"
+    );
+
+    // Nothing was typed into the form.
+    let out = headless_json(
+        &[
+            "browser",
+            "value",
+            "input[name=username]",
+            "--session",
+            &sid,
+            "--tab",
+            "active",
+        ],
+        10,
+    );
+    assert_success(&out, "read the username field");
+    assert_eq!(parse_json(&out)["data"]["value"], "");
+}
@@ -0,0 +1,176 @@
+//! E2E tests for `browser save`.
+
+use crate::harness::{
+    SessionGuard, assert_failure, assert_success, headless, headless_json, parse_json, skip,
+    stdout_str, unique_session, wait_page_ready,
+};
+
+fn start_session() -> (String, String) {
+    let (sid, profile) = unique_session("s");
+    let out = headless_json(
+        &[
+            "browser",
+            "start",
+            "--mode",
+            "local",
+            "--headless",
+            "--set-session-id",
+            &sid,
+            "--profile",
+            &profile,
+            "--open-url",
+            "about:blank",
+        ],
+        30,
+    );
+    assert_success(&out, "start session");
+    let v = parse_json(&out);
+    let sid = v["data"]["session"]["session_id"]
+        .as_str()
+        .unwrap()
+        .to_string();
+    let tid = v["data"]["tab"]["tab_id"].as_str().unwrap().to_string();
+
+    let goto_out = headless_json(
+        &[
+            "browser",
+            "goto",
+            "about:blank",
+            "--session",
+            &sid,
+            "--tab",
+            &tid,
+        ],
+        30,
+    );
+    assert_success(&goto_out, "goto about:blank");
+    wait_page_ready(&sid, &tid);
+
+    (sid, tid)
+}
+
+fn inject_fixture(sid: &str, tid: &str) {
+    let js = r#"document.body.innerHTML = `
+  <main>
+    <h1>Save Contract Fixture</h1>
+    <p style="color: rgb(1, 2, 3)">Archive this page.</p>
+  </main>
+`;
+document.title = 'Save Fixture';
+void(0)"#;
+    let out = headless_json(&["browser", "eval", js, "--session", sid, "--tab", tid], 10);
+    assert_success(&out, "inject fixture");
+}
+
+fn assert_meta(v: &serde_json::Value) {
+    assert!(v["meta"]["duration_ms"].is_number());
+    assert!(v["meta"]["warnings"].is_array());
+    assert!(v["meta"]["pagination"].is_null());
+    assert!(v["meta"]["truncated"].is_boolean());
+}
+
+#[test]
+fn save_mhtml_json_happy_path() {
+    if skip() {
+        return;
+    }
+
+    let (sid, tid) = start_session();
+    let _guard = SessionGuard::new(&sid);
+    inject_fixture(&sid, &tid);
+
+    let tmp = tempfile::tempdir().expect("create temp dir");
+    let path = tmp.path().join("fixture.mhtml");
+    let path_str = path.to_string_lossy().to_string();
+
+    let out = headless_json(
+        &[
+            "browser",
+            "save",
+            &path_str,
+            "--session",
+            &sid,
+            "--tab",
+            &tid,
+        ],
+        30,
+    );
+    assert_success(&out, "save mhtml json");
+    let v = parse_json(&out);
+    let content = std::fs::read_to_string(&path).expect("mhtml file should exist");
+
+    assert_eq!(v["command"], "browser save");
+    assert_eq!(v["ok"], true);
+    assert_meta(&v);
+    assert_eq!(v["context"]["title"], "Save Fixture");
+    assert_eq!(v["data"]["format"], "mhtml");
+    assert_eq!(v["data"]["artifact"]["path"], path_str);
+    assert_eq!(v["data"]["artifact"]["mime_type"], "multipart/related");
+    assert_eq!(v["data"]["artifact"]["bytes"], content.len());
+    assert!(content.contains("Content-Type: multipart/related"));
+    assert!(content.contains("Save Contract Fixture"));
+}
+
+#[test]
+fn save_html_inlines_and_strips_scripts() {
+    if skip() {
+        return;
+    }
+
+    let (sid, tid) = start_session();
+    let _guard = SessionGuard::new(&sid);
+    inject_fixture(&sid, &tid);
+
+    let tmp = tempfile::tempdir().expect("create temp dir");
+    let path = tmp.path().join("fixture.html");
+    let path_str = path.to_string_lossy().to_string();
+
+    let out = headless(
+        &[
+            "browser",
+            "save",
+            &path_str,
+            "--format",
+            "html",
+            "--session",
+            &sid,
+            "--tab",
+            &tid,
+        ],
+        30,
+    );
+    assert_success(&out, "save html text");
+    let text = stdout_str(&out);
+    assert!(text.contains(&format!("path: {path_str}")), "got: {text}");
+
+    let html = std::fs::read_to_string(&path).expect("html file should exist");
+    assert!(html.starts_with("<!DOCTYPE html>"));
+    assert!(html.contains("Save Contract Fixture"));
+    assert!(!html.contains("<script"));
+}
+
+#[test]
+fn save_missing_session_fails() {
+    if skip() {
+        return;
+    }
+
+    let tmp = tempfile::tempdir().expect("create temp dir");
+    let path_str = tmp.path().join("x.mhtml").to_string_lossy().to_string();
+    let out = headless_json(
+        &[
+            "browser",
+            "save",
+            &path_str,
+            "--session",
+            "nope-session",
+            "--tab",
+            "t1",
+        ],
+        10,
+    );
+    assert_failure(&out, "save missing session");
+    let v = parse_json(&out);
+    assert_eq!(v["error"]["code"], "SESSION_NOT_FOUND");
+    assert!(v["context"].is_null());
+}
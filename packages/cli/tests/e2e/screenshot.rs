@@ -302,6 +302,110 @@ fn screenshot_selector() {
     close_session(&sid);
 }
 
+#[test]
+fn screenshot_selector_clips_to_element_below_the_fold() {
+    if skip() {
+        return;
+    }
+    let (sid, tid) = start_session();
+    let _guard = SessionGuard::new(&sid);
+
+    // Safe: hardcoded test fixture
+    let js = r#"document.body.style.margin = '0'; document.body.textContent = ''; var s = document.createElement('div'); s.style.height = '2000px'; document.body.appendChild(s); var w = document.createElement('div'); w.id = 'widget'; w.style.cssText = 'width:300px;height:120px;margin-left:40px;background:#c00'; document.body.appendChild(w); void(0)"#;
+    let out = headless_json(
+        &["browser", "eval", js, "--session", &sid, "--tab", &tid],
+        10,
+    );
+    assert_success(&out, "inject tall fixture");
+
+    let tmp = tempfile::NamedTempFile::new().unwrap();
+    let path = format!("{}.png", tmp.path().to_string_lossy());
+    drop(tmp);
+
+    let out = headless_json(
+        &[
+            "browser",
+            "screenshot",
+            &path,
+            "--session",
+            &sid,
+            "--tab",
+            &tid,
+            "--selector",
+            "#widget",
+        ],
+        15,
+    );
+    assert_success(&out, "screenshot widget");
+    let v = parse_json(&out);
+
+    // PNG IHDR: width and height are big-endian u32s at bytes 16..24.
+    let bytes = std::fs::read(&path).unwrap();
+    let width = u32::from_be_bytes(bytes[16..20].try_into().unwrap());
+    let height = u32::from_be_bytes(bytes[20..24].try_into().unwrap());
+    assert_eq!(
+        width * 120,
+        height * 300,
+        "unexpected size {width}x{height}"
+    );
+
+    // The element was scrolled into view, so the clip had to add the offset.
+    let out = headless_json(
+        &[
+            "browser",
+            "eval",
+            "window.scrollY > 0",
+            "--session",
+            &sid,
+            "--tab",
+            &tid,
+        ],
+        10,
+    );
+    assert_success(&out, "read scroll position");
+    assert_eq!(parse_json(&out)["data"]["value"], true);
+
+    let _ = std::fs::remove_file(v["data"]["artifact"]["path"].as_str().unwrap());
+    close_session(&sid);
+}
+
+#[test]
+fn screenshot_selector_rejects_hidden_element() {
+    if skip() {
+        return;
+    }
+    let (sid, tid) = start_session();
+    let _guard = SessionGuard::new(&sid);
+
+    // Safe: hardcoded test fixture
+    let js = r#"document.body.textContent = ''; var h = document.createElement('div'); h.id = 'hidden'; h.style.display = 'none'; document.body.appendChild(h); void(0)"#;
+    let out = headless_json(
+        &["browser", "eval", js, "--session", &sid, "--tab", &tid],
+        10,
+    );
+    assert_success(&out, "inject hidden fixture");
+
+    let out = headless_json(
+        &[
+            "browser",
+            "screenshot",
+            "/tmp/actionbook-hidden.png",
+            "--session",
+            &sid,
+            "--tab",
+            &tid,
+            "--selector",
+            "#hidden",
+        ],
+        15,
+    );
+    assert_failure(&out, "screenshot hidden element");
+    let v = parse_json(&out);
+    assert_eq!(v["error"]["code"], "ELEMENT_NOT_VISIBLE");
+
+    close_session(&sid);
+}
+
 #[test]
 fn screenshot_annotate() {
     if skip() {
@@ -4,8 +4,8 @@
 //! invocation → HTTP request → format output pipeline.
 
 use crate::harness::{
-    api_base_url, assert_failure, assert_success, headless_json_with_env, headless_with_env, skip,
-    stdout_str,
+    SessionGuard, api_base_url, assert_failure, assert_success, headless_json,
+    headless_json_with_env, headless_with_env, parse_json, skip, stdout_str, unique_session, url_a,
 };
 
 fn api_env() -> Vec<(&'static str, String)> {
@@ -123,3 +123,74 @@ fn search_connection_failure_exits_nonzero() {
     let out = headless_with_env(&["search", "test"], &env, 15);
     assert_failure(&out, "search connection failure");
 }
+
+#[test]
+fn search_from_page_scores_actions_with_page_context() {
+    if skip() {
+        return;
+    }
+
+    let (sid, profile) = unique_session("s");
+    let out = headless_json(
+        &[
+            "browser",
+            "start",
+            "--mode",
+            "local",
+            "--headless",
+            "--set-session-id",
+            &sid,
+            "--profile",
+            &profile,
+            "--open-url",
+            &url_a(),
+        ],
+        30,
+    );
+    assert_success(&out, "start session");
+    let _guard = SessionGuard::new(&sid);
+    let tid = parse_json(&out)["data"]["tab"]["tab_id"]
+        .as_str()
+        .unwrap()
+        .to_string();
+
+    let out = headless_search_json(
+        &[
+            "search",
+            "users",
+            "--from-page",
+            "--session",
+            &sid,
+            "--tab",
+            &tid,
+        ],
+        15,
+    );
+    assert_success(&out, "search --from-page");
+    let data: serde_json::Value =
+        serde_json::from_str(&stdout_str(&out)).expect("output should be valid JSON");
+    let action = &data[0]["groups"][0]["actions"][0];
+    assert!(
+        action["score"].is_number(),
+        "action should carry a score: {action}"
+    );
+    assert!(
+        action["matches"].is_object(),
+        "action should list matches: {action}"
+    );
+
+    // An unknown session is an error, not a context-free search.
+    let out = headless_search(
+        &[
+            "search",
+            "users",
+            "--from-page",
+            "--session",
+            "nope",
+            "--tab",
+            "t1",
+        ],
+        15,
+    );
+    assert_failure(&out, "search --from-page unknown session");
+}
@@ -0,0 +1,94 @@
+//! E2E tests for `actionbook serve`: the HTTP API reaches the same daemon
+//! as the CLI. Only session-less commands are used, so no browser is needed.
+
+use std::io::{Read, Write};
+use std::net::{TcpListener, TcpStream};
+use std::process::{Child, Command, Stdio};
+use std::time::{Duration, Instant};
+
+use serde_json::Value;
+
+use crate::harness::{SoloEnv, skip};
+
+struct Server(Child);
+
+impl Drop for Server {
+    fn drop(&mut self) {
+        let _ = self.0.kill();
+        let _ = self.0.wait();
+    }
+}
+
+fn free_port() -> u16 {
+    TcpListener::bind("127.0.0.1:0")
+        .unwrap()
+        .local_addr()
+        .unwrap()
+        .port()
+}
+
+/// One HTTP/1.1 request; returns the status code and the JSON body.
+fn request(port: u16, method: &str, path: &str, body: Option<&str>) -> (u16, Value) {
+    let mut stream = TcpStream::connect(("127.0.0.1", port)).expect("connect");
+    stream
+        .set_read_timeout(Some(Duration::from_secs(30)))
+        .unwrap();
+    let body = body.unwrap_or("");
+    write!(
+        stream,
+        "{method} {path} HTTP/1.1\r\nHost: localhost:{port}\r\nContent-Type: application/json\r\nContent-Length: {}\r\n\r\n{body}",
+        body.len()
+    )
+    .unwrap();
+    let mut raw = String::new();
+    stream.read_to_string(&mut raw).unwrap();
+    let (head, body) = raw.split_once("\r\n\r\n").expect("response head");
+    let status = head.split(' ').nth(1).unwrap().parse().unwrap();
+    (status, serde_json::from_str(body).expect("json body"))
+}
+
+#[test]
+fn serve_runs_browser_commands_over_http() {
+    if skip() {
+        return;
+    }
+    let env = SoloEnv::new();
+    let port = free_port();
+    let _server = Server(
+        Command::new(assert_cmd::cargo::cargo_bin("actionbook"))
+            .env("ACTIONBOOK_HOME", &env.actionbook_home)
+            .args(["serve", "--port", &port.to_string()])
+            .stdout(Stdio::null())
+            .stderr(Stdio::null())
+            .spawn()
+            .expect("spawn serve"),
+    );
+    let deadline = Instant::now() + Duration::from_secs(10);
+    while TcpStream::connect(("127.0.0.1", port)).is_err() {
+        assert!(Instant::now() < deadline, "serve never started listening");
+        std::thread::sleep(Duration::from_millis(50));
+    }
+
+    let (status, health) = request(port, "GET", "/v1/health", None);
+    assert_eq!(status, 200);
+    assert_eq!(health["ok"], true);
+
+    let (status, out) = request(port, "POST", "/v1/browser/list-sessions", Some("{}"));
+    assert_eq!(status, 200, "{out}");
+    assert_eq!(out["ok"], true);
+    assert_eq!(out["command"], "browser list-sessions");
+    assert_eq!(out["data"]["total_sessions"], 0);
+
+    let (status, out) = request(
+        port,
+        "POST",
+        "/v1/browser/status",
+        Some(r#"{"session": "missing"}"#),
+    );
+    assert_eq!(status, 422, "{out}");
+    assert_eq!(out["ok"], false);
+
+    let (status, out) = request(port, "POST", "/v1/browser/goto", Some("{}"));
+    assert_eq!(status, 400, "{out}");
+    assert_eq!(out["error"]["code"], "INVALID_ARGUMENT");
+}
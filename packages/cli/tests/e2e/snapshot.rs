@@ -492,6 +492,84 @@ fn snap_compact_flag_reduces_nodes() {
     close_session(&sid);
 }
 
+#[test]
+fn snap_format_json_writes_tree() {
+    if skip() {
+        return;
+    }
+    let (sid, tid) = start_session(&url_a());
+    let _guard = SessionGuard::new(&sid);
+
+    let out = headless_json(
+        &[
+            "browser",
+            "snapshot",
+            "--session",
+            &sid,
+            "--tab",
+            &tid,
+            "--format",
+            "json",
+        ],
+        30,
+    );
+    assert_success(&out, "snapshot --format json");
+    let v = parse_json(&out);
+    assert_eq!(v["data"]["format"], "snapshot");
+    assert_eq!(v["data"]["content_format"], "json");
+    let path = v["data"]["path"].as_str().unwrap();
+    assert!(path.ends_with(".json"), "json snapshot path: {path}");
+    let tree: serde_json::Value =
+        serde_json::from_str(&std::fs::read_to_string(path).unwrap()).unwrap();
+    assert!(tree[0]["role"].is_string(), "tree root must carry a role");
+
+    close_session(&sid);
+}
+
+#[test]
+fn snap_format_compact_folds_similar_items() {
+    if skip() {
+        return;
+    }
+    let (sid, tid) = start_session(&url_a());
+    let _guard = SessionGuard::new(&sid);
+
+    let js = r#"document.body.innerHTML = '<ul>' +
+  Array.from({length: 12}, (_, i) => '<li><a href="?i=' + i + '">Item ' + i + '</a></li>').join('') +
+  '</ul>'; void(0)"#;
+    let out = headless_json(
+        &["browser", "eval", js, "--session", &sid, "--tab", &tid],
+        10,
+    );
+    assert_success(&out, "inject list");
+
+    let out = headless_json(
+        &[
+            "browser",
+            "snapshot",
+            "--session",
+            &sid,
+            "--tab",
+            &tid,
+            "--format",
+            "compact",
+        ],
+        30,
+    );
+    assert_success(&out, "snapshot --format compact");
+    let v = parse_json(&out);
+    assert_eq!(v["data"]["content_format"], "compact");
+    let content = std::fs::read_to_string(v["data"]["path"].as_str().unwrap()).unwrap();
+    assert!(
+        content.contains("×10 similar items"),
+        "compact snapshot must fold the list: {content}"
+    );
+    assert!(content.contains("Item 1"), "{content}");
+    assert!(!content.contains("Item 5"), "{content}");
+
+    close_session(&sid);
+}
+
 #[test]
 fn snap_depth_flag_limits_nodes() {
     if skip() {
@@ -867,6 +945,51 @@ fn snap_cursor_content_has_clickable() {
     close_session(&sid);
 }
 
+#[test]
+fn snap_cdp_ax_backend_finds_the_same_cursor_elements() {
+    if skip() {
+        return;
+    }
+    let (sid, tid) = start_session(&url_cursor_fixture());
+    let _guard = SessionGuard::new(&sid);
+
+    let snapshot = |backend: &str| {
+        let out = headless_json(
+            &[
+                "browser",
+                "snapshot",
+                "--session",
+                &sid,
+                "--tab",
+                &tid,
+                "--backend",
+                backend,
+            ],
+            30,
+        );
+        assert_success(&out, &format!("snapshot --backend {backend}"));
+        parse_json(&out)
+    };
+    let js = snapshot("js");
+    let cdp_ax = snapshot("cdp-ax");
+
+    assert_eq!(js["data"]["backend"], "js");
+    assert_eq!(cdp_ax["data"]["backend"], "cdp-ax");
+    assert_eq!(
+        cdp_ax["data"]["stats"], js["data"]["stats"],
+        "both backends should report the same nodes"
+    );
+    assert!(
+        cdp_ax["data"]["stats"]["interactive_count"]
+            .as_u64()
+            .unwrap_or(0)
+            >= 3,
+        "cdp-ax should surface the fixture's clickable divs"
+    );
+
+    close_session(&sid);
+}
+
 // ===========================================================================
 // Group 4: snapshot — Error Paths (§3.1)
 // ===========================================================================
@@ -528,3 +528,138 @@ fn session_storage_tab_not_found_json() {
     assert!(v["context"]["tab_id"].is_null());
     assert_error_envelope(&v, "TAB_NOT_FOUND");
 }
+
+#[test]
+fn local_storage_clear_without_key_empties_storage() {
+    if skip() {
+        return;
+    }
+
+    let base_url = url_a();
+    let (sid, tid) = start_session(&base_url);
+    let _guard = SessionGuard::new(&sid);
+
+    set_storage(LOCAL, &sid, &tid, "clear-all-a", "1");
+    set_storage(LOCAL, &sid, &tid, "clear-all-b", "2");
+
+    let out = headless_json(
+        &[
+            "browser",
+            "local-storage",
+            "clear",
+            "--session",
+            &sid,
+            "--tab",
+            &tid,
+        ],
+        10,
+    );
+    assert_success(&out, "local-storage clear all");
+    let v = parse_json(&out);
+    assert_eq!(v["data"]["action"], "clear");
+    assert!(v["data"]["affected"].as_u64().unwrap_or(0) >= 2);
+
+    let list_out = headless_json(
+        &[
+            "browser",
+            "local-storage",
+            "list",
+            "--session",
+            &sid,
+            "--tab",
+            &tid,
+        ],
+        10,
+    );
+    assert_success(&list_out, "local-storage list after clear all");
+    let list_v = parse_json(&list_out);
+    assert_eq!(list_v["data"]["items"].as_array().map(Vec::len), Some(0));
+}
+
+#[test]
+fn indexeddb_list_and_dump_read_object_stores() {
+    if skip() {
+        return;
+    }
+
+    let base_url = url_a();
+    let (sid, tid) = start_session(&base_url);
+    let _guard = SessionGuard::new(&sid);
+
+    let seed = "new Promise((resolve, reject) => {
+        const req = indexedDB.open('e2e-db', 2);
+        req.onupgradeneeded = () => {
+            const store = req.result.createObjectStore('todos', { keyPath: 'id' });
+            store.createIndex('by_title', 'title');
+        };
+        req.onsuccess = () => {
+            const tx = req.result.transaction('todos', 'readwrite');
+            tx.objectStore('todos').put({ id: 1, title: 'b', due: new Date(0) });
+            tx.objectStore('todos').put({ id: 2, title: 'a', tags: new Set(['x']) });
+            tx.oncomplete = () => { req.result.close(); resolve('ok'); };
+            tx.onerror = () => reject(tx.error);
+        };
+        req.onerror = () => reject(req.error);
+    })";
+    let out = headless_json(
+        &["browser", "eval", seed, "--session", &sid, "--tab", &tid],
+        10,
+    );
+    assert_success(&out, "seed indexeddb");
+
+    let out = headless_json(
+        &[
+            "browser",
+            "indexeddb",
+            "list",
+            "--session",
+            &sid,
+            "--tab",
+            &tid,
+        ],
+        10,
+    );
+    assert_success(&out, "indexeddb list");
+    let v = parse_json(&out);
+    let db = v["data"]["databases"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .find(|d| d["name"] == "e2e-db")
+        .expect("e2e-db listed")
+        .clone();
+    assert_eq!(db["version"], 2);
+    assert_eq!(db["stores"][0]["name"], "todos");
+    assert_eq!(db["stores"][0]["key_path"], "id");
+    assert_eq!(db["stores"][0]["entries"], 2);
+    assert_eq!(db["stores"][0]["indexes"][0]["name"], "by_title");
+
+    let out = headless_json(
+        &[
+            "browser",
+            "indexeddb",
+            "dump",
+            "e2e-db",
+            "todos",
+            "--index",
+            "by_title",
+            "--limit",
+            "1",
+            "--session",
+            &sid,
+            "--tab",
+            &tid,
+        ],
+        10,
+    );
+    assert_success(&out, "indexeddb dump");
+    let v = parse_json(&out);
+    assert_eq!(v["data"]["count"], 1);
+    assert_eq!(v["data"]["has_more"], true);
+    assert_eq!(v["data"]["entries"][0]["key"], "a");
+    assert_eq!(v["data"]["entries"][0]["primary_key"], 2);
+    assert_eq!(
+        v["data"]["entries"][0]["value"]["tags"],
+        serde_json::json!(["x"])
+    );
+}
@@ -1,4 +1,4 @@
-//! Browser tab management E2E tests: list-tabs, new-tab, close-tab.
+//! Browser tab management E2E tests: list-tabs, new-tab, close-tab, window.
 //!
 //! Each test is self-contained: start -> operate -> assert -> close.
 //! Covers BOTH JSON and text output.
@@ -8,8 +8,8 @@
 use crate::harness::{
     SessionGuard, assert_context_object, assert_context_with_session, assert_error_envelope,
     assert_failure, assert_meta, assert_native_tab_id, assert_success, assert_tab_id, headless,
-    headless_json, new_tab_json, parse_json, skip, start_named_session, start_session, stdout_str,
-    unique_session, url_a, url_b, url_c,
+    headless_json, new_tab_json, parse_json, skip, start_named_session, start_session,
+    start_session_with, stdout_str, unique_session, url_a, url_b, url_c,
 };
 
 // ===========================================================================
@@ -389,6 +389,39 @@ fn tab_new_tab_multiple_urls_partial_failure_text() {
     assert!(text.contains("[failed] javascript:alert(1) - INVALID_ARGUMENT:"));
 }
 
+#[test]
+fn tab_new_tab_over_session_download_budget_json() {
+    if skip() {
+        return;
+    }
+    let (sid, _t1) = start_session_with(&url_a(), &["--max-download-bytes", "1"]);
+    let _guard = SessionGuard::new(&sid);
+
+    let out = headless_json(&["browser", "new-tab", &url_b(), "--session", &sid], 30);
+    assert_failure(&out, "new-tab over session download budget");
+    let v = parse_json(&out);
+
+    assert_eq!(v["command"], "browser new-tab");
+    assert_error_envelope(&v, "DOWNLOAD_BUDGET_EXCEEDED");
+}
+
+#[test]
+fn tab_new_tab_within_session_download_budget_reports_bytes() {
+    if skip() {
+        return;
+    }
+    let (sid, _t1) = start_session_with(&url_a(), &["--max-download-bytes", "10000000"]);
+    let _guard = SessionGuard::new(&sid);
+
+    let out = headless_json(&["browser", "new-tab", &url_b(), "--session", &sid], 30);
+    assert_success(&out, "new-tab within session download budget");
+    let v = parse_json(&out);
+
+    let download = &v["data"]["tab"]["download"];
+    assert_eq!(download["limit"], 10_000_000);
+    assert!(download["bytes"].as_u64().unwrap_or(0) > 0);
+}
+
 // ===========================================================================
 // Group 3: close-tab — Basic
 // ===========================================================================
@@ -1255,3 +1288,188 @@ fn tab_new_tab_with_tab_alias_conflict() {
         "conflict should return TAB_ID_CONFLICT"
     );
 }
+
+// ===========================================================================
+// Group: window
+// ===========================================================================
+
+#[test]
+fn tab_window_resize_json() {
+    if skip() {
+        return;
+    }
+    let (sid, tid) = start_session(&url_a());
+    let _guard = SessionGuard::new(&sid);
+
+    let out = headless_json(
+        &[
+            "browser",
+            "window",
+            "--session",
+            &sid,
+            "--tab",
+            &tid,
+            "--size",
+            "1024x700",
+        ],
+        15,
+    );
+    assert_success(&out, "window --size");
+    let v = parse_json(&out);
+    assert_eq!(v["command"], "browser window");
+    assert_eq!(v["data"]["width"], 1024);
+    assert_eq!(v["data"]["height"], 700);
+    assert!(v["data"]["window_id"].is_i64());
+}
+
+#[test]
+fn tab_window_invalid_size_json() {
+    if skip() {
+        return;
+    }
+    let (sid, tid) = start_session(&url_a());
+    let _guard = SessionGuard::new(&sid);
+
+    let out = headless_json(
+        &[
+            "browser",
+            "window",
+            "--session",
+            &sid,
+            "--tab",
+            &tid,
+            "--size",
+            "wide",
+        ],
+        15,
+    );
+    assert_failure(&out, "window --size wide");
+    let v = parse_json(&out);
+    assert_error_envelope(&v, "INVALID_ARGUMENT");
+}
+
+// ===========================================================================
+// Group: switch — active tab used with --tab active
+// ===========================================================================
+
+#[test]
+fn tab_switch_makes_tab_active_target_the_switched_tab() {
+    if skip() {
+        return;
+    }
+    let (sid, t1) = start_session(&url_a());
+    let _guard = SessionGuard::new(&sid);
+    let t2 = new_tab_json(&sid, &url_b());
+
+    // Before any switch, `--tab active` means the first tab.
+    let out = headless_json(
+        &["browser", "url", "--session", &sid, "--tab", "active"],
+        10,
+    );
+    assert_success(&out, "url before switch");
+    let v = parse_json(&out);
+    assert_eq!(v["context"]["tab_id"], t1);
+    assert!(v["data"].get("__ctx_tab").is_none());
+
+    let out = headless_json(&["browser", "switch", &t2, "--session", &sid], 10);
+    assert_success(&out, "switch");
+    let v = parse_json(&out);
+    assert_eq!(v["command"], "browser switch");
+    assert_eq!(v["data"]["tab_id"], t2);
+    assert_eq!(v["data"]["previous_tab_id"], t1);
+
+    let out = headless_json(
+        &["browser", "url", "--session", &sid, "--tab", "active"],
+        10,
+    );
+    assert_success(&out, "url after switch");
+    let v = parse_json(&out);
+    assert_eq!(v["context"]["tab_id"], t2);
+    assert!(
+        v["context"]["url"]
+            .as_str()
+            .unwrap_or("")
+            .starts_with(&url_b()),
+        "--tab active should run on the switched-to tab: {v}"
+    );
+
+    // Switching never makes --tab optional.
+    let out = headless_json(&["browser", "url", "--session", &sid], 10);
+    assert_failure(&out, "url without --tab after switch");
+
+    let out = headless_json(&["browser", "list-tabs", "--session", &sid], 10);
+    let v = parse_json(&out);
+    let active: Vec<&str> = v["data"]["tabs"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .filter(|t| t["active"] == true)
+        .filter_map(|t| t["tab_id"].as_str())
+        .collect();
+    assert_eq!(active, vec![t2.as_str()]);
+
+    // Closing the active tab falls back to the first tab.
+    let out = headless(
+        &["browser", "close-tab", "--session", &sid, "--tab", &t2],
+        30,
+    );
+    assert_success(&out, "close switched tab");
+    let out = headless_json(
+        &["browser", "url", "--session", &sid, "--tab", "active"],
+        10,
+    );
+    assert_eq!(parse_json(&out)["context"]["tab_id"], t1);
+}
+
+#[test]
+fn tab_flag_accepts_position_and_url_substring() {
+    if skip() {
+        return;
+    }
+    let (sid, t1) = start_session(&url_a());
+    let _guard = SessionGuard::new(&sid);
+    let t2 = new_tab_json(&sid, &url_b());
+    let _t3 = new_tab_json(&sid, &url_c());
+
+    let url_of = |tab: &str| {
+        let out = headless_json(&["browser", "url", "--session", &sid, "--tab", tab], 10);
+        assert_success(&out, &format!("url --tab {tab}"));
+        parse_json(&out)
+    };
+
+    // A 1-based position in list-tabs.
+    let v = url_of("2");
+    assert_eq!(v["context"]["tab_id"], t2);
+    assert!(v["data"]["url"].as_str().unwrap().ends_with("/page-b"));
+
+    // Part of exactly one tab's current URL.
+    let v = url_of("page-a");
+    assert_eq!(v["context"]["tab_id"], t1);
+
+    // A tab ID still wins.
+    assert_eq!(url_of(&t2)["context"]["tab_id"], t2);
+
+    let out = headless_json(&["browser", "url", "--session", &sid, "--tab", "9"], 10);
+    assert_failure(&out, "position past the last tab");
+    assert_error_envelope(&parse_json(&out), "TAB_NOT_FOUND");
+
+    let out = headless_json(
+        &["browser", "url", "--session", &sid, "--tab", "/page-"],
+        10,
+    );
+    assert_failure(&out, "substring matching every tab");
+    assert_error_envelope(&parse_json(&out), "INVALID_ARGUMENT");
+}
+
+#[test]
+fn tab_switch_nonexistent_tab_json() {
+    if skip() {
+        return;
+    }
+    let (sid, _t1) = start_session(&url_a());
+    let _guard = SessionGuard::new(&sid);
+
+    let out = headless_json(&["browser", "switch", "t99", "--session", &sid], 10);
+    assert_failure(&out, "switch to missing tab");
+    assert_error_envelope(&parse_json(&out), "TAB_NOT_FOUND");
+}
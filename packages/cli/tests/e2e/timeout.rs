@@ -1,5 +1,6 @@
 use crate::harness::{
-    SessionGuard, assert_error_envelope, assert_failure, parse_json, skip, start_session, url_slow,
+    SessionGuard, SoloEnv, assert_error_envelope, assert_failure, assert_success, parse_json, skip,
+    start_session, url_slow,
 };
 
 #[test]
@@ -35,3 +36,165 @@ fn global_timeout_applies_to_browser_commands() {
     assert_eq!(v["context"]["session_id"], sid);
     assert_eq!(v["context"]["tab_id"], tid);
 }
+
+#[test]
+fn goto_timeout_flag_bounds_the_navigation() {
+    if skip() {
+        return;
+    }
+
+    let (sid, tid) = start_session("about:blank");
+    let _guard = SessionGuard::new(&sid);
+
+    let slow_url = url_slow();
+    let out = crate::harness::headless_json(
+        &[
+            "browser",
+            "goto",
+            &slow_url,
+            "--session",
+            &sid,
+            "--tab",
+            &tid,
+            "--timeout",
+            "50",
+        ],
+        10,
+    );
+
+    assert_failure(&out, "goto --timeout");
+    let v = parse_json(&out);
+    assert_eq!(v["command"], "browser goto");
+    assert_error_envelope(&v, "TIMEOUT");
+    assert!(
+        v["error"]["message"]
+            .as_str()
+            .unwrap_or("")
+            .contains("50ms"),
+        "message should name the timeout: {}",
+        v["error"]
+    );
+}
+
+#[test]
+fn navigation_timeout_defaults_from_config() {
+    if skip() {
+        return;
+    }
+    let env = SoloEnv::new();
+    std::fs::write(
+        env.config_path(),
+        "version = 1\n\n[browser]\nnavigation_timeout_ms = 50\n",
+    )
+    .expect("write config");
+
+    let out = env.headless_json(
+        &[
+            "browser",
+            "start",
+            "--mode",
+            "local",
+            "--headless",
+            "--open-url",
+            "about:blank",
+        ],
+        30,
+    );
+    assert_success(&out, "start");
+    let v = parse_json(&out);
+    let sid = v["data"]["session"]["session_id"]
+        .as_str()
+        .unwrap()
+        .to_string();
+    let tid = v["data"]["tab"]["tab_id"].as_str().unwrap().to_string();
+
+    let slow_url = url_slow();
+    let out = env.headless_json(
+        &[
+            "browser",
+            "goto",
+            &slow_url,
+            "--session",
+            &sid,
+            "--tab",
+            &tid,
+        ],
+        10,
+    );
+    assert_failure(&out, "goto under a 50ms configured timeout");
+    assert_error_envelope(&parse_json(&out), "TIMEOUT");
+
+    // The flag still wins over the configured default.
+    let out = env.headless_json(
+        &[
+            "browser",
+            "goto",
+            &slow_url,
+            "--session",
+            &sid,
+            "--tab",
+            &tid,
+            "--timeout",
+            "10000",
+        ],
+        15,
+    );
+    assert_success(&out, "goto --timeout overrides config");
+
+    env.headless(&["browser", "close", "--session", &sid], 30);
+}
+
+#[cfg(unix)]
+#[test]
+fn ctrl_c_interrupts_a_running_command() {
+    if skip() {
+        return;
+    }
+
+    let (sid, tid) = start_session("about:blank");
+    let _guard = SessionGuard::new(&sid);
+
+    let child = std::process::Command::new(assert_cmd::cargo::cargo_bin("actionbook"))
+        .env(
+            "ACTIONBOOK_HOME",
+            &crate::harness::shared_env().actionbook_home,
+        )
+        .args([
+            "--json",
+            "browser",
+            "wait",
+            "element",
+            "#never-appears",
+            "--session",
+            &sid,
+            "--tab",
+            &tid,
+            "--timeout",
+            "30000",
+        ])
+        .stdin(std::process::Stdio::null())
+        .stdout(std::process::Stdio::piped())
+        .stderr(std::process::Stdio::piped())
+        .spawn()
+        .expect("spawn wait");
+    std::thread::sleep(std::time::Duration::from_secs(2));
+    let started = std::time::Instant::now();
+    let status = std::process::Command::new("kill")
+        .args(["-INT", &child.id().to_string()])
+        .status()
+        .expect("send SIGINT");
+    assert!(status.success());
+    let out = child.wait_with_output().expect("wait for interrupted cli");
+
+    assert_eq!(out.status.code(), Some(130));
+    assert!(started.elapsed() < std::time::Duration::from_secs(5));
+    let v = parse_json(&out);
+    assert_error_envelope(&v, "INTERRUPTED");
+
+    // The daemon dropped the wait loop and keeps serving the tab.
+    let out = crate::harness::headless_json(
+        &["browser", "eval", "1 + 1", "--session", &sid, "--tab", &tid],
+        10,
+    );
+    assert_success(&out, "eval after interrupt");
+}
@@ -0,0 +1,67 @@
+//! E2E tests for `browser trace`: a session-wide Chrome performance trace.
+
+use std::fs;
+
+use crate::harness::{
+    SessionGuard, assert_error_envelope, assert_failure, assert_success, headless_json, parse_json,
+    skip, start_session, url_a,
+};
+
+#[test]
+fn trace_writes_a_chrome_json_trace() {
+    if skip() {
+        return;
+    }
+    let (sid, tid) = start_session(&url_a());
+    let _guard = SessionGuard::new(&sid);
+
+    let dir = tempfile::tempdir().unwrap();
+    let out = dir.path().join("trace.json");
+    let out_path = out.to_string_lossy().to_string();
+
+    let started = headless_json(
+        &[
+            "browser",
+            "trace",
+            "start",
+            "--out",
+            &out_path,
+            "--session",
+            &sid,
+        ],
+        20,
+    );
+    assert_success(&started, "trace start");
+    let again = headless_json(
+        &[
+            "browser",
+            "trace",
+            "start",
+            "--out",
+            &out_path,
+            "--session",
+            &sid,
+        ],
+        20,
+    );
+    assert_failure(&again, "second trace start");
+    assert_error_envelope(&parse_json(&again), "TRACE_ACTIVE");
+
+    assert_success(
+        &headless_json(&["browser", "reload", "--session", &sid, "--tab", &tid], 20),
+        "reload while tracing",
+    );
+
+    let stopped = headless_json(&["browser", "trace", "stop", "--session", &sid], 90);
+    assert_success(&stopped, "trace stop");
+    let data = &parse_json(&stopped)["data"];
+    assert!(data["bytes"].as_u64().unwrap() > 0, "{data}");
+    let trace: serde_json::Value =
+        serde_json::from_str(&fs::read_to_string(&out).unwrap()).expect("trace is JSON");
+    let events = trace["traceEvents"].as_array().expect("traceEvents array");
+    assert!(!events.is_empty());
+
+    let out = headless_json(&["browser", "trace", "stop", "--session", &sid], 20);
+    assert_failure(&out, "stop without a trace");
+    assert_error_envelope(&parse_json(&out), "TRACE_NOT_ACTIVE");
+}
@@ -227,6 +227,50 @@ fn wait_element_timeout_json() {
     assert_eq!(v["error"]["retryable"], true);
 }
 
+#[test]
+fn wait_element_streams_progress_before_the_envelope() {
+    if skip() {
+        return;
+    }
+
+    let (sid, tid) = start_session("about:blank");
+    let _guard = SessionGuard::new(&sid);
+
+    let out = headless_json(
+        &[
+            "--stream",
+            "browser",
+            "wait",
+            "element",
+            ".never-there",
+            "--session",
+            &sid,
+            "--tab",
+            &tid,
+            "--timeout",
+            "2500",
+        ],
+        10,
+    );
+    assert_failure(&out, "wait element stream");
+    let lines: Vec<serde_json::Value> = stdout_str(&out)
+        .lines()
+        .map(|l| serde_json::from_str(l).expect("each stdout line is JSON"))
+        .collect();
+    let (envelope, events) = lines.split_last().expect("at least the envelope");
+
+    assert!(!events.is_empty(), "expected progress events: {lines:?}");
+    for event in events {
+        assert_eq!(event["type"], "progress");
+        assert_eq!(event["command"], "browser wait element");
+        assert_eq!(event["data"]["kind"], "element");
+        assert_eq!(event["data"]["match_count"], 0);
+        assert_eq!(event["data"]["ready_state"], "complete");
+        assert!(event["data"]["elapsed_ms"].is_number());
+    }
+    assert_error_envelope(envelope, "TIMEOUT");
+}
+
 #[test]
 fn wait_navigation_json_happy_path() {
     if skip() {
@@ -845,6 +889,38 @@ fn wait_condition_timeout_json() {
     assert_eq!(v["error"]["retryable"], true);
 }
 
+#[test]
+fn wait_condition_honours_poll_interval() {
+    if skip() {
+        return;
+    }
+
+    let (sid, tid) = start_session("about:blank");
+    let _guard = SessionGuard::new(&sid);
+
+    // Truthy on the fourth evaluation: three 200ms gaps before it.
+    let out = headless_json(
+        &[
+            "browser",
+            "wait",
+            "condition",
+            "(window.__polls = (window.__polls || 0) + 1) >= 4",
+            "--poll-interval",
+            "200",
+            "--session",
+            &sid,
+            "--tab",
+            &tid,
+            "--timeout",
+            "5000",
+        ],
+        10,
+    );
+    assert_success(&out, "wait condition with poll interval");
+    let v = parse_json(&out);
+    assert!(v["data"]["elapsed_ms"].as_u64().unwrap() >= 600);
+}
+
 #[test]
 fn wait_condition_text_output() {
     if skip() {
@@ -951,3 +1027,117 @@ fn wait_tab_not_found_json() {
     assert!(v["context"]["tab_id"].is_null());
     assert_error_envelope(&v, "TAB_NOT_FOUND");
 }
+
+#[test]
+fn wait_element_hidden_waits_for_removal() {
+    if skip() {
+        return;
+    }
+
+    let (sid, tid) = start_session("about:blank");
+    let _guard = SessionGuard::new(&sid);
+    let js = r#"const s = document.createElement('div');
+s.className = 'spinner';
+s.textContent = 'Loading';
+document.body.appendChild(s);
+setTimeout(() => { s.style.display = 'none'; }, 300);
+void(0)"#;
+    let out = headless_json(
+        &["browser", "eval", js, "--session", &sid, "--tab", &tid],
+        10,
+    );
+    assert_success(&out, "add spinner");
+
+    let out = headless_json(
+        &[
+            "browser",
+            "wait",
+            "element",
+            ".spinner",
+            "--hidden",
+            "--session",
+            &sid,
+            "--tab",
+            &tid,
+            "--timeout",
+            "5000",
+        ],
+        10,
+    );
+    assert_success(&out, "wait element --hidden");
+    let v = parse_json(&out);
+    assert_eq!(v["data"]["observed_value"]["state"], "hidden");
+    assert!(v["data"]["elapsed_ms"].as_u64().unwrap() >= 200);
+}
+
+#[test]
+fn wait_text_and_url_follow_page_changes() {
+    if skip() {
+        return;
+    }
+
+    let (sid, tid) = start_session("about:blank");
+    let _guard = SessionGuard::new(&sid);
+    schedule_element(&sid, &tid);
+
+    let out = headless_json(
+        &[
+            "browser",
+            "wait",
+            "text",
+            "Ready",
+            "--selector",
+            ELEMENT_SELECTOR,
+            "--session",
+            &sid,
+            "--tab",
+            &tid,
+            "--timeout",
+            "5000",
+        ],
+        10,
+    );
+    assert_success(&out, "wait text");
+    let v = parse_json(&out);
+    assert_eq!(v["command"], "browser wait text");
+    assert_eq!(v["data"]["kind"], "text");
+
+    schedule_navigation_to(&sid, &tid, &url_b());
+    let out = headless_json(
+        &[
+            "browser",
+            "wait",
+            "url",
+            "/page-b$",
+            "--session",
+            &sid,
+            "--tab",
+            &tid,
+            "--timeout",
+            "10000",
+        ],
+        15,
+    );
+    assert_success(&out, "wait url");
+    let v = parse_json(&out);
+    assert_eq!(v["data"]["kind"], "url");
+    assert_eq!(v["data"]["observed_value"], url_b());
+
+    let out = headless_json(
+        &[
+            "browser",
+            "wait",
+            "url",
+            "never-matches",
+            "--session",
+            &sid,
+            "--tab",
+            &tid,
+            "--timeout",
+            "300",
+        ],
+        10,
+    );
+    assert_failure(&out, "wait url timeout");
+    assert_error_envelope(&parse_json(&out), "TIMEOUT");
+}
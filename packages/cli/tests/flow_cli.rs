@@ -0,0 +1,121 @@
+//! Integration tests for `actionbook flow`.
+
+use assert_cmd::Command;
+use serde_json::Value;
+
+const SPEC: &str = "\
+import { test, expect } from '@playwright/test';
+
+test('docs', async ({ page }) => {
+  await page.goto('https://example.com/');
+  await page.getByRole('link', { name: 'Docs' }).click();
+  await expect(page).toHaveURL('https://example.com/docs');
+  await page.evaluate(() => window.scrollTo(0, 0));
+});
+";
+
+fn flow(args: &[&str]) -> std::process::Output {
+    Command::cargo_bin("actionbook")
+        .expect("binary exists")
+        .arg("flow")
+        .args(args)
+        .output()
+        .expect("run actionbook flow")
+}
+
+#[test]
+fn flow_import_writes_a_run_script_next_to_the_spec() {
+    let dir = tempfile::tempdir().unwrap();
+    let spec = dir.path().join("docs.spec.ts");
+    std::fs::write(&spec, SPEC).unwrap();
+
+    let output = flow(&["import", spec.to_str().unwrap()]);
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(
+        output.status.success(),
+        "stdout:\n{stdout}\nstderr:\n{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    assert!(stdout.contains("3 step(s)"), "stdout:\n{stdout}");
+    assert!(stdout.contains("skipped line 7"), "stdout:\n{stdout}");
+
+    let yaml = std::fs::read_to_string(dir.path().join("docs.yaml")).unwrap();
+    assert!(yaml.starts_with("# Imported from docs.spec.ts"), "{yaml}");
+    let script: serde_yaml::Value = serde_yaml::from_str(&yaml).unwrap();
+    assert_eq!(script["steps"][0]["goto"], "https://example.com/");
+    assert_eq!(script["steps"][1]["click"], "role=link[name=\"Docs\"]");
+    assert_eq!(
+        script["steps"][2]["assert"]["url"],
+        "https://example.com/docs"
+    );
+}
+
+#[test]
+fn flow_import_refuses_to_overwrite_without_force() {
+    let dir = tempfile::tempdir().unwrap();
+    let spec = dir.path().join("docs.spec.ts");
+    let out = dir.path().join("docs.yaml");
+    std::fs::write(&spec, SPEC).unwrap();
+    std::fs::write(&out, "steps: []\n").unwrap();
+
+    let output = flow(&["--json", "import", spec.to_str().unwrap()]);
+    assert!(!output.status.success());
+    let envelope: Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(envelope["error"]["code"], "INVALID_ARGUMENT");
+    assert_eq!(std::fs::read_to_string(&out).unwrap(), "steps: []\n");
+
+    let output = flow(&["import", spec.to_str().unwrap(), "--force"]);
+    assert!(output.status.success());
+    assert!(
+        std::fs::read_to_string(&out)
+            .unwrap()
+            .contains("goto: https://example.com/")
+    );
+}
+
+#[test]
+fn flow_export_round_trips_through_import() {
+    let dir = tempfile::tempdir().unwrap();
+    let flow_path = dir.path().join("shop.yaml");
+    std::fs::write(
+        &flow_path,
+        "\
+steps:
+  - goto: https://shop.test/
+  - click: role=link[name=\"Sale\"]
+  - fill: { selector: '#search', text: red shoes }
+  - browser: [press, Enter]
+  - assert: { url: /search?q=red }
+  - browser: [scroll, down]
+",
+    )
+    .unwrap();
+
+    let output = flow(&["--json", "export", flow_path.to_str().unwrap()]);
+    assert!(output.status.success());
+    let envelope: Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(envelope["data"]["steps"], 5);
+    assert_eq!(envelope["data"]["skipped"][0]["step"], 6);
+    let spec = dir.path().join("shop.spec.ts");
+    let code = std::fs::read_to_string(&spec).unwrap();
+    assert!(
+        code.contains("await page.getByRole('link', { name: 'Sale' }).click();"),
+        "{code}"
+    );
+
+    let back = dir.path().join("back.yaml");
+    let output = flow(&[
+        "import",
+        spec.to_str().unwrap(),
+        "--out",
+        back.to_str().unwrap(),
+    ]);
+    assert!(output.status.success());
+    let original: serde_yaml::Value =
+        serde_yaml::from_str(&std::fs::read_to_string(&flow_path).unwrap()).unwrap();
+    let imported: serde_yaml::Value =
+        serde_yaml::from_str(&std::fs::read_to_string(&back).unwrap()).unwrap();
+    let mut expected = original["steps"].as_sequence().unwrap().clone();
+    expected.pop();
+    assert_eq!(imported["steps"].as_sequence().unwrap(), &expected);
+}
@@ -0,0 +1,100 @@
+//! Integration tests for `actionbook get`.
+//!
+//! Like `search_cli.rs`, each test points `ACTIONBOOK_API_URL` at a local
+//! `wiremock` server and runs the real CLI binary.
+
+use assert_cmd::Command;
+use serde_json::Value;
+use wiremock::matchers::{method, path, query_param};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+const DOCUMENT: &str = "\
+## Overview
+
+Action found: airbnb.com:/:default
+
+## Steps
+
+1. Fill the location field
+2. Press search
+
+## Selectors
+
+- location: #bigsearch-query-location-input
+
+## History
+
+Long change log.";
+
+#[tokio::test]
+async fn get_sections_returns_only_the_requested_parts() {
+    let mock = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/api/get_action_by_area_id"))
+        .and(query_param("area_id", "airbnb.com:/:default"))
+        .and(query_param("sections", "steps,selectors"))
+        .respond_with(ResponseTemplate::new(200).set_body_string(DOCUMENT))
+        .expect(1)
+        .mount(&mock)
+        .await;
+
+    let output = Command::cargo_bin("actionbook")
+        .expect("binary exists")
+        .env("ACTIONBOOK_API_URL", mock.uri())
+        .args([
+            "--json",
+            "get",
+            "airbnb.com:/:default",
+            "--sections",
+            "steps,Selectors",
+        ])
+        .output()
+        .expect("run actionbook get");
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(
+        output.status.success(),
+        "expected success\nstdout:\n{stdout}\nstderr:\n{stderr}"
+    );
+    let parsed: Value = serde_json::from_str(stdout.trim()).expect("JSON output");
+    let result = parsed["result"].as_str().unwrap();
+    assert!(result.contains("Press search"), "{result}");
+    assert!(
+        result.contains("#bigsearch-query-location-input"),
+        "{result}"
+    );
+    assert!(!result.contains("Overview"), "{result}");
+    assert!(!result.contains("change log"), "{result}");
+    assert_eq!(
+        parsed["sections"],
+        serde_json::json!(["Steps", "Selectors"])
+    );
+}
+
+#[tokio::test]
+async fn get_max_tokens_trims_the_document() {
+    let mock = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/api/get_action_by_area_id"))
+        .and(query_param("max_tokens", "12"))
+        .respond_with(ResponseTemplate::new(200).set_body_string(DOCUMENT))
+        .mount(&mock)
+        .await;
+
+    let output = Command::cargo_bin("actionbook")
+        .expect("binary exists")
+        .env("ACTIONBOOK_API_URL", mock.uri())
+        .args(["get", "airbnb.com:/:default", "--max-tokens", "12"])
+        .output()
+        .expect("run actionbook get");
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(output.status.success(), "stdout:\n{stdout}");
+    assert!(stdout.starts_with("## Overview"), "stdout:\n{stdout}");
+    assert!(
+        stdout.contains("[truncated to ~12 tokens"),
+        "stdout:\n{stdout}"
+    );
+    assert!(!stdout.contains("Press search"), "stdout:\n{stdout}");
+}
@@ -0,0 +1,118 @@
+//! Integration tests for `actionbook publish`.
+//!
+//! Like `search_cli.rs`, uploads go to a local `wiremock` server through
+//! `ACTIONBOOK_API_URL`.
+
+use assert_cmd::Command;
+use serde_json::{Value, json};
+use wiremock::matchers::{body_partial_json, method, path};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+const DRAFT: &str = "\
+area_id: example.com:/:default
+title: Open the docs
+steps:
+  - goto: https://example.com/
+  - click: a.docs
+";
+
+fn write_draft(dir: &tempfile::TempDir, name: &str, body: &str) -> std::path::PathBuf {
+    let file = dir.path().join(name);
+    std::fs::write(&file, body).unwrap();
+    file
+}
+
+#[tokio::test]
+async fn publish_uploads_the_draft_with_its_source() {
+    let mock = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(path("/api/publish_action"))
+        .and(body_partial_json(json!({
+            "source_id": "example",
+            "action_book": { "area_id": "example.com:/:default", "title": "Open the docs" },
+        })))
+        .respond_with(ResponseTemplate::new(200).set_body_string("Published revision 3."))
+        .expect(1)
+        .mount(&mock)
+        .await;
+
+    let dir = tempfile::tempdir().unwrap();
+    let draft = write_draft(&dir, "draft.yaml", DRAFT);
+    let output = Command::cargo_bin("actionbook")
+        .expect("binary exists")
+        .env("ACTIONBOOK_API_URL", mock.uri())
+        .arg("publish")
+        .arg(&draft)
+        .args(["--source", "example"])
+        .output()
+        .expect("run actionbook publish");
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(
+        output.status.success(),
+        "expected success\nstdout:\n{stdout}\nstderr:\n{stderr}"
+    );
+    assert!(
+        stdout.contains("Published example.com:/:default (2 steps)"),
+        "stdout:\n{stdout}"
+    );
+    assert!(
+        stdout.contains("Published revision 3."),
+        "stdout:\n{stdout}"
+    );
+}
+
+#[tokio::test]
+async fn publish_dry_run_reports_problems_without_uploading() {
+    let mock = MockServer::start().await;
+    Mock::given(method("POST"))
+        .respond_with(ResponseTemplate::new(200))
+        .expect(0)
+        .mount(&mock)
+        .await;
+
+    let dir = tempfile::tempdir().unwrap();
+    let draft = write_draft(
+        &dir,
+        "draft.json",
+        r#"{"area_id": "example.com", "steps": [{"click": "a"}, {"teleport": "b"}]}"#,
+    );
+    let output = Command::cargo_bin("actionbook")
+        .expect("binary exists")
+        .env("ACTIONBOOK_API_URL", mock.uri())
+        .args(["--json", "publish"])
+        .arg(&draft)
+        .arg("--dry-run")
+        .output()
+        .expect("run actionbook publish");
+
+    assert!(!output.status.success());
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let first: Value =
+        serde_json::from_str(stdout.lines().next().unwrap_or_default()).expect("JSON report");
+    assert_eq!(first["valid"], false);
+    let errors = first["errors"].as_array().unwrap();
+    assert_eq!(errors.len(), 2, "{errors:?}");
+    assert!(errors[1].as_str().unwrap().starts_with("step 2:"));
+}
+
+#[tokio::test]
+async fn publish_dry_run_accepts_a_valid_draft() {
+    let dir = tempfile::tempdir().unwrap();
+    let draft = write_draft(&dir, "draft.yaml", DRAFT);
+    let output = Command::cargo_bin("actionbook")
+        .expect("binary exists")
+        .arg("publish")
+        .arg(&draft)
+        .arg("--dry-run")
+        .output()
+        .expect("run actionbook publish");
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(output.status.success(), "stdout:\n{stdout}");
+    assert!(
+        stdout.contains("is valid: example.com:/:default (2 steps)"),
+        "stdout:\n{stdout}"
+    );
+}
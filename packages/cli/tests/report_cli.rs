@@ -0,0 +1,144 @@
+//! Integration tests for `actionbook report` and the report `actionbook run
+//! --area-id` posts when a run ends.
+//!
+//! Like `search_cli.rs`, each test points `ACTIONBOOK_API_URL` at a local
+//! `wiremock` server and runs the real CLI binary.
+
+use assert_cmd::Command;
+use serde_json::json;
+use wiremock::matchers::{body_partial_json, header, method, path};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+#[tokio::test]
+async fn report_posts_outcome_and_notes() {
+    let mock = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(path("/api/report_action"))
+        .and(header("X-API-Key", "k1"))
+        .and(body_partial_json(json!({
+            "area_id": "airbnb.com:/:default",
+            "outcome": "failure",
+            "notes": "search button moved",
+        })))
+        .respond_with(ResponseTemplate::new(200).set_body_string("Thanks, recorded."))
+        .expect(1)
+        .mount(&mock)
+        .await;
+
+    let output = Command::cargo_bin("actionbook")
+        .expect("binary exists")
+        .env("ACTIONBOOK_API_URL", mock.uri())
+        .args([
+            "--api-key",
+            "k1",
+            "report",
+            "airbnb.com:/:default",
+            "--outcome",
+            "failure",
+            "--notes",
+            "search button moved",
+        ])
+        .output()
+        .expect("run actionbook report");
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(
+        output.status.success(),
+        "expected success\nstdout:\n{stdout}\nstderr:\n{stderr}"
+    );
+    assert!(
+        stdout.contains("Reported failure for airbnb.com:/:default"),
+        "stdout:\n{stdout}"
+    );
+    assert!(stdout.contains("Thanks, recorded."), "stdout:\n{stdout}");
+}
+
+#[tokio::test]
+async fn report_attaches_run_report_file() {
+    let mock = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(path("/api/report_action"))
+        .and(body_partial_json(json!({
+            "outcome": "success",
+            "run_report": { "total": 3, "failed": 0 },
+        })))
+        .respond_with(ResponseTemplate::new(200))
+        .expect(1)
+        .mount(&mock)
+        .await;
+
+    let dir = tempfile::tempdir().expect("tempdir");
+    let file = dir.path().join("report.json");
+    std::fs::write(&file, r#"{"total": 3, "failed": 0, "steps": []}"#).unwrap();
+
+    let output = Command::cargo_bin("actionbook")
+        .expect("binary exists")
+        .env("ACTIONBOOK_API_URL", mock.uri())
+        .args([
+            "--json",
+            "report",
+            "x.com:/:default",
+            "--outcome",
+            "success",
+        ])
+        .arg("--run-report")
+        .arg(&file)
+        .output()
+        .expect("run actionbook report");
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(
+        output.status.success(),
+        "expected success\nstdout:\n{stdout}\nstderr:\n{stderr}"
+    );
+    let parsed: serde_json::Value = serde_json::from_str(stdout.trim()).expect("JSON output");
+    assert_eq!(parsed["success"], true);
+    assert_eq!(parsed["outcome"], "success");
+}
+
+#[tokio::test]
+async fn report_rejects_unknown_outcome() {
+    let output = Command::cargo_bin("actionbook")
+        .expect("binary exists")
+        .args(["report", "x.com:/:default", "--outcome", "maybe"])
+        .output()
+        .expect("run actionbook report");
+    assert!(!output.status.success());
+}
+
+#[tokio::test]
+async fn run_with_area_id_reports_the_failed_run() {
+    let mock = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(path("/api/report_action"))
+        .and(body_partial_json(json!({
+            "area_id": "x.com:/:default",
+            "outcome": "failure",
+        })))
+        .respond_with(ResponseTemplate::new(200))
+        .expect(1)
+        .mount(&mock)
+        .await;
+
+    // The script fails to load, so the run ends before reaching the daemon.
+    let output = Command::cargo_bin("actionbook")
+        .expect("binary exists")
+        .env("ACTIONBOOK_API_URL", mock.uri())
+        .args([
+            "run",
+            "/nonexistent/script.yaml",
+            "--session",
+            "s1",
+            "--area-id",
+            "x.com:/:default",
+        ])
+        .output()
+        .expect("run actionbook run");
+
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(!output.status.success(), "stderr:\n{stderr}");
+    assert!(stderr.contains("INVALID_SCRIPT"), "stderr:\n{stderr}");
+    assert!(!stderr.contains("failed to report"), "stderr:\n{stderr}");
+}
@@ -77,6 +77,8 @@ async fn search_prints_results_against_mock_server() {
     );
 }
 
+/// `--json` passes the server's data through, with a relevance `score` and
+/// the matched query words per field added to every action.
 #[tokio::test]
 async fn search_json_mode_outputs_raw_data() {
     let mock = MockServer::start().await;
@@ -103,11 +105,20 @@ async fn search_json_mode_outputs_raw_data() {
         "expected success\nstdout:\n{stdout}\nstderr:\n{stderr}"
     );
 
-    let parsed: Value =
+    let mut parsed: Value =
         serde_json::from_str(stdout.trim()).expect("stdout should be valid JSON in --json mode");
+    let action = parsed[0]["groups"][0]["actions"][0]
+        .as_object_mut()
+        .expect("action object");
+    // "github" is the site name: an exact site match scores 3.
+    assert_eq!(action.remove("score"), Some(json!(3.0)));
+    assert_eq!(
+        action.remove("matches"),
+        Some(json!({ "site": ["github"] }))
+    );
     assert_eq!(
         parsed, expected_data,
-        "--json output should be the unwrapped data field"
+        "--json output should be the unwrapped data field plus scores"
     );
 }
 
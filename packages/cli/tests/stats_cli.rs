@@ -0,0 +1,101 @@
+//! Integration tests for `actionbook stats`.
+//!
+//! Each test runs the real CLI binary against its own `ACTIONBOOK_HOME`, with
+//! `ACTIONBOOK_API_URL` pointed at a local `wiremock` server.
+
+use std::path::Path;
+
+use assert_cmd::Command;
+use serde_json::{Value, json};
+use wiremock::matchers::{body_partial_json, method, path};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+fn actionbook(home: &Path, api: &str, args: &[&str]) -> std::process::Output {
+    Command::cargo_bin("actionbook")
+        .expect("binary exists")
+        .env("ACTIONBOOK_HOME", home)
+        .env("ACTIONBOOK_API_URL", api)
+        .args(args)
+        .output()
+        .expect("run actionbook")
+}
+
+fn stats_json(home: &Path, api: &str) -> Value {
+    let out = actionbook(home, api, &["--json", "stats"]);
+    assert!(
+        out.status.success(),
+        "{}",
+        String::from_utf8_lossy(&out.stderr)
+    );
+    serde_json::from_slice(&out.stdout).expect("JSON output")
+}
+
+async fn mount_get(mock: &MockServer) {
+    Mock::given(method("GET"))
+        .and(path("/api/get_action_by_area_id"))
+        .respond_with(ResponseTemplate::new(200).set_body_string("## Steps"))
+        .mount(mock)
+        .await;
+}
+
+#[tokio::test]
+async fn stats_record_nothing_until_enabled() {
+    let home = tempfile::tempdir().unwrap();
+    let mock = MockServer::start().await;
+    mount_get(&mock).await;
+
+    actionbook(home.path(), &mock.uri(), &["get", "a.com:/:x"]);
+    let stats = stats_json(home.path(), &mock.uri());
+    assert_eq!(stats["data"]["enabled"], false);
+    assert_eq!(stats["data"]["total"], 0);
+    assert!(!home.path().join("stats/stats.json").exists());
+}
+
+#[tokio::test]
+async fn stats_count_runs_and_failures_then_upload() {
+    let home = tempfile::tempdir().unwrap();
+    let mock = MockServer::start().await;
+    mount_get(&mock).await;
+    Mock::given(method("POST"))
+        .and(path("/api/usage_stats"))
+        .and(body_partial_json(json!({
+            "commands": { "get": { "count": 2 }, "publish": { "count": 1 } },
+        })))
+        .respond_with(ResponseTemplate::new(200).set_body_string("ok"))
+        .expect(1)
+        .mount(&mock)
+        .await;
+
+    let api = mock.uri();
+    assert!(
+        actionbook(home.path(), &api, &["stats", "enable"])
+            .status
+            .success()
+    );
+    actionbook(home.path(), &api, &["get", "a.com:/:x"]);
+    actionbook(home.path(), &api, &["get", "a.com:/:y"]);
+    let missing = home.path().join("missing.yaml");
+    actionbook(home.path(), &api, &["publish", missing.to_str().unwrap()]);
+
+    let stats = stats_json(home.path(), &api);
+    assert_eq!(stats["data"]["enabled"], true);
+    assert_eq!(stats["data"]["total"], 3);
+    let commands = stats["data"]["commands"].as_array().unwrap();
+    assert_eq!(commands[0]["command"], "get");
+    assert_eq!(commands[0]["count"], 2);
+    assert_eq!(commands[1]["command"], "publish");
+    assert_eq!(commands[1]["failures"], json!({ "INVALID_ARGUMENT": 1 }));
+
+    let out = actionbook(home.path(), &api, &["stats", "upload", "--reset"]);
+    let stdout = String::from_utf8_lossy(&out.stdout);
+    assert!(
+        out.status.success(),
+        "{}",
+        String::from_utf8_lossy(&out.stderr)
+    );
+    assert!(
+        stdout.contains("Uploaded usage stats for 2 commands; local counters cleared"),
+        "{stdout}"
+    );
+    assert_eq!(stats_json(home.path(), &api)["data"]["total"], 0);
+}
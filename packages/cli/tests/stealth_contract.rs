@@ -2,6 +2,7 @@ use std::fs;
 use std::path::{Path, PathBuf};
 
 use actionbook_cli::daemon::browser::launch_chrome;
+use actionbook_cli::types::HeadlessMode;
 use tempfile::tempdir;
 
 #[cfg(unix)]
@@ -37,10 +38,13 @@ async fn launch_chrome_adds_stealth_flags_and_omits_open_url_arg() {
 
     let (mut child, port) = launch_chrome(
         fake_chrome.to_str().expect("fake chrome path"),
-        true,
+        Some(HeadlessMode::New),
         user_data_dir.to_str().expect("user data dir"),
         Some("https://example.com/stealth-check"),
         true,
+        Default::default(),
+        None,
+        None,
     )
     .await
     .expect("launch fake chrome");